@@ -3,6 +3,7 @@
 
 //! Global AI configuration
 
+use crate::emergent::ConsensusTuning;
 use crate::ineru::IneruConfig;
 use crate::kaneru::KaneruConfig;
 use crate::nested_learning::NestedConfig;
@@ -23,9 +24,17 @@ pub struct AiConfig {
     /// Enable predictive validation
     pub predictive_validation: bool,
 
+    /// Predicted validity probability above which expensive validation is skipped
+    #[serde(default = "default_prediction_skip_threshold")]
+    pub prediction_skip_threshold: f32,
+
     /// Enable adaptive consensus
     pub adaptive_consensus: bool,
 
+    /// Network-signal thresholds for adaptive consensus
+    #[serde(default)]
+    pub consensus_tuning: ConsensusTuning,
+
     /// IoT mode (reduced resource usage)
     pub iot_mode: bool,
 }
@@ -37,12 +46,18 @@ impl Default for AiConfig {
             nested_learning: NestedConfig::default(),
             kaneru: KaneruConfig::default(),
             predictive_validation: true,
+            prediction_skip_threshold: default_prediction_skip_threshold(),
             adaptive_consensus: true,
+            consensus_tuning: ConsensusTuning::default(),
             iot_mode: false,
         }
     }
 }
 
+fn default_prediction_skip_threshold() -> f32 {
+    0.98
+}
+
 impl AiConfig {
     /// Create IoT-optimized configuration
     pub fn iot() -> Self {
@@ -51,7 +66,9 @@ impl AiConfig {
             nested_learning: NestedConfig::iot(),
             kaneru: KaneruConfig::iot(),
             predictive_validation: false, // Too expensive for IoT
+            prediction_skip_threshold: default_prediction_skip_threshold(),
             adaptive_consensus: true,
+            consensus_tuning: ConsensusTuning::default(),
             iot_mode: true,
         }
     }
@@ -63,7 +80,9 @@ impl AiConfig {
             nested_learning: NestedConfig::full_power(),
             kaneru: KaneruConfig::full_power(),
             predictive_validation: true,
+            prediction_skip_threshold: default_prediction_skip_threshold(),
             adaptive_consensus: true,
+            consensus_tuning: ConsensusTuning::default(),
             iot_mode: false,
         }
     }
@@ -83,6 +102,10 @@ impl AiConfig {
         self.titans.validate()?;
         self.nested_learning.validate()?;
         self.kaneru.validate()?;
+        self.consensus_tuning.validate()?;
+        if !(self.prediction_skip_threshold > 0.5 && self.prediction_skip_threshold <= 1.0) {
+            return Err("prediction_skip_threshold must be in (0.5, 1.0]".to_string());
+        }
         Ok(())
     }
 }
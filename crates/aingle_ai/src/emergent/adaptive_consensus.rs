@@ -3,8 +3,21 @@
 
 //! Adaptive Consensus
 //!
-//! Adjust consensus level based on transaction importance.
+//! Adjust consensus level based on transaction importance and network
+//! conditions.
+//!
+//! Network conditions arrive through [`NetworkSignal`] readings. Each signal
+//! is smoothed with an EMA and compared against a hysteresis band from
+//! [`ConsensusTuning`]; the consensus posture only moves when a smoothed
+//! signal leaves its band by the configured margin, and never more often than
+//! the cooldown allows.
+
+use std::collections::VecDeque;
 
+use serde::{Deserialize, Serialize};
+
+use super::network_signal::{Ema, NetworkSignal, SignalKind};
+use crate::config::AiConfig;
 use crate::types::{AiTransaction, ConsensusLevel, ValidationPrediction};
 
 /// Adaptive consensus based on transaction importance
@@ -14,11 +27,35 @@ pub struct AdaptiveConsensus {
 
     /// Override rules
     override_rules: Vec<OverrideRule>,
+
+    /// Signal thresholds; `None` when adaptation is disabled
+    tuning: Option<ConsensusTuning>,
+
+    /// Current posture
+    posture: ConsensusPosture,
+
+    /// One smoother per signal, indexed like [`SignalKind::ALL`]
+    smoothers: [Ema; 3],
+
+    /// Time of the last posture change
+    last_adjustment_ms: Option<u64>,
+
+    /// Recent posture changes, oldest first
+    history: VecDeque<Adjustment>,
 }
 
 impl AdaptiveConsensus {
     /// Create new adaptive consensus
     pub fn new() -> Self {
+        Self::from_config(&AiConfig::default())
+    }
+
+    /// Create adaptive consensus using the signal thresholds from `config`
+    ///
+    /// With `adaptive_consensus` disabled, network signals are still smoothed
+    /// but the posture stays [`ConsensusPosture::Balanced`].
+    pub fn from_config(config: &AiConfig) -> Self {
+        let alpha = config.consensus_tuning.smoothing_alpha;
         Self {
             importance_model: ImportanceModel::new(),
             override_rules: vec![
@@ -33,6 +70,13 @@ impl AdaptiveConsensus {
                     level: ConsensusLevel::Local,
                 },
             ],
+            tuning: config
+                .adaptive_consensus
+                .then(|| config.consensus_tuning.clone()),
+            posture: ConsensusPosture::Balanced,
+            smoothers: [Ema::new(alpha), Ema::new(alpha), Ema::new(alpha)],
+            last_adjustment_ms: None,
+            history: VecDeque::new(),
         }
     }
 
@@ -52,12 +96,99 @@ impl AdaptiveConsensus {
         // Evaluate importance
         let importance = self.importance_model.evaluate(tx, prediction);
 
-        match importance {
+        let level = match importance {
             Importance::Critical => ConsensusLevel::Full, // All validators
             Importance::High => ConsensusLevel::Majority, // 67% validators
             Importance::Normal => ConsensusLevel::Quorum, // 51% validators
             Importance::Low => ConsensusLevel::Local,     // Local validation only
+        };
+
+        match self.posture {
+            ConsensusPosture::Aggressive => relax_level(level),
+            ConsensusPosture::Balanced => level,
+            ConsensusPosture::Conservative => escalate_level(level),
+        }
+    }
+
+    /// Feed one reading of network conditions taken at `now_ms`
+    ///
+    /// Returns the adjustment if the reading moved the posture. The posture
+    /// steps towards [`ConsensusPosture::Conservative`] when any smoothed
+    /// signal rises above its band by the margin, and towards
+    /// [`ConsensusPosture::Aggressive`] when every smoothed signal falls
+    /// below its band by the margin. It moves one step at a time and waits
+    /// `cooldown_ms` between steps.
+    pub fn observe(&mut self, signal: &dyn NetworkSignal, now_ms: u64) -> Option<Adjustment> {
+        let mut smoothed = [0.0; 3];
+        for ((value, ema), kind) in smoothed
+            .iter_mut()
+            .zip(self.smoothers.iter_mut())
+            .zip(SignalKind::ALL)
+        {
+            *value = ema.update(kind.read(signal));
         }
+
+        let tuning = self.tuning.as_ref()?;
+        if let Some(last) = self.last_adjustment_ms {
+            if now_ms < last.saturating_add(tuning.cooldown_ms) {
+                return None;
+            }
+        }
+
+        // Position of each signal relative to its band, in band widths past
+        // the upper (positive) or lower (negative) edge
+        let excess: Vec<(SignalKind, f32, f32)> = SignalKind::ALL
+            .into_iter()
+            .zip(smoothed)
+            .map(|(kind, value)| (kind, value, tuning.band(kind).excess(value)))
+            .collect();
+        let margin = tuning.hysteresis_margin;
+
+        let (new, (trigger, value, _)) = if let Some(worst) = excess
+            .iter()
+            .filter(|(_, _, e)| *e > margin)
+            .max_by(|a, b| a.2.total_cmp(&b.2))
+        {
+            (self.posture.escalate()?, *worst)
+        } else if excess.iter().all(|(_, _, e)| *e < -margin) {
+            let closest = excess.iter().max_by(|a, b| a.2.total_cmp(&b.2))?;
+            (self.posture.relax()?, *closest)
+        } else {
+            return None;
+        };
+
+        let adjustment = Adjustment {
+            at_ms: now_ms,
+            old: self.posture,
+            new,
+            trigger,
+            value,
+        };
+        self.posture = new;
+        self.last_adjustment_ms = Some(now_ms);
+        if self.history.len() == tuning.history_capacity {
+            self.history.pop_front();
+        }
+        if tuning.history_capacity > 0 {
+            self.history.push_back(adjustment.clone());
+        }
+        Some(adjustment)
+    }
+
+    /// Current consensus posture
+    pub fn posture(&self) -> ConsensusPosture {
+        self.posture
+    }
+
+    /// Smoothed value of `kind`, or `None` before the first reading
+    pub fn smoothed(&self, kind: SignalKind) -> Option<f32> {
+        let index = SignalKind::ALL.iter().position(|k| *k == kind)?;
+        self.smoothers[index].value()
+    }
+
+    /// Recent posture changes, oldest first
+    pub fn history(&self) -> &VecDeque<Adjustment> {
+        &self.history
     }
 
     /// Add an override rule
@@ -77,6 +208,169 @@ impl Default for AdaptiveConsensus {
     }
 }
 
+/// Step a consensus level up by one
+fn escalate_level(level: ConsensusLevel) -> ConsensusLevel {
+    match level {
+        ConsensusLevel::Local => ConsensusLevel::Quorum,
+        ConsensusLevel::Quorum => ConsensusLevel::Majority,
+        ConsensusLevel::Majority | ConsensusLevel::Full => ConsensusLevel::Full,
+    }
+}
+
+/// Step a consensus level down by one
+fn relax_level(level: ConsensusLevel) -> ConsensusLevel {
+    match level {
+        ConsensusLevel::Full => ConsensusLevel::Majority,
+        ConsensusLevel::Majority => ConsensusLevel::Quorum,
+        ConsensusLevel::Quorum | ConsensusLevel::Local => ConsensusLevel::Local,
+    }
+}
+
+/// How the network conditions shift the importance-based consensus level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConsensusPosture {
+    /// Healthy network - one level less than importance asks for
+    Aggressive,
+    /// The level importance asks for
+    Balanced,
+    /// Degraded network - one level more than importance asks for
+    Conservative,
+}
+
+impl ConsensusPosture {
+    fn escalate(self) -> Option<Self> {
+        match self {
+            Self::Aggressive => Some(Self::Balanced),
+            Self::Balanced => Some(Self::Conservative),
+            Self::Conservative => None,
+        }
+    }
+
+    fn relax(self) -> Option<Self> {
+        match self {
+            Self::Aggressive => None,
+            Self::Balanced => Some(Self::Aggressive),
+            Self::Conservative => Some(Self::Balanced),
+        }
+    }
+}
+
+/// A recorded posture change
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Adjustment {
+    /// Time of the reading that caused the change
+    pub at_ms: u64,
+    /// Posture before the change
+    pub old: ConsensusPosture,
+    /// Posture after the change
+    pub new: ConsensusPosture,
+    /// Signal that triggered the change
+    pub trigger: SignalKind,
+    /// Smoothed value of the triggering signal
+    pub value: f32,
+}
+
+/// Hysteresis band for one signal
+///
+/// Readings between `low` and `high` never move the posture.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SignalBand {
+    /// Lower edge
+    pub low: f32,
+    /// Upper edge
+    pub high: f32,
+}
+
+impl SignalBand {
+    /// Distance of `value` outside the band, in band widths: positive above
+    /// `high`, negative below `low`, zero inside
+    fn excess(&self, value: f32) -> f32 {
+        let width = self.high - self.low;
+        if value > self.high {
+            (value - self.high) / width
+        } else if value < self.low {
+            (value - self.low) / width
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Thresholds for network-driven consensus adjustment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConsensusTuning {
+    /// EMA weight of the newest reading
+    pub smoothing_alpha: f32,
+
+    /// Band for the latency percentile, in milliseconds
+    pub latency_band: SignalBand,
+
+    /// Band for peer churn, as the fraction of peers joining or leaving
+    pub churn_band: SignalBand,
+
+    /// Band for fork rate, in forks per block
+    pub fork_band: SignalBand,
+
+    /// How far past a band edge, in band widths, a signal must go
+    pub hysteresis_margin: f32,
+
+    /// Minimum time between posture changes
+    pub cooldown_ms: u64,
+
+    /// Number of adjustments kept in the history
+    pub history_capacity: usize,
+}
+
+impl Default for ConsensusTuning {
+    fn default() -> Self {
+        Self {
+            smoothing_alpha: 0.2,
+            latency_band: SignalBand {
+                low: 200.0,
+                high: 800.0,
+            },
+            churn_band: SignalBand {
+                low: 0.02,
+                high: 0.10,
+            },
+            fork_band: SignalBand {
+                low: 0.01,
+                high: 0.05,
+            },
+            hysteresis_margin: 0.1,
+            cooldown_ms: 30_000,
+            history_capacity: 64,
+        }
+    }
+}
+
+impl ConsensusTuning {
+    fn band(&self, kind: SignalKind) -> &SignalBand {
+        match kind {
+            SignalKind::LatencyPercentile => &self.latency_band,
+            SignalKind::PeerChurn => &self.churn_band,
+            SignalKind::ForkRate => &self.fork_band,
+        }
+    }
+
+    /// Validate tuning
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.smoothing_alpha > 0.0 && self.smoothing_alpha <= 1.0) {
+            return Err("smoothing_alpha must be in (0.0, 1.0]".to_string());
+        }
+        for kind in SignalKind::ALL {
+            let band = self.band(kind);
+            if !(band.low >= 0.0 && band.low < band.high) {
+                return Err(format!("{} band must satisfy 0 <= low < high", kind));
+            }
+        }
+        if self.hysteresis_margin.is_nan() || self.hysteresis_margin < 0.0 {
+            return Err("hysteresis_margin must be >= 0.0".to_string());
+        }
+        Ok(())
+    }
+}
+
 /// Importance model for transactions
 struct ImportanceModel {
     /// Weight for size factor
@@ -180,6 +474,15 @@ pub enum OverrideCondition {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::emergent::NetworkConditions;
+
+    fn conditions(latency_percentile: f32) -> NetworkConditions {
+        NetworkConditions {
+            latency_percentile,
+            peer_churn: 0.05,
+            fork_rate: 0.02,
+        }
+    }
 
     fn make_test_tx(id: u8, size: usize) -> AiTransaction {
         AiTransaction {
@@ -259,4 +562,90 @@ mod tests {
         // The entry type override should match (inserted at position 0)
         assert_eq!(level, ConsensusLevel::Full);
     }
+
+    #[test]
+    fn test_no_oscillation_within_band() {
+        let mut consensus = AdaptiveConsensus::new();
+
+        // Spikes well past the upper edge, and readings hovering just above
+        // it, stay inside the margin once smoothed
+        let script = [
+            300.0, 1500.0, 300.0, 300.0, 1400.0, 300.0, 850.0, 850.0, 400.0, 1200.0, 200.0,
+        ];
+        for (i, latency) in script.into_iter().enumerate() {
+            let now = i as u64 * 60_000;
+            assert_eq!(consensus.observe(&conditions(latency), now), None);
+        }
+        assert_eq!(consensus.posture(), ConsensusPosture::Balanced);
+        assert!(consensus.history().is_empty());
+    }
+
+    #[test]
+    fn test_single_adjustment_after_sustained_shift() {
+        let mut consensus = AdaptiveConsensus::new();
+        for i in 0..5 {
+            consensus.observe(&conditions(400.0), i * 1_000);
+        }
+
+        let adjustments: Vec<Adjustment> = (5..40)
+            .filter_map(|i| consensus.observe(&conditions(2_000.0), i * 1_000))
+            .collect();
+
+        assert_eq!(adjustments.len(), 1);
+        let adjustment = &adjustments[0];
+        assert_eq!(adjustment.old, ConsensusPosture::Balanced);
+        assert_eq!(adjustment.new, ConsensusPosture::Conservative);
+        assert_eq!(adjustment.trigger, SignalKind::LatencyPercentile);
+        assert!(adjustment.value > 860.0);
+        assert_eq!(consensus.history().len(), 1);
+        assert_eq!(consensus.history()[0], *adjustment);
+
+        // Conservative posture raises the importance-based level
+        let tx = make_test_tx(1, 500);
+        assert_eq!(
+            consensus.determine_level(&tx, &make_prediction(0.3)),
+            ConsensusLevel::Majority
+        );
+    }
+
+    #[test]
+    fn test_cooldown_between_adjustments() {
+        let config = AiConfig {
+            consensus_tuning: ConsensusTuning {
+                smoothing_alpha: 1.0,
+                ..ConsensusTuning::default()
+            },
+            ..AiConfig::default()
+        };
+        let mut consensus = AdaptiveConsensus::from_config(&config);
+        let quiet = NetworkConditions {
+            latency_percentile: 50.0,
+            peer_churn: 0.0,
+            fork_rate: 0.0,
+        };
+
+        let first = consensus.observe(&conditions(2_000.0), 0).unwrap();
+        assert_eq!(first.new, ConsensusPosture::Conservative);
+        assert_eq!(consensus.observe(&quiet, 10_000), None);
+        assert_eq!(consensus.observe(&quiet, 29_999), None);
+
+        let second = consensus.observe(&quiet, 30_000).unwrap();
+        assert_eq!(second.old, ConsensusPosture::Conservative);
+        assert_eq!(second.new, ConsensusPosture::Balanced);
+        assert_eq!(consensus.history().len(), 2);
+    }
+
+    #[test]
+    fn test_disabled_never_adjusts() {
+        let config = AiConfig {
+            adaptive_consensus: false,
+            ..AiConfig::default()
+        };
+        let mut consensus = AdaptiveConsensus::from_config(&config);
+        for i in 0..20 {
+            assert_eq!(consensus.observe(&conditions(5_000.0), i * 60_000), None);
+        }
+        assert_eq!(consensus.posture(), ConsensusPosture::Balanced);
+        assert!(consensus.smoothed(SignalKind::LatencyPercentile).unwrap() > 4_000.0);
+    }
 }
@@ -10,12 +10,18 @@
 //!
 //! - **PredictiveValidator**: Predict validation outcomes before full validation
 //! - **AdaptiveConsensus**: Adjust consensus level based on transaction importance
+//!   and smoothed network signals
 
 mod adaptive_consensus;
+mod network_signal;
 mod predictive_validator;
+mod triple_model;
 
-pub use adaptive_consensus::AdaptiveConsensus;
-pub use predictive_validator::PredictiveValidator;
+pub use adaptive_consensus::{
+    AdaptiveConsensus, Adjustment, ConsensusPosture, ConsensusTuning, SignalBand,
+};
+pub use network_signal::{Ema, NetworkConditions, NetworkSignal, SignalKind};
+pub use predictive_validator::{GatedValidation, PredictiveValidator, TrainingReport};
 
 use crate::ineru::IneruMemory;
 use crate::nested_learning::NestedLearning;
@@ -50,6 +56,15 @@ impl AiLayer {
         }
     }
 
+    /// Feed a reading of network conditions to adaptive consensus
+    pub fn observe_network(
+        &mut self,
+        signal: &dyn NetworkSignal,
+        now_ms: u64,
+    ) -> Option<Adjustment> {
+        self.consensus.observe(signal, now_ms)
+    }
+
     /// Process a transaction through the full AI pipeline
     pub fn process(&mut self, tx: &AiTransaction) -> AiProcessResult {
         // 1. Process through Ineru memory
@@ -0,0 +1,148 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Network Signals
+//!
+//! Readings of network health that drive consensus adaptation, and the EMA
+//! used to smooth them.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Source of network-condition readings
+pub trait NetworkSignal {
+    /// Latency at the tracked percentile, in milliseconds
+    fn latency_percentile(&self) -> f32;
+
+    /// Fraction of peers that joined or left since the last reading
+    fn peer_churn(&self) -> f32;
+
+    /// Forks observed per block since the last reading
+    fn fork_rate(&self) -> f32;
+}
+
+/// A fixed snapshot of network conditions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct NetworkConditions {
+    /// Latency at the tracked percentile, in milliseconds
+    pub latency_percentile: f32,
+    /// Fraction of peers that joined or left
+    pub peer_churn: f32,
+    /// Forks per block
+    pub fork_rate: f32,
+}
+
+impl NetworkSignal for NetworkConditions {
+    fn latency_percentile(&self) -> f32 {
+        self.latency_percentile
+    }
+
+    fn peer_churn(&self) -> f32 {
+        self.peer_churn
+    }
+
+    fn fork_rate(&self) -> f32 {
+        self.fork_rate
+    }
+}
+
+/// One of the signals a [`NetworkSignal`] provides
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SignalKind {
+    /// [`NetworkSignal::latency_percentile`]
+    LatencyPercentile,
+    /// [`NetworkSignal::peer_churn`]
+    PeerChurn,
+    /// [`NetworkSignal::fork_rate`]
+    ForkRate,
+}
+
+impl SignalKind {
+    /// Every signal kind
+    pub const ALL: [SignalKind; 3] = [
+        SignalKind::LatencyPercentile,
+        SignalKind::PeerChurn,
+        SignalKind::ForkRate,
+    ];
+
+    /// Read this signal from `source`
+    pub fn read(self, source: &dyn NetworkSignal) -> f32 {
+        match self {
+            SignalKind::LatencyPercentile => source.latency_percentile(),
+            SignalKind::PeerChurn => source.peer_churn(),
+            SignalKind::ForkRate => source.fork_rate(),
+        }
+    }
+}
+
+impl fmt::Display for SignalKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SignalKind::LatencyPercentile => "latency_percentile",
+            SignalKind::PeerChurn => "peer_churn",
+            SignalKind::ForkRate => "fork_rate",
+        })
+    }
+}
+
+/// Exponential moving average
+#[derive(Debug, Clone)]
+pub struct Ema {
+    alpha: f32,
+    value: Option<f32>,
+}
+
+impl Ema {
+    /// Create an EMA giving weight `alpha` to each new sample
+    pub fn new(alpha: f32) -> Self {
+        Self { alpha, value: None }
+    }
+
+    /// Add a sample and return the smoothed value
+    ///
+    /// The first sample is taken as-is; non-finite samples are ignored.
+    pub fn update(&mut self, sample: f32) -> f32 {
+        if sample.is_finite() {
+            self.value = Some(match self.value {
+                Some(value) => value + self.alpha * (sample - value),
+                None => sample,
+            });
+        }
+        self.value.unwrap_or(0.0)
+    }
+
+    /// Current smoothed value, or `None` before the first sample
+    pub fn value(&self) -> Option<f32> {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ema_smooths_spikes() {
+        let mut ema = Ema::new(0.25);
+        assert_eq!(ema.value(), None);
+        assert_eq!(ema.update(100.0), 100.0);
+        assert_eq!(ema.update(500.0), 200.0);
+        assert_eq!(ema.update(f32::NAN), 200.0);
+        assert_eq!(ema.update(200.0), 200.0);
+    }
+
+    #[test]
+    fn test_signal_kind_reads_source() {
+        let conditions = NetworkConditions {
+            latency_percentile: 350.0,
+            peer_churn: 0.05,
+            fork_rate: 0.02,
+        };
+        let read: Vec<f32> = SignalKind::ALL
+            .into_iter()
+            .map(|kind| kind.read(&conditions))
+            .collect();
+        assert_eq!(read, vec![350.0, 0.05, 0.02]);
+    }
+}
@@ -4,11 +4,25 @@
 //! Predictive Validator
 //!
 //! Predict validation outcome before full validation.
+//!
+//! For triples, the validator keeps a model of which triples tend to be
+//! valid. It can be warm-started from historical outcomes with
+//! [`PredictiveValidator::train_from`], and shipped to other nodes with
+//! [`PredictiveValidator::export_model`]. The model only ever lets a triple
+//! skip *expensive* validation; hard logic checks always run and always win.
+
+use aingle_graph::{GraphDB, Triple, TripleId};
 
+use super::triple_model::TripleModel;
+use crate::config::AiConfig;
+use crate::error::{AiError, AiResult};
 use crate::ineru::IneruMemory;
 use crate::nested_learning::NestedLearning;
 use crate::types::{AiTransaction, ValidationPrediction};
 
+/// Passes over the labeled history when warm-starting
+const TRAINING_EPOCHS: usize = 10;
+
 /// Predict validation outcome before full validation
 pub struct PredictiveValidator {
     /// Confidence boost for known patterns
@@ -19,18 +33,129 @@ pub struct PredictiveValidator {
 
     /// History of predictions for accuracy tracking
     prediction_history: Vec<PredictionRecord>,
+
+    /// Validity model for triples
+    model: TripleModel,
+
+    /// Probability above which expensive validation is skipped; `None` never skips
+    skip_threshold: Option<f32>,
 }
 
 impl PredictiveValidator {
     /// Create new predictive validator
     pub fn new() -> Self {
+        Self::from_config(&AiConfig::default())
+    }
+
+    /// Create a predictive validator using the skip threshold from `config`
+    ///
+    /// With `predictive_validation` disabled, expensive validation is never
+    /// skipped.
+    pub fn from_config(config: &AiConfig) -> Self {
         Self {
             known_pattern_boost: 0.2,
             min_confidence: 0.5,
             prediction_history: Vec::new(),
+            model: TripleModel::new(),
+            skip_threshold: config
+                .predictive_validation
+                .then_some(config.prediction_skip_threshold),
+        }
+    }
+
+    /// Warm-start the triple model from historical validation outcomes
+    ///
+    /// Each label pairs a triple ID in `graph` with whether that triple
+    /// passed validation. IDs that are not in the graph are counted in the
+    /// report and skipped.
+    pub fn train_from(
+        &mut self,
+        graph: &GraphDB,
+        labels: impl IntoIterator<Item = (TripleId, bool)>,
+    ) -> AiResult<TrainingReport> {
+        let mut samples = Vec::new();
+        let mut missing = 0;
+        for (id, valid) in labels {
+            match graph
+                .get(&id)
+                .map_err(|e| AiError::QueryError(e.to_string()))?
+            {
+                Some(triple) => samples.push((triple, valid)),
+                None => missing += 1,
+            }
+        }
+
+        for _ in 0..TRAINING_EPOCHS {
+            for (triple, valid) in &samples {
+                self.model.update(triple, *valid);
+            }
+        }
+
+        Ok(TrainingReport {
+            trained: samples.len(),
+            missing,
+        })
+    }
+
+    /// Update the triple model with one validation outcome
+    pub fn observe(&mut self, triple: &Triple, valid: bool) {
+        self.model.update(triple, valid);
+    }
+
+    /// Calibrated probability that `triple` passes validation
+    pub fn probability_valid(&self, triple: &Triple) -> f32 {
+        self.model.probability(triple)
+    }
+
+    /// Whether `triple` is confident enough to skip expensive validation
+    pub fn can_skip(&self, triple: &Triple) -> bool {
+        self.skip_threshold
+            .is_some_and(|threshold| self.probability_valid(triple) >= threshold)
+    }
+
+    /// Validate a triple, skipping `expensive` when the model is confident
+    ///
+    /// `hard` always runs first and a failure there rejects the triple
+    /// whatever the prediction. The prediction never rejects a triple.
+    pub fn validate_triple(
+        &self,
+        triple: &Triple,
+        hard: impl FnOnce(&Triple) -> bool,
+        expensive: impl FnOnce(&Triple) -> bool,
+    ) -> GatedValidation {
+        let probability_valid = self.probability_valid(triple);
+        if !hard(triple) {
+            return GatedValidation {
+                valid: false,
+                skipped_expensive: false,
+                probability_valid,
+            };
+        }
+
+        let skipped_expensive = self.can_skip(triple);
+        GatedValidation {
+            valid: skipped_expensive || expensive(triple),
+            skipped_expensive,
+            probability_valid,
         }
     }
 
+    /// Serialize the triple model for import on another node
+    pub fn export_model(&self) -> AiResult<Vec<u8>> {
+        self.model.to_bytes()
+    }
+
+    /// Replace the triple model with one from [`PredictiveValidator::export_model`]
+    pub fn import_model(&mut self, bytes: &[u8]) -> AiResult<()> {
+        self.model = TripleModel::from_bytes(bytes)?;
+        Ok(())
+    }
+
+    /// Number of labeled triples the model has learned from, counting epochs
+    pub fn model_samples(&self) -> u64 {
+        self.model.samples()
+    }
+
     /// Predict validation outcome
     pub fn predict(
         &self,
@@ -146,6 +271,26 @@ struct PredictionRecord {
     confidence: f32,
 }
 
+/// Outcome of [`PredictiveValidator::train_from`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrainingReport {
+    /// Labeled triples found in the graph and trained on
+    pub trained: usize,
+    /// Labels whose triple was not in the graph
+    pub missing: usize,
+}
+
+/// Outcome of [`PredictiveValidator::validate_triple`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GatedValidation {
+    /// Whether the triple is accepted
+    pub valid: bool,
+    /// Expensive validation was skipped on the model's prediction
+    pub skipped_expensive: bool,
+    /// The model's probability that the triple is valid
+    pub probability_valid: f32,
+}
+
 /// Prediction accuracy statistics
 #[derive(Debug, Clone)]
 pub struct PredictionAccuracy {
@@ -162,6 +307,7 @@ mod tests {
     use super::*;
     use crate::ineru::IneruConfig;
     use crate::nested_learning::NestedConfig;
+    use aingle_graph::{NodeId, Predicate, Value};
 
     fn make_test_tx(id: u8) -> AiTransaction {
         AiTransaction {
@@ -205,4 +351,178 @@ mod tests {
         assert_eq!(accuracy.sample_count, 10);
         assert!(accuracy.validity_accuracy >= 0.0 && accuracy.validity_accuracy <= 1.0);
     }
+
+    /// Labeled triples where validity follows from the triple's shape:
+    /// readings must be numbers, owners must be users, labels are free text
+    fn fixture(n: usize) -> Vec<(Triple, bool)> {
+        (0..n)
+            .map(|i| {
+                let sensor = NodeId::named(format!("sensor:{}", i));
+                match i % 5 {
+                    0 => (
+                        Triple::new(
+                            sensor,
+                            Predicate::named("reading"),
+                            Value::Integer(i as i64),
+                        ),
+                        true,
+                    ),
+                    1 => (
+                        Triple::new(sensor, Predicate::named("reading"), Value::literal("n/a")),
+                        false,
+                    ),
+                    2 => (
+                        Triple::new(
+                            sensor,
+                            Predicate::named("owner"),
+                            Value::Node(NodeId::named(format!("user:{}", i))),
+                        ),
+                        true,
+                    ),
+                    3 => (
+                        Triple::new(
+                            sensor,
+                            Predicate::named("owner"),
+                            Value::Node(NodeId::named(format!("device:{}", i))),
+                        ),
+                        false,
+                    ),
+                    _ => (
+                        Triple::new(sensor, Predicate::named("label"), Value::literal("lab")),
+                        true,
+                    ),
+                }
+            })
+            .collect()
+    }
+
+    fn held_out_accuracy(validator: &PredictiveValidator, held_out: &[(Triple, bool)]) -> f32 {
+        let correct = held_out
+            .iter()
+            .filter(|(triple, valid)| (validator.probability_valid(triple) >= 0.5) == *valid)
+            .count();
+        correct as f32 / held_out.len() as f32
+    }
+
+    fn trained() -> PredictiveValidator {
+        let graph = GraphDB::memory().unwrap();
+        let mut labels = Vec::new();
+        for (triple, valid) in fixture(200) {
+            labels.push((graph.insert(triple).unwrap(), valid));
+        }
+        let mut validator = PredictiveValidator::new();
+        validator.train_from(&graph, labels).unwrap();
+        validator
+    }
+
+    #[test]
+    fn test_warm_start_beats_cold_model() {
+        let held_out: Vec<_> = fixture(300).into_iter().skip(200).collect();
+        let cold = PredictiveValidator::new();
+        let warm = trained();
+
+        let cold_accuracy = held_out_accuracy(&cold, &held_out);
+        let warm_accuracy = held_out_accuracy(&warm, &held_out);
+        assert!((cold_accuracy - 0.6).abs() < 1e-6);
+        assert!(
+            warm_accuracy > cold_accuracy,
+            "warm {} <= cold {}",
+            warm_accuracy,
+            cold_accuracy
+        );
+        assert!(warm_accuracy > 0.95);
+
+        // Probabilities move toward the observed outcomes
+        let (valid, invalid) = (&held_out[0].0, &held_out[1].0);
+        assert!(warm.probability_valid(valid) > 0.9);
+        assert!(warm.probability_valid(invalid) < 0.1);
+        assert_eq!(cold.probability_valid(valid), 0.5);
+    }
+
+    #[test]
+    fn test_train_from_counts_missing_triples() {
+        let graph = GraphDB::memory().unwrap();
+        let (stored, absent) = {
+            let mut fixture = fixture(2).into_iter();
+            (fixture.next().unwrap(), fixture.next().unwrap())
+        };
+        let id = graph.insert(stored.0).unwrap();
+
+        let mut validator = PredictiveValidator::new();
+        let report = validator
+            .train_from(&graph, [(id, true), (absent.0.id(), false)])
+            .unwrap();
+        assert_eq!(
+            report,
+            TrainingReport {
+                trained: 1,
+                missing: 1
+            }
+        );
+        assert_eq!(validator.model_samples(), TRAINING_EPOCHS as u64);
+    }
+
+    #[test]
+    fn test_export_import_model() {
+        let warm = trained();
+        let bytes = warm.export_model().unwrap();
+
+        let mut shipped = PredictiveValidator::new();
+        shipped.import_model(&bytes).unwrap();
+        for (triple, _) in fixture(10) {
+            assert_eq!(
+                shipped.probability_valid(&triple),
+                warm.probability_valid(&triple)
+            );
+        }
+
+        assert!(shipped.import_model(b"not a model").is_err());
+        let wrong_version =
+            String::from_utf8(bytes)
+                .unwrap()
+                .replacen("\"version\":1", "\"version\":99", 1);
+        assert!(matches!(
+            shipped.import_model(wrong_version.as_bytes()),
+            Err(AiError::EncodingError(_))
+        ));
+    }
+
+    #[test]
+    fn test_prediction_never_overrides_hard_failure() {
+        let warm = trained();
+        let (valid, _) = fixture(1).remove(0);
+        assert!(warm.can_skip(&valid));
+
+        let rejected = warm.validate_triple(&valid, |_| false, |_| true);
+        assert!(!rejected.valid);
+        assert!(!rejected.skipped_expensive);
+
+        let skipped = warm.validate_triple(&valid, |_| true, |_| panic!("should be skipped"));
+        assert!(skipped.valid);
+        assert!(skipped.skipped_expensive);
+    }
+
+    #[test]
+    fn test_skip_threshold_from_config() {
+        let (valid, _) = fixture(1).remove(0);
+        let bytes = trained().export_model().unwrap();
+
+        let mut disabled = PredictiveValidator::from_config(&AiConfig::iot());
+        disabled.import_model(&bytes).unwrap();
+        assert!(!disabled.can_skip(&valid));
+
+        let config = AiConfig {
+            prediction_skip_threshold: 1.0,
+            ..AiConfig::default()
+        };
+        let mut strict = PredictiveValidator::from_config(&config);
+        strict.import_model(&bytes).unwrap();
+        assert!(!strict.can_skip(&valid));
+        let result = strict.validate_triple(&valid, |_| true, |_| false);
+        assert!(!result.valid);
+        assert!(!result.skipped_expensive);
+
+        // Cold models sit at 0.5 and never skip
+        assert!(!PredictiveValidator::new().can_skip(&valid));
+    }
 }
@@ -0,0 +1,170 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Triple Validity Model
+//!
+//! Logistic regression over hashed features of a triple's shape: its
+//! predicate, the kind of its object and the namespaces of its nodes. The
+//! output is the probability that the triple passes validation; an untrained
+//! model answers 0.5 for everything.
+
+use aingle_graph::{NodeId, Triple, Value};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AiError, AiResult};
+
+/// Format version of exported models
+const MODEL_VERSION: u32 = 1;
+
+/// Number of hashed feature buckets
+const BUCKETS: usize = 1 << 12;
+
+/// SGD step size
+const LEARNING_RATE: f32 = 0.1;
+
+/// L2 regularization strength
+const L2: f32 = 1e-4;
+
+/// Logistic regression over hashed triple features
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct TripleModel {
+    version: u32,
+    weights: Vec<f32>,
+    bias: f32,
+    samples: u64,
+}
+
+impl TripleModel {
+    /// Create an untrained model
+    pub(crate) fn new() -> Self {
+        Self {
+            version: MODEL_VERSION,
+            weights: vec![0.0; BUCKETS],
+            bias: 0.0,
+            samples: 0,
+        }
+    }
+
+    /// Probability that `triple` is valid
+    pub(crate) fn probability(&self, triple: &Triple) -> f32 {
+        let score: f32 = features(triple, self.weights.len())
+            .iter()
+            .map(|&i| self.weights[i])
+            .sum();
+        sigmoid(score + self.bias)
+    }
+
+    /// One SGD step on a labeled triple
+    pub(crate) fn update(&mut self, triple: &Triple, valid: bool) {
+        let error = self.probability(triple) - if valid { 1.0 } else { 0.0 };
+        for i in features(triple, self.weights.len()) {
+            let w = &mut self.weights[i];
+            *w -= LEARNING_RATE * (error + L2 * *w);
+        }
+        self.bias -= LEARNING_RATE * error;
+        self.samples += 1;
+    }
+
+    /// Number of training steps taken
+    pub(crate) fn samples(&self) -> u64 {
+        self.samples
+    }
+
+    /// Serialize for shipping to another node
+    pub(crate) fn to_bytes(&self) -> AiResult<Vec<u8>> {
+        Ok(serde_json::to_vec(self)?)
+    }
+
+    /// Deserialize a model produced by [`TripleModel::to_bytes`]
+    pub(crate) fn from_bytes(bytes: &[u8]) -> AiResult<Self> {
+        let model: Self = serde_json::from_slice(bytes)?;
+        if model.version != MODEL_VERSION {
+            return Err(AiError::EncodingError(format!(
+                "unsupported model version {} (expected {})",
+                model.version, MODEL_VERSION
+            )));
+        }
+        if model.weights.len() != BUCKETS {
+            return Err(AiError::EncodingError(format!(
+                "model has {} weights (expected {})",
+                model.weights.len(),
+                BUCKETS
+            )));
+        }
+        if !model.bias.is_finite() || model.weights.iter().any(|w| !w.is_finite()) {
+            return Err(AiError::EncodingError(
+                "model contains non-finite weights".to_string(),
+            ));
+        }
+        Ok(model)
+    }
+}
+
+fn sigmoid(x: f32) -> f32 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+/// Bucket indices of a triple's features
+fn features(triple: &Triple, buckets: usize) -> Vec<usize> {
+    let predicate = triple.predicate.as_str();
+    let kind = value_kind(&triple.object);
+    let subject = node_namespace(&triple.subject);
+
+    let mut names = vec![
+        format!("p:{}", predicate),
+        format!("o:{}", kind),
+        format!("po:{}|{}", predicate, kind),
+        format!("s:{}", subject),
+        format!("ps:{}|{}", predicate, subject),
+    ];
+    if let Value::Node(object) = &triple.object {
+        let object = node_namespace(object);
+        names.push(format!("on:{}", object));
+        names.push(format!("pon:{}|{}", predicate, object));
+    }
+
+    let mut indices: Vec<usize> = names
+        .iter()
+        .map(|name| (fnv1a(name.as_bytes()) % buckets as u64) as usize)
+        .collect();
+    indices.sort_unstable();
+    indices.dedup();
+    indices
+}
+
+fn value_kind(value: &Value) -> String {
+    match value {
+        Value::Node(_) => "node".to_string(),
+        Value::String(_) => "string".to_string(),
+        Value::Integer(_) => "integer".to_string(),
+        Value::Float(_) => "float".to_string(),
+        Value::Boolean(_) => "boolean".to_string(),
+        Value::DateTime(_) => "datetime".to_string(),
+        Value::Bytes(_) => "bytes".to_string(),
+        Value::Typed { datatype, .. } => format!("typed:{}", datatype),
+        Value::LangString { .. } => "langstring".to_string(),
+        Value::Json(_) => "json".to_string(),
+        Value::Null => "null".to_string(),
+    }
+}
+
+/// The namespace part of a node name: up to the last `/` or `#` for IRIs,
+/// otherwise up to the first `:`
+fn node_namespace(node: &NodeId) -> &str {
+    match node {
+        NodeId::Named(name) => match name.rfind(['/', '#']) {
+            Some(end) => &name[..end],
+            None => name.split_once(':').map_or("", |(ns, _)| ns),
+        },
+        NodeId::Hash(_) => "#hash",
+        NodeId::Blank(_) | NodeId::Anonymous(_) => "_",
+    }
+}
+
+/// FNV-1a: stable across platforms and releases, so exported models stay
+/// meaningful on other nodes
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
@@ -31,6 +31,10 @@ pub struct NestedConfig {
 
     /// Maximum parallel validation group size
     pub max_parallel_group: usize,
+
+    /// Levels that start frozen (0 = transaction, 1 = optimizer, 2 = meta)
+    #[serde(default)]
+    pub frozen_levels: Vec<usize>,
 }
 
 impl Default for NestedConfig {
@@ -44,6 +48,7 @@ impl Default for NestedConfig {
             learning_rate: 0.01,
             parallel_validation: true,
             max_parallel_group: 10,
+            frozen_levels: Vec::new(),
         }
     }
 }
@@ -60,6 +65,7 @@ impl NestedConfig {
             learning_rate: 0.001,
             parallel_validation: false, // Sequential for simplicity
             max_parallel_group: 1,
+            frozen_levels: Vec::new(),
         }
     }
 
@@ -74,6 +80,7 @@ impl NestedConfig {
             learning_rate: 0.05,
             parallel_validation: true,
             max_parallel_group: 100,
+            frozen_levels: Vec::new(),
         }
     }
 
@@ -94,6 +101,17 @@ impl NestedConfig {
         if self.learning_rate < 0.0 || self.learning_rate > 1.0 {
             return Err("learning_rate must be between 0.0 and 1.0".to_string());
         }
+        if let Some(level) = self
+            .frozen_levels
+            .iter()
+            .find(|&&l| l >= super::LEVEL_COUNT)
+        {
+            return Err(format!(
+                "frozen_levels contains {} but there are only {} levels",
+                level,
+                super::LEVEL_COUNT
+            ));
+        }
         Ok(())
     }
 }
@@ -114,4 +132,15 @@ mod tests {
         assert!(config.validate().is_ok());
         assert!(config.meta_update_interval > NestedConfig::default().meta_update_interval);
     }
+
+    #[test]
+    fn test_frozen_levels_validated() {
+        let mut config = NestedConfig {
+            frozen_levels: vec![2],
+            ..NestedConfig::default()
+        };
+        assert!(config.validate().is_ok());
+        config.frozen_levels = vec![0, 3];
+        assert!(config.validate().is_err());
+    }
 }
@@ -40,7 +40,9 @@ impl MetaLevel {
     }
 
     /// Update parameters based on block statistics
-    pub fn update(&mut self, stats: &BlockStats) {
+    ///
+    /// Returns the efficiency (throughput / latency) the update acted on.
+    pub fn update(&mut self, stats: &BlockStats) -> f64 {
         // Record history
         let throughput = if stats.processing_time_ms > 0 {
             (stats.tx_count as f64) / (stats.processing_time_ms as f64)
@@ -84,6 +86,8 @@ impl MetaLevel {
         // Update target throughput based on recent performance
         self.params.target_throughput = avg_throughput * 1000.0; // Convert to tx/s
         self.params.target_latency = avg_latency;
+
+        efficiency
     }
 
     /// Adjust parameters to improve efficiency
@@ -113,6 +117,19 @@ impl MetaLevel {
         self.params.clone()
     }
 
+    /// Snapshot of the learned parameters
+    ///
+    /// Order: validation strictness, gossip multiplier, target throughput,
+    /// target latency.
+    pub fn parameters(&self) -> Vec<f32> {
+        vec![
+            self.params.validation_strictness,
+            self.params.gossip_multiplier,
+            self.params.target_throughput as f32,
+            self.params.target_latency as f32,
+        ]
+    }
+
     /// Set target efficiency
     pub fn set_target_efficiency(&mut self, target: f64) {
         self.target_efficiency = target.max(0.1);
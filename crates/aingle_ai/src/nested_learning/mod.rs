@@ -11,6 +11,10 @@
 //! - **Optimizer-Level**: Validation strategies (medium updates, ~100 transactions)
 //! - **Transaction-Level**: Data processing (fast updates, per transaction)
 //!
+//! Levels are indexed from fastest to slowest: 0 is the transaction level,
+//! 1 the optimizer level and 2 the meta level. Any level can be frozen, which
+//! stops its parameter updates while it keeps producing outputs.
+//!
 //! ## Example
 //!
 //! ```rust,no_run
@@ -33,9 +37,10 @@ pub use meta_level::MetaLevel;
 pub use optimizer_level::{OptimizerLevel, ValidationPlan, ValidationStrategy};
 pub use transaction_level::{ProcessedTransaction, TransactionLevel};
 
-use crate::error::AiResult;
+use crate::error::{AiError, AiResult};
 use crate::types::AiTransaction;
 use parking_lot::RwLock;
+use std::collections::VecDeque;
 use std::sync::Arc;
 use tracing::{debug, trace};
 
@@ -58,11 +63,66 @@ pub struct NestedLearning {
 
     /// Transaction counter for optimizer-level updates
     tx_count: u64,
+
+    /// Freeze flag and metrics per level, indexed by level
+    levels: [LevelState; LEVEL_COUNT],
+}
+
+/// Number of levels
+pub const LEVEL_COUNT: usize = 3;
+
+/// Index of the transaction level
+pub const TRANSACTION_LEVEL: usize = 0;
+
+/// Index of the optimizer level
+pub const OPTIMIZER_LEVEL: usize = 1;
+
+/// Index of the meta level
+pub const META_LEVEL: usize = 2;
+
+/// Scores kept per level
+const SCORE_WINDOW: usize = 100;
+
+const LEVEL_NAMES: [&str; LEVEL_COUNT] = ["transaction", "optimizer", "meta"];
+
+/// Freeze flag and update bookkeeping for one level
+#[derive(Debug, Clone, Default)]
+struct LevelState {
+    frozen: bool,
+    updates: u64,
+    last_update_ms: Option<u64>,
+    scores: VecDeque<f32>,
+}
+
+impl LevelState {
+    fn record(&mut self, score: f32) {
+        self.updates += 1;
+        self.last_update_ms = Some(
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_millis() as u64,
+        );
+        if self.scores.len() == SCORE_WINDOW {
+            self.scores.pop_front();
+        }
+        self.scores.push_back(score);
+    }
 }
 
 impl NestedLearning {
     /// Create a new nested learning system
+    ///
+    /// Levels listed in `config.frozen_levels` start frozen; out-of-range
+    /// entries are ignored.
     pub fn new(config: NestedConfig) -> Self {
+        let mut levels: [LevelState; LEVEL_COUNT] = Default::default();
+        for &level in &config.frozen_levels {
+            if let Some(state) = levels.get_mut(level) {
+                state.frozen = true;
+            }
+        }
+
         Self {
             meta_level: Arc::new(RwLock::new(MetaLevel::new(&config))),
             optimizer_level: Arc::new(RwLock::new(OptimizerLevel::new(&config))),
@@ -70,6 +130,7 @@ impl NestedLearning {
             config,
             block_count: 0,
             tx_count: 0,
+            levels,
         }
     }
 
@@ -93,7 +154,9 @@ impl NestedLearning {
         self.tx_count += 1;
 
         // 4. Check if optimizer-level update is needed
-        if self.tx_count % self.config.optimizer_update_interval == 0 {
+        if self.tx_count % self.config.optimizer_update_interval == 0
+            && !self.levels[OPTIMIZER_LEVEL].frozen
+        {
             debug!(
                 tx_count = self.tx_count,
                 "Triggering optimizer-level update"
@@ -114,25 +177,84 @@ impl NestedLearning {
         self.block_count += 1;
 
         // Check if meta-level update is needed
-        if self.block_count % self.config.meta_update_interval == 0 {
+        if self.block_count % self.config.meta_update_interval == 0
+            && !self.levels[META_LEVEL].frozen
+        {
             debug!(
                 block_count = self.block_count,
                 "Triggering meta-level update"
             );
-            let mut ml = self.meta_level.write();
-            ml.update(block_stats);
+            let efficiency = self.meta_level.write().update(block_stats);
+            self.levels[META_LEVEL].record(efficiency as f32);
         }
     }
 
     /// Learn from validation outcome
     pub fn learn(&mut self, tx: &AiTransaction, outcome: &ValidationOutcome) {
         // Update optimizer level with outcome
-        let mut ol = self.optimizer_level.write();
-        ol.learn(tx, outcome);
+        if !self.levels[OPTIMIZER_LEVEL].frozen {
+            let error = self.optimizer_level.write().learn(tx, outcome);
+            self.levels[OPTIMIZER_LEVEL].record(error);
+        }
 
         // Update transaction level features
-        let mut tl = self.transaction_level.write();
-        tl.update_features(tx, outcome);
+        if !self.levels[TRANSACTION_LEVEL].frozen {
+            let confidence = self.transaction_level.write().update_features(tx, outcome);
+            self.levels[TRANSACTION_LEVEL].record(confidence);
+        }
+    }
+
+    /// Stop parameter updates on `level`
+    ///
+    /// A frozen level still produces outputs from its current parameters.
+    pub fn freeze_level(&mut self, level: usize) -> AiResult<()> {
+        self.level_state(level)?.frozen = true;
+        Ok(())
+    }
+
+    /// Resume parameter updates on `level`
+    pub fn unfreeze_level(&mut self, level: usize) -> AiResult<()> {
+        self.level_state(level)?.frozen = false;
+        Ok(())
+    }
+
+    /// Freeze every level
+    pub fn freeze_all(&mut self) {
+        for state in &mut self.levels {
+            state.frozen = true;
+        }
+    }
+
+    /// Indices of the frozen levels, suitable for `NestedConfig::frozen_levels`
+    pub fn frozen_levels(&self) -> Vec<usize> {
+        (0..LEVEL_COUNT)
+            .filter(|&level| self.levels[level].frozen)
+            .collect()
+    }
+
+    /// Per-level metrics, indexed by level
+    pub fn metrics(&self) -> Vec<LevelMetrics> {
+        self.levels
+            .iter()
+            .enumerate()
+            .map(|(level, state)| LevelMetrics {
+                level,
+                name: LEVEL_NAMES[level],
+                frozen: state.frozen,
+                updates: state.updates,
+                last_update_ms: state.last_update_ms,
+                scores: state.scores.iter().copied().collect(),
+            })
+            .collect()
+    }
+
+    fn level_state(&mut self, level: usize) -> AiResult<&mut LevelState> {
+        self.levels.get_mut(level).ok_or_else(|| {
+            AiError::InvalidConfig(format!(
+                "no nested learning level {} (there are {})",
+                level, LEVEL_COUNT
+            ))
+        })
     }
 
     /// Get current meta parameters
@@ -169,6 +291,28 @@ pub struct NestedResult {
     pub meta_params: MetaParams,
 }
 
+/// Update metrics for one level
+///
+/// Each update records a score: the classification confidence before the
+/// update for the transaction level, the absolute complexity prediction
+/// error for the optimizer level, and the efficiency (throughput / latency)
+/// for the meta level.
+#[derive(Debug, Clone)]
+pub struct LevelMetrics {
+    /// Level index
+    pub level: usize,
+    /// Level name
+    pub name: &'static str,
+    /// Whether the level is frozen
+    pub frozen: bool,
+    /// Number of parameter updates
+    pub updates: u64,
+    /// Time of the last update (ms since epoch)
+    pub last_update_ms: Option<u64>,
+    /// Most recent scores, oldest first
+    pub scores: Vec<f32>,
+}
+
 /// Block statistics for meta-level updates
 #[derive(Debug, Clone, Default)]
 pub struct BlockStats {
@@ -286,4 +430,79 @@ mod tests {
 
         assert!(nested.stats().meta_update_count >= 2);
     }
+
+    #[test]
+    fn test_frozen_level_keeps_parameters() {
+        let mut nested = NestedLearning::new(NestedConfig::default());
+        nested.freeze_level(OPTIMIZER_LEVEL).unwrap();
+
+        let transaction_before = nested.transaction_level.read().parameters();
+        let optimizer_before = nested.optimizer_level.read().parameters();
+
+        let outcome = ValidationOutcome {
+            valid: true,
+            time_ms: 40,
+            error: None,
+        };
+        for i in 0..100 {
+            let tx = make_test_tx(i);
+            let result = nested.process(&tx).unwrap();
+            assert!(!result.processed.features.is_empty());
+            nested.learn(&tx, &outcome);
+        }
+
+        let transaction_after = nested.transaction_level.read().parameters();
+        let optimizer_after = nested.optimizer_level.read().parameters();
+        let bits = |params: &[f32]| params.iter().map(|p| p.to_bits()).collect::<Vec<_>>();
+        assert_eq!(bits(&optimizer_before), bits(&optimizer_after));
+        assert_ne!(bits(&transaction_before), bits(&transaction_after));
+
+        let metrics = nested.metrics();
+        assert_eq!(metrics.len(), LEVEL_COUNT);
+        assert!(metrics[OPTIMIZER_LEVEL].frozen);
+        assert_eq!(metrics[OPTIMIZER_LEVEL].updates, 0);
+        assert_eq!(metrics[OPTIMIZER_LEVEL].last_update_ms, None);
+        assert_eq!(metrics[TRANSACTION_LEVEL].updates, 100);
+        assert_eq!(metrics[TRANSACTION_LEVEL].scores.len(), 100);
+        assert!(metrics[TRANSACTION_LEVEL].last_update_ms.is_some());
+    }
+
+    #[test]
+    fn test_freeze_state_from_config() {
+        let mut config = NestedConfig {
+            meta_update_interval: 1,
+            frozen_levels: vec![META_LEVEL],
+            ..NestedConfig::default()
+        };
+        let mut nested = NestedLearning::new(config.clone());
+
+        let before = nested.get_meta_params();
+        let stats = BlockStats {
+            tx_count: 10,
+            processing_time_ms: 1000,
+            latency_p50: 500,
+            ..BlockStats::default()
+        };
+        for _ in 0..5 {
+            nested.on_new_block(&stats);
+        }
+        assert_eq!(
+            nested.get_meta_params().validation_strictness,
+            before.validation_strictness
+        );
+        assert_eq!(nested.metrics()[META_LEVEL].updates, 0);
+
+        nested.freeze_all();
+        nested.unfreeze_level(META_LEVEL).unwrap();
+        config.frozen_levels = nested.frozen_levels();
+        let json = serde_json::to_string(&config).unwrap();
+        let restored: NestedConfig = serde_json::from_str(&json).unwrap();
+        let restored = NestedLearning::new(restored);
+        assert_eq!(
+            restored.frozen_levels(),
+            vec![TRANSACTION_LEVEL, OPTIMIZER_LEVEL]
+        );
+
+        assert!(nested.freeze_level(LEVEL_COUNT).is_err());
+    }
 }
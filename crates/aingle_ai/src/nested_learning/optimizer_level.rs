@@ -108,7 +108,9 @@ impl OptimizerLevel {
     }
 
     /// Learn from validation outcome
-    pub fn learn(&mut self, tx: &AiTransaction, outcome: &ValidationOutcome) {
+    ///
+    /// Returns the absolute complexity prediction error before the update.
+    pub fn learn(&mut self, tx: &AiTransaction, outcome: &ValidationOutcome) -> f32 {
         let features = tx.extract_features();
 
         // Record learning history
@@ -137,6 +139,19 @@ impl OptimizerLevel {
             // Invalid - be more conservative
             self.strategy_selector.adjust_threshold(0.01);
         }
+
+        (predicted_complexity - actual_complexity).abs()
+    }
+
+    /// Snapshot of the learned parameters
+    ///
+    /// Order: complexity model weights, complexity model bias, fast-path
+    /// threshold.
+    pub fn parameters(&self) -> Vec<f32> {
+        let mut parameters = self.complexity_model.weights.clone();
+        parameters.push(self.complexity_model.bias);
+        parameters.push(self.strategy_selector.fast_path_threshold);
+        parameters
     }
 
     /// Periodic update based on accumulated history
@@ -63,9 +63,25 @@ impl TransactionLevel {
     }
 
     /// Update feature extractor based on validation outcome
-    pub fn update_features(&mut self, tx: &AiTransaction, outcome: &ValidationOutcome) {
+    ///
+    /// Returns the classification confidence before the update.
+    pub fn update_features(&mut self, tx: &AiTransaction, outcome: &ValidationOutcome) -> f32 {
         let features = self.feature_extractor.extract(tx);
+        let confidence = self.classifier.confidence(&features);
         self.classifier.update(&features, outcome.valid);
+        confidence
+    }
+
+    /// Snapshot of the learned parameters
+    ///
+    /// The classifier centroids, flattened in type-name order.
+    pub fn parameters(&self) -> Vec<f32> {
+        let mut names: Vec<_> = self.classifier.centroids.keys().collect();
+        names.sort();
+        names
+            .into_iter()
+            .flat_map(|name| self.classifier.centroids[name].iter().copied())
+            .collect()
     }
 
     /// Get recent transaction patterns
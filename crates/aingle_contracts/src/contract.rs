@@ -12,6 +12,9 @@ use std::collections::HashMap;
 use crate::error::{ContractError, Result};
 use crate::types::{Address, ContractId};
 
+/// State schema key listing the graph predicates a contract may write
+pub const GRAPH_PREDICATES_KEY: &str = "graph_predicates";
+
 /// Function visibility/mutability
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FunctionType {
@@ -125,6 +128,9 @@ pub struct Contract {
     pub metadata: HashMap<String, serde_json::Value>,
     /// WASM code (if compiled)
     pub wasm_code: Option<Vec<u8>>,
+    /// Whether the contract may use floating-point instructions
+    #[serde(default)]
+    pub nondeterministic: bool,
     /// Creation timestamp
     pub created_at: u64,
 }
@@ -153,6 +159,24 @@ impl Contract {
             .collect()
     }
 
+    /// Graph predicates this contract may assert or retract
+    ///
+    /// Read from the `graph_predicates` array of the state schema; a contract
+    /// without one cannot write to the graph.
+    pub fn graph_predicates(&self) -> Vec<&str> {
+        self.state_schema
+            .as_ref()
+            .and_then(|schema| schema.get(GRAPH_PREDICATES_KEY))
+            .and_then(|v| v.as_array())
+            .map(|preds| preds.iter().filter_map(|p| p.as_str()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Check if the contract may write triples with this predicate
+    pub fn can_write_predicate(&self, predicate: &str) -> bool {
+        self.graph_predicates().contains(&predicate)
+    }
+
     /// Compute contract hash
     pub fn hash(&self) -> [u8; 32] {
         let mut hasher = Sha256::new();
@@ -169,6 +193,16 @@ impl Contract {
         self.wasm_code.is_some()
     }
 
+    /// Audit the WASM code for nondeterministic constructs
+    ///
+    /// See [`crate::determinism`]. A contract without WASM code passes.
+    pub fn audit_determinism(&self) -> Result<()> {
+        match &self.wasm_code {
+            Some(code) => crate::determinism::audit(code, self.nondeterministic),
+            None => Ok(()),
+        }
+    }
+
     /// Get contract size in bytes
     pub fn size(&self) -> usize {
         self.wasm_code.as_ref().map(|c| c.len()).unwrap_or(0)
@@ -185,6 +219,7 @@ pub struct ContractBuilder {
     functions: HashMap<String, ContractFunction>,
     metadata: HashMap<String, serde_json::Value>,
     wasm_code: Option<Vec<u8>>,
+    nondeterministic: bool,
 }
 
 impl ContractBuilder {
@@ -199,6 +234,7 @@ impl ContractBuilder {
             functions: HashMap::new(),
             metadata: HashMap::new(),
             wasm_code: None,
+            nondeterministic: false,
         }
     }
 
@@ -226,6 +262,22 @@ impl ContractBuilder {
         self
     }
 
+    /// Declare the graph predicates the contract may write
+    ///
+    /// Stored under `graph_predicates` in the state schema.
+    pub fn graph_predicates(mut self, predicates: Vec<&str>) -> Self {
+        let schema = self
+            .state_schema
+            .get_or_insert_with(|| serde_json::json!({}));
+        if let serde_json::Value::Object(map) = schema {
+            map.insert(
+                GRAPH_PREDICATES_KEY.to_string(),
+                serde_json::json!(predicates),
+            );
+        }
+        self
+    }
+
     /// Add a function with just name and params
     pub fn function(mut self, name: &str, params: Vec<&str>) -> Self {
         let func = ContractFunction::new(name).with_params(params);
@@ -279,6 +331,15 @@ impl ContractBuilder {
         self
     }
 
+    /// Mark the contract as nondeterministic
+    ///
+    /// Allows floating-point instructions in its WASM code, whose results
+    /// may differ between nodes.
+    pub fn nondeterministic(mut self) -> Self {
+        self.nondeterministic = true;
+        self
+    }
+
     /// Build the contract
     pub fn build(self) -> Result<Contract> {
         if self.name.is_empty() {
@@ -308,6 +369,7 @@ impl ContractBuilder {
             functions: self.functions,
             metadata: self.metadata,
             wasm_code: self.wasm_code,
+            nondeterministic: self.nondeterministic,
             created_at,
         })
     }
@@ -549,4 +611,21 @@ mod tests {
         assert_eq!(contract.name, deserialized.name);
         assert_eq!(contract.version, deserialized.version);
     }
+
+    #[test]
+    fn test_graph_predicates() {
+        let contract = ContractBuilder::new("provenance")
+            .state_schema(serde_json::json!({"products": "u64"}))
+            .graph_predicates(vec!["current_owner"])
+            .build()
+            .unwrap();
+
+        assert_eq!(contract.graph_predicates(), vec!["current_owner"]);
+        assert!(contract.can_write_predicate("current_owner"));
+        assert!(!contract.can_write_predicate("manufacturer"));
+        assert!(contract.state_schema.unwrap().get("products").is_some());
+
+        let plain = ContractBuilder::new("plain").build().unwrap();
+        assert!(plain.graph_predicates().is_empty());
+    }
 }
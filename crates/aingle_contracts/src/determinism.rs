@@ -0,0 +1,215 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Deterministic execution audit
+//!
+//! Contract results must be reproducible on every node, so the WASM code of a
+//! contract is audited before it is deployed:
+//!
+//! - Floating-point instructions are rejected, since NaN payloads differ
+//!   across architectures. Contracts built with
+//!   [`ContractBuilder::nondeterministic`](crate::contract::ContractBuilder::nondeterministic)
+//!   may use them.
+//! - Host imports that expose wall-clock time or randomness are always
+//!   rejected. Contracts use the deterministic host functions in
+//!   [`DETERMINISTIC_HOST_FUNCTIONS`] instead: the block timestamp and a PRNG
+//!   seeded from the call context.
+
+use wasmparser::{Operator, Parser, Payload, TypeRef};
+
+use crate::error::{ContractError, Result};
+
+/// Module that contract host functions are imported from
+pub const HOST_MODULE: &str = "env";
+
+/// Host functions that stand in for wall-clock time and randomness
+///
+/// Their results depend only on the execution context, so every node
+/// computes the same value.
+pub const DETERMINISTIC_HOST_FUNCTIONS: &[&str] =
+    &["block_number", "block_timestamp", "random_u64"];
+
+/// Import name segments that indicate access to wall-clock time or randomness
+const NONDETERMINISTIC_IMPORT_SEGMENTS: &[&str] = &[
+    "clock",
+    "date",
+    "entropy",
+    "getrandom",
+    "gettimeofday",
+    "now",
+    "rand",
+    "random",
+    "time",
+    "timestamp",
+];
+
+/// Audit a WASM module for constructs that make execution nondeterministic
+///
+/// Fails with [`ContractError::NonDeterministicConstruct`] naming the first
+/// offending import or instruction. Float instructions are accepted when
+/// `allow_floats` is set; time and randomness imports never are.
+pub fn audit(code: &[u8], allow_floats: bool) -> Result<()> {
+    wasmparser::validate(code).map_err(|e| ContractError::InvalidWasm(e.to_string()))?;
+
+    let mut imported_functions = 0u32;
+    let mut defined_functions = 0u32;
+    for payload in Parser::new(0).parse_all(code) {
+        match payload.map_err(invalid_wasm)? {
+            Payload::ImportSection(reader) => {
+                for import in reader.into_imports() {
+                    let import = import.map_err(invalid_wasm)?;
+                    if matches!(import.ty, TypeRef::Func(_) | TypeRef::FuncExact(_)) {
+                        imported_functions += 1;
+                    }
+                    if is_nondeterministic_import(import.module, import.name) {
+                        return Err(ContractError::NonDeterministicConstruct(format!(
+                            "import {}.{} exposes wall-clock time or randomness",
+                            import.module, import.name
+                        )));
+                    }
+                }
+            }
+            Payload::CodeSectionEntry(body) if !allow_floats => {
+                let index = imported_functions + defined_functions;
+                defined_functions += 1;
+                let operators = body.get_operators_reader().map_err(invalid_wasm)?;
+                for item in operators.into_iter_with_offsets() {
+                    let (op, offset) = item.map_err(invalid_wasm)?;
+                    if let Some(name) = float_instruction(&op) {
+                        return Err(ContractError::NonDeterministicConstruct(format!(
+                            "float instruction {} in function {} at offset {:#x}",
+                            name, index, offset
+                        )));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Check whether an import exposes wall-clock time or randomness
+pub fn is_nondeterministic_import(module: &str, name: &str) -> bool {
+    if module == HOST_MODULE && DETERMINISTIC_HOST_FUNCTIONS.contains(&name) {
+        return false;
+    }
+    name.to_ascii_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .any(|segment| NONDETERMINISTIC_IMPORT_SEGMENTS.contains(&segment))
+}
+
+/// Name of `op` if it is a floating-point instruction
+///
+/// Covers arithmetic, constants, loads and stores, conversions to and from
+/// integers, and the SIMD `f32x4`/`f64x2` lanes.
+fn float_instruction(op: &Operator<'_>) -> Option<String> {
+    let debug = format!("{:?}", op);
+    let name = debug.split([' ', '{', '(']).next().unwrap_or_default();
+    (name.contains("F32") || name.contains("F64")).then(|| name.to_string())
+}
+
+fn invalid_wasm(e: wasmparser::BinaryReaderError) -> ContractError {
+    ContractError::InvalidWasm(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_encoder::{
+        CodeSection, EntityType, Function, FunctionSection, ImportSection, Module, TypeSection,
+        ValType,
+    };
+
+    /// Module with one function of type `ty -> ty` whose body is built by `body`
+    fn module(ty: ValType, imports: &[(&str, &str)], body: impl FnOnce(&mut Function)) -> Vec<u8> {
+        let mut types = TypeSection::new();
+        types.ty().function([ty], [ty]);
+        types.ty().function([], [ValType::I64]);
+
+        let mut import_section = ImportSection::new();
+        for (module, name) in imports {
+            import_section.import(module, name, EntityType::Function(1));
+        }
+        let mut functions = FunctionSection::new();
+        functions.function(0);
+        let mut function = Function::new([]);
+        body(&mut function);
+        function.instructions().end();
+        let mut code = CodeSection::new();
+        code.function(&function);
+
+        let mut module = Module::new();
+        module.section(&types);
+        if !imports.is_empty() {
+            module.section(&import_section);
+        }
+        module.section(&functions).section(&code);
+        module.finish()
+    }
+
+    fn float_module() -> Vec<u8> {
+        module(ValType::F32, &[], |f| {
+            f.instructions().local_get(0).local_get(0).f32_add();
+        })
+    }
+
+    #[test]
+    fn test_integer_module_passes() {
+        let code = module(ValType::I32, &[(HOST_MODULE, "block_timestamp")], |f| {
+            f.instructions().local_get(0).local_get(0).i32_add();
+        });
+        assert!(audit(&code, false).is_ok());
+    }
+
+    #[test]
+    fn test_float_instruction_named() {
+        match audit(&float_module(), false) {
+            Err(ContractError::NonDeterministicConstruct(detail)) => {
+                assert!(detail.contains("F32Add"), "{}", detail);
+                assert!(detail.contains("function 0"), "{}", detail);
+            }
+            other => panic!("expected NonDeterministicConstruct, got {:?}", other),
+        }
+        assert!(audit(&float_module(), true).is_ok());
+    }
+
+    #[test]
+    fn test_time_and_random_imports_denied() {
+        for (module_name, name) in [
+            ("wasi_snapshot_preview1", "clock_time_get"),
+            ("wasi_snapshot_preview1", "random_get"),
+            (HOST_MODULE, "now"),
+            (HOST_MODULE, "Date.now"),
+        ] {
+            let code = module(ValType::I32, &[(module_name, name)], |f| {
+                f.instructions().local_get(0);
+            });
+            // The flag for floats does not relax the import check
+            match audit(&code, true) {
+                Err(ContractError::NonDeterministicConstruct(detail)) => {
+                    assert!(detail.contains(name), "{}", detail);
+                }
+                other => panic!("{}.{} accepted: {:?}", module_name, name, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_import_classification() {
+        for name in DETERMINISTIC_HOST_FUNCTIONS {
+            assert!(!is_nondeterministic_import(HOST_MODULE, name));
+        }
+        assert!(is_nondeterministic_import("other", "block_timestamp"));
+        assert!(!is_nondeterministic_import(HOST_MODULE, "storage_read"));
+        assert!(!is_nondeterministic_import(HOST_MODULE, "operand"));
+    }
+
+    #[test]
+    fn test_invalid_module_rejected() {
+        assert!(matches!(
+            audit(b"not wasm", false),
+            Err(ContractError::InvalidWasm(_))
+        ));
+    }
+}
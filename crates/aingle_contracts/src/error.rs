@@ -63,6 +63,10 @@ pub enum ContractError {
     #[error("Invalid WASM: {0}")]
     InvalidWasm(String),
 
+    /// Construct that makes execution differ between nodes
+    #[error("Non-deterministic construct: {0}")]
+    NonDeterministicConstruct(String),
+
     /// Host function error
     #[error("Host function error: {0}")]
     HostFunctionError(String),
@@ -7,6 +7,8 @@
 //! - Domain-specific language for contract definitions
 //! - WASM-based execution environment
 //! - Secure host functions for blockchain interaction
+//! - Deterministic execution audit of contract code
+//! - Staged, permissioned reads and writes of the semantic graph
 //! - Efficient contract storage
 //!
 //! ## Contract Definition
@@ -37,6 +39,7 @@
 //! ```
 
 pub mod contract;
+pub mod determinism;
 pub mod error;
 pub mod storage;
 pub mod types;
@@ -52,7 +55,7 @@ pub mod prelude {
     pub use crate::types::{Address, CallResult, ContractId, Gas};
 
     #[cfg(feature = "runtime")]
-    pub use crate::runtime::{ContractRuntime, ExecutionContext};
+    pub use crate::runtime::{ContractRuntime, ExecutionContext, GraphOp, HostEnv};
 }
 
 pub use prelude::*;
@@ -5,6 +5,7 @@
 //!
 //! Provides sandboxed execution environment for contracts.
 
+use aingle_graph::{GraphDB, Triple, TripleId, TriplePattern};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::{debug, info};
@@ -33,6 +34,19 @@ pub struct ExecutionContext {
     pub depth: u32,
     /// Max call depth
     pub max_depth: u32,
+    /// Graph mutations staged by the call, committed only if it succeeds
+    pub staged_graph: Vec<GraphOp>,
+    /// Values drawn so far from the call's PRNG
+    pub random_draws: u64,
+}
+
+/// A graph mutation staged by a contract call
+#[derive(Debug, Clone, PartialEq)]
+pub enum GraphOp {
+    /// Insert a triple
+    Assert(Box<Triple>),
+    /// Delete a triple
+    Retract(TripleId),
 }
 
 impl ExecutionContext {
@@ -50,9 +64,18 @@ impl ExecutionContext {
                 .unwrap_or(0),
             depth: 0,
             max_depth: 10,
+            staged_graph: Vec::new(),
+            random_draws: 0,
         }
     }
 
+    /// Set the block the call executes in
+    pub fn with_block(mut self, number: u64, timestamp: u64) -> Self {
+        self.block_number = number;
+        self.block_timestamp = timestamp;
+        self
+    }
+
     /// Set value
     pub fn with_value(mut self, value: u64) -> Self {
         self.value = value;
@@ -85,6 +108,13 @@ impl Default for ExecutionContext {
     }
 }
 
+/// Native implementation of a contract function
+///
+/// Receives the host environment and the call arguments, and returns the
+/// call's value. Returning an error reverts the call.
+pub type NativeFunction =
+    Arc<dyn Fn(&mut HostEnv<'_>, &[serde_json::Value]) -> Result<serde_json::Value> + Send + Sync>;
+
 /// Contract runtime
 pub struct ContractRuntime {
     /// Storage backend
@@ -93,6 +123,42 @@ pub struct ContractRuntime {
     contracts: HashMap<Address, ContractInstance>,
     /// Gas prices for operations
     gas_prices: GasPrices,
+    /// Semantic graph exposed to contracts through host functions
+    graph: Option<Arc<GraphDB>>,
+    /// Native function bodies, by contract address and function name
+    natives: HashMap<(Address, String), NativeFunction>,
+}
+
+/// Host environment passed to native contract functions
+///
+/// Gives access to the execution context and the graph host functions in
+/// [`host`].
+pub struct HostEnv<'a> {
+    /// Context of the running call
+    pub ctx: &'a mut ExecutionContext,
+    /// The contract being executed
+    pub instance: &'a ContractInstance,
+    graph: Option<&'a GraphDB>,
+    gas_prices: &'a GasPrices,
+}
+
+impl HostEnv<'_> {
+    /// Consume gas, failing the call if the limit is exceeded
+    pub fn charge(&mut self, amount: u64) -> Result<()> {
+        self.ctx
+            .gas_limit
+            .consume(amount)
+            .map_err(|_| ContractError::OutOfGas {
+                used: amount,
+                limit: self.ctx.gas_limit.0,
+            })
+    }
+
+    fn graph(&self) -> Result<&GraphDB> {
+        self.graph.ok_or_else(|| {
+            ContractError::HostFunctionError("No graph attached to the runtime".into())
+        })
+    }
 }
 
 /// Gas prices for different operations
@@ -110,6 +176,12 @@ pub struct GasPrices {
     pub event_emit: u64,
     /// Cost for logging
     pub log: u64,
+    /// Base cost for a graph query
+    pub graph_read: u64,
+    /// Cost per triple returned by a graph query
+    pub graph_read_per_triple: u64,
+    /// Cost for staging a graph assertion or retraction
+    pub graph_write: u64,
 }
 
 impl Default for GasPrices {
@@ -121,6 +193,9 @@ impl Default for GasPrices {
             storage_write: 5000,
             event_emit: 375,
             log: 100,
+            graph_read: 200,
+            graph_read_per_triple: 50,
+            graph_write: 5000,
         }
     }
 }
@@ -132,6 +207,8 @@ impl ContractRuntime {
             storage: Arc::new(MemoryStorage::new()),
             contracts: HashMap::new(),
             gas_prices: GasPrices::default(),
+            graph: None,
+            natives: HashMap::new(),
         })
     }
 
@@ -141,6 +218,8 @@ impl ContractRuntime {
             storage,
             contracts: HashMap::new(),
             gas_prices: GasPrices::default(),
+            graph: None,
+            natives: HashMap::new(),
         }
     }
 
@@ -150,6 +229,34 @@ impl ContractRuntime {
         self
     }
 
+    /// Attach the semantic graph contracts read and write through host functions
+    pub fn with_graph(mut self, graph: Arc<GraphDB>) -> Self {
+        self.graph = Some(graph);
+        self
+    }
+
+    /// Register a native body for a function of a deployed contract
+    ///
+    /// Calls to the function run `body` instead of the built-in handlers.
+    pub fn register_native<F>(&mut self, address: &Address, function: &str, body: F) -> Result<()>
+    where
+        F: Fn(&mut HostEnv<'_>, &[serde_json::Value]) -> Result<serde_json::Value>
+            + Send
+            + Sync
+            + 'static,
+    {
+        let instance = self
+            .contracts
+            .get(address)
+            .ok_or_else(|| ContractError::ContractNotFound(address.to_hex()))?;
+        if !instance.contract.has_function(function) {
+            return Err(ContractError::FunctionNotFound(function.to_string()));
+        }
+        self.natives
+            .insert((address.clone(), function.to_string()), Arc::new(body));
+        Ok(())
+    }
+
     /// Deploy a contract
     pub fn deploy(
         &mut self,
@@ -160,6 +267,9 @@ impl ContractRuntime {
     ) -> Result<Address> {
         info!("Deploying contract: {}", contract.name);
 
+        // Results must be reproducible on every node
+        contract.audit_determinism()?;
+
         // Create instance
         let instance = ContractInstance::new(contract.clone(), deployer, initial_state.clone());
         let address = instance.address.clone();
@@ -241,12 +351,58 @@ impl ContractRuntime {
                 limit: ctx.gas_limit.0 + base_cost,
             })?;
 
-        // Execute (simplified - real impl would run WASM)
-        let result = self.execute_function(instance, func.name.as_str(), args, ctx)?;
+        // Execute (simplified - real impl would run WASM). Graph writes staged
+        // by a failed call are discarded so it leaves no side effects.
+        let staged = ctx.staged_graph.len();
+        let mut result = match self.execute_function(instance, func.name.as_str(), args, ctx) {
+            Ok(result) => result,
+            Err(e) => {
+                ctx.staged_graph.truncate(staged);
+                return Err(e);
+            }
+        };
+
+        // Nested calls leave their writes to the outermost call
+        if ctx.depth == 0 {
+            self.commit_graph(ctx, &mut result)?;
+        }
 
         Ok(result)
     }
 
+    /// Apply the graph mutations staged in `ctx` to the graph
+    fn commit_graph(&self, ctx: &mut ExecutionContext, result: &mut CallResult) -> Result<()> {
+        if ctx.staged_graph.is_empty() {
+            return Ok(());
+        }
+        let graph = self.graph.as_ref().ok_or_else(|| {
+            ContractError::HostFunctionError("No graph attached to the runtime".into())
+        })?;
+
+        for op in ctx.staged_graph.drain(..) {
+            match op {
+                GraphOp::Assert(triple) => {
+                    let id = triple.id();
+                    if !graph.contains(&triple).map_err(graph_error)? {
+                        graph.insert(*triple).map_err(graph_error)?;
+                    }
+                    result.events.push(Event::new(
+                        "GraphAsserted",
+                        serde_json::json!({ "triple_id": id.to_hex() }),
+                    ));
+                }
+                GraphOp::Retract(id) => {
+                    graph.delete(&id).map_err(graph_error)?;
+                    result.events.push(Event::new(
+                        "GraphRetracted",
+                        serde_json::json!({ "triple_id": id.to_hex() }),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Maximum size of a single storage value (64KB)
     const MAX_STORAGE_VALUE_SIZE: usize = 64 * 1024;
 
@@ -271,6 +427,21 @@ impl ContractRuntime {
                 limit: ctx.gas_limit.0 + input_gas,
             })?;
 
+        if let Some(native) = self
+            .natives
+            .get(&(instance.address.clone(), function.to_string()))
+        {
+            let mut env = HostEnv {
+                ctx,
+                instance,
+                graph: self.graph.as_deref(),
+                gas_prices: &self.gas_prices,
+            };
+            result.value = native(&mut env, args)?;
+            result.gas_used = gas_start - ctx.gas_limit.remaining();
+            return Ok(result);
+        }
+
         // Simplified execution - in real impl, this would run WASM code
         match function {
             "get" | "balance_of" | "get_balance" => {
@@ -388,6 +559,10 @@ impl ContractRuntime {
     }
 }
 
+fn graph_error(e: aingle_graph::Error) -> ContractError {
+    ContractError::HostFunctionError(format!("Graph error: {}", e))
+}
+
 impl Default for ContractRuntime {
     fn default() -> Self {
         Self::new().expect("Failed to create default runtime")
@@ -414,10 +589,33 @@ pub mod host {
     }
 
     /// Get current block timestamp
+    ///
+    /// Contracts use this instead of the wall clock.
     pub fn block_timestamp(ctx: &ExecutionContext) -> u64 {
         ctx.block_timestamp
     }
 
+    /// Draw the next value from the call's PRNG
+    ///
+    /// The sequence is derived from the contract, caller, value and block,
+    /// so every node executing the call draws the same values. It is not
+    /// unpredictable: anyone who knows the call context can compute it.
+    pub fn random_u64(ctx: &mut ExecutionContext) -> u64 {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(b"aingle_prng:");
+        hasher.update(ctx.contract.as_bytes());
+        hasher.update(ctx.caller.as_bytes());
+        hasher.update(&ctx.value.to_le_bytes());
+        hasher.update(&ctx.block_number.to_le_bytes());
+        hasher.update(&ctx.block_timestamp.to_le_bytes());
+        hasher.update(&ctx.random_draws.to_le_bytes());
+        ctx.random_draws += 1;
+
+        let mut value = [0u8; 8];
+        value.copy_from_slice(&hasher.finalize().as_bytes()[..8]);
+        u64::from_le_bytes(value)
+    }
+
     /// Get caller address
     pub fn caller(ctx: &ExecutionContext) -> &Address {
         &ctx.caller
@@ -440,6 +638,111 @@ pub mod host {
         hasher.update(data);
         hasher.finalize().into()
     }
+
+    /// Maximum number of triples a single `graph_find` may return
+    pub const MAX_GRAPH_FIND_RESULTS: usize = 256;
+
+    /// Find triples matching `pattern`
+    ///
+    /// Sees the call's own staged writes. Returns at most `limit` triples
+    /// (capped at [`MAX_GRAPH_FIND_RESULTS`]) and charges gas per triple
+    /// returned.
+    pub fn graph_find(
+        env: &mut HostEnv<'_>,
+        pattern: &TriplePattern,
+        limit: usize,
+    ) -> Result<Vec<Triple>> {
+        env.charge(env.gas_prices.graph_read)?;
+        let limit = limit.min(MAX_GRAPH_FIND_RESULTS);
+
+        let mut found: Vec<Triple> = env
+            .graph()?
+            .find(pattern.clone())
+            .map_err(graph_error)?
+            .into_iter()
+            .filter(|t| !is_retracted(env.ctx, &t.id()))
+            .collect();
+        for op in &env.ctx.staged_graph {
+            if let GraphOp::Assert(triple) = op {
+                if pattern.matches(triple) && !found.iter().any(|t| t.id() == triple.id()) {
+                    found.push(triple.as_ref().clone());
+                }
+            }
+        }
+        found.truncate(limit);
+
+        env.charge(env.gas_prices.graph_read_per_triple * found.len() as u64)?;
+        Ok(found)
+    }
+
+    /// Stage the insertion of `triple`
+    ///
+    /// The contract's state schema must list the triple's predicate.
+    pub fn graph_assert(env: &mut HostEnv<'_>, triple: Triple) -> Result<TripleId> {
+        check_writable(env, triple.predicate.as_str())?;
+        env.graph()?;
+        env.charge(env.gas_prices.graph_write)?;
+
+        let id = triple.id();
+        env.ctx
+            .staged_graph
+            .retain(|op| op != &GraphOp::Retract(id.clone()));
+        env.ctx.staged_graph.push(GraphOp::Assert(Box::new(triple)));
+        Ok(id)
+    }
+
+    /// Stage the deletion of the triple with `id`
+    ///
+    /// The triple must exist (or have been asserted earlier in the call) and
+    /// the contract's state schema must list its predicate.
+    pub fn graph_retract(env: &mut HostEnv<'_>, id: &TripleId) -> Result<()> {
+        let staged = env.ctx.staged_graph.iter().find_map(|op| match op {
+            GraphOp::Assert(t) if &t.id() == id => Some(t.as_ref().clone()),
+            _ => None,
+        });
+        let stored = env
+            .graph()?
+            .get(id)
+            .map_err(graph_error)?
+            .filter(|_| !is_retracted(env.ctx, id));
+        let predicate = match staged.as_ref().or(stored.as_ref()) {
+            Some(triple) => triple.predicate.as_str().to_string(),
+            None => {
+                return Err(ContractError::InvalidInput(format!(
+                    "Triple not found: {}",
+                    id
+                )))
+            }
+        };
+        check_writable(env, &predicate)?;
+        env.charge(env.gas_prices.graph_write)?;
+
+        // Retracting a triple asserted earlier in the call just unstages it
+        env.ctx
+            .staged_graph
+            .retain(|op| !matches!(op, GraphOp::Assert(t) if &t.id() == id));
+        if stored.is_some() {
+            env.ctx.staged_graph.push(GraphOp::Retract(id.clone()));
+        }
+        Ok(())
+    }
+
+    fn check_writable(env: &HostEnv<'_>, predicate: &str) -> Result<()> {
+        if env.instance.contract.can_write_predicate(predicate) {
+            Ok(())
+        } else {
+            Err(ContractError::PermissionDenied(format!(
+                "Contract {} may not write predicate '{}'",
+                env.instance.contract.name, predicate
+            )))
+        }
+    }
+
+    fn is_retracted(ctx: &ExecutionContext, id: &TripleId) -> bool {
+        ctx.staged_graph
+            .iter()
+            .any(|op| matches!(op, GraphOp::Retract(r) if r == id))
+    }
 }
 
 #[cfg(test)]
@@ -585,9 +888,266 @@ mod tests {
             block_timestamp: 0,
             depth: 10,
             max_depth: 10,
+            staged_graph: Vec::new(),
+            random_draws: 0,
         };
 
         let result = ctx.nested();
         assert!(matches!(result, Err(ContractError::ReentrancyDetected(_))));
     }
+
+    fn owner_triple(product: &str, owner: &str) -> Triple {
+        Triple::new(
+            aingle_graph::NodeId::named(product),
+            aingle_graph::Predicate::named("current_owner"),
+            aingle_graph::Value::Node(aingle_graph::NodeId::named(owner)),
+        )
+    }
+
+    /// Deploys a provenance contract whose `transfer(product, new_owner)`
+    /// moves `current_owner`, failing unless the caller is the owner.
+    fn provenance_runtime(graph: Arc<GraphDB>) -> (ContractRuntime, Address) {
+        let mut runtime = ContractRuntime::new().unwrap().with_graph(graph);
+        let contract = ContractBuilder::new("provenance")
+            .graph_predicates(vec!["current_owner"])
+            .function("transfer", vec!["product", "new_owner"])
+            .function("tag", vec!["product"])
+            .build()
+            .unwrap();
+        let deployer = Address::derive("deployer");
+        let ctx = ExecutionContext::new(deployer.clone(), Address::zero());
+        let address = runtime
+            .deploy(contract, deployer, serde_json::json!({}), &ctx)
+            .unwrap();
+
+        runtime
+            .register_native(&address, "transfer", |env, args| {
+                let product = args[0].as_str().unwrap_or_default();
+                let new_owner = args[1].as_str().unwrap_or_default();
+                let pattern = TriplePattern::subject(aingle_graph::NodeId::named(product))
+                    .with_predicate(aingle_graph::Predicate::named("current_owner"));
+
+                let current = host::graph_find(env, &pattern, 1)?;
+                let current = current.first().ok_or_else(|| {
+                    ContractError::ExecutionError(format!("{} has no owner", product))
+                })?;
+                let new_id = host::graph_assert(env, owner_triple(product, new_owner))?;
+                host::graph_retract(env, &current.id())?;
+
+                // The check runs after staging so a failure must discard the writes
+                let owner = match &current.object {
+                    aingle_graph::Value::Node(aingle_graph::NodeId::Named(name)) => name.clone(),
+                    _ => String::new(),
+                };
+                if env.ctx.caller != Address::derive(&owner) {
+                    return Err(ContractError::PermissionDenied(
+                        "caller is not the owner".into(),
+                    ));
+                }
+                Ok(serde_json::json!(new_id.to_hex()))
+            })
+            .unwrap();
+        runtime
+            .register_native(&address, "tag", |env, args| {
+                let product = args[0].as_str().unwrap_or_default();
+                let triple = Triple::new(
+                    aingle_graph::NodeId::named(product),
+                    aingle_graph::Predicate::named("manufacturer"),
+                    aingle_graph::Value::literal("acme"),
+                );
+                host::graph_assert(env, triple)?;
+                Ok(serde_json::Value::Null)
+            })
+            .unwrap();
+
+        (runtime, address)
+    }
+
+    fn owners(graph: &GraphDB, product: &str) -> Vec<Triple> {
+        graph
+            .find(TriplePattern::subject(aingle_graph::NodeId::named(product)))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_graph_transfer_commits_on_success() {
+        let graph = Arc::new(GraphDB::memory().unwrap());
+        graph.insert(owner_triple("product:1", "alice")).unwrap();
+        let (runtime, address) = provenance_runtime(graph.clone());
+
+        let mut ctx = ExecutionContext::new(Address::derive("alice"), address.clone());
+        let result = runtime
+            .call(
+                &address,
+                "transfer",
+                &[serde_json::json!("product:1"), serde_json::json!("bob")],
+                &mut ctx,
+            )
+            .unwrap();
+
+        let triples = owners(&graph, "product:1");
+        assert_eq!(triples.len(), 1);
+        assert_eq!(triples[0].id(), owner_triple("product:1", "bob").id());
+        assert!(ctx.staged_graph.is_empty());
+        assert!(result.events.iter().any(|e| e.name == "GraphAsserted"));
+        assert!(result.events.iter().any(|e| e.name == "GraphRetracted"));
+        // Base, read, one returned triple and two writes
+        assert!(result.gas_used >= 200 + 50 + 2 * 5000);
+    }
+
+    #[test]
+    fn test_failed_call_leaves_graph_untouched() {
+        let graph = Arc::new(GraphDB::memory().unwrap());
+        graph.insert(owner_triple("product:1", "alice")).unwrap();
+        let (runtime, address) = provenance_runtime(graph.clone());
+
+        let mut ctx = ExecutionContext::new(Address::derive("mallory"), address.clone());
+        let result = runtime.call(
+            &address,
+            "transfer",
+            &[serde_json::json!("product:1"), serde_json::json!("mallory")],
+            &mut ctx,
+        );
+
+        assert!(matches!(result, Err(ContractError::PermissionDenied(_))));
+        assert!(ctx.staged_graph.is_empty());
+        let triples = owners(&graph, "product:1");
+        assert_eq!(triples.len(), 1);
+        assert_eq!(triples[0].id(), owner_triple("product:1", "alice").id());
+    }
+
+    #[test]
+    fn test_graph_write_requires_declared_predicate() {
+        let graph = Arc::new(GraphDB::memory().unwrap());
+        let (runtime, address) = provenance_runtime(graph.clone());
+
+        let mut ctx = ExecutionContext::new(Address::derive("anyone"), address.clone());
+        let result = runtime.call(&address, "tag", &[serde_json::json!("product:1")], &mut ctx);
+
+        assert!(matches!(result, Err(ContractError::PermissionDenied(_))));
+        assert!(owners(&graph, "product:1").is_empty());
+    }
+
+    #[test]
+    fn test_graph_find_is_bounded() {
+        let graph = Arc::new(GraphDB::memory().unwrap());
+        for i in 0..(host::MAX_GRAPH_FIND_RESULTS + 10) {
+            graph
+                .insert(owner_triple(&format!("product:{}", i), "alice"))
+                .unwrap();
+        }
+        let (runtime, address) = provenance_runtime(graph);
+        let instance = runtime.get_contract(&address).unwrap();
+        let mut ctx = ExecutionContext::new(Address::zero(), address.clone());
+        let mut env = HostEnv {
+            ctx: &mut ctx,
+            instance,
+            graph: runtime.graph.as_deref(),
+            gas_prices: &runtime.gas_prices,
+        };
+
+        let pattern = TriplePattern::predicate(aingle_graph::Predicate::named("current_owner"));
+        let found = host::graph_find(&mut env, &pattern, usize::MAX).unwrap();
+        assert_eq!(found.len(), host::MAX_GRAPH_FIND_RESULTS);
+        let found = host::graph_find(&mut env, &pattern, 3).unwrap();
+        assert_eq!(found.len(), 3);
+    }
+
+    /// Module with one exported `f32 -> f32` function that adds its argument to itself
+    fn float_module() -> Vec<u8> {
+        use wasm_encoder::{
+            CodeSection, ExportKind, ExportSection, Function, FunctionSection, Module, TypeSection,
+            ValType,
+        };
+
+        let mut types = TypeSection::new();
+        types.ty().function([ValType::F32], [ValType::F32]);
+        let mut functions = FunctionSection::new();
+        functions.function(0);
+        let mut exports = ExportSection::new();
+        exports.export("double", ExportKind::Func, 0);
+        let mut body = Function::new([]);
+        body.instructions()
+            .local_get(0)
+            .local_get(0)
+            .f32_add()
+            .end();
+        let mut code = CodeSection::new();
+        code.function(&body);
+
+        let mut module = Module::new();
+        module
+            .section(&types)
+            .section(&functions)
+            .section(&exports)
+            .section(&code);
+        module.finish()
+    }
+
+    #[test]
+    fn test_deploy_rejects_float_module() {
+        let mut runtime = ContractRuntime::new().unwrap();
+        let deployer = Address::derive("deployer");
+        let ctx = ExecutionContext::new(deployer.clone(), Address::zero());
+
+        let contract = ContractBuilder::new("floaty")
+            .function("double", vec!["x"])
+            .wasm(float_module())
+            .build()
+            .unwrap();
+        let result = runtime.deploy(contract, deployer.clone(), serde_json::json!({}), &ctx);
+        match result {
+            Err(ContractError::NonDeterministicConstruct(detail)) => {
+                assert!(detail.contains("F32Add"), "{}", detail)
+            }
+            other => panic!("expected NonDeterministicConstruct, got {:?}", other),
+        }
+        assert!(runtime.list_contracts().is_empty());
+
+        let contract = ContractBuilder::new("floaty")
+            .function("double", vec!["x"])
+            .wasm(float_module())
+            .nondeterministic()
+            .build()
+            .unwrap();
+        let address = runtime
+            .deploy(contract, deployer, serde_json::json!({}), &ctx)
+            .unwrap();
+        assert!(runtime.has_contract(&address));
+    }
+
+    #[test]
+    fn test_prng_is_reproducible() {
+        let mut runtime = ContractRuntime::new().unwrap();
+        let contract = ContractBuilder::new("lottery")
+            .function("draw", vec![])
+            .build()
+            .unwrap();
+        let deployer = Address::derive("deployer");
+        let ctx = ExecutionContext::new(deployer.clone(), Address::zero());
+        let address = runtime
+            .deploy(contract, deployer, serde_json::json!({}), &ctx)
+            .unwrap();
+        runtime
+            .register_native(&address, "draw", |env, _args| {
+                let draws: Vec<u64> = (0..4).map(|_| host::random_u64(env.ctx)).collect();
+                Ok(serde_json::json!(draws))
+            })
+            .unwrap();
+
+        let run = |caller: &str| {
+            let mut ctx = ExecutionContext::new(Address::derive(caller), address.clone())
+                .with_block(42, 1_700_000_000);
+            runtime.call(&address, "draw", &[], &mut ctx).unwrap().value
+        };
+
+        let first = run("alice");
+        assert_eq!(first, run("alice"));
+        assert_ne!(first, run("bob"));
+
+        // Successive draws within a call differ
+        let draws: Vec<u64> = serde_json::from_value(first).unwrap();
+        assert_eq!(draws.len(), 4);
+        assert!(draws.windows(2).all(|w| w[0] != w[1]));
+    }
 }
@@ -139,6 +139,7 @@ impl Claims {
 }
 
 /// Create token request
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Deserialize)]
 pub struct CreateTokenRequest {
     /// Username or API key
@@ -148,6 +149,7 @@ pub struct CreateTokenRequest {
 }
 
 /// Token response
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Serialize)]
 pub struct TokenResponse {
     /// Access token
@@ -163,6 +165,21 @@ pub struct TokenResponse {
 /// Create a new token
 ///
 /// POST /api/v1/auth/token
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/v1/auth/token",
+        tag = "auth",
+        request_body = CreateTokenRequest,
+        responses(
+            (status = 200, description = "Access and refresh tokens", body = TokenResponse),
+            (status = 401, description = "Invalid credentials", body = ErrorResponse),
+            (status = 500, description = "The tokens could not be signed", body = ErrorResponse),
+        ),
+        security(()),
+    )
+)]
 pub async fn create_token(
     State(state): State<AppState>,
     Json(req): Json<CreateTokenRequest>,
@@ -201,6 +218,7 @@ pub async fn create_token(
 }
 
 /// Refresh token request
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Deserialize)]
 pub struct RefreshTokenRequest {
     /// Refresh token
@@ -210,6 +228,21 @@ pub struct RefreshTokenRequest {
 /// Refresh a token
 ///
 /// POST /api/v1/auth/refresh
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/v1/auth/refresh",
+        tag = "auth",
+        request_body = RefreshTokenRequest,
+        responses(
+            (status = 200, description = "A new token pair; the refresh token is single-use", body = TokenResponse),
+            (status = 401, description = "Invalid, expired or already used refresh token", body = ErrorResponse),
+            (status = 500, description = "The tokens could not be signed", body = ErrorResponse),
+        ),
+        security(()),
+    )
+)]
 pub async fn refresh_token(
     State(_state): State<AppState>,
     Json(req): Json<RefreshTokenRequest>,
@@ -268,6 +301,7 @@ pub async fn refresh_token(
 }
 
 /// Verify token request
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Deserialize)]
 pub struct VerifyTokenRequest {
     /// Token to verify
@@ -275,6 +309,7 @@ pub struct VerifyTokenRequest {
 }
 
 /// Token verification response
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Serialize)]
 pub struct VerifyTokenResponse {
     /// Whether token is valid
@@ -290,6 +325,19 @@ pub struct VerifyTokenResponse {
 /// Verify a token
 ///
 /// POST /api/v1/auth/verify
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/v1/auth/verify",
+        tag = "auth",
+        request_body = VerifyTokenRequest,
+        responses(
+            (status = 200, description = "Whether the token is valid, with its claims", body = VerifyTokenResponse),
+        ),
+        security(()),
+    )
+)]
 pub async fn verify_token_endpoint(
     Json(req): Json<VerifyTokenRequest>,
 ) -> Json<VerifyTokenResponse> {
@@ -326,6 +374,7 @@ pub fn verify_token(token: &str) -> Result<Claims> {
 }
 
 /// Register request
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Deserialize)]
 pub struct RegisterRequest {
     /// Username
@@ -335,6 +384,7 @@ pub struct RegisterRequest {
 }
 
 /// Register response
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Serialize)]
 pub struct RegisterResponse {
     /// User ID
@@ -348,6 +398,20 @@ pub struct RegisterResponse {
 /// Register a new user
 ///
 /// POST /api/v1/auth/register
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/v1/auth/register",
+        tag = "auth",
+        request_body = RegisterRequest,
+        responses(
+            (status = 200, description = "The user was registered", body = RegisterResponse),
+            (status = 400, description = "Username taken or invalid", body = ErrorResponse),
+        ),
+        security(()),
+    )
+)]
 pub async fn register(
     State(state): State<AppState>,
     Json(req): Json<RegisterRequest>,
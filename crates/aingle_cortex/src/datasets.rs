@@ -0,0 +1,346 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Named datasets: several isolated graphs served by one Córtex.
+//!
+//! The graph an [`AppState`] is created with is the **default dataset**, which
+//! the unprefixed routes (`/api/v1/triples`, ...) keep serving. Additional
+//! datasets are declared in
+//! [`CortexConfig::datasets`](crate::server::CortexConfig::datasets) or created
+//! at runtime through `POST /api/v1/datasets`, and are served under
+//! `/api/v1/datasets/{name}/...`. Each dataset has its own graph backend,
+//! tombstones and event broadcaster; the logic engine, proof store, audit log
+//! and users are shared by all of them.
+//!
+//! Datasets are opened lazily, on the first request that touches them, and
+//! stay open for the lifetime of the server.
+
+use aingle_graph::GraphDB;
+use axum::extract::{FromRequestParts, RawPathParams};
+use axum::http::request::Parts;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tokio::sync::RwLock;
+
+use crate::error::{Error, Result};
+use crate::middleware::RequestPrincipal;
+use crate::state::{AppState, EventBroadcaster};
+use crate::tombstones::TombstoneStore;
+
+/// Name of the dataset served by the unprefixed routes.
+pub const DEFAULT_DATASET: &str = "default";
+
+/// Longest accepted dataset name.
+pub const MAX_DATASET_NAME_LEN: usize = 64;
+
+/// A named dataset and where its graph is stored.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DatasetConfig {
+    /// Name used in routes and auth scopes
+    pub name: String,
+    /// Graph database path, or `":memory:"` for a volatile dataset. Tombstones
+    /// are kept in a `tombstones.sled` next to it.
+    pub db_path: String,
+}
+
+impl DatasetConfig {
+    /// A dataset stored at `db_path`.
+    pub fn new(name: impl Into<String>, db_path: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            db_path: db_path.into(),
+        }
+    }
+
+    /// A volatile in-memory dataset.
+    pub fn memory(name: impl Into<String>) -> Self {
+        Self::new(name, ":memory:")
+    }
+}
+
+/// A dataset as listed by the admin endpoint.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize)]
+pub struct DatasetInfo {
+    pub name: String,
+    /// Graph database path; `None` for the default dataset, whose storage is
+    /// the server's own `db_path`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub db_path: Option<String>,
+    /// Whether the dataset has been opened since the server started
+    pub open: bool,
+}
+
+/// The per-dataset parts of an [`AppState`].
+#[derive(Clone)]
+pub struct DatasetHandle {
+    pub graph: Arc<RwLock<GraphDB>>,
+    pub tombstones: Arc<TombstoneStore>,
+    pub broadcaster: Arc<EventBroadcaster>,
+}
+
+impl DatasetHandle {
+    /// Open the graph and tombstones of `config`.
+    fn open(config: &DatasetConfig) -> Result<Self> {
+        let (graph, tombstones) = if config.db_path == ":memory:" {
+            (GraphDB::memory()?, TombstoneStore::new())
+        } else {
+            let dir = Path::new(&config.db_path)
+                .parent()
+                .unwrap_or(Path::new("."));
+            std::fs::create_dir_all(dir)?;
+            let graph = GraphDB::sled(&config.db_path)?;
+            let tombstones =
+                TombstoneStore::with_sled(&dir.join("tombstones.sled").to_string_lossy())
+                    .map_err(Error::Internal)?;
+            (graph, tombstones)
+        };
+        log::info!("Opened dataset \"{}\" at {}", config.name, config.db_path);
+        Ok(Self {
+            graph: Arc::new(RwLock::new(graph)),
+            tombstones: Arc::new(tombstones),
+            broadcaster: Arc::new(EventBroadcaster::new()),
+        })
+    }
+}
+
+struct Entry {
+    config: DatasetConfig,
+    handle: Option<DatasetHandle>,
+}
+
+/// Registry of the named datasets, shared through [`AppState::datasets`].
+///
+/// The default dataset is not part of the registry; it is the graph of the
+/// [`AppState`] itself.
+pub struct DatasetRegistry {
+    /// Directory datasets created at runtime are stored under, one
+    /// subdirectory each. `None` keeps them in memory.
+    root: Option<PathBuf>,
+    entries: Mutex<BTreeMap<String, Entry>>,
+}
+
+impl DatasetRegistry {
+    /// A registry whose runtime-created datasets live in memory.
+    pub fn new() -> Self {
+        Self {
+            root: None,
+            entries: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// A registry whose runtime-created datasets are stored under `root`.
+    pub fn with_root(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: Some(root.into()),
+            entries: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Declare a dataset without opening it.
+    ///
+    /// Fails with [`Error::InvalidInput`] for a malformed or reserved name and
+    /// with [`Error::Conflict`] if the name is taken.
+    pub fn register(&self, config: DatasetConfig) -> Result<DatasetInfo> {
+        validate_name(&config.name)?;
+        let mut entries = self.entries.lock().unwrap();
+        if entries.contains_key(&config.name) {
+            return Err(Error::Conflict(format!(
+                "Dataset \"{}\" already exists",
+                config.name
+            )));
+        }
+        let info = DatasetInfo {
+            name: config.name.clone(),
+            db_path: Some(config.db_path.clone()),
+            open: false,
+        };
+        entries.insert(
+            config.name.clone(),
+            Entry {
+                config,
+                handle: None,
+            },
+        );
+        Ok(info)
+    }
+
+    /// Declare a dataset stored under the registry's root directory.
+    pub fn create(&self, name: &str) -> Result<DatasetInfo> {
+        let db_path = match &self.root {
+            Some(root) => root
+                .join(name)
+                .join("graph.sled")
+                .to_string_lossy()
+                .to_string(),
+            None => ":memory:".to_string(),
+        };
+        self.register(DatasetConfig::new(name, db_path))
+    }
+
+    /// Return the handle of a dataset, opening it on first use.
+    ///
+    /// Fails with [`Error::DatasetNotFound`] for an unknown name.
+    pub fn open(&self, name: &str) -> Result<DatasetHandle> {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries
+            .get_mut(name)
+            .ok_or_else(|| Error::DatasetNotFound(name.to_string()))?;
+        if let Some(handle) = &entry.handle {
+            return Ok(handle.clone());
+        }
+        let handle = DatasetHandle::open(&entry.config)?;
+        entry.handle = Some(handle.clone());
+        Ok(handle)
+    }
+
+    /// The registered datasets, sorted by name.
+    pub fn list(&self) -> Vec<DatasetInfo> {
+        self.entries
+            .lock()
+            .unwrap()
+            .values()
+            .map(|entry| DatasetInfo {
+                name: entry.config.name.clone(),
+                db_path: Some(entry.config.db_path.clone()),
+                open: entry.handle.is_some(),
+            })
+            .collect()
+    }
+
+    /// The handles of the datasets opened so far.
+    pub fn opened(&self) -> Vec<(String, DatasetHandle)> {
+        self.entries
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|(name, entry)| Some((name.clone(), entry.handle.clone()?)))
+            .collect()
+    }
+}
+
+impl Default for DatasetRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Check that `name` can be used in a route and an auth scope.
+fn validate_name(name: &str) -> Result<()> {
+    if name == DEFAULT_DATASET {
+        return Err(Error::InvalidInput(format!(
+            "Dataset name \"{}\" is reserved",
+            DEFAULT_DATASET
+        )));
+    }
+    let valid = !name.is_empty()
+        && name.len() <= MAX_DATASET_NAME_LEN
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-' || b == b'_')
+        && !name.starts_with(['-', '_']);
+    if !valid {
+        return Err(Error::InvalidInput(format!(
+            "Invalid dataset name \"{}\": use 1-{} lowercase letters, digits, '-' or '_'",
+            name, MAX_DATASET_NAME_LEN
+        )));
+    }
+    Ok(())
+}
+
+/// Extractor for the [`AppState`] of the dataset a request addresses.
+///
+/// Routes nested under `/api/v1/datasets/{dataset}` resolve the named
+/// dataset; all others resolve the default one. The caller's
+/// [`RequestPrincipal`] is checked against the dataset and replaced by its
+/// [dataset view](RequestPrincipal::for_dataset), so handlers extracting the
+/// principal after this only see the roles that apply here. A caller without
+/// access is rejected with `403`, an unknown dataset with `404`.
+pub struct Dataset(pub AppState);
+
+impl FromRequestParts<AppState> for Dataset {
+    type Rejection = Error;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self> {
+        let name = RawPathParams::from_request_parts(parts, state)
+            .await
+            .ok()
+            .and_then(|params| {
+                params
+                    .iter()
+                    .find(|(key, _)| *key == "dataset")
+                    .map(|(_, value)| value.to_string())
+            })
+            .unwrap_or_else(|| DEFAULT_DATASET.to_string());
+
+        let principal = parts
+            .extensions
+            .get::<RequestPrincipal>()
+            .cloned()
+            .unwrap_or_else(RequestPrincipal::unauthenticated);
+        let scoped = principal
+            .for_dataset(&name)
+            .ok_or_else(|| Error::Forbidden(format!("No access to dataset \"{}\"", name)))?;
+        parts.extensions.insert(scoped);
+
+        Ok(Self(state.dataset(&name)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dataset_names() {
+        assert!(validate_name("customer-a").is_ok());
+        assert!(validate_name("tenant_42").is_ok());
+        for name in ["", "default", "Customer", "a/b", "..", "-a", "a@b"] {
+            assert!(
+                matches!(validate_name(name), Err(Error::InvalidInput(_))),
+                "{name:?} was accepted"
+            );
+        }
+        assert!(validate_name(&"a".repeat(MAX_DATASET_NAME_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn test_registry_opens_lazily() {
+        let registry = DatasetRegistry::new();
+        registry.create("customer-a").unwrap();
+        assert!(matches!(
+            registry.create("customer-a"),
+            Err(Error::Conflict(_))
+        ));
+        assert!(!registry.list()[0].open);
+        assert!(matches!(
+            registry.open("missing"),
+            Err(Error::DatasetNotFound(_))
+        ));
+
+        let first = registry.open("customer-a").unwrap();
+        let again = registry.open("customer-a").unwrap();
+        assert!(Arc::ptr_eq(&first.graph, &again.graph));
+        assert!(registry.list()[0].open);
+        assert_eq!(registry.opened().len(), 1);
+    }
+
+    #[test]
+    fn test_registry_root_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = DatasetRegistry::with_root(dir.path());
+        let info = registry.create("customer-a").unwrap();
+        let expected = dir.path().join("customer-a").join("graph.sled");
+        assert_eq!(info.db_path.as_deref(), Some(&*expected.to_string_lossy()));
+
+        registry.open("customer-a").unwrap();
+        assert!(dir
+            .path()
+            .join("customer-a")
+            .join("tombstones.sled")
+            .exists());
+    }
+}
@@ -98,12 +98,17 @@ pub enum Error {
     #[error("Conflict: {0}")]
     Conflict(String),
 
+    /// The requested dataset is not registered.
+    #[error("Dataset not found: {0}")]
+    DatasetNotFound(String),
+
     /// The request should be redirected to another node (e.g., Raft leader).
     #[error("Redirect to {0}")]
     Redirect(String),
 }
 
 /// The standard JSON response body for an API error.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Serialize)]
 pub struct ErrorResponse {
     /// A human-readable error message.
@@ -140,6 +145,7 @@ impl Error {
             Error::Timeout(_) => StatusCode::REQUEST_TIMEOUT,
             Error::BadRequest(_) => StatusCode::BAD_REQUEST,
             Error::Conflict(_) => StatusCode::CONFLICT,
+            Error::DatasetNotFound(_) => StatusCode::NOT_FOUND,
             Error::Redirect(_) => StatusCode::TEMPORARY_REDIRECT,
         }
     }
@@ -168,6 +174,7 @@ impl Error {
             Error::Timeout(_) => "TIMEOUT",
             Error::BadRequest(_) => "BAD_REQUEST",
             Error::Conflict(_) => "CONFLICT",
+            Error::DatasetNotFound(_) => "DATASET_NOT_FOUND",
             Error::Redirect(_) => "REDIRECT",
         }
     }
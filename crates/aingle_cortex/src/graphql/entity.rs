@@ -0,0 +1,307 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Nested entity resolution
+//!
+//! `entity(id)` returns a subject with its triples grouped by predicate.
+//! Object values that are nodes expose a lazily resolved `entity` field, so a
+//! client can follow links to any depth in one query. The server bounds the
+//! traversal with [`EntityLimits`], and a node already on the path from the
+//! root comes back as a reference with `cycle: true` instead of recursing.
+//!
+//! Subject lookups go through an [`EntityLoader`]: every `entity` field
+//! resolved in the same pass queues its subject, and the first to run reads
+//! the whole queue from the [`SubjectStore`] in one call.
+
+use async_graphql::*;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use super::schema::{BooleanValue, FloatValue, IntegerValue, StringValue, TripleValue};
+use crate::state::AppState;
+use aingle_graph::{GraphDB, NodeId};
+
+/// Bounds on one `entity` traversal
+///
+/// Taken from the schema data when present, otherwise the defaults apply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EntityLimits {
+    /// Deepest link an `entity` field may follow; the root is depth 0
+    pub max_depth: usize,
+    /// Most entities a single traversal may resolve
+    pub max_nodes: usize,
+}
+
+impl Default for EntityLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: 8,
+            max_nodes: 1000,
+        }
+    }
+}
+
+/// Reads the triples of a batch of subjects
+///
+/// [`GraphSubjectStore`] is used unless an `Arc<dyn SubjectStore>` is in the
+/// schema data.
+pub trait SubjectStore: Send + Sync {
+    /// The triples of each subject in `subjects`; subjects without triples
+    /// may be left out
+    fn subjects(
+        &self,
+        graph: &GraphDB,
+        subjects: &[NodeId],
+    ) -> aingle_graph::Result<HashMap<NodeId, Vec<aingle_graph::Triple>>>;
+}
+
+/// Looks every subject up in the graph's subject index
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GraphSubjectStore;
+
+impl SubjectStore for GraphSubjectStore {
+    fn subjects(
+        &self,
+        graph: &GraphDB,
+        subjects: &[NodeId],
+    ) -> aingle_graph::Result<HashMap<NodeId, Vec<aingle_graph::Triple>>> {
+        let mut found = HashMap::with_capacity(subjects.len());
+        for subject in subjects {
+            found.insert(subject.clone(), graph.get_subject(subject)?);
+        }
+        Ok(found)
+    }
+}
+
+#[derive(Default)]
+struct LoaderQueue {
+    cache: HashMap<NodeId, Arc<Vec<aingle_graph::Triple>>>,
+    pending: HashSet<NodeId>,
+}
+
+/// Batches and caches the subject lookups of one `entity` traversal
+pub struct EntityLoader {
+    state: AppState,
+    store: Arc<dyn SubjectStore>,
+    limits: EntityLimits,
+    resolved: AtomicUsize,
+    queue: std::sync::Mutex<LoaderQueue>,
+    fetch: tokio::sync::Mutex<()>,
+}
+
+impl EntityLoader {
+    /// A loader over `state`'s graph, with the store and limits from the
+    /// schema data
+    pub fn new(ctx: &Context<'_>, state: AppState) -> Arc<Self> {
+        let store = ctx
+            .data_opt::<Arc<dyn SubjectStore>>()
+            .cloned()
+            .unwrap_or_else(|| Arc::new(GraphSubjectStore));
+        let limits = ctx.data_opt::<EntityLimits>().copied().unwrap_or_default();
+        Arc::new(Self {
+            state,
+            store,
+            limits,
+            resolved: AtomicUsize::new(0),
+            queue: std::sync::Mutex::new(LoaderQueue::default()),
+            fetch: tokio::sync::Mutex::new(()),
+        })
+    }
+
+    /// The entity `id` at `depth`, reached through `path`
+    ///
+    /// `None` when the subject has no triples.
+    pub(crate) async fn resolve(
+        self: &Arc<Self>,
+        id: NodeId,
+        depth: usize,
+        path: &[NodeId],
+    ) -> Result<Option<Entity>> {
+        if depth > self.limits.max_depth {
+            return Err(Error::new(format!(
+                "Entity depth limit of {} reached at {}",
+                self.limits.max_depth,
+                node_label(&id)
+            )));
+        }
+        if self.resolved.fetch_add(1, Ordering::Relaxed) >= self.limits.max_nodes {
+            return Err(Error::new(format!(
+                "Entity budget of {} nodes exhausted",
+                self.limits.max_nodes
+            )));
+        }
+
+        let triples = self.load(&id).await?;
+        if triples.is_empty() {
+            return Ok(None);
+        }
+
+        let mut path = path.to_vec();
+        path.push(id.clone());
+        Ok(Some(Entity {
+            id,
+            depth,
+            path: Arc::new(path),
+            triples,
+            loader: Arc::clone(self),
+        }))
+    }
+
+    fn cached(&self, id: &NodeId) -> Option<Arc<Vec<aingle_graph::Triple>>> {
+        self.queue.lock().unwrap().cache.get(id).cloned()
+    }
+
+    async fn load(&self, id: &NodeId) -> Result<Arc<Vec<aingle_graph::Triple>>> {
+        if let Some(hit) = self.cached(id) {
+            return Ok(hit);
+        }
+        self.queue.lock().unwrap().pending.insert(id.clone());
+
+        // Sibling fields are polled before this one resumes, so they get to
+        // queue their subjects for the same read
+        tokio::task::yield_now().await;
+
+        // Whoever drained the queue caches it before releasing the lock, so
+        // past this point `id` is either cached or still pending
+        let _fetch = self.fetch.lock().await;
+        if let Some(hit) = self.cached(id) {
+            return Ok(hit);
+        }
+
+        let batch: Vec<NodeId> = self.queue.lock().unwrap().pending.drain().collect();
+        let mut found = {
+            let graph = self.state.graph.read().await;
+            self.store.subjects(&graph, &batch)?
+        };
+
+        let mut queue = self.queue.lock().unwrap();
+        for subject in batch {
+            let triples = found.remove(&subject).unwrap_or_default();
+            queue.cache.insert(subject, Arc::new(triples));
+        }
+        Ok(queue.cache.get(id).cloned().unwrap_or_default())
+    }
+}
+
+/// The IRI of a named node, the display form of any other
+fn node_label(id: &NodeId) -> String {
+    id.as_name()
+        .map(str::to_string)
+        .unwrap_or_else(|| id.to_string())
+}
+
+/// A subject with its triples grouped by predicate
+pub struct Entity {
+    id: NodeId,
+    depth: usize,
+    path: Arc<Vec<NodeId>>,
+    triples: Arc<Vec<aingle_graph::Triple>>,
+    loader: Arc<EntityLoader>,
+}
+
+#[Object]
+impl Entity {
+    /// Subject IRI
+    async fn id(&self) -> String {
+        node_label(&self.id)
+    }
+
+    /// Distance from the queried entity
+    async fn depth(&self) -> i32 {
+        self.depth as i32
+    }
+
+    /// Properties, one per predicate, sorted by predicate
+    async fn properties(&self) -> Vec<Property> {
+        let mut grouped: BTreeMap<&str, Vec<EntityValue>> = BTreeMap::new();
+        for triple in self.triples.iter() {
+            let value = match &triple.object {
+                aingle_graph::Value::Node(node) => EntityValue::Node(EntityNode {
+                    id: node.clone(),
+                    depth: self.depth + 1,
+                    path: Arc::clone(&self.path),
+                    loader: Arc::clone(&self.loader),
+                }),
+                other => EntityValue::from(TripleValue::from(other.clone())),
+            };
+            grouped
+                .entry(triple.predicate.as_str())
+                .or_default()
+                .push(value);
+        }
+
+        grouped
+            .into_iter()
+            .map(|(predicate, values)| Property {
+                predicate: predicate.to_string(),
+                values,
+            })
+            .collect()
+    }
+}
+
+/// All values of one predicate on an entity
+#[derive(SimpleObject)]
+pub struct Property {
+    /// Predicate (relationship)
+    pub predicate: String,
+    /// Object values, in store order
+    pub values: Vec<EntityValue>,
+}
+
+/// Value of an entity property
+#[derive(Union)]
+pub enum EntityValue {
+    String(StringValue),
+    Integer(IntegerValue),
+    Float(FloatValue),
+    Boolean(BooleanValue),
+    Node(EntityNode),
+}
+
+impl From<TripleValue> for EntityValue {
+    fn from(v: TripleValue) -> Self {
+        match v {
+            TripleValue::String(s) => EntityValue::String(s),
+            TripleValue::Integer(i) => EntityValue::Integer(i),
+            TripleValue::Float(f) => EntityValue::Float(f),
+            TripleValue::Boolean(b) => EntityValue::Boolean(b),
+            TripleValue::Node(n) => EntityValue::String(StringValue { value: n.iri }),
+        }
+    }
+}
+
+/// Link to another node, resolved only when `entity` is selected
+pub struct EntityNode {
+    id: NodeId,
+    depth: usize,
+    path: Arc<Vec<NodeId>>,
+    loader: Arc<EntityLoader>,
+}
+
+#[Object]
+impl EntityNode {
+    /// Node IRI
+    async fn iri(&self) -> String {
+        node_label(&self.id)
+    }
+
+    /// Whether the node is already on the path from the queried entity, in
+    /// which case `entity` is null
+    async fn cycle(&self) -> bool {
+        self.path.contains(&self.id)
+    }
+
+    /// The linked entity, null for a cycle or a node without triples
+    ///
+    /// Fails past the server's depth limit or resolved-node budget.
+    async fn entity(&self) -> Result<Option<Entity>> {
+        if self.path.contains(&self.id) {
+            return Ok(None);
+        }
+        self.loader
+            .resolve(self.id.clone(), self.depth, &self.path)
+            .await
+    }
+}
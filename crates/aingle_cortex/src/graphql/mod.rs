@@ -6,10 +6,12 @@
 //! Provides a complete GraphQL schema with queries, mutations, and subscriptions
 //! for interacting with the AIngle semantic graph.
 
+mod entity;
 mod resolvers;
 mod schema;
 mod subscriptions;
 
+pub use entity::*;
 pub use resolvers::*;
 pub use schema::*;
 pub use subscriptions::*;
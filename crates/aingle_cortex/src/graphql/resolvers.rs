@@ -5,18 +5,42 @@
 
 use async_graphql::*;
 
+use super::entity::{Entity, EntityLoader};
 use super::schema::*;
+use crate::datasets::DEFAULT_DATASET;
+use crate::middleware::RequestPrincipal;
 use crate::state::AppState;
 use aingle_graph::{NodeId, Predicate, TriplePattern};
 
+/// The state of the dataset a resolver was asked for, the default one when
+/// `dataset` is omitted.
+///
+/// A [`RequestPrincipal`] in the context data is checked against the dataset
+/// the same way REST requests are.
+fn dataset_state(ctx: &Context<'_>, dataset: Option<&str>) -> Result<AppState> {
+    let state = ctx.data::<AppState>()?;
+    let name = dataset.unwrap_or(DEFAULT_DATASET);
+    if let Some(principal) = ctx.data_opt::<RequestPrincipal>() {
+        if principal.for_dataset(name).is_none() {
+            return Err(Error::new(format!("No access to dataset \"{}\"", name)));
+        }
+    }
+    Ok(state.dataset(name)?)
+}
+
 /// Query root
 pub struct QueryRoot;
 
 #[Object]
 impl QueryRoot {
     /// Get a triple by ID (hash)
-    async fn triple(&self, ctx: &Context<'_>, id: ID) -> Result<Option<Triple>> {
-        let state = ctx.data::<AppState>()?;
+    async fn triple(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        dataset: Option<String>,
+    ) -> Result<Option<Triple>> {
+        let state = &dataset_state(ctx, dataset.as_deref())?;
         let graph = state.graph.read().await;
 
         let triple_id = aingle_graph::TripleId::from_hex(&id.to_string())
@@ -29,6 +53,21 @@ impl QueryRoot {
         }
     }
 
+    /// Get a subject with its properties grouped by predicate
+    ///
+    /// Node values can be followed through their `entity` field, up to the
+    /// server's depth limit and resolved-node budget.
+    async fn entity(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        dataset: Option<String>,
+    ) -> Result<Option<Entity>> {
+        let state = dataset_state(ctx, dataset.as_deref())?;
+        let loader = EntityLoader::new(ctx, state);
+        loader.resolve(NodeId::named(&id), 0, &[]).await
+    }
+
     /// Query triples with filters
     async fn triples(
         &self,
@@ -36,8 +75,9 @@ impl QueryRoot {
         filter: Option<TripleFilter>,
         #[graphql(default = 100)] limit: i32,
         #[graphql(default = 0)] offset: i32,
+        dataset: Option<String>,
     ) -> Result<Vec<Triple>> {
-        let state = ctx.data::<AppState>()?;
+        let state = &dataset_state(ctx, dataset.as_deref())?;
         let graph = state.graph.read().await;
 
         let mut pattern = TriplePattern::any();
@@ -67,8 +107,9 @@ impl QueryRoot {
         ctx: &Context<'_>,
         pattern: PatternInput,
         #[graphql(default = 100)] limit: i32,
+        dataset: Option<String>,
     ) -> Result<QueryResult> {
-        let state = ctx.data::<AppState>()?;
+        let state = &dataset_state(ctx, dataset.as_deref())?;
         let graph = state.graph.read().await;
 
         let mut pat = TriplePattern::any();
@@ -97,8 +138,8 @@ impl QueryRoot {
     }
 
     /// Get graph statistics
-    async fn stats(&self, ctx: &Context<'_>) -> Result<GraphStats> {
-        let state = ctx.data::<AppState>()?;
+    async fn stats(&self, ctx: &Context<'_>, dataset: Option<String>) -> Result<GraphStats> {
+        let state = &dataset_state(ctx, dataset.as_deref())?;
         let stats = state.stats().await;
 
         Ok(GraphStats {
@@ -114,8 +155,9 @@ impl QueryRoot {
         &self,
         ctx: &Context<'_>,
         #[graphql(default = 100)] limit: i32,
+        dataset: Option<String>,
     ) -> Result<Vec<String>> {
-        let state = ctx.data::<AppState>()?;
+        let state = &dataset_state(ctx, dataset.as_deref())?;
         let graph = state.graph.read().await;
 
         let triples = graph.find(TriplePattern::any())?;
@@ -132,8 +174,9 @@ impl QueryRoot {
         &self,
         ctx: &Context<'_>,
         #[graphql(default = 100)] limit: i32,
+        dataset: Option<String>,
     ) -> Result<Vec<String>> {
-        let state = ctx.data::<AppState>()?;
+        let state = &dataset_state(ctx, dataset.as_deref())?;
         let graph = state.graph.read().await;
 
         let triples = graph.find(TriplePattern::any())?;
@@ -154,8 +197,13 @@ pub struct MutationRoot;
 #[Object]
 impl MutationRoot {
     /// Create a new triple (routed through the same path as REST for DAG/Raft consistency)
-    async fn create_triple(&self, ctx: &Context<'_>, input: TripleInput) -> Result<Triple> {
-        let state = ctx.data::<AppState>()?;
+    async fn create_triple(
+        &self,
+        ctx: &Context<'_>,
+        input: TripleInput,
+        dataset: Option<String>,
+    ) -> Result<Triple> {
+        let state = &dataset_state(ctx, dataset.as_deref())?;
 
         let object: aingle_graph::Value = input.object.into();
 
@@ -220,9 +268,51 @@ impl MutationRoot {
         Ok(triple.into())
     }
 
+    /// Insert many triples at once, reporting the outcome of each
+    ///
+    /// Same semantics as `POST /api/v1/triples/batch`: `validate` runs the
+    /// logic rules on every triple, and with `atomic` (the default) any
+    /// rejected triple aborts the whole batch.
+    async fn add_triples(
+        &self,
+        ctx: &Context<'_>,
+        triples: Vec<TripleInput>,
+        #[graphql(default)] validate: bool,
+        #[graphql(default = true)] atomic: bool,
+        dataset: Option<String>,
+    ) -> Result<BatchInsertReport> {
+        let state = &dataset_state(ctx, dataset.as_deref())?;
+
+        let mut items = Vec::with_capacity(triples.len());
+        for (i, input) in triples.into_iter().enumerate() {
+            let object = input
+                .object
+                .into_dto()
+                .ok_or_else(|| Error::new(format!("Triple [{}]: object has no value", i)))?;
+            items.push(crate::rest::CreateTripleRequest {
+                subject: input.subject,
+                predicate: input.predicate,
+                object,
+            });
+        }
+
+        let req = crate::rest::BatchInsertRequest {
+            triples: items,
+            validate,
+            atomic,
+        };
+        let resp = crate::service::triples::batch_insert(state, req, None).await?;
+        Ok(resp.into())
+    }
+
     /// Delete a triple by ID (routed through the same path as REST for DAG/Raft consistency)
-    async fn delete_triple(&self, ctx: &Context<'_>, id: ID) -> Result<bool> {
-        let state = ctx.data::<AppState>()?;
+    async fn delete_triple(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        dataset: Option<String>,
+    ) -> Result<bool> {
+        let state = &dataset_state(ctx, dataset.as_deref())?;
 
         let triple_id = aingle_graph::TripleId::from_hex(&id.to_string())
             .ok_or_else(|| Error::new("Invalid triple ID"))?;
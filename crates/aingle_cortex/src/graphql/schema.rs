@@ -156,6 +156,24 @@ impl From<ValueInput> for aingle_graph::Value {
     }
 }
 
+impl ValueInput {
+    /// The REST form of this value, or `None` if no field is set
+    pub fn into_dto(self) -> Option<crate::rest::ValueDto> {
+        use crate::rest::ValueDto;
+        if let Some(s) = self.string {
+            Some(ValueDto::String(s))
+        } else if let Some(i) = self.integer {
+            Some(ValueDto::Integer(i))
+        } else if let Some(f) = self.float {
+            Some(ValueDto::Float(f))
+        } else if let Some(b) = self.boolean {
+            Some(ValueDto::Boolean(b))
+        } else {
+            self.node.map(|node| ValueDto::Node { node })
+        }
+    }
+}
+
 /// Pattern input for queries
 #[derive(Debug, Clone, InputObject)]
 pub struct PatternInput {
@@ -226,6 +244,60 @@ pub struct TripleValidationResult {
     pub proof_hash: Option<String>,
 }
 
+/// Result of a bulk triple insert
+#[derive(Debug, Clone, SimpleObject)]
+pub struct BatchInsertReport {
+    /// False when an atomic batch was aborted and nothing was written
+    pub committed: bool,
+    /// Newly inserted triples
+    pub inserted: i32,
+    /// Triples already present or repeated in the batch
+    pub duplicates: i32,
+    /// Triples that failed input checks or validation
+    pub rejected: i32,
+    /// One entry per input triple, in input order
+    pub results: Vec<BatchItemReport>,
+}
+
+/// Outcome of one triple in a bulk insert
+#[derive(Debug, Clone, SimpleObject)]
+pub struct BatchItemReport {
+    /// Position in the input list
+    pub index: i32,
+    /// One of `inserted`, `duplicate`, `rejected`, `aborted`
+    pub status: String,
+    /// Triple hash
+    pub id: Option<String>,
+    /// Why the triple was rejected
+    pub error: Option<String>,
+}
+
+impl From<crate::rest::BatchInsertResponse> for BatchInsertReport {
+    fn from(resp: crate::rest::BatchInsertResponse) -> Self {
+        let inserted = resp
+            .results
+            .iter()
+            .filter(|r| r.status == crate::rest::BatchItemStatus::Inserted)
+            .count();
+        Self {
+            committed: resp.committed,
+            inserted: inserted as i32,
+            duplicates: resp.duplicates as i32,
+            rejected: resp.rejected as i32,
+            results: resp
+                .results
+                .into_iter()
+                .map(|r| BatchItemReport {
+                    index: r.index as i32,
+                    status: r.status.as_str().to_string(),
+                    id: r.id,
+                    error: r.error,
+                })
+                .collect(),
+        }
+    }
+}
+
 /// Validation message
 #[derive(Debug, Clone, SimpleObject)]
 pub struct ValidationMessage {
@@ -240,6 +312,8 @@ pub struct ValidationMessage {
 /// Validation event for subscriptions
 #[derive(Debug, Clone, SimpleObject)]
 pub struct ValidationEvent {
+    /// Event sequence number, usable as `afterSeq` when resubscribing
+    pub seq: u64,
     /// Triple hash
     pub hash: String,
     /// Whether valid
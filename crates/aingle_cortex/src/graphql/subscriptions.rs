@@ -22,14 +22,79 @@
 //!   }
 //! }
 //! ```
+//!
+//! ## Resuming after a reconnect
+//!
+//! Every event carries a `seq` number. Subscriptions that take `afterSeq`
+//! first replay the buffered events after that number, then continue live, so
+//! a client that reconnects with the last `seq` it saw misses nothing. If the
+//! events after the cursor have already left the buffer, the subscription
+//! fails with the error code `RESYNC_REQUIRED` and the client must re-read
+//! the graph before subscribing again. A subscriber that falls too far behind
+//! the live events gets the same error (reason `LAGGED`), after which the
+//! subscription ends.
 
 use async_graphql::*;
 use futures::Stream;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
 use tokio_stream::wrappers::BroadcastStream;
 use tokio_stream::StreamExt;
 
 use super::schema::{TripleFilter, ValidationEvent};
-use crate::state::{AppState, Event};
+use crate::state::{AppState, Event, ReplayError, SequencedEvent};
+
+/// Buffered events after `after_seq`, followed by live events, mapped by `f`
+///
+/// Events a lagging subscriber missed cannot be recovered, so the stream ends
+/// with a `RESYNC_REQUIRED` error instead of silently skipping them.
+fn sequenced_events<T>(
+    state: &AppState,
+    after_seq: Option<u64>,
+    mut f: impl FnMut(SequencedEvent) -> Option<T> + Send + 'static,
+) -> Result<impl Stream<Item = Result<T>>> {
+    let replay = state.broadcaster.subscribe_from(after_seq).map_err(|e| {
+        Error::new(e.to_string()).extend_with(|_, ext| {
+            ext.set("code", "RESYNC_REQUIRED");
+            match e {
+                ReplayError::CursorExpired { oldest_seq, .. } => {
+                    ext.set("reason", "CURSOR_EXPIRED");
+                    ext.set("oldestSeq", oldest_seq);
+                }
+                ReplayError::CursorAhead { latest_seq, .. } => {
+                    ext.set("reason", "CURSOR_AHEAD");
+                    ext.set("latestSeq", latest_seq);
+                }
+            }
+        })
+    })?;
+
+    let live = BroadcastStream::new(replay.live).map(|result| {
+        result.map_err(|BroadcastStreamRecvError::Lagged(missed)| {
+            Error::new(format!("subscriber lagged, missed {missed} events")).extend_with(
+                |_, ext| {
+                    ext.set("code", "RESYNC_REQUIRED");
+                    ext.set("reason", "LAGGED");
+                    ext.set("missed", missed);
+                },
+            )
+        })
+    });
+    let mut lagged = false;
+    Ok(tokio_stream::iter(replay.backlog)
+        .map(Ok)
+        .chain(live)
+        .map_while(move |event| {
+            if lagged {
+                return None;
+            }
+            lagged = event.is_err();
+            Some(event)
+        })
+        .filter_map(move |event| match event {
+            Ok(event) => f(event).map(Ok),
+            Err(e) => Some(Err(e)),
+        }))
+}
 
 /// Subscription root for GraphQL real-time updates
 pub struct SubscriptionRoot;
@@ -59,18 +124,18 @@ impl SubscriptionRoot {
         &self,
         ctx: &Context<'_>,
         #[graphql(desc = "Filter criteria for triples")] filter: Option<TripleFilter>,
-    ) -> impl Stream<Item = TripleEvent> {
+        #[graphql(desc = "Replay events after this sequence number first")] after_seq: Option<u64>,
+    ) -> Result<impl Stream<Item = Result<TripleEvent>>> {
         let state = ctx.data_unchecked::<AppState>();
-        let rx = state.broadcaster.subscribe();
 
-        BroadcastStream::new(rx).filter_map(move |result| {
-            match result {
-                Ok(Event::TripleAdded {
+        sequenced_events(state, after_seq, move |e| {
+            match e.event {
+                Event::TripleAdded {
                     hash,
                     subject,
                     predicate,
                     object,
-                }) => {
+                } => {
                     // Apply filter if provided
                     if let Some(ref f) = filter {
                         if let Some(ref s) = f.subject {
@@ -96,12 +161,13 @@ impl SubscriptionRoot {
                     }
 
                     Some(TripleEvent {
+                        seq: e.seq,
                         event_type: "ADDED".to_string(),
                         hash,
                         subject,
                         predicate,
                         object: object.to_string(),
-                        timestamp: chrono::Utc::now(),
+                        timestamp: e.timestamp,
                     })
                 }
                 _ => None,
@@ -121,14 +187,18 @@ impl SubscriptionRoot {
     ///   }
     /// }
     /// ```
-    async fn triple_deleted(&self, ctx: &Context<'_>) -> impl Stream<Item = TripleDeletionEvent> {
+    async fn triple_deleted(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Replay events after this sequence number first")] after_seq: Option<u64>,
+    ) -> Result<impl Stream<Item = Result<TripleDeletionEvent>>> {
         let state = ctx.data_unchecked::<AppState>();
-        let rx = state.broadcaster.subscribe();
 
-        BroadcastStream::new(rx).filter_map(|result| match result {
-            Ok(Event::TripleDeleted { hash }) => Some(TripleDeletionEvent {
+        sequenced_events(state, after_seq, |e| match e.event {
+            Event::TripleDeleted { hash } => Some(TripleDeletionEvent {
+                seq: e.seq,
                 hash,
-                timestamp: chrono::Utc::now(),
+                timestamp: e.timestamp,
             }),
             _ => None,
         })
@@ -158,24 +228,25 @@ impl SubscriptionRoot {
         &self,
         ctx: &Context<'_>,
         #[graphql(desc = "Only emit valid validation events", default = false)] valid_only: bool,
-    ) -> impl Stream<Item = ValidationEvent> {
+        #[graphql(desc = "Replay events after this sequence number first")] after_seq: Option<u64>,
+    ) -> Result<impl Stream<Item = Result<ValidationEvent>>> {
         let state = ctx.data_unchecked::<AppState>();
-        let rx = state.broadcaster.subscribe();
 
-        BroadcastStream::new(rx).filter_map(move |result| match result {
-            Ok(Event::ValidationCompleted {
+        sequenced_events(state, after_seq, move |e| match e.event {
+            Event::ValidationCompleted {
                 hash,
                 valid,
                 proof_hash,
-            }) => {
+            } => {
                 if valid_only && !valid {
                     None
                 } else {
                     Some(ValidationEvent {
+                        seq: e.seq,
                         hash,
                         valid,
                         proof_hash,
-                        timestamp: chrono::Utc::now(),
+                        timestamp: e.timestamp,
                     })
                 }
             }
@@ -207,17 +278,17 @@ impl SubscriptionRoot {
         &self,
         ctx: &Context<'_>,
         #[graphql(desc = "Filter by specific agent ID")] agent_id: Option<String>,
-    ) -> impl Stream<Item = AgentActivityEvent> {
+        #[graphql(desc = "Replay events after this sequence number first")] after_seq: Option<u64>,
+    ) -> Result<impl Stream<Item = Result<AgentActivityEvent>>> {
         let state = ctx.data_unchecked::<AppState>();
-        let rx = state.broadcaster.subscribe();
 
-        BroadcastStream::new(rx).filter_map(move |result| match result {
-            Ok(Event::TripleAdded {
+        sequenced_events(state, after_seq, move |e| match e.event {
+            Event::TripleAdded {
                 hash,
                 subject,
                 predicate,
                 ..
-            }) => {
+            } => {
                 // Extract agent from subject if it matches pattern
                 if let Some(ref filter_agent) = agent_id {
                     if !subject.contains(filter_agent) {
@@ -226,19 +297,21 @@ impl SubscriptionRoot {
                 }
 
                 Some(AgentActivityEvent {
+                    seq: e.seq,
                     agent_id: subject.clone(),
                     action: "ADDED_TRIPLE".to_string(),
                     triple_hash: hash,
                     predicate: Some(predicate),
-                    timestamp: chrono::Utc::now(),
+                    timestamp: e.timestamp,
                 })
             }
-            Ok(Event::TripleDeleted { hash }) => Some(AgentActivityEvent {
+            Event::TripleDeleted { hash } => Some(AgentActivityEvent {
+                seq: e.seq,
                 agent_id: "system".to_string(),
                 action: "DELETED_TRIPLE".to_string(),
                 triple_hash: hash,
                 predicate: None,
-                timestamp: chrono::Utc::now(),
+                timestamp: e.timestamp,
             }),
             _ => None,
         })
@@ -277,7 +350,8 @@ impl SubscriptionRoot {
 
     /// Subscribe to all events (as JSON)
     ///
-    /// Raw event stream for debugging or custom processing.
+    /// Raw event stream for debugging or custom processing. Each event has
+    /// its `seq` added alongside `type` and `data`.
     ///
     /// # Example
     ///
@@ -286,20 +360,22 @@ impl SubscriptionRoot {
     ///   events
     /// }
     /// ```
-    async fn events(&self, ctx: &Context<'_>) -> impl Stream<Item = String> {
+    async fn events(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Replay events after this sequence number first")] after_seq: Option<u64>,
+    ) -> Result<impl Stream<Item = Result<String>>> {
         let state = ctx.data_unchecked::<AppState>();
-        let rx = state.broadcaster.subscribe();
 
-        BroadcastStream::new(rx).filter_map(|result| match result {
-            Ok(event) => Some(event.to_json()),
-            _ => None,
-        })
+        sequenced_events(state, after_seq, |e| Some(e.to_json()))
     }
 }
 
 /// Triple event (addition)
 #[derive(Debug, Clone, SimpleObject)]
 pub struct TripleEvent {
+    /// Event sequence number, usable as `afterSeq` when resubscribing
+    pub seq: u64,
     /// Event type (ADDED, UPDATED, etc.)
     pub event_type: String,
     /// Triple hash
@@ -317,6 +393,8 @@ pub struct TripleEvent {
 /// Triple deletion event
 #[derive(Debug, Clone, SimpleObject)]
 pub struct TripleDeletionEvent {
+    /// Event sequence number, usable as `afterSeq` when resubscribing
+    pub seq: u64,
     /// Triple hash that was deleted
     pub hash: String,
     /// Deletion timestamp
@@ -326,6 +404,8 @@ pub struct TripleDeletionEvent {
 /// Agent activity event
 #[derive(Debug, Clone, SimpleObject)]
 pub struct AgentActivityEvent {
+    /// Event sequence number, usable as `afterSeq` when resubscribing
+    pub seq: u64,
     /// Agent ID
     pub agent_id: String,
     /// Action performed
@@ -125,6 +125,25 @@
 //! }
 //! ```
 //!
+//! ### Nested entities
+//!
+//! ```graphql
+//! query {
+//!   entity(id: "alice") {
+//!     properties {
+//!       predicate
+//!       values {
+//!         ... on StringValue { value }
+//!         ... on EntityNode {
+//!           iri
+//!           entity { properties { predicate } }
+//!         }
+//!       }
+//!     }
+//!   }
+//! }
+//! ```
+//!
 //! ### Mutation
 //!
 //! ```graphql
@@ -160,12 +179,20 @@
 //!   ?person <knows> ?friend .
 //! }
 //! ```
+//!
+//! Updates are posted to `/sparql/update` as `application/sparql-update` and
+//! need the `write` role (or `admin`):
+//!
+//! ```sparql
+//! DELETE WHERE { ?person <status> "inactive" }
+//! ```
 
 #[cfg(feature = "auth")]
 pub mod auth;
 pub mod client;
 #[cfg(feature = "cluster")]
 pub mod cluster_init;
+pub mod datasets;
 pub mod embedder;
 pub mod error;
 #[cfg(feature = "graphql")]
@@ -179,14 +206,17 @@ pub mod proofs;
 pub mod rest;
 pub mod server;
 pub mod service;
+pub mod shutdown;
 #[cfg(feature = "sparql")]
 pub mod sparql;
 pub mod state;
+pub mod tombstones;
 pub mod wasm_types;
 
 pub use client::{CortexClientConfig, CortexInternalClient};
 pub use error::{Error, Result};
 pub use server::{CortexConfig, CortexServer};
+pub use shutdown::ShutdownHandle;
 pub use state::AppState;
 
 /// Re-export commonly used types
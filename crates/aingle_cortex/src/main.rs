@@ -78,6 +78,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             "--memory" => {
                 config.db_path = Some(":memory:".to_string());
             }
+            "--swagger-ui" => {
+                config.swagger_ui = true;
+            }
             "--mcp" => {
                 config.mcp_mode = true;
             }
@@ -108,12 +111,43 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     i += 1;
                 }
             }
+            "--max-batch" if i + 1 < args.len() => {
+                config.max_batch_triples = args[i + 1]
+                    .parse()
+                    .unwrap_or(aingle_cortex::state::DEFAULT_MAX_BATCH_TRIPLES);
+                i += 1;
+            }
+            "--tombstone-retention" if i + 1 < args.len() => {
+                config.tombstone_retention = args[i + 1]
+                    .parse()
+                    .map(std::time::Duration::from_secs)
+                    .unwrap_or(aingle_cortex::state::DEFAULT_TOMBSTONE_RETENTION);
+                i += 1;
+            }
+            "--event-replay" if i + 1 < args.len() => {
+                config.event_replay_buffer = args[i + 1]
+                    .parse()
+                    .unwrap_or(aingle_cortex::state::DEFAULT_EVENT_REPLAY_BUFFER);
+                i += 1;
+            }
             "--flush-interval" => {
                 if i + 1 < args.len() {
                     config.flush_interval_secs = args[i + 1].parse().unwrap_or(300);
                     i += 1;
                 }
             }
+            "--proof-sweep-interval" if i + 1 < args.len() => {
+                config.proof_expiry_sweep_secs = args[i + 1].parse().unwrap_or(60);
+                i += 1;
+            }
+            "--warn-revoked-signer" => {
+                config.warn_on_revoked_signer = true;
+            }
+            "--shutdown-timeout" if i + 1 < args.len() => {
+                config.shutdown_drain_timeout =
+                    std::time::Duration::from_secs(args[i + 1].parse().unwrap_or(30));
+                i += 1;
+            }
             "--help" => {
                 print_help();
                 return Ok(());
@@ -187,6 +221,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     #[allow(unused_variables)]
     let db_path = config.db_path.clone();
     let flush_interval_secs = config.flush_interval_secs;
+    let proof_expiry_sweep_secs = config.proof_expiry_sweep_secs;
 
     // Create and run server
     #[allow(unused_mut)]
@@ -256,6 +291,26 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         tracing::info!(interval_secs = interval_secs, "Periodic auto-flush enabled");
     }
 
+    // Spawn periodic proof expiry sweep if enabled
+    if proof_expiry_sweep_secs > 0 {
+        let sweep_state = server.state().clone();
+        let interval_secs = proof_expiry_sweep_secs;
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(interval_secs));
+            loop {
+                interval.tick().await;
+                let marked = sweep_state
+                    .proof_store
+                    .sweep_expired(chrono::Utc::now())
+                    .await;
+                if marked > 0 {
+                    tracing::info!(marked = marked, "Marked expired proofs");
+                }
+            }
+        });
+        tracing::debug!(interval_secs = interval_secs, "Proof expiry sweep enabled");
+    }
+
     // Keep a reference to the state for shutdown flush
     let state_for_shutdown = server.state().clone();
     let snapshot_dir_for_shutdown = snapshot_dir.clone();
@@ -280,53 +335,30 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
-    // Set up graceful shutdown with data flush (handles both SIGINT and SIGTERM)
-    let shutdown_signal = async move {
-        let ctrl_c = tokio::signal::ctrl_c();
-
-        #[cfg(unix)]
-        let terminate = async {
-            tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
-                .expect("Failed to install SIGTERM handler")
-                .recv()
-                .await;
-        };
-
-        #[cfg(not(unix))]
-        let terminate = std::future::pending::<()>();
-
-        tokio::select! {
-            _ = ctrl_c => {
-                tracing::info!("SIGINT received — shutting down...");
-            }
-            _ = terminate => {
-                tracing::info!("SIGTERM received — shutting down...");
-            }
-        }
-
-        // Gracefully shut down Raft before flushing data
-        #[cfg(feature = "cluster")]
-        if let Some(ref raft) = state_for_shutdown.raft {
-            tracing::info!("Shutting down Raft...");
-            match tokio::time::timeout(std::time::Duration::from_secs(10), raft.shutdown()).await {
-                Ok(Ok(())) => tracing::info!("Raft shut down gracefully"),
-                Ok(Err(e)) => tracing::error!("Raft shutdown error: {e}"),
-                Err(_) => tracing::error!("Raft shutdown timed out after 10s"),
-            }
-        }
+    // Serve until SIGINT/SIGTERM; `run` drains connections and flushes the
+    // graph, proof store and tombstones before returning.
+    server.run().await?;
 
-        // Flush graph database and save Ineru snapshot
-        if let Err(e) = state_for_shutdown
-            .flush(snapshot_dir_for_shutdown.as_deref())
-            .await
-        {
-            tracing::error!("Failed to flush data on shutdown: {}", e);
-        } else {
-            tracing::info!("Data flushed successfully");
+    // Gracefully shut down Raft before the final flush
+    #[cfg(feature = "cluster")]
+    if let Some(ref raft) = state_for_shutdown.raft {
+        tracing::info!("Shutting down Raft...");
+        match tokio::time::timeout(std::time::Duration::from_secs(10), raft.shutdown()).await {
+            Ok(Ok(())) => tracing::info!("Raft shut down gracefully"),
+            Ok(Err(e)) => tracing::error!("Raft shutdown error: {e}"),
+            Err(_) => tracing::error!("Raft shutdown timed out after 10s"),
         }
-    };
+    }
 
-    server.run_with_shutdown(shutdown_signal).await?;
+    // Flush again and save the Ineru snapshot
+    if let Err(e) = state_for_shutdown
+        .flush(snapshot_dir_for_shutdown.as_deref())
+        .await
+    {
+        tracing::error!("Failed to flush data on shutdown: {}", e);
+    } else {
+        tracing::info!("Data flushed successfully");
+    }
 
     Ok(())
 }
@@ -345,8 +377,21 @@ fn print_help() {
         "    --db <PATH>          Path to graph database (default: ~/.aingle/cortex/graph.sled)"
     );
     println!("    --memory             Use volatile in-memory storage (no persistence)");
+    println!("    --swagger-ui         Serve Swagger UI for the OpenAPI spec at /api/docs");
     println!("    --embed-model <DIR>  Directory with a neural embedding model (requires --features neural-embeddings; falls back to hash if absent)");
     println!("    --flush-interval <S> Periodic flush interval in seconds (default: 300, 0=off)");
+    println!(
+        "    --proof-sweep-interval <S> Seconds between expired-proof sweeps (default: 60, 0=off)"
+    );
+    println!(
+        "    --warn-revoked-signer Warn when a validated proof's signing key has revoked proofs"
+    );
+    println!("    --shutdown-timeout <S> Seconds to drain connections on shutdown (default: 30)");
+    println!("    --max-batch <N>      Maximum triples per batch insert (default: 10000)");
+    println!("    --event-replay <N>   Events kept for subscription replay (default: 1024)");
+    println!(
+        "    --tombstone-retention <S> Seconds deleted triples are kept for history (default: 2592000)"
+    );
     println!("    --mcp                Serve MCP over stdio (requires --features mcp)");
     println!(
         "    --mcp-http-token <T> Bearer token for the /mcp HTTP endpoint (requires --features mcp-http)"
@@ -448,7 +448,7 @@ impl AingleMcp {
     /// hash already exists (see `GraphStore::insert_batch`), so retrying the same
     /// batch converges to the same state without error.
     #[tool(
-        description = "Atomically bulk-insert triples into the semantic graph. Duplicates are skipped silently.",
+        description = "Bulk-insert triples into the semantic graph and return a per-item report. Duplicates are skipped. Set `validate` to check each triple against the logic rules; with `atomic` (the default) any rejected triple aborts the whole batch.",
         annotations(
             read_only_hint = false,
             destructive_hint = false,
@@ -0,0 +1,345 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Request audit middleware
+//!
+//! Records who called which route, when, against which dataset and with what
+//! outcome, as an [`AuditEntry`] in the server's [`AuditLog`]. Writes are
+//! always audited; reads only when [`RequestAuditConfig::reads`] is set.
+//!
+//! Request bodies are never stored. The triples of a create or batch request
+//! are summarized by subject, predicate and object, leaving out the literal
+//! objects of the predicates listed in
+//! [`RequestAuditConfig::sensitive_predicates`]; see [`Redactor`]. Other
+//! bodies are recorded by size only.
+//!
+//! ## Usage
+//!
+//! ```rust,ignore
+//! use aingle_cortex::middleware::{request_audit, RequestAuditConfig, RequestAuditor};
+//!
+//! let config = RequestAuditConfig::default().with_sensitive_predicate("ex:ssn");
+//! let auditor = RequestAuditor::new(config, state.audit_log.clone());
+//! let app = Router::new()
+//!     .layer(axum::middleware::from_fn_with_state(auditor, request_audit));
+//! ```
+//!
+//! The layer must run inside the namespace extractor, which supplies the
+//! caller's identity.
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::{MatchedPath, Request, State},
+    http::{Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::RwLock;
+
+use crate::datasets::DEFAULT_DATASET;
+use crate::middleware::{RequestNamespace, RequestPrincipal};
+use crate::rest::audit::{AuditEntry, AuditLog, RequestAudit, RequestSummary, TripleSummary};
+use crate::rest::{BatchInsertRequest, CreateTripleRequest, ValueDto};
+
+/// Most triples listed in one [`RequestSummary`]; the rest are only counted
+pub const MAX_SUMMARY_TRIPLES: usize = 100;
+
+/// Which requests are audited, and what is redacted from them
+#[derive(Debug, Clone, Default)]
+pub struct RequestAuditConfig {
+    /// Audit reads (`GET`, `HEAD`, `OPTIONS`) as well as writes
+    pub reads: bool,
+    /// Predicates whose literal objects are left out of request summaries
+    pub sensitive_predicates: Vec<String>,
+}
+
+impl RequestAuditConfig {
+    /// Sets whether reads are audited.
+    pub fn with_reads(mut self, reads: bool) -> Self {
+        self.reads = reads;
+        self
+    }
+
+    /// Adds a predicate whose literal objects are redacted.
+    pub fn with_sensitive_predicate(mut self, predicate: impl Into<String>) -> Self {
+        self.sensitive_predicates.push(predicate.into());
+        self
+    }
+}
+
+/// Summarizes request bodies, redacting the literal objects of sensitive
+/// predicates
+///
+/// Node objects are kept whatever the predicate: they name another subject,
+/// which is recorded anyway.
+#[derive(Debug, Clone, Default)]
+pub struct Redactor {
+    sensitive: HashSet<String>,
+}
+
+impl Redactor {
+    /// Creates a redactor for the given sensitive predicates.
+    pub fn new<I, S>(sensitive_predicates: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            sensitive: sensitive_predicates.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Whether literal objects of `predicate` are redacted
+    pub fn is_sensitive(&self, predicate: &str) -> bool {
+        self.sensitive.contains(predicate)
+    }
+
+    /// The audit record of one triple
+    pub fn redact(&self, triple: &CreateTripleRequest) -> TripleSummary {
+        let literal = !matches!(triple.object, ValueDto::Node { .. });
+        TripleSummary {
+            subject: triple.subject.clone(),
+            predicate: triple.predicate.clone(),
+            object: if literal && self.is_sensitive(&triple.predicate) {
+                None
+            } else {
+                Some(triple.object.clone())
+            },
+        }
+    }
+
+    /// Summarizes a request body
+    ///
+    /// The triples of a single-triple body, or of a batch body as a JSON
+    /// array, a `{"triples": [...]}` object or NDJSON, are listed; any other
+    /// body is recorded by size only.
+    pub fn summarize(&self, body: &[u8]) -> RequestSummary {
+        let triples = parse_triples(body);
+        RequestSummary {
+            bytes: body.len(),
+            triple_count: triples.len(),
+            triples: triples
+                .iter()
+                .take(MAX_SUMMARY_TRIPLES)
+                .map(|triple| self.redact(triple))
+                .collect(),
+        }
+    }
+}
+
+/// The triples of a create or batch body, or none for any other body
+fn parse_triples(body: &[u8]) -> Vec<CreateTripleRequest> {
+    if let Ok(triple) = serde_json::from_slice::<CreateTripleRequest>(body) {
+        return vec![triple];
+    }
+    if let Ok(triples) = serde_json::from_slice::<Vec<CreateTripleRequest>>(body) {
+        return triples;
+    }
+    if let Ok(batch) = serde_json::from_slice::<BatchInsertRequest>(body) {
+        return batch.triples;
+    }
+    std::str::from_utf8(body)
+        .ok()
+        .and_then(|text| {
+            text.lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| serde_json::from_str(line).ok())
+                .collect::<Option<Vec<CreateTripleRequest>>>()
+        })
+        .unwrap_or_default()
+}
+
+/// State of the [`request_audit`] middleware
+#[derive(Clone)]
+pub struct RequestAuditor {
+    reads: bool,
+    redactor: Arc<Redactor>,
+    log: Arc<RwLock<AuditLog>>,
+    max_body_size: usize,
+}
+
+impl RequestAuditor {
+    /// Creates an auditor recording into `log`.
+    pub fn new(config: RequestAuditConfig, log: Arc<RwLock<AuditLog>>) -> Self {
+        Self {
+            reads: config.reads,
+            redactor: Arc::new(Redactor::new(config.sensitive_predicates)),
+            log,
+            max_body_size: 1024 * 1024,
+        }
+    }
+
+    /// Sets the largest body read for a summary (default: 1MB).
+    ///
+    /// A larger body is rejected with `413 Payload Too Large`; match the
+    /// server's body limit.
+    pub fn with_max_body_size(mut self, bytes: usize) -> Self {
+        self.max_body_size = bytes;
+        self
+    }
+}
+
+/// Middleware recording an audit entry for each audited request
+///
+/// The entry's action is `http_write` or `http_read` and its resource the
+/// matched route, so path parameters such as triple ids are not repeated.
+pub async fn request_audit(
+    State(auditor): State<RequestAuditor>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let method = req.method().clone();
+    let write = method != Method::GET && method != Method::HEAD && method != Method::OPTIONS;
+    if !write && !auditor.reads {
+        return next.run(req).await;
+    }
+
+    let started = Instant::now();
+    let principal = req
+        .extensions()
+        .get::<RequestPrincipal>()
+        .cloned()
+        .unwrap_or_else(RequestPrincipal::unauthenticated);
+    let namespace = req
+        .extensions()
+        .get::<RequestNamespace>()
+        .and_then(|RequestNamespace(ns)| ns.clone());
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or_else(|| req.uri().path(), MatchedPath::as_str)
+        .to_string();
+    let dataset = dataset_of(req.uri().path()).to_string();
+    let request_id = req
+        .headers()
+        .get("x-request-id")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+
+    let (summary, response) = if write {
+        let (parts, body) = req.into_parts();
+        match to_bytes(body, auditor.max_body_size).await {
+            Ok(bytes) => {
+                let summary = auditor.redactor.summarize(&bytes);
+                let req = Request::from_parts(parts, Body::from(bytes));
+                (Some(summary), next.run(req).await)
+            }
+            Err(_) => (None, StatusCode::PAYLOAD_TOO_LARGE.into_response()),
+        }
+    } else {
+        (None, next.run(req).await)
+    };
+
+    let entry = AuditEntry {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        user_id: principal
+            .user_id
+            .or_else(|| namespace.clone())
+            .unwrap_or_else(|| "anonymous".to_string()),
+        namespace,
+        action: if write { "http_write" } else { "http_read" }.to_string(),
+        resource: route,
+        details: None,
+        request_id,
+        request: Some(RequestAudit {
+            method: method.to_string(),
+            dataset,
+            status: response.status().as_u16(),
+            latency_us: started.elapsed().as_micros() as u64,
+            summary,
+        }),
+    };
+    auditor.log.write().await.record(entry);
+
+    response
+}
+
+/// The dataset a request path addresses
+fn dataset_of(path: &str) -> &str {
+    path.strip_prefix("/api/v1/datasets/")
+        .and_then(|rest| rest.split_once('/'))
+        .map_or(DEFAULT_DATASET, |(dataset, _)| dataset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn body(value: serde_json::Value) -> Vec<u8> {
+        serde_json::to_vec(&value).unwrap()
+    }
+
+    #[test]
+    fn test_redacts_literal_objects_of_sensitive_predicates() {
+        let redactor = Redactor::new(["ex:ssn"]);
+        let summary = redactor.summarize(&body(json!({
+            "triples": [
+                {"subject": "ex:alice", "predicate": "ex:ssn", "object": "123-45-6789"},
+                {"subject": "ex:alice", "predicate": "ex:ssn", "object": {"node": "ex:ssn-record"}},
+                {"subject": "ex:alice", "predicate": "ex:name", "object": "Alice"},
+            ]
+        })));
+
+        assert_eq!(summary.triple_count, 3);
+        let objects: Vec<_> = summary
+            .triples
+            .iter()
+            .map(|t| serde_json::to_value(&t.object).unwrap())
+            .collect();
+        assert_eq!(
+            objects,
+            [
+                json!(null),
+                json!({"node": "ex:ssn-record"}),
+                json!("Alice")
+            ]
+        );
+        assert!(summary.triples.iter().all(|t| t.subject == "ex:alice"));
+    }
+
+    #[test]
+    fn test_summarizes_every_batch_format() {
+        let redactor = Redactor::new(["ex:ssn"]);
+        let triple = json!({"subject": "ex:bob", "predicate": "ex:ssn", "object": 42});
+
+        let single = redactor.summarize(&body(triple.clone()));
+        let array = redactor.summarize(&body(json!([triple, triple])));
+        let ndjson = format!("{triple}\n\n{triple}\n");
+        let ndjson = redactor.summarize(ndjson.as_bytes());
+        assert_eq!(single.triple_count, 1);
+        assert_eq!((array.triple_count, ndjson.triple_count), (2, 2));
+        for summary in [single, array, ndjson] {
+            assert!(summary.triples.iter().all(|t| t.object.is_none()));
+        }
+
+        let update = redactor.summarize(b"DELETE WHERE { ?s <ex:ssn> \"123\" }");
+        assert_eq!(update.triple_count, 0);
+        assert!(update.triples.is_empty());
+        assert!(update.bytes > 0);
+    }
+
+    #[test]
+    fn test_caps_listed_triples() {
+        let triples: Vec<_> = (0..MAX_SUMMARY_TRIPLES + 5)
+            .map(|i| json!({"subject": format!("ex:s{i}"), "predicate": "ex:p", "object": i}))
+            .collect();
+        let summary = Redactor::default().summarize(&body(json!(triples)));
+        assert_eq!(summary.triple_count, MAX_SUMMARY_TRIPLES + 5);
+        assert_eq!(summary.triples.len(), MAX_SUMMARY_TRIPLES);
+    }
+
+    #[test]
+    fn test_dataset_of() {
+        assert_eq!(
+            dataset_of("/api/v1/datasets/customer-a/triples"),
+            "customer-a"
+        );
+        assert_eq!(dataset_of("/api/v1/datasets"), DEFAULT_DATASET);
+        assert_eq!(dataset_of("/api/v1/datasets/customer-a"), DEFAULT_DATASET);
+        assert_eq!(dataset_of("/api/v1/triples"), DEFAULT_DATASET);
+    }
+}
@@ -5,7 +5,8 @@
 //!
 //! This module provides middleware components for the Córtex API server:
 //!
-//! - **Rate Limiting**: Token bucket algorithm to prevent API abuse
+//! - **Rate Limiting**: Per-client token buckets to prevent API abuse
+//! - **Request Audit**: Who called which route, with redacted request summaries
 //! - **Metrics**: Request/response metrics collection
 //! - **Logging**: Enhanced request/response logging
 //!
@@ -19,8 +20,16 @@
 //!     .layer(rate_limiter.into_layer());
 //! ```
 
+pub mod audit;
 pub mod namespace;
 pub mod rate_limit;
 
-pub use namespace::{is_in_namespace, namespace_extractor, scope_subject, RequestNamespace};
-pub use rate_limit::{RateLimitError, RateLimiter, RateLimiterLayer};
+pub use audit::{request_audit, Redactor, RequestAuditConfig, RequestAuditor};
+pub use namespace::{
+    is_in_namespace, namespace_extractor, scope_subject, RequestNamespace, RequestPrincipal,
+    DATASET_SCOPE_SEPARATOR, WRITE_ROLE,
+};
+pub use rate_limit::{
+    RateLimitError, RateLimitKey, RateLimitResponse, RateLimitStats, RateLimiter, RateLimiterLayer,
+    RateScope, ScopeCounters, ScopeLimit,
+};
@@ -5,46 +5,150 @@
 //!
 //! Extracts the `namespace` from JWT claims and injects it into Axum request
 //! extensions so downstream handlers can scope queries/mutations by namespace.
+//! The caller's identity and roles are injected alongside it as a
+//! [`RequestPrincipal`].
 
 use axum::{body::Body, http::Request, middleware::Next, response::Response};
 
+/// Role that lets a caller use write-scoped endpoints, such as SPARQL UPDATE.
+pub const WRITE_ROLE: &str = "write";
+
+/// Separates a role from the dataset it is limited to, as in
+/// `write:triples@customer-a`.
+pub const DATASET_SCOPE_SEPARATOR: char = '@';
+
 /// Namespace extracted from JWT claims, available via request extensions.
 #[derive(Debug, Clone)]
 pub struct RequestNamespace(pub Option<String>);
 
+/// The authenticated caller, available via request extensions.
+#[derive(Debug, Clone, Default)]
+pub struct RequestPrincipal {
+    /// User id (`sub` claim) of the caller, if authenticated
+    pub user_id: Option<String>,
+    /// Roles granted to the caller
+    pub roles: Vec<String>,
+}
+
+impl RequestPrincipal {
+    /// The principal for a request without valid credentials.
+    ///
+    /// Without the `auth` feature every caller is trusted and is treated as an
+    /// admin; with it, an unauthenticated caller has no roles.
+    pub fn unauthenticated() -> Self {
+        Self {
+            user_id: None,
+            roles: if cfg!(feature = "auth") {
+                Vec::new()
+            } else {
+                vec!["admin".to_string()]
+            },
+        }
+    }
+
+    /// Resolve the principal from an optional request extension.
+    pub fn from_extension(ext: Option<axum::Extension<RequestPrincipal>>) -> Self {
+        ext.map(|axum::Extension(p)| p)
+            .unwrap_or_else(Self::unauthenticated)
+    }
+
+    /// Check if the principal has a specific role
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles.iter().any(|r| r == role)
+    }
+
+    /// Check if the principal may use admin-only endpoints
+    pub fn is_admin(&self) -> bool {
+        self.has_role("admin")
+    }
+
+    /// Check if the principal may use write-scoped endpoints
+    ///
+    /// Admins always may; anyone else needs the [`WRITE_ROLE`].
+    pub fn can_write(&self) -> bool {
+        self.is_admin() || self.has_role(WRITE_ROLE)
+    }
+
+    /// The principal as seen inside `dataset`, or `None` without access to it.
+    ///
+    /// A role qualified with a dataset (`write@customer-a`) only applies in
+    /// that dataset and is returned without the qualifier there. A principal
+    /// holding any qualified role is confined to the datasets it names, unless
+    /// it also holds the unqualified `admin` role; principals without
+    /// qualified roles may use every dataset.
+    pub fn for_dataset(&self, dataset: &str) -> Option<RequestPrincipal> {
+        let mut scoped = false;
+        let mut granted = false;
+        let mut roles = Vec::with_capacity(self.roles.len());
+        for role in &self.roles {
+            match role.rsplit_once(DATASET_SCOPE_SEPARATOR) {
+                Some((role, ds)) => {
+                    scoped = true;
+                    if ds == dataset {
+                        granted = true;
+                        roles.push(role.to_string());
+                    }
+                }
+                None => roles.push(role.clone()),
+            }
+        }
+        if scoped && !granted && !self.is_admin() {
+            return None;
+        }
+        Some(RequestPrincipal {
+            user_id: self.user_id.clone(),
+            roles,
+        })
+    }
+}
+
 /// Middleware that extracts namespace from JWT claims and stores it in request extensions.
 ///
 /// If auth is not enabled or no namespace is present in the token, sets `None`.
 /// Downstream handlers can read `RequestNamespace` from extensions and enforce
-/// namespace boundaries accordingly.
+/// namespace boundaries accordingly. A `RequestPrincipal` is inserted as well.
 pub async fn namespace_extractor(mut req: Request<Body>, next: Next) -> Response {
-    // Try to extract namespace from the Authorization header
-    let namespace = extract_namespace_from_token(&req);
+    // Try to extract namespace and principal from the Authorization header
+    let (namespace, principal) = extract_from_token(&req);
     req.extensions_mut().insert(RequestNamespace(namespace));
+    req.extensions_mut().insert(principal);
     next.run(req).await
 }
 
-/// Extract namespace from Bearer token in Authorization header.
+/// Extract namespace and principal from Bearer token in Authorization header.
 ///
-/// Returns `None` if:
+/// The namespace is `None` if:
 /// - No Authorization header present
 /// - Token is invalid or cannot be decoded
 /// - Claims do not contain a namespace field
 /// - Auth feature is not enabled
+///
+/// The principal falls back to [`RequestPrincipal::unauthenticated`] unless the
+/// token is valid.
 #[cfg(feature = "auth")]
-fn extract_namespace_from_token(req: &Request<Body>) -> Option<String> {
-    let auth_header = req.headers().get("authorization")?.to_str().ok()?;
-    let token = auth_header.strip_prefix("Bearer ")?;
+fn extract_from_token(req: &Request<Body>) -> (Option<String>, RequestPrincipal) {
+    let claims = req
+        .headers()
+        .get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .and_then(|token| crate::auth::verify_token(token).ok());
 
-    match crate::auth::verify_token(token) {
-        Ok(claims) => claims.namespace.clone(),
-        Err(_) => None,
+    match claims {
+        Some(claims) => (
+            claims.namespace,
+            RequestPrincipal {
+                user_id: Some(claims.sub),
+                roles: claims.roles,
+            },
+        ),
+        None => (None, RequestPrincipal::unauthenticated()),
     }
 }
 
 #[cfg(not(feature = "auth"))]
-fn extract_namespace_from_token(_req: &Request<Body>) -> Option<String> {
-    None
+fn extract_from_token(_req: &Request<Body>) -> (Option<String>, RequestPrincipal) {
+    (None, RequestPrincipal::unauthenticated())
 }
 
 /// Helper: check if a subject belongs to the given namespace.
@@ -72,6 +176,54 @@ mod tests {
         assert!(!is_in_namespace("agent:a1", "mayros"));
     }
 
+    #[test]
+    fn test_principal_roles() {
+        let admin = RequestPrincipal {
+            user_id: Some("u1".into()),
+            roles: vec!["user".into(), "admin".into()],
+        };
+        assert!(admin.is_admin());
+        assert!(admin.can_write());
+        assert!(!RequestPrincipal::default().is_admin());
+        let writer = RequestPrincipal {
+            user_id: Some("u2".into()),
+            roles: vec!["user".into(), WRITE_ROLE.into()],
+        };
+        assert!(writer.can_write() && !writer.is_admin());
+        assert!(!RequestPrincipal::default().can_write());
+        assert_eq!(
+            RequestPrincipal::from_extension(None).is_admin(),
+            !cfg!(feature = "auth")
+        );
+    }
+
+    #[test]
+    fn test_principal_for_dataset() {
+        let tenant = RequestPrincipal {
+            user_id: Some("u3".into()),
+            roles: vec!["user".into(), "write@customer-a".into()],
+        };
+        let in_a = tenant.for_dataset("customer-a").unwrap();
+        assert_eq!(in_a.roles, ["user", WRITE_ROLE]);
+        assert!(in_a.can_write());
+        assert!(tenant.for_dataset("customer-b").is_none());
+        assert!(tenant.for_dataset("default").is_none());
+
+        // Unscoped principals keep their roles everywhere
+        let writer = RequestPrincipal {
+            user_id: Some("u2".into()),
+            roles: vec![WRITE_ROLE.into()],
+        };
+        assert!(writer.for_dataset("customer-b").unwrap().can_write());
+
+        // A global admin reaches every dataset, without the scoped roles elsewhere
+        let admin = RequestPrincipal {
+            user_id: Some("u1".into()),
+            roles: vec!["admin".into(), "audit@customer-a".into()],
+        };
+        assert_eq!(admin.for_dataset("customer-b").unwrap().roles, ["admin"]);
+    }
+
     #[test]
     fn test_scope_subject() {
         assert_eq!(scope_subject("agent:a1", "mayros"), "mayros:agent:a1");
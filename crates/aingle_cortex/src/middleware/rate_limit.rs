@@ -3,23 +3,31 @@
 
 //! Rate limiting middleware using Token Bucket algorithm
 //!
-//! This module implements a token bucket rate limiter that tracks requests per IP address.
-//! Each IP gets its own bucket that refills at a constant rate.
+//! This module implements a token bucket rate limiter that tracks requests per
+//! client. Each client gets one bucket per [`RateScope`], so a burst of writes
+//! does not eat into the same client's read budget.
 //!
 //! ## How it works
 //!
-//! 1. Each IP address gets a bucket with N tokens (capacity)
-//! 2. Each request consumes 1 token
-//! 3. Tokens refill at a constant rate (e.g., 100 per minute)
-//! 4. If bucket is empty, request is rejected with 429 Too Many Requests
+//! 1. Each request is charged to a client: a configured API key, else the
+//!    subject of a valid JWT, or otherwise the client IP address
+//! 2. The route decides the scope: proof validation, writes, or reads
+//! 3. Each client gets a bucket per scope with N tokens (burst capacity),
+//!    refilling at the scope's rate (e.g., 100 per minute)
+//! 4. Each request consumes 1 token; if the bucket is empty, the request is
+//!    rejected with 429 Too Many Requests and a `Retry-After` header
+//! 5. Buckets idle for longer than the idle timeout expire, and at most
+//!    `max_buckets` are kept, evicting the least recently used first, so
+//!    rotating keys cannot exhaust memory
 //!
 //! ## Example
 //!
 //! ```rust,ignore
-//! use aingle_cortex::middleware::RateLimiter;
+//! use aingle_cortex::middleware::{RateLimiter, RateScope, ScopeLimit};
 //!
-//! // Allow 100 requests per minute per IP
-//! let limiter = RateLimiter::new(100);
+//! // Allow 100 requests per minute per client, but only 10 proof validations
+//! let limiter = RateLimiter::new(100)
+//!     .with_scope_limit(RateScope::Proof, ScopeLimit::new(10));
 //! let app = Router::new()
 //!     .route("/api/v1/triples", get(handler))
 //!     .layer(limiter.into_layer());
@@ -27,18 +35,29 @@
 
 use axum::{
     extract::{ConnectInfo, Request},
-    http::{HeaderValue, StatusCode},
+    http::{header, HeaderValue, Method, StatusCode},
     response::{IntoResponse, Response},
 };
 use dashmap::DashMap;
+use serde::Serialize;
 use std::{
+    collections::HashSet,
     net::{IpAddr, SocketAddr},
-    sync::Arc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
     time::{Duration, Instant},
 };
 use thiserror::Error;
 use tower::{Layer, Service};
 
+/// Default cap on the number of buckets a limiter keeps
+pub const DEFAULT_MAX_BUCKETS: usize = 100_000;
+
+/// Default time after which an unused bucket expires
+pub const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(600);
+
 /// Rate limit error
 #[derive(Debug, Error)]
 pub enum RateLimitError {
@@ -51,17 +70,29 @@ pub enum RateLimitError {
     IpNotAvailable,
 }
 
+/// Body of a `429 Too Many Requests` response
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RateLimitResponse {
+    /// Human-readable message
+    pub error: String,
+    /// Always `RATE_LIMIT_EXCEEDED`
+    pub code: String,
+    /// Seconds until a token is available, also sent as `Retry-After`
+    pub retry_after: u64,
+}
+
 impl IntoResponse for RateLimitError {
     fn into_response(self) -> Response {
         let (status, message) = match &self {
             RateLimitError::TooManyRequests(secs) => {
                 let mut response = (
                     StatusCode::TOO_MANY_REQUESTS,
-                    axum::Json(serde_json::json!({
-                        "error": self.to_string(),
-                        "code": "RATE_LIMIT_EXCEEDED",
-                        "retry_after": secs
-                    })),
+                    axum::Json(RateLimitResponse {
+                        error: self.to_string(),
+                        code: "RATE_LIMIT_EXCEEDED".to_string(),
+                        retry_after: *secs,
+                    }),
                 )
                     .into_response();
 
@@ -84,6 +115,118 @@ impl IntoResponse for RateLimitError {
     }
 }
 
+/// Class of request, each with its own limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateScope {
+    /// Safe methods (`GET`, `HEAD`, `OPTIONS`)
+    Read,
+    /// Anything that may change state
+    Write,
+    /// Proof and triple validation, which is costly to serve
+    Proof,
+}
+
+impl RateScope {
+    /// Scope of a request, from its method and path
+    ///
+    /// Routes with a `verify`, `verify-batch` or `validate` path segment are
+    /// [`RateScope::Proof`] whatever their method.
+    pub fn classify(method: &Method, path: &str) -> Self {
+        let proof = path
+            .split('/')
+            .any(|segment| matches!(segment, "verify" | "verify-batch" | "validate"));
+        if proof {
+            RateScope::Proof
+        } else if method == Method::GET || method == Method::HEAD || method == Method::OPTIONS {
+            RateScope::Read
+        } else {
+            RateScope::Write
+        }
+    }
+
+    fn index(self) -> usize {
+        match self {
+            RateScope::Read => 0,
+            RateScope::Write => 1,
+            RateScope::Proof => 2,
+        }
+    }
+}
+
+/// Rate and burst for one [`RateScope`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScopeLimit {
+    /// Sustained requests per minute
+    pub requests_per_minute: u32,
+    /// Bucket capacity (max requests in a burst)
+    pub burst: u32,
+}
+
+impl ScopeLimit {
+    /// Limit of `requests_per_minute`, with a burst of the same size
+    pub fn new(requests_per_minute: u32) -> Self {
+        Self {
+            requests_per_minute,
+            burst: requests_per_minute,
+        }
+    }
+
+    /// Set the burst capacity
+    pub fn with_burst(mut self, burst: u32) -> Self {
+        self.burst = burst;
+        self
+    }
+}
+
+/// Client a request is charged to
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RateLimitKey {
+    /// Subject of a verified JWT
+    Subject(String),
+    /// A configured API key, by its BLAKE3 digest
+    ApiKey([u8; 32]),
+    /// Client IP address, for anonymous requests
+    Ip(IpAddr),
+}
+
+impl From<IpAddr> for RateLimitKey {
+    fn from(ip: IpAddr) -> Self {
+        RateLimitKey::Ip(ip)
+    }
+}
+
+/// Allowed and throttled requests in one scope
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ScopeCounters {
+    /// Requests let through
+    pub allowed: u64,
+    /// Requests answered with 429
+    pub throttled: u64,
+}
+
+/// Snapshot of the limiter's counters, for metrics
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RateLimitStats {
+    /// Read requests
+    pub reads: ScopeCounters,
+    /// Write requests
+    pub writes: ScopeCounters,
+    /// Proof validation requests
+    pub proofs: ScopeCounters,
+    /// Buckets dropped for idleness or to stay under the bucket cap
+    pub evicted: u64,
+    /// Buckets currently held
+    pub active_buckets: usize,
+}
+
+#[derive(Default)]
+struct Counters {
+    allowed: [AtomicU64; 3],
+    throttled: [AtomicU64; 3],
+    evicted: AtomicU64,
+}
+
 /// Token bucket for rate limiting
 #[derive(Debug, Clone)]
 struct TokenBucket {
@@ -108,6 +251,11 @@ impl TokenBucket {
         }
     }
 
+    /// Create a full bucket for `limit`
+    fn for_limit(limit: ScopeLimit) -> Self {
+        Self::new(limit.burst as f64, limit.requests_per_minute as f64 / 60.0)
+    }
+
     /// Refill tokens based on elapsed time
     fn refill(&mut self) {
         let now = Instant::now();
@@ -129,7 +277,7 @@ impl TokenBucket {
             // Calculate retry-after in seconds
             let tokens_needed = amount - self.tokens;
             let retry_after = (tokens_needed / self.refill_rate).ceil() as u64;
-            Err(retry_after)
+            Err(retry_after.max(1))
         }
     }
 
@@ -143,12 +291,17 @@ impl TokenBucket {
 /// Rate limiter using token bucket algorithm
 #[derive(Clone)]
 pub struct RateLimiter {
-    /// Token buckets per IP address
-    buckets: Arc<DashMap<IpAddr, TokenBucket>>,
-    /// Requests per minute
-    requests_per_minute: u32,
-    /// Burst capacity (max requests in bucket)
-    burst_capacity: u32,
+    /// Token buckets per client and scope
+    buckets: Arc<DashMap<(RateLimitKey, RateScope), TokenBucket>>,
+    /// Limits, indexed by [`RateScope::index`]
+    limits: [ScopeLimit; 3],
+    /// Digests of the API keys that identify a client
+    api_keys: Arc<HashSet<[u8; 32]>>,
+    /// Most buckets kept at once
+    max_buckets: usize,
+    /// Unused buckets older than this expire
+    idle_timeout: Duration,
+    counters: Arc<Counters>,
     /// Use secure IP extraction (X-Forwarded-For, X-Real-IP)
     secure_ip: bool,
 }
@@ -158,7 +311,7 @@ impl RateLimiter {
     ///
     /// # Arguments
     ///
-    /// * `requests_per_minute` - Maximum requests per minute per IP
+    /// * `requests_per_minute` - Maximum requests per minute per client, in every scope
     ///
     /// # Example
     ///
@@ -168,15 +321,55 @@ impl RateLimiter {
     pub fn new(requests_per_minute: u32) -> Self {
         Self {
             buckets: Arc::new(DashMap::new()),
-            requests_per_minute,
-            burst_capacity: requests_per_minute, // Same as rate by default
+            limits: [ScopeLimit::new(requests_per_minute); 3],
+            api_keys: Arc::new(HashSet::new()),
+            max_buckets: DEFAULT_MAX_BUCKETS,
+            idle_timeout: DEFAULT_IDLE_TIMEOUT,
+            counters: Arc::new(Counters::default()),
             secure_ip: false,
         }
     }
 
-    /// Set burst capacity (max tokens in bucket)
+    /// Set burst capacity (max tokens in bucket) for every scope
     pub fn with_burst_capacity(mut self, capacity: u32) -> Self {
-        self.burst_capacity = capacity;
+        for limit in &mut self.limits {
+            limit.burst = capacity;
+        }
+        self
+    }
+
+    /// Set the rate and burst for one scope
+    pub fn with_scope_limit(mut self, scope: RateScope, limit: ScopeLimit) -> Self {
+        self.limits[scope.index()] = limit;
+        self
+    }
+
+    /// Accept these API keys as client identities
+    ///
+    /// A key is read from `X-API-Key` or a non-JWT bearer token. Unknown keys
+    /// are ignored, so rotating made-up keys falls back to the IP bucket.
+    pub fn with_api_keys<I, K>(mut self, keys: I) -> Self
+    where
+        I: IntoIterator<Item = K>,
+        K: AsRef<str>,
+    {
+        self.api_keys = Arc::new(
+            keys.into_iter()
+                .map(|key| *blake3::hash(key.as_ref().as_bytes()).as_bytes())
+                .collect(),
+        );
+        self
+    }
+
+    /// Set the most buckets kept at once
+    pub fn with_max_buckets(mut self, max_buckets: usize) -> Self {
+        self.max_buckets = max_buckets.max(1);
+        self
+    }
+
+    /// Set how long an unused bucket lives
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = idle_timeout;
         self
     }
 
@@ -186,42 +379,177 @@ impl RateLimiter {
         self
     }
 
-    /// Check rate limit for given IP
+    /// Limit applied to `scope`
+    pub fn limit(&self, scope: RateScope) -> ScopeLimit {
+        self.limits[scope.index()]
+    }
+
+    /// Check rate limit for a read from the given IP
     pub fn check(&self, ip: IpAddr) -> Result<u64, RateLimitError> {
-        let refill_rate = self.requests_per_minute as f64 / 60.0; // tokens per second
+        self.check_key(&RateLimitKey::Ip(ip), RateScope::Read)
+    }
 
-        let mut entry = self
-            .buckets
-            .entry(ip)
-            .or_insert_with(|| TokenBucket::new(self.burst_capacity as f64, refill_rate));
+    /// Charge one request in `scope` to `key`, returning the tokens left
+    pub fn check_key(&self, key: &RateLimitKey, scope: RateScope) -> Result<u64, RateLimitError> {
+        let limit = self.limit(scope);
+        let bucket_key = (key.clone(), scope);
+        if !self.buckets.contains_key(&bucket_key) {
+            self.make_room();
+        }
 
-        match entry.consume(1.0) {
-            Ok(()) => {
-                let remaining = entry.remaining();
-                Ok(remaining)
+        let result = {
+            let mut entry = self
+                .buckets
+                .entry(bucket_key)
+                .or_insert_with(|| TokenBucket::for_limit(limit));
+            if entry.last_refill.elapsed() >= self.idle_timeout {
+                *entry = TokenBucket::for_limit(limit);
             }
-            Err(retry_after) => Err(RateLimitError::TooManyRequests(retry_after)),
-        }
+            entry.consume(1.0).map(|()| entry.remaining())
+        };
+
+        let counters = match result {
+            Ok(_) => &self.counters.allowed,
+            Err(_) => &self.counters.throttled,
+        };
+        counters[scope.index()].fetch_add(1, Ordering::Relaxed);
+        result.map_err(RateLimitError::TooManyRequests)
     }
 
-    /// Get current bucket state for IP
+    /// Get current read bucket state for IP
     pub fn bucket_info(&self, ip: IpAddr) -> Option<(u64, u64)> {
-        self.buckets.get_mut(&ip).map(|mut bucket| {
-            bucket.refill();
-            (bucket.remaining(), self.burst_capacity as u64)
-        })
+        let scope = RateScope::Read;
+        self.buckets
+            .get_mut(&(RateLimitKey::Ip(ip), scope))
+            .map(|mut bucket| (bucket.remaining(), self.limit(scope).burst as u64))
     }
 
     /// Clear old buckets (cleanup)
     pub fn cleanup(&self, max_age: Duration) {
-        self.buckets
-            .retain(|_, bucket| bucket.last_refill.elapsed() < max_age);
+        self.evict(|bucket| bucket.last_refill.elapsed() >= max_age);
+    }
+
+    /// Drop buckets unused for longer than the idle timeout
+    ///
+    /// Returns how many were dropped. Runs on its own when the bucket cap is
+    /// reached; call it periodically to release memory sooner.
+    pub fn purge_idle(&self) -> usize {
+        let idle_timeout = self.idle_timeout;
+        self.evict(|bucket| bucket.last_refill.elapsed() >= idle_timeout)
+    }
+
+    /// Snapshot of the request and eviction counters
+    pub fn stats(&self) -> RateLimitStats {
+        let scope = |scope: RateScope| ScopeCounters {
+            allowed: self.counters.allowed[scope.index()].load(Ordering::Relaxed),
+            throttled: self.counters.throttled[scope.index()].load(Ordering::Relaxed),
+        };
+        RateLimitStats {
+            reads: scope(RateScope::Read),
+            writes: scope(RateScope::Write),
+            proofs: scope(RateScope::Proof),
+            evicted: self.counters.evicted.load(Ordering::Relaxed),
+            active_buckets: self.buckets.len(),
+        }
     }
 
     /// Convert to tower Layer
     pub fn into_layer(self) -> RateLimiterLayer {
         RateLimiterLayer { limiter: self }
     }
+
+    /// Make space for one more bucket if the cap is reached
+    ///
+    /// Idle buckets go first. If every bucket is active, the least recently
+    /// used sixteenth is dropped so the scan is not repeated on every new client.
+    fn make_room(&self) {
+        if self.buckets.len() < self.max_buckets {
+            return;
+        }
+        self.purge_idle();
+        if self.buckets.len() < self.max_buckets {
+            return;
+        }
+
+        let mut last_used: Vec<Instant> = self.buckets.iter().map(|b| b.last_refill).collect();
+        if last_used.is_empty() {
+            return;
+        }
+        let count = (self.max_buckets / 16).clamp(1, last_used.len());
+        let (_, cutoff, _) = last_used.select_nth_unstable(count - 1);
+        let cutoff = *cutoff;
+        self.evict(|bucket| bucket.last_refill <= cutoff);
+    }
+
+    /// Remove the buckets matching `stale`, returning how many went
+    fn evict(&self, stale: impl Fn(&TokenBucket) -> bool) -> usize {
+        let mut removed = 0;
+        self.buckets.retain(|_, bucket| {
+            let drop = stale(bucket);
+            removed += drop as usize;
+            !drop
+        });
+        self.counters
+            .evicted
+            .fetch_add(removed as u64, Ordering::Relaxed);
+        removed
+    }
+
+    /// Client to charge `req` to
+    fn identify(&self, req: &Request) -> RateLimitKey {
+        let bearer = req
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+
+        // Known API keys first: they are cheap to check and the MCP bearer
+        // token is not a JWT.
+        let api_key = req
+            .headers()
+            .get("x-api-key")
+            .and_then(|value| value.to_str().ok())
+            .into_iter()
+            .chain(bearer)
+            .map(|key| *blake3::hash(key.as_bytes()).as_bytes())
+            .find(|digest| self.api_keys.contains(digest));
+        if let Some(digest) = api_key {
+            return RateLimitKey::ApiKey(digest);
+        }
+
+        if let Some(subject) = bearer.and_then(jwt_subject) {
+            return RateLimitKey::Subject(subject);
+        }
+
+        // Extract IP address.
+        // 1. If behind a proxy, try X-Forwarded-For / X-Real-IP headers.
+        // 2. Fall back to ConnectInfo<SocketAddr> (direct connection IP).
+        let ip = if self.secure_ip {
+            extract_proxy_ip(req).or_else(|| extract_connect_ip(req))
+        } else {
+            extract_connect_ip(req).or_else(|| extract_proxy_ip(req))
+        };
+
+        // Last resort: assume localhost for sidecar usage.
+        RateLimitKey::Ip(ip.unwrap_or(IpAddr::V4(std::net::Ipv4Addr::LOCALHOST)))
+    }
+}
+
+/// Subject of `token` if it is a valid JWT
+///
+/// Without `AINGLE_JWT_SECRET` no token can be verified (the auth module
+/// panics rather than use a default secret), so requests fall back to IP.
+#[cfg(feature = "auth")]
+fn jwt_subject(token: &str) -> Option<String> {
+    std::env::var_os("AINGLE_JWT_SECRET")?;
+    crate::auth::verify_token(token)
+        .ok()
+        .map(|claims| claims.sub)
+}
+
+#[cfg(not(feature = "auth"))]
+fn jwt_subject(_token: &str) -> Option<String> {
+    None
 }
 
 impl Default for RateLimiter {
@@ -277,25 +605,11 @@ where
         let mut inner = self.inner.clone();
 
         Box::pin(async move {
-            // Extract IP address.
-            // 1. If behind a proxy, try X-Forwarded-For / X-Real-IP headers.
-            // 2. Fall back to ConnectInfo<SocketAddr> (direct connection IP).
-            let ip = if limiter.secure_ip {
-                extract_proxy_ip(&req).or_else(|| extract_connect_ip(&req))
-            } else {
-                extract_connect_ip(&req).or_else(|| extract_proxy_ip(&req))
-            };
-
-            let ip = match ip {
-                Some(ip) => ip,
-                None => {
-                    // Last resort: assume localhost for sidecar usage.
-                    IpAddr::V4(std::net::Ipv4Addr::LOCALHOST)
-                }
-            };
+            let key = limiter.identify(&req);
+            let scope = RateScope::classify(req.method(), req.uri().path());
 
             // Check rate limit
-            match limiter.check(ip) {
+            match limiter.check_key(&key, scope) {
                 Ok(remaining) => {
                     // Call inner service
                     let mut response = inner.call(req).await?;
@@ -304,7 +618,7 @@ where
                     let headers = response.headers_mut();
                     headers.insert(
                         "X-RateLimit-Limit",
-                        HeaderValue::from(limiter.requests_per_minute),
+                        HeaderValue::from(limiter.limit(scope).requests_per_minute),
                     );
                     headers.insert("X-RateLimit-Remaining", HeaderValue::from(remaining));
 
@@ -461,4 +775,125 @@ mod tests {
         bucket.refill();
         assert!(bucket.tokens >= 9.0); // Allow some slack for timing
     }
+
+    #[test]
+    fn test_classify_scopes() {
+        assert_eq!(
+            RateScope::classify(&Method::GET, "/api/v1/triples"),
+            RateScope::Read
+        );
+        assert_eq!(
+            RateScope::classify(&Method::POST, "/api/v1/triples/batch"),
+            RateScope::Write
+        );
+        assert_eq!(
+            RateScope::classify(&Method::DELETE, "/api/v1/triples/abc"),
+            RateScope::Write
+        );
+        assert_eq!(
+            RateScope::classify(&Method::GET, "/api/v1/proofs/abc/verify"),
+            RateScope::Proof
+        );
+        assert_eq!(
+            RateScope::classify(&Method::POST, "/api/v1/validate"),
+            RateScope::Proof
+        );
+        assert_eq!(
+            RateScope::classify(&Method::GET, "/api/v1/proofs/verify-me"),
+            RateScope::Read
+        );
+    }
+
+    #[test]
+    fn test_scopes_have_separate_buckets() {
+        let limiter = RateLimiter::new(100)
+            .with_scope_limit(RateScope::Write, ScopeLimit::new(60).with_burst(2));
+        let key = RateLimitKey::Subject("alice".into());
+
+        assert!(limiter.check_key(&key, RateScope::Write).is_ok());
+        assert!(limiter.check_key(&key, RateScope::Write).is_ok());
+        assert!(matches!(
+            limiter.check_key(&key, RateScope::Write),
+            Err(RateLimitError::TooManyRequests(1))
+        ));
+
+        // Reads are charged to their own bucket
+        assert_eq!(limiter.check_key(&key, RateScope::Read).unwrap(), 99);
+
+        let stats = limiter.stats();
+        assert_eq!(
+            stats.writes,
+            ScopeCounters {
+                allowed: 2,
+                throttled: 1
+            }
+        );
+        assert_eq!(stats.reads.allowed, 1);
+        assert_eq!(stats.active_buckets, 2);
+    }
+
+    #[test]
+    fn test_max_buckets_evicts_least_recently_used() {
+        let limiter = RateLimiter::new(10).with_max_buckets(4);
+        let key = |i: u8| RateLimitKey::Ip(IpAddr::V4(Ipv4Addr::new(10, 0, 0, i)));
+
+        for i in 0..4 {
+            limiter.check_key(&key(i), RateScope::Read).unwrap();
+            std::thread::sleep(Duration::from_millis(2));
+        }
+        // Touch the oldest so the second becomes least recently used
+        limiter.check_key(&key(0), RateScope::Read).unwrap();
+        limiter.check_key(&key(4), RateScope::Read).unwrap();
+
+        assert_eq!(limiter.buckets.len(), 4);
+        assert!(limiter.buckets.contains_key(&(key(0), RateScope::Read)));
+        assert!(!limiter.buckets.contains_key(&(key(1), RateScope::Read)));
+        assert_eq!(limiter.stats().evicted, 1);
+    }
+
+    #[test]
+    fn test_idle_bucket_expires() {
+        let limiter = RateLimiter::new(1).with_idle_timeout(Duration::from_millis(20));
+        let key = RateLimitKey::Subject("alice".into());
+
+        limiter.check_key(&key, RateScope::Read).unwrap();
+        assert!(limiter.check_key(&key, RateScope::Read).is_err());
+
+        std::thread::sleep(Duration::from_millis(40));
+
+        // At 1 req/min the bucket would still be empty; expiry resets it
+        assert!(limiter.check_key(&key, RateScope::Read).is_ok());
+
+        std::thread::sleep(Duration::from_millis(40));
+        assert_eq!(limiter.purge_idle(), 1);
+        assert_eq!(limiter.stats().active_buckets, 0);
+    }
+
+    #[test]
+    fn test_identify_prefers_known_api_key_over_ip() {
+        let limiter = RateLimiter::new(10).with_api_keys(["secret-key"]);
+        let request = |name: &str, value: &str| {
+            Request::builder()
+                .header(name, value)
+                .header("x-real-ip", "192.0.2.7")
+                .body(axum::body::Body::empty())
+                .unwrap()
+        };
+
+        let known = limiter.identify(&request("x-api-key", "secret-key"));
+        assert_eq!(
+            known,
+            RateLimitKey::ApiKey(*blake3::hash(b"secret-key").as_bytes())
+        );
+        assert_eq!(
+            limiter.identify(&request("authorization", "Bearer secret-key")),
+            known
+        );
+
+        // Unknown keys do not get a bucket of their own
+        assert_eq!(
+            limiter.identify(&request("x-api-key", "made-up")),
+            RateLimitKey::Ip("192.0.2.7".parse().unwrap())
+        );
+    }
 }
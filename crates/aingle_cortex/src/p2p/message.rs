@@ -130,6 +130,7 @@ impl TripleWire {
             NodeId::Named(s) => s.clone(),
             NodeId::Hash(h) => format!("hash:{}", hex::encode(h)),
             NodeId::Blank(id) => format!("_:b{}", id),
+            NodeId::Anonymous(id) => format!("_:a{}", hex::encode(id)),
         };
 
         let predicate = triple.predicate.as_str().to_string();
@@ -145,6 +146,7 @@ impl TripleWire {
                 NodeId::Named(s) => s.clone(),
                 NodeId::Hash(h) => format!("hash:{}", hex::encode(h)),
                 NodeId::Blank(id) => format!("_:b{}", id),
+                NodeId::Anonymous(id) => format!("_:a{}", hex::encode(id)),
             }),
             source: triple.meta.source.clone(),
         }
@@ -217,6 +219,9 @@ fn value_to_json(v: &Value) -> serde_json::Value {
             NodeId::Blank(id) => {
                 serde_json::json!({ "type": "node", "value": format!("_:b{}", id) })
             }
+            NodeId::Anonymous(id) => {
+                serde_json::json!({ "type": "node", "value": format!("_:a{}", hex::encode(id)) })
+            }
         },
         Value::Json(j) => j.clone(),
         Value::Null => serde_json::Value::Null,
@@ -14,6 +14,7 @@
 //! - **Caching**: LRU cache for verification results
 //! - **Batch Operations**: Efficient batch proof submission and verification
 //! - **Statistics**: Track proof counts, verification rates, and cache hits
+//! - **Lifecycle**: Expiry and revocation, with expired and revoked proofs kept
 //!
 //! ## Architecture
 //!
@@ -45,6 +46,7 @@
 //!     proof_type: ProofType::Schnorr,
 //!     proof_data: vec![...],
 //!     metadata: None,
+//!     expires_at: None,
 //! };
 //! let proof_id = store.submit(request).await?;
 //!
@@ -58,8 +60,14 @@ pub mod store;
 pub mod verification;
 
 pub use backend::ProofBackend;
-pub use store::{ProofId, ProofMetadata, ProofStore, ProofType, StoredProof, SubmitProofRequest};
-pub use verification::{ProofVerifier, VerificationError, VerificationResult};
+pub use store::{
+    ProofId, ProofMetadata, ProofStatus, ProofStore, ProofType, StoredProof, SubmitProofRequest,
+    SIGNING_KEY_FIELD,
+};
+pub use verification::{
+    MembershipProofData, ProofVerifier, RangeProofData, VerificationError, VerificationResult,
+    VerifierConfig,
+};
 
 /// Re-export commonly used types
 pub mod prelude {
@@ -5,22 +5,28 @@
 //!
 //! Provides in-memory storage of zero-knowledge proofs with LRU caching
 //! for verification results.
+//!
+//! Stored proofs have a lifecycle [`ProofStatus`]: a proof is valid until it
+//! passes its optional `expires_at` or is revoked. Expired and revoked proofs
+//! are kept, so their history stays available for audits.
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
 use super::backend::{MemoryProofBackend, ProofBackend, SledProofBackend};
-use super::verification::{VerificationError, VerificationResult};
+use super::verification::{VerificationError, VerificationResult, VerifierConfig};
 use super::ProofVerifier;
 
 /// Unique identifier for a proof
 pub type ProofId = String;
 
 /// Types of zero-knowledge proofs supported
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum ProofType {
@@ -54,7 +60,11 @@ impl std::fmt::Display for ProofType {
     }
 }
 
+/// Key of [`ProofMetadata::extra`] naming the key a proof was signed with
+pub const SIGNING_KEY_FIELD: &str = "signing_key";
+
 /// Metadata associated with a proof
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ProofMetadata {
     /// Submitter ID (user/agent that submitted)
@@ -65,6 +75,40 @@ pub struct ProofMetadata {
     pub extra: HashMap<String, serde_json::Value>,
 }
 
+impl ProofMetadata {
+    /// The key the proof was signed with, from the `signing_key` extra field
+    pub fn signing_key(&self) -> Option<&str> {
+        self.extra.get(SIGNING_KEY_FIELD)?.as_str()
+    }
+}
+
+/// Lifecycle status of a stored proof
+///
+/// This is independent of whether the proof verified: a proof that failed
+/// verification is still `Valid` until it expires or is revoked.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum ProofStatus {
+    /// Neither expired nor revoked
+    #[default]
+    Valid,
+    /// Past its expiry timestamp
+    Expired {
+        /// When the proof expired
+        expired_at: DateTime<Utc>,
+    },
+    /// Revoked by an administrator
+    Revoked {
+        /// Why the proof was revoked
+        reason: String,
+        /// Who revoked it
+        revoked_by: String,
+        /// When it was revoked
+        revoked_at: DateTime<Utc>,
+    },
+}
+
 /// A stored proof with metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StoredProof {
@@ -80,6 +124,15 @@ pub struct StoredProof {
     pub verified: bool,
     /// Last verification timestamp
     pub verified_at: Option<DateTime<Utc>>,
+    /// Messages from the last verification
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub verification_details: Vec<String>,
+    /// When the proof expires, if ever
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Lifecycle status as last recorded; see [`StoredProof::status_at`]
+    #[serde(default)]
+    pub status: ProofStatus,
     /// Metadata
     pub metadata: ProofMetadata,
 }
@@ -94,6 +147,9 @@ impl StoredProof {
             created_at: Utc::now(),
             verified: false,
             verified_at: None,
+            verification_details: Vec::new(),
+            expires_at: None,
+            status: ProofStatus::Valid,
             metadata,
         }
     }
@@ -104,22 +160,63 @@ impl StoredProof {
         self.verified_at = Some(Utc::now());
     }
 
+    /// Record the outcome of a verification
+    pub fn record_verification(&mut self, result: &VerificationResult) {
+        self.verified = result.valid;
+        self.verified_at = Some(result.verified_at);
+        self.verification_details = result.details.clone();
+    }
+
     /// Get size in bytes
     pub fn size_bytes(&self) -> usize {
         self.data.len()
     }
+
+    /// Status of the proof at `now`
+    ///
+    /// A valid proof past `expires_at` is reported as expired even before the
+    /// expiry sweep has recorded it.
+    pub fn status_at(&self, now: DateTime<Utc>) -> ProofStatus {
+        match (&self.status, self.expires_at) {
+            (ProofStatus::Valid, Some(expires_at)) if expires_at <= now => ProofStatus::Expired {
+                expired_at: expires_at,
+            },
+            (status, _) => status.clone(),
+        }
+    }
+
+    /// Current status of the proof
+    pub fn current_status(&self) -> ProofStatus {
+        self.status_at(Utc::now())
+    }
+
+    /// Whether the proof has been revoked
+    pub fn is_revoked(&self) -> bool {
+        matches!(self.status, ProofStatus::Revoked { .. })
+    }
 }
 
 /// Request to submit a new proof
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Clone, Deserialize)]
 pub struct SubmitProofRequest {
     /// Type of proof
     pub proof_type: ProofType,
     /// Proof data (JSON string or bytes)
+    ///
+    /// Range proofs take the form of [`RangeProofData`] and membership proofs
+    /// that should be checked against their leaf [`MembershipProofData`];
+    /// other kinds take `aingle_zk::ProofData` fields.
+    ///
+    /// [`RangeProofData`]: super::verification::RangeProofData
+    /// [`MembershipProofData`]: super::verification::MembershipProofData
     pub proof_data: serde_json::Value,
     /// Optional metadata
     #[serde(default)]
     pub metadata: Option<ProofMetadata>,
+    /// When the proof expires; never if omitted
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
 /// Response from submitting a proof
@@ -141,6 +238,8 @@ pub struct ProofStore {
     verifier: ProofVerifier,
     /// Statistics
     stats: Arc<RwLock<ProofStoreStats>>,
+    /// Warn when validating a proof signed by a key with revoked proofs
+    warn_on_revoked_signer: AtomicBool,
 }
 
 /// Simple LRU cache implementation
@@ -253,6 +352,7 @@ impl ProofStore {
             verification_cache: Arc::new(RwLock::new(LruCache::new(1000))),
             verifier: ProofVerifier::new(),
             stats: Arc::new(RwLock::new(initial_stats)),
+            warn_on_revoked_signer: AtomicBool::new(false),
         })
     }
 
@@ -263,37 +363,110 @@ impl ProofStore {
             verification_cache: Arc::new(RwLock::new(LruCache::new(cache_size))),
             verifier: ProofVerifier::new(),
             stats: Arc::new(RwLock::new(ProofStoreStats::default())),
+            warn_on_revoked_signer: AtomicBool::new(false),
         }
     }
 
+    /// Warn when [`validate`](Self::validate) is given a proof whose signing
+    /// key (see [`ProofMetadata::signing_key`]) has revoked proofs
+    ///
+    /// The warning is logged and added to the verification details; the proof
+    /// is still validated and stored.
+    pub fn set_warn_on_revoked_signer(&self, warn: bool) {
+        self.warn_on_revoked_signer.store(warn, Ordering::Relaxed);
+    }
+
     /// Submit a new proof
     pub async fn submit(&self, request: SubmitProofRequest) -> Result<ProofId, VerificationError> {
         // Serialize proof data
         let proof_bytes = serde_json::to_vec(&request.proof_data)
             .map_err(|e| VerificationError::InvalidProofData(e.to_string()))?;
 
-        let proof_size = proof_bytes.len();
         let metadata = request.metadata.unwrap_or_default();
-        let stored_proof = StoredProof::new(request.proof_type.clone(), proof_bytes, metadata);
-        let proof_id = stored_proof.id.clone();
+        let mut stored_proof = StoredProof::new(request.proof_type, proof_bytes, metadata);
+        stored_proof.expires_at = request.expires_at;
+        self.insert(&stored_proof).await?;
+        Ok(stored_proof.id)
+    }
 
-        // Serialize and store via backend
-        let serialized = serde_json::to_vec(&stored_proof)
+    /// Verify a proof and store it together with the outcome
+    ///
+    /// Verification runs in strict mode, so only proofs that can be checked
+    /// in full are accepted. Proof data that fails to parse is returned as an
+    /// error and nothing is stored; a proof that parses but does not verify
+    /// is stored with `verified: false`.
+    pub async fn validate(
+        &self,
+        request: SubmitProofRequest,
+    ) -> Result<(StoredProof, VerificationResult), VerificationError> {
+        let proof_bytes = serde_json::to_vec(&request.proof_data)
             .map_err(|e| VerificationError::InvalidProofData(e.to_string()))?;
-        self.backend
-            .put(&proof_id, &serialized)
-            .map_err(|e| VerificationError::InvalidProofData(e))?;
+        let metadata = request.metadata.unwrap_or_default();
+        let mut stored_proof = StoredProof::new(request.proof_type, proof_bytes, metadata);
+        stored_proof.expires_at = request.expires_at;
+
+        let verifier = ProofVerifier::with_config(VerifierConfig {
+            strict_mode: true,
+            ..VerifierConfig::default()
+        });
+        let mut result = verifier.verify(&stored_proof).await?;
+        if self.warn_on_revoked_signer.load(Ordering::Relaxed) {
+            if let Some(key) = stored_proof.metadata.signing_key() {
+                let revoked = self.count_revoked_by_key(key);
+                if revoked > 0 {
+                    log::warn!(
+                        "Proof {} is signed by key {} which has {} revoked proof(s)",
+                        stored_proof.id,
+                        key,
+                        revoked
+                    );
+                    result.details.push(format!(
+                        "Warning: signing key {} has {} revoked proof(s)",
+                        key, revoked
+                    ));
+                }
+            }
+        }
+        stored_proof.record_verification(&result);
+        self.insert(&stored_proof).await?;
+
+        self.verification_cache
+            .write()
+            .await
+            .insert(stored_proof.id.clone(), result.clone());
+        let mut stats = self.stats.write().await;
+        stats.total_verifications += 1;
+        if result.valid {
+            stats.successful_verifications += 1;
+        } else {
+            stats.failed_verifications += 1;
+        }
+
+        Ok((stored_proof, result))
+    }
+
+    /// Persist a new proof and count it in the stats
+    async fn insert(&self, proof: &StoredProof) -> Result<(), VerificationError> {
+        self.put(proof)?;
 
         // Update stats
         let mut stats = self.stats.write().await;
         stats.total_proofs += 1;
         *stats
             .proofs_by_type
-            .entry(request.proof_type.to_string())
+            .entry(proof.proof_type.to_string())
             .or_insert(0) += 1;
-        stats.total_size_bytes += proof_size;
+        stats.total_size_bytes += proof.size_bytes();
+        Ok(())
+    }
 
-        Ok(proof_id)
+    /// Write a proof to the backend
+    fn put(&self, proof: &StoredProof) -> Result<(), VerificationError> {
+        let serialized = serde_json::to_vec(proof)
+            .map_err(|e| VerificationError::InvalidProofData(e.to_string()))?;
+        self.backend
+            .put(&proof.id, &serialized)
+            .map_err(VerificationError::Storage)
     }
 
     /// Submit multiple proofs in batch
@@ -361,7 +534,7 @@ impl ProofStore {
         // Update proof's verified status in backend
         if let Ok(Some(bytes)) = self.backend.get(proof_id) {
             if let Ok(mut stored) = serde_json::from_slice::<StoredProof>(&bytes) {
-                stored.mark_verified(result.valid);
+                stored.record_verification(&result);
                 if let Ok(updated) = serde_json::to_vec(&stored) {
                     let _ = self.backend.put(proof_id, &updated);
                 }
@@ -398,6 +571,67 @@ impl ProofStore {
         results
     }
 
+    /// Revoke a proof, recording who revoked it, when and why
+    ///
+    /// Expired proofs can be revoked too; a proof can only be revoked once.
+    /// The proof is kept, so its history stays available.
+    pub async fn revoke(
+        &self,
+        proof_id: &ProofId,
+        reason: &str,
+        revoked_by: &str,
+    ) -> Result<StoredProof, VerificationError> {
+        let mut proof = self
+            .get(proof_id)
+            .await
+            .ok_or_else(|| VerificationError::ProofNotFound(proof_id.clone()))?;
+        if proof.is_revoked() {
+            return Err(VerificationError::AlreadyRevoked(proof_id.clone()));
+        }
+
+        proof.status = ProofStatus::Revoked {
+            reason: reason.to_string(),
+            revoked_by: revoked_by.to_string(),
+            revoked_at: Utc::now(),
+        };
+        self.put(&proof)?;
+        Ok(proof)
+    }
+
+    /// Record every valid proof past its expiry as expired
+    ///
+    /// Expired proofs are marked, not deleted. Returns the number of proofs
+    /// marked.
+    pub async fn sweep_expired(&self, now: DateTime<Utc>) -> usize {
+        let mut marked = 0;
+        for mut proof in self.list(None).await {
+            if proof.status != ProofStatus::Valid {
+                continue;
+            }
+            let status = proof.status_at(now);
+            if status == ProofStatus::Valid {
+                continue;
+            }
+            proof.status = status;
+            match self.put(&proof) {
+                Ok(()) => marked += 1,
+                Err(e) => log::warn!("Failed to mark proof {} as expired: {}", proof.id, e),
+            }
+        }
+        marked
+    }
+
+    /// Number of revoked proofs signed with `key`
+    fn count_revoked_by_key(&self, key: &str) -> usize {
+        let Ok(all) = self.backend.list_all() else {
+            return 0;
+        };
+        all.into_iter()
+            .filter_map(|(_, bytes)| serde_json::from_slice::<StoredProof>(&bytes).ok())
+            .filter(|p| p.is_revoked() && p.metadata.signing_key() == Some(key))
+            .count()
+    }
+
     /// Delete a proof
     pub async fn delete(&self, proof_id: &ProofId) -> bool {
         // Get proof data for stats update before deleting
@@ -523,6 +757,9 @@ impl ProofStore {
                 created_at,
                 verified: snap.verified,
                 verified_at,
+                verification_details: Vec::new(),
+                expires_at: None,
+                status: ProofStatus::Valid,
                 metadata,
             };
 
@@ -575,6 +812,7 @@ mod tests {
                 "response": vec![2u8; 32],
             }),
             metadata: None,
+            expires_at: None,
         };
 
         let proof_id = store.submit(request).await.unwrap();
@@ -602,6 +840,7 @@ mod tests {
                 },
                 proof_data: serde_json::json!({"index": i}),
                 metadata: None,
+                expires_at: None,
             };
             store.submit(request).await.unwrap();
         }
@@ -624,6 +863,7 @@ mod tests {
             proof_type: ProofType::Membership,
             proof_data: serde_json::json!({"test": "data"}),
             metadata: None,
+            expires_at: None,
         };
 
         let proof_id = store.submit(request).await.unwrap();
@@ -646,6 +886,7 @@ mod tests {
                 proof_type: ProofType::Knowledge,
                 proof_data: serde_json::json!({"iteration": i}),
                 metadata: None,
+                expires_at: None,
             };
             store.submit(request).await.unwrap();
         }
@@ -665,11 +906,13 @@ mod tests {
                 proof_type: ProofType::Schnorr,
                 proof_data: serde_json::json!({"id": 1}),
                 metadata: None,
+                expires_at: None,
             },
             SubmitProofRequest {
                 proof_type: ProofType::Equality,
                 proof_data: serde_json::json!({"id": 2}),
                 metadata: None,
+                expires_at: None,
             },
         ];
 
@@ -700,6 +943,177 @@ mod tests {
         assert!(proof.verified_at.is_none());
     }
 
+    fn signed_membership_request(key: &str) -> SubmitProofRequest {
+        let leaves: Vec<&[u8]> = vec![b"alice", b"bob", b"carol", b"dave"];
+        let tree = aingle_zk::MerkleTree::new(&leaves).unwrap();
+        let mut extra = HashMap::new();
+        extra.insert(SIGNING_KEY_FIELD.to_string(), serde_json::json!(key));
+        SubmitProofRequest {
+            proof_type: ProofType::Membership,
+            proof_data: serde_json::json!({
+                "root": tree.root(),
+                "proof": tree.prove(2).unwrap(),
+                "leaf": hex::encode(b"carol"),
+            }),
+            metadata: Some(ProofMetadata {
+                extra,
+                ..Default::default()
+            }),
+            expires_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_proof_expiry() {
+        let store = ProofStore::new();
+        let expires_at = Utc::now() + chrono::Duration::hours(1);
+        let id = store
+            .submit(SubmitProofRequest {
+                proof_type: ProofType::Knowledge,
+                proof_data: serde_json::json!({"expiring": true}),
+                metadata: None,
+                expires_at: Some(expires_at),
+            })
+            .await
+            .unwrap();
+        let never = store
+            .submit(SubmitProofRequest {
+                proof_type: ProofType::Knowledge,
+                proof_data: serde_json::json!({"expiring": false}),
+                metadata: None,
+                expires_at: None,
+            })
+            .await
+            .unwrap();
+
+        let proof = store.get(&id).await.unwrap();
+        assert_eq!(proof.current_status(), ProofStatus::Valid);
+        // Past its expiry the proof reads as expired before any sweep
+        let later = expires_at + chrono::Duration::seconds(1);
+        assert_eq!(
+            proof.status_at(later),
+            ProofStatus::Expired {
+                expired_at: expires_at
+            }
+        );
+        assert_eq!(proof.status, ProofStatus::Valid);
+
+        assert_eq!(store.sweep_expired(Utc::now()).await, 0);
+        assert_eq!(store.sweep_expired(later).await, 1);
+        let proof = store.get(&id).await.unwrap();
+        assert_eq!(
+            proof.status,
+            ProofStatus::Expired {
+                expired_at: expires_at
+            }
+        );
+        assert_eq!(store.get(&never).await.unwrap().status, ProofStatus::Valid);
+
+        // Marked, not deleted, and not marked twice
+        assert_eq!(store.count().await, 2);
+        assert_eq!(store.sweep_expired(later).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_proof_revocation() {
+        let store = ProofStore::new();
+        let id = store
+            .submit(SubmitProofRequest {
+                proof_type: ProofType::Schnorr,
+                proof_data: serde_json::json!({"key": "value"}),
+                metadata: None,
+                expires_at: Some(Utc::now() - chrono::Duration::minutes(1)),
+            })
+            .await
+            .unwrap();
+        assert!(matches!(
+            store.get(&id).await.unwrap().current_status(),
+            ProofStatus::Expired { .. }
+        ));
+
+        // Revocation takes precedence over expiry
+        let revoked = store
+            .revoke(&id, "key compromised", "operator")
+            .await
+            .unwrap();
+        match revoked.current_status() {
+            ProofStatus::Revoked {
+                reason, revoked_by, ..
+            } => {
+                assert_eq!(reason, "key compromised");
+                assert_eq!(revoked_by, "operator");
+            }
+            other => panic!("expected revoked, got {other:?}"),
+        }
+        assert_eq!(store.get(&id).await.unwrap().status, revoked.status);
+        assert_eq!(store.sweep_expired(Utc::now()).await, 0);
+
+        assert!(matches!(
+            store.revoke(&id, "again", "operator").await,
+            Err(VerificationError::AlreadyRevoked(_))
+        ));
+        assert!(matches!(
+            store
+                .revoke(&"missing".to_string(), "gone", "operator")
+                .await,
+            Err(VerificationError::ProofNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_warn_on_revoked_signer() {
+        let store = ProofStore::new();
+        let (first, _) = store
+            .validate(signed_membership_request("key-1"))
+            .await
+            .unwrap();
+        store
+            .revoke(&first.id, "key leaked", "operator")
+            .await
+            .unwrap();
+
+        // Off by default
+        let (_, result) = store
+            .validate(signed_membership_request("key-1"))
+            .await
+            .unwrap();
+        assert!(result.valid);
+        assert!(!result.details.iter().any(|d| d.starts_with("Warning")));
+
+        store.set_warn_on_revoked_signer(true);
+        let (proof, result) = store
+            .validate(signed_membership_request("key-1"))
+            .await
+            .unwrap();
+        assert!(result.valid);
+        assert!(result
+            .details
+            .contains(&"Warning: signing key key-1 has 1 revoked proof(s)".to_string()));
+        assert_eq!(proof.verification_details, result.details);
+
+        let (_, result) = store
+            .validate(signed_membership_request("key-2"))
+            .await
+            .unwrap();
+        assert!(!result.details.iter().any(|d| d.starts_with("Warning")));
+    }
+
+    #[tokio::test]
+    async fn test_lifecycle_fields_default_when_missing() {
+        let legacy = serde_json::json!({
+            "id": "legacy",
+            "proof_type": "schnorr",
+            "data": [1, 2, 3],
+            "created_at": Utc::now(),
+            "verified": true,
+            "verified_at": null,
+            "metadata": {"submitter": null, "tags": [], "extra": {}},
+        });
+        let proof: StoredProof = serde_json::from_value(legacy).unwrap();
+        assert!(proof.expires_at.is_none());
+        assert_eq!(proof.current_status(), ProofStatus::Valid);
+    }
+
     #[tokio::test]
     async fn test_sled_proof_store_persistence() {
         let dir = tempfile::TempDir::new().unwrap();
@@ -711,6 +1125,7 @@ mod tests {
                 proof_type: ProofType::Schnorr,
                 proof_data: serde_json::json!({"key": "value"}),
                 metadata: None,
+                expires_at: None,
             };
             proof_id = store.submit(request).await.unwrap();
             assert_eq!(store.count().await, 1);
@@ -737,6 +1152,7 @@ mod tests {
                 proof_type: ProofType::Membership,
                 proof_data: serde_json::json!({"proof": true}),
                 metadata: None,
+                expires_at: None,
             };
             let id = store.submit(request).await.unwrap();
             store.delete(&id).await;
@@ -42,6 +42,14 @@ pub enum VerificationError {
     /// ZK library error
     #[error("ZK error: {0}")]
     ZkError(String),
+
+    /// Proof storage backend error
+    #[error("Storage error: {0}")]
+    Storage(String),
+
+    /// Proof was already revoked
+    #[error("Proof already revoked: {0}")]
+    AlreadyRevoked(String),
 }
 
 /// Result of proof verification
@@ -89,6 +97,71 @@ impl VerificationResult {
     }
 }
 
+/// `proof_data` of a [`ProofType::Range`] proof
+///
+/// The JSON form of `aingle_zk::RangeProof`: a Bulletproof that the value
+/// behind `commitment` lies in `[0, 2^n_bits)`. A `blinding` field, if sent,
+/// is ignored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RangeProofData {
+    /// Compressed Pedersen commitment to the value
+    pub commitment: [u8; 32],
+    /// Serialized Bulletproof
+    pub proof_bytes: Vec<u8>,
+    /// Bits in the range: 8, 16, 32 or 64
+    pub n_bits: usize,
+}
+
+impl RangeProofData {
+    /// Parse and sanity-check stored proof data
+    pub fn parse(data: &[u8]) -> Result<Self, VerificationError> {
+        let parsed: Self = serde_json::from_slice(data)
+            .map_err(|e| VerificationError::InvalidProofData(format!("range proof: {e}")))?;
+        // Bulletproof generators are sized by n_bits, so it must be checked
+        // before any are built.
+        if !matches!(parsed.n_bits, 8 | 16 | 32 | 64) {
+            return Err(VerificationError::InvalidProofData(format!(
+                "range proof: n_bits must be 8, 16, 32 or 64, got {}",
+                parsed.n_bits
+            )));
+        }
+        Ok(parsed)
+    }
+}
+
+/// `proof_data` of a [`ProofType::Membership`] proof
+///
+/// `root` and `proof` are as in `aingle_zk::ProofData::Membership`, with the
+/// path from leaf to root in `proof.proof_nodes`. `leaf` is the hex-encoded
+/// leaf data; without it only the proof's shape can be checked, not that the
+/// leaf is a member.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MembershipProofData {
+    /// Root the leaf is claimed to be under
+    pub root: [u8; 32],
+    /// Merkle path from the leaf to `root`
+    pub proof: aingle_zk::MerkleProof,
+    /// Leaf data, hex-encoded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub leaf: Option<String>,
+}
+
+impl MembershipProofData {
+    /// Parse stored proof data; `None` if it is not in this form
+    fn parse(data: &[u8]) -> Option<Self> {
+        serde_json::from_slice(data).ok()
+    }
+
+    /// Decoded leaf data, if given
+    fn leaf_bytes(&self) -> Result<Option<Vec<u8>>, VerificationError> {
+        self.leaf
+            .as_deref()
+            .map(hex::decode)
+            .transpose()
+            .map_err(|e| VerificationError::InvalidProofData(format!("membership leaf: {e}")))
+    }
+}
+
 /// Reconstruct a `ZkProof` from a `StoredProof` whose `data` field contains
 /// only the raw `proof_data` JSON (without the ZkProof envelope).
 fn reconstruct_zk_proof(proof: &StoredProof) -> Result<aingle_zk::ZkProof, VerificationError> {
@@ -199,39 +272,86 @@ impl ProofVerifier {
             )));
         }
 
-        // Deserialize the proof data into aingle_zk::ZkProof.
-        // The stored data may be just the raw proof_data (without the ZkProof
-        // envelope) when submitted via the REST API, since submit() only
-        // persists request.proof_data. Try full envelope first, then
-        // reconstruct from StoredProof.proof_type + raw proof data.
-        let zk_proof: aingle_zk::ZkProof =
-            serde_json::from_slice(&proof.data).or_else(|_| reconstruct_zk_proof(proof))?;
-
-        // Verify based on proof type
-        let valid = match proof.proof_type {
-            ProofType::Schnorr => self.verify_schnorr(&zk_proof).await?,
-            ProofType::Equality => self.verify_equality(&zk_proof).await?,
-            ProofType::Membership => self.verify_membership(&zk_proof).await?,
-            ProofType::NonMembership => self.verify_non_membership(&zk_proof).await?,
-            ProofType::Range => self.verify_range(&zk_proof).await?,
-            ProofType::HashOpening => self.verify_hash_opening(&zk_proof).await?,
-            ProofType::Knowledge => self.verify_knowledge(&zk_proof).await?,
+        // Range proofs and membership proofs that carry their leaf have a
+        // typed form of their own and are checked in full.
+        let (valid, detail) = match proof.proof_type {
+            ProofType::Range => {
+                let range = RangeProofData::parse(&proof.data)?;
+                let detail = format!("Committed value is in [0, 2^{})", range.n_bits);
+                (self.verify_range(&range)?, Some(detail))
+            }
+            ProofType::Membership => match MembershipProofData::parse(&proof.data) {
+                Some(membership) if membership.leaf.is_some() => {
+                    let detail = format!(
+                        "Leaf {} is under root {}",
+                        membership.proof.leaf_index,
+                        hex::encode(membership.root)
+                    );
+                    (self.verify_membership_leaf(&membership)?, Some(detail))
+                }
+                _ => (self.verify_zk_proof(proof).await?, None),
+            },
+            _ => (self.verify_zk_proof(proof).await?, None),
         };
 
         let elapsed = start.elapsed();
         let verification_time_us = elapsed.as_micros() as u64;
 
-        if valid {
-            Ok(VerificationResult::success(
-                proof.proof_type.clone(),
-                verification_time_us,
-            ))
+        let result = if valid {
+            VerificationResult::success(proof.proof_type.clone(), verification_time_us)
         } else {
-            Ok(VerificationResult::failure(
+            VerificationResult::failure(
                 proof.proof_type.clone(),
                 "Proof verification returned false".to_string(),
                 verification_time_us,
-            ))
+            )
+        };
+        Ok(match detail {
+            Some(detail) if valid => result.with_detail(detail),
+            _ => result,
+        })
+    }
+
+    /// Verify a proof held as an `aingle_zk::ZkProof`
+    ///
+    /// In strict mode, proofs that can only be checked structurally without
+    /// their underlying data are refused rather than reported valid.
+    async fn verify_zk_proof(&self, proof: &StoredProof) -> Result<bool, VerificationError> {
+        if self.config.strict_mode {
+            match proof.proof_type {
+                ProofType::Membership | ProofType::NonMembership => {
+                    return Err(VerificationError::MissingData(
+                        "membership proofs need `root`, `proof` and the hex `leaf`".to_string(),
+                    ))
+                }
+                ProofType::HashOpening => {
+                    return Err(VerificationError::MissingData(
+                        "hash openings can only be checked against the committed data".to_string(),
+                    ))
+                }
+                _ => {}
+            }
+        }
+
+        // Deserialize the proof data into aingle_zk::ZkProof.
+        // The stored data may be just the raw proof_data (without the ZkProof
+        // envelope) when submitted via the REST API, since submit() only
+        // persists request.proof_data. Try full envelope first, then
+        // reconstruct from StoredProof.proof_type + raw proof data.
+        let zk_proof: aingle_zk::ZkProof =
+            serde_json::from_slice(&proof.data).or_else(|_| reconstruct_zk_proof(proof))?;
+
+        // Verify based on proof type
+        match proof.proof_type {
+            ProofType::Schnorr => self.verify_schnorr(&zk_proof).await,
+            ProofType::Equality => self.verify_equality(&zk_proof).await,
+            ProofType::Membership => self.verify_membership(&zk_proof).await,
+            ProofType::NonMembership => self.verify_non_membership(&zk_proof).await,
+            ProofType::HashOpening => self.verify_hash_opening(&zk_proof).await,
+            ProofType::Knowledge => self.verify_knowledge(&zk_proof).await,
+            ProofType::Range => Err(VerificationError::InvalidProofData(
+                "range proofs are not stored as ZkProof".to_string(),
+            )),
         }
     }
 
@@ -286,12 +406,38 @@ impl ProofVerifier {
             .map_err(|e| VerificationError::ZkError(e.to_string()))
     }
 
-    async fn verify_range(&self, zk_proof: &aingle_zk::ZkProof) -> Result<bool, VerificationError> {
-        // Range proofs (bulletproofs) verification
-        aingle_zk::ProofVerifier::verify(zk_proof)
+    #[cfg(feature = "bulletproofs")]
+    fn verify_range(&self, range: &RangeProofData) -> Result<bool, VerificationError> {
+        let proof = aingle_zk::RangeProof {
+            proof_bytes: range.proof_bytes.clone(),
+            commitment: range.commitment,
+            n_bits: range.n_bits,
+            blinding: [0u8; 32],
+        };
+        aingle_zk::RangeProofGenerator::new(range.n_bits)
+            .verify(&proof)
             .map_err(|e| VerificationError::ZkError(e.to_string()))
     }
 
+    #[cfg(not(feature = "bulletproofs"))]
+    fn verify_range(&self, _range: &RangeProofData) -> Result<bool, VerificationError> {
+        Err(VerificationError::UnsupportedProofType(
+            "range proofs need the `bulletproofs` feature".to_string(),
+        ))
+    }
+
+    fn verify_membership_leaf(
+        &self,
+        membership: &MembershipProofData,
+    ) -> Result<bool, VerificationError> {
+        let Some(leaf) = membership.leaf_bytes()? else {
+            return Err(VerificationError::MissingData(
+                "membership leaf".to_string(),
+            ));
+        };
+        Ok(membership.proof.root == membership.root && membership.proof.verify(&leaf))
+    }
+
     async fn verify_hash_opening(
         &self,
         zk_proof: &aingle_zk::ZkProof,
@@ -4,18 +4,25 @@
 //! Audit log for tracking API actions
 //!
 //! Provides an append-only, file-backed audit log with REST endpoints
-//! for querying and aggregating audit entries.
+//! for querying and aggregating audit entries. The file is rotated once it
+//! reaches a configured size; see [`AuditRotation`]. Handlers record the
+//! actions they take, and the [request audit
+//! middleware](crate::middleware::audit) records the requests themselves.
 
 use axum::{
     extract::{Query, State},
     routing::get,
     Json, Router,
 };
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::io::{BufRead, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use crate::error::{Error, Result};
+use crate::middleware::RequestPrincipal;
+use crate::rest::ValueDto;
 use crate::state::AppState;
 
 /// A single audit log entry.
@@ -37,6 +44,72 @@ pub struct AuditEntry {
     /// Unique request ID for correlation
     #[serde(skip_serializing_if = "Option::is_none")]
     pub request_id: Option<String>,
+    /// The HTTP request, for entries recorded by the request audit middleware
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request: Option<RequestAudit>,
+}
+
+/// An audited HTTP request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestAudit {
+    /// HTTP method
+    pub method: String,
+    /// Dataset the request was served from
+    pub dataset: String,
+    /// Response status code
+    pub status: u16,
+    /// Time to produce the response, in microseconds
+    pub latency_us: u64,
+    /// Redacted summary of the request body, for writes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<RequestSummary>,
+}
+
+/// What a request body contained, without the body itself
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RequestSummary {
+    /// Size of the body in bytes
+    pub bytes: usize,
+    /// Number of triples in the body
+    pub triple_count: usize,
+    /// The first triples of the body, at most
+    /// [`MAX_SUMMARY_TRIPLES`](crate::middleware::audit::MAX_SUMMARY_TRIPLES)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub triples: Vec<TripleSummary>,
+}
+
+/// A triple of a request body, as recorded in the audit log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TripleSummary {
+    /// Subject
+    pub subject: String,
+    /// Predicate
+    pub predicate: String,
+    /// Object, `None` if it is a literal of a sensitive predicate
+    pub object: Option<ValueDto>,
+}
+
+/// When a file-backed audit log starts a new file
+///
+/// The full file is renamed to `<file>.<n>`, numbering rotated files from 1,
+/// oldest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuditRotation {
+    /// Size in bytes at which the file is rotated
+    pub max_bytes: u64,
+    /// Number of rotated files kept, deleting the oldest first.
+    /// `None` keeps every rotated file.
+    pub max_files: Option<usize>,
+}
+
+impl Default for AuditRotation {
+    /// Rotates at 64 MiB and keeps every rotated file.
+    fn default() -> Self {
+        Self {
+            max_bytes: 64 * 1024 * 1024,
+            max_files: None,
+        }
+    }
 }
 
 /// Audit log with optional JSONL file backing.
@@ -45,6 +118,8 @@ pub struct AuditLog {
     max_entries: usize,
     /// Optional file path for JSONL persistence.
     log_path: Option<PathBuf>,
+    /// When the JSONL file is rotated; `None` lets it grow.
+    rotation: Option<AuditRotation>,
 }
 
 impl AuditLog {
@@ -53,36 +128,60 @@ impl AuditLog {
             entries: Vec::new(),
             max_entries,
             log_path: None,
+            rotation: None,
         }
     }
 
     /// Create a file-backed audit log. Reads existing entries from the JSONL file on disk.
+    ///
+    /// Entries of the most recently rotated file are read first, so a
+    /// rotation just before a restart does not empty the log.
     pub fn with_path(max_entries: usize, path: PathBuf) -> Self {
         let mut entries = Vec::new();
 
         // Read existing entries from JSONL file
-        if let Ok(file) = std::fs::File::open(&path) {
-            let reader = std::io::BufReader::new(file);
-            for line in reader.lines() {
-                if let Ok(line) = line {
+        let newest_rotated = rotated_files(&path).pop().map(|(_, rotated)| rotated);
+        for file in newest_rotated.iter().chain([&path]) {
+            if let Ok(opened) = std::fs::File::open(file) {
+                let reader = std::io::BufReader::new(opened);
+                for line in reader.lines() {
+                    let line = match line {
+                        Ok(line) => line,
+                        Err(e) => {
+                            log::warn!("Audit log read failed ({}): {e}", file.display());
+                            break;
+                        }
+                    };
                     if let Ok(entry) = serde_json::from_str::<AuditEntry>(&line) {
                         entries.push(entry);
                     }
                 }
             }
-            // Keep only the last max_entries
-            if entries.len() > max_entries {
-                entries = entries.split_off(entries.len() - max_entries);
-            }
+        }
+        // Keep only the last max_entries
+        if entries.len() > max_entries {
+            entries = entries.split_off(entries.len() - max_entries);
         }
 
         Self {
             entries,
             max_entries,
             log_path: Some(path),
+            rotation: None,
         }
     }
 
+    /// Rotate the JSONL file as `rotation` says; `None` lets it grow.
+    pub fn set_rotation(&mut self, rotation: Option<AuditRotation>) {
+        self.rotation = rotation;
+    }
+
+    /// Builder form of [`set_rotation`](Self::set_rotation).
+    pub fn with_rotation(mut self, rotation: AuditRotation) -> Self {
+        self.rotation = Some(rotation);
+        self
+    }
+
     /// Record a new audit entry.
     pub fn record(&mut self, entry: AuditEntry) {
         // Append to file if file-backed
@@ -100,6 +199,14 @@ impl AuditLog {
                             } else if let Err(e) = file.sync_all() {
                                 log::warn!("Audit log fsync failed: {e}");
                             }
+                            let full = self.rotation.is_some_and(|rotation| {
+                                file.metadata()
+                                    .is_ok_and(|meta| meta.len() >= rotation.max_bytes)
+                            });
+                            if full {
+                                drop(file);
+                                self.rotate(path);
+                            }
                         }
                         Err(e) => {
                             log::error!("Audit log open failed ({}): {e}", path.display());
@@ -133,38 +240,21 @@ impl AuditLog {
         self.entries
             .iter()
             .rev() // newest first
-            .filter(|e| {
-                if let Some(uid) = user_id {
-                    if e.user_id != uid {
-                        return false;
-                    }
-                }
-                if let Some(ns) = namespace {
-                    if e.namespace.as_deref() != Some(ns) {
-                        return false;
-                    }
-                }
-                if let Some(act) = action {
-                    if e.action != act {
-                        return false;
-                    }
-                }
-                if let Some(f) = from {
-                    if e.timestamp.as_str() < f {
-                        return false;
-                    }
-                }
-                if let Some(t) = to {
-                    if e.timestamp.as_str() > t {
-                        return false;
-                    }
-                }
-                true
-            })
+            .filter(|e| passes_filters(e, user_id, namespace, action, from, to))
             .take(limit)
             .collect()
     }
 
+    /// Entries recorded after `since`, oldest first.
+    ///
+    /// A client pages forward by passing the timestamp of the last entry it
+    /// received. Entries with unparsable timestamps are skipped.
+    pub fn since(&self, since: DateTime<Utc>) -> impl Iterator<Item = &AuditEntry> {
+        self.entries.iter().filter(move |e| {
+            DateTime::parse_from_rfc3339(&e.timestamp).is_ok_and(|t| t.with_timezone(&Utc) > since)
+        })
+    }
+
     /// Get aggregate stats.
     pub fn stats(&self) -> AuditStats {
         let mut actions: HashMap<String, usize> = HashMap::new();
@@ -190,6 +280,98 @@ impl AuditLog {
     pub fn len(&self) -> usize {
         self.entries.len()
     }
+
+    /// Rename the full JSONL file to the next rotated name, deleting the
+    /// oldest rotated files beyond the limit.
+    fn rotate(&self, path: &Path) {
+        let mut rotated = rotated_files(path);
+        let next = rotated.last().map_or(1, |(n, _)| n + 1);
+        let target = rotated_path(path, next);
+        if let Err(e) = std::fs::rename(path, &target) {
+            log::error!("Audit log rotation failed ({}): {e}", path.display());
+            return;
+        }
+        rotated.push((next, target));
+
+        let Some(max_files) = self.rotation.and_then(|r| r.max_files) else {
+            return;
+        };
+        let excess = rotated.len().saturating_sub(max_files);
+        for (_, old) in rotated.drain(..excess) {
+            if let Err(e) = std::fs::remove_file(&old) {
+                log::warn!("Removing rotated audit log {} failed: {e}", old.display());
+            }
+        }
+    }
+}
+
+/// Whether `e` passes the filters of [`AuditLog::query`]
+fn passes_filters(
+    e: &AuditEntry,
+    user_id: Option<&str>,
+    namespace: Option<&str>,
+    action: Option<&str>,
+    from: Option<&str>,
+    to: Option<&str>,
+) -> bool {
+    if let Some(uid) = user_id {
+        if e.user_id != uid {
+            return false;
+        }
+    }
+    if let Some(ns) = namespace {
+        if e.namespace.as_deref() != Some(ns) {
+            return false;
+        }
+    }
+    if let Some(act) = action {
+        if e.action != act {
+            return false;
+        }
+    }
+    if let Some(f) = from {
+        if e.timestamp.as_str() < f {
+            return false;
+        }
+    }
+    if let Some(t) = to {
+        if e.timestamp.as_str() > t {
+            return false;
+        }
+    }
+    true
+}
+
+/// Path of the `n`th rotated file of `path`
+fn rotated_path(path: &Path, n: u64) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{n}"));
+    path.with_file_name(name)
+}
+
+/// The rotated files of `path` with their numbers, oldest first
+fn rotated_files(path: &Path) -> Vec<(u64, PathBuf)> {
+    let (Some(dir), Some(name)) = (path.parent(), path.file_name()) else {
+        return Vec::new();
+    };
+    let dir = if dir.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        dir
+    };
+    let prefix = format!("{}.", name.to_string_lossy());
+    let mut rotated: Vec<(u64, PathBuf)> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name();
+            let n = file_name.to_str()?.strip_prefix(&prefix)?.parse().ok()?;
+            Some((n, entry.path()))
+        })
+        .collect();
+    rotated.sort_unstable_by_key(|(n, _)| *n);
+    rotated
 }
 
 impl Default for AuditLog {
@@ -214,6 +396,8 @@ pub struct AuditStats {
 /// Query parameters for the audit endpoint.
 #[derive(Debug, Deserialize)]
 pub struct AuditQueryParams {
+    /// Only entries recorded after this RFC 3339 timestamp, oldest first
+    pub since: Option<String>,
     pub user_id: Option<String>,
     pub namespace: Option<String>,
     pub action: Option<String>,
@@ -227,27 +411,75 @@ fn default_limit() -> usize {
     100
 }
 
+/// Read the audit log (admin only)
+///
 /// GET /api/v1/audit
+///
+/// Newest entries first, or with `since`, the entries after it oldest first:
+/// pass the timestamp of the last entry of a page to get the next one.
 pub async fn get_audit_log(
     State(state): State<AppState>,
+    principal_ext: Option<axum::Extension<RequestPrincipal>>,
     Query(params): Query<AuditQueryParams>,
-) -> Json<Vec<AuditEntry>> {
+) -> Result<Json<Vec<AuditEntry>>> {
+    if !RequestPrincipal::from_extension(principal_ext).is_admin() {
+        return Err(Error::Forbidden(
+            "Reading the audit log requires the admin role".to_string(),
+        ));
+    }
+
     let log = state.audit_log.read().await;
-    let entries = log.query(
-        params.user_id.as_deref(),
-        params.namespace.as_deref(),
-        params.action.as_deref(),
-        params.from.as_deref(),
-        params.to.as_deref(),
-        params.limit,
-    );
-    Json(entries.into_iter().cloned().collect())
+    let filters = |e: &&AuditEntry| {
+        passes_filters(
+            e,
+            params.user_id.as_deref(),
+            params.namespace.as_deref(),
+            params.action.as_deref(),
+            params.from.as_deref(),
+            params.to.as_deref(),
+        )
+    };
+    let entries: Vec<AuditEntry> = match params.since.as_deref() {
+        Some(since) => {
+            let since = DateTime::parse_from_rfc3339(since)
+                .map_err(|e| Error::InvalidInput(format!("Invalid since timestamp: {e}")))?;
+            log.since(since.with_timezone(&Utc))
+                .filter(filters)
+                .take(params.limit)
+                .cloned()
+                .collect()
+        }
+        None => log
+            .query(
+                params.user_id.as_deref(),
+                params.namespace.as_deref(),
+                params.action.as_deref(),
+                params.from.as_deref(),
+                params.to.as_deref(),
+                params.limit,
+            )
+            .into_iter()
+            .cloned()
+            .collect(),
+    };
+    Ok(Json(entries))
 }
 
+/// Aggregate the audit log (admin only)
+///
 /// GET /api/v1/audit/stats
-pub async fn get_audit_stats(State(state): State<AppState>) -> Json<AuditStats> {
+pub async fn get_audit_stats(
+    State(state): State<AppState>,
+    principal_ext: Option<axum::Extension<RequestPrincipal>>,
+) -> Result<Json<AuditStats>> {
+    if !RequestPrincipal::from_extension(principal_ext).is_admin() {
+        return Err(Error::Forbidden(
+            "Reading the audit log requires the admin role".to_string(),
+        ));
+    }
+
     let log = state.audit_log.read().await;
-    Json(log.stats())
+    Ok(Json(log.stats()))
 }
 
 /// Create the audit router.
@@ -270,6 +502,7 @@ mod tests {
             resource: "/api/v1/triples".to_string(),
             details: None,
             request_id: None,
+            request: None,
         }
     }
 
@@ -335,6 +568,54 @@ mod tests {
         let _ = std::fs::remove_dir(&dir);
     }
 
+    #[test]
+    fn test_audit_log_rotates_by_size() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        // Fixed timestamps, so every line has the same size
+        let entry = || AuditEntry {
+            timestamp: "2026-03-16T00:00:00+00:00".to_string(),
+            ..make_entry("create", "user1", None)
+        };
+        let entry_bytes = serde_json::to_string(&entry()).unwrap().len() as u64;
+        let rotation = AuditRotation {
+            max_bytes: entry_bytes * 2,
+            max_files: Some(2),
+        };
+
+        let mut log = AuditLog::with_path(100, path.clone()).with_rotation(rotation);
+        for _ in 0..7 {
+            log.record(entry());
+        }
+
+        // Rotated every two entries, keeping the two newest rotated files
+        let rotated: Vec<u64> = rotated_files(&path).iter().map(|(n, _)| *n).collect();
+        assert_eq!(rotated, [2, 3]);
+        let lines = |p: &Path| std::fs::read_to_string(p).unwrap().lines().count();
+        assert_eq!(lines(&path), 1);
+        assert_eq!(lines(&rotated_path(&path, 3)), 2);
+
+        // A restart reads the newest rotated file and the current one
+        let log = AuditLog::with_path(100, path.clone());
+        assert_eq!(log.len(), 3);
+    }
+
+    #[test]
+    fn test_since_pages_oldest_first() {
+        let mut log = AuditLog::new(100);
+        for minute in 0..5 {
+            let mut entry = make_entry(&format!("action-{}", minute), "user1", None);
+            entry.timestamp = format!("2026-03-16T00:{:02}:00+00:00", minute);
+            log.record(entry);
+        }
+
+        let since = DateTime::parse_from_rfc3339("2026-03-16T00:01:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let actions: Vec<&str> = log.since(since).map(|e| e.action.as_str()).collect();
+        assert_eq!(actions, ["action-2", "action-3", "action-4"]);
+    }
+
     #[test]
     fn test_audit_stats() {
         let mut log = AuditLog::new(100);
@@ -0,0 +1,128 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Dataset administration endpoints
+//!
+//! Lists and creates the named datasets served under
+//! `/api/v1/datasets/{dataset}/...`; see [`crate::datasets`].
+
+use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
+use serde::{Deserialize, Serialize};
+
+use crate::datasets::{DatasetInfo, DEFAULT_DATASET};
+use crate::error::{Error, Result};
+use crate::middleware::{RequestNamespace, RequestPrincipal};
+use crate::rest::audit::AuditEntry;
+use crate::state::AppState;
+
+/// Create the dataset administration router
+pub fn datasets_router() -> Router<AppState> {
+    Router::new().route("/api/v1/datasets", get(list_datasets).post(create_dataset))
+}
+
+/// Response for listing datasets
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Serialize)]
+pub struct ListDatasetsResponse {
+    /// The default dataset first, then the named ones by name
+    pub datasets: Vec<DatasetInfo>,
+}
+
+/// Request to create a dataset
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Deserialize)]
+pub struct CreateDatasetRequest {
+    /// Lowercase letters, digits, `-` and `_`
+    pub name: String,
+}
+
+/// List the datasets (admin only)
+///
+/// GET /api/v1/datasets
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/v1/datasets",
+        tag = "datasets",
+        responses(
+            (status = 200, description = "The datasets served", body = ListDatasetsResponse),
+            (status = 403, description = "The caller lacks the admin role", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn list_datasets(
+    State(state): State<AppState>,
+    principal_ext: Option<axum::Extension<RequestPrincipal>>,
+) -> Result<Json<ListDatasetsResponse>> {
+    if !RequestPrincipal::from_extension(principal_ext).is_admin() {
+        return Err(Error::Forbidden(
+            "Listing datasets requires the admin role".to_string(),
+        ));
+    }
+
+    let mut datasets = vec![DatasetInfo {
+        name: DEFAULT_DATASET.to_string(),
+        db_path: None,
+        open: true,
+    }];
+    datasets.extend(state.datasets.list());
+    Ok(Json(ListDatasetsResponse { datasets }))
+}
+
+/// Create a dataset (admin only)
+///
+/// POST /api/v1/datasets
+///
+/// The dataset is stored under the server's datasets directory and opened on
+/// first use.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/v1/datasets",
+        tag = "datasets",
+        request_body = CreateDatasetRequest,
+        responses(
+            (status = 201, description = "The dataset was created", body = DatasetInfo),
+            (status = 400, description = "Invalid or reserved name", body = ErrorResponse),
+            (status = 403, description = "The caller lacks the admin role", body = ErrorResponse),
+            (status = 409, description = "A dataset with this name exists", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn create_dataset(
+    State(state): State<AppState>,
+    ns_ext: Option<axum::Extension<RequestNamespace>>,
+    principal_ext: Option<axum::Extension<RequestPrincipal>>,
+    Json(req): Json<CreateDatasetRequest>,
+) -> Result<(StatusCode, Json<DatasetInfo>)> {
+    let principal = RequestPrincipal::from_extension(principal_ext);
+    if !principal.is_admin() {
+        return Err(Error::Forbidden(
+            "Creating datasets requires the admin role".to_string(),
+        ));
+    }
+
+    let info = state.datasets.create(&req.name)?;
+
+    {
+        let namespace = ns_ext.and_then(|axum::Extension(RequestNamespace(ns))| ns);
+        let mut audit = state.audit_log.write().await;
+        audit.record(AuditEntry {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            user_id: principal
+                .user_id
+                .or_else(|| namespace.clone())
+                .unwrap_or_else(|| "anonymous".to_string()),
+            namespace,
+            action: "create".to_string(),
+            resource: format!("/api/v1/datasets/{}", info.name),
+            details: info.db_path.clone(),
+            request_id: None,
+            request: None,
+        });
+    }
+
+    Ok((StatusCode::CREATED, Json(info)))
+}
@@ -178,7 +178,7 @@ pub async fn remember(
         entry = entry.with_embedding(ineru::Embedding::new(emb));
     }
 
-    let mut memory = state.memory.write().await;
+    let memory = state.memory.write().await;
     let id = memory
         .remember(entry)
         .map_err(|e| Error::Internal(format!("Memory store failed: {e}")))?;
@@ -280,7 +280,7 @@ pub async fn consolidate(State(state): State<AppState>) -> Result<Json<Consolida
     }
 
     // Non-cluster mode: direct consolidation
-    let mut memory = state.memory.write().await;
+    let memory = state.memory.write().await;
     let count = memory
         .consolidate()
         .map_err(|e| Error::Internal(format!("Consolidation failed: {e}")))?;
@@ -352,7 +352,7 @@ pub async fn forget(State(state): State<AppState>, Path(id): Path<String>) -> Re
     let memory_id = MemoryId::from_hex(&id)
         .ok_or_else(|| Error::InvalidInput(format!("Invalid memory ID: {id}")))?;
 
-    let mut memory = state.memory.write().await;
+    let memory = state.memory.write().await;
     memory
         .forget(&memory_id)
         .map_err(|e| Error::NotFound(format!("Memory not found: {e}")))?;
@@ -399,6 +399,7 @@ pub async fn checkpoint(
             tags: vec!["checkpoint".to_string(), "memory".to_string()],
             extra: Default::default(),
         }),
+        expires_at: None,
     };
 
     let proof_id = state
@@ -502,9 +503,8 @@ pub async fn vector_search(
     Json(req): Json<VectorSearchRequest>,
 ) -> Result<Json<Vec<MemoryResultDto>>> {
     let memory = state.memory.read().await;
-    let results = memory
-        .ltm
-        .vector_search_memories(&req.embedding, req.k, req.min_similarity);
+    let ltm = memory.ltm();
+    let results = ltm.vector_search_memories(&req.embedding, req.k, req.min_similarity);
 
     let mut dtos: Vec<MemoryResultDto> = results
         .into_iter()
@@ -540,17 +540,18 @@ pub async fn vector_index_stats(
     State(state): State<AppState>,
 ) -> Result<Json<VectorIndexStatsDto>> {
     let memory = state.memory.read().await;
-    let stats = memory
-        .ltm
-        .hnsw_index()
-        .map(|idx| idx.stats())
-        .unwrap_or(ineru::hnsw::HnswStats {
-            point_count: 0,
-            deleted_count: 0,
-            dimensions: 0,
-            max_layer: 0,
-            memory_bytes: 0,
-        });
+    let stats =
+        memory
+            .ltm()
+            .hnsw_index()
+            .map(|idx| idx.stats())
+            .unwrap_or(ineru::hnsw::HnswStats {
+                point_count: 0,
+                deleted_count: 0,
+                dimensions: 0,
+                max_layer: 0,
+                memory_bytes: 0,
+            });
 
     Ok(Json(VectorIndexStatsDto {
         point_count: stats.point_count,
@@ -562,8 +563,8 @@ pub async fn vector_index_stats(
 
 /// Force rebuild of the HNSW vector index.
 pub async fn rebuild_vector_index(State(state): State<AppState>) -> Result<StatusCode> {
-    let mut memory = state.memory.write().await;
-    if let Some(hnsw) = memory.ltm.hnsw_index_mut() {
+    let memory = state.memory.write().await;
+    if let Some(hnsw) = memory.ltm_mut().hnsw_index_mut() {
         hnsw.rebuild();
         tracing::info!("HNSW index rebuilt, {} active points", hnsw.len());
     }
@@ -7,25 +7,50 @@
 //!
 //! ### Triples
 //! - `POST   /api/v1/triples` - Create triple
-//! - `POST   /api/v1/triples/batch` - Batch insert triples (atomic)
+//! - `POST   /api/v1/triples/batch` - Batch insert triples (JSON or NDJSON, per-item report)
 //! - `GET    /api/v1/triples/:id` - Get triple by hash
-//! - `DELETE /api/v1/triples/:id` - Delete triple
-//! - `GET    /api/v1/triples` - List triples (with filters)
+//! - `DELETE /api/v1/triples/:id` - Delete triple (soft delete, optional `?reason=`)
+//! - `GET    /api/v1/triples/:id/history` - Creation/deletion history of a triple
+//! - `GET    /api/v1/triples` - List triples (with filters; `?include_deleted=true` for admins)
+//! - `POST   /api/v1/triples/purge` - Drop tombstones past retention (admin)
 //!
 //! ### Queries
 //! - `POST   /api/v1/query` - Pattern matching query
 //! - `GET    /api/v1/graph/stats` - Graph statistics
 //!
+//! ### Datasets
+//! - `GET    /api/v1/datasets` - List datasets (admin)
+//! - `POST   /api/v1/datasets` - Create a dataset (admin)
+//! - `/api/v1/datasets/:name/...` - The triple, query and stats endpoints
+//!   above, against the named dataset instead of the default one
+//!
+//! ### Audit
+//! - `GET    /api/v1/audit` - Audit entries, newest first, or oldest first after `?since=` (admin)
+//! - `GET    /api/v1/audit/stats` - Audit entry counts by action, user and namespace (admin)
+//!
+//! ### Events
+//! - `GET    /api/v1/subscribe` - Stream events over a WebSocket (optional `?after_seq=`)
+//! - `GET    /api/v1/subscribe/poll` - Long poll for events after `?after_seq=`
+//!
 //! ### Validation
 //! - `POST   /api/v1/validate` - Validate triple(s)
 //! - `GET    /api/v1/proof/:hash` - Get proof
 //! - `POST   /api/v1/verify` - Verify proof
 //!
+//! ### ZK Proofs
+//! - `POST   /api/v1/proofs/validate` - Verify a range or membership proof and store the outcome
+//! - `GET    /api/v1/proofs/:id/status` - Valid, expired or revoked, with the validation record
+//! - `POST   /api/v1/proofs/:id/revoke` - Revoke a proof (admin)
+//!
 //! ### Skill Verification (Phase 3)
 //! - `POST   /api/v1/skills/validate` - Validate semantic skill manifest
 //! - `POST   /api/v1/skills/sandbox` - Create temporary sandbox namespace
 //! - `DELETE /api/v1/skills/sandbox/:id` - Clean up sandbox namespace
 //!
+//! ### API description (feature `openapi`)
+//! - `GET    /api/openapi.json` - OpenAPI 3 spec of these endpoints
+//! - `GET    /api/docs` - Swagger UI (only when `CortexConfig::swagger_ui` is set)
+//!
 //! ### Reputation (Phase 3)
 //! - `GET    /api/v1/agents/:id/consistency` - Agent assertion consistency score
 //! - `POST   /api/v1/assertions/verify-batch` - Batch verify assertion proofs
@@ -37,8 +62,11 @@ pub mod cluster;
 pub(crate) mod cluster_utils;
 #[cfg(feature = "dag")]
 pub mod dag;
+pub mod datasets;
 mod memory;
 mod observability;
+#[cfg(feature = "openapi")]
+pub mod openapi;
 #[cfg(feature = "p2p")]
 mod p2p;
 mod proof;
@@ -49,6 +77,7 @@ pub mod raft_rpc;
 mod reputation;
 pub mod skill_verification;
 mod stats;
+pub mod subscribe;
 mod triples;
 
 // Re-export from proof (legacy validation endpoints)
@@ -69,7 +98,8 @@ pub use reputation::{
 pub use proof_api::{
     BatchSubmitRequest, BatchSubmitResponse, BatchVerifyRequest, BatchVerifyResponse,
     DeleteProofResponse, GetProofRequest, ListProofsQuery, ListProofsResponse, ProofResponse,
-    ProofStatsResponse, SubmitProofResponse, VerifyProofByIdRequest, VerifyProofResponse,
+    ProofStatsResponse, ProofStatusResponse, RevokeProofRequest, SubmitProofResponse,
+    ValidateProofResponse, VerifyProofByIdRequest, VerifyProofResponse,
 };
 
 // Re-export from other modules
@@ -90,21 +120,34 @@ use axum::{
     Router,
 };
 
+/// Routes served once per dataset, relative to `/api/v1` for the default
+/// dataset and to `/api/v1/datasets/{dataset}` for the named ones
+fn dataset_routes() -> Router<AppState> {
+    Router::new()
+        // Triple CRUD
+        .route("/triples", post(triples::create_triple))
+        .route("/triples", get(triples::list_triples))
+        .route("/triples/batch", post(triples::batch_insert_triples))
+        .route("/triples/{id}", get(triples::get_triple))
+        .route("/triples/{id}", delete(triples::delete_triple))
+        .route("/triples/{id}/history", get(triples::get_triple_history))
+        .route("/triples/purge", post(triples::purge_tombstones))
+        // Query endpoints
+        .route("/query", post(query::query_pattern))
+        .route("/query/subjects", get(query::list_subjects))
+        .route("/query/predicates", get(query::list_predicates))
+        // Stats
+        .route("/stats", get(stats::get_stats))
+}
+
 /// Create REST API router
 pub fn router() -> Router<AppState> {
     let router = Router::new()
-        // Triple CRUD
-        .route("/api/v1/triples", post(triples::create_triple))
-        .route("/api/v1/triples", get(triples::list_triples))
-        .route("/api/v1/triples/batch", post(triples::batch_insert_triples))
-        .route("/api/v1/triples/{id}", get(triples::get_triple))
-        .route("/api/v1/triples/{id}", delete(triples::delete_triple))
-        // Query endpoints
-        .route("/api/v1/query", post(query::query_pattern))
-        .route("/api/v1/query/subjects", get(query::list_subjects))
-        .route("/api/v1/query/predicates", get(query::list_predicates))
-        // Stats & Management
-        .route("/api/v1/stats", get(stats::get_stats))
+        // Triples, queries and stats of the default and the named datasets
+        .nest("/api/v1", dataset_routes())
+        .nest("/api/v1/datasets/{dataset}", dataset_routes())
+        .merge(datasets::datasets_router())
+        // Management
         .route("/api/v1/health", get(stats::health_check))
         .route("/api/v1/flush", post(stats::flush_data))
         // Validation/Proofs (legacy)
@@ -116,6 +159,7 @@ pub fn router() -> Router<AppState> {
         .route("/api/v1/proofs", get(proof_api::list_proofs))
         .route("/api/v1/proofs/batch", post(proof_api::submit_proofs_batch))
         .route("/api/v1/proofs/stats", get(proof_api::get_proof_stats))
+        .route("/api/v1/proofs/validate", post(proof_api::validate_proof))
         .route(
             "/api/v1/proofs/verify/batch",
             post(proof_api::verify_proofs_batch),
@@ -126,6 +170,11 @@ pub fn router() -> Router<AppState> {
             "/api/v1/proofs/{id}/verify",
             get(proof_api::verify_proof_by_id),
         )
+        .route(
+            "/api/v1/proofs/{id}/status",
+            get(proof_api::get_proof_status),
+        )
+        .route("/api/v1/proofs/{id}/revoke", post(proof_api::revoke_proof))
         // Ineru memory endpoints
         .merge(memory::memory_router())
         // Semantic Observability endpoints
@@ -135,7 +184,9 @@ pub fn router() -> Router<AppState> {
         // Reputation endpoints (Phase 3)
         .merge(reputation::reputation_router())
         // Audit log endpoints (Phase 6.5)
-        .merge(audit::audit_router());
+        .merge(audit::audit_router())
+        // Event subscriptions (WebSocket and long poll)
+        .merge(subscribe::subscribe_router());
 
     // P2P endpoints (feature-gated)
     #[cfg(feature = "p2p")]
@@ -151,5 +202,9 @@ pub fn router() -> Router<AppState> {
     #[cfg(feature = "dag")]
     let router = router.merge(dag::dag_router());
 
+    // OpenAPI spec (feature-gated)
+    #[cfg(feature = "openapi")]
+    let router = router.merge(openapi::router());
+
     router
 }
@@ -0,0 +1,297 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! OpenAPI 3 description of the REST API
+//!
+//! The spec is generated from the `utoipa` annotations on the handlers and
+//! DTOs and served at `GET /api/openapi.json`. When
+//! [`CortexConfig::swagger_ui`](crate::server::CortexConfig::swagger_ui) is
+//! set, a Swagger UI page rendering it is served at `GET /api/docs`.
+//!
+//! Every operation documents its error responses with the
+//! [`ErrorResponse`](crate::error::ErrorResponse) body, and a shared `429`
+//! response with the rate limiter's body and `Retry-After` header.
+//!
+//! Request bodies rejected by the JSON extractor (malformed JSON, missing
+//! fields) are answered by `axum` before the handler runs, with a plain-text
+//! body, and are not part of the spec.
+
+use crate::state::AppState;
+use axum::{
+    http::header,
+    response::{Html, IntoResponse},
+    routing::get,
+    Router,
+};
+use once_cell::sync::Lazy;
+use utoipa::openapi::{
+    header::Header,
+    schema::{Object, Ref, SchemaType},
+    security::{ApiKey, ApiKeyValue, SecurityRequirement, SecurityScheme},
+    Content, ResponseBuilder,
+};
+use utoipa::{Modify, OpenApi};
+
+/// Name of the shared `429 Too Many Requests` response component
+const RATE_LIMITED_RESPONSE: &str = "RateLimited";
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "AIngle Cortex API",
+        description = "REST API for the Córtex semantic graph: triples, pattern queries, \
+                       statistics and proof validation."
+    ),
+    paths(
+        super::triples::create_triple,
+        super::triples::list_triples,
+        super::triples::batch_insert_triples,
+        super::triples::get_triple,
+        super::triples::delete_triple,
+        super::triples::get_triple_history,
+        super::triples::purge_tombstones,
+        super::query::query_pattern,
+        super::query::list_subjects,
+        super::query::list_predicates,
+        super::stats::get_stats,
+        super::stats::health_check,
+        super::proof::validate_triples,
+        super::proof_api::validate_proof,
+        super::proof_api::get_proof_status,
+        super::proof_api::revoke_proof,
+        super::datasets::list_datasets,
+        super::datasets::create_dataset,
+    ),
+    components(schemas(
+        crate::error::ErrorResponse,
+        crate::middleware::RateLimitResponse,
+        super::triples::TripleDto,
+        super::triples::ValueDto,
+        super::triples::CreateTripleRequest,
+        super::triples::ListTriplesResponse,
+        super::triples::DeletedTripleDto,
+        super::triples::TripleHistoryResponse,
+        super::triples::PurgeTombstonesResponse,
+        super::triples::BatchInsertRequest,
+        super::triples::BatchItemStatus,
+        super::triples::BatchItemResult,
+        super::triples::BatchInsertResponse,
+        crate::tombstones::HistoryEventKind,
+        crate::tombstones::HistoryEvent,
+        super::query::PatternQueryRequest,
+        super::query::PatternQueryResponse,
+        super::query::PatternDescription,
+        super::query::ListSubjectsResponse,
+        super::query::ListPredicatesResponse,
+        super::stats::StatsResponse,
+        super::stats::GraphStatsDto,
+        super::stats::ServerStatsDto,
+        super::stats::HealthResponse,
+        super::stats::ComponentHealth,
+        super::stats::ComponentStatus,
+        super::proof::ValidateRequest,
+        super::proof::ValidateTripleInput,
+        super::proof::ValidateResponse,
+        super::proof::TripleValidationResult,
+        super::proof::ValidationMessage,
+        super::proof_api::ValidateProofResponse,
+        super::proof_api::ProofStatusResponse,
+        super::proof_api::RevokeProofRequest,
+        crate::proofs::ProofStatus,
+        crate::proofs::ProofType,
+        crate::proofs::ProofMetadata,
+        crate::proofs::SubmitProofRequest,
+        crate::datasets::DatasetInfo,
+        super::datasets::ListDatasetsResponse,
+        super::datasets::CreateDatasetRequest,
+    )),
+    tags(
+        (name = "triples", description = "Create, read, delete and list triples"),
+        (name = "query", description = "Pattern queries over the graph"),
+        (name = "stats", description = "Statistics and health"),
+        (name = "validation", description = "Triple and proof validation"),
+        (name = "datasets", description = "Named datasets served next to the default one"),
+    )
+)]
+pub struct ApiDoc;
+
+/// Authentication endpoints, documented when the `auth` feature is enabled
+#[cfg(feature = "auth")]
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        crate::auth::create_token,
+        crate::auth::refresh_token,
+        crate::auth::verify_token_endpoint,
+        crate::auth::register,
+    ),
+    components(schemas(
+        crate::auth::CreateTokenRequest,
+        crate::auth::TokenResponse,
+        crate::auth::RefreshTokenRequest,
+        crate::auth::VerifyTokenRequest,
+        crate::auth::VerifyTokenResponse,
+        crate::auth::RegisterRequest,
+        crate::auth::RegisterResponse,
+    )),
+    tags((name = "auth", description = "Token issuance and user registration"))
+)]
+struct AuthApi;
+
+/// Registers the security schemes and makes credentials optional globally.
+///
+/// A bearer JWT scopes the caller to its namespace and carries its roles; an
+/// `X-API-Key` only identifies the client for rate limiting. Anonymous
+/// requests are accepted.
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        let mut security = Vec::new();
+
+        #[cfg(feature = "auth")]
+        {
+            use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder};
+
+            components.add_security_scheme(
+                "bearer_auth",
+                SecurityScheme::Http(
+                    HttpBuilder::new()
+                        .scheme(HttpAuthScheme::Bearer)
+                        .bearer_format("JWT")
+                        .description(Some(
+                            "Access token from `/api/v1/auth/token`; scopes writes to the \
+                             token's namespace and carries its roles",
+                        ))
+                        .build(),
+                ),
+            );
+            security.push(SecurityRequirement::new(
+                "bearer_auth",
+                Vec::<String>::new(),
+            ));
+        }
+
+        components.add_security_scheme(
+            "api_key",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::with_description(
+                "X-API-Key",
+                "Identifies the client for rate limiting; grants no permissions",
+            ))),
+        );
+        security.push(SecurityRequirement::new("api_key", Vec::<String>::new()));
+        security.push(SecurityRequirement::default());
+
+        openapi.security = Some(security);
+    }
+}
+
+/// Adds the `429 Too Many Requests` response to every operation.
+struct RateLimitAddon;
+
+impl Modify for RateLimitAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let mut retry_after = Header::new(Object::with_type(SchemaType::Integer));
+        retry_after.description = Some("Seconds until the request may be retried".to_string());
+
+        let response = ResponseBuilder::new()
+            .description("Rate limit exceeded for this client and request class")
+            .content(
+                "application/json",
+                Content::new(Ref::from_schema_name("RateLimitResponse")),
+            )
+            .header("Retry-After", retry_after)
+            .build();
+        openapi
+            .components
+            .get_or_insert_with(Default::default)
+            .responses
+            .insert(RATE_LIMITED_RESPONSE.to_string(), response.into());
+
+        for item in openapi.paths.paths.values_mut() {
+            for operation in item.operations.values_mut() {
+                operation.responses.responses.insert(
+                    "429".to_string(),
+                    Ref::from_response_name(RATE_LIMITED_RESPONSE).into(),
+                );
+            }
+        }
+    }
+}
+
+/// Returns the OpenAPI document for the REST API.
+pub fn spec() -> utoipa::openapi::OpenApi {
+    let mut spec = ApiDoc::openapi();
+    #[cfg(feature = "auth")]
+    spec.merge(AuthApi::openapi());
+    SecurityAddon.modify(&mut spec);
+    RateLimitAddon.modify(&mut spec);
+    spec
+}
+
+static SPEC_JSON: Lazy<String> = Lazy::new(|| {
+    spec()
+        .to_json()
+        .expect("OpenAPI document serializes to JSON")
+});
+
+/// Router serving the spec at `/api/openapi.json`
+pub fn router() -> Router<AppState> {
+    Router::new().route("/api/openapi.json", get(openapi_json))
+}
+
+/// Router serving the Swagger UI page at `/api/docs`
+pub fn swagger_ui_router() -> Router<AppState> {
+    Router::new().route("/api/docs", get(swagger_ui))
+}
+
+async fn openapi_json() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "application/json")],
+        SPEC_JSON.as_str(),
+    )
+}
+
+async fn swagger_ui() -> Html<&'static str> {
+    Html(SWAGGER_UI_HTML)
+}
+
+/// Swagger UI page; the assets are loaded from the `swagger-ui-dist` CDN.
+const SWAGGER_UI_HTML: &str = r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8" />
+  <title>AIngle Cortex API</title>
+  <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css" />
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js" crossorigin></script>
+  <script>
+    window.onload = () => {
+      window.ui = SwaggerUIBundle({ url: "/api/openapi.json", dom_id: "#swagger-ui" });
+    };
+  </script>
+</body>
+</html>
+"##;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_operation_documents_rate_limiting() {
+        let spec = spec();
+        for (path, item) in &spec.paths.paths {
+            for operation in item.operations.values() {
+                assert!(
+                    operation.responses.responses.contains_key("429"),
+                    "{} lacks a 429 response",
+                    path
+                );
+            }
+        }
+    }
+}
@@ -17,6 +17,7 @@ use aingle_graph::{NodeId, Predicate, Triple, Value};
 
 /// Request to validate triples
 #[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Deserialize)]
 pub struct ValidateRequest {
     /// Triples to validate
@@ -27,6 +28,7 @@ pub struct ValidateRequest {
 
 /// Triple input for validation
 #[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Deserialize)]
 pub struct ValidateTripleInput {
     pub subject: String,
@@ -35,6 +37,7 @@ pub struct ValidateTripleInput {
 }
 
 /// Validation response
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Serialize)]
 pub struct ValidateResponse {
     /// Overall validity
@@ -46,6 +49,7 @@ pub struct ValidateResponse {
 }
 
 /// Individual triple validation result
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Serialize)]
 pub struct TripleValidationResult {
     /// Triple that was validated
@@ -57,6 +61,7 @@ pub struct TripleValidationResult {
 }
 
 /// Validation message
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Serialize)]
 pub struct ValidationMessage {
     /// Message level: "info", "warning", "error"
@@ -70,6 +75,19 @@ pub struct ValidationMessage {
 /// Validate triples against logic rules
 ///
 /// POST /api/v1/validate
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/v1/validate",
+        tag = "validation",
+        request_body = ValidateRequest,
+        responses(
+            (status = 200, description = "Validation results per triple", body = ValidateResponse),
+            (status = 403, description = "A subject is outside the caller's namespace", body = ErrorResponse),
+        ),
+    )
+)]
 pub async fn validate_triples(
     State(state): State<AppState>,
     ns_ext: Option<axum::Extension<RequestNamespace>>,
@@ -10,8 +10,12 @@ use axum::{
 use serde::{Deserialize, Serialize};
 
 use crate::error::{Error, Result};
-use crate::middleware::{is_in_namespace, RequestNamespace};
-use crate::proofs::{ProofId, ProofMetadata, ProofType, StoredProof, SubmitProofRequest};
+use crate::middleware::{is_in_namespace, RequestNamespace, RequestPrincipal};
+use crate::proofs::{
+    ProofId, ProofMetadata, ProofStatus, ProofType, StoredProof, SubmitProofRequest,
+    VerificationError,
+};
+use crate::rest::audit::AuditEntry;
 use crate::state::AppState;
 
 /// Submit a new proof
@@ -54,6 +58,49 @@ pub async fn submit_proof(
     }))
 }
 
+/// Verify a proof and store it with the outcome
+///
+/// POST /api/v1/proofs/validate
+///
+/// Delegates to [`crate::service::proof::validate_proof`]. A proof that does
+/// not verify is stored and answered with `valid: false`; proof data that
+/// cannot be parsed is rejected with 400 and not stored.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/v1/proofs/validate",
+        tag = "validation",
+        request_body = SubmitProofRequest,
+        responses(
+            (status = 200, description = "The proof was verified and stored with the outcome", body = ValidateProofResponse),
+            (status = 400, description = "The proof data could not be parsed; nothing was stored", body = ErrorResponse),
+            (status = 403, description = "Submitter outside the caller's namespace", body = ErrorResponse),
+            (status = 500, description = "The proof could not be stored", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn validate_proof(
+    State(state): State<AppState>,
+    ns_ext: Option<axum::Extension<RequestNamespace>>,
+    Json(request): Json<SubmitProofRequest>,
+) -> Result<Json<ValidateProofResponse>> {
+    // Enforce namespace: submitter must belong to the namespace
+    if let Some(axum::Extension(RequestNamespace(Some(ref ns)))) = ns_ext {
+        if let Some(submitter) = request.metadata.as_ref().and_then(|m| m.submitter.as_ref()) {
+            if !is_in_namespace(submitter, ns) {
+                return Err(Error::Forbidden(format!(
+                    "Submitter \"{}\" is not in namespace \"{}\"",
+                    submitter, ns
+                )));
+            }
+        }
+    }
+
+    let resp = crate::service::proof::validate_proof(&state, request).await?;
+    Ok(Json(resp))
+}
+
 /// Submit multiple proofs in batch
 ///
 /// POST /api/v1/proofs/batch
@@ -99,6 +146,114 @@ pub async fn get_proof(
     Ok(Json(resp))
 }
 
+/// Get the lifecycle status of a proof
+///
+/// GET /api/v1/proofs/:id/status
+///
+/// Delegates to [`crate::service::proof::get_proof_status`].
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/v1/proofs/{id}/status",
+        tag = "validation",
+        params(("id" = String, Path, description = "Proof identifier")),
+        responses(
+            (status = 200, description = "The proof's status and validation record", body = ProofStatusResponse),
+            (status = 404, description = "No proof with this identifier", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn get_proof_status(
+    State(state): State<AppState>,
+    Path(proof_id): Path<ProofId>,
+) -> Result<Json<ProofStatusResponse>> {
+    let resp =
+        crate::service::proof::get_proof_status(&state, GetProofRequest { proof_id }).await?;
+    Ok(Json(resp))
+}
+
+/// Revoke a proof (admin only)
+///
+/// POST /api/v1/proofs/:id/revoke
+///
+/// The proof is kept with its revocation recorded; revoking it again is a
+/// conflict.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/v1/proofs/{id}/revoke",
+        tag = "validation",
+        params(("id" = String, Path, description = "Proof identifier")),
+        request_body = RevokeProofRequest,
+        responses(
+            (status = 200, description = "The proof was revoked", body = ProofStatusResponse),
+            (status = 400, description = "Empty reason", body = ErrorResponse),
+            (status = 403, description = "The caller lacks the admin role", body = ErrorResponse),
+            (status = 404, description = "No proof with this identifier", body = ErrorResponse),
+            (status = 409, description = "The proof is already revoked", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn revoke_proof(
+    State(state): State<AppState>,
+    ns_ext: Option<axum::Extension<RequestNamespace>>,
+    principal_ext: Option<axum::Extension<RequestPrincipal>>,
+    Path(proof_id): Path<ProofId>,
+    Json(request): Json<RevokeProofRequest>,
+) -> Result<Json<ProofStatusResponse>> {
+    let principal = RequestPrincipal::from_extension(principal_ext);
+    if !principal.is_admin() {
+        return Err(Error::Forbidden(
+            "Revoking proofs requires the admin role".to_string(),
+        ));
+    }
+    let reason = request.reason.trim();
+    if reason.is_empty() {
+        return Err(Error::InvalidInput(
+            "A revocation reason is required".to_string(),
+        ));
+    }
+
+    let namespace = ns_ext.and_then(|axum::Extension(RequestNamespace(ns))| ns);
+    let revoked_by = principal
+        .user_id
+        .or_else(|| namespace.clone())
+        .unwrap_or_else(|| "anonymous".to_string());
+
+    let proof = match state
+        .proof_store
+        .revoke(&proof_id, reason, &revoked_by)
+        .await
+    {
+        Ok(proof) => proof,
+        Err(VerificationError::ProofNotFound(_)) => {
+            return Err(Error::NotFound(format!("Proof {} not found", proof_id)))
+        }
+        Err(VerificationError::AlreadyRevoked(_)) => {
+            return Err(Error::Conflict(format!(
+                "Proof {} is already revoked",
+                proof_id
+            )))
+        }
+        Err(e) => return Err(Error::Internal(e.to_string())),
+    };
+
+    state.audit_log.write().await.record(AuditEntry {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        user_id: revoked_by,
+        namespace,
+        action: "revoke".to_string(),
+        resource: format!("/api/v1/proofs/{}", proof_id),
+        details: Some(reason.to_string()),
+        request_id: None,
+        request: None,
+    });
+
+    Ok(Json(ProofStatusResponse::from(proof)))
+}
+
 /// Verify a proof
 ///
 /// GET /api/v1/proofs/:id/verify
@@ -291,6 +446,12 @@ pub struct ProofResponse {
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub verified: bool,
     pub verified_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub verification_details: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Current lifecycle status
+    pub status: ProofStatus,
     pub metadata: ProofMetadata,
     pub size_bytes: usize,
 }
@@ -298,18 +459,67 @@ pub struct ProofResponse {
 impl From<StoredProof> for ProofResponse {
     fn from(proof: StoredProof) -> Self {
         let size_bytes = proof.size_bytes();
+        let status = proof.current_status();
         Self {
             id: proof.id,
             proof_type: proof.proof_type,
             created_at: proof.created_at,
             verified: proof.verified,
             verified_at: proof.verified_at,
+            verification_details: proof.verification_details,
+            expires_at: proof.expires_at,
+            status,
             metadata: proof.metadata,
             size_bytes,
         }
     }
 }
 
+/// Status of a proof with the record of its validation
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Serialize)]
+pub struct ProofStatusResponse {
+    #[cfg_attr(feature = "openapi", schema(value_type = String))]
+    pub proof_id: ProofId,
+    /// Current lifecycle status
+    pub status: ProofStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub proof_type: ProofType,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Outcome of the last verification
+    pub verified: bool,
+    pub verified_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub verification_details: Vec<String>,
+    pub metadata: ProofMetadata,
+}
+
+impl From<StoredProof> for ProofStatusResponse {
+    fn from(proof: StoredProof) -> Self {
+        let status = proof.current_status();
+        Self {
+            proof_id: proof.id,
+            status,
+            expires_at: proof.expires_at,
+            proof_type: proof.proof_type,
+            created_at: proof.created_at,
+            verified: proof.verified,
+            verified_at: proof.verified_at,
+            verification_details: proof.verification_details,
+            metadata: proof.metadata,
+        }
+    }
+}
+
+/// Request to revoke a proof
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Deserialize)]
+pub struct RevokeProofRequest {
+    /// Why the proof is revoked, e.g. a compromised signing key
+    pub reason: String,
+}
+
 /// Request to verify a stored proof by its ID.
 ///
 /// Tool/handler INPUT: the path parameter of `GET /api/v1/proofs/:id/verify`
@@ -342,6 +552,22 @@ pub struct VerifyProofResponse {
     pub verification_time_us: u64,
 }
 
+/// Outcome of `POST /api/v1/proofs/validate`
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Serialize)]
+pub struct ValidateProofResponse {
+    /// Identifier the proof was stored under
+    #[cfg_attr(feature = "openapi", schema(value_type = String))]
+    pub proof_id: ProofId,
+    pub proof_type: ProofType,
+    pub valid: bool,
+    pub verified_at: chrono::DateTime<chrono::Utc>,
+    pub details: Vec<String>,
+    pub verification_time_us: u64,
+    /// Lifecycle status of the stored proof
+    pub status: ProofStatus,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct BatchVerifyRequest {
     pub proof_ids: Vec<ProofId>,
@@ -405,6 +631,7 @@ mod tests {
                 "response": vec![2u8; 32],
             }),
             metadata: None,
+            expires_at: None,
         };
 
         let response = submit_proof(AxumState(state.clone()), None, Json(request))
@@ -429,6 +656,7 @@ mod tests {
                 proof_type: ProofType::Schnorr,
                 proof_data: serde_json::json!({"test": "data"}),
                 metadata: None,
+                expires_at: None,
             };
             submit_proof(AxumState(state.clone()), None, Json(request))
                 .await
@@ -456,6 +684,7 @@ mod tests {
             proof_type: ProofType::Equality,
             proof_data: serde_json::json!({"test": "data"}),
             metadata: None,
+            expires_at: None,
         };
 
         submit_proof(AxumState(state.clone()), None, Json(request))
@@ -466,4 +695,25 @@ mod tests {
 
         assert_eq!(response.0.total_proofs, 1);
     }
+
+    #[tokio::test]
+    async fn test_validate_proof_rejects_malformed_data() {
+        use axum::response::IntoResponse;
+
+        let state = AppState::new().unwrap();
+        let request = SubmitProofRequest {
+            proof_type: ProofType::Range,
+            proof_data: serde_json::json!({"n_bits": 32}),
+            metadata: None,
+            expires_at: None,
+        };
+
+        let err = validate_proof(AxumState(state), None, Json(request))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.into_response().status(),
+            axum::http::StatusCode::BAD_REQUEST
+        );
+    }
 }
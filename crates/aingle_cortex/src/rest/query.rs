@@ -3,19 +3,17 @@
 
 //! Query endpoints for pattern matching
 
-use axum::{
-    extract::{Query, State},
-    Json,
-};
+use axum::{extract::Query, Json};
 use serde::{Deserialize, Serialize};
 
+use crate::datasets::Dataset;
 use crate::error::Result;
 use crate::middleware::RequestNamespace;
 use crate::rest::triples::{TripleDto, ValueDto};
-use crate::state::AppState;
 
 /// Pattern query request
 #[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Deserialize)]
 pub struct PatternQueryRequest {
     /// Subject pattern (None = wildcard)
@@ -35,6 +33,7 @@ fn default_limit() -> usize {
 
 /// Pattern query response
 #[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Serialize)]
 pub struct PatternQueryResponse {
     /// Matching triples
@@ -47,6 +46,7 @@ pub struct PatternQueryResponse {
 
 /// Description of the query pattern
 #[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Serialize)]
 pub struct PatternDescription {
     pub subject: Option<String>,
@@ -57,8 +57,21 @@ pub struct PatternDescription {
 /// Execute pattern matching query
 ///
 /// POST /api/v1/query
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/v1/query",
+        tag = "query",
+        request_body = PatternQueryRequest,
+        responses(
+            (status = 200, description = "Triples matching the pattern", body = PatternQueryResponse),
+            (status = 400, description = "Invalid pattern", body = ErrorResponse),
+        ),
+    )
+)]
 pub async fn query_pattern(
-    State(state): State<AppState>,
+    Dataset(state): Dataset,
     ns_ext: Option<axum::Extension<RequestNamespace>>,
     Json(req): Json<PatternQueryRequest>,
 ) -> Result<Json<PatternQueryResponse>> {
@@ -70,6 +83,11 @@ pub async fn query_pattern(
 
 /// Query parameters for listing subjects
 #[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
 #[derive(Debug, Deserialize)]
 pub struct ListSubjectsQuery {
     /// Filter by predicate
@@ -82,8 +100,22 @@ pub struct ListSubjectsQuery {
 /// List all unique subjects
 ///
 /// GET /api/v1/query/subjects
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/v1/query/subjects",
+        tag = "query",
+        params(
+            ListSubjectsQuery,
+        ),
+        responses(
+            (status = 200, description = "Distinct subjects", body = ListSubjectsResponse),
+        ),
+    )
+)]
 pub async fn list_subjects(
-    State(state): State<AppState>,
+    Dataset(state): Dataset,
     ns_ext: Option<axum::Extension<RequestNamespace>>,
     Query(query): Query<ListSubjectsQuery>,
 ) -> Result<Json<ListSubjectsResponse>> {
@@ -95,6 +127,7 @@ pub async fn list_subjects(
 
 /// Response for listing subjects
 #[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Serialize)]
 pub struct ListSubjectsResponse {
     pub subjects: Vec<String>,
@@ -103,6 +136,11 @@ pub struct ListSubjectsResponse {
 
 /// Query parameters for listing predicates
 #[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
 #[derive(Debug, Deserialize)]
 pub struct ListPredicatesQuery {
     /// Filter by subject
@@ -115,8 +153,22 @@ pub struct ListPredicatesQuery {
 /// List all unique predicates
 ///
 /// GET /api/v1/query/predicates
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/v1/query/predicates",
+        tag = "query",
+        params(
+            ListPredicatesQuery,
+        ),
+        responses(
+            (status = 200, description = "Distinct predicates", body = ListPredicatesResponse),
+        ),
+    )
+)]
 pub async fn list_predicates(
-    State(state): State<AppState>,
+    Dataset(state): Dataset,
     ns_ext: Option<axum::Extension<RequestNamespace>>,
     Query(query): Query<ListPredicatesQuery>,
 ) -> Result<Json<ListPredicatesResponse>> {
@@ -128,6 +180,7 @@ pub async fn list_predicates(
 
 /// Response for listing predicates
 #[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Serialize)]
 pub struct ListPredicatesResponse {
     pub predicates: Vec<String>,
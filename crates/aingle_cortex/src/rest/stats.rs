@@ -6,11 +6,13 @@
 use axum::{extract::State, Json};
 use serde::Serialize;
 
+use crate::datasets::Dataset;
 use crate::error::Result;
 use crate::state::AppState;
 
 /// Graph statistics response
 #[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Serialize)]
 pub struct StatsResponse {
     /// Graph statistics
@@ -21,6 +23,7 @@ pub struct StatsResponse {
 
 /// Graph statistics DTO
 #[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Serialize)]
 pub struct GraphStatsDto {
     /// Total number of triples
@@ -35,6 +38,7 @@ pub struct GraphStatsDto {
 
 /// Server statistics DTO
 #[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Serialize)]
 pub struct ServerStatsDto {
     /// Number of connected WebSocket clients
@@ -48,11 +52,24 @@ pub struct ServerStatsDto {
 /// Get graph and server statistics
 ///
 /// GET /api/v1/stats
-pub async fn get_stats(State(state): State<AppState>) -> Result<Json<StatsResponse>> {
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/v1/stats",
+        tag = "stats",
+        responses(
+            (status = 200, description = "Graph and server statistics", body = StatsResponse),
+            (status = 500, description = "The graph could not be read", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn get_stats(Dataset(state): Dataset) -> Result<Json<StatsResponse>> {
     Ok(Json(crate::service::stats::graph_stats(&state).await?))
 }
 
 /// Health check response
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Serialize)]
 pub struct HealthResponse {
     /// Overall health status
@@ -62,6 +79,7 @@ pub struct HealthResponse {
 }
 
 /// Component health
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Serialize)]
 pub struct ComponentHealth {
     /// Graph database health
@@ -71,6 +89,7 @@ pub struct ComponentHealth {
 }
 
 /// Individual component status
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Serialize)]
 pub struct ComponentStatus {
     /// Status: "healthy", "degraded", "unhealthy"
@@ -103,6 +122,18 @@ pub async fn flush_data(State(state): State<AppState>) -> Result<Json<FlushRespo
 /// Health check endpoint
 ///
 /// GET /api/v1/health
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/v1/health",
+        tag = "stats",
+        responses(
+            (status = 200, description = "Component health", body = HealthResponse),
+        ),
+        security(()),
+    )
+)]
 pub async fn health_check(State(state): State<AppState>) -> Json<HealthResponse> {
     // Check graph health
     let graph_health = {
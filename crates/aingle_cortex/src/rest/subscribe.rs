@@ -0,0 +1,271 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Event subscription endpoints
+//!
+//! Graph events are streamed over a WebSocket, or fetched with long polls by
+//! clients that cannot hold one open. Both take an optional `after_seq`
+//! cursor and replay the buffered events after it first, so a client that
+//! resumes from the last `seq` it saw misses nothing.
+//!
+//! On a graceful shutdown, WebSocket subscribers receive a close frame with
+//! code [`SERVICE_RESTART`] and long polls return at once with
+//! `shutting_down: true`; either way the client should reconnect with its
+//! cursor once the server is back.
+
+use axum::{
+    extract::{
+        ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade},
+        Query, State,
+    },
+    response::Response,
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::error::{Error, Result};
+use crate::shutdown::{SessionGuard, RESTART_REASON, SERVICE_RESTART};
+use crate::state::{AppState, EventBroadcaster, SequencedEvent};
+
+/// WebSocket close code sent when the cursor cannot be resumed from
+pub const RESYNC_REQUIRED: u16 = 4000;
+
+/// Default wait of a long poll, in seconds
+pub const DEFAULT_POLL_TIMEOUT_SECS: u64 = 25;
+
+/// Longest wait a long poll may ask for, in seconds
+pub const MAX_POLL_TIMEOUT_SECS: u64 = 60;
+
+/// Create the subscription router
+pub fn subscribe_router() -> Router<AppState> {
+    Router::new()
+        .route("/api/v1/subscribe", get(subscribe_events))
+        .route("/api/v1/subscribe/poll", get(poll_events))
+}
+
+/// Query parameters of a WebSocket subscription
+#[derive(Debug, Deserialize)]
+pub struct SubscribeParams {
+    /// Replay buffered events after this sequence number first.
+    pub after_seq: Option<u64>,
+}
+
+/// Query parameters of a long poll
+#[derive(Debug, Deserialize)]
+pub struct PollParams {
+    /// Return events after this sequence number; `None` waits for new ones.
+    pub after_seq: Option<u64>,
+    /// Seconds to wait for an event (default 25, at most 60).
+    pub timeout_secs: Option<u64>,
+}
+
+/// Long poll response
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PollResponse {
+    /// Events after the cursor, oldest first, each with its `seq`.
+    pub events: Vec<serde_json::Value>,
+    /// Sequence number of the latest event broadcast.
+    pub latest_seq: u64,
+    /// `true` if the server is shutting down and the client should retry later.
+    pub shutting_down: bool,
+}
+
+/// Counts a subscriber on the broadcaster until dropped
+struct Subscriber<'a>(&'a EventBroadcaster);
+
+impl Drop for Subscriber<'_> {
+    fn drop(&mut self) {
+        self.0.unsubscribe();
+    }
+}
+
+/// Stream events over a WebSocket
+///
+/// GET /api/v1/subscribe
+pub async fn subscribe_events(
+    State(state): State<AppState>,
+    Query(params): Query<SubscribeParams>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    let session = state.shutdown.session();
+    ws.on_upgrade(move |socket| stream_events(socket, state, params.after_seq, session))
+}
+
+async fn stream_events(
+    mut socket: WebSocket,
+    state: AppState,
+    after_seq: Option<u64>,
+    _session: SessionGuard,
+) {
+    let replay = match state.broadcaster.subscribe_from(after_seq) {
+        Ok(replay) => replay,
+        Err(e) => {
+            let _ = socket.send(close(RESYNC_REQUIRED, &e.to_string())).await;
+            return;
+        }
+    };
+    let _subscriber = Subscriber(&state.broadcaster);
+
+    for event in &replay.backlog {
+        if socket.send(text(event)).await.is_err() {
+            return;
+        }
+    }
+
+    let mut live = replay.live;
+    let shutdown = state.shutdown.triggered();
+    tokio::pin!(shutdown);
+    loop {
+        tokio::select! {
+            _ = &mut shutdown => {
+                let _ = socket.send(close(SERVICE_RESTART, RESTART_REASON)).await;
+                return;
+            }
+            event = live.recv() => match event {
+                Ok(event) => {
+                    if socket.send(text(&event)).await.is_err() {
+                        return;
+                    }
+                }
+                Err(RecvError::Lagged(missed)) => {
+                    let reason = format!("missed {missed} events");
+                    let _ = socket.send(close(RESYNC_REQUIRED, &reason)).await;
+                    return;
+                }
+                Err(RecvError::Closed) => return,
+            },
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+fn text(event: &SequencedEvent) -> Message {
+    Message::Text(event.to_json().into())
+}
+
+fn close(code: u16, reason: &str) -> Message {
+    Message::Close(Some(CloseFrame {
+        code,
+        reason: reason.into(),
+    }))
+}
+
+/// Wait for events after a cursor
+///
+/// GET /api/v1/subscribe/poll
+///
+/// Returns as soon as there are events after `after_seq`, when the timeout
+/// elapses (with no events), or when the server starts shutting down.
+/// Answers 409 if the cursor can no longer be resumed from.
+pub async fn poll_events(
+    State(state): State<AppState>,
+    Query(params): Query<PollParams>,
+) -> Result<Json<PollResponse>> {
+    let replay = state
+        .broadcaster
+        .subscribe_from(params.after_seq)
+        .map_err(|e| Error::Conflict(format!("resync required: {e}")))?;
+    let _subscriber = Subscriber(&state.broadcaster);
+
+    let mut events: Vec<_> = replay
+        .backlog
+        .iter()
+        .map(SequencedEvent::to_value)
+        .collect();
+    let mut live = replay.live;
+
+    if events.is_empty() && !state.shutdown.is_triggered() {
+        let timeout = params
+            .timeout_secs
+            .unwrap_or(DEFAULT_POLL_TIMEOUT_SECS)
+            .min(MAX_POLL_TIMEOUT_SECS);
+        tokio::select! {
+            _ = state.shutdown.triggered() => {}
+            _ = tokio::time::sleep(Duration::from_secs(timeout)) => {}
+            event = live.recv() => {
+                if let Ok(event) = event {
+                    events.push(event.to_value());
+                }
+            }
+        }
+        // Collect whatever else has already arrived
+        while let Ok(event) = live.try_recv() {
+            events.push(event.to_value());
+        }
+    }
+
+    Ok(Json(PollResponse {
+        events,
+        latest_seq: state.broadcaster.latest_seq(),
+        shutting_down: state.shutdown.is_triggered(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::Event;
+
+    fn event(n: usize) -> Event {
+        Event::TripleDeleted {
+            hash: n.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_poll_replays_after_cursor() {
+        let state = AppState::new().unwrap();
+        state.broadcaster.broadcast(event(1));
+        state.broadcaster.broadcast(event(2));
+
+        let params = PollParams {
+            after_seq: Some(1),
+            timeout_secs: Some(0),
+        };
+        let Json(response) = poll_events(State(state.clone()), Query(params))
+            .await
+            .unwrap();
+        assert_eq!(response.events.len(), 1);
+        assert_eq!(response.events[0]["seq"], 2);
+        assert_eq!(response.latest_seq, 2);
+        assert!(!response.shutting_down);
+        assert_eq!(state.broadcaster.client_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_poll_returns_on_shutdown() {
+        let state = AppState::new().unwrap();
+        let params = PollParams {
+            after_seq: None,
+            timeout_secs: Some(MAX_POLL_TIMEOUT_SECS),
+        };
+        let poll = tokio::spawn(poll_events(State(state.clone()), Query(params)));
+        tokio::task::yield_now().await;
+        state.shutdown.trigger();
+
+        let Json(response) = tokio::time::timeout(Duration::from_secs(5), poll)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert!(response.events.is_empty());
+        assert!(response.shutting_down);
+    }
+
+    #[tokio::test]
+    async fn test_poll_rejects_cursor_ahead() {
+        let state = AppState::new().unwrap();
+        let params = PollParams {
+            after_seq: Some(5),
+            timeout_secs: Some(0),
+        };
+        let err = poll_events(State(state), Query(params)).await.unwrap_err();
+        assert!(matches!(err, Error::Conflict(_)));
+    }
+}
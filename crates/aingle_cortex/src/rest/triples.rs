@@ -4,16 +4,17 @@
 //! Triple CRUD operations
 
 use axum::{
-    extract::{Path, Query, State},
+    extract::{Path, Query},
     http::StatusCode,
     Json,
 };
 use serde::{Deserialize, Serialize};
 
+use crate::datasets::Dataset;
 use crate::error::{Error, Result};
-use crate::middleware::{is_in_namespace, RequestNamespace};
-use crate::state::AppState;
-use aingle_graph::{NodeId, Triple, TripleId, Value};
+use crate::middleware::{is_in_namespace, RequestNamespace, RequestPrincipal};
+use crate::tombstones::{HistoryEvent, HistoryEventKind, TripleRecord};
+use aingle_graph::{NodeId, Predicate, Triple, TripleId, Value};
 
 // `AuditEntry` and `Event` are only referenced from the DAG/cluster write paths
 // below; the non-cluster direct-write path delegates those side-effects to the
@@ -29,6 +30,7 @@ use axum::http::HeaderMap;
 
 /// Triple data transfer object
 #[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TripleDto {
     /// Triple hash (read-only)
@@ -47,7 +49,8 @@ pub struct TripleDto {
 
 /// Value data transfer object
 #[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ValueDto {
     /// String value
@@ -108,6 +111,7 @@ impl From<Triple> for TripleDto {
 
 /// Request to create a triple
 #[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Deserialize)]
 pub struct CreateTripleRequest {
     pub subject: String,
@@ -128,6 +132,11 @@ pub struct TripleIdRequest {
 
 /// Query parameters for listing triples
 #[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
 #[derive(Debug, Deserialize)]
 pub struct ListTriplesQuery {
     /// Filter by subject
@@ -148,11 +157,35 @@ fn default_limit() -> usize {
     100
 }
 
+/// Path parameters of the single-triple routes
+///
+/// A struct rather than a bare `String` so the routes can also be nested under
+/// `/api/v1/datasets/{dataset}`.
+#[derive(Debug, Deserialize)]
+pub struct TriplePath {
+    /// Hex hash of the triple
+    pub id: String,
+}
+
 /// Create a new triple
 ///
 /// POST /api/v1/triples
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/v1/triples",
+        tag = "triples",
+        request_body = CreateTripleRequest,
+        responses(
+            (status = 201, description = "The triple was created", body = TripleDto),
+            (status = 400, description = "Empty subject or predicate", body = ErrorResponse),
+            (status = 403, description = "Subject outside the caller's namespace", body = ErrorResponse),
+        ),
+    )
+)]
 pub async fn create_triple(
-    State(state): State<AppState>,
+    Dataset(state): Dataset,
     ns_ext: Option<axum::Extension<RequestNamespace>>,
     Json(req): Json<CreateTripleRequest>,
 ) -> Result<(StatusCode, Json<TripleDto>)> {
@@ -255,6 +288,7 @@ pub async fn create_triple(
                 resource: format!("/api/v1/triples/{}", hash),
                 details: Some(format!("subject={} (dag)", req.subject)),
                 request_id: None,
+                request: None,
             });
         }
 
@@ -321,6 +355,7 @@ pub async fn create_triple(
                 resource: format!("/api/v1/triples/{}", hash),
                 details: Some(format!("subject={}", req.subject)),
                 request_id: None,
+                request: None,
             });
         }
 
@@ -399,10 +434,26 @@ fn parse_consistency_header(headers: &HeaderMap) -> aingle_raft::ConsistencyLeve
 /// Get a triple by hash
 ///
 /// GET /api/v1/triples/:id
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/v1/triples/{id}",
+        tag = "triples",
+        params(
+            ("id" = String, Path, description = "Hex hash of the triple"),
+        ),
+        responses(
+            (status = 200, description = "The triple", body = TripleDto),
+            (status = 400, description = "Malformed triple id", body = ErrorResponse),
+            (status = 404, description = "No live triple with this id", body = ErrorResponse),
+        ),
+    )
+)]
 pub async fn get_triple(
-    State(state): State<AppState>,
+    Dataset(state): Dataset,
     #[cfg(feature = "cluster")] headers: HeaderMap,
-    Path(id): Path<String>,
+    Path(TriplePath { id }): Path<TriplePath>,
 ) -> Result<Json<TripleDto>> {
     // Apply consistency level for cluster reads
     #[cfg(feature = "cluster")]
@@ -429,19 +480,61 @@ pub async fn get_triple(
     Ok(Json(dto))
 }
 
+/// Query parameters for deleting a triple
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+#[derive(Debug, Default, Deserialize)]
+pub struct DeleteTripleQuery {
+    /// Why the triple is being deleted, kept in its history
+    pub reason: Option<String>,
+}
+
 /// Delete a triple
 ///
-/// DELETE /api/v1/triples/:id
+/// DELETE /api/v1/triples/:id?reason=...
+///
+/// The triple leaves the graph but is kept as a tombstone, with the caller and
+/// reason, until a retention purge drops it.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        delete,
+        path = "/api/v1/triples/{id}",
+        tag = "triples",
+        params(
+            ("id" = String, Path, description = "Hex hash of the triple"),
+            DeleteTripleQuery,
+        ),
+        responses(
+            (status = 204, description = "The triple was deleted and kept as a tombstone"),
+            (status = 400, description = "Malformed triple id", body = ErrorResponse),
+            (status = 403, description = "Subject outside the caller's namespace", body = ErrorResponse),
+            (status = 404, description = "No live triple with this id", body = ErrorResponse),
+        ),
+    )
+)]
 pub async fn delete_triple(
-    State(state): State<AppState>,
+    Dataset(state): Dataset,
     ns_ext: Option<axum::Extension<RequestNamespace>>,
-    Path(id): Path<String>,
+    principal_ext: Option<axum::Extension<RequestPrincipal>>,
+    Path(TriplePath { id }): Path<TriplePath>,
+    Query(params): Query<DeleteTripleQuery>,
 ) -> Result<StatusCode> {
     let triple_id = TripleId::from_hex(&id)
         .ok_or_else(|| Error::InvalidInput(format!("Invalid triple ID: {}", id)))?;
 
+    let namespace = ns_ext
+        .as_ref()
+        .and_then(|axum::Extension(RequestNamespace(ns))| ns.clone());
+    let actor = RequestPrincipal::from_extension(principal_ext)
+        .user_id
+        .or_else(|| namespace.clone());
+
     // Enforce namespace on delete
-    if let Some(axum::Extension(RequestNamespace(Some(ref ns)))) = ns_ext {
+    if let Some(ref ns) = namespace {
         let graph = state.graph.read().await;
         if let Some(triple) = graph.get(&triple_id)? {
             if !is_in_namespace(&triple.subject.to_string(), ns) {
@@ -463,16 +556,12 @@ pub async fn delete_triple(
             .dag_seq_counter
             .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
 
-        let (parents, subject_for_dag) = {
+        let (parents, existing) = {
             let graph = state.graph.read().await;
             let tips = graph.dag_tips().unwrap_or_default();
-            let subj = graph
-                .get(&triple_id)
-                .ok()
-                .flatten()
-                .map(|t| t.subject.to_string());
-            (tips, subj)
+            (tips, graph.get(&triple_id).ok().flatten())
         };
+        let subject_for_dag = existing.as_ref().map(|t| t.subject.to_string());
 
         let mut action = aingle_graph::dag::DagAction {
             parents,
@@ -510,6 +599,11 @@ pub async fn delete_triple(
             ));
         }
 
+        if let Some(triple) = existing {
+            state
+                .tombstones
+                .record_deletion(&triple, actor, params.reason, chrono::Utc::now())?;
+        }
         state
             .broadcaster
             .broadcast(Event::TripleDeleted { hash: id });
@@ -519,6 +613,7 @@ pub async fn delete_triple(
     // Cluster mode (non-DAG): route deletes through Raft
     #[cfg(feature = "cluster")]
     if let Some(ref raft) = state.raft {
+        let existing = state.graph.read().await.get(&triple_id).ok().flatten();
         let raft_req = aingle_raft::CortexRequest {
             kind: aingle_wal::WalEntryKind::TripleDelete {
                 triple_id: *triple_id.as_bytes(),
@@ -538,6 +633,11 @@ pub async fn delete_triple(
             ));
         }
 
+        if let Some(triple) = existing {
+            state
+                .tombstones
+                .record_deletion(&triple, actor, params.reason, chrono::Utc::now())?;
+        }
         state
             .broadcaster
             .broadcast(Event::TripleDeleted { hash: id });
@@ -556,11 +656,15 @@ pub async fn delete_triple(
     // Delegate the shared delete + DAG action + audit + event side-effects to the
     // service layer; the cluster-only WAL replication below remains a transport
     // concern.
-    let namespace = ns_ext
-        .as_ref()
-        .and_then(|axum::Extension(RequestNamespace(ns))| ns.clone());
-
-    crate::service::triples::delete_triple(&state, &id, namespace, None).await?;
+    crate::service::triples::delete_triple_with_reason(
+        &state,
+        &id,
+        namespace,
+        None,
+        actor,
+        params.reason,
+    )
+    .await?;
 
     // Append to WAL (legacy cluster path). The service call above already
     // performed the graph delete and side-effects; a WAL failure here happens
@@ -576,15 +680,55 @@ pub async fn delete_triple(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Query parameter opting a triple listing into tombstoned triples
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+#[derive(Debug, Default, Deserialize)]
+pub struct IncludeDeletedQuery {
+    /// Also return deleted triples (admin only)
+    #[serde(default)]
+    pub include_deleted: bool,
+}
+
 /// List triples with filters
 ///
 /// GET /api/v1/triples
+///
+/// With `include_deleted=true` (admin only) tombstoned triples matching the
+/// same filters are returned in `deleted`, separately from the live triples.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/v1/triples",
+        tag = "triples",
+        params(
+            ListTriplesQuery,
+            IncludeDeletedQuery,
+        ),
+        responses(
+            (status = 200, description = "A page of matching triples", body = ListTriplesResponse),
+            (status = 403, description = "`include_deleted` requested without the admin role", body = ErrorResponse),
+        ),
+    )
+)]
 pub async fn list_triples(
-    State(state): State<AppState>,
+    Dataset(state): Dataset,
     #[cfg(feature = "cluster")] headers: HeaderMap,
     ns_ext: Option<axum::Extension<RequestNamespace>>,
+    principal_ext: Option<axum::Extension<RequestPrincipal>>,
     Query(query): Query<ListTriplesQuery>,
+    Query(deleted): Query<IncludeDeletedQuery>,
 ) -> Result<Json<ListTriplesResponse>> {
+    if deleted.include_deleted && !RequestPrincipal::from_extension(principal_ext).is_admin() {
+        return Err(Error::Forbidden(
+            "Listing deleted triples requires the admin role".to_string(),
+        ));
+    }
+
     // Apply consistency level for cluster reads
     #[cfg(feature = "cluster")]
     if let Some(ref raft) = state.raft {
@@ -605,42 +749,403 @@ pub async fn list_triples(
     }
 
     let namespace = ns_ext.and_then(|axum::Extension(RequestNamespace(ns))| ns);
-    let resp = crate::service::triples::list_triples(&state, query, namespace).await?;
+    let subject = query.subject.clone().map(|s| NodeId::named(&s));
+    let predicate = query.predicate.clone().map(|p| Predicate::named(&p));
+    let mut resp = crate::service::triples::list_triples(&state, query, namespace.clone()).await?;
+
+    if deleted.include_deleted {
+        let graph = state.graph.read().await;
+        for record in state.tombstones.deleted()? {
+            let t = &record.triple;
+            if subject.as_ref().is_some_and(|s| &t.subject != s)
+                || predicate.as_ref().is_some_and(|p| &t.predicate != p)
+                || namespace
+                    .as_ref()
+                    .is_some_and(|ns| !is_in_namespace(&t.subject.to_string(), ns))
+                // Re-created through a path that bypassed the tombstone store
+                || graph.get(&t.id())?.is_some()
+            {
+                continue;
+            }
+            resp.deleted.push(DeletedTripleDto::from(record));
+        }
+    }
+
     Ok(Json(resp))
 }
 
 /// Response for listing triples
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Serialize)]
 pub struct ListTriplesResponse {
     pub triples: Vec<TripleDto>,
     pub total: usize,
     pub limit: usize,
     pub offset: usize,
+    /// Tombstoned triples, only with `include_deleted=true`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub deleted: Vec<DeletedTripleDto>,
+}
+
+/// A tombstoned triple
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize)]
+pub struct DeletedTripleDto {
+    #[serde(flatten)]
+    pub triple: TripleDto,
+    /// When the triple was deleted
+    pub deleted_at: String,
+    /// Who deleted it, when known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_by: Option<String>,
+    /// Why it was deleted, when given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl From<TripleRecord> for DeletedTripleDto {
+    fn from(record: TripleRecord) -> Self {
+        let deletion = record.deletion().cloned();
+        Self {
+            deleted_at: record
+                .deleted_at
+                .map(|at| at.to_rfc3339())
+                .unwrap_or_default(),
+            deleted_by: deletion.as_ref().and_then(|e| e.actor.clone()),
+            reason: deletion.and_then(|e| e.reason),
+            triple: record.triple.into(),
+        }
+    }
+}
+
+/// History of a single triple
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Serialize)]
+pub struct TripleHistoryResponse {
+    pub id: String,
+    /// `active` or `deleted`
+    pub status: String,
+    pub triple: TripleDto,
+    /// Creation and deletion events, oldest first
+    pub events: Vec<HistoryEvent>,
+}
+
+/// Get the creation/deletion history of a triple
+///
+/// GET /api/v1/triples/:id/history
+///
+/// Works for live and tombstoned triples; purged triples are not found.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/v1/triples/{id}/history",
+        tag = "triples",
+        params(
+            ("id" = String, Path, description = "Hex hash of the triple"),
+        ),
+        responses(
+            (status = 200, description = "Creation and deletion history", body = TripleHistoryResponse),
+            (status = 400, description = "Malformed triple id", body = ErrorResponse),
+            (status = 403, description = "Subject outside the caller's namespace", body = ErrorResponse),
+            (status = 404, description = "Unknown or purged triple", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn get_triple_history(
+    Dataset(state): Dataset,
+    ns_ext: Option<axum::Extension<RequestNamespace>>,
+    Path(TriplePath { id }): Path<TriplePath>,
+) -> Result<Json<TripleHistoryResponse>> {
+    let triple_id = TripleId::from_hex(&id)
+        .ok_or_else(|| Error::InvalidInput(format!("Invalid triple ID: {}", id)))?;
+
+    let live = state.graph.read().await.get(&triple_id)?;
+    let record = state.tombstones.get(&triple_id)?;
+
+    let (status, triple, events) = match (live, record) {
+        (Some(triple), Some(record)) => ("active", triple, record.events),
+        (Some(triple), None) => {
+            let created = HistoryEvent {
+                event: HistoryEventKind::Created,
+                at: triple.meta.created_at,
+                actor: triple.meta.source.clone(),
+                reason: None,
+            };
+            ("active", triple, vec![created])
+        }
+        (None, Some(record)) if record.is_deleted() => ("deleted", record.triple, record.events),
+        _ => return Err(Error::NotFound(format!("Triple {} not found", id))),
+    };
+
+    if let Some(axum::Extension(RequestNamespace(Some(ref ns)))) = ns_ext {
+        if !is_in_namespace(&triple.subject.to_string(), ns) {
+            return Err(Error::Forbidden(format!(
+                "Triple subject is not in namespace \"{}\"",
+                ns
+            )));
+        }
+    }
+
+    Ok(Json(TripleHistoryResponse {
+        id,
+        status: status.to_string(),
+        triple: triple.into(),
+        events,
+    }))
+}
+
+/// Query parameters for purging tombstones
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+#[derive(Debug, Default, Deserialize)]
+pub struct PurgeTombstonesQuery {
+    /// Purge tombstones older than this many seconds; defaults to the
+    /// configured retention
+    pub older_than_secs: Option<u64>,
+}
+
+/// Response for a tombstone purge
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Serialize)]
+pub struct PurgeTombstonesResponse {
+    /// Number of tombstones physically removed
+    pub purged: usize,
+    /// Ids of the removed triples
+    pub ids: Vec<String>,
+    /// Tombstones deleted before this instant were purged
+    pub cutoff: String,
+}
+
+/// Physically remove tombstones past their retention (admin only)
+///
+/// POST /api/v1/triples/purge?older_than_secs=N
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/v1/triples/purge",
+        tag = "triples",
+        params(
+            PurgeTombstonesQuery,
+        ),
+        responses(
+            (status = 200, description = "Tombstones past retention were removed", body = PurgeTombstonesResponse),
+            (status = 400, description = "`older_than_secs` is out of range", body = ErrorResponse),
+            (status = 403, description = "The caller lacks the admin role", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn purge_tombstones(
+    Dataset(state): Dataset,
+    ns_ext: Option<axum::Extension<RequestNamespace>>,
+    principal_ext: Option<axum::Extension<RequestPrincipal>>,
+    Query(params): Query<PurgeTombstonesQuery>,
+) -> Result<Json<PurgeTombstonesResponse>> {
+    let principal = RequestPrincipal::from_extension(principal_ext);
+    if !principal.is_admin() {
+        return Err(Error::Forbidden(
+            "Purging deleted triples requires the admin role".to_string(),
+        ));
+    }
+
+    let retention = params
+        .older_than_secs
+        .map(std::time::Duration::from_secs)
+        .unwrap_or(state.tombstone_retention);
+    let retention = chrono::Duration::from_std(retention)
+        .map_err(|_| Error::InvalidInput("older_than_secs is out of range".to_string()))?;
+    let cutoff = chrono::Utc::now()
+        .checked_sub_signed(retention)
+        .ok_or_else(|| Error::InvalidInput("older_than_secs is out of range".to_string()))?;
+
+    let ids: Vec<String> = state
+        .tombstones
+        .purge_before(cutoff)?
+        .iter()
+        .map(TripleId::to_hex)
+        .collect();
+
+    {
+        let namespace = ns_ext.and_then(|axum::Extension(RequestNamespace(ns))| ns);
+        let mut audit = state.audit_log.write().await;
+        audit.record(crate::rest::audit::AuditEntry {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            user_id: principal
+                .user_id
+                .or_else(|| namespace.clone())
+                .unwrap_or_else(|| "anonymous".to_string()),
+            namespace,
+            action: "purge".to_string(),
+            resource: "/api/v1/triples/purge".to_string(),
+            details: Some(format!(
+                "purged={}, cutoff={}",
+                ids.len(),
+                cutoff.to_rfc3339()
+            )),
+            request_id: None,
+            request: None,
+        });
+    }
+
+    Ok(Json(PurgeTombstonesResponse {
+        purged: ids.len(),
+        ids,
+        cutoff: cutoff.to_rfc3339(),
+    }))
 }
 
 /// Request to batch-insert multiple triples
 #[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Deserialize)]
 pub struct BatchInsertRequest {
     pub triples: Vec<CreateTripleRequest>,
+    /// Run each triple through the logic engine before inserting
+    #[serde(default)]
+    pub validate: bool,
+    /// All-or-nothing: if any item is rejected, nothing is inserted
+    #[serde(default = "default_atomic")]
+    pub atomic: bool,
+}
+
+fn default_atomic() -> bool {
+    true
+}
+
+impl BatchInsertRequest {
+    /// Atomic, unvalidated batch of `triples`
+    pub fn new(triples: Vec<CreateTripleRequest>) -> Self {
+        Self {
+            triples,
+            validate: false,
+            atomic: default_atomic(),
+        }
+    }
+}
+
+/// Query parameters for `POST /api/v1/triples/batch`
+///
+/// When present these override the `validate`/`atomic` fields of a JSON
+/// object body; they are the only way to set them for array and NDJSON bodies.
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+#[derive(Debug, Default, Deserialize)]
+pub struct BatchInsertQuery {
+    pub validate: Option<bool>,
+    pub atomic: Option<bool>,
+}
+
+/// Outcome of a single batch item
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchItemStatus {
+    /// Written to the graph
+    Inserted,
+    /// Already in the graph, or repeated earlier in the batch
+    Duplicate,
+    /// Failed input checks or logic validation
+    Rejected,
+    /// Valid, but not written because an atomic batch had rejections
+    Aborted,
+}
+
+impl BatchItemStatus {
+    /// Wire name of the status, as serialized in REST responses
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BatchItemStatus::Inserted => "inserted",
+            BatchItemStatus::Duplicate => "duplicate",
+            BatchItemStatus::Rejected => "rejected",
+            BatchItemStatus::Aborted => "aborted",
+        }
+    }
+}
+
+/// Per-item entry of the batch report, in request order
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchItemResult {
+    /// Position of the item in the request
+    pub index: usize,
+    pub status: BatchItemStatus,
+    /// Triple hash, for items that could be built into a triple
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Why the item was rejected
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 /// Response for batch insert
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Serialize)]
 pub struct BatchInsertResponse {
+    /// Accepted triples (inserted and duplicates)
     pub inserted: Vec<TripleDto>,
     pub total: usize,
     pub duplicates: usize,
+    /// Number of rejected items
+    pub rejected: usize,
+    /// `false` when an atomic batch was aborted and nothing was written
+    pub committed: bool,
+    /// One entry per request item
+    pub results: Vec<BatchItemResult>,
 }
 
-/// Insert multiple triples atomically
+/// Insert multiple triples
 ///
 /// POST /api/v1/triples/batch
+///
+/// The body is either a JSON array of triples, a `{"triples": [...]}` object,
+/// or NDJSON (one triple per line) when sent as `application/x-ndjson`. At
+/// most `AppState::max_batch_triples` items are accepted.
+///
+/// `?validate=true` runs each triple through the logic engine. With
+/// `?atomic=true` (the default) any rejected item aborts the whole batch and
+/// the report comes back with `422`; with `?atomic=false` valid items are
+/// inserted and rejected ones are listed in the report.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/v1/triples/batch",
+        tag = "triples",
+        request_body(
+            content = BatchInsertRequest,
+            description = "A `{\"triples\": [...]}` object or a JSON array of triples; \
+                           NDJSON (one triple per line) is accepted as `application/x-ndjson`",
+            content_type = "application/json"
+        ),
+        params(
+            BatchInsertQuery,
+        ),
+        responses(
+            (status = 201, description = "The batch was processed and committed", body = BatchInsertResponse),
+            (status = 200, description = "The batch was empty", body = BatchInsertResponse),
+            (status = 400, description = "The body could not be parsed", body = ErrorResponse),
+            (status = 403, description = "A subject is outside the caller's namespace", body = ErrorResponse),
+            (status = 422, description = "An atomic batch had rejected items and nothing was written", body = BatchInsertResponse),
+        ),
+    )
+)]
 pub async fn batch_insert_triples(
-    State(state): State<AppState>,
+    Dataset(state): Dataset,
     ns_ext: Option<axum::Extension<RequestNamespace>>,
-    Json(req): Json<BatchInsertRequest>,
+    Query(query): Query<BatchInsertQuery>,
+    headers: axum::http::HeaderMap,
+    body: axum::body::Bytes,
 ) -> Result<(StatusCode, Json<BatchInsertResponse>)> {
+    let mut req = parse_batch_body(&headers, &body)?;
+    req.validate = query.validate.unwrap_or(req.validate);
+    req.atomic = query.atomic.unwrap_or(req.atomic);
     let empty = req.triples.is_empty();
 
     // Enforce namespace scoping (transport concern — stays in REST).
@@ -659,11 +1164,13 @@ pub async fn batch_insert_triples(
         .as_ref()
         .and_then(|axum::Extension(RequestNamespace(ns))| ns.clone());
 
-    // Delegate the shared validate + atomic insert + audit + event side-effects.
+    // Delegate the shared validate + insert + audit + event side-effects.
     let resp = crate::service::triples::batch_insert(&state, req, namespace).await?;
 
     // An empty batch is a no-op success (parity with the prior handler).
-    let status = if empty {
+    let status = if !resp.committed {
+        StatusCode::UNPROCESSABLE_ENTITY
+    } else if empty {
         StatusCode::OK
     } else {
         StatusCode::CREATED
@@ -671,6 +1178,40 @@ pub async fn batch_insert_triples(
     Ok((status, Json(resp)))
 }
 
+/// Decode a batch body as NDJSON, a JSON array, or a `{"triples": [...]}` object
+fn parse_batch_body(headers: &axum::http::HeaderMap, body: &[u8]) -> Result<BatchInsertRequest> {
+    let ndjson = headers
+        .get(axum::http::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with("application/x-ndjson"));
+
+    if ndjson {
+        let text = std::str::from_utf8(body)
+            .map_err(|_| Error::InvalidInput("NDJSON body is not valid UTF-8".to_string()))?;
+        let triples = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(n, line)| {
+                serde_json::from_str(line)
+                    .map_err(|e| Error::InvalidInput(format!("NDJSON line {}: {}", n + 1, e)))
+            })
+            .collect::<Result<Vec<CreateTripleRequest>>>()?;
+        return Ok(BatchInsertRequest::new(triples));
+    }
+
+    let is_array = body
+        .iter()
+        .find(|b| !b.is_ascii_whitespace())
+        .is_some_and(|b| *b == b'[');
+    let parsed = if is_array {
+        serde_json::from_slice(body).map(BatchInsertRequest::new)
+    } else {
+        serde_json::from_slice(body)
+    };
+    parsed.map_err(|e| Error::InvalidInput(format!("Invalid batch body: {}", e)))
+}
+
 /// Re-export shared Raft write error handler for this module.
 #[cfg(feature = "cluster")]
 use crate::rest::cluster_utils::handle_raft_write_error;
@@ -3,17 +3,25 @@
 
 //! The main Córtex API server.
 
+use crate::datasets::{DatasetConfig, DatasetRegistry};
 use crate::error::Result;
+use crate::middleware::{
+    request_audit, RateLimiter, RateScope, RequestAuditConfig, RequestAuditor, ScopeLimit,
+};
 use crate::rest;
+use crate::rest::audit::AuditRotation;
+use crate::shutdown::{self, ShutdownHandle};
 use crate::state::AppState;
 
 use axum::extract::DefaultBodyLimit;
 use axum::Router;
 use std::net::SocketAddr;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
 use tower_http::cors::CorsLayer;
 use tower_http::trace::TraceLayer;
-use tracing::info;
+use tracing::{info, warn};
 
 /// Configuration for the `CortexServer`.
 #[derive(Debug, Clone)]
@@ -27,24 +35,67 @@ pub struct CortexConfig {
     /// If `true`, the GraphQL playground interface will be served at `/graphql`.
     /// **Must be false in production** (exposes schema to unauthenticated users).
     pub graphql_playground: bool,
+    /// If `true`, a Swagger UI page for the OpenAPI spec will be served at
+    /// `/api/docs` (requires the `openapi` feature). The spec itself is always
+    /// served at `/api/openapi.json`.
+    pub swagger_ui: bool,
     /// If `true`, HTTP request tracing will be enabled for debugging.
     pub tracing: bool,
-    /// If `true`, IP-based rate limiting will be enabled.
+    /// If `true`, per-client rate limiting will be enabled.
+    ///
+    /// Clients are identified by a known API key (`X-Api-Key` or the MCP
+    /// bearer token), then by JWT subject, then by IP address.
     pub rate_limit_enabled: bool,
-    /// The number of requests allowed per minute per IP address if rate limiting is enabled.
+    /// The number of requests allowed per minute per client if rate limiting is enabled.
+    ///
+    /// Applies to reads, and to writes and proof validation unless overridden.
     pub rate_limit_rpm: u32,
+    /// Limit for state-changing requests. `None` = same as `rate_limit_rpm`.
+    pub rate_limit_writes: Option<ScopeLimit>,
+    /// Limit for proof and triple validation. `None` = same as `rate_limit_rpm`.
+    pub rate_limit_proofs: Option<ScopeLimit>,
     /// Optional file path for JSONL audit log persistence.
     pub audit_log_path: Option<PathBuf>,
+    /// When the audit log file is rotated. `None` lets it grow (default:
+    /// rotate at 64 MiB, keeping every rotated file).
+    pub audit_log_rotation: Option<AuditRotation>,
+    /// Audit every write request, and reads if configured, with redacted
+    /// request summaries. `None` = only the handlers' own entries (default).
+    pub request_audit: Option<RequestAuditConfig>,
     /// Maximum request body size in bytes (default: 1MB).
     pub max_body_size: usize,
+    /// Maximum number of triples in one batch insert (default: 10,000).
+    pub max_batch_triples: usize,
+    /// Number of recent events kept so subscribers can resume from a cursor
+    /// (default: 1024, 0 = no replay).
+    pub event_replay_buffer: usize,
+    /// How long deleted triples are kept as tombstones before a purge may drop
+    /// them (default: 30 days).
+    pub tombstone_retention: std::time::Duration,
+    /// How long a shutdown waits for in-flight requests and subscriptions to
+    /// finish before the server exits anyway (default: 30 seconds).
+    pub shutdown_drain_timeout: Duration,
     /// Periodic flush interval in seconds (0 = disabled, default: 300).
     pub flush_interval_secs: u64,
+    /// Interval in seconds of the sweep that marks expired proofs
+    /// (0 = disabled, default: 60).
+    pub proof_expiry_sweep_secs: u64,
+    /// If `true`, validating a proof whose signing key has revoked proofs
+    /// logs a warning and adds it to the verification details (default: false).
+    pub warn_on_revoked_signer: bool,
     /// Path to the graph database directory.
     ///
     /// - `Some(":memory:")` — volatile in-memory storage (no persistence).
     /// - `Some(path)` — persist to the given directory.
     /// - `None` — persist to the default `~/.aingle/cortex/graph.sled`.
     pub db_path: Option<String>,
+    /// Named datasets served under `/api/v1/datasets/{name}` next to the
+    /// default one at `db_path`. Each is opened on first use.
+    pub datasets: Vec<DatasetConfig>,
+    /// Directory datasets created through `POST /api/v1/datasets` are stored
+    /// in. `None` uses a `datasets` directory next to `db_path`, or memory
+    /// when `db_path` is `":memory:"`.
+    pub datasets_dir: Option<String>,
     /// If `true`, serve MCP over stdio instead of binding a TCP listener.
     pub mcp_mode: bool,
     /// Bearer token required on the `/mcp` HTTP endpoint. None = not configured.
@@ -71,13 +122,26 @@ impl Default for CortexConfig {
             port: 19090,
             cors_allowed_origins: vec![], // CORS disabled by default
             graphql_playground: false,    // Disabled by default for security
+            swagger_ui: false,
             tracing: true,
             rate_limit_enabled: true,
             rate_limit_rpm: 100,
+            rate_limit_writes: None,
+            rate_limit_proofs: None,
             audit_log_path: None,
+            audit_log_rotation: Some(AuditRotation::default()),
+            request_audit: None,
             max_body_size: 1024 * 1024, // 1MB
+            max_batch_triples: crate::state::DEFAULT_MAX_BATCH_TRIPLES,
+            event_replay_buffer: crate::state::DEFAULT_EVENT_REPLAY_BUFFER,
+            tombstone_retention: crate::state::DEFAULT_TOMBSTONE_RETENTION,
+            shutdown_drain_timeout: Duration::from_secs(30),
             flush_interval_secs: 300,
+            proof_expiry_sweep_secs: 60,
+            warn_on_revoked_signer: false,
             db_path: None,
+            datasets: Vec::new(),
+            datasets_dir: None,
             mcp_mode: false,
             mcp_http_token: None,
             mcp_http_allow_anonymous: false,
@@ -109,6 +173,12 @@ impl CortexConfig {
         self.host = host.into();
         self
     }
+
+    /// Adds a named dataset.
+    pub fn with_dataset(mut self, dataset: DatasetConfig) -> Self {
+        self.datasets.push(dataset);
+        self
+    }
 }
 
 /// The Córtex API Server.
@@ -118,6 +188,7 @@ impl CortexConfig {
 pub struct CortexServer {
     config: CortexConfig,
     state: AppState,
+    rate_limiter: RateLimiter,
 }
 
 impl CortexServer {
@@ -130,15 +201,44 @@ impl CortexServer {
     pub fn new(config: CortexConfig) -> Result<Self> {
         let db_path = resolve_db_path(&config.db_path);
         let embedder = crate::embedder::build_embedder(config.embed_model.as_deref());
-        let state =
+        let mut state =
             AppState::with_db_path_and_embedder(&db_path, config.audit_log_path.clone(), embedder)?;
         info!("Graph database: {}", db_path);
-        Ok(Self { config, state })
+        if db_path != ":memory:" {
+            let dir = Path::new(&db_path).parent().unwrap_or(Path::new("."));
+            state.datasets = Arc::new(DatasetRegistry::with_root(dir.join("datasets")));
+        }
+        Ok(Self::with_state(config, state))
     }
 
     /// Creates a new `CortexServer` with a given configuration and a pre-existing `AppState`.
-    pub fn with_state(config: CortexConfig, state: AppState) -> Self {
-        Self { config, state }
+    pub fn with_state(config: CortexConfig, mut state: AppState) -> Self {
+        state.max_batch_triples = config.max_batch_triples;
+        state.tombstone_retention = config.tombstone_retention;
+        state
+            .proof_store
+            .set_warn_on_revoked_signer(config.warn_on_revoked_signer);
+        state
+            .broadcaster
+            .set_replay_capacity(config.event_replay_buffer);
+        match state.audit_log.try_write() {
+            Ok(mut audit_log) => audit_log.set_rotation(config.audit_log_rotation),
+            Err(_) => warn!("Audit log in use, keeping its rotation settings"),
+        }
+        if let Some(ref dir) = config.datasets_dir {
+            state.datasets = Arc::new(DatasetRegistry::with_root(dir));
+        }
+        for dataset in &config.datasets {
+            if let Err(e) = state.datasets.register(dataset.clone()) {
+                warn!("Skipping dataset \"{}\": {}", dataset.name, e);
+            }
+        }
+        let rate_limiter = build_rate_limiter(&config);
+        Self {
+            config,
+            state,
+            rate_limiter,
+        }
     }
 
     /// Returns a reference to the shared `AppState`.
@@ -156,6 +256,17 @@ impl CortexServer {
         &self.config
     }
 
+    /// Returns the rate limiter, whose counters are shared with the running router.
+    pub fn rate_limiter(&self) -> &RateLimiter {
+        &self.rate_limiter
+    }
+
+    /// Returns a handle that shuts the running server down gracefully when
+    /// triggered.
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.state.shutdown.clone()
+    }
+
     /// Builds the `axum` router, combining all API routes and middleware.
     pub fn build_router(&self) -> Router {
         let mut app: Router<AppState> = Router::new();
@@ -163,6 +274,12 @@ impl CortexServer {
         // Add REST API routes.
         app = app.merge(rest::router());
 
+        // Add the Swagger UI page if enabled.
+        #[cfg(feature = "openapi")]
+        if self.config.swagger_ui {
+            app = app.merge(rest::openapi::swagger_ui_router());
+        }
+
         // Add SPARQL routes if the feature is enabled.
         #[cfg(feature = "sparql")]
         {
@@ -175,6 +292,14 @@ impl CortexServer {
             app = app.merge(crate::auth::router());
         }
 
+        // Audit requests. Added before the namespace extraction below, so that
+        // it runs inside it and sees the caller's principal.
+        if let Some(ref audit) = self.config.request_audit {
+            let auditor = RequestAuditor::new(audit.clone(), self.state.audit_log.clone())
+                .with_max_body_size(self.config.max_body_size);
+            app = app.layer(axum::middleware::from_fn_with_state(auditor, request_audit));
+        }
+
         // Add namespace extraction middleware (requires auth feature for JWT parsing).
         #[cfg(feature = "auth")]
         let app = {
@@ -271,12 +396,7 @@ impl CortexServer {
 
         // Rate limiting layer.
         let app = if self.config.rate_limit_enabled {
-            use crate::middleware::RateLimiter;
-
-            let rate_limiter = RateLimiter::new(self.config.rate_limit_rpm)
-                .with_burst_capacity(self.config.rate_limit_rpm);
-
-            app.layer(rate_limiter.into_layer())
+            app.layer(self.rate_limiter.clone().into_layer())
         } else {
             app
         };
@@ -320,13 +440,58 @@ impl CortexServer {
         }
     }
 
-    /// Runs the server indefinitely.
+    /// Runs the server until SIGINT, SIGTERM or the
+    /// [`shutdown_handle`](Self::shutdown_handle) is triggered.
+    ///
+    /// On shutdown the server stops accepting connections, lets in-flight
+    /// requests finish and closes event subscriptions with a "server
+    /// restarting" frame, waiting at most
+    /// [`shutdown_drain_timeout`](CortexConfig::shutdown_drain_timeout). The
+    /// graph, proof store and tombstones are flushed before it returns.
+    /// If cluster TLS is configured, the server will accept HTTPS connections.
     pub async fn run(self) -> Result<()> {
+        let shutdown = self.shutdown_handle();
+        let signals = tokio::spawn(async move {
+            shutdown::os_signal().await;
+            shutdown.trigger();
+        });
+        let result = self.serve().await;
+        signals.abort();
+        result
+    }
+
+    /// Runs the server with a graceful shutdown signal.
+    ///
+    /// The server will run until the `shutdown_signal` future completes or
+    /// the [`shutdown_handle`](Self::shutdown_handle) is triggered, then
+    /// drains and flushes as [`run`](Self::run) does. OS signals are left to
+    /// the caller.
+    pub async fn run_with_shutdown<F>(self, shutdown_signal: F) -> Result<()>
+    where
+        F: std::future::Future<Output = ()> + Send + 'static,
+    {
+        let shutdown = self.shutdown_handle();
+        let signal = tokio::spawn(async move {
+            shutdown_signal.await;
+            shutdown.trigger();
+        });
+        let result = self.serve().await;
+        signal.abort();
+        result
+    }
+
+    /// Serves until the shutdown handle is triggered, then drains and flushes.
+    async fn serve(self) -> Result<()> {
         let addr: SocketAddr = format!("{}:{}", self.config.host, self.config.port)
             .parse()
             .map_err(|e| crate::error::Error::Internal(format!("Invalid address: {}", e)))?;
 
         let router = self.build_router();
+        let shutdown = self.shutdown_handle();
+        let stop = {
+            let shutdown = shutdown.clone();
+            async move { shutdown.triggered().await }
+        };
 
         #[cfg(feature = "cluster")]
         if let Some(ref tls_config) = self.state.tls_server_config {
@@ -338,8 +503,10 @@ impl CortexServer {
                 inner: tcp_listener,
                 acceptor: tls_acceptor,
             };
-            axum::serve(tls_listener, router.into_make_service()).await?;
-            return Ok(());
+            let server =
+                axum::serve(tls_listener, router.into_make_service()).with_graceful_shutdown(stop);
+            drain(server, &shutdown, self.config.shutdown_drain_timeout).await?;
+            return self.finish().await;
         }
 
         info!("Starting Córtex API server on http://{}", addr);
@@ -350,59 +517,81 @@ impl CortexServer {
         info!("SPARQL: http://{}/sparql", addr);
 
         let listener = tokio::net::TcpListener::bind(addr).await?;
-        axum::serve(
+        let server = axum::serve(
             listener,
             router.into_make_service_with_connect_info::<SocketAddr>(),
         )
-        .await?;
-
-        Ok(())
+        .with_graceful_shutdown(stop);
+        drain(server, &shutdown, self.config.shutdown_drain_timeout).await?;
+        self.finish().await
     }
 
-    /// Runs the server with a graceful shutdown signal.
+    /// Flushes the stores once the server has drained.
     ///
-    /// The server will run until the `shutdown_signal` future completes.
-    /// If cluster TLS is configured, the server will accept HTTPS connections.
-    pub async fn run_with_shutdown<F>(self, shutdown_signal: F) -> Result<()>
-    where
-        F: std::future::Future<Output = ()> + Send + 'static,
-    {
-        let addr: SocketAddr = format!("{}:{}", self.config.host, self.config.port)
-            .parse()
-            .map_err(|e| crate::error::Error::Internal(format!("Invalid address: {}", e)))?;
-
-        let router = self.build_router();
-
-        #[cfg(feature = "cluster")]
-        if let Some(ref tls_config) = self.state.tls_server_config {
-            info!("Starting Córtex API server on https://{}", addr);
-
-            let tls_acceptor = tokio_rustls::TlsAcceptor::from(tls_config.clone());
-            let tcp_listener = tokio::net::TcpListener::bind(addr).await?;
-            let tls_listener = TlsListener {
-                inner: tcp_listener,
-                acceptor: tls_acceptor,
-            };
-            axum::serve(tls_listener, router.into_make_service())
-                .with_graceful_shutdown(shutdown_signal)
-                .await?;
-
-            info!("Córtex API server stopped");
-            return Ok(());
+    /// Audit entries are written as they are recorded and need no flush; the
+    /// Ineru snapshot is left to the embedder, which knows where it lives.
+    async fn finish(self) -> Result<()> {
+        match self.state.flush(None).await {
+            Ok(()) => info!("Data flushed on shutdown"),
+            Err(e) => tracing::error!("Failed to flush data on shutdown: {}", e),
         }
+        info!("Córtex API server stopped");
+        Ok(())
+    }
+}
 
-        info!("Starting Córtex API server on http://{}", addr);
+/// Drives `server` until the shutdown is triggered, then gives open
+/// connections and event subscriptions up to `timeout` to finish.
+///
+/// Connections still open when the timeout elapses are abandoned.
+async fn drain<S>(server: S, shutdown: &ShutdownHandle, timeout: Duration) -> Result<()>
+where
+    S: std::future::IntoFuture<Output = std::io::Result<()>>,
+{
+    let server = server.into_future();
+    tokio::pin!(server);
+
+    tokio::select! {
+        result = &mut server => return Ok(result?),
+        _ = shutdown.triggered() => {}
+    }
+    info!(
+        "Shutting down — draining connections for up to {}s",
+        timeout.as_secs_f64()
+    );
+
+    let deadline = tokio::time::Instant::now() + timeout;
+    match tokio::time::timeout_at(deadline, &mut server).await {
+        Ok(result) => result?,
+        Err(_) => warn!("Drain timeout elapsed with requests still in flight"),
+    }
+    if tokio::time::timeout_at(deadline, shutdown.sessions_closed())
+        .await
+        .is_err()
+    {
+        warn!(
+            open_sessions = shutdown.open_sessions(),
+            "Drain timeout elapsed with event subscriptions still open"
+        );
+    }
+    Ok(())
+}
 
-        let listener = tokio::net::TcpListener::bind(addr).await?;
-        axum::serve(
-            listener,
-            router.into_make_service_with_connect_info::<SocketAddr>(),
+/// Build the per-client rate limiter described by `config`.
+fn build_rate_limiter(config: &CortexConfig) -> RateLimiter {
+    let default = ScopeLimit::new(config.rate_limit_rpm);
+    let limiter = RateLimiter::new(config.rate_limit_rpm)
+        .with_scope_limit(
+            RateScope::Write,
+            config.rate_limit_writes.unwrap_or(default),
         )
-        .with_graceful_shutdown(shutdown_signal)
-        .await?;
-
-        info!("Córtex API server stopped");
-        Ok(())
+        .with_scope_limit(
+            RateScope::Proof,
+            config.rate_limit_proofs.unwrap_or(default),
+        );
+    match &config.mcp_http_token {
+        Some(token) => limiter.with_api_keys([token]),
+        None => limiter,
     }
 }
 
@@ -476,6 +665,7 @@ mod tests {
         assert_eq!(config.host, "127.0.0.1");
         assert_eq!(config.port, 19090);
         assert!(config.cors_allowed_origins.is_empty());
+        assert_eq!(config.shutdown_drain_timeout, Duration::from_secs(30));
     }
 
     #[test]
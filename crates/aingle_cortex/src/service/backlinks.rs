@@ -95,8 +95,8 @@ pub async fn backlinks(state: &crate::state::AppState, note: &str) -> Backlinks
     let mut text_of: BTreeMap<String, String> = BTreeMap::new();
     {
         let mem = state.memory.read().await;
-        let mut entries = mem.stm.all_entries();
-        entries.extend(mem.ltm.all_entries());
+        let mut entries = mem.stm().all_entries();
+        entries.extend(mem.ltm().all_entries());
         for e in entries {
             if e.entry_type != crate::service::ingest::CHUNK_ENTRY_TYPE {
                 continue;
@@ -128,7 +128,9 @@ pub async fn note_context(state: &crate::state::AppState, note: &str, limit: usi
     let mut own_text = String::new();
     let (stm_entries, ltm_entries) = {
         let mem = state.memory.read().await;
-        (mem.stm.all_entries(), mem.ltm.all_entries())
+        let stm_entries = mem.stm().all_entries();
+        let ltm_entries = mem.ltm().all_entries();
+        (stm_entries, ltm_entries)
     };
     for e in stm_entries.iter().chain(ltm_entries.iter()) {
         if e.entry_type != crate::service::ingest::CHUNK_ENTRY_TYPE {
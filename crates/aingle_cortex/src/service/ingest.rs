@@ -379,7 +379,7 @@ pub async fn ingest_path_with_progress(
             let texts: Vec<String> = extraction.chunks.iter().map(|c| c.text.clone()).collect();
             let embeddings = state.embedder.embed_passages(&texts);
 
-            let mut mem = state.memory.write().await;
+            let mem = state.memory.write().await;
             for (chunk, embedding) in extraction.chunks.iter().zip(embeddings) {
                 let mut entry = MemoryEntry::new(
                     CHUNK_ENTRY_TYPE,
@@ -479,12 +479,12 @@ async fn purge_source(state: &AppState, rel_path: &str, namespace: Option<String
 
     // Ineru: forget every chunk that came from this source.
     {
-        let mut mem = state.memory.write().await;
+        let mem = state.memory.write().await;
         let ids: Vec<MemoryId> = mem
-            .stm
+            .stm()
             .all_entries()
             .into_iter()
-            .chain(mem.ltm.all_entries())
+            .chain(mem.ltm().all_entries())
             .filter(|e| e.entry_type == CHUNK_ENTRY_TYPE && e.metadata.source == rel_path)
             .map(|e| e.id)
             .collect();
@@ -4,7 +4,11 @@
 //! Proof verification business logic shared by REST and MCP.
 
 use crate::error::{Error, Result};
-use crate::rest::{GetProofRequest, ProofResponse, VerifyProofByIdRequest, VerifyProofResponse};
+use crate::proofs::{SubmitProofRequest, VerificationError};
+use crate::rest::{
+    GetProofRequest, ProofResponse, ProofStatusResponse, ValidateProofResponse,
+    VerifyProofByIdRequest, VerifyProofResponse,
+};
 use crate::state::AppState;
 
 /// Fetch a stored proof by its ID.
@@ -24,6 +28,27 @@ pub async fn get_proof(state: &AppState, req: GetProofRequest) -> Result<ProofRe
     Ok(ProofResponse::from(proof))
 }
 
+/// Fetch the lifecycle status of a stored proof.
+///
+/// Semantics:
+/// - Proof exists -> `Ok(ProofStatusResponse)` with its current status
+///   (valid, expired or revoked) and the record of its validation.
+/// - Proof does not exist -> `Err(Error::NotFound(..))`.
+pub async fn get_proof_status(
+    state: &AppState,
+    req: GetProofRequest,
+) -> Result<ProofStatusResponse> {
+    let proof_id = req.proof_id;
+
+    let proof = state
+        .proof_store
+        .get(&proof_id)
+        .await
+        .ok_or_else(|| Error::NotFound(format!("Proof {} not found", proof_id)))?;
+
+    Ok(ProofStatusResponse::from(proof))
+}
+
 /// Verify a stored proof by its ID.
 ///
 /// Semantics (preserved from commit 53cca2c, "proof verify endpoint returns
@@ -48,7 +73,7 @@ pub async fn verify_proof(
             details: result.details,
             verification_time_us: result.verification_time_us,
         }),
-        Err(crate::proofs::VerificationError::ProofNotFound(_)) => {
+        Err(VerificationError::ProofNotFound(_)) => {
             Err(Error::NotFound(format!("Proof {} not found", proof_id)))
         }
         Err(e) => {
@@ -65,10 +90,38 @@ pub async fn verify_proof(
     }
 }
 
+/// Verify a proof and store it together with the outcome.
+///
+/// Semantics:
+/// - Proof verifies -> `Ok(ValidateProofResponse { valid: true, .. })`.
+/// - Proof parses but does not verify (wrong leaf, tampered commitment) ->
+///   `Ok(ValidateProofResponse { valid: false, .. })`; the proof is still
+///   stored so the failed attempt is on record.
+/// - Proof data is malformed, or of a kind that cannot be checked in full ->
+///   `Err(Error::InvalidInput(..))` and nothing is stored.
+pub async fn validate_proof(
+    state: &AppState,
+    req: SubmitProofRequest,
+) -> Result<ValidateProofResponse> {
+    match state.proof_store.validate(req).await {
+        Ok((proof, result)) => Ok(ValidateProofResponse {
+            status: proof.current_status(),
+            proof_id: proof.id,
+            proof_type: proof.proof_type,
+            valid: result.valid,
+            verified_at: result.verified_at,
+            details: result.details,
+            verification_time_us: result.verification_time_us,
+        }),
+        Err(VerificationError::Storage(e)) => Err(Error::Internal(e)),
+        Err(e) => Err(Error::InvalidInput(e.to_string())),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::proofs::{ProofType, SubmitProofRequest};
+    use crate::proofs::ProofType;
 
     #[tokio::test]
     async fn verifying_invalid_proof_returns_valid_false() {
@@ -85,6 +138,7 @@ mod tests {
                 proof_type: ProofType::Schnorr,
                 proof_data: serde_json::json!({ "garbage": "not-a-zk-proof" }),
                 metadata: None,
+                expires_at: None,
             })
             .await
             .expect("submit should succeed; only verification is expected to fail");
@@ -127,6 +181,7 @@ mod tests {
                 proof_type: ProofType::Schnorr,
                 proof_data: serde_json::json!({ "some": "data" }),
                 metadata: None,
+                expires_at: None,
             })
             .await
             .expect("submit should succeed");
@@ -143,4 +198,139 @@ mod tests {
         assert_eq!(resp.id, proof_id);
         assert_eq!(resp.proof_type, ProofType::Schnorr);
     }
+
+    fn membership_request(leaf: Option<&[u8]>) -> SubmitProofRequest {
+        let leaves: Vec<&[u8]> = vec![b"alice", b"bob", b"carol", b"dave"];
+        let tree = aingle_zk::MerkleTree::new(&leaves).unwrap();
+        let mut data = serde_json::json!({
+            "root": tree.root(),
+            "proof": tree.prove(2).unwrap(),
+        });
+        if let Some(leaf) = leaf {
+            data["leaf"] = serde_json::Value::String(hex::encode(leaf));
+        }
+        SubmitProofRequest {
+            proof_type: ProofType::Membership,
+            proof_data: data,
+            metadata: None,
+            expires_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn validating_membership_proof_checks_the_leaf() {
+        let state = AppState::with_db_path(":memory:", None).unwrap();
+
+        let resp = validate_proof(&state, membership_request(Some(b"carol")))
+            .await
+            .expect("well-formed proof must validate");
+        assert!(resp.valid, "{:?}", resp.details);
+
+        let resp = validate_proof(&state, membership_request(Some(b"mallory")))
+            .await
+            .expect("a leaf that is not a member is an answer, not an error");
+        assert!(!resp.valid);
+
+        // The outcome is stored with the proof
+        let stored = state.proof_store.get(&resp.proof_id).await.unwrap();
+        assert!(!stored.verified);
+        assert!(stored.verified_at.is_some());
+        assert!(!stored.verification_details.is_empty());
+    }
+
+    #[tokio::test]
+    async fn validating_malformed_membership_proof_is_invalid_input() {
+        let state = AppState::with_db_path(":memory:", None).unwrap();
+
+        let err = validate_proof(&state, membership_request(None))
+            .await
+            .expect_err("a membership proof without its leaf cannot be checked");
+        assert!(matches!(err, Error::InvalidInput(_)), "got {err:?}");
+
+        let mut req = membership_request(Some(b"carol"));
+        req.proof_data["leaf"] = serde_json::json!("not hex");
+        let err = validate_proof(&state, req).await.unwrap_err();
+        assert!(matches!(err, Error::InvalidInput(_)), "got {err:?}");
+
+        // Nothing was stored for the rejected requests
+        assert_eq!(state.proof_store.stats().await.total_proofs, 0);
+    }
+
+    #[cfg(feature = "bulletproofs")]
+    fn range_request(proof: &aingle_zk::RangeProof) -> SubmitProofRequest {
+        SubmitProofRequest {
+            proof_type: ProofType::Range,
+            proof_data: serde_json::json!({
+                "commitment": proof.commitment,
+                "proof_bytes": proof.proof_bytes,
+                "n_bits": proof.n_bits,
+            }),
+            metadata: None,
+            expires_at: None,
+        }
+    }
+
+    #[cfg(feature = "bulletproofs")]
+    #[tokio::test]
+    async fn validating_range_proof() {
+        let state = AppState::with_db_path(":memory:", None).unwrap();
+        let generator = aingle_zk::RangeProofGenerator::new(32);
+        let proof = generator.prove(1_000).unwrap();
+
+        let resp = validate_proof(&state, range_request(&proof)).await.unwrap();
+        assert!(resp.valid, "{:?}", resp.details);
+        assert_eq!(resp.proof_type, ProofType::Range);
+        let stored = state.proof_store.get(&resp.proof_id).await.unwrap();
+        assert!(stored.verified);
+
+        // A proof paired with another value's commitment does not verify
+        let other = generator.prove(7).unwrap();
+        let mut tampered = proof.clone();
+        tampered.commitment = other.commitment;
+        let resp = validate_proof(&state, range_request(&tampered))
+            .await
+            .unwrap();
+        assert!(!resp.valid);
+    }
+
+    #[cfg(feature = "bulletproofs")]
+    #[tokio::test]
+    async fn validating_malformed_range_proof_is_invalid_input() {
+        let state = AppState::with_db_path(":memory:", None).unwrap();
+        let proof = aingle_zk::RangeProofGenerator::new(32)
+            .prove(1_000)
+            .unwrap();
+
+        let mut truncated = proof.clone();
+        truncated.proof_bytes.truncate(40);
+        let err = validate_proof(&state, range_request(&truncated))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidInput(_)), "got {err:?}");
+
+        let mut bad_bits = proof.clone();
+        bad_bits.n_bits = 1 << 20;
+        let err = validate_proof(&state, range_request(&bad_bits))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidInput(_)), "got {err:?}");
+    }
+
+    #[cfg(not(feature = "bulletproofs"))]
+    #[tokio::test]
+    async fn validating_range_proof_without_bulletproofs_is_refused() {
+        let state = AppState::with_db_path(":memory:", None).unwrap();
+        let req = SubmitProofRequest {
+            proof_type: ProofType::Range,
+            proof_data: serde_json::json!({
+                "commitment": vec![0u8; 32],
+                "proof_bytes": vec![0u8; 64],
+                "n_bits": 32,
+            }),
+            metadata: None,
+            expires_at: None,
+        };
+        let err = validate_proof(&state, req).await.unwrap_err();
+        assert!(matches!(err, Error::InvalidInput(_)), "got {err:?}");
+    }
 }
@@ -3,9 +3,16 @@
 
 //! SPARQL execution business logic shared by REST and MCP.
 
-use crate::error::Result;
-use crate::sparql::{execute_query, parse_sparql, SparqlRequest, SparqlResponse};
-use crate::state::AppState;
+use crate::error::{Error, Result};
+use crate::middleware::is_in_namespace;
+use crate::rest::audit::AuditEntry;
+use crate::rest::ValueDto;
+use crate::sparql::{
+    check_update, execute_query, parse_sparql, parse_sparql_update, plan_update, SparqlRequest,
+    SparqlResponse, SparqlUpdateResponse,
+};
+use crate::state::{AppState, Event};
+use aingle_graph::Triple;
 
 /// Parse and execute a SPARQL query against the shared graph.
 ///
@@ -33,6 +40,125 @@ pub async fn execute(state: &AppState, req: SparqlRequest) -> Result<SparqlRespo
     })
 }
 
+/// Parse and apply a SPARQL update to the shared graph.
+///
+/// The whole request is checked before anything is written, so an unsupported
+/// or malformed operation changes nothing. Operations then apply in order,
+/// each seeing the ones before it: triples are removed with `delete` and
+/// added with one `insert_batch` per operation. With a `namespace`, every
+/// triple an operation touches must have its subject in it. Removed triples
+/// keep a tombstone, one `sparql_update` audit entry names `actor` (falling
+/// back to the namespace), and an event is broadcast per changed triple.
+pub async fn update(
+    state: &AppState,
+    update: &str,
+    namespace: Option<String>,
+    actor: Option<String>,
+) -> Result<SparqlUpdateResponse> {
+    let start = std::time::Instant::now();
+
+    let parsed = parse_sparql_update(update)?;
+    check_update(&parsed)?;
+
+    let mut inserted: Vec<Triple> = Vec::new();
+    let mut deleted: Vec<Triple> = Vec::new();
+    {
+        let graph = state.graph.read().await;
+        for operation in &parsed.update.operations {
+            let changes = plan_update(&graph, operation)?;
+
+            if let Some(ref ns) = namespace {
+                let outside = changes
+                    .delete
+                    .iter()
+                    .chain(&changes.insert)
+                    .find(|t| !t.subject.as_name().is_some_and(|s| is_in_namespace(s, ns)));
+                if let Some(triple) = outside {
+                    return Err(Error::Forbidden(format!(
+                        "Subject \"{}\" is not in namespace \"{}\"",
+                        triple.subject, ns
+                    )));
+                }
+            }
+
+            for triple in changes.delete {
+                if graph.delete(&triple.id())? {
+                    deleted.push(triple);
+                }
+            }
+
+            let mut seen = std::collections::HashSet::new();
+            let mut new_triples = Vec::new();
+            for triple in changes.insert {
+                let id = triple.id();
+                if seen.insert(id.clone()) && graph.get(&id)?.is_none() {
+                    new_triples.push(triple);
+                }
+            }
+            if !new_triples.is_empty() {
+                graph.insert_batch(new_triples.clone())?;
+                inserted.extend(new_triples);
+            }
+        }
+    }
+
+    let actor = actor.or_else(|| namespace.clone());
+    let now = chrono::Utc::now();
+    for triple in &deleted {
+        state
+            .tombstones
+            .record_deletion(triple, actor.clone(), None, now)?;
+    }
+    for triple in &inserted {
+        state
+            .tombstones
+            .record_creation(&triple.id(), namespace.clone(), now)?;
+    }
+
+    {
+        let mut audit = state.audit_log.write().await;
+        audit.record(AuditEntry {
+            timestamp: now.to_rfc3339(),
+            user_id: actor.unwrap_or_else(|| "anonymous".to_string()),
+            namespace,
+            action: "sparql_update".to_string(),
+            resource: "/api/v1/sparql/update".to_string(),
+            details: Some(format!(
+                "operations={}, inserted={}, deleted={}",
+                parsed.update.operations.len(),
+                inserted.len(),
+                deleted.len()
+            )),
+            request_id: None,
+            request: None,
+        });
+    }
+
+    for triple in &deleted {
+        state.broadcaster.broadcast(Event::TripleDeleted {
+            hash: triple.id().to_hex(),
+        });
+    }
+    for triple in &inserted {
+        state.broadcaster.broadcast(Event::TripleAdded {
+            hash: triple.id().to_hex(),
+            subject: triple
+                .subject
+                .as_name()
+                .map_or_else(|| triple.subject.to_string(), str::to_string),
+            predicate: triple.predicate.as_str().to_string(),
+            object: serde_json::to_value(ValueDto::from(triple.object.clone())).unwrap_or_default(),
+        });
+    }
+
+    Ok(SparqlUpdateResponse {
+        operations: parsed.update.operations.len(),
+        inserted: inserted.len(),
+        deleted: deleted.len(),
+        execution_time_ms: start.elapsed().as_millis() as u64,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
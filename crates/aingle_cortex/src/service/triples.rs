@@ -6,8 +6,8 @@
 use crate::error::{Error, Result};
 use crate::rest::audit::AuditEntry;
 use crate::rest::{
-    BatchInsertRequest, BatchInsertResponse, CreateTripleRequest, ListTriplesQuery,
-    ListTriplesResponse, TripleDto,
+    BatchInsertRequest, BatchInsertResponse, BatchItemResult, BatchItemStatus, CreateTripleRequest,
+    ListTriplesQuery, ListTriplesResponse, TripleDto,
 };
 use crate::state::{AppState, Event};
 use aingle_graph::{NodeId, Predicate, Triple, TripleId, TriplePattern, Value};
@@ -126,6 +126,11 @@ pub async fn insert_triple_inner(
         id
     };
 
+    // Re-asserting a deleted triple brings it back; close its tombstone.
+    state
+        .tombstones
+        .record_creation(&triple_id, namespace.clone(), chrono::Utc::now())?;
+
     {
         let mut audit = state.audit_log.write().await;
         audit.record(AuditEntry {
@@ -136,6 +141,7 @@ pub async fn insert_triple_inner(
             resource: format!("/api/v1/triples/{}", triple_id.to_hex()),
             details: Some(format!("subject={}", subject)),
             request_id: None,
+            request: None,
         });
     }
 
@@ -149,13 +155,21 @@ pub async fn insert_triple_inner(
     Ok(triple.into())
 }
 
-/// Atomic bulk insert of triples, returning the per-row stored forms plus
-/// insert/duplicate counts.
+/// Bulk insert of triples, returning the stored forms, counts, and a per-item
+/// report in request order.
+///
+/// Mirrors the REST batch handler's non-cluster direct-write path: checks
+/// every row (non-empty subject/predicate, plus the logic engine when
+/// `req.validate` is set), drops rows already in the graph or repeated in the
+/// batch as duplicates, and writes the rest with a single atomic
+/// `insert_batch`. A `batch_create` audit entry is recorded and a
+/// `TripleAdded` event is broadcast per newly inserted row. `namespace` scopes
+/// the audit entry.
 ///
-/// Mirrors the REST batch handler's non-cluster direct-write path: validates
-/// every row, performs an atomic `insert_batch` (which silently skips
-/// duplicates), records a single `batch_create` audit entry, and broadcasts a
-/// `TripleAdded` event per row. `namespace` scopes the audit entry.
+/// With `req.atomic` any rejected row aborts the batch: nothing is written and
+/// the response has `committed: false`, with the valid rows marked
+/// `aborted`. Otherwise valid rows are inserted and rejected rows are only
+/// reported. Batches larger than `state.max_batch_triples` are refused.
 ///
 /// NOTE: cluster/Raft routing and namespace ENFORCEMENT are transport concerns
 /// and remain in the REST handler.
@@ -164,78 +178,136 @@ pub async fn batch_insert(
     req: BatchInsertRequest,
     namespace: Option<String>,
 ) -> Result<BatchInsertResponse> {
-    if req.triples.is_empty() {
-        return Ok(BatchInsertResponse {
-            inserted: vec![],
-            total: 0,
-            duplicates: 0,
-        });
+    if req.triples.len() > state.max_batch_triples {
+        return Err(Error::InvalidInput(format!(
+            "Batch of {} triples exceeds the limit of {}",
+            req.triples.len(),
+            state.max_batch_triples
+        )));
     }
 
-    // Validate all inputs first
-    for (i, t) in req.triples.iter().enumerate() {
-        if t.subject.is_empty() {
-            return Err(Error::InvalidInput(format!(
-                "Triple [{}]: subject cannot be empty",
-                i
-            )));
-        }
-        if t.predicate.is_empty() {
-            return Err(Error::InvalidInput(format!(
-                "Triple [{}]: predicate cannot be empty",
-                i
-            )));
-        }
-    }
+    // Check every row and sort it into rejected / duplicate / pending.
+    let mut results = Vec::with_capacity(req.triples.len());
+    let mut pending: Vec<(usize, Triple)> = Vec::new();
+    let mut accepted: Vec<(usize, TripleId)> = Vec::new();
+    {
+        let logic = if req.validate {
+            Some(state.logic.read().await)
+        } else {
+            None
+        };
+        let graph = state.graph.read().await;
+        let mut seen = std::collections::HashSet::new();
+
+        for (index, t) in req.triples.iter().enumerate() {
+            let rejected = |error: String, id: Option<String>| BatchItemResult {
+                index,
+                status: BatchItemStatus::Rejected,
+                id,
+                error: Some(error),
+            };
+            if t.subject.is_empty() {
+                results.push(rejected("subject cannot be empty".to_string(), None));
+                continue;
+            }
+            if t.predicate.is_empty() {
+                results.push(rejected("predicate cannot be empty".to_string(), None));
+                continue;
+            }
 
-    // Build Triple objects
-    let triples: Vec<Triple> = req
-        .triples
-        .iter()
-        .map(|t| {
-            let object: Value = t.object.clone().into();
-            Triple::new(
+            let triple = Triple::new(
                 NodeId::named(&t.subject),
                 Predicate::named(&t.predicate),
-                object,
-            )
-        })
-        .collect();
+                t.object.clone().into(),
+            );
+            let id = triple.id();
+
+            if let Some(ref logic) = logic {
+                let validation = logic.validate(&triple);
+                if !validation.is_valid() {
+                    let reasons: Vec<String> = validation
+                        .rejections
+                        .iter()
+                        .map(|r| format!("{}: {}", r.rule_id, r.reason))
+                        .collect();
+                    results.push(rejected(reasons.join("; "), Some(id.to_hex())));
+                    continue;
+                }
+            }
 
-    let count_before = {
-        let graph = state.graph.read().await;
-        graph.count()
-    };
+            let status = if !seen.insert(id.clone()) || graph.get(&id)?.is_some() {
+                BatchItemStatus::Duplicate
+            } else {
+                pending.push((index, triple));
+                BatchItemStatus::Inserted
+            };
+            results.push(BatchItemResult {
+                index,
+                status,
+                id: Some(id.to_hex()),
+                error: None,
+            });
+            accepted.push((index, id));
+        }
+    }
 
-    // Atomic batch insert
-    let ids = {
-        let graph = state.graph.read().await;
-        graph.insert_batch(triples)?
-    };
+    let rejected = results
+        .iter()
+        .filter(|r| r.status == BatchItemStatus::Rejected)
+        .count();
+    let duplicates = results
+        .iter()
+        .filter(|r| r.status == BatchItemStatus::Duplicate)
+        .count();
 
-    let count_after = {
-        let graph = state.graph.read().await;
-        graph.count()
-    };
+    if req.atomic && rejected > 0 {
+        for result in &mut results {
+            if result.status != BatchItemStatus::Rejected {
+                result.status = BatchItemStatus::Aborted;
+            }
+        }
+        return Ok(BatchInsertResponse {
+            inserted: vec![],
+            total: 0,
+            duplicates: 0,
+            rejected,
+            committed: false,
+            results,
+        });
+    }
 
-    let actually_inserted = count_after - count_before;
-    let duplicates = ids.len() - actually_inserted;
+    // Atomic batch insert of the new rows
+    let new_rows: Vec<usize> = pending.iter().map(|(index, _)| *index).collect();
+    if !pending.is_empty() {
+        let graph = state.graph.read().await;
+        graph.insert_batch(pending.into_iter().map(|(_, t)| t).collect())?;
+    }
+    let now = chrono::Utc::now();
+    for (index, id) in &accepted {
+        if results[*index].status == BatchItemStatus::Inserted {
+            state
+                .tombstones
+                .record_creation(id, namespace.clone(), now)?;
+        }
+    }
 
     // Build response DTOs
-    let inserted: Vec<TripleDto> = ids
+    let created_at = chrono::Utc::now().to_rfc3339();
+    let inserted: Vec<TripleDto> = accepted
         .iter()
-        .zip(req.triples.iter())
-        .map(|(id, t)| TripleDto {
-            id: Some(id.to_hex()),
-            subject: format!("<{}>", t.subject),
-            predicate: format!("<{}>", t.predicate),
-            object: t.object.clone(),
-            created_at: Some(chrono::Utc::now().to_rfc3339()),
+        .map(|(index, id)| {
+            let t = &req.triples[*index];
+            TripleDto {
+                id: Some(id.to_hex()),
+                subject: format!("<{}>", t.subject),
+                predicate: format!("<{}>", t.predicate),
+                object: t.object.clone(),
+                created_at: Some(created_at.clone()),
+            }
         })
         .collect();
 
-    // Record audit entry
-    {
+    if !req.triples.is_empty() {
         let mut audit = state.audit_log.write().await;
         audit.record(AuditEntry {
             timestamp: chrono::Utc::now().to_rfc3339(),
@@ -244,17 +316,21 @@ pub async fn batch_insert(
             action: "batch_create".to_string(),
             resource: "/api/v1/triples/batch".to_string(),
             details: Some(format!(
-                "inserted={}, duplicates={}",
-                actually_inserted, duplicates
+                "inserted={}, duplicates={}, rejected={}",
+                new_rows.len(),
+                duplicates,
+                rejected
             )),
             request_id: None,
+            request: None,
         });
     }
 
     // Broadcast events for new triples
-    for (id, t) in ids.iter().zip(req.triples.iter()) {
+    for index in new_rows {
+        let t = &req.triples[index];
         state.broadcaster.broadcast(Event::TripleAdded {
-            hash: id.to_hex(),
+            hash: results[index].id.clone().unwrap_or_default(),
             subject: t.subject.clone(),
             predicate: t.predicate.clone(),
             object: serde_json::to_value(&t.object).unwrap_or_default(),
@@ -264,7 +340,10 @@ pub async fn batch_insert(
     Ok(BatchInsertResponse {
         total: inserted.len(),
         duplicates,
+        rejected,
+        committed: true,
         inserted,
+        results,
     })
 }
 
@@ -300,21 +379,34 @@ pub async fn delete_triple(
     state: &AppState,
     id: &str,
     namespace: Option<String>,
-    #[cfg_attr(not(feature = "dag"), allow(unused_variables))] origin: Option<&str>,
+    origin: Option<&str>,
+) -> Result<()> {
+    delete_triple_with_reason(state, id, namespace, origin, None, None).await
+}
+
+/// Delete a triple, recording who deleted it and why.
+///
+/// Behaves like [`delete_triple`]; additionally the removed triple is kept in
+/// `state.tombstones` with a deletion event attributed to `actor` (falling back
+/// to the namespace, then `origin`) and carrying `reason`.
+pub async fn delete_triple_with_reason(
+    state: &AppState,
+    id: &str,
+    namespace: Option<String>,
+    origin: Option<&str>,
+    actor: Option<String>,
+    reason: Option<String>,
 ) -> Result<()> {
     let triple_id = TripleId::from_hex(id)
         .ok_or_else(|| Error::InvalidInput(format!("Invalid triple ID: {}", id)))?;
 
-    let deleted = {
+    let removed = {
         let graph = state.graph.read().await;
 
-        // Look up subject before deleting (for DAG indexing)
+        // Snapshot the triple before deleting (for the tombstone and DAG indexing)
+        let existing = graph.get(&triple_id)?;
         #[cfg(feature = "dag")]
-        let subject_for_dag = graph
-            .get(&triple_id)
-            .ok()
-            .flatten()
-            .map(|t| t.subject.to_string());
+        let subject_for_dag = existing.as_ref().map(|t| t.subject.to_string());
 
         let deleted = graph.delete(&triple_id)?;
 
@@ -352,10 +444,17 @@ pub async fn delete_triple(
             }
         }
 
-        deleted
+        existing.filter(|_| deleted)
     };
 
-    if deleted {
+    if let Some(triple) = removed {
+        let actor = actor
+            .or_else(|| namespace.clone())
+            .or_else(|| origin.map(str::to_string));
+        state
+            .tombstones
+            .record_deletion(&triple, actor, reason.clone(), chrono::Utc::now())?;
+
         // Record audit entry
         {
             let mut audit = state.audit_log.write().await;
@@ -365,8 +464,9 @@ pub async fn delete_triple(
                 namespace,
                 action: "delete".to_string(),
                 resource: format!("/api/v1/triples/{}", id),
-                details: None,
+                details: reason.map(|r| format!("reason={}", r)),
                 request_id: None,
+                request: None,
             });
         }
 
@@ -426,6 +526,7 @@ pub async fn list_triples(
         total,
         limit: query.limit,
         offset: query.offset,
+        deleted: Vec::new(),
     })
 }
 
@@ -463,12 +564,10 @@ mod tests {
     #[tokio::test]
     async fn batch_insert_two_triples_count_is_two() {
         let state = AppState::with_db_path(":memory:", None).unwrap();
-        let batch = BatchInsertRequest {
-            triples: vec![
-                req("ex:alice", "ex:knows", "ex:bob"),
-                req("ex:alice", "ex:knows", "ex:carol"),
-            ],
-        };
+        let batch = BatchInsertRequest::new(vec![
+            req("ex:alice", "ex:knows", "ex:bob"),
+            req("ex:alice", "ex:knows", "ex:carol"),
+        ]);
         let resp = batch_insert(&state, batch, None).await.unwrap();
         assert_eq!(resp.total, 2);
         assert_eq!(resp.duplicates, 0);
@@ -476,6 +575,116 @@ mod tests {
         assert_eq!(count, 2);
     }
 
+    async fn state_rejecting(predicate: &str) -> AppState {
+        let state = AppState::with_db_path(":memory:", None).unwrap();
+        let rule = aingle_logic::Rule::integrity("no-banned")
+            .when_predicate(predicate)
+            .reject("predicate is banned")
+            .build();
+        state.logic.write().await.add_rule(rule);
+        state
+    }
+
+    #[tokio::test]
+    async fn batch_insert_reports_duplicates_in_batch_and_graph() {
+        let state = AppState::with_db_path(":memory:", None).unwrap();
+        batch_insert(
+            &state,
+            BatchInsertRequest::new(vec![req("ex:alice", "ex:knows", "ex:bob")]),
+            None,
+        )
+        .await
+        .unwrap();
+
+        let batch = BatchInsertRequest::new(vec![
+            req("ex:alice", "ex:knows", "ex:bob"),
+            req("ex:alice", "ex:knows", "ex:carol"),
+            req("ex:alice", "ex:knows", "ex:carol"),
+        ]);
+        let resp = batch_insert(&state, batch, None).await.unwrap();
+        let statuses: Vec<_> = resp.results.iter().map(|r| r.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                BatchItemStatus::Duplicate,
+                BatchItemStatus::Inserted,
+                BatchItemStatus::Duplicate,
+            ]
+        );
+        assert_eq!(resp.duplicates, 2);
+        assert!(resp.committed);
+        assert_eq!(state.graph.read().await.count(), 2);
+    }
+
+    #[tokio::test]
+    async fn atomic_batch_with_rejection_writes_nothing() {
+        let state = state_rejecting("ex:banned").await;
+        let mut batch = BatchInsertRequest::new(vec![
+            req("ex:alice", "ex:knows", "ex:bob"),
+            req("ex:alice", "ex:banned", "ex:carol"),
+            req("", "ex:knows", "ex:carol"),
+        ]);
+        batch.validate = true;
+
+        let resp = batch_insert(&state, batch, None).await.unwrap();
+        assert!(!resp.committed);
+        assert_eq!(resp.rejected, 2);
+        assert_eq!(resp.results[0].status, BatchItemStatus::Aborted);
+        assert_eq!(resp.results[1].status, BatchItemStatus::Rejected);
+        assert_eq!(
+            resp.results[1].error.as_deref(),
+            Some("no-banned: predicate is banned")
+        );
+        assert_eq!(
+            resp.results[2].error.as_deref(),
+            Some("subject cannot be empty")
+        );
+        assert_eq!(state.graph.read().await.count(), 0);
+    }
+
+    #[tokio::test]
+    async fn best_effort_batch_inserts_the_valid_rows() {
+        let state = state_rejecting("ex:banned").await;
+        let mut batch = BatchInsertRequest::new(vec![
+            req("ex:alice", "ex:knows", "ex:bob"),
+            req("ex:alice", "ex:banned", "ex:carol"),
+        ]);
+        batch.validate = true;
+        batch.atomic = false;
+
+        let resp = batch_insert(&state, batch, None).await.unwrap();
+        assert!(resp.committed);
+        assert_eq!(resp.rejected, 1);
+        assert_eq!(resp.total, 1);
+        assert_eq!(resp.results[0].status, BatchItemStatus::Inserted);
+        assert_eq!(state.graph.read().await.count(), 1);
+    }
+
+    #[tokio::test]
+    async fn rules_are_skipped_unless_validate_is_set() {
+        let state = state_rejecting("ex:banned").await;
+        let batch = BatchInsertRequest::new(vec![req("ex:alice", "ex:banned", "ex:carol")]);
+
+        let resp = batch_insert(&state, batch, None).await.unwrap();
+        assert!(resp.committed);
+        assert_eq!(resp.rejected, 0);
+        assert_eq!(state.graph.read().await.count(), 1);
+    }
+
+    #[tokio::test]
+    async fn batch_over_the_limit_is_invalid_input() {
+        let mut state = AppState::with_db_path(":memory:", None).unwrap();
+        state.max_batch_triples = 1;
+        let batch = BatchInsertRequest::new(vec![
+            req("ex:alice", "ex:knows", "ex:bob"),
+            req("ex:alice", "ex:knows", "ex:carol"),
+        ]);
+
+        let err = batch_insert(&state, batch, None).await.unwrap_err();
+        assert!(matches!(err, Error::InvalidInput(_)));
+        assert_eq!(state.graph.read().await.count(), 0);
+    }
+
     #[tokio::test]
     async fn get_triple_round_trips_and_missing_is_not_found() {
         let state = AppState::with_db_path(":memory:", None).unwrap();
@@ -372,8 +372,8 @@ fn mean_sim(
 /// Mean per-note embedding from Ineru `doc_chunk` entries, grouped by source_path.
 pub(crate) fn per_note_vectors(mem: &ineru::IneruMemory) -> BTreeMap<String, Vec<f32>> {
     let mut sums: BTreeMap<String, (Vec<f32>, usize)> = BTreeMap::new();
-    let mut entries = mem.stm.all_entries();
-    entries.extend(mem.ltm.all_entries());
+    let mut entries = mem.stm().all_entries();
+    entries.extend(mem.ltm().all_entries());
     for e in entries {
         if e.entry_type != crate::service::ingest::CHUNK_ENTRY_TYPE {
             continue;
@@ -0,0 +1,194 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Graceful shutdown coordination
+//!
+//! A [`ShutdownHandle`] is shared by the server, the request handlers and the
+//! embedder. Once it is triggered — by SIGINT/SIGTERM or explicitly — the
+//! server stops accepting connections and drains the open ones:
+//!
+//! - in-flight HTTP requests run to completion, up to the drain timeout;
+//! - long polls return at once with the events gathered so far;
+//! - WebSocket subscribers receive a close frame with code
+//!   [`SERVICE_RESTART`] and the reason [`RESTART_REASON`].
+//!
+//! The WebSocket sessions are counted, so the server can wait for their close
+//! frames to go out before it flushes its stores and returns.
+
+use std::sync::Arc;
+use tokio::sync::watch;
+
+/// WebSocket close code sent to subscribers on shutdown (RFC 6455 "Service Restart")
+pub const SERVICE_RESTART: u16 = 1012;
+
+/// WebSocket close reason sent to subscribers on shutdown
+pub const RESTART_REASON: &str = "server restarting";
+
+/// Triggers and observes a graceful shutdown.
+///
+/// Cheap to clone; all clones share the same state.
+#[derive(Clone, Debug)]
+pub struct ShutdownHandle {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    triggered: watch::Sender<bool>,
+    sessions: watch::Sender<usize>,
+}
+
+impl ShutdownHandle {
+    /// Creates a handle that has not been triggered.
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                triggered: watch::Sender::new(false),
+                sessions: watch::Sender::new(0),
+            }),
+        }
+    }
+
+    /// Starts the shutdown. Calling it again has no further effect.
+    pub fn trigger(&self) {
+        self.inner.triggered.send_if_modified(|triggered| {
+            let changed = !*triggered;
+            *triggered = true;
+            changed
+        });
+    }
+
+    /// Returns `true` once the shutdown has been triggered.
+    pub fn is_triggered(&self) -> bool {
+        *self.inner.triggered.borrow()
+    }
+
+    /// Completes once the shutdown has been triggered.
+    pub async fn triggered(&self) {
+        let mut rx = self.inner.triggered.subscribe();
+        // The sender lives as long as `self`, so this cannot fail
+        let _ = rx.wait_for(|triggered| *triggered).await;
+    }
+
+    /// Returns the number of open WebSocket sessions.
+    pub fn open_sessions(&self) -> usize {
+        *self.inner.sessions.borrow()
+    }
+
+    /// Counts a long-lived session until the returned guard is dropped.
+    pub(crate) fn session(&self) -> SessionGuard {
+        self.inner.sessions.send_modify(|n| *n += 1);
+        SessionGuard {
+            handle: self.clone(),
+        }
+    }
+
+    /// Completes once every session has ended.
+    pub(crate) async fn sessions_closed(&self) {
+        let mut rx = self.inner.sessions.subscribe();
+        let _ = rx.wait_for(|n| *n == 0).await;
+    }
+}
+
+impl Default for ShutdownHandle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Keeps a session counted by its [`ShutdownHandle`] while alive.
+pub(crate) struct SessionGuard {
+    handle: ShutdownHandle,
+}
+
+impl Drop for SessionGuard {
+    fn drop(&mut self) {
+        self.handle
+            .inner
+            .sessions
+            .send_modify(|n| *n = n.saturating_sub(1));
+    }
+}
+
+/// Completes on SIGINT, or on SIGTERM on Unix.
+pub(crate) async fn os_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("Failed to listen for SIGINT: {e}");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::error!("Failed to listen for SIGTERM: {e}");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => tracing::info!("SIGINT received — shutting down..."),
+        _ = terminate => tracing::info!("SIGTERM received — shutting down..."),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_trigger_wakes_waiters() {
+        let handle = ShutdownHandle::new();
+        assert!(!handle.is_triggered());
+
+        let waiter = tokio::spawn({
+            let handle = handle.clone();
+            async move { handle.triggered().await }
+        });
+        handle.trigger();
+        handle.trigger();
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(handle.is_triggered());
+
+        // Waiting after the fact completes at once
+        tokio::time::timeout(Duration::from_secs(1), handle.triggered())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_sessions_are_counted() {
+        let handle = ShutdownHandle::new();
+        let first = handle.session();
+        let second = handle.session();
+        assert_eq!(handle.open_sessions(), 2);
+
+        drop(first);
+        let closed = tokio::spawn({
+            let handle = handle.clone();
+            async move { handle.sessions_closed().await }
+        });
+        tokio::task::yield_now().await;
+        assert!(!closed.is_finished());
+
+        drop(second);
+        tokio::time::timeout(Duration::from_secs(1), closed)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(handle.open_sessions(), 0);
+    }
+}
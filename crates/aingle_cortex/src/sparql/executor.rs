@@ -2,16 +2,47 @@
 // SPDX-License-Identifier: Apache-2.0 OR Commercial
 
 //! SPARQL query executor
+//!
+//! Solutions bind variable names to graph [`Value`]s: subjects and predicates
+//! as [`Value::Node`], objects as stored. FILTER and ORDER BY compare values by
+//! type, so numbers compare numerically, `xsd:dateTime`s as instants and
+//! strings lexically.
 
-use super::{ParsedQuery, QueryType, SparqlResult};
+use super::{ParsedQuery, ParsedUpdate, QueryType, SparqlResult};
 use crate::error::{Error, Result};
-use aingle_graph::{GraphDB, TriplePattern as GraphTriplePattern};
+use aingle_graph::{
+    GraphDB, NodeId, Predicate, Triple, TriplePattern as GraphTriplePattern, Value,
+};
 use spargebra::{
-    algebra::{Expression, GraphPattern},
-    term::{NamedNodePattern, TermPattern},
-    Query,
+    algebra::{Expression, Function, GraphPattern, OrderExpression},
+    term::{GraphName, GraphNamePattern, Literal, NamedNodePattern, TermPattern, TriplePattern},
+    GraphUpdateOperation, Query,
 };
-use std::collections::HashMap;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{HashMap, HashSet};
+
+/// Variable bindings of one query solution
+type Solution = HashMap<String, Value>;
+
+/// XML Schema datatype namespace
+const XSD: &str = "http://www.w3.org/2001/XMLSchema#";
+
+/// XML Schema datatypes derived from `xsd:integer`
+const XSD_INTEGER_TYPES: &[&str] = &[
+    "integer",
+    "int",
+    "long",
+    "short",
+    "byte",
+    "nonNegativeInteger",
+    "nonPositiveInteger",
+    "positiveInteger",
+    "negativeInteger",
+    "unsignedLong",
+    "unsignedInt",
+    "unsignedShort",
+    "unsignedByte",
+];
 
 /// Execute a parsed SPARQL query against the graph
 pub fn execute_query(graph: &GraphDB, query: &ParsedQuery) -> Result<SparqlResult> {
@@ -27,28 +58,25 @@ pub fn execute_query(graph: &GraphDB, query: &ParsedQuery) -> Result<SparqlResul
 fn execute_select(graph: &GraphDB, query: &ParsedQuery) -> Result<SparqlResult> {
     match &query.query {
         Query::Select { pattern, .. } => {
-            let mut bindings = Vec::new();
-            let mut variables = Vec::new();
-
             // Execute the graph pattern
             let results = execute_pattern(graph, pattern)?;
 
-            // Extract variables from first result
-            if let Some(first) = results.first() {
-                variables = first.keys().cloned().collect();
-            }
-
-            // Convert to JSON bindings
-            for result in results {
-                let binding: serde_json::Value =
-                    serde_json::to_value(&result).map_err(|e| Error::Internal(e.to_string()))?;
-                bindings.push(binding);
-            }
+            // Variables in SELECT order, or every bound name when not projected
+            let variables = projected_variables(pattern).unwrap_or_else(|| {
+                let mut names: Vec<String> = results
+                    .iter()
+                    .flat_map(|solution| solution.keys().cloned())
+                    .collect::<HashSet<_>>()
+                    .into_iter()
+                    .collect();
+                names.sort();
+                names
+            });
 
             Ok(SparqlResult {
                 result_type: "bindings".to_string(),
                 variables: Some(variables),
-                bindings: Some(bindings),
+                bindings: Some(results.iter().map(solution_to_json).collect()),
                 boolean: None,
                 triple_count: None,
             })
@@ -86,12 +114,7 @@ fn execute_construct(graph: &GraphDB, query: &ParsedQuery) -> Result<SparqlResul
             Ok(SparqlResult {
                 result_type: "graph".to_string(),
                 variables: None,
-                bindings: Some(
-                    results
-                        .into_iter()
-                        .map(|r| serde_json::to_value(&r).unwrap_or_default())
-                        .collect(),
-                ),
+                bindings: Some(results.iter().map(solution_to_json).collect()),
                 boolean: None,
                 triple_count: Some(count),
             })
@@ -125,344 +148,1086 @@ fn execute_describe(graph: &GraphDB, _query: &ParsedQuery) -> Result<SparqlResul
     })
 }
 
-/// Execute a graph pattern and return bindings
-fn execute_pattern(
-    graph: &GraphDB,
-    pattern: &GraphPattern,
-) -> Result<Vec<HashMap<String, String>>> {
-    let mut results = Vec::new();
+/// Render a solution as a JSON object of display strings
+fn solution_to_json(solution: &Solution) -> serde_json::Value {
+    serde_json::Value::Object(
+        solution
+            .iter()
+            .map(|(name, value)| (name.clone(), serde_json::Value::String(value.to_string())))
+            .collect(),
+    )
+}
 
+/// Variables of the outermost projection, looking through solution modifiers
+fn projected_variables(pattern: &GraphPattern) -> Option<Vec<String>> {
+    match pattern {
+        GraphPattern::Project { variables, .. } => {
+            Some(variables.iter().map(|v| v.as_str().to_string()).collect())
+        }
+        GraphPattern::Slice { inner, .. }
+        | GraphPattern::Distinct { inner }
+        | GraphPattern::Reduced { inner } => projected_variables(inner),
+        _ => None,
+    }
+}
+
+/// Execute a graph pattern and return its solutions
+fn execute_pattern(graph: &GraphDB, pattern: &GraphPattern) -> Result<Vec<Solution>> {
     match pattern {
         GraphPattern::Bgp { patterns } => {
-            // Basic Graph Pattern - match triple patterns
             if patterns.is_empty() {
                 // No patterns - return all triples
-                let all_triples = graph.find(GraphTriplePattern::any())?;
-                for triple in all_triples {
-                    let mut binding = HashMap::new();
-                    binding.insert("s".to_string(), triple.subject.to_string());
-                    binding.insert("p".to_string(), triple.predicate.to_string());
-                    binding.insert("o".to_string(), format!("{}", triple.object));
-                    results.push(binding);
+                return all_triples(graph);
+            }
+            evaluate_bgp(graph, patterns, Solution::new())
+        }
+        GraphPattern::Filter { inner, expr } => {
+            let results = match inner.as_ref() {
+                // `?var = constant` conjuncts select index lookups up front;
+                // the whole filter still runs on what comes back.
+                GraphPattern::Bgp { patterns } if !patterns.is_empty() => {
+                    let mut seed = Solution::new();
+                    collect_equalities(expr, &mut seed);
+                    seed.retain(|name, _| can_push_down(patterns, name));
+                    evaluate_bgp(graph, patterns, seed)?
                 }
-            } else {
-                // Match each triple pattern
-                for pattern in patterns {
-                    let all_triples = graph.find(GraphTriplePattern::any())?;
-
-                    for triple in all_triples {
-                        let mut binding = HashMap::new();
-                        let mut matched = true;
-
-                        // Match subject
-                        match &pattern.subject {
-                            TermPattern::Variable(v) => {
-                                binding.insert(v.as_str().to_string(), triple.subject.to_string());
-                            }
-                            TermPattern::NamedNode(n) => {
-                                // Skip if subject doesn't match
-                                if triple.subject.to_string() != format!("<{}>", n.as_str()) {
-                                    matched = false;
-                                }
-                            }
-                            _ => {
-                                matched = false;
-                            }
-                        }
-
-                        if !matched {
-                            continue;
-                        }
-
-                        // Match predicate
-                        match &pattern.predicate {
-                            NamedNodePattern::Variable(v) => {
-                                binding
-                                    .insert(v.as_str().to_string(), triple.predicate.to_string());
-                            }
-                            NamedNodePattern::NamedNode(n) => {
-                                // The predicate in graph is stored as just the local name
-                                // but SPARQL pattern has full IRI
-                                let pred_str = triple.predicate.to_string();
-                                let expected = format!("<{}>", n.as_str());
-
-                                // Try exact match first
-                                if pred_str != expected {
-                                    // Try matching just the local name
-                                    let local_name = n.as_str().rsplit('/').next().unwrap_or("");
-                                    if pred_str != format!("<{}>", local_name) {
-                                        matched = false;
-                                    }
-                                }
-                            }
-                        }
-
-                        if !matched {
-                            continue;
-                        }
+                _ => execute_pattern(graph, inner)?,
+            };
 
-                        // Match object
-                        match &pattern.object {
-                            TermPattern::Variable(v) => {
-                                binding
-                                    .insert(v.as_str().to_string(), format!("{}", triple.object));
-                            }
-                            TermPattern::NamedNode(n) => {
-                                // Skip if object doesn't match
-                                if triple.object.to_string() != format!("<{}>", n.as_str()) {
-                                    matched = false;
-                                }
-                            }
-                            TermPattern::Literal(lit) => {
-                                // Skip if object doesn't match
-                                if triple.object.to_string() != format!("\"{}\"", lit.value()) {
-                                    matched = false;
-                                }
-                            }
-                            _ => {
-                                matched = false;
-                            }
-                        }
-
-                        if matched {
-                            results.push(binding);
-                        }
-                    }
+            let mut kept = Vec::with_capacity(results.len());
+            for solution in results {
+                if evaluate_filter_expression(graph, expr, &solution)? {
+                    kept.push(solution);
                 }
             }
-        }
-        GraphPattern::Filter { inner, expr } => {
-            // Execute inner pattern first
-            results = execute_pattern(graph, inner)?;
-            // Apply filter expression
-            results.retain(|binding| evaluate_filter_expression(expr, binding).unwrap_or(false));
+            Ok(kept)
         }
         GraphPattern::Project { inner, variables } => {
-            // Execute inner pattern first
-            results = execute_pattern(graph, inner)?;
-            // Project only the specified variables
-            results = results
+            // Keep only the projected variables
+            Ok(execute_pattern(graph, inner)?
                 .into_iter()
-                .map(|mut binding| {
-                    // Keep only the projected variables
-                    let projected: HashMap<String, String> = variables
+                .map(|mut solution| {
+                    variables
                         .iter()
-                        .filter_map(|v| {
-                            let var_name = v.as_str().to_string();
-                            binding.remove(&var_name).map(|val| (var_name, val))
-                        })
-                        .collect();
-                    projected
+                        .filter_map(|v| solution.remove_entry(v.as_str()))
+                        .collect()
                 })
-                .collect();
+                .collect())
         }
         GraphPattern::Join { left, right } => {
-            // Execute both patterns and join
-            let left_results = execute_pattern(graph, left)?;
-            let right_results = execute_pattern(graph, right)?;
-
-            // Simple join - combine all
-            for l in &left_results {
-                for r in &right_results {
-                    let mut combined = l.clone();
-                    combined.extend(r.clone());
-                    results.push(combined);
+            let left = execute_pattern(graph, left)?;
+            let right = execute_pattern(graph, right)?;
+
+            let mut results = Vec::new();
+            for l in &left {
+                for r in &right {
+                    if let Some(merged) = merge(l, r) {
+                        results.push(merged);
+                    }
                 }
             }
+            Ok(results)
         }
         GraphPattern::Union { left, right } => {
-            // Union - combine both result sets
-            results.extend(execute_pattern(graph, left)?);
+            let mut results = execute_pattern(graph, left)?;
             results.extend(execute_pattern(graph, right)?);
+            Ok(results)
         }
-        GraphPattern::LeftJoin { left, right, .. } => {
-            // Optional pattern
-            results = execute_pattern(graph, left)?;
-            if let Ok(right_results) = execute_pattern(graph, right) {
-                for r in right_results {
-                    if !results.iter().any(|l| l == &r) {
-                        results.push(r);
+        GraphPattern::LeftJoin {
+            left,
+            right,
+            expression,
+        } => {
+            // Optional pattern: keep each left solution that nothing extends
+            let left = execute_pattern(graph, left)?;
+            let right = execute_pattern(graph, right)?;
+
+            let mut results = Vec::new();
+            for l in left {
+                let mut extended = false;
+                for r in &right {
+                    let Some(merged) = merge(&l, r) else {
+                        continue;
+                    };
+                    let keep = match expression {
+                        Some(expr) => evaluate_filter_expression(graph, expr, &merged)?,
+                        None => true,
+                    };
+                    if keep {
+                        extended = true;
+                        results.push(merged);
                     }
                 }
+                if !extended {
+                    results.push(l);
+                }
+            }
+            Ok(results)
+        }
+        GraphPattern::Minus { left, right } => {
+            let right = execute_pattern(graph, right)?;
+            Ok(execute_pattern(graph, left)?
+                .into_iter()
+                .filter(|l| {
+                    !right
+                        .iter()
+                        .any(|r| l.keys().any(|name| r.contains_key(name)) && merge(l, r).is_some())
+                })
+                .collect())
+        }
+        GraphPattern::Extend {
+            inner,
+            variable,
+            expression,
+        } => {
+            let mut results = execute_pattern(graph, inner)?;
+            for solution in &mut results {
+                // An expression error leaves the variable unbound
+                if let Some(value) = evaluate(graph, expression, solution)? {
+                    solution.insert(variable.as_str().to_string(), value);
+                }
             }
+            Ok(results)
+        }
+        GraphPattern::OrderBy { inner, expression } => {
+            let results = execute_pattern(graph, inner)?;
+
+            let mut keyed = Vec::with_capacity(results.len());
+            for solution in results {
+                let mut keys = Vec::with_capacity(expression.len());
+                for order in expression {
+                    let (OrderExpression::Asc(expr) | OrderExpression::Desc(expr)) = order;
+                    keys.push(evaluate(graph, expr, &solution)?);
+                }
+                keyed.push((keys, solution));
+            }
+
+            // Stable, so ties keep the order of the inner pattern
+            keyed.sort_by(|(a, _), (b, _)| {
+                expression
+                    .iter()
+                    .zip(a.iter().zip(b))
+                    .map(|(order, (x, y))| {
+                        let ordering = order_values(x.as_ref(), y.as_ref());
+                        match order {
+                            OrderExpression::Desc(_) => ordering.reverse(),
+                            OrderExpression::Asc(_) => ordering,
+                        }
+                    })
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or(Ordering::Equal)
+            });
+            Ok(keyed.into_iter().map(|(_, solution)| solution).collect())
+        }
+        GraphPattern::Distinct { inner } => {
+            let mut seen = HashSet::new();
+            Ok(execute_pattern(graph, inner)?
+                .into_iter()
+                .filter(|solution| {
+                    let mut key: Vec<(String, String)> = solution
+                        .iter()
+                        .map(|(name, value)| (name.clone(), value.to_string()))
+                        .collect();
+                    key.sort();
+                    seen.insert(key)
+                })
+                .collect())
+        }
+        GraphPattern::Reduced { inner } => execute_pattern(graph, inner),
+        GraphPattern::Slice {
+            inner,
+            start,
+            length,
+        } => {
+            // LIMIT/OFFSET apply to the ordered solutions of the inner pattern
+            let results = execute_pattern(graph, inner)?.into_iter().skip(*start);
+            Ok(match length {
+                Some(length) => results.take(*length).collect(),
+                None => results.collect(),
+            })
         }
         _ => {
             // For unsupported patterns, return all triples
-            let all_triples = graph.find(GraphTriplePattern::any())?;
-            for triple in all_triples {
-                let mut binding = HashMap::new();
-                binding.insert("s".to_string(), triple.subject.to_string());
-                binding.insert("p".to_string(), triple.predicate.to_string());
-                binding.insert("o".to_string(), format!("{}", triple.object));
-                results.push(binding);
-            }
+            all_triples(graph)
         }
     }
+}
 
-    Ok(results)
+/// Every triple as an `?s ?p ?o` solution
+fn all_triples(graph: &GraphDB) -> Result<Vec<Solution>> {
+    Ok(graph
+        .find(GraphTriplePattern::any())?
+        .into_iter()
+        .map(|triple| {
+            Solution::from([
+                ("s".to_string(), Value::Node(triple.subject)),
+                (
+                    "p".to_string(),
+                    Value::Node(NodeId::named(triple.predicate.as_str())),
+                ),
+                ("o".to_string(), triple.object),
+            ])
+        })
+        .collect())
 }
 
-/// Evaluate a FILTER expression against a variable binding
-fn evaluate_filter_expression(
-    expr: &Expression,
-    binding: &HashMap<String, String>,
-) -> Result<bool> {
-    match expr {
-        // Comparisons
-        Expression::Equal(left, right) => {
-            let l = evaluate_term(left, binding)?;
-            let r = evaluate_term(right, binding)?;
-            Ok(l == r)
-        }
-        Expression::Less(left, right) => {
-            let l = evaluate_term(left, binding)?;
-            let r = evaluate_term(right, binding)?;
-            compare_values(&l, &r, |a, b| a < b)
+/// Merge two compatible solutions, or `None` if a shared variable differs
+fn merge(left: &Solution, right: &Solution) -> Option<Solution> {
+    let mut merged = left.clone();
+    for (name, value) in right {
+        match merged.get(name) {
+            Some(existing) if existing != value => return None,
+            Some(_) => {}
+            None => {
+                merged.insert(name.clone(), value.clone());
+            }
         }
-        Expression::Greater(left, right) => {
-            let l = evaluate_term(left, binding)?;
-            let r = evaluate_term(right, binding)?;
-            compare_values(&l, &r, |a, b| a > b)
+    }
+    Some(merged)
+}
+
+// ============================================================================
+// Basic graph patterns
+// ============================================================================
+
+/// What a triple pattern position requires of a stored triple
+enum Slot {
+    /// An unbound variable, bound by the match
+    Any(String),
+    /// A constant or an already bound variable
+    Fixed(Value),
+    /// A term that never matches stored data
+    Never,
+}
+
+/// Join the triple patterns of a BGP, starting from `seed` bindings
+///
+/// Each step picks the pattern with the most fixed positions and looks it up
+/// through the graph indexes once per partial solution.
+fn evaluate_bgp(
+    graph: &GraphDB,
+    patterns: &[TriplePattern],
+    seed: Solution,
+) -> Result<Vec<Solution>> {
+    let mut remaining: Vec<&TriplePattern> = patterns.iter().collect();
+    let mut bound: HashSet<String> = seed.keys().cloned().collect();
+    let mut solutions = vec![seed];
+
+    while !remaining.is_empty() && !solutions.is_empty() {
+        let next = (0..remaining.len())
+            .max_by_key(|&i| (fixed_positions(remaining[i], &bound), Reverse(i)))
+            .unwrap_or(0);
+        let pattern = remaining.remove(next);
+
+        let mut extended = Vec::new();
+        for solution in &solutions {
+            for triple in lookup(graph, pattern, solution)? {
+                if let Some(matched) = bind_triple(pattern, &triple, solution) {
+                    extended.push(matched);
+                }
+            }
         }
-        Expression::LessOrEqual(left, right) => {
-            let l = evaluate_term(left, binding)?;
-            let r = evaluate_term(right, binding)?;
-            compare_values(&l, &r, |a, b| a <= b)
+
+        bound.extend(pattern_variables(pattern));
+        solutions = extended;
+    }
+
+    Ok(solutions)
+}
+
+/// Variable name of a pattern position, if it is not a constant
+fn term_variable(term: &TermPattern) -> Option<String> {
+    match term {
+        TermPattern::Variable(v) => Some(v.as_str().to_string()),
+        // Blank nodes in a query act as variables that are never projected
+        TermPattern::BlankNode(b) => Some(format!("_:{}", b.as_str())),
+        _ => None,
+    }
+}
+
+/// Variable name of a predicate position, if it is not a constant
+fn predicate_variable(predicate: &NamedNodePattern) -> Option<String> {
+    match predicate {
+        NamedNodePattern::Variable(v) => Some(v.as_str().to_string()),
+        NamedNodePattern::NamedNode(_) => None,
+    }
+}
+
+/// Variables a triple pattern binds
+fn pattern_variables(pattern: &TriplePattern) -> impl Iterator<Item = String> {
+    [
+        term_variable(&pattern.subject),
+        predicate_variable(&pattern.predicate),
+        term_variable(&pattern.object),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// Number of positions fixed by constants or already bound variables
+fn fixed_positions(pattern: &TriplePattern, bound: &HashSet<String>) -> usize {
+    let fixed = |variable: Option<String>| variable.is_none_or(|name| bound.contains(&name));
+    [
+        fixed(term_variable(&pattern.subject)),
+        fixed(predicate_variable(&pattern.predicate)),
+        fixed(term_variable(&pattern.object)),
+    ]
+    .into_iter()
+    .filter(|&f| f)
+    .count()
+}
+
+/// Whether an equality on `name` can seed the lookups of a BGP
+///
+/// Predicates also match by local name, so a variable in predicate position
+/// may bind to a different node than the constant it was compared with.
+fn can_push_down(patterns: &[TriplePattern], name: &str) -> bool {
+    let is = |variable: Option<String>| variable.as_deref() == Some(name);
+    patterns
+        .iter()
+        .any(|p| is(term_variable(&p.subject)) || is(term_variable(&p.object)))
+        && !patterns
+            .iter()
+            .any(|p| is(predicate_variable(&p.predicate)))
+}
+
+/// Collect `?var = constant` conjuncts whose match is exact term equality
+fn collect_equalities(expr: &Expression, seed: &mut Solution) {
+    match expr {
+        Expression::And(left, right) => {
+            collect_equalities(left, seed);
+            collect_equalities(right, seed);
         }
-        Expression::GreaterOrEqual(left, right) => {
-            let l = evaluate_term(left, binding)?;
-            let r = evaluate_term(right, binding)?;
-            compare_values(&l, &r, |a, b| a >= b)
+        Expression::Equal(left, right) => {
+            let (variable, constant) = match (left.as_ref(), right.as_ref()) {
+                (Expression::Variable(v), c) | (c, Expression::Variable(v)) => (v, c),
+                _ => return,
+            };
+            // Numbers and dates compare by value (`1 = 1.0`), which an index
+            // lookup on the stored term would miss.
+            let value = match constant {
+                Expression::NamedNode(node) => iri_value(node.as_str()),
+                Expression::Literal(lit) => match literal_value(lit) {
+                    value @ Value::String(_) => value,
+                    _ => return,
+                },
+                _ => return,
+            };
+            seed.insert(variable.as_str().to_string(), value);
         }
+        _ => {}
+    }
+}
 
-        // Logical operators
-        Expression::And(left, right) => Ok(evaluate_filter_expression(left, binding)?
-            && evaluate_filter_expression(right, binding)?),
-        Expression::Or(left, right) => Ok(evaluate_filter_expression(left, binding)?
-            || evaluate_filter_expression(right, binding)?),
-        Expression::Not(inner) => Ok(!evaluate_filter_expression(inner, binding)?),
+/// Resolve a subject or object position against a partial solution
+fn term_slot(term: &TermPattern, solution: &Solution) -> Slot {
+    match term {
+        TermPattern::NamedNode(node) => Slot::Fixed(iri_value(node.as_str())),
+        TermPattern::Literal(lit) => Slot::Fixed(literal_value(lit)),
+        _ => match term_variable(term) {
+            Some(name) => variable_slot(name, solution),
+            None => Slot::Never,
+        },
+    }
+}
+
+/// Resolve a predicate position against a partial solution
+fn predicate_slot(predicate: &NamedNodePattern, solution: &Solution) -> Slot {
+    match predicate {
+        NamedNodePattern::NamedNode(node) => Slot::Fixed(iri_value(node.as_str())),
+        NamedNodePattern::Variable(v) => variable_slot(v.as_str().to_string(), solution),
+    }
+}
 
-        // Built-in functions
-        Expression::Bound(var) => {
-            let var_name = var.as_str();
-            Ok(binding.contains_key(var_name))
+fn variable_slot(name: String, solution: &Solution) -> Slot {
+    match solution.get(&name) {
+        Some(value) => Slot::Fixed(value.clone()),
+        None => Slot::Any(name),
+    }
+}
+
+/// Find the stored triples a pattern can match under a partial solution
+fn lookup(graph: &GraphDB, pattern: &TriplePattern, solution: &Solution) -> Result<Vec<Triple>> {
+    let mut query = GraphTriplePattern::any();
+
+    match term_slot(&pattern.subject, solution) {
+        Slot::Fixed(Value::Node(node)) => query = query.with_subject(node),
+        Slot::Any(_) => {}
+        Slot::Fixed(_) | Slot::Never => return Ok(Vec::new()),
+    }
+    match term_slot(&pattern.object, solution) {
+        Slot::Fixed(value) => query = query.with_object(value),
+        Slot::Any(_) => {}
+        Slot::Never => return Ok(Vec::new()),
+    }
+
+    // The predicate in graph may be stored as just the local name while the
+    // SPARQL pattern has the full IRI, so both are looked up.
+    let predicates = match predicate_slot(&pattern.predicate, solution) {
+        Slot::Fixed(Value::Node(NodeId::Named(iri))) => {
+            let local_name = iri.rsplit('/').next().unwrap_or("");
+            let mut names = vec![Predicate::named(iri.as_str())];
+            if !local_name.is_empty() && local_name != iri {
+                names.push(Predicate::named(local_name));
+            }
+            names.into_iter().map(Some).collect()
         }
-        Expression::FunctionCall(func, args) => evaluate_function_call(func, args, binding),
+        Slot::Any(_) => vec![None],
+        Slot::Fixed(_) | Slot::Never => return Ok(Vec::new()),
+    };
+
+    let mut triples = Vec::new();
+    for predicate in predicates {
+        let query = match predicate {
+            Some(predicate) => query.clone().with_predicate(predicate),
+            None => query.clone(),
+        };
+        triples.extend(graph.find(query)?);
+    }
+    Ok(triples)
+}
 
-        // Exists (subquery)
-        Expression::Exists(_) => {
-            // Simplified: always return true for now
-            Ok(true)
+/// Extend a partial solution with the variables a matched triple binds
+fn bind_triple(pattern: &TriplePattern, triple: &Triple, solution: &Solution) -> Option<Solution> {
+    let mut extended = solution.clone();
+    let positions = [
+        (
+            term_slot(&pattern.subject, solution),
+            Value::Node(triple.subject.clone()),
+        ),
+        (
+            predicate_slot(&pattern.predicate, solution),
+            Value::Node(NodeId::named(triple.predicate.as_str())),
+        ),
+        (term_slot(&pattern.object, solution), triple.object.clone()),
+    ];
+
+    for (slot, value) in positions {
+        match slot {
+            Slot::Any(name) => match extended.get(&name) {
+                // The same variable twice in one pattern, e.g. `?x <p> ?x`
+                Some(existing) if *existing != value => return None,
+                Some(_) => {}
+                None => {
+                    extended.insert(name, value);
+                }
+            },
+            // Constants were applied by the index lookup
+            Slot::Fixed(_) => {}
+            Slot::Never => return None,
         }
+    }
+    Some(extended)
+}
 
-        _ => {
-            // For unsupported expressions, return true (pass filter)
-            Ok(true)
+// ============================================================================
+// Updates
+// ============================================================================
+
+/// The SPARQL update forms [`plan_update`] supports
+pub const SUPPORTED_UPDATES: &str = "INSERT DATA, DELETE DATA, DELETE WHERE and \
+    DELETE/INSERT ... WHERE with a single basic graph pattern, on the default graph";
+
+/// Triples one update operation removes from and adds to the graph
+#[derive(Debug, Default)]
+pub struct UpdateChanges {
+    /// Stored triples to delete
+    pub delete: Vec<Triple>,
+    /// Triples to insert
+    pub insert: Vec<Triple>,
+}
+
+/// Reject an update with any operation outside [`SUPPORTED_UPDATES`]
+///
+/// Checking the whole request first keeps an unsupported operation from
+/// leaving the ones before it applied.
+pub fn check_update(update: &ParsedUpdate) -> Result<()> {
+    for operation in &update.update.operations {
+        if let Some(form) = unsupported_form(operation) {
+            return Err(Error::BadRequest(format!(
+                "Unsupported SPARQL update: {form}. Supported: {SUPPORTED_UPDATES}"
+            )));
         }
     }
+    Ok(())
 }
 
-/// Evaluate an expression term to a string value
-fn evaluate_term(expr: &Expression, binding: &HashMap<String, String>) -> Result<String> {
-    match expr {
-        Expression::Variable(var) => binding
-            .get(var.as_str())
-            .cloned()
-            .ok_or_else(|| Error::UnboundVariable(var.as_str().to_string())),
-        Expression::Literal(lit) => Ok(lit.value().to_string()),
-        Expression::NamedNode(node) => Ok(node.as_str().to_string()),
-        _ => Err(Error::UnsupportedExpression),
+/// Name of what makes an update operation unsupported, if anything
+fn unsupported_form(operation: &GraphUpdateOperation) -> Option<&'static str> {
+    match operation {
+        GraphUpdateOperation::InsertData { data } => data
+            .iter()
+            .any(|quad| quad.graph_name != GraphName::DefaultGraph)
+            .then_some("INSERT DATA into a named graph"),
+        GraphUpdateOperation::DeleteData { data } => data
+            .iter()
+            .any(|quad| quad.graph_name != GraphName::DefaultGraph)
+            .then_some("DELETE DATA from a named graph"),
+        GraphUpdateOperation::DeleteInsert {
+            delete,
+            insert,
+            using,
+            pattern,
+        } => {
+            if using.is_some() {
+                Some("USING")
+            } else if !matches!(pattern.as_ref(), GraphPattern::Bgp { .. }) {
+                Some("a WHERE clause other than a single basic graph pattern")
+            } else if delete
+                .iter()
+                .any(|quad| quad.graph_name != GraphNamePattern::DefaultGraph)
+                || insert
+                    .iter()
+                    .any(|quad| quad.graph_name != GraphNamePattern::DefaultGraph)
+            {
+                Some("a template on a named graph")
+            } else {
+                None
+            }
+        }
+        GraphUpdateOperation::Load { .. } => Some("LOAD"),
+        GraphUpdateOperation::Clear { .. } => Some("CLEAR"),
+        GraphUpdateOperation::Create { .. } => Some("CREATE"),
+        GraphUpdateOperation::Drop { .. } => Some("DROP"),
     }
 }
 
-/// Compare two values, trying numeric comparison first, then string comparison
-fn compare_values<F>(left: &str, right: &str, cmp: F) -> Result<bool>
-where
-    F: Fn(f64, f64) -> bool,
-{
-    // Try numeric comparison first
-    if let (Ok(l), Ok(r)) = (left.parse::<f64>(), right.parse::<f64>()) {
-        return Ok(cmp(l, r));
+/// Work out what one update operation deletes and inserts, without writing
+///
+/// DATA forms take their triples as given. Template forms evaluate the WHERE
+/// pattern once and fill the templates in per solution, skipping template
+/// triples left with an unbound variable; blank nodes in an INSERT template
+/// are fresh for every solution. Deleted triples are looked up like query
+/// patterns, so a full predicate IRI also removes triples stored under its
+/// local name.
+pub fn plan_update(graph: &GraphDB, operation: &GraphUpdateOperation) -> Result<UpdateChanges> {
+    let (delete, insert, solutions) = match operation {
+        GraphUpdateOperation::InsertData { data } => {
+            let insert = data
+                .iter()
+                .map(|quad| TriplePattern {
+                    subject: quad.subject.clone().into(),
+                    predicate: quad.predicate.clone().into(),
+                    object: quad.object.clone().into(),
+                })
+                .collect();
+            (Vec::new(), insert, vec![Solution::new()])
+        }
+        GraphUpdateOperation::DeleteData { data } => {
+            let delete = data
+                .iter()
+                .map(|quad| TriplePattern {
+                    subject: quad.subject.clone().into(),
+                    predicate: quad.predicate.clone().into(),
+                    object: spargebra::term::Term::from(quad.object.clone()).into(),
+                })
+                .collect();
+            (delete, Vec::new(), vec![Solution::new()])
+        }
+        GraphUpdateOperation::DeleteInsert {
+            delete,
+            insert,
+            pattern,
+            ..
+        } => {
+            let GraphPattern::Bgp { patterns } = pattern.as_ref() else {
+                return Err(Error::BadRequest(format!(
+                    "Unsupported SPARQL update: a WHERE clause other than a single basic \
+                     graph pattern. Supported: {SUPPORTED_UPDATES}"
+                )));
+            };
+            let delete = delete
+                .iter()
+                .map(|quad| TriplePattern {
+                    subject: quad.subject.clone().into(),
+                    predicate: quad.predicate.clone(),
+                    object: quad.object.clone().into(),
+                })
+                .collect();
+            let insert = insert
+                .iter()
+                .map(|quad| TriplePattern {
+                    subject: quad.subject.clone(),
+                    predicate: quad.predicate.clone(),
+                    object: quad.object.clone(),
+                })
+                .collect();
+            let solutions = evaluate_bgp(graph, patterns, Solution::new())?;
+            (delete, insert, solutions)
+        }
+        _ => {
+            let form = unsupported_form(operation).unwrap_or("this operation");
+            return Err(Error::BadRequest(format!(
+                "Unsupported SPARQL update: {form}. Supported: {SUPPORTED_UPDATES}"
+            )));
+        }
+    };
+
+    let mut changes = UpdateChanges::default();
+    let mut seen = HashSet::new();
+    for solution in &solutions {
+        for template in &delete {
+            if pattern_variables(template).any(|name| !solution.contains_key(&name)) {
+                continue;
+            }
+            for triple in lookup(graph, template, solution)? {
+                if seen.insert(triple.id()) {
+                    changes.delete.push(triple);
+                }
+            }
+        }
+
+        let mut blank_nodes = HashMap::new();
+        for template in &insert {
+            if let Some(triple) = instantiate(template, solution, &mut blank_nodes) {
+                changes.insert.push(triple);
+            }
+        }
     }
-    // Fall back to lexicographic string comparison
-    // We can't use the same comparator for strings, so we need special handling
-    // This is a limitation - for now just return false for string comparisons
-    Ok(false)
+    Ok(changes)
 }
 
-/// Evaluate a SPARQL function call
-fn evaluate_function_call(
-    func: &spargebra::algebra::Function,
-    args: &[Expression],
-    binding: &HashMap<String, String>,
+/// Fill an INSERT template in from a solution, or `None` if a position is
+/// unbound or holds a term that cannot go there
+fn instantiate(
+    template: &TriplePattern,
+    solution: &Solution,
+    blank_nodes: &mut HashMap<String, NodeId>,
+) -> Option<Triple> {
+    let mut node = |term: &TermPattern| match term {
+        TermPattern::BlankNode(b) => Some(Value::Node(
+            blank_nodes
+                .entry(b.as_str().to_string())
+                .or_insert_with(NodeId::blank)
+                .clone(),
+        )),
+        _ => match term_slot(term, solution) {
+            Slot::Fixed(value) => Some(value),
+            Slot::Any(_) | Slot::Never => None,
+        },
+    };
+
+    let Some(Value::Node(subject)) = node(&template.subject) else {
+        return None;
+    };
+    let object = node(&template.object)?;
+    let predicate = match predicate_slot(&template.predicate, solution) {
+        Slot::Fixed(Value::Node(NodeId::Named(iri))) => Predicate::named(iri),
+        _ => return None,
+    };
+    Some(Triple::new(subject, predicate, object))
+}
+
+// ============================================================================
+// Expressions
+// ============================================================================
+
+/// Evaluate a FILTER expression against a variable binding
+///
+/// Evaluation errors, such as comparing a number with a string or reading an
+/// unbound variable, make the filter false rather than failing the query.
+fn evaluate_filter_expression(
+    graph: &GraphDB,
+    expr: &Expression,
+    binding: &Solution,
 ) -> Result<bool> {
-    use spargebra::algebra::Function;
+    Ok(boolean(graph, expr, binding)?.unwrap_or(false))
+}
 
-    match func {
-        Function::Regex => {
-            // REGEX(text, pattern) or REGEX(text, pattern, flags)
-            if args.len() >= 2 {
-                let text_val = evaluate_term(&args[0], binding)?;
-                let pattern_val = evaluate_term(&args[1], binding)?;
-                let flags_val = if args.len() >= 3 {
-                    Some(evaluate_term(&args[2], binding)?)
-                } else {
-                    None
-                };
-                evaluate_regex(&text_val, &pattern_val, flags_val.as_deref())
-            } else {
-                Ok(false)
+/// Effective boolean value of an expression; `None` is an evaluation error
+fn boolean(graph: &GraphDB, expr: &Expression, binding: &Solution) -> Result<Option<bool>> {
+    Ok(evaluate(graph, expr, binding)?
+        .as_ref()
+        .and_then(effective_boolean))
+}
+
+/// Evaluate an expression to a value; `None` is an evaluation error
+fn evaluate(graph: &GraphDB, expr: &Expression, binding: &Solution) -> Result<Option<Value>> {
+    let value = match expr {
+        Expression::Variable(var) => binding.get(var.as_str()).cloned(),
+        Expression::Literal(lit) => Some(literal_value(lit)),
+        Expression::NamedNode(node) => Some(iri_value(node.as_str())),
+
+        // Logical operators; an error only matters if it decides the result
+        Expression::And(left, right) => {
+            match (
+                boolean(graph, left, binding)?,
+                boolean(graph, right, binding)?,
+            ) {
+                (Some(false), _) | (_, Some(false)) => Some(Value::Boolean(false)),
+                (Some(true), Some(true)) => Some(Value::Boolean(true)),
+                _ => None,
             }
         }
-        Function::Str => {
-            // STR() function converts to string - always succeeds if arg exists
-            if !args.is_empty() {
-                let _ = evaluate_term(&args[0], binding)?;
-                Ok(true)
-            } else {
-                Ok(false)
+        Expression::Or(left, right) => {
+            match (
+                boolean(graph, left, binding)?,
+                boolean(graph, right, binding)?,
+            ) {
+                (Some(true), _) | (_, Some(true)) => Some(Value::Boolean(true)),
+                (Some(false), Some(false)) => Some(Value::Boolean(false)),
+                _ => None,
             }
         }
-        Function::LangMatches => {
-            // Simplified: always return true for now
-            Ok(true)
+        Expression::Not(inner) => boolean(graph, inner, binding)?.map(|b| Value::Boolean(!b)),
+
+        // Comparisons
+        Expression::Equal(left, right) => operands(graph, left, right, binding)?
+            .map(|(l, r)| Value::Boolean(values_equal(&l, &r))),
+        Expression::SameTerm(left, right) => {
+            operands(graph, left, right, binding)?.map(|(l, r)| Value::Boolean(l == r))
+        }
+        Expression::Less(left, right) => {
+            ordering(graph, left, right, binding)?.map(|o| Value::Boolean(o.is_lt()))
         }
-        Function::IsIri => {
-            // Check if the expression is an IRI
-            if !args.is_empty() {
-                if let Expression::NamedNode(_) = &args[0] {
-                    Ok(true)
-                } else {
-                    Ok(false)
+        Expression::LessOrEqual(left, right) => {
+            ordering(graph, left, right, binding)?.map(|o| Value::Boolean(o.is_le()))
+        }
+        Expression::Greater(left, right) => {
+            ordering(graph, left, right, binding)?.map(|o| Value::Boolean(o.is_gt()))
+        }
+        Expression::GreaterOrEqual(left, right) => {
+            ordering(graph, left, right, binding)?.map(|o| Value::Boolean(o.is_ge()))
+        }
+        Expression::In(needle, list) => {
+            let Some(needle) = evaluate(graph, needle, binding)? else {
+                return Ok(None);
+            };
+            let mut found = Some(false);
+            for item in list {
+                match evaluate(graph, item, binding)? {
+                    Some(value) if values_equal(&needle, &value) => {
+                        found = Some(true);
+                        break;
+                    }
+                    Some(_) => {}
+                    None => found = None,
                 }
-            } else {
-                Ok(false)
             }
+            found.map(Value::Boolean)
         }
-        Function::IsBlank => {
-            // Check if the expression is a blank node
-            if !args.is_empty() {
-                if let Ok(val) = evaluate_term(&args[0], binding) {
-                    Ok(val.starts_with("_:"))
-                } else {
-                    Ok(false)
+
+        // Arithmetic
+        Expression::Add(left, right) => {
+            arithmetic(graph, left, right, binding, i64::checked_add, |a, b| a + b)?
+        }
+        Expression::Subtract(left, right) => {
+            arithmetic(graph, left, right, binding, i64::checked_sub, |a, b| a - b)?
+        }
+        Expression::Multiply(left, right) => {
+            arithmetic(graph, left, right, binding, i64::checked_mul, |a, b| a * b)?
+        }
+        Expression::Divide(left, right) => operands(graph, left, right, binding)?
+            .and_then(|(l, r)| Some((number(&l)?, number(&r)?)))
+            .filter(|&(_, divisor)| divisor != 0.0)
+            .map(|(dividend, divisor)| Value::Float(dividend / divisor)),
+        Expression::UnaryPlus(inner) => {
+            evaluate(graph, inner, binding)?.filter(|v| number(v).is_some())
+        }
+        Expression::UnaryMinus(inner) => {
+            evaluate(graph, inner, binding)?.and_then(|value| match value {
+                Value::Integer(i) => i.checked_neg().map(Value::Integer),
+                other => number(&other).map(|f| Value::Float(-f)),
+            })
+        }
+
+        // Built-in forms
+        Expression::Bound(var) => Some(Value::Boolean(binding.contains_key(var.as_str()))),
+        Expression::If(condition, then, otherwise) => match boolean(graph, condition, binding)? {
+            Some(true) => evaluate(graph, then, binding)?,
+            Some(false) => evaluate(graph, otherwise, binding)?,
+            None => None,
+        },
+        Expression::Coalesce(list) => {
+            let mut first = None;
+            for item in list {
+                if let Some(value) = evaluate(graph, item, binding)? {
+                    first = Some(value);
+                    break;
                 }
-            } else {
-                Ok(false)
             }
+            first
+        }
+        Expression::Exists(pattern) => {
+            let solutions = execute_pattern(graph, pattern)?;
+            Some(Value::Boolean(
+                solutions.iter().any(|s| merge(binding, s).is_some()),
+            ))
         }
-        Function::IsLiteral => {
-            // Check if the expression is a literal
-            if !args.is_empty() {
-                Ok(matches!(&args[0], Expression::Literal(_)))
+        Expression::FunctionCall(func, args) => evaluate_function_call(graph, func, args, binding)?,
+
+        #[allow(unreachable_patterns)]
+        _ => return Err(Error::UnsupportedExpression),
+    };
+
+    Ok(value)
+}
+
+/// Evaluate both operands of a binary operator
+fn operands(
+    graph: &GraphDB,
+    left: &Expression,
+    right: &Expression,
+    binding: &Solution,
+) -> Result<Option<(Value, Value)>> {
+    Ok(evaluate(graph, left, binding)?.zip(evaluate(graph, right, binding)?))
+}
+
+/// Order the operands of a comparison, `None` if they are not comparable
+fn ordering(
+    graph: &GraphDB,
+    left: &Expression,
+    right: &Expression,
+    binding: &Solution,
+) -> Result<Option<Ordering>> {
+    Ok(operands(graph, left, right, binding)?.and_then(|(l, r)| compare_values(&l, &r)))
+}
+
+/// Apply an arithmetic operator, staying in integers when both operands are
+fn arithmetic(
+    graph: &GraphDB,
+    left: &Expression,
+    right: &Expression,
+    binding: &Solution,
+    integer: fn(i64, i64) -> Option<i64>,
+    float: fn(f64, f64) -> f64,
+) -> Result<Option<Value>> {
+    Ok(
+        operands(graph, left, right, binding)?.and_then(|(l, r)| match (&l, &r) {
+            (Value::Integer(a), Value::Integer(b)) => integer(*a, *b).map(Value::Integer),
+            _ => Some(Value::Float(float(number(&l)?, number(&r)?))),
+        }),
+    )
+}
+
+/// Evaluate a SPARQL function call
+fn evaluate_function_call(
+    graph: &GraphDB,
+    func: &Function,
+    args: &[Expression],
+    binding: &Solution,
+) -> Result<Option<Value>> {
+    let mut values = Vec::with_capacity(args.len());
+    for arg in args {
+        values.push(evaluate(graph, arg, binding)?);
+    }
+    let arg = |i: usize| values.get(i).cloned().flatten();
+    let text = |i: usize| arg(i).as_ref().and_then(string_value);
+
+    let value = match func {
+        Function::Str => arg(0).and_then(|v| lexical_form(&v)).map(Value::String),
+        Function::Lang => arg(0).and_then(|v| match v {
+            Value::LangString { lang, .. } => Some(Value::String(lang)),
+            Value::Node(_) => None,
+            _ => Some(Value::String(String::new())),
+        }),
+        Function::LangMatches => match (arg(0), arg(1)) {
+            (Some(Value::String(tag)), Some(Value::String(range))) => {
+                Some(Value::Boolean(lang_matches(&tag, &range)))
+            }
+            _ => None,
+        },
+        Function::IsIri => {
+            arg(0).map(|v| Value::Boolean(matches!(v, Value::Node(NodeId::Named(_)))))
+        }
+        Function::IsBlank => arg(0).map(|v| {
+            Value::Boolean(matches!(
+                v,
+                Value::Node(NodeId::Blank(_) | NodeId::Anonymous(_) | NodeId::Hash(_))
+            ))
+        }),
+        Function::IsLiteral => arg(0).map(|v| Value::Boolean(!matches!(v, Value::Node(_)))),
+        Function::IsNumeric => arg(0).map(|v| Value::Boolean(number(&v).is_some())),
+        Function::StrLen => text(0).map(|s| Value::Integer(s.chars().count() as i64)),
+        Function::UCase => text(0).map(|s| Value::String(s.to_uppercase())),
+        Function::LCase => text(0).map(|s| Value::String(s.to_lowercase())),
+        Function::StrStarts => text(0)
+            .zip(text(1))
+            .map(|(s, prefix)| Value::Boolean(s.starts_with(&prefix))),
+        Function::StrEnds => text(0)
+            .zip(text(1))
+            .map(|(s, suffix)| Value::Boolean(s.ends_with(&suffix))),
+        Function::Contains => text(0)
+            .zip(text(1))
+            .map(|(s, part)| Value::Boolean(s.contains(&part))),
+        Function::Regex => {
+            // REGEX(text, pattern) or REGEX(text, pattern, flags)
+            let (Some(text_val), Some(pattern_val)) = (text(0), text(1)) else {
+                return Ok(None);
+            };
+            let flags_val = text(2);
+            Some(Value::Boolean(evaluate_regex(
+                &text_val,
+                &pattern_val,
+                flags_val.as_deref(),
+            )?))
+        }
+        _ => return Err(Error::UnsupportedExpression),
+    };
+
+    Ok(value)
+}
+
+// ============================================================================
+// Values
+// ============================================================================
+
+/// Graph value of an IRI
+fn iri_value(iri: &str) -> Value {
+    Value::Node(NodeId::named(iri))
+}
+
+/// Graph value of an RDF literal, typed by its datatype
+fn literal_value(lit: &Literal) -> Value {
+    let value = lit.value();
+    if let Some(lang) = lit.language() {
+        return Value::LangString {
+            value: value.to_string(),
+            lang: lang.to_string(),
+        };
+    }
+
+    let datatype = lit.datatype().as_str();
+    let parsed = match datatype.strip_prefix(XSD) {
+        Some("string") => Some(Value::String(value.to_string())),
+        Some("boolean") => match value {
+            "true" | "1" => Some(Value::Boolean(true)),
+            "false" | "0" => Some(Value::Boolean(false)),
+            _ => None,
+        },
+        Some("dateTime") => Some(Value::DateTime(value.to_string())),
+        Some("decimal" | "double" | "float") => value.parse().ok().map(Value::Float),
+        Some(local) if XSD_INTEGER_TYPES.contains(&local) => value.parse().ok().map(Value::Integer),
+        _ => None,
+    };
+
+    parsed.unwrap_or_else(|| Value::Typed {
+        value: value.to_string(),
+        datatype: datatype.to_string(),
+    })
+}
+
+/// Text of a string literal, with or without a language tag
+fn string_value(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::LangString { value, .. } => Some(value.clone()),
+        _ => None,
+    }
+}
+
+/// Lexical form for `STR()`; blank nodes have none
+fn lexical_form(value: &Value) -> Option<String> {
+    match value {
+        Value::Node(NodeId::Named(iri)) => Some(iri.clone()),
+        Value::Node(_) => None,
+        Value::String(s) | Value::DateTime(s) => Some(s.clone()),
+        Value::Typed { value, .. } | Value::LangString { value, .. } => Some(value.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+/// Basic language range matching for `LANGMATCHES()`
+fn lang_matches(tag: &str, range: &str) -> bool {
+    if range == "*" {
+        return !tag.is_empty();
+    }
+    let tag = tag.to_ascii_lowercase();
+    let range = range.to_ascii_lowercase();
+    tag == range || tag.starts_with(&format!("{}-", range))
+}
+
+/// Numeric value of integer, float and XSD numeric literals
+fn number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Integer(i) => Some(*i as f64),
+        Value::Float(f) => Some(*f),
+        Value::Typed { value, datatype } => {
+            let local = datatype.strip_prefix(XSD)?;
+            if matches!(local, "decimal" | "double" | "float") || XSD_INTEGER_TYPES.contains(&local)
+            {
+                value.trim().parse().ok()
             } else {
-                Ok(false)
+                None
             }
         }
-        _ => {
-            // For unsupported functions, return true
-            Ok(true)
+        _ => None,
+    }
+}
+
+/// Instant of a date-time literal; a missing offset is read as UTC
+fn datetime(value: &Value) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+    let text = match value {
+        Value::DateTime(s) => s,
+        Value::Typed { value, datatype } if datatype.strip_prefix(XSD) == Some("dateTime") => value,
+        _ => return None,
+    };
+    chrono::DateTime::parse_from_rfc3339(text).ok().or_else(|| {
+        chrono::NaiveDateTime::parse_from_str(text, "%Y-%m-%dT%H:%M:%S%.f")
+            .ok()
+            .map(|naive| naive.and_utc().fixed_offset())
+    })
+}
+
+/// Effective boolean value of a literal
+fn effective_boolean(value: &Value) -> Option<bool> {
+    match value {
+        Value::Boolean(b) => Some(*b),
+        Value::String(s) => Some(!s.is_empty()),
+        Value::LangString { value, .. } => Some(!value.is_empty()),
+        Value::Integer(_) | Value::Float(_) | Value::Typed { .. } => {
+            number(value).map(|n| n != 0.0 && !n.is_nan())
         }
+        _ => None,
+    }
+}
+
+/// Compare two values of the same kind: numbers, date-times, strings or
+/// booleans. Values of different kinds are not comparable.
+fn compare_values(left: &Value, right: &Value) -> Option<Ordering> {
+    if let (Some(l), Some(r)) = (number(left), number(right)) {
+        return l.partial_cmp(&r);
+    }
+    if let (Some(l), Some(r)) = (datetime(left), datetime(right)) {
+        return Some(l.cmp(&r));
+    }
+    match (left, right) {
+        (Value::String(l), Value::String(r)) => Some(l.cmp(r)),
+        (Value::Boolean(l), Value::Boolean(r)) => Some(l.cmp(r)),
+        _ => None,
+    }
+}
+
+/// SPARQL `=`: value equality for comparable kinds, term equality otherwise
+fn values_equal(left: &Value, right: &Value) -> bool {
+    match compare_values(left, right) {
+        Some(ordering) => ordering.is_eq(),
+        None => left == right,
+    }
+}
+
+/// ORDER BY rank: blank nodes, IRIs, then literals grouped by kind
+fn order_rank(value: &Value) -> u8 {
+    match value {
+        Value::Node(NodeId::Named(_)) => 1,
+        Value::Node(_) => 0,
+        Value::Boolean(_) => 2,
+        _ if number(value).is_some() => 3,
+        _ if datetime(value).is_some() => 4,
+        Value::String(_) | Value::LangString { .. } => 5,
+        _ => 6,
+    }
+}
+
+/// Total order for ORDER BY keys, with unbound keys first
+fn order_values(left: Option<&Value>, right: Option<&Value>) -> Ordering {
+    match (left, right) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Less,
+        (Some(_), None) => Ordering::Greater,
+        (Some(l), Some(r)) => order_rank(l).cmp(&order_rank(r)).then_with(|| {
+            compare_values(l, r).unwrap_or_else(|| l.to_string().cmp(&r.to_string()))
+        }),
     }
 }
 
@@ -500,6 +1265,63 @@ fn evaluate_regex(text: &str, pattern: &str, flags: Option<&str>) -> Result<bool
 #[cfg(test)]
 mod tests {
     use super::*;
+    use spargebra::term::{NamedNode, Variable};
+
+    fn integer(value: &str) -> Literal {
+        Literal::new_typed_literal(value, NamedNode::new_unchecked(format!("{XSD}integer")))
+    }
+
+    fn var(name: &str) -> TermPattern {
+        TermPattern::Variable(Variable::new(name).unwrap())
+    }
+
+    fn iri(value: &str) -> NamedNode {
+        NamedNode::new_unchecked(value)
+    }
+
+    fn triple(subject: TermPattern, predicate: &str, object: TermPattern) -> TriplePattern {
+        TriplePattern {
+            subject,
+            predicate: NamedNodePattern::NamedNode(iri(predicate)),
+            object,
+        }
+    }
+
+    /// People with an age and, for some, a city
+    fn people() -> GraphDB {
+        let graph = GraphDB::memory().unwrap();
+        for (name, age, city) in [
+            ("alice", 30, Some("Paris")),
+            ("bob", 9, Some("Lima")),
+            ("carol", 10, None),
+        ] {
+            let subject = NodeId::named(format!("http://example.org/{name}"));
+            graph
+                .insert(Triple::new(
+                    subject.clone(),
+                    Predicate::named("http://example.org/age"),
+                    Value::Integer(age),
+                ))
+                .unwrap();
+            if let Some(city) = city {
+                graph
+                    .insert(Triple::new(
+                        subject,
+                        Predicate::named("http://example.org/city"),
+                        Value::literal(city),
+                    ))
+                    .unwrap();
+            }
+        }
+        graph
+    }
+
+    fn names(solutions: &[Solution], variable: &str) -> Vec<String> {
+        solutions
+            .iter()
+            .map(|s| s.get(variable).map(|v| v.to_string()).unwrap_or_default())
+            .collect()
+    }
 
     #[test]
     fn test_execute_basic_select() {
@@ -559,27 +1381,29 @@ mod tests {
     #[test]
     fn test_filter_comparison_numeric() {
         // Test numeric comparison in filter expression directly
+        let graph = GraphDB::memory().unwrap();
         let mut binding = HashMap::new();
-        binding.insert("age".to_string(), "25".to_string());
+        binding.insert("age".to_string(), Value::Integer(25));
 
-        use spargebra::term::{Literal, Variable};
+        use spargebra::term::Variable;
 
         // Test: ?age > 18
         let var_age = Variable::new("age").unwrap();
         let expr = Expression::Greater(
             Box::new(Expression::Variable(var_age)),
-            Box::new(Expression::Literal(Literal::new_simple_literal("18"))),
+            Box::new(Expression::Literal(integer("18"))),
         );
 
-        let result = evaluate_filter_expression(&expr, &binding).unwrap();
+        let result = evaluate_filter_expression(&graph, &expr, &binding).unwrap();
         assert!(result); // 25 > 18 should be true
     }
 
     #[test]
     fn test_filter_regex() {
         // Test regex in filter expression directly
+        let graph = GraphDB::memory().unwrap();
         let mut binding = HashMap::new();
-        binding.insert("name".to_string(), "John Smith".to_string());
+        binding.insert("name".to_string(), Value::literal("John Smith"));
 
         use spargebra::algebra::Function;
         use spargebra::term::{Literal, Variable};
@@ -593,40 +1417,42 @@ mod tests {
             vec![Expression::Variable(var_name), Expression::Literal(pattern)],
         );
 
-        let result = evaluate_filter_expression(&expr, &binding).unwrap();
+        let result = evaluate_filter_expression(&graph, &expr, &binding).unwrap();
         assert!(result); // "John Smith" matches "^John"
     }
 
     #[test]
     fn test_filter_logical_and() {
         // Test AND logic in filter expression directly
+        let graph = GraphDB::memory().unwrap();
         let mut binding = HashMap::new();
-        binding.insert("age".to_string(), "25".to_string());
+        binding.insert("age".to_string(), Value::Integer(25));
 
-        use spargebra::term::{Literal, Variable};
+        use spargebra::term::Variable;
 
         // Test: ?age >= 18 && ?age <= 30
         let var_age = Variable::new("age").unwrap();
         let expr = Expression::And(
             Box::new(Expression::GreaterOrEqual(
                 Box::new(Expression::Variable(var_age.clone())),
-                Box::new(Expression::Literal(Literal::new_simple_literal("18"))),
+                Box::new(Expression::Literal(integer("18"))),
             )),
             Box::new(Expression::LessOrEqual(
                 Box::new(Expression::Variable(var_age)),
-                Box::new(Expression::Literal(Literal::new_simple_literal("30"))),
+                Box::new(Expression::Literal(integer("30"))),
             )),
         );
 
-        let result = evaluate_filter_expression(&expr, &binding).unwrap();
+        let result = evaluate_filter_expression(&graph, &expr, &binding).unwrap();
         assert!(result); // 25 >= 18 && 25 <= 30 should be true
     }
 
     #[test]
     fn test_filter_not_equal() {
         // Test NOT with equality in filter expression directly
+        let graph = GraphDB::memory().unwrap();
         let mut binding = HashMap::new();
-        binding.insert("city".to_string(), "LA".to_string());
+        binding.insert("city".to_string(), Value::literal("LA"));
 
         use spargebra::term::{Literal, Variable};
 
@@ -637,27 +1463,290 @@ mod tests {
             Box::new(Expression::Literal(Literal::new_simple_literal("NYC"))),
         )));
 
-        let result = evaluate_filter_expression(&expr, &binding).unwrap();
+        let result = evaluate_filter_expression(&graph, &expr, &binding).unwrap();
         assert!(result); // "LA" != "NYC" should be true
     }
 
     #[test]
     fn test_evaluate_filter_bound() {
+        let graph = GraphDB::memory().unwrap();
         let mut binding = HashMap::new();
-        binding.insert("x".to_string(), "value".to_string());
+        binding.insert("x".to_string(), Value::literal("value"));
 
         // Create a BOUND(?x) expression
         use spargebra::term::Variable;
         let var = Variable::new("x").unwrap();
         let expr = Expression::Bound(var.clone());
 
-        let result = evaluate_filter_expression(&expr, &binding).unwrap();
+        let result = evaluate_filter_expression(&graph, &expr, &binding).unwrap();
         assert!(result);
 
         // Test unbound variable
         let var_y = Variable::new("y").unwrap();
         let expr_y = Expression::Bound(var_y);
-        let result_y = evaluate_filter_expression(&expr_y, &binding).unwrap();
+        let result_y = evaluate_filter_expression(&graph, &expr_y, &binding).unwrap();
         assert!(!result_y);
     }
+
+    #[test]
+    fn test_bgp_joins_on_shared_variables() {
+        let graph = people();
+        let pattern = GraphPattern::Bgp {
+            patterns: vec![
+                triple(var("s"), "http://example.org/age", var("age")),
+                triple(var("s"), "http://example.org/city", var("city")),
+            ],
+        };
+
+        let mut results = execute_pattern(&graph, &pattern).unwrap();
+        results.sort_by_key(|s| s["s"].to_string());
+
+        // carol has no city, so only two people satisfy both patterns
+        assert_eq!(
+            names(&results, "s"),
+            vec!["<http://example.org/alice>", "<http://example.org/bob>"]
+        );
+        assert_eq!(names(&results, "age"), vec!["30", "9"]);
+        assert_eq!(names(&results, "city"), vec!["\"Paris\"", "\"Lima\""]);
+    }
+
+    #[test]
+    fn test_plan_delete_insert_where() {
+        use spargebra::term::{GroundQuadPattern, GroundTermPattern, QuadPattern};
+
+        let graph = people();
+        let city = || Variable::new("city").unwrap();
+        let operation = GraphUpdateOperation::DeleteInsert {
+            delete: vec![GroundQuadPattern {
+                subject: GroundTermPattern::Variable(Variable::new("s").unwrap()),
+                predicate: NamedNodePattern::NamedNode(iri("http://example.org/city")),
+                object: GroundTermPattern::Variable(city()),
+                graph_name: GraphNamePattern::DefaultGraph,
+            }],
+            insert: vec![QuadPattern {
+                subject: var("s"),
+                predicate: NamedNodePattern::NamedNode(iri("http://example.org/visited")),
+                object: TermPattern::Variable(city()),
+                graph_name: GraphNamePattern::DefaultGraph,
+            }],
+            using: None,
+            pattern: Box::new(GraphPattern::Bgp {
+                patterns: vec![
+                    triple(var("s"), "http://example.org/city", var("city")),
+                    triple(var("s"), "http://example.org/age", var("age")),
+                ],
+            }),
+        };
+
+        let changes = plan_update(&graph, &operation).unwrap();
+        // Only alice and bob have a city; carol's age alone does not match
+        assert_eq!(changes.delete.len(), 2);
+        assert_eq!(changes.insert.len(), 2);
+        let mut visited: Vec<String> = changes
+            .insert
+            .iter()
+            .map(|t| t.object.to_string())
+            .collect();
+        visited.sort();
+        assert_eq!(visited, vec!["\"Lima\"", "\"Paris\""]);
+        assert!(changes
+            .insert
+            .iter()
+            .all(|t| t.predicate.as_str() == "http://example.org/visited"));
+        // Planning writes nothing
+        assert_eq!(graph.count(), 5);
+    }
+
+    #[test]
+    fn test_check_update_rejects_unsupported_forms() {
+        use spargebra::algebra::GraphTarget;
+        use spargebra::Update;
+
+        let parsed = |operation| ParsedUpdate {
+            original: String::new(),
+            update: Update {
+                base_iri: None,
+                operations: vec![operation],
+            },
+        };
+        let clear = GraphUpdateOperation::Clear {
+            silent: false,
+            graph: GraphTarget::DefaultGraph,
+        };
+        let err = check_update(&parsed(clear)).unwrap_err();
+        assert!(matches!(err, Error::BadRequest(ref m) if m.contains("CLEAR")));
+
+        let filtered = GraphUpdateOperation::DeleteInsert {
+            delete: vec![],
+            insert: vec![],
+            using: None,
+            pattern: Box::new(GraphPattern::Filter {
+                expr: Expression::Bound(Variable::new("s").unwrap()),
+                inner: Box::new(GraphPattern::Bgp { patterns: vec![] }),
+            }),
+        };
+        assert!(check_update(&parsed(filtered)).is_err());
+
+        let insert = GraphUpdateOperation::InsertData { data: vec![] };
+        assert!(check_update(&parsed(insert)).is_ok());
+    }
+
+    #[test]
+    fn test_filter_pushdown_keeps_results() {
+        let graph = people();
+        let bgp = GraphPattern::Bgp {
+            patterns: vec![
+                triple(var("s"), "http://example.org/age", var("age")),
+                triple(var("s"), "http://example.org/city", var("city")),
+            ],
+        };
+        let filtered = GraphPattern::Filter {
+            expr: Expression::Equal(
+                Box::new(Expression::Variable(Variable::new("city").unwrap())),
+                Box::new(Expression::Literal(Literal::new_simple_literal("Lima"))),
+            ),
+            inner: Box::new(bgp),
+        };
+
+        let results = execute_pattern(&graph, &filtered).unwrap();
+        assert_eq!(names(&results, "s"), vec!["<http://example.org/bob>"]);
+        assert_eq!(results[0]["age"], Value::Integer(9));
+
+        let mut seed = Solution::new();
+        if let GraphPattern::Filter { expr, .. } = &filtered {
+            collect_equalities(expr, &mut seed);
+        }
+        assert_eq!(seed.get("city"), Some(&Value::literal("Lima")));
+    }
+
+    #[test]
+    fn test_order_by_numeric_then_slice() {
+        let graph = people();
+        let age = Expression::Variable(Variable::new("age").unwrap());
+        let ordered = GraphPattern::OrderBy {
+            inner: Box::new(GraphPattern::Bgp {
+                patterns: vec![triple(var("s"), "http://example.org/age", var("age"))],
+            }),
+            expression: vec![OrderExpression::Asc(age.clone())],
+        };
+
+        // Numeric order, not the lexical "10" < "30" < "9"
+        let results = execute_pattern(&graph, &ordered).unwrap();
+        assert_eq!(names(&results, "age"), vec!["9", "10", "30"]);
+
+        let descending = GraphPattern::OrderBy {
+            inner: Box::new(GraphPattern::Bgp {
+                patterns: vec![triple(var("s"), "http://example.org/age", var("age"))],
+            }),
+            expression: vec![OrderExpression::Desc(age)],
+        };
+        let page = GraphPattern::Slice {
+            inner: Box::new(descending),
+            start: 1,
+            length: Some(1),
+        };
+        let results = execute_pattern(&graph, &page).unwrap();
+        assert_eq!(names(&results, "age"), vec!["10"]);
+    }
+
+    #[test]
+    fn test_compare_values_by_type() {
+        assert_eq!(
+            compare_values(&Value::Integer(2), &Value::Float(10.5)),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            compare_values(&Value::literal("b"), &Value::literal("a")),
+            Some(Ordering::Greater)
+        );
+        // Instants, not strings: 10:00+02:00 is before 09:00Z
+        assert_eq!(
+            compare_values(
+                &Value::DateTime("2024-05-01T10:00:00+02:00".to_string()),
+                &Value::DateTime("2024-05-01T09:00:00Z".to_string()),
+            ),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            compare_values(&Value::Integer(1), &Value::literal("1")),
+            None
+        );
+        assert!(!values_equal(&Value::Integer(1), &Value::literal("1")));
+        assert!(values_equal(&Value::Integer(1), &Value::Float(1.0)));
+    }
+
+    #[test]
+    fn test_order_values_ranks_kinds() {
+        let iri = Value::Node(NodeId::named("http://example.org/a"));
+        let number = Value::Integer(5);
+        let text = Value::literal("5");
+
+        assert_eq!(order_values(None, Some(&iri)), Ordering::Less);
+        assert_eq!(order_values(Some(&iri), Some(&number)), Ordering::Less);
+        assert_eq!(order_values(Some(&number), Some(&text)), Ordering::Less);
+    }
+
+    #[test]
+    fn test_filter_strstarts_and_datetime() {
+        let graph = GraphDB::memory().unwrap();
+        let mut binding = HashMap::new();
+        binding.insert("name".to_string(), Value::literal("John Smith"));
+        binding.insert(
+            "at".to_string(),
+            Value::DateTime("2024-05-01T12:00:00Z".to_string()),
+        );
+
+        let starts = Expression::FunctionCall(
+            Function::StrStarts,
+            vec![
+                Expression::Variable(Variable::new("name").unwrap()),
+                Expression::Literal(Literal::new_simple_literal("John")),
+            ],
+        );
+        assert!(evaluate_filter_expression(&graph, &starts, &binding).unwrap());
+
+        let after = Expression::Greater(
+            Box::new(Expression::Variable(Variable::new("at").unwrap())),
+            Box::new(Expression::Literal(Literal::new_typed_literal(
+                "2024-01-01T00:00:00Z",
+                iri(&format!("{XSD}dateTime")),
+            ))),
+        );
+        assert!(evaluate_filter_expression(&graph, &after, &binding).unwrap());
+    }
+
+    #[test]
+    fn test_filter_unbound_and_type_errors_are_false() {
+        let graph = GraphDB::memory().unwrap();
+        let mut binding = HashMap::new();
+        binding.insert("age".to_string(), Value::Integer(25));
+
+        // ?missing > 1 and ?age > "text" are errors, so the filter rejects
+        let unbound = Expression::Greater(
+            Box::new(Expression::Variable(Variable::new("missing").unwrap())),
+            Box::new(Expression::Literal(integer("1"))),
+        );
+        assert!(!evaluate_filter_expression(&graph, &unbound, &binding).unwrap());
+
+        let mismatched = Expression::Greater(
+            Box::new(Expression::Variable(Variable::new("age").unwrap())),
+            Box::new(Expression::Literal(Literal::new_simple_literal("text"))),
+        );
+        assert!(!evaluate_filter_expression(&graph, &mismatched, &binding).unwrap());
+
+        // ... unless the other side of an OR holds
+        let either = Expression::Or(
+            Box::new(unbound),
+            Box::new(Expression::Bound(Variable::new("age").unwrap())),
+        );
+        assert!(evaluate_filter_expression(&graph, &either, &binding).unwrap());
+    }
+
+    #[test]
+    fn test_unsupported_function_is_an_error() {
+        let graph = GraphDB::memory().unwrap();
+        let expr = Expression::FunctionCall(Function::Md5, vec![]);
+        let result = evaluate_filter_expression(&graph, &expr, &HashMap::new());
+        assert!(matches!(result, Err(Error::UnsupportedExpression)));
+    }
 }
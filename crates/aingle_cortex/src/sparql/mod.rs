@@ -3,7 +3,8 @@
 
 //! SPARQL query engine for Córtex
 //!
-//! Provides SPARQL 1.1 query support for the AIngle graph.
+//! Provides SPARQL 1.1 query support for the AIngle graph, plus the
+//! INSERT DATA, DELETE DATA and DELETE/INSERT ... WHERE update forms.
 
 mod executor;
 mod parser;
@@ -11,17 +12,28 @@ mod parser;
 pub use executor::*;
 pub use parser::*;
 
-use axum::{extract::State, routing::post, Json, Router};
+use axum::{
+    extract::State,
+    http::{header, HeaderMap},
+    routing::post,
+    Json, Router,
+};
 use serde::{Deserialize, Serialize};
 
-use crate::error::Result;
+use crate::error::{Error, Result};
+use crate::middleware::{RequestNamespace, RequestPrincipal};
 use crate::state::AppState;
 
+/// Content type of a SPARQL update request body
+pub const SPARQL_UPDATE_CONTENT_TYPE: &str = "application/sparql-update";
+
 /// Create SPARQL router
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/sparql", post(execute_sparql))
         .route("/api/v1/sparql", post(execute_sparql))
+        .route("/sparql/update", post(execute_sparql_update))
+        .route("/api/v1/sparql/update", post(execute_sparql_update))
 }
 
 /// SPARQL query request
@@ -68,6 +80,60 @@ pub async fn execute_sparql(
     Ok(Json(resp))
 }
 
+/// SPARQL update response
+#[derive(Debug, Serialize)]
+pub struct SparqlUpdateResponse {
+    /// Number of update operations applied
+    pub operations: usize,
+    /// Number of triples added
+    pub inserted: usize,
+    /// Number of triples removed
+    pub deleted: usize,
+    /// Execution time in milliseconds
+    pub execution_time_ms: u64,
+}
+
+/// Apply a SPARQL update
+///
+/// POST /sparql/update with an `application/sparql-update` body. Requires
+/// write scope; see [`RequestPrincipal::can_write`].
+pub async fn execute_sparql_update(
+    State(state): State<AppState>,
+    ns_ext: Option<axum::Extension<RequestNamespace>>,
+    principal_ext: Option<axum::Extension<RequestPrincipal>>,
+    headers: HeaderMap,
+    body: String,
+) -> Result<Json<SparqlUpdateResponse>> {
+    let principal = RequestPrincipal::from_extension(principal_ext);
+    if !principal.can_write() {
+        return Err(Error::Forbidden(
+            "SPARQL UPDATE requires write scope".to_string(),
+        ));
+    }
+
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    if !content_type.starts_with(SPARQL_UPDATE_CONTENT_TYPE) {
+        return Err(Error::BadRequest(format!(
+            "SPARQL updates must be sent as {SPARQL_UPDATE_CONTENT_TYPE}"
+        )));
+    }
+
+    // Guard: if Raft is initialized, all writes MUST go through Raft.
+    #[cfg(feature = "cluster")]
+    if state.raft.is_some() {
+        return Err(Error::BadRequest(
+            "SPARQL UPDATE is not replicated in cluster mode; use the triples API".into(),
+        ));
+    }
+
+    let namespace = ns_ext.and_then(|axum::Extension(RequestNamespace(ns))| ns);
+    let resp = crate::service::sparql::update(&state, &body, namespace, principal.user_id).await?;
+    Ok(Json(resp))
+}
+
 /// SPARQL result
 #[derive(Debug)]
 pub struct SparqlResult {
@@ -4,7 +4,7 @@
 //! SPARQL query parser
 
 use crate::error::{Error, Result};
-use spargebra::{Query, SparqlParser};
+use spargebra::{Query, SparqlParser, Update};
 
 /// Parsed SPARQL query
 #[derive(Debug)]
@@ -46,6 +46,27 @@ pub fn parse_sparql(query: &str) -> Result<ParsedQuery> {
     })
 }
 
+/// Parsed SPARQL update request
+#[derive(Debug)]
+pub struct ParsedUpdate {
+    /// The original update string
+    pub original: String,
+    /// Parsed update operations, in request order
+    pub update: Update,
+}
+
+/// Parse a SPARQL update string
+pub fn parse_sparql_update(update: &str) -> Result<ParsedUpdate> {
+    let parsed = SparqlParser::new()
+        .parse_update(update)
+        .map_err(|e| Error::SparqlParseError(format!("Failed to parse SPARQL update: {}", e)))?;
+
+    Ok(ParsedUpdate {
+        original: update.to_string(),
+        update: parsed,
+    })
+}
+
 /// Extract variable names from a SELECT query
 pub fn extract_variables(query: &ParsedQuery) -> Vec<String> {
     match &query.query {
@@ -76,6 +97,14 @@ mod tests {
         assert_eq!(parsed.query_type, QueryType::Ask);
     }
 
+    #[test]
+    fn test_parse_update() {
+        let update = "INSERT DATA { <http://example.org/a> <http://example.org/p> 1 }";
+        let parsed = parse_sparql_update(update).unwrap();
+        assert_eq!(parsed.update.operations.len(), 1);
+        assert!(parse_sparql_update("SELECT ?s WHERE { ?s ?p ?o }").is_err());
+    }
+
     #[test]
     fn test_parse_invalid() {
         let query = "INVALID QUERY";
@@ -12,8 +12,11 @@ use tokio::sync::RwLock;
 
 #[cfg(feature = "auth")]
 use crate::auth::UserStore;
+use crate::datasets::{DatasetRegistry, DEFAULT_DATASET};
 use crate::proofs::ProofStore;
 use crate::rest::audit::AuditLog;
+use crate::shutdown::ShutdownHandle;
+use crate::tombstones::TombstoneStore;
 
 // ---------------------------------------------------------------------------
 // Cache type aliases (avoid clippy::type_complexity on the struct fields)
@@ -39,6 +42,16 @@ type LocalGraphCache = std::sync::Mutex<
     >,
 >;
 
+/// Default for [`AppState::max_batch_triples`].
+pub const DEFAULT_MAX_BATCH_TRIPLES: usize = 10_000;
+
+/// Default for [`AppState::tombstone_retention`]: 30 days.
+pub const DEFAULT_TOMBSTONE_RETENTION: std::time::Duration =
+    std::time::Duration::from_secs(30 * 24 * 60 * 60);
+
+/// Default number of recent events [`EventBroadcaster`] keeps for replay.
+pub const DEFAULT_EVENT_REPLAY_BUFFER: usize = 1024;
+
 /// The shared state accessible by all API handlers.
 ///
 /// This struct uses `Arc` and `RwLock` to provide safe, concurrent access
@@ -122,6 +135,16 @@ pub struct AppState {
     /// captured at router-build time. `None` means no static token is configured.
     #[cfg(feature = "mcp")]
     pub mcp_token: std::sync::Arc<std::sync::RwLock<Vec<String>>>,
+    /// Maximum number of triples accepted by one batch insert.
+    pub max_batch_triples: usize,
+    /// Tombstones and history of deleted triples.
+    pub tombstones: Arc<TombstoneStore>,
+    /// How long tombstones are kept before a purge may drop them.
+    pub tombstone_retention: std::time::Duration,
+    /// Signals a graceful shutdown to long-lived requests and subscriptions.
+    pub shutdown: ShutdownHandle,
+    /// Named datasets served next to the default one (this state's graph).
+    pub datasets: Arc<DatasetRegistry>,
 }
 
 impl AppState {
@@ -182,6 +205,11 @@ impl AppState {
             )),
             #[cfg(feature = "mcp")]
             mcp_token: std::sync::Arc::new(std::sync::RwLock::new(Vec::new())),
+            max_batch_triples: DEFAULT_MAX_BATCH_TRIPLES,
+            tombstones: Arc::new(TombstoneStore::new()),
+            tombstone_retention: DEFAULT_TOMBSTONE_RETENTION,
+            shutdown: ShutdownHandle::new(),
+            datasets: Arc::new(DatasetRegistry::new()),
         })
     }
 
@@ -240,6 +268,11 @@ impl AppState {
             )),
             #[cfg(feature = "mcp")]
             mcp_token: std::sync::Arc::new(std::sync::RwLock::new(Vec::new())),
+            max_batch_triples: DEFAULT_MAX_BATCH_TRIPLES,
+            tombstones: Arc::new(TombstoneStore::new()),
+            tombstone_retention: DEFAULT_TOMBSTONE_RETENTION,
+            shutdown: ShutdownHandle::new(),
+            datasets: Arc::new(DatasetRegistry::new()),
         }
     }
 
@@ -298,6 +331,11 @@ impl AppState {
             )),
             #[cfg(feature = "mcp")]
             mcp_token: std::sync::Arc::new(std::sync::RwLock::new(Vec::new())),
+            max_batch_triples: DEFAULT_MAX_BATCH_TRIPLES,
+            tombstones: Arc::new(TombstoneStore::new()),
+            tombstone_retention: DEFAULT_TOMBSTONE_RETENTION,
+            shutdown: ShutdownHandle::new(),
+            datasets: Arc::new(DatasetRegistry::new()),
         })
     }
 
@@ -424,6 +462,27 @@ impl AppState {
             Arc::new(ProofStore::new())
         };
 
+        // Tombstones live in their own sibling sled DB, like the ProofStore.
+        let tombstones = if db_path != ":memory:" {
+            let tombstone_db_path = Path::new(db_path)
+                .parent()
+                .unwrap_or(Path::new("."))
+                .join("tombstones.sled");
+            let tombstone_db_str = tombstone_db_path.to_string_lossy();
+            match TombstoneStore::with_sled(&tombstone_db_str) {
+                Ok(ts) => Arc::new(ts),
+                Err(e) => {
+                    log::warn!(
+                        "Failed to open Sled TombstoneStore: {}. Falling back to in-memory.",
+                        e
+                    );
+                    Arc::new(TombstoneStore::new())
+                }
+            }
+        } else {
+            Arc::new(TombstoneStore::new())
+        };
+
         #[cfg(feature = "auth")]
         let user_store = {
             let store = Arc::new(UserStore::new());
@@ -473,6 +532,11 @@ impl AppState {
             )),
             #[cfg(feature = "mcp")]
             mcp_token: std::sync::Arc::new(std::sync::RwLock::new(Vec::new())),
+            max_batch_triples: DEFAULT_MAX_BATCH_TRIPLES,
+            tombstones,
+            tombstone_retention: DEFAULT_TOMBSTONE_RETENTION,
+            shutdown: ShutdownHandle::new(),
+            datasets: Arc::new(DatasetRegistry::new()),
         })
     }
 
@@ -492,6 +556,19 @@ impl AppState {
             log::warn!("Failed to flush proof store: {}", e);
         }
 
+        // Flush tombstones
+        if let Err(e) = self.tombstones.flush() {
+            log::warn!("Failed to flush tombstone store: {}", e);
+        }
+
+        // Flush the named datasets opened so far
+        for (name, dataset) in self.datasets.opened() {
+            dataset.graph.read().await.flush()?;
+            if let Err(e) = dataset.tombstones.flush() {
+                log::warn!("Failed to flush tombstones of dataset {}: {}", name, e);
+            }
+        }
+
         // Save Ineru memory snapshot.
         //
         // NEVER persist while the embedder is a not-yet-loaded placeholder
@@ -612,6 +689,33 @@ impl AppState {
         crate::client::CortexInternalClient::default_client()
     }
 
+    /// Returns the state serving the dataset `name`, opening it on first use.
+    ///
+    /// The result shares everything with `self` except the graph, tombstones
+    /// and event broadcaster. [`DEFAULT_DATASET`] resolves to `self`.
+    pub fn dataset(&self, name: &str) -> crate::error::Result<AppState> {
+        if name == DEFAULT_DATASET {
+            return Ok(self.clone());
+        }
+
+        // Raft and the WAL replicate the default graph only
+        #[cfg(feature = "cluster")]
+        if self.raft.is_some() || self.wal.is_some() {
+            return Err(crate::error::Error::BadRequest(format!(
+                "Dataset \"{}\" is not available in cluster mode",
+                name
+            )));
+        }
+
+        let handle = self.datasets.open(name)?;
+        Ok(AppState {
+            graph: handle.graph,
+            tombstones: handle.tombstones,
+            broadcaster: handle.broadcaster,
+            ..self.clone()
+        })
+    }
+
     /// Gathers and returns statistics about the graph and connected clients.
     pub async fn stats(&self) -> GraphStats {
         let graph = self.graph.read().await;
@@ -707,23 +811,58 @@ pub struct GraphStats {
 }
 
 /// A broadcaster for sending real-time `Event`s to WebSocket subscribers.
+///
+/// Every event is numbered with a sequence number, starting at 1, and the
+/// most recent ones are kept in a bounded buffer so a reconnecting client can
+/// pick up where it left off with [`EventBroadcaster::subscribe_from`].
 pub struct EventBroadcaster {
     /// The underlying `tokio::sync::broadcast` sender.
     sender: tokio::sync::broadcast::Sender<Event>,
+    /// Sender for the same events together with their sequence numbers.
+    sequenced: tokio::sync::broadcast::Sender<SequencedEvent>,
+    /// Recent events for replay. Held while an event is numbered and sent, so
+    /// a subscriber sees each event exactly once, from the buffer or live.
+    replay: std::sync::Mutex<ReplayBuffer>,
     /// An atomic counter for the number of connected clients.
     client_count: std::sync::atomic::AtomicUsize,
 }
 
+struct ReplayBuffer {
+    next_seq: u64,
+    capacity: usize,
+    events: std::collections::VecDeque<SequencedEvent>,
+}
+
 impl EventBroadcaster {
     /// Creates a new `EventBroadcaster`.
     pub fn new() -> Self {
+        Self::with_replay_capacity(DEFAULT_EVENT_REPLAY_BUFFER)
+    }
+
+    /// Creates an `EventBroadcaster` that keeps the last `capacity` events for replay.
+    pub fn with_replay_capacity(capacity: usize) -> Self {
         let (sender, _) = tokio::sync::broadcast::channel(1024);
+        let (sequenced, _) = tokio::sync::broadcast::channel(1024);
         Self {
             sender,
+            sequenced,
+            replay: std::sync::Mutex::new(ReplayBuffer {
+                next_seq: 1,
+                capacity,
+                events: std::collections::VecDeque::with_capacity(capacity.min(1024)),
+            }),
             client_count: std::sync::atomic::AtomicUsize::new(0),
         }
     }
 
+    /// Changes how many events are kept for replay, dropping the oldest if needed.
+    pub fn set_replay_capacity(&self, capacity: usize) {
+        let mut replay = self.replay();
+        replay.capacity = capacity;
+        let excess = replay.events.len().saturating_sub(capacity);
+        replay.events.drain(..excess);
+    }
+
     /// Subscribes to the broadcast channel to receive events.
     /// This also increments the client count.
     pub fn subscribe(&self) -> tokio::sync::broadcast::Receiver<Event> {
@@ -732,6 +871,47 @@ impl EventBroadcaster {
         self.sender.subscribe()
     }
 
+    /// Subscribes to sequenced events, replaying those after `after_seq` first.
+    ///
+    /// With `after_seq: None` nothing is replayed. Fails if events after the
+    /// cursor are no longer buffered, or if the cursor is ahead of the last
+    /// event (as after a server restart); the client must then resync.
+    /// This also increments the client count.
+    pub fn subscribe_from(&self, after_seq: Option<u64>) -> Result<EventReplay, ReplayError> {
+        let replay = self.replay();
+        let latest_seq = replay.next_seq - 1;
+        let backlog = match after_seq {
+            None => Vec::new(),
+            Some(after_seq) if after_seq > latest_seq => {
+                return Err(ReplayError::CursorAhead {
+                    after_seq,
+                    latest_seq,
+                })
+            }
+            Some(after_seq) => {
+                let oldest_seq = replay.events.front().map_or(replay.next_seq, |e| e.seq);
+                if after_seq + 1 < oldest_seq {
+                    return Err(ReplayError::CursorExpired {
+                        after_seq,
+                        oldest_seq,
+                    });
+                }
+                replay
+                    .events
+                    .iter()
+                    .filter(|e| e.seq > after_seq)
+                    .cloned()
+                    .collect()
+            }
+        };
+        let live = self.sequenced.subscribe();
+        drop(replay);
+
+        self.client_count
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        Ok(EventReplay { backlog, live })
+    }
+
     /// Decrements the client count when a client unsubscribes.
     pub fn unsubscribe(&self) {
         // Use fetch_update to prevent underflow wrapping to usize::MAX.
@@ -744,13 +924,38 @@ impl EventBroadcaster {
 
     /// Broadcasts an `Event` to all active subscribers.
     pub fn broadcast(&self, event: Event) {
+        let mut replay = self.replay();
+        let sequenced = SequencedEvent {
+            seq: replay.next_seq,
+            timestamp: chrono::Utc::now(),
+            event: event.clone(),
+        };
+        replay.next_seq += 1;
+        if replay.capacity > 0 {
+            if replay.events.len() == replay.capacity {
+                replay.events.pop_front();
+            }
+            replay.events.push_back(sequenced.clone());
+        }
+        let _ = self.sequenced.send(sequenced);
+        drop(replay);
+
         let _ = self.sender.send(event);
     }
 
+    /// Sequence number of the most recent event, or 0 if none was sent.
+    pub fn latest_seq(&self) -> u64 {
+        self.replay().next_seq - 1
+    }
+
     /// Returns the number of currently connected clients.
     pub fn client_count(&self) -> usize {
         self.client_count.load(std::sync::atomic::Ordering::SeqCst)
     }
+
+    fn replay(&self) -> std::sync::MutexGuard<'_, ReplayBuffer> {
+        self.replay.lock().unwrap_or_else(|e| e.into_inner())
+    }
 }
 
 impl Default for EventBroadcaster {
@@ -791,6 +996,64 @@ impl Event {
     }
 }
 
+/// An [`Event`] together with its sequence number.
+#[derive(Debug, Clone)]
+pub struct SequencedEvent {
+    /// Position of the event in the broadcast order, starting at 1.
+    pub seq: u64,
+    /// When the event was broadcast.
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// The event itself.
+    pub event: Event,
+}
+
+impl SequencedEvent {
+    /// Serializes the event to a JSON value with an added `seq` field.
+    pub fn to_value(&self) -> serde_json::Value {
+        let mut value = serde_json::to_value(&self.event).unwrap_or_default();
+        if let serde_json::Value::Object(ref mut map) = value {
+            map.insert("seq".to_string(), self.seq.into());
+        }
+        value
+    }
+
+    /// Serializes the event to a JSON string with an added `seq` field.
+    pub fn to_json(&self) -> String {
+        self.to_value().to_string()
+    }
+}
+
+/// A subscription from [`EventBroadcaster::subscribe_from`].
+pub struct EventReplay {
+    /// Buffered events after the cursor, oldest first.
+    pub backlog: Vec<SequencedEvent>,
+    /// Events broadcast after the backlog was taken.
+    pub live: tokio::sync::broadcast::Receiver<SequencedEvent>,
+}
+
+/// Why [`EventBroadcaster::subscribe_from`] could not resume from a cursor.
+///
+/// Either way some events cannot be replayed and the client must resync.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ReplayError {
+    /// Events after the cursor have been dropped from the replay buffer.
+    #[error("events after {after_seq} are no longer buffered (oldest is {oldest_seq})")]
+    CursorExpired {
+        /// The requested cursor.
+        after_seq: u64,
+        /// Oldest sequence number still buffered.
+        oldest_seq: u64,
+    },
+    /// The cursor is past the last event, e.g. after a server restart.
+    #[error("cursor {after_seq} is ahead of the latest event {latest_seq}")]
+    CursorAhead {
+        /// The requested cursor.
+        after_seq: u64,
+        /// Sequence number of the latest event.
+        latest_seq: u64,
+    },
+}
+
 // ---------------------------------------------------------------------------
 // Sandbox Manager
 // ---------------------------------------------------------------------------
@@ -868,6 +1131,71 @@ impl Default for SandboxManager {
 mod tests {
     use super::*;
 
+    fn ping_seqs(replay: &EventReplay) -> Vec<u64> {
+        replay.backlog.iter().map(|e| e.seq).collect()
+    }
+
+    #[tokio::test]
+    async fn broadcaster_replays_after_cursor_then_goes_live() {
+        let broadcaster = EventBroadcaster::with_replay_capacity(8);
+        for _ in 0..5 {
+            broadcaster.broadcast(Event::Ping);
+        }
+        assert_eq!(broadcaster.latest_seq(), 5);
+
+        let mut replay = broadcaster.subscribe_from(Some(2)).unwrap();
+        assert_eq!(ping_seqs(&replay), vec![3, 4, 5]);
+        broadcaster.broadcast(Event::Ping);
+        assert_eq!(replay.live.recv().await.unwrap().seq, 6);
+
+        // Caught up, and no cursor: nothing to replay
+        assert!(broadcaster
+            .subscribe_from(Some(6))
+            .unwrap()
+            .backlog
+            .is_empty());
+        assert!(broadcaster.subscribe_from(None).unwrap().backlog.is_empty());
+    }
+
+    #[test]
+    fn broadcaster_rejects_cursors_it_cannot_resume() {
+        let broadcaster = EventBroadcaster::with_replay_capacity(3);
+        for _ in 0..10 {
+            broadcaster.broadcast(Event::Ping);
+        }
+
+        // Only 8, 9 and 10 are buffered, so resuming after 7 is the limit
+        assert_eq!(
+            ping_seqs(&broadcaster.subscribe_from(Some(7)).unwrap()),
+            vec![8, 9, 10]
+        );
+        assert_eq!(
+            broadcaster.subscribe_from(Some(6)).err(),
+            Some(ReplayError::CursorExpired {
+                after_seq: 6,
+                oldest_seq: 8
+            })
+        );
+        assert_eq!(
+            broadcaster.subscribe_from(Some(11)).err(),
+            Some(ReplayError::CursorAhead {
+                after_seq: 11,
+                latest_seq: 10
+            })
+        );
+
+        // Shrinking the buffer drops the oldest events
+        broadcaster.set_replay_capacity(1);
+        assert!(matches!(
+            broadcaster.subscribe_from(Some(8)),
+            Err(ReplayError::CursorExpired { oldest_seq: 10, .. })
+        ));
+        broadcaster.set_replay_capacity(0);
+        broadcaster.broadcast(Event::Ping);
+        assert!(broadcaster.subscribe_from(Some(11)).is_ok());
+        assert!(broadcaster.subscribe_from(Some(10)).is_err());
+    }
+
     #[test]
     fn appstate_has_default_hash_embedder() {
         let state = AppState::new().unwrap();
@@ -0,0 +1,339 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Tombstones and history for deleted triples
+//!
+//! Deleting a triple through Córtex removes it from the graph, so normal reads
+//! never see it, but a copy is kept here together with who deleted it, when,
+//! and why. Investigators can list removed triples and read a triple's
+//! creation/deletion history until a retention purge physically drops the
+//! record.
+//!
+//! Triples that were never deleted have no record; their history is derived
+//! from the graph's own metadata.
+
+use aingle_graph::{Triple, TripleId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::error::{Error, Result};
+
+/// What happened to a triple
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryEventKind {
+    /// The triple was asserted into the graph
+    Created,
+    /// The triple was deleted (tombstoned)
+    Deleted,
+}
+
+/// A single entry in a triple's history
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEvent {
+    pub event: HistoryEventKind,
+    pub at: DateTime<Utc>,
+    /// Who performed the change, when known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+    /// Why the change was made, when given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// The retained copy and history of a triple that has been deleted at least once
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TripleRecord {
+    /// The triple as it was when last deleted
+    pub triple: Triple,
+    /// Creation and deletion events, oldest first
+    pub events: Vec<HistoryEvent>,
+    /// Set while the triple is tombstoned; cleared if it is re-created
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+impl TripleRecord {
+    /// Whether the triple is currently tombstoned
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    /// The most recent deletion event, if the triple is tombstoned
+    pub fn deletion(&self) -> Option<&HistoryEvent> {
+        self.deleted_at?;
+        self.events
+            .iter()
+            .rev()
+            .find(|e| e.event == HistoryEventKind::Deleted)
+    }
+}
+
+/// Store of tombstoned triples and their history
+///
+/// Records are cached in memory and written through to a Sled tree when the
+/// store is persistent.
+pub struct TombstoneStore {
+    records: RwLock<HashMap<TripleId, TripleRecord>>,
+    tree: Option<sled::Tree>,
+}
+
+impl TombstoneStore {
+    /// Create an in-memory store
+    pub fn new() -> Self {
+        Self {
+            records: RwLock::new(HashMap::new()),
+            tree: None,
+        }
+    }
+
+    /// Open (or create) a persistent store in the Sled database at `path`
+    pub fn with_sled(path: &str) -> std::result::Result<Self, String> {
+        let db = sled::open(path).map_err(|e| format!("sled open error (tombstones): {e}"))?;
+        let tree = db
+            .open_tree("tombstones")
+            .map_err(|e| format!("sled open_tree(tombstones) error: {e}"))?;
+
+        let mut records = HashMap::new();
+        for item in tree.iter() {
+            let (k, v) = item.map_err(|e| format!("sled tombstones scan error: {e}"))?;
+            let Ok(bytes) = <[u8; 32]>::try_from(k.as_ref()) else {
+                log::warn!("Skipping tombstone with malformed key");
+                continue;
+            };
+            match serde_json::from_slice::<TripleRecord>(&v) {
+                Ok(record) => {
+                    records.insert(TripleId::new(bytes), record);
+                }
+                Err(e) => log::warn!("Skipping unreadable tombstone: {}", e),
+            }
+        }
+
+        Ok(Self {
+            records: RwLock::new(records),
+            tree: Some(tree),
+        })
+    }
+
+    /// Record that `triple` was deleted
+    ///
+    /// The first time a triple is deleted its creation event is reconstructed
+    /// from the triple's metadata.
+    pub fn record_deletion(
+        &self,
+        triple: &Triple,
+        actor: Option<String>,
+        reason: Option<String>,
+        at: DateTime<Utc>,
+    ) -> Result<()> {
+        let id = triple.id();
+        let mut records = self.write()?;
+        let record = records.entry(id.clone()).or_insert_with(|| TripleRecord {
+            triple: triple.clone(),
+            events: vec![HistoryEvent {
+                event: HistoryEventKind::Created,
+                at: triple.meta.created_at,
+                actor: triple.meta.source.clone(),
+                reason: None,
+            }],
+            deleted_at: None,
+        });
+        record.triple = triple.clone();
+        record.events.push(HistoryEvent {
+            event: HistoryEventKind::Deleted,
+            at,
+            actor,
+            reason,
+        });
+        record.deleted_at = Some(at);
+        self.persist(&id, record)
+    }
+
+    /// Record that a previously deleted triple was created again
+    ///
+    /// Does nothing for triples without a record. Returns whether a tombstone
+    /// was cleared.
+    pub fn record_creation(
+        &self,
+        id: &TripleId,
+        actor: Option<String>,
+        at: DateTime<Utc>,
+    ) -> Result<bool> {
+        let mut records = self.write()?;
+        let Some(record) = records.get_mut(id) else {
+            return Ok(false);
+        };
+        if !record.is_deleted() {
+            return Ok(false);
+        }
+        record.events.push(HistoryEvent {
+            event: HistoryEventKind::Created,
+            at,
+            actor,
+            reason: None,
+        });
+        record.deleted_at = None;
+        self.persist(id, record)?;
+        Ok(true)
+    }
+
+    /// Look up the record for a triple
+    pub fn get(&self, id: &TripleId) -> Result<Option<TripleRecord>> {
+        Ok(self.read()?.get(id).cloned())
+    }
+
+    /// All currently tombstoned triples, most recently deleted first
+    pub fn deleted(&self) -> Result<Vec<TripleRecord>> {
+        let mut deleted: Vec<TripleRecord> = self
+            .read()?
+            .values()
+            .filter(|r| r.is_deleted())
+            .cloned()
+            .collect();
+        deleted.sort_by_key(|r| std::cmp::Reverse(r.deleted_at));
+        Ok(deleted)
+    }
+
+    /// Number of currently tombstoned triples
+    pub fn deleted_count(&self) -> Result<usize> {
+        Ok(self.read()?.values().filter(|r| r.is_deleted()).count())
+    }
+
+    /// Physically remove every tombstone deleted before `cutoff`
+    ///
+    /// Returns the ids of the purged triples.
+    pub fn purge_before(&self, cutoff: DateTime<Utc>) -> Result<Vec<TripleId>> {
+        let mut records = self.write()?;
+        let expired: Vec<TripleId> = records
+            .iter()
+            .filter(|(_, r)| r.deleted_at.is_some_and(|at| at < cutoff))
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        for id in &expired {
+            if let Some(tree) = &self.tree {
+                tree.remove(id.as_bytes())
+                    .map_err(|e| Error::Internal(format!("sled tombstones delete error: {e}")))?;
+            }
+            records.remove(id);
+        }
+        Ok(expired)
+    }
+
+    /// Flush pending writes to durable storage
+    pub fn flush(&self) -> std::result::Result<(), String> {
+        if let Some(tree) = &self.tree {
+            tree.flush()
+                .map_err(|e| format!("sled tombstones flush error: {e}"))?;
+        }
+        Ok(())
+    }
+
+    fn persist(&self, id: &TripleId, record: &TripleRecord) -> Result<()> {
+        if let Some(tree) = &self.tree {
+            let bytes = serde_json::to_vec(record)
+                .map_err(|e| Error::Internal(format!("tombstone encode error: {e}")))?;
+            tree.insert(id.as_bytes(), bytes)
+                .map_err(|e| Error::Internal(format!("sled tombstones insert error: {e}")))?;
+        }
+        Ok(())
+    }
+
+    fn read(&self) -> Result<std::sync::RwLockReadGuard<'_, HashMap<TripleId, TripleRecord>>> {
+        self.records
+            .read()
+            .map_err(|_| Error::Internal("TombstoneStore lock poisoned".to_string()))
+    }
+
+    fn write(&self) -> Result<std::sync::RwLockWriteGuard<'_, HashMap<TripleId, TripleRecord>>> {
+        self.records
+            .write()
+            .map_err(|_| Error::Internal("TombstoneStore lock poisoned".to_string()))
+    }
+}
+
+impl Default for TombstoneStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aingle_graph::{NodeId, Predicate, Value};
+    use chrono::Duration;
+
+    fn triple(subject: &str) -> Triple {
+        Triple::new(
+            NodeId::named(subject),
+            Predicate::named("ex:status"),
+            Value::literal("active"),
+        )
+    }
+
+    #[test]
+    fn test_deletion_then_recreation() {
+        let store = TombstoneStore::new();
+        let t = triple("ex:alice");
+        let id = t.id();
+        let now = Utc::now();
+
+        store
+            .record_deletion(&t, Some("auditor".into()), Some("GDPR".into()), now)
+            .unwrap();
+        let record = store.get(&id).unwrap().unwrap();
+        assert!(record.is_deleted());
+        assert_eq!(record.events.len(), 2);
+        assert_eq!(record.events[0].event, HistoryEventKind::Created);
+        assert_eq!(record.deletion().unwrap().reason.as_deref(), Some("GDPR"));
+
+        assert!(store.record_creation(&id, None, now).unwrap());
+        assert!(!store.record_creation(&id, None, now).unwrap());
+        let record = store.get(&id).unwrap().unwrap();
+        assert!(!record.is_deleted());
+        assert_eq!(record.events.len(), 3);
+        assert_eq!(store.deleted_count().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_purge_respects_cutoff() {
+        let store = TombstoneStore::new();
+        let now = Utc::now();
+        let old = triple("ex:old");
+        let recent = triple("ex:recent");
+        store
+            .record_deletion(&old, None, None, now - Duration::days(40))
+            .unwrap();
+        store.record_deletion(&recent, None, None, now).unwrap();
+
+        let purged = store.purge_before(now - Duration::days(30)).unwrap();
+        assert_eq!(purged, vec![old.id()]);
+        assert!(store.get(&old.id()).unwrap().is_none());
+        assert!(store.get(&recent.id()).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_sled_store_reopens() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tombstones.sled");
+        let path = path.to_string_lossy().to_string();
+        let t = triple("ex:persisted");
+
+        {
+            let store = TombstoneStore::with_sled(&path).unwrap();
+            store
+                .record_deletion(&t, Some("admin".into()), None, Utc::now())
+                .unwrap();
+            store.flush().unwrap();
+        }
+
+        let store = TombstoneStore::with_sled(&path).unwrap();
+        let record = store.get(&t.id()).unwrap().unwrap();
+        assert!(record.is_deleted());
+        assert_eq!(record.triple.subject, t.subject);
+    }
+}
@@ -0,0 +1,148 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Integration tests for bulk triple ingestion
+//!
+//! Drives `POST /api/v1/triples/batch` through the REST router:
+//! - NDJSON and JSON array bodies
+//! - Per-item report and graph counts
+//! - Atomic batches aborted by a rejected triple
+
+use aingle_cortex::rest;
+use aingle_cortex::state::AppState;
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+const BATCH_SIZE: usize = 10_000;
+
+fn triple(i: usize) -> Value {
+    json!({
+        "subject": format!("ex:sensor{}", i),
+        "predicate": "ex:reading",
+        "object": i as i64,
+    })
+}
+
+async fn post_batch(
+    state: &AppState,
+    uri: &str,
+    content_type: &str,
+    body: String,
+) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-type", content_type)
+        .body(Body::from(body))
+        .unwrap();
+    let response = rest::router()
+        .with_state(state.clone())
+        .oneshot(request)
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn test_ndjson_batch_of_10k_triples() {
+    let state = AppState::new().unwrap();
+    let body: String = (0..BATCH_SIZE)
+        .map(|i| format!("{}\n", triple(i)))
+        .collect();
+
+    let (status, report) = post_batch(
+        &state,
+        "/api/v1/triples/batch",
+        "application/x-ndjson",
+        body,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(report["committed"], true);
+    assert_eq!(report["total"], BATCH_SIZE);
+    assert_eq!(report["rejected"], 0);
+    assert_eq!(report["results"].as_array().unwrap().len(), BATCH_SIZE);
+    assert_eq!(state.graph.read().await.count(), BATCH_SIZE);
+}
+
+#[tokio::test]
+async fn test_json_array_batch_of_10k_triples() {
+    let state = AppState::new().unwrap();
+    let body = Value::Array((0..BATCH_SIZE).map(triple).collect()).to_string();
+
+    let (status, report) =
+        post_batch(&state, "/api/v1/triples/batch", "application/json", body).await;
+
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(report["total"], BATCH_SIZE);
+    assert_eq!(state.graph.read().await.count(), BATCH_SIZE);
+
+    // Re-sending the same batch only reports duplicates
+    let body = Value::Array((0..BATCH_SIZE).map(triple).collect()).to_string();
+    let (_, report) = post_batch(&state, "/api/v1/triples/batch", "application/json", body).await;
+    assert_eq!(report["duplicates"], BATCH_SIZE);
+    assert_eq!(report["results"][0]["status"], "duplicate");
+    assert_eq!(state.graph.read().await.count(), BATCH_SIZE);
+}
+
+#[tokio::test]
+async fn test_atomic_batch_with_invalid_triple_is_aborted() {
+    let state = AppState::new().unwrap();
+    let body = format!(
+        "{}\n{}\n",
+        triple(0),
+        json!({"subject": "", "predicate": "ex:reading", "object": 1})
+    );
+
+    let (status, report) = post_batch(
+        &state,
+        "/api/v1/triples/batch",
+        "application/x-ndjson",
+        body.clone(),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(report["committed"], false);
+    assert_eq!(report["results"][0]["status"], "aborted");
+    assert_eq!(report["results"][1]["status"], "rejected");
+    assert_eq!(state.graph.read().await.count(), 0);
+
+    // The same body in best-effort mode keeps the valid triple
+    let (status, report) = post_batch(
+        &state,
+        "/api/v1/triples/batch?atomic=false",
+        "application/x-ndjson",
+        body,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(report["results"][0]["status"], "inserted");
+    assert_eq!(state.graph.read().await.count(), 1);
+}
+
+#[tokio::test]
+async fn test_malformed_ndjson_line_is_bad_request() {
+    let state = AppState::new().unwrap();
+    let body = format!("{}\nnot json\n", triple(0));
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/v1/triples/batch")
+        .header("content-type", "application/x-ndjson")
+        .body(Body::from(body))
+        .unwrap();
+    let response = rest::router()
+        .with_state(state.clone())
+        .oneshot(request)
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(state.graph.read().await.count(), 0);
+}
@@ -47,6 +47,7 @@ async fn test_proof_store_sled_roundtrip_data_integrity() {
                     tags: vec![format!("tag-{}", i)],
                     extra: Default::default(),
                 }),
+                expires_at: None,
             };
             let id = store.submit(request).await.unwrap();
             proof_ids.push(id);
@@ -286,6 +287,7 @@ async fn test_app_state_flush_restore_roundtrip() {
                 proof_type: ProofType::Schnorr,
                 proof_data: serde_json::json!({"flush_test": i}),
                 metadata: None,
+                expires_at: None,
             };
             ids.push(state.proof_store.submit(request).await.unwrap());
         }
@@ -553,6 +555,7 @@ async fn test_audit_log_fsync_integrity() {
                 resource: format!("/api/v1/triples/{}", i),
                 details: Some(format!("detail-{}", i)),
                 request_id: Some(format!("req-{}", i)),
+                request: None,
             });
         }
         assert_eq!(log.len(), 50);
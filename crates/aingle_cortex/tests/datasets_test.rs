@@ -0,0 +1,338 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Integration tests for named datasets
+//!
+//! Drives the dataset routes through the REST router:
+//! - `/api/v1/datasets/:name/...` reads and writes only that dataset's graph
+//! - The unprefixed routes keep serving the default dataset
+//! - Dataset-qualified roles (`write:triples@customer-a`) confine a caller
+//! - `GET`/`POST /api/v1/datasets` list and create datasets (admin)
+
+use aingle_cortex::datasets::DatasetConfig;
+use aingle_cortex::middleware::RequestPrincipal;
+use aingle_cortex::state::AppState;
+use aingle_cortex::{rest, CortexConfig, CortexServer};
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+fn admin() -> RequestPrincipal {
+    RequestPrincipal {
+        user_id: Some("operator".to_string()),
+        roles: vec!["admin".to_string()],
+    }
+}
+
+fn tenant_a() -> RequestPrincipal {
+    RequestPrincipal {
+        user_id: Some("customer-a-service".to_string()),
+        roles: vec!["write:triples@customer-a".to_string()],
+    }
+}
+
+fn two_datasets() -> AppState {
+    let state = AppState::new().unwrap();
+    for name in ["customer-a", "customer-b"] {
+        state
+            .datasets
+            .register(DatasetConfig::memory(name))
+            .unwrap();
+    }
+    state
+}
+
+async fn send(
+    state: &AppState,
+    method: &str,
+    uri: &str,
+    body: Option<Value>,
+    principal: RequestPrincipal,
+) -> (StatusCode, Value) {
+    let mut request = Request::builder().method(method).uri(uri);
+    if body.is_some() {
+        request = request.header("content-type", "application/json");
+    }
+    let mut request = request
+        .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))
+        .unwrap();
+    request.extensions_mut().insert(principal);
+
+    let response = rest::router()
+        .with_state(state.clone())
+        .oneshot(request)
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let value = if bytes.is_empty() {
+        Value::Null
+    } else {
+        serde_json::from_slice(&bytes).unwrap()
+    };
+    (status, value)
+}
+
+fn triple(subject: &str) -> Option<Value> {
+    Some(json!({
+        "subject": subject,
+        "predicate": "ex:plan",
+        "object": "enterprise",
+    }))
+}
+
+#[tokio::test]
+async fn test_triple_is_invisible_in_other_datasets() {
+    let state = two_datasets();
+
+    let (status, body) = send(
+        &state,
+        "POST",
+        "/api/v1/datasets/customer-a/triples",
+        triple("ex:acme"),
+        admin(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let id = body["id"].as_str().unwrap().to_string();
+
+    let (status, body) = send(
+        &state,
+        "GET",
+        "/api/v1/datasets/customer-a/triples",
+        None,
+        admin(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 1);
+    let (status, _) = send(
+        &state,
+        "GET",
+        &format!("/api/v1/datasets/customer-a/triples/{}", id),
+        None,
+        admin(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Neither the other dataset nor the default one sees it
+    for prefix in ["/api/v1/datasets/customer-b", "/api/v1"] {
+        let (status, body) = send(&state, "GET", &format!("{prefix}/triples"), None, admin()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["total"], 0, "{prefix}");
+        let (status, _) = send(
+            &state,
+            "GET",
+            &format!("{prefix}/triples/{}", id),
+            None,
+            admin(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "{prefix}");
+        let (_, body) = send(&state, "GET", &format!("{prefix}/stats"), None, admin()).await;
+        assert_eq!(body["graph"]["triple_count"], 0, "{prefix}");
+    }
+    assert_eq!(state.graph.read().await.stats().triple_count, 0);
+
+    // Deleting through the wrong dataset leaves the triple alone
+    let (status, _) = send(
+        &state,
+        "DELETE",
+        &format!("/api/v1/datasets/customer-b/triples/{}", id),
+        None,
+        admin(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (_, body) = send(
+        &state,
+        "POST",
+        "/api/v1/datasets/customer-a/query",
+        Some(json!({ "subject": "ex:acme" })),
+        admin(),
+    )
+    .await;
+    assert_eq!(body["total"], 1);
+}
+
+#[tokio::test]
+async fn test_scoped_token_is_confined_to_its_dataset() {
+    let state = two_datasets();
+
+    let (status, _) = send(
+        &state,
+        "POST",
+        "/api/v1/datasets/customer-a/triples",
+        triple("ex:acme"),
+        tenant_a(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    for (method, uri, body) in [
+        ("GET", "/api/v1/datasets/customer-b/triples", None),
+        (
+            "POST",
+            "/api/v1/datasets/customer-b/triples",
+            triple("ex:acme"),
+        ),
+        ("GET", "/api/v1/datasets/customer-b/stats", None),
+        ("GET", "/api/v1/triples", None),
+        ("POST", "/api/v1/triples", triple("ex:acme")),
+    ] {
+        let (status, body) = send(&state, method, uri, body, tenant_a()).await;
+        assert_eq!(status, StatusCode::FORBIDDEN, "{method} {uri}");
+        assert_eq!(body["code"], "FORBIDDEN");
+    }
+
+    // A global admin reaches every dataset; unknown ones are not found
+    let (status, _) = send(
+        &state,
+        "GET",
+        "/api/v1/datasets/customer-b/triples",
+        None,
+        admin(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = send(
+        &state,
+        "GET",
+        "/api/v1/datasets/customer-c/triples",
+        None,
+        admin(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "DATASET_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn test_scoped_admin_role_applies_within_its_dataset() {
+    let state = two_datasets();
+    let tenant_admin = RequestPrincipal {
+        user_id: Some("customer-a-admin".to_string()),
+        roles: vec!["admin@customer-a".to_string()],
+    };
+
+    let (status, _) = send(
+        &state,
+        "GET",
+        "/api/v1/datasets/customer-a/triples?include_deleted=true",
+        None,
+        tenant_admin.clone(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Not an admin of the server
+    let (status, _) = send(&state, "GET", "/api/v1/datasets", None, tenant_admin).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_admin_creates_and_lists_datasets() {
+    let state = AppState::new().unwrap();
+
+    let (status, body) = send(
+        &state,
+        "POST",
+        "/api/v1/datasets",
+        Some(json!({ "name": "customer-a" })),
+        admin(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["name"], "customer-a");
+    assert_eq!(body["open"], false);
+
+    let (status, _) = send(
+        &state,
+        "POST",
+        "/api/v1/datasets",
+        Some(json!({ "name": "customer-a" })),
+        admin(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    for name in ["default", "Customer A", "../etc"] {
+        let (status, _) = send(
+            &state,
+            "POST",
+            "/api/v1/datasets",
+            Some(json!({ "name": name })),
+            admin(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{name}");
+    }
+    let (status, _) = send(
+        &state,
+        "POST",
+        "/api/v1/datasets",
+        Some(json!({ "name": "customer-b" })),
+        tenant_a(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // Usable right away, and opened by the first request
+    let (status, _) = send(
+        &state,
+        "POST",
+        "/api/v1/datasets/customer-a/triples",
+        triple("ex:acme"),
+        tenant_a(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, body) = send(&state, "GET", "/api/v1/datasets", None, admin()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body["datasets"],
+        json!([
+            { "name": "default", "open": true },
+            { "name": "customer-a", "db_path": ":memory:", "open": true },
+        ])
+    );
+}
+
+#[tokio::test]
+async fn test_configured_datasets_persist_separately() {
+    let dir = tempfile::tempdir().unwrap();
+    let archive = dir.path().join("archive").join("graph.sled");
+
+    let mut config = CortexConfig::default()
+        .with_dataset(DatasetConfig::new("archive", archive.to_string_lossy()));
+    config.db_path = Some(dir.path().join("graph.sled").to_string_lossy().to_string());
+    config.rate_limit_enabled = false;
+    let server = CortexServer::new(config).unwrap();
+    assert!(!archive.exists(), "datasets open lazily");
+
+    let request = Request::post("/api/v1/datasets/archive/triples")
+        .header("content-type", "application/json")
+        .body(Body::from(triple("ex:acme").unwrap().to_string()))
+        .unwrap();
+    let response = server.build_router().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert!(archive.exists());
+    assert!(dir.path().join("archive").join("tombstones.sled").exists());
+
+    let state = server.state();
+    assert_eq!(state.stats().await.triple_count, 0);
+    let archived = state.dataset("archive").unwrap();
+    assert_eq!(archived.stats().await.triple_count, 1);
+    state.flush(None).await.unwrap();
+
+    // Runtime-created datasets go next to the default graph
+    let info = state.datasets.create("customer-a").unwrap();
+    let expected = dir
+        .path()
+        .join("datasets")
+        .join("customer-a")
+        .join("graph.sled");
+    assert_eq!(info.db_path.as_deref(), Some(&*expected.to_string_lossy()));
+}
@@ -0,0 +1,152 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Integration tests for graceful shutdown
+//!
+//! Starts a server with a long poll and a WebSocket subscription open,
+//! triggers its shutdown handle and checks that both are answered within the
+//! drain window and that the server then returns — after the drain timeout
+//! at the latest, even if a connection is still open.
+
+use aingle_cortex::rest::subscribe::PollResponse;
+use aingle_cortex::shutdown::{RESTART_REASON, SERVICE_RESTART};
+use aingle_cortex::state::Event;
+use aingle_cortex::{CortexConfig, CortexServer};
+use futures::StreamExt;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::time::{sleep, timeout};
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use tokio_tungstenite::tungstenite::Message;
+
+async fn free_port() -> u16 {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    listener.local_addr().unwrap().port()
+}
+
+fn server(port: u16, drain: Duration) -> CortexServer {
+    let mut config = CortexConfig::default()
+        .with_host("127.0.0.1")
+        .with_port(port);
+    config.db_path = Some(":memory:".to_string());
+    config.rate_limit_enabled = false;
+    config.tracing = false;
+    config.shutdown_drain_timeout = drain;
+    CortexServer::new(config).unwrap()
+}
+
+#[tokio::test]
+async fn test_shutdown_drains_subscribers() {
+    let port = free_port().await;
+    let server = server(port, Duration::from_secs(5));
+    let state = server.state().clone();
+    let shutdown = server.shutdown_handle();
+    let running = tokio::spawn(server.run());
+    sleep(Duration::from_millis(300)).await;
+
+    let (mut ws, _) =
+        tokio_tungstenite::connect_async(format!("ws://127.0.0.1:{port}/api/v1/subscribe"))
+            .await
+            .unwrap();
+    let poll = tokio::spawn(
+        reqwest::Client::new()
+            .get(format!(
+                "http://127.0.0.1:{port}/api/v1/subscribe/poll?timeout_secs=60"
+            ))
+            .send(),
+    );
+    sleep(Duration::from_millis(200)).await;
+
+    // A live event reaches the WebSocket subscriber with its sequence number
+    state.broadcaster.broadcast(Event::TripleDeleted {
+        hash: "abc".to_string(),
+    });
+    let Message::Text(text) = timeout(Duration::from_secs(2), ws.next())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap()
+    else {
+        panic!("expected a text frame");
+    };
+    let event: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(event["seq"], 1);
+    assert_eq!(event["type"], "TripleDeleted");
+
+    // The long poll was answered by the same event; poll again and keep it open
+    let first: PollResponse = poll.await.unwrap().unwrap().json().await.unwrap();
+    assert_eq!(first.events.len(), 1);
+    let poll = tokio::spawn(
+        reqwest::Client::new()
+            .get(format!(
+                "http://127.0.0.1:{port}/api/v1/subscribe/poll?after_seq=1&timeout_secs=60"
+            ))
+            .send(),
+    );
+    sleep(Duration::from_millis(200)).await;
+
+    shutdown.trigger();
+
+    let response = timeout(Duration::from_secs(5), poll)
+        .await
+        .expect("long poll answered within the drain window")
+        .unwrap()
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let body: PollResponse = response.json().await.unwrap();
+    assert!(body.events.is_empty());
+    assert!(body.shutting_down);
+
+    let frame = timeout(Duration::from_secs(5), ws.next())
+        .await
+        .expect("close frame within the drain window")
+        .unwrap()
+        .unwrap();
+    let Message::Close(Some(close)) = frame else {
+        panic!("expected a close frame, got {frame:?}");
+    };
+    assert_eq!(close.code, CloseCode::from(SERVICE_RESTART));
+    assert_eq!(close.reason.as_str(), RESTART_REASON);
+    drop(ws);
+
+    timeout(Duration::from_secs(5), running)
+        .await
+        .expect("server stops after draining")
+        .unwrap()
+        .unwrap();
+    assert_eq!(state.shutdown.open_sessions(), 0);
+    assert_eq!(state.broadcaster.client_count(), 0);
+}
+
+#[tokio::test]
+async fn test_shutdown_gives_up_after_drain_timeout() {
+    let port = free_port().await;
+    let server = server(port, Duration::from_millis(300));
+    let shutdown = server.shutdown_handle();
+    let running = tokio::spawn(server.run());
+    sleep(Duration::from_millis(300)).await;
+
+    // A client that never finishes its request holds the connection open
+    let mut stalled = tokio::net::TcpStream::connect(("127.0.0.1", port))
+        .await
+        .unwrap();
+    stalled
+        .write_all(b"GET /api/v1/health HTTP/1.1\r\nHost: localhost\r\n")
+        .await
+        .unwrap();
+    sleep(Duration::from_millis(100)).await;
+
+    shutdown.trigger();
+    timeout(Duration::from_secs(3), running)
+        .await
+        .expect("server stops once the drain timeout elapses")
+        .unwrap()
+        .unwrap();
+
+    // New connections are refused
+    let refused = reqwest::Client::new()
+        .get(format!("http://127.0.0.1:{port}/api/v1/health"))
+        .send()
+        .await;
+    assert!(refused.is_err());
+}
@@ -0,0 +1,287 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Integration tests for nested entity resolution over GraphQL
+//!
+//! Covers grouping by predicate, the depth limit and node budget, cycles,
+//! and batching of the subject lookups.
+
+#[cfg(feature = "graphql")]
+mod tests {
+    use aingle_cortex::graphql::{
+        EntityLimits, GraphSubjectStore, MutationRoot, QueryRoot, SubjectStore, SubscriptionRoot,
+    };
+    use aingle_cortex::AppState;
+    use aingle_graph::{GraphDB, NodeId, Predicate, Triple, Value};
+    use async_graphql::Schema;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Counts the batched reads going to the graph
+    #[derive(Default)]
+    struct CountingStore {
+        reads: AtomicUsize,
+        subjects: AtomicUsize,
+    }
+
+    impl SubjectStore for CountingStore {
+        fn subjects(
+            &self,
+            graph: &GraphDB,
+            subjects: &[NodeId],
+        ) -> aingle_graph::Result<HashMap<NodeId, Vec<Triple>>> {
+            self.reads.fetch_add(1, Ordering::SeqCst);
+            self.subjects.fetch_add(subjects.len(), Ordering::SeqCst);
+            GraphSubjectStore.subjects(graph, subjects)
+        }
+    }
+
+    async fn link(state: &AppState, subject: &str, predicate: &str, object: &str) {
+        let graph = state.graph.read().await;
+        graph
+            .insert(Triple::new(
+                NodeId::named(subject),
+                Predicate::named(predicate),
+                Value::Node(NodeId::named(object)),
+            ))
+            .unwrap();
+    }
+
+    async fn literal(state: &AppState, subject: &str, predicate: &str, object: &str) {
+        let graph = state.graph.read().await;
+        graph
+            .insert(Triple::new(
+                NodeId::named(subject),
+                Predicate::named(predicate),
+                Value::String(object.to_string()),
+            ))
+            .unwrap();
+    }
+
+    async fn execute(
+        state: &AppState,
+        limits: EntityLimits,
+        store: Arc<CountingStore>,
+        query: &str,
+    ) -> async_graphql::Response {
+        let store: Arc<dyn SubjectStore> = store;
+        let schema = Schema::build(QueryRoot, MutationRoot, SubscriptionRoot)
+            .data(state.clone())
+            .data(limits)
+            .data(store)
+            .finish();
+        schema.execute(query).await
+    }
+
+    #[tokio::test]
+    async fn test_entity_groups_properties_by_predicate() {
+        let state = AppState::new().unwrap();
+        literal(&state, "alice", "name", "Alice").await;
+        literal(&state, "alice", "nick", "al").await;
+        literal(&state, "alice", "nick", "ally").await;
+        link(&state, "alice", "knows", "bob").await;
+        literal(&state, "bob", "name", "Bob").await;
+
+        let resp = execute(
+            &state,
+            EntityLimits::default(),
+            Arc::default(),
+            r#"{ entity(id: "alice") {
+                id
+                properties {
+                    predicate
+                    values {
+                        ... on StringValue { value }
+                        ... on EntityNode { iri entity { id depth } }
+                    }
+                }
+            } }"#,
+        )
+        .await;
+        assert!(resp.errors.is_empty(), "{:?}", resp.errors);
+        let data = resp.data.into_json().unwrap();
+
+        let entity = &data["entity"];
+        assert_eq!(entity["id"], "alice");
+        let props = entity["properties"].as_array().unwrap();
+        let predicates: Vec<&str> = props
+            .iter()
+            .map(|p| p["predicate"].as_str().unwrap())
+            .collect();
+        assert_eq!(predicates, vec!["knows", "name", "nick"]);
+
+        let mut nicks: Vec<&str> = props[2]["values"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v["value"].as_str().unwrap())
+            .collect();
+        nicks.sort();
+        assert_eq!(nicks, vec!["al", "ally"]);
+
+        let bob = &props[0]["values"][0];
+        assert_eq!(bob["iri"], "bob");
+        assert_eq!(bob["entity"]["id"], "bob");
+        assert_eq!(bob["entity"]["depth"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_entity_is_null() {
+        let state = AppState::new().unwrap();
+        let resp = execute(
+            &state,
+            EntityLimits::default(),
+            Arc::default(),
+            r#"{ entity(id: "nobody") { id } }"#,
+        )
+        .await;
+        assert!(resp.errors.is_empty(), "{:?}", resp.errors);
+        assert!(resp.data.into_json().unwrap()["entity"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_depth_limit() {
+        let state = AppState::new().unwrap();
+        link(&state, "n0", "next", "n1").await;
+        link(&state, "n1", "next", "n2").await;
+        link(&state, "n2", "next", "n3").await;
+        literal(&state, "n3", "name", "end").await;
+
+        let limits = EntityLimits {
+            max_depth: 2,
+            ..EntityLimits::default()
+        };
+        let query = r#"{ entity(id: "n0") { properties { values { ... on EntityNode {
+            entity { id properties { values { ... on EntityNode {
+                entity { id properties { values { ... on EntityNode {
+                    entity { id }
+                } } } }
+            } } } }
+        } } } } }"#;
+        let resp = execute(&state, limits, Arc::default(), query).await;
+
+        // n1 and n2 resolve, n3 is one link too deep
+        assert_eq!(resp.errors.len(), 1);
+        assert!(resp.errors[0].message.contains("depth limit of 2"));
+
+        let data = resp.data.into_json().unwrap();
+        let n1 = &data["entity"]["properties"][0]["values"][0]["entity"];
+        assert_eq!(n1["id"], "n1");
+        let n2 = &n1["properties"][0]["values"][0]["entity"];
+        assert_eq!(n2["id"], "n2");
+        assert!(n2["properties"][0]["values"][0]["entity"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_node_budget() {
+        let state = AppState::new().unwrap();
+        for i in 0..10 {
+            link(&state, "hub", "has", &format!("leaf{}", i)).await;
+            literal(&state, &format!("leaf{}", i), "name", "leaf").await;
+        }
+
+        let limits = EntityLimits {
+            max_nodes: 5,
+            ..EntityLimits::default()
+        };
+        let resp = execute(
+            &state,
+            limits,
+            Arc::default(),
+            r#"{ entity(id: "hub") { properties { values {
+                ... on EntityNode { entity { id } }
+            } } } }"#,
+        )
+        .await;
+
+        // The hub takes one slot, leaving four for the ten leaves
+        assert_eq!(resp.errors.len(), 6);
+        assert!(resp
+            .errors
+            .iter()
+            .all(|e| e.message.contains("budget of 5 nodes")));
+    }
+
+    #[tokio::test]
+    async fn test_cycle_returns_reference() {
+        let state = AppState::new().unwrap();
+        link(&state, "a", "next", "b").await;
+        link(&state, "b", "next", "a").await;
+        link(&state, "a", "self", "a").await;
+
+        let resp = execute(
+            &state,
+            EntityLimits::default(),
+            Arc::default(),
+            r#"{ entity(id: "a") { properties { predicate values { ... on EntityNode {
+                iri cycle entity { id properties { values { ... on EntityNode {
+                    iri cycle entity { id }
+                } } } }
+            } } } } }"#,
+        )
+        .await;
+        assert!(resp.errors.is_empty(), "{:?}", resp.errors);
+        let data = resp.data.into_json().unwrap();
+        let props = &data["entity"]["properties"];
+
+        // a -> b resolves, b -> a points back at the root
+        assert_eq!(props[0]["predicate"], "next");
+        let to_b = &props[0]["values"][0];
+        assert_eq!(to_b["cycle"], false);
+        assert_eq!(to_b["entity"]["id"], "b");
+        let back = &to_b["entity"]["properties"][0]["values"][0];
+        assert_eq!(back["iri"], "a");
+        assert_eq!(back["cycle"], true);
+        assert!(back["entity"].is_null());
+
+        // A self link is a cycle right away
+        assert_eq!(props[1]["predicate"], "self");
+        assert_eq!(props[1]["values"][0]["cycle"], true);
+        assert!(props[1]["values"][0]["entity"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_linked_nodes_are_read_in_batches() {
+        let state = AppState::new().unwrap();
+        for i in 0..50 {
+            let friend = format!("friend{}", i);
+            link(&state, "root", "knows", &friend).await;
+            link(&state, &friend, "worksAt", &format!("org{}", i % 5)).await;
+        }
+        for i in 0..5 {
+            literal(&state, &format!("org{}", i), "name", "Org").await;
+        }
+
+        let store = Arc::new(CountingStore::default());
+        let resp = execute(
+            &state,
+            EntityLimits::default(),
+            store.clone(),
+            r#"{ entity(id: "root") { properties { values { ... on EntityNode {
+                entity { id properties { values { ... on EntityNode {
+                    entity { id }
+                } } } }
+            } } } } }"#,
+        )
+        .await;
+        assert!(resp.errors.is_empty(), "{:?}", resp.errors);
+
+        let data = resp.data.into_json().unwrap();
+        let friends = data["entity"]["properties"][0]["values"]
+            .as_array()
+            .unwrap();
+        assert_eq!(friends.len(), 50);
+        assert!(friends.iter().all(
+            |f| f["entity"]["properties"][0]["values"][0]["entity"]["id"]
+                .as_str()
+                .unwrap()
+                .starts_with("org")
+        ));
+
+        // One read per level rather than one per node, and each of the 56
+        // subjects is read once
+        assert_eq!(store.reads.load(Ordering::SeqCst), 3);
+        assert_eq!(store.subjects.load(Ordering::SeqCst), 56);
+    }
+}
@@ -387,4 +387,135 @@ mod tests {
         // This test verifies that broadcasting doesn't panic even if buffers overflow
         // (tokio broadcast channel drops old messages when full)
     }
+
+    /// Helper to broadcast a numbered TripleAdded event
+    fn broadcast_triple(state: &AppState, i: usize) {
+        broadcast_event(
+            state,
+            aingle_cortex::state::Event::TripleAdded {
+                hash: format!("hash_{}", i),
+                subject: "ex:Indexer".to_string(),
+                predicate: "ex:saw".to_string(),
+                object: serde_json::Value::from(i),
+            },
+        );
+    }
+
+    /// Next subscription item, as (seq, hash)
+    async fn next_triple<S>(stream: &mut S) -> (u64, String)
+    where
+        S: futures::Stream<Item = async_graphql::Response> + Unpin,
+    {
+        let resp = timeout(Duration::from_secs(1), stream.next())
+            .await
+            .expect("event within timeout")
+            .expect("stream still open");
+        assert!(resp.errors.is_empty(), "{:?}", resp.errors);
+        let data = resp.data.into_json().unwrap();
+        (
+            data["tripleAdded"]["seq"].as_u64().unwrap(),
+            data["tripleAdded"]["hash"].as_str().unwrap().to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_replay_after_cursor_then_live() {
+        let server = create_test_server();
+        let state = server.state().clone();
+        let schema = aingle_cortex::graphql::create_schema(state.clone());
+
+        for i in 1..=10 {
+            broadcast_triple(&state, i);
+        }
+
+        let mut stream = schema.execute_stream(async_graphql::Request::new(
+            "subscription { tripleAdded(afterSeq: 4) { seq hash } }",
+        ));
+
+        // Events 5-10 are replayed from the buffer, in order
+        for expected in 5..=10u64 {
+            let (seq, hash) = next_triple(&mut stream).await;
+            assert_eq!(seq, expected);
+            assert_eq!(hash, format!("hash_{}", expected));
+        }
+
+        // ...and then the subscription continues live
+        broadcast_triple(&state, 11);
+        broadcast_triple(&state, 12);
+        assert_eq!(next_triple(&mut stream).await.0, 11);
+        assert_eq!(next_triple(&mut stream).await.0, 12);
+
+        // Nothing was replayed twice
+        assert!(timeout(Duration::from_millis(100), stream.next())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_replay_cursor_out_of_buffer_requires_resync() {
+        let mut config = CortexConfig::default().with_host("127.0.0.1").with_port(0);
+        config.event_replay_buffer = 5;
+        let server = CortexServer::new(config).expect("Failed to create server");
+        let state = server.state().clone();
+        let schema = aingle_cortex::graphql::create_schema(state.clone());
+
+        for i in 1..=10 {
+            broadcast_triple(&state, i);
+        }
+
+        // Events 3-5 have been dropped from the buffer
+        let mut stream = schema.execute_stream(async_graphql::Request::new(
+            "subscription { tripleAdded(afterSeq: 2) { seq } }",
+        ));
+        let resp = timeout(Duration::from_secs(1), stream.next())
+            .await
+            .unwrap()
+            .unwrap();
+        let err = resp.errors.first().expect("resync error");
+        let ext = serde_json::to_value(err).unwrap()["extensions"].clone();
+        assert_eq!(ext["code"], "RESYNC_REQUIRED");
+        assert_eq!(ext["reason"], "CURSOR_EXPIRED");
+        assert_eq!(ext["oldestSeq"], 6);
+
+        // The oldest buffered cursor still works
+        let mut stream = schema.execute_stream(async_graphql::Request::new(
+            "subscription { tripleAdded(afterSeq: 5) { seq hash } }",
+        ));
+        assert_eq!(next_triple(&mut stream).await.0, 6);
+    }
+
+    #[tokio::test]
+    async fn test_lagging_subscriber_requires_resync() {
+        let server = create_test_server();
+        let state = server.state().clone();
+        let schema = aingle_cortex::graphql::create_schema(state.clone());
+
+        // The first poll runs the resolver, which subscribes to live events
+        let mut stream = schema.execute_stream(async_graphql::Request::new(
+            "subscription { tripleAdded { seq } }",
+        ));
+        assert!(timeout(Duration::from_millis(50), stream.next())
+            .await
+            .is_err());
+
+        // More events than the live channel holds, none of them read
+        for i in 1..=1100 {
+            broadcast_triple(&state, i);
+        }
+
+        let resp = timeout(Duration::from_secs(1), stream.next())
+            .await
+            .unwrap()
+            .unwrap();
+        let err = resp.errors.first().expect("resync error");
+        let ext = serde_json::to_value(err).unwrap()["extensions"].clone();
+        assert_eq!(ext["code"], "RESYNC_REQUIRED");
+        assert_eq!(ext["reason"], "LAGGED");
+
+        // Nothing after the gap is delivered
+        assert!(timeout(Duration::from_secs(1), stream.next())
+            .await
+            .unwrap()
+            .is_none());
+    }
 }
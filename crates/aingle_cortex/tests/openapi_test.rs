@@ -0,0 +1,189 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Integration tests for the generated OpenAPI spec
+//!
+//! Fetches `/api/openapi.json` through the server router, checks it parses as
+//! an OpenAPI 3.0 document and documents the REST endpoints, their error
+//! responses and the security schemes, and that the Swagger UI route follows
+//! `CortexConfig::swagger_ui`.
+
+#![cfg(feature = "openapi")]
+
+use aingle_cortex::{CortexConfig, CortexServer};
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use serde_json::Value;
+use tower::ServiceExt;
+
+fn server(swagger_ui: bool) -> CortexServer {
+    let config = CortexConfig {
+        db_path: Some(":memory:".to_string()),
+        rate_limit_enabled: false,
+        swagger_ui,
+        ..Default::default()
+    };
+    CortexServer::new(config).unwrap()
+}
+
+async fn get(server: &CortexServer, uri: &str) -> (StatusCode, Vec<u8>) {
+    let response = server
+        .build_router()
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, bytes.to_vec())
+}
+
+async fn spec() -> Value {
+    let (status, body) = get(&server(false), "/api/openapi.json").await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Must conform to the OpenAPI 3.0 object model, not just be JSON
+    let typed: openapiv3::OpenAPI = serde_json::from_slice(&body).unwrap();
+    assert!(typed.openapi.starts_with("3.0"), "got {}", typed.openapi);
+
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn test_spec_documents_rest_endpoints() {
+    let spec = spec().await;
+    let paths = spec["paths"].as_object().unwrap();
+
+    for (path, method) in [
+        ("/api/v1/triples", "post"),
+        ("/api/v1/triples", "get"),
+        ("/api/v1/triples/batch", "post"),
+        ("/api/v1/triples/{id}", "get"),
+        ("/api/v1/triples/{id}", "delete"),
+        ("/api/v1/triples/{id}/history", "get"),
+        ("/api/v1/triples/purge", "post"),
+        ("/api/v1/query", "post"),
+        ("/api/v1/query/subjects", "get"),
+        ("/api/v1/query/predicates", "get"),
+        ("/api/v1/stats", "get"),
+        ("/api/v1/health", "get"),
+        ("/api/v1/validate", "post"),
+        ("/api/v1/proofs/validate", "post"),
+        ("/api/v1/proofs/{id}/status", "get"),
+        ("/api/v1/proofs/{id}/revoke", "post"),
+        ("/api/v1/auth/token", "post"),
+        ("/api/v1/auth/refresh", "post"),
+        ("/api/v1/auth/verify", "post"),
+        ("/api/v1/auth/register", "post"),
+    ] {
+        assert!(
+            paths.get(path).and_then(|p| p.get(method)).is_some(),
+            "{} {} is not documented",
+            method.to_uppercase(),
+            path
+        );
+    }
+
+    let schemas = spec["components"]["schemas"].as_object().unwrap();
+    for name in [
+        "TripleDto",
+        "ErrorResponse",
+        "SubmitProofRequest",
+        "ValidateProofResponse",
+        "TokenResponse",
+        "RateLimitResponse",
+    ] {
+        assert!(schemas.contains_key(name), "schema {} is missing", name);
+    }
+}
+
+/// Collects every `$ref` in the document.
+fn refs<'a>(value: &'a Value, out: &mut Vec<&'a str>) {
+    match value {
+        Value::Object(map) => {
+            if let Some(Value::String(target)) = map.get("$ref") {
+                out.push(target);
+            }
+            map.values().for_each(|v| refs(v, out));
+        }
+        Value::Array(items) => items.iter().for_each(|v| refs(v, out)),
+        _ => {}
+    }
+}
+
+#[tokio::test]
+async fn test_spec_references_resolve() {
+    let spec = spec().await;
+    let mut targets = Vec::new();
+    refs(&spec, &mut targets);
+    assert!(!targets.is_empty());
+
+    for target in targets {
+        let pointer = target.strip_prefix('#').unwrap();
+        assert!(
+            spec.pointer(pointer).is_some(),
+            "dangling reference {}",
+            target
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_spec_documents_errors_and_parameters() {
+    let spec = spec().await;
+
+    let get_triple = &spec["paths"]["/api/v1/triples/{id}"]["get"];
+    assert_eq!(
+        get_triple["responses"]["404"]["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/ErrorResponse"
+    );
+    assert_eq!(
+        get_triple["responses"]["429"]["$ref"],
+        "#/components/responses/RateLimited"
+    );
+    assert!(spec["components"]["responses"]["RateLimited"]["headers"]["Retry-After"].is_object());
+
+    let batch = &spec["paths"]["/api/v1/triples/batch"]["post"]["responses"];
+    assert!(batch["422"].is_object());
+
+    let params: Vec<&str> = spec["paths"]["/api/v1/triples"]["get"]["parameters"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["name"].as_str().unwrap())
+        .collect();
+    for name in ["subject", "predicate", "limit", "offset", "include_deleted"] {
+        assert!(params.contains(&name), "missing parameter {}", name);
+    }
+}
+
+#[tokio::test]
+async fn test_spec_declares_security_schemes() {
+    let spec = spec().await;
+    let schemes = &spec["components"]["securitySchemes"];
+
+    assert_eq!(schemes["bearer_auth"]["scheme"], "bearer");
+    assert_eq!(schemes["api_key"]["in"], "header");
+    assert_eq!(schemes["api_key"]["name"], "X-API-Key");
+
+    // Credentials are optional: the last global requirement is anonymous
+    let security = spec["security"].as_array().unwrap();
+    assert_eq!(security.last().unwrap(), &serde_json::json!({}));
+
+    // Obtaining a token needs no credentials
+    assert_eq!(
+        spec["paths"]["/api/v1/auth/token"]["post"]["security"],
+        serde_json::json!([{}])
+    );
+}
+
+#[tokio::test]
+async fn test_swagger_ui_follows_config() {
+    let (status, _) = get(&server(false), "/api/docs").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, body) = get(&server(true), "/api/docs").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(String::from_utf8(body)
+        .unwrap()
+        .contains("/api/openapi.json"));
+}
@@ -3,8 +3,14 @@
 
 //! Integration tests for the proof storage and verification system
 
+use aingle_cortex::middleware::RequestPrincipal;
 use aingle_cortex::prelude::*;
 use aingle_cortex::proofs::{ProofMetadata, SubmitProofRequest};
+use aingle_cortex::rest;
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use serde_json::{json, Value};
+use tower::ServiceExt;
 
 #[tokio::test]
 async fn test_proof_store_lifecycle() {
@@ -19,6 +25,7 @@ async fn test_proof_store_lifecycle() {
             "response": vec![2u8; 32],
         }),
         metadata: None,
+        expires_at: None,
     };
 
     let proof_id = store.submit(request).await.expect("Failed to submit proof");
@@ -54,6 +61,7 @@ async fn test_proof_verification() {
         proof_type: ProofType::HashOpening,
         proof_data: proof_json,
         metadata: None,
+        expires_at: None,
     };
 
     let proof_id = store.submit(request).await.expect("Failed to submit");
@@ -73,16 +81,19 @@ async fn test_batch_proof_submission() {
             proof_type: ProofType::Knowledge,
             proof_data: serde_json::json!({"id": 1}),
             metadata: None,
+            expires_at: None,
         },
         SubmitProofRequest {
             proof_type: ProofType::Equality,
             proof_data: serde_json::json!({"id": 2}),
             metadata: None,
+            expires_at: None,
         },
         SubmitProofRequest {
             proof_type: ProofType::Membership,
             proof_data: serde_json::json!({"id": 3}),
             metadata: None,
+            expires_at: None,
         },
     ];
 
@@ -111,6 +122,7 @@ async fn test_batch_proof_verification() {
             proof_type: ProofType::HashOpening,
             proof_data: proof_json,
             metadata: None,
+            expires_at: None,
         };
 
         let proof_id = store.submit(request).await.unwrap();
@@ -143,6 +155,7 @@ async fn test_proof_filtering_by_type() {
             proof_type,
             proof_data: serde_json::json!({"index": i}),
             metadata: None,
+            expires_at: None,
         };
 
         store.submit(request).await.unwrap();
@@ -180,6 +193,7 @@ async fn test_proof_metadata() {
         proof_type: ProofType::Knowledge,
         proof_data: serde_json::json!({"test": "data"}),
         metadata: Some(metadata.clone()),
+        expires_at: None,
     };
 
     let proof_id = store.submit(request).await.unwrap();
@@ -207,6 +221,7 @@ async fn test_verification_cache() {
         proof_type: ProofType::HashOpening,
         proof_data: proof_json,
         metadata: None,
+        expires_at: None,
     };
 
     let proof_id = store.submit(request).await.unwrap();
@@ -245,6 +260,7 @@ async fn test_proof_statistics() {
             proof_type,
             proof_data: serde_json::json!({"index": i}),
             metadata: None,
+            expires_at: None,
         };
 
         store.submit(request).await.unwrap();
@@ -272,6 +288,7 @@ async fn test_proof_verification_with_merkle_tree() {
         proof_type: ProofType::Membership,
         proof_data: proof_json,
         metadata: None,
+        expires_at: None,
     };
 
     let proof_id = store.submit(request).await.unwrap();
@@ -295,6 +312,7 @@ async fn test_schnorr_proof_verification() {
         proof_type: ProofType::Knowledge,
         proof_data: proof_json,
         metadata: None,
+        expires_at: None,
     };
 
     let proof_id = store.submit(request).await.unwrap();
@@ -322,6 +340,7 @@ async fn test_app_state_integration() {
         proof_type: ProofType::HashOpening,
         proof_data: proof_json,
         metadata: None,
+        expires_at: None,
     };
 
     let proof_id = state.proof_store.submit(request).await.unwrap();
@@ -345,6 +364,7 @@ async fn test_clear_all_proofs() {
             proof_type: ProofType::Knowledge,
             proof_data: serde_json::json!({"index": i}),
             metadata: None,
+            expires_at: None,
         };
         store.submit(request).await.unwrap();
     }
@@ -378,6 +398,7 @@ async fn test_concurrent_proof_operations() {
                 proof_type: ProofType::Knowledge,
                 proof_data: serde_json::json!({"task": i}),
                 metadata: None,
+                expires_at: None,
             };
             store_clone.submit(request).await
         });
@@ -407,3 +428,210 @@ async fn test_proof_type_display() {
     assert_eq!(ProofType::HashOpening.to_string(), "hash-opening");
     assert_eq!(ProofType::Knowledge.to_string(), "knowledge");
 }
+
+async fn send(
+    state: &AppState,
+    method: &str,
+    uri: &str,
+    body: Option<Value>,
+    roles: &[&str],
+) -> (StatusCode, Value) {
+    let mut request = Request::builder().method(method).uri(uri);
+    if body.is_some() {
+        request = request.header("content-type", "application/json");
+    }
+    let mut request = request
+        .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))
+        .unwrap();
+    request.extensions_mut().insert(RequestPrincipal {
+        user_id: Some("operator".to_string()),
+        roles: roles.iter().map(|r| r.to_string()).collect(),
+    });
+
+    let response = rest::router()
+        .with_state(state.clone())
+        .oneshot(request)
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let value = if bytes.is_empty() {
+        Value::Null
+    } else {
+        serde_json::from_slice(&bytes).unwrap()
+    };
+    (status, value)
+}
+
+#[tokio::test]
+async fn test_proof_status_endpoint() {
+    let state = AppState::new().unwrap();
+    let commitment = aingle_zk::HashCommitment::commit(b"status test");
+    let proof_json = serde_json::to_value(aingle_zk::ZkProof::hash_opening(&commitment)).unwrap();
+    let expires_at = chrono::Utc::now() - chrono::Duration::minutes(5);
+
+    let (status, body) = send(
+        &state,
+        "POST",
+        "/api/v1/proofs",
+        Some(json!({ "proof_type": "hashopening", "proof_data": proof_json })),
+        &[],
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let valid_id = body["proof_id"].as_str().unwrap().to_string();
+    let (_, body) = send(
+        &state,
+        "POST",
+        "/api/v1/proofs",
+        Some(json!({
+            "proof_type": "hashopening",
+            "proof_data": proof_json,
+            "expires_at": expires_at,
+        })),
+        &[],
+    )
+    .await;
+    let expired_id = body["proof_id"].as_str().unwrap().to_string();
+
+    // Verify first so the status carries the validation record
+    let (status, _) = send(
+        &state,
+        "GET",
+        &format!("/api/v1/proofs/{}/verify", valid_id),
+        None,
+        &[],
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = send(
+        &state,
+        "GET",
+        &format!("/api/v1/proofs/{}/status", valid_id),
+        None,
+        &[],
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["proof_id"], valid_id);
+    assert_eq!(body["status"], json!({ "state": "valid" }));
+    assert_eq!(body["verified"], true);
+    assert!(body["verified_at"].is_string());
+
+    let (status, body) = send(
+        &state,
+        "GET",
+        &format!("/api/v1/proofs/{}/status", expired_id),
+        None,
+        &[],
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"]["state"], "expired");
+    assert_eq!(body["status"]["expired_at"], body["expires_at"]);
+    assert_eq!(body["verified"], false);
+
+    let (status, body) = send(&state, "GET", "/api/v1/proofs/missing/status", None, &[]).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "NOT_FOUND");
+
+    // Listing and fetching include the current status
+    let (_, body) = send(&state, "GET", "/api/v1/proofs", None, &[]).await;
+    assert_eq!(body["count"], 2);
+    let (_, body) = send(
+        &state,
+        "GET",
+        &format!("/api/v1/proofs/{}", expired_id),
+        None,
+        &[],
+    )
+    .await;
+    assert_eq!(body["status"]["state"], "expired");
+}
+
+#[tokio::test]
+async fn test_revoke_proof_endpoint() {
+    let state = AppState::new().unwrap();
+    let proof_id = state
+        .proof_store
+        .submit(SubmitProofRequest {
+            proof_type: ProofType::Knowledge,
+            proof_data: json!({ "key": "value" }),
+            metadata: None,
+            expires_at: None,
+        })
+        .await
+        .unwrap();
+    let uri = format!("/api/v1/proofs/{}/revoke", proof_id);
+
+    let (status, _) = send(
+        &state,
+        "POST",
+        &uri,
+        Some(json!({ "reason": "key compromised" })),
+        &["write:triples"],
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = send(
+        &state,
+        "POST",
+        &uri,
+        Some(json!({ "reason": " " })),
+        &["admin"],
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = send(
+        &state,
+        "POST",
+        &uri,
+        Some(json!({ "reason": "key compromised" })),
+        &["admin"],
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"]["state"], "revoked");
+    assert_eq!(body["status"]["reason"], "key compromised");
+    assert_eq!(body["status"]["revoked_by"], "operator");
+    assert!(body["status"]["revoked_at"].is_string());
+
+    let (status, body) = send(
+        &state,
+        "GET",
+        &format!("/api/v1/proofs/{}/status", proof_id),
+        None,
+        &[],
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"]["state"], "revoked");
+
+    // The revocation is audited, and the proof kept
+    let (status, _) = send(
+        &state,
+        "POST",
+        &uri,
+        Some(json!({ "reason": "again" })),
+        &["admin"],
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, _) = send(
+        &state,
+        "POST",
+        "/api/v1/proofs/missing/revoke",
+        Some(json!({ "reason": "gone" })),
+        &["admin"],
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(state.proof_store.count().await, 1);
+    let audit = state.audit_log.read().await;
+    let entries = audit.query(None, None, Some("revoke"), None, None, 10);
+    assert_eq!(entries.len(), 1);
+    let entry = entries[0];
+    assert_eq!(entry.user_id, "operator");
+    assert_eq!(entry.details.as_deref(), Some("key compromised"));
+}
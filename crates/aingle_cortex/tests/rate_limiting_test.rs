@@ -9,11 +9,18 @@
 //! - Multiple IPs isolation
 //! - Rate limit headers
 //! - 429 responses
-
-use aingle_cortex::middleware::RateLimiter;
+//! - Per-client buckets over HTTP
+//! - Bucket expiry after idleness
+
+use aingle_cortex::middleware::{RateLimitKey, RateLimiter, RateScope};
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::routing::get;
+use axum::Router;
 use std::net::{IpAddr, Ipv4Addr};
 use std::time::Duration;
 use tokio::time::sleep;
+use tower::ServiceExt;
 
 #[tokio::test]
 async fn test_rate_limiter_basic() {
@@ -348,3 +355,58 @@ fn test_rate_limiter_builder() {
 
     assert!(limiter.check(ip).is_err());
 }
+
+async fn get_triples(app: &Router, api_key: &str) -> axum::response::Response {
+    let request = Request::builder()
+        .uri("/api/v1/triples")
+        .header("x-api-key", api_key)
+        .body(Body::empty())
+        .unwrap();
+    app.clone().oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_clients_on_same_route_are_limited_separately() {
+    let limiter = RateLimiter::new(60)
+        .with_burst_capacity(3)
+        .with_api_keys(["noisy-key", "quiet-key"]);
+    let app = Router::new()
+        .route("/api/v1/triples", get(|| async { "ok" }))
+        .layer(limiter.clone().into_layer());
+
+    // Both clients arrive from the same address
+    for _ in 0..3 {
+        let response = get_triples(&app, "noisy-key").await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    let throttled = get_triples(&app, "noisy-key").await;
+    assert_eq!(throttled.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(throttled.headers()["retry-after"], "1");
+
+    let unaffected = get_triples(&app, "quiet-key").await;
+    assert_eq!(unaffected.status(), StatusCode::OK);
+    assert_eq!(unaffected.headers()["x-ratelimit-remaining"], "2");
+
+    let stats = limiter.stats();
+    assert_eq!(stats.reads.allowed, 4);
+    assert_eq!(stats.reads.throttled, 1);
+}
+
+#[tokio::test]
+async fn test_idle_buckets_expire() {
+    let limiter = RateLimiter::new(1).with_idle_timeout(Duration::from_millis(50));
+    let client = RateLimitKey::Subject("user-1".to_string());
+
+    limiter.check_key(&client, RateScope::Write).unwrap();
+    assert!(limiter.check_key(&client, RateScope::Write).is_err());
+
+    sleep(Duration::from_millis(100)).await;
+
+    assert_eq!(limiter.purge_idle(), 1);
+    assert_eq!(limiter.stats().active_buckets, 0);
+    assert!(
+        limiter.check_key(&client, RateScope::Write).is_ok(),
+        "Expired client should start with a full bucket"
+    );
+}
@@ -0,0 +1,167 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Integration tests for the request audit middleware
+//!
+//! Drives the REST router with the audit layer installed:
+//! - A write is audited with the caller, route, dataset, status and a
+//!   summary whose sensitive literal objects are redacted
+//! - `GET /api/v1/audit` pages through entries and requires the admin role
+
+use aingle_cortex::middleware::{
+    request_audit, RequestAuditConfig, RequestAuditor, RequestPrincipal,
+};
+use aingle_cortex::rest;
+use aingle_cortex::state::AppState;
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+fn admin() -> RequestPrincipal {
+    RequestPrincipal {
+        user_id: Some("compliance".to_string()),
+        roles: vec!["admin".to_string()],
+    }
+}
+
+fn writer() -> RequestPrincipal {
+    RequestPrincipal {
+        user_id: Some("hr-service".to_string()),
+        roles: vec!["write".to_string()],
+    }
+}
+
+async fn send(
+    state: &AppState,
+    method: &str,
+    uri: &str,
+    body: Option<Value>,
+    principal: RequestPrincipal,
+) -> (StatusCode, Value) {
+    let auditor = RequestAuditor::new(
+        RequestAuditConfig::default().with_sensitive_predicate("ex:ssn"),
+        state.audit_log.clone(),
+    );
+    let mut request = Request::builder().method(method).uri(uri);
+    if body.is_some() {
+        request = request.header("content-type", "application/json");
+    }
+    let mut request = request
+        .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))
+        .unwrap();
+    request.extensions_mut().insert(principal);
+
+    let response = rest::router()
+        .layer(axum::middleware::from_fn_with_state(auditor, request_audit))
+        .with_state(state.clone())
+        .oneshot(request)
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let value = if bytes.is_empty() {
+        Value::Null
+    } else {
+        serde_json::from_slice(&bytes).unwrap()
+    };
+    (status, value)
+}
+
+#[tokio::test]
+async fn test_write_is_audited_with_sensitive_object_redacted() {
+    let state = AppState::new().unwrap();
+
+    let (status, _) = send(
+        &state,
+        "POST",
+        "/api/v1/triples",
+        Some(json!({
+            "subject": "ex:alice",
+            "predicate": "ex:ssn",
+            "object": "123-45-6789",
+        })),
+        writer(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, entries) = send(
+        &state,
+        "GET",
+        "/api/v1/audit?action=http_write",
+        None,
+        admin(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let entries = entries.as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    let entry = &entries[0];
+    assert_eq!(entry["user_id"], "hr-service");
+    assert_eq!(entry["resource"], "/api/v1/triples");
+    assert_eq!(entry["request"]["method"], "POST");
+    assert_eq!(entry["request"]["dataset"], "default");
+    assert_eq!(entry["request"]["status"], 201);
+    let triple = &entry["request"]["summary"]["triples"][0];
+    assert_eq!(triple["subject"], "ex:alice");
+    assert_eq!(triple["predicate"], "ex:ssn");
+    assert!(triple["object"].is_null());
+
+    // The value is nowhere in the log, handler entries included
+    let log = state.audit_log.read().await;
+    let all = log.query(None, None, None, None, None, usize::MAX);
+    assert!(!serde_json::to_string(&all).unwrap().contains("123-45-6789"));
+}
+
+#[tokio::test]
+async fn test_audit_endpoint_requires_admin_and_pages_with_since() {
+    let state = AppState::new().unwrap();
+    for subject in ["ex:a", "ex:b", "ex:c"] {
+        let (status, _) = send(
+            &state,
+            "POST",
+            "/api/v1/triples",
+            Some(json!({"subject": subject, "predicate": "ex:name", "object": "x"})),
+            writer(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    for uri in ["/api/v1/audit", "/api/v1/audit/stats"] {
+        let (status, body) = send(&state, "GET", uri, None, writer()).await;
+        assert_eq!(status, StatusCode::FORBIDDEN, "{uri}");
+        assert_eq!(body["code"], "FORBIDDEN");
+    }
+
+    // Page forward two entries at a time, oldest first
+    let mut since = "1970-01-01T00:00:00Z".to_string();
+    let mut subjects = Vec::new();
+    loop {
+        let uri = format!(
+            "/api/v1/audit?action=http_write&limit=2&since={}",
+            since.replace('+', "%2B")
+        );
+        let (status, page) = send(&state, "GET", &uri, None, admin()).await;
+        assert_eq!(status, StatusCode::OK);
+        let page = page.as_array().unwrap().clone();
+        let Some(last) = page.last() else { break };
+        since = last["timestamp"].as_str().unwrap().to_string();
+        subjects.extend(
+            page.iter()
+                .map(|e| e["request"]["summary"]["triples"][0]["subject"].clone()),
+        );
+    }
+    assert_eq!(subjects, ["ex:a", "ex:b", "ex:c"]);
+
+    let (status, _) = send(
+        &state,
+        "GET",
+        "/api/v1/audit?since=yesterday",
+        None,
+        admin(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,332 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! SPARQL conformance tests
+//!
+//! Runs queries against a small fixture graph and compares the solutions
+//! with expected tables:
+//! - FILTER comparisons, logical operators, REGEX, STRSTARTS and BOUND
+//! - ORDER BY over integers, strings and date-times
+//! - LIMIT/OFFSET applied after ordering
+
+#![cfg(feature = "sparql")]
+
+use aingle_cortex::sparql::{execute_query, parse_sparql};
+use aingle_graph::{GraphDB, NodeId, Predicate, Triple, Value};
+
+const EX: &str = "http://example.org/";
+
+/// Whether the expected rows are in result order
+#[derive(Clone, Copy)]
+enum Order {
+    Exact,
+    Any,
+}
+
+fn ex(local: &str) -> String {
+    format!("{EX}{local}")
+}
+
+/// Four people with names, ages, cities, birth instants and a `knows` cycle
+///
+/// | person | name  | age | city  | born                      | knows |
+/// |--------|-------|-----|-------|---------------------------|-------|
+/// | alice  | Alice | 30  | Paris | 1994-03-01T08:00:00Z      | bob   |
+/// | bob    | Bob   | 25  | Lima  | 1999-07-15T12:00:00+02:00 | carol |
+/// | carol  | Carol | 41  |       | 1983-11-30T23:30:00-05:00 | alice |
+/// | dave   | Dave  | 17  | Paris | 1999-07-15T11:00:00Z      |       |
+fn fixture() -> GraphDB {
+    let graph = GraphDB::memory().unwrap();
+    let people = [
+        (
+            "alice",
+            "Alice",
+            30,
+            Some("Paris"),
+            "1994-03-01T08:00:00Z",
+            Some("bob"),
+        ),
+        (
+            "bob",
+            "Bob",
+            25,
+            Some("Lima"),
+            "1999-07-15T12:00:00+02:00",
+            Some("carol"),
+        ),
+        (
+            "carol",
+            "Carol",
+            41,
+            None,
+            "1983-11-30T23:30:00-05:00",
+            Some("alice"),
+        ),
+        (
+            "dave",
+            "Dave",
+            17,
+            Some("Paris"),
+            "1999-07-15T11:00:00Z",
+            None,
+        ),
+    ];
+
+    for (id, name, age, city, born, knows) in people {
+        let subject = NodeId::named(ex(id));
+        let add = |predicate: &str, object: Value| {
+            graph
+                .insert(Triple::new(
+                    subject.clone(),
+                    Predicate::named(ex(predicate)),
+                    object,
+                ))
+                .unwrap();
+        };
+        add("name", Value::literal(name));
+        add("age", Value::Integer(age));
+        add("born", Value::DateTime(born.to_string()));
+        if let Some(city) = city {
+            add("city", Value::literal(city));
+        }
+        if let Some(knows) = knows {
+            add("knows", Value::Node(NodeId::named(ex(knows))));
+        }
+    }
+    graph
+}
+
+/// Run a SELECT query and compare its variables and solution table
+fn check(query: &str, variables: &[&str], expected: &[&[&str]], order: Order) {
+    let graph = fixture();
+    let query = format!("PREFIX ex: <{EX}>\n{query}");
+    let parsed = parse_sparql(&query).unwrap();
+    let result = execute_query(&graph, &parsed).unwrap();
+
+    assert_eq!(result.result_type, "bindings");
+    let vars = result.variables.unwrap();
+    assert_eq!(vars, variables, "variables of {query}");
+
+    let mut rows: Vec<Vec<String>> = result
+        .bindings
+        .unwrap()
+        .iter()
+        .map(|binding| {
+            vars.iter()
+                .map(|v| binding[v].as_str().unwrap_or_default().to_string())
+                .collect()
+        })
+        .collect();
+    let mut expected: Vec<Vec<String>> = expected
+        .iter()
+        .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+        .collect();
+
+    if let Order::Any = order {
+        rows.sort();
+        expected.sort();
+    }
+    assert_eq!(rows, expected, "solutions of {query}");
+}
+
+#[test]
+fn bgp_joins_patterns_on_shared_variables() {
+    check(
+        "SELECT ?name ?city WHERE { ?p ex:name ?name . ?p ex:city ?city }",
+        &["name", "city"],
+        &[
+            &[r#""Alice""#, r#""Paris""#],
+            &[r#""Bob""#, r#""Lima""#],
+            &[r#""Dave""#, r#""Paris""#],
+        ],
+        Order::Any,
+    );
+}
+
+#[test]
+fn bgp_follows_node_objects() {
+    check(
+        "SELECT ?a ?c WHERE { ?a ex:knows ?b . ?b ex:knows ?c }",
+        &["a", "c"],
+        &[
+            &["<http://example.org/alice>", "<http://example.org/carol>"],
+            &["<http://example.org/bob>", "<http://example.org/alice>"],
+            &["<http://example.org/carol>", "<http://example.org/bob>"],
+        ],
+        Order::Any,
+    );
+}
+
+#[test]
+fn filter_numeric_comparison() {
+    check(
+        "SELECT ?name WHERE { ?p ex:name ?name ; ex:age ?age FILTER(?age > 20) }",
+        &["name"],
+        &[&[r#""Alice""#], &[r#""Bob""#], &[r#""Carol""#]],
+        Order::Any,
+    );
+}
+
+#[test]
+fn filter_logical_and() {
+    check(
+        "SELECT ?name ?age WHERE { ?p ex:name ?name ; ex:age ?age FILTER(?age >= 18 && ?age < 40) }",
+        &["name", "age"],
+        &[&[r#""Alice""#, "30"], &[r#""Bob""#, "25"]],
+        Order::Any,
+    );
+}
+
+#[test]
+fn filter_logical_or_with_unbound_operand() {
+    // carol has no city: the left operand is an error, the right one holds
+    check(
+        r#"SELECT ?name WHERE {
+            ?p ex:name ?name ; ex:age ?age
+            OPTIONAL { ?p ex:city ?city }
+            FILTER(?city = "Lima" || ?age > 40)
+        }"#,
+        &["name"],
+        &[&[r#""Bob""#], &[r#""Carol""#]],
+        Order::Any,
+    );
+}
+
+#[test]
+fn filter_not_equal() {
+    check(
+        r#"SELECT ?name WHERE { ?p ex:name ?name ; ex:city ?city FILTER(?city != "Paris") }"#,
+        &["name"],
+        &[&[r#""Bob""#]],
+        Order::Any,
+    );
+}
+
+#[test]
+fn filter_regex_case_insensitive() {
+    check(
+        r#"SELECT ?name WHERE { ?p ex:name ?name FILTER(regex(?name, "^a", "i")) }"#,
+        &["name"],
+        &[&[r#""Alice""#]],
+        Order::Any,
+    );
+}
+
+#[test]
+fn filter_strstarts() {
+    check(
+        r#"SELECT ?p WHERE { ?p ex:name ?name FILTER(STRSTARTS(?name, "C")) }"#,
+        &["p"],
+        &[&["<http://example.org/carol>"]],
+        Order::Any,
+    );
+}
+
+#[test]
+fn filter_not_bound_after_optional() {
+    check(
+        "SELECT ?name WHERE { ?p ex:name ?name OPTIONAL { ?p ex:city ?city } FILTER(!BOUND(?city)) }",
+        &["name"],
+        &[&[r#""Carol""#]],
+        Order::Any,
+    );
+}
+
+#[test]
+fn filter_equality_on_bound_iri() {
+    check(
+        "SELECT ?name ?age WHERE { ?p ex:name ?name ; ex:age ?age FILTER(?p = ex:bob) }",
+        &["name", "age"],
+        &[&[r#""Bob""#, "25"]],
+        Order::Any,
+    );
+}
+
+#[test]
+fn filter_datetime_comparison() {
+    check(
+        r#"SELECT ?name WHERE {
+            ?p ex:name ?name ; ex:born ?born
+            FILTER(?born > "1995-01-01T00:00:00Z"^^<http://www.w3.org/2001/XMLSchema#dateTime>)
+        }"#,
+        &["name"],
+        &[&[r#""Bob""#], &[r#""Dave""#]],
+        Order::Any,
+    );
+}
+
+#[test]
+fn order_by_integer() {
+    check(
+        "SELECT ?name ?age WHERE { ?p ex:name ?name ; ex:age ?age } ORDER BY ?age",
+        &["name", "age"],
+        &[
+            &[r#""Dave""#, "17"],
+            &[r#""Bob""#, "25"],
+            &[r#""Alice""#, "30"],
+            &[r#""Carol""#, "41"],
+        ],
+        Order::Exact,
+    );
+}
+
+#[test]
+fn order_by_string() {
+    check(
+        "SELECT ?name WHERE { ?p ex:name ?name } ORDER BY DESC(?name)",
+        &["name"],
+        &[
+            &[r#""Dave""#],
+            &[r#""Carol""#],
+            &[r#""Bob""#],
+            &[r#""Alice""#],
+        ],
+        Order::Exact,
+    );
+}
+
+#[test]
+fn order_by_datetime_instant() {
+    // bob (10:00Z) precedes dave (11:00Z) although "…T11" < "…T12" lexically
+    check(
+        "SELECT ?name WHERE { ?p ex:name ?name ; ex:born ?born } ORDER BY ?born",
+        &["name"],
+        &[
+            &[r#""Carol""#],
+            &[r#""Alice""#],
+            &[r#""Bob""#],
+            &[r#""Dave""#],
+        ],
+        Order::Exact,
+    );
+}
+
+#[test]
+fn order_by_desc_with_limit() {
+    check(
+        "SELECT ?name WHERE { ?p ex:name ?name ; ex:age ?age } ORDER BY DESC(?age) LIMIT 2",
+        &["name"],
+        &[&[r#""Carol""#], &[r#""Alice""#]],
+        Order::Exact,
+    );
+}
+
+#[test]
+fn limit_and_offset_apply_after_order() {
+    check(
+        "SELECT ?name WHERE { ?p ex:name ?name ; ex:age ?age } ORDER BY ?age LIMIT 2 OFFSET 1",
+        &["name"],
+        &[&[r#""Bob""#], &[r#""Alice""#]],
+        Order::Exact,
+    );
+}
+
+#[test]
+fn distinct_ordered_values() {
+    check(
+        "SELECT DISTINCT ?city WHERE { ?p ex:city ?city } ORDER BY ?city",
+        &["city"],
+        &[&[r#""Lima""#], &[r#""Paris""#]],
+        Order::Exact,
+    );
+}
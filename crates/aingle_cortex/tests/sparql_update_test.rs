@@ -0,0 +1,276 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Integration tests for SPARQL UPDATE
+//!
+//! Drives `POST /api/v1/sparql/update` through the SPARQL router:
+//! - INSERT DATA is visible to a following SELECT
+//! - DELETE WHERE removes exactly the triples its pattern matches
+//! - Updates need write scope and an `application/sparql-update` body
+//! - Unsupported update forms are refused before anything is written
+
+#![cfg(feature = "sparql")]
+
+use aingle_cortex::middleware::{RequestPrincipal, WRITE_ROLE};
+use aingle_cortex::sparql;
+use aingle_cortex::state::AppState;
+use aingle_graph::{NodeId, Predicate, Triple, TriplePattern, Value};
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use serde_json::json;
+use tower::ServiceExt;
+
+const EX: &str = "http://example.org/";
+
+fn writer() -> RequestPrincipal {
+    RequestPrincipal {
+        user_id: Some("loader".to_string()),
+        roles: vec!["user".to_string(), WRITE_ROLE.to_string()],
+    }
+}
+
+fn reader() -> RequestPrincipal {
+    RequestPrincipal {
+        user_id: Some("reader".to_string()),
+        roles: vec!["user".to_string()],
+    }
+}
+
+async fn send(
+    state: &AppState,
+    uri: &str,
+    content_type: &str,
+    body: String,
+    principal: RequestPrincipal,
+) -> (StatusCode, serde_json::Value) {
+    let mut request = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-type", content_type)
+        .body(Body::from(body))
+        .unwrap();
+    request.extensions_mut().insert(principal);
+
+    let response = sparql::router()
+        .with_state(state.clone())
+        .oneshot(request)
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+async fn update(
+    state: &AppState,
+    update: &str,
+    principal: RequestPrincipal,
+) -> (StatusCode, serde_json::Value) {
+    send(
+        state,
+        "/api/v1/sparql/update",
+        "application/sparql-update",
+        update.to_string(),
+        principal,
+    )
+    .await
+}
+
+async fn select(state: &AppState, query: &str) -> Vec<serde_json::Value> {
+    let (status, body) = send(
+        state,
+        "/api/v1/sparql",
+        "application/json",
+        json!({ "query": query }).to_string(),
+        reader(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    body["bindings"].as_array().unwrap().clone()
+}
+
+fn state() -> AppState {
+    AppState::with_db_path(":memory:", None).unwrap()
+}
+
+#[tokio::test]
+async fn insert_data_is_visible_to_select() {
+    let state = state();
+
+    let (status, body) = update(
+        &state,
+        &format!("INSERT DATA {{ <{EX}alice> <{EX}name> \"Alice\" . <{EX}alice> <{EX}age> 30 }}"),
+        writer(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["inserted"], 2);
+    assert_eq!(body["deleted"], 0);
+
+    let rows = select(
+        &state,
+        &format!("SELECT ?name WHERE {{ <{EX}alice> <{EX}name> ?name }}"),
+    )
+    .await;
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["name"], "\"Alice\"");
+
+    // Re-inserting the same data adds nothing
+    let (_, body) = update(
+        &state,
+        &format!("INSERT DATA {{ <{EX}alice> <{EX}age> 30 }}"),
+        writer(),
+    )
+    .await;
+    assert_eq!(body["inserted"], 0);
+
+    let audit = state.audit_log.read().await;
+    let entries = audit.query(None, None, Some("sparql_update"), None, None, 10);
+    assert_eq!(entries.len(), 2);
+    assert!(entries.iter().all(|e| e.user_id == "loader"));
+}
+
+#[tokio::test]
+async fn delete_where_removes_exactly_the_matches() {
+    let state = state();
+    {
+        let graph = state.graph.read().await;
+        for (person, city) in [("alice", "Paris"), ("bob", "Lima"), ("carol", "Paris")] {
+            graph
+                .insert(Triple::new(
+                    NodeId::named(format!("{EX}{person}")),
+                    Predicate::named(format!("{EX}city")),
+                    Value::literal(city),
+                ))
+                .unwrap();
+            graph
+                .insert(Triple::new(
+                    NodeId::named(format!("{EX}{person}")),
+                    Predicate::named(format!("{EX}name")),
+                    Value::literal(person),
+                ))
+                .unwrap();
+        }
+    }
+
+    let (status, body) = update(
+        &state,
+        &format!("DELETE WHERE {{ ?person <{EX}city> \"Paris\" }}"),
+        writer(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["deleted"], 2);
+
+    let graph = state.graph.read().await;
+    let cities = graph
+        .find(TriplePattern::predicate(Predicate::named(format!(
+            "{EX}city"
+        ))))
+        .unwrap();
+    assert_eq!(cities.len(), 1);
+    assert_eq!(cities[0].subject, NodeId::named(format!("{EX}bob")));
+    let names = graph
+        .find(TriplePattern::predicate(Predicate::named(format!(
+            "{EX}name"
+        ))))
+        .unwrap();
+    assert_eq!(names.len(), 3);
+    drop(graph);
+
+    let removed = state
+        .tombstones
+        .get(
+            &Triple::new(
+                NodeId::named(format!("{EX}alice")),
+                Predicate::named(format!("{EX}city")),
+                Value::literal("Paris"),
+            )
+            .id(),
+        )
+        .unwrap()
+        .expect("deleted triple keeps a tombstone");
+    assert!(removed.is_deleted());
+}
+
+#[tokio::test]
+async fn delete_insert_where_rewrites_matches() {
+    let state = state();
+    update(
+        &state,
+        &format!("INSERT DATA {{ <{EX}alice> <{EX}status> \"draft\" . <{EX}bob> <{EX}status> \"final\" }}"),
+        writer(),
+    )
+    .await;
+
+    let (status, body) = update(
+        &state,
+        &format!(
+            "DELETE {{ ?doc <{EX}status> \"draft\" }} \
+             INSERT {{ ?doc <{EX}status> \"review\" }} \
+             WHERE {{ ?doc <{EX}status> \"draft\" }}"
+        ),
+        writer(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(
+        (body["deleted"].clone(), body["inserted"].clone()),
+        (json!(1), json!(1))
+    );
+
+    let rows = select(
+        &state,
+        &format!("SELECT ?doc WHERE {{ ?doc <{EX}status> \"review\" }}"),
+    )
+    .await;
+    assert_eq!(rows.len(), 1);
+}
+
+#[tokio::test]
+async fn update_without_write_scope_is_forbidden() {
+    let state = state();
+
+    let (status, _) = update(
+        &state,
+        &format!("INSERT DATA {{ <{EX}mallory> <{EX}name> \"Mallory\" }}"),
+        reader(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let graph = state.graph.read().await;
+    assert_eq!(graph.count(), 0);
+}
+
+#[tokio::test]
+async fn unsupported_update_forms_are_rejected() {
+    let state = state();
+
+    for form in [
+        "CLEAR DEFAULT".to_string(),
+        format!("LOAD <{EX}data.ttl>"),
+        format!("INSERT DATA {{ GRAPH <{EX}g> {{ <{EX}a> <{EX}p> 1 }} }}"),
+        // The first operation is fine, but nothing applies when a later one is not
+        format!("INSERT DATA {{ <{EX}a> <{EX}p> 1 }} ; DROP ALL"),
+    ] {
+        let (status, body) = update(&state, &form, writer()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{form}");
+        let error = body["error"].as_str().unwrap();
+        assert!(
+            error.contains("INSERT DATA, DELETE DATA, DELETE WHERE"),
+            "{error}"
+        );
+    }
+    assert_eq!(state.graph.read().await.count(), 0);
+
+    let (status, _) = send(
+        &state,
+        "/api/v1/sparql/update",
+        "text/plain",
+        format!("INSERT DATA {{ <{EX}a> <{EX}p> 1 }}"),
+        writer(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,250 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Integration tests for soft-deleted triples
+//!
+//! Drives the tombstone lifecycle through the REST router:
+//! - `DELETE /api/v1/triples/:id?reason=` keeps a tombstone
+//! - Tombstoned triples are invisible to get, list and pattern queries
+//! - `GET /api/v1/triples/:id/history` and `?include_deleted=true`
+//! - `POST /api/v1/triples/purge` honours retention and the admin role
+
+use aingle_cortex::middleware::RequestPrincipal;
+use aingle_cortex::rest;
+use aingle_cortex::state::AppState;
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+fn admin() -> RequestPrincipal {
+    RequestPrincipal {
+        user_id: Some("auditor".to_string()),
+        roles: vec!["admin".to_string()],
+    }
+}
+
+fn reader() -> RequestPrincipal {
+    RequestPrincipal {
+        user_id: Some("reader".to_string()),
+        roles: vec!["user".to_string()],
+    }
+}
+
+async fn send(
+    state: &AppState,
+    method: &str,
+    uri: &str,
+    body: Option<Value>,
+    principal: RequestPrincipal,
+) -> (StatusCode, Value) {
+    let mut request = Request::builder().method(method).uri(uri);
+    if body.is_some() {
+        request = request.header("content-type", "application/json");
+    }
+    let mut request = request
+        .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))
+        .unwrap();
+    request.extensions_mut().insert(principal);
+
+    let response = rest::router()
+        .with_state(state.clone())
+        .oneshot(request)
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let value = if bytes.is_empty() {
+        Value::Null
+    } else {
+        serde_json::from_slice(&bytes).unwrap()
+    };
+    (status, value)
+}
+
+async fn create(state: &AppState, subject: &str) -> String {
+    let (status, body) = send(
+        state,
+        "POST",
+        "/api/v1/triples",
+        Some(json!({
+            "subject": subject,
+            "predicate": "ex:email",
+            "object": "someone@example.com",
+        })),
+        admin(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    body["id"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn test_deleted_triple_is_hidden_but_has_history() {
+    let state = AppState::new().unwrap();
+    let id = create(&state, "ex:alice").await;
+    create(&state, "ex:bob").await;
+
+    let (status, _) = send(
+        &state,
+        "DELETE",
+        &format!("/api/v1/triples/{}?reason=gdpr-erasure", id),
+        None,
+        admin(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    // Normal reads no longer see it
+    let (status, _) = send(
+        &state,
+        "GET",
+        &format!("/api/v1/triples/{}", id),
+        None,
+        admin(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (_, list) = send(&state, "GET", "/api/v1/triples", None, admin()).await;
+    assert_eq!(list["total"], 1);
+    assert!(list.get("deleted").is_none());
+
+    let (_, matches) = send(
+        &state,
+        "POST",
+        "/api/v1/query",
+        Some(json!({ "subject": "ex:alice" })),
+        admin(),
+    )
+    .await;
+    assert_eq!(matches["total"], 0);
+
+    // ...but its history survives
+    let (status, history) = send(
+        &state,
+        "GET",
+        &format!("/api/v1/triples/{}/history", id),
+        None,
+        reader(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(history["status"], "deleted");
+    let events = history["events"].as_array().unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0]["event"], "created");
+    assert_eq!(events[1]["event"], "deleted");
+    assert_eq!(events[1]["actor"], "auditor");
+    assert_eq!(events[1]["reason"], "gdpr-erasure");
+
+    // Admins can list it alongside live triples
+    let (status, list) = send(
+        &state,
+        "GET",
+        "/api/v1/triples?include_deleted=true",
+        None,
+        admin(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(list["total"], 1);
+    let deleted = list["deleted"].as_array().unwrap();
+    assert_eq!(deleted.len(), 1);
+    assert_eq!(deleted[0]["id"], id.as_str());
+    assert_eq!(deleted[0]["reason"], "gdpr-erasure");
+}
+
+#[tokio::test]
+async fn test_include_deleted_and_purge_require_admin() {
+    let state = AppState::new().unwrap();
+
+    let (status, _) = send(
+        &state,
+        "GET",
+        "/api/v1/triples?include_deleted=true",
+        None,
+        reader(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = send(&state, "POST", "/api/v1/triples/purge", None, reader()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_purge_respects_retention() {
+    let state = AppState::new().unwrap();
+    let id = create(&state, "ex:carol").await;
+    send(
+        &state,
+        "DELETE",
+        &format!("/api/v1/triples/{}", id),
+        None,
+        admin(),
+    )
+    .await;
+
+    // Within the default retention nothing is purged
+    let (status, report) = send(&state, "POST", "/api/v1/triples/purge", None, admin()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report["purged"], 0);
+
+    let (_, report) = send(
+        &state,
+        "POST",
+        "/api/v1/triples/purge?older_than_secs=0",
+        None,
+        admin(),
+    )
+    .await;
+    assert_eq!(report["purged"], 1);
+    assert_eq!(report["ids"][0], id.as_str());
+
+    let (status, _) = send(
+        &state,
+        "GET",
+        &format!("/api/v1/triples/{}/history", id),
+        None,
+        admin(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_recreated_triple_is_active_again() {
+    let state = AppState::new().unwrap();
+    let id = create(&state, "ex:dave").await;
+    send(
+        &state,
+        "DELETE",
+        &format!("/api/v1/triples/{}", id),
+        None,
+        admin(),
+    )
+    .await;
+    assert_eq!(create(&state, "ex:dave").await, id);
+
+    let (_, history) = send(
+        &state,
+        "GET",
+        &format!("/api/v1/triples/{}/history", id),
+        None,
+        admin(),
+    )
+    .await;
+    assert_eq!(history["status"], "active");
+    assert_eq!(history["events"].as_array().unwrap().len(), 3);
+
+    let (_, list) = send(
+        &state,
+        "GET",
+        "/api/v1/triples?include_deleted=true",
+        None,
+        admin(),
+    )
+    .await;
+    assert!(list.get("deleted").is_none());
+}
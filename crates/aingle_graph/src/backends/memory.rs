@@ -5,29 +5,40 @@
 //!
 //! Provides fast, ephemeral storage for testing and temporary graphs.
 
-use super::StorageBackend;
-use crate::{Result, Triple, TripleId};
-use std::collections::HashMap;
+use super::{decode_assertions, encode_assertions, BackendInfo, StorageBackend};
+use crate::{Result, Triple, TripleId, TripleMeta};
+use std::collections::{BTreeSet, HashMap};
+use std::ops::Bound;
 use std::sync::RwLock;
 
+/// A secondary index: its definition and its keys
+type StoredIndex = (Vec<u8>, BTreeSet<Vec<u8>>);
+
 /// In-memory storage backend
 pub struct MemoryBackend {
     /// Triple storage
     triples: RwLock<HashMap<[u8; 32], Vec<u8>>>,
+    /// Further assertions of stored triples
+    assertions: RwLock<HashMap<[u8; 32], Vec<u8>>>,
+    /// Secondary index definitions and keys, by index name
+    indexes: RwLock<HashMap<String, StoredIndex>>,
+    /// Shutdown marker
+    marker: RwLock<Option<Vec<u8>>>,
 }
 
 impl MemoryBackend {
     /// Create a new empty in-memory backend
     pub fn new() -> Self {
-        Self {
-            triples: RwLock::new(HashMap::new()),
-        }
+        Self::with_capacity(0)
     }
 
     /// Create with pre-allocated capacity
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
             triples: RwLock::new(HashMap::with_capacity(capacity)),
+            assertions: RwLock::new(HashMap::new()),
+            indexes: RwLock::new(HashMap::new()),
+            marker: RwLock::new(None),
         }
     }
 }
@@ -67,7 +78,12 @@ impl StorageBackend for MemoryBackend {
             .triples
             .write()
             .map_err(|_| crate::Error::Storage("lock poisoned".into()))?;
-        Ok(triples.remove(id.as_bytes()).is_some())
+        let removed = triples.remove(id.as_bytes()).is_some();
+        self.assertions
+            .write()
+            .map_err(|_| crate::Error::Storage("lock poisoned".into()))?
+            .remove(id.as_bytes());
+        Ok(removed)
     }
 
     fn iter_all(&self) -> Result<Vec<Triple>> {
@@ -82,6 +98,130 @@ impl StorageBackend for MemoryBackend {
             .collect())
     }
 
+    fn put_assertions(&self, id: &TripleId, assertions: &[TripleMeta]) -> Result<()> {
+        let mut stored = self
+            .assertions
+            .write()
+            .map_err(|_| crate::Error::Storage("lock poisoned".into()))?;
+        if assertions.is_empty() {
+            stored.remove(id.as_bytes());
+        } else {
+            stored.insert(*id.as_bytes(), encode_assertions(assertions));
+        }
+        Ok(())
+    }
+
+    fn get_assertions(&self, id: &TripleId) -> Result<Vec<TripleMeta>> {
+        let stored = self
+            .assertions
+            .read()
+            .map_err(|_| crate::Error::Storage("lock poisoned".into()))?;
+        match stored.get(id.as_bytes()) {
+            Some(bytes) => decode_assertions(bytes),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    fn iter_assertions(&self) -> Result<Vec<(TripleId, Vec<TripleMeta>)>> {
+        let stored = self
+            .assertions
+            .read()
+            .map_err(|_| crate::Error::Storage("lock poisoned".into()))?;
+        stored
+            .iter()
+            .map(|(id, bytes)| Ok((TripleId::new(*id), decode_assertions(bytes)?)))
+            .collect()
+    }
+
+    fn put_index_def(&self, name: &str, def: &[u8]) -> Result<()> {
+        let mut indexes = self
+            .indexes
+            .write()
+            .map_err(|_| crate::Error::Storage("lock poisoned".into()))?;
+        indexes.entry(name.to_string()).or_default().0 = def.to_vec();
+        Ok(())
+    }
+
+    fn iter_index_defs(&self) -> Result<Vec<(String, Vec<u8>)>> {
+        let indexes = self
+            .indexes
+            .read()
+            .map_err(|_| crate::Error::Storage("lock poisoned".into()))?;
+        Ok(indexes
+            .iter()
+            .map(|(name, (def, _))| (name.clone(), def.clone()))
+            .collect())
+    }
+
+    fn put_index_entries(&self, name: &str, keys: &[Vec<u8>]) -> Result<()> {
+        let mut indexes = self
+            .indexes
+            .write()
+            .map_err(|_| crate::Error::Storage("lock poisoned".into()))?;
+        let (_, entries) = indexes.entry(name.to_string()).or_default();
+        entries.extend(keys.iter().cloned());
+        Ok(())
+    }
+
+    fn delete_index_entries(&self, name: &str, keys: &[Vec<u8>]) -> Result<()> {
+        let mut indexes = self
+            .indexes
+            .write()
+            .map_err(|_| crate::Error::Storage("lock poisoned".into()))?;
+        if let Some((_, entries)) = indexes.get_mut(name) {
+            for key in keys {
+                entries.remove(key);
+            }
+        }
+        Ok(())
+    }
+
+    fn scan_index(&self, name: &str, start: &[u8], end: Option<&[u8]>) -> Result<Vec<Vec<u8>>> {
+        let indexes = self
+            .indexes
+            .read()
+            .map_err(|_| crate::Error::Storage("lock poisoned".into()))?;
+        let Some((_, entries)) = indexes.get(name) else {
+            return Ok(Vec::new());
+        };
+        let end = end.map_or(Bound::Unbounded, Bound::Excluded);
+        Ok(entries
+            .range::<[u8], _>((Bound::Included(start), end))
+            .cloned()
+            .collect())
+    }
+
+    fn index_len(&self, name: &str) -> usize {
+        self.indexes
+            .read()
+            .map(|indexes| indexes.get(name).map_or(0, |(_, entries)| entries.len()))
+            .unwrap_or(0)
+    }
+
+    fn drop_index(&self, name: &str) -> Result<()> {
+        self.indexes
+            .write()
+            .map_err(|_| crate::Error::Storage("lock poisoned".into()))?
+            .remove(name);
+        Ok(())
+    }
+
+    fn put_marker(&self, marker: &[u8]) -> Result<()> {
+        *self
+            .marker
+            .write()
+            .map_err(|_| crate::Error::Storage("lock poisoned".into()))? = Some(marker.to_vec());
+        Ok(())
+    }
+
+    fn get_marker(&self) -> Result<Option<Vec<u8>>> {
+        Ok(self
+            .marker
+            .read()
+            .map_err(|_| crate::Error::Storage("lock poisoned".into()))?
+            .clone())
+    }
+
     fn count(&self) -> usize {
         self.triples.read().map(|t| t.len()).unwrap_or(0)
     }
@@ -93,6 +233,10 @@ impl StorageBackend for MemoryBackend {
             .unwrap_or(0)
     }
 
+    fn info(&self) -> BackendInfo {
+        BackendInfo::new("memory")
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -153,4 +297,23 @@ mod tests {
         let all = backend.iter_all().unwrap();
         assert_eq!(all.len(), 5);
     }
+
+    #[test]
+    fn test_assertions_deleted_with_triple() {
+        let backend = MemoryBackend::new();
+        let triple = Triple::literal("a", "b", "c");
+        let id = triple.id();
+        let other = TripleMeta::new().with_asserted_by(NodeId::named("agent:b"));
+
+        backend.put(&id, &triple).unwrap();
+        backend
+            .put_assertions(&id, std::slice::from_ref(&other))
+            .unwrap();
+        assert_eq!(backend.get_assertions(&id).unwrap(), vec![other]);
+        assert_eq!(backend.iter_assertions().unwrap().len(), 1);
+
+        backend.delete(&id).unwrap();
+        assert!(backend.get_assertions(&id).unwrap().is_empty());
+        assert!(backend.iter_assertions().unwrap().is_empty());
+    }
 }
@@ -20,7 +20,8 @@ pub mod rocksdb;
 #[cfg(feature = "sqlite-backend")]
 pub mod sqlite;
 
-use crate::{Result, Triple, TripleId};
+use crate::cache::SubjectCacheOptions;
+use crate::{Error, Result, Triple, TripleId, TripleMeta};
 
 /// Trait for storage backends
 pub trait StorageBackend: Send + Sync {
@@ -56,6 +57,91 @@ pub trait StorageBackend: Send + Sync {
         Ok(())
     }
 
+    /// Store the further assertions of a triple, replacing those stored before
+    ///
+    /// The first assertion is the metadata stored with the triple itself;
+    /// these are the ones recorded when the same triple was inserted again.
+    /// Deleting the triple deletes them too.
+    fn put_assertions(&self, id: &TripleId, assertions: &[TripleMeta]) -> Result<()> {
+        let _ = (id, assertions);
+        Err(Error::BackendUnavailable(
+            "backend does not store triple provenance".into(),
+        ))
+    }
+
+    /// Get the further assertions of a triple
+    fn get_assertions(&self, _id: &TripleId) -> Result<Vec<TripleMeta>> {
+        Ok(Vec::new())
+    }
+
+    /// Iterate over the further assertions of all triples that have any
+    fn iter_assertions(&self) -> Result<Vec<(TripleId, Vec<TripleMeta>)>> {
+        Ok(Vec::new())
+    }
+
+    /// Store the definition of a secondary index, replacing the one stored
+    /// before
+    ///
+    /// Definitions are opaque to the backend; see [`crate::secondary`].
+    fn put_index_def(&self, name: &str, def: &[u8]) -> Result<()> {
+        let _ = (name, def);
+        Err(Error::BackendUnavailable(
+            "backend does not store secondary indexes".into(),
+        ))
+    }
+
+    /// Iterate over the names and definitions of all secondary indexes
+    fn iter_index_defs(&self) -> Result<Vec<(String, Vec<u8>)>> {
+        Ok(Vec::new())
+    }
+
+    /// Add keys to a secondary index; keys already present are kept once
+    fn put_index_entries(&self, name: &str, keys: &[Vec<u8>]) -> Result<()> {
+        let _ = (name, keys);
+        Err(Error::BackendUnavailable(
+            "backend does not store secondary indexes".into(),
+        ))
+    }
+
+    /// Remove keys from a secondary index
+    fn delete_index_entries(&self, name: &str, keys: &[Vec<u8>]) -> Result<()> {
+        let _ = (name, keys);
+        Err(Error::BackendUnavailable(
+            "backend does not store secondary indexes".into(),
+        ))
+    }
+
+    /// Get the keys of a secondary index from `start` (inclusive) up to
+    /// `end` (exclusive), or to the last key if `end` is `None`, in byte order
+    fn scan_index(&self, _name: &str, _start: &[u8], _end: Option<&[u8]>) -> Result<Vec<Vec<u8>>> {
+        Ok(Vec::new())
+    }
+
+    /// Count the keys of a secondary index
+    fn index_len(&self, _name: &str) -> usize {
+        0
+    }
+
+    /// Delete a secondary index: its definition and all its keys
+    fn drop_index(&self, _name: &str) -> Result<()> {
+        Ok(())
+    }
+
+    /// Store the shutdown marker, replacing the one stored before
+    ///
+    /// The marker is opaque to the backend; see [`crate::integrity`].
+    /// Backends that keep no marker ignore it, and their stores always pass
+    /// the quick check on open.
+    fn put_marker(&self, marker: &[u8]) -> Result<()> {
+        let _ = marker;
+        Ok(())
+    }
+
+    /// Get the shutdown marker, if one is stored
+    fn get_marker(&self) -> Result<Option<Vec<u8>>> {
+        Ok(None)
+    }
+
     /// Flush pending writes to disk
     fn flush(&self) -> Result<()> {
         Ok(())
@@ -66,19 +152,170 @@ pub trait StorageBackend: Send + Sync {
         Ok(())
     }
 
+    /// Describe the backend and the settings it was opened with
+    fn info(&self) -> BackendInfo {
+        BackendInfo::new("custom")
+    }
+
     /// Downcasting support, so callers can access backend-specific handles
     /// (e.g. the shared `sled::Db` used by the persistent DAG).
     fn as_any(&self) -> &dyn std::any::Any;
 }
 
+/// Block compression for persistent backends
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    /// Store blocks uncompressed
+    None,
+    /// LZ4: fast, moderate ratio
+    Lz4,
+    /// Zstandard: slower, better ratio
+    Zstd,
+}
+
+/// How much of a store is checked when it is opened
+///
+/// See [`crate::integrity`] for what each level detects.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VerifyOnOpen {
+    /// Open without checking
+    #[default]
+    Off,
+    /// Check that the store was closed cleanly and still holds the number
+    /// of triples it was closed with
+    Quick,
+    /// The quick check, then cross-check every stored triple against the
+    /// indexes
+    Full,
+}
+
+/// Backend-agnostic tuning for persistent storage
+///
+/// Unset fields keep the backend's own default. Backend-specific knobs live
+/// in `SledOptions` and `RocksOptions`, which both convert from this type.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StorageOptions {
+    /// Memory for cached pages or blocks, in bytes
+    pub cache_bytes: Option<usize>,
+    /// Block compression
+    pub compression: Option<Compression>,
+    /// Memory for buffered writes before they are flushed, in bytes
+    pub write_buffer_bytes: Option<usize>,
+    /// Reject writes with [`Error::ReadOnly`](crate::Error::ReadOnly)
+    pub read_only: bool,
+    /// Integrity check run when the database is opened
+    pub verify_on_open: VerifyOnOpen,
+    /// Rebuild the indexes when the check on open fails, instead of
+    /// refusing to open with [`Error::Integrity`](crate::Error::Integrity)
+    pub auto_rebuild: bool,
+    /// Limits of the read cache for subject lookups; see [`crate::cache`]
+    pub subject_cache: SubjectCacheOptions,
+}
+
+impl StorageOptions {
+    /// Set the cache size in bytes
+    pub fn with_cache_bytes(mut self, bytes: usize) -> Self {
+        self.cache_bytes = Some(bytes);
+        self
+    }
+
+    /// Set the block compression
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+
+    /// Set the write buffer size in bytes
+    pub fn with_write_buffer_bytes(mut self, bytes: usize) -> Self {
+        self.write_buffer_bytes = Some(bytes);
+        self
+    }
+
+    /// Open the database read-only
+    pub fn read_only(mut self) -> Self {
+        self.read_only = true;
+        self
+    }
+
+    /// Set the integrity check run on open
+    pub fn with_verify_on_open(mut self, verify: VerifyOnOpen) -> Self {
+        self.verify_on_open = verify;
+        self
+    }
+
+    /// Rebuild the indexes instead of refusing to open when the check on
+    /// open fails
+    pub fn auto_rebuild(mut self) -> Self {
+        self.auto_rebuild = true;
+        self
+    }
+
+    /// Cache the triples of up to `max_entries` recently read subjects
+    pub fn with_subject_cache(mut self, max_entries: usize) -> Self {
+        self.subject_cache.max_entries = max_entries;
+        self
+    }
+
+    /// Limit the subject cache to `bytes` of encoded triples
+    pub fn with_subject_cache_bytes(mut self, bytes: usize) -> Self {
+        self.subject_cache.max_bytes = Some(bytes);
+        self
+    }
+}
+
+/// Effective settings of an open backend
+///
+/// `None` means the setting is unknown or does not apply to the backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackendInfo {
+    /// Backend name: `memory`, `sled`, `rocksdb`, `sqlite` or `custom`
+    pub backend: String,
+    /// Filesystem path of the database
+    pub path: Option<String>,
+    /// Cache size in bytes
+    pub cache_bytes: Option<usize>,
+    /// Block compression
+    pub compression: Option<Compression>,
+    /// Write buffer size in bytes
+    pub write_buffer_bytes: Option<usize>,
+    /// Whether writes are rejected
+    pub read_only: bool,
+}
+
+impl BackendInfo {
+    /// Create an info record with only the backend name set
+    pub fn new(backend: impl Into<String>) -> Self {
+        Self {
+            backend: backend.into(),
+            path: None,
+            cache_bytes: None,
+            compression: None,
+            write_buffer_bytes: None,
+            read_only: false,
+        }
+    }
+}
+
+/// Serialize a triple's further assertions for storage
+pub(crate) fn encode_assertions(assertions: &[TripleMeta]) -> Vec<u8> {
+    bincode::serde::encode_to_vec(assertions, bincode::config::standard()).unwrap_or_default()
+}
+
+/// Deserialize a triple's further assertions
+pub(crate) fn decode_assertions(bytes: &[u8]) -> Result<Vec<TripleMeta>> {
+    bincode::serde::decode_from_slice(bytes, bincode::config::standard())
+        .map(|(assertions, _)| assertions)
+        .map_err(|e| Error::Serialization(format!("corrupt triple assertions: {}", e)))
+}
+
 // Re-exports
 pub use memory::MemoryBackend;
 
 #[cfg(feature = "sled-backend")]
-pub use self::sled::SledBackend;
+pub use self::sled::{SledBackend, SledOptions};
 
 #[cfg(feature = "rocksdb-backend")]
-pub use self::rocksdb::RocksBackend;
+pub use self::rocksdb::{RocksBackend, RocksOptions};
 
 #[cfg(feature = "sqlite-backend")]
 pub use self::sqlite::SqliteBackend;
@@ -170,4 +407,24 @@ mod tests {
         assert!(backend.flush().is_ok());
         assert!(backend.close().is_ok());
     }
+
+    #[test]
+    fn test_storage_options_builder() {
+        let options = StorageOptions::default()
+            .with_cache_bytes(64 << 20)
+            .with_compression(Compression::Zstd)
+            .with_write_buffer_bytes(8 << 20)
+            .read_only()
+            .with_verify_on_open(VerifyOnOpen::Quick)
+            .auto_rebuild();
+
+        assert_eq!(options.cache_bytes, Some(64 << 20));
+        assert_eq!(options.compression, Some(Compression::Zstd));
+        assert_eq!(options.write_buffer_bytes, Some(8 << 20));
+        assert!(options.read_only);
+        assert_eq!(options.verify_on_open, VerifyOnOpen::Quick);
+        assert!(options.auto_rebuild);
+        assert_eq!(StorageOptions::default().verify_on_open, VerifyOnOpen::Off);
+        assert_eq!(MemoryBackend::new().info(), BackendInfo::new("memory"));
+    }
 }
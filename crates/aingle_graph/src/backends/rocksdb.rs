@@ -6,27 +6,110 @@
 //! Provides high-performance persistent storage using RocksDB.
 //! Best for production workloads with high throughput requirements.
 
-use super::StorageBackend;
-use crate::{Error, Result, Triple, TripleId};
-use rocksdb::{Options, DB};
+use super::{
+    decode_assertions, encode_assertions, BackendInfo, Compression, StorageBackend, StorageOptions,
+};
+use crate::{Error, Result, Triple, TripleId, TripleMeta};
+use rocksdb::{BlockBasedOptions, Cache, DBCompressionType, Direction, IteratorMode, Options, DB};
+
+/// Block cache size RocksDB uses when none is configured (32 MiB)
+const DEFAULT_CACHE_BYTES: usize = 32 * 1024 * 1024;
+
+/// Memtable size RocksDB uses when none is configured (64 MiB)
+const DEFAULT_WRITE_BUFFER_BYTES: usize = 64 * 1024 * 1024;
+
+/// Key prefix for further assertions of a triple, followed by its ID.
+/// Triples themselves are keyed by the bare 32-byte ID.
+const ASSERTIONS_PREFIX: &[u8] = b"assertions/";
+
+fn assertions_key(id: &TripleId) -> Vec<u8> {
+    [ASSERTIONS_PREFIX, id.as_bytes().as_slice()].concat()
+}
+
+/// Whether a key holds a triple rather than its assertions
+fn is_triple_key(key: &[u8]) -> bool {
+    key.len() == 32
+}
+
+/// Options for opening a RocksDB database
+///
+/// Compression defaults to LZ4. Read-only databases are opened with
+/// RocksDB's read-only mode, which does not take the lock file and so can
+/// read the files of a database another process is writing to. It sees the
+/// data as of the moment it was opened.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RocksOptions {
+    /// Backend-agnostic settings
+    pub storage: StorageOptions,
+    /// Maximum number of open files; `None` keeps RocksDB's default
+    pub max_open_files: Option<i32>,
+}
+
+impl From<StorageOptions> for RocksOptions {
+    fn from(storage: StorageOptions) -> Self {
+        Self {
+            storage,
+            ..Self::default()
+        }
+    }
+}
 
 /// RocksDB-based storage backend
 pub struct RocksBackend {
     /// The RocksDB instance
     db: DB,
+    /// Settings the database was opened with
+    info: BackendInfo,
 }
 
 impl RocksBackend {
     /// Open or create a RocksDB database at the given path
     pub fn open(path: &str) -> Result<Self> {
+        Self::open_with(path, RocksOptions::default())
+    }
+
+    /// Open a RocksDB database at the given path with explicit options
+    ///
+    /// A read-only open fails if the database does not exist yet.
+    pub fn open_with(path: &str, options: RocksOptions) -> Result<Self> {
+        let storage = &options.storage;
+        let compression = storage.compression.unwrap_or(Compression::Lz4);
+        let cache_bytes = storage.cache_bytes.unwrap_or(DEFAULT_CACHE_BYTES);
+        let write_buffer_bytes = storage
+            .write_buffer_bytes
+            .unwrap_or(DEFAULT_WRITE_BUFFER_BYTES);
+
         let mut opts = Options::default();
-        opts.create_if_missing(true);
-        opts.set_compression_type(rocksdb::DBCompressionType::Lz4);
+        opts.create_if_missing(!storage.read_only);
+        opts.set_compression_type(match compression {
+            Compression::None => DBCompressionType::None,
+            Compression::Lz4 => DBCompressionType::Lz4,
+            Compression::Zstd => DBCompressionType::Zstd,
+        });
+        opts.set_write_buffer_size(write_buffer_bytes);
+        if let Some(max_open_files) = options.max_open_files {
+            opts.set_max_open_files(max_open_files);
+        }
+        let mut table = BlockBasedOptions::default();
+        table.set_block_cache(&Cache::new_lru_cache(cache_bytes));
+        opts.set_block_based_table_factory(&table);
 
-        let db = DB::open(&opts, path)
-            .map_err(|e| Error::Storage(format!("failed to open rocksdb: {}", e)))?;
+        let db = if storage.read_only {
+            DB::open_for_read_only(&opts, path, false)
+        } else {
+            DB::open(&opts, path)
+        }
+        .map_err(|e| Error::Storage(format!("failed to open rocksdb: {}", e)))?;
 
-        Ok(Self { db })
+        let info = BackendInfo {
+            path: Some(path.to_string()),
+            cache_bytes: Some(cache_bytes),
+            compression: Some(compression),
+            write_buffer_bytes: Some(write_buffer_bytes),
+            read_only: storage.read_only,
+            ..BackendInfo::new("rocksdb")
+        };
+        Ok(Self { db, info })
     }
 
     /// Open with custom options
@@ -34,12 +117,25 @@ impl RocksBackend {
         let db = DB::open(&opts, path)
             .map_err(|e| Error::Storage(format!("failed to open rocksdb: {}", e)))?;
 
-        Ok(Self { db })
+        let info = BackendInfo {
+            path: Some(path.to_string()),
+            ..BackendInfo::new("rocksdb")
+        };
+        Ok(Self { db, info })
+    }
+
+    /// Fail if the database was opened read-only
+    fn check_writable(&self) -> Result<()> {
+        if self.info.read_only {
+            return Err(Error::ReadOnly("rocksdb opened read-only".into()));
+        }
+        Ok(())
     }
 }
 
 impl StorageBackend for RocksBackend {
     fn put(&self, id: &TripleId, triple: &Triple) -> Result<()> {
+        self.check_writable()?;
         let bytes = triple.to_bytes();
         self.db
             .put(id.as_bytes(), bytes)
@@ -56,6 +152,7 @@ impl StorageBackend for RocksBackend {
     }
 
     fn delete(&self, id: &TripleId) -> Result<bool> {
+        self.check_writable()?;
         // Check if exists first
         let exists = self
             .db
@@ -68,21 +165,25 @@ impl StorageBackend for RocksBackend {
                 .delete(id.as_bytes())
                 .map_err(|e| Error::Storage(format!("rocksdb delete error: {}", e)))?;
         }
+        self.db
+            .delete(assertions_key(id))
+            .map_err(|e| Error::Storage(format!("rocksdb delete error: {}", e)))?;
 
         Ok(exists)
     }
 
     fn iter_all(&self) -> Result<Vec<Triple>> {
         let mut triples = Vec::new();
-        let iter = self.db.iterator(rocksdb::IteratorMode::Start);
+        let iter = self.db.iterator(IteratorMode::Start);
 
         for item in iter {
             match item {
-                Ok((_, value)) => {
+                Ok((key, value)) if is_triple_key(&key) => {
                     if let Some(triple) = Triple::from_bytes(&value) {
                         triples.push(triple);
                     }
                 }
+                Ok(_) => {}
                 Err(e) => return Err(Error::Storage(format!("rocksdb iteration error: {}", e))),
             }
         }
@@ -90,9 +191,52 @@ impl StorageBackend for RocksBackend {
         Ok(triples)
     }
 
+    fn put_assertions(&self, id: &TripleId, assertions: &[TripleMeta]) -> Result<()> {
+        self.check_writable()?;
+        let key = assertions_key(id);
+        let result = if assertions.is_empty() {
+            self.db.delete(key)
+        } else {
+            self.db.put(key, encode_assertions(assertions))
+        };
+        result.map_err(|e| Error::Storage(format!("rocksdb put error: {}", e)))
+    }
+
+    fn get_assertions(&self, id: &TripleId) -> Result<Vec<TripleMeta>> {
+        match self.db.get(assertions_key(id)) {
+            Ok(Some(bytes)) => decode_assertions(&bytes),
+            Ok(None) => Ok(Vec::new()),
+            Err(e) => Err(Error::Storage(format!("rocksdb get error: {}", e))),
+        }
+    }
+
+    fn iter_assertions(&self) -> Result<Vec<(TripleId, Vec<TripleMeta>)>> {
+        let mut all = Vec::new();
+        let iter = self
+            .db
+            .iterator(IteratorMode::From(ASSERTIONS_PREFIX, Direction::Forward));
+
+        for item in iter {
+            let (key, value) =
+                item.map_err(|e| Error::Storage(format!("rocksdb iteration error: {}", e)))?;
+            let Some(id) = key.strip_prefix(ASSERTIONS_PREFIX) else {
+                break;
+            };
+            let Ok(id) = <[u8; 32]>::try_from(id) else {
+                continue;
+            };
+            all.push((TripleId::new(id), decode_assertions(&value)?));
+        }
+
+        Ok(all)
+    }
+
     fn count(&self) -> usize {
         // RocksDB doesn't have a fast count, need to iterate
-        self.db.iterator(rocksdb::IteratorMode::Start).count()
+        self.db
+            .iterator(IteratorMode::Start)
+            .filter(|item| item.as_ref().is_ok_and(|(key, _)| is_triple_key(key)))
+            .count()
     }
 
     fn size_bytes(&self) -> usize {
@@ -106,12 +250,20 @@ impl StorageBackend for RocksBackend {
     }
 
     fn flush(&self) -> Result<()> {
+        // Nothing is buffered in read-only mode
+        if self.info.read_only {
+            return Ok(());
+        }
         self.db
             .flush()
             .map_err(|e| Error::Storage(format!("rocksdb flush error: {}", e)))?;
         Ok(())
     }
 
+    fn info(&self) -> BackendInfo {
+        self.info.clone()
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -149,4 +301,140 @@ mod tests {
         // Verify deleted
         assert!(backend.get(&id).unwrap().is_none());
     }
+
+    #[test]
+    fn test_read_only_rejects_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let path_str = path.to_str().unwrap();
+
+        let kept = Triple::new(
+            NodeId::named("ro:kept"),
+            Predicate::named("data"),
+            Value::literal("before"),
+        );
+        {
+            let backend = RocksBackend::open(path_str).unwrap();
+            backend.put(&kept.id(), &kept).unwrap();
+            backend.flush().unwrap();
+        }
+
+        let options = RocksOptions::from(StorageOptions::default().read_only());
+        let backend = RocksBackend::open_with(path_str, options).unwrap();
+        assert!(backend.info().read_only);
+        assert!(backend.get(&kept.id()).unwrap().is_some());
+
+        let added = Triple::new(
+            NodeId::named("ro:added"),
+            Predicate::named("data"),
+            Value::literal("after"),
+        );
+        assert!(matches!(
+            backend.put(&added.id(), &added),
+            Err(Error::ReadOnly(_))
+        ));
+        assert!(matches!(
+            backend.apply_batch(&[(&added.id(), &added)]),
+            Err(Error::ReadOnly(_))
+        ));
+        assert!(matches!(
+            backend.delete(&kept.id()),
+            Err(Error::ReadOnly(_))
+        ));
+        assert!(backend.flush().is_ok());
+        assert_eq!(backend.count(), 1);
+    }
+
+    #[test]
+    fn test_read_only_while_writer_is_open() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let path_str = path.to_str().unwrap();
+
+        let writer = RocksBackend::open(path_str).unwrap();
+        let triple = Triple::new(
+            NodeId::named("live:node"),
+            Predicate::named("data"),
+            Value::literal("shared"),
+        );
+        writer.put(&triple.id(), &triple).unwrap();
+        writer.flush().unwrap();
+
+        let options = RocksOptions::from(StorageOptions::default().read_only());
+        let reader = RocksBackend::open_with(path_str, options).unwrap();
+        assert!(reader.get(&triple.id()).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_reopen_with_compression() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let path_str = path.to_str().unwrap();
+
+        let triples: Vec<Triple> = (0..100)
+            .map(|i| {
+                Triple::new(
+                    NodeId::named(format!("zstd:{}", i)),
+                    Predicate::named("data"),
+                    Value::literal(format!("value {}", i)),
+                )
+            })
+            .collect();
+        {
+            let backend = RocksBackend::open(path_str).unwrap();
+            for triple in &triples {
+                backend.put(&triple.id(), triple).unwrap();
+            }
+            backend.flush().unwrap();
+        }
+
+        let options = RocksOptions::from(
+            StorageOptions::default()
+                .with_compression(Compression::Zstd)
+                .with_cache_bytes(8 << 20)
+                .with_write_buffer_bytes(4 << 20),
+        );
+        let backend = RocksBackend::open_with(path_str, options).unwrap();
+        let info = backend.info();
+        assert_eq!(info.backend, "rocksdb");
+        assert_eq!(info.compression, Some(Compression::Zstd));
+        assert_eq!(info.cache_bytes, Some(8 << 20));
+        assert_eq!(info.write_buffer_bytes, Some(4 << 20));
+
+        for triple in &triples {
+            let retrieved = backend.get(&triple.id()).unwrap().unwrap();
+            assert_eq!(retrieved.object, triple.object);
+        }
+        assert_eq!(backend.count(), triples.len());
+    }
+
+    #[test]
+    fn test_assertions_kept_apart_from_triples() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let path_str = path.to_str().unwrap();
+
+        let triple = Triple::literal("rocks:test", "property", "value");
+        let id = triple.id();
+        let second = TripleMeta::new().with_asserted_by(NodeId::named("agent:b"));
+        {
+            let backend = RocksBackend::open(path_str).unwrap();
+            backend.put(&id, &triple).unwrap();
+            backend
+                .put_assertions(&id, std::slice::from_ref(&second))
+                .unwrap();
+            backend.flush().unwrap();
+        }
+
+        let backend = RocksBackend::open(path_str).unwrap();
+        assert_eq!(backend.count(), 1);
+        assert_eq!(backend.iter_all().unwrap().len(), 1);
+        assert_eq!(
+            backend.iter_assertions().unwrap(),
+            vec![(id.clone(), vec![second])]
+        );
+
+        backend.delete(&id).unwrap();
+        assert!(backend.get_assertions(&id).unwrap().is_empty());
+    }
 }
@@ -6,8 +6,63 @@
 //! Provides persistent, transactional storage using the Sled embedded database.
 //! This is the default backend for production use.
 
-use super::StorageBackend;
-use crate::{Error, Result, Triple, TripleId};
+use std::borrow::Cow;
+
+use super::{
+    decode_assertions, encode_assertions, BackendInfo, Compression, StorageBackend, StorageOptions,
+};
+use crate::{Error, Result, Triple, TripleId, TripleMeta};
+
+/// Page cache size sled uses when none is configured (1 GiB)
+const DEFAULT_CACHE_BYTES: usize = 1024 * 1024 * 1024;
+
+/// Key of the shutdown marker in the default tree
+const MARKER_KEY: &[u8] = b"shutdown_marker";
+
+/// Key of the compression setting in the default tree
+const COMPRESSION_KEY: &[u8] = b"compression";
+
+/// Options for opening a Sled database
+///
+/// Sled has no separate write buffer, so `write_buffer_bytes` is ignored.
+/// Only zstd compression is supported, and it requires the
+/// `sled-compression` feature. Triples and their assertions are compressed
+/// value by value (sled's own compression links a zstd version that
+/// conflicts with RocksDB's). The setting is recorded on first open; leaving
+/// `compression` unset reopens with the recorded one, and asking for a
+/// different one fails with [`Error::Config`].
+///
+/// Sled has no native read-only mode: `read_only` rejects writes through
+/// this handle, but the database is still locked exclusively, so it cannot
+/// be opened while another process holds it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SledOptions {
+    /// Backend-agnostic settings
+    pub storage: StorageOptions,
+    /// Zstd level used when compression is enabled (1–22)
+    pub compression_factor: i32,
+    /// Interval between background flushes; `None` disables them
+    pub flush_every_ms: Option<u64>,
+}
+
+impl Default for SledOptions {
+    fn default() -> Self {
+        Self {
+            storage: StorageOptions::default(),
+            compression_factor: 5,
+            flush_every_ms: Some(500),
+        }
+    }
+}
+
+impl From<StorageOptions> for SledOptions {
+    fn from(storage: StorageOptions) -> Self {
+        Self {
+            storage,
+            ..Self::default()
+        }
+    }
+}
 
 /// Sled-based storage backend
 pub struct SledBackend {
@@ -15,19 +70,121 @@ pub struct SledBackend {
     db: sled::Db,
     /// Tree for triple storage
     triples: sled::Tree,
+    /// Tree for further assertions of stored triples
+    assertions: sled::Tree,
+    /// Tree for secondary index definitions; each index's keys have a tree
+    /// of their own
+    index_defs: sled::Tree,
+    /// Zstd level of stored triples and assertions; `None` if uncompressed
+    zstd_level: Option<i32>,
+    /// Settings the database was opened with
+    info: BackendInfo,
 }
 
 impl SledBackend {
     /// Open or create a Sled database at the given path
     pub fn open(path: &str) -> Result<Self> {
-        let db = sled::open(path)
+        Self::open_with(path, SledOptions::default())
+    }
+
+    /// Open a Sled database at the given path with explicit options
+    ///
+    /// A read-only open fails with [`Error::NotFound`] if the database
+    /// does not exist yet.
+    pub fn open_with(path: &str, options: SledOptions) -> Result<Self> {
+        let storage = &options.storage;
+        if storage.read_only && !std::path::Path::new(path).exists() {
+            return Err(Error::NotFound(format!("sled db at {}", path)));
+        }
+
+        if let Some(requested) = storage.compression {
+            check_compression(requested)?;
+        }
+        let cache_bytes = storage.cache_bytes.unwrap_or(DEFAULT_CACHE_BYTES);
+
+        let db = sled::Config::new()
+            .path(path)
+            .cache_capacity(cache_bytes as u64)
+            .flush_every_ms(options.flush_every_ms)
+            .open()
             .map_err(|e| Error::Storage(format!("failed to open sled db: {}", e)))?;
 
         let triples = db
             .open_tree("triples")
             .map_err(|e| Error::Storage(format!("failed to open triples tree: {}", e)))?;
+        let assertions = db
+            .open_tree("assertions")
+            .map_err(|e| Error::Storage(format!("failed to open assertions tree: {}", e)))?;
+        let index_defs = db
+            .open_tree("index_defs")
+            .map_err(|e| Error::Storage(format!("failed to open index_defs tree: {}", e)))?;
+
+        let compression = Self::settle_compression(&db, &triples, storage)?;
+        let info = BackendInfo {
+            path: Some(path.to_string()),
+            cache_bytes: Some(cache_bytes),
+            compression: Some(compression),
+            read_only: storage.read_only,
+            ..BackendInfo::new("sled")
+        };
+        Ok(Self {
+            db,
+            triples,
+            assertions,
+            index_defs,
+            zstd_level: (compression == Compression::Zstd).then_some(options.compression_factor),
+            info,
+        })
+    }
 
-        Ok(Self { db, triples })
+    /// The compression the database is stored with
+    ///
+    /// A database records its compression on first open. One written before
+    /// the setting was recorded holds uncompressed values.
+    fn settle_compression(
+        db: &sled::Db,
+        triples: &sled::Tree,
+        storage: &StorageOptions,
+    ) -> Result<Compression> {
+        let recorded = match db
+            .get(COMPRESSION_KEY)
+            .map_err(|e| Error::Storage(format!("sled get error: {}", e)))?
+        {
+            Some(name) => Some(match name.as_ref() {
+                b"none" => Compression::None,
+                b"zstd" => Compression::Zstd,
+                other => {
+                    return Err(Error::Config(format!(
+                        "sled db records unknown compression `{}`",
+                        String::from_utf8_lossy(other)
+                    )))
+                }
+            }),
+            None if !triples.is_empty() => Some(Compression::None),
+            None => None,
+        };
+
+        let compression = match (recorded, storage.compression) {
+            (Some(recorded), Some(requested)) if recorded != requested => {
+                return Err(Error::Config(format!(
+                    "sled db is stored with {:?} compression, not {:?}",
+                    recorded, requested
+                )))
+            }
+            (Some(recorded), _) => recorded,
+            (None, requested) => requested.unwrap_or(Compression::None),
+        };
+        check_compression(compression)?;
+
+        if recorded.is_none() && !storage.read_only {
+            let name: &[u8] = match compression {
+                Compression::Zstd => b"zstd",
+                _ => b"none",
+            };
+            db.insert(COMPRESSION_KEY, name)
+                .map_err(|e| Error::Storage(format!("sled insert error: {}", e)))?;
+        }
+        Ok(compression)
     }
 
     /// Returns a handle to the underlying Sled database (cheaply clonable;
@@ -46,14 +203,48 @@ impl SledBackend {
         let triples = db
             .open_tree("triples")
             .map_err(|e| Error::Storage(format!("failed to open triples tree: {}", e)))?;
+        let assertions = db
+            .open_tree("assertions")
+            .map_err(|e| Error::Storage(format!("failed to open assertions tree: {}", e)))?;
+        let index_defs = db
+            .open_tree("index_defs")
+            .map_err(|e| Error::Storage(format!("failed to open index_defs tree: {}", e)))?;
 
-        Ok(Self { db, triples })
+        let info = BackendInfo {
+            cache_bytes: Some(DEFAULT_CACHE_BYTES),
+            compression: Some(Compression::None),
+            ..BackendInfo::new("sled")
+        };
+        Ok(Self {
+            db,
+            triples,
+            assertions,
+            index_defs,
+            zstd_level: None,
+            info,
+        })
+    }
+
+    /// Open the tree holding the keys of a secondary index
+    fn index_tree(&self, name: &str) -> Result<sled::Tree> {
+        self.db
+            .open_tree(format!("index:{}", name))
+            .map_err(|e| Error::Storage(format!("failed to open index tree: {}", e)))
+    }
+
+    /// Fail if the database was opened read-only
+    fn check_writable(&self) -> Result<()> {
+        if self.info.read_only {
+            return Err(Error::ReadOnly("sled db opened read-only".into()));
+        }
+        Ok(())
     }
 }
 
 impl StorageBackend for SledBackend {
     fn put(&self, id: &TripleId, triple: &Triple) -> Result<()> {
-        let bytes = triple.to_bytes();
+        self.check_writable()?;
+        let bytes = compress(triple.to_bytes(), self.zstd_level)?;
         self.triples
             .insert(id.as_bytes(), bytes)
             .map_err(|e| Error::Storage(format!("sled insert error: {}", e)))?;
@@ -62,13 +253,17 @@ impl StorageBackend for SledBackend {
 
     fn get(&self, id: &TripleId) -> Result<Option<Triple>> {
         match self.triples.get(id.as_bytes()) {
-            Ok(Some(bytes)) => Ok(Triple::from_bytes(&bytes)),
+            Ok(Some(bytes)) => Ok(Triple::from_bytes(&decompress(&bytes, self.zstd_level)?)),
             Ok(None) => Ok(None),
             Err(e) => Err(Error::Storage(format!("sled get error: {}", e))),
         }
     }
 
     fn delete(&self, id: &TripleId) -> Result<bool> {
+        self.check_writable()?;
+        self.assertions
+            .remove(id.as_bytes())
+            .map_err(|e| Error::Storage(format!("sled delete error: {}", e)))?;
         match self.triples.remove(id.as_bytes()) {
             Ok(Some(_)) => Ok(true),
             Ok(None) => Ok(false),
@@ -81,7 +276,8 @@ impl StorageBackend for SledBackend {
         for result in self.triples.iter() {
             match result {
                 Ok((_, bytes)) => {
-                    if let Some(triple) = Triple::from_bytes(&bytes) {
+                    if let Some(triple) = Triple::from_bytes(&decompress(&bytes, self.zstd_level)?)
+                    {
                         triples.push(triple);
                     }
                 }
@@ -92,9 +288,10 @@ impl StorageBackend for SledBackend {
     }
 
     fn apply_batch(&self, items: &[(&TripleId, &Triple)]) -> Result<()> {
+        self.check_writable()?;
         let mut batch = ::sled::Batch::default();
         for (id, triple) in items {
-            let bytes = triple.to_bytes();
+            let bytes = compress(triple.to_bytes(), self.zstd_level)?;
             batch.insert(id.as_bytes().as_slice(), bytes);
         }
         self.triples
@@ -103,6 +300,126 @@ impl StorageBackend for SledBackend {
         Ok(())
     }
 
+    fn put_assertions(&self, id: &TripleId, assertions: &[TripleMeta]) -> Result<()> {
+        self.check_writable()?;
+        let result = if assertions.is_empty() {
+            self.assertions.remove(id.as_bytes()).map(|_| ())
+        } else {
+            let bytes = compress(encode_assertions(assertions), self.zstd_level)?;
+            self.assertions.insert(id.as_bytes(), bytes).map(|_| ())
+        };
+        result.map_err(|e| Error::Storage(format!("sled insert error: {}", e)))
+    }
+
+    fn get_assertions(&self, id: &TripleId) -> Result<Vec<TripleMeta>> {
+        match self.assertions.get(id.as_bytes()) {
+            Ok(Some(bytes)) => decode_assertions(&decompress(&bytes, self.zstd_level)?),
+            Ok(None) => Ok(Vec::new()),
+            Err(e) => Err(Error::Storage(format!("sled get error: {}", e))),
+        }
+    }
+
+    fn iter_assertions(&self) -> Result<Vec<(TripleId, Vec<TripleMeta>)>> {
+        let mut all = Vec::new();
+        for result in self.assertions.iter() {
+            let (key, bytes) =
+                result.map_err(|e| Error::Storage(format!("sled iteration error: {}", e)))?;
+            let Ok(id) = <[u8; 32]>::try_from(key.as_ref()) else {
+                continue;
+            };
+            all.push((
+                TripleId::new(id),
+                decode_assertions(&decompress(&bytes, self.zstd_level)?)?,
+            ));
+        }
+        Ok(all)
+    }
+
+    fn put_index_def(&self, name: &str, def: &[u8]) -> Result<()> {
+        self.check_writable()?;
+        self.index_defs
+            .insert(name.as_bytes(), def)
+            .map_err(|e| Error::Storage(format!("sled insert error: {}", e)))?;
+        Ok(())
+    }
+
+    fn iter_index_defs(&self) -> Result<Vec<(String, Vec<u8>)>> {
+        let mut defs = Vec::new();
+        for result in self.index_defs.iter() {
+            let (name, def) =
+                result.map_err(|e| Error::Storage(format!("sled iteration error: {}", e)))?;
+            defs.push((String::from_utf8_lossy(&name).into_owned(), def.to_vec()));
+        }
+        Ok(defs)
+    }
+
+    fn put_index_entries(&self, name: &str, keys: &[Vec<u8>]) -> Result<()> {
+        self.check_writable()?;
+        let mut batch = ::sled::Batch::default();
+        for key in keys {
+            batch.insert(key.as_slice(), ::sled::IVec::default());
+        }
+        self.index_tree(name)?
+            .apply_batch(batch)
+            .map_err(|e| Error::Storage(format!("sled batch insert error: {}", e)))
+    }
+
+    fn delete_index_entries(&self, name: &str, keys: &[Vec<u8>]) -> Result<()> {
+        self.check_writable()?;
+        let mut batch = ::sled::Batch::default();
+        for key in keys {
+            batch.remove(key.as_slice());
+        }
+        self.index_tree(name)?
+            .apply_batch(batch)
+            .map_err(|e| Error::Storage(format!("sled delete error: {}", e)))
+    }
+
+    fn scan_index(&self, name: &str, start: &[u8], end: Option<&[u8]>) -> Result<Vec<Vec<u8>>> {
+        let tree = self.index_tree(name)?;
+        let range = match end {
+            Some(end) => tree.range(start..end),
+            None => tree.range(start..),
+        };
+        let mut keys = Vec::new();
+        for result in range {
+            let (key, _) =
+                result.map_err(|e| Error::Storage(format!("sled iteration error: {}", e)))?;
+            keys.push(key.to_vec());
+        }
+        Ok(keys)
+    }
+
+    fn index_len(&self, name: &str) -> usize {
+        self.index_tree(name).map(|tree| tree.len()).unwrap_or(0)
+    }
+
+    fn drop_index(&self, name: &str) -> Result<()> {
+        self.check_writable()?;
+        self.index_defs
+            .remove(name.as_bytes())
+            .map_err(|e| Error::Storage(format!("sled delete error: {}", e)))?;
+        self.db
+            .drop_tree(format!("index:{}", name))
+            .map_err(|e| Error::Storage(format!("sled delete error: {}", e)))?;
+        Ok(())
+    }
+
+    fn put_marker(&self, marker: &[u8]) -> Result<()> {
+        self.check_writable()?;
+        self.db
+            .insert(MARKER_KEY, marker)
+            .map_err(|e| Error::Storage(format!("sled insert error: {}", e)))?;
+        Ok(())
+    }
+
+    fn get_marker(&self) -> Result<Option<Vec<u8>>> {
+        match self.db.get(MARKER_KEY) {
+            Ok(marker) => Ok(marker.map(|bytes| bytes.to_vec())),
+            Err(e) => Err(Error::Storage(format!("sled get error: {}", e))),
+        }
+    }
+
     fn count(&self) -> usize {
         self.triples.len()
     }
@@ -122,11 +439,52 @@ impl StorageBackend for SledBackend {
         self.flush()
     }
 
+    fn info(&self) -> BackendInfo {
+        self.info.clone()
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
 }
 
+fn check_compression(compression: Compression) -> Result<()> {
+    match compression {
+        Compression::None => Ok(()),
+        Compression::Zstd if cfg!(feature = "sled-compression") => Ok(()),
+        Compression::Zstd => Err(Error::BackendUnavailable(
+            "sled zstd compression requires the `sled-compression` feature".into(),
+        )),
+        Compression::Lz4 => Err(Error::Config("sled supports only zstd compression".into())),
+    }
+}
+
+#[cfg(feature = "sled-compression")]
+fn compress(value: Vec<u8>, zstd_level: Option<i32>) -> Result<Vec<u8>> {
+    match zstd_level {
+        Some(level) => Ok(zstd::bulk::compress(&value, level)?),
+        None => Ok(value),
+    }
+}
+
+#[cfg(not(feature = "sled-compression"))]
+fn compress(value: Vec<u8>, _zstd_level: Option<i32>) -> Result<Vec<u8>> {
+    Ok(value)
+}
+
+#[cfg(feature = "sled-compression")]
+fn decompress(stored: &[u8], zstd_level: Option<i32>) -> Result<Cow<'_, [u8]>> {
+    match zstd_level {
+        Some(_) => Ok(Cow::Owned(zstd::stream::decode_all(stored)?)),
+        None => Ok(Cow::Borrowed(stored)),
+    }
+}
+
+#[cfg(not(feature = "sled-compression"))]
+fn decompress(stored: &[u8], _zstd_level: Option<i32>) -> Result<Cow<'_, [u8]>> {
+    Ok(Cow::Borrowed(stored))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -183,4 +541,205 @@ mod tests {
             assert_eq!(retrieved.object.as_string(), Some("important"));
         }
     }
+
+    #[test]
+    fn test_assertions_persist() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let path_str = path.to_str().unwrap();
+
+        let triple = Triple::literal("persist:test", "data", "important");
+        let id = triple.id();
+        let second = TripleMeta::new()
+            .with_asserted_by(NodeId::named("agent:b"))
+            .with_confidence(0.9);
+
+        {
+            let backend = SledBackend::open(path_str).unwrap();
+            backend.put(&id, &triple).unwrap();
+            backend
+                .put_assertions(&id, std::slice::from_ref(&second))
+                .unwrap();
+            backend.flush().unwrap();
+        }
+
+        let backend = SledBackend::open(path_str).unwrap();
+        assert_eq!(backend.count(), 1);
+        assert_eq!(backend.get_assertions(&id).unwrap(), vec![second.clone()]);
+        assert_eq!(
+            backend.iter_assertions().unwrap(),
+            vec![(id.clone(), vec![second])]
+        );
+
+        backend.delete(&id).unwrap();
+        assert!(backend.iter_assertions().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_read_only_rejects_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let path_str = path.to_str().unwrap();
+
+        let kept = Triple::new(
+            NodeId::named("ro:kept"),
+            Predicate::named("data"),
+            Value::literal("before"),
+        );
+        {
+            let backend = SledBackend::open(path_str).unwrap();
+            backend.put(&kept.id(), &kept).unwrap();
+            backend.flush().unwrap();
+        }
+
+        let options = SledOptions::from(StorageOptions::default().read_only());
+        let backend = SledBackend::open_with(path_str, options).unwrap();
+        assert!(backend.info().read_only);
+        assert!(backend.get(&kept.id()).unwrap().is_some());
+
+        let added = Triple::new(
+            NodeId::named("ro:added"),
+            Predicate::named("data"),
+            Value::literal("after"),
+        );
+        assert!(matches!(
+            backend.put(&added.id(), &added),
+            Err(Error::ReadOnly(_))
+        ));
+        assert!(matches!(
+            backend.apply_batch(&[(&added.id(), &added)]),
+            Err(Error::ReadOnly(_))
+        ));
+        assert!(matches!(
+            backend.delete(&kept.id()),
+            Err(Error::ReadOnly(_))
+        ));
+        assert_eq!(backend.count(), 1);
+    }
+
+    #[test]
+    fn test_read_only_requires_existing_db() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("missing.db");
+
+        let options = SledOptions::from(StorageOptions::default().read_only());
+        let result = SledBackend::open_with(path.to_str().unwrap(), options);
+        assert!(matches!(result, Err(Error::NotFound(_))));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_open_with_reports_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let path_str = path.to_str().unwrap();
+
+        let options = SledOptions::from(StorageOptions::default().with_cache_bytes(16 << 20));
+        let backend = SledBackend::open_with(path_str, options).unwrap();
+        let info = backend.info();
+        assert_eq!(info.backend, "sled");
+        assert_eq!(info.path.as_deref(), Some(path_str));
+        assert_eq!(info.cache_bytes, Some(16 << 20));
+        assert_eq!(info.compression, Some(Compression::None));
+        assert_eq!(info.write_buffer_bytes, None);
+        assert!(!info.read_only);
+    }
+
+    #[test]
+    fn test_lz4_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+
+        let options =
+            SledOptions::from(StorageOptions::default().with_compression(Compression::Lz4));
+        let result = SledBackend::open_with(path.to_str().unwrap(), options);
+        assert!(matches!(result, Err(Error::Config(_))));
+        assert!(!path.exists());
+    }
+
+    #[cfg(not(feature = "sled-compression"))]
+    #[test]
+    fn test_zstd_requires_feature() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+
+        let options =
+            SledOptions::from(StorageOptions::default().with_compression(Compression::Zstd));
+        let result = SledBackend::open_with(path.to_str().unwrap(), options);
+        assert!(matches!(result, Err(Error::BackendUnavailable(_))));
+    }
+
+    #[test]
+    fn test_uncompressed_db_keeps_its_setting() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let path_str = path.to_str().unwrap();
+
+        let triple = Triple::literal("plain:test", "data", "uncompressed");
+        {
+            let backend = SledBackend::open(path_str).unwrap();
+            backend.put(&triple.id(), &triple).unwrap();
+            backend.flush().unwrap();
+        }
+
+        let options =
+            SledOptions::from(StorageOptions::default().with_compression(Compression::Zstd));
+        assert!(SledBackend::open_with(path_str, options).is_err());
+
+        let backend = SledBackend::open(path_str).unwrap();
+        assert_eq!(backend.info().compression, Some(Compression::None));
+        assert!(backend.get(&triple.id()).unwrap().is_some());
+    }
+
+    #[cfg(feature = "sled-compression")]
+    #[test]
+    fn test_reopen_with_compression() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let path_str = path.to_str().unwrap();
+        let options =
+            || SledOptions::from(StorageOptions::default().with_compression(Compression::Zstd));
+
+        let triple = Triple::new(
+            NodeId::named("zstd:test"),
+            Predicate::named("data"),
+            Value::literal("compressed ".repeat(64)),
+        );
+        let id = triple.id();
+        let meta = TripleMeta::new()
+            .with_asserted_by(NodeId::named("agent:b"))
+            .with_confidence(0.9);
+        {
+            let backend = SledBackend::open_with(path_str, options()).unwrap();
+            backend.put(&id, &triple).unwrap();
+            backend
+                .put_assertions(&id, std::slice::from_ref(&meta))
+                .unwrap();
+            backend.flush().unwrap();
+
+            // Values are stored compressed
+            let stored = backend.triples.get(id.as_bytes()).unwrap().unwrap();
+            assert!(stored.len() < triple.to_bytes().len());
+        }
+
+        let backend = SledBackend::open_with(path_str, options()).unwrap();
+        assert_eq!(backend.info().compression, Some(Compression::Zstd));
+        let retrieved = backend.get(&id).unwrap().unwrap();
+        assert_eq!(retrieved.object, triple.object);
+        assert_eq!(backend.iter_all().unwrap().len(), 1);
+        assert_eq!(backend.get_assertions(&id).unwrap(), vec![meta.clone()]);
+        assert_eq!(backend.iter_assertions().unwrap(), vec![(id, vec![meta])]);
+        drop(backend);
+
+        // Unset compression follows the recorded setting; a different one fails
+        let backend = SledBackend::open(path_str).unwrap();
+        assert_eq!(backend.info().compression, Some(Compression::Zstd));
+        drop(backend);
+        let uncompressed =
+            SledOptions::from(StorageOptions::default().with_compression(Compression::None));
+        assert!(matches!(
+            SledBackend::open_with(path_str, uncompressed),
+            Err(Error::Config(_))
+        ));
+    }
 }
@@ -6,8 +6,8 @@
 //! Provides portable, lightweight storage for IoT and embedded devices.
 //! SQLite is ideal for resource-constrained environments.
 
-use super::StorageBackend;
-use crate::{Error, Result, Triple, TripleId};
+use super::{decode_assertions, encode_assertions, BackendInfo, StorageBackend};
+use crate::{Error, Result, Triple, TripleId, TripleMeta};
 use rusqlite::{params, Connection};
 use std::sync::Mutex;
 
@@ -67,6 +67,61 @@ impl SqliteBackend {
         )
         .map_err(|e| Error::Storage(format!("failed to create index: {}", e)))?;
 
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS assertions (
+                id BLOB PRIMARY KEY,
+                data BLOB NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| Error::Storage(format!("failed to create table: {}", e)))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS index_defs (
+                name TEXT PRIMARY KEY,
+                data BLOB NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| Error::Storage(format!("failed to create table: {}", e)))?;
+
+        // BLOBs compare as bytes, so keys scan in the order other backends use
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS index_entries (
+                name TEXT NOT NULL,
+                key BLOB NOT NULL,
+                PRIMARY KEY (name, key)
+            ) WITHOUT ROWID",
+            [],
+        )
+        .map_err(|e| Error::Storage(format!("failed to create table: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Run a statement taking an index name and a key once per key, in one
+    /// transaction
+    fn write_index_entries(&self, sql: &str, name: &str, keys: &[Vec<u8>]) -> Result<()> {
+        let mut conn = self
+            .conn
+            .lock()
+            .map_err(|_| Error::Storage("lock poisoned".into()))?;
+
+        let tx = conn
+            .transaction()
+            .map_err(|e| Error::Storage(format!("sqlite transaction error: {}", e)))?;
+        {
+            let mut stmt = tx
+                .prepare(sql)
+                .map_err(|e| Error::Storage(format!("sqlite prepare error: {}", e)))?;
+            for key in keys {
+                stmt.execute(params![name, key])
+                    .map_err(|e| Error::Storage(format!("sqlite write error: {}", e)))?;
+            }
+        }
+        tx.commit()
+            .map_err(|e| Error::Storage(format!("sqlite commit error: {}", e)))?;
+
         Ok(())
     }
 }
@@ -120,6 +175,11 @@ impl StorageBackend for SqliteBackend {
                 params![id.as_bytes().as_slice()],
             )
             .map_err(|e| Error::Storage(format!("sqlite delete error: {}", e)))?;
+        conn.execute(
+            "DELETE FROM assertions WHERE id = ?1",
+            params![id.as_bytes().as_slice()],
+        )
+        .map_err(|e| Error::Storage(format!("sqlite delete error: {}", e)))?;
 
         Ok(changes > 0)
     }
@@ -153,6 +213,176 @@ impl StorageBackend for SqliteBackend {
         Ok(triples)
     }
 
+    fn put_assertions(&self, id: &TripleId, assertions: &[TripleMeta]) -> Result<()> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|_| Error::Storage("lock poisoned".into()))?;
+
+        let result = if assertions.is_empty() {
+            conn.execute(
+                "DELETE FROM assertions WHERE id = ?1",
+                params![id.as_bytes().as_slice()],
+            )
+        } else {
+            conn.execute(
+                "INSERT OR REPLACE INTO assertions (id, data) VALUES (?1, ?2)",
+                params![id.as_bytes().as_slice(), encode_assertions(assertions)],
+            )
+        };
+        result.map_err(|e| Error::Storage(format!("sqlite insert error: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn get_assertions(&self, id: &TripleId) -> Result<Vec<TripleMeta>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|_| Error::Storage("lock poisoned".into()))?;
+
+        let result: std::result::Result<Vec<u8>, _> = conn.query_row(
+            "SELECT data FROM assertions WHERE id = ?1",
+            params![id.as_bytes().as_slice()],
+            |row| row.get(0),
+        );
+
+        match result {
+            Ok(bytes) => decode_assertions(&bytes),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(Vec::new()),
+            Err(e) => Err(Error::Storage(format!("sqlite query error: {}", e))),
+        }
+    }
+
+    fn iter_assertions(&self) -> Result<Vec<(TripleId, Vec<TripleMeta>)>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|_| Error::Storage("lock poisoned".into()))?;
+
+        let mut stmt = conn
+            .prepare("SELECT id, data FROM assertions")
+            .map_err(|e| Error::Storage(format!("sqlite prepare error: {}", e)))?;
+
+        let rows = stmt
+            .query_map([], |row| {
+                let id: Vec<u8> = row.get(0)?;
+                let bytes: Vec<u8> = row.get(1)?;
+                Ok((id, bytes))
+            })
+            .map_err(|e| Error::Storage(format!("sqlite query error: {}", e)))?;
+
+        let mut all = Vec::new();
+        for row in rows {
+            let (id, bytes) =
+                row.map_err(|e| Error::Storage(format!("sqlite query error: {}", e)))?;
+            if let Ok(id) = <[u8; 32]>::try_from(id.as_slice()) {
+                all.push((TripleId::new(id), decode_assertions(&bytes)?));
+            }
+        }
+
+        Ok(all)
+    }
+
+    fn put_index_def(&self, name: &str, def: &[u8]) -> Result<()> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|_| Error::Storage("lock poisoned".into()))?;
+
+        conn.execute(
+            "INSERT OR REPLACE INTO index_defs (name, data) VALUES (?1, ?2)",
+            params![name, def],
+        )
+        .map_err(|e| Error::Storage(format!("sqlite insert error: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn iter_index_defs(&self) -> Result<Vec<(String, Vec<u8>)>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|_| Error::Storage("lock poisoned".into()))?;
+
+        let mut stmt = conn
+            .prepare("SELECT name, data FROM index_defs")
+            .map_err(|e| Error::Storage(format!("sqlite prepare error: {}", e)))?;
+
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| Error::Storage(format!("sqlite query error: {}", e)))?;
+
+        rows.map(|row| row.map_err(|e| Error::Storage(format!("sqlite query error: {}", e))))
+            .collect()
+    }
+
+    fn put_index_entries(&self, name: &str, keys: &[Vec<u8>]) -> Result<()> {
+        self.write_index_entries(
+            "INSERT OR IGNORE INTO index_entries (name, key) VALUES (?1, ?2)",
+            name,
+            keys,
+        )
+    }
+
+    fn delete_index_entries(&self, name: &str, keys: &[Vec<u8>]) -> Result<()> {
+        self.write_index_entries(
+            "DELETE FROM index_entries WHERE name = ?1 AND key = ?2",
+            name,
+            keys,
+        )
+    }
+
+    fn scan_index(&self, name: &str, start: &[u8], end: Option<&[u8]>) -> Result<Vec<Vec<u8>>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|_| Error::Storage("lock poisoned".into()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT key FROM index_entries
+                 WHERE name = ?1 AND key >= ?2 AND (?3 IS NULL OR key < ?3)
+                 ORDER BY key",
+            )
+            .map_err(|e| Error::Storage(format!("sqlite prepare error: {}", e)))?;
+
+        let rows = stmt
+            .query_map(params![name, start, end], |row| row.get(0))
+            .map_err(|e| Error::Storage(format!("sqlite query error: {}", e)))?;
+
+        rows.map(|row| row.map_err(|e| Error::Storage(format!("sqlite query error: {}", e))))
+            .collect()
+    }
+
+    fn index_len(&self, name: &str) -> usize {
+        let conn = match self.conn.lock() {
+            Ok(c) => c,
+            Err(_) => return 0,
+        };
+
+        conn.query_row(
+            "SELECT COUNT(*) FROM index_entries WHERE name = ?1",
+            params![name],
+            |row| row.get(0),
+        )
+        .unwrap_or(0)
+    }
+
+    fn drop_index(&self, name: &str) -> Result<()> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|_| Error::Storage("lock poisoned".into()))?;
+
+        conn.execute("DELETE FROM index_entries WHERE name = ?1", params![name])
+            .map_err(|e| Error::Storage(format!("sqlite delete error: {}", e)))?;
+        conn.execute("DELETE FROM index_defs WHERE name = ?1", params![name])
+            .map_err(|e| Error::Storage(format!("sqlite delete error: {}", e)))?;
+
+        Ok(())
+    }
+
     fn count(&self) -> usize {
         let conn = match self.conn.lock() {
             Ok(c) => c,
@@ -193,6 +423,10 @@ impl StorageBackend for SqliteBackend {
         Ok(())
     }
 
+    fn info(&self) -> BackendInfo {
+        BackendInfo::new("sqlite")
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -243,4 +477,26 @@ mod tests {
         let all = backend.iter_all().unwrap();
         assert_eq!(all.len(), 10);
     }
+
+    #[test]
+    fn test_sqlite_assertions() {
+        let backend = SqliteBackend::memory().unwrap();
+        let triple = Triple::literal("sqlite:test", "property", "value");
+        let id = triple.id();
+        let second = TripleMeta::new().with_asserted_by(NodeId::named("agent:b"));
+
+        backend.put(&id, &triple).unwrap();
+        backend
+            .put_assertions(&id, std::slice::from_ref(&second))
+            .unwrap();
+        assert_eq!(backend.count(), 1);
+        assert_eq!(backend.get_assertions(&id).unwrap(), vec![second.clone()]);
+        assert_eq!(
+            backend.iter_assertions().unwrap(),
+            vec![(id.clone(), vec![second])]
+        );
+
+        backend.delete(&id).unwrap();
+        assert!(backend.iter_assertions().unwrap().is_empty());
+    }
 }
@@ -0,0 +1,507 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Binary snapshot files for backup and replication seeding.
+//!
+//! [`GraphDB::export_snapshot`](crate::GraphDB::export_snapshot) writes every
+//! triple of a graph, together with all of its assertions, as a stream of
+//! blocks; [`GraphDB::import_snapshot`](crate::GraphDB::import_snapshot)
+//! loads one through the batch insert path. Only the public store API is
+//! used on either side, so a snapshot taken from one backend can be loaded
+//! into any other.
+//!
+//! # Format
+//!
+//! All integers are little-endian.
+//!
+//! ```text
+//! magic        8 bytes   "AGSNAP\r\n"
+//! header_len   u32
+//! header       bincode   version, compression, counts, content checksum
+//! block*       u32 stored_len, u32 triple_count, [u8; 32] blake3, payload
+//! ```
+//!
+//! A block's payload is a bincode list of triples with their further
+//! assertions, zstd-compressed if the header says so. Its blake3 hash is
+//! taken before compression. The content checksum is the blake3 hash of all
+//! block hashes in order, so it also catches missing or reordered blocks.
+
+use crate::backends::Compression;
+use crate::{Error, GraphDB, Result, Triple, TripleMeta, TriplePattern};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+
+/// Version written to new snapshots; older versions remain readable.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// Number of triples per block when none is configured.
+pub const SNAPSHOT_BLOCK_SIZE: usize = 10_000;
+
+const MAGIC: &[u8; 8] = b"AGSNAP\r\n";
+
+/// Largest header or block accepted on import, to reject garbage lengths
+/// before allocating for them.
+const MAX_SECTION_BYTES: u32 = 1 << 30;
+
+/// How a snapshot is written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotOptions {
+    /// Triples per block; a failed import resumes at a block boundary
+    pub block_size: usize,
+    /// Block compression: `None`, or `Zstd` with the `snapshot-zstd` feature
+    pub compression: Compression,
+    /// Zstd level used when compression is enabled (1–22)
+    pub zstd_level: i32,
+}
+
+impl Default for SnapshotOptions {
+    fn default() -> Self {
+        Self {
+            block_size: SNAPSHOT_BLOCK_SIZE,
+            compression: Compression::None,
+            zstd_level: 3,
+        }
+    }
+}
+
+impl SnapshotOptions {
+    /// Set the number of triples per block
+    pub fn with_block_size(mut self, block_size: usize) -> Self {
+        self.block_size = block_size;
+        self
+    }
+
+    /// Set the block compression
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+}
+
+/// What a snapshot holds, as recorded in its header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotInfo {
+    /// Format version
+    pub version: u32,
+    /// Block compression
+    pub compression: Compression,
+    /// Number of triples
+    pub triple_count: u64,
+    /// Number of assertions, counting the one stored with each triple
+    pub assertion_count: u64,
+    /// Number of blocks
+    pub block_count: u64,
+    /// Blake3 hash of the block hashes, in order
+    pub checksum: [u8; 32],
+}
+
+#[derive(Serialize, Deserialize)]
+struct Header {
+    version: u32,
+    compression: u8,
+    triple_count: u64,
+    assertion_count: u64,
+    block_count: u64,
+    checksum: [u8; 32],
+}
+
+impl Header {
+    fn info(&self) -> Result<SnapshotInfo> {
+        let compression = match self.compression {
+            0 => Compression::None,
+            1 => Compression::Zstd,
+            tag => {
+                return Err(Error::Unsupported(format!(
+                    "snapshot compression tag {}",
+                    tag
+                )))
+            }
+        };
+        Ok(SnapshotInfo {
+            version: self.version,
+            compression,
+            triple_count: self.triple_count,
+            assertion_count: self.assertion_count,
+            block_count: self.block_count,
+            checksum: self.checksum,
+        })
+    }
+}
+
+/// A triple and the assertions recorded after the one stored with it.
+#[derive(Serialize)]
+struct RecordRef<'a> {
+    triple: &'a Triple,
+    assertions: &'a [TripleMeta],
+}
+
+#[derive(Deserialize)]
+struct Record {
+    triple: Triple,
+    assertions: Vec<TripleMeta>,
+}
+
+/// Encodes one block of triples, returning the uncompressed payload and the
+/// number of assertions in it.
+fn encode_block(db: &GraphDB, triples: &[Triple]) -> Result<(Vec<u8>, u64)> {
+    let mut provenance = Vec::with_capacity(triples.len());
+    for triple in triples {
+        // Empty if deleted since it was listed; the triple's own meta remains
+        provenance.push(db.provenance(&triple.id())?);
+    }
+
+    let mut assertions = 0;
+    let records: Vec<RecordRef<'_>> = triples
+        .iter()
+        .zip(&provenance)
+        .map(|(triple, metas)| {
+            let further = metas.get(1..).unwrap_or_default();
+            assertions += 1 + further.len() as u64;
+            RecordRef {
+                triple,
+                assertions: further,
+            }
+        })
+        .collect();
+
+    let payload = bincode::serde::encode_to_vec(&records, bincode::config::standard())?;
+    Ok((payload, assertions))
+}
+
+fn check_compression(options: &SnapshotOptions) -> Result<u8> {
+    match options.compression {
+        Compression::None => Ok(0),
+        Compression::Zstd if cfg!(feature = "snapshot-zstd") => Ok(1),
+        Compression::Zstd => Err(Error::BackendUnavailable(
+            "zstd snapshots require the `snapshot-zstd` feature".into(),
+        )),
+        Compression::Lz4 => Err(Error::Config(
+            "snapshots support only zstd compression".into(),
+        )),
+    }
+}
+
+#[cfg(feature = "snapshot-zstd")]
+fn compress(payload: Vec<u8>, compression: Compression, level: i32) -> Result<Vec<u8>> {
+    match compression {
+        Compression::Zstd => Ok(zstd::bulk::compress(&payload, level)?),
+        _ => Ok(payload),
+    }
+}
+
+#[cfg(not(feature = "snapshot-zstd"))]
+fn compress(payload: Vec<u8>, _compression: Compression, _level: i32) -> Result<Vec<u8>> {
+    Ok(payload)
+}
+
+#[cfg(feature = "snapshot-zstd")]
+fn decompress(stored: Vec<u8>, compression: Compression) -> Result<Vec<u8>> {
+    match compression {
+        Compression::Zstd => Ok(zstd::stream::decode_all(stored.as_slice())?),
+        _ => Ok(stored),
+    }
+}
+
+#[cfg(not(feature = "snapshot-zstd"))]
+fn decompress(stored: Vec<u8>, compression: Compression) -> Result<Vec<u8>> {
+    match compression {
+        Compression::None => Ok(stored),
+        _ => Err(Error::BackendUnavailable(
+            "zstd snapshots require the `snapshot-zstd` feature".into(),
+        )),
+    }
+}
+
+/// Writes a snapshot of `db`; see [`GraphDB::export_snapshot_with`].
+pub(crate) fn export<W: Write>(
+    db: &GraphDB,
+    mut writer: W,
+    options: &SnapshotOptions,
+) -> Result<SnapshotInfo> {
+    if options.block_size == 0 {
+        return Err(Error::Config("snapshot block size must be positive".into()));
+    }
+    let compression = check_compression(options)?;
+
+    let triples = db.find(TriplePattern::any())?;
+    let blocks: Vec<&[Triple]> = triples.chunks(options.block_size).collect();
+
+    // The header comes first, so the blocks are encoded once to checksum
+    // them and again to write them
+    let mut hashes = Vec::with_capacity(blocks.len());
+    let mut assertion_count = 0;
+    for block in &blocks {
+        let (payload, assertions) = encode_block(db, block)?;
+        hashes.push(*blake3::hash(&payload).as_bytes());
+        assertion_count += assertions;
+    }
+    let mut content = blake3::Hasher::new();
+    for hash in &hashes {
+        content.update(hash);
+    }
+
+    let header = Header {
+        version: SNAPSHOT_VERSION,
+        compression,
+        triple_count: triples.len() as u64,
+        assertion_count,
+        block_count: blocks.len() as u64,
+        checksum: *content.finalize().as_bytes(),
+    };
+    let header_bytes = bincode::serde::encode_to_vec(&header, bincode::config::standard())?;
+    writer.write_all(MAGIC)?;
+    writer.write_all(&(header_bytes.len() as u32).to_le_bytes())?;
+    writer.write_all(&header_bytes)?;
+
+    for (i, block) in blocks.iter().enumerate() {
+        let (payload, _) = encode_block(db, block)?;
+        if blake3::hash(&payload).as_bytes() != &hashes[i] {
+            return Err(Error::Integrity(format!(
+                "snapshot block {} changed during export",
+                i
+            )));
+        }
+        let stored = compress(payload, options.compression, options.zstd_level)?;
+        writer.write_all(&(stored.len() as u32).to_le_bytes())?;
+        writer.write_all(&(block.len() as u32).to_le_bytes())?;
+        writer.write_all(&hashes[i])?;
+        writer.write_all(&stored)?;
+    }
+    writer.flush()?;
+
+    header.info()
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> std::io::Result<u32> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_section<R: Read>(reader: &mut R, len: u32) -> Result<Vec<u8>> {
+    if len > MAX_SECTION_BYTES {
+        return Err(Error::Serialization(format!(
+            "snapshot section of {} bytes",
+            len
+        )));
+    }
+    let mut bytes = vec![0u8; len as usize];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Reads the magic and header of a snapshot.
+fn read_header<R: Read>(reader: &mut R) -> Result<SnapshotInfo> {
+    let mut magic = [0u8; 8];
+    reader.read_exact(&mut magic)?;
+    if &magic != MAGIC {
+        return Err(Error::Unsupported("not an aingle_graph snapshot".into()));
+    }
+
+    let len = read_u32(reader)?;
+    let bytes = read_section(reader, len)?;
+    let (header, _): (Header, _) =
+        bincode::serde::decode_from_slice(&bytes, bincode::config::standard())?;
+    if header.version > SNAPSHOT_VERSION {
+        return Err(Error::Unsupported(format!(
+            "snapshot version {} is newer than {}",
+            header.version, SNAPSHOT_VERSION
+        )));
+    }
+    header.info()
+}
+
+/// Reads a snapshot's header without loading it.
+pub fn read_snapshot_info<R: Read>(mut reader: R) -> Result<SnapshotInfo> {
+    read_header(&mut reader)
+}
+
+/// Reads, checks and loads one block, returning its block hash.
+fn import_block<R: Read>(
+    db: &GraphDB,
+    reader: &mut R,
+    info: &SnapshotInfo,
+    load: bool,
+) -> Result<[u8; 32]> {
+    let stored_len = read_u32(reader)?;
+    let triple_count = read_u32(reader)?;
+    let mut hash = [0u8; 32];
+    reader.read_exact(&mut hash)?;
+    let stored = read_section(reader, stored_len)?;
+    if !load {
+        return Ok(hash);
+    }
+
+    let payload = decompress(stored, info.compression)?;
+    if blake3::hash(&payload).as_bytes() != &hash {
+        return Err(Error::Integrity("block checksum mismatch".into()));
+    }
+    let (records, _): (Vec<Record>, _) =
+        bincode::serde::decode_from_slice(&payload, bincode::config::standard())?;
+    if records.len() != triple_count as usize {
+        return Err(Error::Integrity(format!(
+            "block holds {} triples, expected {}",
+            records.len(),
+            triple_count
+        )));
+    }
+
+    let mut items = Vec::with_capacity(records.len());
+    for Record { triple, assertions } in records {
+        if db.get(&triple.id())?.is_some() {
+            continue;
+        }
+        items.push((triple.clone(), triple.meta.clone()));
+        items.extend(assertions.into_iter().map(|meta| (triple.clone(), meta)));
+    }
+    db.insert_batch_with_meta(items)?;
+    Ok(hash)
+}
+
+/// Loads a snapshot into `db`; see [`GraphDB::import_snapshot_from`].
+pub(crate) fn import<R: Read>(
+    db: &GraphDB,
+    mut reader: R,
+    first_block: u64,
+) -> Result<SnapshotInfo> {
+    let info = read_header(&mut reader)?;
+
+    let mut content = blake3::Hasher::new();
+    for block in 0..info.block_count {
+        let hash = import_block(db, &mut reader, &info, block >= first_block).map_err(|e| {
+            Error::SnapshotBlock {
+                block,
+                reason: e.to_string(),
+            }
+        })?;
+        content.update(&hash);
+    }
+
+    if content.finalize().as_bytes() != &info.checksum {
+        return Err(Error::Integrity(
+            "snapshot content checksum mismatch".into(),
+        ));
+    }
+    Ok(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NodeId;
+
+    fn populated(n: usize) -> GraphDB {
+        let db = GraphDB::memory().unwrap();
+        for i in 0..n {
+            db.insert(Triple::literal(
+                format!("user:{}", i),
+                "name",
+                format!("User {}", i),
+            ))
+            .unwrap();
+        }
+        db
+    }
+
+    fn assert_same(a: &GraphDB, b: &GraphDB) {
+        assert_eq!(a.count(), b.count());
+        for triple in a.find(TriplePattern::any()).unwrap().iter().step_by(7) {
+            let id = triple.id();
+            assert_eq!(b.get(&id).unwrap().as_ref(), Some(triple));
+            assert_eq!(b.provenance(&id).unwrap(), a.provenance(&id).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let source = populated(250);
+        let extra = TripleMeta::new().with_asserted_by(NodeId::named("agent:b"));
+        source
+            .insert_with_meta(Triple::literal("user:3", "name", "User 3"), extra)
+            .unwrap();
+
+        let mut bytes = Vec::new();
+        let options = SnapshotOptions::default().with_block_size(40);
+        let info = source.export_snapshot_with(&mut bytes, &options).unwrap();
+        assert_eq!(info.version, SNAPSHOT_VERSION);
+        assert_eq!(info.triple_count, 250);
+        assert_eq!(info.assertion_count, 251);
+        assert_eq!(info.block_count, 7);
+        assert_eq!(read_snapshot_info(bytes.as_slice()).unwrap(), info);
+
+        let target = GraphDB::memory().unwrap();
+        assert_eq!(target.import_snapshot(bytes.as_slice()).unwrap(), info);
+        assert_same(&source, &target);
+
+        // Loading it again changes nothing
+        target.import_snapshot(bytes.as_slice()).unwrap();
+        assert_same(&source, &target);
+    }
+
+    #[test]
+    fn test_empty_graph() {
+        let mut bytes = Vec::new();
+        let info = populated(0).export_snapshot(&mut bytes).unwrap();
+        assert_eq!((info.triple_count, info.block_count), (0, 0));
+
+        let target = GraphDB::memory().unwrap();
+        target.import_snapshot(bytes.as_slice()).unwrap();
+        assert_eq!(target.count(), 0);
+    }
+
+    #[test]
+    fn test_corrupted_block_is_detected_and_resumable() {
+        let source = populated(100);
+        let mut bytes = Vec::new();
+        let options = SnapshotOptions::default().with_block_size(30);
+        source.export_snapshot_with(&mut bytes, &options).unwrap();
+
+        // The last byte belongs to the payload of block 3
+        let mut corrupted = bytes.clone();
+        *corrupted.last_mut().unwrap() ^= 0xff;
+
+        let target = GraphDB::memory().unwrap();
+        match target.import_snapshot(corrupted.as_slice()) {
+            Err(Error::SnapshotBlock { block, .. }) => assert_eq!(block, 3),
+            other => panic!("expected a block error, got {:?}", other),
+        }
+        assert_eq!(target.count(), 90);
+
+        target.import_snapshot_from(bytes.as_slice(), 3).unwrap();
+        assert_same(&source, &target);
+    }
+
+    #[test]
+    fn test_rejects_other_files() {
+        let target = GraphDB::memory().unwrap();
+        assert!(matches!(
+            target.import_snapshot(&b"not a snapshot at all"[..]),
+            Err(Error::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn test_lz4_is_rejected() {
+        let options = SnapshotOptions::default().with_compression(Compression::Lz4);
+        let result = populated(1).export_snapshot_with(Vec::new(), &options);
+        assert!(matches!(result, Err(Error::Config(_))));
+    }
+
+    #[cfg(feature = "snapshot-zstd")]
+    #[test]
+    fn test_zstd_round_trip() {
+        let source = populated(500);
+        let mut plain = Vec::new();
+        source.export_snapshot(&mut plain).unwrap();
+
+        let mut packed = Vec::new();
+        let options = SnapshotOptions::default().with_compression(Compression::Zstd);
+        let info = source.export_snapshot_with(&mut packed, &options).unwrap();
+        assert_eq!(info.compression, Compression::Zstd);
+        assert!(packed.len() < plain.len());
+
+        let target = GraphDB::memory().unwrap();
+        target.import_snapshot(packed.as_slice()).unwrap();
+        assert_same(&source, &target);
+    }
+}
@@ -0,0 +1,263 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Read cache for subject lookups
+//!
+//! Looking up every triple of a subject, as
+//! [`GraphDB::get_subject`](crate::GraphDB::get_subject) does, can be served
+//! from a least-recently-used cache of the triples of recently read
+//! subjects. The cache is off by default; enable it with
+//! [`StorageOptions::with_subject_cache`](crate::StorageOptions::with_subject_cache).
+//!
+//! Entries are filled while the store's index read lock is held, and a write
+//! invalidates the subjects it touches while it holds the index write lock,
+//! once its triples are in the backend and the indexes. An entry therefore
+//! never holds triples a reader could not see, and never outlives a
+//! committed write to its subject. Triples stored but not yet indexed, as in
+//! the middle of a batch, leave the cache alone.
+//!
+//! Only patterns binding the subject alone are cached; reads through a
+//! [`GraphSnapshot`](crate::GraphSnapshot) or a query always go to the
+//! indexes.
+
+use crate::{NodeId, Triple};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+/// Limits of the subject read cache
+///
+/// The cache is disabled while `max_entries` is zero, as by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SubjectCacheOptions {
+    /// Maximum number of cached subjects
+    pub max_entries: usize,
+    /// Maximum encoded size of the cached triples, in bytes
+    pub max_bytes: Option<usize>,
+}
+
+impl SubjectCacheOptions {
+    /// Returns `true` if the cache is enabled
+    pub fn is_enabled(&self) -> bool {
+        self.max_entries > 0
+    }
+}
+
+/// Counters of the subject read cache, reported in
+/// [`GraphStats`](crate::GraphStats)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SubjectCacheStats {
+    /// Lookups answered from the cache
+    pub hits: u64,
+    /// Lookups that read the indexes and the backend
+    pub misses: u64,
+    /// Number of cached subjects
+    pub entries: usize,
+    /// Encoded size of the cached triples, in bytes
+    pub bytes: usize,
+}
+
+/// An LRU of the triples of recently read subjects
+pub(crate) struct SubjectCache {
+    options: SubjectCacheOptions,
+    lru: Mutex<Lru>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+#[derive(Default)]
+struct Lru {
+    entries: HashMap<NodeId, Entry>,
+    /// Subjects by the tick of their last use, least recent first
+    recency: BTreeMap<u64, NodeId>,
+    tick: u64,
+    bytes: usize,
+}
+
+struct Entry {
+    triples: Vec<Triple>,
+    bytes: usize,
+    used: u64,
+}
+
+impl SubjectCache {
+    /// Creates a cache with the given limits, or `None` if they disable it
+    pub(crate) fn new(options: SubjectCacheOptions) -> Option<Self> {
+        options.is_enabled().then(|| Self {
+            options,
+            lru: Mutex::new(Lru::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Lru> {
+        self.lru.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Returns the cached triples of a subject, counting a hit or a miss
+    pub(crate) fn get(&self, subject: &NodeId) -> Option<Vec<Triple>> {
+        let mut lru = self.lock();
+        let tick = lru.next_tick();
+        let Lru {
+            entries, recency, ..
+        } = &mut *lru;
+        match entries.get_mut(subject) {
+            Some(entry) => {
+                if let Some(subject) = recency.remove(&entry.used) {
+                    recency.insert(tick, subject);
+                }
+                entry.used = tick;
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(entry.triples.clone())
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// Caches the triples of a subject, evicting the least recently used
+    /// subjects to stay within the limits
+    ///
+    /// Call while holding the index read lock the triples were read under.
+    /// Triples larger than the whole byte budget are not cached.
+    pub(crate) fn insert(&self, subject: NodeId, triples: &[Triple]) {
+        let bytes = triples.iter().map(|t| t.to_bytes().len()).sum();
+        if self.options.max_bytes.is_some_and(|max| bytes > max) {
+            return;
+        }
+
+        let mut lru = self.lock();
+        lru.remove(&subject);
+        let used = lru.next_tick();
+        lru.recency.insert(used, subject.clone());
+        lru.entries.insert(
+            subject,
+            Entry {
+                triples: triples.to_vec(),
+                bytes,
+                used,
+            },
+        );
+        lru.bytes += bytes;
+
+        while lru.entries.len() > self.options.max_entries
+            || self.options.max_bytes.is_some_and(|max| lru.bytes > max)
+        {
+            let Some((_, oldest)) = lru.recency.pop_first() else {
+                break;
+            };
+            if let Some(entry) = lru.entries.remove(&oldest) {
+                lru.bytes -= entry.bytes;
+            }
+        }
+    }
+
+    /// Drops the entries of the given subjects
+    ///
+    /// Call while holding the index write lock of the write that changed them.
+    pub(crate) fn invalidate<'a>(&self, subjects: impl IntoIterator<Item = &'a NodeId>) {
+        let mut lru = self.lock();
+        for subject in subjects {
+            lru.remove(subject);
+        }
+    }
+
+    /// Drops every entry
+    pub(crate) fn clear(&self) {
+        *self.lock() = Lru::default();
+    }
+
+    /// Returns the counters and the current size of the cache
+    pub(crate) fn stats(&self) -> SubjectCacheStats {
+        let lru = self.lock();
+        SubjectCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: lru.entries.len(),
+            bytes: lru.bytes,
+        }
+    }
+}
+
+impl Lru {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn remove(&mut self, subject: &NodeId) {
+        if let Some(entry) = self.entries.remove(subject) {
+            self.recency.remove(&entry.used);
+            self.bytes -= entry.bytes;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Fixed timestamps, so triples compare equal and encode to the same size
+    fn triples(subject: &str, n: usize) -> Vec<Triple> {
+        (0..n)
+            .map(|i| {
+                let mut triple = Triple::literal(subject, "p", i.to_string());
+                triple.meta.created_at = chrono::DateTime::UNIX_EPOCH;
+                triple.meta.asserted_at = chrono::DateTime::UNIX_EPOCH;
+                triple
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let cache = SubjectCache::new(SubjectCacheOptions {
+            max_entries: 2,
+            max_bytes: None,
+        })
+        .unwrap();
+        let (a, b, c) = (NodeId::named("a"), NodeId::named("b"), NodeId::named("c"));
+
+        cache.insert(a.clone(), &triples("a", 1));
+        cache.insert(b.clone(), &triples("b", 1));
+        // Using `a` leaves `b` the least recently used
+        assert!(cache.get(&a).is_some());
+        cache.insert(c.clone(), &triples("c", 1));
+
+        assert!(cache.get(&b).is_none());
+        assert_eq!(cache.get(&a), Some(triples("a", 1)));
+        assert_eq!(cache.get(&c), Some(triples("c", 1)));
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (3, 1, 2));
+    }
+
+    #[test]
+    fn test_byte_budget() {
+        let one: usize = triples("a", 1).iter().map(|t| t.to_bytes().len()).sum();
+        let cache = SubjectCache::new(SubjectCacheOptions {
+            max_entries: 10,
+            max_bytes: Some(one * 2),
+        })
+        .unwrap();
+        assert!(SubjectCache::new(SubjectCacheOptions::default()).is_none());
+
+        // Larger than the whole budget
+        cache.insert(NodeId::named("a"), &triples("a", 3));
+        assert_eq!(cache.stats().entries, 0);
+
+        cache.insert(NodeId::named("a"), &triples("a", 1));
+        cache.insert(NodeId::named("b"), &triples("b", 1));
+        cache.insert(NodeId::named("c"), &triples("c", 1));
+        let stats = cache.stats();
+        assert_eq!(stats.entries, 2);
+        assert!(stats.bytes <= one * 2);
+        assert!(cache.get(&NodeId::named("a")).is_none());
+
+        cache.invalidate([&NodeId::named("b")]);
+        cache.clear();
+        assert_eq!(cache.stats().bytes, 0);
+    }
+}
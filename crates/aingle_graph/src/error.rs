@@ -42,6 +42,26 @@ pub enum Error {
 
     /// A required storage backend feature is not enabled.
     BackendUnavailable(String),
+
+    /// A write was attempted on a database opened read-only.
+    ReadOnly(String),
+
+    /// The input uses a feature this crate does not implement.
+    Unsupported(String),
+
+    /// The store failed an integrity check.
+    Integrity(String),
+
+    /// A triple breaks the enforced schema, or a stored schema is malformed.
+    Schema(String),
+
+    /// A snapshot block could not be loaded; the blocks before it were.
+    SnapshotBlock {
+        /// Index of the failed block, to resume the import from
+        block: u64,
+        /// What went wrong
+        reason: String,
+    },
 }
 
 impl fmt::Display for Error {
@@ -57,6 +77,13 @@ impl fmt::Display for Error {
             Self::Io(err) => write!(f, "I/O error: {}", err),
             Self::Config(msg) => write!(f, "config error: {}", msg),
             Self::BackendUnavailable(msg) => write!(f, "backend unavailable: {}", msg),
+            Self::ReadOnly(msg) => write!(f, "read-only: {}", msg),
+            Self::Unsupported(msg) => write!(f, "unsupported: {}", msg),
+            Self::Integrity(msg) => write!(f, "integrity check failed: {}", msg),
+            Self::Schema(msg) => write!(f, "schema violation: {}", msg),
+            Self::SnapshotBlock { block, reason } => {
+                write!(f, "snapshot block {}: {}", block, reason)
+            }
         }
     }
 }
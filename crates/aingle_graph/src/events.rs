@@ -0,0 +1,406 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Change feed for in-process subscribers
+//!
+//! Every committed insert and delete is published as a [`GraphEvent`] to the
+//! receivers returned by [`GraphDB::subscribe`](crate::GraphDB::subscribe).
+//!
+//! Events are staged while the store's index lock is held, so they are queued
+//! in commit order. They are handed to receivers only after that lock is
+//! released: a subscriber that queries the graph while a writer waits on its
+//! full channel cannot deadlock the writer. Delivery is done by one writer at
+//! a time; other writers leave their events queued for it and return.
+
+use crate::{Triple, TripleId};
+use std::collections::VecDeque;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, TryLockError};
+use std::time::{Duration, Instant};
+
+/// Default number of events buffered per subscriber
+pub const DEFAULT_EVENT_CAPACITY: usize = 1024;
+
+/// A committed change to the graph
+#[derive(Debug, Clone, PartialEq)]
+pub enum GraphEvent {
+    /// A triple was inserted
+    Inserted(TripleId, Triple),
+    /// A triple was deleted
+    Deleted(TripleId, Triple),
+}
+
+impl GraphEvent {
+    /// The ID of the changed triple
+    pub fn id(&self) -> &TripleId {
+        match self {
+            Self::Inserted(id, _) | Self::Deleted(id, _) => id,
+        }
+    }
+
+    /// The changed triple
+    pub fn triple(&self) -> &Triple {
+        match self {
+            Self::Inserted(_, triple) | Self::Deleted(_, triple) => triple,
+        }
+    }
+}
+
+/// What to do when a subscriber's channel is full
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Discard the oldest queued event to make room; see [`Receiver::dropped`]
+    #[default]
+    DropOldest,
+    /// Make the delivering writer wait until the subscriber catches up
+    ///
+    /// The wait happens outside the graph's locks, so the subscriber may read
+    /// the graph, and may write to it as long as its own channel is not full.
+    Block,
+}
+
+struct Queue<T> {
+    items: VecDeque<T>,
+    dropped: u64,
+    /// The graph was dropped: no more events will arrive
+    closed: bool,
+    /// The receiver was dropped: events are discarded
+    detached: bool,
+}
+
+struct Channel<T> {
+    queue: Mutex<Queue<T>>,
+    readable: Condvar,
+    writable: Condvar,
+    capacity: usize,
+    policy: OverflowPolicy,
+}
+
+impl<T> Channel<T> {
+    fn new(capacity: usize, policy: OverflowPolicy) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            queue: Mutex::new(Queue {
+                items: VecDeque::with_capacity(capacity.min(DEFAULT_EVENT_CAPACITY)),
+                dropped: 0,
+                closed: false,
+                detached: false,
+            }),
+            readable: Condvar::new(),
+            writable: Condvar::new(),
+            capacity,
+            policy,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Queue<T>> {
+        self.queue.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Queue an item, applying the overflow policy. Returns `false` once the
+    /// receiver is gone.
+    fn send(&self, item: T) -> bool {
+        let mut queue = self.lock();
+        loop {
+            if queue.detached {
+                return false;
+            }
+            if queue.items.len() < self.capacity {
+                break;
+            }
+            match self.policy {
+                OverflowPolicy::DropOldest => {
+                    queue.items.pop_front();
+                    queue.dropped += 1;
+                    break;
+                }
+                OverflowPolicy::Block => {
+                    queue = self.writable.wait(queue).unwrap_or_else(|e| e.into_inner());
+                }
+            }
+        }
+        queue.items.push_back(item);
+        self.readable.notify_one();
+        true
+    }
+
+    fn is_detached(&self) -> bool {
+        self.lock().detached
+    }
+
+    fn close(&self) {
+        self.lock().closed = true;
+        self.readable.notify_all();
+    }
+}
+
+/// Receiving end of a graph subscription
+///
+/// Dropping the receiver unsubscribes it.
+pub struct Receiver<T> {
+    channel: Arc<Channel<T>>,
+}
+
+impl<T> Receiver<T> {
+    /// Wait for the next event
+    ///
+    /// Returns `None` once the graph has been dropped and every queued event
+    /// has been received.
+    pub fn recv(&self) -> Option<T> {
+        let mut queue = self.channel.lock();
+        loop {
+            if let Some(item) = self.take(&mut queue) {
+                return Some(item);
+            }
+            if queue.closed {
+                return None;
+            }
+            queue = self
+                .channel
+                .readable
+                .wait(queue)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+    }
+
+    /// Wait up to `timeout` for the next event
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<T> {
+        let deadline = Instant::now() + timeout;
+        let mut queue = self.channel.lock();
+        loop {
+            if let Some(item) = self.take(&mut queue) {
+                return Some(item);
+            }
+            let now = Instant::now();
+            if queue.closed || now >= deadline {
+                return None;
+            }
+            queue = self
+                .channel
+                .readable
+                .wait_timeout(queue, deadline - now)
+                .unwrap_or_else(|e| e.into_inner())
+                .0;
+        }
+    }
+
+    /// Take the next event if one is queued
+    pub fn try_recv(&self) -> Option<T> {
+        let mut queue = self.channel.lock();
+        self.take(&mut queue)
+    }
+
+    /// Number of events waiting to be received
+    pub fn len(&self) -> usize {
+        self.channel.lock().items.len()
+    }
+
+    /// Returns `true` if no events are waiting
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` once the graph has been dropped and every queued event
+    /// has been received
+    pub fn is_closed(&self) -> bool {
+        let queue = self.channel.lock();
+        queue.closed && queue.items.is_empty()
+    }
+
+    /// Number of events discarded under [`OverflowPolicy::DropOldest`]
+    pub fn dropped(&self) -> u64 {
+        self.channel.lock().dropped
+    }
+
+    /// Maximum number of queued events
+    pub fn capacity(&self) -> usize {
+        self.channel.capacity
+    }
+
+    /// Overflow policy of this subscription
+    pub fn policy(&self) -> OverflowPolicy {
+        self.channel.policy
+    }
+
+    fn take(&self, queue: &mut Queue<T>) -> Option<T> {
+        let item = queue.items.pop_front()?;
+        self.channel.writable.notify_one();
+        Some(item)
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut queue = self.channel.lock();
+        queue.detached = true;
+        queue.items.clear();
+        self.channel.writable.notify_all();
+    }
+}
+
+/// Fan-out of committed changes to subscribers
+#[derive(Default)]
+pub(crate) struct EventBus {
+    subscribers: Mutex<Vec<Arc<Channel<GraphEvent>>>>,
+    /// Events staged in commit order, not yet delivered
+    pending: Mutex<VecDeque<GraphEvent>>,
+    /// Held by the writer currently delivering
+    delivering: Mutex<()>,
+}
+
+impl EventBus {
+    /// Register a new subscriber
+    pub(crate) fn subscribe(
+        &self,
+        capacity: usize,
+        policy: OverflowPolicy,
+    ) -> Receiver<GraphEvent> {
+        let channel = Arc::new(Channel::new(capacity, policy));
+        let mut subscribers = lock(&self.subscribers);
+        subscribers.retain(|c| !c.is_detached());
+        subscribers.push(channel.clone());
+        Receiver { channel }
+    }
+
+    /// Returns `true` if events should be staged
+    pub(crate) fn is_active(&self) -> bool {
+        !lock(&self.subscribers).is_empty()
+    }
+
+    /// Stage events for delivery. Call while holding the lock that orders
+    /// commits, so that staging order is commit order.
+    pub(crate) fn stage(&self, events: impl IntoIterator<Item = GraphEvent>) {
+        lock(&self.pending).extend(events);
+    }
+
+    /// Deliver staged events. Call after releasing the commit lock.
+    pub(crate) fn deliver(&self) {
+        loop {
+            if lock(&self.pending).is_empty() {
+                return;
+            }
+            let _guard = match self.delivering.try_lock() {
+                Ok(guard) => guard,
+                Err(TryLockError::Poisoned(e)) => e.into_inner(),
+                // The current deliverer picks up our events before it stops
+                Err(TryLockError::WouldBlock) => return,
+            };
+            loop {
+                let batch: Vec<GraphEvent> = lock(&self.pending).drain(..).collect();
+                if batch.is_empty() {
+                    break;
+                }
+                let mut subscribers = lock(&self.subscribers).clone();
+                for event in batch {
+                    subscribers.retain(|channel| channel.send(event.clone()));
+                }
+                lock(&self.subscribers).retain(|c| !c.is_detached());
+            }
+            // Events staged after the drain but before the guard is released
+            // were left to us; loop to deliver them.
+        }
+    }
+}
+
+impl Drop for EventBus {
+    fn drop(&mut self) {
+        for channel in lock(&self.subscribers).iter() {
+            channel.close();
+        }
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NodeId, Predicate, Value};
+    use std::thread;
+
+    fn event(n: i64) -> GraphEvent {
+        let triple = Triple::new(
+            NodeId::named(format!("event:{}", n)),
+            Predicate::named("seq"),
+            Value::Integer(n),
+        );
+        GraphEvent::Inserted(triple.id(), triple)
+    }
+
+    fn seq(event: &GraphEvent) -> i64 {
+        match event.triple().object {
+            Value::Integer(n) => n,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_drop_oldest_keeps_newest() {
+        let bus = EventBus::default();
+        let rx = bus.subscribe(3, OverflowPolicy::DropOldest);
+
+        bus.stage((0..5).map(event));
+        bus.deliver();
+
+        let received: Vec<i64> = std::iter::from_fn(|| rx.try_recv())
+            .map(|e| seq(&e))
+            .collect();
+        assert_eq!(received, vec![2, 3, 4]);
+        assert_eq!(rx.dropped(), 2);
+    }
+
+    #[test]
+    fn test_block_waits_for_receiver() {
+        let bus = Arc::new(EventBus::default());
+        let rx = bus.subscribe(2, OverflowPolicy::Block);
+
+        let writer = {
+            let bus = bus.clone();
+            thread::spawn(move || {
+                bus.stage((0..10).map(event));
+                bus.deliver();
+            })
+        };
+
+        let received: Vec<i64> = (0..10).map(|_| seq(&rx.recv().unwrap())).collect();
+        writer.join().unwrap();
+        assert_eq!(received, (0..10).collect::<Vec<_>>());
+        assert_eq!(rx.dropped(), 0);
+    }
+
+    #[test]
+    fn test_dropping_receiver_releases_blocked_writer() {
+        let bus = Arc::new(EventBus::default());
+        let rx = bus.subscribe(1, OverflowPolicy::Block);
+
+        let writer = {
+            let bus = bus.clone();
+            thread::spawn(move || {
+                bus.stage((0..5).map(event));
+                bus.deliver();
+            })
+        };
+
+        assert_eq!(rx.recv().map(|e| seq(&e)), Some(0));
+        drop(rx);
+        writer.join().unwrap();
+        assert!(!bus.is_active());
+    }
+
+    #[test]
+    fn test_recv_ends_when_bus_dropped() {
+        let bus = EventBus::default();
+        let rx = bus.subscribe(4, OverflowPolicy::DropOldest);
+        bus.stage([event(1)]);
+        bus.deliver();
+        assert!(!rx.is_closed());
+        drop(bus);
+
+        assert!(!rx.is_closed());
+        assert_eq!(rx.recv().map(|e| seq(&e)), Some(1));
+        assert!(rx.is_closed());
+        assert!(rx.recv().is_none());
+        assert!(rx.recv_timeout(Duration::from_millis(10)).is_none());
+    }
+}
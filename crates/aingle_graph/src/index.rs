@@ -8,11 +8,17 @@
 //! - POS: Find all triples for a predicate, or predicate+object
 //! - OSP: Find all triples pointing to an object
 
-use crate::{NodeId, Predicate, Triple, TripleId, Value};
-use std::collections::{BTreeMap, HashSet};
+use crate::{
+    Component, NodeId, OrderKey, Predicate, PredicateStats, ProvenanceFilter, SortOrder, Triple,
+    TripleId, TripleMeta, TriplePattern, Value,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::Bound;
 
 /// Types of indexes available
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum IndexType {
     /// Subject-Predicate-Object index
     SPO,
@@ -22,6 +28,44 @@ pub enum IndexType {
     OSP,
 }
 
+impl IndexType {
+    /// Every index, in the order they are kept
+    pub const ALL: [IndexType; 3] = [IndexType::SPO, IndexType::POS, IndexType::OSP];
+
+    /// The two key levels a triple is filed under in this index
+    pub fn keys(&self, triple: &Triple) -> (Vec<u8>, Vec<u8>) {
+        let (s, p, o) = (
+            triple.subject.to_bytes(),
+            triple.predicate.to_bytes(),
+            triple.object.sort_key(),
+        );
+        match self {
+            IndexType::SPO => (s, p),
+            IndexType::POS => (p, o),
+            IndexType::OSP => (o, s),
+        }
+    }
+}
+
+/// The provenance fields of one assertion, kept for filtering without
+/// reading the triple from storage
+#[derive(Debug, Clone)]
+struct Assertion {
+    asserted_by: Option<NodeId>,
+    asserted_at: DateTime<Utc>,
+    confidence: Option<f64>,
+}
+
+impl From<&TripleMeta> for Assertion {
+    fn from(meta: &TripleMeta) -> Self {
+        Self {
+            asserted_by: meta.asserted_by.clone(),
+            asserted_at: meta.asserted_at,
+            confidence: meta.confidence,
+        }
+    }
+}
+
 /// A triple index for efficient lookups
 #[derive(Debug)]
 pub struct TripleIndex {
@@ -31,8 +75,29 @@ pub struct TripleIndex {
     pos: BTreeMap<Vec<u8>, BTreeMap<Vec<u8>, HashSet<TripleId>>>,
     /// OSP index: object -> subject -> predicate -> triple_id
     osp: BTreeMap<Vec<u8>, BTreeMap<Vec<u8>, HashSet<TripleId>>>,
+    /// Per-predicate cardinalities, kept in step with the indexes
+    predicates: HashMap<Predicate, PredicateStats>,
+    /// Every assertion of each indexed triple
+    assertions: HashMap<TripleId, Vec<Assertion>>,
+    /// Insertion sequence number of each indexed triple
+    sequence: HashMap<TripleId, u64>,
+    /// The sequence number given to the next inserted triple
+    next_sequence: u64,
 }
 
+/// Groups of triple IDs sharing a sort key, in key order
+pub type OrderedGroups<'a> = Box<dyn Iterator<Item = Vec<TripleId>> + 'a>;
+
+/// Triple IDs borrowed from an index
+pub type MatchingIds<'a> = Box<dyn Iterator<Item = &'a TripleId> + 'a>;
+
+/// Triple IDs borrowed from an index, each with the index key of one of its
+/// components
+pub type KeyedIds<'a> = Box<dyn Iterator<Item = (&'a [u8], &'a TripleId)> + 'a>;
+
+/// Every entry of an index: both key levels and the triple ID
+pub type IndexEntries<'a> = Box<dyn Iterator<Item = (&'a [u8], &'a [u8], &'a TripleId)> + 'a>;
+
 impl TripleIndex {
     /// Create a new empty index
     pub fn new() -> Self {
@@ -40,6 +105,10 @@ impl TripleIndex {
             spo: BTreeMap::new(),
             pos: BTreeMap::new(),
             osp: BTreeMap::new(),
+            predicates: HashMap::new(),
+            assertions: HashMap::new(),
+            sequence: HashMap::new(),
+            next_sequence: 0,
         }
     }
 
@@ -50,20 +119,19 @@ impl TripleIndex {
         let o = triple.object.sort_key();
 
         // SPO index
-        self.spo
+        let subject_ids = self
+            .spo
             .entry(s.clone())
             .or_default()
             .entry(p.clone())
-            .or_default()
-            .insert(id.clone());
+            .or_default();
+        let new_subject = subject_ids.is_empty();
+        let inserted = subject_ids.insert(id.clone());
 
         // POS index
-        self.pos
-            .entry(p.clone())
-            .or_default()
-            .entry(o.clone())
-            .or_default()
-            .insert(id.clone());
+        let object_ids = self.pos.entry(p).or_default().entry(o.clone()).or_default();
+        let new_object = object_ids.is_empty();
+        object_ids.insert(id.clone());
 
         // OSP index
         self.osp
@@ -71,7 +139,18 @@ impl TripleIndex {
             .or_default()
             .entry(s)
             .or_default()
-            .insert(id);
+            .insert(id.clone());
+
+        if inserted {
+            self.sequence.insert(id.clone(), self.next_sequence);
+            self.next_sequence += 1;
+            self.assertions
+                .insert(id, vec![Assertion::from(&triple.meta)]);
+            let stats = self.predicates.entry(triple.predicate.clone()).or_default();
+            stats.triple_count += 1;
+            stats.subject_count += usize::from(new_subject);
+            stats.object_count += usize::from(new_object);
+        }
     }
 
     /// Remove a triple from all indexes
@@ -81,11 +160,14 @@ impl TripleIndex {
         let o = triple.object.sort_key();
 
         // Remove from SPO
+        let mut removed = false;
+        let mut last_for_subject = false;
         if let Some(predicates) = self.spo.get_mut(&s) {
             if let Some(objects) = predicates.get_mut(&p) {
-                objects.remove(id);
+                removed = objects.remove(id);
                 if objects.is_empty() {
                     predicates.remove(&p);
+                    last_for_subject = true;
                 }
             }
             if predicates.is_empty() {
@@ -94,11 +176,13 @@ impl TripleIndex {
         }
 
         // Remove from POS
+        let mut last_for_object = false;
         if let Some(objects) = self.pos.get_mut(&p) {
             if let Some(subjects) = objects.get_mut(&o) {
                 subjects.remove(id);
                 if subjects.is_empty() {
                     objects.remove(&o);
+                    last_for_object = true;
                 }
             }
             if objects.is_empty() {
@@ -118,6 +202,19 @@ impl TripleIndex {
                 self.osp.remove(&o);
             }
         }
+
+        if removed {
+            self.assertions.remove(id);
+            self.sequence.remove(id);
+            if let Some(stats) = self.predicates.get_mut(&triple.predicate) {
+                stats.triple_count -= 1;
+                stats.subject_count -= usize::from(last_for_subject);
+                stats.object_count -= usize::from(last_for_object);
+                if stats.triple_count == 0 {
+                    self.predicates.remove(&triple.predicate);
+                }
+            }
+        }
     }
 
     /// Find all triple IDs for a given subject
@@ -223,6 +320,280 @@ impl TripleIndex {
         None
     }
 
+    /// Find triple IDs matching a pattern, using the index that covers its
+    /// constrained positions
+    pub fn find(&self, pattern: &TriplePattern) -> Vec<TripleId> {
+        match (&pattern.subject, &pattern.predicate, &pattern.object) {
+            (Some(s), Some(p), Some(o)) => self.find_exact(s, p, o).into_iter().collect(),
+            (Some(s), Some(p), None) => self.find_by_subject_predicate(s, p),
+            (None, Some(p), Some(o)) => self.find_by_predicate_object(p, o),
+            (Some(s), None, Some(o)) => self.find_by_object_subject(o, s),
+            (Some(s), None, None) => self.find_by_subject(s),
+            (None, Some(p), None) => self.find_by_predicate(p),
+            (None, None, Some(o)) => self.find_by_object(o),
+            (None, None, None) => self.assertions.keys().cloned().collect(),
+        }
+    }
+
+    /// Iterate the IDs of triples matching a pattern without collecting them
+    pub fn matching(&self, pattern: &TriplePattern) -> MatchingIds<'_> {
+        let (s, p, o) = (
+            pattern.subject.as_ref().map(NodeId::to_bytes),
+            pattern.predicate.as_ref().map(Predicate::to_bytes),
+            pattern.object.as_ref().map(Value::sort_key),
+        );
+        match (s, p, o) {
+            (Some(s), Some(p), Some(o)) => {
+                let with_object = self.osp.get(&o).and_then(|subjects| subjects.get(&s));
+                let ids = self.spo.get(&s).and_then(|predicates| predicates.get(&p));
+                Box::new(
+                    ids.into_iter()
+                        .flatten()
+                        .filter(move |id| with_object.is_some_and(|ids| ids.contains(id))),
+                )
+            }
+            (Some(s), Some(p), None) => Box::new(
+                self.spo
+                    .get(&s)
+                    .and_then(|predicates| predicates.get(&p))
+                    .into_iter()
+                    .flatten(),
+            ),
+            (None, Some(p), Some(o)) => Box::new(
+                self.pos
+                    .get(&p)
+                    .and_then(|objects| objects.get(&o))
+                    .into_iter()
+                    .flatten(),
+            ),
+            (Some(s), None, Some(o)) => Box::new(
+                self.osp
+                    .get(&o)
+                    .and_then(|subjects| subjects.get(&s))
+                    .into_iter()
+                    .flatten(),
+            ),
+            (Some(s), None, None) => nested_ids(self.spo.get(&s)),
+            (None, Some(p), None) => nested_ids(self.pos.get(&p)),
+            (None, None, Some(o)) => nested_ids(self.osp.get(&o)),
+            (None, None, None) => Box::new(self.assertions.keys()),
+        }
+    }
+
+    /// Iterate the IDs of triples matching a pattern with the index key of
+    /// one component, if an index serving the pattern holds that key
+    ///
+    /// Keys are subject and predicate bytes and object sort keys. A bound
+    /// component always has its key. Otherwise subjects come from SPO (or
+    /// OSP when only the object is bound), predicates from POS (or SPO when
+    /// only the subject is bound) and objects from OSP (or POS when only the
+    /// predicate is bound).
+    pub fn component_keys(
+        &self,
+        pattern: &TriplePattern,
+        component: Component,
+    ) -> Option<KeyedIds<'_>> {
+        let bound = match component {
+            Component::Subject => pattern.subject.as_ref().map(|s| (&self.spo, s.to_bytes())),
+            Component::Predicate => pattern
+                .predicate
+                .as_ref()
+                .map(|p| (&self.pos, p.to_bytes())),
+            Component::Object => pattern.object.as_ref().map(|o| (&self.osp, o.sort_key())),
+        };
+        if let Some((map, bytes)) = bound {
+            return Some(match map.get_key_value(&bytes) {
+                Some((key, _)) => {
+                    Box::new(self.matching(pattern).map(move |id| (key.as_slice(), id)))
+                }
+                None => Box::new(std::iter::empty()),
+            });
+        }
+
+        match (
+            component,
+            &pattern.subject,
+            &pattern.predicate,
+            &pattern.object,
+        ) {
+            (Component::Subject, None, None, None) => Some(nested_keyed(&self.spo)),
+            (Component::Subject, None, None, Some(o)) => Some(keyed(self.osp.get(&o.sort_key()))),
+            (Component::Predicate, None, None, None) => Some(nested_keyed(&self.pos)),
+            (Component::Predicate, Some(s), None, None) => Some(keyed(self.spo.get(&s.to_bytes()))),
+            (Component::Object, None, None, None) => Some(nested_keyed(&self.osp)),
+            (Component::Object, None, Some(p), None) => Some(keyed(self.pos.get(&p.to_bytes()))),
+            _ => None,
+        }
+    }
+
+    /// Find triple IDs matching a pattern grouped by a sort key, if the
+    /// index serving the pattern already iterates in that key's order
+    ///
+    /// Object order comes from OSP (or from POS when only the predicate is
+    /// bound), subject order from SPO (or from OSP when only the object is
+    /// bound). A bound key position yields a single group. IDs within a
+    /// group are unordered. Insertion order is never natural to an index.
+    pub fn ordered_groups(
+        &self,
+        pattern: &TriplePattern,
+        key: OrderKey,
+        order: SortOrder,
+    ) -> Option<OrderedGroups<'_>> {
+        let bound = match key {
+            OrderKey::Subject => pattern.subject.is_some(),
+            OrderKey::Object => pattern.object.is_some(),
+            OrderKey::InsertionTime => return None,
+        };
+        if bound {
+            return Some(Box::new(std::iter::once(self.find(pattern))));
+        }
+
+        match (key, &pattern.subject, &pattern.predicate, &pattern.object) {
+            (OrderKey::Object, None, None, None) => Some(nested_groups(&self.osp, order)),
+            (OrderKey::Object, None, Some(p), None) => Some(match self.pos.get(&p.to_bytes()) {
+                Some(objects) => flat_groups(objects, order),
+                None => Box::new(std::iter::empty()),
+            }),
+            (OrderKey::Subject, None, None, None) => Some(nested_groups(&self.spo, order)),
+            (OrderKey::Subject, None, None, Some(o)) => Some(match self.osp.get(&o.sort_key()) {
+                Some(subjects) => flat_groups(subjects, order),
+                None => Box::new(std::iter::empty()),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Get up to `limit` triple IDs with a predicate, with their object
+    /// keys, in (object key, ID) order after the position `after`
+    ///
+    /// Positions depend only on triple content, so a walk over a predicate
+    /// can be resumed from the last position returned, even after the index
+    /// is rebuilt.
+    pub fn predicate_positions(
+        &self,
+        predicate: &Predicate,
+        after: Option<&(Vec<u8>, TripleId)>,
+        limit: usize,
+    ) -> Vec<(Vec<u8>, TripleId)> {
+        let Some(objects) = self.pos.get(&predicate.to_bytes()) else {
+            return Vec::new();
+        };
+        let start = match after {
+            Some((object, _)) => Bound::Included(object.clone()),
+            None => Bound::Unbounded,
+        };
+
+        let mut positions = Vec::new();
+        for (object, ids) in objects.range((start, Bound::Unbounded)) {
+            let mut ids: Vec<&TripleId> = ids
+                .iter()
+                .filter(|id| after.is_none_or(|(o, last)| object != o || *id > last))
+                .collect();
+            ids.sort_unstable();
+            for id in ids {
+                if positions.len() == limit {
+                    return positions;
+                }
+                positions.push((object.clone(), id.clone()));
+            }
+        }
+        positions
+    }
+
+    /// Get the insertion sequence number of an indexed triple
+    ///
+    /// Numbers increase with every insert. When the index is rebuilt from
+    /// storage, triples are numbered in the order they were first asserted.
+    pub fn sequence(&self, id: &TripleId) -> Option<u64> {
+        self.sequence.get(id).copied()
+    }
+
+    /// Check whether a triple is indexed
+    pub fn contains(&self, id: &TripleId) -> bool {
+        self.sequence.contains_key(id)
+    }
+
+    /// Iterate the IDs of all indexed triples, in no particular order
+    pub fn ids(&self) -> impl Iterator<Item = &TripleId> {
+        self.sequence.keys()
+    }
+
+    /// Check whether one index files a triple under the keys it should
+    pub fn holds(&self, index: IndexType, triple: &Triple, id: &TripleId) -> bool {
+        let (outer, inner) = index.keys(triple);
+        self.map(index)
+            .get(&outer)
+            .and_then(|level| level.get(&inner))
+            .is_some_and(|ids| ids.contains(id))
+    }
+
+    /// Iterate every entry of one index, in key order
+    pub fn entries(&self, index: IndexType) -> IndexEntries<'_> {
+        Box::new(self.map(index).iter().flat_map(|(outer, level)| {
+            level.iter().flat_map(move |(inner, ids)| {
+                ids.iter()
+                    .map(move |id| (outer.as_slice(), inner.as_slice(), id))
+            })
+        }))
+    }
+
+    fn map(&self, index: IndexType) -> &BTreeMap<Vec<u8>, BTreeMap<Vec<u8>, HashSet<TripleId>>> {
+        match index {
+            IndexType::SPO => &self.spo,
+            IndexType::POS => &self.pos,
+            IndexType::OSP => &self.osp,
+        }
+    }
+
+    /// Drop one entry of one index, leaving the others as they are
+    #[cfg(test)]
+    pub(crate) fn remove_entry(&mut self, index: IndexType, triple: &Triple, id: &TripleId) {
+        let (outer, inner) = index.keys(triple);
+        let map = match index {
+            IndexType::SPO => &mut self.spo,
+            IndexType::POS => &mut self.pos,
+            IndexType::OSP => &mut self.osp,
+        };
+        if let Some(ids) = map.get_mut(&outer).and_then(|level| level.get_mut(&inner)) {
+            ids.remove(id);
+        }
+    }
+
+    /// Get the number of indexed triples
+    pub fn len(&self) -> usize {
+        self.sequence.len()
+    }
+
+    /// Check whether no triples are indexed
+    pub fn is_empty(&self) -> bool {
+        self.sequence.is_empty()
+    }
+
+    /// Record a further assertion of an indexed triple
+    pub fn add_assertion(&mut self, id: &TripleId, meta: &TripleMeta) {
+        if let Some(assertions) = self.assertions.get_mut(id) {
+            assertions.push(Assertion::from(meta));
+        }
+    }
+
+    /// Check whether any assertion of a triple satisfies the filter
+    pub fn matches_provenance(&self, id: &TripleId, filter: &ProvenanceFilter) -> bool {
+        self.assertions.get(id).is_some_and(|assertions| {
+            assertions
+                .iter()
+                .any(|a| filter.accepts(a.asserted_by.as_ref(), a.asserted_at, a.confidence))
+        })
+    }
+
+    /// Find all triple IDs with an assertion satisfying the filter
+    pub fn find_by_provenance(&self, filter: &ProvenanceFilter) -> Vec<TripleId> {
+        self.assertions
+            .keys()
+            .filter(|id| self.matches_provenance(id, filter))
+            .cloned()
+            .collect()
+    }
+
     /// Get count of unique subjects
     pub fn subject_count(&self) -> usize {
         self.spo.len()
@@ -238,14 +609,69 @@ impl TripleIndex {
         self.osp.len()
     }
 
+    /// Get triple, subject and object counts for every predicate in use
+    pub fn predicate_stats(&self) -> &HashMap<Predicate, PredicateStats> {
+        &self.predicates
+    }
+
     /// Clear all indexes
     pub fn clear(&mut self) {
         self.spo.clear();
         self.pos.clear();
         self.osp.clear();
+        self.predicates.clear();
+        self.assertions.clear();
+        self.sequence.clear();
+        self.next_sequence = 0;
     }
 }
 
+/// Iterate a map's values forwards or backwards
+fn directed<'a, T: 'a>(
+    iter: impl DoubleEndedIterator<Item = T> + 'a,
+    order: SortOrder,
+) -> Box<dyn Iterator<Item = T> + 'a> {
+    match order {
+        SortOrder::Asc => Box::new(iter),
+        SortOrder::Desc => Box::new(iter.rev()),
+    }
+}
+
+/// One group per outer key of a two-level index
+fn nested_groups(
+    map: &BTreeMap<Vec<u8>, BTreeMap<Vec<u8>, HashSet<TripleId>>>,
+    order: SortOrder,
+) -> OrderedGroups<'_> {
+    Box::new(directed(map.values(), order).map(|inner| inner.values().flatten().cloned().collect()))
+}
+
+/// One group per key of an index level
+fn flat_groups(map: &BTreeMap<Vec<u8>, HashSet<TripleId>>, order: SortOrder) -> OrderedGroups<'_> {
+    Box::new(directed(map.values(), order).map(|ids| ids.iter().cloned().collect()))
+}
+
+/// Every ID below the second level of an index entry
+fn nested_ids(inner: Option<&BTreeMap<Vec<u8>, HashSet<TripleId>>>) -> MatchingIds<'_> {
+    Box::new(inner.into_iter().flat_map(|inner| inner.values().flatten()))
+}
+
+/// Every ID of a two-level index with its outer key
+fn nested_keyed(map: &BTreeMap<Vec<u8>, BTreeMap<Vec<u8>, HashSet<TripleId>>>) -> KeyedIds<'_> {
+    Box::new(
+        map.iter()
+            .flat_map(|(key, inner)| inner.values().flatten().map(move |id| (key.as_slice(), id))),
+    )
+}
+
+/// Every ID of an index level with its key
+fn keyed(map: Option<&BTreeMap<Vec<u8>, HashSet<TripleId>>>) -> KeyedIds<'_> {
+    Box::new(
+        map.into_iter()
+            .flatten()
+            .flat_map(|(key, ids)| ids.iter().map(move |id| (key.as_slice(), id))),
+    )
+}
+
 impl Default for TripleIndex {
     fn default() -> Self {
         Self::new()
@@ -376,4 +802,88 @@ mod tests {
         assert_eq!(index.subject_count(), 2); // alice, bob
         assert_eq!(index.predicate_count(), 2); // has_name, has_age
     }
+
+    #[test]
+    fn test_predicate_stats() {
+        let mut index = TripleIndex::new();
+        let works_at = Predicate::named("works_at");
+        let triples = [
+            ("user:alice", "org:acme"),
+            ("user:bob", "org:acme"),
+            ("user:bob", "org:globex"),
+        ]
+        .map(|(s, o)| {
+            Triple::new(
+                NodeId::named(s),
+                works_at.clone(),
+                Value::Node(NodeId::named(o)),
+            )
+        });
+        for triple in &triples {
+            index.insert(triple, triple.id());
+        }
+
+        let stats = index.predicate_stats()[&works_at];
+        assert_eq!(stats.triple_count, 3);
+        assert_eq!(stats.subject_count, 2); // alice, bob
+        assert_eq!(stats.object_count, 2); // acme, globex
+
+        // bob keeps another employer, globex loses its only employee
+        index.remove(&triples[2], &triples[2].id());
+        let stats = index.predicate_stats()[&works_at];
+        assert_eq!(stats.triple_count, 2);
+        assert_eq!(stats.subject_count, 2);
+        assert_eq!(stats.object_count, 1);
+
+        index.remove(&triples[0], &triples[0].id());
+        index.remove(&triples[1], &triples[1].id());
+        assert!(index.predicate_stats().is_empty());
+    }
+
+    #[test]
+    fn test_provenance_lookup() {
+        let mut index = TripleIndex::new();
+        let mut triple = test_triple();
+        triple.meta = TripleMeta::new()
+            .with_asserted_by(NodeId::named("agent:a"))
+            .with_confidence(0.5);
+        let id = triple.id();
+        index.insert(&triple, id.clone());
+
+        let by_b = ProvenanceFilter {
+            asserted_by: Some(NodeId::named("agent:b")),
+            ..Default::default()
+        };
+        assert!(!index.matches_provenance(&id, &by_b));
+
+        index.add_assertion(
+            &id,
+            &TripleMeta::new().with_asserted_by(NodeId::named("agent:b")),
+        );
+        assert!(index.matches_provenance(&id, &by_b));
+        assert_eq!(index.find_by_provenance(&by_b), vec![id.clone()]);
+
+        index.remove(&triple, &id);
+        assert!(index.find_by_provenance(&by_b).is_empty());
+    }
+
+    #[test]
+    fn test_entries_and_holds() {
+        let mut index = TripleIndex::new();
+        let triple = test_triple();
+        let id = triple.id();
+        index.insert(&triple, id.clone());
+
+        for index_type in IndexType::ALL {
+            assert!(index.holds(index_type, &triple, &id));
+            let entries: Vec<_> = index.entries(index_type).collect();
+            let (outer, inner) = index_type.keys(&triple);
+            assert_eq!(entries, vec![(&outer[..], &inner[..], &id)]);
+        }
+
+        index.remove_entry(IndexType::POS, &triple, &id);
+        assert!(!index.holds(IndexType::POS, &triple, &id));
+        assert!(index.holds(IndexType::SPO, &triple, &id));
+        assert_eq!(index.ids().count(), 1);
+    }
 }
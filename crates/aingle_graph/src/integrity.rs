@@ -0,0 +1,346 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Integrity checks and repair for the triple indexes.
+//!
+//! The SPO, POS and OSP indexes are built from the triples in the storage
+//! backend when a store is opened, and kept up to date by every write.
+//! [`GraphDB::verify_indexes`](crate::GraphDB::verify_indexes) checks that
+//! they still agree with each other and with the backend, and
+//! [`GraphDB::rebuild_indexes`](crate::GraphDB::rebuild_indexes) rebuilds
+//! them from the backend, [`REBUILD_CHUNK`] triples at a time.
+//!
+//! Backends that support it also keep a shutdown marker recording whether
+//! the store was closed cleanly and how many triples it held then. The first
+//! write after a flush marks the store dirty; every flush, and dropping the
+//! store, marks it clean again. What is checked on open is set by
+//! [`StorageOptions::verify_on_open`](crate::StorageOptions::verify_on_open):
+//!
+//! - [`VerifyOnOpen::Quick`]: the marker must be clean and its triple count
+//!   must match the backend's. A store without a marker passes.
+//! - [`VerifyOnOpen::Full`]: the quick check, then a full
+//!   [`verify_indexes`](crate::GraphDB::verify_indexes), which also catches
+//!   stored triples that can no longer be decoded.
+//!
+//! A store failing the check refuses to open with [`Error::Integrity`],
+//! unless [`StorageOptions::auto_rebuild`](crate::StorageOptions::auto_rebuild)
+//! is set: then its indexes are rebuilt and verified in full, and it opens
+//! if they agree with the backend.
+//!
+//! # Examples
+//!
+//! ```
+//! use aingle_graph::{GraphDB, Triple};
+//!
+//! # fn main() -> Result<(), aingle_graph::Error> {
+//! let db = GraphDB::memory()?;
+//! db.insert(Triple::literal("sensor:1", "reading", "17"))?;
+//!
+//! let report = db.verify_indexes(None)?;
+//! assert!(report.is_consistent());
+//! assert_eq!(report.checked, 1);
+//!
+//! let mut chunks = 0;
+//! db.rebuild_indexes_with_progress(|_| chunks += 1)?;
+//! assert_eq!(chunks, 1);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`VerifyOnOpen::Quick`]: crate::VerifyOnOpen::Quick
+//! [`VerifyOnOpen::Full`]: crate::VerifyOnOpen::Full
+
+use crate::backends::StorageBackend;
+use crate::index::{IndexType, TripleIndex};
+use crate::{Error, Result, Triple, TripleId};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+/// Number of triples indexed between two progress reports of
+/// [`GraphDB::rebuild_indexes_with_progress`](crate::GraphDB::rebuild_indexes_with_progress).
+pub const REBUILD_CHUNK: usize = 10_000;
+
+/// How an index disagrees with the backend about a triple.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DivergenceKind {
+    /// The triple is stored, but the index does not file it under its keys.
+    Missing,
+    /// The index files the triple where the backend has no such triple.
+    Orphaned,
+}
+
+/// One disagreement between an index and the backend.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Divergence {
+    /// The triple the index disagrees about
+    pub id: TripleId,
+    /// The index that disagrees
+    pub index: IndexType,
+    /// How it disagrees
+    pub kind: DivergenceKind,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            DivergenceKind::Missing => "missing from",
+            DivergenceKind::Orphaned => "orphaned in",
+        };
+        write!(f, "triple {} {} {:?}", self.id, kind, self.index)
+    }
+}
+
+/// The result of [`GraphDB::verify_indexes`](crate::GraphDB::verify_indexes).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntegrityReport {
+    /// Number of triples in the backend
+    pub stored: usize,
+    /// Number of triples in the indexes
+    pub indexed: usize,
+    /// Number of triples checked against the backend
+    pub checked: usize,
+    /// Whether only a sample of the indexed triples was checked
+    pub sampled: bool,
+    /// Every disagreement found, ordered by triple
+    pub divergences: Vec<Divergence>,
+}
+
+impl IntegrityReport {
+    /// Returns `true` if the indexes agree with the backend.
+    pub fn is_consistent(&self) -> bool {
+        self.stored == self.indexed && self.divergences.is_empty()
+    }
+
+    /// The triples at least one index disagrees about.
+    pub fn divergent_ids(&self) -> BTreeSet<TripleId> {
+        self.divergences.iter().map(|d| d.id.clone()).collect()
+    }
+}
+
+impl fmt::Display for IntegrityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} triples stored, {} indexed, {} divergent",
+            self.stored,
+            self.indexed,
+            self.divergent_ids().len()
+        )
+    }
+}
+
+/// Progress of an index rebuild, reported after every chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RebuildProgress {
+    /// Triples indexed so far
+    pub indexed: usize,
+    /// Triples to index in total
+    pub total: usize,
+}
+
+/// The shutdown marker a backend stores for its store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShutdownMarker {
+    /// Whether every write was flushed when the marker was written
+    pub clean: bool,
+    /// Number of stored triples when the marker was written
+    pub triple_count: u64,
+}
+
+impl ShutdownMarker {
+    /// Serialize the marker for storage.
+    pub fn encode(&self) -> Vec<u8> {
+        bincode::serde::encode_to_vec(self, bincode::config::standard()).unwrap_or_default()
+    }
+
+    /// Deserialize a stored marker.
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        bincode::serde::decode_from_slice(bytes, bincode::config::standard())
+            .map(|(marker, _)| marker)
+            .map_err(|e| Error::Serialization(format!("corrupt shutdown marker: {}", e)))
+    }
+}
+
+/// The keys each triple is filed under in one index
+type Placements<'a> = HashMap<&'a TripleId, Vec<(&'a [u8], &'a [u8])>>;
+
+/// Cross-check the indexes against each other and the backend.
+///
+/// With `sample`, up to that many indexed triples, spread evenly over their
+/// IDs, are read from the backend; otherwise every stored and indexed triple
+/// is. Index entries of triples missing from the other indexes are always
+/// checked, since finding them needs no backend reads.
+pub(crate) fn verify(
+    index: &TripleIndex,
+    backend: &dyn StorageBackend,
+    sample: Option<usize>,
+) -> Result<IntegrityReport> {
+    // Where each index files each triple
+    let mut filed: HashMap<IndexType, Placements<'_>> = HashMap::new();
+    for index_type in IndexType::ALL {
+        let entries = filed.entry(index_type).or_default();
+        for (outer, inner, id) in index.entries(index_type) {
+            entries.entry(id).or_default().push((outer, inner));
+        }
+    }
+
+    let mut to_check: BTreeSet<TripleId> = filed
+        .values()
+        .flat_map(|entries| entries.keys())
+        .filter(|id| !index.contains(id))
+        .map(|id| (*id).clone())
+        .collect();
+    let mut stored: HashMap<TripleId, Triple> = HashMap::new();
+    match sample {
+        Some(n) => {
+            let mut ids: Vec<&TripleId> = index.ids().collect();
+            ids.sort_unstable();
+            let step = ids.len().div_ceil(n.max(1)).max(1);
+            to_check.extend(ids.into_iter().step_by(step).cloned());
+            for id in &to_check {
+                if let Some(triple) = backend.get(id)? {
+                    stored.insert(id.clone(), triple);
+                }
+            }
+        }
+        None => {
+            stored = backend
+                .iter_all()?
+                .into_iter()
+                .map(|triple| (triple.id(), triple))
+                .collect();
+            to_check.extend(stored.keys().cloned());
+            to_check.extend(index.ids().cloned());
+        }
+    }
+
+    let mut divergences = BTreeSet::new();
+    for id in &to_check {
+        for index_type in IndexType::ALL {
+            let places = filed[&index_type].get(id);
+            let divergence = |kind| Divergence {
+                id: id.clone(),
+                index: index_type,
+                kind,
+            };
+            match stored.get(id) {
+                Some(triple) => {
+                    if !index.holds(index_type, triple, id) {
+                        divergences.insert(divergence(DivergenceKind::Missing));
+                    }
+                    let (outer, inner) = index_type.keys(triple);
+                    if places
+                        .is_some_and(|places| places.iter().any(|&(o, i)| o != outer || i != inner))
+                    {
+                        divergences.insert(divergence(DivergenceKind::Orphaned));
+                    }
+                }
+                None if places.is_some() => {
+                    divergences.insert(divergence(DivergenceKind::Orphaned));
+                }
+                None => {}
+            }
+        }
+    }
+
+    Ok(IntegrityReport {
+        stored: backend.count(),
+        indexed: index.len(),
+        checked: to_check.len(),
+        sampled: sample.is_some(),
+        divergences: divergences.into_iter().collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::MemoryBackend;
+
+    fn indexed(triples: &[Triple]) -> (TripleIndex, MemoryBackend) {
+        let mut index = TripleIndex::new();
+        let backend = MemoryBackend::new();
+        for triple in triples {
+            backend.put(&triple.id(), triple).unwrap();
+            index.insert(triple, triple.id());
+        }
+        (index, backend)
+    }
+
+    fn readings(n: usize) -> Vec<Triple> {
+        (0..n)
+            .map(|i| Triple::literal(format!("sensor:{}", i), "reading", i.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_consistent_indexes() {
+        let (index, backend) = indexed(&readings(10));
+        let report = verify(&index, &backend, None).unwrap();
+        assert!(report.is_consistent(), "{report}");
+        assert_eq!(
+            (report.stored, report.indexed, report.checked),
+            (10, 10, 10)
+        );
+        assert!(!report.sampled);
+
+        let report = verify(&index, &backend, Some(3)).unwrap();
+        assert!(report.is_consistent());
+        assert!(report.sampled);
+        assert!(report.checked <= 4, "{}", report.checked);
+    }
+
+    #[test]
+    fn test_detects_missing_entry() {
+        let triples = readings(5);
+        let (mut index, backend) = indexed(&triples);
+        let lost = &triples[2];
+        index.remove_entry(IndexType::POS, lost, &lost.id());
+
+        let report = verify(&index, &backend, None).unwrap();
+        assert!(!report.is_consistent());
+        assert_eq!(
+            report.divergences,
+            vec![Divergence {
+                id: lost.id(),
+                index: IndexType::POS,
+                kind: DivergenceKind::Missing,
+            }]
+        );
+        assert_eq!(report.divergent_ids().len(), 1);
+    }
+
+    #[test]
+    fn test_detects_orphans_and_unindexed_triples() {
+        let triples = readings(3);
+        let (index, backend) = indexed(&triples);
+
+        // Stored behind the indexes' back
+        let unindexed = Triple::literal("sensor:9", "reading", "9");
+        backend.put(&unindexed.id(), &unindexed).unwrap();
+        // Gone from the backend but still indexed
+        backend.delete(&triples[0].id()).unwrap();
+
+        let report = verify(&index, &backend, None).unwrap();
+        assert_eq!((report.stored, report.indexed), (3, 3));
+        let kinds: Vec<_> = report
+            .divergences
+            .iter()
+            .map(|d| (d.id.clone(), d.kind))
+            .collect();
+        assert_eq!(kinds.len(), 6);
+        assert!(kinds.contains(&(unindexed.id(), DivergenceKind::Missing)));
+        assert!(kinds.contains(&(triples[0].id(), DivergenceKind::Orphaned)));
+    }
+
+    #[test]
+    fn test_marker_round_trip() {
+        let marker = ShutdownMarker {
+            clean: true,
+            triple_count: 42,
+        };
+        assert_eq!(ShutdownMarker::decode(&marker.encode()).unwrap(), marker);
+        assert!(ShutdownMarker::decode(&[0xff]).is_err());
+    }
+}
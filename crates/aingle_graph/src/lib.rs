@@ -70,13 +70,22 @@
 //! ```
 
 pub mod backends;
+pub mod backup;
+pub mod cache;
 #[cfg(feature = "crdt")]
 pub mod crdt;
 pub mod error;
+pub mod events;
 pub mod index;
+pub mod integrity;
+pub mod merge;
 pub mod node;
+pub mod planner;
 pub mod predicate;
 pub mod query;
+pub mod schema;
+pub mod secondary;
+pub mod snapshot;
 pub mod store;
 pub mod triple;
 pub mod value;
@@ -88,25 +97,41 @@ pub mod rdf;
 pub mod dag;
 
 // Re-exports
+pub use backup::{read_snapshot_info, SnapshotInfo, SnapshotOptions, SNAPSHOT_VERSION};
+pub use cache::{SubjectCacheOptions, SubjectCacheStats};
 pub use error::{Error, Result};
+pub use events::{GraphEvent, OverflowPolicy, Receiver};
 pub use index::{IndexType, TripleIndex};
+pub use integrity::{Divergence, DivergenceKind, IntegrityReport, RebuildProgress, REBUILD_CHUNK};
+pub use merge::{MergePolicy, MergeReport, MergeResolution, MERGE_BATCH_SIZE};
 pub use node::NodeId;
+pub use planner::{JoinPattern, PlanStep, QueryPlan, Solutions, Term, Var};
 pub use predicate::Predicate;
-pub use query::{QueryBuilder, QueryResult, TriplePattern};
+pub use query::{
+    Component, OrderKey, ProvenanceFilter, QueryBuilder, QueryResult, QueryStats, SortOrder,
+    TriplePattern,
+};
+pub use schema::{
+    Cardinality, ClassDecl, EnforcementMode, GraphSchema, ObjectKind, PredicateDecl,
+    SchemaViolation,
+};
+pub use secondary::{IndexInfo, IndexKind, IndexState, INDEX_BUILD_CHUNK};
+pub use snapshot::GraphSnapshot;
 pub use store::GraphStore;
 pub use triple::{Triple, TripleBuilder, TripleId, TripleMeta};
 pub use value::Value;
 
 #[cfg(feature = "sled-backend")]
-pub use backends::sled::SledBackend;
+pub use backends::sled::{SledBackend, SledOptions};
 
 #[cfg(feature = "rocksdb-backend")]
-pub use backends::rocksdb::RocksBackend;
+pub use backends::rocksdb::{RocksBackend, RocksOptions};
 
 #[cfg(feature = "sqlite-backend")]
 pub use backends::sqlite::SqliteBackend;
 
 pub use backends::memory::MemoryBackend;
+pub use backends::{BackendInfo, Compression, StorageOptions, VerifyOnOpen};
 
 /// The main entry point for interacting with a semantic graph database.
 ///
@@ -252,6 +277,42 @@ impl GraphDB {
         })
     }
 
+    /// Creates or opens a `GraphDB` using the `Sled` storage backend with
+    /// explicit cache, compression and read-only settings.
+    ///
+    /// Accepts either [`SledOptions`] or backend-agnostic [`StorageOptions`].
+    /// Writes to a read-only database fail with [`Error::ReadOnly`]. The
+    /// integrity check set by [`StorageOptions::verify_on_open`] runs before
+    /// the database is returned; see [`integrity`].
+    ///
+    /// Requires the `sled-backend` feature; zstd compression also needs
+    /// `sled-compression`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "sled-backend")]
+    /// # fn example() -> Result<(), aingle_graph::Error> {
+    /// use aingle_graph::{GraphDB, StorageOptions};
+    ///
+    /// let options = StorageOptions::default().with_cache_bytes(64 << 20);
+    /// let db = GraphDB::sled_with("./my_graph.db", options)?;
+    /// assert_eq!(db.backend_info().cache_bytes, Some(64 << 20));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "sled-backend")]
+    pub fn sled_with(path: &str, options: impl Into<SledOptions>) -> Result<Self> {
+        let options = options.into();
+        let backend = SledBackend::open_with(path, options.clone())?;
+        let store = GraphStore::open(Box::new(backend), &options.storage)?;
+        Ok(Self {
+            store,
+            #[cfg(feature = "dag")]
+            dag_store: None,
+        })
+    }
+
     /// Creates or opens a `GraphDB` using the `RocksDB` storage backend.
     ///
     /// RocksDB is a high-performance key-value store optimized for fast storage.
@@ -285,6 +346,42 @@ impl GraphDB {
         })
     }
 
+    /// Creates or opens a `GraphDB` using the `RocksDB` storage backend with
+    /// explicit cache, compression, write buffer and read-only settings.
+    ///
+    /// Accepts either [`RocksOptions`] or backend-agnostic [`StorageOptions`].
+    /// A read-only database can be opened while another process writes to
+    /// it; writes through it fail with [`Error::ReadOnly`]. RocksDB keeps
+    /// no shutdown marker, so only [`VerifyOnOpen::Full`] checks anything.
+    ///
+    /// Requires the `rocksdb-backend` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "rocksdb-backend")]
+    /// # fn example() -> Result<(), aingle_graph::Error> {
+    /// use aingle_graph::{Compression, GraphDB, StorageOptions};
+    ///
+    /// let options = StorageOptions::default()
+    ///     .with_compression(Compression::Zstd)
+    ///     .read_only();
+    /// let db = GraphDB::rocksdb_with("./my_graph.db", options)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "rocksdb-backend")]
+    pub fn rocksdb_with(path: &str, options: impl Into<RocksOptions>) -> Result<Self> {
+        let options = options.into();
+        let backend = RocksBackend::open_with(path, options.clone())?;
+        let store = GraphStore::open(Box::new(backend), &options.storage)?;
+        Ok(Self {
+            store,
+            #[cfg(feature = "dag")]
+            dag_store: None,
+        })
+    }
+
     /// Creates or opens a `GraphDB` using the `SQLite` storage backend.
     ///
     /// SQLite provides a familiar SQL-based interface and is suitable for
@@ -319,6 +416,35 @@ impl GraphDB {
         })
     }
 
+    /// Creates a `GraphDB` on a storage backend opened by the caller.
+    ///
+    /// Only the integrity and subject cache settings of `options` are used;
+    /// see [`integrity`] and [`cache`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aingle_graph::{GraphDB, MemoryBackend, StorageOptions, VerifyOnOpen};
+    ///
+    /// # fn main() -> Result<(), aingle_graph::Error> {
+    /// let options = StorageOptions::default().with_verify_on_open(VerifyOnOpen::Full);
+    /// let db = GraphDB::with_backend(Box::new(MemoryBackend::new()), &options)?;
+    /// assert_eq!(db.count(), 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_backend(
+        backend: Box<dyn backends::StorageBackend>,
+        options: &StorageOptions,
+    ) -> Result<Self> {
+        let store = GraphStore::open(backend, options)?;
+        Ok(Self {
+            store,
+            #[cfg(feature = "dag")]
+            dag_store: None,
+        })
+    }
+
     /// Creates an in-memory `GraphDB` with DAG enabled.
     #[cfg(feature = "dag")]
     pub fn memory_with_dag() -> Result<Self> {
@@ -712,6 +838,69 @@ impl GraphDB {
         self.store.insert_batch(triples)
     }
 
+    /// Inserts a [`Triple`] asserted with the given [`TripleMeta`].
+    ///
+    /// The [`TripleId`] does not depend on metadata, so asserting a triple
+    /// that is already stored records another assertion of it instead of
+    /// failing. Query assertions with [`provenance`](Self::provenance) or the
+    /// provenance filters on [`QueryBuilder`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aingle_graph::{GraphDB, Triple, TripleMeta, NodeId};
+    ///
+    /// # fn main() -> Result<(), aingle_graph::Error> {
+    /// let db = GraphDB::memory()?;
+    /// let fact = || Triple::literal("sensor:1", "reading", "42");
+    ///
+    /// let id = db.insert_with_meta(
+    ///     fact(),
+    ///     TripleMeta::new().with_asserted_by(NodeId::named("agent:a")),
+    /// )?;
+    /// let again = db.insert_with_meta(
+    ///     fact(),
+    ///     TripleMeta::new()
+    ///         .with_asserted_by(NodeId::named("agent:b"))
+    ///         .with_confidence(0.9),
+    /// )?;
+    ///
+    /// assert_eq!(id, again);
+    /// assert_eq!(db.count(), 1);
+    /// assert_eq!(db.provenance(&id)?.len(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn insert_with_meta(&self, triple: Triple, meta: TripleMeta) -> Result<TripleId> {
+        self.store.insert_with_meta(triple, meta)
+    }
+
+    /// Inserts a batch of [`Triple`]s, each asserted with its own [`TripleMeta`].
+    ///
+    /// See [`insert_with_meta`](Self::insert_with_meta). Returns one ID per
+    /// item, in order.
+    pub fn insert_batch_with_meta(
+        &self,
+        items: Vec<(Triple, TripleMeta)>,
+    ) -> Result<Vec<TripleId>> {
+        self.store.insert_batch_with_meta(items)
+    }
+
+    /// Returns every assertion of a triple, first the one stored with it.
+    ///
+    /// Empty if no triple with the given ID exists in the graph.
+    pub fn provenance(&self, id: &TripleId) -> Result<Vec<TripleMeta>> {
+        self.store.provenance(id)
+    }
+
+    /// Returns the insertion sequence number of a triple, if it is stored.
+    ///
+    /// Queries ordered by [`OrderKey::InsertionTime`] sort by this number.
+    /// See [`GraphStore::sequence`].
+    pub fn sequence(&self, id: &TripleId) -> Result<Option<u64>> {
+        self.store.sequence(id)
+    }
+
     /// Retrieves a [`Triple`] by its unique [`TripleId`].
     ///
     /// Returns `None` if no triple with the given ID exists in the graph.
@@ -772,6 +961,49 @@ impl GraphDB {
         self.store.delete(id)
     }
 
+    /// Subscribes to committed inserts and deletes.
+    ///
+    /// Equivalent to [`subscribe_with`](Self::subscribe_with) with
+    /// [`DEFAULT_EVENT_CAPACITY`](events::DEFAULT_EVENT_CAPACITY) and
+    /// [`OverflowPolicy::DropOldest`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aingle_graph::{GraphDB, GraphEvent, Triple, NodeId, Predicate, Value};
+    ///
+    /// # fn main() -> Result<(), aingle_graph::Error> {
+    /// let db = GraphDB::memory()?;
+    /// let events = db.subscribe();
+    ///
+    /// let id = db.insert(Triple::new(
+    ///     NodeId::named("user:alice"),
+    ///     Predicate::named("has_name"),
+    ///     Value::literal("Alice"),
+    /// ))?;
+    /// db.delete(&id)?;
+    ///
+    /// assert!(matches!(events.try_recv(), Some(GraphEvent::Inserted(..))));
+    /// assert!(matches!(events.try_recv(), Some(GraphEvent::Deleted(..))));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn subscribe(&self) -> Receiver<GraphEvent> {
+        self.subscribe_with(events::DEFAULT_EVENT_CAPACITY, OverflowPolicy::DropOldest)
+    }
+
+    /// Subscribes to committed inserts and deletes with a bounded channel of
+    /// `capacity` events and the given overflow policy.
+    ///
+    /// Every write path emits events, including batch inserts, prefix deletes
+    /// and DAG-recorded changes: one [`GraphEvent`] per triple actually
+    /// inserted or deleted, in commit order. Duplicates skipped by a batch
+    /// and deletes of missing triples emit nothing. Dropping the receiver
+    /// unsubscribes it.
+    pub fn subscribe_with(&self, capacity: usize, policy: OverflowPolicy) -> Receiver<GraphEvent> {
+        self.store.subscribe(capacity, policy)
+    }
+
     /// Begins building a new query using a fluent [`QueryBuilder`].
     ///
     /// The query builder provides a convenient API for constructing pattern-based
@@ -810,6 +1042,38 @@ impl GraphDB {
         QueryBuilder::new(&self.store)
     }
 
+    /// Takes a consistent read snapshot of the graph.
+    ///
+    /// Every read through the snapshot sees the same triples, while other
+    /// threads keep writing: a triple committed by a batch is either in all
+    /// of the snapshot's indexes or in none. Writers wait until the snapshot
+    /// is dropped, so keep it short-lived, and do not use this `GraphDB` on
+    /// the same thread while holding it. See [`GraphSnapshot`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aingle_graph::{GraphDB, Triple, NodeId, Predicate, Value, TriplePattern};
+    ///
+    /// # fn main() -> Result<(), aingle_graph::Error> {
+    /// let db = GraphDB::memory()?;
+    ///
+    /// db.insert(Triple::new(
+    ///     NodeId::named("user:alice"),
+    ///     Predicate::named("has_name"),
+    ///     Value::literal("Alice"),
+    /// ))?;
+    ///
+    /// let snapshot = db.snapshot()?;
+    /// let by_subject = snapshot.find(TriplePattern::subject(NodeId::named("user:alice")))?;
+    /// assert_eq!(by_subject.len(), snapshot.count());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn snapshot(&self) -> Result<GraphSnapshot<'_>> {
+        Ok(GraphSnapshot::new(self.store.view()?))
+    }
+
     /// Finds all triples matching a given [`TriplePattern`].
     ///
     /// A pattern can specify constraints on any combination of subject, predicate,
@@ -841,6 +1105,237 @@ impl GraphDB {
         self.store.find(pattern)
     }
 
+    /// Returns the plan [`find`](Self::find) would use for a pattern, without
+    /// running it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aingle_graph::{GraphDB, IndexType, TriplePattern, Value};
+    ///
+    /// # fn main() -> Result<(), aingle_graph::Error> {
+    /// let db = GraphDB::memory()?;
+    ///
+    /// let plan = db.explain(TriplePattern::object(Value::literal("Alice")))?;
+    /// assert_eq!(plan.steps[0].index, Some(IndexType::OSP));
+    /// println!("{}", plan);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn explain(&self, pattern: TriplePattern) -> Result<QueryPlan> {
+        let mut query = self.query();
+        if let Some(subject) = pattern.subject {
+            query = query.subject(subject);
+        }
+        if let Some(predicate) = pattern.predicate {
+            query = query.predicate(predicate);
+        }
+        if let Some(object) = pattern.object {
+            query = query.object(object);
+        }
+        query.explain()
+    }
+
+    /// Creates a secondary index on a predicate and indexes its existing
+    /// triples.
+    ///
+    /// Queries constraining only the predicate with an
+    /// [`object_range`](QueryBuilder::object_range) read their matches from
+    /// the index once it is built. Existing triples are indexed in chunks of
+    /// [`INDEX_BUILD_CHUNK`], releasing the store between chunks; calling
+    /// this again after an interrupted build finishes it. See
+    /// [`secondary`] for details.
+    ///
+    /// Indexes are stored by the memory, Sled and SQLite backends.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Error::BackendUnavailable` if the backend does not store
+    /// secondary indexes, or an `Error::Index` if the predicate already has
+    /// an index of another kind.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aingle_graph::{GraphDB, IndexKind, IndexState, Predicate};
+    ///
+    /// # fn main() -> Result<(), aingle_graph::Error> {
+    /// let db = GraphDB::memory()?;
+    ///
+    /// let info = db.create_index(Predicate::named("has_timestamp"), IndexKind::NumericObject)?;
+    /// assert_eq!(info.state, IndexState::Ready);
+    /// assert_eq!(db.indexes()?.len(), 1);
+    ///
+    /// assert!(db.drop_index(&Predicate::named("has_timestamp"))?);
+    /// assert!(db.indexes()?.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn create_index(&self, predicate: Predicate, kind: IndexKind) -> Result<IndexInfo> {
+        self.store.create_index(predicate, kind)
+    }
+
+    /// Creates a secondary index on a predicate if it has none, and indexes
+    /// up to `max_triples` more of its existing triples.
+    ///
+    /// For spreading a build out, e.g. over idle periods; the index is used
+    /// by queries once the returned state is [`IndexState::Ready`].
+    ///
+    /// # Errors
+    ///
+    /// As for [`create_index`](Self::create_index).
+    pub fn build_index_step(
+        &self,
+        predicate: &Predicate,
+        kind: IndexKind,
+        max_triples: usize,
+    ) -> Result<IndexInfo> {
+        self.store.build_index_step(predicate, kind, max_triples)
+    }
+
+    /// Drops the secondary index on a predicate, returning `Ok(false)` if it
+    /// has none.
+    pub fn drop_index(&self, predicate: &Predicate) -> Result<bool> {
+        self.store.drop_index(predicate)
+    }
+
+    /// Lists the secondary indexes with their state and size, ordered by
+    /// predicate.
+    pub fn indexes(&self) -> Result<Vec<IndexInfo>> {
+        self.store.indexes()
+    }
+
+    /// Cross-checks the SPO, POS and OSP indexes against each other and the
+    /// storage backend, reporting every triple they disagree about.
+    ///
+    /// With `sample`, only about that many indexed triples are read from the
+    /// backend, spread evenly over their IDs; the indexes are still
+    /// compared with each other in full. Writes wait until the check is
+    /// done. See [`integrity`].
+    pub fn verify_indexes(&self, sample: Option<usize>) -> Result<IntegrityReport> {
+        self.store.verify_indexes(sample)
+    }
+
+    /// Rebuilds the SPO, POS and OSP indexes from the triples in the storage
+    /// backend, returning the number of triples indexed.
+    ///
+    /// Reads and writes wait until the rebuild is done.
+    pub fn rebuild_indexes(&self) -> Result<usize> {
+        self.store.rebuild_indexes()
+    }
+
+    /// Rebuilds the indexes like [`rebuild_indexes`](Self::rebuild_indexes),
+    /// calling `progress` after every [`REBUILD_CHUNK`] triples.
+    ///
+    /// `progress` runs while the store is locked and must not use it.
+    pub fn rebuild_indexes_with_progress(
+        &self,
+        progress: impl FnMut(RebuildProgress),
+    ) -> Result<usize> {
+        self.store.rebuild_indexes_with_progress(progress)
+    }
+
+    /// Installs a schema that every new triple is screened against.
+    ///
+    /// With [`EnforcementMode::Warn`] a triple whose predicate is not
+    /// declared, or whose object is of a kind its predicate does not take,
+    /// is logged and inserted; with [`EnforcementMode::Reject`] the insert
+    /// fails with [`Error::Schema`] naming the declaration it breaks. A batch
+    /// is refused whole. Triples already stored are not checked; see
+    /// [`schema_violations`](Self::schema_violations) and [`schema`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aingle_graph::{EnforcementMode, GraphDB, GraphSchema, Triple};
+    ///
+    /// # fn main() -> Result<(), aingle_graph::Error> {
+    /// let db = GraphDB::memory()?;
+    /// db.insert(Triple::literal("user:alice", "has_name", "Alice"))?;
+    ///
+    /// // Enforce what the data already uses
+    /// db.set_schema(db.infer_schema()?, EnforcementMode::Reject)?;
+    /// assert!(db.insert(Triple::literal("user:bob", "has_name", "Bob")).is_ok());
+    /// assert!(db.insert(Triple::literal("user:bob", "hasName", "Bob")).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_schema(&self, schema: GraphSchema, mode: EnforcementMode) -> Result<()> {
+        self.store.set_schema(schema, mode)
+    }
+
+    /// Returns the installed schema and its enforcement mode, if any.
+    pub fn schema(&self) -> Result<Option<(GraphSchema, EnforcementMode)>> {
+        Ok(self
+            .store
+            .schema()?
+            .map(|(schema, mode)| (GraphSchema::clone(&schema), mode)))
+    }
+
+    /// Checks the stored triples against the installed schema, including
+    /// cardinalities and the predicates each class requires.
+    ///
+    /// Runs whatever the enforcement mode; empty when no schema is installed.
+    pub fn schema_violations(&self) -> Result<Vec<SchemaViolation>> {
+        let Some((schema, _)) = self.store.schema()? else {
+            return Ok(Vec::new());
+        };
+        Ok(schema.check_all(&self.find(TriplePattern::any())?))
+    }
+
+    /// Drafts a schema from the stored triples.
+    ///
+    /// See [`GraphSchema::infer`]; triples of a stored schema are left out.
+    pub fn infer_schema(&self) -> Result<GraphSchema> {
+        Ok(GraphSchema::infer(&self.find(TriplePattern::any())?))
+    }
+
+    /// Stores a schema in the graph as triples, replacing any stored before.
+    ///
+    /// The schema travels with the database; read it back with
+    /// [`load_schema`](Self::load_schema). Storing a schema does not install
+    /// it. Returns the number of triples written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aingle_graph::{EnforcementMode, GraphDB, GraphSchema, ObjectKind, PredicateDecl};
+    ///
+    /// # fn main() -> Result<(), aingle_graph::Error> {
+    /// let db = GraphDB::memory()?;
+    /// let schema = GraphSchema::new()
+    ///     .with_predicate(PredicateDecl::new("has_age").with_kind(ObjectKind::Integer));
+    /// db.store_schema(&schema)?;
+    ///
+    /// let stored = db.load_schema()?.unwrap_or_default();
+    /// assert_eq!(stored, schema);
+    /// db.set_schema(stored, EnforcementMode::Warn)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn store_schema(&self, schema: &GraphSchema) -> Result<usize> {
+        self.delete_by_subject_prefix(schema::vocab::SUBJECT_PREFIX)?;
+        Ok(self.insert_batch(schema.to_triples())?.len())
+    }
+
+    /// Reads the schema stored with [`store_schema`](Self::store_schema),
+    /// or `None` if there is none.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Schema`] if the stored schema is malformed.
+    pub fn load_schema(&self) -> Result<Option<GraphSchema>> {
+        let triples: Vec<Triple> = self
+            .find(TriplePattern::any())?
+            .into_iter()
+            .filter(GraphSchema::is_schema_triple)
+            .collect();
+        if triples.is_empty() {
+            return Ok(None);
+        }
+        GraphSchema::from_triples(&triples).map(Some)
+    }
+
     /// Traverses the graph from a starting node, following the given predicates.
     ///
     /// This performs a breadth-first traversal starting from the `start` node,
@@ -907,6 +1402,23 @@ impl GraphDB {
         self.store.stats()
     }
 
+    /// Returns the storage backend and the settings it was opened with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aingle_graph::GraphDB;
+    ///
+    /// # fn main() -> Result<(), aingle_graph::Error> {
+    /// let db = GraphDB::memory()?;
+    /// assert_eq!(db.backend_info().backend, "memory");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn backend_info(&self) -> BackendInfo {
+        self.store.backend_info()
+    }
+
     /// Flushes any buffered writes to the underlying storage backend.
     ///
     /// For persistent backends (e.g., Sled), this ensures all data is
@@ -1118,11 +1630,83 @@ impl GraphDB {
         Ok(deleted)
     }
 
+    /// Replaces every blank node with a named node: `prefix` followed by the
+    /// node's [`blank_label`](NodeId::blank_label).
+    ///
+    /// Blank nodes cannot be referred to from outside the graph; skolemizing
+    /// gives them IRIs that can be shared. A node always gets the same name,
+    /// so skolemizing copies of a graph names their blank nodes alike. Every
+    /// assertion of a rewritten triple is kept. Returns the number of triples
+    /// rewritten.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aingle_graph::{GraphDB, NodeId, Predicate, Triple, Value};
+    ///
+    /// # fn main() -> Result<(), aingle_graph::Error> {
+    /// let db = GraphDB::memory()?;
+    /// let address = NodeId::anonymous();
+    /// db.insert(Triple::new(
+    ///     NodeId::named("user:alice"),
+    ///     Predicate::named("lives_at"),
+    ///     Value::Node(address.clone()),
+    /// ))?;
+    ///
+    /// assert_eq!(db.skolemize("https://example.org/.well-known/genid/")?, 1);
+    /// let skolem = format!(
+    ///     "https://example.org/.well-known/genid/{}",
+    ///     address.blank_label().unwrap()
+    /// );
+    /// let triples = db.get_subject(&NodeId::named("user:alice"))?;
+    /// assert_eq!(triples[0].object_node(), Some(&NodeId::named(skolem)));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn skolemize(&self, prefix: &str) -> Result<usize> {
+        let skolem = |node: &NodeId| match node.blank_label() {
+            Some(label) => NodeId::named(format!("{}{}", prefix, label)),
+            None => node.clone(),
+        };
+
+        let mut rewritten = 0;
+        for triple in self.find(TriplePattern::any())? {
+            let blank_object = triple.object_node().is_some_and(NodeId::is_blank);
+            if !triple.subject.is_blank() && !blank_object {
+                continue;
+            }
+
+            let object = match triple.object_node() {
+                Some(node) => Value::Node(skolem(node)),
+                None => triple.object.clone(),
+            };
+            let named = Triple::new(skolem(&triple.subject), triple.predicate.clone(), object);
+
+            let id = triple.id();
+            let metas = self.provenance(&id)?;
+            if metas.is_empty() {
+                // Deleted since it was listed
+                continue;
+            }
+            self.insert_batch_with_meta(
+                metas
+                    .into_iter()
+                    .map(|meta| (named.clone(), meta))
+                    .collect(),
+            )?;
+            self.delete(&id)?;
+            rewritten += 1;
+        }
+        Ok(rewritten)
+    }
+
     // ========== RDF Import/Export (requires "rdf" feature) ==========
 
     /// Imports triples from a string in Turtle format.
     ///
-    /// Turtle is a compact, human-readable RDF serialization format.
+    /// Turtle is a compact, human-readable RDF serialization format. Each
+    /// blank node label becomes a new [`NodeId::anonymous`] node, so blank
+    /// nodes of separate imports never unify, even if their labels match.
     ///
     /// Requires the `rdf` feature.
     ///
@@ -1156,7 +1740,8 @@ impl GraphDB {
     /// Imports triples from a string in N-Triples format.
     ///
     /// N-Triples is a line-based RDF serialization format where each line represents
-    /// one triple.
+    /// one triple. Blank nodes are imported as in
+    /// [`import_turtle`](Self::import_turtle).
     ///
     /// Requires the `rdf` feature.
     ///
@@ -1186,8 +1771,145 @@ impl GraphDB {
         self.insert_batch(triples)
     }
 
+    /// Imports triples from a JSON-LD 1.1 document.
+    ///
+    /// The document's `@context` must be inline: a context referenced by URL
+    /// fails with [`Error::Unsupported`] instead of being fetched. Nested
+    /// node objects without `@id` become anonymous nodes, and value objects
+    /// map onto the typed [`Value`] variants, e.g. `xsd:integer` onto
+    /// [`Value::Integer`]. See [`rdf::jsonld`] for the supported subset.
+    ///
+    /// Requires the `rdf` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "rdf")]
+    /// # fn example() -> Result<(), aingle_graph::Error> {
+    /// use aingle_graph::GraphDB;
+    ///
+    /// let db = GraphDB::memory()?;
+    ///
+    /// let jsonld = r#"{
+    ///     "@context": {"ex": "http://example.org/"},
+    ///     "@id": "ex:alice",
+    ///     "ex:knows": {"@id": "ex:bob"},
+    ///     "ex:age": 30
+    /// }"#;
+    ///
+    /// let ids = db.import_jsonld(jsonld)?;
+    /// println!("Imported {} triples", ids.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "rdf")]
+    pub fn import_jsonld(&self, jsonld: &str) -> Result<Vec<TripleId>> {
+        use rdf::{JsonLdParser, RdfParser};
+        let triples = JsonLdParser::parse_to_triples(jsonld)?;
+        self.insert_batch(triples)
+    }
+
+    /// Merges every triple of `other` into this graph, with all of its
+    /// assertions.
+    ///
+    /// Missing triples are inserted in batches; triples already stored here
+    /// are handled by `policy`. Only public store APIs are used, so the two
+    /// graphs may use different backends, e.g. to migrate a memory prototype
+    /// to sled. See the [`merge`] module.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aingle_graph::{GraphDB, MergePolicy, Triple};
+    ///
+    /// # fn main() -> Result<(), aingle_graph::Error> {
+    /// let a = GraphDB::memory()?;
+    /// let b = GraphDB::memory()?;
+    /// a.insert(Triple::literal("user:alice", "name", "Alice"))?;
+    /// b.insert(Triple::literal("user:alice", "name", "Alice"))?;
+    /// b.insert(Triple::literal("user:bob", "name", "Bob"))?;
+    ///
+    /// let report = a.merge_from(&b, MergePolicy::SkipExisting)?;
+    /// assert_eq!((report.inserted, report.skipped), (1, 1));
+    /// assert_eq!(a.count(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn merge_from(&self, other: &GraphDB, policy: MergePolicy) -> Result<MergeReport> {
+        merge::merge(self, other, &policy)
+    }
+
+    /// Writes a binary snapshot of the graph with the default
+    /// [`SnapshotOptions`].
+    ///
+    /// The snapshot holds every triple with all of its assertions and can be
+    /// loaded into a graph on any backend with
+    /// [`import_snapshot`](Self::import_snapshot). See the [`backup`] module
+    /// for the format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aingle_graph::{GraphDB, Triple};
+    ///
+    /// # fn main() -> Result<(), aingle_graph::Error> {
+    /// let db = GraphDB::memory()?;
+    /// db.insert(Triple::literal("user:alice", "name", "Alice"))?;
+    ///
+    /// let mut bytes = Vec::new();
+    /// let info = db.export_snapshot(&mut bytes)?;
+    /// assert_eq!(info.triple_count, 1);
+    ///
+    /// let copy = GraphDB::memory()?;
+    /// copy.import_snapshot(bytes.as_slice())?;
+    /// assert_eq!(copy.count(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn export_snapshot<W: std::io::Write>(&self, writer: W) -> Result<SnapshotInfo> {
+        backup::export(self, writer, &SnapshotOptions::default())
+    }
+
+    /// Writes a binary snapshot of the graph with the given options.
+    ///
+    /// Zstd compression requires the `snapshot-zstd` feature. Writes made to
+    /// the graph during the export fail it with [`Error::Integrity`].
+    pub fn export_snapshot_with<W: std::io::Write>(
+        &self,
+        writer: W,
+        options: &SnapshotOptions,
+    ) -> Result<SnapshotInfo> {
+        backup::export(self, writer, options)
+    }
+
+    /// Loads a snapshot written by [`export_snapshot`](Self::export_snapshot).
+    ///
+    /// Blocks are checked against their checksums and loaded one at a time
+    /// through the batch insert path; triples already in the graph are
+    /// skipped. If a block fails, the error is [`Error::SnapshotBlock`] and
+    /// the blocks before it stay loaded, so the import can be resumed with
+    /// [`import_snapshot_from`](Self::import_snapshot_from).
+    pub fn import_snapshot<R: std::io::Read>(&self, reader: R) -> Result<SnapshotInfo> {
+        backup::import(self, reader, 0)
+    }
+
+    /// Loads a snapshot starting at block `first_block`.
+    ///
+    /// Earlier blocks are read to verify the content checksum but not loaded.
+    pub fn import_snapshot_from<R: std::io::Read>(
+        &self,
+        reader: R,
+        first_block: u64,
+    ) -> Result<SnapshotInfo> {
+        backup::import(self, reader, first_block)
+    }
+
     /// Exports all triples in the graph to a string in Turtle format.
     ///
+    /// Blank nodes are written as `_:b1`, `_:b2`, ... in order of first
+    /// appearance; use [`skolemize`](Self::skolemize) first to export them as
+    /// IRIs instead.
+    ///
     /// Requires the `rdf` feature.
     ///
     /// # Examples
@@ -1248,6 +1970,56 @@ impl GraphDB {
         NTriplesSerializer::serialize_triples(&triples)
     }
 
+    /// Exports all triples in the graph to a JSON-LD document.
+    ///
+    /// Triples are grouped by subject into node objects under `@graph`, and
+    /// blank nodes referenced once are nested inside their referrer. IRIs
+    /// are compacted against `context` when given, and otherwise against a
+    /// context generated from the namespaces in the graph; either way the
+    /// context is written as the document's `@context`.
+    ///
+    /// Requires the `rdf` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "rdf")]
+    /// # fn example() -> Result<(), aingle_graph::Error> {
+    /// use aingle_graph::{GraphDB, Triple, NodeId, Predicate, Value};
+    /// use serde_json::json;
+    ///
+    /// let db = GraphDB::memory()?;
+    ///
+    /// db.insert(Triple::new(
+    ///     NodeId::named("http://example.org/alice"),
+    ///     Predicate::named("http://example.org/knows"),
+    ///     Value::Node(NodeId::named("http://example.org/bob")),
+    /// ))?;
+    ///
+    /// let context = json!({"ex": "http://example.org/", "knows": "ex:knows"});
+    /// let jsonld = db.export_jsonld(context.as_object().cloned())?;
+    /// println!("{}", jsonld);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "rdf")]
+    pub fn export_jsonld(
+        &self,
+        context: Option<serde_json::Map<String, serde_json::Value>>,
+    ) -> Result<String> {
+        use rdf::{JsonLdSerializer, RdfTriple};
+        let triples: Vec<_> = self
+            .find(TriplePattern::any())?
+            .iter()
+            .map(RdfTriple::from_triple)
+            .collect();
+        let serializer = match context {
+            Some(context) => JsonLdSerializer::with_context(context),
+            None => JsonLdSerializer::new(),
+        };
+        serializer.serialize_with_options(&triples)
+    }
+
     /// Exports all triples matching a [`TriplePattern`] to a string in Turtle format.
     ///
     /// This allows you to export a subset of the graph based on a pattern.
@@ -1297,6 +2069,24 @@ pub struct GraphStats {
     pub object_count: usize,
     /// The approximate size of the database on disk in bytes.
     pub storage_bytes: usize,
+    /// Cardinalities of each predicate, used to plan multi-pattern queries.
+    pub predicates: std::collections::HashMap<Predicate, PredicateStats>,
+    /// The secondary indexes and their sizes, ordered by predicate.
+    pub indexes: Vec<IndexInfo>,
+    /// Counters of the subject read cache; zero while it is disabled, and
+    /// in the stats of a snapshot.
+    pub subject_cache: SubjectCacheStats,
+}
+
+/// Cardinalities of a single predicate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PredicateStats {
+    /// The number of triples using the predicate.
+    pub triple_count: usize,
+    /// The number of unique subjects with the predicate.
+    pub subject_count: usize,
+    /// The number of unique objects of the predicate.
+    pub object_count: usize,
 }
 
 /// Version information
@@ -1564,6 +2354,98 @@ mod tests {
         assert_eq!(results.len(), 2);
     }
 
+    fn cached_db() -> GraphDB {
+        let options = StorageOptions::default().with_subject_cache(16);
+        GraphDB::with_backend(Box::new(MemoryBackend::new()), &options).unwrap()
+    }
+
+    #[test]
+    fn test_get_subject_hits_cache() {
+        let db = cached_db();
+        let alice = NodeId::named("user:alice");
+        db.insert(Triple::literal("user:alice", "has_name", "Alice"))
+            .unwrap();
+        db.insert(Triple::literal("user:bob", "has_name", "Bob"))
+            .unwrap();
+
+        let first = db.get_subject(&alice).unwrap();
+        for _ in 0..3 {
+            assert_eq!(db.get_subject(&alice).unwrap(), first);
+        }
+        let stats = db.stats().subject_cache;
+        assert_eq!((stats.hits, stats.misses, stats.entries), (3, 1, 1));
+
+        // Other patterns are not cached
+        db.find(TriplePattern::subject(alice).with_predicate(Predicate::named("has_name")))
+            .unwrap();
+        assert_eq!(db.stats().subject_cache.misses, 1);
+
+        // Disabled by default
+        let db = GraphDB::memory().unwrap();
+        db.get_subject(&NodeId::named("user:alice")).unwrap();
+        assert_eq!(db.stats().subject_cache, SubjectCacheStats::default());
+    }
+
+    #[test]
+    fn test_write_invalidates_cached_subject() {
+        let db = cached_db();
+        let alice = NodeId::named("user:alice");
+        let name = db
+            .insert(Triple::literal("user:alice", "has_name", "Alice"))
+            .unwrap();
+        db.insert(Triple::literal("user:bob", "has_name", "Bob"))
+            .unwrap();
+        db.get_subject(&alice).unwrap();
+        db.get_subject(&NodeId::named("user:bob")).unwrap();
+        assert_eq!(db.stats().subject_cache.entries, 2);
+
+        db.insert(Triple::literal("user:alice", "has_age", "30"))
+            .unwrap();
+        assert_eq!(db.stats().subject_cache.entries, 1);
+        assert_eq!(db.get_subject(&alice).unwrap().len(), 2);
+
+        db.insert_batch(vec![Triple::literal("user:alice", "has_role", "admin")])
+            .unwrap();
+        assert_eq!(db.get_subject(&alice).unwrap().len(), 3);
+
+        db.delete(&name).unwrap();
+        assert_eq!(db.get_subject(&alice).unwrap().len(), 2);
+
+        // Bob was never touched, so only his lookup could hit
+        let stats = db.stats().subject_cache;
+        assert_eq!((stats.hits, stats.misses), (0, 5));
+        db.get_subject(&NodeId::named("user:bob")).unwrap();
+        assert_eq!(db.stats().subject_cache.hits, 1);
+    }
+
+    #[test]
+    fn test_cached_subjects_match_uncached_reads() {
+        let db = cached_db();
+        let subject = |n: usize| NodeId::named(format!("user:{}", n % 5));
+        for n in 0..40 {
+            let triple = Triple::new(
+                subject(n),
+                Predicate::named("score"),
+                Value::integer(n as i64),
+            );
+            let id = db.insert(triple).unwrap();
+            if n % 7 == 0 {
+                db.delete(&id).unwrap();
+            }
+            // Cached reads and reads straight from the indexes agree
+            for s in 0..5 {
+                let cached = db.get_subject(&subject(s)).unwrap();
+                let uncached = db
+                    .snapshot()
+                    .unwrap()
+                    .find(TriplePattern::subject(subject(s)))
+                    .unwrap();
+                assert_eq!(cached, uncached);
+            }
+        }
+        assert!(db.stats().subject_cache.hits > 0);
+    }
+
     #[test]
     fn test_get_predicate() {
         let db = GraphDB::memory().unwrap();
@@ -1626,6 +2508,7 @@ mod tests {
             predicate_count: 10,
             object_count: 75,
             storage_bytes: 1024,
+            ..Default::default()
         };
 
         let cloned = stats.clone();
@@ -1641,6 +2524,7 @@ mod tests {
             predicate_count: 3,
             object_count: 8,
             storage_bytes: 512,
+            ..Default::default()
         };
 
         let debug_str = format!("{:?}", stats);
@@ -1776,6 +2660,100 @@ mod tests {
         assert_eq!(deleted_none, 0);
     }
 
+    fn age_schema() -> GraphSchema {
+        GraphSchema::new().with_predicate(
+            PredicateDecl::new("has_age")
+                .with_kind(ObjectKind::Integer)
+                .with_cardinality(Cardinality::One),
+        )
+    }
+
+    fn age(subject: &str, value: Value) -> Triple {
+        Triple::new(NodeId::named(subject), Predicate::named("has_age"), value)
+    }
+
+    #[test]
+    fn test_schema_off_and_warn_insert_everything() {
+        for mode in [EnforcementMode::Off, EnforcementMode::Warn] {
+            let db = GraphDB::memory().unwrap();
+            db.set_schema(age_schema(), mode).unwrap();
+
+            db.insert(age("user:alice", Value::literal("thirty")))
+                .unwrap();
+            db.insert_batch(vec![
+                Triple::literal("user:alice", "hasAge", "30"),
+                age("user:alice", Value::integer(30)),
+            ])
+            .unwrap();
+            assert_eq!(db.count(), 3, "{mode:?}");
+            assert_eq!(db.schema().unwrap().unwrap().1, mode);
+            // Wrong kind, undeclared and two ages for one subject
+            assert_eq!(db.schema_violations().unwrap().len(), 3, "{mode:?}");
+        }
+    }
+
+    #[test]
+    fn test_schema_reject_refuses_with_the_declaration() {
+        let db = GraphDB::memory().unwrap();
+        db.set_schema(age_schema(), EnforcementMode::Reject)
+            .unwrap();
+
+        let err = db
+            .insert(age("user:alice", Value::literal("thirty")))
+            .unwrap_err();
+        assert!(matches!(err, Error::Schema(_)), "{err}");
+        let message = err.to_string();
+        assert!(message.contains("object is string"), "{message}");
+        assert!(message.contains("<has_age> takes integer"), "{message}");
+
+        // A batch with one bad triple is refused whole
+        let err = db
+            .insert_batch(vec![
+                age("user:alice", Value::integer(30)),
+                Triple::literal("user:alice", "hasAge", "30"),
+            ])
+            .unwrap_err();
+        assert!(err.to_string().contains("not declared"), "{err}");
+        assert_eq!(db.count(), 0);
+
+        // Cardinality is only a hint
+        db.insert(age("user:alice", Value::integer(30))).unwrap();
+        db.insert(age("user:alice", Value::integer(31))).unwrap();
+        assert_eq!(db.count(), 2);
+        assert!(db.schema_violations().unwrap()[0].is_hint());
+    }
+
+    #[test]
+    fn test_stored_schema_round_trips_and_is_never_screened() {
+        let db = GraphDB::memory().unwrap();
+        db.insert(Triple::new(
+            NodeId::named("user:alice"),
+            Predicate::rdf_type(),
+            Value::Node(NodeId::named("Person")),
+        ))
+        .unwrap();
+        db.insert(age("user:alice", Value::integer(30))).unwrap();
+        db.insert(Triple::literal("user:alice", "has_name", "Alice"))
+            .unwrap();
+        assert_eq!(db.load_schema().unwrap(), None);
+
+        let schema = db.infer_schema().unwrap();
+        db.set_schema(schema.clone(), EnforcementMode::Reject)
+            .unwrap();
+        let written = db.store_schema(&schema).unwrap();
+        assert_eq!(written, schema.to_triples().len());
+        assert_eq!(db.load_schema().unwrap(), Some(schema.clone()));
+        assert!(db.schema_violations().unwrap().is_empty());
+
+        // Storing again replaces the stored schema
+        let smaller = age_schema();
+        db.store_schema(&smaller).unwrap();
+        assert_eq!(db.count(), 3 + smaller.to_triples().len());
+        assert_eq!(db.load_schema().unwrap(), Some(smaller));
+        // The draft left the stored schema's own triples out
+        assert_eq!(db.infer_schema().unwrap(), schema);
+    }
+
     #[cfg(feature = "dag")]
     mod dag_tests {
         use super::*;
@@ -0,0 +1,250 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Graph-level merge of one `GraphDB` into another.
+//!
+//! [`GraphDB::merge_from`](crate::GraphDB::merge_from) copies every triple of
+//! a source graph, together with all of its assertions, into the target
+//! graph. Only the public store API is used on either side, so any two
+//! backends can be merged (e.g. memory into sled, sled into SQLite).
+//!
+//! Triples are content-addressed: a source triple whose [`TripleId`] is
+//! already stored in the target is a *conflict*, even if its metadata
+//! differs. The [`MergePolicy`] decides what happens to it.
+
+use crate::{GraphDB, Result, Triple, TripleId, TripleMeta, TriplePattern};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Number of source triples written to the target per batch.
+pub const MERGE_BATCH_SIZE: usize = 1000;
+
+/// How a conflicting triple is handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeResolution {
+    /// Leave the target's triple and its provenance unchanged.
+    KeepExisting,
+    /// Record the source's assertions as further assertions of the target's
+    /// triple.
+    MergeProvenance,
+    /// Replace the target's triple and its provenance with the source's.
+    Replace,
+}
+
+/// Resolves a conflict given the target's triple and the source's.
+pub type MergeResolver = dyn Fn(&Triple, &Triple) -> MergeResolution + Send + Sync;
+
+/// How [`GraphDB::merge_from`](crate::GraphDB::merge_from) handles source
+/// triples that are already stored in the target.
+#[derive(Default)]
+pub enum MergePolicy {
+    /// Skip them; content addressing makes the merge a deduplicating union.
+    #[default]
+    SkipExisting,
+    /// Ask a resolver, called with the target's triple and the source's.
+    Custom(Box<MergeResolver>),
+}
+
+impl MergePolicy {
+    /// Creates a [`MergePolicy::Custom`] from a resolver function.
+    pub fn custom<F>(resolver: F) -> Self
+    where
+        F: Fn(&Triple, &Triple) -> MergeResolution + Send + Sync + 'static,
+    {
+        Self::Custom(Box::new(resolver))
+    }
+
+    fn resolve(&self, existing: &Triple, incoming: &Triple) -> MergeResolution {
+        match self {
+            Self::SkipExisting => MergeResolution::KeepExisting,
+            Self::Custom(resolver) => resolver(existing, incoming),
+        }
+    }
+}
+
+impl fmt::Debug for MergePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SkipExisting => f.write_str("SkipExisting"),
+            Self::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// The outcome of a merge.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MergeReport {
+    /// Source triples that were missing from the target and were inserted.
+    pub inserted: usize,
+    /// Conflicting triples left unchanged.
+    pub skipped: usize,
+    /// Conflicting triples whose provenance was merged.
+    pub merged: usize,
+    /// Conflicting triples replaced by the source's.
+    pub replaced: usize,
+    /// Wall-clock time the merge took.
+    pub elapsed: Duration,
+}
+
+impl MergeReport {
+    /// The number of source triples processed.
+    pub fn total(&self) -> usize {
+        self.inserted + self.skipped + self.merged + self.replaced
+    }
+}
+
+/// Merges `source` into `target`; see [`GraphDB::merge_from`].
+pub(crate) fn merge(
+    target: &GraphDB,
+    source: &GraphDB,
+    policy: &MergePolicy,
+) -> Result<MergeReport> {
+    let started = Instant::now();
+    let mut report = MergeReport::default();
+
+    let triples = source.find(TriplePattern::any())?;
+    for chunk in triples.chunks(MERGE_BATCH_SIZE) {
+        let mut items = Vec::with_capacity(chunk.len());
+        for triple in chunk {
+            let id = triple.id();
+            match target.get(&id)? {
+                None => report.inserted += 1,
+                Some(existing) => match policy.resolve(&existing, triple) {
+                    MergeResolution::KeepExisting => {
+                        report.skipped += 1;
+                        continue;
+                    }
+                    MergeResolution::MergeProvenance => report.merged += 1,
+                    MergeResolution::Replace => {
+                        target.delete(&id)?;
+                        report.replaced += 1;
+                    }
+                },
+            }
+            items.extend(assertions(source, triple, &id)?);
+        }
+        // Assertions after a triple's first are recorded against it
+        target.insert_batch_with_meta(items)?;
+    }
+
+    report.elapsed = started.elapsed();
+    Ok(report)
+}
+
+/// Every assertion of a source triple, paired with the triple, first the one
+/// stored with it.
+fn assertions(
+    source: &GraphDB,
+    triple: &Triple,
+    id: &TripleId,
+) -> Result<Vec<(Triple, TripleMeta)>> {
+    let mut metas = source.provenance(id)?;
+    if metas.is_empty() {
+        // Deleted from the source since it was listed
+        metas.push(triple.meta.clone());
+    }
+    Ok(metas
+        .into_iter()
+        .map(|meta| (triple.clone(), meta))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NodeId;
+
+    fn fact(subject: &str, value: &str) -> Triple {
+        Triple::literal(subject, "reading", value)
+    }
+
+    fn asserted_by(agent: &str) -> TripleMeta {
+        TripleMeta::new().with_asserted_by(NodeId::named(agent))
+    }
+
+    #[test]
+    fn test_merge_overlapping_graphs_is_union() {
+        let target = GraphDB::memory().unwrap();
+        let source = GraphDB::memory().unwrap();
+        for i in 0..6 {
+            target.insert(fact(&format!("sensor:{}", i), "1")).unwrap();
+        }
+        for i in 3..10 {
+            source.insert(fact(&format!("sensor:{}", i), "1")).unwrap();
+        }
+
+        let report = target.merge_from(&source, MergePolicy::default()).unwrap();
+
+        assert_eq!(report.inserted, 4);
+        assert_eq!(report.skipped, 3);
+        assert_eq!(report.total(), source.count());
+        assert_eq!(target.count(), 10);
+        assert_eq!(source.count(), 7);
+    }
+
+    #[test]
+    fn test_merge_carries_provenance_of_new_triples() {
+        let target = GraphDB::memory().unwrap();
+        let source = GraphDB::memory().unwrap();
+        let id = source
+            .insert_with_meta(fact("sensor:1", "42"), asserted_by("agent:a"))
+            .unwrap();
+        source
+            .insert_with_meta(fact("sensor:1", "42"), asserted_by("agent:b"))
+            .unwrap();
+
+        target.merge_from(&source, MergePolicy::default()).unwrap();
+
+        assert_eq!(
+            target.provenance(&id).unwrap(),
+            source.provenance(&id).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_custom_policy_resolutions() {
+        let target = GraphDB::memory().unwrap();
+        let source = GraphDB::memory().unwrap();
+        let keep = target
+            .insert_with_meta(fact("sensor:keep", "1"), asserted_by("agent:target"))
+            .unwrap();
+        let merge = target
+            .insert_with_meta(fact("sensor:merge", "1"), asserted_by("agent:target"))
+            .unwrap();
+        let replace = target
+            .insert_with_meta(fact("sensor:replace", "1"), asserted_by("agent:target"))
+            .unwrap();
+        for subject in ["sensor:keep", "sensor:merge", "sensor:replace"] {
+            source
+                .insert_with_meta(fact(subject, "1"), asserted_by("agent:source"))
+                .unwrap();
+        }
+
+        let policy = MergePolicy::custom(|existing, _incoming| {
+            if existing.subject == NodeId::named("sensor:merge") {
+                MergeResolution::MergeProvenance
+            } else if existing.subject == NodeId::named("sensor:replace") {
+                MergeResolution::Replace
+            } else {
+                MergeResolution::KeepExisting
+            }
+        });
+        let report = target.merge_from(&source, policy).unwrap();
+
+        assert_eq!((report.skipped, report.merged, report.replaced), (1, 1, 1));
+        assert_eq!(target.count(), 3);
+
+        let agents = |id: &TripleId| -> Vec<Option<NodeId>> {
+            target
+                .provenance(id)
+                .unwrap()
+                .into_iter()
+                .map(|meta| meta.asserted_by)
+                .collect()
+        };
+        let by = |agent: &str| Some(NodeId::named(agent));
+        assert_eq!(agents(&keep), vec![by("agent:target")]);
+        assert_eq!(agents(&merge), vec![by("agent:target"), by("agent:source")]);
+        assert_eq!(agents(&replace), vec![by("agent:source")]);
+    }
+}
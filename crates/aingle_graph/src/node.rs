@@ -12,7 +12,7 @@ use std::fmt;
 ///
 /// Nodes are the entities in the graph that are connected by relationships (predicates).
 /// A node can be identified in three ways: by a human-readable name, by a cryptographic
-/// hash, or as an anonymous blank node.
+/// hash, or as a blank node without a global name.
 ///
 /// # Examples
 ///
@@ -44,6 +44,17 @@ use std::fmt;
 /// let node = NodeId::blank();
 /// assert!(node.is_blank());
 /// ```
+///
+/// Creating an anonymous node, which stays distinct from every other node
+/// even across restarts:
+///
+/// ```
+/// use aingle_graph::NodeId;
+///
+/// let node = NodeId::anonymous();
+/// assert!(node.is_anonymous());
+/// assert!(node.is_blank());
+/// ```
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
 pub enum NodeId {
     /// A named node, identified by a string, similar to a URI in RDF.
@@ -54,8 +65,16 @@ pub enum NodeId {
     /// A node identified by a 32-byte content hash, typically from an AIngle entry or action.
     Hash([u8; 32]),
 
-    /// A blank (or anonymous) node, identified by a unique, auto-generated ID.
+    /// A blank node, identified by an ID from a per-process counter.
+    ///
+    /// The counter restarts with the process, so prefer
+    /// [`Anonymous`](Self::Anonymous) for nodes that are persisted.
     Blank(u64),
+
+    /// An anonymous blank node, identified by a 128-bit ID that is unique
+    /// across processes. The ID is stored with the node, so the node and the
+    /// [`TripleId`](crate::TripleId)s of its triples survive a reopen.
+    Anonymous([u8; 16]),
 }
 
 impl NodeId {
@@ -114,6 +133,49 @@ impl NodeId {
         Self::Blank(id)
     }
 
+    /// Creates a new `Anonymous` node, distinct from every other node.
+    ///
+    /// IDs combine a per-process random key with a counter, so they never
+    /// repeat within a process and collide across processes with negligible
+    /// probability. Unlike [`blank`](Self::blank), nodes created before a
+    /// restart cannot be confused with nodes created after it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aingle_graph::NodeId;
+    ///
+    /// assert_ne!(NodeId::anonymous(), NodeId::anonymous());
+    /// ```
+    pub fn anonymous() -> Self {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        use std::sync::OnceLock;
+        static KEY: OnceLock<[u8; 32]> = OnceLock::new();
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        let key = KEY.get_or_init(|| {
+            let mut seed = blake3::Hasher::new();
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default();
+            seed.update(&now.as_nanos().to_le_bytes());
+            seed.update(&std::process::id().to_le_bytes());
+            seed.update(&(&COUNTER as *const AtomicU64 as usize).to_le_bytes());
+            *seed.finalize().as_bytes()
+        });
+        let n = COUNTER.fetch_add(1, Ordering::SeqCst);
+        let hash = blake3::keyed_hash(key, &n.to_le_bytes());
+
+        let mut id = [0u8; 16];
+        id.copy_from_slice(&hash.as_bytes()[..16]);
+        Self::Anonymous(id)
+    }
+
+    /// Creates an `Anonymous` node with a specific ID.
+    pub fn anonymous_with_id(id: [u8; 16]) -> Self {
+        Self::Anonymous(id)
+    }
+
     /// Returns `true` if this is a `Named` node.
     pub fn is_named(&self) -> bool {
         matches!(self, Self::Named(_))
@@ -124,9 +186,14 @@ impl NodeId {
         matches!(self, Self::Hash(_))
     }
 
-    /// Returns `true` if this is a `Blank` node.
+    /// Returns `true` if this is a blank node, either `Blank` or `Anonymous`.
     pub fn is_blank(&self) -> bool {
-        matches!(self, Self::Blank(_))
+        matches!(self, Self::Blank(_) | Self::Anonymous(_))
+    }
+
+    /// Returns `true` if this is an `Anonymous` node.
+    pub fn is_anonymous(&self) -> bool {
+        matches!(self, Self::Anonymous(_))
     }
 
     /// Returns the name if this is a `Named` node.
@@ -145,6 +212,25 @@ impl NodeId {
         }
     }
 
+    /// For blank nodes, returns a label that identifies the node for as long as
+    /// it is stored: `b<n>` for `Blank` and `a<hex>` for `Anonymous` nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aingle_graph::NodeId;
+    ///
+    /// assert_eq!(NodeId::blank_with_id(7).blank_label().as_deref(), Some("b7"));
+    /// assert_eq!(NodeId::named("user:alice").blank_label(), None);
+    /// ```
+    pub fn blank_label(&self) -> Option<String> {
+        match self {
+            Self::Blank(id) => Some(format!("b{}", id)),
+            Self::Anonymous(id) => Some(format!("a{}", hex::encode(id))),
+            _ => None,
+        }
+    }
+
     /// For `Named` nodes, returns the namespace prefix (the part before the first colon).
     ///
     /// # Examples
@@ -204,6 +290,7 @@ impl fmt::Display for NodeId {
             Self::Named(name) => write!(f, "<{}>", name),
             Self::Hash(hash) => write!(f, "_:hash:{}", hex::encode(&hash[..8])),
             Self::Blank(id) => write!(f, "_:b{}", id),
+            Self::Anonymous(id) => write!(f, "_:a{}", hex::encode(id)),
         }
     }
 }
@@ -269,6 +356,23 @@ mod tests {
         assert_ne!(node1, node2); // Each blank node is unique
     }
 
+    #[test]
+    fn test_anonymous_node() {
+        let ids: std::collections::HashSet<_> = (0..1000).map(|_| NodeId::anonymous()).collect();
+        assert_eq!(ids.len(), 1000);
+
+        let node = NodeId::anonymous_with_id([0xab; 16]);
+        assert!(node.is_anonymous());
+        assert!(node.is_blank());
+        assert!(!NodeId::blank().is_anonymous());
+        assert_eq!(node.blank_label(), Some(format!("a{}", "ab".repeat(16))));
+        assert_eq!(format!("{}", node), format!("_:a{}", "ab".repeat(16)));
+
+        // The ID is what is stored, so it survives a round trip
+        let restored = NodeId::from_storage_bytes(&node.to_bytes()).unwrap();
+        assert_eq!(restored, node);
+    }
+
     #[test]
     fn test_display() {
         let named = NodeId::named("user:alice");
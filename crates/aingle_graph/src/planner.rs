@@ -0,0 +1,635 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Planning and execution of multi-pattern queries.
+//!
+//! Patterns share [`Var`]s to express joins, e.g. "people who work at org X
+//! and live in city Y". The planner orders the patterns by estimated result
+//! size, using the per-predicate cardinalities in [`GraphStats`], and the
+//! executor joins them with index nested loops.
+//!
+//! Solutions are rows of values laid out by a shared variable list, so a join
+//! on a set of variables is a join on a set of column positions.
+
+use crate::store::StoreView;
+use crate::{
+    GraphStats, GraphStore, IndexKind, IndexType, NodeId, Predicate, Result, TriplePattern, Value,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A named query variable, shared between patterns to join them.
+///
+/// # Examples
+///
+/// ```
+/// use aingle_graph::Var;
+///
+/// let person = Var::new("person");
+/// assert_eq!(person.name(), "person");
+/// assert_eq!(person.to_string(), "?person");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Var(String);
+
+impl Var {
+    /// Creates a variable with the given name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+
+    /// Returns the variable's name, without the leading `?`.
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Var {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "?{}", self.0)
+    }
+}
+
+/// One position of a [`JoinPattern`]: a variable or a constant.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Term<T> {
+    /// Matches anything and binds the variable, or must equal its binding.
+    Var(Var),
+    /// Matches only this constant.
+    Const(T),
+}
+
+impl<T> Term<T> {
+    /// Returns the variable, if this position is one.
+    pub fn var(&self) -> Option<&Var> {
+        match self {
+            Self::Var(var) => Some(var),
+            Self::Const(_) => None,
+        }
+    }
+}
+
+impl<T> From<Var> for Term<T> {
+    fn from(var: Var) -> Self {
+        Self::Var(var)
+    }
+}
+
+impl<T> From<&Var> for Term<T> {
+    fn from(var: &Var) -> Self {
+        Self::Var(var.clone())
+    }
+}
+
+impl From<NodeId> for Term<NodeId> {
+    fn from(node: NodeId) -> Self {
+        Self::Const(node)
+    }
+}
+
+impl From<Predicate> for Term<Predicate> {
+    fn from(predicate: Predicate) -> Self {
+        Self::Const(predicate)
+    }
+}
+
+impl From<Value> for Term<Value> {
+    fn from(value: Value) -> Self {
+        Self::Const(value)
+    }
+}
+
+impl From<NodeId> for Term<Value> {
+    fn from(node: NodeId) -> Self {
+        Self::Const(Value::Node(node))
+    }
+}
+
+/// A triple pattern whose positions may be variables.
+///
+/// Variables bind to [`Value`]s: subjects as [`Value::Node`], and predicates
+/// as a named [`Value::Node`] of the predicate's URI, so a variable can join a
+/// subject with an object.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JoinPattern {
+    /// The subject position.
+    pub subject: Term<NodeId>,
+    /// The predicate position.
+    pub predicate: Term<Predicate>,
+    /// The object position.
+    pub object: Term<Value>,
+}
+
+impl JoinPattern {
+    /// Creates a pattern from its three positions.
+    pub fn new(
+        subject: impl Into<Term<NodeId>>,
+        predicate: impl Into<Term<Predicate>>,
+        object: impl Into<Term<Value>>,
+    ) -> Self {
+        Self {
+            subject: subject.into(),
+            predicate: predicate.into(),
+            object: object.into(),
+        }
+    }
+
+    /// Returns the variables of the pattern, in subject, predicate, object order.
+    pub fn vars(&self) -> impl Iterator<Item = &Var> {
+        [self.subject.var(), self.predicate.var(), self.object.var()]
+            .into_iter()
+            .flatten()
+    }
+}
+
+impl fmt::Display for JoinPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.subject {
+            Term::Var(var) => write!(f, "{} ", var)?,
+            Term::Const(node) => write!(f, "{} ", node)?,
+        }
+        match &self.predicate {
+            Term::Var(var) => write!(f, "{} ", var)?,
+            Term::Const(predicate) => write!(f, "{} ", predicate)?,
+        }
+        match &self.object {
+            Term::Var(var) => write!(f, "{}", var),
+            Term::Const(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// One step of a [`QueryPlan`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanStep {
+    /// Position of the pattern in the query, starting at 0.
+    pub pattern: usize,
+    /// The index used to look up the pattern, or `None` for a full scan or
+    /// a secondary index.
+    pub index: Option<IndexType>,
+    /// The secondary index the object range of a single-pattern query is
+    /// read from, if any.
+    #[serde(default)]
+    pub range_index: Option<IndexKind>,
+    /// Estimated matches per partial solution reaching this step.
+    pub estimate: usize,
+    /// Constant constraints of the pattern, all resolved by the index lookup.
+    pub filters: Vec<String>,
+    /// Variables already bound by earlier steps that the pattern joins on.
+    pub join_vars: Vec<Var>,
+}
+
+/// The plan chosen for a query: join order, index use and pagination.
+///
+/// Its `Display` output is the query's explain text, one step per line.
+/// The plan serializes with serde, e.g. for an explain option on an API.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueryPlan {
+    /// The patterns, in query order.
+    pub patterns: Vec<JoinPattern>,
+    /// The steps, in execution order.
+    pub steps: Vec<PlanStep>,
+    /// Estimated matches of the whole query, before offset and limit.
+    pub estimated_rows: usize,
+    /// Matches skipped before the first returned one.
+    pub offset: usize,
+    /// Maximum number of matches returned.
+    pub limit: Option<usize>,
+}
+
+impl QueryPlan {
+    /// Orders the patterns for execution.
+    ///
+    /// Each step takes the remaining pattern with the smallest estimate, given
+    /// the variables bound so far. Patterns sharing a bound variable go before
+    /// patterns that would produce a cross product.
+    pub fn new(patterns: Vec<JoinPattern>, stats: &GraphStats) -> Self {
+        let mut remaining: Vec<usize> = (0..patterns.len()).collect();
+        let mut bound: Vec<Var> = Vec::new();
+        let mut steps = Vec::with_capacity(patterns.len());
+
+        while !remaining.is_empty() {
+            let (position, estimate) = remaining
+                .iter()
+                .enumerate()
+                .map(|(position, &i)| {
+                    let pattern = &patterns[i];
+                    let connected =
+                        bound.is_empty() || pattern.vars().any(|var| bound.contains(var));
+                    (position, (!connected, estimate(pattern, &bound, stats), i))
+                })
+                .min_by_key(|&(_, key)| key)
+                .map(|(position, (_, estimate, _))| (position, estimate))
+                .unwrap_or((0, 0));
+            let i = remaining.remove(position);
+            let pattern = &patterns[i];
+
+            let mut join_vars: Vec<Var> = Vec::new();
+            for var in pattern.vars() {
+                if bound.contains(var) && !join_vars.contains(var) {
+                    join_vars.push(var.clone());
+                }
+            }
+            steps.push(PlanStep {
+                pattern: i,
+                index: index_for(pattern, &bound),
+                range_index: None,
+                estimate,
+                filters: filters(pattern),
+                join_vars,
+            });
+            for var in pattern.vars() {
+                if !bound.contains(var) {
+                    bound.push(var.clone());
+                }
+            }
+        }
+
+        let estimated_rows = steps
+            .iter()
+            .fold(1usize, |rows, step| rows.saturating_mul(step.estimate));
+        Self {
+            patterns,
+            steps,
+            estimated_rows,
+            offset: 0,
+            limit: None,
+        }
+    }
+
+    /// Records the offset and limit applied to the matches.
+    ///
+    /// Both apply after matching completes, so the total count stays exact.
+    pub fn with_pagination(mut self, offset: usize, limit: Option<usize>) -> Self {
+        self.offset = offset;
+        self.limit = limit;
+        self
+    }
+
+    /// Returns the variables of the query, in order of first appearance.
+    pub fn vars(&self) -> Vec<Var> {
+        let mut vars: Vec<Var> = Vec::new();
+        for var in self.patterns.iter().flat_map(JoinPattern::vars) {
+            if !vars.contains(var) {
+                vars.push(var.clone());
+            }
+        }
+        vars
+    }
+
+    /// Runs the plan against a store, one index nested-loop join per step.
+    ///
+    /// Every step reads the same consistent view of the store.
+    pub fn execute(&self, store: &GraphStore) -> Result<Solutions> {
+        self.execute_in(&store.view()?)
+    }
+
+    /// Runs the plan against a view of a store.
+    pub(crate) fn execute_in(&self, store: &StoreView<'_>) -> Result<Solutions> {
+        let vars = self.vars();
+        let mut rows: Vec<Vec<Option<Value>>> = vec![vec![None; vars.len()]];
+
+        for step in &self.steps {
+            let pattern = &self.patterns[step.pattern];
+            let mut joined = Vec::new();
+            for row in &rows {
+                let Some(lookup) = lookup_pattern(pattern, &vars, row) else {
+                    continue;
+                };
+                for triple in store.find(lookup)? {
+                    let mut extended = row.clone();
+                    let matched = bind(&mut extended, &vars, &pattern.subject, || {
+                        Value::Node(triple.subject.clone())
+                    }) && bind(&mut extended, &vars, &pattern.predicate, || {
+                        predicate_value(&triple.predicate)
+                    }) && bind(&mut extended, &vars, &pattern.object, || {
+                        triple.object.clone()
+                    });
+                    if matched {
+                        joined.push(extended);
+                    }
+                }
+            }
+            rows = joined;
+            if rows.is_empty() {
+                break;
+            }
+        }
+
+        // Every variable appears in some pattern, so complete rows are fully bound
+        let rows = rows
+            .into_iter()
+            .map(|row| row.into_iter().flatten().collect())
+            .collect();
+        Ok(Solutions { vars, rows })
+    }
+}
+
+impl fmt::Display for QueryPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (n, step) in self.steps.iter().enumerate() {
+            let index = match (step.range_index, step.index) {
+                (Some(IndexKind::NumericObject), _) => "numeric_object index",
+                (None, Some(IndexType::SPO)) => "SPO",
+                (None, Some(IndexType::POS)) => "POS",
+                (None, Some(IndexType::OSP)) => "OSP",
+                (None, None) => "scan",
+            };
+            write!(
+                f,
+                "{}. #{} {} via {} (est. {})",
+                n + 1,
+                step.pattern,
+                self.patterns[step.pattern],
+                index,
+                step.estimate
+            )?;
+            if !step.filters.is_empty() {
+                write!(f, " filter {}", step.filters.join(", "))?;
+            }
+            if !step.join_vars.is_empty() {
+                let vars: Vec<String> = step.join_vars.iter().map(Var::to_string).collect();
+                write!(f, " join on {}", vars.join(", "))?;
+            }
+            writeln!(f)?;
+        }
+
+        write!(f, "est. {} rows", self.estimated_rows)?;
+        if self.offset > 0 || self.limit.is_some() {
+            write!(f, "; offset {}", self.offset)?;
+            if let Some(limit) = self.limit {
+                write!(f, ", limit {}", limit)?;
+            }
+            write!(f, " after matching")?;
+        }
+        writeln!(f)
+    }
+}
+
+/// The solutions of a multi-pattern query.
+///
+/// Each row holds one value per variable, in the order of [`vars`](Self::vars).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Solutions {
+    vars: Vec<Var>,
+    rows: Vec<Vec<Value>>,
+}
+
+impl Solutions {
+    /// Returns the variables, in column order.
+    pub fn vars(&self) -> &[Var] {
+        &self.vars
+    }
+
+    /// Returns the rows, each with one value per variable.
+    pub fn rows(&self) -> &[Vec<Value>] {
+        &self.rows
+    }
+
+    /// Returns the column of a variable, if the query binds it.
+    pub fn column(&self, var: &Var) -> Option<usize> {
+        self.vars.iter().position(|v| v == var)
+    }
+
+    /// Returns the value of a variable in a row.
+    pub fn get(&self, row: usize, var: &Var) -> Option<&Value> {
+        let column = self.column(var)?;
+        self.rows.get(row)?.get(column)
+    }
+
+    /// Returns the number of solutions.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns `true` if there are no solutions.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Skips `offset` rows and keeps at most `limit` of the rest.
+    pub(crate) fn paginate(&mut self, offset: usize, limit: Option<usize>) {
+        self.rows.drain(..offset.min(self.rows.len()));
+        if let Some(limit) = limit {
+            self.rows.truncate(limit);
+        }
+    }
+}
+
+/// Value a predicate binds to.
+fn predicate_value(predicate: &Predicate) -> Value {
+    Value::Node(NodeId::named(predicate.as_str()))
+}
+
+/// Whether a position is a constant or a variable bound by an earlier step.
+fn is_fixed<T>(term: &Term<T>, bound: &[Var]) -> bool {
+    match term {
+        Term::Var(var) => bound.contains(var),
+        Term::Const(_) => true,
+    }
+}
+
+/// The index a lookup of `pattern` uses once `bound` are known.
+fn index_for(pattern: &JoinPattern, bound: &[Var]) -> Option<IndexType> {
+    match (
+        is_fixed(&pattern.subject, bound),
+        is_fixed(&pattern.predicate, bound),
+        is_fixed(&pattern.object, bound),
+    ) {
+        (true, _, false) | (true, true, true) => Some(IndexType::SPO),
+        (false, true, _) => Some(IndexType::POS),
+        (_, false, true) => Some(IndexType::OSP),
+        (false, false, false) => None,
+    }
+}
+
+/// Constant constraints of a pattern, as `position = constant`.
+fn filters(pattern: &JoinPattern) -> Vec<String> {
+    let mut filters = Vec::new();
+    if let Term::Const(node) = &pattern.subject {
+        filters.push(format!("subject = {}", node));
+    }
+    if let Term::Const(predicate) = &pattern.predicate {
+        filters.push(format!("predicate = {}", predicate));
+    }
+    if let Term::Const(value) = &pattern.object {
+        filters.push(format!("object = {}", value));
+    }
+    filters
+}
+
+/// Estimated matches of `pattern` for one partial solution binding `bound`.
+///
+/// A fixed subject or object divides the predicate's triples by its distinct
+/// subjects or objects; without a constant predicate the graph totals are used.
+fn estimate(pattern: &JoinPattern, bound: &[Var], stats: &GraphStats) -> usize {
+    let (triples, subjects, objects) = match &pattern.predicate {
+        Term::Const(predicate) => match stats.predicates.get(predicate) {
+            Some(p) => (p.triple_count, p.subject_count, p.object_count),
+            // A predicate that is not in the graph matches nothing
+            None => return 0,
+        },
+        Term::Var(_) => (stats.triple_count, stats.subject_count, stats.object_count),
+    };
+
+    let mut estimate = triples;
+    if is_fixed(&pattern.subject, bound) {
+        estimate = estimate.div_ceil(subjects.max(1));
+    }
+    if is_fixed(&pattern.object, bound) {
+        estimate = estimate.div_ceil(objects.max(1));
+    }
+    estimate
+}
+
+/// Binds a position to the value it matched, or checks the existing binding.
+fn bind<T>(
+    row: &mut [Option<Value>],
+    vars: &[Var],
+    term: &Term<T>,
+    value: impl FnOnce() -> Value,
+) -> bool {
+    let Term::Var(var) = term else {
+        // Constants were applied by the index lookup
+        return true;
+    };
+    let Some(column) = vars.iter().position(|v| v == var) else {
+        return false;
+    };
+    let value = value();
+    match &row[column] {
+        Some(existing) => *existing == value,
+        None => {
+            row[column] = Some(value);
+            true
+        }
+    }
+}
+
+/// The index lookup for a pattern under a partial solution, or `None` if a
+/// bound value cannot appear in its position (e.g. a literal as subject).
+fn lookup_pattern(
+    pattern: &JoinPattern,
+    vars: &[Var],
+    row: &[Option<Value>],
+) -> Option<TriplePattern> {
+    let binding = |var: &Var| {
+        let column = vars.iter().position(|v| v == var)?;
+        row[column].clone()
+    };
+    let mut lookup = TriplePattern::any();
+
+    match &pattern.subject {
+        Term::Const(node) => lookup.subject = Some(node.clone()),
+        Term::Var(var) => match binding(var) {
+            Some(Value::Node(node)) => lookup.subject = Some(node),
+            Some(_) => return None,
+            None => {}
+        },
+    }
+    match &pattern.predicate {
+        Term::Const(predicate) => lookup.predicate = Some(predicate.clone()),
+        Term::Var(var) => match binding(var) {
+            Some(Value::Node(NodeId::Named(uri))) => lookup.predicate = Some(Predicate::named(uri)),
+            Some(_) => return None,
+            None => {}
+        },
+    }
+    match &pattern.object {
+        Term::Const(value) => lookup.object = Some(value.clone()),
+        Term::Var(var) => lookup.object = binding(var),
+    }
+
+    Some(lookup)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PredicateStats;
+
+    fn stats(predicates: &[(&str, usize, usize, usize)]) -> GraphStats {
+        GraphStats {
+            triple_count: predicates.iter().map(|p| p.1).sum(),
+            subject_count: 100,
+            object_count: 100,
+            predicates: predicates
+                .iter()
+                .map(|&(name, triple_count, subject_count, object_count)| {
+                    (
+                        Predicate::named(name),
+                        PredicateStats {
+                            triple_count,
+                            subject_count,
+                            object_count,
+                        },
+                    )
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_estimate_divides_by_distinct_values() {
+        let stats = stats(&[("works_at", 100, 100, 4)]);
+        let person = Var::new("person");
+
+        let open = JoinPattern::new(&person, Predicate::named("works_at"), Var::new("org"));
+        assert_eq!(estimate(&open, &[], &stats), 100);
+
+        let by_org = JoinPattern::new(
+            &person,
+            Predicate::named("works_at"),
+            NodeId::named("org:acme"),
+        );
+        assert_eq!(estimate(&by_org, &[], &stats), 25);
+
+        // Once ?person is bound, each person has one employer
+        assert_eq!(estimate(&open, &[person], &stats), 1);
+
+        let unknown = JoinPattern::new(Var::new("s"), Predicate::named("missing"), Var::new("o"));
+        assert_eq!(estimate(&unknown, &[], &stats), 0);
+    }
+
+    #[test]
+    fn test_plan_avoids_cross_products() {
+        let stats = stats(&[
+            ("a", 10, 10, 10),
+            ("b", 1000, 1000, 1000),
+            ("c", 20, 20, 20),
+        ]);
+        let (x, y, z, w) = (Var::new("x"), Var::new("y"), Var::new("z"), Var::new("w"));
+        let plan = QueryPlan::new(
+            vec![
+                JoinPattern::new(&x, Predicate::named("a"), &y),
+                JoinPattern::new(&y, Predicate::named("b"), &z),
+                JoinPattern::new(&w, Predicate::named("c"), &w),
+            ],
+            &stats,
+        );
+
+        // "c" is cheaper than "b" but shares no variable with "a"
+        let order: Vec<usize> = plan.steps.iter().map(|s| s.pattern).collect();
+        assert_eq!(order, vec![0, 1, 2]);
+        assert_eq!(plan.steps[1].join_vars, vec![y]);
+        assert_eq!(plan.steps[1].index, Some(IndexType::SPO));
+    }
+
+    #[test]
+    fn test_solutions_paginate() {
+        let x = Var::new("x");
+        let mut solutions = Solutions {
+            vars: vec![x.clone()],
+            rows: (0..5).map(|i| vec![Value::integer(i)]).collect(),
+        };
+
+        solutions.paginate(1, Some(2));
+        assert_eq!(solutions.len(), 2);
+        assert_eq!(solutions.get(0, &x), Some(&Value::integer(1)));
+        assert_eq!(solutions.get(1, &x), Some(&Value::integer(2)));
+        assert_eq!(solutions.get(0, &Var::new("y")), None);
+    }
+}
@@ -6,7 +6,15 @@
 //! This module provides a `QueryBuilder` for pattern matching and a `TraversalBuilder`
 //! for graph traversal.
 
-use crate::{GraphStore, NodeId, Predicate, Result, Triple, Value};
+use crate::planner::{JoinPattern, QueryPlan, Solutions, Term, Var};
+use crate::store::StoreView;
+use crate::{Error, GraphStore, NodeId, Predicate, Result, Triple, TripleMeta, Value};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::ops::{Bound, RangeBounds};
+use std::time::Instant;
 
 /// A pattern for matching `(Subject, Predicate, Object)` triples.
 ///
@@ -168,6 +176,180 @@ impl TriplePattern {
     }
 }
 
+/// Constraints on who asserted a triple, when, and how confidently.
+///
+/// A triple matches if at least one of its assertions satisfies every
+/// constraint that is set. Assertions without a confidence score never
+/// satisfy a minimum confidence.
+///
+/// # Examples
+///
+/// ```
+/// use aingle_graph::{NodeId, ProvenanceFilter, TripleMeta};
+///
+/// let filter = ProvenanceFilter {
+///     asserted_by: Some(NodeId::named("agent:x")),
+///     min_confidence: Some(0.8),
+///     ..Default::default()
+/// };
+///
+/// let meta = TripleMeta::new()
+///     .with_asserted_by(NodeId::named("agent:x"))
+///     .with_confidence(0.9);
+/// assert!(filter.matches(&meta));
+/// assert!(!filter.matches(&meta.with_confidence(0.5)));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProvenanceFilter {
+    /// The agent that made the assertion.
+    pub asserted_by: Option<NodeId>,
+    /// The lowest accepted confidence score.
+    pub min_confidence: Option<f32>,
+    /// The assertion time range, including the start and excluding the end.
+    pub asserted_between: Option<(DateTime<Utc>, DateTime<Utc>)>,
+}
+
+impl ProvenanceFilter {
+    /// Returns `true` if no constraint is set.
+    pub fn is_empty(&self) -> bool {
+        self.asserted_by.is_none()
+            && self.min_confidence.is_none()
+            && self.asserted_between.is_none()
+    }
+
+    /// Returns `true` if the assertion described by `meta` satisfies the filter.
+    pub fn matches(&self, meta: &TripleMeta) -> bool {
+        self.accepts(meta.asserted_by.as_ref(), meta.asserted_at, meta.confidence)
+    }
+
+    pub(crate) fn accepts(
+        &self,
+        asserted_by: Option<&NodeId>,
+        asserted_at: DateTime<Utc>,
+        confidence: Option<f64>,
+    ) -> bool {
+        if let Some(ref agent) = self.asserted_by {
+            if asserted_by != Some(agent) {
+                return false;
+            }
+        }
+        if let Some(min) = self.min_confidence {
+            // Compared at f32 precision so `0.8` accepts a stored `0.8`
+            if !confidence.is_some_and(|c| c as f32 >= min) {
+                return false;
+            }
+        }
+        if let Some((start, end)) = self.asserted_between {
+            if asserted_at < start || asserted_at >= end {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Describes the constraints, for query plans.
+    pub(crate) fn describe(&self) -> Vec<String> {
+        let mut filters = Vec::new();
+        if let Some(ref agent) = self.asserted_by {
+            filters.push(format!("asserted_by = {}", agent));
+        }
+        if let Some(min) = self.min_confidence {
+            filters.push(format!("confidence >= {}", min));
+        }
+        if let Some((start, end)) = self.asserted_between {
+            filters.push(format!(
+                "asserted_at in [{}, {})",
+                start.to_rfc3339(),
+                end.to_rfc3339()
+            ));
+        }
+        filters
+    }
+}
+
+/// A numeric range objects must fall in, set by
+/// [`QueryBuilder::object_range`].
+#[derive(Debug, Clone)]
+pub(crate) struct ObjectRange {
+    start: Bound<Value>,
+    end: Bound<Value>,
+}
+
+impl ObjectRange {
+    fn new(range: impl RangeBounds<Value>) -> Self {
+        Self {
+            start: range.start_bound().cloned(),
+            end: range.end_bound().cloned(),
+        }
+    }
+
+    /// Rejects bounds that are not an `Integer` or a `Float`.
+    fn check(&self) -> Result<()> {
+        let numeric = |value: Option<&Value>| {
+            value.is_none_or(|v| matches!(v, Value::Integer(_) | Value::Float(_)))
+        };
+        if numeric(self.start()) && numeric(self.end()) {
+            Ok(())
+        } else {
+            Err(Error::Query(
+                "object_range bounds must be integers or floats".into(),
+            ))
+        }
+    }
+
+    /// Returns `true` if `value` is an `Integer` or `Float` within the range.
+    ///
+    /// Compared exactly when both are integers, otherwise as floats.
+    pub(crate) fn contains(&self, value: &Value) -> bool {
+        if !matches!(value, Value::Integer(_) | Value::Float(_)) {
+            return false;
+        }
+        let above_start = match &self.start {
+            Bound::Included(start) => compare_numbers(value, start) != Ordering::Less,
+            Bound::Excluded(start) => compare_numbers(value, start) == Ordering::Greater,
+            Bound::Unbounded => true,
+        };
+        let below_end = match &self.end {
+            Bound::Included(end) => compare_numbers(value, end) != Ordering::Greater,
+            Bound::Excluded(end) => compare_numbers(value, end) == Ordering::Less,
+            Bound::Unbounded => true,
+        };
+        above_start && below_end
+    }
+
+    /// The value of the start bound, whether inclusive or not.
+    pub(crate) fn start(&self) -> Option<&Value> {
+        match &self.start {
+            Bound::Included(value) | Bound::Excluded(value) => Some(value),
+            Bound::Unbounded => None,
+        }
+    }
+
+    /// The value of the end bound, whether inclusive or not.
+    pub(crate) fn end(&self) -> Option<&Value> {
+        match &self.end {
+            Bound::Included(value) | Bound::Excluded(value) => Some(value),
+            Bound::Unbounded => None,
+        }
+    }
+
+    /// Describes the bounds, for query plans.
+    fn describe(&self) -> Vec<String> {
+        let mut filters = Vec::new();
+        match &self.start {
+            Bound::Included(start) => filters.push(format!("?o >= {}", start)),
+            Bound::Excluded(start) => filters.push(format!("?o > {}", start)),
+            Bound::Unbounded => {}
+        }
+        match &self.end {
+            Bound::Included(end) => filters.push(format!("?o <= {}", end)),
+            Bound::Excluded(end) => filters.push(format!("?o < {}", end)),
+            Bound::Unbounded => {}
+        }
+        filters
+    }
+}
+
 /// The result of a query execution.
 ///
 /// Contains the matched triples along with metadata about the result set,
@@ -233,10 +415,57 @@ impl QueryResult {
     }
 }
 
+/// The key [`QueryBuilder::order_by`] sorts matches by.
+///
+/// Matches with equal keys are ordered by [`TripleId`](crate::TripleId).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OrderKey {
+    /// The subject, in the key order of the SPO index.
+    Subject,
+    /// The object, in [`Value::sort_key`] order, which is the key order of
+    /// the OSP index.
+    Object,
+    /// The order triples were inserted in; see [`GraphStore::sequence`].
+    InsertionTime,
+}
+
+/// The direction of an ordering.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SortOrder {
+    /// Smallest key first.
+    #[default]
+    Asc,
+    /// Largest key first.
+    Desc,
+}
+
+/// A position in a triple, for [`QueryBuilder::count_distinct`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Component {
+    /// The subject.
+    Subject,
+    /// The predicate.
+    Predicate,
+    /// The object.
+    Object,
+}
+
+/// Measurements from [`QueryBuilder::execute_with_stats`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryStats {
+    /// The plan the query ran with.
+    pub plan: QueryPlan,
+    /// Triples read from the store. Unordered queries read every match
+    /// before offset and limit apply; ordered ones may read fewer.
+    pub rows_examined: usize,
+    /// Execution time in microseconds.
+    pub elapsed_micros: u64,
+}
+
 /// A builder for constructing and executing queries against a [`GraphStore`].
 ///
 /// Provides a fluent API for building pattern-based queries with optional
-/// pagination through limit and offset.
+/// ordering, deduplication and pagination through limit and offset.
 ///
 /// # Examples
 ///
@@ -292,21 +521,76 @@ impl QueryResult {
 /// # Ok(())
 /// # }
 /// ```
+///
+/// Multi-pattern query joining on shared variables:
+///
+/// ```
+/// use aingle_graph::{GraphDB, Triple, NodeId, Predicate, Value, Var};
+///
+/// # fn main() -> Result<(), aingle_graph::Error> {
+/// let db = GraphDB::memory()?;
+///
+/// db.insert(Triple::link(
+///     NodeId::named("user:alice"),
+///     Predicate::works_at(),
+///     NodeId::named("org:acme"),
+/// ))?;
+/// db.insert(Triple::new(
+///     NodeId::named("user:alice"),
+///     Predicate::located_in(),
+///     Value::literal("Lima"),
+/// ))?;
+///
+/// let person = Var::new("person");
+/// let solutions = db.query()
+///     .pattern(&person, Predicate::works_at(), NodeId::named("org:acme"))
+///     .pattern(&person, Predicate::located_in(), Value::literal("Lima"))
+///     .solve()?;
+///
+/// assert_eq!(solutions.len(), 1);
+/// assert_eq!(
+///     solutions.get(0, &person),
+///     Some(&Value::Node(NodeId::named("user:alice")))
+/// );
+/// # Ok(())
+/// # }
+/// ```
 pub struct QueryBuilder<'a> {
-    store: &'a GraphStore,
+    source: Source<'a>,
     pattern: TriplePattern,
+    provenance: ProvenanceFilter,
+    object_range: Option<ObjectRange>,
+    patterns: Vec<JoinPattern>,
+    order: Option<(OrderKey, SortOrder)>,
+    distinct: bool,
     limit: Option<usize>,
     offset: usize,
+    exact: bool,
 }
 
 impl<'a> QueryBuilder<'a> {
     /// Creates a new `QueryBuilder` for a given `GraphStore`.
     pub fn new(store: &'a GraphStore) -> Self {
+        Self::with_source(Source::Store(store))
+    }
+
+    /// Creates a `QueryBuilder` that reads a consistent view of a store.
+    pub(crate) fn on_view(view: &'a StoreView<'a>) -> Self {
+        Self::with_source(Source::View(view))
+    }
+
+    fn with_source(source: Source<'a>) -> Self {
         Self {
-            store,
+            source,
             pattern: TriplePattern::default(),
+            provenance: ProvenanceFilter::default(),
+            object_range: None,
+            patterns: Vec::new(),
+            order: None,
+            distinct: false,
             limit: None,
             offset: 0,
+            exact: false,
         }
     }
 
@@ -343,6 +627,166 @@ impl<'a> QueryBuilder<'a> {
         self
     }
 
+    /// Only matches triples asserted by the given agent.
+    ///
+    /// Provenance filters are checked during index iteration, before triples
+    /// are read from storage. A triple matches if one of its assertions
+    /// satisfies all of them; see [`ProvenanceFilter`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aingle_graph::{GraphDB, Triple, TripleMeta, NodeId};
+    /// use chrono::{Duration, Utc};
+    ///
+    /// # fn main() -> Result<(), aingle_graph::Error> {
+    /// let db = GraphDB::memory()?;
+    /// db.insert_with_meta(
+    ///     Triple::literal("sensor:1", "reading", "42"),
+    ///     TripleMeta::new()
+    ///         .with_asserted_by(NodeId::named("agent:x"))
+    ///         .with_confidence(0.9),
+    /// )?;
+    ///
+    /// // Everything agent:x asserted last week with confidence >= 0.8
+    /// let now = Utc::now();
+    /// let results = db.query()
+    ///     .asserted_by(NodeId::named("agent:x"))
+    ///     .min_confidence(0.8)
+    ///     .asserted_between(now - Duration::weeks(1), now + Duration::seconds(1))
+    ///     .execute()?;
+    ///
+    /// assert_eq!(results.len(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn asserted_by(mut self, agent: NodeId) -> Self {
+        self.provenance.asserted_by = Some(agent);
+        self
+    }
+
+    /// Only matches triples asserted with at least the given confidence.
+    pub fn min_confidence(mut self, confidence: f32) -> Self {
+        self.provenance.min_confidence = Some(confidence);
+        self
+    }
+
+    /// Only matches triples asserted at or after `start` and before `end`.
+    pub fn asserted_between(mut self, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        self.provenance.asserted_between = Some((start, end));
+        self
+    }
+
+    /// Only matches triples whose object is an `Integer` or `Float` within
+    /// `range`. Objects are compared exactly when both are integers,
+    /// otherwise as floats.
+    ///
+    /// When only the predicate is constrained and it has a ready
+    /// [`IndexKind::NumericObject`](crate::IndexKind::NumericObject) index,
+    /// the matches are read from that index; see
+    /// [`GraphDB::create_index`](crate::GraphDB::create_index). Otherwise
+    /// every triple matching the pattern is read and checked. Ordered
+    /// queries with a range sort the matches in memory.
+    ///
+    /// # Errors
+    ///
+    /// Running the query returns an `Error::Query` if a bound is not an
+    /// `Integer` or a `Float`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aingle_graph::{GraphDB, NodeId, Predicate, Triple, Value};
+    ///
+    /// # fn main() -> Result<(), aingle_graph::Error> {
+    /// let db = GraphDB::memory()?;
+    /// for (sensor, reading) in [("sensor:1", 17.5), ("sensor:2", 42.0), ("sensor:3", 23.0)] {
+    ///     db.insert(Triple::new(
+    ///         NodeId::named(sensor),
+    ///         Predicate::named("reading"),
+    ///         Value::Float(reading),
+    ///     ))?;
+    /// }
+    ///
+    /// let results = db.query()
+    ///     .predicate(Predicate::named("reading"))
+    ///     .object_range(Value::integer(20)..=Value::integer(42))
+    ///     .execute()?;
+    ///
+    /// assert_eq!(results.len(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn object_range(mut self, range: impl RangeBounds<Value>) -> Self {
+        self.object_range = Some(ObjectRange::new(range));
+        self
+    }
+
+    /// Adds a pattern to a multi-pattern query.
+    ///
+    /// Each position is a [`Var`](crate::Var) or a constant; patterns that
+    /// share a variable are joined on it. Run the query with
+    /// [`solve`](Self::solve).
+    pub fn pattern(
+        mut self,
+        subject: impl Into<Term<NodeId>>,
+        predicate: impl Into<Term<Predicate>>,
+        object: impl Into<Term<Value>>,
+    ) -> Self {
+        self.patterns
+            .push(JoinPattern::new(subject, predicate, object));
+        self
+    }
+
+    /// Orders the matches of a single-pattern query by `key`.
+    ///
+    /// When the index chosen for the pattern already iterates in key order
+    /// (OSP or POS for objects, SPO or OSP for subjects), the matches are
+    /// read in that order and only those up to `offset + limit` are read
+    /// from storage. Otherwise they are sorted in memory, keeping only the
+    /// best `offset + limit` of them at a time when a limit is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aingle_graph::{GraphDB, NodeId, OrderKey, Predicate, SortOrder, Triple, Value};
+    ///
+    /// # fn main() -> Result<(), aingle_graph::Error> {
+    /// let db = GraphDB::memory()?;
+    /// for (sensor, reading) in [("sensor:1", 17), ("sensor:2", 42), ("sensor:3", 23)] {
+    ///     db.insert(Triple::new(
+    ///         NodeId::named(sensor),
+    ///         Predicate::named("reading"),
+    ///         Value::integer(reading),
+    ///     ))?;
+    /// }
+    ///
+    /// let top = db.query()
+    ///     .predicate(Predicate::named("reading"))
+    ///     .order_by(OrderKey::Object, SortOrder::Desc)
+    ///     .limit(2)
+    ///     .execute()?;
+    ///
+    /// assert_eq!(top.triples[0].object, Value::integer(42));
+    /// assert_eq!(top.triples[1].object, Value::integer(23));
+    /// assert_eq!(top.total_count, 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn order_by(mut self, key: OrderKey, order: SortOrder) -> Self {
+        self.order = Some((key, order));
+        self
+    }
+
+    /// Drops duplicate triples from the matches of a single-pattern query.
+    ///
+    /// Applies to full triples after pattern matching and before ordering
+    /// and pagination, so `total_count` counts distinct triples.
+    pub fn distinct(mut self) -> Self {
+        self.distinct = true;
+        self
+    }
+
     /// Sets the maximum number of results to return.
     ///
     /// # Examples
@@ -394,9 +838,68 @@ impl<'a> QueryBuilder<'a> {
     }
 
     /// Executes the constructed query.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Error::Query` if the query has patterns added with
+    /// [`pattern`](Self::pattern); those are run by [`solve`](Self::solve).
     pub fn execute(self) -> Result<QueryResult> {
-        let mut triples = self.store.find(self.pattern)?;
-        let total_count = triples.len();
+        self.run().map(|(result, _)| result)
+    }
+
+    /// Executes the query, also returning the number of triples read.
+    fn run(self) -> Result<(QueryResult, usize)> {
+        if !self.patterns.is_empty() {
+            return Err(Error::Query(
+                "multi-pattern queries return solutions, use solve()".into(),
+            ));
+        }
+
+        let (mut triples, total_count, rows_examined) = match (&self.object_range, self.order) {
+            (Some(range), order) => {
+                range.check()?;
+                let (mut triples, rows_examined) = self.source.read(|view| {
+                    let (mut triples, rows_examined) =
+                        view.find_in_range(&self.pattern, &self.provenance, range)?;
+                    if let Some((key, order)) = order {
+                        view.sort(&mut triples, key, order);
+                    }
+                    Ok((triples, rows_examined))
+                })?;
+                if self.distinct {
+                    let mut seen = HashSet::new();
+                    triples.retain(|triple| seen.insert(triple.id()));
+                }
+                let total_count = triples.len();
+                (triples, total_count, rows_examined)
+            }
+            (None, Some((key, order))) => {
+                let window = self.limit.map(|limit| self.offset.saturating_add(limit));
+                let matches = self.source.read(|view| {
+                    view.find_ordered(
+                        &self.pattern,
+                        &self.provenance,
+                        key,
+                        order,
+                        self.distinct,
+                        window,
+                    )
+                })?;
+                (matches.triples, matches.total_count, matches.rows_examined)
+            }
+            (None, None) => {
+                let mut triples = self
+                    .source
+                    .read(|view| view.find_with_provenance(self.pattern, &self.provenance))?;
+                let rows_examined = triples.len();
+                if self.distinct {
+                    let mut seen = HashSet::new();
+                    triples.retain(|triple| seen.insert(triple.id()));
+                }
+                let total_count = triples.len();
+                (triples, total_count, rows_examined)
+            }
+        };
 
         // Apply offset
         if self.offset > 0 {
@@ -407,21 +910,389 @@ impl<'a> QueryBuilder<'a> {
             }
         }
 
-        // Apply limit
+        // Apply limit; an ordered query only returns matches up to it
         let has_more = if let Some(limit) = self.limit {
-            let exceeded = triples.len() > limit;
             triples.truncate(limit);
-            exceeded
+            total_count > self.offset.saturating_add(limit)
         } else {
             false
         };
 
-        Ok(QueryResult {
+        let result = QueryResult {
             triples,
             total_count,
             has_more,
+        };
+        Ok((result, rows_examined))
+    }
+
+    /// Executes the constructed query, also reporting its plan, the triples
+    /// it examined and the time it took.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aingle_graph::{GraphDB, IndexType, NodeId};
+    ///
+    /// # fn main() -> Result<(), aingle_graph::Error> {
+    /// let db = GraphDB::memory()?;
+    ///
+    /// let (results, stats) = db.query()
+    ///     .subject(NodeId::named("user:alice"))
+    ///     .execute_with_stats()?;
+    ///
+    /// assert_eq!(stats.plan.steps[0].index, Some(IndexType::SPO));
+    /// assert_eq!(stats.rows_examined, results.total_count);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn execute_with_stats(self) -> Result<(QueryResult, QueryStats)> {
+        let plan = self.explain()?;
+        let started = Instant::now();
+        let (result, rows_examined) = self.run()?;
+
+        let stats = QueryStats {
+            plan,
+            rows_examined,
+            elapsed_micros: started.elapsed().as_micros() as u64,
+        };
+        Ok((result, stats))
+    }
+
+    /// Plans the query without running it.
+    ///
+    /// The plan's `Display` output shows the join order, the index used by
+    /// each step, the constraints it applies, estimated matches and how
+    /// offset and limit are handled. A single-pattern query plans as one step
+    /// with `?s`, `?p` and `?o` for its unconstrained positions; if its
+    /// object range is read from a secondary index, the step names it in
+    /// [`range_index`](crate::PlanStep::range_index).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aingle_graph::{GraphDB, IndexType, Predicate};
+    ///
+    /// # fn main() -> Result<(), aingle_graph::Error> {
+    /// let db = GraphDB::memory()?;
+    ///
+    /// let plan = db.query()
+    ///     .predicate(Predicate::named("has_name"))
+    ///     .limit(10)
+    ///     .explain()?;
+    ///
+    /// assert_eq!(plan.steps[0].index, Some(IndexType::POS));
+    /// assert_eq!(plan.limit, Some(10));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn explain(&self) -> Result<QueryPlan> {
+        let patterns = if self.patterns.is_empty() {
+            let pattern = &self.pattern;
+            vec![JoinPattern {
+                subject: constant_or(pattern.subject.clone(), "s"),
+                predicate: constant_or(pattern.predicate.clone(), "p"),
+                object: constant_or(pattern.object.clone(), "o"),
+            }]
+        } else {
+            self.patterns.clone()
+        };
+        let (stats, range_index) = self.source.read(|view| {
+            let range_index = match &self.object_range {
+                Some(_) => view.range_index(&self.pattern),
+                None => None,
+            };
+            Ok((view.stats(), range_index))
+        })?;
+        let mut plan = QueryPlan::new(patterns, &stats);
+        if self.patterns.is_empty() {
+            let step = &mut plan.steps[0];
+            step.filters.extend(self.provenance.describe());
+            if let Some(range) = &self.object_range {
+                step.filters.extend(range.describe());
+            }
+            if range_index.is_some() {
+                step.index = None;
+                step.range_index = range_index;
+            }
+        }
+        Ok(plan.with_pagination(self.offset, self.limit))
+    }
+
+    /// Makes [`count`](Self::count) and [`count_distinct`](Self::count_distinct)
+    /// iterate the matches even when index statistics could answer them.
+    pub fn exact(mut self) -> Self {
+        self.exact = true;
+        self
+    }
+
+    /// Counts the matches without reading them from the store.
+    ///
+    /// A query constraining only the predicate, with no provenance filter,
+    /// is answered from the predicate's index statistics unless
+    /// [`exact`](Self::exact) is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Error::Query` if the query has patterns added with
+    /// [`pattern`](Self::pattern), a limit, an offset or an object range;
+    /// aggregates cover every match of a single pattern.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aingle_graph::{GraphDB, Triple, NodeId, Predicate, Value};
+    ///
+    /// # fn main() -> Result<(), aingle_graph::Error> {
+    /// let db = GraphDB::memory()?;
+    ///
+    /// for i in 0..5 {
+    ///     db.insert(Triple::new(
+    ///         NodeId::named(format!("user:{}", i)),
+    ///         Predicate::named("has_age"),
+    ///         Value::integer(20 + i),
+    ///     ))?;
+    /// }
+    ///
+    /// assert_eq!(db.query().predicate(Predicate::named("has_age")).count()?, 5);
+    /// assert_eq!(
+    ///     db.query().predicate(Predicate::named("has_age")).sum_objects()?,
+    ///     Value::integer(110)
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn count(self) -> Result<usize> {
+        self.check_aggregate()?;
+        if let Some(predicate) = self.statistics_predicate() {
+            return Ok(self
+                .source
+                .read(|view| view.predicate_stats(predicate))?
+                .triple_count);
+        }
+        self.source
+            .read(|view| view.count_matches(&self.pattern, &self.provenance))
+    }
+
+    /// Counts the distinct values of one component among the matches.
+    ///
+    /// Keys are read from the index when one serving the pattern holds the
+    /// component, otherwise matches are read one at a time. Subject and
+    /// object counts of a query constraining only the predicate come from
+    /// index statistics, as for [`count`](Self::count).
+    ///
+    /// # Errors
+    ///
+    /// As for [`count`](Self::count).
+    pub fn count_distinct(self, component: Component) -> Result<usize> {
+        self.check_aggregate()?;
+        if let Some(predicate) = self.statistics_predicate() {
+            let stats = self.source.read(|view| view.predicate_stats(predicate))?;
+            return Ok(match component {
+                Component::Subject => stats.subject_count,
+                Component::Predicate => usize::from(stats.triple_count > 0),
+                Component::Object => stats.object_count,
+            });
+        }
+        let mut seen: HashSet<Vec<u8>> = HashSet::new();
+        self.source.read(|view| {
+            view.for_each_key(&self.pattern, &self.provenance, component, |key| {
+                if !seen.contains(key) {
+                    seen.insert(key.to_vec());
+                }
+                Ok(())
+            })
+        })?;
+        Ok(seen.len())
+    }
+
+    /// Sums the objects of the matches.
+    ///
+    /// The sum is an `Integer` when every object is one and the total fits
+    /// in an `i64`, otherwise a `Float`. No matches sum to `Integer(0)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Error::Query` if an object is not an `Integer` or a
+    /// `Float`, or as for [`count`](Self::count).
+    pub fn sum_objects(self) -> Result<Value> {
+        let mut integer_sum = Some(0i64);
+        let mut float_sum = 0.0;
+        self.fold_numbers("sum_objects", |value| match value {
+            Value::Integer(n) => {
+                integer_sum = integer_sum.and_then(|sum| sum.checked_add(n));
+                float_sum += n as f64;
+            }
+            other => {
+                integer_sum = None;
+                float_sum += other.as_float().unwrap_or_default();
+            }
+        })?;
+        Ok(match integer_sum {
+            Some(sum) => Value::Integer(sum),
+            None => Value::Float(float_sum),
         })
     }
+
+    /// Averages the objects of the matches, or returns `None` if nothing
+    /// matches.
+    ///
+    /// # Errors
+    ///
+    /// As for [`sum_objects`](Self::sum_objects).
+    pub fn avg_objects(self) -> Result<Option<f64>> {
+        let mut sum = 0.0;
+        let mut count = 0usize;
+        self.fold_numbers("avg_objects", |value| {
+            sum += value.as_float().unwrap_or_default();
+            count += 1;
+        })?;
+        Ok((count > 0).then(|| sum / count as f64))
+    }
+
+    /// Returns the numerically smallest object of the matches, or `None` if
+    /// nothing matches.
+    ///
+    /// # Errors
+    ///
+    /// As for [`sum_objects`](Self::sum_objects).
+    pub fn min_object(self) -> Result<Option<Value>> {
+        self.extreme_object("min_object", Ordering::Less)
+    }
+
+    /// Returns the numerically largest object of the matches, or `None` if
+    /// nothing matches.
+    ///
+    /// # Errors
+    ///
+    /// As for [`sum_objects`](Self::sum_objects).
+    pub fn max_object(self) -> Result<Option<Value>> {
+        self.extreme_object("max_object", Ordering::Greater)
+    }
+
+    /// Keeps the object that compares as `wanted` against every other.
+    fn extreme_object(self, op: &str, wanted: Ordering) -> Result<Option<Value>> {
+        let mut best: Option<Value> = None;
+        self.fold_numbers(op, |value| {
+            if best
+                .as_ref()
+                .is_none_or(|best| compare_numbers(&value, best) == wanted)
+            {
+                best = Some(value);
+            }
+        })?;
+        Ok(best)
+    }
+
+    /// Calls `f` with the object of every match, read from its index key.
+    fn fold_numbers(&self, op: &str, mut f: impl FnMut(Value)) -> Result<()> {
+        self.check_aggregate()?;
+        self.source.read(|view| {
+            view.for_each_key(&self.pattern, &self.provenance, Component::Object, |key| {
+                let value = Value::numeric_from_sort_key(key)
+                    .ok_or_else(|| Error::Query(format!("{op} needs integer or float objects")))?;
+                f(value);
+                Ok(())
+            })
+        })
+    }
+
+    /// Rejects settings that aggregates do not support.
+    fn check_aggregate(&self) -> Result<()> {
+        if !self.patterns.is_empty() {
+            return Err(Error::Query(
+                "aggregates apply to single-pattern queries".into(),
+            ));
+        }
+        if self.limit.is_some() || self.offset > 0 {
+            return Err(Error::Query(
+                "aggregates cover every match, limit and offset do not apply".into(),
+            ));
+        }
+        if self.object_range.is_some() {
+            return Err(Error::Query(
+                "object ranges apply to queries run by execute()".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// The predicate whose index statistics answer this query's counts, if
+    /// only the predicate is constrained and [`exact`](Self::exact) is unset.
+    fn statistics_predicate(&self) -> Option<&Predicate> {
+        let pattern = &self.pattern;
+        if self.exact
+            || !self.provenance.is_empty()
+            || pattern.subject.is_some()
+            || pattern.object.is_some()
+        {
+            return None;
+        }
+        pattern.predicate.as_ref()
+    }
+
+    /// Plans and runs a multi-pattern query.
+    ///
+    /// Limit and offset apply to the solutions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Error::Query` if provenance filters, ordering, `distinct`
+    /// or an object range are set; those apply to single-pattern queries run
+    /// by [`execute`](Self::execute).
+    pub fn solve(self) -> Result<Solutions> {
+        if !self.provenance.is_empty() {
+            return Err(Error::Query(
+                "provenance filters apply to single-pattern queries, use execute()".into(),
+            ));
+        }
+        if self.order.is_some() || self.distinct || self.object_range.is_some() {
+            return Err(Error::Query(
+                "ordering, distinct and object ranges apply to single-pattern queries, use execute()"
+                    .into(),
+            ));
+        }
+        let plan = self.explain()?;
+        let mut solutions = self.source.read(|view| plan.execute_in(view))?;
+        solutions.paginate(self.offset, self.limit);
+        Ok(solutions)
+    }
+}
+
+/// Where a [`QueryBuilder`] reads from.
+enum Source<'a> {
+    /// A store, read under a fresh view for each step of the query.
+    Store(&'a GraphStore),
+    /// A view held by a [`GraphSnapshot`](crate::GraphSnapshot).
+    View(&'a StoreView<'a>),
+}
+
+impl Source<'_> {
+    fn read<T>(&self, f: impl FnOnce(&StoreView<'_>) -> Result<T>) -> Result<T> {
+        match self {
+            Source::Store(store) => f(&store.view()?),
+            Source::View(view) => f(view),
+        }
+    }
+}
+
+/// Compares two numeric values, exactly when both are integers.
+fn compare_numbers(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Integer(a), Value::Integer(b)) => a.cmp(b),
+        _ => a
+            .as_float()
+            .unwrap_or_default()
+            .total_cmp(&b.as_float().unwrap_or_default()),
+    }
+}
+
+/// A pattern position from an optional constraint, a variable when unset.
+fn constant_or<T>(constraint: Option<T>, var: &str) -> Term<T> {
+    match constraint {
+        Some(value) => Term::Const(value),
+        None => Term::Var(Var::new(var)),
+    }
 }
 
 /// A builder for performing graph traversals.
@@ -514,6 +1385,7 @@ impl<'a> TraversalBuilder<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{IndexType, TripleId};
 
     #[test]
     fn test_pattern_matches() {
@@ -573,4 +1445,555 @@ mod tests {
         assert!(!result.is_empty());
         assert_eq!(result.first().unwrap().subject, t1.subject);
     }
+
+    fn filtered_query<'a>(db: &'a crate::GraphDB, pattern: &TriplePattern) -> QueryBuilder<'a> {
+        let mut query = db.query();
+        if let Some(subject) = &pattern.subject {
+            query = query.subject(subject.clone());
+        }
+        if let Some(predicate) = &pattern.predicate {
+            query = query.predicate(predicate.clone());
+        }
+        if let Some(object) = &pattern.object {
+            query = query.object(object.clone());
+        }
+        query
+    }
+
+    #[test]
+    fn test_explain_index_for_each_pattern_shape() {
+        let db = crate::GraphDB::memory().unwrap();
+        let s = || NodeId::named("user:alice");
+        let p = || Predicate::named("has_name");
+        let o = || Value::literal("Alice");
+        db.insert(Triple::new(s(), p(), o())).unwrap();
+
+        let cases = [
+            (
+                TriplePattern::subject(s())
+                    .with_predicate(p())
+                    .with_object(o()),
+                Some(IndexType::SPO),
+            ),
+            (
+                TriplePattern::subject(s()).with_predicate(p()),
+                Some(IndexType::SPO),
+            ),
+            (
+                TriplePattern::predicate(p()).with_object(o()),
+                Some(IndexType::POS),
+            ),
+            (
+                TriplePattern::subject(s()).with_object(o()),
+                Some(IndexType::OSP),
+            ),
+            (TriplePattern::subject(s()), Some(IndexType::SPO)),
+            (TriplePattern::predicate(p()), Some(IndexType::POS)),
+            (TriplePattern::object(o()), Some(IndexType::OSP)),
+            (TriplePattern::any(), None),
+        ];
+
+        for (pattern, index) in cases {
+            let plan = db.explain(pattern.clone()).unwrap();
+            assert_eq!(plan.steps.len(), 1);
+            assert_eq!(plan.steps[0].index, index, "{:?}", pattern);
+            assert_eq!(plan.estimated_rows, 1, "{:?}", pattern);
+
+            let (result, stats) = filtered_query(&db, &pattern).execute_with_stats().unwrap();
+            assert_eq!(result.len(), 1, "{:?}", pattern);
+            assert_eq!(stats.rows_examined, 1, "{:?}", pattern);
+            assert_eq!(stats.plan, plan);
+        }
+    }
+
+    #[test]
+    fn test_explain_output_and_serialization() {
+        let db = crate::GraphDB::memory().unwrap();
+        for i in 0..20 {
+            db.insert(Triple::new(
+                NodeId::named(format!("user:{}", i)),
+                Predicate::named("has_type"),
+                Value::literal("user"),
+            ))
+            .unwrap();
+        }
+
+        let plan = db
+            .query()
+            .predicate(Predicate::named("has_type"))
+            .limit(10)
+            .offset(5)
+            .explain()
+            .unwrap();
+        assert_eq!(plan.steps[0].filters, vec!["predicate = <has_type>"]);
+        assert_eq!(plan.estimated_rows, 20);
+        assert_eq!(
+            plan.to_string(),
+            "1. #0 ?s <has_type> ?o via POS (est. 20) filter predicate = <has_type>\n\
+             est. 20 rows; offset 5, limit 10 after matching\n"
+        );
+
+        let json = serde_json::to_value(&plan).unwrap();
+        assert_eq!(json["steps"][0]["index"], "POS");
+        assert_eq!(json["patterns"][0]["subject"]["var"], "s");
+        let parsed: QueryPlan = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, plan);
+
+        // A full scan examines every triple, however few it returns
+        let (result, stats) = db.query().limit(1).execute_with_stats().unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(stats.rows_examined, 20);
+        assert_eq!(stats.plan.steps[0].index, None);
+        assert!(stats.plan.to_string().contains("via scan"));
+    }
+
+    #[test]
+    fn test_query_by_provenance() {
+        let db = crate::GraphDB::memory().unwrap();
+        let day = chrono::Duration::days(1);
+        let now = Utc::now();
+        let agent = |name: &str| NodeId::named(name);
+        let meta = |name: &str, confidence: f64, days_ago: i32| {
+            TripleMeta::new()
+                .with_asserted_by(agent(name))
+                .with_confidence(confidence)
+                .with_asserted_at(now - day * days_ago)
+        };
+
+        let fact = || Triple::literal("sensor:1", "reading", "42");
+        db.insert_with_meta(fact(), meta("agent:x", 0.5, 2))
+            .unwrap();
+        db.insert_with_meta(fact(), meta("agent:y", 0.8, 20))
+            .unwrap();
+        db.insert_with_meta(
+            Triple::literal("sensor:2", "reading", "7"),
+            meta("agent:x", 0.95, 3),
+        )
+        .unwrap();
+        db.insert(Triple::literal("sensor:3", "reading", "1"))
+            .unwrap();
+
+        let count = |query: QueryBuilder<'_>| query.execute().unwrap().len();
+        assert_eq!(count(db.query()), 3);
+        assert_eq!(count(db.query().asserted_by(agent("agent:x"))), 2);
+        assert_eq!(count(db.query().asserted_by(agent("agent:y"))), 1);
+        assert_eq!(count(db.query().min_confidence(0.8)), 2);
+
+        // All constraints must hold for the same assertion
+        let last_week = db
+            .query()
+            .predicate(Predicate::named("reading"))
+            .asserted_by(agent("agent:x"))
+            .min_confidence(0.8)
+            .asserted_between(now - day * 7, now);
+        assert_eq!(
+            last_week.execute().unwrap().first().unwrap().subject,
+            NodeId::named("sensor:2")
+        );
+        assert_eq!(
+            count(
+                db.query()
+                    .asserted_by(agent("agent:y"))
+                    .asserted_between(now - day * 7, now)
+            ),
+            0
+        );
+
+        let plan = db
+            .query()
+            .asserted_by(agent("agent:x"))
+            .min_confidence(0.8)
+            .explain()
+            .unwrap();
+        assert_eq!(
+            plan.steps[0].filters,
+            vec!["asserted_by = <agent:x>", "confidence >= 0.8"]
+        );
+
+        let x = Var::new("x");
+        let result = db
+            .query()
+            .pattern(&x, Predicate::named("reading"), Value::literal("42"))
+            .min_confidence(0.5)
+            .solve();
+        assert!(matches!(result, Err(Error::Query(_))));
+    }
+
+    fn ordering_db() -> crate::GraphDB {
+        let db = crate::GraphDB::memory().unwrap();
+        for i in 0..60i64 {
+            let object = if i % 4 == 0 {
+                Value::literal(format!("v{}", (i * 7) % 11))
+            } else {
+                Value::integer((i * 37) % 23)
+            };
+            db.insert(Triple::new(
+                NodeId::named(format!("s{}", (i * 5) % 9)),
+                Predicate::named(format!("p{}", i % 3)),
+                object,
+            ))
+            .unwrap();
+        }
+        db
+    }
+
+    /// Sorts and dedupes every match, then paginates.
+    fn naive_ordered(
+        db: &crate::GraphDB,
+        pattern: &TriplePattern,
+        key: OrderKey,
+        order: SortOrder,
+    ) -> Vec<TripleId> {
+        let mut keyed: Vec<(Vec<u8>, TripleId)> = db
+            .find(pattern.clone())
+            .unwrap()
+            .into_iter()
+            .map(|t| {
+                let sort_key = match key {
+                    OrderKey::Subject => t.subject.to_bytes(),
+                    OrderKey::Object => t.object.sort_key(),
+                    OrderKey::InsertionTime => db
+                        .sequence(&t.id())
+                        .unwrap()
+                        .unwrap()
+                        .to_be_bytes()
+                        .to_vec(),
+                };
+                (sort_key, t.id())
+            })
+            .collect();
+        keyed.sort();
+        keyed.dedup();
+        if order == SortOrder::Desc {
+            keyed.reverse();
+        }
+        keyed.into_iter().map(|(_, id)| id).collect()
+    }
+
+    fn ordering_patterns() -> Vec<TriplePattern> {
+        vec![
+            TriplePattern::any(),
+            TriplePattern::predicate(Predicate::named("p1")),
+            TriplePattern::object(Value::integer(19)),
+            TriplePattern::subject(NodeId::named("s4")),
+            TriplePattern::subject(NodeId::named("s4")).with_predicate(Predicate::named("p2")),
+            TriplePattern::predicate(Predicate::named("p0")).with_object(Value::literal("v0")),
+        ]
+    }
+
+    #[test]
+    fn test_order_by_matches_naive_sort() {
+        let db = ordering_db();
+        let keys = [OrderKey::Subject, OrderKey::Object, OrderKey::InsertionTime];
+
+        for pattern in ordering_patterns() {
+            for key in keys {
+                for order in [SortOrder::Asc, SortOrder::Desc] {
+                    let expected = naive_ordered(&db, &pattern, key, order);
+                    assert!(!expected.is_empty(), "{:?}", pattern);
+
+                    let all = filtered_query(&db, &pattern)
+                        .order_by(key, order)
+                        .execute()
+                        .unwrap();
+                    let ids: Vec<TripleId> = all.triples.iter().map(Triple::id).collect();
+                    assert_eq!(ids, expected, "{:?} {:?} {:?}", pattern, key, order);
+                    assert_eq!(all.total_count, expected.len());
+                    assert!(!all.has_more);
+
+                    let page = filtered_query(&db, &pattern)
+                        .order_by(key, order)
+                        .distinct()
+                        .offset(1)
+                        .limit(3)
+                        .execute()
+                        .unwrap();
+                    let ids: Vec<TripleId> = page.triples.iter().map(Triple::id).collect();
+                    let window: Vec<TripleId> = expected.iter().skip(1).take(3).cloned().collect();
+                    assert_eq!(ids, window, "{:?} {:?} {:?}", pattern, key, order);
+                    assert_eq!(page.total_count, expected.len());
+                    assert_eq!(page.has_more, expected.len() > 4);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_top_k_reads_only_the_window() {
+        let db = ordering_db();
+        let natural = [
+            (TriplePattern::any(), OrderKey::Object),
+            (TriplePattern::any(), OrderKey::Subject),
+            (TriplePattern::any(), OrderKey::InsertionTime),
+            (
+                TriplePattern::predicate(Predicate::named("p1")),
+                OrderKey::Object,
+            ),
+            (TriplePattern::object(Value::integer(19)), OrderKey::Subject),
+        ];
+
+        for (pattern, key) in natural {
+            let expected = naive_ordered(&db, &pattern, key, SortOrder::Desc);
+            let (result, stats) = filtered_query(&db, &pattern)
+                .order_by(key, SortOrder::Desc)
+                .limit(2)
+                .execute_with_stats()
+                .unwrap();
+            let ids: Vec<TripleId> = result.triples.iter().map(Triple::id).collect();
+            assert_eq!(ids, expected[..2], "{:?} {:?}", pattern, key);
+            assert_eq!(stats.rows_examined, 2, "{:?} {:?}", pattern, key);
+            assert_eq!(result.total_count, expected.len());
+            assert!(result.has_more);
+        }
+
+        // Subject order under POS needs every match read, then a bounded sort
+        let pattern = TriplePattern::predicate(Predicate::named("p1"));
+        let expected = naive_ordered(&db, &pattern, OrderKey::Subject, SortOrder::Asc);
+        let (result, stats) = filtered_query(&db, &pattern)
+            .order_by(OrderKey::Subject, SortOrder::Asc)
+            .limit(2)
+            .execute_with_stats()
+            .unwrap();
+        let ids: Vec<TripleId> = result.triples.iter().map(Triple::id).collect();
+        assert_eq!(ids, expected[..2]);
+        assert_eq!(stats.rows_examined, expected.len());
+
+        let empty = db
+            .query()
+            .order_by(OrderKey::Object, SortOrder::Asc)
+            .limit(0)
+            .execute()
+            .unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(empty.total_count, 60);
+    }
+
+    #[test]
+    fn test_order_by_respects_provenance_and_deletes() {
+        let db = ordering_db();
+        let first = db.find(TriplePattern::any()).unwrap();
+        let victim = naive_ordered(
+            &db,
+            &TriplePattern::any(),
+            OrderKey::InsertionTime,
+            SortOrder::Asc,
+        )[0]
+        .clone();
+        db.delete(&victim).unwrap();
+        assert_eq!(db.sequence(&victim).unwrap(), None);
+
+        let result = db
+            .query()
+            .order_by(OrderKey::InsertionTime, SortOrder::Asc)
+            .execute()
+            .unwrap();
+        assert_eq!(result.total_count, first.len() - 1);
+        assert!(result.triples.iter().all(|t| t.id() != victim));
+
+        let late = db
+            .insert_with_meta(
+                Triple::literal("s0", "p0", "late"),
+                TripleMeta::new().with_asserted_by(NodeId::named("agent:x")),
+            )
+            .unwrap();
+        let newest = db
+            .query()
+            .order_by(OrderKey::InsertionTime, SortOrder::Desc)
+            .limit(1)
+            .execute()
+            .unwrap();
+        assert_eq!(newest.triples[0].id(), late);
+
+        let by_agent = db
+            .query()
+            .asserted_by(NodeId::named("agent:x"))
+            .order_by(OrderKey::Object, SortOrder::Asc)
+            .execute()
+            .unwrap();
+        assert_eq!(by_agent.total_count, 1);
+        assert_eq!(by_agent.triples[0].id(), late);
+    }
+
+    #[test]
+    fn test_distinct_and_order_rejected_by_solve() {
+        let db = ordering_db();
+        let plain = db
+            .query()
+            .predicate(Predicate::named("p2"))
+            .execute()
+            .unwrap();
+        let distinct = db
+            .query()
+            .predicate(Predicate::named("p2"))
+            .distinct()
+            .execute()
+            .unwrap();
+        let mut ids: Vec<TripleId> = plain.triples.iter().map(Triple::id).collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(distinct.total_count, ids.len());
+
+        let var = Var::new("s");
+        let result = db
+            .query()
+            .pattern(&var, Predicate::named("p2"), Value::integer(19))
+            .order_by(OrderKey::Subject, SortOrder::Asc)
+            .solve();
+        assert!(matches!(result, Err(Error::Query(_))));
+        let result = db
+            .query()
+            .pattern(&var, Predicate::named("p2"), Value::integer(19))
+            .distinct()
+            .solve();
+        assert!(matches!(result, Err(Error::Query(_))));
+    }
+
+    /// 10k triples over 97 subjects and 5 predicates. Objects of `p0` to
+    /// `p3` are integers and quarter floats, those of `p4` are strings.
+    fn aggregate_db() -> crate::GraphDB {
+        let db = crate::GraphDB::memory().unwrap();
+        let triples = (0..10_000i64)
+            .map(|i| {
+                let object = match (i % 5, i % 3) {
+                    (4, _) => Value::literal(format!("v{}", i % 13)),
+                    (_, 0) => Value::integer((i * 31) % 1000 - 500),
+                    _ => Value::float((i % 777) as f64 * 0.25 - 50.0),
+                };
+                Triple::new(
+                    NodeId::named(format!("s{}", i % 97)),
+                    Predicate::named(format!("p{}", i % 5)),
+                    object,
+                )
+            })
+            .collect();
+        db.insert_batch(triples).unwrap();
+        db
+    }
+
+    #[test]
+    fn test_aggregates_match_materialized_fold() {
+        let db = aggregate_db();
+        let patterns = [
+            TriplePattern::any(),
+            TriplePattern::predicate(Predicate::named("p0")),
+            TriplePattern::predicate(Predicate::named("p2")),
+            TriplePattern::subject(NodeId::named("s5")),
+            TriplePattern::subject(NodeId::named("s5")).with_predicate(Predicate::named("p1")),
+            TriplePattern::object(Value::integer(-376)),
+            TriplePattern::object(Value::literal("v3")),
+            TriplePattern::predicate(Predicate::named("p3")).with_object(Value::float(-49.75)),
+            TriplePattern::subject(NodeId::named("s5")).with_object(Value::float(-49.75)),
+            TriplePattern::subject(NodeId::named("missing")),
+        ];
+
+        for pattern in &patterns {
+            let triples = db.find(pattern.clone()).unwrap();
+
+            assert_eq!(filtered_query(&db, pattern).count().unwrap(), triples.len());
+            assert_eq!(
+                filtered_query(&db, pattern).exact().count().unwrap(),
+                triples.len()
+            );
+            let subjects: HashSet<_> = triples.iter().map(|t| t.subject.clone()).collect();
+            let predicates: HashSet<_> = triples.iter().map(|t| t.predicate.clone()).collect();
+            let objects: HashSet<_> = triples.iter().map(|t| t.object.sort_key()).collect();
+            for (component, expected) in [
+                (Component::Subject, subjects.len()),
+                (Component::Predicate, predicates.len()),
+                (Component::Object, objects.len()),
+            ] {
+                assert_eq!(
+                    filtered_query(&db, pattern)
+                        .count_distinct(component)
+                        .unwrap(),
+                    expected,
+                    "{component:?} of {pattern:?}"
+                );
+                assert_eq!(
+                    filtered_query(&db, pattern)
+                        .exact()
+                        .count_distinct(component)
+                        .unwrap(),
+                    expected
+                );
+            }
+
+            if triples.iter().any(|t| t.object.as_float().is_none()) {
+                continue;
+            }
+            let numbers: Vec<&Value> = triples.iter().map(|t| &t.object).collect();
+            let expected_sum = if numbers.iter().all(|v| v.as_integer().is_some()) {
+                Value::integer(numbers.iter().filter_map(|v| v.as_integer()).sum())
+            } else {
+                Value::float(numbers.iter().filter_map(|v| v.as_float()).sum())
+            };
+            let float_sum: f64 = numbers.iter().filter_map(|v| v.as_float()).sum();
+            let expected_avg = (!numbers.is_empty()).then(|| float_sum / numbers.len() as f64);
+            let expected_min = numbers
+                .iter()
+                .min_by(|a, b| compare_numbers(a, b))
+                .map(|v| (*v).clone());
+            let expected_max = numbers
+                .iter()
+                .max_by(|a, b| compare_numbers(a, b))
+                .map(|v| (*v).clone());
+
+            assert_eq!(
+                filtered_query(&db, pattern).sum_objects().unwrap(),
+                expected_sum
+            );
+            assert_eq!(
+                filtered_query(&db, pattern).avg_objects().unwrap(),
+                expected_avg
+            );
+            assert_eq!(
+                filtered_query(&db, pattern)
+                    .min_object()
+                    .unwrap()
+                    .map(|v| v.as_float()),
+                expected_min.map(|v| v.as_float())
+            );
+            assert_eq!(
+                filtered_query(&db, pattern)
+                    .max_object()
+                    .unwrap()
+                    .map(|v| v.as_float()),
+                expected_max.map(|v| v.as_float())
+            );
+        }
+
+        // Mixed integers and floats sum to a float
+        let sum = db
+            .query()
+            .predicate(Predicate::named("p0"))
+            .sum_objects()
+            .unwrap();
+        assert!(matches!(sum, Value::Float(_)));
+        let sum = db
+            .query()
+            .object(Value::integer(-376))
+            .sum_objects()
+            .unwrap();
+        assert!(matches!(sum, Value::Integer(_)));
+    }
+
+    #[test]
+    fn test_numeric_aggregate_of_non_numeric_objects_fails() {
+        let db = aggregate_db();
+        let strings = || db.query().predicate(Predicate::named("p4"));
+        assert!(matches!(strings().sum_objects(), Err(Error::Query(_))));
+        assert!(matches!(strings().avg_objects(), Err(Error::Query(_))));
+        assert!(matches!(strings().min_object(), Err(Error::Query(_))));
+        assert!(matches!(strings().max_object(), Err(Error::Query(_))));
+        assert!(matches!(
+            db.query().sum_objects(),
+            Err(Error::Query(message)) if message.contains("sum_objects")
+        ));
+
+        // Counting does not care about object types
+        let matches = db.find(TriplePattern::predicate(Predicate::named("p4")));
+        assert_eq!(strings().count().unwrap(), matches.unwrap().len());
+        assert_eq!(strings().count_distinct(Component::Object).unwrap(), 13);
+        assert!(matches!(strings().limit(10).count(), Err(Error::Query(_))));
+    }
 }
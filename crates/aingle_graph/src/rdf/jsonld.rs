@@ -0,0 +1,1298 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! JSON-LD 1.1 parser and serializer
+//!
+//! [`JsonLdParser`] converts a JSON-LD document to triples following the
+//! JSON-LD to RDF algorithm, for the part of JSON-LD 1.1 that needs neither
+//! network access nor named graphs:
+//!
+//! - `@context` given inline, as an object or an array of objects, with
+//!   prefixes, term definitions (`@id`, `@type`, `@language`, `@container`),
+//!   `@vocab`, `@base`, `@language` and keyword aliases such as
+//!   `"id": "@id"`. A context given by URL is rejected with
+//!   [`Error::Unsupported`] rather than fetched.
+//! - node objects with `@id` and `@type`. A nested node object becomes a node
+//!   of its own, a blank node unless it has an `@id`.
+//! - value objects with `@value`, `@type` and `@language`. Native numbers and
+//!   booleans become `xsd:integer`, `xsd:double` and `xsd:boolean`, so they
+//!   import as [`Value::Integer`](crate::Value::Integer),
+//!   [`Value::Float`](crate::Value::Float) and
+//!   [`Value::Boolean`](crate::Value::Boolean). A number written with a
+//!   fraction or exponent, such as `2.0`, is a double. A `@json` value is an
+//!   `rdf:JSON` literal of its compact serialization.
+//! - `@graph`, `@set`, and `@list` as an `rdf:first`/`rdf:rest` collection.
+//!
+//! Triples of a named `@graph` are imported into the one graph. Names that
+//! do not expand to an absolute IRI, such as `has_name` in a document
+//! without `@vocab`, are kept as written rather than dropped, so graphs whose
+//! names are not IRIs survive a round trip.
+//!
+//! [`JsonLdSerializer`] groups triples by subject into node objects, nests
+//! each blank node referenced exactly once inside its referrer, and compacts
+//! IRIs against a supplied context or one generated from the namespaces the
+//! data uses.
+//!
+//! # Example
+//!
+//! ```rust
+//! use aingle_graph::rdf::{JsonLdParser, JsonLdSerializer};
+//!
+//! let doc = r#"{
+//!     "@context": {"ex": "http://example.org/", "age": "ex:age"},
+//!     "@id": "ex:alice",
+//!     "@type": "ex:Person",
+//!     "age": 30,
+//!     "ex:address": {"ex:city": "Tallinn"}
+//! }"#;
+//! let triples = JsonLdParser::parse(doc)?;
+//! assert_eq!(triples.len(), 4);
+//!
+//! let output = JsonLdSerializer::serialize(&triples)?;
+//! assert_eq!(JsonLdParser::parse(&output)?.len(), 4);
+//! # Ok::<(), aingle_graph::Error>(())
+//! ```
+
+use super::serializer::number_blank_nodes;
+use super::{NamespaceMap, RdfParser, RdfSerializer, RdfTerm, RdfTriple};
+use crate::{Error, Result};
+use indexmap::IndexMap;
+use serde_json::{json, Map, Value as Json};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+const RDF_FIRST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#first";
+const RDF_REST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#rest";
+const RDF_NIL: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#nil";
+const RDF_JSON: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#JSON";
+const XSD_INTEGER: &str = "http://www.w3.org/2001/XMLSchema#integer";
+const XSD_DOUBLE: &str = "http://www.w3.org/2001/XMLSchema#double";
+const XSD_BOOLEAN: &str = "http://www.w3.org/2001/XMLSchema#boolean";
+
+/// Parser for JSON-LD (.jsonld) documents
+pub struct JsonLdParser;
+
+impl JsonLdParser {
+    /// Parse a JSON-LD document
+    pub fn parse(content: &str) -> Result<Vec<RdfTriple>> {
+        let document: Json = serde_json::from_str(content).map_err(|e| invalid(e.to_string()))?;
+        Self::parse_value(&document)
+    }
+
+    /// Parse a JSON-LD document that has already been read as JSON
+    pub fn parse_value(document: &Json) -> Result<Vec<RdfTriple>> {
+        let mut expander = Expander::default();
+        expander.document(document, &Context::default())?;
+        Ok(expander.triples)
+    }
+}
+
+impl RdfParser for JsonLdParser {
+    fn parse(content: &str) -> Result<Vec<RdfTriple>> {
+        JsonLdParser::parse(content)
+    }
+}
+
+/// Serializer for JSON-LD (.jsonld) documents
+///
+/// The output is an object with the `@context` used for compaction and a
+/// `@graph` of node objects, one per subject that is not nested elsewhere.
+pub struct JsonLdSerializer {
+    context: Option<Map<String, Json>>,
+}
+
+impl JsonLdSerializer {
+    /// Create a serializer that generates its context from the data
+    ///
+    /// The generated context declares the standard prefixes the data uses
+    /// (`rdf`, `xsd`, ...) and `ns1`, `ns2`, ... for its other namespaces.
+    pub fn new() -> Self {
+        Self { context: None }
+    }
+
+    /// Create a serializer that compacts IRIs against `context`
+    ///
+    /// Terms whose IRI equals a predicate are used as its key; other IRIs
+    /// are written as compact IRIs of the context's prefixes, or in full.
+    pub fn with_context(context: Map<String, Json>) -> Self {
+        Self {
+            context: Some(context),
+        }
+    }
+
+    /// Serialize triples to JSON-LD with a generated context
+    pub fn serialize(triples: &[RdfTriple]) -> Result<String> {
+        Self::new().serialize_with_options(triples)
+    }
+
+    /// Serialize triples with the configured context
+    pub fn serialize_with_options(&self, triples: &[RdfTriple]) -> Result<String> {
+        let document = self.to_document(triples)?;
+        serde_json::to_string_pretty(&document).map_err(|e| Error::Serialization(e.to_string()))
+    }
+
+    /// Build the JSON-LD document for triples
+    pub fn to_document(&self, triples: &[RdfTriple]) -> Result<Json> {
+        let triples = number_blank_nodes(triples);
+        let context_map = match &self.context {
+            Some(context) => context.clone(),
+            None => generated_context(&triples),
+        };
+        let context = Context::default().update(&Json::Object(context_map.clone()))?;
+
+        let mut groups: IndexMap<String, Vec<&RdfTriple>> = IndexMap::new();
+        let mut references: HashMap<String, usize> = HashMap::new();
+        for triple in &triples {
+            groups
+                .entry(term_key(&triple.subject))
+                .or_default()
+                .push(triple);
+            if triple.object.is_blank() {
+                *references.entry(term_key(&triple.object)).or_default() += 1;
+            }
+        }
+
+        let writer = NodeWriter {
+            context: &context,
+            nested: nested_nodes(&groups, &references),
+            groups: &groups,
+        };
+        let graph: Vec<Json> = groups
+            .keys()
+            .filter(|key| !writer.nested.contains(*key))
+            .map(|key| writer.node_object(key))
+            .collect();
+
+        let mut document = Map::new();
+        document.insert("@context".to_string(), Json::Object(context_map));
+        document.insert("@graph".to_string(), Json::Array(graph));
+        Ok(Json::Object(document))
+    }
+}
+
+impl Default for JsonLdSerializer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RdfSerializer for JsonLdSerializer {
+    fn serialize(triples: &[RdfTriple]) -> Result<String> {
+        JsonLdSerializer::serialize(triples)
+    }
+}
+
+// ========== Context processing ==========
+
+/// An active context: the term definitions in scope at some point of a
+/// document
+#[derive(Debug, Clone, Default)]
+struct Context {
+    base: Option<String>,
+    vocab: Option<String>,
+    language: Option<String>,
+    terms: HashMap<String, TermDefinition>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct TermDefinition {
+    /// The expanded IRI or aliased keyword; `None` for a term mapped to null
+    iri: Option<String>,
+    /// Whether the term expands compact IRIs like `term:suffix`
+    prefix: bool,
+    /// `@id`, `@vocab`, `@json` or a datatype IRI
+    type_mapping: Option<String>,
+    /// `Some(None)` for a term that removes the default language
+    language: Option<Option<String>>,
+    /// Whether values are an ordered `@list`
+    list: bool,
+}
+
+impl Context {
+    /// The context after applying a local `@context` value
+    fn update(&self, local: &Json) -> Result<Context> {
+        let mut result = self.clone();
+        for item in as_slice(local) {
+            match item {
+                Json::Null => result = Context::default(),
+                Json::String(url) => {
+                    return Err(Error::Unsupported(format!(
+                        "remote JSON-LD context \"{}\"; only inline contexts can be imported",
+                        url
+                    )))
+                }
+                Json::Object(definitions) => result.define_all(definitions)?,
+                _ => return Err(invalid("@context must be an object, an array or null")),
+            }
+        }
+        Ok(result)
+    }
+
+    fn define_all(&mut self, definitions: &Map<String, Json>) -> Result<()> {
+        if let Some(base) = definitions.get("@base") {
+            self.base = match base {
+                Json::Null => None,
+                Json::String(iri) => Some(match &self.base {
+                    Some(current) if !is_absolute(iri) => resolve(current, iri),
+                    _ => iri.clone(),
+                }),
+                _ => return Err(invalid("@base must be a string or null")),
+            };
+        }
+        if let Some(vocab) = definitions.get("@vocab") {
+            self.vocab = match vocab {
+                Json::Null => None,
+                Json::String(iri) => self.expand_iri(iri, true),
+                _ => return Err(invalid("@vocab must be a string or null")),
+            };
+        }
+        if let Some(language) = definitions.get("@language") {
+            self.language = match language {
+                Json::Null => None,
+                Json::String(tag) => Some(tag.clone()),
+                _ => return Err(invalid("@language must be a string or null")),
+            };
+        }
+        for key in definitions.keys().filter(|key| key.starts_with('@')) {
+            match key.as_str() {
+                "@base" | "@vocab" | "@language" | "@version" | "@protected" | "@propagate" => {}
+                "@import" => return Err(unsupported("@import in a context")),
+                "@direction" => return Err(unsupported("@direction")),
+                other => return Err(invalid(format!("invalid context entry {}", other))),
+            }
+        }
+
+        let mut defined = HashMap::new();
+        for term in definitions.keys().filter(|key| !key.starts_with('@')) {
+            self.define_term(definitions, term, &mut defined)?;
+        }
+        Ok(())
+    }
+
+    /// Create the definition of `term`, first defining the terms of the same
+    /// context its IRI refers to
+    fn define_term(
+        &mut self,
+        definitions: &Map<String, Json>,
+        term: &str,
+        defined: &mut HashMap<String, bool>,
+    ) -> Result<()> {
+        match defined.get(term) {
+            Some(true) => return Ok(()),
+            Some(false) => {
+                return Err(invalid(format!("cyclic IRI mapping for term \"{}\"", term)))
+            }
+            None => {}
+        }
+        defined.insert(term.to_string(), false);
+
+        let mut definition = TermDefinition::default();
+        let (id, simple) = match &definitions[term] {
+            Json::Null => {
+                self.terms.insert(term.to_string(), definition);
+                defined.insert(term.to_string(), true);
+                return Ok(());
+            }
+            Json::String(id) => (Some(Json::String(id.clone())), true),
+            Json::Object(map) => {
+                for (key, value) in map {
+                    match key.as_str() {
+                        "@id" | "@protected" => {}
+                        "@type" => {
+                            let Json::String(datatype) = value else {
+                                return Err(invalid(format!("@type of term \"{}\"", term)));
+                            };
+                            definition.type_mapping = Some(match datatype.as_str() {
+                                "@id" | "@vocab" | "@json" => datatype.clone(),
+                                _ => self
+                                    .expand_dependency(definitions, datatype, term, defined)?
+                                    .ok_or_else(|| {
+                                        invalid(format!("@type of term \"{}\"", term))
+                                    })?,
+                            });
+                        }
+                        "@language" => {
+                            definition.language = Some(match value {
+                                Json::Null => None,
+                                Json::String(tag) => Some(tag.clone()),
+                                _ => {
+                                    return Err(invalid(format!("@language of term \"{}\"", term)))
+                                }
+                            });
+                        }
+                        "@container" => {
+                            for container in as_slice(value) {
+                                match container.as_str() {
+                                    Some("@list") => definition.list = true,
+                                    Some("@set") => {}
+                                    _ => {
+                                        return Err(unsupported(format!(
+                                            "@container {}",
+                                            container
+                                        )))
+                                    }
+                                }
+                            }
+                        }
+                        "@prefix" => definition.prefix = value.as_bool().unwrap_or(false),
+                        "@context" => return Err(unsupported("scoped contexts")),
+                        "@reverse" => return Err(unsupported("@reverse")),
+                        other => {
+                            return Err(unsupported(format!("{} in a term definition", other)))
+                        }
+                    }
+                }
+                (map.get("@id").cloned(), false)
+            }
+            _ => return Err(invalid(format!("definition of term \"{}\"", term))),
+        };
+
+        definition.iri = match id {
+            Some(Json::Null) => None,
+            Some(Json::String(id)) if id != term => {
+                self.expand_dependency(definitions, &id, term, defined)?
+            }
+            Some(Json::String(_)) | None => {
+                if term.contains(':') {
+                    self.expand_dependency(definitions, term, term, defined)?
+                } else if let Some(vocab) = &self.vocab {
+                    Some(format!("{}{}", vocab, term))
+                } else {
+                    return Err(invalid(format!("term \"{}\" has no IRI", term)));
+                }
+            }
+            Some(_) => return Err(invalid(format!("@id of term \"{}\"", term))),
+        };
+        if simple && !term.contains([':', '/']) {
+            definition.prefix |= definition
+                .iri
+                .as_deref()
+                .is_some_and(|iri| iri.ends_with(['/', '#', ':', '?', '[', ']', '@']));
+        }
+
+        self.terms.insert(term.to_string(), definition);
+        defined.insert(term.to_string(), true);
+        Ok(())
+    }
+
+    /// Expand `value` inside the context being defined, defining the term
+    /// or prefix it uses first
+    fn expand_dependency(
+        &mut self,
+        definitions: &Map<String, Json>,
+        value: &str,
+        term: &str,
+        defined: &mut HashMap<String, bool>,
+    ) -> Result<Option<String>> {
+        let dependency = value.split_once(':').map_or(value, |(prefix, _)| prefix);
+        if dependency != term && definitions.contains_key(dependency) {
+            self.define_term(definitions, dependency, defined)?;
+        }
+        Ok(self.expand_iri(value, true))
+    }
+
+    /// Expand a term, compact IRI or relative IRI
+    ///
+    /// `vocab` selects property and type position, where terms and `@vocab`
+    /// apply; elsewhere relative IRIs resolve against `@base`. `None` means
+    /// the value is a term mapped to null.
+    fn expand_iri(&self, value: &str, vocab: bool) -> Option<String> {
+        if value.starts_with('@') {
+            return Some(value.to_string());
+        }
+        if vocab {
+            if let Some(definition) = self.terms.get(value) {
+                return definition.iri.clone();
+            }
+        }
+        if let Some((prefix, suffix)) = value.split_once(':') {
+            if prefix != "_" && !suffix.starts_with("//") {
+                if let Some(TermDefinition {
+                    iri: Some(iri),
+                    prefix: true,
+                    ..
+                }) = self.terms.get(prefix)
+                {
+                    return Some(format!("{}{}", iri, suffix));
+                }
+            }
+            return Some(value.to_string());
+        }
+        match (&self.vocab, &self.base) {
+            (Some(vocab_iri), _) if vocab => Some(format!("{}{}", vocab_iri, value)),
+            (_, Some(base)) if !vocab => Some(resolve(base, value)),
+            _ => Some(value.to_string()),
+        }
+    }
+
+    /// The keyword `key` stands for, directly or through an alias
+    fn keyword<'a>(&'a self, key: &'a str) -> Option<&'a str> {
+        if key.starts_with('@') {
+            return Some(key);
+        }
+        self.terms
+            .get(key)?
+            .iri
+            .as_deref()
+            .filter(|iri| iri.starts_with('@'))
+    }
+}
+
+// ========== Expansion to triples ==========
+
+#[derive(Default)]
+struct Expander {
+    triples: Vec<RdfTriple>,
+    /// Blank node labels of the document, relabelled `b1`, `b2`, ... like
+    /// the nodes without `@id`, so the two never collide
+    blank_labels: HashMap<String, String>,
+    blank_count: usize,
+}
+
+impl Expander {
+    fn document(&mut self, document: &Json, context: &Context) -> Result<()> {
+        match document {
+            Json::Array(items) => {
+                for item in items {
+                    self.document(item, context)?;
+                }
+            }
+            Json::Object(map) => {
+                self.node(map, context)?;
+            }
+            _ => return Err(invalid("a document must be an object or an array")),
+        }
+        Ok(())
+    }
+
+    /// Emit the triples of a node object and return its subject
+    fn node(&mut self, map: &Map<String, Json>, context: &Context) -> Result<RdfTerm> {
+        let context = match map.get("@context") {
+            Some(local) => context.update(local)?,
+            None => context.clone(),
+        };
+
+        let mut id = None;
+        let mut types = Vec::new();
+        let mut graph = None;
+        let mut properties = Vec::new();
+        for (key, value) in map {
+            match context.keyword(key) {
+                Some("@id") => {
+                    id = Some(
+                        value
+                            .as_str()
+                            .ok_or_else(|| invalid("@id must be a string"))?,
+                    )
+                }
+                Some("@type") => types.extend(as_slice(value)),
+                Some("@graph") => graph = Some(value),
+                Some(keyword @ ("@reverse" | "@included" | "@nest")) => {
+                    return Err(unsupported(keyword))
+                }
+                Some(_) => {}
+                None => properties.push((key, value)),
+            }
+        }
+
+        let subject = match id {
+            Some(id) => self.reference(id, &context, false),
+            None => self.fresh_blank(),
+        };
+        for node_type in types {
+            let node_type = node_type
+                .as_str()
+                .ok_or_else(|| invalid("@type must be a string"))?;
+            let object = self.reference(node_type, &context, true);
+            self.emit(&subject, RDF_TYPE, object);
+        }
+        for (key, value) in properties {
+            let Some(predicate) = context.expand_iri(key, true) else {
+                continue;
+            };
+            if predicate.starts_with("_:") {
+                continue;
+            }
+            let definition = context.terms.get(key);
+            if definition.is_some_and(|d| d.list) {
+                let head = self.list(value, definition, &context)?;
+                self.emit(&subject, &predicate, head);
+            } else {
+                let mut objects = Vec::new();
+                self.objects(value, definition, &context, &mut objects)?;
+                for object in objects {
+                    self.emit(&subject, &predicate, object);
+                }
+            }
+        }
+        if let Some(graph) = graph {
+            for item in as_slice(graph) {
+                let Json::Object(node) = item else {
+                    return Err(invalid("@graph must contain node objects"));
+                };
+                self.node(node, &context)?;
+            }
+        }
+        Ok(subject)
+    }
+
+    /// Convert the values of a property to object terms
+    fn objects(
+        &mut self,
+        value: &Json,
+        definition: Option<&TermDefinition>,
+        context: &Context,
+        out: &mut Vec<RdfTerm>,
+    ) -> Result<()> {
+        let type_mapping = definition.and_then(|d| d.type_mapping.as_deref());
+        if type_mapping == Some("@json") {
+            out.push(RdfTerm::typed_literal(value.to_string(), RDF_JSON));
+            return Ok(());
+        }
+
+        match value {
+            Json::Null => {}
+            Json::Array(items) => {
+                for item in items {
+                    self.objects(item, definition, context, out)?;
+                }
+            }
+            Json::String(s) => out.push(match type_mapping {
+                Some("@id") => self.reference(s, context, false),
+                Some("@vocab") => self.reference(s, context, true),
+                Some(datatype) => RdfTerm::typed_literal(s, datatype),
+                None => {
+                    let language = match definition.and_then(|d| d.language.as_ref()) {
+                        Some(language) => language.as_ref(),
+                        None => context.language.as_ref(),
+                    };
+                    match language {
+                        Some(language) => RdfTerm::lang_literal(s, language),
+                        None => RdfTerm::literal(s),
+                    }
+                }
+            }),
+            Json::Bool(_) | Json::Number(_) => {
+                let datatype = type_mapping.filter(|t| !t.starts_with('@'));
+                out.push(native_literal(value, datatype));
+            }
+            Json::Object(map) => {
+                let keyword = |wanted: &str| {
+                    map.iter()
+                        .find(|(key, _)| context.keyword(key) == Some(wanted))
+                        .map(|(_, value)| value)
+                };
+                if keyword("@value").is_some() {
+                    if let Some(literal) = self.value_object(map, context)? {
+                        out.push(literal);
+                    }
+                } else if let Some(items) = keyword("@list") {
+                    out.push(self.list(items, definition, context)?);
+                } else if let Some(items) = keyword("@set") {
+                    self.objects(items, definition, context, out)?;
+                } else {
+                    out.push(self.node(map, context)?);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn value_object(
+        &mut self,
+        map: &Map<String, Json>,
+        context: &Context,
+    ) -> Result<Option<RdfTerm>> {
+        let mut value = &Json::Null;
+        let mut datatype = None;
+        let mut language = None;
+        for (key, item) in map {
+            match context.keyword(key) {
+                Some("@value") => value = item,
+                Some("@type") => {
+                    datatype = Some(
+                        item.as_str()
+                            .ok_or_else(|| invalid("@type of a value must be a string"))?,
+                    )
+                }
+                Some("@language") => {
+                    language = Some(
+                        item.as_str()
+                            .ok_or_else(|| invalid("@language must be a string"))?,
+                    )
+                }
+                Some("@index") | Some("@context") => {}
+                Some("@direction") => return Err(unsupported("@direction")),
+                _ => return Err(invalid(format!("unexpected {} in a value object", key))),
+            }
+        }
+
+        if datatype == Some("@json") {
+            return Ok(Some(RdfTerm::typed_literal(value.to_string(), RDF_JSON)));
+        }
+        let datatype = datatype.and_then(|d| context.expand_iri(d, true));
+        Ok(match value {
+            Json::Null => None,
+            Json::String(s) => Some(match (datatype, language) {
+                (Some(datatype), _) => RdfTerm::typed_literal(s, datatype),
+                (None, Some(language)) => RdfTerm::lang_literal(s, language),
+                (None, None) => RdfTerm::literal(s),
+            }),
+            Json::Bool(_) | Json::Number(_) => Some(native_literal(value, datatype.as_deref())),
+            _ => return Err(invalid("@value must be a string, number, boolean or null")),
+        })
+    }
+
+    /// Emit an `rdf:first`/`rdf:rest` collection and return its head
+    fn list(
+        &mut self,
+        items: &Json,
+        definition: Option<&TermDefinition>,
+        context: &Context,
+    ) -> Result<RdfTerm> {
+        let mut values = Vec::new();
+        self.objects(items, definition, context, &mut values)?;
+
+        let mut head = RdfTerm::iri(RDF_NIL);
+        for value in values.into_iter().rev() {
+            let cell = self.fresh_blank();
+            self.emit(&cell, RDF_FIRST, value);
+            self.emit(&cell, RDF_REST, head);
+            head = cell;
+        }
+        Ok(head)
+    }
+
+    /// The term for a node reference, `@id` or `@type` value
+    fn reference(&mut self, value: &str, context: &Context, vocab: bool) -> RdfTerm {
+        let iri = context
+            .expand_iri(value, vocab)
+            .unwrap_or_else(|| value.to_string());
+        match iri.strip_prefix("_:") {
+            Some(label) => self.labelled_blank(label),
+            None => RdfTerm::Iri(iri),
+        }
+    }
+
+    fn labelled_blank(&mut self, label: &str) -> RdfTerm {
+        if let Some(existing) = self.blank_labels.get(label) {
+            return RdfTerm::BlankNode(existing.clone());
+        }
+        let RdfTerm::BlankNode(fresh) = self.fresh_blank() else {
+            unreachable!()
+        };
+        self.blank_labels.insert(label.to_string(), fresh.clone());
+        RdfTerm::BlankNode(fresh)
+    }
+
+    fn fresh_blank(&mut self) -> RdfTerm {
+        self.blank_count += 1;
+        RdfTerm::BlankNode(format!("b{}", self.blank_count))
+    }
+
+    fn emit(&mut self, subject: &RdfTerm, predicate: &str, object: RdfTerm) {
+        self.triples.push(RdfTriple::new(
+            subject.clone(),
+            RdfTerm::iri(predicate),
+            object,
+        ));
+    }
+}
+
+/// The literal for a native JSON number or boolean
+fn native_literal(value: &Json, datatype: Option<&str>) -> RdfTerm {
+    match value {
+        Json::Bool(b) => RdfTerm::typed_literal(b.to_string(), datatype.unwrap_or(XSD_BOOLEAN)),
+        Json::Number(n) => match n.as_i64() {
+            Some(i) if datatype != Some(XSD_DOUBLE) => {
+                RdfTerm::typed_literal(i.to_string(), datatype.unwrap_or(XSD_INTEGER))
+            }
+            _ => RdfTerm::typed_literal(
+                format!("{:E}", n.as_f64().unwrap_or(f64::NAN)),
+                datatype.unwrap_or(XSD_DOUBLE),
+            ),
+        },
+        _ => unreachable!("only numbers and booleans are native literals"),
+    }
+}
+
+// ========== Compaction ==========
+
+/// Writes node objects, compacting IRIs against the context
+struct NodeWriter<'a> {
+    context: &'a Context,
+    groups: &'a IndexMap<String, Vec<&'a RdfTriple>>,
+    /// Blank nodes written inside their only referrer
+    nested: HashSet<String>,
+}
+
+impl NodeWriter<'_> {
+    fn node_object(&self, key: &str) -> Json {
+        let mut node = Map::new();
+        if !self.nested.contains(key) {
+            node.insert("@id".to_string(), Json::String(self.compact_id(key)));
+        }
+
+        let mut types = Vec::new();
+        let mut properties: IndexMap<String, Vec<Json>> = IndexMap::new();
+        for triple in self.groups.get(key).into_iter().flatten() {
+            let predicate = triple.predicate.as_iri().unwrap_or_default();
+            if let (RDF_TYPE, RdfTerm::Iri(iri)) = (predicate, &triple.object) {
+                types.push(Json::String(self.compact_type(iri)));
+                continue;
+            }
+            let (property, definition) = self.compact_property(predicate);
+            let value = match &triple.object {
+                RdfTerm::Iri(iri) => id_object(self.compact_id(iri)),
+                RdfTerm::BlankNode(_) => {
+                    let object = term_key(&triple.object);
+                    if self.nested.contains(&object) {
+                        self.node_object(&object)
+                    } else {
+                        id_object(object)
+                    }
+                }
+                RdfTerm::Literal {
+                    value,
+                    datatype,
+                    language,
+                } => self.literal(value, datatype.as_deref(), language.as_deref(), definition),
+            };
+            properties.entry(property).or_default().push(value);
+        }
+
+        if !types.is_empty() {
+            node.insert("@type".to_string(), one_or_many(types));
+        }
+        for (property, values) in properties {
+            node.insert(property, one_or_many(values));
+        }
+        Json::Object(node)
+    }
+
+    fn literal(
+        &self,
+        value: &str,
+        datatype: Option<&str>,
+        language: Option<&str>,
+        definition: Option<&TermDefinition>,
+    ) -> Json {
+        // Native values would pick up the term's coercion or language
+        let coerced = definition.is_some_and(|d| d.type_mapping.is_some() || d.language.is_some());
+        let datatype = match (datatype, language) {
+            (_, Some(language)) => return json!({"@value": value, "@language": language}),
+            (None, None) if coerced || self.context.language.is_some() => {
+                return json!({ "@value": value })
+            }
+            (None, None) => return Json::String(value.to_string()),
+            (Some(datatype), None) => datatype,
+        };
+
+        let native = match datatype {
+            XSD_INTEGER if !coerced => value.parse::<i64>().ok().map(Json::from),
+            XSD_DOUBLE if !coerced => value
+                .parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+                .map(Json::Number),
+            XSD_BOOLEAN if !coerced => value.parse::<bool>().ok().map(Json::Bool),
+            RDF_JSON => serde_json::from_str::<Json>(value)
+                .ok()
+                .map(|json| json!({"@value": json, "@type": "@json"})),
+            _ => None,
+        };
+        native.unwrap_or_else(|| json!({"@value": value, "@type": self.compact_type(datatype)}))
+    }
+
+    /// The key for a predicate, and the term definition it uses
+    fn compact_property(&self, iri: &str) -> (String, Option<&TermDefinition>) {
+        // Terms that reshape their values cannot hold arbitrary objects
+        let term = self
+            .context
+            .terms
+            .iter()
+            .filter(|(_, d)| {
+                d.iri.as_deref() == Some(iri)
+                    && !d.list
+                    && d.type_mapping.as_deref() != Some("@json")
+            })
+            .min_by(|(a, _), (b, _)| a.len().cmp(&b.len()).then(a.cmp(b)));
+        match term {
+            Some((term, definition)) => (term.clone(), Some(definition)),
+            None => (self.compact_vocab(iri), None),
+        }
+    }
+
+    /// A `@type` value or datatype
+    fn compact_type(&self, iri: &str) -> String {
+        self.context
+            .terms
+            .iter()
+            .filter(|(_, d)| d.iri.as_deref() == Some(iri))
+            .map(|(term, _)| term)
+            .min_by(|a, b| a.len().cmp(&b.len()).then(a.cmp(b)))
+            .cloned()
+            .unwrap_or_else(|| self.compact_vocab(iri))
+    }
+
+    fn compact_vocab(&self, iri: &str) -> String {
+        if let Some(suffix) = self
+            .context
+            .vocab
+            .as_deref()
+            .and_then(|vocab| iri.strip_prefix(vocab))
+        {
+            if !suffix.is_empty()
+                && !suffix.contains(':')
+                && !self.context.terms.contains_key(suffix)
+            {
+                return suffix.to_string();
+            }
+        }
+        self.compact_id(iri)
+    }
+
+    /// An `@id` value: a compact IRI of the longest matching prefix
+    fn compact_id(&self, iri: &str) -> String {
+        if iri.starts_with("_:") {
+            return iri.to_string();
+        }
+        self.context
+            .terms
+            .iter()
+            .filter(|(_, d)| d.prefix)
+            .filter_map(|(term, d)| {
+                let namespace = d.iri.as_deref()?;
+                let suffix = iri.strip_prefix(namespace)?;
+                (!suffix.is_empty() && !suffix.starts_with("//")).then_some((
+                    term,
+                    namespace.len(),
+                    suffix,
+                ))
+            })
+            .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(a.0)))
+            .map_or_else(
+                || iri.to_string(),
+                |(term, _, suffix)| format!("{}:{}", term, suffix),
+            )
+    }
+}
+
+/// The blank nodes to nest inside their referrer: those referenced exactly
+/// once and reachable from a node written at the top level
+fn nested_nodes(
+    groups: &IndexMap<String, Vec<&RdfTriple>>,
+    references: &HashMap<String, usize>,
+) -> HashSet<String> {
+    let mut nested: HashSet<String> = groups
+        .keys()
+        .filter(|key| key.starts_with("_:") && references.get(*key) == Some(&1))
+        .cloned()
+        .collect();
+
+    loop {
+        let mut reached = HashSet::new();
+        let mut stack: Vec<&str> = groups
+            .keys()
+            .filter(|key| !nested.contains(*key))
+            .map(String::as_str)
+            .collect();
+        while let Some(key) = stack.pop() {
+            for triple in &groups[key] {
+                if !triple.object.is_blank() {
+                    continue;
+                }
+                let object = term_key(&triple.object);
+                if nested.contains(&object) && reached.insert(object.clone()) {
+                    if let Some((object, _)) = groups.get_key_value(&object) {
+                        stack.push(object);
+                    }
+                }
+            }
+        }
+
+        // A cycle of blank nodes hangs off nothing; write one of them at the
+        // top level and look again
+        match groups
+            .keys()
+            .find(|key| nested.contains(*key) && !reached.contains(*key))
+        {
+            Some(key) => {
+                nested.remove(key);
+            }
+            None => return nested,
+        }
+    }
+}
+
+/// A context declaring prefixes for the namespaces `triples` use
+fn generated_context(triples: &[RdfTriple]) -> Map<String, Json> {
+    let iris: Vec<&str> = triples
+        .iter()
+        .flat_map(|t| [&t.subject, &t.predicate, &t.object])
+        .filter_map(|term| match term {
+            RdfTerm::Iri(iri) => Some(iri.as_str()),
+            RdfTerm::Literal { datatype, .. } => datatype.as_deref(),
+            RdfTerm::BlankNode(_) => None,
+        })
+        .collect();
+    // A prefix named like the scheme of a name, e.g. `user` for
+    // `user:alice`, would change what that name expands to
+    let schemes: HashSet<&str> = iris
+        .iter()
+        .filter_map(|iri| iri.split_once(':').map(|(scheme, _)| scheme))
+        .collect();
+
+    let defaults = NamespaceMap::with_defaults();
+    let mut context = Map::new();
+    let mut namespaces = HashSet::new();
+    let mut generated = 0;
+    for iri in iris {
+        if let Some(prefix) = defaults.get_prefix(iri) {
+            let namespace = defaults.get_iri(prefix).unwrap_or_default();
+            if iri.len() > namespace.len() && !schemes.contains(prefix) {
+                context
+                    .entry(prefix)
+                    .or_insert_with(|| Json::String(namespace.to_string()));
+            }
+            continue;
+        }
+        let Some(namespace) = namespace_of(iri) else {
+            continue;
+        };
+        if namespaces.insert(namespace) {
+            let prefix = loop {
+                generated += 1;
+                let prefix = format!("ns{}", generated);
+                if !schemes.contains(prefix.as_str()) {
+                    break prefix;
+                }
+            };
+            context.insert(prefix, Json::String(namespace.to_string()));
+        }
+    }
+    context
+}
+
+/// The part of an `http://host/path/name` IRI up to its last `/` or `#`
+fn namespace_of(iri: &str) -> Option<&str> {
+    let authority = iri.find("://")? + 3;
+    let cut = iri.rfind(['/', '#'])?;
+    (cut > authority && cut + 1 < iri.len()).then(|| &iri[..=cut])
+}
+
+// ========== Helpers ==========
+
+/// The key of a subject: its IRI, or `_:label` for a blank node
+fn term_key(term: &RdfTerm) -> String {
+    match term {
+        RdfTerm::Iri(iri) => iri.clone(),
+        RdfTerm::BlankNode(label) => format!("_:{}", label),
+        RdfTerm::Literal { value, .. } => value.clone(),
+    }
+}
+
+fn id_object(id: String) -> Json {
+    json!({ "@id": id })
+}
+
+fn one_or_many(mut values: Vec<Json>) -> Json {
+    if values.len() == 1 {
+        values.pop().unwrap_or_default()
+    } else {
+        Json::Array(values)
+    }
+}
+
+fn as_slice(value: &Json) -> &[Json] {
+    match value {
+        Json::Array(items) => items,
+        other => std::slice::from_ref(other),
+    }
+}
+
+fn is_absolute(iri: &str) -> bool {
+    iri.split_once(':').is_some_and(|(scheme, _)| {
+        scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    })
+}
+
+/// Resolve a relative IRI against `base`, without dot-segment removal
+fn resolve(base: &str, relative: &str) -> String {
+    if is_absolute(relative) {
+        return relative.to_string();
+    }
+    let base = base.split('#').next().unwrap_or(base);
+    let authority_end = base.find("://").map_or(0, |i| {
+        base[i + 3..]
+            .find('/')
+            .map_or(base.len(), |path| i + 3 + path)
+    });
+    if relative.is_empty() {
+        base.to_string()
+    } else if relative.starts_with('#') {
+        format!("{}{}", base, relative)
+    } else if relative.starts_with("//") {
+        let scheme_end = base.find(':').map_or(0, |i| i + 1);
+        format!("{}{}", &base[..scheme_end], relative)
+    } else if relative.starts_with('/') {
+        format!("{}{}", &base[..authority_end], relative)
+    } else {
+        match base.rfind('/').filter(|&i| i >= authority_end) {
+            Some(i) => format!("{}{}", &base[..=i], relative),
+            None => format!("{}/{}", base, relative),
+        }
+    }
+}
+
+fn invalid(message: impl fmt::Display) -> Error {
+    Error::InvalidTriple(format!("JSON-LD: {}", message))
+}
+
+fn unsupported(feature: impl fmt::Display) -> Error {
+    Error::Unsupported(format!("JSON-LD feature {}", feature))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object_of<'a>(triples: &'a [RdfTriple], predicate: &str) -> &'a RdfTerm {
+        &triples
+            .iter()
+            .find(|t| t.predicate.as_iri() == Some(predicate))
+            .unwrap_or_else(|| panic!("no {predicate}"))
+            .object
+    }
+
+    #[test]
+    fn test_context_terms_and_aliases() {
+        // `name` refers to a prefix defined after it
+        let doc = r#"{
+            "@context": {
+                "name": "foaf:name",
+                "foaf": "http://xmlns.com/foaf/0.1/",
+                "id": "@id",
+                "type": "@type",
+                "homepage": {"@id": "foaf:homepage", "@type": "@id"},
+                "@base": "http://example.org/people/"
+            },
+            "id": "alice",
+            "type": "foaf:Person",
+            "name": "Alice",
+            "homepage": "../alice.html",
+            "nickname": "Al"
+        }"#;
+        let triples = JsonLdParser::parse(doc).unwrap();
+        assert_eq!(triples.len(), 4);
+        assert!(triples
+            .iter()
+            .all(|t| t.subject == RdfTerm::iri("http://example.org/people/alice")));
+        assert_eq!(
+            object_of(&triples, RDF_TYPE),
+            &RdfTerm::iri("http://xmlns.com/foaf/0.1/Person")
+        );
+        assert_eq!(
+            object_of(&triples, "http://xmlns.com/foaf/0.1/name"),
+            &RdfTerm::literal("Alice")
+        );
+        assert_eq!(
+            object_of(&triples, "http://xmlns.com/foaf/0.1/homepage"),
+            &RdfTerm::iri("http://example.org/people/../alice.html")
+        );
+        // Not an IRI, but kept rather than dropped
+        assert_eq!(object_of(&triples, "nickname"), &RdfTerm::literal("Al"));
+    }
+
+    #[test]
+    fn test_value_objects() {
+        let doc = r#"{
+            "@context": {
+                "@vocab": "http://example.org/",
+                "@language": "en",
+                "xsd": "http://www.w3.org/2001/XMLSchema#",
+                "code": {"@language": null},
+                "settings": {"@type": "@json"}
+            },
+            "count": 3,
+            "ratio": 2.0,
+            "big": {"@value": 7, "@type": "xsd:double"},
+            "active": false,
+            "label": "Widget",
+            "code": "W-1",
+            "title": {"@value": "Chisme", "@language": "es"},
+            "sku": {"@value": "0042", "@type": "xsd:string"},
+            "settings": {"b": [1, 2], "a": null}
+        }"#;
+        let triples = JsonLdParser::parse(doc).unwrap();
+        let object = |name: &str| object_of(&triples, &format!("http://example.org/{name}"));
+
+        assert_eq!(object("count"), &RdfTerm::typed_literal("3", XSD_INTEGER));
+        assert_eq!(object("ratio"), &RdfTerm::typed_literal("2E0", XSD_DOUBLE));
+        assert_eq!(object("big"), &RdfTerm::typed_literal("7E0", XSD_DOUBLE));
+        assert_eq!(
+            object("active"),
+            &RdfTerm::typed_literal("false", XSD_BOOLEAN)
+        );
+        assert_eq!(object("label"), &RdfTerm::lang_literal("Widget", "en"));
+        assert_eq!(object("code"), &RdfTerm::literal("W-1"));
+        assert_eq!(object("title"), &RdfTerm::lang_literal("Chisme", "es"));
+        assert_eq!(
+            object("sku"),
+            &RdfTerm::typed_literal("0042", "http://www.w3.org/2001/XMLSchema#string")
+        );
+        assert_eq!(
+            object("settings"),
+            &RdfTerm::typed_literal(r#"{"a":null,"b":[1,2]}"#, RDF_JSON)
+        );
+        assert_eq!(object("ratio").to_value(), crate::Value::Float(2.0));
+    }
+
+    #[test]
+    fn test_lists_sets_and_nesting() {
+        let doc = r#"{
+            "@context": {
+                "ex": "http://example.org/",
+                "steps": {"@id": "ex:steps", "@container": "@list"}
+            },
+            "@id": "ex:recipe",
+            "steps": ["mix", "bake"],
+            "ex:tags": {"@set": ["quick", "easy"]},
+            "ex:author": {"ex:name": "Ana", "ex:friend": {"@id": "_:x"}},
+            "ex:editor": {"@id": "_:x"}
+        }"#;
+        let triples = JsonLdParser::parse(doc).unwrap();
+        // steps + 2 list cells, 2 tags, author + name + friend, editor
+        assert_eq!(triples.len(), 1 + 4 + 2 + 3 + 1);
+
+        let head = object_of(&triples, "http://example.org/steps");
+        assert!(head.is_blank());
+        let cell = |term: &RdfTerm, predicate: &str| {
+            triples
+                .iter()
+                .find(|t| &t.subject == term && t.predicate.as_iri() == Some(predicate))
+                .map(|t| t.object.clone())
+                .unwrap()
+        };
+        assert_eq!(cell(head, RDF_FIRST), RdfTerm::literal("mix"));
+        let second = cell(head, RDF_REST);
+        assert_eq!(cell(&second, RDF_FIRST), RdfTerm::literal("bake"));
+        assert_eq!(cell(&second, RDF_REST), RdfTerm::iri(RDF_NIL));
+
+        let author = object_of(&triples, "http://example.org/author");
+        assert_eq!(
+            cell(author, "http://example.org/name"),
+            RdfTerm::literal("Ana")
+        );
+        // Both references to `_:x` are one node, distinct from the author
+        let friend = cell(author, "http://example.org/friend");
+        assert_eq!(object_of(&triples, "http://example.org/editor"), &friend);
+        assert_ne!(&friend, author);
+    }
+
+    #[test]
+    fn test_remote_context_is_unsupported() {
+        for doc in [
+            r#"{"@context": "https://schema.org/", "name": "x"}"#,
+            r#"{"@context": [{"ex": "http://example.org/"}, "https://w3id.org/ctx"]}"#,
+            r#"{"@context": {"@import": "https://w3id.org/ctx"}}"#,
+        ] {
+            let err = JsonLdParser::parse(doc).unwrap_err();
+            assert!(matches!(err, Error::Unsupported(_)), "{doc}: {err}");
+        }
+        let err = JsonLdParser::parse(r#"{"@context": "https://schema.org/"}"#).unwrap_err();
+        assert!(err.to_string().contains("https://schema.org/"), "{err}");
+
+        assert!(matches!(
+            JsonLdParser::parse("{not json"),
+            Err(Error::InvalidTriple(_))
+        ));
+    }
+
+    #[test]
+    fn test_compaction_with_supplied_context() {
+        let triples = vec![
+            RdfTriple::new(
+                RdfTerm::iri("http://example.org/alice"),
+                RdfTerm::iri("http://example.org/name"),
+                RdfTerm::literal("Alice"),
+            ),
+            RdfTriple::new(
+                RdfTerm::iri("http://example.org/alice"),
+                RdfTerm::iri("http://example.org/code"),
+                RdfTerm::literal("ex:bob"),
+            ),
+            RdfTriple::new(
+                RdfTerm::iri("http://example.org/alice"),
+                RdfTerm::iri("http://example.org/age"),
+                RdfTerm::typed_literal("30", XSD_INTEGER),
+            ),
+        ];
+        let context = json!({
+            "ex": "http://example.org/",
+            "name": "ex:name",
+            "code": {"@id": "ex:code", "@type": "@id"}
+        });
+        let document = JsonLdSerializer::with_context(context.as_object().unwrap().clone())
+            .to_document(&triples)
+            .unwrap();
+
+        let node = &document["@graph"][0];
+        assert_eq!(document["@context"], context);
+        assert_eq!(node["@id"], "ex:alice");
+        assert_eq!(node["name"], "Alice");
+        assert_eq!(node["ex:age"], 30);
+        // A plain string would read back as an IRI under `code`
+        assert_eq!(node["code"], json!({"@value": "ex:bob"}));
+
+        let again = JsonLdParser::parse_value(&document).unwrap();
+        assert_eq!(again.len(), triples.len());
+        assert!(triples.iter().all(|t| again.contains(t)));
+    }
+
+    #[test]
+    fn test_blank_nodes_nest_once() {
+        let blank = |label: &str| RdfTerm::blank(label);
+        let knows = RdfTerm::iri("http://example.org/knows");
+        let triples = vec![
+            RdfTriple::new(
+                RdfTerm::iri("http://example.org/alice"),
+                knows.clone(),
+                blank("p"),
+            ),
+            RdfTriple::new(blank("p"), knows.clone(), blank("q")),
+            RdfTriple::new(blank("q"), knows.clone(), blank("r")),
+            // `r` and `s` only point at each other
+            RdfTriple::new(blank("r"), knows.clone(), blank("s")),
+            RdfTriple::new(blank("s"), knows.clone(), blank("r")),
+        ];
+        let document = JsonLdSerializer::new().to_document(&triples).unwrap();
+        let graph = document["@graph"].as_array().unwrap();
+
+        // `alice` nests `p` and `q`; `r` is referenced twice
+        assert_eq!(graph.len(), 2);
+        assert_eq!(graph[0]["@id"], "ns1:alice");
+        let q = &graph[0]["ns1:knows"]["ns1:knows"];
+        assert_eq!(q["ns1:knows"], json!({"@id": "_:b3"}));
+        assert_eq!(graph[1]["@id"], "_:b3");
+        assert_eq!(graph[1]["ns1:knows"]["ns1:knows"], json!({"@id": "_:b3"}));
+
+        let again = JsonLdParser::parse_value(&document).unwrap();
+        assert_eq!(again.len(), triples.len());
+    }
+
+    #[test]
+    fn test_resolve() {
+        let base = "http://example.org/a/b?q#frag";
+        assert_eq!(resolve(base, "c"), "http://example.org/a/c");
+        assert_eq!(resolve(base, "/c"), "http://example.org/c");
+        assert_eq!(resolve(base, "#x"), "http://example.org/a/b?q#x");
+        assert_eq!(resolve(base, "//other.org/c"), "http://other.org/c");
+        assert_eq!(resolve("http://example.org", "c"), "http://example.org/c");
+        assert_eq!(resolve(base, "urn:x"), "urn:x");
+    }
+}
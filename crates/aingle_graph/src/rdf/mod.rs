@@ -7,6 +7,12 @@
 //! - Turtle (.ttl) - Terse RDF Triple Language
 //! - N-Triples (.nt) - Line-based triple format
 //! - N-Quads (.nq) - N-Triples with graph context
+//! - JSON-LD (.jsonld) - JSON with inline `@context`, see [`jsonld`]
+//!
+//! Blank node labels only identify a node within one document. Parsing maps
+//! each label to a fresh [`NodeId::anonymous`] through a [`BlankNodeScope`],
+//! so blank nodes from separate imports never unify, and the serializers
+//! number blank nodes `_:b1`, `_:b2`, ... within each export.
 //!
 //! # Example
 //!
@@ -26,15 +32,53 @@
 //! # Ok::<(), aingle_graph::Error>(())
 //! ```
 
+pub mod jsonld;
 pub mod namespace;
 pub mod parser;
 pub mod serializer;
 
+pub use jsonld::{JsonLdParser, JsonLdSerializer};
 pub use namespace::{Namespace, NamespaceMap, PREFIX_AINGLE, PREFIX_RDF, PREFIX_RDFS, PREFIX_XSD};
 pub use parser::{NTriplesParser, RdfParser, TurtleParser};
 pub use serializer::{NTriplesSerializer, RdfSerializer, TurtleSerializer};
 
 use crate::{Error, NodeId, Predicate, Result, Triple, Value};
+use std::collections::HashMap;
+
+/// Maps the blank node labels of one document to graph nodes.
+///
+/// The first use of a label mints a new [`NodeId::anonymous`]; later uses in
+/// the same scope return the same node. Use one scope per document, so equal
+/// labels in different documents stay different nodes.
+#[derive(Debug, Default)]
+pub struct BlankNodeScope {
+    nodes: HashMap<String, NodeId>,
+}
+
+impl BlankNodeScope {
+    /// Creates an empty scope.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the node for a blank node label, minting it on first use.
+    pub fn node(&mut self, label: &str) -> NodeId {
+        self.nodes
+            .entry(label.to_string())
+            .or_insert_with(NodeId::anonymous)
+            .clone()
+    }
+
+    /// Returns the number of distinct labels seen.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns `true` if no label has been seen.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
 
 /// An RDF term that can be a subject, predicate, or object
 #[derive(Debug, Clone, PartialEq)]
@@ -113,17 +157,18 @@ impl RdfTerm {
     }
 
     /// Convert to a NodeId (for subjects)
+    ///
+    /// A blank node becomes a new anonymous node on every call; use
+    /// [`to_node_id_in`](Self::to_node_id_in) to keep its label's identity.
     pub fn to_node_id(&self) -> Option<NodeId> {
+        self.to_node_id_in(&mut BlankNodeScope::new())
+    }
+
+    /// Convert to a NodeId, resolving blank nodes in `scope`
+    pub fn to_node_id_in(&self, scope: &mut BlankNodeScope) -> Option<NodeId> {
         match self {
             Self::Iri(iri) => Some(NodeId::named(iri)),
-            Self::BlankNode(id) => {
-                // Parse blank node ID as u64 if possible
-                if let Ok(n) = id.parse::<u64>() {
-                    Some(NodeId::blank_with_id(n))
-                } else {
-                    Some(NodeId::named(format!("_:{}", id)))
-                }
-            }
+            Self::BlankNode(label) => Some(scope.node(label)),
             Self::Literal { .. } => None, // Literals can't be subjects in RDF
         }
     }
@@ -137,10 +182,18 @@ impl RdfTerm {
     }
 
     /// Convert to a Value (for objects)
+    ///
+    /// A blank node becomes a new anonymous node on every call; use
+    /// [`to_value_in`](Self::to_value_in) to keep its label's identity.
     pub fn to_value(&self) -> Value {
+        self.to_value_in(&mut BlankNodeScope::new())
+    }
+
+    /// Convert to a Value, resolving blank nodes in `scope`
+    pub fn to_value_in(&self, scope: &mut BlankNodeScope) -> Value {
         match self {
             Self::Iri(iri) => Value::Node(NodeId::named(iri)),
-            Self::BlankNode(id) => Value::Node(NodeId::named(format!("_:{}", id))),
+            Self::BlankNode(label) => Value::Node(scope.node(label)),
             Self::Literal {
                 value,
                 datatype,
@@ -200,36 +253,36 @@ impl RdfTriple {
     }
 
     /// Convert to an aingle_graph Triple
+    ///
+    /// Blank nodes are resolved in a scope of their own; use
+    /// [`to_triple_in`](Self::to_triple_in) for triples of the same document.
     pub fn to_triple(&self) -> Result<Triple> {
+        self.to_triple_in(&mut BlankNodeScope::new())
+    }
+
+    /// Convert to an aingle_graph Triple, resolving blank nodes in `scope`
+    pub fn to_triple_in(&self, scope: &mut BlankNodeScope) -> Result<Triple> {
         let subject = self
             .subject
-            .to_node_id()
+            .to_node_id_in(scope)
             .ok_or_else(|| Error::InvalidTriple("subject must be IRI or blank node".into()))?;
         let predicate = self
             .predicate
             .to_predicate()
             .ok_or_else(|| Error::InvalidTriple("predicate must be IRI".into()))?;
-        let object = self.object.to_value();
+        let object = self.object.to_value_in(scope);
 
         Ok(Triple::new(subject, predicate, object))
     }
 
     /// Create from an aingle_graph Triple
     pub fn from_triple(triple: &Triple) -> Self {
-        let subject = match &triple.subject {
-            NodeId::Named(name) => RdfTerm::Iri(name.clone()),
-            NodeId::Hash(hash) => RdfTerm::Iri(format!("urn:hash:{}", hex_encode(hash))),
-            NodeId::Blank(id) => RdfTerm::BlankNode(id.to_string()),
-        };
+        let subject = node_term(&triple.subject);
 
         let predicate = RdfTerm::Iri(triple.predicate.as_str().to_string());
 
         let object = match &triple.object {
-            Value::Node(node) => match node {
-                NodeId::Named(name) => RdfTerm::Iri(name.clone()),
-                NodeId::Hash(hash) => RdfTerm::Iri(format!("urn:hash:{}", hex_encode(hash))),
-                NodeId::Blank(id) => RdfTerm::BlankNode(id.to_string()),
-            },
+            Value::Node(node) => node_term(node),
             Value::String(s) => RdfTerm::literal(s),
             Value::Integer(n) => {
                 RdfTerm::typed_literal(n.to_string(), "http://www.w3.org/2001/XMLSchema#integer")
@@ -267,6 +320,18 @@ impl RdfTriple {
 }
 
 // Helper functions
+
+/// The term for a node; blank nodes are labelled by their stored ID
+fn node_term(node: &NodeId) -> RdfTerm {
+    match node {
+        NodeId::Named(name) => RdfTerm::Iri(name.clone()),
+        NodeId::Hash(hash) => RdfTerm::Iri(format!("urn:hash:{}", hex_encode(hash))),
+        NodeId::Blank(_) | NodeId::Anonymous(_) => {
+            RdfTerm::BlankNode(node.blank_label().unwrap_or_default())
+        }
+    }
+}
+
 fn hex_encode(bytes: &[u8]) -> String {
     bytes.iter().map(|b| format!("{:02x}", b)).collect()
 }
@@ -324,6 +389,28 @@ mod tests {
         assert_eq!(node.as_name(), Some("http://example.org/alice"));
     }
 
+    #[test]
+    fn test_blank_node_scope() {
+        let rdf = RdfTriple::new(
+            RdfTerm::blank("x"),
+            RdfTerm::iri("http://example.org/knows"),
+            RdfTerm::blank("x"),
+        );
+
+        // Within one scope a label is one node
+        let mut scope = BlankNodeScope::new();
+        let first = rdf.to_triple_in(&mut scope).unwrap();
+        let again = rdf.to_triple_in(&mut scope).unwrap();
+        assert!(first.subject.is_anonymous());
+        assert_eq!(first.object_node(), Some(&first.subject));
+        assert_eq!(again.subject, first.subject);
+        assert_eq!(scope.len(), 1);
+
+        // Another scope mints another node
+        let other = rdf.to_triple_in(&mut BlankNodeScope::new()).unwrap();
+        assert_ne!(other.subject, first.subject);
+    }
+
     #[test]
     fn test_rdf_triple_conversion() {
         let rdf = RdfTriple::new(
@@ -200,10 +200,13 @@ impl NamespaceMap {
         self.prefixes.iter().map(|(k, v)| (k.as_str(), v.as_str()))
     }
 
-    /// Generate Turtle @prefix declarations
+    /// Generate Turtle @prefix declarations, sorted by prefix
     pub fn to_turtle_prefixes(&self) -> String {
+        let mut prefixes: Vec<_> = self.prefixes.iter().collect();
+        prefixes.sort();
+
         let mut result = String::new();
-        for (prefix, iri) in &self.prefixes {
+        for (prefix, iri) in prefixes {
             result.push_str(&format!("@prefix {}: <{}> .\n", prefix, iri));
         }
         result
@@ -356,6 +359,22 @@ mod tests {
 
         assert!(turtle.contains("@prefix rdf:"));
         assert!(turtle.contains("@prefix xsd:"));
+
+        // Stable order, so repeated serializations are identical
+        let prefixes: Vec<&str> = turtle
+            .lines()
+            .map(|line| line.split(':').next().unwrap())
+            .collect();
+        assert_eq!(
+            prefixes,
+            [
+                "@prefix aingle",
+                "@prefix owl",
+                "@prefix rdf",
+                "@prefix rdfs",
+                "@prefix xsd"
+            ]
+        );
     }
 
     #[test]
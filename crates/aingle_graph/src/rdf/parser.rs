@@ -5,7 +5,7 @@
 //!
 //! This module provides parsers for standard RDF serialization formats.
 
-use super::{NamespaceMap, RdfTerm, RdfTriple};
+use super::{BlankNodeScope, NamespaceMap, RdfTerm, RdfTriple};
 use crate::{Error, Result, Triple};
 
 /// Trait for RDF parsers
@@ -14,9 +14,15 @@ pub trait RdfParser {
     fn parse(content: &str) -> Result<Vec<RdfTriple>>;
 
     /// Parse RDF content directly into aingle_graph Triples
+    ///
+    /// Blank nodes become new anonymous nodes, one per label in the document.
     fn parse_to_triples(content: &str) -> Result<Vec<Triple>> {
         let rdf_triples = Self::parse(content)?;
-        rdf_triples.into_iter().map(|t| t.to_triple()).collect()
+        let mut scope = BlankNodeScope::new();
+        rdf_triples
+            .iter()
+            .map(|t| t.to_triple_in(&mut scope))
+            .collect()
     }
 }
 
@@ -394,7 +400,9 @@ fn parse_term<I: Iterator<Item = char> + Clone>(
             if chars.peek() == Some(&']') {
                 chars.next();
                 *blank_counter += 1;
-                Ok(RdfTerm::BlankNode(format!("b{}", blank_counter)))
+                // `[` cannot appear in a `_:` label, so this never names a
+                // labelled blank node of the same document
+                Ok(RdfTerm::BlankNode(format!("[]{}", blank_counter)))
             } else {
                 Err(Error::InvalidTriple(
                     "Blank node property lists not yet supported".into(),
@@ -739,6 +747,25 @@ mod tests {
         assert!(triples[0].subject.is_blank());
     }
 
+    #[test]
+    fn test_parse_blank_nodes_to_triples() {
+        let ttl = r#"
+            @prefix ex: <http://example.org/> .
+            _:b1 ex:knows _:b2 .
+            _:b2 ex:knows _:b1 .
+            [] ex:knows _:b1 .
+        "#;
+
+        let triples = TurtleParser::parse_to_triples(ttl).unwrap();
+        assert_eq!(triples.len(), 3);
+        assert!(triples.iter().all(|t| t.subject.is_anonymous()));
+        assert_eq!(triples[0].object_node(), Some(&triples[1].subject));
+        assert_eq!(triples[1].object_node(), Some(&triples[0].subject));
+        // `[]` is a node of its own, not `_:b1`
+        assert_ne!(triples[2].subject, triples[0].subject);
+        assert_eq!(triples[2].object_node(), Some(&triples[0].subject));
+    }
+
     #[test]
     fn test_to_aingle_triple() {
         let rdf = RdfTriple::new(
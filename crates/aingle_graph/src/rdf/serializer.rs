@@ -4,9 +4,12 @@
 //! RDF serializers for Turtle and N-Triples formats
 //!
 //! This module provides serializers for standard RDF serialization formats.
+//! Blank nodes are written as `_:b1`, `_:b2`, ... in order of first
+//! appearance, whatever their labels, so each output numbers them afresh.
 
 use super::{NamespaceMap, RdfTerm, RdfTriple};
 use crate::{Result, Triple};
+use std::collections::HashMap;
 use std::io::Write;
 
 /// Trait for RDF serializers
@@ -72,6 +75,7 @@ impl TurtleSerializer {
 
     /// Serialize triples with configured options
     pub fn serialize_with_options(&self, triples: &[RdfTriple]) -> Result<String> {
+        let triples = &number_blank_nodes(triples);
         let mut output = String::new();
 
         // Write prefix declarations
@@ -252,7 +256,7 @@ impl NTriplesSerializer {
     pub fn serialize(triples: &[RdfTriple]) -> Result<String> {
         let mut output = String::new();
 
-        for triple in triples {
+        for triple in &number_blank_nodes(triples) {
             output.push_str(&Self::format_term(&triple.subject));
             output.push(' ');
             output.push_str(&Self::format_term(&triple.predicate));
@@ -292,6 +296,30 @@ impl RdfSerializer for NTriplesSerializer {
     }
 }
 
+/// Relabels blank nodes `b1`, `b2`, ... in order of first appearance
+pub(super) fn number_blank_nodes(triples: &[RdfTriple]) -> Vec<RdfTriple> {
+    let mut labels: HashMap<String, String> = HashMap::new();
+    let mut relabel = |term: &RdfTerm| match term {
+        RdfTerm::BlankNode(label) => {
+            let next = labels.len() + 1;
+            let label = labels
+                .entry(label.clone())
+                .or_insert_with(|| format!("b{}", next));
+            RdfTerm::BlankNode(label.clone())
+        }
+        other => other.clone(),
+    };
+
+    triples
+        .iter()
+        .map(|triple| RdfTriple {
+            subject: relabel(&triple.subject),
+            predicate: triple.predicate.clone(),
+            object: relabel(&triple.object),
+        })
+        .collect()
+}
+
 /// Escape special characters in a string literal
 fn escape_string(s: &str) -> String {
     let mut result = String::with_capacity(s.len());
@@ -383,6 +411,29 @@ mod tests {
         assert!(output.contains(";"));
     }
 
+    #[test]
+    fn test_blank_nodes_numbered_per_output() {
+        let knows = || RdfTerm::iri("http://example.org/knows");
+        let triples = vec![
+            RdfTriple::new(RdfTerm::blank("x9"), knows(), RdfTerm::blank("[]1")),
+            RdfTriple::new(RdfTerm::blank("[]1"), knows(), RdfTerm::blank("x9")),
+        ];
+
+        let nt = NTriplesSerializer::serialize(&triples).unwrap();
+        assert_eq!(
+            nt,
+            "_:b1 <http://example.org/knows> _:b2 .\n\
+             _:b2 <http://example.org/knows> _:b1 .\n"
+        );
+
+        let ttl = TurtleSerializer::new()
+            .pretty(false)
+            .serialize_with_options(&triples)
+            .unwrap();
+        assert!(ttl.contains("_:b1 <http://example.org/knows> _:b2 .\n"));
+        assert!(!ttl.contains("x9") && !ttl.contains("[]"));
+    }
+
     #[test]
     fn test_escape_string() {
         assert_eq!(escape_string("hello"), "hello");
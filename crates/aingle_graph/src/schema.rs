@@ -0,0 +1,821 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Schema declarations checked when triples are inserted.
+//!
+//! A [`GraphSchema`] lists the predicates a graph uses, with the kinds of
+//! object each takes ([`ObjectKind`], one per [`Value`] variant) and whether
+//! a subject should have at most one object for it ([`Cardinality`]). It can
+//! also list, per class, the predicates every subject of the class should
+//! have; a subject is in a class when it has an `rdf:type` triple pointing
+//! at the class node.
+//!
+//! Installed with [`GraphDB::set_schema`](crate::GraphDB::set_schema), the
+//! schema screens every new triple according to its [`EnforcementMode`]: a
+//! triple with an undeclared predicate or an object of the wrong kind is
+//! logged (`Warn`) or refused with [`Error::Schema`] (`Reject`). Cardinality
+//! is a hint: a second object for a single-valued predicate is only logged,
+//! since an update may insert the new value before deleting the old. Missing
+//! required predicates cannot be told apart from a subject still being
+//! written, so they are only reported by
+//! [`GraphDB::schema_violations`](crate::GraphDB::schema_violations).
+//!
+//! A schema is stored in the graph itself as triples in the [`vocab`]
+//! vocabulary, with [`GraphDB::store_schema`](crate::GraphDB::store_schema),
+//! so it travels with the database. Those triples are never screened.
+//! [`GraphDB::infer_schema`](crate::GraphDB::infer_schema) drafts a schema
+//! from the data already stored.
+//!
+//! # Examples
+//!
+//! ```
+//! use aingle_graph::{
+//!     Cardinality, EnforcementMode, GraphDB, GraphSchema, NodeId, ObjectKind, Predicate,
+//!     PredicateDecl, Triple, Value,
+//! };
+//!
+//! # fn main() -> Result<(), aingle_graph::Error> {
+//! let db = GraphDB::memory()?;
+//! let schema = GraphSchema::new().with_predicate(
+//!     PredicateDecl::new("has_age")
+//!         .with_kind(ObjectKind::Integer)
+//!         .with_cardinality(Cardinality::One),
+//! );
+//! db.set_schema(schema, EnforcementMode::Reject)?;
+//!
+//! let alice = || NodeId::named("user:alice");
+//! db.insert(Triple::new(alice(), Predicate::named("has_age"), Value::integer(30)))?;
+//! assert!(db
+//!     .insert(Triple::new(alice(), Predicate::named("has_age"), Value::literal("30")))
+//!     .is_err());
+//! assert!(db
+//!     .insert(Triple::new(alice(), Predicate::named("hasAge"), Value::integer(30)))
+//!     .is_err());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{Error, NodeId, Predicate, Result, Triple, Value};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+
+/// The vocabulary a [`GraphSchema`] is stored in.
+///
+/// Each declared predicate is a subject named [`PREDICATE_PREFIX`] followed
+/// by the predicate, and each class a subject named [`CLASS_PREFIX`]
+/// followed by the class node's name:
+///
+/// ```text
+/// <aingle:schema:predicate:has_age> <aingle:schema#predicate>   "has_age"
+/// <aingle:schema:predicate:has_age> <aingle:schema#objectKind>  "integer"
+/// <aingle:schema:predicate:has_age> <aingle:schema#cardinality> "one"
+/// <aingle:schema:class:Person>      <aingle:schema#class>       <Person>
+/// <aingle:schema:class:Person>      <aingle:schema#requires>    "has_age"
+/// ```
+///
+/// [`PREDICATE_PREFIX`]: vocab::PREDICATE_PREFIX
+/// [`CLASS_PREFIX`]: vocab::CLASS_PREFIX
+pub mod vocab {
+    /// Prefix of every predicate of the vocabulary
+    pub const NS: &str = "aingle:schema#";
+    /// Prefix of every subject the schema is stored under
+    pub const SUBJECT_PREFIX: &str = "aingle:schema:";
+    /// Prefix of the subjects declaring predicates
+    pub const PREDICATE_PREFIX: &str = "aingle:schema:predicate:";
+    /// Prefix of the subjects declaring classes
+    pub const CLASS_PREFIX: &str = "aingle:schema:class:";
+    /// The declared predicate, as a string
+    pub const PREDICATE: &str = "aingle:schema#predicate";
+    /// An object kind the predicate takes, as its name; none means any
+    pub const OBJECT_KIND: &str = "aingle:schema#objectKind";
+    /// `"one"` or `"many"`
+    pub const CARDINALITY: &str = "aingle:schema#cardinality";
+    /// The declared class node
+    pub const CLASS: &str = "aingle:schema#class";
+    /// A predicate every subject of the class should have, as a string
+    pub const REQUIRES: &str = "aingle:schema#requires";
+}
+
+/// The predicate that puts a subject in a class
+const RDF_TYPE: &str = "rdf:type";
+
+/// What the schema does with a new triple that breaks it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EnforcementMode {
+    /// Triples are not checked
+    #[default]
+    Off,
+    /// Violations are logged and the triple is inserted
+    Warn,
+    /// Violations other than cardinality hints fail the insert
+    Reject,
+}
+
+/// The kind of an object, one per [`Value`] variant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ObjectKind {
+    /// A reference to another node
+    Node,
+    /// A string literal
+    String,
+    /// An integer literal
+    Integer,
+    /// A floating-point literal
+    Float,
+    /// A boolean literal
+    Boolean,
+    /// A date-time literal
+    DateTime,
+    /// A literal with an explicit datatype
+    Typed,
+    /// A string literal with a language tag
+    LangString,
+    /// Binary data
+    Bytes,
+    /// A JSON value
+    Json,
+    /// The null value
+    Null,
+}
+
+impl ObjectKind {
+    /// Every kind, in declaration order
+    pub const ALL: [ObjectKind; 11] = [
+        Self::Node,
+        Self::String,
+        Self::Integer,
+        Self::Float,
+        Self::Boolean,
+        Self::DateTime,
+        Self::Typed,
+        Self::LangString,
+        Self::Bytes,
+        Self::Json,
+        Self::Null,
+    ];
+
+    /// The kind of `value`.
+    pub fn of(value: &Value) -> Self {
+        match value {
+            Value::Node(_) => Self::Node,
+            Value::String(_) => Self::String,
+            Value::Integer(_) => Self::Integer,
+            Value::Float(_) => Self::Float,
+            Value::Boolean(_) => Self::Boolean,
+            Value::DateTime(_) => Self::DateTime,
+            Value::Typed { .. } => Self::Typed,
+            Value::LangString { .. } => Self::LangString,
+            Value::Bytes(_) => Self::Bytes,
+            Value::Json(_) => Self::Json,
+            Value::Null => Self::Null,
+        }
+    }
+
+    /// The name of the kind, as stored in schema triples
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Node => "node",
+            Self::String => "string",
+            Self::Integer => "integer",
+            Self::Float => "float",
+            Self::Boolean => "boolean",
+            Self::DateTime => "date_time",
+            Self::Typed => "typed",
+            Self::LangString => "lang_string",
+            Self::Bytes => "bytes",
+            Self::Json => "json",
+            Self::Null => "null",
+        }
+    }
+
+    /// The kind named `name`, as returned by [`as_str`](Self::as_str).
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.as_str() == name)
+    }
+}
+
+impl fmt::Display for ObjectKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// How many objects a subject should have for a predicate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Cardinality {
+    /// At most one
+    One,
+    /// Any number
+    #[default]
+    Many,
+}
+
+impl Cardinality {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::One => "one",
+            Self::Many => "many",
+        }
+    }
+}
+
+/// A declared predicate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PredicateDecl {
+    /// The predicate
+    pub predicate: Predicate,
+    /// The kinds of object it takes; empty takes any
+    pub kinds: BTreeSet<ObjectKind>,
+    /// How many objects a subject should have for it
+    pub cardinality: Cardinality,
+}
+
+impl PredicateDecl {
+    /// Declares `predicate`, taking objects of any kind, any number of times.
+    pub fn new(predicate: impl Into<String>) -> Self {
+        Self {
+            predicate: Predicate::named(predicate),
+            kinds: BTreeSet::new(),
+            cardinality: Cardinality::Many,
+        }
+    }
+
+    /// Adds a kind of object the predicate takes.
+    pub fn with_kind(mut self, kind: ObjectKind) -> Self {
+        self.kinds.insert(kind);
+        self
+    }
+
+    /// Sets how many objects a subject should have for the predicate.
+    pub fn with_cardinality(mut self, cardinality: Cardinality) -> Self {
+        self.cardinality = cardinality;
+        self
+    }
+
+    /// Whether the predicate takes `value`
+    pub fn accepts(&self, value: &Value) -> bool {
+        self.kinds.is_empty() || self.kinds.contains(&ObjectKind::of(value))
+    }
+}
+
+impl fmt::Display for PredicateDecl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} takes ", self.predicate)?;
+        if self.kinds.is_empty() {
+            f.write_str("any object")?;
+        } else {
+            let kinds: Vec<_> = self.kinds.iter().map(ObjectKind::as_str).collect();
+            f.write_str(&kinds.join(" | "))?;
+        }
+        if self.cardinality == Cardinality::One {
+            f.write_str(", at most one per subject")?;
+        }
+        Ok(())
+    }
+}
+
+/// A declared class and the predicates its subjects should have
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassDecl {
+    /// The class node, the object of its subjects' `rdf:type` triples
+    pub class: NodeId,
+    /// Predicates every subject of the class should have
+    pub required: BTreeSet<Predicate>,
+}
+
+impl ClassDecl {
+    /// Declares the class named `class`, requiring nothing.
+    pub fn new(class: impl Into<String>) -> Self {
+        Self {
+            class: NodeId::named(class),
+            required: BTreeSet::new(),
+        }
+    }
+
+    /// Adds a predicate every subject of the class should have.
+    pub fn requires(mut self, predicate: impl Into<String>) -> Self {
+        self.required.insert(Predicate::named(predicate));
+        self
+    }
+}
+
+/// A way a triple or subject breaks a [`GraphSchema`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaViolation {
+    /// The predicate is not declared
+    Undeclared {
+        /// The triple
+        triple: Triple,
+    },
+    /// The object is of a kind the predicate does not take
+    WrongKind {
+        /// The triple
+        triple: Triple,
+        /// The predicate's declaration
+        decl: PredicateDecl,
+    },
+    /// The subject has more than one object for a single-valued predicate
+    TooMany {
+        /// The subject
+        subject: NodeId,
+        /// The predicate's declaration
+        decl: PredicateDecl,
+        /// How many objects the subject has for it
+        count: usize,
+    },
+    /// A subject of a class lacks a predicate the class requires
+    MissingRequired {
+        /// The subject
+        subject: NodeId,
+        /// The class
+        class: NodeId,
+        /// The missing predicate
+        predicate: Predicate,
+    },
+}
+
+impl SchemaViolation {
+    /// Whether the violation is only a hint, logged but never refused
+    pub fn is_hint(&self) -> bool {
+        matches!(self, Self::TooMany { .. })
+    }
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Undeclared { triple } => write!(
+                f,
+                "{} {} {}: predicate is not declared in the schema",
+                triple.subject, triple.predicate, triple.object
+            ),
+            Self::WrongKind { triple, decl } => write!(
+                f,
+                "{} {} {}: object is {}, but {}",
+                triple.subject,
+                triple.predicate,
+                triple.object,
+                ObjectKind::of(&triple.object),
+                decl
+            ),
+            Self::TooMany {
+                subject,
+                decl,
+                count,
+            } => write!(f, "{} has {} objects, but {}", subject, count, decl),
+            Self::MissingRequired {
+                subject,
+                class,
+                predicate,
+            } => write!(
+                f,
+                "{} is a {} but has no {}, which the class requires",
+                subject, class, predicate
+            ),
+        }
+    }
+}
+
+/// Declared predicates and classes of a graph
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GraphSchema {
+    predicates: BTreeMap<Predicate, PredicateDecl>,
+    classes: BTreeMap<NodeId, ClassDecl>,
+}
+
+impl GraphSchema {
+    /// Creates a schema declaring nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declares a predicate, replacing any earlier declaration of it.
+    pub fn with_predicate(mut self, decl: PredicateDecl) -> Self {
+        self.predicates.insert(decl.predicate.clone(), decl);
+        self
+    }
+
+    /// Declares a class, replacing any earlier declaration of it.
+    pub fn with_class(mut self, decl: ClassDecl) -> Self {
+        self.classes.insert(decl.class.clone(), decl);
+        self
+    }
+
+    /// The declaration of `predicate`, if any
+    pub fn predicate(&self, predicate: &Predicate) -> Option<&PredicateDecl> {
+        self.predicates.get(predicate)
+    }
+
+    /// The declared predicates, in predicate order
+    pub fn predicates(&self) -> impl Iterator<Item = &PredicateDecl> {
+        self.predicates.values()
+    }
+
+    /// The declaration of `class`, if any
+    pub fn class(&self, class: &NodeId) -> Option<&ClassDecl> {
+        self.classes.get(class)
+    }
+
+    /// The declared classes, in class order
+    pub fn classes(&self) -> impl Iterator<Item = &ClassDecl> {
+        self.classes.values()
+    }
+
+    /// Returns `true` if the schema declares nothing.
+    pub fn is_empty(&self) -> bool {
+        self.predicates.is_empty() && self.classes.is_empty()
+    }
+
+    /// Whether `triple` is part of a stored schema, and so never checked
+    pub fn is_schema_triple(triple: &Triple) -> bool {
+        triple.predicate.as_str().starts_with(vocab::NS)
+    }
+
+    /// Checks the predicate and object kind of one triple.
+    ///
+    /// Cardinality and required predicates depend on the rest of the graph;
+    /// see [`GraphDB::schema_violations`](crate::GraphDB::schema_violations).
+    pub fn check(&self, triple: &Triple) -> Option<SchemaViolation> {
+        if Self::is_schema_triple(triple) {
+            return None;
+        }
+        match self.predicates.get(&triple.predicate) {
+            None => Some(SchemaViolation::Undeclared {
+                triple: triple.clone(),
+            }),
+            Some(decl) if !decl.accepts(&triple.object) => Some(SchemaViolation::WrongKind {
+                triple: triple.clone(),
+                decl: decl.clone(),
+            }),
+            Some(_) => None,
+        }
+    }
+
+    /// Checks every triple of a graph, and its subjects' cardinalities and
+    /// required predicates.
+    ///
+    /// Violations are listed triple by triple, then subject by subject.
+    pub fn check_all(&self, triples: &[Triple]) -> Vec<SchemaViolation> {
+        let mut violations: Vec<_> = triples.iter().filter_map(|t| self.check(t)).collect();
+
+        let mut subjects: BTreeMap<&NodeId, BTreeMap<&Predicate, usize>> = BTreeMap::new();
+        for triple in triples.iter().filter(|t| !Self::is_schema_triple(t)) {
+            *subjects
+                .entry(&triple.subject)
+                .or_default()
+                .entry(&triple.predicate)
+                .or_default() += 1;
+        }
+        let classes = classes_of(triples);
+        for (subject, counts) in &subjects {
+            for (predicate, &count) in counts {
+                let single = self
+                    .predicates
+                    .get(*predicate)
+                    .filter(|decl| decl.cardinality == Cardinality::One);
+                if let Some(decl) = single.filter(|_| count > 1) {
+                    violations.push(SchemaViolation::TooMany {
+                        subject: (*subject).clone(),
+                        decl: decl.clone(),
+                        count,
+                    });
+                }
+            }
+            for class in classes.get(*subject).into_iter().flatten() {
+                let Some(decl) = self.classes.get(*class) else {
+                    continue;
+                };
+                for predicate in &decl.required {
+                    if !counts.contains_key(predicate) {
+                        violations.push(SchemaViolation::MissingRequired {
+                            subject: (*subject).clone(),
+                            class: (*class).clone(),
+                            predicate: predicate.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        violations
+    }
+
+    /// Drafts a schema describing `triples`.
+    ///
+    /// Each predicate is declared with the kinds of object it has, single
+    /// valued if no subject has two objects for it. Each class is declared
+    /// requiring the predicates all of its subjects have. Review the draft
+    /// before enforcing it: it accepts every mistake already in the data.
+    pub fn infer(triples: &[Triple]) -> Self {
+        let mut schema = Self::new();
+        let mut counts: HashMap<(&NodeId, &Predicate), usize> = HashMap::new();
+        for triple in triples.iter().filter(|t| !Self::is_schema_triple(t)) {
+            schema
+                .predicates
+                .entry(triple.predicate.clone())
+                .or_insert_with(|| PredicateDecl {
+                    predicate: triple.predicate.clone(),
+                    kinds: BTreeSet::new(),
+                    cardinality: Cardinality::One,
+                })
+                .kinds
+                .insert(ObjectKind::of(&triple.object));
+            *counts
+                .entry((&triple.subject, &triple.predicate))
+                .or_default() += 1;
+        }
+        for ((_, predicate), count) in counts {
+            if count > 1 {
+                if let Some(decl) = schema.predicates.get_mut(predicate) {
+                    decl.cardinality = Cardinality::Many;
+                }
+            }
+        }
+
+        let mut members: BTreeMap<&NodeId, Vec<&NodeId>> = BTreeMap::new();
+        for (subject, classes) in classes_of(triples) {
+            for class in classes {
+                members.entry(class).or_default().push(subject);
+            }
+        }
+        for (class, subjects) in members {
+            let mut required: Option<BTreeSet<Predicate>> = None;
+            for subject in subjects {
+                let has: BTreeSet<Predicate> = triples
+                    .iter()
+                    .filter(|t| &t.subject == subject && t.predicate.as_str() != RDF_TYPE)
+                    .map(|t| t.predicate.clone())
+                    .collect();
+                required = Some(match required {
+                    Some(required) => required.intersection(&has).cloned().collect(),
+                    None => has,
+                });
+            }
+            schema = schema.with_class(ClassDecl {
+                class: class.clone(),
+                required: required.unwrap_or_default(),
+            });
+        }
+        schema
+    }
+
+    /// The triples storing the schema, in the [`vocab`] vocabulary
+    pub fn to_triples(&self) -> Vec<Triple> {
+        let literal = |subject: &NodeId, predicate: &str, value: &str| {
+            Triple::new(
+                subject.clone(),
+                Predicate::named(predicate),
+                Value::literal(value),
+            )
+        };
+        let mut triples = Vec::new();
+        for decl in self.predicates.values() {
+            let subject = NodeId::named(format!(
+                "{}{}",
+                vocab::PREDICATE_PREFIX,
+                decl.predicate.as_str()
+            ));
+            triples.push(literal(&subject, vocab::PREDICATE, decl.predicate.as_str()));
+            for kind in &decl.kinds {
+                triples.push(literal(&subject, vocab::OBJECT_KIND, kind.as_str()));
+            }
+            triples.push(literal(
+                &subject,
+                vocab::CARDINALITY,
+                decl.cardinality.as_str(),
+            ));
+        }
+        for decl in self.classes.values() {
+            let name = decl
+                .class
+                .as_name()
+                .map_or_else(|| decl.class.to_string(), str::to_string);
+            let subject = NodeId::named(format!("{}{}", vocab::CLASS_PREFIX, name));
+            triples.push(Triple::new(
+                subject.clone(),
+                Predicate::named(vocab::CLASS),
+                Value::Node(decl.class.clone()),
+            ));
+            for predicate in &decl.required {
+                triples.push(literal(&subject, vocab::REQUIRES, predicate.as_str()));
+            }
+        }
+        triples
+    }
+
+    /// Reads a schema from the triples storing it.
+    ///
+    /// Triples outside the [`vocab`] vocabulary are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Schema`] if a declaration is malformed, e.g. names
+    /// an unknown object kind or has no predicate.
+    pub fn from_triples(triples: &[Triple]) -> Result<Self> {
+        let mut subjects: BTreeMap<&NodeId, Vec<&Triple>> = BTreeMap::new();
+        for triple in triples.iter().filter(|t| Self::is_schema_triple(t)) {
+            subjects.entry(&triple.subject).or_default().push(triple);
+        }
+
+        let malformed = |subject: &NodeId, reason: &str| {
+            Error::Schema(format!("stored declaration {} {}", subject, reason))
+        };
+        let mut schema = Self::new();
+        for (subject, triples) in subjects {
+            let string = |predicate: &'static str| {
+                triples
+                    .iter()
+                    .filter(move |t| t.predicate.as_str() == predicate)
+                    .map(move |t| {
+                        t.object
+                            .as_string()
+                            .ok_or_else(|| malformed(subject, predicate))
+                    })
+            };
+            let class = triples
+                .iter()
+                .find(|t| t.predicate.as_str() == vocab::CLASS)
+                .map(|t| t.object.as_node().cloned());
+            if let Some(class) = class {
+                let class = class.ok_or_else(|| malformed(subject, "has a literal class"))?;
+                let required = string(vocab::REQUIRES)
+                    .map(|name| name.map(Predicate::named))
+                    .collect::<Result<_>>()?;
+                schema = schema.with_class(ClassDecl { class, required });
+                continue;
+            }
+
+            let predicate = string(vocab::PREDICATE)
+                .next()
+                .ok_or_else(|| malformed(subject, "declares no predicate"))??;
+            let kinds = string(vocab::OBJECT_KIND)
+                .map(|name| {
+                    let name = name?;
+                    ObjectKind::parse(name).ok_or_else(|| {
+                        malformed(subject, &format!("has unknown object kind {}", name))
+                    })
+                })
+                .collect::<Result<_>>()?;
+            let cardinality = match string(vocab::CARDINALITY).next().transpose()? {
+                Some("one") => Cardinality::One,
+                Some("many") | None => Cardinality::Many,
+                Some(other) => {
+                    return Err(malformed(
+                        subject,
+                        &format!("has unknown cardinality {}", other),
+                    ))
+                }
+            };
+            schema = schema.with_predicate(PredicateDecl {
+                predicate: Predicate::named(predicate),
+                kinds,
+                cardinality,
+            });
+        }
+        Ok(schema)
+    }
+}
+
+/// The classes of each subject, from its `rdf:type` triples
+fn classes_of(triples: &[Triple]) -> BTreeMap<&NodeId, Vec<&NodeId>> {
+    let mut classes: BTreeMap<&NodeId, Vec<&NodeId>> = BTreeMap::new();
+    for triple in triples {
+        if triple.predicate.as_str() == RDF_TYPE {
+            if let Some(class) = triple.object.as_node() {
+                classes.entry(&triple.subject).or_default().push(class);
+            }
+        }
+    }
+    classes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn person_schema() -> GraphSchema {
+        GraphSchema::new()
+            .with_predicate(PredicateDecl::new(RDF_TYPE).with_kind(ObjectKind::Node))
+            .with_predicate(
+                PredicateDecl::new("has_age")
+                    .with_kind(ObjectKind::Integer)
+                    .with_cardinality(Cardinality::One),
+            )
+            .with_predicate(
+                PredicateDecl::new("knows")
+                    .with_kind(ObjectKind::Node)
+                    .with_kind(ObjectKind::String),
+            )
+            .with_class(ClassDecl::new("Person").requires("has_age"))
+    }
+
+    fn triple(subject: &str, predicate: &str, object: Value) -> Triple {
+        Triple::new(NodeId::named(subject), Predicate::named(predicate), object)
+    }
+
+    #[test]
+    fn test_check_flags_undeclared_and_wrong_kind() {
+        let schema = person_schema();
+        assert_eq!(
+            schema.check(&triple("user:alice", "has_age", Value::integer(30))),
+            None
+        );
+
+        let typo = schema
+            .check(&triple("user:alice", "hasAge", Value::integer(30)))
+            .unwrap();
+        assert!(matches!(typo, SchemaViolation::Undeclared { .. }));
+
+        let wrong = schema
+            .check(&triple("user:alice", "has_age", Value::literal("30")))
+            .unwrap();
+        assert!(matches!(wrong, SchemaViolation::WrongKind { .. }));
+        let message = wrong.to_string();
+        assert!(message.contains("object is string"), "{message}");
+        assert!(
+            message.contains("<has_age> takes integer, at most one per subject"),
+            "{message}"
+        );
+    }
+
+    #[test]
+    fn test_check_all_reports_cardinality_and_required() {
+        let schema = person_schema();
+        let person = || Value::Node(NodeId::named("Person"));
+        let violations = schema.check_all(&[
+            triple("user:alice", RDF_TYPE, person()),
+            triple("user:alice", "has_age", Value::integer(30)),
+            triple("user:alice", "has_age", Value::integer(31)),
+            triple("user:bob", RDF_TYPE, person()),
+        ]);
+
+        assert_eq!(violations.len(), 2);
+        assert!(matches!(
+            &violations[0],
+            SchemaViolation::TooMany { count: 2, .. }
+        ));
+        assert!(violations[0].is_hint());
+        assert_eq!(
+            violations[1],
+            SchemaViolation::MissingRequired {
+                subject: NodeId::named("user:bob"),
+                class: NodeId::named("Person"),
+                predicate: Predicate::named("has_age"),
+            }
+        );
+    }
+
+    #[test]
+    fn test_schema_triples_round_trip() {
+        let schema = person_schema();
+        let triples = schema.to_triples();
+        assert!(triples.iter().all(GraphSchema::is_schema_triple));
+        assert_eq!(GraphSchema::from_triples(&triples).unwrap(), schema);
+
+        let bad = vec![triple(
+            "aingle:schema:predicate:x",
+            vocab::OBJECT_KIND,
+            Value::literal("decimal"),
+        )];
+        assert!(matches!(
+            GraphSchema::from_triples(&bad),
+            Err(Error::Schema(_))
+        ));
+    }
+
+    #[test]
+    fn test_infer_drafts_kinds_cardinality_and_classes() {
+        let person = || Value::Node(NodeId::named("Person"));
+        let schema = GraphSchema::infer(&[
+            triple("user:alice", RDF_TYPE, person()),
+            triple("user:alice", "has_age", Value::integer(30)),
+            triple(
+                "user:alice",
+                "knows",
+                Value::Node(NodeId::named("user:bob")),
+            ),
+            triple("user:alice", "knows", Value::literal("carol")),
+            triple("user:bob", RDF_TYPE, person()),
+            triple("user:bob", "has_age", Value::integer(41)),
+        ]);
+
+        let has_age = schema.predicate(&Predicate::named("has_age")).unwrap();
+        assert_eq!(has_age.kinds, BTreeSet::from([ObjectKind::Integer]));
+        assert_eq!(has_age.cardinality, Cardinality::One);
+        let knows = schema.predicate(&Predicate::named("knows")).unwrap();
+        assert_eq!(
+            knows.kinds,
+            BTreeSet::from([ObjectKind::Node, ObjectKind::String])
+        );
+        assert_eq!(knows.cardinality, Cardinality::Many);
+
+        let class = schema.class(&NodeId::named("Person")).unwrap();
+        assert_eq!(
+            class.required,
+            BTreeSet::from([Predicate::named("has_age")])
+        );
+    }
+}
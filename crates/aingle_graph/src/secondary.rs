@@ -0,0 +1,288 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Opt-in secondary indexes over the objects of a predicate.
+//!
+//! The SPO, POS and OSP indexes answer range queries on a predicate's
+//! objects only by reading every triple with the predicate. A secondary
+//! index created with [`GraphDB::create_index`](crate::GraphDB::create_index)
+//! keeps the triples of one predicate sorted by object, so a query with an
+//! [`object_range`](crate::QueryBuilder::object_range) on that predicate reads
+//! only the matches.
+//!
+//! Index entries are stored by the backend next to the triples and kept up
+//! to date by every insert and delete. Existing triples are indexed in
+//! chunks, each holding the index write lock only while it runs, and the
+//! build progress is stored with the index definition, so an interrupted
+//! build resumes where it stopped. The planner only uses an index once its
+//! build is complete.
+//!
+//! # Examples
+//!
+//! ```
+//! use aingle_graph::{GraphDB, IndexKind, NodeId, Predicate, Triple, Value};
+//!
+//! # fn main() -> Result<(), aingle_graph::Error> {
+//! let db = GraphDB::memory()?;
+//! for (sensor, reading) in [("sensor:1", 17), ("sensor:2", 42), ("sensor:3", 23)] {
+//!     db.insert(Triple::new(
+//!         NodeId::named(sensor),
+//!         Predicate::named("reading"),
+//!         Value::integer(reading),
+//!     ))?;
+//! }
+//!
+//! let info = db.create_index(Predicate::named("reading"), IndexKind::NumericObject)?;
+//! assert_eq!(info.entries, 3);
+//!
+//! let query = db.query()
+//!     .predicate(Predicate::named("reading"))
+//!     .object_range(Value::integer(20)..);
+//! assert_eq!(query.explain()?.steps[0].range_index, Some(IndexKind::NumericObject));
+//! assert_eq!(query.execute()?.len(), 2);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::query::ObjectRange;
+use crate::{Error, Predicate, Result, Triple, TripleId, Value};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Number of triples indexed per chunk by
+/// [`GraphDB::create_index`](crate::GraphDB::create_index).
+pub const INDEX_BUILD_CHUNK: usize = 10_000;
+
+/// Length of the numeric prefix of an index key.
+const NUMERIC_KEY_LEN: usize = 8;
+
+/// Length of the sort key of an `Integer` or `Float` object.
+const OBJECT_KEY_LEN: usize = 9;
+
+/// Length of a triple ID.
+const ID_LEN: usize = 32;
+
+/// The kinds of secondary index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IndexKind {
+    /// `Integer` and `Float` objects of the predicate, sorted by numeric
+    /// value and then subject. Triples with other objects are not indexed.
+    NumericObject,
+}
+
+impl fmt::Display for IndexKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IndexKind::NumericObject => write!(f, "numeric_object"),
+        }
+    }
+}
+
+/// Whether a secondary index can be used by queries yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IndexState {
+    /// Existing triples are still being indexed; queries do not use it.
+    Building,
+    /// Every triple of the predicate is indexed.
+    Ready,
+}
+
+/// A secondary index and its size, as listed by
+/// [`GraphDB::indexes`](crate::GraphDB::indexes) and
+/// [`GraphStats::indexes`](crate::GraphStats::indexes).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexInfo {
+    /// The indexed predicate.
+    pub predicate: Predicate,
+    /// The kind of index.
+    pub kind: IndexKind,
+    /// Whether the build is complete.
+    pub state: IndexState,
+    /// The number of indexed triples.
+    pub entries: usize,
+}
+
+/// The stored definition of a secondary index.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct IndexDef {
+    pub predicate: Predicate,
+    pub kind: IndexKind,
+    pub progress: BuildProgress,
+}
+
+/// How far the build of a secondary index has come.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) enum BuildProgress {
+    /// Triples up to this (object key, ID) position of the predicate's POS
+    /// index are indexed, none if the build has not started.
+    Building(Option<(Vec<u8>, TripleId)>),
+    /// The build is complete.
+    Ready,
+}
+
+impl IndexDef {
+    /// A definition for an index whose build has not started.
+    pub fn new(predicate: Predicate, kind: IndexKind) -> Self {
+        Self {
+            predicate,
+            kind,
+            progress: BuildProgress::Building(None),
+        }
+    }
+
+    /// The name the backend stores the index under.
+    pub fn name(&self) -> &str {
+        self.predicate.as_str()
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.progress == BuildProgress::Ready
+    }
+
+    pub fn info(&self, entries: usize) -> IndexInfo {
+        IndexInfo {
+            predicate: self.predicate.clone(),
+            kind: self.kind,
+            state: if self.is_ready() {
+                IndexState::Ready
+            } else {
+                IndexState::Building
+            },
+            entries,
+        }
+    }
+
+    /// The index key of a triple, or `None` if the index does not hold it.
+    ///
+    /// Keys sort by numeric value, then object, subject and ID. Integers and
+    /// floats share the numeric prefix, so keys of large integers that round
+    /// to the same float are only ordered exactly by the object part.
+    pub fn entry_key(&self, triple: &Triple, id: &TripleId) -> Option<Vec<u8>> {
+        if triple.predicate != self.predicate {
+            return None;
+        }
+        match self.kind {
+            IndexKind::NumericObject => {
+                let numeric = numeric_key(&triple.object)?;
+                let mut key = numeric.to_be_bytes().to_vec();
+                key.extend(triple.object.sort_key());
+                key.extend(triple.subject.to_bytes());
+                key.extend(id.as_bytes());
+                Some(key)
+            }
+        }
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        bincode::serde::encode_to_vec(self, bincode::config::standard()).unwrap_or_default()
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        bincode::serde::decode_from_slice(bytes, bincode::config::standard())
+            .map(|(def, _)| def)
+            .map_err(|e| Error::Serialization(format!("corrupt index definition: {}", e)))
+    }
+}
+
+/// The object and triple ID of an index key.
+pub(crate) fn decode_entry(key: &[u8]) -> Option<(Value, TripleId)> {
+    if key.len() < NUMERIC_KEY_LEN + OBJECT_KEY_LEN + ID_LEN {
+        return None;
+    }
+    let object =
+        Value::numeric_from_sort_key(&key[NUMERIC_KEY_LEN..NUMERIC_KEY_LEN + OBJECT_KEY_LEN])?;
+    let id = <[u8; ID_LEN]>::try_from(&key[key.len() - ID_LEN..]).ok()?;
+    Some((object, TripleId::new(id)))
+}
+
+/// The keys to scan for a range: from the first (inclusive) to the second
+/// (exclusive), or to the end if there is none.
+///
+/// The bounds only cover the numeric prefix, so the scan may return keys
+/// just outside the range; callers check each object against it.
+pub(crate) fn scan_bounds(range: &ObjectRange) -> (Vec<u8>, Option<Vec<u8>>) {
+    let start = match range.start().map(numeric_key) {
+        Some(Some(numeric)) => numeric.to_be_bytes().to_vec(),
+        _ => Vec::new(),
+    };
+    let end = match range.end().map(numeric_key) {
+        Some(Some(numeric)) => numeric
+            .checked_add(1)
+            .map(|next| next.to_be_bytes().to_vec()),
+        _ => None,
+    };
+    (start, end)
+}
+
+/// The numeric value of an `Integer` or `Float` as a key that sorts like
+/// [`f64::total_cmp`].
+fn numeric_key(value: &Value) -> Option<u64> {
+    let bits = match value {
+        Value::Integer(_) | Value::Float(_) => value.as_float()?.to_bits(),
+        _ => return None,
+    };
+    Some(if bits >> 63 == 0 {
+        bits ^ (1u64 << 63)
+    } else {
+        !bits
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NodeId;
+
+    fn entry(def: &IndexDef, object: Value) -> Vec<u8> {
+        let triple = Triple::new(NodeId::named("s"), def.predicate.clone(), object);
+        def.entry_key(&triple, &triple.id()).unwrap()
+    }
+
+    #[test]
+    fn test_keys_sort_numerically_across_types() {
+        let def = IndexDef::new(Predicate::named("reading"), IndexKind::NumericObject);
+        let values = [
+            Value::Float(f64::NEG_INFINITY),
+            Value::integer(-5),
+            Value::Float(-0.5),
+            Value::Float(-0.0),
+            Value::integer(0),
+            Value::Float(0.25),
+            Value::integer(1),
+            Value::Float(1.5),
+            Value::integer(i64::MAX),
+        ];
+        let keys: Vec<Vec<u8>> = values.iter().map(|v| entry(&def, v.clone())).collect();
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+
+        for (key, value) in keys.iter().zip(&values) {
+            assert_eq!(decode_entry(key).unwrap().0, *value);
+        }
+    }
+
+    #[test]
+    fn test_only_numeric_objects_of_the_predicate_are_indexed() {
+        let def = IndexDef::new(Predicate::named("reading"), IndexKind::NumericObject);
+        let text = Triple::literal("s", "reading", "high");
+        let other = Triple::new(
+            NodeId::named("s"),
+            Predicate::named("other"),
+            Value::integer(1),
+        );
+        assert!(def.entry_key(&text, &text.id()).is_none());
+        assert!(def.entry_key(&other, &other.id()).is_none());
+    }
+
+    #[test]
+    fn test_definition_roundtrip() {
+        let mut def = IndexDef::new(Predicate::named("reading"), IndexKind::NumericObject);
+        def.progress = BuildProgress::Building(Some((vec![2, 1], TripleId::new([7; 32]))));
+        let decoded = IndexDef::decode(&def.encode()).unwrap();
+        assert_eq!(decoded.predicate, def.predicate);
+        assert_eq!(decoded.progress, def.progress);
+        assert_eq!(decoded.info(3).state, IndexState::Building);
+        assert!(IndexDef::decode(b"junk").is_err());
+    }
+}
@@ -0,0 +1,136 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Consistent read snapshots
+//!
+//! A [`GraphSnapshot`] answers every read from the same state of the graph,
+//! however many writes are committed meanwhile by other threads. It holds the
+//! store's index read lock for as long as it lives: writers wait for it, so
+//! snapshots should be short-lived.
+//!
+//! The lock is not reentrant. While a thread holds a snapshot it must not
+//! write to the [`GraphDB`](crate::GraphDB) it came from, nor read it other
+//! than through the snapshot: with a writer waiting, a second read lock on
+//! the same thread can block forever.
+
+use crate::query::{QueryBuilder, TriplePattern};
+use crate::store::StoreView;
+use crate::{GraphStats, NodeId, Predicate, Result, Triple, TripleId};
+
+/// A consistent read-only view of a graph
+///
+/// Created by [`GraphDB::snapshot`](crate::GraphDB::snapshot).
+pub struct GraphSnapshot<'a> {
+    view: StoreView<'a>,
+}
+
+impl<'a> GraphSnapshot<'a> {
+    pub(crate) fn new(view: StoreView<'a>) -> Self {
+        Self { view }
+    }
+
+    /// Finds all triples matching a pattern
+    pub fn find(&self, pattern: TriplePattern) -> Result<Vec<Triple>> {
+        self.view.find(pattern)
+    }
+
+    /// Starts a query that reads this snapshot
+    pub fn query(&self) -> QueryBuilder<'_> {
+        QueryBuilder::on_view(&self.view)
+    }
+
+    /// Traverses the graph from a node, following the given predicates
+    pub fn traverse(&self, start: &NodeId, predicates: &[Predicate]) -> Result<Vec<NodeId>> {
+        self.view.traverse(start, predicates)
+    }
+
+    /// Returns the number of triples in the snapshot
+    pub fn count(&self) -> usize {
+        self.view.count()
+    }
+
+    /// Retrieves a triple by its ID, if it is in the snapshot
+    pub fn get(&self, id: &TripleId) -> Result<Option<Triple>> {
+        self.view.get(id)
+    }
+
+    /// Returns `true` if a triple with the same content is in the snapshot
+    pub fn contains(&self, triple: &Triple) -> bool {
+        self.view.contains(triple)
+    }
+
+    /// Returns statistics about the snapshot
+    pub fn stats(&self) -> GraphStats {
+        self.view.stats()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GraphDB, Value};
+
+    fn triple(n: usize) -> Triple {
+        Triple::new(
+            NodeId::named(format!("user:{n}")),
+            Predicate::named("has_score"),
+            Value::integer(n as i64),
+        )
+    }
+
+    #[test]
+    fn test_snapshot_reads_agree() {
+        let db = GraphDB::memory().unwrap();
+        db.insert_batch((0..10).map(triple).collect()).unwrap();
+        db.insert(Triple::link(
+            NodeId::named("user:0"),
+            Predicate::named("knows"),
+            NodeId::named("user:1"),
+        ))
+        .unwrap();
+
+        let snapshot = db.snapshot().unwrap();
+        assert_eq!(snapshot.count(), 11);
+        assert_eq!(snapshot.stats().triple_count, 11);
+        assert_eq!(snapshot.find(TriplePattern::any()).unwrap().len(), 11);
+        assert_eq!(
+            snapshot
+                .query()
+                .predicate(Predicate::named("has_score"))
+                .count()
+                .unwrap(),
+            10
+        );
+        assert_eq!(
+            snapshot
+                .traverse(&NodeId::named("user:0"), &[Predicate::named("knows")])
+                .unwrap(),
+            vec![NodeId::named("user:1")]
+        );
+
+        let first = triple(0);
+        assert!(snapshot.contains(&first));
+        let stored = snapshot.get(&first.id()).unwrap().unwrap();
+        assert_eq!(stored.id(), first.id());
+        assert!(!snapshot.contains(&triple(10)));
+        assert_eq!(snapshot.get(&triple(10).id()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_new_snapshot_sees_committed_writes() {
+        let db = GraphDB::memory().unwrap();
+        db.insert(triple(0)).unwrap();
+
+        let snapshot = db.snapshot().unwrap();
+        assert_eq!(snapshot.count(), 1);
+        drop(snapshot);
+
+        db.insert(triple(1)).unwrap();
+        db.delete(&triple(0).id()).unwrap();
+
+        let snapshot = db.snapshot().unwrap();
+        assert_eq!(snapshot.count(), 1);
+        assert!(snapshot.contains(&triple(1)));
+        assert!(!snapshot.contains(&triple(0)));
+    }
+}
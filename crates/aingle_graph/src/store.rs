@@ -6,11 +6,20 @@
 //! `GraphStore` orchestrates operations between the storage backend and the in-memory triple indexes.
 
 use crate::{
-    backends::StorageBackend, index::TripleIndex, Error, GraphStats, NodeId, Predicate, Result,
-    Triple, TripleId, TriplePattern,
+    backends::{BackendInfo, StorageBackend, StorageOptions, VerifyOnOpen},
+    cache::SubjectCache,
+    events::{EventBus, GraphEvent, OverflowPolicy, Receiver},
+    index::TripleIndex,
+    integrity::{self, IntegrityReport, RebuildProgress, ShutdownMarker, REBUILD_CHUNK},
+    query::ObjectRange,
+    schema::{Cardinality, EnforcementMode, GraphSchema, SchemaViolation},
+    secondary::{self, BuildProgress, IndexDef, IndexInfo, IndexKind, INDEX_BUILD_CHUNK},
+    Component, Error, GraphStats, NodeId, OrderKey, Predicate, PredicateStats, ProvenanceFilter,
+    Result, SortOrder, Triple, TripleId, TripleMeta, TriplePattern,
 };
-use std::collections::HashSet;
-use std::sync::{Arc, RwLock};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::sync::{Arc, RwLock, RwLockReadGuard};
 
 /// The main storage engine for the graph database.
 ///
@@ -22,36 +31,250 @@ pub struct GraphStore {
     backend: Box<dyn StorageBackend>,
     /// The in-memory indexes (SPO, POS, OSP) for fast triple pattern matching.
     index: Arc<RwLock<TripleIndex>>,
+    /// Definitions of the secondary indexes, whose entries the backend
+    /// stores. Only changed while holding the `index` write lock.
+    secondary: RwLock<HashMap<Predicate, IndexDef>>,
+    /// Subscribers to committed inserts and deletes.
+    events: EventBus,
+    /// Whether the backend's shutdown marker says the store is clean. Held
+    /// shared by every write, and exclusively by flushes, integrity checks
+    /// and index rebuilds, so those never see a write half done.
+    writes: RwLock<bool>,
+    /// Whether the backend rejects writes, the marker's included.
+    read_only: bool,
+    /// Triples of recently read subjects, if the cache is enabled. Filled
+    /// under the `index` read lock and invalidated under its write lock.
+    subject_cache: Option<SubjectCache>,
+    /// The schema new triples are screened against, and how.
+    schema: RwLock<Option<(Arc<GraphSchema>, EnforcementMode)>>,
 }
 
 impl GraphStore {
     /// Creates a new `GraphStore` with the given storage backend.
     ///
     /// This will also build the initial in-memory indexes from the data
-    /// already present in the backend.
+    /// already present in the backend, and load the definitions of the
+    /// secondary indexes it stores.
     pub fn new(backend: Box<dyn StorageBackend>) -> Result<Self> {
-        let store = Self {
+        Self::open(backend, &StorageOptions::default())
+    }
+
+    /// Creates a `GraphStore` with the given storage backend, running the
+    /// integrity check set by [`StorageOptions::verify_on_open`].
+    ///
+    /// Only the integrity and subject cache settings of `options` are used;
+    /// the backend was opened with its own. See [`crate::integrity`] and
+    /// [`crate::cache`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Error::Integrity` if the check fails and
+    /// [`StorageOptions::auto_rebuild`] is not set, or if the indexes still
+    /// disagree with the backend after rebuilding them.
+    pub fn open(backend: Box<dyn StorageBackend>, options: &StorageOptions) -> Result<Self> {
+        let mut secondary = HashMap::new();
+        for (_, bytes) in backend.iter_index_defs()? {
+            let def = IndexDef::decode(&bytes)?;
+            secondary.insert(def.predicate.clone(), def);
+        }
+        let marker = backend
+            .get_marker()?
+            .map(|bytes| ShutdownMarker::decode(&bytes))
+            .transpose()?;
+        let read_only = backend.info().read_only;
+        let mut store = Self {
             backend,
             index: Arc::new(RwLock::new(TripleIndex::new())),
+            secondary: RwLock::new(secondary),
+            events: EventBus::default(),
+            writes: RwLock::new(marker.is_some_and(|marker| marker.clean)),
+            read_only,
+            subject_cache: SubjectCache::new(options.subject_cache),
+            schema: RwLock::new(None),
         };
-        store.rebuild_indexes()?;
+        let opened = store
+            .rebuild_indexes()
+            .and_then(|_| store.repair_on_open(options, marker.as_ref()));
+        match opened {
+            // The next flush records the repaired store
+            Ok(true) => {
+                if let Ok(clean) = store.writes.get_mut() {
+                    *clean = false;
+                }
+            }
+            Ok(false) => {}
+            // Keep the marker as it is, so the next open checks again
+            Err(e) => {
+                if let Ok(clean) = store.writes.get_mut() {
+                    *clean = true;
+                }
+                return Err(e);
+            }
+        }
+
+        // Without a marker an unclean shutdown would go unnoticed
+        if marker.is_none() && !store.read_only {
+            store.put_marker(false)?;
+        }
         Ok(store)
     }
 
-    /// Rebuilds the in-memory indexes from the storage backend.
-    fn rebuild_indexes(&self) -> Result<()> {
+    /// Runs the integrity check on open, rebuilding the indexes if it fails
+    /// and `auto_rebuild` is set. Returns whether they were rebuilt.
+    fn repair_on_open(
+        &self,
+        options: &StorageOptions,
+        marker: Option<&ShutdownMarker>,
+    ) -> Result<bool> {
+        let Some(problem) = self.check_on_open(options.verify_on_open, marker)? else {
+            return Ok(false);
+        };
+        if !options.auto_rebuild {
+            return Err(Error::Integrity(problem));
+        }
+        log::warn!("{}; rebuilding graph indexes", problem);
+        self.rebuild_indexes()?;
+        let report = self.verify_indexes(None)?;
+        if !report.is_consistent() {
+            return Err(Error::Integrity(format!(
+                "{} after rebuilding indexes",
+                report
+            )));
+        }
+        Ok(true)
+    }
+
+    /// Runs the integrity check on open, returning what is wrong, if anything.
+    fn check_on_open(
+        &self,
+        verify: VerifyOnOpen,
+        marker: Option<&ShutdownMarker>,
+    ) -> Result<Option<String>> {
+        if verify == VerifyOnOpen::Off {
+            return Ok(None);
+        }
+        if let Some(marker) = marker {
+            if !marker.clean {
+                return Ok(Some("store was not closed cleanly".to_string()));
+            }
+            let count = self.backend.count() as u64;
+            if marker.triple_count != count {
+                return Ok(Some(format!(
+                    "store was closed with {} triples but holds {}",
+                    marker.triple_count, count
+                )));
+            }
+        }
+        if verify == VerifyOnOpen::Full {
+            let report = self.verify_indexes(None)?;
+            if !report.is_consistent() {
+                return Ok(Some(report.to_string()));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Stores a shutdown marker for the current contents of the backend.
+    fn put_marker(&self, clean: bool) -> Result<()> {
+        let marker = ShutdownMarker {
+            clean,
+            triple_count: self.backend.count() as u64,
+        };
+        self.backend.put_marker(&marker.encode())
+    }
+
+    /// Marks the store dirty before the first write after a flush.
+    ///
+    /// Writers hold the returned guard for the whole write, so a flush
+    /// cannot mark the store clean while a write is under way. The guard
+    /// must not be taken again while held.
+    fn begin_write(&self) -> Result<RwLockReadGuard<'_, bool>> {
+        loop {
+            let clean = self
+                .writes
+                .read()
+                .map_err(|_| Error::Storage("lock poisoned".into()))?;
+            if !*clean {
+                return Ok(clean);
+            }
+            drop(clean);
+
+            let mut clean = self
+                .writes
+                .write()
+                .map_err(|_| Error::Storage("lock poisoned".into()))?;
+            if *clean && !self.read_only {
+                self.put_marker(false)?;
+            }
+            *clean = false;
+        }
+    }
+
+    /// Rebuilds the SPO, POS and OSP indexes from the storage backend.
+    ///
+    /// Returns the number of triples indexed.
+    pub fn rebuild_indexes(&self) -> Result<usize> {
+        self.rebuild_indexes_with_progress(|_| {})
+    }
+
+    /// Rebuilds the SPO, POS and OSP indexes from the storage backend,
+    /// calling `progress` after every [`REBUILD_CHUNK`] triples.
+    ///
+    /// Writes and reads wait until the rebuild is done, so `progress` must
+    /// not use the store. Returns the number of triples indexed.
+    pub fn rebuild_indexes_with_progress(
+        &self,
+        mut progress: impl FnMut(RebuildProgress),
+    ) -> Result<usize> {
+        let _writes = self
+            .writes
+            .write()
+            .map_err(|_| Error::Storage("lock poisoned".into()))?;
         let mut index = self
             .index
             .write()
             .map_err(|_| Error::Index("lock poisoned".into()))?;
         index.clear();
+        if let Some(cache) = &self.subject_cache {
+            cache.clear();
+        }
 
-        for triple in self.backend.iter_all()? {
-            let id = triple.id();
-            index.insert(&triple, id);
+        // Number triples in the order they were first asserted
+        let mut triples = self.backend.iter_all()?;
+        triples.sort_by_key(|triple| triple.meta.asserted_at);
+        let total = triples.len();
+        let mut indexed = 0;
+        for chunk in triples.chunks(REBUILD_CHUNK) {
+            for triple in chunk {
+                index.insert(triple, triple.id());
+            }
+            indexed += chunk.len();
+            progress(RebuildProgress { indexed, total });
+        }
+        for (id, assertions) in self.backend.iter_assertions()? {
+            for meta in &assertions {
+                index.add_assertion(&id, meta);
+            }
         }
 
-        Ok(())
+        Ok(total)
+    }
+
+    /// Cross-checks the SPO, POS and OSP indexes against each other and the
+    /// storage backend.
+    ///
+    /// With `sample`, only about that many indexed triples are read from the
+    /// backend. Writes wait until the check is done.
+    pub fn verify_indexes(&self, sample: Option<usize>) -> Result<IntegrityReport> {
+        let _writes = self
+            .writes
+            .write()
+            .map_err(|_| Error::Storage("lock poisoned".into()))?;
+        let index = self
+            .index
+            .read()
+            .map_err(|_| Error::Index("lock poisoned".into()))?;
+        integrity::verify(&index, self.backend.as_ref(), sample)
     }
 
     /// Inserts a single `Triple` into the store.
@@ -60,6 +283,8 @@ impl GraphStore {
     ///
     /// Returns an `Error::Duplicate` if a triple with the same content already exists.
     pub fn insert(&self, triple: Triple) -> Result<TripleId> {
+        self.screen([&triple])?;
+        let _writing = self.begin_write()?;
         let id = triple.id();
 
         // Check for duplicates
@@ -75,7 +300,15 @@ impl GraphStore {
             .index
             .write()
             .map_err(|_| Error::Index("lock poisoned".into()))?;
+        self.update_secondary([(&id, &triple)], true)?;
         index.insert(&triple, id.clone());
+        self.invalidate_subjects([&triple.subject]);
+        if self.events.is_active() {
+            self.events
+                .stage([GraphEvent::Inserted(id.clone(), triple)]);
+        }
+        drop(index);
+        self.events.deliver();
 
         Ok(id)
     }
@@ -85,6 +318,8 @@ impl GraphStore {
     /// In batch mode, duplicates are silently skipped instead of returning an error.
     /// Uses an atomic batch write when supported by the backend (e.g., Sled).
     pub fn insert_batch(&self, triples: Vec<Triple>) -> Result<Vec<TripleId>> {
+        let _writing = self.begin_write()?;
+
         // Phase 1: Collect non-duplicate triples and their IDs
         let mut new_triples: Vec<(TripleId, Triple)> = Vec::with_capacity(triples.len());
         let mut all_ids = Vec::with_capacity(triples.len());
@@ -100,6 +335,8 @@ impl GraphStore {
             }
         }
 
+        self.screen(new_triples.iter().map(|(_, triple)| triple))?;
+
         // Phase 2: Atomic batch write to backend
         if !new_triples.is_empty() {
             let batch_items: Vec<(&TripleId, &Triple)> = new_triples
@@ -115,14 +352,187 @@ impl GraphStore {
                 .index
                 .write()
                 .map_err(|_| Error::Index("lock poisoned".into()))?;
+            self.update_secondary(new_triples.iter().map(|(id, triple)| (id, triple)), true)?;
             for (id, triple) in &new_triples {
                 index.insert(triple, id.clone());
             }
+            self.invalidate_subjects(new_triples.iter().map(|(_, triple)| &triple.subject));
+            if self.events.is_active() {
+                self.events.stage(
+                    new_triples
+                        .into_iter()
+                        .map(|(id, triple)| GraphEvent::Inserted(id, triple)),
+                );
+            }
+            drop(index);
+            self.events.deliver();
         }
 
         Ok(all_ids.into_iter().map(|(id, _)| id).collect())
     }
 
+    /// Inserts a `Triple` asserted with the given metadata.
+    ///
+    /// If the triple is already stored, the metadata is recorded as a further
+    /// assertion of it instead of failing, unless an identical assertion is
+    /// already recorded. The stored triple keeps the metadata of its first
+    /// assertion; [`provenance`](Self::provenance) returns all of them.
+    pub fn insert_with_meta(&self, mut triple: Triple, meta: TripleMeta) -> Result<TripleId> {
+        triple.meta = meta;
+        let id = triple.id();
+        if self.backend.exists(&id)? {
+            self.add_assertion(&id, triple.meta)?;
+            Ok(id)
+        } else {
+            self.insert(triple)
+        }
+    }
+
+    /// Inserts a batch of `Triple`s, each asserted with its own metadata.
+    ///
+    /// New triples are written with [`insert_batch`](Self::insert_batch);
+    /// triples already stored, or repeated within the batch, have their
+    /// metadata recorded as further assertions.
+    pub fn insert_batch_with_meta(
+        &self,
+        items: Vec<(Triple, TripleMeta)>,
+    ) -> Result<Vec<TripleId>> {
+        let mut ids = Vec::with_capacity(items.len());
+        let mut new_triples = Vec::new();
+        let mut repeats = Vec::new();
+        let mut seen = HashSet::new();
+
+        for (mut triple, meta) in items {
+            triple.meta = meta;
+            let id = triple.id();
+            ids.push(id.clone());
+            if seen.insert(id.clone()) && !self.backend.exists(&id)? {
+                new_triples.push(triple);
+            } else {
+                repeats.push((id, triple.meta));
+            }
+        }
+
+        self.insert_batch(new_triples)?;
+        for (id, meta) in repeats {
+            self.add_assertion(&id, meta)?;
+        }
+
+        Ok(ids)
+    }
+
+    /// Installs the schema new triples are screened against.
+    ///
+    /// See [`GraphDB::set_schema`](crate::GraphDB::set_schema).
+    pub fn set_schema(&self, schema: GraphSchema, mode: EnforcementMode) -> Result<()> {
+        *self
+            .schema
+            .write()
+            .map_err(|_| Error::Storage("lock poisoned".into()))? = Some((Arc::new(schema), mode));
+        Ok(())
+    }
+
+    /// The installed schema and its enforcement mode, if any
+    pub fn schema(&self) -> Result<Option<(Arc<GraphSchema>, EnforcementMode)>> {
+        Ok(self
+            .schema
+            .read()
+            .map_err(|_| Error::Storage("lock poisoned".into()))?
+            .clone())
+    }
+
+    /// Checks new triples against the installed schema.
+    ///
+    /// Violations are logged in `Warn` mode; in `Reject` mode any that is
+    /// not a cardinality hint fails the whole write, listing each of them.
+    fn screen<'t>(&self, triples: impl IntoIterator<Item = &'t Triple>) -> Result<()> {
+        let Some((schema, mode)) = self.schema()? else {
+            return Ok(());
+        };
+        if mode == EnforcementMode::Off {
+            return Ok(());
+        }
+
+        let mut violations = Vec::new();
+        let mut added: HashMap<(&NodeId, &Predicate), usize> = HashMap::new();
+        for triple in triples {
+            if let Some(violation) = schema.check(triple) {
+                violations.push(violation);
+                continue;
+            }
+            let Some(decl) = schema
+                .predicate(&triple.predicate)
+                .filter(|decl| decl.cardinality == Cardinality::One)
+            else {
+                continue;
+            };
+            let earlier = added
+                .entry((&triple.subject, &triple.predicate))
+                .or_default();
+            *earlier += 1;
+            let stored = self
+                .find(
+                    TriplePattern::subject(triple.subject.clone())
+                        .with_predicate(triple.predicate.clone()),
+                )?
+                .len();
+            if stored + *earlier > 1 {
+                violations.push(SchemaViolation::TooMany {
+                    subject: triple.subject.clone(),
+                    decl: decl.clone(),
+                    count: stored + *earlier,
+                });
+            }
+        }
+
+        for violation in &violations {
+            log::warn!("Schema: {}", violation);
+        }
+        let refused: Vec<String> = violations
+            .iter()
+            .filter(|violation| !violation.is_hint())
+            .map(ToString::to_string)
+            .collect();
+        if mode == EnforcementMode::Reject && !refused.is_empty() {
+            return Err(Error::Schema(refused.join("; ")));
+        }
+        Ok(())
+    }
+
+    /// Records a further assertion of a stored triple.
+    fn add_assertion(&self, id: &TripleId, meta: TripleMeta) -> Result<()> {
+        let _writing = self.begin_write()?;
+        // Held across the read-modify-write so concurrent assertions are not lost
+        let mut index = self
+            .index
+            .write()
+            .map_err(|_| Error::Index("lock poisoned".into()))?;
+
+        let Some(triple) = self.backend.get(id)? else {
+            return Err(Error::NotFound(format!("triple {}", id)));
+        };
+        let mut assertions = self.backend.get_assertions(id)?;
+        if triple.meta == meta || assertions.contains(&meta) {
+            return Ok(());
+        }
+
+        index.add_assertion(id, &meta);
+        assertions.push(meta);
+        self.backend.put_assertions(id, &assertions)
+    }
+
+    /// Returns every assertion of a triple, first the one stored with it.
+    ///
+    /// Empty if the triple is not stored.
+    pub fn provenance(&self, id: &TripleId) -> Result<Vec<TripleMeta>> {
+        let Some(triple) = self.backend.get(id)? else {
+            return Ok(Vec::new());
+        };
+        let mut assertions = vec![triple.meta];
+        assertions.extend(self.backend.get_assertions(id)?);
+        Ok(assertions)
+    }
+
     /// Retrieves a `Triple` by its `TripleId`.
     pub fn get(&self, id: &TripleId) -> Result<Option<Triple>> {
         self.backend.get(id)
@@ -134,15 +544,25 @@ impl GraphStore {
     ///
     /// `Ok(true)` if the triple was found and deleted, `Ok(false)` otherwise.
     pub fn delete(&self, id: &TripleId) -> Result<bool> {
+        let _writing = self.begin_write()?;
+        // Hold the index lock across the backend delete, so no reader sees an
+        // indexed triple that is gone from storage
+        let mut index = self
+            .index
+            .write()
+            .map_err(|_| Error::Index("lock poisoned".into()))?;
+
         // Get the triple first to update indexes
         if let Some(triple) = self.backend.get(id)? {
             self.backend.delete(id)?;
-
-            let mut index = self
-                .index
-                .write()
-                .map_err(|_| Error::Index("lock poisoned".into()))?;
             index.remove(&triple, id);
+            self.update_secondary([(id, &triple)], false)?;
+            self.invalidate_subjects([&triple.subject]);
+            if self.events.is_active() {
+                self.events.stage([GraphEvent::Deleted(id.clone(), triple)]);
+            }
+            drop(index);
+            self.events.deliver();
 
             Ok(true)
         } else {
@@ -150,47 +570,359 @@ impl GraphStore {
         }
     }
 
-    /// Finds all triples that match a given `TriplePattern`.
+    /// Drops the cached triples of subjects a write changed.
     ///
-    /// The store will attempt to use the most efficient index based on the
-    /// components specified in the pattern.
-    pub fn find(&self, pattern: TriplePattern) -> Result<Vec<Triple>> {
+    /// Called with the index write lock held, once the write is in the
+    /// backend and the indexes, so no reader can cache what it replaced.
+    fn invalidate_subjects<'t>(&self, subjects: impl IntoIterator<Item = &'t NodeId>) {
+        if let Some(cache) = &self.subject_cache {
+            cache.invalidate(subjects);
+        }
+    }
+
+    /// Adds or removes the secondary index entries of triples.
+    ///
+    /// Called with the index write lock held. Entries are written whether
+    /// or not the index build has reached the triple, so it is indexed once
+    /// the build completes either way.
+    fn update_secondary<'t>(
+        &self,
+        triples: impl IntoIterator<Item = (&'t TripleId, &'t Triple)>,
+        insert: bool,
+    ) -> Result<()> {
+        let secondary = self
+            .secondary
+            .read()
+            .map_err(|_| Error::Index("lock poisoned".into()))?;
+        if secondary.is_empty() {
+            return Ok(());
+        }
+
+        let mut keys: HashMap<&Predicate, Vec<Vec<u8>>> = HashMap::new();
+        for (id, triple) in triples {
+            if let Some(def) = secondary.get(&triple.predicate) {
+                if let Some(key) = def.entry_key(triple, id) {
+                    keys.entry(&triple.predicate).or_default().push(key);
+                }
+            }
+        }
+        for (predicate, keys) in keys {
+            let name = predicate.as_str();
+            if insert {
+                self.backend.put_index_entries(name, &keys)?;
+            } else {
+                self.backend.delete_index_entries(name, &keys)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Creates a secondary index on a predicate and indexes its existing
+    /// triples, or finishes building it if an earlier build was interrupted.
+    ///
+    /// Triples are indexed [`INDEX_BUILD_CHUNK`] at a time with
+    /// [`build_index_step`](Self::build_index_step), so writers and readers
+    /// only wait for one chunk at a time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Error::BackendUnavailable` if the backend does not store
+    /// secondary indexes, or an `Error::Index` if the predicate already has
+    /// an index of another kind.
+    pub fn create_index(&self, predicate: Predicate, kind: IndexKind) -> Result<IndexInfo> {
+        loop {
+            let info = self.build_index_step(&predicate, kind, INDEX_BUILD_CHUNK)?;
+            if info.state == secondary::IndexState::Ready {
+                return Ok(info);
+            }
+        }
+    }
+
+    /// Creates a secondary index on a predicate if it has none, and indexes
+    /// up to `max_triples` more of its existing triples.
+    ///
+    /// Holds the index write lock for the step. Progress is stored with the
+    /// index definition, so steps can be spread out and the build resumes
+    /// where it stopped after the store is reopened. Once every triple is
+    /// indexed the index is [`Ready`](secondary::IndexState::Ready) and
+    /// queries start using it.
+    ///
+    /// # Errors
+    ///
+    /// As for [`create_index`](Self::create_index).
+    pub fn build_index_step(
+        &self,
+        predicate: &Predicate,
+        kind: IndexKind,
+        max_triples: usize,
+    ) -> Result<IndexInfo> {
+        let _writing = self.begin_write()?;
         let index = self
             .index
-            .read()
+            .write()
+            .map_err(|_| Error::Index("lock poisoned".into()))?;
+        let mut secondary = self
+            .secondary
+            .write()
             .map_err(|_| Error::Index("lock poisoned".into()))?;
 
-        // Determine the best index to use based on pattern
-        let ids = match (&pattern.subject, &pattern.predicate, &pattern.object) {
-            // Exact match - use SPO with all components
-            (Some(s), Some(p), Some(o)) => {
-                if let Some(id) = index.find_exact(s, p, o) {
-                    vec![id]
-                } else {
-                    vec![]
+        let def = match secondary.get_mut(predicate) {
+            Some(def) if def.kind != kind => {
+                return Err(Error::Index(format!(
+                    "predicate {} already has a {} index",
+                    predicate, def.kind
+                )));
+            }
+            Some(def) => def,
+            None => {
+                let def = IndexDef::new(predicate.clone(), kind);
+                self.backend.put_index_def(def.name(), &def.encode())?;
+                secondary.entry(predicate.clone()).or_insert(def)
+            }
+        };
+
+        if let (BuildProgress::Building(after), true) = (&def.progress, max_triples > 0) {
+            let positions = index.predicate_positions(predicate, after.as_ref(), max_triples);
+            let mut keys = Vec::with_capacity(positions.len());
+            for (_, id) in &positions {
+                if let Some(triple) = self.backend.get(id)? {
+                    keys.extend(def.entry_key(&triple, id));
                 }
             }
-            // Subject + Predicate - use SPO
-            (Some(s), Some(p), None) => index.find_by_subject_predicate(s, p),
-            // Predicate + Object - use POS
-            (None, Some(p), Some(o)) => index.find_by_predicate_object(p, o),
-            // Object + Subject - use OSP
-            (Some(s), None, Some(o)) => index.find_by_object_subject(o, s),
-            // Subject only - use SPO
-            (Some(s), None, None) => index.find_by_subject(s),
-            // Predicate only - use POS
-            (None, Some(p), None) => index.find_by_predicate(p),
-            // Object only - use OSP
-            (None, None, Some(o)) => index.find_by_object(o),
-            // Wildcard - scan all
-            (None, None, None) => {
-                return self.backend.iter_all();
+            self.backend.put_index_entries(def.name(), &keys)?;
+
+            def.progress = if positions.len() < max_triples {
+                BuildProgress::Ready
+            } else {
+                BuildProgress::Building(positions.into_iter().last())
+            };
+            self.backend.put_index_def(def.name(), &def.encode())?;
+        }
+
+        Ok(def.info(self.backend.index_len(def.name())))
+    }
+
+    /// Drops the secondary index on a predicate, with all its entries.
+    ///
+    /// Returns `Ok(false)` if the predicate has no index.
+    pub fn drop_index(&self, predicate: &Predicate) -> Result<bool> {
+        let _writing = self.begin_write()?;
+        let _index = self
+            .index
+            .write()
+            .map_err(|_| Error::Index("lock poisoned".into()))?;
+        let mut secondary = self
+            .secondary
+            .write()
+            .map_err(|_| Error::Index("lock poisoned".into()))?;
+        match secondary.remove(predicate) {
+            Some(def) => {
+                self.backend.drop_index(def.name())?;
+                Ok(true)
             }
+            None => Ok(false),
+        }
+    }
+
+    /// Lists the secondary indexes, ordered by predicate.
+    pub fn indexes(&self) -> Result<Vec<IndexInfo>> {
+        Ok(self.view()?.indexes())
+    }
+
+    /// Takes a consistent read view of the store.
+    ///
+    /// The view holds the index read lock until it is dropped, so every read
+    /// through it sees the same triples. See [`StoreView`].
+    pub(crate) fn view(&self) -> Result<StoreView<'_>> {
+        let index = self
+            .index
+            .read()
+            .map_err(|_| Error::Index("lock poisoned".into()))?;
+        let secondary = self
+            .secondary
+            .read()
+            .map_err(|_| Error::Index("lock poisoned".into()))?;
+        Ok(StoreView {
+            backend: self.backend.as_ref(),
+            index,
+            secondary,
+        })
+    }
+
+    /// Finds all triples that match a given `TriplePattern`.
+    ///
+    /// The store will attempt to use the most efficient index based on the
+    /// components specified in the pattern. A pattern binding only the
+    /// subject is answered from the subject cache when it is enabled.
+    pub fn find(&self, pattern: TriplePattern) -> Result<Vec<Triple>> {
+        let view = self.view()?;
+        let (Some(cache), Some(subject), None, None) = (
+            &self.subject_cache,
+            &pattern.subject,
+            &pattern.predicate,
+            &pattern.object,
+        ) else {
+            return view.find(pattern);
+        };
+        if let Some(triples) = cache.get(subject) {
+            return Ok(triples);
+        }
+        let subject = subject.clone();
+        let triples = view.find(pattern)?;
+        // Still under the read lock, so no write has changed the subject since
+        cache.insert(subject, &triples);
+        Ok(triples)
+    }
+
+    /// Finds all triples that match a given `TriplePattern` and have an
+    /// assertion satisfying the `ProvenanceFilter`.
+    ///
+    /// The filter is checked against the index before triples are read from
+    /// the backend.
+    pub fn find_with_provenance(
+        &self,
+        pattern: TriplePattern,
+        provenance: &ProvenanceFilter,
+    ) -> Result<Vec<Triple>> {
+        self.view()?.find_with_provenance(pattern, provenance)
+    }
+
+    /// Returns the insertion sequence number of a triple, if it is stored.
+    ///
+    /// Numbers increase with every insert. They are kept in memory with the
+    /// indexes; when a store is reopened, triples are renumbered in the order
+    /// they were first asserted.
+    pub fn sequence(&self, id: &TripleId) -> Result<Option<u64>> {
+        Ok(self.view()?.sequence(id))
+    }
+
+    /// Returns `true` if a triple with the same content already exists in the store.
+    pub fn contains(&self, triple: &Triple) -> Result<bool> {
+        Ok(self.view()?.contains(triple))
+    }
+
+    /// Traverses the graph starting from a node and following a set of predicates.
+    ///
+    /// The whole traversal reads one consistent view of the store.
+    pub fn traverse(&self, start: &NodeId, predicates: &[Predicate]) -> Result<Vec<NodeId>> {
+        self.view()?.traverse(start, predicates)
+    }
+
+    /// Returns the total number of triples in the store.
+    ///
+    /// Counts indexed triples, so a batch being written is counted only once
+    /// it can be found.
+    pub fn count(&self) -> usize {
+        self.view().map(|view| view.count()).unwrap_or(0)
+    }
+
+    /// Access the underlying storage backend as `Any` for downcasting
+    /// (e.g. to reach the Sled `Db` for the shared persistent DAG).
+    pub fn backend_as_any(&self) -> &dyn std::any::Any {
+        self.backend.as_any()
+    }
+
+    /// Subscribes to committed inserts and deletes.
+    ///
+    /// See [`GraphDB::subscribe_with`](crate::GraphDB::subscribe_with).
+    pub fn subscribe(&self, capacity: usize, policy: OverflowPolicy) -> Receiver<GraphEvent> {
+        self.events.subscribe(capacity, policy)
+    }
+
+    /// Returns the storage backend and the settings it was opened with.
+    pub fn backend_info(&self) -> BackendInfo {
+        self.backend.info()
+    }
+
+    /// Flushes any buffered writes to the underlying storage backend.
+    ///
+    /// For persistent backends (e.g., Sled), this ensures all data is
+    /// written to disk. For in-memory backends, this is a no-op. Either way
+    /// the store is marked clean until the next write.
+    pub fn flush(&self) -> Result<()> {
+        let mut clean = self
+            .writes
+            .write()
+            .map_err(|_| Error::Storage("lock poisoned".into()))?;
+        self.backend.flush()?;
+        if !*clean && !self.read_only {
+            self.put_marker(true)?;
+            self.backend.flush()?;
+            *clean = true;
+        }
+        Ok(())
+    }
+
+    /// Returns statistics about the graph, such as triple and node counts.
+    pub fn stats(&self) -> GraphStats {
+        let mut stats = self
+            .view()
+            .map(|view| view.stats())
+            .unwrap_or_else(|_| GraphStats {
+                storage_bytes: self.backend.size_bytes(),
+                ..Default::default()
+            });
+        if let Some(cache) = &self.subject_cache {
+            stats.subject_cache = cache.stats();
+        }
+        stats
+    }
+}
+
+impl Drop for GraphStore {
+    fn drop(&mut self) {
+        if let Err(e) = self.flush() {
+            log::warn!("Failed to mark graph store closed cleanly: {}", e);
+        }
+    }
+}
+
+/// A consistent read view of a [`GraphStore`].
+///
+/// Holds the index read lock, so writers wait until the view is dropped.
+/// Reads go through the indexes: a triple is visible once it is in all of
+/// them and until it has left all of them, and an indexed triple is always
+/// readable from the backend. Writers keep this so by storing triples
+/// before indexing them and holding the index write lock while deleting.
+pub(crate) struct StoreView<'a> {
+    backend: &'a dyn StorageBackend,
+    index: RwLockReadGuard<'a, TripleIndex>,
+    secondary: RwLockReadGuard<'a, HashMap<Predicate, IndexDef>>,
+}
+
+impl StoreView<'_> {
+    /// Finds all triples that match a given `TriplePattern`.
+    pub fn find(&self, pattern: TriplePattern) -> Result<Vec<Triple>> {
+        self.find_with_provenance(pattern, &ProvenanceFilter::default())
+    }
+
+    /// Finds all triples that match a given `TriplePattern` and have an
+    /// assertion satisfying the `ProvenanceFilter`.
+    ///
+    /// The filter is checked against the index before triples are read from
+    /// the backend.
+    pub fn find_with_provenance(
+        &self,
+        pattern: TriplePattern,
+        provenance: &ProvenanceFilter,
+    ) -> Result<Vec<Triple>> {
+        let index = &*self.index;
+
+        let ids = match (&pattern.subject, &pattern.predicate, &pattern.object) {
+            // Wildcard - every indexed triple, not every stored one
+            (None, None, None) if provenance.is_empty() => index.find(&pattern),
+            // Wildcard with provenance - scan the assertions
+            (None, None, None) => index.find_by_provenance(provenance),
+            // Otherwise the index covering the bound components
+            _ => index.find(&pattern),
         };
 
         // Fetch full triples from the backend using the retrieved IDs.
         let mut triples = Vec::with_capacity(ids.len());
         for id in ids {
+            if !provenance.is_empty() && !index.matches_provenance(&id, provenance) {
+                continue;
+            }
             if let Some(triple) = self.backend.get(&id)? {
                 triples.push(triple);
             }
@@ -199,10 +931,286 @@ impl GraphStore {
         Ok(triples)
     }
 
-    /// Returns `true` if a triple with the same content already exists in the store.
-    pub fn contains(&self, triple: &Triple) -> Result<bool> {
-        let id = triple.id();
-        Ok(self.backend.get(&id)?.is_some())
+    /// Finds the first `window` triples matching a pattern in the given order,
+    /// or all of them if `window` is `None`.
+    ///
+    /// When the index serving the pattern already iterates in the order of
+    /// `key`, only the triples inside the window are read from the backend.
+    /// Otherwise a heap keeps the best `window` matches while they are read;
+    /// for insertion order the keys come from the index, so again only the
+    /// window is read. Matches with equal keys are ordered by ID.
+    pub fn find_ordered(
+        &self,
+        pattern: &TriplePattern,
+        provenance: &ProvenanceFilter,
+        key: OrderKey,
+        order: SortOrder,
+        distinct: bool,
+        window: Option<usize>,
+    ) -> Result<OrderedMatches> {
+        let index = &*self.index;
+
+        let mut seen = HashSet::new();
+        let mut accepts = |id: &TripleId| {
+            (provenance.is_empty() || index.matches_provenance(id, provenance))
+                && (!distinct || seen.insert(id.clone()))
+        };
+
+        if let Some(groups) = index.ordered_groups(pattern, key, order) {
+            let mut ids = Vec::new();
+            let mut total_count = 0;
+            for group in groups {
+                let mut group: Vec<TripleId> = group.into_iter().filter(|id| accepts(id)).collect();
+                total_count += group.len();
+                // Past the window, groups are only counted
+                if window.is_none_or(|w| ids.len() < w) {
+                    group.sort_unstable();
+                    if order == SortOrder::Desc {
+                        group.reverse();
+                    }
+                    ids.extend(group);
+                }
+            }
+            if let Some(w) = window {
+                ids.truncate(w);
+            }
+            let triples = self.fetch(ids)?;
+            return Ok(OrderedMatches {
+                rows_examined: triples.len(),
+                triples,
+                total_count,
+            });
+        }
+
+        let ids: Vec<TripleId> = index.find(pattern).into_iter().filter(accepts).collect();
+        let total_count = ids.len();
+
+        if key == OrderKey::InsertionTime {
+            let mut ranked = TopK::new(order, window);
+            for id in ids {
+                ranked.push(index.sequence(&id), id);
+            }
+            let triples = self.fetch(ranked.into_sorted())?;
+            return Ok(OrderedMatches {
+                rows_examined: triples.len(),
+                triples,
+                total_count,
+            });
+        }
+
+        let mut ranked = TopK::new(order, window);
+        let mut rows_examined = 0;
+        for id in ids {
+            if let Some(triple) = self.backend.get(&id)? {
+                rows_examined += 1;
+                let sort_key = match key {
+                    OrderKey::Subject => triple.subject.to_bytes(),
+                    _ => triple.object.sort_key(),
+                };
+                ranked.push((sort_key, id), triple);
+            }
+        }
+        Ok(OrderedMatches {
+            triples: ranked.into_sorted(),
+            total_count,
+            rows_examined,
+        })
+    }
+
+    /// The secondary index that serves an object range on a pattern, if any.
+    ///
+    /// Only a pattern constraining just the predicate is served, once the
+    /// predicate's index is ready.
+    pub fn range_index(&self, pattern: &TriplePattern) -> Option<IndexKind> {
+        self.ready_index(pattern).map(|def| def.kind)
+    }
+
+    fn ready_index(&self, pattern: &TriplePattern) -> Option<&IndexDef> {
+        if pattern.subject.is_some() || pattern.object.is_some() {
+            return None;
+        }
+        let def = self.secondary.get(pattern.predicate.as_ref()?)?;
+        def.is_ready().then_some(def)
+    }
+
+    /// Finds the triples matching a pattern whose object is in a range and
+    /// that have an assertion satisfying the `ProvenanceFilter`, also
+    /// returning the number of triples read.
+    ///
+    /// Matches are read from the predicate's secondary index when
+    /// [`range_index`](Self::range_index) finds one, in its order. Otherwise
+    /// every match of the pattern is read and checked.
+    pub fn find_in_range(
+        &self,
+        pattern: &TriplePattern,
+        provenance: &ProvenanceFilter,
+        range: &ObjectRange,
+    ) -> Result<(Vec<Triple>, usize)> {
+        let index = &*self.index;
+        let accepts =
+            |id: &TripleId| provenance.is_empty() || index.matches_provenance(id, provenance);
+
+        if let Some(def) = self.ready_index(pattern) {
+            let (start, end) = secondary::scan_bounds(range);
+            let mut ids = Vec::new();
+            for key in self
+                .backend
+                .scan_index(def.name(), &start, end.as_deref())?
+            {
+                if let Some((object, id)) = secondary::decode_entry(&key) {
+                    if range.contains(&object) && index.contains(&id) && accepts(&id) {
+                        ids.push(id);
+                    }
+                }
+            }
+            let triples = self.fetch(ids)?;
+            let rows_examined = triples.len();
+            return Ok((triples, rows_examined));
+        }
+
+        let mut triples = Vec::new();
+        let mut rows_examined = 0;
+        for id in index.matching(pattern) {
+            if !accepts(id) {
+                continue;
+            }
+            if let Some(triple) = self.backend.get(id)? {
+                rows_examined += 1;
+                if range.contains(&triple.object) {
+                    triples.push(triple);
+                }
+            }
+        }
+        Ok((triples, rows_examined))
+    }
+
+    /// Sorts triples by `key`, ties by ID, as [`find_ordered`](Self::find_ordered)
+    /// orders its matches.
+    pub fn sort(&self, triples: &mut [Triple], key: OrderKey, order: SortOrder) {
+        triples.sort_by_cached_key(|triple| {
+            let id = triple.id();
+            let sort_key = match key {
+                OrderKey::Subject => triple.subject.to_bytes(),
+                OrderKey::Object => triple.object.sort_key(),
+                OrderKey::InsertionTime => self
+                    .index
+                    .sequence(&id)
+                    .unwrap_or_default()
+                    .to_be_bytes()
+                    .to_vec(),
+            };
+            (sort_key, id)
+        });
+        if order == SortOrder::Desc {
+            triples.reverse();
+        }
+    }
+
+    /// Lists the secondary indexes, ordered by predicate.
+    pub fn indexes(&self) -> Vec<IndexInfo> {
+        let mut indexes: Vec<IndexInfo> = self
+            .secondary
+            .values()
+            .map(|def| def.info(self.backend.index_len(def.name())))
+            .collect();
+        indexes.sort_by(|a, b| a.predicate.cmp(&b.predicate));
+        indexes
+    }
+
+    /// Counts the triples matching a pattern that have an assertion
+    /// satisfying the `ProvenanceFilter`, without reading them.
+    pub fn count_matches(
+        &self,
+        pattern: &TriplePattern,
+        provenance: &ProvenanceFilter,
+    ) -> Result<usize> {
+        let index = &*self.index;
+        Ok(index
+            .matching(pattern)
+            .filter(|id| provenance.is_empty() || index.matches_provenance(id, provenance))
+            .count())
+    }
+
+    /// Calls `f` with the key of one component of every triple matching a
+    /// pattern that has an assertion satisfying the `ProvenanceFilter`.
+    ///
+    /// Keys are subject and predicate bytes and object sort keys. They come
+    /// from the index when one serving the pattern holds the component;
+    /// otherwise matches are read from the backend one at a time. No matches
+    /// are collected either way.
+    pub fn for_each_key(
+        &self,
+        pattern: &TriplePattern,
+        provenance: &ProvenanceFilter,
+        component: Component,
+        mut f: impl FnMut(&[u8]) -> Result<()>,
+    ) -> Result<()> {
+        let index = &*self.index;
+        let accepts =
+            |id: &TripleId| provenance.is_empty() || index.matches_provenance(id, provenance);
+
+        if let Some(keys) = index.component_keys(pattern, component) {
+            for (key, id) in keys {
+                if accepts(id) {
+                    f(key)?;
+                }
+            }
+            return Ok(());
+        }
+
+        for id in index.matching(pattern) {
+            if !accepts(id) {
+                continue;
+            }
+            if let Some(triple) = self.backend.get(id)? {
+                let key = match component {
+                    Component::Subject => triple.subject.to_bytes(),
+                    Component::Predicate => triple.predicate.to_bytes(),
+                    Component::Object => triple.object.sort_key(),
+                };
+                f(&key)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the cardinalities of a predicate, kept with the indexes.
+    pub fn predicate_stats(&self, predicate: &Predicate) -> Result<PredicateStats> {
+        let index = &*self.index;
+        Ok(index
+            .predicate_stats()
+            .get(predicate)
+            .copied()
+            .unwrap_or_default())
+    }
+
+    /// Reads triples from the backend, skipping IDs no longer stored.
+    fn fetch(&self, ids: Vec<TripleId>) -> Result<Vec<Triple>> {
+        let mut triples = Vec::with_capacity(ids.len());
+        for id in ids {
+            if let Some(triple) = self.backend.get(&id)? {
+                triples.push(triple);
+            }
+        }
+        Ok(triples)
+    }
+
+    /// Returns the insertion sequence number of a triple, if it is indexed.
+    pub fn sequence(&self, id: &TripleId) -> Option<u64> {
+        self.index.sequence(id)
+    }
+
+    /// Retrieves an indexed `Triple` by its `TripleId`.
+    pub fn get(&self, id: &TripleId) -> Result<Option<Triple>> {
+        if !self.index.contains(id) {
+            return Ok(None);
+        }
+        self.backend.get(id)
+    }
+
+    /// Returns `true` if a triple with the same content is indexed.
+    pub fn contains(&self, triple: &Triple) -> bool {
+        self.index.contains(&triple.id())
     }
 
     /// Traverses the graph starting from a node and following a set of predicates.
@@ -244,39 +1252,108 @@ impl GraphStore {
         Ok(result)
     }
 
-    /// Returns the total number of triples in the store.
+    /// Returns the number of indexed triples.
     pub fn count(&self) -> usize {
-        self.backend.count()
-    }
-
-    /// Access the underlying storage backend as `Any` for downcasting
-    /// (e.g. to reach the Sled `Db` for the shared persistent DAG).
-    pub fn backend_as_any(&self) -> &dyn std::any::Any {
-        self.backend.as_any()
-    }
-
-    /// Flushes any buffered writes to the underlying storage backend.
-    ///
-    /// For persistent backends (e.g., Sled), this ensures all data is
-    /// written to disk. For in-memory backends, this is a no-op.
-    pub fn flush(&self) -> Result<()> {
-        self.backend.flush()
+        self.index.len()
     }
 
     /// Returns statistics about the graph, such as triple and node counts.
     pub fn stats(&self) -> GraphStats {
-        let index = self.index.read().ok();
-
         GraphStats {
             triple_count: self.count(),
-            subject_count: index.as_ref().map(|i| i.subject_count()).unwrap_or(0),
-            predicate_count: index.as_ref().map(|i| i.predicate_count()).unwrap_or(0),
-            object_count: index.as_ref().map(|i| i.object_count()).unwrap_or(0),
+            subject_count: self.index.subject_count(),
+            predicate_count: self.index.predicate_count(),
+            object_count: self.index.object_count(),
             storage_bytes: self.backend.size_bytes(),
+            predicates: self.index.predicate_stats().clone(),
+            indexes: self.indexes(),
+            subject_cache: Default::default(),
         }
     }
 }
 
+/// The result of [`GraphStore::find_ordered`].
+pub(crate) struct OrderedMatches {
+    /// The matches inside the window, in order.
+    pub triples: Vec<Triple>,
+    /// The number of matches, ignoring the window.
+    pub total_count: usize,
+    /// The number of triples read from the backend.
+    pub rows_examined: usize,
+}
+
+/// Keeps the first `capacity` items in the order of their keys.
+///
+/// A max-heap by rank holds the items; once it is full, the worst one is
+/// dropped on every push.
+struct TopK<K, T> {
+    heap: BinaryHeap<Ranked<K, T>>,
+    order: SortOrder,
+    capacity: Option<usize>,
+}
+
+impl<K: Ord, T> TopK<K, T> {
+    fn new(order: SortOrder, capacity: Option<usize>) -> Self {
+        Self {
+            heap: BinaryHeap::new(),
+            order,
+            capacity,
+        }
+    }
+
+    fn push(&mut self, key: K, item: T) {
+        if self.capacity == Some(0) {
+            return;
+        }
+        self.heap.push(Ranked {
+            key,
+            order: self.order,
+            item,
+        });
+        if self.capacity.is_some_and(|c| self.heap.len() > c) {
+            self.heap.pop();
+        }
+    }
+
+    fn into_sorted(self) -> Vec<T> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|ranked| ranked.item)
+            .collect()
+    }
+}
+
+/// An item ranked by its key, first in the requested order ranking lowest.
+struct Ranked<K, T> {
+    key: K,
+    order: SortOrder,
+    item: T,
+}
+
+impl<K: Ord, T> Ord for Ranked<K, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.order {
+            SortOrder::Asc => self.key.cmp(&other.key),
+            SortOrder::Desc => other.key.cmp(&self.key),
+        }
+    }
+}
+
+impl<K: Ord, T> PartialOrd for Ranked<K, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Ord, T> PartialEq for Ranked<K, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<K: Ord, T> Eq for Ranked<K, T> {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -400,4 +1477,194 @@ mod tests {
         assert!(reachable.contains(&NodeId::named("user:bob")));
         assert!(reachable.contains(&NodeId::named("user:charlie")));
     }
+
+    #[test]
+    fn test_same_fact_from_two_sources_dedupes() {
+        let store = test_store();
+        let fact = || Triple::literal("sensor:1", "reading", "42");
+        let first = TripleMeta::new()
+            .with_asserted_by(NodeId::named("agent:a"))
+            .with_confidence(0.6);
+        let second = TripleMeta::new()
+            .with_asserted_by(NodeId::named("agent:b"))
+            .with_confidence(0.9);
+
+        let id = store.insert_with_meta(fact(), first.clone()).unwrap();
+        assert_eq!(store.insert_with_meta(fact(), second.clone()).unwrap(), id);
+        // Repeating an assertion records nothing new
+        store.insert_with_meta(fact(), second.clone()).unwrap();
+
+        assert_eq!(store.count(), 1);
+        assert_eq!(store.get(&id).unwrap().unwrap().meta, first);
+        assert_eq!(store.provenance(&id).unwrap(), vec![first, second]);
+
+        let by_b = ProvenanceFilter {
+            asserted_by: Some(NodeId::named("agent:b")),
+            ..Default::default()
+        };
+        assert_eq!(
+            store
+                .find_with_provenance(TriplePattern::any(), &by_b)
+                .unwrap()
+                .len(),
+            1
+        );
+
+        store.delete(&id).unwrap();
+        assert!(store.provenance(&id).unwrap().is_empty());
+        assert!(store
+            .find_with_provenance(TriplePattern::any(), &by_b)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_batch_with_meta_and_rebuild() {
+        let backend = MemoryBackend::new();
+        let fact = Triple::literal("doc:1", "mentions", "aingle");
+        let other = Triple::literal("doc:2", "mentions", "aingle");
+        let by = |agent: &str| TripleMeta::new().with_asserted_by(NodeId::named(agent));
+
+        let store = GraphStore::new(Box::new(backend)).unwrap();
+        let ids = store
+            .insert_batch_with_meta(vec![
+                (fact.clone(), by("agent:a")),
+                (other, by("agent:a")),
+                (fact.clone(), by("agent:b")),
+            ])
+            .unwrap();
+        assert_eq!(ids[0], ids[2]);
+        assert_eq!(store.count(), 2);
+        assert_eq!(store.provenance(&ids[0]).unwrap().len(), 2);
+        assert_eq!(store.provenance(&ids[1]).unwrap().len(), 1);
+
+        // Indexes rebuilt from the backend see the further assertions
+        let by_b = ProvenanceFilter {
+            asserted_by: Some(NodeId::named("agent:b")),
+            ..Default::default()
+        };
+        store.rebuild_indexes().unwrap();
+        let found = store
+            .find_with_provenance(
+                TriplePattern::predicate(Predicate::named("mentions")),
+                &by_b,
+            )
+            .unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id(), ids[0]);
+    }
+
+    fn marker(store: &GraphStore) -> Option<ShutdownMarker> {
+        store
+            .backend
+            .get_marker()
+            .unwrap()
+            .map(|bytes| ShutdownMarker::decode(&bytes).unwrap())
+    }
+
+    #[test]
+    fn test_corrupt_index_is_detected_and_rebuilt() {
+        use crate::index::IndexType;
+        use crate::integrity::DivergenceKind;
+
+        let store = test_store();
+        let triples: Vec<Triple> = (0..20)
+            .map(|i| Triple::literal(format!("sensor:{}", i), "reading", i.to_string()))
+            .collect();
+        store.insert_batch(triples.clone()).unwrap();
+        assert!(store.verify_indexes(None).unwrap().is_consistent());
+
+        // POS loses a triple that SPO and OSP still have
+        let lost = &triples[7];
+        store
+            .index
+            .write()
+            .unwrap()
+            .remove_entry(IndexType::POS, lost, &lost.id());
+        let pattern = TriplePattern::predicate(Predicate::named("reading"));
+        assert_eq!(store.find(pattern.clone()).unwrap().len(), 19);
+
+        let report = store.verify_indexes(None).unwrap();
+        assert!(!report.is_consistent());
+        assert_eq!(
+            report.divergent_ids().into_iter().collect::<Vec<_>>(),
+            vec![lost.id()]
+        );
+        assert_eq!(report.divergences[0].index, IndexType::POS);
+        assert_eq!(report.divergences[0].kind, DivergenceKind::Missing);
+
+        let mut reports = Vec::new();
+        assert_eq!(
+            store
+                .rebuild_indexes_with_progress(|progress| reports.push(progress))
+                .unwrap(),
+            20
+        );
+        assert_eq!(
+            reports,
+            vec![RebuildProgress {
+                indexed: 20,
+                total: 20
+            }]
+        );
+        assert!(store.verify_indexes(None).unwrap().is_consistent());
+        assert_eq!(store.find(pattern).unwrap().len(), 20);
+    }
+
+    #[test]
+    fn test_shutdown_marker_follows_writes() {
+        let store = test_store();
+        // A new store is dirty until it is first flushed
+        assert_eq!(marker(&store).map(|m| m.clean), Some(false));
+
+        store.insert(Triple::literal("a", "p", "1")).unwrap();
+        store.flush().unwrap();
+        assert_eq!(
+            marker(&store),
+            Some(ShutdownMarker {
+                clean: true,
+                triple_count: 1
+            })
+        );
+
+        store.insert(Triple::literal("b", "p", "2")).unwrap();
+        assert_eq!(marker(&store).map(|m| m.clean), Some(false));
+        store.flush().unwrap();
+        assert_eq!(marker(&store).map(|m| m.triple_count), Some(2));
+    }
+
+    #[test]
+    fn test_verify_on_open() {
+        let with_marker = |clean: bool, triple_count: u64| {
+            let backend = MemoryBackend::new();
+            let triple = Triple::literal("a", "p", "1");
+            backend.put(&triple.id(), &triple).unwrap();
+            let marker = ShutdownMarker {
+                clean,
+                triple_count,
+            };
+            backend.put_marker(&marker.encode()).unwrap();
+            Box::new(backend)
+        };
+        let quick = StorageOptions::default().with_verify_on_open(VerifyOnOpen::Quick);
+
+        // Unchecked, a dirty store opens as before
+        assert!(GraphStore::open(with_marker(false, 1), &StorageOptions::default()).is_ok());
+        assert!(GraphStore::open(with_marker(true, 1), &quick).is_ok());
+
+        for backend in [with_marker(false, 1), with_marker(true, 3)] {
+            let Err(Error::Integrity(problem)) = GraphStore::open(backend, &quick) else {
+                panic!("unclean store opened");
+            };
+            assert!(!problem.is_empty());
+        }
+
+        let store = GraphStore::open(with_marker(false, 1), &quick.clone().auto_rebuild()).unwrap();
+        assert_eq!(store.count(), 1);
+        // Still dirty until flushed
+        assert_eq!(marker(&store).map(|m| m.clean), Some(false));
+
+        let full = StorageOptions::default().with_verify_on_open(VerifyOnOpen::Full);
+        assert!(GraphStore::open(with_marker(true, 1), &full).is_ok());
+    }
 }
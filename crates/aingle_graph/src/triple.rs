@@ -40,7 +40,7 @@ use std::fmt;
 /// let hex_repr = id.to_hex();
 /// println!("Triple ID: {}", id);
 /// ```
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct TripleId(pub [u8; 32]);
 
 impl TripleId {
@@ -131,6 +131,10 @@ impl From<[u8; 32]> for TripleId {
 /// Metadata allows you to track information about where a triple came from,
 /// who created it, when it was created, and how confident you are in its validity.
 ///
+/// Metadata is not part of the [`TripleId`]: the same fact asserted by two
+/// sources is stored once, with one `TripleMeta` per assertion (see
+/// [`GraphDB::provenance`](crate::GraphDB::provenance)).
+///
 /// # Examples
 ///
 /// ```
@@ -140,10 +144,12 @@ impl From<[u8; 32]> for TripleId {
 ///     .with_author(NodeId::named("user:alice"))
 ///     .with_source("manual_entry")
 ///     .with_confidence(0.95)
+///     .with_asserted_by(NodeId::named("agent:crawler"))
 ///     .validated();
 ///
 /// assert!(meta.validated);
 /// assert_eq!(meta.confidence, Some(0.95));
+/// assert_eq!(meta.asserted_by, Some(NodeId::named("agent:crawler")));
 /// ```
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TripleMeta {
@@ -161,18 +167,27 @@ pub struct TripleMeta {
     pub validated: bool,
     /// A map for storing arbitrary custom properties.
     pub properties: std::collections::HashMap<String, String>,
+    /// The agent that asserted the triple into the graph.
+    #[serde(default)]
+    pub asserted_by: Option<NodeId>,
+    /// The timestamp of the assertion.
+    #[serde(default = "Utc::now")]
+    pub asserted_at: DateTime<Utc>,
 }
 
 impl Default for TripleMeta {
     fn default() -> Self {
+        let now = Utc::now();
         Self {
-            created_at: Utc::now(),
+            created_at: now,
             author: None,
             signature: None,
             source: None,
             confidence: None,
             validated: false,
             properties: std::collections::HashMap::new(),
+            asserted_by: None,
+            asserted_at: now,
         }
     }
 }
@@ -212,6 +227,61 @@ impl TripleMeta {
         self.properties.insert(key.into(), value.into());
         self
     }
+
+    /// Sets the agent that asserted the triple.
+    pub fn with_asserted_by(mut self, agent: NodeId) -> Self {
+        self.asserted_by = Some(agent);
+        self
+    }
+
+    /// Sets the timestamp of the assertion.
+    pub fn with_asserted_at(mut self, at: DateTime<Utc>) -> Self {
+        self.asserted_at = at;
+        self
+    }
+}
+
+/// Layout of [`TripleMeta`] before the assertion fields were added, kept to
+/// read triples stored by earlier versions.
+#[derive(Deserialize)]
+struct LegacyTripleMeta {
+    created_at: DateTime<Utc>,
+    author: Option<NodeId>,
+    signature: Option<Vec<u8>>,
+    source: Option<String>,
+    confidence: Option<f64>,
+    validated: bool,
+    properties: std::collections::HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct LegacyTriple {
+    subject: NodeId,
+    predicate: Predicate,
+    object: Value,
+    meta: LegacyTripleMeta,
+}
+
+impl From<LegacyTriple> for Triple {
+    fn from(legacy: LegacyTriple) -> Self {
+        let meta = legacy.meta;
+        Self {
+            subject: legacy.subject,
+            predicate: legacy.predicate,
+            object: legacy.object,
+            meta: TripleMeta {
+                created_at: meta.created_at,
+                author: meta.author,
+                signature: meta.signature,
+                source: meta.source,
+                confidence: meta.confidence,
+                validated: meta.validated,
+                properties: meta.properties,
+                asserted_by: None,
+                asserted_at: meta.created_at,
+            },
+        }
+    }
 }
 
 /// A semantic triple, representing a single fact as a `(Subject, Predicate, Object)` statement.
@@ -411,10 +481,17 @@ impl Triple {
     }
 
     /// Deserializes a `Triple` from a byte slice.
+    ///
+    /// Also reads triples stored before [`TripleMeta`] recorded who asserted
+    /// them; those get no `asserted_by` and their creation time as `asserted_at`.
     pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
-        bincode::serde::decode_from_slice(bytes, bincode::config::standard())
-            .map(|(v, _)| v)
-            .ok()
+        let config = bincode::config::standard();
+        match bincode::serde::decode_from_slice::<Self, _>(bytes, config) {
+            Ok((triple, read)) if read == bytes.len() => Some(triple),
+            _ => bincode::serde::decode_from_slice::<LegacyTriple, _>(bytes, config)
+                .map(|(legacy, _)| legacy.into())
+                .ok(),
+        }
     }
 
     /// Creates a set of pre-computed, lexicographically sortable keys for database indexing.
@@ -695,4 +772,66 @@ mod tests {
         let restored = TripleId::from_hex(&hex).unwrap();
         assert_eq!(id, restored);
     }
+
+    #[test]
+    fn test_meta_excluded_from_id() {
+        let plain = Triple::literal("user:alice", "has_name", "Alice");
+        let asserted = Triple::with_meta(
+            NodeId::named("user:alice"),
+            Predicate::named("has_name"),
+            Value::literal("Alice"),
+            TripleMeta::new()
+                .with_asserted_by(NodeId::named("agent:x"))
+                .with_confidence(0.4),
+        );
+        assert_eq!(plain.id(), asserted.id());
+
+        let restored = Triple::from_bytes(&asserted.to_bytes()).unwrap();
+        assert_eq!(restored.meta, asserted.meta);
+    }
+
+    #[test]
+    fn test_legacy_layout_decodes() {
+        #[derive(Serialize)]
+        struct OldMeta {
+            created_at: DateTime<Utc>,
+            author: Option<NodeId>,
+            signature: Option<Vec<u8>>,
+            source: Option<String>,
+            confidence: Option<f64>,
+            validated: bool,
+            properties: std::collections::HashMap<String, String>,
+        }
+        #[derive(Serialize)]
+        struct OldTriple {
+            subject: NodeId,
+            predicate: Predicate,
+            object: Value,
+            meta: OldMeta,
+        }
+
+        let created_at = Utc::now() - chrono::Duration::days(3);
+        let old = OldTriple {
+            subject: NodeId::named("a"),
+            predicate: Predicate::named("b"),
+            object: Value::literal("c"),
+            meta: OldMeta {
+                created_at,
+                author: Some(NodeId::named("system")),
+                signature: None,
+                source: Some("import".into()),
+                confidence: Some(0.5),
+                validated: true,
+                properties: Default::default(),
+            },
+        };
+        let bytes = bincode::serde::encode_to_vec(&old, bincode::config::standard()).unwrap();
+
+        let triple = Triple::from_bytes(&bytes).unwrap();
+        assert_eq!(triple.id(), Triple::literal("a", "b", "c").id());
+        assert_eq!(triple.meta.source.as_deref(), Some("import"));
+        assert_eq!(triple.meta.confidence, Some(0.5));
+        assert_eq!(triple.meta.asserted_by, None);
+        assert_eq!(triple.meta.asserted_at, created_at);
+    }
 }
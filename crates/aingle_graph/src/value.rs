@@ -367,6 +367,23 @@ impl Value {
             Self::Null => vec![255u8], // Sort nulls last
         }
     }
+
+    /// Recovers an `Integer` or `Float` from its [`sort_key`](Self::sort_key),
+    /// or returns `None` for keys of any other type.
+    pub(crate) fn numeric_from_sort_key(key: &[u8]) -> Option<Self> {
+        let (&tag, rest) = key.split_first()?;
+        let sortable = u64::from_be_bytes(rest.try_into().ok()?);
+        match tag {
+            2 => Some(Self::Integer((sortable ^ (1u64 << 63)) as i64)),
+            // -0.0 is stored as 0, like a NaN with every bit set
+            3 if sortable == 0 => Some(Self::Float(-0.0)),
+            3 if sortable & (1u64 << 63) != 0 => {
+                Some(Self::Float(f64::from_bits(sortable ^ (1u64 << 63))))
+            }
+            3 => Some(Self::Float(f64::from_bits(!sortable))),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for Value {
@@ -500,6 +517,26 @@ mod tests {
         assert_eq!(val, restored);
     }
 
+    #[test]
+    fn test_numeric_from_sort_key() {
+        for value in [
+            Value::integer(0),
+            Value::integer(i64::MIN),
+            Value::integer(-42),
+            Value::integer(i64::MAX),
+            Value::float(0.0),
+            Value::float(-0.0),
+            Value::float(-3.5),
+            Value::float(1e300),
+            Value::float(f64::NEG_INFINITY),
+        ] {
+            let decoded = Value::numeric_from_sort_key(&value.sort_key()).unwrap();
+            assert_eq!(decoded.to_bytes(), value.to_bytes());
+        }
+        assert!(Value::numeric_from_sort_key(&Value::literal("12345678").sort_key()).is_none());
+        assert!(Value::numeric_from_sort_key(&Value::boolean(true).sort_key()).is_none());
+    }
+
     #[test]
     fn test_sort_order() {
         let v1 = Value::integer(1);
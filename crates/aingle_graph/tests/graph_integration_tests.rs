@@ -6,7 +6,7 @@
 //! Tests graph database operations across different backends,
 //! complex queries, traversals, and data integrity.
 
-use aingle_graph::{GraphDB, NodeId, Predicate, Triple, TriplePattern, Value};
+use aingle_graph::{GraphDB, NodeId, Predicate, Triple, TriplePattern, Value, Var};
 use std::collections::HashSet;
 
 // ============================================================================
@@ -719,3 +719,1236 @@ fn test_get_nonexistent_triple() {
     let result = db.get(&id).unwrap();
     assert!(result.is_none());
 }
+
+// ============================================================================
+// Multi-Pattern Query Tests
+// ============================================================================
+
+/// 60 people across 6 orgs and 10 cities; only 3 of them are certified
+fn org_chart() -> GraphDB {
+    let db = GraphDB::memory().unwrap();
+
+    for i in 0..60 {
+        let person = NodeId::named(format!("user:{}", i));
+        db.insert(Triple::link(
+            person.clone(),
+            Predicate::works_at(),
+            NodeId::named(format!("org:{}", i % 6)),
+        ))
+        .unwrap();
+        db.insert(Triple::new(
+            person,
+            Predicate::named("lives_in"),
+            Value::literal(format!("city {}", i % 10)),
+        ))
+        .unwrap();
+    }
+    for i in [7, 21, 42] {
+        db.insert(Triple::link(
+            NodeId::named(format!("board:{}", i % 2)),
+            Predicate::certifies(),
+            NodeId::named(format!("user:{}", i)),
+        ))
+        .unwrap();
+    }
+
+    db
+}
+
+#[test]
+fn test_predicate_stats_in_graph_stats() {
+    let db = org_chart();
+    let stats = db.stats();
+
+    let works_at = stats.predicates[&Predicate::works_at()];
+    assert_eq!(works_at.triple_count, 60);
+    assert_eq!(works_at.subject_count, 60);
+    assert_eq!(works_at.object_count, 6);
+
+    let certifies = stats.predicates[&Predicate::certifies()];
+    assert_eq!(certifies.triple_count, 3);
+    assert_eq!(certifies.subject_count, 2);
+}
+
+#[test]
+fn test_multi_pattern_query_matches_nested_loop() {
+    let db = org_chart();
+    let (person, org, city, issuer) = (
+        Var::new("person"),
+        Var::new("org"),
+        Var::new("city"),
+        Var::new("issuer"),
+    );
+
+    let query = db
+        .query()
+        .pattern(&person, Predicate::works_at(), &org)
+        .pattern(&person, Predicate::named("lives_in"), &city)
+        .pattern(&issuer, Predicate::certifies(), &person);
+
+    // The 3 certifications are looked up first, then joined on ?person
+    let plan = query.explain().unwrap();
+    let explain = plan.to_string();
+    assert!(
+        explain.starts_with("1. #2 ?issuer <certifies> ?person via POS (est. 3)"),
+        "{}",
+        explain
+    );
+    assert!(explain.contains("join on ?person"), "{}", explain);
+    assert_eq!(plan.steps[0].pattern, 2);
+
+    let solutions = query.solve().unwrap();
+    assert_eq!(solutions.vars(), &[person.clone(), org, city, issuer]);
+
+    // Hand-written nested loop over the same data
+    let mut expected = HashSet::new();
+    for cert in db
+        .find(TriplePattern::predicate(Predicate::certifies()))
+        .unwrap()
+    {
+        let Value::Node(who) = &cert.object else {
+            continue;
+        };
+        let works = db
+            .find(TriplePattern::subject(who.clone()).with_predicate(Predicate::works_at()))
+            .unwrap();
+        let lives = db
+            .find(TriplePattern::subject(who.clone()).with_predicate(Predicate::named("lives_in")))
+            .unwrap();
+        for w in &works {
+            for l in &lives {
+                expected.insert(vec![
+                    cert.object.clone(),
+                    w.object.clone(),
+                    l.object.clone(),
+                    Value::Node(cert.subject.clone()),
+                ]);
+            }
+        }
+    }
+
+    let actual: HashSet<Vec<Value>> = solutions.rows().iter().cloned().collect();
+    assert_eq!(solutions.len(), 3);
+    assert_eq!(actual, expected);
+    assert!(actual.contains(&vec![
+        Value::Node(NodeId::named("user:21")),
+        Value::Node(NodeId::named("org:3")),
+        Value::literal("city 1"),
+        Value::Node(NodeId::named("board:1")),
+    ]));
+    assert_eq!(
+        solutions
+            .get(0, &person)
+            .map(|v| matches!(v, Value::Node(_))),
+        Some(true)
+    );
+}
+
+#[test]
+fn test_multi_pattern_query_requires_solve() {
+    let db = org_chart();
+    let result = db
+        .query()
+        .pattern(Var::new("s"), Predicate::works_at(), Var::new("o"))
+        .execute();
+    assert!(result.is_err());
+}
+
+// ============================================================================
+// Storage Options Tests
+// ============================================================================
+
+#[cfg(feature = "sled-backend")]
+#[test]
+fn test_sled_unclean_shutdown_is_detected() {
+    use aingle_graph::backends::StorageBackend;
+    use aingle_graph::{Error, SledBackend, StorageOptions, VerifyOnOpen};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("graph.sled");
+    let path = path.to_str().unwrap();
+    let quick = StorageOptions::default().with_verify_on_open(VerifyOnOpen::Quick);
+
+    {
+        let db = GraphDB::sled_with(path, quick.clone()).unwrap();
+        db.insert(Triple::literal("user:alice", "has_name", "Alice"))
+            .unwrap();
+    }
+    // Dropped cleanly
+    let db = GraphDB::sled_with(path, quick.clone()).unwrap();
+    assert_eq!(db.count(), 1);
+    drop(db);
+
+    // A triple written straight to the backend, as if the process died
+    // before the store was flushed
+    {
+        let backend = SledBackend::open(path).unwrap();
+        let bob = Triple::literal("user:bob", "has_name", "Bob");
+        backend.put(&bob.id(), &bob).unwrap();
+        backend.flush().unwrap();
+    }
+    let err = GraphDB::sled_with(path, quick.clone()).err().unwrap();
+    assert!(matches!(err, Error::Integrity(_)), "{err}");
+
+    let db = GraphDB::sled_with(path, quick.clone().auto_rebuild()).unwrap();
+    assert_eq!(db.count(), 2);
+    assert!(db.verify_indexes(Some(1)).unwrap().is_consistent());
+    drop(db);
+    assert!(GraphDB::sled_with(path, quick).is_ok());
+}
+
+#[cfg(feature = "sled-backend")]
+#[test]
+fn test_sled_read_only_graph_rejects_writes() {
+    use aingle_graph::{Compression, Error, StorageOptions};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("graph.sled");
+    let path = path.to_str().unwrap();
+
+    let alice = Triple::new(
+        NodeId::named("user:alice"),
+        Predicate::named("has_name"),
+        Value::literal("Alice"),
+    );
+    {
+        let db =
+            GraphDB::sled_with(path, StorageOptions::default().with_cache_bytes(8 << 20)).unwrap();
+        db.insert(alice.clone()).unwrap();
+        db.flush().unwrap();
+    }
+
+    let db = GraphDB::sled_with(path, StorageOptions::default().read_only()).unwrap();
+    let info = db.backend_info();
+    assert_eq!(info.backend, "sled");
+    assert_eq!(info.path.as_deref(), Some(path));
+    assert_eq!(info.compression, Some(Compression::None));
+    assert!(info.read_only);
+
+    let bob = Triple::new(
+        NodeId::named("user:bob"),
+        Predicate::named("has_name"),
+        Value::literal("Bob"),
+    );
+    assert!(matches!(db.insert(bob.clone()), Err(Error::ReadOnly(_))));
+    assert!(matches!(
+        db.insert_batch(vec![bob]),
+        Err(Error::ReadOnly(_))
+    ));
+    assert!(matches!(db.delete(&alice.id()), Err(Error::ReadOnly(_))));
+
+    // Reads and indexes are unaffected by the rejected writes
+    assert_eq!(db.count(), 1);
+    let names = db
+        .find(TriplePattern::predicate(Predicate::named("has_name")))
+        .unwrap();
+    assert_eq!(names, vec![alice]);
+}
+
+// ============================================================================
+// Change Feed Tests
+// ============================================================================
+
+fn drain(events: &aingle_graph::Receiver<aingle_graph::GraphEvent>) -> Vec<String> {
+    use aingle_graph::GraphEvent;
+
+    std::iter::from_fn(|| events.try_recv())
+        .map(|event| match event {
+            GraphEvent::Inserted(_, t) => format!("+{}", t.subject),
+            GraphEvent::Deleted(_, t) => format!("-{}", t.subject),
+        })
+        .collect()
+}
+
+fn named(subject: &str) -> Triple {
+    Triple::new(
+        NodeId::named(subject),
+        Predicate::named("has_name"),
+        Value::literal(subject),
+    )
+}
+
+#[test]
+fn test_change_feed_emits_every_write_in_commit_order() {
+    let db = GraphDB::memory().unwrap();
+    let events = db.subscribe();
+
+    let a = db.insert(named("user:a")).unwrap();
+    db.insert_batch(vec![named("user:b"), named("tmp:c"), named("user:a")])
+        .unwrap();
+    db.insert(named("tmp:d")).unwrap();
+    db.delete(&a).unwrap();
+    db.delete_by_subject_prefix("tmp:").unwrap();
+
+    // Duplicates and deletes of missing triples emit nothing
+    assert!(db.insert(named("user:b")).is_err());
+    assert!(!db.delete(&a).unwrap());
+
+    let mut received = drain(&events);
+    // The prefix delete visits triples in backend order
+    received[5..].sort();
+    assert_eq!(
+        received,
+        vec![
+            "+<user:a>",
+            "+<user:b>",
+            "+<tmp:c>",
+            "+<tmp:d>",
+            "-<user:a>",
+            "-<tmp:c>",
+            "-<tmp:d>",
+        ]
+    );
+    assert_eq!(events.dropped(), 0);
+}
+
+#[test]
+fn test_change_feed_event_carries_id_and_triple() {
+    use aingle_graph::GraphEvent;
+
+    let db = GraphDB::memory().unwrap();
+    let events = db.subscribe();
+    let triple = named("user:alice");
+    let id = db.insert(triple.clone()).unwrap();
+
+    assert_eq!(events.try_recv(), Some(GraphEvent::Inserted(id, triple)));
+    assert!(events.try_recv().is_none());
+}
+
+#[test]
+fn test_change_feed_drop_oldest_overflow() {
+    use aingle_graph::OverflowPolicy;
+
+    let db = GraphDB::memory().unwrap();
+    let small = db.subscribe_with(2, OverflowPolicy::DropOldest);
+    let large = db.subscribe_with(16, OverflowPolicy::DropOldest);
+
+    for i in 0..5 {
+        db.insert(named(&format!("user:{}", i))).unwrap();
+    }
+
+    assert_eq!(small.dropped(), 3);
+    assert_eq!(drain(&small), vec!["+<user:3>", "+<user:4>"]);
+    assert_eq!(large.dropped(), 0);
+    assert_eq!(drain(&large).len(), 5);
+}
+
+#[test]
+fn test_change_feed_block_applies_backpressure_without_deadlock() {
+    use aingle_graph::OverflowPolicy;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let db = Arc::new(GraphDB::memory().unwrap());
+    let events = db.subscribe_with(1, OverflowPolicy::Block);
+
+    let writer = {
+        let db = db.clone();
+        std::thread::spawn(move || {
+            for i in 0..50 {
+                db.insert(named(&format!("user:{}", i))).unwrap();
+            }
+        })
+    };
+
+    // The consumer reads the graph for every event while the writer waits
+    // on the full channel.
+    let mut received = Vec::new();
+    while received.len() < 50 {
+        let event = events
+            .recv_timeout(Duration::from_secs(10))
+            .expect("writer stalled");
+        assert!(db.contains(event.triple()).unwrap());
+        received.push(event.triple().subject.to_string());
+    }
+    writer.join().unwrap();
+
+    let expected: Vec<String> = (0..50).map(|i| format!("<user:{}>", i)).collect();
+    assert_eq!(received, expected);
+    assert_eq!(events.dropped(), 0);
+}
+
+#[test]
+fn test_change_feed_orders_concurrent_writers() {
+    use aingle_graph::{GraphEvent, OverflowPolicy};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    let db = Arc::new(GraphDB::memory().unwrap());
+    let events = db.subscribe_with(4096, OverflowPolicy::Block);
+
+    let writers: Vec<_> = (0..4)
+        .map(|w| {
+            let db = db.clone();
+            std::thread::spawn(move || {
+                for i in 0..100 {
+                    let id = db.insert(named(&format!("w{}:{}", w, i))).unwrap();
+                    if i % 2 == 0 {
+                        db.delete(&id).unwrap();
+                    }
+                }
+            })
+        })
+        .collect();
+    for writer in writers {
+        writer.join().unwrap();
+    }
+
+    // Each triple's insert precedes its delete, and each writer's own
+    // inserts arrive in the order it made them.
+    let mut live = HashSet::new();
+    let mut last_seq: HashMap<String, i64> = HashMap::new();
+    for event in std::iter::from_fn(|| events.try_recv()) {
+        match event {
+            GraphEvent::Inserted(id, t) => {
+                assert!(live.insert(id));
+                let name = t.subject.as_name().unwrap().to_string();
+                let (writer, seq) = name.split_once(':').unwrap();
+                let seq: i64 = seq.parse().unwrap();
+                let previous = last_seq.insert(writer.to_string(), seq);
+                assert!(previous.is_none_or(|p| p < seq));
+            }
+            GraphEvent::Deleted(id, _) => assert!(live.remove(&id)),
+        }
+    }
+    assert_eq!(live.len(), 200);
+    assert_eq!(db.count(), 200);
+}
+
+#[test]
+fn test_change_feed_unsubscribe_on_drop() {
+    use aingle_graph::OverflowPolicy;
+
+    let db = GraphDB::memory().unwrap();
+    let events = db.subscribe_with(1, OverflowPolicy::Block);
+    drop(events);
+
+    // A dropped blocking subscriber never stalls the writer
+    for i in 0..10 {
+        db.insert(named(&format!("user:{}", i))).unwrap();
+    }
+    assert_eq!(db.count(), 10);
+}
+
+// ============================================================================
+// Graph Merge Tests
+// ============================================================================
+
+fn reading(i: usize) -> Triple {
+    Triple::new(
+        NodeId::named(&format!("sensor:{}", i)),
+        Predicate::named("reading"),
+        Value::integer(i as i64),
+    )
+}
+
+#[cfg(feature = "sled-backend")]
+#[test]
+fn test_merge_memory_into_sled_is_union() {
+    use aingle_graph::{MergePolicy, MERGE_BATCH_SIZE};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("graph.sled");
+    let path = path.to_str().unwrap();
+
+    // More than one batch, overlapping the target by 500 triples
+    let source = GraphDB::memory().unwrap();
+    source
+        .insert_batch((500..2000).map(reading).collect())
+        .unwrap();
+    assert!(source.count() > MERGE_BATCH_SIZE);
+    {
+        let target = GraphDB::sled(path).unwrap();
+        target
+            .insert_batch((0..1000).map(reading).collect())
+            .unwrap();
+
+        let report = target.merge_from(&source, MergePolicy::default()).unwrap();
+        assert_eq!(report.inserted, 1000);
+        assert_eq!(report.skipped, 500);
+        assert_eq!(target.count(), 2000);
+        target.flush().unwrap();
+    }
+
+    // Merged triples are persisted and indexed
+    let target = GraphDB::sled(path).unwrap();
+    assert_eq!(target.count(), 2000);
+    let found = target
+        .find(TriplePattern::subject(NodeId::named("sensor:1999")))
+        .unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].id(), reading(1999).id());
+}
+
+#[cfg(all(feature = "sled-backend", feature = "sqlite-backend"))]
+#[test]
+fn test_merge_sled_into_sqlite() {
+    use aingle_graph::MergePolicy;
+
+    let dir = tempfile::tempdir().unwrap();
+    let sled_path = dir.path().join("graph.sled");
+    let sqlite_path = dir.path().join("graph.sqlite");
+
+    let source = GraphDB::sled(sled_path.to_str().unwrap()).unwrap();
+    source.insert_batch((0..50).map(reading).collect()).unwrap();
+    let target = GraphDB::sqlite(sqlite_path.to_str().unwrap()).unwrap();
+    target
+        .insert_batch((25..75).map(reading).collect())
+        .unwrap();
+
+    let report = target
+        .merge_from(&source, MergePolicy::SkipExisting)
+        .unwrap();
+    assert_eq!((report.inserted, report.skipped), (25, 25));
+    assert_eq!(target.count(), 75);
+}
+
+#[cfg(all(feature = "sled-backend", feature = "sqlite-backend"))]
+#[test]
+fn test_snapshot_sled_into_sqlite() {
+    use aingle_graph::{SnapshotOptions, TripleMeta};
+
+    let dir = tempfile::tempdir().unwrap();
+    let sled_path = dir.path().join("graph.sled");
+    let sqlite_path = dir.path().join("graph.sqlite");
+    let snapshot_path = dir.path().join("graph.snap");
+
+    let source = GraphDB::sled(sled_path.to_str().unwrap()).unwrap();
+    source
+        .insert_batch((0..300).map(reading).collect())
+        .unwrap();
+    let confirmed = TripleMeta::new().with_asserted_by(NodeId::named("agent:b"));
+    source.insert_with_meta(reading(42), confirmed).unwrap();
+
+    let file = std::fs::File::create(&snapshot_path).unwrap();
+    let options = SnapshotOptions::default().with_block_size(64);
+    let info = source
+        .export_snapshot_with(std::io::BufWriter::new(file), &options)
+        .unwrap();
+    assert_eq!((info.triple_count, info.block_count), (300, 5));
+
+    let target = GraphDB::sqlite(sqlite_path.to_str().unwrap()).unwrap();
+    let file = std::fs::File::open(&snapshot_path).unwrap();
+    target
+        .import_snapshot(std::io::BufReader::new(file))
+        .unwrap();
+    assert_eq!(target.count(), 300);
+
+    for n in (0..300).step_by(21) {
+        let id = reading(n).id();
+        assert_eq!(target.get(&id).unwrap(), source.get(&id).unwrap());
+    }
+    assert_eq!(target.provenance(&reading(42).id()).unwrap().len(), 2);
+}
+
+// ============================================================================
+// Snapshot Isolation Tests
+// ============================================================================
+
+/// Writes batches of sensor readings on one thread while another checks that
+/// every snapshot sees the same triples through each index.
+fn check_snapshots_during_batch_writes(db: GraphDB) {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    const BATCHES: usize = 50;
+    const BATCH_SIZE: usize = 40;
+
+    fn reading(n: usize) -> Triple {
+        Triple::new(
+            NodeId::named(format!("sensor:{}", n % 7)),
+            Predicate::named("reads"),
+            Value::integer(n as i64),
+        )
+    }
+
+    let db = Arc::new(db);
+    let done = Arc::new(AtomicBool::new(false));
+
+    let writer = {
+        let db = Arc::clone(&db);
+        let done = Arc::clone(&done);
+        std::thread::spawn(move || {
+            for batch in 0..BATCHES {
+                let start = batch * BATCH_SIZE;
+                db.insert_batch((start..start + BATCH_SIZE).map(reading).collect())
+                    .unwrap();
+                // Delete part of the previous batch, so reads race deletes too
+                if batch > 0 {
+                    db.delete(&reading(start - 1).id()).unwrap();
+                }
+            }
+            done.store(true, Ordering::SeqCst);
+        })
+    };
+
+    let mut checks = 0;
+    while !done.load(Ordering::SeqCst) || checks == 0 {
+        let snapshot = db.snapshot().unwrap();
+        let by_predicate = snapshot
+            .find(TriplePattern::predicate(Predicate::named("reads")))
+            .unwrap();
+        let all = snapshot.find(TriplePattern::any()).unwrap();
+        assert_eq!(by_predicate.len(), snapshot.count());
+        assert_eq!(all.len(), snapshot.count());
+
+        let mut by_subject = 0;
+        for sensor in 0..7 {
+            by_subject += snapshot
+                .find(TriplePattern::subject(NodeId::named(format!(
+                    "sensor:{sensor}"
+                ))))
+                .unwrap()
+                .len();
+        }
+        assert_eq!(by_subject, snapshot.count());
+
+        for triple in &by_predicate {
+            let by_object = snapshot
+                .find(TriplePattern::object(triple.object.clone()))
+                .unwrap();
+            assert_eq!(by_object.len(), 1);
+            assert_eq!(by_object[0].id(), triple.id());
+            assert!(snapshot.get(&triple.id()).unwrap().is_some());
+        }
+        assert_eq!(
+            snapshot
+                .query()
+                .predicate(Predicate::named("reads"))
+                .count()
+                .unwrap(),
+            snapshot.count()
+        );
+        checks += 1;
+    }
+
+    writer.join().unwrap();
+    assert_eq!(db.count(), BATCHES * BATCH_SIZE - (BATCHES - 1));
+}
+
+#[test]
+fn test_snapshot_isolation_memory() {
+    check_snapshots_during_batch_writes(GraphDB::memory().unwrap());
+}
+
+#[cfg(feature = "sled-backend")]
+#[test]
+fn test_snapshot_isolation_sled() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = GraphDB::sled(dir.path().join("graph.sled").to_str().unwrap()).unwrap();
+    check_snapshots_during_batch_writes(db);
+}
+
+// ============================================================================
+// Blank Node Tests
+// ============================================================================
+
+#[cfg(feature = "sled-backend")]
+#[test]
+fn test_anonymous_node_ids_survive_reopen() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("graph.sled");
+    let path = path.to_str().unwrap();
+
+    let address = NodeId::anonymous();
+    let triple = Triple::new(
+        NodeId::named("user:alice"),
+        Predicate::named("lives_at"),
+        Value::Node(address.clone()),
+    );
+    let id = {
+        let db = GraphDB::sled(path).unwrap();
+        let id = db.insert(triple.clone()).unwrap();
+        db.flush().unwrap();
+        id
+    };
+
+    let db = GraphDB::sled(path).unwrap();
+    let stored = db.get(&id).unwrap().unwrap();
+    assert_eq!(stored.object_node(), Some(&address));
+    assert_eq!(stored.id(), id);
+    assert_eq!(db.get_object(&Value::Node(address)).unwrap().len(), 1);
+
+    // Nodes minted after the reopen are new ones
+    let fresh = NodeId::anonymous();
+    assert!(db.get_object(&Value::Node(fresh)).unwrap().is_empty());
+}
+
+#[test]
+fn test_skolemize_names_blank_nodes() {
+    let db = GraphDB::memory().unwrap();
+    let person = NodeId::anonymous();
+    let legacy = NodeId::blank_with_id(3);
+    let knows = Predicate::named("knows");
+    let id = db
+        .insert(Triple::new(
+            person.clone(),
+            knows.clone(),
+            Value::Node(legacy.clone()),
+        ))
+        .unwrap();
+    db.insert(Triple::link("user:alice", "knows", "user:bob"))
+        .unwrap();
+
+    assert_eq!(db.skolemize("urn:skolem:").unwrap(), 1);
+    assert!(db.get(&id).unwrap().is_none());
+    assert_eq!(db.count(), 2);
+
+    let skolem =
+        |node: &NodeId| NodeId::named(format!("urn:skolem:{}", node.blank_label().unwrap()));
+    let rewritten = db.get_subject(&skolem(&person)).unwrap();
+    assert_eq!(rewritten.len(), 1);
+    assert_eq!(
+        rewritten[0].object_node(),
+        Some(&NodeId::named("urn:skolem:b3"))
+    );
+
+    // Nothing is left to rewrite
+    assert_eq!(db.skolemize("urn:skolem:").unwrap(), 0);
+}
+
+#[cfg(feature = "rdf")]
+mod rdf_blank_nodes {
+    use super::*;
+
+    const PEOPLE: &str = r#"
+        @prefix ex: <http://example.org/> .
+        _:p ex:name "Someone" .
+        _:p ex:knows _:q .
+        _:q ex:name "Someone else" .
+    "#;
+
+    fn blank_nodes(db: &GraphDB) -> HashSet<NodeId> {
+        db.find(TriplePattern::any())
+            .unwrap()
+            .into_iter()
+            .flat_map(|t| [Some(t.subject.clone()), t.object_node().cloned()])
+            .flatten()
+            .filter(NodeId::is_blank)
+            .collect()
+    }
+
+    #[test]
+    fn test_separate_imports_never_unify() {
+        let db = GraphDB::memory().unwrap();
+        db.import_turtle(PEOPLE).unwrap();
+        let first = blank_nodes(&db);
+        db.import_turtle(PEOPLE).unwrap();
+        let both = blank_nodes(&db);
+
+        assert_eq!(first.len(), 2);
+        assert_eq!(both.len(), 4);
+        assert_eq!(db.count(), 6);
+        assert!(both.iter().all(NodeId::is_anonymous));
+    }
+
+    #[test]
+    fn test_export_numbers_blank_nodes() {
+        let db = GraphDB::memory().unwrap();
+        db.import_turtle(PEOPLE).unwrap();
+
+        let nt = db.export_ntriples().unwrap();
+        let labels: HashSet<_> = nt
+            .split_whitespace()
+            .filter(|term| term.starts_with("_:"))
+            .collect();
+        assert_eq!(labels, HashSet::from(["_:b1", "_:b2"]));
+
+        // The export reads back as the same shape
+        let copy = GraphDB::memory().unwrap();
+        copy.import_ntriples(&nt).unwrap();
+        assert_eq!(copy.count(), 3);
+        assert_eq!(blank_nodes(&copy).len(), 2);
+    }
+
+    #[test]
+    fn test_skolemize_then_export_has_no_blank_nodes() {
+        let db = GraphDB::memory().unwrap();
+        db.import_turtle(PEOPLE).unwrap();
+
+        assert_eq!(
+            db.skolemize("http://example.org/.well-known/genid/")
+                .unwrap(),
+            3
+        );
+        assert!(blank_nodes(&db).is_empty());
+
+        for export in [db.export_turtle().unwrap(), db.export_ntriples().unwrap()] {
+            assert!(!export.contains("_:"), "{export}");
+            assert!(export.contains("/.well-known/genid/a"));
+        }
+    }
+}
+
+#[cfg(feature = "rdf")]
+mod rdf_jsonld {
+    use super::*;
+    use aingle_graph::Error;
+    use serde_json::json;
+
+    const PEOPLE_JSONLD: &str = include_str!("fixtures/people.jsonld");
+    const PEOPLE_TTL: &str = include_str!("fixtures/people.ttl");
+    const CATALOG_JSONLD: &str = include_str!("fixtures/catalog.jsonld");
+    const RDF_JSON: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#JSON";
+
+    /// The triples of a graph, sorted, with every blank node written `_`
+    fn shape(db: &GraphDB) -> Vec<String> {
+        let name = |node: &NodeId| match node.as_name() {
+            Some(name) if !node.is_blank() => name.to_string(),
+            _ => "_".to_string(),
+        };
+        let mut shape: Vec<String> = db
+            .find(TriplePattern::any())
+            .unwrap()
+            .iter()
+            .map(|t| {
+                let object = match &t.object {
+                    Value::Node(node) => name(node),
+                    other => format!("{:?}", other),
+                };
+                format!("{} {} {}", name(&t.subject), t.predicate.as_str(), object)
+            })
+            .collect();
+        shape.sort();
+        shape
+    }
+
+    fn imported(jsonld: &str) -> GraphDB {
+        let db = GraphDB::memory().unwrap();
+        db.import_jsonld(jsonld).unwrap();
+        db
+    }
+
+    #[test]
+    fn test_jsonld_import_matches_turtle() {
+        let from_jsonld = imported(PEOPLE_JSONLD);
+        let from_turtle = GraphDB::memory().unwrap();
+        from_turtle.import_turtle(PEOPLE_TTL).unwrap();
+
+        assert_eq!(from_jsonld.count(), 14);
+        assert_eq!(shape(&from_jsonld), shape(&from_turtle));
+
+        // Typed values land on the matching variants
+        let alice = NodeId::named("http://example.org/alice");
+        let object = |predicate: &str| {
+            from_jsonld
+                .find(
+                    TriplePattern::subject(alice.clone())
+                        .with_predicate(Predicate::named(predicate)),
+                )
+                .unwrap()
+                .remove(0)
+                .object
+        };
+        assert_eq!(object("http://example.org/age"), Value::Integer(30));
+        assert_eq!(object("http://example.org/height"), Value::Float(1.68));
+        assert_eq!(object("http://example.org/verified"), Value::Boolean(true));
+        assert_eq!(
+            object("http://example.org/born"),
+            Value::DateTime("1990-04-01T00:00:00Z".to_string())
+        );
+        assert_eq!(
+            object("http://example.org/bio"),
+            Value::lang_string("Ingeniera", "es")
+        );
+        assert!(object("http://example.org/address")
+            .as_node()
+            .is_some_and(NodeId::is_anonymous));
+    }
+
+    #[test]
+    fn test_jsonld_fixtures_round_trip() {
+        for fixture in [PEOPLE_JSONLD, CATALOG_JSONLD] {
+            let db = imported(fixture);
+            let original = shape(&db);
+
+            // With the generated context
+            let exported = db.export_jsonld(None).unwrap();
+            assert_eq!(shape(&imported(&exported)), original, "{exported}");
+
+            // With a context of our own
+            let context = json!({
+                "ex": "http://example.org/",
+                "schema": "http://schema.org/",
+                "name": "http://schema.org/name",
+                "age": {"@id": "ex:age", "@type": "http://www.w3.org/2001/XMLSchema#integer"}
+            });
+            let exported = db.export_jsonld(context.as_object().cloned()).unwrap();
+            let document: serde_json::Value = serde_json::from_str(&exported).unwrap();
+            assert_eq!(document["@context"], context);
+            assert_eq!(shape(&imported(&exported)), original, "{exported}");
+        }
+    }
+
+    #[test]
+    fn test_jsonld_catalog_import() {
+        let db = imported(CATALOG_JSONLD);
+        let widget = NodeId::named("http://shop.example/items/widget-1");
+        let objects = |predicate: &str| -> Vec<Value> {
+            db.find(
+                TriplePattern::subject(widget.clone()).with_predicate(Predicate::named(predicate)),
+            )
+            .unwrap()
+            .into_iter()
+            .map(|t| t.object)
+            .collect()
+        };
+
+        assert_eq!(
+            objects("http://schema.org/name"),
+            vec![Value::lang_string("Widget", "en")]
+        );
+        assert_eq!(
+            objects("http://shop.example/terms/specs"),
+            vec![Value::typed(
+                r#"{"ports":["usb","hdmi"],"weight":1.5}"#,
+                RDF_JSON
+            )]
+        );
+        assert_eq!(
+            objects("http://shop.example/terms/relatedTo"),
+            vec![Value::Node(NodeId::named(
+                "http://shop.example/items/widget-2"
+            ))]
+        );
+
+        // Both offers are sold by the one `_:shop` node
+        let offers = objects("http://schema.org/offers");
+        assert_eq!(offers.len(), 2);
+        let sellers: HashSet<NodeId> = offers
+            .iter()
+            .flat_map(|offer| {
+                db.find(
+                    TriplePattern::subject(offer.as_node().unwrap().clone())
+                        .with_predicate(Predicate::named("http://schema.org/seller")),
+                )
+                .unwrap()
+            })
+            .filter_map(|t| t.object_node().cloned())
+            .collect();
+        assert_eq!(sellers.len(), 1);
+    }
+
+    #[test]
+    fn test_jsonld_export_groups_by_subject() {
+        let db = GraphDB::memory().unwrap();
+        db.insert(Triple::literal("user:alice", "has_name", "Alice"))
+            .unwrap();
+        db.insert(Triple::new(
+            NodeId::named("user:alice"),
+            Predicate::named("has_age"),
+            Value::Integer(30),
+        ))
+        .unwrap();
+        db.insert(Triple::link("user:alice", "knows", "user:bob"))
+            .unwrap();
+        db.insert(Triple::new(
+            NodeId::named("user:alice"),
+            Predicate::named("settings"),
+            Value::typed(r#"{"theme":"dark"}"#, RDF_JSON),
+        ))
+        .unwrap();
+
+        let exported = db.export_jsonld(None).unwrap();
+        let document: serde_json::Value = serde_json::from_str(&exported).unwrap();
+        assert_eq!(
+            document["@graph"],
+            json!([{
+                "@id": "user:alice",
+                "has_age": 30,
+                "has_name": "Alice",
+                "knows": {"@id": "user:bob"},
+                "settings": {"@value": {"theme": "dark"}, "@type": "@json"}
+            }])
+        );
+        // Names that are not IRIs come back unchanged
+        assert_eq!(shape(&imported(&exported)), shape(&db));
+    }
+
+    #[test]
+    fn test_jsonld_remote_context_is_rejected() {
+        let db = GraphDB::memory().unwrap();
+        let doc = r#"{"@context": "https://schema.org/", "@id": "ex:a", "name": "A"}"#;
+        let err = db.import_jsonld(doc).unwrap_err();
+        assert!(matches!(err, Error::Unsupported(_)), "{err}");
+        assert!(err.to_string().contains("https://schema.org/"));
+        assert_eq!(db.count(), 0);
+    }
+}
+
+// ============================================================================
+// Secondary Index Tests
+// ============================================================================
+
+fn timestamped(i: i64) -> Triple {
+    // Integers, floats and the odd non-numeric object under one predicate
+    let object = match i % 5 {
+        0 => Value::Float(i as f64 + 0.5),
+        4 => Value::literal(format!("t{}", i)),
+        _ => Value::integer(i),
+    };
+    Triple::new(
+        NodeId::named(format!("event:{}", i)),
+        Predicate::named("has_timestamp"),
+        object,
+    )
+}
+
+/// Subjects matched by a range query, sorted.
+fn range_subjects(
+    db: &GraphDB,
+    range: (std::ops::Bound<Value>, std::ops::Bound<Value>),
+) -> Vec<String> {
+    let mut subjects: Vec<String> = db
+        .query()
+        .predicate(Predicate::named("has_timestamp"))
+        .object_range(range)
+        .execute()
+        .unwrap()
+        .triples
+        .iter()
+        .map(|t| t.subject.as_name().unwrap().to_string())
+        .collect();
+    subjects.sort();
+    subjects
+}
+
+/// Checks that range queries return the same matches with and without a
+/// numeric index, and that the planner uses the index once it is built.
+fn check_range_queries_with_and_without_index(db: GraphDB) {
+    use aingle_graph::{IndexKind, IndexState};
+    use std::ops::Bound::{Excluded, Included, Unbounded};
+
+    db.insert_batch((0..300).map(timestamped).collect())
+        .unwrap();
+    db.insert(Triple::new(
+        NodeId::named("event:other"),
+        Predicate::named("has_size"),
+        Value::integer(50),
+    ))
+    .unwrap();
+
+    let ranges = [
+        (Included(Value::integer(40)), Excluded(Value::integer(60))),
+        (Excluded(Value::Float(40.5)), Included(Value::integer(61))),
+        (Unbounded, Included(Value::Float(10.0))),
+        (Included(Value::integer(290)), Unbounded),
+        (Included(Value::integer(500)), Unbounded),
+        (Unbounded, Unbounded),
+    ];
+    let scanned: Vec<Vec<String>> = ranges
+        .iter()
+        .cloned()
+        .map(|r| range_subjects(&db, r))
+        .collect();
+    assert_eq!(scanned[0].len(), 16);
+    assert!(scanned[0].contains(&"event:50".to_string()));
+    assert!(scanned[4].is_empty());
+    assert_eq!(scanned[5].len(), 240);
+
+    let query = || {
+        db.query()
+            .predicate(Predicate::named("has_timestamp"))
+            .object_range(Value::integer(40)..Value::integer(60))
+    };
+    let plan = query().explain().unwrap();
+    assert_eq!(plan.steps[0].range_index, None);
+    assert!(plan.to_string().contains("via POS"), "{plan}");
+
+    let info = db
+        .create_index(Predicate::named("has_timestamp"), IndexKind::NumericObject)
+        .unwrap();
+    assert_eq!(info.state, IndexState::Ready);
+    assert_eq!(info.entries, 240);
+
+    let plan = query().explain().unwrap();
+    assert_eq!(plan.steps[0].range_index, Some(IndexKind::NumericObject));
+    assert_eq!(plan.steps[0].index, None);
+    let text = plan.to_string();
+    assert!(text.contains("via numeric_object index"), "{text}");
+    assert!(text.contains("?o >= 40, ?o < 60"), "{text}");
+    let indexed: Vec<Vec<String>> = ranges
+        .iter()
+        .cloned()
+        .map(|r| range_subjects(&db, r))
+        .collect();
+    assert_eq!(indexed, scanned);
+
+    // Only the matches are read
+    let (results, stats) = query().execute_with_stats().unwrap();
+    assert_eq!(results.len(), 16);
+    assert_eq!(stats.rows_examined, 16);
+
+    // A bound subject is served by SPO instead
+    let plan = query()
+        .subject(NodeId::named("event:41"))
+        .explain()
+        .unwrap();
+    assert_eq!(plan.steps[0].range_index, None);
+    assert_eq!(
+        query()
+            .subject(NodeId::named("event:41"))
+            .execute()
+            .unwrap()
+            .len(),
+        1
+    );
+
+    // Inserts and deletes keep the index up to date
+    db.insert(timestamped(1001)).unwrap();
+    db.insert(Triple::new(
+        NodeId::named("event:late"),
+        Predicate::named("has_timestamp"),
+        Value::Float(45.25),
+    ))
+    .unwrap();
+    db.delete(&timestamped(41).id()).unwrap();
+    let mut expected = scanned[0].clone();
+    expected.retain(|s| s != "event:41");
+    expected.push("event:late".to_string());
+    expected.sort();
+    assert_eq!(range_subjects(&db, ranges[0].clone()), expected);
+    assert_eq!(
+        range_subjects(&db, ranges[3].clone()).len(),
+        scanned[3].len() + 1
+    );
+
+    let stats = db.stats();
+    assert_eq!(stats.indexes.len(), 1);
+    assert_eq!(
+        stats.indexes[0].predicate,
+        Predicate::named("has_timestamp")
+    );
+    assert_eq!(stats.indexes[0].entries, 241);
+
+    // Ordering applies to the indexed matches, floats sorting after integers
+    let ordered = query()
+        .order_by(
+            aingle_graph::OrderKey::Object,
+            aingle_graph::SortOrder::Desc,
+        )
+        .limit(2)
+        .execute()
+        .unwrap();
+    assert_eq!(ordered.triples[0].object, Value::Float(55.5));
+    assert_eq!(ordered.triples[1].object, Value::Float(50.5));
+    assert_eq!(ordered.total_count, 16);
+
+    assert!(db.drop_index(&Predicate::named("has_timestamp")).unwrap());
+    assert!(!db.drop_index(&Predicate::named("has_timestamp")).unwrap());
+    assert!(db.indexes().unwrap().is_empty());
+    assert_eq!(query().explain().unwrap().steps[0].range_index, None);
+    assert_eq!(range_subjects(&db, ranges[0].clone()), expected);
+}
+
+#[test]
+fn test_range_queries_with_and_without_index_memory() {
+    check_range_queries_with_and_without_index(GraphDB::memory().unwrap());
+}
+
+#[cfg(feature = "sled-backend")]
+#[test]
+fn test_range_queries_with_and_without_index_sled() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("graph.sled");
+    check_range_queries_with_and_without_index(GraphDB::sled(path.to_str().unwrap()).unwrap());
+}
+
+#[cfg(feature = "sqlite-backend")]
+#[test]
+fn test_range_queries_with_and_without_index_sqlite() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("graph.sqlite");
+    check_range_queries_with_and_without_index(GraphDB::sqlite(path.to_str().unwrap()).unwrap());
+}
+
+#[cfg(feature = "sled-backend")]
+#[test]
+fn test_index_build_resumes_after_reopen() {
+    use aingle_graph::{IndexKind, IndexState};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("graph.sled");
+    let path = path.to_str().unwrap();
+    let predicate = Predicate::named("has_timestamp");
+    fn query(db: &GraphDB) -> aingle_graph::QueryBuilder<'_> {
+        db.query()
+            .predicate(Predicate::named("has_timestamp"))
+            .object_range(Value::integer(100)..)
+    }
+
+    {
+        let db = GraphDB::sled(path).unwrap();
+        db.insert_batch((0..250).map(timestamped).collect())
+            .unwrap();
+
+        // Interrupted after two chunks
+        db.build_index_step(&predicate, IndexKind::NumericObject, 60)
+            .unwrap();
+        let info = db
+            .build_index_step(&predicate, IndexKind::NumericObject, 60)
+            .unwrap();
+        assert_eq!(info.state, IndexState::Building);
+        assert_eq!(info.entries, 70);
+        assert_eq!(query(&db).explain().unwrap().steps[0].range_index, None);
+        // Triples written during the build are indexed right away
+        db.insert(timestamped(998)).unwrap();
+        db.flush().unwrap();
+    }
+
+    let db = GraphDB::sled(path).unwrap();
+    let listed = db.indexes().unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].state, IndexState::Building);
+    assert_eq!(listed[0].entries, 71);
+    let scanned = query(&db).execute().unwrap().len();
+
+    let info = db
+        .create_index(predicate.clone(), IndexKind::NumericObject)
+        .unwrap();
+    assert_eq!(info.state, IndexState::Ready);
+    assert_eq!(info.entries, 201);
+    assert_eq!(
+        query(&db).explain().unwrap().steps[0].range_index,
+        Some(IndexKind::NumericObject)
+    );
+    assert_eq!(query(&db).execute().unwrap().len(), scanned);
+
+    // Dropping is persisted too
+    db.drop_index(&predicate).unwrap();
+    db.flush().unwrap();
+    drop(db);
+    let db = GraphDB::sled(path).unwrap();
+    assert!(db.indexes().unwrap().is_empty());
+    assert_eq!(query(&db).execute().unwrap().len(), scanned);
+}
+
+#[test]
+fn test_range_filter_rejects_non_numeric_bounds() {
+    use aingle_graph::{IndexKind, IndexState};
+
+    let db = GraphDB::memory().unwrap();
+    db.insert(timestamped(1)).unwrap();
+    let query = || db.query().predicate(Predicate::named("has_timestamp"));
+
+    assert!(query()
+        .object_range(Value::literal("a")..)
+        .execute()
+        .is_err());
+    assert!(query().object_range(Value::integer(0)..).count().is_err());
+    assert!(query().object_range(Value::integer(0)..).solve().is_err());
+
+    // A step of no triples defines the index without finishing it
+    let info = db
+        .build_index_step(
+            &Predicate::named("has_timestamp"),
+            IndexKind::NumericObject,
+            0,
+        )
+        .unwrap();
+    assert_eq!((info.state, info.entries), (IndexState::Building, 0));
+    let info = db
+        .create_index(Predicate::named("has_timestamp"), IndexKind::NumericObject)
+        .unwrap();
+    assert_eq!((info.state, info.entries), (IndexState::Ready, 1));
+}
@@ -24,6 +24,7 @@ fn node_to_str(node: &NodeId) -> String {
         NodeId::Named(s) => s.clone(),
         NodeId::Hash(h) => format!("hash:{:x?}", &h[..8]),
         NodeId::Blank(id) => format!("_:b{}", id),
+        NodeId::Anonymous(id) => format!("_:a{:x?}", &id[..8]),
     }
 }
 
@@ -70,10 +71,7 @@ impl BuiltinRules {
             Rule::integrity("no_self_reference")
                 .name("No Self References")
                 .description("Prevents nodes from having relationships with themselves.")
-                .when(|t| match &t.object {
-                    Value::Node(node) => node_to_str(node) == node_to_str(&t.subject),
-                    _ => false,
-                })
+                .when_self_reference()
                 .reject("Self-references are not allowed.")
                 .priority(100)
                 .build(),
@@ -84,7 +82,7 @@ impl BuiltinRules {
             Rule::integrity("no_empty_predicate")
                 .name("No Empty Predicates")
                 .description("Predicates must have a non-empty name.")
-                .when(|t| t.predicate.as_str().is_empty())
+                .when_predicate("")
                 .reject("Predicate cannot be empty.")
                 .priority(100)
                 .build(),
@@ -424,10 +422,7 @@ impl BuiltinRules {
         ruleset.add(
             Rule::integrity("no_self_reference")
                 .name("No Self References")
-                .when(|t| match &t.object {
-                    Value::Node(node) => node_to_str(node) == node_to_str(&t.subject),
-                    _ => false,
-                })
+                .when_self_reference()
                 .reject("Self-references not allowed.")
                 .priority(100)
                 .build(),
@@ -437,7 +432,7 @@ impl BuiltinRules {
         ruleset.add(
             Rule::integrity("no_empty_predicate")
                 .name("No Empty Predicates")
-                .when(|t| t.predicate.as_str().is_empty())
+                .when_predicate("")
                 .reject("Empty predicate.")
                 .priority(100)
                 .build(),
@@ -0,0 +1,815 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Triple encoding of rule sets
+//!
+//! A [`RuleSet`] can be stored in the graph it validates, so that a node loads
+//! its rules from data at startup instead of from code. Every rule becomes a
+//! node named `{namespace}{rule id}`, and its conditions, action and patterns
+//! become nodes below it:
+//!
+//! ```text
+//! rules:no_empty_predicate              logic:has_kind       "integrity"
+//!                                       logic:has_name       "No Empty Predicates"
+//!                                       logic:has_priority   100
+//!                                       logic:is_enabled     true
+//!                                       logic:position       1
+//!                                       logic:has_condition  rules:no_empty_predicate/condition/0
+//!                                       logic:has_action     rules:no_empty_predicate/action
+//! rules:no_empty_predicate/condition/0  logic:type           "predicate_equals"
+//!                                       logic:position       0
+//!                                       logic:value          ""
+//! rules:no_empty_predicate/action       logic:type           "reject"
+//!                                       logic:value          "Empty predicate."
+//! ```
+//!
+//! Subject and object matches point to a pattern node with `logic:pattern`.
+//! Triple patterns (in `exists`, `not_exists` and `infer`) use
+//! `logic:subject`, `logic:predicate` and `logic:object` instead. A pattern
+//! node has a `logic:type`, a `logic:value` unless it is `any`, and a
+//! `logic:datatype` if it is a `typed_literal`. A `builtin` condition names
+//! its `logic:function`, links its two operands with `logic:argument` (each
+//! with a `logic:position`, a `logic:type` and a `logic:value`), and gives
+//! its result variable, if any, as `logic:result`. The rule set's own name
+//! and description are stored on the `{namespace}` node.
+//!
+//! To hot-reload, [`subscribe`](aingle_graph::GraphDB::subscribe) to the
+//! graph and call [`RuleSet::from_graph`] again when a changed triple's
+//! subject starts with the namespace.
+
+use std::collections::{HashMap, HashSet};
+
+use aingle_graph::{GraphDB, NodeId, Predicate, Triple, TriplePattern as GraphPattern, Value};
+
+use crate::error::{Error, Result};
+use crate::functions::{Builtin, Term};
+use crate::rule::{Action, Condition, Pattern, Rule, RuleKind, RuleSet, TriplePattern};
+
+/// Kind of a rule; its presence marks a node as a rule
+pub const HAS_KIND: &str = "logic:has_kind";
+/// Name of a rule or rule set
+pub const HAS_NAME: &str = "logic:has_name";
+/// Description of a rule or rule set
+pub const HAS_DESCRIPTION: &str = "logic:has_description";
+/// Priority of a rule
+pub const HAS_PRIORITY: &str = "logic:has_priority";
+/// Whether a rule is enabled
+pub const IS_ENABLED: &str = "logic:is_enabled";
+/// Link from a rule to one of its conditions
+pub const HAS_CONDITION: &str = "logic:has_condition";
+/// Link from a rule to its action
+pub const HAS_ACTION: &str = "logic:has_action";
+/// Order of a rule in its set, or of a condition in its rule
+pub const POSITION: &str = "logic:position";
+/// Type of a condition, action or pattern
+pub const TYPE: &str = "logic:type";
+/// Literal argument of a condition, action or pattern
+pub const VALUE: &str = "logic:value";
+/// Datatype of a typed literal pattern
+pub const DATATYPE: &str = "logic:datatype";
+/// Link from a subject or object match to its pattern
+pub const PATTERN: &str = "logic:pattern";
+/// Link from a triple pattern to its subject pattern
+pub const SUBJECT: &str = "logic:subject";
+/// Predicate of a triple pattern
+pub const PREDICATE: &str = "logic:predicate";
+/// Link from a triple pattern to its object pattern
+pub const OBJECT: &str = "logic:object";
+/// Name of a built-in's function
+pub const FUNCTION: &str = "logic:function";
+/// Link from a built-in to one of its operands
+pub const ARGUMENT: &str = "logic:argument";
+/// Variable receiving a built-in's result
+pub const RESULT: &str = "logic:result";
+
+impl RuleSet {
+    /// Encode this rule set as triples under `namespace`
+    ///
+    /// Fails if two rules share an ID or a rule has a [`Condition::Custom`]
+    /// closure, which has no data form.
+    pub fn to_triples(&self, namespace: &str) -> Result<Vec<Triple>> {
+        let mut encoder = Encoder::default();
+        encoder.add(namespace, HAS_NAME, Value::literal(&self.name));
+        encoder.add(
+            namespace,
+            HAS_DESCRIPTION,
+            Value::literal(&self.description),
+        );
+
+        let mut seen = HashSet::new();
+        for (position, rule) in self.rules.iter().enumerate() {
+            if !seen.insert(rule.id.as_str()) {
+                return Err(Error::InvalidRule(format!(
+                    "duplicate rule id `{}` in rule set `{}`",
+                    rule.id, self.name
+                )));
+            }
+            encoder.rule(&format!("{}{}", namespace, rule.id), rule, position)?;
+        }
+        Ok(encoder.triples)
+    }
+
+    /// Load the rule set stored under `namespace`
+    ///
+    /// Rules are returned in their encoded order. If any rule node is
+    /// malformed, the error lists every offending node and what is wrong
+    /// with it.
+    pub fn from_graph(graph: &GraphDB, namespace: &str) -> Result<RuleSet> {
+        let mut nodes: HashMap<String, Vec<Triple>> = HashMap::new();
+        for triple in graph.find(GraphPattern::any())? {
+            if let Some(name) = triple.subject.as_name() {
+                if name.starts_with(namespace) {
+                    nodes.entry(name.to_string()).or_default().push(triple);
+                }
+            }
+        }
+        let decoder = Decoder { nodes };
+
+        let mut rule_nodes: Vec<&str> = decoder
+            .nodes
+            .iter()
+            .filter(|(name, triples)| {
+                name.as_str() != namespace
+                    && triples.iter().any(|t| t.predicate.as_str() == HAS_KIND)
+            })
+            .map(|(name, _)| name.as_str())
+            .collect();
+        rule_nodes.sort_unstable();
+
+        let mut rules = Vec::new();
+        let mut problems = Vec::new();
+        for node in rule_nodes {
+            match decoder.rule(node, &node[namespace.len()..]) {
+                Ok(rule) => rules.push(rule),
+                Err(problem) => problems.push(format!("rule `{}`: {}", node, problem)),
+            }
+        }
+
+        let mut ruleset = RuleSet::new(namespace);
+        match decoder.optional_string(namespace, HAS_NAME) {
+            Ok(name) => ruleset.name = name.unwrap_or(ruleset.name),
+            Err(problem) => problems.push(format!("rule set `{}`: {}", namespace, problem)),
+        }
+        match decoder.optional_string(namespace, HAS_DESCRIPTION) {
+            Ok(description) => ruleset.description = description.unwrap_or_default(),
+            Err(problem) => problems.push(format!("rule set `{}`: {}", namespace, problem)),
+        }
+
+        if !problems.is_empty() {
+            return Err(Error::InvalidRule(format!(
+                "{} malformed rule node(s) under `{}`: {}",
+                problems.len(),
+                namespace,
+                problems.join("; ")
+            )));
+        }
+
+        // Stable, so unpositioned rules stay sorted by node name
+        rules.sort_by_key(|(position, _)| position.unwrap_or(i64::MAX));
+        ruleset.rules = rules.into_iter().map(|(_, rule)| rule).collect();
+        Ok(ruleset)
+    }
+}
+
+fn kind_name(kind: RuleKind) -> &'static str {
+    match kind {
+        RuleKind::Integrity => "integrity",
+        RuleKind::Authority => "authority",
+        RuleKind::Temporal => "temporal",
+        RuleKind::Inference => "inference",
+        RuleKind::Constraint => "constraint",
+    }
+}
+
+fn parse_kind(name: &str) -> Option<RuleKind> {
+    match name {
+        "integrity" => Some(RuleKind::Integrity),
+        "authority" => Some(RuleKind::Authority),
+        "temporal" => Some(RuleKind::Temporal),
+        "inference" => Some(RuleKind::Inference),
+        "constraint" => Some(RuleKind::Constraint),
+        _ => None,
+    }
+}
+
+#[derive(Default)]
+struct Encoder {
+    triples: Vec<Triple>,
+}
+
+impl Encoder {
+    fn add(&mut self, subject: &str, predicate: &str, object: Value) {
+        self.triples.push(Triple::new(
+            NodeId::named(subject),
+            Predicate::named(predicate),
+            object,
+        ));
+    }
+
+    fn link(&mut self, subject: &str, predicate: &str, object: &str) {
+        self.add(subject, predicate, Value::Node(NodeId::named(object)));
+    }
+
+    fn rule(&mut self, node: &str, rule: &Rule, position: usize) -> Result<()> {
+        self.add(node, HAS_KIND, Value::literal(kind_name(rule.kind)));
+        self.add(node, HAS_NAME, Value::literal(&rule.name));
+        self.add(node, HAS_DESCRIPTION, Value::literal(&rule.description));
+        self.add(node, HAS_PRIORITY, Value::Integer(rule.priority.into()));
+        self.add(node, IS_ENABLED, Value::Boolean(rule.enabled));
+        self.add(node, POSITION, Value::Integer(position as i64));
+
+        for (i, condition) in rule.conditions.iter().enumerate() {
+            let condition_node = format!("{}/condition/{}", node, i);
+            self.link(node, HAS_CONDITION, &condition_node);
+            self.add(&condition_node, POSITION, Value::Integer(i as i64));
+            self.condition(&condition_node, &rule.id, condition)?;
+        }
+
+        let action_node = format!("{}/action", node);
+        self.link(node, HAS_ACTION, &action_node);
+        self.action(&action_node, &rule.action);
+        Ok(())
+    }
+
+    fn condition(&mut self, node: &str, rule_id: &str, condition: &Condition) -> Result<()> {
+        match condition {
+            Condition::PredicateEquals(predicate) => {
+                self.add(node, TYPE, Value::literal("predicate_equals"));
+                self.add(node, VALUE, Value::literal(predicate));
+            }
+            Condition::SubjectMatches(pattern) => {
+                self.add(node, TYPE, Value::literal("subject_matches"));
+                self.pattern_link(node, PATTERN, "pattern", pattern);
+            }
+            Condition::ObjectMatches(pattern) => {
+                self.add(node, TYPE, Value::literal("object_matches"));
+                self.pattern_link(node, PATTERN, "pattern", pattern);
+            }
+            Condition::Exists(pattern) => {
+                self.add(node, TYPE, Value::literal("exists"));
+                self.triple_pattern(node, pattern);
+            }
+            Condition::NotExists(pattern) => {
+                self.add(node, TYPE, Value::literal("not_exists"));
+                self.triple_pattern(node, pattern);
+            }
+            Condition::SelfReference => {
+                self.add(node, TYPE, Value::literal("self_reference"));
+            }
+            Condition::Builtin(builtin) => {
+                self.add(node, TYPE, Value::literal("builtin"));
+                self.add(node, FUNCTION, Value::literal(builtin.name()));
+                let (a, b) = builtin.operands();
+                for (i, term) in [a, b].into_iter().enumerate() {
+                    let argument = format!("{}/argument/{}", node, i);
+                    self.link(node, ARGUMENT, &argument);
+                    self.add(&argument, POSITION, Value::Integer(i as i64));
+                    self.term(&argument, term);
+                }
+                if let Some(out) = builtin.output() {
+                    self.add(node, RESULT, Value::literal(out));
+                }
+            }
+            Condition::Custom(_) => {
+                return Err(Error::InvalidRule(format!(
+                    "rule `{}` has a custom condition, which cannot be encoded as triples",
+                    rule_id
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn action(&mut self, node: &str, action: &Action) {
+        match action {
+            Action::Accept => self.add(node, TYPE, Value::literal("accept")),
+            Action::Reject(reason) => {
+                self.add(node, TYPE, Value::literal("reject"));
+                self.add(node, VALUE, Value::literal(reason));
+            }
+            Action::Infer(pattern) => {
+                self.add(node, TYPE, Value::literal("infer"));
+                self.triple_pattern(node, pattern);
+            }
+            Action::Warn(message) => {
+                self.add(node, TYPE, Value::literal("warn"));
+                self.add(node, VALUE, Value::literal(message));
+            }
+            Action::ChainTo(rule_id) => {
+                self.add(node, TYPE, Value::literal("chain_to"));
+                self.add(node, VALUE, Value::literal(rule_id));
+            }
+        }
+    }
+
+    fn term(&mut self, node: &str, term: &Term) {
+        let (kind, value) = match term {
+            Term::Variable(var) => ("variable", Value::literal(var)),
+            Term::Integer(i) => ("integer", Value::Integer(*i)),
+            Term::Float(f) => ("float", Value::Float(*f)),
+            Term::String(s) => ("string", Value::literal(s)),
+        };
+        self.add(node, TYPE, Value::literal(kind));
+        self.add(node, VALUE, value);
+    }
+
+    fn triple_pattern(&mut self, node: &str, pattern: &TriplePattern) {
+        self.pattern_link(node, SUBJECT, "subject", &pattern.subject);
+        self.add(node, PREDICATE, Value::literal(&pattern.predicate));
+        self.pattern_link(node, OBJECT, "object", &pattern.object);
+    }
+
+    fn pattern_link(&mut self, owner: &str, predicate: &str, suffix: &str, pattern: &Pattern) {
+        let node = format!("{}/{}", owner, suffix);
+        self.link(owner, predicate, &node);
+        let (kind, value) = match pattern {
+            Pattern::Any => ("any", None),
+            Pattern::Node(v) => ("node", Some(v)),
+            Pattern::Literal(v) => ("literal", Some(v)),
+            Pattern::Variable(v) => ("variable", Some(v)),
+            Pattern::Prefix(v) => ("prefix", Some(v)),
+            Pattern::Regex(v) => ("regex", Some(v)),
+            Pattern::TypedLiteral { value, datatype } => {
+                self.add(&node, DATATYPE, Value::literal(datatype));
+                ("typed_literal", Some(value))
+            }
+        };
+        self.add(&node, TYPE, Value::literal(kind));
+        if let Some(value) = value {
+            self.add(&node, VALUE, Value::literal(value));
+        }
+    }
+}
+
+/// A decoding problem, described relative to the node being decoded
+type Decoded<T> = std::result::Result<T, String>;
+
+struct Decoder {
+    nodes: HashMap<String, Vec<Triple>>,
+}
+
+impl Decoder {
+    fn single(&self, node: &str, predicate: &str) -> Decoded<Option<&Value>> {
+        let mut values = self
+            .nodes
+            .get(node)
+            .into_iter()
+            .flatten()
+            .filter(|t| t.predicate.as_str() == predicate)
+            .map(|t| &t.object);
+        let first = values.next();
+        let extra = values.count();
+        if extra > 0 {
+            return Err(format!("{} {} values, expected one", extra + 1, predicate));
+        }
+        Ok(first)
+    }
+
+    fn required(&self, node: &str, predicate: &str) -> Decoded<&Value> {
+        self.single(node, predicate)?
+            .ok_or_else(|| format!("missing {}", predicate))
+    }
+
+    fn string(&self, node: &str, predicate: &str) -> Decoded<String> {
+        let value = self.required(node, predicate)?;
+        value
+            .as_string()
+            .map(str::to_string)
+            .ok_or_else(|| format!("{} must be a literal, found {}", predicate, value))
+    }
+
+    fn optional_string(&self, node: &str, predicate: &str) -> Decoded<Option<String>> {
+        match self.single(node, predicate)? {
+            None => Ok(None),
+            Some(value) => value
+                .as_string()
+                .map(|s| Some(s.to_string()))
+                .ok_or_else(|| format!("{} must be a literal, found {}", predicate, value)),
+        }
+    }
+
+    fn position(&self, node: &str) -> Decoded<Option<i64>> {
+        match self.single(node, POSITION)? {
+            None => Ok(None),
+            Some(value) => value
+                .as_integer()
+                .map(Some)
+                .ok_or_else(|| format!("{} must be an integer, found {}", POSITION, value)),
+        }
+    }
+
+    fn link(&self, node: &str, predicate: &str) -> Decoded<String> {
+        let value = self.required(node, predicate)?;
+        value
+            .as_node()
+            .and_then(NodeId::as_name)
+            .map(str::to_string)
+            .ok_or_else(|| format!("{} must link to a named node, found {}", predicate, value))
+    }
+
+    fn rule(&self, node: &str, id: &str) -> Decoded<(Option<i64>, Rule)> {
+        let kind = self.string(node, HAS_KIND)?;
+        let kind = parse_kind(&kind).ok_or_else(|| format!("unknown rule kind \"{}\"", kind))?;
+
+        let mut rule = Rule::new(
+            id,
+            self.optional_string(node, HAS_NAME)?.unwrap_or_default(),
+        );
+        rule.kind = kind;
+        rule.description = self
+            .optional_string(node, HAS_DESCRIPTION)?
+            .unwrap_or_default();
+
+        if let Some(value) = self.single(node, HAS_PRIORITY)? {
+            rule.priority = value
+                .as_integer()
+                .and_then(|p| i32::try_from(p).ok())
+                .ok_or_else(|| {
+                    format!("{} must be a 32-bit integer, found {}", HAS_PRIORITY, value)
+                })?;
+        }
+        if let Some(value) = self.single(node, IS_ENABLED)? {
+            rule.enabled = value
+                .as_boolean()
+                .ok_or_else(|| format!("{} must be a boolean, found {}", IS_ENABLED, value))?;
+        }
+
+        let mut conditions = Vec::new();
+        for triple in self.nodes.get(node).into_iter().flatten() {
+            if triple.predicate.as_str() != HAS_CONDITION {
+                continue;
+            }
+            let condition_node = triple
+                .object
+                .as_node()
+                .and_then(NodeId::as_name)
+                .ok_or_else(|| {
+                    format!(
+                        "{} must link to a named node, found {}",
+                        HAS_CONDITION, triple.object
+                    )
+                })?;
+            let decoded = self
+                .position(condition_node)
+                .and_then(|position| Ok((position, self.condition(condition_node)?)))
+                .map_err(|problem| format!("condition `{}`: {}", condition_node, problem))?;
+            conditions.push((decoded.0, condition_node, decoded.1));
+        }
+        conditions
+            .sort_by(|a, b| (a.0.unwrap_or(i64::MAX), a.1).cmp(&(b.0.unwrap_or(i64::MAX), b.1)));
+        rule.conditions = conditions.into_iter().map(|(_, _, c)| c).collect();
+
+        let action_node = self.link(node, HAS_ACTION)?;
+        rule.action = self
+            .action(&action_node)
+            .map_err(|problem| format!("action `{}`: {}", action_node, problem))?;
+
+        Ok((self.position(node)?, rule))
+    }
+
+    fn condition(&self, node: &str) -> Decoded<Condition> {
+        let kind = self.string(node, TYPE)?;
+        match kind.as_str() {
+            "predicate_equals" => Ok(Condition::PredicateEquals(self.string(node, VALUE)?)),
+            "subject_matches" => Ok(Condition::SubjectMatches(
+                self.linked_pattern(node, PATTERN)?,
+            )),
+            "object_matches" => Ok(Condition::ObjectMatches(
+                self.linked_pattern(node, PATTERN)?,
+            )),
+            "exists" => Ok(Condition::Exists(self.triple_pattern(node)?)),
+            "not_exists" => Ok(Condition::NotExists(self.triple_pattern(node)?)),
+            "self_reference" => Ok(Condition::SelfReference),
+            "builtin" => Ok(Condition::Builtin(self.builtin(node)?)),
+            _ => Err(format!("unknown condition type \"{}\"", kind)),
+        }
+    }
+
+    fn builtin(&self, node: &str) -> Decoded<Builtin> {
+        let function = self.string(node, FUNCTION)?;
+        let output = self.optional_string(node, RESULT)?;
+
+        let mut arguments = Vec::new();
+        for triple in self.nodes.get(node).into_iter().flatten() {
+            if triple.predicate.as_str() != ARGUMENT {
+                continue;
+            }
+            let argument = triple
+                .object
+                .as_node()
+                .and_then(NodeId::as_name)
+                .ok_or_else(|| {
+                    format!(
+                        "{} must link to a named node, found {}",
+                        ARGUMENT, triple.object
+                    )
+                })?;
+            let decoded = self
+                .position(argument)
+                .and_then(|position| Ok((position, self.term(argument)?)))
+                .map_err(|problem| format!("argument `{}`: {}", argument, problem))?;
+            arguments.push((decoded.0, argument, decoded.1));
+        }
+        if arguments.len() != 2 {
+            return Err(format!(
+                "built-in {} takes 2 arguments, found {}",
+                function,
+                arguments.len()
+            ));
+        }
+        arguments
+            .sort_by(|a, b| (a.0.unwrap_or(i64::MAX), a.1).cmp(&(b.0.unwrap_or(i64::MAX), b.1)));
+        let mut terms = arguments.into_iter().map(|(_, _, term)| term);
+        let (a, b) = (terms.next().unwrap(), terms.next().unwrap());
+
+        let has_output = output.is_some();
+        Builtin::from_parts(&function, a, b, output).ok_or_else(|| match function.as_str() {
+            "add" | "sub" | "mul" | "concat" => {
+                format!("built-in {} needs a {}", function, RESULT)
+            }
+            "greater_than" | "less_than" | "equal" | "str_starts" if has_output => {
+                format!("built-in {} takes no {}", function, RESULT)
+            }
+            _ => format!("unknown built-in \"{}\"", function),
+        })
+    }
+
+    fn term(&self, node: &str) -> Decoded<Term> {
+        let kind = self.string(node, TYPE)?;
+        let value = self.required(node, VALUE)?;
+        let term = match kind.as_str() {
+            "variable" => value.as_string().map(Term::var),
+            "string" => value.as_string().map(Term::string),
+            "integer" => value.as_integer().map(Term::Integer),
+            "float" => value.as_float().map(Term::Float),
+            _ => return Err(format!("unknown argument type \"{}\"", kind)),
+        };
+        term.ok_or_else(|| format!("{} {} does not fit type \"{}\"", VALUE, value, kind))
+    }
+
+    fn action(&self, node: &str) -> Decoded<Action> {
+        let kind = self.string(node, TYPE)?;
+        match kind.as_str() {
+            "accept" => Ok(Action::Accept),
+            "reject" => Ok(Action::Reject(self.string(node, VALUE)?)),
+            "infer" => Ok(Action::Infer(self.triple_pattern(node)?)),
+            "warn" => Ok(Action::Warn(self.string(node, VALUE)?)),
+            "chain_to" => Ok(Action::ChainTo(self.string(node, VALUE)?)),
+            _ => Err(format!("unknown action type \"{}\"", kind)),
+        }
+    }
+
+    fn triple_pattern(&self, node: &str) -> Decoded<TriplePattern> {
+        Ok(TriplePattern::new(
+            self.linked_pattern(node, SUBJECT)?,
+            self.string(node, PREDICATE)?,
+            self.linked_pattern(node, OBJECT)?,
+        ))
+    }
+
+    fn linked_pattern(&self, owner: &str, predicate: &str) -> Decoded<Pattern> {
+        let node = self.link(owner, predicate)?;
+        self.pattern(&node)
+            .map_err(|problem| format!("pattern `{}`: {}", node, problem))
+    }
+
+    fn pattern(&self, node: &str) -> Decoded<Pattern> {
+        let kind = self.string(node, TYPE)?;
+        match kind.as_str() {
+            "any" => Ok(Pattern::Any),
+            "node" => Ok(Pattern::Node(self.string(node, VALUE)?)),
+            "literal" => Ok(Pattern::Literal(self.string(node, VALUE)?)),
+            "variable" => Ok(Pattern::Variable(self.string(node, VALUE)?)),
+            "prefix" => Ok(Pattern::Prefix(self.string(node, VALUE)?)),
+            "regex" => Ok(Pattern::Regex(self.string(node, VALUE)?)),
+            "typed_literal" => Ok(Pattern::TypedLiteral {
+                value: self.string(node, VALUE)?,
+                datatype: self.string(node, DATATYPE)?,
+            }),
+            _ => Err(format!("unknown pattern type \"{}\"", kind)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BuiltinRules, RuleEngine};
+
+    const NS: &str = "rules:";
+
+    fn store(ruleset: &RuleSet) -> GraphDB {
+        let graph = GraphDB::memory().unwrap();
+        graph.insert_batch(ruleset.to_triples(NS).unwrap()).unwrap();
+        graph
+    }
+
+    fn outcome(engine: &RuleEngine, triple: &Triple) -> (bool, Vec<(String, String)>) {
+        let result = engine.validate(triple);
+        let rejections = result
+            .rejections
+            .iter()
+            .map(|r| (r.rule_id.clone(), r.reason.clone()))
+            .collect();
+        (result.is_valid(), rejections)
+    }
+
+    #[test]
+    fn test_minimal_round_trip_validates_identically() {
+        let original = BuiltinRules::minimal();
+        let loaded = RuleSet::from_graph(&store(&original), NS).unwrap();
+
+        assert_eq!(loaded.name, original.name);
+        assert_eq!(loaded.description, original.description);
+        let ids = |rs: &RuleSet| rs.rules.iter().map(|r| r.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&loaded), ids(&original));
+
+        let probes = [
+            Triple::new(
+                NodeId::named("alice"),
+                Predicate::named("knows"),
+                Value::Node(NodeId::named("bob")),
+            ),
+            Triple::new(
+                NodeId::named("alice"),
+                Predicate::named("knows"),
+                Value::Node(NodeId::named("alice")),
+            ),
+            Triple::new(
+                NodeId::named("alice"),
+                Predicate::named("name"),
+                Value::literal("alice"),
+            ),
+            Triple::new(
+                NodeId::named("alice"),
+                Predicate::named(""),
+                Value::literal("x"),
+            ),
+            Triple::new(
+                NodeId::blank_with_id(7),
+                Predicate::named(""),
+                Value::Node(NodeId::blank_with_id(7)),
+            ),
+            Triple::new(
+                NodeId::hash([1; 32]),
+                Predicate::named("same"),
+                Value::Node(NodeId::hash([1; 32])),
+            ),
+        ];
+
+        let before = RuleEngine::with_rules(original);
+        let after = RuleEngine::with_rules(loaded);
+        let mut rejected = 0;
+        for probe in &probes {
+            let expected = outcome(&before, probe);
+            assert_eq!(outcome(&after, probe), expected, "probe {:?}", probe);
+            rejected += usize::from(!expected.0);
+        }
+        assert_eq!(rejected, 4);
+    }
+
+    #[test]
+    fn test_round_trip_preserves_every_form() {
+        let mut original = RuleSet::new("forms");
+        original.description = "One of everything".to_string();
+        original.add(
+            Rule::inference("grandparent")
+                .name("Grandparent")
+                .when_predicate("parent_of")
+                .when_subject(Pattern::Variable("x".into()))
+                .when_object(Pattern::Variable("y".into()))
+                .when_exists(TriplePattern::new(
+                    Pattern::Variable("y".into()),
+                    "parent_of",
+                    Pattern::Variable("z".into()),
+                ))
+                .when_builtin(Builtin::Concat(
+                    Term::var("x"),
+                    Term::string("-"),
+                    "label".into(),
+                ))
+                .infer(TriplePattern::new(
+                    Pattern::Variable("x".into()),
+                    "grandparent_of",
+                    Pattern::Variable("z".into()),
+                ))
+                .priority(-3)
+                .build(),
+        );
+        let mut disabled = Rule::constraint("typed")
+            .when_object(Pattern::TypedLiteral {
+                value: "1".into(),
+                datatype: "xsd:int".into(),
+            })
+            .when_not_exists(TriplePattern::new(
+                Pattern::Any,
+                "p",
+                Pattern::Regex("^a".into()),
+            ))
+            .when_builtin(Builtin::LessThan(Term::Float(0.5), Term::Integer(2)))
+            .warn("typed one")
+            .build();
+        disabled.enabled = false;
+        original.add(disabled);
+        original.add(
+            Rule::authority("chain")
+                .when_subject(Pattern::Prefix("user:".into()))
+                .when_object(Pattern::Literal("x".into()))
+                .build(),
+        );
+        original.rules[2].action = Action::ChainTo("typed".into());
+
+        let loaded = RuleSet::from_graph(&store(&original), NS).unwrap();
+        assert_eq!(
+            serde_json::to_value(&loaded).unwrap(),
+            serde_json::to_value(&original).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_custom_condition_cannot_be_encoded() {
+        let mut ruleset = RuleSet::new("custom");
+        ruleset.add(Rule::integrity("closure").when(|_| true).build());
+        let err = ruleset.to_triples(NS).unwrap_err().to_string();
+        assert!(err.contains("`closure`"), "{}", err);
+    }
+
+    #[test]
+    fn test_malformed_rules_are_listed() {
+        let graph = store(&BuiltinRules::minimal());
+        let add = |s: &str, p: &str, o: Value| {
+            graph
+                .insert(Triple::new(NodeId::named(s), Predicate::named(p), o))
+                .unwrap();
+        };
+        // Unknown kind, and an action of an unknown type
+        add("rules:bad_kind", HAS_KIND, Value::literal("sorcery"));
+        add("rules:bad_action", HAS_KIND, Value::literal("integrity"));
+        add(
+            "rules:bad_action",
+            HAS_ACTION,
+            Value::Node(NodeId::named("rules:bad_action/action")),
+        );
+        add("rules:bad_action/action", TYPE, Value::literal("explode"));
+        // Second priority on a valid rule
+        add("rules:no_self_reference", HAS_PRIORITY, Value::Integer(5));
+
+        let err = RuleSet::from_graph(&graph, NS).unwrap_err().to_string();
+        assert!(err.contains("3 malformed"), "{}", err);
+        assert!(
+            err.contains("rule `rules:bad_kind`: unknown rule kind \"sorcery\""),
+            "{}",
+            err
+        );
+        assert!(
+            err.contains("rule `rules:bad_action`: action `rules:bad_action/action`: unknown action type \"explode\""),
+            "{}",
+            err
+        );
+        assert!(
+            err.contains("rule `rules:no_self_reference`: 2 logic:has_priority values"),
+            "{}",
+            err
+        );
+        assert!(!err.contains("no_empty_predicate"), "{}", err);
+    }
+
+    #[test]
+    fn test_reload_picks_up_changes() {
+        let graph = store(&BuiltinRules::minimal());
+        let rx = graph.subscribe();
+
+        let node = NodeId::named("rules:no_self_reference");
+        let enabled = graph
+            .find(GraphPattern::subject(node.clone()).with_predicate(Predicate::named(IS_ENABLED)))
+            .unwrap();
+        graph.delete(&enabled[0].id()).unwrap();
+        graph
+            .insert(Triple::new(
+                node,
+                Predicate::named(IS_ENABLED),
+                Value::Boolean(false),
+            ))
+            .unwrap();
+
+        let touched = std::iter::from_fn(|| rx.try_recv())
+            .filter(|e| {
+                e.triple()
+                    .subject
+                    .as_name()
+                    .is_some_and(|n| n.starts_with(NS))
+            })
+            .count();
+        assert_eq!(touched, 2);
+
+        let reloaded = RuleSet::from_graph(&graph, NS).unwrap();
+        assert!(!reloaded.get("no_self_reference").unwrap().enabled);
+        let engine = RuleEngine::with_rules(reloaded);
+        let self_ref = Triple::new(
+            NodeId::named("a"),
+            Predicate::named("knows"),
+            Value::Node(NodeId::named("a")),
+        );
+        assert!(engine.validate(&self_ref).is_valid());
+    }
+}
@@ -7,10 +7,13 @@
 //! - Forward chaining: Apply rules to derive new facts
 //! - Backward chaining: Work backwards from a goal to find supporting facts
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 
-use aingle_graph::{GraphDB, NodeId, Predicate, Triple, TriplePattern as GraphPattern, Value};
+use aingle_graph::{
+    GraphDB, NodeId, Predicate, Triple, TripleId, TriplePattern as GraphPattern, Value,
+};
 use log::{debug, trace};
 
 use crate::error::{Error, Result};
@@ -27,6 +30,10 @@ pub struct RuleEngine {
     mode: InferenceMode,
     /// The maximum depth for inference to prevent infinite loops.
     max_depth: usize,
+    /// Whether per-rule evaluation counts and timings are recorded.
+    profiling: bool,
+    /// How contradictory derivations of different rules are resolved.
+    conflict_strategy: ConflictStrategy,
     /// Statistics tracking various engine operations.
     stats: Arc<RwLock<EngineStats>>,
     /// A cache of triples inferred by the engine.
@@ -38,11 +45,15 @@ impl RuleEngine {
     /// - An empty `RuleSet`.
     /// - `InferenceMode::Forward`.
     /// - A `max_depth` of 100.
+    /// - Rule profiling disabled.
+    /// - `ConflictStrategy::HighestPriorityWins`.
     pub fn new() -> Self {
         Self {
             rules: RuleSet::new("default"),
             mode: InferenceMode::Forward,
             max_depth: 100,
+            profiling: false,
+            conflict_strategy: ConflictStrategy::default(),
             stats: Arc::new(RwLock::new(EngineStats::default())),
             inferred: Arc::new(RwLock::new(Vec::new())),
         }
@@ -58,6 +69,8 @@ impl RuleEngine {
             rules,
             mode: InferenceMode::Forward,
             max_depth: 100,
+            profiling: false,
+            conflict_strategy: ConflictStrategy::default(),
             stats: Arc::new(RwLock::new(EngineStats::default())),
             inferred: Arc::new(RwLock::new(Vec::new())),
         }
@@ -83,6 +96,45 @@ impl RuleEngine {
         self.max_depth = depth;
     }
 
+    /// Enables or disables per-rule profiling.
+    ///
+    /// While enabled, every rule evaluation is timed and counted in
+    /// [`EngineStats::rule_profiles`]. While disabled, rules are not timed.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to record rule profiles.
+    pub fn set_profiling(&mut self, enabled: bool) {
+        self.profiling = enabled;
+    }
+
+    /// Returns `true` if per-rule profiling is enabled.
+    pub fn is_profiling(&self) -> bool {
+        self.profiling
+    }
+
+    /// Sets how contradictory derivations of different rules are resolved.
+    ///
+    /// # Arguments
+    ///
+    /// * `strategy` - The `ConflictStrategy` to apply.
+    pub fn set_conflict_strategy(&mut self, strategy: ConflictStrategy) {
+        self.conflict_strategy = strategy;
+    }
+
+    /// Returns the current `ConflictStrategy`.
+    pub fn conflict_strategy(&self) -> ConflictStrategy {
+        self.conflict_strategy
+    }
+
+    /// Returns warnings about rules that may derive contradictory
+    /// conclusions but that the current `ConflictStrategy` cannot rank.
+    ///
+    /// See [`RuleSet::conflict_warnings`].
+    pub fn conflict_warnings(&self) -> Vec<String> {
+        self.rules.conflict_warnings(self.conflict_strategy)
+    }
+
     /// Adds a single `Rule` to the engine's `RuleSet`.
     ///
     /// # Arguments
@@ -111,7 +163,8 @@ impl RuleEngine {
             .clone()
     }
 
-    /// Resets all collected `EngineStats` to their default (zero) values.
+    /// Resets all collected `EngineStats`, including rule profiles, to their
+    /// default (zero) values.
     pub fn clear_stats(&self) {
         let mut guard = self
             .stats
@@ -140,6 +193,10 @@ impl RuleEngine {
     ///
     /// The validation process involves checking each rule's conditions against the given triple.
     /// Actions such as `Accept`, `Reject`, `Warn`, `Infer`, and `ChainTo` are processed.
+    /// Rules are evaluated by descending priority, then by rule ID. Contradictory
+    /// inferences are resolved by the engine's `ConflictStrategy`; suppressed ones are
+    /// recorded in the result, and under `ConflictStrategy::RejectWithError` the triple
+    /// is rejected and nothing is inferred from it.
     ///
     /// # Arguments
     ///
@@ -157,14 +214,22 @@ impl RuleEngine {
         stats.validations += 1;
 
         let mut result = ValidationResult::new();
-        let mut bindings = Bindings::new();
+        let mut derivations = Vec::new();
 
         // Evaluate rules by priority
         for rule in self.rules.enabled_sorted() {
             stats.rules_evaluated += 1;
             trace!("Evaluating rule: {}", rule.id);
 
-            if rule.matches(triple, &mut bindings) {
+            let started = self.profiling.then(Instant::now);
+            let mut bindings = Bindings::new();
+            let matched = rule.try_matches(triple, &mut bindings).unwrap_or_else(|e| {
+                result.add_warning(&rule.id, &e.to_string());
+                stats.warnings += 1;
+                false
+            });
+            let mut derived = 0;
+            if matched {
                 match &rule.action {
                     Action::Accept => {
                         result.add_match(&rule.id, "accepted");
@@ -177,32 +242,163 @@ impl RuleEngine {
                         result.add_warning(&rule.id, message);
                         stats.warnings += 1;
                     }
-                    Action::Infer(pattern) => {
-                        if let Some(inferred) = pattern.instantiate(&bindings) {
-                            let mut inf = self
-                                .inferred
-                                .write()
-                                .unwrap_or_else(|poisoned| poisoned.into_inner());
-                            inf.push(inferred);
-                            stats.inferences += 1;
+                    Action::Infer(pattern) => match Self::conclude(rule, pattern, &bindings) {
+                        Ok(inferred) => {
+                            derivations.push(Derivation {
+                                rule,
+                                source: triple.id(),
+                                triple: inferred,
+                            });
+                            derived = 1;
                         }
-                    }
+                        Err(e) => {
+                            result.add_warning(&rule.id, &e.to_string());
+                            stats.warnings += 1;
+                        }
+                    },
                     Action::ChainTo(next_rule_id) => {
                         result.add_chain(&rule.id, next_rule_id);
                     }
                 }
-                bindings.clear();
+            }
+
+            if let Some(started) = started {
+                stats.profile_mut(&rule.id).record(
+                    started.elapsed(),
+                    usize::from(matched),
+                    derived,
+                );
             }
         }
 
+        let (kept, suppressed) = self.resolve_conflicts(derivations);
+        if self.conflict_strategy == ConflictStrategy::RejectWithError && !suppressed.is_empty() {
+            let conflict = &suppressed[0];
+            result.reject(&conflict.rule_id, &conflict.to_string());
+            stats.rejections += 1;
+        } else {
+            stats.inferences += kept.len();
+            self.inferred
+                .write()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .extend(kept.into_iter().map(|d| d.triple));
+            result.suppressed = suppressed;
+        }
+
         result
     }
 
+    /// Resolves contradictory derivations according to the `ConflictStrategy`.
+    ///
+    /// Derivations from the same source triple contradict each other when
+    /// different rules derive different objects for the same subject and
+    /// predicate. Within each such group the preferred rule's derivations are
+    /// kept, along with any that agree with them; the rest are suppressed.
+    ///
+    /// `derivations` must be in rule evaluation order.
+    ///
+    /// # Returns
+    ///
+    /// The kept derivations, in their original order, and the suppressed ones.
+    fn resolve_conflicts<'r>(
+        &self,
+        derivations: Vec<Derivation<'r>>,
+    ) -> (Vec<Derivation<'r>>, Vec<SuppressedDerivation>) {
+        let mut groups: HashMap<_, Vec<usize>> = HashMap::new();
+        for (i, d) in derivations.iter().enumerate() {
+            groups
+                .entry((&d.source, &d.triple.subject, &d.triple.predicate))
+                .or_default()
+                .push(i);
+        }
+
+        let mut suppressed_by = HashMap::new();
+        for members in groups.values() {
+            let first = &derivations[members[0]];
+            if members
+                .iter()
+                .all(|&i| derivations[i].rule.id == first.rule.id)
+            {
+                continue;
+            }
+
+            // Members are in evaluation order, so ties go to the earlier rule
+            let mut winner = first.rule;
+            if self.conflict_strategy == ConflictStrategy::MostSpecificWins {
+                for &i in members {
+                    let rule = derivations[i].rule;
+                    if rule.conditions.len() > winner.conditions.len() {
+                        winner = rule;
+                    }
+                }
+            }
+
+            let winning: Vec<_> = members
+                .iter()
+                .map(|&i| &derivations[i])
+                .filter(|d| d.rule.id == winner.id)
+                .map(|d| &d.triple.object)
+                .collect();
+            for &i in members {
+                let d = &derivations[i];
+                if d.rule.id != winner.id && !winning.contains(&&d.triple.object) {
+                    suppressed_by.insert(i, winner.id.clone());
+                }
+            }
+        }
+
+        if suppressed_by.is_empty() {
+            return (derivations, Vec::new());
+        }
+
+        let mut kept = Vec::new();
+        let mut suppressed = Vec::new();
+        for (i, d) in derivations.into_iter().enumerate() {
+            match suppressed_by.remove(&i) {
+                Some(winner) => suppressed.push(SuppressedDerivation {
+                    rule_id: d.rule.id.clone(),
+                    triple: d.triple,
+                    winner,
+                }),
+                None => kept.push(d),
+            }
+        }
+        (kept, suppressed)
+    }
+
+    /// Instantiates the pattern an inference rule concludes with the bindings
+    /// of one match.
+    ///
+    /// A pattern the bindings cannot fill in is a broken rule rather than a
+    /// non-match, so it is reported instead of quietly deriving nothing.
+    fn conclude(rule: &Rule, pattern: &TriplePattern, bindings: &Bindings) -> Result<Triple> {
+        pattern.instantiate(bindings).ok_or_else(|| {
+            let unbound = [&pattern.subject, &pattern.object]
+                .into_iter()
+                .find_map(|p| match p {
+                    Pattern::Variable(var) if !bindings.is_bound(var) => Some(var),
+                    _ => None,
+                });
+            Error::InvalidRule(match unbound {
+                Some(var) => format!(
+                    "rule `{}`: inferred pattern reads ?{}, which no condition binds",
+                    rule.id, var
+                ),
+                None => format!(
+                    "rule `{}`: inferred pattern must name a node or variable as its subject",
+                    rule.id
+                ),
+            })
+        })
+    }
+
     /// Performs forward-chaining inference on a given `GraphDB`.
     ///
     /// This method iteratively applies all `Inference` rules to the facts present in the
     /// graph (and any newly inferred facts) until no new facts can be derived.
     /// This process continues until a fixpoint is reached or the `max_depth` is exceeded.
+    /// Contradictory inferences are resolved by the engine's `ConflictStrategy`, and
+    /// suppressed ones are recorded in the result.
     ///
     /// # Arguments
     ///
@@ -211,7 +407,8 @@ impl RuleEngine {
     /// # Returns
     ///
     /// A `Result` containing a `ForwardChainResult` which includes the number of iterations
-    /// and all new facts inferred, or an `Error` if the process exceeds `max_depth`.
+    /// and all new facts inferred, or an `Error` if the process exceeds `max_depth` or, under
+    /// `ConflictStrategy::RejectWithError`, if two rules derive contradictory facts.
     pub fn forward_chain(&self, graph: &GraphDB) -> Result<ForwardChainResult> {
         let mut stats = self
             .stats
@@ -220,12 +417,12 @@ impl RuleEngine {
         let mut result = ForwardChainResult::new();
         let mut iteration = 0;
 
-        // Get all inference rules
+        // Get all inference rules, in evaluation order
         let inference_rules: Vec<_> = self
             .rules
-            .by_kind(RuleKind::Inference)
+            .enabled_sorted()
             .into_iter()
-            .filter(|r| r.enabled)
+            .filter(|r| r.kind == RuleKind::Inference)
             .collect();
 
         if inference_rules.is_empty() {
@@ -241,29 +438,69 @@ impl RuleEngine {
                 });
             }
 
-            let mut new_facts = Vec::new();
+            let mut derivations = Vec::new();
+            let mut profiles = Vec::new();
             stats.forward_iterations += 1;
 
             // For each inference rule
-            for rule in &inference_rules {
+            for &rule in &inference_rules {
                 stats.rules_evaluated += 1;
 
                 // Find all triples that match the rule's conditions
+                let started = self.profiling.then(Instant::now);
                 let matches = self.find_matching_triples(graph, rule)?;
+                let matched = matches.len();
 
-                for (_triple, bindings) in matches {
+                for (triple, bindings) in matches {
                     if let Action::Infer(pattern) = &rule.action {
-                        if let Some(inferred) = pattern.instantiate(&bindings) {
-                            // Check if this fact already exists
-                            if !graph.contains(&inferred)? && !result.contains(&inferred) {
-                                debug!("Forward chain inferred: {:?}", inferred);
-                                new_facts.push(inferred.clone());
-                                result.add_inference(rule.id.clone(), inferred);
-                                stats.inferences += 1;
-                            }
-                        }
+                        derivations.push(Derivation {
+                            rule,
+                            source: triple.id(),
+                            triple: Self::conclude(rule, pattern, &bindings)?,
+                        });
                     }
                 }
+
+                if let Some(started) = started {
+                    profiles.push((rule, started.elapsed(), matched));
+                }
+            }
+
+            let (kept, suppressed) = self.resolve_conflicts(derivations);
+            if self.conflict_strategy == ConflictStrategy::RejectWithError {
+                if let Some(conflict) = suppressed.first() {
+                    return Err(Error::RuleConflict(conflict.to_string()));
+                }
+            }
+            for conflict in suppressed {
+                let id = conflict.triple.id();
+                if !result
+                    .suppressed
+                    .iter()
+                    .any(|s| s.rule_id == conflict.rule_id && s.triple.id() == id)
+                {
+                    result.suppressed.push(conflict);
+                }
+            }
+
+            let mut new_facts = Vec::new();
+            let mut derived: HashMap<&str, usize> = HashMap::new();
+            for Derivation { rule, triple, .. } in kept {
+                // Check if this fact already exists
+                if !graph.contains(&triple)? && !result.contains(&triple) {
+                    debug!("Forward chain inferred: {:?}", triple);
+                    new_facts.push(triple.clone());
+                    result.add_inference(rule.id.clone(), triple);
+                    stats.inferences += 1;
+                    *derived.entry(&rule.id).or_default() += 1;
+                }
+            }
+
+            for (rule, elapsed, matched) in profiles {
+                let derived = derived.get(rule.id.as_str()).copied().unwrap_or(0);
+                stats
+                    .profile_mut(&rule.id)
+                    .record(elapsed, matched, derived);
             }
 
             if new_facts.is_empty() {
@@ -382,12 +619,12 @@ impl RuleEngine {
             return Ok(true);
         }
 
-        // Try to prove using inference rules
+        // Try to prove using inference rules, in evaluation order
         let inference_rules: Vec<_> = self
             .rules
-            .by_kind(RuleKind::Inference)
+            .enabled_sorted()
             .into_iter()
-            .filter(|r| r.enabled)
+            .filter(|r| r.kind == RuleKind::Inference)
             .collect();
 
         for rule in inference_rules {
@@ -397,19 +634,22 @@ impl RuleEngine {
                     // Try to prove all conditions
                     let mut all_conditions_proved = true;
 
-                    for condition in &rule.conditions {
-                        if let Condition::Exists(pattern) = condition {
-                            if !self.prove_goal(
+                    for condition in rule.evaluation_order()? {
+                        let proved = match condition {
+                            Condition::Exists(pattern) => self.prove_goal(
                                 graph,
                                 pattern,
                                 bindings,
                                 depth + 1,
                                 visited,
                                 result,
-                            )? {
-                                all_conditions_proved = false;
-                                break;
-                            }
+                            )?,
+                            Condition::Builtin(builtin) => builtin.evaluate(bindings),
+                            _ => true,
+                        };
+                        if !proved {
+                            all_conditions_proved = false;
+                            break;
                         }
                     }
 
@@ -534,61 +774,49 @@ impl RuleEngine {
         rule: &Rule,
     ) -> Result<Vec<(Triple, Bindings)>> {
         let mut results = Vec::new();
+        let conditions = rule.evaluation_order()?;
 
         // For now, we need to iterate all triples and check conditions
         // This could be optimized with index lookups
         let all_triples = graph.find(GraphPattern::any())?;
 
         for triple in all_triples {
-            let mut bindings = Bindings::new();
-            let mut matches = true;
-
-            for condition in &rule.conditions {
-                match condition {
-                    Condition::PredicateEquals(pred) => {
-                        if triple.predicate.as_str() != pred {
-                            matches = false;
-                            break;
-                        }
-                    }
-                    Condition::SubjectMatches(pattern) => {
-                        if !pattern.matches_node(&triple.subject, &mut bindings) {
-                            matches = false;
-                            break;
-                        }
-                    }
-                    Condition::ObjectMatches(pattern) => {
-                        if !pattern.matches_value(&triple.object, &mut bindings) {
-                            matches = false;
-                            break;
-                        }
-                    }
-                    Condition::Exists(pattern) => {
-                        let gp = self.triple_pattern_to_graph_pattern(pattern, &bindings);
-                        if graph.find(gp)?.is_empty() {
-                            matches = false;
-                            break;
+            // `Exists` may match several triples, each extending the bindings
+            let mut solutions = vec![Bindings::new()];
+
+            for condition in &conditions {
+                let mut next = Vec::new();
+                for mut bindings in solutions {
+                    match condition {
+                        Condition::Exists(pattern) => {
+                            let gp = self.triple_pattern_to_graph_pattern(pattern, &bindings);
+                            for found in graph.find(gp)? {
+                                let mut extended = bindings.clone();
+                                if pattern.matches(&found, &mut extended) {
+                                    next.push(extended);
+                                }
+                            }
                         }
-                    }
-                    Condition::NotExists(pattern) => {
-                        let gp = self.triple_pattern_to_graph_pattern(pattern, &bindings);
-                        if !graph.find(gp)?.is_empty() {
-                            matches = false;
-                            break;
+                        Condition::NotExists(pattern) => {
+                            let gp = self.triple_pattern_to_graph_pattern(pattern, &bindings);
+                            if graph.find(gp)?.is_empty() {
+                                next.push(bindings);
+                            }
                         }
-                    }
-                    Condition::Custom(f) => {
-                        if !f(&triple) {
-                            matches = false;
-                            break;
+                        _ => {
+                            if condition.matches(&triple, &mut bindings) {
+                                next.push(bindings);
+                            }
                         }
                     }
                 }
+                solutions = next;
+                if solutions.is_empty() {
+                    break;
+                }
             }
 
-            if matches {
-                results.push((triple, bindings));
-            }
+            results.extend(solutions.into_iter().map(|b| (triple.clone(), b)));
         }
 
         Ok(results)
@@ -614,7 +842,10 @@ impl RuleEngine {
     ) -> GraphPattern {
         let subject = match &pattern.subject {
             Pattern::Node(id) => Some(NodeId::named(id)),
-            Pattern::Variable(var) => bindings.get(var).map(NodeId::named),
+            Pattern::Variable(var) => match bindings.value(var) {
+                Some(Value::Node(node)) => Some(node.clone()),
+                _ => bindings.get(var).map(NodeId::named),
+            },
             _ => None,
         };
 
@@ -623,12 +854,14 @@ impl RuleEngine {
         let object = match &pattern.object {
             Pattern::Node(id) => Some(Value::Node(NodeId::named(id))),
             Pattern::Literal(lit) => Some(Value::literal(lit.clone())),
-            Pattern::Variable(var) => bindings.get(var).map(|v| {
-                if v.contains(':') {
-                    Value::Node(NodeId::named(v))
-                } else {
-                    Value::literal(v.clone())
-                }
+            Pattern::Variable(var) => bindings.value(var).cloned().or_else(|| {
+                bindings.get(var).map(|v| {
+                    if v.contains(':') {
+                        Value::Node(NodeId::named(v))
+                    } else {
+                        Value::literal(v.clone())
+                    }
+                })
             }),
             _ => None,
         };
@@ -666,6 +899,24 @@ pub enum InferenceMode {
     Hybrid,
 }
 
+/// Specifies how the `RuleEngine` resolves contradictory derivations.
+///
+/// Two derivations contradict each other when different rules, fired by the
+/// same triple, derive different objects for the same subject and predicate
+/// (e.g. `status` of `approved` and of `rejected`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConflictStrategy {
+    /// The rule with the highest priority wins; ties go to the lower rule ID.
+    #[default]
+    HighestPriorityWins,
+    /// The rule with the most conditions wins; ties are broken as in
+    /// `HighestPriorityWins`.
+    MostSpecificWins,
+    /// Neither derivation is kept: validation rejects the triple and forward
+    /// chaining fails with `Error::RuleConflict`.
+    RejectWithError,
+}
+
 /// Collects and stores statistics about the operations performed by the `RuleEngine`.
 #[derive(Debug, Clone, Default)]
 pub struct EngineStats {
@@ -683,6 +934,72 @@ pub struct EngineStats {
     pub forward_iterations: usize,
     /// The number of backward-chaining queries performed.
     pub backward_queries: usize,
+    /// Per-rule profiles keyed by rule ID, recorded while profiling is enabled.
+    pub rule_profiles: HashMap<String, RuleProfile>,
+}
+
+impl EngineStats {
+    /// Returns up to `n` rules with the highest cumulative evaluation time,
+    /// slowest first.
+    pub fn top_rules_by_time(&self, n: usize) -> Vec<(&str, &RuleProfile)> {
+        let mut rules: Vec<_> = self
+            .rule_profiles
+            .iter()
+            .map(|(id, profile)| (id.as_str(), profile))
+            .collect();
+        rules.sort_by(|a, b| b.1.total_time.cmp(&a.1.total_time).then(a.0.cmp(b.0)));
+        rules.truncate(n);
+        rules
+    }
+
+    /// Returns the profile of a rule, creating an empty one if needed.
+    fn profile_mut(&mut self, rule_id: &str) -> &mut RuleProfile {
+        if !self.rule_profiles.contains_key(rule_id) {
+            self.rule_profiles
+                .insert(rule_id.to_string(), RuleProfile::default());
+        }
+        self.rule_profiles
+            .get_mut(rule_id)
+            .expect("profile inserted above")
+    }
+}
+
+/// Evaluation counts and timings of a single rule.
+///
+/// During validation a rule is evaluated once per triple; during forward
+/// chaining, once per iteration against the whole graph.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuleProfile {
+    /// The number of times the rule was evaluated.
+    pub evaluations: usize,
+    /// The number of triples the rule matched.
+    pub matches: usize,
+    /// The total time spent evaluating the rule.
+    pub total_time: Duration,
+    /// The longest single evaluation.
+    pub max_time: Duration,
+    /// The number of triples the rule derived.
+    pub derived: usize,
+}
+
+impl RuleProfile {
+    /// Returns the mean time of one evaluation.
+    pub fn mean_time(&self) -> Duration {
+        match u32::try_from(self.evaluations) {
+            Ok(0) => Duration::ZERO,
+            Ok(n) => self.total_time / n,
+            Err(_) => self.total_time.div_f64(self.evaluations as f64),
+        }
+    }
+
+    /// Records one evaluation.
+    fn record(&mut self, elapsed: Duration, matches: usize, derived: usize) {
+        self.evaluations += 1;
+        self.matches += matches;
+        self.total_time += elapsed;
+        self.max_time = self.max_time.max(elapsed);
+        self.derived += derived;
+    }
 }
 
 /// Represents the outcome of a validation operation performed by the `RuleEngine`.
@@ -698,6 +1015,8 @@ pub struct ValidationResult {
     pub warnings: Vec<RuleWarning>,
     /// A list of rule chains that were triggered, indicating one rule leading to another.
     pub chains: Vec<(String, String)>,
+    /// Inferences dropped in favor of a contradictory one from a preferred rule.
+    pub suppressed: Vec<SuppressedDerivation>,
 }
 
 impl ValidationResult {
@@ -709,6 +1028,7 @@ impl ValidationResult {
             rejections: Vec::new(),
             warnings: Vec::new(),
             chains: Vec::new(),
+            suppressed: Vec::new(),
         }
     }
 
@@ -776,6 +1096,34 @@ pub struct RuleWarning {
     pub message: String,
 }
 
+/// An inference dropped because a preferred rule derived a contradictory one.
+#[derive(Debug, Clone)]
+pub struct SuppressedDerivation {
+    /// The ID of the rule whose inference was dropped.
+    pub rule_id: String,
+    /// The dropped inference.
+    pub triple: Triple,
+    /// The ID of the rule whose contradictory inference was preferred.
+    pub winner: String,
+}
+
+impl std::fmt::Display for SuppressedDerivation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "rule `{}` inferred ({}), contradicting rule `{}`",
+            self.rule_id, self.triple, self.winner
+        )
+    }
+}
+
+/// A triple inferred by a rule from a source triple, before conflict resolution.
+struct Derivation<'r> {
+    rule: &'r Rule,
+    source: TripleId,
+    triple: Triple,
+}
+
 /// The result of a forward-chaining inference run by the `RuleEngine`.
 #[derive(Debug, Clone, Default)]
 pub struct ForwardChainResult {
@@ -783,6 +1131,8 @@ pub struct ForwardChainResult {
     pub iterations: usize,
     /// A list of all triples inferred, paired with the ID of the rule that produced them.
     pub inferences: Vec<(String, Triple)>,
+    /// Inferences dropped in favor of a contradictory one from a preferred rule.
+    pub suppressed: Vec<SuppressedDerivation>,
 }
 
 impl ForwardChainResult {
@@ -883,6 +1233,7 @@ impl BackwardChainResult {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::functions::{Builtin, Term};
 
     #[test]
     fn test_engine_creation() {
@@ -962,6 +1313,383 @@ mod tests {
         assert_eq!(result.count(), 0);
     }
 
+    #[test]
+    fn test_rule_profiling() {
+        let mut engine = RuleEngine::new();
+        engine.add_rule(Rule::integrity("cheap").accept().build());
+        engine.add_rule(
+            Rule::integrity("expensive")
+                .when(|_| {
+                    std::thread::sleep(Duration::from_millis(2));
+                    true
+                })
+                .warn("slow check")
+                .build(),
+        );
+        engine.add_rule(
+            Rule::inference("derive")
+                .when_predicate("p")
+                .infer(TriplePattern::new(
+                    Pattern::Node("x".into()),
+                    "q",
+                    Pattern::Literal("y".into()),
+                ))
+                .build(),
+        );
+        engine.add_rule(
+            Rule::integrity("never")
+                .when_predicate("other")
+                .reject("unused")
+                .build(),
+        );
+
+        let triple = Triple::new(
+            NodeId::named("a"),
+            Predicate::named("p"),
+            Value::literal("b"),
+        );
+
+        // Nothing is recorded while profiling is disabled
+        engine.validate(&triple);
+        assert!(engine.stats().rule_profiles.is_empty());
+
+        engine.set_profiling(true);
+        for _ in 0..3 {
+            engine.validate(&triple);
+        }
+
+        let stats = engine.stats();
+        let top = stats.top_rules_by_time(2);
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].0, "expensive");
+        assert!(top[0].1.max_time >= Duration::from_millis(2));
+        assert!(top[0].1.total_time >= Duration::from_millis(6));
+        assert!(top[0].1.mean_time() >= Duration::from_millis(2));
+
+        let profiles = &stats.rule_profiles;
+        assert_eq!(profiles.len(), 4);
+        assert!(profiles.values().all(|p| p.evaluations == 3));
+        assert_eq!(profiles["cheap"].matches, 3);
+        assert_eq!(profiles["never"].matches, 0);
+        assert_eq!(profiles["derive"].derived, 3);
+        assert_eq!(profiles["expensive"].derived, 0);
+
+        engine.clear_stats();
+        assert!(engine.stats().rule_profiles.is_empty());
+    }
+
+    #[test]
+    fn test_forward_chain_profiling() {
+        let mut engine = RuleEngine::new();
+        engine.set_profiling(true);
+        engine.add_rule(
+            Rule::inference("mortal")
+                .when_subject(Pattern::Variable("x".into()))
+                .when_predicate("is_a")
+                .when_object(Pattern::Node("human".into()))
+                .infer(TriplePattern::new(
+                    Pattern::Variable("x".into()),
+                    "is_a",
+                    Pattern::Node("mortal".into()),
+                ))
+                .build(),
+        );
+
+        let graph = GraphDB::memory().unwrap();
+        for name in ["socrates", "plato"] {
+            graph
+                .insert(Triple::new(
+                    NodeId::named(name),
+                    Predicate::named("is_a"),
+                    Value::Node(NodeId::named("human")),
+                ))
+                .unwrap();
+        }
+
+        let result = engine.forward_chain(&graph).unwrap();
+        let profile = &engine.stats().rule_profiles["mortal"];
+        assert_eq!(profile.evaluations, result.iterations);
+        assert_eq!(profile.derived, result.count());
+        assert!(profile.matches >= profile.derived);
+    }
+
+    #[test]
+    fn test_threshold_rule_across_two_triples() {
+        let mut engine = RuleEngine::new();
+        // Built-ins come first here; they are delayed until the patterns bind ?v and ?t
+        engine.add_rule(
+            Rule::inference("over_threshold")
+                .when_builtin(Builtin::Sub(
+                    Term::var("v"),
+                    Term::var("t"),
+                    "excess".into(),
+                ))
+                .when_builtin(Builtin::GreaterThan(Term::var("v"), Term::var("t")))
+                .when_predicate("reading")
+                .when_subject(Pattern::Variable("s".into()))
+                .when_object(Pattern::Variable("v".into()))
+                .when_exists(TriplePattern::new(
+                    Pattern::Variable("s".into()),
+                    "threshold",
+                    Pattern::Variable("t".into()),
+                ))
+                .infer(TriplePattern::new(
+                    Pattern::Variable("s".into()),
+                    "exceeds_by",
+                    Pattern::Variable("excess".into()),
+                ))
+                .build(),
+        );
+
+        let graph = GraphDB::memory().unwrap();
+        let sensors = [
+            ("sensor:hot", Value::Integer(42)),
+            ("sensor:cold", Value::Integer(10)),
+            ("sensor:broken", Value::literal("high")),
+        ];
+        for (sensor, reading) in sensors {
+            let subject = NodeId::named(sensor);
+            graph
+                .insert(Triple::new(
+                    subject.clone(),
+                    Predicate::named("reading"),
+                    reading,
+                ))
+                .unwrap();
+            graph
+                .insert(Triple::new(
+                    subject,
+                    Predicate::named("threshold"),
+                    Value::Integer(40),
+                ))
+                .unwrap();
+        }
+
+        let result = engine.forward_chain(&graph).unwrap();
+        let inferred: Vec<_> = result.inferences.iter().map(|(_, t)| t.id()).collect();
+        let expected = Triple::new(
+            NodeId::named("sensor:hot"),
+            Predicate::named("exceeds_by"),
+            Value::Integer(2),
+        );
+        assert_eq!(inferred, vec![expected.id()]);
+    }
+
+    #[test]
+    fn test_builtin_with_unbound_input() {
+        let rule = Rule::inference("needs_limit")
+            .when_predicate("reading")
+            .when_object(Pattern::Variable("v".into()))
+            .when_builtin(Builtin::GreaterThan(Term::var("v"), Term::var("limit")))
+            .infer(TriplePattern::new(
+                Pattern::Node("alarm".into()),
+                "raised",
+                Pattern::Literal("yes".into()),
+            ))
+            .build();
+        let err = rule.evaluation_order().unwrap_err();
+        assert!(matches!(err, Error::InvalidRule(_)));
+        assert!(err.to_string().contains("?limit"), "{}", err);
+
+        let mut engine = RuleEngine::new();
+        engine.add_rule(rule);
+
+        let graph = GraphDB::memory().unwrap();
+        let reading = Triple::new(
+            NodeId::named("sensor:1"),
+            Predicate::named("reading"),
+            Value::Integer(5),
+        );
+        graph.insert(reading.clone()).unwrap();
+        assert!(matches!(
+            engine.forward_chain(&graph),
+            Err(Error::InvalidRule(_))
+        ));
+
+        let result = engine.validate(&reading);
+        assert!(result.is_valid());
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].message.contains("?limit"));
+    }
+
+    #[test]
+    fn test_inferred_pattern_with_unbound_variable() {
+        let mut engine = RuleEngine::new();
+        engine.add_rule(
+            Rule::inference("inverse_parent_child")
+                .when_predicate("parent_of")
+                .infer(TriplePattern::new(
+                    Pattern::Variable("o".into()),
+                    "child_of",
+                    Pattern::Variable("s".into()),
+                ))
+                .build(),
+        );
+
+        let graph = GraphDB::memory().unwrap();
+        let fact = Triple::new(
+            NodeId::named("alice"),
+            Predicate::named("parent_of"),
+            Value::Node(NodeId::named("bob")),
+        );
+        graph.insert(fact.clone()).unwrap();
+        let err = engine.forward_chain(&graph).unwrap_err();
+        assert!(matches!(err, Error::InvalidRule(_)));
+        assert!(err.to_string().contains("?o"), "{}", err);
+
+        let result = engine.validate(&fact);
+        assert!(result.is_valid());
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].message.contains("?o"));
+        assert!(engine.inferred_triples().is_empty());
+    }
+
+    /// An order reviewed as flagged, and two rules that infer contradictory
+    /// statuses for it: `approve` has the higher priority, `reject_flagged`
+    /// the more conditions.
+    fn status_conflict(strategy: ConflictStrategy) -> (RuleEngine, Triple) {
+        let mut engine = RuleEngine::new();
+        engine.set_conflict_strategy(strategy);
+        let status = |value: &str| {
+            TriplePattern::new(
+                Pattern::Variable("x".into()),
+                "status",
+                Pattern::Literal(value.into()),
+            )
+        };
+        engine.add_rule(
+            Rule::inference("reject_flagged")
+                .when_subject(Pattern::Variable("x".into()))
+                .when_predicate("review")
+                .when_object(Pattern::Literal("flagged".into()))
+                .infer(status("rejected"))
+                .priority(5)
+                .build(),
+        );
+        engine.add_rule(
+            Rule::inference("approve")
+                .when_subject(Pattern::Variable("x".into()))
+                .when_predicate("review")
+                .infer(status("approved"))
+                .priority(10)
+                .build(),
+        );
+
+        let triple = Triple::new(
+            NodeId::named("order:1"),
+            Predicate::named("review"),
+            Value::literal("flagged"),
+        );
+        (engine, triple)
+    }
+
+    fn status_of(triple: &Triple) -> Value {
+        assert_eq!(triple.predicate.as_str(), "status");
+        triple.object.clone()
+    }
+
+    #[test]
+    fn test_validate_conflict_strategies() {
+        let (engine, triple) = status_conflict(ConflictStrategy::HighestPriorityWins);
+        let result = engine.validate(&triple);
+        assert!(result.is_valid());
+        let inferred = engine.inferred_triples();
+        assert_eq!(inferred.len(), 1);
+        assert_eq!(status_of(&inferred[0]), Value::literal("approved"));
+        assert_eq!(result.suppressed.len(), 1);
+        assert_eq!(result.suppressed[0].rule_id, "reject_flagged");
+        assert_eq!(result.suppressed[0].winner, "approve");
+        assert_eq!(
+            status_of(&result.suppressed[0].triple),
+            Value::literal("rejected")
+        );
+
+        let (engine, triple) = status_conflict(ConflictStrategy::MostSpecificWins);
+        let result = engine.validate(&triple);
+        assert!(result.is_valid());
+        let inferred = engine.inferred_triples();
+        assert_eq!(inferred.len(), 1);
+        assert_eq!(status_of(&inferred[0]), Value::literal("rejected"));
+        assert_eq!(result.suppressed[0].rule_id, "approve");
+        assert_eq!(result.suppressed[0].winner, "reject_flagged");
+
+        let (engine, triple) = status_conflict(ConflictStrategy::RejectWithError);
+        let result = engine.validate(&triple);
+        assert!(!result.is_valid());
+        assert_eq!(result.rejections[0].rule_id, "reject_flagged");
+        assert!(result.rejections[0].reason.contains("`approve`"));
+        assert!(engine.inferred_triples().is_empty());
+        assert_eq!(engine.stats().inferences, 0);
+
+        // Without a conflict, both strategies agree
+        let other = Triple::new(
+            NodeId::named("order:2"),
+            Predicate::named("review"),
+            Value::literal("clean"),
+        );
+        let result = engine.validate(&other);
+        assert!(result.is_valid());
+        assert!(result.suppressed.is_empty());
+        assert_eq!(engine.inferred_triples().len(), 1);
+    }
+
+    #[test]
+    fn test_equal_priority_conflict_goes_to_lower_rule_id() {
+        let (mut engine, triple) = status_conflict(ConflictStrategy::HighestPriorityWins);
+        engine.rules = RuleSet {
+            rules: engine
+                .rules
+                .rules
+                .into_iter()
+                .map(|rule| Rule {
+                    priority: 0,
+                    ..rule
+                })
+                .collect(),
+            ..RuleSet::default()
+        };
+        assert_eq!(engine.conflict_warnings().len(), 1);
+
+        for _ in 0..3 {
+            engine.clear_inferred();
+            let result = engine.validate(&triple);
+            assert_eq!(result.suppressed[0].winner, "approve");
+            assert_eq!(
+                status_of(&engine.inferred_triples()[0]),
+                Value::literal("approved")
+            );
+        }
+    }
+
+    #[test]
+    fn test_forward_chain_conflict_strategies() {
+        let graph = GraphDB::memory().unwrap();
+        let (engine, triple) = status_conflict(ConflictStrategy::HighestPriorityWins);
+        graph.insert(triple).unwrap();
+
+        let result = engine.forward_chain(&graph).unwrap();
+        assert_eq!(result.count(), 1);
+        assert_eq!(result.inferences[0].0, "approve");
+        assert_eq!(
+            status_of(&result.inferences[0].1),
+            Value::literal("approved")
+        );
+        // Recorded once, though every iteration suppresses it again
+        assert_eq!(result.suppressed.len(), 1);
+        assert_eq!(result.suppressed[0].rule_id, "reject_flagged");
+
+        let (engine, _) = status_conflict(ConflictStrategy::MostSpecificWins);
+        let result = engine.forward_chain(&graph).unwrap();
+        assert_eq!(result.count(), 1);
+        assert_eq!(result.inferences[0].0, "reject_flagged");
+        assert_eq!(result.suppressed[0].rule_id, "approve");
+
+        let (engine, _) = status_conflict(ConflictStrategy::RejectWithError);
+        let err = engine.forward_chain(&graph).unwrap_err();
+        assert!(matches!(err, Error::RuleConflict(_)));
+        assert!(engine.inferred_triples().is_empty());
+    }
+
     #[test]
     fn test_inference_mode() {
         let mut engine = RuleEngine::new();
@@ -5,6 +5,8 @@
 
 use thiserror::Error;
 
+use crate::validator::ValidationResult;
+
 /// A specialized `Result` type for logic engine operations.
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -54,6 +56,27 @@ pub enum Error {
     /// An error occurred during data serialization or deserialization.
     #[error("Serialization error: {0}")]
     SerializationError(String),
+
+    /// A rule test fixture could not be read or parsed.
+    #[error("Invalid test fixture: {0}")]
+    InvalidFixture(String),
+
+    /// A triple was refused by validation; holds the full result.
+    #[error("Triple rejected: {}", describe_rejection(.0))]
+    Rejected(Box<ValidationResult>),
+}
+
+/// Joins the error messages of a rejected triple's validation result.
+fn describe_rejection(result: &ValidationResult) -> String {
+    if result.errors.is_empty() {
+        return "validation failed".to_string();
+    }
+    result
+        .errors
+        .iter()
+        .map(|e| e.message.as_str())
+        .collect::<Vec<_>>()
+        .join("; ")
 }
 
 impl From<aingle_graph::Error> for Error {
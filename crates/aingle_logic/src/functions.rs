@@ -0,0 +1,374 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Built-in predicates for rule conditions
+//!
+//! Patterns can only match the shape of a triple. Built-ins compare and
+//! compute over the values that patterns have bound:
+//!
+//! - Comparisons: [`Builtin::GreaterThan`], [`Builtin::LessThan`] and
+//!   [`Builtin::Equal`] over numbers
+//! - Arithmetic: [`Builtin::Add`], [`Builtin::Sub`] and [`Builtin::Mul`],
+//!   which bind their result to a variable
+//! - Strings: [`Builtin::Concat`] and [`Builtin::StrStarts`]
+//!
+//! A built-in whose operands have the wrong type (a node where a number is
+//! expected, say) or overflow simply does not match. A built-in must not read
+//! a variable that nothing binds; [`Rule::evaluation_order`] reports that.
+//!
+//! [`Rule::evaluation_order`]: crate::Rule::evaluation_order
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use aingle_graph::Value;
+use serde::{Deserialize, Serialize};
+
+use crate::rule::Bindings;
+
+/// An operand of a built-in predicate
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Term {
+    /// The value bound to a variable
+    Variable(String),
+    /// An integer constant
+    Integer(i64),
+    /// A floating-point constant
+    Float(f64),
+    /// A string constant
+    String(String),
+}
+
+impl Term {
+    /// Creates a variable operand
+    pub fn var(name: impl Into<String>) -> Self {
+        Self::Variable(name.into())
+    }
+
+    /// Creates a string operand
+    pub fn string(s: impl Into<String>) -> Self {
+        Self::String(s.into())
+    }
+
+    /// The variable this operand reads, if any
+    pub fn variable(&self) -> Option<&str> {
+        match self {
+            Self::Variable(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    fn resolve(&self, bindings: &Bindings) -> Option<Value> {
+        match self {
+            Self::Variable(name) => bindings
+                .value(name)
+                .cloned()
+                .or_else(|| bindings.get(name).cloned().map(Value::String)),
+            Self::Integer(i) => Some(Value::Integer(*i)),
+            Self::Float(f) => Some(Value::Float(*f)),
+            Self::String(s) => Some(Value::String(s.clone())),
+        }
+    }
+}
+
+impl From<i64> for Term {
+    fn from(i: i64) -> Self {
+        Self::Integer(i)
+    }
+}
+
+impl From<f64> for Term {
+    fn from(f: f64) -> Self {
+        Self::Float(f)
+    }
+}
+
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Variable(name) => write!(f, "?{}", name),
+            Self::Integer(i) => write!(f, "{}", i),
+            Self::Float(x) => write!(f, "{:?}", x),
+            Self::String(s) => write!(f, "{:?}", s),
+        }
+    }
+}
+
+/// A built-in predicate over bound values
+///
+/// The last field of `Add`, `Sub`, `Mul` and `Concat` names the variable that
+/// receives the result. If that variable is already bound, the built-in
+/// matches only when the result equals it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Builtin {
+    /// The first number is greater than the second
+    GreaterThan(Term, Term),
+    /// The first number is less than the second
+    LessThan(Term, Term),
+    /// The two numbers are equal
+    Equal(Term, Term),
+    /// Binds the sum of two numbers
+    Add(Term, Term, String),
+    /// Binds the difference of two numbers
+    Sub(Term, Term, String),
+    /// Binds the product of two numbers
+    Mul(Term, Term, String),
+    /// Binds the concatenation of two strings
+    Concat(Term, Term, String),
+    /// The first string starts with the second
+    StrStarts(Term, Term),
+}
+
+impl Builtin {
+    /// Name of the built-in, as used in the triple encoding
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::GreaterThan(..) => "greater_than",
+            Self::LessThan(..) => "less_than",
+            Self::Equal(..) => "equal",
+            Self::Add(..) => "add",
+            Self::Sub(..) => "sub",
+            Self::Mul(..) => "mul",
+            Self::Concat(..) => "concat",
+            Self::StrStarts(..) => "str_starts",
+        }
+    }
+
+    /// Creates the built-in called `name`
+    ///
+    /// `output` is required by the built-ins that bind a result and must be
+    /// absent for the others.
+    pub fn from_parts(name: &str, a: Term, b: Term, output: Option<String>) -> Option<Self> {
+        Some(match (name, output) {
+            ("greater_than", None) => Self::GreaterThan(a, b),
+            ("less_than", None) => Self::LessThan(a, b),
+            ("equal", None) => Self::Equal(a, b),
+            ("str_starts", None) => Self::StrStarts(a, b),
+            ("add", Some(out)) => Self::Add(a, b, out),
+            ("sub", Some(out)) => Self::Sub(a, b, out),
+            ("mul", Some(out)) => Self::Mul(a, b, out),
+            ("concat", Some(out)) => Self::Concat(a, b, out),
+            _ => return None,
+        })
+    }
+
+    /// The two operands
+    pub fn operands(&self) -> (&Term, &Term) {
+        match self {
+            Self::GreaterThan(a, b)
+            | Self::LessThan(a, b)
+            | Self::Equal(a, b)
+            | Self::StrStarts(a, b)
+            | Self::Add(a, b, _)
+            | Self::Sub(a, b, _)
+            | Self::Mul(a, b, _)
+            | Self::Concat(a, b, _) => (a, b),
+        }
+    }
+
+    /// The variable that receives the result, if any
+    pub fn output(&self) -> Option<&str> {
+        match self {
+            Self::Add(_, _, out)
+            | Self::Sub(_, _, out)
+            | Self::Mul(_, _, out)
+            | Self::Concat(_, _, out) => Some(out),
+            _ => None,
+        }
+    }
+
+    /// Variables that must be bound before the built-in can run
+    pub fn inputs(&self) -> impl Iterator<Item = &str> {
+        let (a, b) = self.operands();
+        a.variable().into_iter().chain(b.variable())
+    }
+
+    /// Evaluates the built-in, binding its result if it has one
+    ///
+    /// Returns `false` if an input is unbound, an operand has the wrong type,
+    /// or the arithmetic overflows.
+    pub fn evaluate(&self, bindings: &mut Bindings) -> bool {
+        let (a, b) = self.operands();
+        let (Some(a), Some(b)) = (a.resolve(bindings), b.resolve(bindings)) else {
+            return false;
+        };
+
+        let result = match self {
+            Self::GreaterThan(..) => return compare(&a, &b) == Some(Ordering::Greater),
+            Self::LessThan(..) => return compare(&a, &b) == Some(Ordering::Less),
+            Self::Equal(..) => return compare(&a, &b) == Some(Ordering::Equal),
+            Self::StrStarts(..) => {
+                return matches!((text(&a), text(&b)), (Some(a), Some(b)) if a.starts_with(b))
+            }
+            Self::Add(..) => arithmetic(&a, &b, i64::checked_add, |x, y| x + y),
+            Self::Sub(..) => arithmetic(&a, &b, i64::checked_sub, |x, y| x - y),
+            Self::Mul(..) => arithmetic(&a, &b, i64::checked_mul, |x, y| x * y),
+            Self::Concat(..) => match (text(&a), text(&b)) {
+                (Some(a), Some(b)) => Some(Value::String(format!("{}{}", a, b))),
+                _ => None,
+            },
+        };
+
+        match (result, self.output()) {
+            (Some(result), Some(out)) => unify(out, result, bindings),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Builtin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (a, b) = self.operands();
+        write!(f, "{}({}, {})", self.name(), a, b)?;
+        if let Some(out) = self.output() {
+            write!(f, " -> ?{}", out)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Number {
+    Int(i64),
+    Float(f64),
+}
+
+impl Number {
+    fn of(value: &Value) -> Option<Self> {
+        match value {
+            Value::Integer(i) => Some(Self::Int(*i)),
+            Value::Float(f) => Some(Self::Float(*f)),
+            _ => None,
+        }
+    }
+
+    fn as_f64(self) -> f64 {
+        match self {
+            Self::Int(i) => i as f64,
+            Self::Float(f) => f,
+        }
+    }
+}
+
+fn compare(a: &Value, b: &Value) -> Option<Ordering> {
+    match (Number::of(a)?, Number::of(b)?) {
+        (Number::Int(x), Number::Int(y)) => Some(x.cmp(&y)),
+        (x, y) => x.as_f64().partial_cmp(&y.as_f64()),
+    }
+}
+
+fn arithmetic(
+    a: &Value,
+    b: &Value,
+    int: fn(i64, i64) -> Option<i64>,
+    float: fn(f64, f64) -> f64,
+) -> Option<Value> {
+    match (Number::of(a)?, Number::of(b)?) {
+        (Number::Int(x), Number::Int(y)) => int(x, y).map(Value::Integer),
+        (x, y) => Some(float(x.as_f64(), y.as_f64()))
+            .filter(|r| r.is_finite())
+            .map(Value::Float),
+    }
+}
+
+fn text(value: &Value) -> Option<&str> {
+    match value {
+        Value::String(s) | Value::LangString { value: s, .. } => Some(s),
+        _ => None,
+    }
+}
+
+/// Bind `var` to `value`, or check it against the value already bound
+fn unify(var: &str, value: Value, bindings: &mut Bindings) -> bool {
+    match Term::var(var).resolve(bindings) {
+        Some(bound) if Number::of(&bound).is_some() && Number::of(&value).is_some() => {
+            compare(&bound, &value) == Some(Ordering::Equal)
+        }
+        Some(bound) => bound == value,
+        None => {
+            bindings.bind_value(var.to_string(), value);
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aingle_graph::NodeId;
+
+    fn bindings(values: &[(&str, Value)]) -> Bindings {
+        let mut bindings = Bindings::new();
+        for (var, value) in values {
+            bindings.bind_value(var.to_string(), value.clone());
+        }
+        bindings
+    }
+
+    #[test]
+    fn test_numeric_comparisons() {
+        let mut b = bindings(&[("v", Value::Integer(42)), ("t", Value::Float(40.5))]);
+
+        assert!(Builtin::GreaterThan(Term::var("v"), Term::var("t")).evaluate(&mut b));
+        assert!(!Builtin::LessThan(Term::var("v"), Term::var("t")).evaluate(&mut b));
+        assert!(Builtin::Equal(Term::var("v"), Term::Float(42.0)).evaluate(&mut b));
+        assert!(Builtin::LessThan(Term::Integer(-1), Term::var("v")).evaluate(&mut b));
+    }
+
+    #[test]
+    fn test_arithmetic_binds_result() {
+        let mut b = bindings(&[("a", Value::Integer(6)), ("b", Value::Integer(7))]);
+
+        assert!(Builtin::Mul(Term::var("a"), Term::var("b"), "p".into()).evaluate(&mut b));
+        assert_eq!(b.value("p"), Some(&Value::Integer(42)));
+        assert_eq!(b.get("p").map(String::as_str), Some("42"));
+
+        assert!(Builtin::Sub(Term::var("p"), 2.5.into(), "q".into()).evaluate(&mut b));
+        assert_eq!(b.value("q"), Some(&Value::Float(39.5)));
+
+        // A bound result is checked, not overwritten
+        assert!(Builtin::Add(Term::var("a"), 36.into(), "p".into()).evaluate(&mut b));
+        assert!(!Builtin::Add(Term::var("a"), Term::var("a"), "p".into()).evaluate(&mut b));
+        assert_eq!(b.value("p"), Some(&Value::Integer(42)));
+    }
+
+    #[test]
+    fn test_overflow_does_not_match() {
+        let mut b = bindings(&[("max", Value::Integer(i64::MAX))]);
+        assert!(!Builtin::Add(Term::var("max"), 1.into(), "r".into()).evaluate(&mut b));
+        assert!(!b.is_bound("r"));
+    }
+
+    #[test]
+    fn test_string_builtins() {
+        let mut b = bindings(&[("name", Value::literal("sensor"))]);
+
+        assert!(
+            Builtin::Concat(Term::var("name"), Term::string(":7"), "id".into()).evaluate(&mut b)
+        );
+        assert_eq!(b.value("id"), Some(&Value::literal("sensor:7")));
+        assert!(Builtin::StrStarts(Term::var("id"), Term::string("sen")).evaluate(&mut b));
+        assert!(!Builtin::StrStarts(Term::var("id"), Term::string("act")).evaluate(&mut b));
+    }
+
+    #[test]
+    fn test_type_mismatch_does_not_match() {
+        let mut b = bindings(&[
+            ("node", Value::Node(NodeId::named("device:1"))),
+            ("text", Value::literal("12")),
+            ("n", Value::Integer(12)),
+        ]);
+
+        assert!(!Builtin::GreaterThan(Term::var("node"), Term::var("n")).evaluate(&mut b));
+        assert!(!Builtin::Equal(Term::var("text"), Term::var("n")).evaluate(&mut b));
+        assert!(!Builtin::Add(Term::var("text"), 1.into(), "r".into()).evaluate(&mut b));
+        assert!(!Builtin::StrStarts(Term::var("node"), Term::string("device")).evaluate(&mut b));
+        assert!(!Builtin::Concat(Term::var("n"), Term::string("x"), "r".into()).evaluate(&mut b));
+    }
+
+    #[test]
+    fn test_unbound_input_does_not_match() {
+        let mut b = Bindings::new();
+        assert!(!Builtin::GreaterThan(Term::var("missing"), 0.into()).evaluate(&mut b));
+    }
+}
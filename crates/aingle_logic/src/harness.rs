@@ -0,0 +1,482 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Rule test harness
+//!
+//! Expected behavior of a rule set is written down as data: each
+//! [`RuleTestCase`] puts a few fixture triples in a graph, validates a
+//! candidate triple against it and states the [`Expectation`].
+//! [`RuleTestRunner::run`] evaluates the cases with a [`PoLValidator`] and
+//! returns a [`TestReport`] whose failures show the actual errors and
+//! derivations next to the expected ones.
+//!
+//! Fixture files are JSON arrays of cases. Triples are written as
+//! `[subject, predicate, object]`, where the object is a JSON string, number,
+//! boolean or `null` literal, or `{"node": id}` for a node:
+//!
+//! ```json
+//! [
+//!   {
+//!     "name": "a node cannot reference itself",
+//!     "given": [["alice", "knows", {"node": "bob"}]],
+//!     "when": ["alice", "knows", {"node": "alice"}],
+//!     "expect": {"invalid_by": "no_self_reference"}
+//!   },
+//!   {
+//!     "name": "parents imply children",
+//!     "when": ["alice", "parent_of", {"node": "bob"}],
+//!     "expect": {"derives_triple": {
+//!       "subject": {"Node": "bob"}, "predicate": "child_of", "object": {"Node": "alice"}
+//!     }}
+//!   }
+//! ]
+//! ```
+//!
+//! Errors raised by the validator's own checks rather than by a rule are
+//! identified by their [`ErrorKind`] in snake case, e.g. `contradiction`.
+//!
+//! Crates that embed a rule set can check a fixture directory from a test
+//! with [`assert_rule_fixtures!`](crate::assert_rule_fixtures).
+
+use std::fmt;
+use std::path::Path;
+
+use aingle_graph::{GraphDB, NodeId, Predicate, Triple, Value};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::rule::{Bindings, RuleSet, TriplePattern};
+use crate::validator::{ErrorKind, LogicValidator, PoLValidator};
+
+/// The outcome a [`RuleTestCase`] expects
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Expectation {
+    /// The candidate triple passes validation
+    Valid,
+    /// Validation fails with an error from the given rule
+    InvalidBy(String),
+    /// Forward chaining derives a triple matching the pattern
+    DerivesTriple(TriplePattern),
+}
+
+impl fmt::Display for Expectation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expectation::Valid => write!(f, "valid"),
+            Expectation::InvalidBy(rule_id) => write!(f, "invalid by {}", rule_id),
+            Expectation::DerivesTriple(pattern) => write!(
+                f,
+                "derives {:?} {} {:?}",
+                pattern.subject, pattern.predicate, pattern.object
+            ),
+        }
+    }
+}
+
+/// A single rule test: fixture triples, a candidate triple and the expected outcome
+#[derive(Debug, Clone)]
+pub struct RuleTestCase {
+    /// A description of the case, shown in reports
+    pub name: String,
+    /// Triples in the graph before the candidate is validated
+    pub given: Vec<Triple>,
+    /// The candidate triple
+    pub when: Triple,
+    /// The expected outcome
+    pub expect: Expectation,
+}
+
+impl RuleTestCase {
+    /// Parses the cases of a JSON fixture document
+    pub fn from_json(json: &str) -> Result<Vec<RuleTestCase>> {
+        let fixtures: Vec<CaseFixture> = serde_json::from_str(json)
+            .map_err(|e| Error::InvalidFixture(format!("malformed fixture: {}", e)))?;
+        fixtures.into_iter().map(RuleTestCase::try_from).collect()
+    }
+
+    /// Loads the cases of a JSON fixture file
+    pub fn load(path: impl AsRef<Path>) -> Result<Vec<RuleTestCase>> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| Error::InvalidFixture(format!("{}: {}", path.display(), e)))?;
+        Self::from_json(&json).map_err(|e| match e {
+            Error::InvalidFixture(msg) => {
+                Error::InvalidFixture(format!("{}: {}", path.display(), msg))
+            }
+            other => other,
+        })
+    }
+
+    /// Loads the cases of every `.json` file in a directory, in file name order
+    pub fn load_dir(dir: impl AsRef<Path>) -> Result<Vec<RuleTestCase>> {
+        let dir = dir.as_ref();
+        let mut paths = Vec::new();
+        for entry in std::fs::read_dir(dir)
+            .map_err(|e| Error::InvalidFixture(format!("{}: {}", dir.display(), e)))?
+        {
+            let path = entry
+                .map_err(|e| Error::InvalidFixture(format!("{}: {}", dir.display(), e)))?
+                .path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+
+        let mut cases = Vec::new();
+        for path in paths {
+            cases.extend(Self::load(path)?);
+        }
+        Ok(cases)
+    }
+}
+
+/// A test case as written in a fixture file
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CaseFixture {
+    name: String,
+    #[serde(default)]
+    given: Vec<TripleFixture>,
+    when: TripleFixture,
+    expect: Expectation,
+}
+
+/// A `[subject, predicate, object]` triple as written in a fixture file
+type TripleFixture = (String, String, serde_json::Value);
+
+impl TryFrom<CaseFixture> for RuleTestCase {
+    type Error = Error;
+
+    fn try_from(fixture: CaseFixture) -> Result<Self> {
+        let triple = |(subject, predicate, object): TripleFixture| -> Result<Triple> {
+            let object = fixture_value(&object).ok_or_else(|| {
+                Error::InvalidFixture(format!(
+                    "case `{}`: unsupported object {}",
+                    fixture.name, object
+                ))
+            })?;
+            Ok(Triple::new(
+                NodeId::named(subject),
+                Predicate::named(predicate),
+                object,
+            ))
+        };
+        let given = fixture
+            .given
+            .iter()
+            .cloned()
+            .map(triple)
+            .collect::<Result<_>>()?;
+        let when = triple(fixture.when.clone())?;
+        Ok(RuleTestCase {
+            name: fixture.name,
+            given,
+            when,
+            expect: fixture.expect,
+        })
+    }
+}
+
+/// Converts a fixture object to a graph value
+fn fixture_value(object: &serde_json::Value) -> Option<Value> {
+    use serde_json::Value as Json;
+
+    Some(match object {
+        Json::String(s) => Value::String(s.clone()),
+        Json::Bool(b) => Value::Boolean(*b),
+        Json::Number(n) => match n.as_i64() {
+            Some(i) => Value::Integer(i),
+            None => Value::Float(n.as_f64()?),
+        },
+        Json::Null => Value::Null,
+        Json::Object(map) if map.len() == 1 => {
+            Value::Node(NodeId::named(map.get("node")?.as_str()?))
+        }
+        _ => return None,
+    })
+}
+
+/// Runs [`RuleTestCase`]s against a rule set
+pub struct RuleTestRunner;
+
+impl RuleTestRunner {
+    /// Runs every case against `rule_set` and reports the outcome of each
+    ///
+    /// Each case starts from an empty in-memory graph holding its `given`
+    /// triples. The candidate is validated in that context; for
+    /// [`Expectation::DerivesTriple`] it is then added to the graph and the
+    /// rules are forward chained.
+    pub fn run(rule_set: RuleSet, cases: &[RuleTestCase]) -> TestReport {
+        let validator = PoLValidator::with_rules(rule_set);
+        let results = cases
+            .iter()
+            .map(|case| {
+                let result = Self::run_case(&validator, case).unwrap_or_else(|e| CaseResult {
+                    name: case.name.clone(),
+                    expected: case.expect.clone(),
+                    passed: false,
+                    errors: vec![format!("harness error: {}", e)],
+                    derived: Vec::new(),
+                });
+                validator.engine().clear_inferred();
+                result
+            })
+            .collect();
+        TestReport { results }
+    }
+
+    fn run_case(validator: &PoLValidator, case: &RuleTestCase) -> Result<CaseResult> {
+        let graph = GraphDB::memory()?;
+        for triple in &case.given {
+            graph.insert(triple.clone())?;
+        }
+
+        let validation = validator.validate_with_context(&case.when, &graph)?;
+        let errors: Vec<(String, String)> = validation
+            .errors
+            .iter()
+            .map(|e| {
+                let id = e
+                    .source_rule
+                    .clone()
+                    .unwrap_or_else(|| error_id(e.kind).to_string());
+                (id, e.message.clone())
+            })
+            .collect();
+
+        let mut derived = Vec::new();
+        if let Expectation::DerivesTriple(_) = &case.expect {
+            graph.insert(case.when.clone())?;
+            validator.engine().forward_chain(&graph)?;
+            for triple in validator.engine().inferred_triples() {
+                if !derived.contains(&triple) {
+                    derived.push(triple);
+                }
+            }
+        }
+
+        let passed = match &case.expect {
+            Expectation::Valid => validation.is_valid && errors.is_empty(),
+            Expectation::InvalidBy(rule_id) => errors.iter().any(|(id, _)| id == rule_id),
+            Expectation::DerivesTriple(pattern) => derived
+                .iter()
+                .any(|t| pattern.matches(t, &mut Bindings::new())),
+        };
+
+        Ok(CaseResult {
+            name: case.name.clone(),
+            expected: case.expect.clone(),
+            passed,
+            errors: errors
+                .into_iter()
+                .map(|(id, message)| format!("{}: {}", id, message))
+                .collect(),
+            derived: derived.iter().map(|t| t.to_string()).collect(),
+        })
+    }
+}
+
+/// Identifies errors raised by the validator itself rather than by a rule
+fn error_id(kind: ErrorKind) -> &'static str {
+    match kind {
+        ErrorKind::Contradiction => "contradiction",
+        ErrorKind::RuleViolation => "rule_violation",
+        ErrorKind::AuthorityViolation => "authority_violation",
+        ErrorKind::TemporalInconsistency => "temporal_inconsistency",
+        ErrorKind::TypeConflict => "type_conflict",
+        ErrorKind::MissingPrecondition => "missing_precondition",
+        ErrorKind::InvalidReference => "invalid_reference",
+        ErrorKind::ConstraintViolation => "constraint_violation",
+    }
+}
+
+/// The outcome of a single [`RuleTestCase`]
+#[derive(Debug, Clone)]
+pub struct CaseResult {
+    /// The name of the case
+    pub name: String,
+    /// The expected outcome
+    pub expected: Expectation,
+    /// Whether the actual outcome met the expectation
+    pub passed: bool,
+    /// Validation errors, as `rule_id: message`
+    pub errors: Vec<String>,
+    /// Derived triples; only collected for [`Expectation::DerivesTriple`]
+    pub derived: Vec<String>,
+}
+
+impl CaseResult {
+    /// Expected versus actual outcome, one line each
+    pub fn diff(&self) -> String {
+        let actual = match &self.expected {
+            Expectation::DerivesTriple(_) => {
+                format!("derived [{}]", self.derived.join("; "))
+            }
+            _ if self.errors.is_empty() => "valid".to_string(),
+            _ => format!("errors [{}]", self.errors.join("; ")),
+        };
+        format!("- expected: {}\n+ actual:   {}", self.expected, actual)
+    }
+}
+
+/// The outcome of a [`RuleTestRunner::run`]
+#[derive(Debug, Clone, Default)]
+pub struct TestReport {
+    /// Results in case order
+    pub results: Vec<CaseResult>,
+}
+
+impl TestReport {
+    /// Number of cases that met their expectation
+    pub fn passed(&self) -> usize {
+        self.results.iter().filter(|r| r.passed).count()
+    }
+
+    /// The cases that did not meet their expectation
+    pub fn failures(&self) -> Vec<&CaseResult> {
+        self.results.iter().filter(|r| !r.passed).collect()
+    }
+
+    /// Whether every case met its expectation
+    pub fn is_success(&self) -> bool {
+        self.results.iter().all(|r| r.passed)
+    }
+}
+
+impl fmt::Display for TestReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} of {} rule test cases passed",
+            self.passed(),
+            self.results.len()
+        )?;
+        for failure in self.failures() {
+            writeln!(f, "\nFAILED: {}\n{}", failure.name, failure.diff())?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs the rule test fixtures in a directory and panics with the report if any case fails
+///
+/// The directory is relative to the calling crate's manifest directory.
+///
+/// ```ignore
+/// #[test]
+/// fn rule_fixtures() {
+///     aingle_logic::assert_rule_fixtures!(my_rules(), "tests/rules");
+/// }
+/// ```
+#[macro_export]
+macro_rules! assert_rule_fixtures {
+    ($rule_set:expr, $dir:expr) => {{
+        let dir = ::std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join($dir);
+        let cases = $crate::harness::RuleTestCase::load_dir(&dir)
+            .unwrap_or_else(|e| panic!("cannot load rule fixtures: {}", e));
+        assert!(!cases.is_empty(), "no rule fixtures in {}", dir.display());
+        let report = $crate::harness::RuleTestRunner::run($rule_set, &cases);
+        assert!(report.is_success(), "{}", report);
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builtin::BuiltinRules;
+    use crate::rule::{Pattern, Rule};
+
+    #[test]
+    fn test_builtin_minimal_fixtures() {
+        assert_rule_fixtures!(BuiltinRules::minimal(), "fixtures/minimal");
+    }
+
+    #[test]
+    fn test_derivation_and_failure_diff() {
+        let cases = RuleTestCase::from_json(
+            r#"[
+                {
+                    "name": "parents imply children",
+                    "when": ["alice", "parent_of", {"node": "bob"}],
+                    "expect": {"derives_triple": {
+                        "subject": {"Node": "bob"},
+                        "predicate": "child_of",
+                        "object": {"Node": "alice"}
+                    }}
+                },
+                {
+                    "name": "wrong rule expected",
+                    "when": ["alice", "knows", {"node": "alice"}],
+                    "expect": {"invalid_by": "no_empty_predicate"}
+                },
+                {
+                    "name": "missing derivation",
+                    "when": ["alice", "knows", {"node": "bob"}],
+                    "expect": {"derives_triple": {
+                        "subject": "Any", "predicate": "child_of", "object": "Any"
+                    }}
+                }
+            ]"#,
+        )
+        .unwrap();
+
+        let mut rules = BuiltinRules::minimal();
+        rules.add(
+            Rule::inference("inverse_parent")
+                .when_subject(Pattern::Variable("s".into()))
+                .when_predicate("parent_of")
+                .when_object(Pattern::Variable("o".into()))
+                .infer(TriplePattern::new(
+                    Pattern::Variable("o".into()),
+                    "child_of",
+                    Pattern::Variable("s".into()),
+                ))
+                .build(),
+        );
+        let report = RuleTestRunner::run(rules, &cases);
+        assert_eq!(report.passed(), 1, "{}", report);
+
+        let failures = report.failures();
+        assert_eq!(failures[0].name, "wrong rule expected");
+        let diff = failures[0].diff();
+        assert!(
+            diff.contains("- expected: invalid by no_empty_predicate"),
+            "{}",
+            diff
+        );
+        assert!(
+            diff.contains("+ actual:   errors [no_self_reference:"),
+            "{}",
+            diff
+        );
+
+        // Derivations of the first case do not leak into the third
+        assert_eq!(failures[1].name, "missing derivation");
+        assert!(
+            failures[1].diff().ends_with("+ actual:   derived []"),
+            "{}",
+            failures[1].diff()
+        );
+    }
+
+    #[test]
+    fn test_malformed_fixtures_rejected() {
+        let unsupported = r#"[{"name": "bad", "when": ["a", "p", [1, 2]], "expect": "valid"}]"#;
+        let err = RuleTestCase::from_json(unsupported)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("case `bad`: unsupported object [1,2]"),
+            "{}",
+            err
+        );
+
+        let unknown = r#"[{"name": "bad", "when": ["a", "p", "o"], "expect": "fails"}]"#;
+        assert!(matches!(
+            RuleTestCase::from_json(unknown),
+            Err(Error::InvalidFixture(_))
+        ));
+    }
+}
@@ -53,18 +53,27 @@
 //! ```
 
 pub mod builtin;
+pub mod encoding;
 pub mod engine;
 pub mod error;
+pub mod functions;
+pub mod harness;
 pub mod proof;
 pub mod rule;
+pub mod validated;
 pub mod validator;
 
 // Re-exports
 pub use builtin::BuiltinRules;
-pub use engine::{EngineStats, InferenceMode, RuleEngine};
+pub use engine::{
+    ConflictStrategy, EngineStats, InferenceMode, RuleEngine, RuleProfile, SuppressedDerivation,
+};
 pub use error::{Error, Result};
-pub use proof::{LogicProof, ProofStep, ProofVerifier};
+pub use functions::{Builtin, Term};
+pub use harness::{Expectation, RuleTestCase, RuleTestRunner, TestReport};
+pub use proof::{LogicProof, ProofStep, ProofVerifier, VerificationReport};
 pub use rule::{Action, Condition, Rule, RuleKind, RuleSet};
+pub use validated::ValidatedGraph;
 pub use validator::{LogicValidator, Severity, ValidationError, ValidationResult};
 
 /// Version information
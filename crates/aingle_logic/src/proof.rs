@@ -6,14 +6,15 @@
 //! Proofs are cryptographic evidence that a logical derivation is valid.
 //! They can be verified without re-running the entire inference process.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
-use aingle_graph::{Triple, Value};
+use aingle_graph::{GraphDB, NodeId, Triple, TripleId, Value};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::engine::RuleEngine;
 use crate::error::{Error, Result};
-use crate::rule::{Bindings, Rule, RuleKind};
+use crate::rule::{Bindings, Rule, RuleKind, RuleSet};
 
 /// A cryptographic proof that a logical derivation is valid.
 ///
@@ -151,6 +152,43 @@ impl LogicProof {
     pub fn from_json(json: &str) -> Result<Self> {
         serde_json::from_str(json).map_err(Error::from)
     }
+
+    /// Serializes the `LogicProof` into its canonical byte form.
+    ///
+    /// This is compact JSON with the metadata keys sorted, so the same proof
+    /// always produces the same bytes. Read it back with
+    /// [`from_canonical_bytes`](Self::from_canonical_bytes).
+    pub fn to_canonical_bytes(&self) -> Result<Vec<u8>> {
+        #[derive(Serialize)]
+        struct Canonical<'a> {
+            id: &'a str,
+            conclusion: &'a ProofConclusion,
+            steps: &'a [ProofStep],
+            timestamp: &'a DateTime<Utc>,
+            hash: &'a str,
+            metadata: BTreeMap<&'a str, &'a str>,
+        }
+
+        serde_json::to_vec(&Canonical {
+            id: &self.id,
+            conclusion: &self.conclusion,
+            steps: &self.steps,
+            timestamp: &self.timestamp,
+            hash: &self.hash,
+            metadata: self
+                .metadata
+                .iter()
+                .map(|(k, v)| (k.as_str(), v.as_str()))
+                .collect(),
+        })
+        .map_err(Error::from)
+    }
+
+    /// Deserializes a `LogicProof` from the bytes produced by
+    /// [`to_canonical_bytes`](Self::to_canonical_bytes).
+    pub fn from_canonical_bytes(bytes: &[u8]) -> Result<Self> {
+        serde_json::from_slice(bytes).map_err(Error::from)
+    }
 }
 
 /// Specifies what a `LogicProof` aims to establish or conclude.
@@ -183,6 +221,28 @@ pub struct TripleData {
     pub predicate: String,
     /// The string representation of the object `Value`.
     pub object: String,
+    /// The `TripleId` of the original triple, when known.
+    ///
+    /// The string forms above are lossy (a named node and a string literal
+    /// print the same), so the ID is what identifies the triple exactly.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<TripleId>,
+}
+
+impl TripleData {
+    /// Returns `true` if this data describes `triple`.
+    ///
+    /// Compares IDs when this data carries one, and the string forms otherwise.
+    pub fn matches(&self, triple: &Triple) -> bool {
+        match &self.id {
+            Some(id) => *id == triple.id(),
+            None => {
+                self.subject == node_id_to_string(&triple.subject)
+                    && self.predicate == triple.predicate.as_str()
+                    && self.object == value_to_string(&triple.object)
+            }
+        }
+    }
 }
 
 impl From<&Triple> for TripleData {
@@ -192,6 +252,7 @@ impl From<&Triple> for TripleData {
             subject: node_id_to_string(&triple.subject),
             predicate: triple.predicate.as_str().to_string(),
             object: value_to_string(&triple.object),
+            id: Some(triple.id()),
         }
     }
 }
@@ -205,9 +266,34 @@ fn node_id_to_string(node: &aingle_graph::NodeId) -> String {
         aingle_graph::NodeId::Named(s) => s.clone(),
         aingle_graph::NodeId::Hash(h) => format!("hash:{}", hex::encode(h)),
         aingle_graph::NodeId::Blank(id) => format!("_:b{}", id),
+        aingle_graph::NodeId::Anonymous(id) => format!("_:a{}", hex::encode(id)),
     }
 }
 
+/// Parses a string produced by [`node_id_to_string`] back into a `NodeId`.
+fn node_id_from_string(s: &str) -> NodeId {
+    if let Some(hex) = s.strip_prefix("hash:") {
+        if let Some(bytes) = hex::decode(hex)
+            .ok()
+            .and_then(|b| <[u8; 32]>::try_from(b).ok())
+        {
+            return NodeId::Hash(bytes);
+        }
+    }
+    if let Some(id) = s.strip_prefix("_:b").and_then(|n| n.parse().ok()) {
+        return NodeId::Blank(id);
+    }
+    if let Some(hex) = s.strip_prefix("_:a") {
+        if let Some(bytes) = hex::decode(hex)
+            .ok()
+            .and_then(|b| <[u8; 16]>::try_from(b).ok())
+        {
+            return NodeId::Anonymous(bytes);
+        }
+    }
+    NodeId::Named(s.to_string())
+}
+
 impl From<Triple> for TripleData {
     /// Converts a `Triple` into `TripleData`.
     fn from(triple: Triple) -> Self {
@@ -443,6 +529,144 @@ impl ProofVerifier {
         result
     }
 
+    /// Verifies a `LogicProof` against a snapshot of the local graph.
+    ///
+    /// In addition to the structural checks of [`verify`](Self::verify), this
+    /// confirms that every premise the proof rests on is actually known here:
+    /// the outputs of `Fact` steps, and any step input that no earlier step
+    /// produced. A premise is accepted if it is in `graph`, or if the
+    /// verifier's inference rules derive it from `graph` by forward chaining.
+    ///
+    /// Inference steps must also use a rule in the verifier's rule set,
+    /// regardless of [`VerifyOptions::check_rules`].
+    ///
+    /// # Arguments
+    ///
+    /// * `proof` - The `LogicProof` to verify.
+    /// * `graph` - The graph holding the facts the proof should rest on.
+    ///
+    /// # Returns
+    ///
+    /// A `VerificationReport` listing the verified steps, the premises missing
+    /// from `graph`, and the steps that apply unknown rules.
+    pub fn verify_against(&self, proof: &LogicProof, graph: &GraphDB) -> VerificationReport {
+        let mut report = VerificationReport {
+            structural: self.verify(proof),
+            ..VerificationReport::default()
+        };
+        let mut derived: Option<Vec<Triple>> = None;
+        // Outputs of verified steps, usable as inputs by later steps
+        let mut established: Vec<&TripleData> = Vec::new();
+        // Outputs of any step, verified or not
+        let mut produced: Vec<&TripleData> = Vec::new();
+
+        for step in &proof.steps {
+            let mut verified = true;
+
+            let premises: Vec<&TripleData> = match step.step_type {
+                StepType::Fact => step.output.iter().collect(),
+                _ => step.inputs.iter().collect(),
+            };
+            for premise in premises {
+                if established.iter().any(|e| same_triple(e, premise)) {
+                    continue;
+                }
+                if step.step_type != StepType::Fact
+                    && produced.iter().any(|p| same_triple(p, premise))
+                {
+                    // Produced by an earlier step that failed; already reported there
+                    verified = false;
+                    continue;
+                }
+                match self.resolve_premise(premise, graph, &mut derived, &mut report) {
+                    Some(id) => report.verified_premises.push(id),
+                    None => {
+                        verified = false;
+                        report.missing_premises.push(MissingPremise {
+                            step_num: step.step_num,
+                            premise: premise.clone(),
+                            triple_id: premise.id.clone(),
+                        });
+                    }
+                }
+            }
+
+            if step.step_type == StepType::Inference
+                && step.rule_id != "fact"
+                && !self.rules.contains_key(&step.rule_id)
+            {
+                verified = false;
+                report.unknown_rules.push(UnknownRule {
+                    step_num: step.step_num,
+                    rule_id: step.rule_id.clone(),
+                });
+            }
+
+            if let Some(output) = &step.output {
+                produced.push(output);
+                if verified {
+                    established.push(output);
+                }
+            }
+            if verified {
+                report.verified_steps.push(step.step_num);
+            }
+        }
+
+        report
+    }
+
+    /// Looks up a premise in `graph`, falling back to the triples the
+    /// verifier's rules derive from it.
+    ///
+    /// Returns the ID of the matching triple, or `None` if the premise is
+    /// neither stored nor derivable.
+    fn resolve_premise(
+        &self,
+        premise: &TripleData,
+        graph: &GraphDB,
+        derived: &mut Option<Vec<Triple>>,
+        report: &mut VerificationReport,
+    ) -> Option<TripleId> {
+        let stored = match &premise.id {
+            Some(id) => graph.get(id).map(|t| t.map(|t| t.id())),
+            None => graph
+                .get_subject(&node_id_from_string(&premise.subject))
+                .map(|triples| triples.iter().find(|t| premise.matches(t)).map(Triple::id)),
+        };
+        match stored {
+            Ok(Some(id)) => return Some(id),
+            Ok(None) => {}
+            Err(e) => report
+                .structural
+                .add_warning(&format!("Graph lookup failed: {}", e)),
+        }
+
+        let derived = derived.get_or_insert_with(|| self.derive_from(graph, report));
+        derived.iter().find(|t| premise.matches(t)).map(Triple::id)
+    }
+
+    /// Forward-chains the verifier's inference rules over `graph`.
+    fn derive_from(&self, graph: &GraphDB, report: &mut VerificationReport) -> Vec<Triple> {
+        if !self.rules.values().any(|r| r.kind == RuleKind::Inference) {
+            return Vec::new();
+        }
+
+        let mut rules = RuleSet::new("proof_verifier");
+        for rule in self.rules.values() {
+            rules.add(rule.clone());
+        }
+        match RuleEngine::with_rules(rules).forward_chain(graph) {
+            Ok(result) => result.inferences.into_iter().map(|(_, t)| t).collect(),
+            Err(e) => {
+                report
+                    .structural
+                    .add_warning(&format!("Could not derive premises: {}", e));
+                Vec::new()
+            }
+        }
+    }
+
     /// Verifies a single `ProofStep`.
     ///
     /// This is a private helper function used internally by `verify()`.
@@ -614,6 +838,71 @@ impl VerifyResult {
     }
 }
 
+/// A premise a proof depends on that is not present in the local graph.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissingPremise {
+    /// The step that uses the premise.
+    pub step_num: usize,
+    /// The premise as recorded in the proof.
+    pub premise: TripleData,
+    /// The ID of the missing triple, so it can be fetched from peers.
+    ///
+    /// `None` if the proof did not record the premise's ID.
+    pub triple_id: Option<TripleId>,
+}
+
+/// An inference step whose rule is not in the verifier's rule set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnknownRule {
+    /// The step that applies the rule.
+    pub step_num: usize,
+    /// The ID of the unknown rule.
+    pub rule_id: String,
+}
+
+/// The outcome of [`ProofVerifier::verify_against`].
+#[derive(Debug, Clone, Default)]
+pub struct VerificationReport {
+    /// The result of the structural checks performed by [`ProofVerifier::verify`].
+    pub structural: VerifyResult,
+    /// Numbers of the steps whose premises and rule were all confirmed.
+    pub verified_steps: Vec<usize>,
+    /// IDs of the graph (or derived) triples that premises resolved to.
+    pub verified_premises: Vec<TripleId>,
+    /// Premises found neither in the graph nor by derivation.
+    pub missing_premises: Vec<MissingPremise>,
+    /// Inference steps that apply rules the verifier does not know.
+    pub unknown_rules: Vec<UnknownRule>,
+}
+
+impl VerificationReport {
+    /// Returns `true` if the proof is structurally valid, every premise was
+    /// found and every rule is known.
+    pub fn is_complete(&self) -> bool {
+        self.structural.is_valid
+            && self.missing_premises.is_empty()
+            && self.unknown_rules.is_empty()
+    }
+
+    /// IDs of the missing premises, for fetching them from peers.
+    pub fn missing_triple_ids(&self) -> Vec<TripleId> {
+        let mut seen = HashSet::new();
+        self.missing_premises
+            .iter()
+            .filter_map(|m| m.triple_id.clone())
+            .filter(|id| seen.insert(id.clone()))
+            .collect()
+    }
+}
+
+/// Returns `true` if two `TripleData` describe the same triple.
+fn same_triple(a: &TripleData, b: &TripleData) -> bool {
+    match (&a.id, &b.id) {
+        (Some(x), Some(y)) => x == y,
+        _ => a.subject == b.subject && a.predicate == b.predicate && a.object == b.object,
+    }
+}
+
 /// Generates a unique identifier for a new `LogicProof`.
 ///
 /// The ID is generated based on the current system time's nanoseconds, formatted as a hexadecimal string.
@@ -671,7 +960,8 @@ fn bindings_to_vec(_bindings: &Bindings) -> Vec<(String, String)> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use aingle_graph::{NodeId, Predicate};
+    use crate::rule::{Pattern, TriplePattern};
+    use aingle_graph::Predicate;
 
     #[test]
     fn test_proof_creation() {
@@ -708,6 +998,29 @@ mod tests {
         assert_eq!(proof.len(), restored.len());
     }
 
+    #[test]
+    fn test_proof_canonical_bytes() {
+        let triple = Triple::new(
+            NodeId::named("socrates"),
+            Predicate::named("is"),
+            Value::Node(NodeId::named("human")),
+        );
+
+        let mut proof = LogicProof::new(ProofConclusion::Triple((&triple).into()));
+        proof.add_step(ProofStep::fact(1, &triple));
+        for key in ["zeta", "alpha", "mu", "beta"] {
+            proof.metadata.insert(key.to_string(), key.to_uppercase());
+        }
+        proof.finalize();
+
+        let bytes = proof.to_canonical_bytes().unwrap();
+        let restored = LogicProof::from_canonical_bytes(&bytes).unwrap();
+        assert_eq!(restored.hash, proof.hash);
+        assert_eq!(restored.metadata, proof.metadata);
+        assert_eq!(restored.to_canonical_bytes().unwrap(), bytes);
+        assert!(ProofVerifier::new().verify(&restored).is_valid);
+    }
+
     #[test]
     fn test_proof_verification() {
         let triple = Triple::new(
@@ -789,4 +1102,140 @@ mod tests {
         let unif = ProofStep::unification(2, "rule1", &bindings, 1);
         assert_eq!(unif.step_type, StepType::Unification);
     }
+
+    fn is_a(subject: &str, class: &str) -> Triple {
+        Triple::new(
+            NodeId::named(subject),
+            Predicate::named("is_a"),
+            Value::Node(NodeId::named(class)),
+        )
+    }
+
+    fn mortal_rule() -> Rule {
+        Rule::inference("mortal")
+            .when_subject(Pattern::Variable("x".into()))
+            .when_predicate("is_a")
+            .when_object(Pattern::Node("human".into()))
+            .infer(TriplePattern::new(
+                Pattern::Variable("x".into()),
+                "is_a",
+                Pattern::Node("mortal".into()),
+            ))
+            .build()
+    }
+
+    /// `a is_a human`, `b is_a human` ⟹ (by `rule_id`) `a is_a mortal`
+    fn two_premise_proof(rule_id: &str) -> LogicProof {
+        let a = is_a("a", "human");
+        let b = is_a("b", "human");
+        let conclusion = is_a("a", "mortal");
+        let mut proof = LogicProof::new(ProofConclusion::Triple((&conclusion).into()));
+        proof.add_step(ProofStep::fact(1, &a));
+        proof.add_step(ProofStep::fact(2, &b));
+        proof.add_step(ProofStep::inference(
+            3,
+            rule_id,
+            vec![&a, &b],
+            &conclusion,
+            &Bindings::new(),
+            1,
+        ));
+        proof.finalize();
+        proof
+    }
+
+    #[test]
+    fn test_verify_against_complete() {
+        let graph = GraphDB::memory().unwrap();
+        graph.insert(is_a("a", "human")).unwrap();
+        graph.insert(is_a("b", "human")).unwrap();
+
+        let mut verifier = ProofVerifier::new();
+        verifier.add_rule(mortal_rule());
+        let report = verifier.verify_against(&two_premise_proof("mortal"), &graph);
+
+        assert!(report.is_complete());
+        assert_eq!(report.verified_steps, vec![1, 2, 3]);
+        assert_eq!(report.verified_premises.len(), 2);
+    }
+
+    #[test]
+    fn test_verify_against_missing_premise() {
+        let graph = GraphDB::memory().unwrap();
+        graph.insert(is_a("a", "human")).unwrap();
+
+        let mut verifier = ProofVerifier::new();
+        verifier.add_rule(mortal_rule());
+        let report = verifier.verify_against(&two_premise_proof("mortal"), &graph);
+
+        assert!(!report.is_complete());
+        assert!(report.structural.is_valid);
+        assert!(report.unknown_rules.is_empty());
+        // Only the fact step for `b` is missing; the inference step is
+        // unverified because it depends on it, but is not reported twice.
+        assert_eq!(report.verified_steps, vec![1]);
+        assert_eq!(report.missing_premises.len(), 1);
+        let missing = &report.missing_premises[0];
+        assert_eq!(missing.step_num, 2);
+        assert_eq!(missing.premise.subject, "b");
+        assert_eq!(missing.triple_id, Some(is_a("b", "human").id()));
+        assert_eq!(report.missing_triple_ids(), vec![is_a("b", "human").id()]);
+    }
+
+    #[test]
+    fn test_verify_against_unknown_rule() {
+        let graph = GraphDB::memory().unwrap();
+        graph.insert(is_a("a", "human")).unwrap();
+        graph.insert(is_a("b", "human")).unwrap();
+
+        let mut verifier = ProofVerifier::new();
+        verifier.add_rule(mortal_rule());
+        let report = verifier.verify_against(&two_premise_proof("peer_only_rule"), &graph);
+
+        assert!(!report.is_complete());
+        assert!(report.missing_premises.is_empty());
+        assert_eq!(report.verified_steps, vec![1, 2]);
+        assert_eq!(report.unknown_rules.len(), 1);
+        assert_eq!(report.unknown_rules[0].step_num, 3);
+        assert_eq!(report.unknown_rules[0].rule_id, "peer_only_rule");
+    }
+
+    #[test]
+    fn test_verify_against_derivable_premise() {
+        // `a is_a mortal` is not stored but follows from `a is_a human`
+        let graph = GraphDB::memory().unwrap();
+        graph.insert(is_a("a", "human")).unwrap();
+
+        let premise = is_a("a", "mortal");
+        let mut proof = LogicProof::new(ProofConclusion::Triple((&premise).into()));
+        proof.add_step(ProofStep::fact(1, &premise));
+        proof.finalize();
+
+        let report = ProofVerifier::new().verify_against(&proof, &graph);
+        assert_eq!(report.missing_premises.len(), 1);
+
+        let mut verifier = ProofVerifier::new();
+        verifier.add_rule(mortal_rule());
+        let report = verifier.verify_against(&proof, &graph);
+        assert!(report.is_complete());
+        assert_eq!(report.verified_premises, vec![premise.id()]);
+    }
+
+    #[test]
+    fn test_triple_data_without_id_matches_by_strings() {
+        let triple = is_a("a", "human");
+        let mut data = TripleData::from(&triple);
+        data.id = None;
+        assert!(data.matches(&triple));
+        assert!(!data.matches(&is_a("a", "mortal")));
+        assert_eq!(node_id_from_string("_:b7"), NodeId::Blank(7));
+        assert_eq!(
+            node_id_from_string(&node_id_to_string(&NodeId::Hash([3; 32]))),
+            NodeId::Hash([3; 32])
+        );
+        assert_eq!(
+            node_id_from_string(&node_id_to_string(&NodeId::Anonymous([5; 16]))),
+            NodeId::Anonymous([5; 16])
+        );
+    }
 }
@@ -8,7 +8,11 @@
 
 use aingle_graph::{NodeId, Predicate, Triple, Value};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+use crate::engine::ConflictStrategy;
+use crate::error::{Error, Result};
+use crate::functions::Builtin;
 
 /// A logical rule with conditions and consequences.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -72,14 +76,92 @@ impl Rule {
     }
 
     /// Checks if this rule's conditions match a given triple and set of bindings.
+    ///
+    /// A rule whose built-ins can never have their inputs bound does not match;
+    /// use [`Rule::try_matches`] to get the reason.
     pub fn matches(&self, triple: &Triple, bindings: &mut Bindings) -> bool {
-        self.conditions.iter().all(|c| c.matches(triple, bindings))
+        self.try_matches(triple, bindings).unwrap_or(false)
+    }
+
+    /// Like [`Rule::matches`], but fails if the rule cannot be evaluated.
+    pub fn try_matches(&self, triple: &Triple, bindings: &mut Bindings) -> Result<bool> {
+        Ok(self
+            .evaluation_order()?
+            .into_iter()
+            .all(|c| c.matches(triple, bindings)))
+    }
+
+    /// Orders the conditions so that each built-in runs after its inputs are bound.
+    ///
+    /// Pattern conditions keep their order and come first. Each built-in
+    /// follows as soon as every variable it reads is bound by a pattern or by
+    /// an earlier built-in. Fails if a built-in reads a variable that nothing
+    /// binds.
+    pub fn evaluation_order(&self) -> Result<Vec<&Condition>> {
+        let (mut pending, mut order): (Vec<_>, Vec<_>) = self
+            .conditions
+            .iter()
+            .partition(|c| matches!(c, Condition::Builtin(_)));
+        if pending.is_empty() {
+            return Ok(order);
+        }
+
+        let mut bound: HashSet<&str> = order.iter().flat_map(|c| c.binds()).collect();
+        while !pending.is_empty() {
+            let before = pending.len();
+            pending.retain(|condition| {
+                let Condition::Builtin(builtin) = condition else {
+                    unreachable!("only built-ins are pending")
+                };
+                if !builtin.inputs().all(|var| bound.contains(var)) {
+                    return true;
+                }
+                bound.extend(builtin.output());
+                order.push(condition);
+                false
+            });
+
+            if pending.len() == before {
+                let Condition::Builtin(builtin) = pending[0] else {
+                    unreachable!("only built-ins are pending")
+                };
+                let unbound = builtin
+                    .inputs()
+                    .find(|var| !bound.contains(var))
+                    .unwrap_or_default();
+                return Err(Error::InvalidRule(format!(
+                    "rule `{}`: built-in {} reads ?{}, which no condition binds",
+                    self.id, builtin, unbound
+                )));
+            }
+        }
+        Ok(order)
     }
 
     /// Returns the rule's fully qualified ID, prefixed by its kind (e.g., "int:my_rule").
     pub fn qualified_id(&self) -> String {
         format!("{}:{}", self.kind.prefix(), self.id)
     }
+
+    /// Returns the predicate both rules infer, if they can infer different
+    /// constant values for it about the same subject.
+    fn conflicting_predicate(&self, other: &Rule) -> Option<&str> {
+        let (Action::Infer(a), Action::Infer(b)) = (&self.action, &other.action) else {
+            return None;
+        };
+        if a.predicate != b.predicate {
+            return None;
+        }
+        if let (Some(s1), Some(s2)) = (a.subject.constant(), b.subject.constant()) {
+            if s1 != s2 {
+                return None;
+            }
+        }
+        match (a.object.constant(), b.object.constant()) {
+            (Some(o1), Some(o2)) if o1 != o2 => Some(&a.predicate),
+            _ => None,
+        }
+    }
 }
 
 /// A builder for creating `Rule`s using a fluent API.
@@ -148,6 +230,18 @@ impl RuleBuilder {
         self
     }
 
+    /// Adds a built-in predicate over bound variables.
+    pub fn when_builtin(mut self, builtin: Builtin) -> Self {
+        self.rule.conditions.push(Condition::Builtin(builtin));
+        self
+    }
+
+    /// Adds a condition that the triple's object must be its own subject.
+    pub fn when_self_reference(mut self) -> Self {
+        self.rule.conditions.push(Condition::SelfReference);
+        self
+    }
+
     /// Adds a custom condition defined by a closure.
     pub fn when<F>(mut self, check: F) -> Self
     where
@@ -246,6 +340,10 @@ pub enum Condition {
     Exists(TriplePattern),
     /// No triple matching the `TriplePattern` may exist in the graph.
     NotExists(TriplePattern),
+    /// The triple's object must be the same node as its subject.
+    SelfReference,
+    /// A built-in predicate over bound variables must hold.
+    Builtin(Builtin),
     /// A custom condition evaluated by a closure.
     Custom(Box<dyn Fn(&Triple) -> bool + Send + Sync>),
 }
@@ -258,6 +356,8 @@ impl std::fmt::Debug for Condition {
             Condition::ObjectMatches(p) => f.debug_tuple("ObjectMatches").field(p).finish(),
             Condition::Exists(p) => f.debug_tuple("Exists").field(p).finish(),
             Condition::NotExists(p) => f.debug_tuple("NotExists").field(p).finish(),
+            Condition::SelfReference => f.write_str("SelfReference"),
+            Condition::Builtin(b) => f.debug_tuple("Builtin").field(b).finish(),
             Condition::Custom(_) => f.debug_tuple("Custom").field(&"<fn>").finish(),
         }
     }
@@ -271,6 +371,8 @@ impl Clone for Condition {
             Condition::ObjectMatches(p) => Condition::ObjectMatches(p.clone()),
             Condition::Exists(p) => Condition::Exists(p.clone()),
             Condition::NotExists(p) => Condition::NotExists(p.clone()),
+            Condition::SelfReference => Condition::SelfReference,
+            Condition::Builtin(b) => Condition::Builtin(b.clone()),
             // Custom closures can't be cloned, so we use a placeholder.
             // This means rules with custom conditions cannot be fully cloned.
             Condition::Custom(_) => Condition::Custom(Box::new(|_| true)),
@@ -287,9 +389,32 @@ impl Condition {
             Condition::ObjectMatches(pattern) => pattern.matches_value(&triple.object, bindings),
             Condition::Exists(_) => true,
             Condition::NotExists(_) => true,
+            Condition::SelfReference => is_self_reference(triple),
+            Condition::Builtin(b) => b.evaluate(bindings),
             Condition::Custom(f) => f(triple),
         }
     }
+
+    /// Variables this condition binds when it matches.
+    pub fn binds(&self) -> Vec<&str> {
+        fn variable(p: &Pattern) -> Option<&str> {
+            match p {
+                Pattern::Variable(var) => Some(var),
+                _ => None,
+            }
+        }
+        match self {
+            Condition::SubjectMatches(p) | Condition::ObjectMatches(p) => {
+                variable(p).into_iter().collect()
+            }
+            Condition::Exists(tp) => variable(&tp.subject)
+                .into_iter()
+                .chain(variable(&tp.object))
+                .collect(),
+            Condition::Builtin(b) => b.output().into_iter().collect(),
+            _ => Vec::new(),
+        }
+    }
 }
 
 // Custom Serialize/Deserialize implementations to handle non-serializable `Custom` variant.
@@ -321,6 +446,13 @@ impl Serialize for Condition {
                 map.serialize_entry("type", "not_exists")?;
                 map.serialize_entry("pattern", p)?;
             }
+            Condition::SelfReference => {
+                map.serialize_entry("type", "self_reference")?;
+            }
+            Condition::Builtin(b) => {
+                map.serialize_entry("type", "builtin")?;
+                map.serialize_entry("builtin", b)?;
+            }
             Condition::Custom(_) => {
                 map.serialize_entry("type", "custom")?;
                 map.serialize_entry("value", "<function>")?;
@@ -354,11 +486,13 @@ impl<'de> Deserialize<'de> for Condition {
                 let mut value: Option<String> = None;
                 let mut pattern: Option<Pattern> = None;
                 let mut triple_pattern: Option<TriplePattern> = None;
+                let mut builtin: Option<Builtin> = None;
 
                 while let Some(key) = map.next_key::<String>()? {
                     match key.as_str() {
                         "type" => cond_type = Some(map.next_value()?),
                         "value" => value = Some(map.next_value()?),
+                        "builtin" => builtin = Some(map.next_value()?),
                         "pattern" => {
                             let v: serde_json::Value = map.next_value()?;
                             if let Ok(p) = serde_json::from_value::<Pattern>(v.clone()) {
@@ -391,6 +525,10 @@ impl<'de> Deserialize<'de> for Condition {
                     "not_exists" => Ok(Condition::NotExists(
                         triple_pattern.ok_or_else(|| de::Error::missing_field("pattern"))?,
                     )),
+                    "self_reference" => Ok(Condition::SelfReference),
+                    "builtin" => Ok(Condition::Builtin(
+                        builtin.ok_or_else(|| de::Error::missing_field("builtin"))?,
+                    )),
                     _ => Err(de::Error::unknown_variant(
                         &cond_type,
                         &[
@@ -399,6 +537,8 @@ impl<'de> Deserialize<'de> for Condition {
                             "object_matches",
                             "exists",
                             "not_exists",
+                            "self_reference",
+                            "builtin",
                         ],
                     )),
                 }
@@ -444,6 +584,15 @@ pub enum Pattern {
 }
 
 impl Pattern {
+    /// Returns the node or literal this pattern matches exactly, if any.
+    fn constant(&self) -> Option<(bool, &str)> {
+        match self {
+            Pattern::Node(id) => Some((true, id)),
+            Pattern::Literal(lit) => Some((false, lit)),
+            _ => None,
+        }
+    }
+
     /// Check if this pattern matches a node
     pub fn matches_node(&self, node: &NodeId, bindings: &mut Bindings) -> bool {
         let node_str = node_id_to_string(node);
@@ -454,7 +603,7 @@ impl Pattern {
                 if let Some(bound) = bindings.get(var) {
                     bound == &node_str
                 } else {
-                    bindings.bind(var.clone(), node_str);
+                    bindings.bind_value(var.clone(), Value::Node(node.clone()));
                     true
                 }
             }
@@ -476,11 +625,10 @@ impl Pattern {
             }
             (Pattern::Literal(lit), Value::String(val)) => val == lit,
             (Pattern::Variable(var), val) => {
-                let val_str = value_to_string(val);
                 if let Some(bound) = bindings.get(var) {
-                    bound == &val_str
+                    bound == &value_to_string(val)
                 } else {
-                    bindings.bind(var.clone(), val_str);
+                    bindings.bind_value(var.clone(), val.clone());
                     true
                 }
             }
@@ -526,7 +674,10 @@ impl TriplePattern {
     pub fn instantiate(&self, bindings: &Bindings) -> Option<Triple> {
         let subject = match &self.subject {
             Pattern::Node(id) => NodeId::named(id),
-            Pattern::Variable(var) => NodeId::named(bindings.get(var)?),
+            Pattern::Variable(var) => match bindings.value(var) {
+                Some(Value::Node(node)) => node.clone(),
+                _ => NodeId::named(bindings.get(var)?),
+            },
             _ => return None,
         };
 
@@ -535,15 +686,18 @@ impl TriplePattern {
         let object = match &self.object {
             Pattern::Node(id) => Value::Node(NodeId::named(id)),
             Pattern::Literal(lit) => Value::String(lit.clone()),
-            Pattern::Variable(var) => {
-                let val = bindings.get(var)?;
-                // Try to parse as node or literal
-                if val.contains(':') {
-                    Value::Node(NodeId::named(val))
-                } else {
-                    Value::String(val.clone())
+            Pattern::Variable(var) => match bindings.value(var) {
+                Some(value) => value.clone(),
+                None => {
+                    let val = bindings.get(var)?;
+                    // Try to parse as node or literal
+                    if val.contains(':') {
+                        Value::Node(NodeId::named(val))
+                    } else {
+                        Value::String(val.clone())
+                    }
                 }
-            }
+            },
             _ => return None,
         };
 
@@ -555,6 +709,8 @@ impl TriplePattern {
 #[derive(Debug, Clone, Default)]
 pub struct Bindings {
     values: HashMap<String, String>,
+    /// Values of variables bound from graph terms or built-in results
+    terms: HashMap<String, Value>,
 }
 
 impl Bindings {
@@ -565,9 +721,21 @@ impl Bindings {
 
     /// Bind a variable to a value
     pub fn bind(&mut self, var: String, value: String) {
+        self.terms.remove(&var);
         self.values.insert(var, value);
     }
 
+    /// Bind a variable to a graph value, keeping its type
+    pub fn bind_value(&mut self, var: String, value: Value) {
+        self.values.insert(var.clone(), value_to_string(&value));
+        self.terms.insert(var, value);
+    }
+
+    /// Get the typed value of a variable bound with [`Bindings::bind_value`]
+    pub fn value(&self, var: &str) -> Option<&Value> {
+        self.terms.get(var)
+    }
+
     /// Get a bound value
     pub fn get(&self, var: &str) -> Option<&String> {
         self.values.get(var)
@@ -580,12 +748,17 @@ impl Bindings {
 
     /// Extend with another set of bindings
     pub fn extend(&mut self, other: &Bindings) {
+        for var in other.values.keys() {
+            self.terms.remove(var);
+        }
         self.values.extend(other.values.clone());
+        self.terms.extend(other.terms.clone());
     }
 
     /// Clear all bindings
     pub fn clear(&mut self) {
         self.values.clear();
+        self.terms.clear();
     }
 }
 
@@ -620,13 +793,47 @@ impl RuleSet {
         self.rules.iter().filter(|r| r.kind == kind).collect()
     }
 
-    /// Get all enabled rules sorted by priority
+    /// Get all enabled rules in evaluation order: highest priority first,
+    /// ties broken by rule ID
     pub fn enabled_sorted(&self) -> Vec<&Rule> {
         let mut rules: Vec<_> = self.rules.iter().filter(|r| r.enabled).collect();
-        rules.sort_by(|a, b| b.priority.cmp(&a.priority));
+        rules.sort_by(|a, b| b.priority.cmp(&a.priority).then_with(|| a.id.cmp(&b.id)));
         rules
     }
 
+    /// Warn about enabled rules that can derive contradictory conclusions
+    /// but that `strategy` cannot rank.
+    ///
+    /// Two inference rules can contradict each other when they infer the
+    /// same predicate with different constant objects (e.g. a `status` of
+    /// `approved` and one of `rejected`). Such a pair is reported when both
+    /// rules share a priority, since the engine then falls back to rule ID
+    /// order; under [`ConflictStrategy::MostSpecificWins`], only when they
+    /// also have the same number of conditions.
+    pub fn conflict_warnings(&self, strategy: ConflictStrategy) -> Vec<String> {
+        let rules = self.enabled_sorted();
+        let mut warnings = Vec::new();
+        for (i, a) in rules.iter().enumerate() {
+            for b in &rules[i + 1..] {
+                if a.priority != b.priority {
+                    continue;
+                }
+                if strategy == ConflictStrategy::MostSpecificWins
+                    && a.conditions.len() != b.conditions.len()
+                {
+                    continue;
+                }
+                if let Some(predicate) = a.conflicting_predicate(b) {
+                    warnings.push(format!(
+                        "rules `{}` and `{}` may infer different `{}` values at the same priority {}",
+                        a.id, b.id, predicate, a.priority
+                    ));
+                }
+            }
+        }
+        warnings
+    }
+
     /// Enable/disable a rule by ID
     pub fn set_enabled(&mut self, id: &str, enabled: bool) -> bool {
         if let Some(rule) = self.rules.iter_mut().find(|r| r.id == id) {
@@ -659,9 +866,15 @@ fn node_id_to_string(node: &NodeId) -> String {
         NodeId::Named(s) => s.clone(),
         NodeId::Hash(h) => format!("hash:{}", hex::encode(h)),
         NodeId::Blank(id) => format!("_:b{}", id),
+        NodeId::Anonymous(id) => format!("_:a{}", hex::encode(id)),
     }
 }
 
+/// Check whether a triple's object is the node it is stated about
+fn is_self_reference(triple: &Triple) -> bool {
+    matches!(&triple.object, Value::Node(node) if node == &triple.subject)
+}
+
 /// Convert a Value to a string for binding purposes
 fn value_to_string(value: &Value) -> String {
     match value {
@@ -752,6 +965,68 @@ mod tests {
         assert_eq!(sorted[2].id, "r3");
     }
 
+    #[test]
+    fn test_enabled_sorted_breaks_ties_by_id() {
+        let mut ruleset = RuleSet::new("test");
+        ruleset.add(Rule::integrity("c").build());
+        ruleset.add(Rule::integrity("a").build());
+        ruleset.add(Rule::integrity("d").priority(1).build());
+        ruleset.add(Rule::integrity("b").build());
+
+        let ids: Vec<_> = ruleset
+            .enabled_sorted()
+            .iter()
+            .map(|r| r.id.as_str())
+            .collect();
+        assert_eq!(ids, ["d", "a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_conflict_warnings() {
+        let status = |value: &str| {
+            TriplePattern::new(
+                Pattern::Variable("x".to_string()),
+                "status",
+                Pattern::Literal(value.to_string()),
+            )
+        };
+        let mut ruleset = RuleSet::new("test");
+        ruleset.add(
+            Rule::inference("approve")
+                .when_predicate("review")
+                .infer(status("approved"))
+                .build(),
+        );
+        ruleset.add(
+            Rule::inference("reject")
+                .when_predicate("review")
+                .when_object(Pattern::Literal("flagged".to_string()))
+                .infer(status("rejected"))
+                .build(),
+        );
+        // Agrees with `approve`, so it cannot conflict
+        ruleset.add(
+            Rule::inference("also_approve")
+                .when_predicate("audit")
+                .infer(status("approved"))
+                .build(),
+        );
+
+        let warnings = ruleset.conflict_warnings(ConflictStrategy::RejectWithError);
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings.iter().all(|w| w.contains("`reject`")));
+        // The rules differ in condition count, so specificity ranks them
+        assert!(ruleset
+            .conflict_warnings(ConflictStrategy::MostSpecificWins)
+            .iter()
+            .all(|w| !w.contains("`approve`")));
+
+        ruleset.rules[1].priority = 1;
+        assert!(ruleset
+            .conflict_warnings(ConflictStrategy::HighestPriorityWins)
+            .is_empty());
+    }
+
     #[test]
     fn test_rule_kind() {
         assert_eq!(RuleKind::Integrity.prefix(), "int");
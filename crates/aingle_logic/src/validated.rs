@@ -0,0 +1,337 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Validated graph writes
+//!
+//! [`ValidatedGraph`] puts a [`RuleEngine`] in front of a [`GraphDB`]: a
+//! triple only reaches the graph after it passes validation against the
+//! rules and the live graph, and the [`LogicProof`] that justified accepting
+//! it is stored with it. The proof travels in the triple's [`TripleMeta`]
+//! under [`PROOF_PROPERTY`], as canonical bytes, and is read back with
+//! [`ValidatedGraph::proof_for`].
+//!
+//! Triples derived by the engine's inference rules can be inserted as well.
+//! They are validated like any other triple and marked with
+//! [`INFERRED_PROPERTY`] and the [`RULE_PROPERTY`] that produced them.
+//!
+//! # Example
+//!
+//! ```
+//! use aingle_graph::{GraphDB, NodeId, Predicate, Triple, Value};
+//! use aingle_logic::{ProofVerifier, RuleEngine, ValidatedGraph};
+//!
+//! # fn main() -> aingle_logic::Result<()> {
+//! let graph = ValidatedGraph::new(GraphDB::memory()?, RuleEngine::new());
+//!
+//! let (id, _) = graph.insert_validated(Triple::new(
+//!     NodeId::named("socrates"),
+//!     Predicate::named("is"),
+//!     Value::Node(NodeId::named("mortal")),
+//! ))?;
+//!
+//! let proof = graph.proof_for(&id)?.expect("stored with the triple");
+//! assert!(ProofVerifier::new().verify(&proof).is_valid);
+//!
+//! // Contradicts the stored triple, so nothing is written
+//! let rejected = graph.insert_validated(Triple::new(
+//!     NodeId::named("socrates"),
+//!     Predicate::named("is_not"),
+//!     Value::Node(NodeId::named("mortal")),
+//! ));
+//! assert!(rejected.is_err());
+//! assert_eq!(graph.graph().count(), 1);
+//! # Ok(())
+//! # }
+//! ```
+
+use aingle_graph::{GraphDB, Triple, TripleId, TripleMeta};
+use log::{debug, warn};
+
+use crate::engine::RuleEngine;
+use crate::error::{Error, Result};
+use crate::proof::{LogicProof, ProofConclusion, ProofStep};
+use crate::rule::Bindings;
+use crate::validator::{LogicValidator, PoLValidator, ValidationResult};
+
+/// [`TripleMeta`] property holding the canonical bytes of the triple's proof
+pub const PROOF_PROPERTY: &str = "logic:proof";
+
+/// [`TripleMeta`] property set to `"true"` on triples derived by inference
+pub const INFERRED_PROPERTY: &str = "logic:inferred";
+
+/// [`TripleMeta`] property naming the rule that derived an inferred triple
+pub const RULE_PROPERTY: &str = "logic:rule";
+
+/// A [`GraphDB`] that only accepts triples passing a [`RuleEngine`]'s
+/// validation, and stores the proof of each accepted triple with it
+pub struct ValidatedGraph {
+    /// The graph triples are written to
+    graph: GraphDB,
+    /// Validator wrapping the rule engine, for the checks against the live graph
+    validator: PoLValidator,
+    /// Whether accepted inserts are followed by inserting derived triples
+    insert_inferred: bool,
+}
+
+impl ValidatedGraph {
+    /// Creates a `ValidatedGraph` validating writes to `graph` with `engine`.
+    ///
+    /// Derived triples are not inserted until enabled with
+    /// [`set_insert_inferred`](Self::set_insert_inferred).
+    pub fn new(graph: GraphDB, engine: RuleEngine) -> Self {
+        Self {
+            graph,
+            validator: PoLValidator::with_engine(engine),
+            insert_inferred: false,
+        }
+    }
+
+    /// Sets whether each accepted insert also inserts the triples the
+    /// engine's inference rules derive from the graph.
+    pub fn set_insert_inferred(&mut self, enabled: bool) {
+        self.insert_inferred = enabled;
+    }
+
+    /// Returns the underlying graph, for reads.
+    ///
+    /// Writing through it bypasses validation.
+    pub fn graph(&self) -> &GraphDB {
+        &self.graph
+    }
+
+    /// Returns the validator, e.g. to add contradiction pairs.
+    pub fn validator_mut(&mut self) -> &mut PoLValidator {
+        &mut self.validator
+    }
+
+    /// Consumes the `ValidatedGraph`, returning the underlying graph.
+    pub fn into_inner(self) -> GraphDB {
+        self.graph
+    }
+
+    /// Validates `triple` against the rules and the live graph, and stores
+    /// it with its proof if it is accepted.
+    ///
+    /// The triple's own metadata is kept; it is marked as validated and the
+    /// proof is added under [`PROOF_PROPERTY`].
+    ///
+    /// # Errors
+    ///
+    /// [`Error::Rejected`] with the full [`ValidationResult`] if validation
+    /// fails, in which case the graph is left unchanged.
+    pub fn insert_validated(&self, triple: Triple) -> Result<(TripleId, LogicProof)> {
+        let result = self.check(&triple)?;
+
+        let mut proof = LogicProof::new(ProofConclusion::Triple((&triple).into()));
+        proof.add_step(ProofStep::fact(1, &triple));
+        for (i, warning) in result.warnings.iter().enumerate() {
+            proof
+                .metadata
+                .insert(format!("warning.{}", i), warning.message.clone());
+        }
+        proof.finalize();
+
+        let meta = triple.meta.clone();
+        let id = self.store(triple, meta, &proof)?;
+
+        if self.insert_inferred {
+            self.insert_derived()?;
+        }
+
+        Ok((id, proof))
+    }
+
+    /// Inserts the triples the engine's inference rules derive from the
+    /// graph, each with a proof naming the rule.
+    ///
+    /// Derived triples that fail validation are skipped. Returns the IDs of
+    /// the triples inserted.
+    pub fn insert_derived(&self) -> Result<Vec<TripleId>> {
+        let derived = self.validator.engine().forward_chain(&self.graph)?;
+        let mut ids = Vec::with_capacity(derived.inferences.len());
+
+        for (rule_id, triple) in derived.inferences {
+            if let Err(e) = self.check(&triple) {
+                warn!("Skipped triple derived by {}: {}", rule_id, e);
+                continue;
+            }
+
+            let mut proof = LogicProof::new(ProofConclusion::Triple((&triple).into()));
+            proof.add_step(ProofStep::inference(
+                1,
+                rule_id.as_str(),
+                vec![],
+                &triple,
+                &Bindings::new(),
+                1,
+            ));
+            proof.finalize();
+
+            let meta = TripleMeta::new()
+                .with_source("inference")
+                .with_property(INFERRED_PROPERTY, "true")
+                .with_property(RULE_PROPERTY, rule_id.as_str());
+            ids.push(self.store(triple, meta, &proof)?);
+        }
+
+        debug!("Inserted {} derived triples", ids.len());
+        Ok(ids)
+    }
+
+    /// Returns the proof stored with a triple, if it was accepted through
+    /// this type.
+    ///
+    /// When a triple was accepted more than once, the latest proof is returned.
+    pub fn proof_for(&self, id: &TripleId) -> Result<Option<LogicProof>> {
+        self.graph
+            .provenance(id)?
+            .iter()
+            .rev()
+            .find_map(|meta| meta.properties.get(PROOF_PROPERTY))
+            .map(|bytes| LogicProof::from_canonical_bytes(bytes.as_bytes()))
+            .transpose()
+    }
+
+    /// Validates a triple in the context of the graph.
+    fn check(&self, triple: &Triple) -> Result<ValidationResult> {
+        let result = self.validator.validate_with_context(triple, &self.graph)?;
+        if !result.is_valid() {
+            return Err(Error::Rejected(Box::new(result)));
+        }
+        Ok(result)
+    }
+
+    /// Writes an accepted triple with its proof.
+    fn store(&self, triple: Triple, meta: TripleMeta, proof: &LogicProof) -> Result<TripleId> {
+        let bytes = proof.to_canonical_bytes()?;
+        let encoded =
+            String::from_utf8(bytes).map_err(|e| Error::SerializationError(e.to_string()))?;
+        let meta = meta.validated().with_property(PROOF_PROPERTY, encoded);
+        Ok(self.graph.insert_with_meta(triple, meta)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rule::{Pattern, Rule, RuleSet, TriplePattern};
+    use crate::validator::ErrorKind;
+    use crate::ProofVerifier;
+    use aingle_graph::{NodeId, Predicate, Value};
+
+    fn fact(subject: &str, predicate: &str, object: &str) -> Triple {
+        Triple::new(
+            NodeId::named(subject),
+            Predicate::named(predicate),
+            Value::Node(NodeId::named(object)),
+        )
+    }
+
+    #[test]
+    fn test_valid_insert_stores_verifiable_proof() {
+        let graph = ValidatedGraph::new(GraphDB::memory().unwrap(), RuleEngine::new());
+
+        let (id, proof) = graph
+            .insert_validated(fact("socrates", "is", "mortal"))
+            .unwrap();
+        assert!(graph.graph().get(&id).unwrap().is_some());
+        assert!(graph.graph().provenance(&id).unwrap()[0].validated);
+
+        let stored = graph.proof_for(&id).unwrap().unwrap();
+        assert_eq!(stored.id, proof.id);
+        assert_eq!(stored.hash, proof.hash);
+
+        let verifier = ProofVerifier::new();
+        assert!(verifier.verify(&stored).is_valid);
+        assert!(verifier
+            .verify_against(&stored, graph.graph())
+            .is_complete());
+    }
+
+    #[test]
+    fn test_contradiction_is_rejected_without_writing() {
+        let graph = ValidatedGraph::new(GraphDB::memory().unwrap(), RuleEngine::new());
+        graph
+            .insert_validated(fact("socrates", "is", "mortal"))
+            .unwrap();
+        let count = graph.graph().count();
+
+        let contradiction = fact("socrates", "is_not", "mortal");
+        let err = graph.insert_validated(contradiction.clone()).unwrap_err();
+        let Error::Rejected(result) = err else {
+            panic!("expected a rejection, got {:?}", err);
+        };
+        assert!(!result.is_valid());
+        assert!(result
+            .errors
+            .iter()
+            .any(|e| e.kind == ErrorKind::Contradiction));
+
+        assert_eq!(graph.graph().count(), count);
+        assert!(!graph.graph().contains(&contradiction).unwrap());
+        assert!(graph.proof_for(&contradiction.id()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_proof_for_unvalidated_triple() {
+        let graph = ValidatedGraph::new(GraphDB::memory().unwrap(), RuleEngine::new());
+        let id = graph.graph().insert(fact("alice", "knows", "bob")).unwrap();
+        assert!(graph.proof_for(&id).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_inferred_triples_are_marked() {
+        let mut rules = RuleSet::new("family");
+        rules.add(
+            Rule::inference("inverse_parent_child")
+                .when_subject(Pattern::Variable("s".to_string()))
+                .when_predicate("parent_of")
+                .when_object(Pattern::Variable("o".to_string()))
+                .infer(TriplePattern::new(
+                    Pattern::Variable("o".to_string()),
+                    "child_of",
+                    Pattern::Variable("s".to_string()),
+                ))
+                .build(),
+        );
+        let mut graph =
+            ValidatedGraph::new(GraphDB::memory().unwrap(), RuleEngine::with_rules(rules));
+
+        // Off by default
+        graph
+            .insert_validated(fact("alice", "parent_of", "bob"))
+            .unwrap();
+        assert_eq!(graph.graph().count(), 1);
+
+        graph.set_insert_inferred(true);
+        graph
+            .insert_validated(fact("carol", "parent_of", "dave"))
+            .unwrap();
+        assert_eq!(graph.graph().count(), 4);
+
+        let derived = graph
+            .graph()
+            .find(
+                aingle_graph::TriplePattern::subject(NodeId::named("dave"))
+                    .with_predicate(Predicate::named("child_of")),
+            )
+            .unwrap();
+        assert_eq!(derived.len(), 1);
+        let id = derived[0].id();
+
+        let meta = &graph.graph().provenance(&id).unwrap()[0];
+        assert_eq!(meta.source.as_deref(), Some("inference"));
+        assert_eq!(
+            meta.properties.get(INFERRED_PROPERTY).map(String::as_str),
+            Some("true")
+        );
+        assert_eq!(
+            meta.properties.get(RULE_PROPERTY).map(String::as_str),
+            Some("inverse_parent_child")
+        );
+
+        let proof = graph.proof_for(&id).unwrap().unwrap();
+        assert_eq!(proof.rules_used(), vec!["inverse_parent_child"]);
+        assert!(ProofVerifier::new().verify(&proof).is_valid);
+    }
+}
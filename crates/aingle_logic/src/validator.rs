@@ -14,7 +14,7 @@ use std::collections::HashMap;
 use aingle_graph::{GraphDB, NodeId, Predicate, Triple, TriplePattern, Value};
 use serde::{Deserialize, Serialize};
 
-use crate::engine::RuleEngine;
+use crate::engine::{EngineStats, RuleEngine};
 use crate::error::Result;
 use crate::rule::{Rule, RuleSet};
 
@@ -65,6 +65,17 @@ pub trait LogicValidator {
 
     /// Returns the general severity level configured for this validator.
     fn severity(&self) -> Severity;
+
+    /// Returns the statistics of the rule engine behind this validator,
+    /// including per-rule profiles when profiling is enabled.
+    ///
+    /// Validators that do not evaluate rules return `None`.
+    fn engine_stats(&self) -> Option<EngineStats> {
+        None
+    }
+
+    /// Resets the statistics returned by [`engine_stats`](Self::engine_stats).
+    fn clear_stats(&self) {}
 }
 
 /// The main implementation of `LogicValidator`, utilizing a `RuleEngine`.
@@ -167,6 +178,15 @@ impl PoLValidator {
         self.engine.add_rule(rule);
     }
 
+    /// Enables or disables per-rule profiling in the internal `RuleEngine`.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to record rule profiles.
+    pub fn set_profiling(&mut self, enabled: bool) {
+        self.engine.set_profiling(enabled);
+    }
+
     /// Returns an immutable reference to the internal `RuleEngine`.
     pub fn engine(&self) -> &RuleEngine {
         &self.engine
@@ -362,6 +382,11 @@ impl LogicValidator for PoLValidator {
             });
         }
 
+        // Report inferences that lost a rule conflict
+        for conflict in engine_result.suppressed {
+            result.info.push(conflict.to_string());
+        }
+
         Ok(result)
     }
 
@@ -448,6 +473,16 @@ impl LogicValidator for PoLValidator {
     fn severity(&self) -> Severity {
         self.severity
     }
+
+    /// Returns the statistics of the internal `RuleEngine`.
+    fn engine_stats(&self) -> Option<EngineStats> {
+        Some(self.engine.stats())
+    }
+
+    /// Resets the statistics of the internal `RuleEngine`.
+    fn clear_stats(&self) {
+        self.engine.clear_stats();
+    }
 }
 
 /// The comprehensive result of a validation process, including errors, warnings, and informational messages.
@@ -459,7 +494,8 @@ pub struct ValidationResult {
     pub errors: Vec<ValidationError>,
     /// A list of `ValidationWarning`s found (non-fatal issues).
     pub warnings: Vec<ValidationWarning>,
-    /// A list of informational messages (currently unused).
+    /// A list of informational messages, such as inferences suppressed by a
+    /// rule conflict.
     pub info: Vec<String>,
 }
 
@@ -680,6 +716,7 @@ fn node_to_string(node: &NodeId) -> String {
         NodeId::Named(s) => s.clone(),
         NodeId::Hash(h) => format!("hash:{}", hex::encode(&h[..8])),
         NodeId::Blank(id) => format!("_:b{}", id),
+        NodeId::Anonymous(id) => format!("_:a{}", hex::encode(&id[..8])),
     }
 }
 
@@ -802,4 +839,54 @@ mod tests {
         assert!(Severity::Warning < Severity::Error);
         assert!(Severity::Error < Severity::Critical);
     }
+
+    #[test]
+    fn test_rule_profile_through_validator() {
+        let mut validator = PoLValidator::new();
+        for i in 0..50 {
+            validator.add_rule(
+                Rule::integrity(format!("cheap_{}", i))
+                    .when_predicate(format!("p{}", i))
+                    .reject("unexpected")
+                    .build(),
+            );
+        }
+        validator.add_rule(
+            Rule::integrity("expensive")
+                .when(|_| {
+                    std::thread::sleep(std::time::Duration::from_millis(2));
+                    false
+                })
+                .reject("never")
+                .build(),
+        );
+        validator.set_profiling(true);
+
+        let graph = GraphDB::memory().unwrap();
+        let triple = Triple::new(
+            NodeId::named("alice"),
+            Predicate::named("knows"),
+            Value::Node(NodeId::named("bob")),
+        );
+        let validator: &dyn LogicValidator = &validator;
+        for _ in 0..3 {
+            assert!(validator
+                .validate_with_context(&triple, &graph)
+                .unwrap()
+                .is_valid());
+        }
+
+        let stats = validator.engine_stats().unwrap();
+        assert_eq!(stats.validations, 3);
+        assert_eq!(stats.rule_profiles.len(), 51);
+        let top = stats.top_rules_by_time(1);
+        assert_eq!(top[0].0, "expensive");
+        assert_eq!(top[0].1.evaluations, 3);
+        assert_eq!(top[0].1.matches, 0);
+
+        validator.clear_stats();
+        let stats = validator.engine_stats().unwrap();
+        assert_eq!(stats.validations, 0);
+        assert!(stats.rule_profiles.is_empty());
+    }
 }
@@ -11,21 +11,27 @@
 //! - Block-wise transfer for large payloads (RFC 7959)
 //! - Multicast discovery
 //! - Resource-based routing
+//! - JSON or CBOR payloads, chosen per peer and marked with Content-Format
 //!
 //! # Resources
 //! - `/.well-known/core` - CoRE Link Format discovery
 //! - `/gossip` - Gossip protocol messages
 //! - `/record` - Record retrieval
 //! - `/announce` - New record announcements
-//! - `/ping` - Liveness checks
+//! - `/ping` - Liveness checks and encoding handshake
+//! - `/metrics` - Node health snapshot (CBOR)
 
 use coap_lite::{CoapOption, MessageClass, MessageType, Packet, RequestType, ResponseType};
 
+use crate::config::{Config, ConfigError};
 use crate::error::{Error, Result};
+use crate::health::HealthHandle;
 use crate::network::Message;
+use crate::wire::WireEncoding;
 use async_io::Async;
-use std::collections::HashMap;
+use std::collections::{btree_map, BTreeMap, HashMap};
 use std::net::{SocketAddr, UdpSocket};
+use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
 /// Maximum CoAP message size (for non-block transfers)
@@ -34,6 +40,12 @@ pub const COAP_MAX_MESSAGE_SIZE: usize = 1024;
 /// Default CoAP port
 pub const COAP_DEFAULT_PORT: u16 = 5683;
 
+/// Content-Format for `application/json`
+pub const CONTENT_FORMAT_JSON: u8 = 50;
+
+/// Content-Format for `application/cbor`
+pub const CONTENT_FORMAT_CBOR: u8 = 60;
+
 /// CoAP multicast address for discovery (IPv4)
 pub const COAP_MULTICAST_IPV4: &str = "224.0.1.187";
 
@@ -44,6 +56,15 @@ pub const COAP_MULTICAST_IPV6: &str = "ff02::fd";
 /// Block size for block-wise transfers (256 bytes = SZX 2)
 pub const BLOCK_SIZE: usize = 256;
 
+/// Smallest block size allowed by RFC 7959 (SZX 0)
+pub const MIN_BLOCK_SIZE: usize = 16;
+
+/// Largest block size allowed by RFC 7959 (SZX 6)
+pub const MAX_BLOCK_SIZE: usize = 1024;
+
+/// Idle time after which a half-finished block transfer is discarded
+pub const BLOCK_TRANSFER_TIMEOUT_MS: u64 = 30_000;
+
 /// Maximum retransmissions for CON messages
 pub const MAX_RETRANSMIT: u8 = 4;
 
@@ -66,16 +87,306 @@ struct PendingRequest {
     retransmits: u8,
 }
 
-/// Block transfer state
+/// Block transfer state for a response body served block by block (Block2)
 #[derive(Debug, Clone)]
-#[allow(dead_code)]
 struct BlockState {
     /// Full data being transferred
     data: Vec<u8>,
-    /// Current block number
+    /// Last block number served
     block_num: u32,
     /// More blocks flag
     more: bool,
+    /// Content-Format of `data`
+    content_format: u8,
+}
+
+/// Identifies a block-wise transfer: remote endpoint plus request token.
+///
+/// Every block of one transfer carries the same token, which is how the
+/// blocks sent by [`CoapServer::send`] are matched on the receiving node.
+type TransferKey = (SocketAddr, Token);
+
+/// Block1/Block2 option value (RFC 7959 §2.2)
+///
+/// Encoded on the wire as `NUM | M | SZX`, where the block size is
+/// `2^(SZX + 4)` bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockOption {
+    /// Block number (0-based)
+    pub num: u32,
+    /// More blocks follow this one
+    pub more: bool,
+    /// Size exponent
+    pub szx: u8,
+}
+
+impl BlockOption {
+    /// Create a block option for the given block size
+    pub fn new(num: u32, more: bool, block_size: usize) -> Result<Self> {
+        if num >= 1 << 20 {
+            return Err(Error::network(format!("Block number {} out of range", num)));
+        }
+        Ok(Self {
+            num,
+            more,
+            szx: Self::szx_for(block_size)?,
+        })
+    }
+
+    /// Map a block size (power of two, 16–1024) to its SZX exponent
+    pub fn szx_for(block_size: usize) -> Result<u8> {
+        if !(MIN_BLOCK_SIZE..=MAX_BLOCK_SIZE).contains(&block_size) || !block_size.is_power_of_two()
+        {
+            return Err(Error::Config(ConfigError::Invalid(format!(
+                "CoAP block size must be a power of two between {} and {}, got {}",
+                MIN_BLOCK_SIZE, MAX_BLOCK_SIZE, block_size
+            ))));
+        }
+        Ok((block_size.trailing_zeros() - 4) as u8)
+    }
+
+    /// Block size in bytes
+    pub fn size(&self) -> usize {
+        1 << (self.szx as usize + 4)
+    }
+
+    /// Byte offset of this block within the full body
+    pub fn offset(&self) -> usize {
+        self.num as usize * self.size()
+    }
+
+    /// Encode as a CoAP uint option value
+    pub fn encode(&self) -> Vec<u8> {
+        let value = (self.num << 4) | (if self.more { 0x08 } else { 0x00 }) | self.szx as u32;
+        encode_uint(value)
+    }
+
+    /// Decode a Block1/Block2 option value
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() > 3 {
+            return Err(Error::network(format!(
+                "Block option too long: {} bytes",
+                bytes.len()
+            )));
+        }
+        let value = bytes.iter().fold(0u32, |acc, b| (acc << 8) | *b as u32);
+        let szx = (value & 0x07) as u8;
+        if szx == 7 {
+            return Err(Error::network(
+                "Block option uses reserved SZX 7".to_string(),
+            ));
+        }
+        Ok(Self {
+            num: value >> 4,
+            more: value & 0x08 != 0,
+            szx,
+        })
+    }
+
+    /// Read a block option from a packet, if present
+    pub fn from_packet(packet: &Packet, option: CoapOption) -> Result<Option<Self>> {
+        match packet
+            .get_option(option)
+            .and_then(|values| values.iter().next())
+        {
+            Some(bytes) => Self::decode(bytes).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Encode an integer as a CoAP uint option value (at least one byte)
+fn encode_uint(value: u32) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let skip = (value.leading_zeros() / 8).min(3) as usize;
+    bytes[skip..].to_vec()
+}
+
+/// Decode a CoAP uint option value (empty means zero)
+fn decode_uint(bytes: &[u8]) -> u32 {
+    bytes
+        .iter()
+        .fold(0u32, |value, &byte| (value << 8) | u32::from(byte))
+}
+
+/// Block-wise transfer settings (RFC 7959)
+#[derive(Debug, Clone)]
+pub struct BlockwiseConfig {
+    /// Block size in bytes (power of two, 16–1024)
+    pub block_size: usize,
+    /// Maximum bytes buffered across all inbound transfers at once
+    pub max_total_size: usize,
+    /// Half-finished transfers idle for longer than this are discarded
+    pub transfer_timeout: Duration,
+}
+
+impl Default for BlockwiseConfig {
+    fn default() -> Self {
+        Self::for_memory_limit(Config::default().memory_limit)
+    }
+}
+
+impl BlockwiseConfig {
+    /// Settings derived from the node's memory budget
+    ///
+    /// Reassembly may use at most a quarter of [`Config::memory_limit`], so a
+    /// peer streaming an oversized body cannot exhaust the node.
+    pub fn for_memory_limit(memory_limit: usize) -> Self {
+        Self {
+            block_size: BLOCK_SIZE,
+            max_total_size: memory_limit / 4,
+            transfer_timeout: Duration::from_millis(BLOCK_TRANSFER_TIMEOUT_MS),
+        }
+    }
+
+    /// Validate the block size and size budget
+    pub fn validate(&self) -> Result<()> {
+        BlockOption::szx_for(self.block_size)?;
+        if self.max_total_size < self.block_size {
+            return Err(Error::Config(ConfigError::Invalid(format!(
+                "CoAP max transfer size ({}) is smaller than the block size ({})",
+                self.max_total_size, self.block_size
+            ))));
+        }
+        Ok(())
+    }
+}
+
+/// Reassembly buffer for one inbound block-wise transfer (Block1 request
+/// bodies on the server side, Block2 response bodies on the client side)
+#[derive(Debug)]
+struct BlockAssembly {
+    /// Received blocks by number; tolerates reordering and duplicates
+    blocks: BTreeMap<u32, Vec<u8>>,
+    /// Block size fixed by the first block seen
+    block_size: usize,
+    /// Number of the final block, once it has arrived
+    last_block: Option<u32>,
+    /// Bytes buffered so far
+    buffered: usize,
+    /// Last time the transfer made progress
+    updated_at: Instant,
+    /// Request for the next Block2 block, resent if its response is lost
+    follow_up: Option<Packet>,
+    /// Times `follow_up` has been resent
+    retries: u8,
+}
+
+impl BlockAssembly {
+    fn new(block_size: usize, now: Instant) -> Self {
+        Self {
+            blocks: BTreeMap::new(),
+            block_size,
+            last_block: None,
+            buffered: 0,
+            updated_at: now,
+            follow_up: None,
+            retries: 0,
+        }
+    }
+}
+
+/// Result of feeding one block into a reassembly buffer
+#[derive(Debug, PartialEq)]
+enum BlockProgress {
+    /// Block stored; more blocks are needed
+    Continue,
+    /// Every block arrived; the reassembled body
+    Complete(Vec<u8>),
+    /// Accepting the block would exceed the configured size budget
+    TooLarge,
+    /// Block size changed mid-transfer or the payload length is wrong
+    Invalid,
+}
+
+/// Block-wise transfer state shared by the receive path
+#[derive(Debug, Default)]
+struct BlockTransfers {
+    /// Bodies being reassembled
+    inbound: HashMap<TransferKey, BlockAssembly>,
+    /// Response bodies being served block by block, with last access time
+    outbound: HashMap<TransferKey, (BlockState, Instant)>,
+    /// Pending message IDs acknowledged since the last retransmission pass
+    acked: Vec<u16>,
+}
+
+impl BlockTransfers {
+    /// Bytes buffered across all inbound transfers
+    fn buffered(&self) -> usize {
+        self.inbound.values().map(|a| a.buffered).sum()
+    }
+
+    /// Store one block and report whether the body is complete
+    fn accept(
+        &mut self,
+        key: TransferKey,
+        block: BlockOption,
+        payload: &[u8],
+        config: &BlockwiseConfig,
+        now: Instant,
+    ) -> BlockProgress {
+        let size = block.size();
+        if payload.len() > size || (block.more && payload.len() != size) {
+            self.inbound.remove(&key);
+            return BlockProgress::Invalid;
+        }
+
+        let buffered_elsewhere = self.buffered();
+        let assembly = self
+            .inbound
+            .entry(key.clone())
+            .or_insert_with(|| BlockAssembly::new(size, now));
+
+        let conflicting_end = match assembly.last_block {
+            Some(last) => block.num > last || (block.num == last) == block.more,
+            None => {
+                !block.more
+                    && assembly
+                        .blocks
+                        .keys()
+                        .next_back()
+                        .is_some_and(|&highest| highest > block.num)
+            }
+        };
+        if assembly.block_size != size || conflicting_end {
+            self.inbound.remove(&key);
+            return BlockProgress::Invalid;
+        }
+
+        let too_large = !assembly.blocks.contains_key(&block.num)
+            && (block.offset() + payload.len() > config.max_total_size
+                || buffered_elsewhere + payload.len() > config.max_total_size);
+        if too_large {
+            self.inbound.remove(&key);
+            return BlockProgress::TooLarge;
+        }
+        if let btree_map::Entry::Vacant(slot) = assembly.blocks.entry(block.num) {
+            slot.insert(payload.to_vec());
+            assembly.buffered += payload.len();
+        }
+        if !block.more {
+            assembly.last_block = Some(block.num);
+        }
+        assembly.updated_at = now;
+
+        match assembly.last_block {
+            Some(last) if assembly.blocks.len() as u64 == last as u64 + 1 => {
+                let assembly = self.inbound.remove(&key).expect("assembly present");
+                BlockProgress::Complete(assembly.blocks.into_values().flatten().collect())
+            }
+            _ => BlockProgress::Continue,
+        }
+    }
+
+    /// Drop transfers that made no progress within `timeout`
+    fn expire(&mut self, timeout: Duration, now: Instant) -> usize {
+        let before = self.inbound.len() + self.outbound.len();
+        self.inbound
+            .retain(|_, assembly| now.duration_since(assembly.updated_at) <= timeout);
+        self.outbound
+            .retain(|_, (_, touched)| now.duration_since(*touched) <= timeout);
+        before - (self.inbound.len() + self.outbound.len())
+    }
 }
 
 /// CoAP Server for handling incoming requests
@@ -93,9 +404,14 @@ pub struct CoapServer {
     message_id: u16,
     /// Pending requests awaiting ACK
     pending_requests: HashMap<u16, PendingRequest>,
-    /// Block transfer states (for future block-wise receive)
-    #[allow(dead_code)]
-    block_states: HashMap<Token, BlockState>,
+    /// Block-wise transfer settings
+    blockwise: BlockwiseConfig,
+    /// In-flight block-wise transfers (updated from the receive path)
+    transfers: Mutex<BlockTransfers>,
+    /// Health snapshot served on `/metrics` (`None` when metrics are disabled)
+    health: Option<HealthHandle>,
+    /// Encoding agreed with each peer from its Ping or Pong (JSON until then)
+    peer_encodings: Mutex<HashMap<SocketAddr, WireEncoding>>,
     /// Running flag
     running: bool,
 }
@@ -110,11 +426,94 @@ impl CoapServer {
             node_id,
             message_id: rand::random(),
             pending_requests: HashMap::new(),
-            block_states: HashMap::new(),
+            blockwise: BlockwiseConfig::default(),
+            transfers: Mutex::new(BlockTransfers::default()),
+            health: None,
+            peer_encodings: Mutex::new(HashMap::new()),
             running: false,
         }
     }
 
+    /// Configure block-wise transfers (RFC 7959)
+    pub fn set_blockwise_config(&mut self, config: BlockwiseConfig) -> Result<()> {
+        config.validate()?;
+        self.blockwise = config;
+        Ok(())
+    }
+
+    /// Get the block-wise transfer settings
+    pub fn blockwise_config(&self) -> &BlockwiseConfig {
+        &self.blockwise
+    }
+
+    /// Serve the node's health snapshot on `/metrics`
+    pub fn set_health_source(&mut self, health: HealthHandle) {
+        self.health = Some(health);
+    }
+
+    /// Encoding used for messages sent to `addr`
+    ///
+    /// JSON until the peer's Ping or Pong shows it can read something smaller.
+    pub fn peer_encoding(&self, addr: &SocketAddr) -> WireEncoding {
+        self.peer_encodings
+            .lock()
+            .ok()
+            .and_then(|encodings| encodings.get(addr).copied())
+            .unwrap_or(WireEncoding::Json)
+    }
+
+    /// Remember the encoding to use towards a peer from its handshake message
+    fn note_handshake(&self, addr: &SocketAddr, message: &Message) {
+        let Some(theirs) = message.advertised_encodings() else {
+            return;
+        };
+        let encoding = WireEncoding::negotiate(&WireEncoding::supported(), theirs);
+        if let Ok(mut encodings) = self.peer_encodings.lock() {
+            if encodings.insert(*addr, encoding) != Some(encoding) {
+                log::debug!("Using {} encoding for {}", encoding, addr);
+            }
+        }
+    }
+
+    /// Decode a message body using the packet's Content-Format
+    ///
+    /// A packet without the option is treated as JSON, as older nodes sent.
+    fn decode_message(packet: &Packet, body: &[u8]) -> Result<Message> {
+        let encoding = match packet
+            .get_option(CoapOption::ContentFormat)
+            .and_then(|values| values.iter().next())
+        {
+            Some(bytes) => {
+                let content_format = decode_uint(bytes);
+                u8::try_from(content_format)
+                    .ok()
+                    .and_then(WireEncoding::from_content_format)
+                    .ok_or_else(|| {
+                        Error::Serialization(format!(
+                            "Unsupported Content-Format {}",
+                            content_format
+                        ))
+                    })?
+            }
+            None => WireEncoding::Json,
+        };
+        encoding.decode(body)
+    }
+
+    /// Encode a message for `addr`, returning the payload and its Content-Format
+    fn encode_message(&self, addr: &SocketAddr, message: &Message) -> Result<(Vec<u8>, u8)> {
+        let encoding = self.peer_encoding(addr);
+        Ok((encoding.encode(message)?, encoding.content_format()))
+    }
+
+    /// Number of block-wise transfers currently in flight
+    pub fn active_block_transfers(&self) -> usize {
+        self.transfers
+            .lock()
+            .map(|t| t.inbound.len() + t.outbound.len())
+            .unwrap_or(0)
+    }
+
     /// Start the CoAP server
     pub async fn start(&mut self) -> Result<()> {
         let addr = format!("{}:{}", self.bind_addr, self.port);
@@ -163,17 +562,148 @@ impl CoapServer {
                 let packet = Packet::from_bytes(&buf[..len])
                     .map_err(|e| Error::network(format!("Failed to parse CoAP packet: {:?}", e)))?;
 
-                // Process the packet and convert to Message
-                if let Some(msg) = self.process_packet(&packet, &addr)? {
-                    return Ok(Some((addr, msg)));
+                // Reassemble block-wise bodies, then convert to Message
+                let (message, replies) = self.handle_datagram(&packet, &addr, Instant::now())?;
+                for reply in &replies {
+                    self.send_packet(&addr, reply).await?;
                 }
-                Ok(None)
+                Ok(message.map(|msg| (addr, msg)))
             }
             Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => Ok(None),
             Err(e) => Err(Error::network(format!("Receive error: {}", e))),
         }
     }
 
+    /// Run an incoming packet through the block-wise layer
+    ///
+    /// Returns the message for the resource handlers once its body is
+    /// complete, plus any packets that must be sent back to `addr`
+    /// (2.31 Continue, Block2 blocks, follow-up requests or errors).
+    fn handle_datagram(
+        &self,
+        packet: &Packet,
+        addr: &SocketAddr,
+        now: Instant,
+    ) -> Result<(Option<Message>, Vec<Packet>)> {
+        let mut transfers = self
+            .transfers
+            .lock()
+            .map_err(|e| Error::Internal(format!("Failed to acquire transfers lock: {}", e)))?;
+
+        let expired = transfers.expire(self.blockwise.transfer_timeout, now);
+        if expired > 0 {
+            log::debug!("Discarded {} stale CoAP block transfers", expired);
+        }
+
+        if packet.header.get_type() == MessageType::Acknowledgement
+            && self
+                .pending_requests
+                .contains_key(&packet.header.message_id)
+        {
+            transfers.acked.push(packet.header.message_id);
+        }
+
+        let key = (*addr, packet.get_token().to_vec());
+
+        match packet.header.code {
+            MessageClass::Response(_) => {
+                // Only Block2 responses carry a body we have to reassemble
+                let Some(block) = BlockOption::from_packet(packet, CoapOption::Block2)? else {
+                    return Ok((None, Vec::new()));
+                };
+
+                match transfers.accept(key.clone(), block, &packet.payload, &self.blockwise, now) {
+                    BlockProgress::Continue => {
+                        let next = BlockOption {
+                            num: block.num + 1,
+                            more: false,
+                            szx: block.szx,
+                        };
+                        let follow_up = Self::block2_request(packet.get_token(), next);
+                        if let Some(assembly) = transfers.inbound.get_mut(&key) {
+                            assembly.follow_up = Some(follow_up.clone());
+                            assembly.retries = 0;
+                        }
+                        Ok((None, vec![follow_up]))
+                    }
+                    BlockProgress::Complete(body) => {
+                        drop(transfers);
+                        let msg = Self::decode_message(packet, &body)?;
+                        Ok((Some(msg), Vec::new()))
+                    }
+                    progress => {
+                        log::warn!("Dropped Block2 response from {}: {:?}", addr, progress);
+                        Ok((None, Vec::new()))
+                    }
+                }
+            }
+            MessageClass::Request(_) => {
+                // Follow-up request for a response body we are serving
+                if let Some(block) = BlockOption::from_packet(packet, CoapOption::Block2)? {
+                    if let Some((state, touched)) = transfers.outbound.get_mut(&key) {
+                        *touched = now;
+                        let reply = self.block2_reply(packet, state, block)?;
+                        return Ok((None, vec![reply]));
+                    }
+                    if block.num > 0 {
+                        let reply =
+                            Self::response_to(packet, ResponseType::RequestEntityIncomplete);
+                        return Ok((None, vec![reply]));
+                    }
+                }
+
+                if Self::extract_path(packet) == "/metrics" {
+                    drop(transfers);
+                    return Ok((None, vec![self.metrics_reply(addr, packet, now)?]));
+                }
+
+                let Some(block) = BlockOption::from_packet(packet, CoapOption::Block1)? else {
+                    drop(transfers);
+                    return Ok((self.process_packet(packet, addr)?, Vec::new()));
+                };
+
+                match transfers.accept(key, block, &packet.payload, &self.blockwise, now) {
+                    BlockProgress::Continue => {
+                        if packet.header.get_type() != MessageType::Confirmable {
+                            return Ok((None, Vec::new()));
+                        }
+                        let mut reply = Self::response_to(packet, ResponseType::Continue);
+                        reply.add_option(CoapOption::Block1, block.encode());
+                        Ok((None, vec![reply]))
+                    }
+                    BlockProgress::Complete(body) => {
+                        drop(transfers);
+                        log::debug!("Reassembled {} byte CoAP request from {}", body.len(), addr);
+                        let mut full = packet.clone();
+                        full.clear_option(CoapOption::Block1);
+                        full.clear_option(CoapOption::Size1);
+                        full.payload = body;
+                        Ok((self.process_packet(&full, addr)?, Vec::new()))
+                    }
+                    BlockProgress::TooLarge => {
+                        log::warn!(
+                            "Rejected CoAP block transfer from {}: exceeds {} bytes",
+                            addr,
+                            self.blockwise.max_total_size
+                        );
+                        let mut reply =
+                            Self::response_to(packet, ResponseType::RequestEntityTooLarge);
+                        reply.add_option(
+                            CoapOption::Size1,
+                            encode_uint(self.blockwise.max_total_size as u32),
+                        );
+                        Ok((None, vec![reply]))
+                    }
+                    BlockProgress::Invalid => {
+                        let reply = Self::response_to(packet, ResponseType::BadRequest);
+                        Ok((None, vec![reply]))
+                    }
+                }
+            }
+            _ => Ok((None, Vec::new())),
+        }
+    }
+
     /// Process incoming CoAP packet
     fn process_packet(&self, packet: &Packet, addr: &SocketAddr) -> Result<Option<Message>> {
         let path = Self::extract_path(packet);
@@ -191,21 +721,26 @@ impl CoapServer {
                 Ok(None) // Handled separately
             }
             "/ping" => {
-                // Liveness check
-                if !packet.payload.is_empty() {
-                    let node_id = String::from_utf8_lossy(&packet.payload).to_string();
-                    Ok(Some(Message::Ping { node_id }))
-                } else {
-                    Ok(Some(Message::Ping {
+                // Liveness check and encoding handshake
+                let msg = match Self::decode_message(packet, &packet.payload) {
+                    Ok(msg @ (Message::Ping { .. } | Message::Pong { .. })) => msg,
+                    // Plain node id, or no payload at all
+                    _ if !packet.payload.is_empty() => Message::Ping {
+                        node_id: String::from_utf8_lossy(&packet.payload).to_string(),
+                        encodings: Vec::new(),
+                    },
+                    _ => Message::Ping {
                         node_id: "unknown".to_string(),
-                    }))
-                }
+                        encodings: Vec::new(),
+                    },
+                };
+                self.note_handshake(addr, &msg);
+                Ok(Some(msg))
             }
             "/gossip" => {
                 // Gossip request
                 if !packet.payload.is_empty() {
-                    let msg: Message = serde_json::from_slice(&packet.payload)
-                        .map_err(|e| Error::Serialization(e.to_string()))?;
+                    let msg = Self::decode_message(packet, &packet.payload)?;
                     Ok(Some(msg))
                 } else {
                     Ok(None)
@@ -214,8 +749,7 @@ impl CoapServer {
             "/record" => {
                 // Record request
                 if !packet.payload.is_empty() {
-                    let msg: Message = serde_json::from_slice(&packet.payload)
-                        .map_err(|e| Error::Serialization(e.to_string()))?;
+                    let msg = Self::decode_message(packet, &packet.payload)?;
                     Ok(Some(msg))
                 } else {
                     Ok(None)
@@ -224,8 +758,7 @@ impl CoapServer {
             "/announce" => {
                 // New record announcement
                 if !packet.payload.is_empty() {
-                    let msg: Message = serde_json::from_slice(&packet.payload)
-                        .map_err(|e| Error::Serialization(e.to_string()))?;
+                    let msg = Self::decode_message(packet, &packet.payload)?;
                     Ok(Some(msg))
                 } else {
                     Ok(None)
@@ -234,8 +767,7 @@ impl CoapServer {
             // RPC endpoint: /rpc/{method}
             p if p.starts_with("/rpc/") => {
                 if !packet.payload.is_empty() {
-                    let msg: Message = serde_json::from_slice(&packet.payload)
-                        .map_err(|e| Error::Serialization(e.to_string()))?;
+                    let msg = Self::decode_message(packet, &packet.payload)?;
                     Ok(Some(msg))
                 } else {
                     Ok(None)
@@ -275,18 +807,19 @@ impl CoapServer {
         message: &Message,
         confirmable: bool,
     ) -> Result<()> {
-        let payload =
-            serde_json::to_vec(message).map_err(|e| Error::Serialization(e.to_string()))?;
+        let (payload, content_format) = self.encode_message(addr, message)?;
 
         // Determine the path based on message type
         let path = Self::message_to_path(message);
 
         // Check if we need block-wise transfer
         if payload.len() > COAP_MAX_MESSAGE_SIZE {
-            return self.send_blocks(addr, &path, &payload, confirmable).await;
+            return self
+                .send_blocks(addr, &path, &payload, content_format, confirmable)
+                .await;
         }
 
-        let packet = self.create_request_packet(&path, &payload, confirmable);
+        let packet = self.create_request_packet(&path, &payload, content_format, confirmable);
         let bytes = packet
             .to_bytes()
             .map_err(|e| Error::network(format!("Failed to serialize packet: {:?}", e)))?;
@@ -325,53 +858,104 @@ impl CoapServer {
         addr: &SocketAddr,
         path: &str,
         data: &[u8],
+        content_format: u8,
         confirmable: bool,
     ) -> Result<()> {
-        let total_blocks = data.len().div_ceil(BLOCK_SIZE);
+        let blocks = self.prepare_blocks(addr, path, data, content_format, confirmable)?;
+        let total_blocks = blocks.len();
 
-        for block_num in 0..total_blocks {
-            let start = block_num * BLOCK_SIZE;
-            let end = std::cmp::min(start + BLOCK_SIZE, data.len());
-            let block_data = &data[start..end];
-            let more = block_num < total_blocks - 1;
+        for (block_num, packet) in blocks.iter().enumerate() {
+            self.send_packet(addr, packet).await?;
+            log::trace!("Sent block {}/{} to {}", block_num + 1, total_blocks, addr);
+        }
 
-            let mut packet = self.create_request_packet(path, block_data, confirmable);
-
-            // Add Block1 option (SZX=2 for 256 bytes)
-            // Block1 format: NUM (4+ bits) | M (1 bit) | SZX (3 bits)
-            let block1_value = ((block_num as u32) << 4) | (if more { 0x08 } else { 0x00 }) | 0x02;
-            let block1_bytes = if block1_value <= 0xFF {
-                vec![block1_value as u8]
-            } else if block1_value <= 0xFFFF {
-                block1_value.to_be_bytes()[2..].to_vec()
-            } else {
-                block1_value.to_be_bytes().to_vec()
-            };
-            packet.add_option(CoapOption::Block1, block1_bytes);
+        Ok(())
+    }
 
-            let bytes = packet
-                .to_bytes()
-                .map_err(|e| Error::network(format!("Failed to serialize block: {:?}", e)))?;
+    /// Split a payload into Block1 request packets
+    ///
+    /// All blocks share one token. Confirmable blocks are tracked
+    /// individually, so a lost block is retransmitted by
+    /// [`handle_retransmissions`](Self::handle_retransmissions) until the
+    /// receiver acknowledges it.
+    fn prepare_blocks(
+        &mut self,
+        addr: &SocketAddr,
+        path: &str,
+        data: &[u8],
+        content_format: u8,
+        confirmable: bool,
+    ) -> Result<Vec<Packet>> {
+        if data.len() > self.blockwise.max_total_size {
+            return Err(Error::network(format!(
+                "Payload of {} bytes exceeds the block-wise limit of {} bytes",
+                data.len(),
+                self.blockwise.max_total_size
+            )));
+        }
 
-            {
-                let socket = self
-                    .socket
-                    .as_ref()
-                    .ok_or_else(|| Error::network("Socket not initialized".to_string()))?;
-                socket
-                    .send_to(&bytes, *addr)
-                    .await
-                    .map_err(|e| Error::network(format!("Send block error: {}", e)))?;
+        let block_size = self.blockwise.block_size;
+        let total_blocks = data.len().div_ceil(block_size);
+        let token = self.generate_token();
+        let mut packets = Vec::with_capacity(total_blocks);
+
+        for (block_num, block_data) in data.chunks(block_size).enumerate() {
+            let more = block_num < total_blocks - 1;
+            let block = BlockOption::new(block_num as u32, more, block_size)?;
+
+            let mut packet =
+                self.create_request_packet(path, block_data, content_format, confirmable);
+            packet.set_token(token.clone());
+            packet.add_option(CoapOption::Block1, block.encode());
+            if block_num == 0 {
+                // Size1 lets the receiver reject an oversized body up front
+                packet.add_option(CoapOption::Size1, encode_uint(data.len() as u32));
             }
 
-            log::trace!("Sent block {}/{} to {}", block_num + 1, total_blocks, addr);
+            if confirmable {
+                self.pending_requests.insert(
+                    packet.header.message_id,
+                    PendingRequest {
+                        packet: packet.clone(),
+                        addr: *addr,
+                        sent_at: Instant::now(),
+                        retransmits: 0,
+                    },
+                );
+            }
+            packets.push(packet);
         }
 
+        Ok(packets)
+    }
+
+    /// Send an already-built packet
+    async fn send_packet(&self, addr: &SocketAddr, packet: &Packet) -> Result<()> {
+        let socket = self
+            .socket
+            .as_ref()
+            .ok_or_else(|| Error::network("Socket not initialized".to_string()))?;
+
+        let bytes = packet
+            .to_bytes()
+            .map_err(|e| Error::network(format!("Failed to serialize packet: {:?}", e)))?;
+
+        socket
+            .send_to(&bytes, *addr)
+            .await
+            .map_err(|e| Error::network(format!("Send error: {}", e)))?;
+
         Ok(())
     }
 
     /// Create a CoAP request packet
-    fn create_request_packet(&mut self, path: &str, payload: &[u8], confirmable: bool) -> Packet {
+    fn create_request_packet(
+        &mut self,
+        path: &str,
+        payload: &[u8],
+        content_format: u8,
+        confirmable: bool,
+    ) -> Packet {
         let mut packet = Packet::new();
 
         packet.header.set_version(1);
@@ -391,8 +975,10 @@ impl CoapServer {
             }
         }
 
-        // Add content format (application/json = 50)
-        packet.add_option(CoapOption::ContentFormat, vec![50]);
+        packet.add_option(
+            CoapOption::ContentFormat,
+            encode_uint(u32::from(content_format)),
+        );
 
         packet.payload = payload.to_vec();
 
@@ -400,6 +986,10 @@ impl CoapServer {
     }
 
     /// Send a CoAP response
+    ///
+    /// Payloads larger than [`COAP_MAX_MESSAGE_SIZE`] are served block-wise:
+    /// the first block goes out now and the peer fetches the rest with
+    /// Block2 follow-up requests.
     pub async fn send_response(
         &self,
         addr: &SocketAddr,
@@ -407,33 +997,170 @@ impl CoapServer {
         response_code: ResponseType,
         payload: Option<&[u8]>,
     ) -> Result<()> {
-        let socket = self
-            .socket
-            .as_ref()
-            .ok_or_else(|| Error::network("Socket not initialized".to_string()))?;
+        let response = self.build_response(
+            addr,
+            request,
+            response_code,
+            payload,
+            CONTENT_FORMAT_JSON,
+            Instant::now(),
+        )?;
+        self.send_packet(addr, &response).await
+    }
+
+    /// Answer `GET /metrics` with the latest CBOR-encoded health snapshot
+    fn metrics_reply(&self, addr: &SocketAddr, request: &Packet, now: Instant) -> Result<Packet> {
+        let Some(ref handle) = self.health else {
+            return Ok(Self::response_to(request, ResponseType::NotFound));
+        };
+        if request.header.code != MessageClass::Request(RequestType::Get) {
+            return Ok(Self::response_to(request, ResponseType::MethodNotAllowed));
+        }
+        let Some(health) = handle.latest() else {
+            return Ok(Self::response_to(request, ResponseType::ServiceUnavailable));
+        };
 
+        let body = health.to_cbor()?;
+        self.build_response(
+            addr,
+            request,
+            ResponseType::Content,
+            Some(&body),
+            CONTENT_FORMAT_CBOR,
+            now,
+        )
+    }
+
+    /// Build a response, registering large bodies for Block2 transfer
+    fn build_response(
+        &self,
+        addr: &SocketAddr,
+        request: &Packet,
+        response_code: ResponseType,
+        payload: Option<&[u8]>,
+        content_format: u8,
+        now: Instant,
+    ) -> Result<Packet> {
+        let Some(data) = payload else {
+            return Ok(Self::response_to(request, response_code));
+        };
+
+        if data.len() <= COAP_MAX_MESSAGE_SIZE {
+            let mut response = Self::response_to(request, response_code);
+            response.add_option(CoapOption::ContentFormat, vec![content_format]);
+            response.payload = data.to_vec();
+            return Ok(response);
+        }
+
+        if data.len() > self.blockwise.max_total_size {
+            return Err(Error::network(format!(
+                "Response of {} bytes exceeds the block-wise limit of {} bytes",
+                data.len(),
+                self.blockwise.max_total_size
+            )));
+        }
+
+        let mut state = BlockState {
+            data: data.to_vec(),
+            block_num: 0,
+            more: true,
+            content_format,
+        };
+        let first = BlockOption::new(0, true, self.blockwise.block_size)?;
+        let mut response = self.block2_reply(request, &mut state, first)?;
+        response.header.code = MessageClass::Response(response_code);
+        response.add_option(CoapOption::Size2, encode_uint(data.len() as u32));
+
+        let mut transfers = self
+            .transfers
+            .lock()
+            .map_err(|e| Error::Internal(format!("Failed to acquire transfers lock: {}", e)))?;
+        transfers
+            .outbound
+            .insert((*addr, request.get_token().to_vec()), (state, now));
+
+        Ok(response)
+    }
+
+    /// Build the reply carrying one block of a response body
+    fn block2_reply(
+        &self,
+        request: &Packet,
+        state: &mut BlockState,
+        requested: BlockOption,
+    ) -> Result<Packet> {
+        // Honour a smaller block size proposed by the peer
+        let block_size = requested.size().min(self.blockwise.block_size);
+        let offset = requested.offset();
+        if offset >= state.data.len() {
+            return Ok(Self::response_to(request, ResponseType::BadOption));
+        }
+
+        let end = (offset + block_size).min(state.data.len());
+        let more = end < state.data.len();
+        let block = BlockOption::new((offset / block_size) as u32, more, block_size)?;
+        state.block_num = block.num;
+        state.more = more;
+
+        let mut response = Self::response_to(request, ResponseType::Content);
+        response.add_option(CoapOption::ContentFormat, vec![state.content_format]);
+        response.add_option(CoapOption::Block2, block.encode());
+        response.payload = state.data[offset..end].to_vec();
+        Ok(response)
+    }
+
+    /// Build an empty piggybacked response to a request
+    fn response_to(request: &Packet, response_code: ResponseType) -> Packet {
         let mut response = Packet::new();
         response.header.set_version(1);
         response.header.set_type(MessageType::Acknowledgement);
         response.header.code = MessageClass::Response(response_code);
         response.header.message_id = request.header.message_id;
         response.set_token(request.get_token().to_vec());
+        response
+    }
 
-        if let Some(data) = payload {
-            response.add_option(CoapOption::ContentFormat, vec![50]);
-            response.payload = data.to_vec();
-        }
-
-        let bytes = response
-            .to_bytes()
-            .map_err(|e| Error::network(format!("Failed to serialize response: {:?}", e)))?;
+    /// Build the request for the next block of a Block2 response
+    fn block2_request(token: &[u8], block: BlockOption) -> Packet {
+        let mut packet = Packet::new();
+        packet.header.set_version(1);
+        packet.header.set_type(MessageType::Confirmable);
+        packet.header.code = MessageClass::Request(RequestType::Get);
+        packet.header.message_id = rand::random();
+        packet.set_token(token.to_vec());
+        packet.add_option(CoapOption::Block2, block.encode());
+        packet
+    }
 
-        socket
-            .send_to(&bytes, *addr)
-            .await
-            .map_err(|e| Error::network(format!("Send response error: {}", e)))?;
+    /// Follow-up Block2 requests whose responses are overdue
+    ///
+    /// Transfers that exhausted [`MAX_RETRANSMIT`] attempts are abandoned.
+    fn stalled_block_requests(&self, now: Instant) -> Result<Vec<(SocketAddr, Packet)>> {
+        let timeout = Duration::from_millis(ACK_TIMEOUT_MS);
+        let mut transfers = self
+            .transfers
+            .lock()
+            .map_err(|e| Error::Internal(format!("Failed to acquire transfers lock: {}", e)))?;
+
+        let mut resend = Vec::new();
+        transfers.inbound.retain(|(addr, _), assembly| {
+            let Some(follow_up) = &assembly.follow_up else {
+                return true;
+            };
+            if now.duration_since(assembly.updated_at) <= timeout {
+                return true;
+            }
+            if assembly.retries >= MAX_RETRANSMIT {
+                log::warn!("CoAP block transfer from {} abandoned after retries", addr);
+                return false;
+            }
+            resend.push((*addr, follow_up.clone()));
+            assembly.retries += 1;
+            assembly.updated_at = now;
+            true
+        });
 
-        Ok(())
+        Ok(resend)
     }
 
     /// Map message type to CoAP path
@@ -465,8 +1192,22 @@ impl CoapServer {
     }
 
     /// Handle retransmissions for pending CON messages
+    ///
+    /// Also resends stalled Block2 follow-ups and discards block-wise
+    /// transfers that have been idle past their timeout.
     pub async fn handle_retransmissions(&mut self) -> Result<()> {
+        self.apply_acks();
+
         let now = Instant::now();
+        for (addr, packet) in self.stalled_block_requests(now)? {
+            if let Err(e) = self.send_packet(&addr, &packet).await {
+                log::warn!("Block re-request failed: {}", e);
+            }
+        }
+        if let Ok(transfers) = self.transfers.get_mut() {
+            transfers.expire(self.blockwise.transfer_timeout, now);
+        }
+
         let timeout = Duration::from_millis(ACK_TIMEOUT_MS);
         let mut to_remove = Vec::new();
         let mut to_retransmit = Vec::new();
@@ -528,6 +1269,17 @@ impl CoapServer {
         }
     }
 
+    /// Clear pending requests acknowledged on the receive path
+    fn apply_acks(&mut self) {
+        let acked = match self.transfers.get_mut() {
+            Ok(transfers) => std::mem::take(&mut transfers.acked),
+            Err(_) => return,
+        };
+        for message_id in acked {
+            self.handle_ack(message_id);
+        }
+    }
+
     /// Join multicast group for discovery
     pub fn join_multicast(&self) -> Result<()> {
         // IPv4 multicast
@@ -584,6 +1336,7 @@ impl CoapServer {
         "</gossip>;rt=\"aingle.gossip\";ct=50,\
              </record>;rt=\"aingle.record\";ct=50,\
              </announce>;rt=\"aingle.announce\";ct=50,\
+             </metrics>;rt=\"aingle.metrics\";ct=60,\
              </ping>;rt=\"aingle.ping\""
             .to_string()
     }
@@ -697,19 +1450,11 @@ mod tests {
         use crate::types::Hash;
 
         // Ping
-        assert_eq!(
-            CoapServer::message_to_path(&Message::Ping {
-                node_id: "test".to_string()
-            }),
-            "/ping"
-        );
+        assert_eq!(CoapServer::message_to_path(&Message::ping("test")), "/ping");
 
         // Pong
         assert_eq!(
-            CoapServer::message_to_path(&Message::Pong {
-                node_id: "test".to_string(),
-                latest_seq: 0
-            }),
+            CoapServer::message_to_path(&Message::pong("test", 0)),
             "/ping"
         );
 
@@ -846,7 +1591,7 @@ mod tests {
     #[test]
     fn test_create_request_packet_non_confirmable() {
         let mut server = CoapServer::new("0.0.0.0".to_string(), 5683, "test".to_string());
-        let packet = server.create_request_packet("/ping", b"hello", false);
+        let packet = server.create_request_packet("/ping", b"hello", CONTENT_FORMAT_JSON, false);
 
         assert_eq!(packet.header.get_type(), MessageType::NonConfirmable);
         assert_eq!(packet.header.code, MessageClass::Request(RequestType::Post));
@@ -856,7 +1601,7 @@ mod tests {
     #[test]
     fn test_create_request_packet_confirmable() {
         let mut server = CoapServer::new("0.0.0.0".to_string(), 5683, "test".to_string());
-        let packet = server.create_request_packet("/gossip", b"data", true);
+        let packet = server.create_request_packet("/gossip", b"data", CONTENT_FORMAT_JSON, true);
 
         assert_eq!(packet.header.get_type(), MessageType::Confirmable);
         assert!(!packet.get_token().is_empty());
@@ -865,7 +1610,7 @@ mod tests {
     #[test]
     fn test_create_request_packet_with_path_segments() {
         let mut server = CoapServer::new("0.0.0.0".to_string(), 5683, "test".to_string());
-        let packet = server.create_request_packet("/a/b/c", b"", false);
+        let packet = server.create_request_packet("/a/b/c", b"", CONTENT_FORMAT_JSON, false);
 
         // Should have multiple UriPath options
         let uri_paths = packet.get_option(CoapOption::UriPath);
@@ -886,7 +1631,7 @@ mod tests {
         let result = server.process_packet(&packet, &addr);
 
         assert!(result.is_ok());
-        if let Ok(Some(Message::Ping { node_id })) = result {
+        if let Ok(Some(Message::Ping { node_id, .. })) = result {
             assert_eq!(node_id, "node123");
         }
     }
@@ -903,7 +1648,7 @@ mod tests {
         let result = server.process_packet(&packet, &addr);
 
         assert!(result.is_ok());
-        if let Ok(Some(Message::Ping { node_id })) = result {
+        if let Ok(Some(Message::Ping { node_id, .. })) = result {
             assert_eq!(node_id, "unknown");
         }
     }
@@ -1061,6 +1806,7 @@ mod tests {
             data: vec![1, 2, 3],
             block_num: 5,
             more: true,
+            content_format: CONTENT_FORMAT_JSON,
         };
         let debug_str = format!("{:?}", state);
         assert!(debug_str.contains("BlockState"));
@@ -1074,6 +1820,7 @@ mod tests {
             data: vec![1, 2, 3],
             block_num: 10,
             more: false,
+            content_format: CONTENT_FORMAT_JSON,
         };
         let state2 = state1.clone();
         assert_eq!(state1.data, state2.data);
@@ -1166,6 +1913,7 @@ mod tests {
             data: vec![10, 20, 30, 40, 50],
             block_num: 42,
             more: true,
+            content_format: CONTENT_FORMAT_JSON,
         };
 
         assert_eq!(state.data.len(), 5);
@@ -1194,7 +1942,7 @@ mod tests {
     #[test]
     fn test_create_request_packet_empty_path() {
         let mut server = CoapServer::new("0.0.0.0".to_string(), 5683, "test".to_string());
-        let packet = server.create_request_packet("/", b"", false);
+        let packet = server.create_request_packet("/", b"", CONTENT_FORMAT_JSON, false);
 
         // Should have content format option
         let cf = packet.get_option(CoapOption::ContentFormat);
@@ -1258,9 +2006,7 @@ mod tests {
         smol::block_on(async {
             let mut server = CoapServer::new("0.0.0.0".to_string(), 5683, "test".to_string());
             let addr: SocketAddr = "127.0.0.1:5684".parse().unwrap();
-            let msg = Message::Ping {
-                node_id: "test".to_string(),
-            };
+            let msg = Message::ping("test");
 
             let result = server.send(&addr, &msg, false).await;
             assert!(result.is_err());
@@ -1326,4 +2072,368 @@ mod tests {
             assert!(!server.pending_requests.contains_key(&100));
         });
     }
+
+    fn blockwise_server(block_size: usize) -> CoapServer {
+        let mut server = CoapServer::new("127.0.0.1".to_string(), 5683, "test".to_string());
+        server
+            .set_blockwise_config(BlockwiseConfig {
+                block_size,
+                ..BlockwiseConfig::default()
+            })
+            .unwrap();
+        server
+    }
+
+    #[test]
+    fn test_block_option_roundtrip() {
+        for (num, more, size) in [(0, true, 16), (5, false, 64), (4095, true, 1024)] {
+            let block = BlockOption::new(num, more, size).unwrap();
+            let decoded = BlockOption::decode(&block.encode()).unwrap();
+            assert_eq!(decoded, block);
+            assert_eq!(decoded.size(), size);
+            assert_eq!(decoded.offset(), num as usize * size);
+        }
+        // NUM=1, M=1, SZX=2 fits in a single byte
+        assert_eq!(BlockOption::new(1, true, 64).unwrap().encode(), vec![0x1A]);
+    }
+
+    #[test]
+    fn test_block_option_rejects_invalid() {
+        for size in [8, 100, 2048] {
+            assert!(BlockOption::szx_for(size).is_err());
+        }
+        assert!(BlockOption::decode(&[0x07]).is_err()); // reserved SZX
+        assert!(BlockOption::decode(&[0, 0, 0, 0]).is_err());
+        assert!(BlockOption::new(1 << 20, false, 64).is_err());
+    }
+
+    #[test]
+    fn test_blockwise_config_memory_limit() {
+        let config = BlockwiseConfig::for_memory_limit(256 * 1024);
+        assert_eq!(config.max_total_size, 64 * 1024);
+        assert_eq!(config.block_size, BLOCK_SIZE);
+        assert!(config.validate().is_ok());
+
+        let mut server = CoapServer::new("0.0.0.0".to_string(), 5683, "test".to_string());
+        let bad = BlockwiseConfig {
+            block_size: 2048,
+            ..BlockwiseConfig::default()
+        };
+        assert!(server.set_blockwise_config(bad).is_err());
+        assert_eq!(server.blockwise_config().block_size, BLOCK_SIZE);
+    }
+
+    #[test]
+    fn test_block1_transfer_with_lost_block() {
+        let mut client = blockwise_server(64);
+        let server = blockwise_server(64);
+        let client_addr: SocketAddr = "127.0.0.1:5690".parse().unwrap();
+        let server_addr: SocketAddr = "127.0.0.1:5683".parse().unwrap();
+        let now = Instant::now();
+
+        let msg = Message::RemoteCall {
+            request_id: "req-1".to_string(),
+            from: "client".to_string(),
+            method: "ingest".to_string(),
+            payload: vec![0xAB; 10 * 1024],
+        };
+        let body = serde_json::to_vec(&msg).unwrap();
+        let blocks = client
+            .prepare_blocks(
+                &server_addr,
+                "/rpc/ingest",
+                &body,
+                CONTENT_FORMAT_JSON,
+                true,
+            )
+            .unwrap();
+        assert_eq!(blocks.len(), body.len().div_ceil(64));
+        assert_eq!(client.pending_requests.len(), blocks.len());
+
+        // Deliver every block except #7, which is lost in transit
+        for (i, block) in blocks.iter().enumerate() {
+            if i == 7 {
+                continue;
+            }
+            let (delivered, replies) = server.handle_datagram(block, &client_addr, now).unwrap();
+            assert!(delivered.is_none());
+            assert_eq!(replies.len(), 1);
+            assert_eq!(
+                replies[0].header.code,
+                MessageClass::Response(ResponseType::Continue)
+            );
+            for reply in &replies {
+                client.handle_datagram(reply, &server_addr, now).unwrap();
+            }
+        }
+        assert_eq!(server.active_block_transfers(), 1);
+
+        // Only the lost block is still awaiting its ACK
+        client.apply_acks();
+        assert_eq!(client.pending_requests.len(), 1);
+        let retry = client
+            .pending_requests
+            .values()
+            .next()
+            .unwrap()
+            .packet
+            .clone();
+        assert_eq!(
+            BlockOption::from_packet(&retry, CoapOption::Block1)
+                .unwrap()
+                .unwrap()
+                .num,
+            7
+        );
+
+        // The retransmission completes the body; the handler sees it whole
+        let (delivered, _) = server.handle_datagram(&retry, &client_addr, now).unwrap();
+        match delivered {
+            Some(Message::RemoteCall {
+                method, payload, ..
+            }) => {
+                assert_eq!(method, "ingest");
+                assert_eq!(payload, vec![0xAB; 10 * 1024]);
+            }
+            other => panic!("expected reassembled RemoteCall, got {:?}", other),
+        }
+        assert_eq!(server.active_block_transfers(), 0);
+    }
+
+    #[test]
+    fn test_block2_transfer_with_lost_block() {
+        let mut client = blockwise_server(64);
+        let server = blockwise_server(64);
+        let client_addr: SocketAddr = "127.0.0.1:5690".parse().unwrap();
+        let server_addr: SocketAddr = "127.0.0.1:5683".parse().unwrap();
+        let now = Instant::now();
+
+        let request = client.create_request_packet("/record", b"", CONTENT_FORMAT_JSON, true);
+        let response = Message::RemoteCallResponse {
+            request_id: "req-2".to_string(),
+            success: true,
+            data: vec![0xCD; 10 * 1024],
+        };
+        let body = serde_json::to_vec(&response).unwrap();
+        let first = server
+            .build_response(
+                &client_addr,
+                &request,
+                ResponseType::Content,
+                Some(&body),
+                CONTENT_FORMAT_JSON,
+                now,
+            )
+            .unwrap();
+        assert!(first.payload.len() <= 64);
+
+        let mut in_flight = vec![first];
+        let mut lost_once = false;
+        let mut delivered = None;
+        while let Some(packet) = in_flight.pop() {
+            let block = BlockOption::from_packet(&packet, CoapOption::Block2)
+                .unwrap()
+                .unwrap();
+            if block.num == 5 && !lost_once {
+                // Response lost: the client re-requests after the ACK timeout
+                lost_once = true;
+                let later = now + Duration::from_millis(ACK_TIMEOUT_MS + 1);
+                let retries = client.stalled_block_requests(later).unwrap();
+                assert_eq!(retries.len(), 1);
+                for (_, retry) in retries {
+                    let (_, replies) = server.handle_datagram(&retry, &client_addr, later).unwrap();
+                    in_flight.extend(replies);
+                }
+                continue;
+            }
+
+            let (msg, follow_ups) = client.handle_datagram(&packet, &server_addr, now).unwrap();
+            if msg.is_some() {
+                delivered = msg;
+                break;
+            }
+            for follow_up in follow_ups {
+                let (_, replies) = server
+                    .handle_datagram(&follow_up, &client_addr, now)
+                    .unwrap();
+                in_flight.extend(replies);
+            }
+        }
+
+        assert!(lost_once);
+        match delivered {
+            Some(Message::RemoteCallResponse { data, success, .. }) => {
+                assert!(success);
+                assert_eq!(data, vec![0xCD; 10 * 1024]);
+            }
+            other => panic!("expected reassembled response, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_block1_rejects_oversized_body() {
+        let mut client = blockwise_server(64);
+        let mut server = blockwise_server(64);
+        server
+            .set_blockwise_config(BlockwiseConfig {
+                block_size: 64,
+                max_total_size: 1024,
+                ..BlockwiseConfig::default()
+            })
+            .unwrap();
+        let client_addr: SocketAddr = "127.0.0.1:5690".parse().unwrap();
+        let server_addr: SocketAddr = "127.0.0.1:5683".parse().unwrap();
+        let now = Instant::now();
+
+        let blocks = client
+            .prepare_blocks(
+                &server_addr,
+                "/gossip",
+                &[1u8; 4096],
+                CONTENT_FORMAT_JSON,
+                true,
+            )
+            .unwrap();
+        let mut rejected = false;
+        for block in &blocks {
+            let (_, replies) = server.handle_datagram(block, &client_addr, now).unwrap();
+            if replies[0].header.code == MessageClass::Response(ResponseType::RequestEntityTooLarge)
+            {
+                rejected = true;
+                break;
+            }
+        }
+        assert!(rejected);
+        assert_eq!(server.active_block_transfers(), 0);
+    }
+
+    #[test]
+    fn test_half_finished_transfer_expires() {
+        let mut client = blockwise_server(64);
+        let server = blockwise_server(64);
+        let client_addr: SocketAddr = "127.0.0.1:5690".parse().unwrap();
+        let server_addr: SocketAddr = "127.0.0.1:5683".parse().unwrap();
+        let now = Instant::now();
+
+        let blocks = client
+            .prepare_blocks(
+                &server_addr,
+                "/gossip",
+                &[1u8; 2048],
+                CONTENT_FORMAT_JSON,
+                false,
+            )
+            .unwrap();
+        for block in blocks.iter().take(3) {
+            let (_, replies) = server.handle_datagram(block, &client_addr, now).unwrap();
+            // NON blocks are not acknowledged
+            assert!(replies.is_empty());
+        }
+        assert_eq!(server.active_block_transfers(), 1);
+
+        let later = now + server.blockwise_config().transfer_timeout + Duration::from_secs(1);
+        let ping = client.create_request_packet("/ping", b"node", CONTENT_FORMAT_JSON, false);
+        server.handle_datagram(&ping, &client_addr, later).unwrap();
+        assert_eq!(server.active_block_transfers(), 0);
+    }
+
+    #[test]
+    fn test_metrics_resource() {
+        let mut client = blockwise_server(64);
+        let mut server = blockwise_server(64);
+        let client_addr: SocketAddr = "127.0.0.1:5690".parse().unwrap();
+        let now = Instant::now();
+
+        let mut request = client.create_request_packet("/metrics", b"", CONTENT_FORMAT_JSON, true);
+        request.header.code = MessageClass::Request(RequestType::Get);
+
+        // Metrics disabled
+        let (_, replies) = server.handle_datagram(&request, &client_addr, now).unwrap();
+        assert_eq!(
+            replies[0].header.code,
+            MessageClass::Response(ResponseType::NotFound)
+        );
+
+        // Enabled, but nothing collected yet
+        let handle = HealthHandle::new();
+        server.set_health_source(handle.clone());
+        let (_, replies) = server.handle_datagram(&request, &client_addr, now).unwrap();
+        assert_eq!(
+            replies[0].header.code,
+            MessageClass::Response(ResponseType::ServiceUnavailable)
+        );
+
+        let node = crate::MinimalNode::new(Config::test_mode()).unwrap();
+        handle.publish(node.health().unwrap());
+        let (message, replies) = server.handle_datagram(&request, &client_addr, now).unwrap();
+        assert!(message.is_none());
+        let reply = &replies[0];
+        assert_eq!(
+            reply.header.code,
+            MessageClass::Response(ResponseType::Content)
+        );
+        assert_eq!(
+            reply.get_option(CoapOption::ContentFormat).unwrap().front(),
+            Some(&vec![CONTENT_FORMAT_CBOR])
+        );
+        let health = crate::health::NodeHealth::from_cbor(&reply.payload).unwrap();
+        assert_eq!(health.node_id, node.public_key().to_hex());
+    }
+
+    #[test]
+    fn test_mixed_version_encoding() {
+        let mut new_node = CoapServer::new("127.0.0.1".to_string(), 5683, "new".to_string());
+        let mut peer = CoapServer::new("127.0.0.1".to_string(), 5684, "peer".to_string());
+        let new_addr: SocketAddr = "127.0.0.1:5683".parse().unwrap();
+        let old_addr: SocketAddr = "127.0.0.1:5690".parse().unwrap();
+        let peer_addr: SocketAddr = "127.0.0.1:5684".parse().unwrap();
+        let gossip = Message::GossipRequest {
+            from_seq: 7,
+            limit: 20,
+        };
+
+        // An old node pings in JSON without listing encodings
+        let old_ping = br#"{"Ping":{"node_id":"old"}}"#;
+        let packet = new_node.create_request_packet("/ping", old_ping, CONTENT_FORMAT_JSON, false);
+        let ping = new_node.process_packet(&packet, &old_addr).unwrap();
+        assert!(matches!(ping, Some(Message::Ping { ref encodings, .. }) if encodings.is_empty()));
+        assert_eq!(new_node.peer_encoding(&old_addr), WireEncoding::Json);
+
+        // ...so it keeps getting plain JSON it can parse
+        let (payload, content_format) = new_node.encode_message(&old_addr, &gossip).unwrap();
+        assert_eq!(content_format, CONTENT_FORMAT_JSON);
+        let parsed: Message = serde_json::from_slice(&payload).unwrap();
+        assert!(matches!(parsed, Message::GossipRequest { from_seq: 7, .. }));
+
+        // and its JSON requests are still understood
+        let packet =
+            new_node.create_request_packet("/gossip", &payload, CONTENT_FORMAT_JSON, false);
+        let received = new_node.process_packet(&packet, &old_addr).unwrap();
+        assert!(matches!(
+            received,
+            Some(Message::GossipRequest { limit: 20, .. })
+        ));
+
+        // Two current nodes switch to CBOR once the handshake is seen
+        let (payload, content_format) = new_node
+            .encode_message(&peer_addr, &Message::ping("new"))
+            .unwrap();
+        assert_eq!(content_format, CONTENT_FORMAT_JSON);
+        let packet = new_node.create_request_packet("/ping", &payload, content_format, false);
+        peer.process_packet(&packet, &new_addr).unwrap();
+        assert_eq!(peer.peer_encoding(&new_addr), WireEncoding::Cbor);
+
+        let (payload, content_format) = peer.encode_message(&new_addr, &gossip).unwrap();
+        assert_eq!(content_format, CONTENT_FORMAT_CBOR);
+        let packet = peer.create_request_packet("/gossip", &payload, content_format, false);
+        let received = new_node.process_packet(&packet, &peer_addr).unwrap();
+        assert!(matches!(
+            received,
+            Some(Message::GossipRequest { from_seq: 7, .. })
+        ));
+
+        // An unknown Content-Format is rejected rather than guessed
+        let packet = peer.create_request_packet("/gossip", &payload, 0, false);
+        assert!(new_node.process_packet(&packet, &peer_addr).is_err());
+    }
 }
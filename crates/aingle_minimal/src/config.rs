@@ -28,7 +28,10 @@
 //! config.enable_mdns = true;
 //! ```
 
-use crate::{ENV_IOT_MODE, ENV_PUBLISH_INTERVAL};
+pub use crate::config_loader::ConfigSource;
+use crate::storage_crypto::StorageEncryption;
+use crate::storage_trait::EvictionPolicy;
+use crate::{ENV_IOT_MODE, ENV_KEYSTORE_PASSPHRASE, ENV_PUBLISH_INTERVAL};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
@@ -71,6 +74,21 @@ impl Default for PowerMode {
     }
 }
 
+impl PowerMode {
+    /// How often the node sweeps expired semantic graph triples.
+    ///
+    /// Lower power modes sweep less often, holding expired triples in memory
+    /// a little longer in exchange for fewer wake-ups and flash writes.
+    pub fn expiry_sweep_interval(&self) -> Duration {
+        match self {
+            Self::Full => Duration::from_secs(30),
+            Self::Balanced => Duration::from_secs(60),
+            Self::Low => Duration::from_secs(300),
+            Self::Critical => Duration::from_secs(900),
+        }
+    }
+}
+
 /// Defines the network transport to be used by the node.
 ///
 /// Different transports are optimized for different environments. CoAP is ideal
@@ -156,6 +174,22 @@ pub enum TransportConfig {
     },
 }
 
+impl TransportConfig {
+    /// Returns the kind of this transport.
+    pub fn kind(&self) -> TransportKind {
+        match self {
+            Self::Memory => TransportKind::Memory,
+            Self::Quic { .. } => TransportKind::Quic,
+            Self::Coap { .. } => TransportKind::Coap,
+            Self::Mesh { .. } => TransportKind::Mesh,
+            #[cfg(feature = "webrtc")]
+            Self::WebRtc { .. } => TransportKind::WebRtc,
+            #[cfg(feature = "ble")]
+            Self::Ble { .. } => TransportKind::Ble,
+        }
+    }
+}
+
 impl Default for TransportConfig {
     /// Defaults to CoAP, as this crate is optimized for IoT.
     fn default() -> Self {
@@ -166,6 +200,46 @@ impl Default for TransportConfig {
     }
 }
 
+/// The kind of a [`TransportConfig`], without its settings.
+///
+/// Names a transport in [`BridgeRoute`]s and in gossip statistics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransportKind {
+    /// The in-memory testing transport.
+    Memory,
+    /// QUIC over UDP.
+    Quic,
+    /// CoAP over UDP, typically on WiFi or Ethernet.
+    Coap,
+    /// Device-to-device mesh networking.
+    Mesh,
+    /// WebRTC data channels.
+    WebRtc,
+    /// Bluetooth Low Energy.
+    Ble,
+}
+
+impl TransportKind {
+    /// Returns the lowercase name of the transport.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Memory => "memory",
+            Self::Quic => "quic",
+            Self::Coap => "coap",
+            Self::Mesh => "mesh",
+            Self::WebRtc => "webrtc",
+            Self::Ble => "ble",
+        }
+    }
+}
+
+impl std::fmt::Display for TransportKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// The type of mesh networking to use for device-to-device communication.
 ///
 /// # Examples
@@ -241,6 +315,37 @@ pub struct GossipConfig {
     /// Higher values increase redundancy and data propagation speed but use more
     /// network bandwidth and CPU.
     pub max_peers: usize,
+    /// The false positive rate the gossip bloom filter is sized for.
+    ///
+    /// The filter is resized on every rotation to hold the observed insertion rate
+    /// at this rate, within the memory ceiling derived from `Config::memory_limit`.
+    #[serde(default = "default_bloom_target_fpr")]
+    pub bloom_target_fpr: f64,
+    /// How often the gossip bloom filter is replaced by a freshly sized one.
+    #[serde(default = "default_bloom_rotation_interval")]
+    pub bloom_rotation_interval: Duration,
+    /// How long the previous bloom filter is kept after a rotation.
+    ///
+    /// During the overlap both filters are consulted and the previous one is still
+    /// sent to peers, so comparisons in flight see every hash recorded before the
+    /// rotation.
+    #[serde(default = "default_bloom_overlap")]
+    pub bloom_overlap: Duration,
+    /// Per-peer request limits and misbehavior scoring.
+    #[serde(default)]
+    pub peer_limits: PeerLimitConfig,
+}
+
+fn default_bloom_target_fpr() -> f64 {
+    0.01
+}
+
+fn default_bloom_rotation_interval() -> Duration {
+    Duration::from_secs(300)
+}
+
+fn default_bloom_overlap() -> Duration {
+    Duration::from_secs(30)
 }
 
 impl Default for GossipConfig {
@@ -251,6 +356,10 @@ impl Default for GossipConfig {
             error_delay: Duration::from_secs(300),
             output_target_mbps: 0.5,
             max_peers: 8,
+            bloom_target_fpr: default_bloom_target_fpr(),
+            bloom_rotation_interval: default_bloom_rotation_interval(),
+            bloom_overlap: default_bloom_overlap(),
+            peer_limits: PeerLimitConfig::default(),
         }
     }
 }
@@ -276,6 +385,14 @@ impl GossipConfig {
             error_delay: Duration::from_secs(30),
             output_target_mbps: 5.0,
             max_peers: 4,
+            bloom_target_fpr: default_bloom_target_fpr(),
+            bloom_rotation_interval: Duration::from_secs(60),
+            bloom_overlap: Duration::from_secs(5),
+            peer_limits: PeerLimitConfig {
+                requests_per_sec: 10.0,
+                request_burst: 20,
+                ..PeerLimitConfig::default()
+            },
         }
     }
 
@@ -299,10 +416,287 @@ impl GossipConfig {
             error_delay: Duration::from_secs(600),
             output_target_mbps: 0.1,
             max_peers: 2,
+            bloom_target_fpr: default_bloom_target_fpr(),
+            bloom_rotation_interval: Duration::from_secs(1800),
+            bloom_overlap: Duration::from_secs(120),
+            peer_limits: PeerLimitConfig {
+                requests_per_sec: 0.5,
+                ..PeerLimitConfig::default()
+            },
+        }
+    }
+}
+
+/// Per-peer limits on inbound sync traffic.
+///
+/// Each peer gets a token bucket for its sync requests; requests beyond it are
+/// refused. Protocol violations (malformed messages, impossible sequence ranges,
+/// bad signatures) and refused requests add to a misbehavior score that halves
+/// every `score_half_life`. A peer whose score reaches `ban_threshold` is ignored
+/// for `ban_duration`. Bans are stored with the node's data and survive restarts.
+///
+/// # Examples
+///
+/// ```
+/// # use aingle_minimal::{Config, PeerLimitConfig};
+/// # use std::time::Duration;
+/// let mut config = Config::default();
+/// config.gossip.peer_limits = PeerLimitConfig {
+///     requests_per_sec: 0.1,
+///     ban_duration: Duration::from_secs(6 * 3600),
+///     ..Default::default()
+/// };
+/// assert!(config.validate().is_ok());
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeerLimitConfig {
+    /// The sustained rate of sync requests accepted from one peer.
+    ///
+    /// Well-behaved peers request about once per two gossip loop delays.
+    pub requests_per_sec: f64,
+    /// The number of sync requests a peer may send in a burst.
+    pub request_burst: u32,
+    /// The misbehavior score at which a peer is banned.
+    pub ban_threshold: f64,
+    /// The time it takes a misbehavior score to halve.
+    pub score_half_life: Duration,
+    /// How long a banned peer is ignored.
+    pub ban_duration: Duration,
+    /// The maximum number of bans kept; the ban closest to expiry is dropped first.
+    pub max_banned: usize,
+}
+
+impl Default for PeerLimitConfig {
+    fn default() -> Self {
+        Self {
+            requests_per_sec: 1.0,
+            request_burst: 10,
+            ban_threshold: 100.0,
+            score_half_life: Duration::from_secs(600),
+            ban_duration: Duration::from_secs(3600),
+            max_banned: 64,
+        }
+    }
+}
+
+/// Relaying of entries between the transports of a multi-transport node.
+///
+/// Each transport gossips on its own, so without bridging an entry created on a
+/// BLE-only sensor never reaches peers that are only on WiFi. A gateway with
+/// both transports lists the directions it relays in `routes`: an entry learned
+/// from a peer on `from` is re-announced to the peers on `to`. Directions that
+/// are not listed are not bridged.
+///
+/// The transport each entry was first learned on is remembered for the last
+/// `seen_cache_size` entries, and an entry is only ever bridged away from that
+/// transport, so a re-announced entry coming back never bounces between them.
+///
+/// # Examples
+///
+/// ```
+/// # use aingle_minimal::{BridgeConfig, BridgeRoute, Config, TransportKind};
+/// let mut config = Config::default();
+/// config.bridge = BridgeConfig::default()
+///     // BLE sensors feed the WiFi network, at most 5 entries a second
+///     .route(BridgeRoute::new(TransportKind::Ble, TransportKind::Coap).with_rate(5.0, 10))
+///     // and hear back about everything
+///     .route(BridgeRoute::new(TransportKind::Coap, TransportKind::Ble));
+/// assert!(config.validate().is_ok());
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BridgeConfig {
+    /// The bridged directions. Empty by default: no bridging.
+    pub routes: Vec<BridgeRoute>,
+    /// How many entries are remembered with the transport they came from.
+    ///
+    /// Also bounds the entries waiting to be bridged on each route.
+    pub seen_cache_size: usize,
+}
+
+impl Default for BridgeConfig {
+    fn default() -> Self {
+        Self {
+            routes: Vec::new(),
+            seen_cache_size: 4096,
         }
     }
 }
 
+impl BridgeConfig {
+    /// Adds a bridged direction.
+    pub fn route(mut self, route: BridgeRoute) -> Self {
+        self.routes.push(route);
+        self
+    }
+
+    /// Returns `true` if any direction is bridged.
+    pub fn is_enabled(&self) -> bool {
+        !self.routes.is_empty()
+    }
+}
+
+/// One bridged direction, from the transport entries are learned on to the
+/// transport they are re-announced on.
+///
+/// Each route has its own token bucket, so a chatty network on one side cannot
+/// use up the gossip budget of the other. Entries over the limit wait for
+/// tokens rather than being dropped.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BridgeRoute {
+    /// The transport entries are learned on.
+    pub from: TransportKind,
+    /// The transport entries are re-announced on.
+    pub to: TransportKind,
+    /// The sustained number of entries relayed per second.
+    pub max_per_sec: f64,
+    /// The number of entries that may be relayed in a burst.
+    pub burst: u32,
+}
+
+impl BridgeRoute {
+    /// Creates a route relaying up to 10 entries a second, in bursts of 20.
+    pub fn new(from: TransportKind, to: TransportKind) -> Self {
+        Self {
+            from,
+            to,
+            max_per_sec: 10.0,
+            burst: 20,
+        }
+    }
+
+    /// Sets the rate limit of the route.
+    pub fn with_rate(mut self, max_per_sec: f64, burst: u32) -> Self {
+        self.max_per_sec = max_per_sec;
+        self.burst = burst;
+        self
+    }
+}
+
+/// Configuration for peer discovery that does not depend on mDNS.
+///
+/// mDNS only works where multicast is allowed on the local link. Sites that block
+/// it, or links where it is meaningless (LoRa, routed networks), can list peers
+/// statically or name a DNS record that resolves to them. Both are re-resolved
+/// periodically so peers that change address are found again, and failed lookups
+/// are retried with exponential backoff.
+///
+/// # Examples
+///
+/// ```
+/// # use aingle_minimal::{Config, DiscoveryConfig};
+/// let mut config = Config::default();
+/// config.enable_mdns = false;
+/// config.discovery = DiscoveryConfig {
+///     bootstrap_peers: vec!["192.168.1.100:5683".to_string(), "gateway.local".to_string()],
+///     dns_service: Some("aingle.example.com".to_string()),
+///     ..Default::default()
+/// };
+/// assert!(config.validate().is_ok());
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscoveryConfig {
+    /// Peers to connect to at startup, as `host:port` or `host`.
+    ///
+    /// Entries without a port use the node's own transport port.
+    pub bootstrap_peers: Vec<String>,
+    /// A DNS name whose address records list the peers of this network.
+    ///
+    /// Only A/AAAA records are consulted; the port comes from the name
+    /// (`name:port`) or defaults to the node's transport port.
+    pub dns_service: Option<String>,
+    /// How often successfully resolved entries are looked up again.
+    pub resolve_interval: Duration,
+    /// The delay before the first retry of a failed lookup.
+    ///
+    /// Each consecutive failure doubles the delay, up to `retry_max`.
+    pub retry_base: Duration,
+    /// The upper bound for the retry delay of a failing lookup.
+    pub retry_max: Duration,
+}
+
+impl Default for DiscoveryConfig {
+    fn default() -> Self {
+        Self {
+            bootstrap_peers: Vec::new(),
+            dns_service: None,
+            resolve_interval: Duration::from_secs(300),
+            retry_base: Duration::from_secs(2),
+            retry_max: Duration::from_secs(300),
+        }
+    }
+}
+
+impl DiscoveryConfig {
+    /// Returns `true` if neither static peers nor a DNS name are configured.
+    pub fn is_empty(&self) -> bool {
+        self.bootstrap_peers.is_empty() && self.dns_service.is_none()
+    }
+}
+
+/// Recording of the node's inputs for later replay.
+///
+/// When `path` is set, the node appends every input it acts on (entries
+/// created, messages received, battery readings and maintenance ticks) to a
+/// ring log at `path`, so a misbehaving field node can be reproduced with
+/// `aingle-minimal replay --log <path>`. See [`Recorder`](crate::replay::Recorder).
+///
+/// The log lives next to the database and counts against the storage budget:
+/// `max_bytes` may not exceed `storage.max_size`. Once full, the oldest
+/// events are dropped.
+///
+/// Recording requires a [`keystore_path`](Config::keystore_path): a replay
+/// has to sign as the recorded node, and the log holds no key material.
+///
+/// # Examples
+///
+/// ```
+/// # use aingle_minimal::{Config, RecordingConfig};
+/// let mut config = Config::iot_mode();
+/// config.recording = RecordingConfig::at("./aingle_inputs.log");
+/// assert!(config.validate().is_err());
+///
+/// config.keystore_path = Some("./node.key".to_string());
+/// assert!(config.validate().is_ok());
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingConfig {
+    /// Where the log is written. `None` (the default) disables recording.
+    pub path: Option<String>,
+    /// The most bytes the log may take on disk, across both of its segments.
+    pub max_bytes: usize,
+    /// The seed the node's random choices are drawn from.
+    ///
+    /// `None` picks a fresh seed at every start. The seed is written to the
+    /// log either way, so a replay makes the same choices. It has no part in
+    /// the node's identity, which comes from the keystore.
+    pub seed: Option<u64>,
+}
+
+impl Default for RecordingConfig {
+    fn default() -> Self {
+        Self {
+            path: None,
+            max_bytes: 64 * 1024,
+            seed: None,
+        }
+    }
+}
+
+impl RecordingConfig {
+    /// Records to `path` with the default size limit.
+    pub fn at(path: impl Into<String>) -> Self {
+        Self {
+            path: Some(path.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Returns `true` if inputs are recorded.
+    pub fn is_enabled(&self) -> bool {
+        self.path.is_some()
+    }
+}
+
 /// The type of storage backend to use for the node's database.
 ///
 /// Different backends offer different tradeoffs between performance, resource usage,
@@ -390,12 +784,40 @@ pub struct StorageConfig {
     /// If `true`, the storage will aggressively prune old data to stay within `max_size`.
     ///
     /// Pruning removes the oldest entries while preserving recent data as
-    /// specified by `keep_recent`.
+    /// specified by `keep_recent`. Lower [`EntryPriority`](crate::EntryPriority)
+    /// classes are pruned first.
     pub aggressive_pruning: bool,
     /// The number of recent entries to always keep, even when pruning.
     ///
     /// This ensures that important recent data is never lost during cleanup.
     pub keep_recent: usize,
+    /// The order in which records are evicted when the budget is enforced.
+    #[serde(default)]
+    pub eviction_policy: EvictionPolicy,
+    /// Fraction of `max_size` at which the node starts evicting records.
+    ///
+    /// Once the database reaches this watermark, the node evicts records in
+    /// [`eviction_policy`](Self::eviction_policy) order until it is back
+    /// under the watermark, then compacts. Must be in `(0.0, 1.0]`.
+    #[serde(default = "default_budget_watermark")]
+    pub budget_watermark: f32,
+    /// Encryption at rest for record payloads, or `None` to store them in
+    /// the clear.
+    ///
+    /// Keys are never written to configuration files; set this from the
+    /// keystore passphrase or a device key when the node starts. A store
+    /// written with encryption can only be opened with its key. See
+    /// [`storage_crypto`](crate::storage_crypto).
+    #[serde(skip)]
+    pub encryption: Option<StorageEncryption>,
+}
+
+fn default_budget_watermark() -> f32 {
+    0.9
+}
+
+fn default_keystore_passphrase_env() -> String {
+    ENV_KEYSTORE_PASSPHRASE.to_string()
 }
 
 impl Default for StorageConfig {
@@ -406,6 +828,9 @@ impl Default for StorageConfig {
             max_size: 5 * 1024 * 1024, // 5MB
             aggressive_pruning: true,
             keep_recent: 1000,
+            eviction_policy: EvictionPolicy::OldestFirst,
+            budget_watermark: 0.9,
+            encryption: None,
         }
     }
 }
@@ -450,6 +875,9 @@ impl StorageConfig {
             max_size: 100 * 1024 * 1024, // 100MB for production
             aggressive_pruning: false,
             keep_recent: 100_000,
+            eviction_policy: EvictionPolicy::OldestFirst,
+            budget_watermark: 0.9,
+            encryption: None,
         }
     }
 
@@ -472,6 +900,9 @@ impl StorageConfig {
             max_size: 10 * 1024 * 1024,
             aggressive_pruning: false,
             keep_recent: 10_000,
+            eviction_policy: EvictionPolicy::OldestFirst,
+            budget_watermark: 0.9,
+            encryption: None,
         }
     }
 }
@@ -555,6 +986,39 @@ pub struct Config {
     /// requiring manual peer configuration.
     pub enable_mdns: bool,
 
+    /// Static and DNS-based peer discovery.
+    ///
+    /// Used alongside mDNS, or instead of it where multicast is unavailable.
+    /// See [`DiscoveryConfig`] for options.
+    #[serde(default)]
+    pub discovery: DiscoveryConfig,
+
+    /// Relaying of entries between transports.
+    ///
+    /// See [`BridgeConfig`]; disabled by default.
+    #[serde(default)]
+    pub bridge: BridgeConfig,
+
+    /// Recording of inputs for replay.
+    ///
+    /// See [`RecordingConfig`]; disabled by default.
+    #[serde(default)]
+    pub recording: RecordingConfig,
+
+    /// Path to the encrypted keystore holding the node's identity.
+    ///
+    /// When set, the node loads its keypair from this file (creating it on
+    /// first run), so peers recognize it across restarts. When `None`, a new
+    /// ephemeral keypair is generated at every start.
+    #[serde(default)]
+    pub keystore_path: Option<String>,
+
+    /// Name of the environment variable holding the keystore passphrase.
+    ///
+    /// The passphrase itself is never stored in the configuration.
+    #[serde(default = "default_keystore_passphrase_env")]
+    pub keystore_passphrase_env: String,
+
     /// The logging level.
     ///
     /// Valid values: "trace", "debug", "info", "warn", "error".
@@ -573,6 +1037,11 @@ impl Default for Config {
             memory_limit: 512 * 1024, // 512KB
             enable_metrics: false,
             enable_mdns: true, // Enable by default for auto-discovery
+            discovery: DiscoveryConfig::default(),
+            bridge: BridgeConfig::default(),
+            recording: RecordingConfig::default(),
+            keystore_path: None,
+            keystore_passphrase_env: ENV_KEYSTORE_PASSPHRASE.to_string(),
             log_level: "info".to_string(),
         }
     }
@@ -619,10 +1088,18 @@ impl Config {
                 max_size: 1024 * 1024, // 1MB
                 aggressive_pruning: true,
                 keep_recent: 100,
+                eviction_policy: EvictionPolicy::OldestFirst,
+                budget_watermark: 0.9,
+                encryption: None,
             },
             memory_limit: 256 * 1024, // 256KB
             enable_metrics: false,
             enable_mdns: true, // Auto-discovery for IoT networks
+            discovery: DiscoveryConfig::default(),
+            bridge: BridgeConfig::default(),
+            recording: RecordingConfig::default(),
+            keystore_path: None,
+            keystore_passphrase_env: ENV_KEYSTORE_PASSPHRASE.to_string(),
             log_level: "warn".to_string(),
         }
     }
@@ -667,10 +1144,18 @@ impl Config {
                 max_size: 512 * 1024, // 512KB
                 aggressive_pruning: true,
                 keep_recent: 50,
+                eviction_policy: EvictionPolicy::OldestFirst,
+                budget_watermark: 0.9,
+                encryption: None,
             },
             memory_limit: 128 * 1024, // 128KB
             enable_metrics: false,
             enable_mdns: false, // Disabled to save power
+            discovery: DiscoveryConfig::default(),
+            bridge: BridgeConfig::default(),
+            recording: RecordingConfig::default(),
+            keystore_path: None,
+            keystore_passphrase_env: ENV_KEYSTORE_PASSPHRASE.to_string(),
             log_level: "error".to_string(),
         }
     }
@@ -711,6 +1196,11 @@ impl Config {
             memory_limit: 512 * 1024 * 1024, // 512MB
             enable_metrics: true,
             enable_mdns: true, // Auto-discovery in production
+            discovery: DiscoveryConfig::default(),
+            bridge: BridgeConfig::default(),
+            recording: RecordingConfig::default(),
+            keystore_path: None,
+            keystore_passphrase_env: ENV_KEYSTORE_PASSPHRASE.to_string(),
             log_level: "info".to_string(),
         }
     }
@@ -722,6 +1212,8 @@ impl Config {
     /// - `AINGLE_PUBLISH_INTERVAL_MS` - Override publish interval in milliseconds
     /// - `AINGLE_GOSSIP_LOOP_ITERATION_DELAY_MS` - Override gossip loop delay in milliseconds
     /// - `AINGLE_MEMORY_LIMIT_KB` - Override memory limit in kilobytes
+    /// - `AINGLE_BOOTSTRAP_PEERS` - Comma-separated static peers (`host:port`)
+    /// - `AINGLE_DNS_SERVICE` - DNS name to resolve for peers
     ///
     /// # Examples
     ///
@@ -764,6 +1256,29 @@ impl Config {
             }
         }
 
+        // Static bootstrap peers, comma separated
+        if let Ok(peers_str) = std::env::var("AINGLE_BOOTSTRAP_PEERS") {
+            config.discovery.bootstrap_peers = peers_str
+                .split(',')
+                .map(|p| p.trim().to_string())
+                .filter(|p| !p.is_empty())
+                .collect();
+        }
+
+        // DNS name listing the network's peers
+        if let Ok(service) = std::env::var("AINGLE_DNS_SERVICE") {
+            if !service.is_empty() {
+                config.discovery.dns_service = Some(service);
+            }
+        }
+
+        // Persistent node identity
+        if let Ok(path) = std::env::var("AINGLE_KEYSTORE_PATH") {
+            if !path.is_empty() {
+                config.keystore_path = Some(path);
+            }
+        }
+
         config
     }
 
@@ -800,11 +1315,24 @@ impl Config {
                 error_delay: Duration::from_millis(100),
                 output_target_mbps: 10.0,
                 max_peers: 5,
+                bloom_target_fpr: default_bloom_target_fpr(),
+                bloom_rotation_interval: Duration::from_secs(10),
+                bloom_overlap: Duration::from_secs(1),
+                peer_limits: PeerLimitConfig {
+                    requests_per_sec: 100.0,
+                    request_burst: 100,
+                    ..PeerLimitConfig::default()
+                },
             },
             storage: StorageConfig::memory(),
             memory_limit: 64 * 1024, // 64KB
             enable_metrics: false,
             enable_mdns: false,
+            discovery: DiscoveryConfig::default(),
+            bridge: BridgeConfig::default(),
+            recording: RecordingConfig::default(),
+            keystore_path: None,
+            keystore_passphrase_env: ENV_KEYSTORE_PASSPHRASE.to_string(),
             log_level: "debug".to_string(),
         }
     }
@@ -814,11 +1342,19 @@ impl Config {
     /// This method checks that:
     /// - Memory limit is at least 64KB
     /// - Storage max size is at least 256KB
+    /// - Discovery retry and re-resolution intervals are usable
+    /// - Bridge routes connect two different transports and have positive limits
+    /// - The recording log fits in the storage budget and a keystore is set
     ///
     /// # Errors
     ///
     /// Returns [`ConfigError::MemoryTooLow`] if memory limit is below 64KB.
     /// Returns [`ConfigError::StorageTooLow`] if storage max size is below 256KB.
+    /// Returns [`ConfigError::Invalid`] if the discovery intervals are zero or inverted,
+    /// a bridge route is invalid, or recording is enabled without a keystore or
+    /// with a log that does not fit.
+    ///
+    /// Only the first violation is returned; [`check`](Self::check) lists all of them.
     ///
     /// # Examples
     ///
@@ -832,14 +1368,249 @@ impl Config {
     /// assert!(bad_config.validate().is_err());
     /// ```
     pub fn validate(&self) -> Result<(), ConfigError> {
+        let Some(error) = self.check().into_iter().next() else {
+            return Ok(());
+        };
+        Err(match error.field.as_str() {
+            "memory_limit" => ConfigError::MemoryTooLow(self.memory_limit),
+            "storage.max_size" => ConfigError::StorageTooLow(self.storage.max_size),
+            _ => ConfigError::Invalid(error.to_string()),
+        })
+    }
+
+    /// Returns every setting that violates a constraint, in the order
+    /// [`validate`](Self::validate) checks them.
+    ///
+    /// The errors name the offending field by its path, e.g.
+    /// `gossip.peer_limits.request_burst`. They carry no [`ConfigSource`];
+    /// [`LoadedConfig::validate`](crate::LoadedConfig::validate) adds the
+    /// layer each value came from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use aingle_minimal::Config;
+    /// let mut config = Config::default();
+    /// config.memory_limit = 1024;
+    /// config.storage.budget_watermark = 1.5;
+    ///
+    /// let errors = config.check();
+    /// assert_eq!(errors.len(), 2);
+    /// assert_eq!(errors[0].field, "memory_limit");
+    /// assert_eq!(errors[1].field, "storage.budget_watermark");
+    /// ```
+    pub fn check(&self) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+
         if self.memory_limit < 64 * 1024 {
-            return Err(ConfigError::MemoryTooLow(self.memory_limit));
+            errors.push(FieldError::new(
+                "memory_limit",
+                self.memory_limit.to_string(),
+                "must be at least 65536 bytes (64KB)",
+            ));
         }
 
         if self.storage.max_size < 256 * 1024 {
-            return Err(ConfigError::StorageTooLow(self.storage.max_size));
+            errors.push(FieldError::new(
+                "storage.max_size",
+                self.storage.max_size.to_string(),
+                "must be at least 262144 bytes (256KB)",
+            ));
+        }
+
+        let discovery = &self.discovery;
+        if discovery.retry_base.is_zero() || discovery.retry_base > discovery.retry_max {
+            errors.push(FieldError::new(
+                "discovery.retry_base",
+                format!("{:?}", discovery.retry_base),
+                "must be non-zero and at most discovery.retry_max",
+            ));
         }
 
+        if discovery.resolve_interval.is_zero() {
+            errors.push(FieldError::new(
+                "discovery.resolve_interval",
+                format!("{:?}", discovery.resolve_interval),
+                "must be non-zero",
+            ));
+        }
+
+        if self.keystore_path.is_some() && self.keystore_passphrase_env.is_empty() {
+            errors.push(FieldError::new(
+                "keystore_passphrase_env",
+                "\"\"",
+                "must name an environment variable when keystore_path is set",
+            ));
+        }
+
+        let gossip = &self.gossip;
+        if !(gossip.bloom_target_fpr > 0.0 && gossip.bloom_target_fpr < 1.0) {
+            errors.push(FieldError::new(
+                "gossip.bloom_target_fpr",
+                gossip.bloom_target_fpr.to_string(),
+                "must be in (0.0, 1.0)",
+            ));
+        }
+
+        if gossip.bloom_rotation_interval.is_zero()
+            || gossip.bloom_overlap >= gossip.bloom_rotation_interval
+        {
+            errors.push(FieldError::new(
+                "gossip.bloom_rotation_interval",
+                format!("{:?}", gossip.bloom_rotation_interval),
+                "must be non-zero and longer than gossip.bloom_overlap",
+            ));
+        }
+
+        let limits = &gossip.peer_limits;
+        if limits.requests_per_sec.is_nan() || limits.requests_per_sec <= 0.0 {
+            errors.push(FieldError::new(
+                "gossip.peer_limits.requests_per_sec",
+                limits.requests_per_sec.to_string(),
+                "must be positive",
+            ));
+        }
+        if limits.request_burst == 0 {
+            errors.push(FieldError::new(
+                "gossip.peer_limits.request_burst",
+                "0",
+                "must be positive",
+            ));
+        }
+        if limits.ban_threshold.is_nan() || limits.ban_threshold <= 0.0 {
+            errors.push(FieldError::new(
+                "gossip.peer_limits.ban_threshold",
+                limits.ban_threshold.to_string(),
+                "must be positive",
+            ));
+        }
+        if limits.score_half_life.is_zero() {
+            errors.push(FieldError::new(
+                "gossip.peer_limits.score_half_life",
+                format!("{:?}", limits.score_half_life),
+                "must be non-zero",
+            ));
+        }
+
+        for (i, route) in self.bridge.routes.iter().enumerate() {
+            let value = format!("{} -> {}", route.from, route.to);
+            if route.from == route.to {
+                errors.push(FieldError::new(
+                    "bridge.routes",
+                    value,
+                    "must connect two different transports",
+                ));
+            } else if !(route.max_per_sec > 0.0 && route.burst > 0) {
+                errors.push(FieldError::new(
+                    "bridge.routes",
+                    value,
+                    "must have a positive max_per_sec and burst",
+                ));
+            } else if self.bridge.routes[..i]
+                .iter()
+                .any(|r| r.from == route.from && r.to == route.to)
+            {
+                errors.push(FieldError::new(
+                    "bridge.routes",
+                    value,
+                    "must not be listed twice",
+                ));
+            }
+        }
+        if self.bridge.is_enabled() && self.bridge.seen_cache_size == 0 {
+            errors.push(FieldError::new(
+                "bridge.seen_cache_size",
+                "0",
+                "must be positive when bridge routes are set",
+            ));
+        }
+
+        if !(self.storage.budget_watermark > 0.0 && self.storage.budget_watermark <= 1.0) {
+            errors.push(FieldError::new(
+                "storage.budget_watermark",
+                self.storage.budget_watermark.to_string(),
+                "must be in (0.0, 1.0]",
+            ));
+        }
+
+        if self.recording.is_enabled()
+            && !(4096..=self.storage.max_size).contains(&self.recording.max_bytes)
+        {
+            errors.push(FieldError::new(
+                "recording.max_bytes",
+                self.recording.max_bytes.to_string(),
+                "must be at least 4096 and at most storage.max_size",
+            ));
+        }
+
+        if self.recording.is_enabled() && self.keystore_path.is_none() {
+            errors.push(FieldError::new(
+                "keystore_path",
+                "None",
+                "must be set when recording is enabled",
+            ));
+        }
+
+        errors
+    }
+}
+
+/// A setting that violates a constraint.
+///
+/// Returned by [`Config::check`], and inside [`ConfigError::Fields`] when a
+/// layered configuration is rejected.
+///
+/// # Examples
+///
+/// ```
+/// # use aingle_minimal::config::{ConfigSource, FieldError};
+/// let error = FieldError::new("memory_limit", "1024", "must be at least 65536 bytes (64KB)")
+///     .with_source(ConfigSource::Env("AINGLE_MEMORY_LIMIT".to_string()));
+/// assert_eq!(
+///     error.to_string(),
+///     "memory_limit = 1024: must be at least 65536 bytes (64KB) (set by env AINGLE_MEMORY_LIMIT)"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldError {
+    /// The path of the offending field, e.g. `storage.max_size`.
+    pub field: String,
+    /// The offending value, as it was given or as it was merged.
+    pub value: String,
+    /// The constraint the value violates.
+    pub constraint: String,
+    /// The layer that set the value, if known.
+    pub source: Option<ConfigSource>,
+}
+
+impl FieldError {
+    /// Creates an error without a source.
+    pub fn new(
+        field: impl Into<String>,
+        value: impl Into<String>,
+        constraint: impl Into<String>,
+    ) -> Self {
+        Self {
+            field: field.into(),
+            value: value.into(),
+            constraint: constraint.into(),
+            source: None,
+        }
+    }
+
+    /// Sets the layer that set the value.
+    pub fn with_source(mut self, source: ConfigSource) -> Self {
+        self.source = Some(source);
+        self
+    }
+}
+
+impl std::fmt::Display for FieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} = {}: {}", self.field, self.value, self.constraint)?;
+        if let Some(source) = &self.source {
+            write!(f, " (set by {})", source)?;
+        }
         Ok(())
     }
 }
@@ -866,6 +1637,12 @@ pub enum ConfigError {
     StorageTooLow(usize),
     /// The configuration contains an invalid setting.
     Invalid(String),
+    /// One or more settings of a layered configuration were rejected.
+    ///
+    /// Returned by [`ConfigLoader`](crate::ConfigLoader) for values that do
+    /// not parse and by [`LoadedConfig::validate`](crate::LoadedConfig::validate)
+    /// for values that violate a constraint.
+    Fields(Vec<FieldError>),
 }
 
 impl std::fmt::Display for ConfigError {
@@ -878,6 +1655,20 @@ impl std::fmt::Display for ConfigError {
                 write!(f, "Storage limit too low: {} bytes (minimum 256KB)", size)
             }
             ConfigError::Invalid(msg) => write!(f, "Invalid configuration: {}", msg),
+            ConfigError::Fields(errors) => match errors.as_slice() {
+                [error] => write!(f, "Invalid configuration: {}", error),
+                errors => {
+                    write!(
+                        f,
+                        "Invalid configuration: {} settings rejected",
+                        errors.len()
+                    )?;
+                    for error in errors {
+                        write!(f, "\n  {}", error)?;
+                    }
+                    Ok(())
+                }
+            },
         }
     }
 }
@@ -935,6 +1726,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_power_mode_expiry_sweep_interval() {
+        assert!(
+            PowerMode::Full.expiry_sweep_interval() < PowerMode::Balanced.expiry_sweep_interval()
+        );
+        assert!(
+            PowerMode::Balanced.expiry_sweep_interval() < PowerMode::Low.expiry_sweep_interval()
+        );
+        assert!(
+            PowerMode::Low.expiry_sweep_interval() < PowerMode::Critical.expiry_sweep_interval()
+        );
+    }
+
     #[test]
     fn test_transport_config_default() {
         let config: TransportConfig = Default::default();
@@ -1129,4 +1933,143 @@ mod tests {
         // node_id is Option<String>
         assert!(config.node_id.is_none() || !config.node_id.as_ref().unwrap().is_empty());
     }
+
+    #[test]
+    fn test_discovery_config_validation() {
+        let mut config = Config::default();
+        assert!(config.discovery.is_empty());
+
+        config.discovery.bootstrap_peers = vec!["10.0.0.1:5683".to_string()];
+        assert!(!config.discovery.is_empty());
+        assert!(config.validate().is_ok());
+
+        config.discovery.retry_base = config.discovery.retry_max + Duration::from_secs(1);
+        assert!(matches!(config.validate(), Err(ConfigError::Invalid(_))));
+    }
+
+    #[test]
+    fn test_discovery_config_defaults_when_missing() {
+        let mut value = serde_json::to_value(Config::default()).unwrap();
+        value.as_object_mut().unwrap().remove("discovery");
+        let config: Config = serde_json::from_value(value).unwrap();
+        assert!(config.discovery.is_empty());
+    }
+
+    #[test]
+    fn test_storage_budget_watermark_validation() {
+        let mut config = Config::default();
+        config.storage.budget_watermark = 0.0;
+        assert!(matches!(config.validate(), Err(ConfigError::Invalid(_))));
+
+        config.storage.budget_watermark = 1.5;
+        assert!(matches!(config.validate(), Err(ConfigError::Invalid(_))));
+
+        config.storage.budget_watermark = 1.0;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_storage_budget_defaults_when_missing() {
+        let mut value = serde_json::to_value(StorageConfig::default()).unwrap();
+        let object = value.as_object_mut().unwrap();
+        object.remove("eviction_policy");
+        object.remove("budget_watermark");
+        let config: StorageConfig = serde_json::from_value(value).unwrap();
+        assert_eq!(config.eviction_policy, EvictionPolicy::OldestFirst);
+        assert_eq!(config.budget_watermark, 0.9);
+    }
+
+    #[test]
+    fn test_gossip_bloom_validation() {
+        let mut config = Config::default();
+        config.gossip.bloom_target_fpr = 0.0;
+        assert!(matches!(config.validate(), Err(ConfigError::Invalid(_))));
+
+        config.gossip.bloom_target_fpr = 0.05;
+        config.gossip.bloom_overlap = config.gossip.bloom_rotation_interval;
+        assert!(matches!(config.validate(), Err(ConfigError::Invalid(_))));
+
+        config.gossip.bloom_overlap = Duration::from_secs(1);
+        assert!(config.validate().is_ok());
+        assert!(Config::iot_mode().validate().is_ok());
+        assert!(Config::low_power().validate().is_ok());
+        assert!(Config::test_mode().validate().is_ok());
+    }
+
+    #[test]
+    fn test_gossip_bloom_defaults_when_missing() {
+        let mut value = serde_json::to_value(GossipConfig::default()).unwrap();
+        let object = value.as_object_mut().unwrap();
+        object.remove("bloom_target_fpr");
+        object.remove("bloom_rotation_interval");
+        object.remove("bloom_overlap");
+        let config: GossipConfig = serde_json::from_value(value).unwrap();
+        assert_eq!(config.bloom_target_fpr, 0.01);
+        assert_eq!(config.bloom_rotation_interval, Duration::from_secs(300));
+        assert_eq!(config.bloom_overlap, Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_peer_limit_validation() {
+        let mut config = Config::default();
+        config.gossip.peer_limits.requests_per_sec = 0.0;
+        assert!(matches!(config.validate(), Err(ConfigError::Invalid(_))));
+
+        config.gossip.peer_limits.requests_per_sec = 1.0;
+        config.gossip.peer_limits.score_half_life = Duration::ZERO;
+        assert!(matches!(config.validate(), Err(ConfigError::Invalid(_))));
+
+        config.gossip.peer_limits.score_half_life = Duration::from_secs(60);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_peer_limits_default_when_missing() {
+        let mut value = serde_json::to_value(GossipConfig::default()).unwrap();
+        value.as_object_mut().unwrap().remove("peer_limits");
+        let config: GossipConfig = serde_json::from_value(value).unwrap();
+        assert_eq!(config.peer_limits.request_burst, 10);
+        assert_eq!(config.peer_limits.max_banned, 64);
+    }
+
+    #[test]
+    fn test_bridge_validation() {
+        let route = || BridgeRoute::new(TransportKind::Ble, TransportKind::Coap);
+        let mut config = Config {
+            bridge: BridgeConfig::default().route(route()),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        for bad in [
+            BridgeConfig::default().route(BridgeRoute::new(TransportKind::Ble, TransportKind::Ble)),
+            BridgeConfig::default().route(route().with_rate(0.0, 10)),
+            BridgeConfig::default().route(route().with_rate(1.0, 0)),
+            BridgeConfig::default().route(route()).route(route()),
+            BridgeConfig {
+                seen_cache_size: 0,
+                ..BridgeConfig::default().route(route())
+            },
+        ] {
+            config.bridge = bad;
+            assert!(matches!(config.validate(), Err(ConfigError::Invalid(_))));
+        }
+    }
+
+    #[test]
+    fn test_bridge_defaults_when_missing() {
+        let mut value = serde_json::to_value(Config::default()).unwrap();
+        value.as_object_mut().unwrap().remove("bridge");
+        let config: Config = serde_json::from_value(value).unwrap();
+        assert!(!config.bridge.is_enabled());
+
+        let route: BridgeRoute =
+            serde_json::from_str(r#"{"from":"ble","to":"coap","max_per_sec":2.0,"burst":4}"#)
+                .unwrap();
+        assert_eq!(route.from, TransportKind::Ble);
+        assert_eq!(
+            TransportConfig::default().kind().to_string(),
+            TransportKind::Coap.as_str()
+        );
+    }
 }
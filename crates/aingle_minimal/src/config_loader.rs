@@ -0,0 +1,924 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Layered configuration loading.
+//!
+//! [`ConfigLoader`] builds a [`Config`] from up to four layers, each one
+//! overriding the layers before it:
+//!
+//! 1. a base configuration, [`Config::default`] or a preset
+//! 2. a TOML file
+//! 3. environment variables
+//! 4. command-line overrides
+//!
+//! Every setting is addressed by its field path, such as `gossip.max_peers` or
+//! `storage.db_path`. Durations are tables of `secs` and `nanos`, so the
+//! publish interval is set through `publish_interval.secs` and
+//! `publish_interval.nanos`. `transport` is replaced as a whole:
+//!
+//! ```toml
+//! memory_limit = 262144
+//! transport = { Quic = { bind_addr = "0.0.0.0", port = 19081 } }
+//!
+//! [gossip]
+//! max_peers = 4
+//! loop_delay = { secs = 0, nanos = 250000000 }
+//! ```
+//!
+//! # Environment variables
+//!
+//! The variable for a setting is `AINGLE_` followed by its path in upper case,
+//! with dots replaced by underscores (see [`env_var_name`]):
+//!
+//! | Setting                               | Variable                                     |
+//! |---------------------------------------|----------------------------------------------|
+//! | `memory_limit`                        | `AINGLE_MEMORY_LIMIT`                        |
+//! | `log_level`                           | `AINGLE_LOG_LEVEL`                           |
+//! | `publish_interval.secs`               | `AINGLE_PUBLISH_INTERVAL_SECS`               |
+//! | `gossip.max_peers`                    | `AINGLE_GOSSIP_MAX_PEERS`                    |
+//! | `gossip.peer_limits.requests_per_sec` | `AINGLE_GOSSIP_PEER_LIMITS_REQUESTS_PER_SEC` |
+//! | `storage.db_path`                     | `AINGLE_STORAGE_DB_PATH`                     |
+//! | `discovery.bootstrap_peers`           | `AINGLE_DISCOVERY_BOOTSTRAP_PEERS`           |
+//! | `transport`                           | `AINGLE_TRANSPORT`                           |
+//!
+//! Values are parsed according to the type of the setting. Lists take a JSON
+//! array or comma-separated strings, and `transport` takes JSON. The legacy
+//! variables read by [`Config::from_env`], such as `AINGLE_MEMORY_LIMIT_KB`,
+//! are not consulted.
+//!
+//! # Provenance and diagnostics
+//!
+//! The [`LoadedConfig`] records which layer set each final value. Unknown keys
+//! in the file are reported as warnings; values of the wrong type and values
+//! violating a constraint are rejected with a [`FieldError`] naming the field,
+//! the value, the constraint and the layer that set it.
+//!
+//! # Examples
+//!
+//! ```
+//! use aingle_minimal::{ConfigLoader, ConfigSource};
+//!
+//! # fn main() -> Result<(), aingle_minimal::config::ConfigError> {
+//! let loaded = ConfigLoader::new()
+//!     .env_vars([("AINGLE_GOSSIP_MAX_PEERS", "3")])
+//!     .set("memory_limit", "131072")
+//!     .load()?;
+//!
+//! assert_eq!(loaded.config.gossip.max_peers, 3);
+//! assert_eq!(
+//!     loaded.source("gossip.max_peers"),
+//!     Some(&ConfigSource::Env("AINGLE_GOSSIP_MAX_PEERS".to_string()))
+//! );
+//! assert_eq!(loaded.source("memory_limit"), Some(&ConfigSource::Cli));
+//! assert_eq!(loaded.source("log_level"), Some(&ConfigSource::Default));
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::config::{Config, ConfigError, FieldError};
+use log::warn;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Prefix of the environment variables read by [`ConfigLoader`].
+pub const ENV_PREFIX: &str = "AINGLE_";
+
+/// Settings that are replaced as a whole rather than field by field.
+///
+/// `transport` is an enum: merging the fields of two variants yields neither.
+const ATOMIC_SETTINGS: &[&str] = &["transport"];
+
+/// Returns the environment variable that sets the field at `path`.
+///
+/// # Examples
+///
+/// ```
+/// # use aingle_minimal::config_loader::env_var_name;
+/// assert_eq!(env_var_name("gossip.max_peers"), "AINGLE_GOSSIP_MAX_PEERS");
+/// ```
+pub fn env_var_name(path: &str) -> String {
+    format!("{}{}", ENV_PREFIX, path.replace('.', "_").to_uppercase())
+}
+
+/// The layer that set a configuration value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// The base configuration the layers are applied to.
+    Default,
+    /// A TOML configuration file.
+    File(PathBuf),
+    /// The named environment variable.
+    Env(String),
+    /// A command-line override.
+    Cli,
+}
+
+impl ConfigSource {
+    /// Position of the layer; later layers override earlier ones.
+    fn rank(&self) -> u8 {
+        match self {
+            Self::Default => 0,
+            Self::File(_) => 1,
+            Self::Env(_) => 2,
+            Self::Cli => 3,
+        }
+    }
+}
+
+impl std::fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::File(path) => write!(f, "file {}", path.display()),
+            Self::Env(name) => write!(f, "env {}", name),
+            Self::Cli => write!(f, "command line"),
+        }
+    }
+}
+
+/// A merged setting and the layer that set it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Setting {
+    /// The value, in its JSON form.
+    pub value: Value,
+    /// The layer the value came from.
+    pub source: ConfigSource,
+}
+
+/// A setting that differs between two configurations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettingDiff {
+    /// The path of the field.
+    pub path: String,
+    /// The value in the first configuration.
+    pub left: Value,
+    /// The value in the second configuration.
+    pub right: Value,
+}
+
+/// Where the environment layer is read from.
+enum EnvLayer {
+    Process,
+    Vars(BTreeMap<String, String>),
+    Disabled,
+}
+
+/// Builds a [`Config`] from a base, a TOML file, the environment and
+/// command-line overrides.
+///
+/// See the [module documentation](self) for the layers and the naming of
+/// settings.
+pub struct ConfigLoader {
+    base: Config,
+    file: Option<PathBuf>,
+    env: EnvLayer,
+    overrides: Vec<(String, String)>,
+}
+
+impl Default for ConfigLoader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConfigLoader {
+    /// Creates a loader over [`Config::default`] that reads the process
+    /// environment.
+    pub fn new() -> Self {
+        Self {
+            base: Config::default(),
+            file: None,
+            env: EnvLayer::Process,
+            overrides: Vec::new(),
+        }
+    }
+
+    /// Sets the base configuration, e.g. [`Config::iot_mode`].
+    pub fn base(mut self, config: Config) -> Self {
+        self.base = config;
+        self
+    }
+
+    /// Reads the TOML file at `path` over the base.
+    pub fn file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.file = Some(path.into());
+        self
+    }
+
+    /// Reads the environment layer from `vars` instead of the process
+    /// environment.
+    pub fn env_vars<K, V>(mut self, vars: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.env = EnvLayer::Vars(
+            vars.into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        );
+        self
+    }
+
+    /// Skips the environment layer.
+    pub fn without_env(mut self) -> Self {
+        self.env = EnvLayer::Disabled;
+        self
+    }
+
+    /// Overrides the field at `path` from the command line.
+    ///
+    /// `value` is parsed like an environment variable. Later calls for the
+    /// same path win.
+    pub fn set(mut self, path: impl Into<String>, value: impl Into<String>) -> Self {
+        self.overrides.push((path.into(), value.into()));
+        self
+    }
+
+    /// Merges the layers without checking the constraints of
+    /// [`Config::check`].
+    ///
+    /// Unknown keys in the file are logged and returned in
+    /// [`LoadedConfig::warnings`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::Invalid`] if the file cannot be read or parsed,
+    /// and [`ConfigError::Fields`] for values of the wrong type and
+    /// command-line overrides of unknown fields.
+    pub fn merge(self) -> Result<LoadedConfig, ConfigError> {
+        let mut layers = Layers::new(&self.base)?;
+
+        if let Some(path) = &self.file {
+            let text = std::fs::read_to_string(path).map_err(|e| {
+                ConfigError::Invalid(format!("cannot read {}: {}", path.display(), e))
+            })?;
+            let table: toml::Table = toml::from_str(&text).map_err(|e| {
+                ConfigError::Invalid(format!("cannot parse {}: {}", path.display(), e))
+            })?;
+            let value = serde_json::to_value(table).map_err(|e| {
+                ConfigError::Invalid(format!("cannot convert {}: {}", path.display(), e))
+            })?;
+            layers.overlay_file(&value, "", path);
+        }
+
+        let env = match self.env {
+            EnvLayer::Process => std::env::vars_os()
+                .filter_map(|(k, v)| Some((k.into_string().ok()?, v.into_string().ok()?)))
+                .filter(|(k, _)| k.starts_with(ENV_PREFIX))
+                .collect(),
+            EnvLayer::Vars(vars) => vars,
+            EnvLayer::Disabled => BTreeMap::new(),
+        };
+        let paths: Vec<String> = layers.expected.keys().cloned().collect();
+        for path in paths {
+            let name = env_var_name(&path);
+            if let Some(raw) = env.get(&name) {
+                layers.set_raw(&path, raw, ConfigSource::Env(name));
+            }
+        }
+
+        for (path, raw) in &self.overrides {
+            if layers.expected.contains_key(path) {
+                layers.set_raw(path, raw, ConfigSource::Cli);
+            } else {
+                layers.errors.push(
+                    FieldError::new(path, raw, "is not a known setting")
+                        .with_source(ConfigSource::Cli),
+                );
+            }
+        }
+
+        if !layers.errors.is_empty() {
+            return Err(ConfigError::Fields(layers.errors));
+        }
+        for warning in &layers.warnings {
+            warn!("{}", warning);
+        }
+
+        let value = unflatten(layers.settings.iter().map(|(path, s)| (path, &s.value)));
+        let mut config: Config = serde_json::from_value(value)
+            .map_err(|e| ConfigError::Invalid(format!("cannot build configuration: {}", e)))?;
+        // Not representable in the layers; kept from the base
+        config.storage.encryption = self.base.storage.encryption;
+
+        Ok(LoadedConfig {
+            config,
+            warnings: layers.warnings,
+            settings: layers.settings,
+        })
+    }
+
+    /// Merges the layers and validates the result.
+    ///
+    /// # Errors
+    ///
+    /// The errors of [`merge`](Self::merge), and [`ConfigError::Fields`]
+    /// listing every constraint the merged configuration violates.
+    pub fn load(self) -> Result<LoadedConfig, ConfigError> {
+        let loaded = self.merge()?;
+        loaded.validate()?;
+        Ok(loaded)
+    }
+}
+
+/// A configuration merged from layers, with the source of each value.
+#[derive(Debug, Clone)]
+pub struct LoadedConfig {
+    /// The merged configuration.
+    pub config: Config,
+    /// Problems that did not stop loading, such as unknown keys in the file.
+    pub warnings: Vec<String>,
+    settings: BTreeMap<String, Setting>,
+}
+
+impl LoadedConfig {
+    /// Returns the final value and source of the field at `path`.
+    pub fn setting(&self, path: &str) -> Option<&Setting> {
+        self.settings.get(path)
+    }
+
+    /// Returns every field with its final value and source, ordered by path.
+    pub fn settings(&self) -> impl Iterator<Item = (&str, &Setting)> {
+        self.settings.iter().map(|(path, s)| (path.as_str(), s))
+    }
+
+    /// Returns the layer that set the field at `path`.
+    ///
+    /// For a table, such as a duration, this is the latest layer that set
+    /// any of its fields.
+    pub fn source(&self, path: &str) -> Option<&ConfigSource> {
+        if let Some(setting) = self.settings.get(path) {
+            return Some(&setting.source);
+        }
+        let prefix = format!("{}.", path);
+        self.settings
+            .range(prefix.clone()..)
+            .take_while(|(p, _)| p.starts_with(&prefix))
+            .map(|(_, s)| &s.source)
+            .max_by_key(|source| source.rank())
+    }
+
+    /// Checks the merged configuration against [`Config::check`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::Fields`] listing every violation, each with the
+    /// layer that set the offending value.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let errors: Vec<FieldError> = self
+            .config
+            .check()
+            .into_iter()
+            .map(|error| FieldError {
+                source: self.source(&error.field).cloned(),
+                ..error
+            })
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Fields(errors))
+        }
+    }
+}
+
+impl Config {
+    /// Loads the configuration from the defaults, an optional TOML file and
+    /// the environment, and validates it.
+    ///
+    /// Use [`ConfigLoader`] to start from a preset or to apply command-line
+    /// overrides.
+    ///
+    /// # Errors
+    ///
+    /// See [`ConfigLoader::load`].
+    pub fn load(path: Option<&Path>) -> Result<LoadedConfig, ConfigError> {
+        let mut loader = ConfigLoader::new();
+        if let Some(path) = path {
+            loader = loader.file(path);
+        }
+        loader.load()
+    }
+
+    /// Returns the fields whose values differ from `other`, ordered by path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use aingle_minimal::Config;
+    /// let diff = Config::default().diff(&Config::low_power());
+    /// assert!(diff.iter().any(|d| d.path == "enable_mdns"));
+    /// assert!(!diff.iter().any(|d| d.path == "transport"));
+    /// ```
+    pub fn diff(&self, other: &Config) -> Vec<SettingDiff> {
+        let left = flatten(&serde_json::to_value(self).expect("configuration serializes"));
+        let mut right = flatten(&serde_json::to_value(other).expect("configuration serializes"));
+        let mut diff: Vec<SettingDiff> = left
+            .into_iter()
+            .filter_map(|(path, left)| {
+                let right = right.remove(&path).unwrap_or(Value::Null);
+                (left != right).then_some(SettingDiff { path, left, right })
+            })
+            .collect();
+        diff.extend(right.into_iter().map(|(path, right)| SettingDiff {
+            path,
+            left: Value::Null,
+            right,
+        }));
+        diff.sort_by(|a, b| a.path.cmp(&b.path));
+        diff
+    }
+}
+
+/// The settings being merged.
+struct Layers {
+    /// The base values, which fix the type of each field
+    expected: BTreeMap<String, Value>,
+    settings: BTreeMap<String, Setting>,
+    errors: Vec<FieldError>,
+    warnings: Vec<String>,
+}
+
+impl Layers {
+    fn new(base: &Config) -> Result<Self, ConfigError> {
+        let value = serde_json::to_value(base)
+            .map_err(|e| ConfigError::Invalid(format!("cannot serialize configuration: {}", e)))?;
+        let expected = flatten(&value);
+        let settings = expected
+            .iter()
+            .map(|(path, value)| {
+                let setting = Setting {
+                    value: value.clone(),
+                    source: ConfigSource::Default,
+                };
+                (path.clone(), setting)
+            })
+            .collect();
+        Ok(Self {
+            expected,
+            settings,
+            errors: Vec::new(),
+            warnings: Vec::new(),
+        })
+    }
+
+    /// Returns `true` if `path` names a table of settings.
+    fn is_table(&self, path: &str) -> bool {
+        let prefix = format!("{}.", path);
+        self.expected
+            .range(prefix.clone()..)
+            .next()
+            .is_some_and(|(p, _)| p.starts_with(&prefix))
+    }
+
+    /// Applies the parsed TOML table `value` found at `prefix`.
+    fn overlay_file(&mut self, value: &Value, prefix: &str, file: &Path) {
+        let Value::Object(table) = value else {
+            return;
+        };
+        for (key, value) in table {
+            let path = if prefix.is_empty() {
+                key.clone()
+            } else {
+                format!("{}.{}", prefix, key)
+            };
+            let source = ConfigSource::File(file.to_path_buf());
+
+            let constraint = self
+                .expected
+                .get(&path)
+                .map(|expected| type_constraint(expected, value));
+            if let Some(constraint) = constraint {
+                match constraint {
+                    None => self.set(&path, value.clone(), source),
+                    Some(constraint) => self.errors.push(
+                        FieldError::new(&path, value.to_string(), constraint).with_source(source),
+                    ),
+                }
+            } else if self.is_table(&path) {
+                if value.is_object() {
+                    self.overlay_file(value, &path, file);
+                } else {
+                    self.errors.push(
+                        FieldError::new(&path, value.to_string(), "must be a table")
+                            .with_source(source),
+                    );
+                }
+            } else {
+                self.warnings
+                    .push(format!("unknown setting {} in {}", path, file.display()));
+            }
+        }
+    }
+
+    /// Applies a value given as text, from the environment or the command line.
+    fn set_raw(&mut self, path: &str, raw: &str, source: ConfigSource) {
+        let expected = &self.expected[path];
+        let parsed = parse_raw(expected, raw);
+        let constraint = describe(expected);
+        match parsed {
+            Some(value) => self.set(path, value, source),
+            None => {
+                self.errors
+                    .push(FieldError::new(path, raw, constraint).with_source(source));
+            }
+        }
+    }
+
+    fn set(&mut self, path: &str, value: Value, source: ConfigSource) {
+        self.settings
+            .insert(path.to_string(), Setting { value, source });
+    }
+}
+
+/// Flattens a serialized configuration into its fields, keyed by path.
+fn flatten(value: &Value) -> BTreeMap<String, Value> {
+    fn walk(value: &Value, path: String, out: &mut BTreeMap<String, Value>) {
+        match value {
+            Value::Object(map) if !ATOMIC_SETTINGS.contains(&path.as_str()) => {
+                for (key, value) in map {
+                    let path = if path.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{}.{}", path, key)
+                    };
+                    walk(value, path, out);
+                }
+            }
+            _ => {
+                out.insert(path, value.clone());
+            }
+        }
+    }
+
+    let mut out = BTreeMap::new();
+    walk(value, String::new(), &mut out);
+    out
+}
+
+/// Rebuilds nested tables from fields keyed by path.
+fn unflatten<'a>(fields: impl Iterator<Item = (&'a String, &'a Value)>) -> Value {
+    let mut root = Map::new();
+    for (path, value) in fields {
+        let (parents, key) = match path.rsplit_once('.') {
+            Some((parents, key)) => (Some(parents), key),
+            None => (None, path.as_str()),
+        };
+        let mut table = &mut root;
+        for parent in parents.into_iter().flat_map(|p| p.split('.')) {
+            table = table
+                .entry(parent)
+                .or_insert_with(|| Value::Object(Map::new()))
+                .as_object_mut()
+                .expect("a field path never passes through a value");
+        }
+        table.insert(key.to_string(), value.clone());
+    }
+    Value::Object(root)
+}
+
+/// Returns the constraint `value` violates if it cannot stand in for
+/// `expected`.
+fn type_constraint(expected: &Value, value: &Value) -> Option<&'static str> {
+    let matches = match expected {
+        Value::Bool(_) => value.is_boolean(),
+        Value::Number(n) if n.is_f64() => value.is_number(),
+        Value::Number(n) if n.is_u64() => value.is_u64(),
+        Value::Number(_) => value.is_i64(),
+        // Optional fields are all strings
+        Value::Null => value.is_string() || value.is_null(),
+        Value::String(_) => value.is_string(),
+        Value::Array(_) => value.is_array(),
+        Value::Object(_) => value.is_object(),
+    };
+    (!matches).then(|| describe(expected))
+}
+
+/// Describes the type of `expected` as a constraint.
+fn describe(expected: &Value) -> &'static str {
+    match expected {
+        Value::Bool(_) => "must be true or false",
+        Value::Number(n) if n.is_f64() => "must be a number",
+        Value::Number(n) if n.is_u64() => "must be a non-negative integer",
+        Value::Number(_) => "must be an integer",
+        Value::Null | Value::String(_) => "must be a string",
+        Value::Array(_) => "must be a list",
+        Value::Object(_) => "must be a table",
+    }
+}
+
+/// Parses text from the environment or the command line as the type of
+/// `expected`.
+fn parse_raw(expected: &Value, raw: &str) -> Option<Value> {
+    let trimmed = raw.trim();
+    match expected {
+        Value::Bool(_) => match trimmed.to_ascii_lowercase().as_str() {
+            "true" | "1" | "yes" | "on" => Some(Value::Bool(true)),
+            "false" | "0" | "no" | "off" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        Value::Number(n) if n.is_f64() => trimmed
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number),
+        Value::Number(n) if n.is_u64() => trimmed.parse::<u64>().ok().map(Value::from),
+        Value::Number(_) => trimmed.parse::<i64>().ok().map(Value::from),
+        Value::Null if raw.is_empty() => Some(Value::Null),
+        Value::Null | Value::String(_) => Some(Value::String(raw.to_string())),
+        Value::Array(_) if !trimmed.starts_with('[') => Some(Value::Array(
+            trimmed
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| Value::String(item.to_string()))
+                .collect(),
+        )),
+        Value::Array(_) | Value::Object(_) => serde_json::from_str::<Value>(trimmed)
+            .ok()
+            .filter(|value| type_constraint(expected, value).is_none()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TransportConfig;
+    use std::time::Duration;
+
+    fn write_toml(name: &str, contents: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("aingle_config_{}_{}", std::process::id(), name));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("aingle.toml");
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn field_errors(result: Result<LoadedConfig, ConfigError>) -> Vec<FieldError> {
+        match result {
+            Err(ConfigError::Fields(errors)) => errors,
+            Err(e) => panic!("expected field errors, got {}", e),
+            Ok(_) => panic!("expected field errors"),
+        }
+    }
+
+    #[test]
+    fn test_env_var_names_follow_paths() {
+        assert_eq!(env_var_name("memory_limit"), "AINGLE_MEMORY_LIMIT");
+        assert_eq!(
+            env_var_name("gossip.peer_limits.requests_per_sec"),
+            "AINGLE_GOSSIP_PEER_LIMITS_REQUESTS_PER_SEC"
+        );
+        assert_eq!(
+            env_var_name("publish_interval.secs"),
+            "AINGLE_PUBLISH_INTERVAL_SECS"
+        );
+    }
+
+    #[test]
+    fn test_precedence_cli_over_env_over_file_over_base() {
+        let path = write_toml(
+            "precedence",
+            r#"
+                memory_limit = 131072
+                log_level = "debug"
+                enable_metrics = true
+
+                [gossip]
+                max_peers = 2
+            "#,
+        );
+
+        let loaded = ConfigLoader::new()
+            .base(Config::iot_mode())
+            .file(&path)
+            .env_vars([
+                ("AINGLE_MEMORY_LIMIT", "196608"),
+                ("AINGLE_LOG_LEVEL", "trace"),
+            ])
+            .set("memory_limit", "262144")
+            .load()
+            .unwrap();
+
+        let config = &loaded.config;
+        // Base
+        assert_eq!(config.publish_interval, Duration::ZERO);
+        // File
+        assert!(config.enable_metrics);
+        assert_eq!(config.gossip.max_peers, 2);
+        // Env over file
+        assert_eq!(config.log_level, "trace");
+        // CLI over env and file
+        assert_eq!(config.memory_limit, 262144);
+        assert!(loaded.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_provenance_is_recorded_per_field() {
+        let path = write_toml(
+            "provenance",
+            r#"
+                enable_metrics = true
+
+                [publish_interval]
+                secs = 1
+                nanos = 0
+
+                [storage]
+                db_path = "/var/lib/aingle/node.db"
+            "#,
+        );
+
+        let loaded = ConfigLoader::new()
+            .file(&path)
+            .env_vars([("AINGLE_PUBLISH_INTERVAL_NANOS", "500000000")])
+            .set(
+                "transport",
+                r#"{"Quic": {"bind_addr": "::", "port": 19081}}"#,
+            )
+            .load()
+            .unwrap();
+
+        let file = ConfigSource::File(path.clone());
+        assert_eq!(loaded.source("enable_metrics"), Some(&file));
+        assert_eq!(loaded.source("storage.db_path"), Some(&file));
+        assert_eq!(loaded.source("publish_interval.secs"), Some(&file));
+        assert_eq!(
+            loaded.source("publish_interval.nanos"),
+            Some(&ConfigSource::Env(
+                "AINGLE_PUBLISH_INTERVAL_NANOS".to_string()
+            ))
+        );
+        // A table reports its latest layer
+        assert_eq!(
+            loaded.source("publish_interval"),
+            Some(&ConfigSource::Env(
+                "AINGLE_PUBLISH_INTERVAL_NANOS".to_string()
+            ))
+        );
+        assert_eq!(loaded.source("transport"), Some(&ConfigSource::Cli));
+        assert_eq!(
+            loaded.source("gossip.max_peers"),
+            Some(&ConfigSource::Default)
+        );
+        assert_eq!(loaded.source("no_such_field"), None);
+
+        assert_eq!(loaded.config.publish_interval, Duration::from_millis(1500));
+        assert_eq!(loaded.config.storage.db_path, "/var/lib/aingle/node.db");
+        assert!(matches!(
+            loaded.config.transport,
+            TransportConfig::Quic { port: 19081, .. }
+        ));
+
+        let setting = loaded.setting("enable_metrics").unwrap();
+        assert_eq!(setting.value, Value::Bool(true));
+        assert_eq!(loaded.settings().count(), loaded.settings.len());
+    }
+
+    #[test]
+    fn test_unknown_file_keys_warn() {
+        let path = write_toml(
+            "unknown",
+            r#"
+                memory_limt = 131072
+
+                [gossip]
+                max_peer = 3
+            "#,
+        );
+
+        let loaded = ConfigLoader::new()
+            .file(&path)
+            .without_env()
+            .load()
+            .unwrap();
+        assert_eq!(loaded.warnings.len(), 2);
+        assert!(loaded.warnings.iter().any(|w| w.contains("memory_limt")));
+        assert!(loaded
+            .warnings
+            .iter()
+            .any(|w| w.contains("gossip.max_peer")));
+        assert_eq!(loaded.config.memory_limit, Config::default().memory_limit);
+    }
+
+    #[test]
+    fn test_type_errors_name_field_and_layer() {
+        let path = write_toml("types", "enable_mdns = \"yes\"\ngossip = 4\n");
+
+        let errors = field_errors(
+            ConfigLoader::new()
+                .file(&path)
+                .env_vars([("AINGLE_GOSSIP_MAX_PEERS", "many")])
+                .set("gossip.no_such_field", "1")
+                .merge(),
+        );
+        assert_eq!(errors.len(), 4);
+
+        let file = ConfigSource::File(path.clone());
+        assert_eq!(errors[0].field, "enable_mdns");
+        assert_eq!(errors[0].constraint, "must be true or false");
+        assert_eq!(errors[0].source, Some(file.clone()));
+        assert_eq!(errors[1].field, "gossip");
+        assert_eq!(errors[1].constraint, "must be a table");
+        assert_eq!(errors[2].field, "gossip.max_peers");
+        assert_eq!(errors[2].value, "many");
+        assert_eq!(errors[2].constraint, "must be a non-negative integer");
+        assert_eq!(
+            errors[2].source,
+            Some(ConfigSource::Env("AINGLE_GOSSIP_MAX_PEERS".to_string()))
+        );
+        assert_eq!(errors[3].field, "gossip.no_such_field");
+        assert_eq!(errors[3].source, Some(ConfigSource::Cli));
+    }
+
+    #[test]
+    fn test_validation_errors_report_constraint_and_layer() {
+        let path = write_toml(
+            "validation",
+            r#"
+                memory_limit = 1024
+
+                [storage]
+                budget_watermark = 1.5
+
+                [gossip]
+                bloom_target_fpr = 1.0
+            "#,
+        );
+
+        let errors = field_errors(
+            ConfigLoader::new()
+                .file(&path)
+                .env_vars([
+                    ("AINGLE_STORAGE_MAX_SIZE", "1000"),
+                    ("AINGLE_GOSSIP_PEER_LIMITS_REQUEST_BURST", "0"),
+                    ("AINGLE_DISCOVERY_RETRY_BASE_SECS", "0"),
+                ])
+                .set("gossip.peer_limits.score_half_life.secs", "0")
+                .set(
+                    "bridge.routes",
+                    r#"[{"from": "ble", "to": "ble", "max_per_sec": 1.0, "burst": 1}]"#,
+                )
+                .load(),
+        );
+
+        let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        let file = path.display();
+        assert_eq!(
+            messages,
+            vec![
+                format!(
+                    "memory_limit = 1024: must be at least 65536 bytes (64KB) (set by file {})",
+                    file
+                ),
+                "storage.max_size = 1000: must be at least 262144 bytes (256KB) \
+                 (set by env AINGLE_STORAGE_MAX_SIZE)"
+                    .to_string(),
+                "discovery.retry_base = 0ns: must be non-zero and at most discovery.retry_max \
+                 (set by env AINGLE_DISCOVERY_RETRY_BASE_SECS)"
+                    .to_string(),
+                format!(
+                    "gossip.bloom_target_fpr = 1: must be in (0.0, 1.0) (set by file {})",
+                    file
+                ),
+                "gossip.peer_limits.request_burst = 0: must be positive \
+                 (set by env AINGLE_GOSSIP_PEER_LIMITS_REQUEST_BURST)"
+                    .to_string(),
+                "gossip.peer_limits.score_half_life = 0ns: must be non-zero (set by command line)"
+                    .to_string(),
+                "bridge.routes = ble -> ble: must connect two different transports \
+                 (set by command line)"
+                    .to_string(),
+                format!(
+                    "storage.budget_watermark = 1.5: must be in (0.0, 1.0] (set by file {})",
+                    file
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_config_load_without_file() {
+        let loaded = Config::load(None).unwrap();
+        assert!(loaded.config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_diff_between_presets() {
+        let diff = Config::iot_mode().diff(&Config::low_power());
+        let paths: Vec<&str> = diff.iter().map(|d| d.path.as_str()).collect();
+        assert!(paths.contains(&"memory_limit"));
+        assert!(paths.contains(&"power_mode"));
+        assert!(paths.contains(&"gossip.max_peers"));
+        assert!(!paths.contains(&"transport"));
+
+        let memory = diff.iter().find(|d| d.path == "memory_limit").unwrap();
+        assert_eq!(memory.left, Value::from(256 * 1024));
+        assert_eq!(memory.right, Value::from(128 * 1024));
+
+        assert!(Config::default().diff(&Config::default()).is_empty());
+    }
+}
@@ -4,11 +4,58 @@
 //! Cryptography for IoT nodes
 //!
 //! Uses Ed25519 for signing/verification and Blake3 for hashing.
+//!
+//! # Keystore
+//!
+//! [`Keypair::save`] writes the signing seed to an encrypted keystore so a
+//! node keeps its identity across restarts. The file layout is:
+//!
+//! ```text
+//! magic "AGKS" | version u8 | m_cost u32 | t_cost u32 | p_cost u32 | salt [16] | nonce [12] | ciphertext [32 + 16]
+//! ```
+//!
+//! The key is derived from the passphrase with Argon2id and the seed is sealed
+//! with ChaCha20-Poly1305, using everything before the ciphertext as associated
+//! data so the KDF parameters cannot be altered without detection.
 
-use crate::error::{Error, Result};
+use crate::error::{CryptoError, Error, Result};
 use crate::types::{AgentPubKey, Hash, Signature};
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
 use ed25519_dalek::{Signer, SigningKey, Verifier, VerifyingKey};
 use rand::RngCore;
+use std::path::Path;
+use zeroize::Zeroize;
+
+/// Magic bytes at the start of a keystore file
+const KEYSTORE_MAGIC: &[u8; 4] = b"AGKS";
+
+/// Current keystore file format version
+pub const KEYSTORE_VERSION: u8 = 1;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const SEED_LEN: usize = 32;
+const TAG_LEN: usize = 16;
+
+/// Magic, version, three KDF parameters, salt and nonce
+const HEADER_LEN: usize = 4 + 1 + 3 * 4 + SALT_LEN + NONCE_LEN;
+
+/// Argon2id memory cost in KiB (the OWASP baseline of 19 MiB)
+#[cfg(not(test))]
+const KDF_MEMORY_KIB: u32 = 19 * 1024;
+/// Cheap parameters keep debug-build tests fast; real files use the baseline
+#[cfg(test)]
+const KDF_MEMORY_KIB: u32 = 64;
+const KDF_ITERATIONS: u32 = 2;
+const KDF_PARALLELISM: u32 = 1;
+
+/// Largest KDF costs accepted from a file, so a crafted keystore cannot
+/// exhaust a small device's memory or stall it for hours
+const KDF_MAX_MEMORY_KIB: u32 = 256 * 1024;
+const KDF_MAX_ITERATIONS: u32 = 16;
+const KDF_MAX_PARALLELISM: u32 = 8;
 
 /// Keypair for signing operations using Ed25519
 pub struct Keypair {
@@ -47,6 +94,174 @@ impl Keypair {
     pub fn seed(&self) -> [u8; 32] {
         self.signing_key.to_bytes()
     }
+
+    /// Save the keypair to an encrypted keystore at `path`
+    ///
+    /// The file is written to a temporary path and renamed into place, and is
+    /// readable only by the owner on Unix.
+    pub fn save(&self, path: impl AsRef<Path>, passphrase: &str) -> Result<()> {
+        let path = path.as_ref();
+        let salt: [u8; SALT_LEN] = random_bytes();
+        let nonce: [u8; NONCE_LEN] = random_bytes();
+
+        let mut file = Vec::with_capacity(HEADER_LEN + SEED_LEN + TAG_LEN);
+        file.extend_from_slice(KEYSTORE_MAGIC);
+        file.push(KEYSTORE_VERSION);
+        for param in [KDF_MEMORY_KIB, KDF_ITERATIONS, KDF_PARALLELISM] {
+            file.extend_from_slice(&param.to_le_bytes());
+        }
+        file.extend_from_slice(&salt);
+        file.extend_from_slice(&nonce);
+
+        let mut key = derive_key(
+            passphrase,
+            &salt,
+            KDF_MEMORY_KIB,
+            KDF_ITERATIONS,
+            KDF_PARALLELISM,
+        )?;
+        let mut seed = self.seed();
+        let sealed = ChaCha20Poly1305::new(Key::from_slice(&key)).encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: &seed,
+                aad: &file,
+            },
+        );
+        key.zeroize();
+        seed.zeroize();
+        let ciphertext = sealed
+            .map_err(|_| CryptoError::EncryptionFailed("could not seal keystore".to_string()))?;
+        file.extend_from_slice(&ciphertext);
+
+        let tmp = path.with_extension("tmp");
+        write_private(&tmp, &file)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// Load a keypair from an encrypted keystore at `path`
+    ///
+    /// A wrong passphrase and a tampered file both fail authentication and
+    /// return [`CryptoError::DecryptionFailed`].
+    pub fn load(path: impl AsRef<Path>, passphrase: &str) -> Result<Self> {
+        let file = std::fs::read(path)?;
+
+        if file.len() < 5 || &file[..4] != KEYSTORE_MAGIC {
+            return Err(CryptoError::DecryptionFailed("not an AIngle keystore".to_string()).into());
+        }
+        if file[4] != KEYSTORE_VERSION {
+            return Err(CryptoError::DecryptionFailed(format!(
+                "unsupported keystore version {}",
+                file[4]
+            ))
+            .into());
+        }
+        if file.len() != HEADER_LEN + SEED_LEN + TAG_LEN {
+            return Err(CryptoError::DecryptionFailed("truncated keystore".to_string()).into());
+        }
+
+        let param = |i: usize| {
+            let offset = 5 + i * 4;
+            let mut bytes = [0u8; 4];
+            bytes.copy_from_slice(&file[offset..offset + 4]);
+            u32::from_le_bytes(bytes)
+        };
+        let (m_cost, t_cost, p_cost) = (param(0), param(1), param(2));
+        for (name, cost, max) in [
+            ("memory cost (KiB)", m_cost, KDF_MAX_MEMORY_KIB),
+            ("iterations", t_cost, KDF_MAX_ITERATIONS),
+            ("parallelism", p_cost, KDF_MAX_PARALLELISM),
+        ] {
+            if cost > max {
+                return Err(CryptoError::DecryptionFailed(format!(
+                    "keystore {} {} exceeds limit {}",
+                    name, cost, max
+                ))
+                .into());
+            }
+        }
+
+        let (header, ciphertext) = file.split_at(HEADER_LEN);
+        let salt = &header[17..17 + SALT_LEN];
+        let nonce = &header[17 + SALT_LEN..];
+
+        let mut key = derive_key(passphrase, salt, m_cost, t_cost, p_cost)?;
+        let opened = ChaCha20Poly1305::new(Key::from_slice(&key)).decrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad: header,
+            },
+        );
+        key.zeroize();
+        let mut plaintext = opened.map_err(|_| {
+            CryptoError::DecryptionFailed("wrong passphrase or corrupted keystore".to_string())
+        })?;
+
+        let mut seed = [0u8; SEED_LEN];
+        seed.copy_from_slice(&plaintext);
+        plaintext.zeroize();
+        let keypair = Self::from_seed(&seed);
+        seed.zeroize();
+        Ok(keypair)
+    }
+
+    /// Load the keystore at `path`, or create it with a new keypair if missing
+    pub fn load_or_create(path: impl AsRef<Path>, passphrase: &str) -> Result<Self> {
+        let path = path.as_ref();
+        if path.exists() {
+            return Self::load(path, passphrase);
+        }
+        let keypair = Self::generate();
+        keypair.save(path, passphrase)?;
+        Ok(keypair)
+    }
+}
+
+/// Derive a 256-bit key from a passphrase with Argon2id
+fn derive_key(
+    passphrase: &str,
+    salt: &[u8],
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+) -> Result<[u8; 32]> {
+    let params = Params::new(m_cost, t_cost, p_cost, Some(32))
+        .map_err(|e| CryptoError::KeyGenerationFailed(format!("invalid KDF parameters: {}", e)))?;
+    let mut key = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| CryptoError::KeyGenerationFailed(format!("key derivation failed: {}", e)))?;
+    Ok(key)
+}
+
+/// Derive a key from a passphrase with the keystore's KDF parameters
+pub(crate) fn derive_passphrase_key(passphrase: &str, salt: &[u8]) -> Result<[u8; 32]> {
+    derive_key(
+        passphrase,
+        salt,
+        KDF_MEMORY_KIB,
+        KDF_ITERATIONS,
+        KDF_PARALLELISM,
+    )
+}
+
+/// Write a file readable only by its owner
+fn write_private(path: &Path, data: &[u8]) -> Result<()> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    file.write_all(data)?;
+    file.sync_all()?;
+    Ok(())
 }
 
 /// Verify an Ed25519 signature
@@ -137,4 +352,88 @@ mod tests {
         let bytes2: [u8; 16] = random_bytes();
         assert_ne!(bytes1, bytes2);
     }
+
+    fn keystore_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!(
+            "aingle_keystore_{}_{}_{}.key",
+            name,
+            std::process::id(),
+            hex::encode(random_bytes::<4>())
+        ))
+    }
+
+    #[test]
+    fn test_keystore_roundtrip() {
+        let path = keystore_path("roundtrip");
+        let kp = Keypair::generate();
+        kp.save(&path, "correct horse").unwrap();
+
+        let loaded = Keypair::load(&path, "correct horse").unwrap();
+        assert_eq!(loaded.public_key(), kp.public_key());
+        assert_eq!(loaded.seed(), kp.seed());
+
+        let bytes = std::fs::read(&path).unwrap();
+        assert_eq!(&bytes[..4], KEYSTORE_MAGIC);
+        assert_eq!(bytes[4], KEYSTORE_VERSION);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_keystore_wrong_passphrase() {
+        let path = keystore_path("wrong");
+        Keypair::generate().save(&path, "correct horse").unwrap();
+
+        let err = Keypair::load(&path, "battery staple").err().unwrap();
+        assert!(matches!(
+            err,
+            Error::Crypto(CryptoError::DecryptionFailed(_))
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_keystore_rejects_tampering_and_unknown_version() {
+        let path = keystore_path("tamper");
+        Keypair::generate().save(&path, "pass").unwrap();
+        let original = std::fs::read(&path).unwrap();
+
+        // KDF parameters are authenticated
+        let mut tampered = original.clone();
+        tampered[9] ^= 1;
+        std::fs::write(&path, &tampered).unwrap();
+        assert!(Keypair::load(&path, "pass").is_err());
+
+        let mut future = original;
+        future[4] = KEYSTORE_VERSION + 1;
+        std::fs::write(&path, &future).unwrap();
+        let err = Keypair::load(&path, "pass").err().unwrap();
+        assert!(err.to_string().contains("unsupported keystore version"));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_keystore_rejects_excessive_kdf_costs() {
+        let path = keystore_path("costs");
+        Keypair::generate().save(&path, "pass").unwrap();
+        let original = std::fs::read(&path).unwrap();
+
+        // m_cost, t_cost and p_cost, each checked before any key derivation
+        for (offset, needle) in [(5, "memory cost"), (9, "iterations"), (13, "parallelism")] {
+            let mut crafted = original.clone();
+            crafted[offset..offset + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+            std::fs::write(&path, &crafted).unwrap();
+            let err = Keypair::load(&path, "pass").err().unwrap();
+            assert!(err.to_string().contains(needle), "{}", err);
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_keystore_load_or_create() {
+        let path = keystore_path("create");
+        let created = Keypair::load_or_create(&path, "pass").unwrap();
+        let loaded = Keypair::load_or_create(&path, "pass").unwrap();
+        assert_eq!(created.public_key(), loaded.public_key());
+        std::fs::remove_file(&path).unwrap();
+    }
 }
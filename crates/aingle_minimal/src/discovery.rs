@@ -4,21 +4,28 @@
 //! Multi-protocol peer discovery for AIngle nodes
 //!
 //! Supports both mDNS/DNS-SD and CoAP multicast discovery for automatic
-//! peer discovery on local networks.
+//! peer discovery on local networks, plus static and unicast DNS bootstrap
+//! for networks where multicast is blocked or unavailable.
 //!
 //! # Discovery Protocols
 //! - **mDNS**: Service type `_aingle._udp.local.` (feature: mdns)
 //! - **CoAP Multicast**: `/.well-known/core` to 224.0.1.187:5683 (feature: coap)
+//! - **Static**: `host:port` entries from [`DiscoveryConfig`]
+//! - **DNS**: A/AAAA records of a configured name, re-resolved periodically.
+//!   This is a plain address lookup; DNS-SD (SRV/PTR) browsing only happens
+//!   over mDNS.
 
+use crate::config::DiscoveryConfig;
 #[cfg(feature = "mdns")]
 use crate::error::Error;
 use crate::error::Result;
 use std::collections::HashMap;
-use std::net::{IpAddr, SocketAddr};
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 #[cfg(feature = "mdns")]
-use std::sync::{Arc, RwLock};
+use std::sync::RwLock;
 
 #[cfg(feature = "coap")]
 use crate::coap::CoapServer;
@@ -32,10 +39,35 @@ pub const SERVICE_TYPE: &str = "_aingle._udp.local.";
 /// Default mDNS port
 pub const DEFAULT_PORT: u16 = 5353;
 
+/// How a peer was discovered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiscoverySource {
+    /// mDNS/DNS-SD on the local link
+    Mdns,
+    /// CoAP multicast discovery response
+    Coap,
+    /// Static bootstrap list from the configuration
+    Static,
+    /// Unicast DNS (A/AAAA) lookup of the configured name
+    Dns,
+}
+
+impl DiscoverySource {
+    /// Short lowercase name, as used in logs
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiscoverySource::Mdns => "mdns",
+            DiscoverySource::Coap => "coap",
+            DiscoverySource::Static => "static",
+            DiscoverySource::Dns => "dns",
+        }
+    }
+}
+
 /// Discovered peer information
 #[derive(Debug, Clone)]
 pub struct DiscoveredPeer {
-    /// Node ID (public key hex)
+    /// Node ID (public key hex), or the configured name for static and DNS peers
     pub node_id: String,
     /// IP addresses
     pub addresses: Vec<IpAddr>,
@@ -47,6 +79,8 @@ pub struct DiscoveredPeer {
     pub last_seen: Instant,
     /// TXT record properties
     pub properties: HashMap<String, String>,
+    /// How this peer was discovered
+    pub source: DiscoverySource,
 }
 
 impl DiscoveredPeer {
@@ -64,11 +98,261 @@ impl DiscoveredPeer {
     }
 }
 
-/// mDNS Discovery service for finding AIngle peers
+/// Resolves host names to socket addresses for static and DNS discovery
+///
+/// Lookups may block; [`Bootstrap`] runs them on smol's blocking thread pool.
+pub trait Resolver: Send + Sync {
+    /// Resolve `name` (`host` or `host:port`), using `default_port` when none is given
+    fn resolve(&self, name: &str, default_port: u16) -> std::io::Result<Vec<SocketAddr>>;
+}
+
+/// Resolver backed by the system's DNS configuration
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemResolver;
+
+impl Resolver for SystemResolver {
+    fn resolve(&self, name: &str, default_port: u16) -> std::io::Result<Vec<SocketAddr>> {
+        if let Ok(addr) = name.parse::<SocketAddr>() {
+            return Ok(vec![addr]);
+        }
+        let (host, port) = split_host_port(name, default_port);
+        Ok((host, port).to_socket_addrs()?.collect())
+    }
+}
+
+/// Split `host:port` into its parts, falling back to `default_port`
+///
+/// Bare IPv6 addresses contain colons, so only a single colon is treated as a
+/// port separator; bracketed IPv6 with a port parses as a `SocketAddr` first.
+fn split_host_port(name: &str, default_port: u16) -> (&str, u16) {
+    if let Some((host, port)) = name.rsplit_once(':') {
+        if !host.contains(':') {
+            if let Ok(port) = port.parse() {
+                return (host, port);
+            }
+        }
+    }
+    (
+        name.trim_start_matches('[').trim_end_matches(']'),
+        default_port,
+    )
+}
+
+/// A static or DNS entry that is resolved and retried independently
+struct BootstrapTarget {
+    name: String,
+    source: DiscoverySource,
+    /// Consecutive failed lookups
+    failures: u32,
+    next_attempt: Instant,
+}
+
+/// Static and unicast DNS peer discovery
+///
+/// Each configured entry is resolved when due. A successful lookup is repeated
+/// after `resolve_interval` so peers that change address are found again; a
+/// failed lookup is retried after `retry_base`, doubling per consecutive
+/// failure up to `retry_max`.
+pub struct Bootstrap {
+    targets: Vec<BootstrapTarget>,
+    resolver: Arc<dyn Resolver>,
+    default_port: u16,
+    resolve_interval: Duration,
+    retry_base: Duration,
+    retry_max: Duration,
+    peers: HashMap<String, DiscoveredPeer>,
+}
+
+impl Bootstrap {
+    /// Create bootstrap discovery from configuration, resolving with the system resolver
+    pub fn new(config: &DiscoveryConfig, default_port: u16) -> Self {
+        let now = Instant::now();
+        let statics = config
+            .bootstrap_peers
+            .iter()
+            .map(|name| (name, DiscoverySource::Static));
+        let dns = config
+            .dns_service
+            .iter()
+            .map(|name| (name, DiscoverySource::Dns));
+        let targets = statics
+            .chain(dns)
+            .map(|(name, source)| BootstrapTarget {
+                name: name.clone(),
+                source,
+                failures: 0,
+                next_attempt: now,
+            })
+            .collect();
+
+        Self {
+            targets,
+            resolver: Arc::new(SystemResolver),
+            default_port,
+            resolve_interval: config.resolve_interval,
+            retry_base: config.retry_base,
+            retry_max: config.retry_max,
+            peers: HashMap::new(),
+        }
+    }
+
+    /// Use a different resolver (e.g. for tests)
+    pub fn with_resolver(mut self, resolver: impl Resolver + 'static) -> Self {
+        self.resolver = Arc::new(resolver);
+        self
+    }
+
+    /// Returns `true` if there is nothing to resolve
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+
+    /// Resolve every entry that is due, returning how many lookups succeeded
+    pub async fn refresh(&mut self, now: Instant) -> usize {
+        let mut resolved = 0;
+
+        for target in self.targets.iter_mut() {
+            if now < target.next_attempt {
+                continue;
+            }
+
+            let resolver = Arc::clone(&self.resolver);
+            let name = target.name.clone();
+            let default_port = self.default_port;
+            let lookup = smol::unblock(move || resolver.resolve(&name, default_port)).await;
+            let addrs = match lookup {
+                Ok(addrs) if !addrs.is_empty() => addrs,
+                Ok(_) => {
+                    Self::record_failure(
+                        target,
+                        now,
+                        self.retry_base,
+                        self.retry_max,
+                        "no addresses",
+                    );
+                    continue;
+                }
+                Err(e) => {
+                    Self::record_failure(
+                        target,
+                        now,
+                        self.retry_base,
+                        self.retry_max,
+                        &e.to_string(),
+                    );
+                    continue;
+                }
+            };
+
+            target.failures = 0;
+            target.next_attempt = now + self.resolve_interval;
+            resolved += 1;
+
+            let addresses: Vec<IpAddr> = addrs.iter().map(|a| a.ip()).collect();
+            let port = addrs[0].port();
+
+            match self.peers.get_mut(&target.name) {
+                Some(peer) => {
+                    if peer.addresses != addresses || peer.port != port {
+                        log::info!(
+                            "Peer {} ({}) moved to {:?}:{}",
+                            target.name,
+                            target.source.as_str(),
+                            addresses,
+                            port
+                        );
+                        peer.addresses = addresses;
+                        peer.port = port;
+                    }
+                    peer.last_seen = now;
+                }
+                None => {
+                    log::info!(
+                        "Discovered peer: {} ({}) at {:?}:{}",
+                        target.name,
+                        target.source.as_str(),
+                        addresses,
+                        port
+                    );
+                    self.peers.insert(
+                        target.name.clone(),
+                        DiscoveredPeer {
+                            node_id: target.name.clone(),
+                            addresses,
+                            port,
+                            discovered_at: now,
+                            last_seen: now,
+                            properties: HashMap::new(),
+                            source: target.source,
+                        },
+                    );
+                }
+            }
+        }
+
+        resolved
+    }
+
+    fn record_failure(
+        target: &mut BootstrapTarget,
+        now: Instant,
+        retry_base: Duration,
+        retry_max: Duration,
+        reason: &str,
+    ) {
+        target.failures = target.failures.saturating_add(1);
+        let delay = Self::retry_delay(retry_base, retry_max, target.failures);
+        target.next_attempt = now + delay;
+        log::warn!(
+            "Failed to resolve {} peer {}: {} (retry in {:?})",
+            target.source.as_str(),
+            target.name,
+            reason,
+            delay
+        );
+    }
+
+    /// Exponential backoff: `base * 2^(failures - 1)`, capped at `max`
+    fn retry_delay(base: Duration, max: Duration, failures: u32) -> Duration {
+        let exponent = failures.saturating_sub(1).min(31);
+        base.saturating_mul(1u32 << exponent).min(max)
+    }
+
+    /// Consecutive failed lookups for a configured entry
+    pub fn failures(&self, name: &str) -> Option<u32> {
+        self.targets
+            .iter()
+            .find(|t| t.name == name)
+            .map(|t| t.failures)
+    }
+
+    /// When a configured entry will next be resolved
+    pub fn next_attempt(&self, name: &str) -> Option<Instant> {
+        self.targets
+            .iter()
+            .find(|t| t.name == name)
+            .map(|t| t.next_attempt)
+    }
+
+    /// Peers resolved so far
+    pub fn peers(&self) -> impl Iterator<Item = &DiscoveredPeer> {
+        self.peers.values()
+    }
+
+    /// Number of peers resolved so far
+    pub fn peer_count(&self) -> usize {
+        self.peers.len()
+    }
+}
+
+/// Discovery service for finding AIngle peers
+///
+/// Combines mDNS browsing with static and DNS [`Bootstrap`] entries; either
+/// side may be absent.
 #[cfg(feature = "mdns")]
 pub struct Discovery {
-    /// Service daemon
-    daemon: ServiceDaemon,
+    /// Service daemon (`None` when mDNS is unavailable or disabled)
+    daemon: Option<ServiceDaemon>,
     /// Our node ID
     node_id: String,
     /// Our service port
@@ -79,6 +363,8 @@ pub struct Discovery {
     registered: bool,
     /// Shutdown flag
     running: Arc<std::sync::atomic::AtomicBool>,
+    /// Static and DNS peers
+    bootstrap: Option<Bootstrap>,
 }
 
 #[cfg(feature = "mdns")]
@@ -88,14 +374,22 @@ impl Discovery {
         let daemon = ServiceDaemon::new()
             .map_err(|e| Error::network(format!("Failed to create mDNS daemon: {}", e)))?;
 
-        Ok(Self {
-            daemon,
+        let mut discovery = Self::without_mdns(node_id, port);
+        discovery.daemon = Some(daemon);
+        Ok(discovery)
+    }
+
+    /// Create a discovery service that only uses static and DNS peers
+    pub fn without_mdns(node_id: String, port: u16) -> Self {
+        Self {
+            daemon: None,
             node_id,
             port,
             peers: Arc::new(RwLock::new(HashMap::new())),
             registered: false,
             running: Arc::new(std::sync::atomic::AtomicBool::new(false)),
-        })
+            bootstrap: None,
+        }
     }
 
     /// Register our service for discovery by others
@@ -103,6 +397,10 @@ impl Discovery {
         if self.registered {
             return Ok(());
         }
+        let Some(daemon) = self.daemon.as_ref() else {
+            log::warn!("mDNS unavailable, not registering service");
+            return Ok(());
+        };
 
         // Get local IP addresses
         let addresses: Vec<IpAddr> = if_addrs::get_if_addrs()
@@ -136,7 +434,7 @@ impl Discovery {
         )
         .map_err(|e| Error::network(format!("Failed to create service info: {}", e)))?;
 
-        self.daemon
+        daemon
             .register(service_info)
             .map_err(|e| Error::network(format!("Failed to register mDNS service: {}", e)))?;
 
@@ -152,11 +450,15 @@ impl Discovery {
 
     /// Start browsing for peers
     pub fn start_browsing(&mut self) -> Result<()> {
+        let Some(daemon) = self.daemon.as_ref() else {
+            log::warn!("mDNS unavailable, relying on static and DNS discovery");
+            return Ok(());
+        };
+
         self.running
             .store(true, std::sync::atomic::Ordering::SeqCst);
 
-        let receiver = self
-            .daemon
+        let receiver = daemon
             .browse(SERVICE_TYPE)
             .map_err(|e| Error::network(format!("Failed to browse mDNS: {}", e)))?;
 
@@ -220,6 +522,7 @@ impl Discovery {
                     discovered_at: Instant::now(),
                     last_seen: Instant::now(),
                     properties: props,
+                    source: DiscoverySource::Mdns,
                 };
 
                 log::info!(
@@ -256,12 +559,30 @@ impl Discovery {
         }
     }
 
+    /// Use static and DNS peers alongside mDNS
+    pub fn set_bootstrap(&mut self, bootstrap: Bootstrap) {
+        self.bootstrap = Some(bootstrap);
+    }
+
+    /// Resolve static and DNS peers that are due, returning how many lookups succeeded
+    pub async fn refresh(&mut self) -> usize {
+        match self.bootstrap.as_mut() {
+            Some(bootstrap) => bootstrap.refresh(Instant::now()).await,
+            None => 0,
+        }
+    }
+
     /// Get discovered peers
     pub fn get_peers(&self) -> Vec<DiscoveredPeer> {
-        self.peers
+        let mut peers: Vec<DiscoveredPeer> = self
+            .peers
             .read()
             .map(|peers| peers.values().cloned().collect())
-            .unwrap_or_default()
+            .unwrap_or_default();
+        if let Some(ref bootstrap) = self.bootstrap {
+            peers.extend(bootstrap.peers().cloned());
+        }
+        peers
     }
 
     /// Get alive peers (seen within timeout)
@@ -285,23 +606,26 @@ impl Discovery {
         self.running
             .store(false, std::sync::atomic::Ordering::SeqCst);
 
+        let Some(daemon) = self.daemon.take() else {
+            return Ok(());
+        };
+
         if self.registered {
             // Unregister our service
             let instance_name = format!("aingle-{}", &self.node_id[..8.min(self.node_id.len())]);
-            let _ = self
-                .daemon
-                .unregister(&format!("{}.{}", instance_name, SERVICE_TYPE));
+            let _ = daemon.unregister(&format!("{}.{}", instance_name, SERVICE_TYPE));
             self.registered = false;
         }
 
-        self.daemon.shutdown().ok();
+        daemon.shutdown().ok();
         log::info!("Stopped mDNS discovery");
         Ok(())
     }
 
     /// Get peer count
     pub fn peer_count(&self) -> usize {
-        self.peers.read().map(|p| p.len()).unwrap_or(0)
+        let bootstrapped = self.bootstrap.as_ref().map(|b| b.peer_count()).unwrap_or(0);
+        self.peers.read().map(|p| p.len()).unwrap_or(0) + bootstrapped
     }
 
     /// Discover peers using CoAP multicast (if coap feature enabled)
@@ -326,6 +650,7 @@ impl Discovery {
                     props.insert("protocol".to_string(), "coap".to_string());
                     props
                 },
+                source: DiscoverySource::Coap,
             };
             peers.insert(node_id.clone(), peer);
             log::info!("Registered CoAP peer: {} at {}", node_id, addr);
@@ -341,17 +666,42 @@ impl Drop for Discovery {
 }
 
 /// Stub Discovery for when mdns feature is disabled
+///
+/// Static and DNS [`Bootstrap`] peers still work without mDNS.
 #[cfg(not(feature = "mdns"))]
 #[allow(dead_code)]
 pub struct Discovery {
     node_id: String,
     port: u16,
+    bootstrap: Option<Bootstrap>,
 }
 
 #[cfg(not(feature = "mdns"))]
 impl Discovery {
     pub fn new(node_id: String, port: u16) -> Result<Self> {
-        Ok(Self { node_id, port })
+        Ok(Self::without_mdns(node_id, port))
+    }
+
+    /// Create a discovery service that only uses static and DNS peers
+    pub fn without_mdns(node_id: String, port: u16) -> Self {
+        Self {
+            node_id,
+            port,
+            bootstrap: None,
+        }
+    }
+
+    /// Use static and DNS peers
+    pub fn set_bootstrap(&mut self, bootstrap: Bootstrap) {
+        self.bootstrap = Some(bootstrap);
+    }
+
+    /// Resolve static and DNS peers that are due, returning how many lookups succeeded
+    pub async fn refresh(&mut self) -> usize {
+        match self.bootstrap.as_mut() {
+            Some(bootstrap) => bootstrap.refresh(Instant::now()).await,
+            None => 0,
+        }
     }
 
     pub fn register(&mut self) -> Result<()> {
@@ -365,15 +715,24 @@ impl Discovery {
     }
 
     pub fn get_peers(&self) -> Vec<DiscoveredPeer> {
-        Vec::new()
+        self.bootstrap
+            .as_ref()
+            .map(|b| b.peers().cloned().collect())
+            .unwrap_or_default()
     }
 
-    pub fn get_alive_peers(&self, _timeout: Duration) -> Vec<DiscoveredPeer> {
-        Vec::new()
+    pub fn get_alive_peers(&self, timeout: Duration) -> Vec<DiscoveredPeer> {
+        self.get_peers()
+            .into_iter()
+            .filter(|p| p.is_alive(timeout))
+            .collect()
     }
 
     pub fn get_peer_addrs(&self) -> Vec<SocketAddr> {
-        Vec::new()
+        self.get_peers()
+            .into_iter()
+            .flat_map(|p| p.socket_addrs())
+            .collect()
     }
 
     pub fn stop(&mut self) -> Result<()> {
@@ -381,7 +740,7 @@ impl Discovery {
     }
 
     pub fn peer_count(&self) -> usize {
-        0
+        self.bootstrap.as_ref().map(|b| b.peer_count()).unwrap_or(0)
     }
 
     /// Discover peers using CoAP multicast (stub)
@@ -413,6 +772,7 @@ mod tests {
             discovered_at: Instant::now(),
             last_seen: Instant::now(),
             properties: HashMap::new(),
+            source: DiscoverySource::Mdns,
         };
 
         let addrs = peer.socket_addrs();
@@ -429,6 +789,7 @@ mod tests {
             discovered_at: Instant::now(),
             last_seen: Instant::now(),
             properties: HashMap::new(),
+            source: DiscoverySource::Mdns,
         };
 
         assert!(peer.is_alive(Duration::from_secs(60)));
@@ -444,4 +805,154 @@ mod tests {
         assert_eq!(discovery.get_peers().len(), 0);
         assert!(discovery.stop().is_ok());
     }
+
+    /// Resolver answering from a shared table; missing names fail
+    #[derive(Clone, Default)]
+    struct MockResolver {
+        table: std::sync::Arc<std::sync::Mutex<HashMap<String, Vec<SocketAddr>>>>,
+        lookups: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    impl MockResolver {
+        fn set(&self, name: &str, addrs: &[&str]) {
+            let addrs = addrs.iter().map(|a| a.parse().unwrap()).collect();
+            self.table.lock().unwrap().insert(name.to_string(), addrs);
+        }
+
+        fn lookups(&self) -> usize {
+            self.lookups.load(std::sync::atomic::Ordering::SeqCst)
+        }
+    }
+
+    impl Resolver for MockResolver {
+        fn resolve(&self, name: &str, _default_port: u16) -> std::io::Result<Vec<SocketAddr>> {
+            self.lookups
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            self.table
+                .lock()
+                .unwrap()
+                .get(name)
+                .cloned()
+                .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "no such host"))
+        }
+    }
+
+    fn discovery_config(peers: &[&str], dns: Option<&str>) -> DiscoveryConfig {
+        DiscoveryConfig {
+            bootstrap_peers: peers.iter().map(|p| p.to_string()).collect(),
+            dns_service: dns.map(|d| d.to_string()),
+            resolve_interval: Duration::from_secs(60),
+            retry_base: Duration::from_secs(1),
+            retry_max: Duration::from_secs(8),
+        }
+    }
+
+    #[test]
+    fn test_bootstrap_tags_sources() {
+        let resolver = MockResolver::default();
+        resolver.set("gateway:5683", &["10.0.0.1:5683"]);
+        resolver.set("peers.example.com", &["10.0.1.1:5683", "10.0.1.2:5683"]);
+
+        let mut bootstrap = Bootstrap::new(
+            &discovery_config(&["gateway:5683"], Some("peers.example.com")),
+            5683,
+        )
+        .with_resolver(resolver);
+        assert_eq!(smol::block_on(bootstrap.refresh(Instant::now())), 2);
+
+        let mut peers: Vec<_> = bootstrap.peers().cloned().collect();
+        peers.sort_by(|a, b| a.node_id.cmp(&b.node_id));
+        assert_eq!(peers.len(), 2);
+        assert_eq!(peers[0].node_id, "gateway:5683");
+        assert_eq!(peers[0].source, DiscoverySource::Static);
+        assert_eq!(peers[1].source, DiscoverySource::Dns);
+        assert_eq!(peers[1].socket_addrs().len(), 2);
+    }
+
+    #[test]
+    fn test_bootstrap_retry_backoff() {
+        let resolver = MockResolver::default();
+        let mut bootstrap = Bootstrap::new(&discovery_config(&["flaky"], None), 5683)
+            .with_resolver(resolver.clone());
+
+        let start = Instant::now();
+        let mut now = start;
+        let mut delays = Vec::new();
+        for _ in 0..5 {
+            assert_eq!(smol::block_on(bootstrap.refresh(now)), 0);
+            let next = bootstrap.next_attempt("flaky").unwrap();
+            delays.push(next - now);
+
+            // Not retried before the backoff expires
+            smol::block_on(bootstrap.refresh(next - Duration::from_millis(1)));
+            now = next;
+        }
+        assert_eq!(resolver.lookups(), 5);
+        assert_eq!(bootstrap.failures("flaky"), Some(5));
+        assert_eq!(delays, [1, 2, 4, 8, 8].map(Duration::from_secs).to_vec());
+
+        // Success resets the failure count and schedules re-resolution
+        resolver.set("flaky", &["10.0.0.9:5683"]);
+        assert_eq!(smol::block_on(bootstrap.refresh(now)), 1);
+        assert_eq!(bootstrap.failures("flaky"), Some(0));
+        assert_eq!(
+            bootstrap.next_attempt("flaky").unwrap(),
+            now + Duration::from_secs(60)
+        );
+    }
+
+    #[test]
+    fn test_bootstrap_rediscovers_moved_peer() {
+        let resolver = MockResolver::default();
+        resolver.set("sensor", &["10.0.0.1:5683"]);
+        let mut bootstrap = Bootstrap::new(&discovery_config(&["sensor"], None), 5683)
+            .with_resolver(resolver.clone());
+
+        let start = Instant::now();
+        smol::block_on(bootstrap.refresh(start));
+        resolver.set("sensor", &["10.0.0.2:5683"]);
+
+        // Not yet due for re-resolution
+        smol::block_on(bootstrap.refresh(start + Duration::from_secs(30)));
+        let peer = bootstrap.peers().next().unwrap().clone();
+        assert_eq!(peer.addresses, vec!["10.0.0.1".parse::<IpAddr>().unwrap()]);
+
+        let later = start + Duration::from_secs(60);
+        smol::block_on(bootstrap.refresh(later));
+        let peer = bootstrap.peers().next().unwrap().clone();
+        assert_eq!(peer.addresses, vec!["10.0.0.2".parse::<IpAddr>().unwrap()]);
+        assert_eq!(peer.discovered_at, start);
+        assert_eq!(peer.last_seen, later);
+        assert_eq!(bootstrap.peer_count(), 1);
+    }
+
+    #[test]
+    fn test_discovery_without_mdns_uses_bootstrap() {
+        let resolver = MockResolver::default();
+        resolver.set("10.0.0.5:5683", &["10.0.0.5:5683"]);
+
+        let mut discovery = Discovery::without_mdns("test-node".to_string(), 5683);
+        assert!(discovery.register().is_ok());
+        assert!(discovery.start_browsing().is_ok());
+        discovery.set_bootstrap(
+            Bootstrap::new(&discovery_config(&["10.0.0.5:5683"], None), 5683)
+                .with_resolver(resolver),
+        );
+
+        assert_eq!(smol::block_on(discovery.refresh()), 1);
+        assert_eq!(discovery.peer_count(), 1);
+        assert_eq!(
+            discovery.get_peer_addrs(),
+            vec!["10.0.0.5:5683".parse::<SocketAddr>().unwrap()]
+        );
+        assert_eq!(discovery.get_peers()[0].source, DiscoverySource::Static);
+        assert!(discovery.stop().is_ok());
+    }
+
+    #[test]
+    fn test_split_host_port() {
+        assert_eq!(split_host_port("gateway:6000", 5683), ("gateway", 6000));
+        assert_eq!(split_host_port("gateway", 5683), ("gateway", 5683));
+        assert_eq!(split_host_port("fe80::1", 5683), ("fe80::1", 5683));
+    }
 }
@@ -15,6 +15,7 @@
 //! - **Crypto**: Cryptographic operation errors
 //! - **Gossip**: Gossip protocol errors
 //! - **Sync**: Synchronization errors
+//! - **Wallet**: Hardware wallet errors
 //!
 //! # Examples
 //!
@@ -58,6 +59,8 @@ pub enum Error {
     Gossip(GossipError),
     /// An error from the sync protocol.
     Sync(SyncError),
+    /// An error from a hardware wallet.
+    Wallet(WalletError),
     /// An error that occurred during data serialization or deserialization.
     Serialization(String),
     /// An error from the underlying I/O system.
@@ -156,6 +159,10 @@ pub enum StorageError {
     SchemaInvalid { reason: String },
     /// Backend not supported.
     BackendNotSupported { backend: String },
+    /// Encryption key does not open the store, or was not given.
+    WrongEncryptionKey { key_id: u32 },
+    /// Store encryption does not match the configuration.
+    EncryptionMismatch { reason: String },
     /// Generic storage error.
     Other(String),
 }
@@ -200,6 +207,27 @@ pub enum SyncError {
     Other(String),
 }
 
+/// Errors related to hardware wallet operations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WalletError {
+    /// No wallet is connected.
+    NotConnected,
+    /// The user rejected the request on the device.
+    UserRejected,
+    /// The device went away in the middle of an operation.
+    Disconnected,
+    /// The device is locked and needs its PIN.
+    Locked,
+    /// The device answered with an unexpected status word.
+    DeviceStatus { status: u16 },
+    /// The device answer could not be parsed.
+    InvalidResponse { reason: String },
+    /// The payload is larger than the device app accepts.
+    PayloadTooLarge { len: usize, max: usize },
+    /// The USB/BLE transport failed.
+    Transport(String),
+}
+
 // ============================================================================
 // Display implementations
 // ============================================================================
@@ -213,6 +241,7 @@ impl std::fmt::Display for Error {
             Error::Storage(e) => write!(f, "Storage error: {}", e),
             Error::Gossip(e) => write!(f, "Gossip error: {}", e),
             Error::Sync(e) => write!(f, "Sync error: {}", e),
+            Error::Wallet(e) => write!(f, "Wallet error: {}", e),
             Error::Serialization(s) => write!(f, "Serialization error: {}", s),
             Error::Io(e) => write!(f, "IO error: {}", e),
             Error::NotInitialized => write!(f, "Node not initialized"),
@@ -313,6 +342,12 @@ impl std::fmt::Display for StorageError {
             StorageError::BackendNotSupported { backend } => {
                 write!(f, "Backend '{}' not supported", backend)
             }
+            StorageError::WrongEncryptionKey { key_id } => {
+                write!(f, "Wrong or missing encryption key {}", key_id)
+            }
+            StorageError::EncryptionMismatch { reason } => {
+                write!(f, "Encryption mismatch: {}", reason)
+            }
             StorageError::Other(s) => write!(f, "{}", s),
         }
     }
@@ -359,6 +394,25 @@ impl std::fmt::Display for SyncError {
     }
 }
 
+impl std::fmt::Display for WalletError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WalletError::NotConnected => write!(f, "Wallet not connected"),
+            WalletError::UserRejected => write!(f, "User rejected the request on the device"),
+            WalletError::Disconnected => write!(f, "Device disconnected"),
+            WalletError::Locked => write!(f, "Device is locked"),
+            WalletError::DeviceStatus { status } => {
+                write!(f, "Device returned status 0x{:04X}", status)
+            }
+            WalletError::InvalidResponse { reason } => write!(f, "Invalid response: {}", reason),
+            WalletError::PayloadTooLarge { len, max } => {
+                write!(f, "Payload too large: {} > {} bytes", len, max)
+            }
+            WalletError::Transport(s) => write!(f, "Transport failed: {}", s),
+        }
+    }
+}
+
 // ============================================================================
 // std::error::Error implementations
 // ============================================================================
@@ -377,6 +431,7 @@ impl std::error::Error for NetworkError {}
 impl std::error::Error for StorageError {}
 impl std::error::Error for GossipError {}
 impl std::error::Error for SyncError {}
+impl std::error::Error for WalletError {}
 
 // ============================================================================
 // From implementations
@@ -418,6 +473,12 @@ impl From<SyncError> for Error {
     }
 }
 
+impl From<WalletError> for Error {
+    fn from(e: WalletError) -> Self {
+        Error::Wallet(e)
+    }
+}
+
 #[cfg(feature = "sqlite")]
 impl From<rusqlite::Error> for Error {
     fn from(e: rusqlite::Error) -> Self {
@@ -479,6 +540,7 @@ impl Error {
             Error::EntryNotFound(_) => true,
             Error::Gossip(GossipError::RateLimitExceeded { .. }) => true,
             Error::Sync(SyncError::Interrupted { .. }) => true,
+            Error::Wallet(WalletError::Disconnected) => true,
             _ => false,
         }
     }
@@ -542,10 +604,17 @@ impl Error {
                 StorageError::MigrationFailed { .. } => "E_STOR_MIGRATION",
                 StorageError::SchemaInvalid { .. } => "E_STOR_SCHEMA",
                 StorageError::BackendNotSupported { .. } => "E_STOR_BACKEND",
+                StorageError::WrongEncryptionKey { .. } => "E_STOR_WRONG_KEY",
+                StorageError::EncryptionMismatch { .. } => "E_STOR_ENCRYPTION",
                 StorageError::Other(_) => "E_STOR_OTHER",
             },
             Error::Gossip(_) => "E_GOSSIP",
             Error::Sync(_) => "E_SYNC",
+            Error::Wallet(e) => match e {
+                WalletError::UserRejected => "E_WALLET_REJECTED",
+                WalletError::Disconnected => "E_WALLET_DISCONNECTED",
+                _ => "E_WALLET",
+            },
             Error::Serialization(_) => "E_SERDE",
             Error::Io(_) => "E_IO",
             Error::NotInitialized => "E_NOT_INIT",
@@ -721,6 +790,10 @@ mod tests {
 
         let sync: Error = SyncError::MissingRecords { count: 5 }.into();
         assert!(matches!(sync, Error::Sync(_)));
+
+        let wallet: Error = WalletError::UserRejected.into();
+        assert!(matches!(wallet, Error::Wallet(WalletError::UserRejected)));
+        assert_eq!(wallet.code(), "E_WALLET_REJECTED");
     }
 
     #[test]
@@ -4,10 +4,11 @@
 //! Optimized Gossip Protocol for AIngle Minimal
 //!
 //! Implements efficient gossip with:
-//! - Bloom filters for set reconciliation
+//! - Bloom filters for set reconciliation, resized to the observed insertion rate
 //! - Token bucket rate limiting
 //! - Priority-based message queuing
 //! - Adaptive timing with exponential backoff
+//! - Bridging of entries between the transports of a multi-transport node
 //!
 //! # Protocol Flow
 //!
@@ -22,20 +23,32 @@
 //!   |                               |
 //! ```
 
-use crate::config::GossipConfig;
+use crate::config::{BridgeConfig, GossipConfig, TransportKind};
+use crate::storage_trait::{EntryPriority, PriorityCounts};
 use crate::types::Hash;
-use std::collections::{BinaryHeap, HashSet, VecDeque};
+use serde::{Deserialize, Serialize};
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 use std::time::{Duration, Instant};
 
 /// Number of bits in the bloom filter (1024 = 16 * 64)
 const BLOOM_FILTER_BITS: usize = 1024;
 
-/// Number of u64 words in the bloom filter
-const BLOOM_FILTER_WORDS: usize = BLOOM_FILTER_BITS / 64;
-
 /// Number of hash functions for bloom filter
+///
+/// Fixed rather than tuned per size: peers only learn a filter's size from
+/// its byte length, so they must agree on the hash count.
 const BLOOM_HASH_COUNT: usize = 3;
 
+/// Smallest adaptive bloom filter, for quiet networks
+const MIN_ADAPTIVE_BLOOM_BITS: usize = 256;
+
+/// Share of `Config::memory_limit` the gossip bloom filters may use (1/32),
+/// split between the active filter and the one kept during rotation overlap
+const BLOOM_MEMORY_SHARE: usize = 32;
+
+/// Weight of the latest window in the smoothed insertion rate
+const BLOOM_RATE_SMOOTHING: f64 = 0.5;
+
 /// Maximum tokens in bucket
 const MAX_BUCKET_TOKENS: f64 = 100.0;
 
@@ -54,40 +67,55 @@ const BACKOFF_MULTIPLIER: f64 = 2.0;
 /// maintaining a small memory footprint suitable for IoT devices.
 ///
 /// **Memory optimization:** Uses packed u64 words instead of Vec<bool>,
-/// reducing memory from 1024 bytes to 128 bytes (8x reduction).
+/// so the default 1024-bit filter takes 128 bytes (8x reduction).
 #[derive(Debug, Clone)]
 pub struct BloomFilter {
     /// Packed bits using u64 words (128 bytes for 1024 bits)
-    bits: [u64; BLOOM_FILTER_WORDS],
+    bits: Vec<u64>,
     /// Number of hash functions to use
     hash_count: usize,
     /// Number of items inserted
     item_count: usize,
-    /// Total number of bits (for custom capacities)
+    /// Total number of bits (always a multiple of 64)
     bit_count: usize,
 }
 
 impl BloomFilter {
     /// Create a new bloom filter with default 1024 bits
     pub fn new() -> Self {
-        Self {
-            bits: [0u64; BLOOM_FILTER_WORDS],
-            hash_count: BLOOM_HASH_COUNT,
-            item_count: 0,
-            bit_count: BLOOM_FILTER_BITS,
-        }
+        Self::with_capacity(BLOOM_FILTER_BITS, BLOOM_HASH_COUNT)
     }
 
     /// Create a bloom filter with custom size (rounded up to multiple of 64)
     pub fn with_capacity(bits: usize, hash_count: usize) -> Self {
-        // Round up to nearest multiple of 64, but cap at BLOOM_FILTER_BITS
-        let bit_count = bits.min(BLOOM_FILTER_BITS);
+        let words = bits.div_ceil(64).max(1);
         Self {
-            bits: [0u64; BLOOM_FILTER_WORDS],
+            bits: vec![0u64; words],
             hash_count,
             item_count: 0,
-            bit_count,
+            bit_count: words * 64,
+        }
+    }
+
+    /// Create a bloom filter sized to hold `items` at `target_fpr`
+    ///
+    /// Uses the fixed gossip hash count, so the filter can be exchanged with
+    /// peers.
+    pub fn for_items(items: usize, target_fpr: f64) -> Self {
+        Self::with_capacity(Self::required_bits(items, target_fpr), BLOOM_HASH_COUNT)
+    }
+
+    /// Bits needed to hold `items` at `target_fpr` with the gossip hash count
+    ///
+    /// Solves `fpr = (1 - e^(-k*n/m))^k` for `m`.
+    pub fn required_bits(items: usize, target_fpr: f64) -> usize {
+        if items == 0 {
+            return 0;
         }
+        let k = BLOOM_HASH_COUNT as f64;
+        let fpr = target_fpr.clamp(f64::MIN_POSITIVE, 1.0 - f64::EPSILON);
+        let bits = -k * items as f64 / (1.0 - fpr.powf(1.0 / k)).ln();
+        bits.ceil() as usize
     }
 
     /// Insert a hash into the filter
@@ -119,10 +147,22 @@ impl BloomFilter {
     /// Clear the filter
     #[inline]
     pub fn clear(&mut self) {
-        self.bits = [0u64; BLOOM_FILTER_WORDS];
+        self.bits.fill(0);
         self.item_count = 0;
     }
 
+    /// Get the number of bits in the filter
+    #[inline]
+    pub fn bit_count(&self) -> usize {
+        self.bit_count
+    }
+
+    /// Get the memory used by the filter's bits
+    #[inline]
+    pub fn memory_bytes(&self) -> usize {
+        self.bits.len() * 8
+    }
+
     /// Get the number of items inserted
     #[inline]
     pub fn len(&self) -> usize {
@@ -185,7 +225,7 @@ impl BloomFilter {
 
     /// Convert to bytes for network transmission (already packed efficiently)
     pub fn to_bytes(&self) -> Vec<u8> {
-        let mut bytes = Vec::with_capacity(BLOOM_FILTER_WORDS * 8);
+        let mut bytes = Vec::with_capacity(self.memory_bytes());
         for word in &self.bits {
             bytes.extend_from_slice(&word.to_le_bytes());
         }
@@ -193,18 +233,26 @@ impl BloomFilter {
     }
 
     /// Create from bytes received over network
+    ///
+    /// The filter size is taken from the byte length, so peers may send
+    /// filters of any size. An empty input gives an empty default filter.
     pub fn from_bytes(bytes: &[u8]) -> Self {
-        let mut bits = [0u64; BLOOM_FILTER_WORDS];
-        for (i, chunk) in bytes.chunks(8).take(BLOOM_FILTER_WORDS).enumerate() {
-            let mut arr = [0u8; 8];
-            arr[..chunk.len()].copy_from_slice(chunk);
-            bits[i] = u64::from_le_bytes(arr);
+        if bytes.is_empty() {
+            return Self::new();
         }
+        let bits: Vec<u64> = bytes
+            .chunks(8)
+            .map(|chunk| {
+                let mut arr = [0u8; 8];
+                arr[..chunk.len()].copy_from_slice(chunk);
+                u64::from_le_bytes(arr)
+            })
+            .collect();
         Self {
+            bit_count: bits.len() * 64,
             bits,
             hash_count: BLOOM_HASH_COUNT,
             item_count: 0, // Unknown after deserialization
-            bit_count: BLOOM_FILTER_BITS,
         }
     }
 }
@@ -215,11 +263,125 @@ impl Default for BloomFilter {
     }
 }
 
+/// Bloom filter that resizes itself to the observed insertion rate
+///
+/// Every `rotation_interval` the filter is replaced by one sized to hold
+/// the smoothed insertion rate over the next interval at the target false
+/// positive rate, capped at `max_bits`. The previous filter is kept for
+/// `overlap`: both receive inserts and answer lookups, and the previous one
+/// is still the one sent to peers, since the fresh filter has not yet seen
+/// the hashes recorded before the rotation.
+#[derive(Debug)]
+struct AdaptiveBloom {
+    /// Filter receiving inserts since the last rotation
+    active: BloomFilter,
+    /// Filter replaced at the last rotation, kept during the overlap
+    previous: Option<BloomFilter>,
+    /// When the active filter was created
+    active_since: Instant,
+    /// Smoothed insertion rate in items per second
+    rate: Option<f64>,
+    /// False positive rate the filter is sized for
+    target_fpr: f64,
+    /// Time between rotations
+    rotation_interval: Duration,
+    /// Time the previous filter is kept after a rotation
+    overlap: Duration,
+    /// Largest size a filter may grow to
+    max_bits: usize,
+    /// Number of rotations so far
+    rotations: u64,
+}
+
+impl AdaptiveBloom {
+    fn new(config: &GossipConfig, memory_limit: usize, now: Instant) -> Self {
+        // Two filters coexist during the overlap, each gets half the share
+        let max_bits = (memory_limit / BLOOM_MEMORY_SHARE / 2 * 8).max(MIN_ADAPTIVE_BLOOM_BITS);
+        Self {
+            active: BloomFilter::with_capacity(BLOOM_FILTER_BITS.min(max_bits), BLOOM_HASH_COUNT),
+            previous: None,
+            active_since: now,
+            rate: None,
+            target_fpr: config.bloom_target_fpr,
+            rotation_interval: config.bloom_rotation_interval,
+            overlap: config.bloom_overlap,
+            max_bits,
+            rotations: 0,
+        }
+    }
+
+    fn insert(&mut self, hash: &Hash, now: Instant) {
+        self.rotate_if_due(now);
+        self.active.insert(hash);
+        if let Some(previous) = &mut self.previous {
+            previous.insert(hash);
+        }
+    }
+
+    fn may_contain(&self, hash: &Hash) -> bool {
+        self.active.may_contain(hash)
+            || self
+                .previous
+                .as_ref()
+                .is_some_and(|previous| previous.may_contain(hash))
+    }
+
+    /// The filter to send to peers
+    fn outgoing(&self) -> &BloomFilter {
+        self.previous.as_ref().unwrap_or(&self.active)
+    }
+
+    /// Drop the previous filter once the overlap has passed, and rotate to a
+    /// resized filter once the interval has passed
+    fn rotate_if_due(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.active_since);
+        if elapsed >= self.overlap {
+            self.previous = None;
+        }
+        if elapsed < self.rotation_interval {
+            return;
+        }
+
+        let observed = self.active.len() as f64 / elapsed.as_secs_f64();
+        let rate = match self.rate {
+            Some(rate) => BLOOM_RATE_SMOOTHING * observed + (1.0 - BLOOM_RATE_SMOOTHING) * rate,
+            None => observed,
+        };
+        self.rate = Some(rate);
+
+        // The next filter covers its own interval plus the following overlap
+        let window = (self.rotation_interval + self.overlap).as_secs_f64();
+        let expected = (rate * window).ceil() as usize;
+        let bits = BloomFilter::required_bits(expected, self.target_fpr)
+            .clamp(MIN_ADAPTIVE_BLOOM_BITS, self.max_bits);
+
+        let next = BloomFilter::with_capacity(bits, BLOOM_HASH_COUNT);
+        self.previous = Some(std::mem::replace(&mut self.active, next));
+        self.active_since = now;
+        self.rotations += 1;
+
+        log::debug!(
+            "Rotated gossip bloom filter to {} bits for {:.2} inserts/s",
+            self.active.bit_count(),
+            rate
+        );
+    }
+
+    fn clear(&mut self) {
+        self.active.clear();
+        self.previous = None;
+    }
+
+    fn memory_bytes(&self) -> usize {
+        self.active.memory_bytes() + self.previous.as_ref().map_or(0, BloomFilter::memory_bytes)
+    }
+}
+
 /// Token Bucket rate limiter for bandwidth control
 ///
 /// Ensures gossip traffic respects the configured bandwidth limits,
 /// preventing network congestion on constrained IoT networks.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TokenBucket {
     /// Current number of tokens
     tokens: f64,
@@ -266,7 +428,12 @@ impl TokenBucket {
     /// # Returns
     /// `true` if tokens were available and consumed, `false` otherwise
     pub fn try_consume(&mut self, tokens: f64) -> bool {
-        self.refill();
+        self.try_consume_at(tokens, Instant::now())
+    }
+
+    /// Try to consume tokens, refilling for the time elapsed up to `now`
+    pub fn try_consume_at(&mut self, tokens: f64, now: Instant) -> bool {
+        self.refill_at(now);
 
         if self.tokens >= tokens {
             self.tokens -= tokens;
@@ -290,8 +457,14 @@ impl TokenBucket {
 
     /// Refill tokens based on elapsed time
     fn refill(&mut self) {
-        let now = Instant::now();
-        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.refill_at(Instant::now());
+    }
+
+    /// Refill tokens for the time elapsed up to `now`
+    fn refill_at(&mut self, now: Instant) {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
 
         if elapsed > 0.0 {
             self.tokens = (self.tokens + elapsed * self.refill_rate).min(self.max_tokens);
@@ -325,6 +498,18 @@ pub enum MessagePriority {
     Critical = 3,
 }
 
+impl From<EntryPriority> for MessagePriority {
+    /// Announcements of Critical entries go out before anything but consensus
+    /// traffic; Bulk entries give way to regular sync.
+    fn from(priority: EntryPriority) -> Self {
+        match priority {
+            EntryPriority::Critical => MessagePriority::Critical,
+            EntryPriority::Normal => MessagePriority::High,
+            EntryPriority::Bulk => MessagePriority::Normal,
+        }
+    }
+}
+
 /// A prioritized message wrapper
 #[derive(Debug)]
 pub struct PrioritizedMessage<T> {
@@ -436,6 +621,57 @@ impl<T> Default for MessageQueue<T> {
     }
 }
 
+/// Pending record announcements, one FIFO per [`EntryPriority`]
+///
+/// Announcements are taken from the Critical queue first, then Normal, then Bulk.
+#[derive(Debug, Default)]
+struct AnnouncementQueue {
+    /// Queues in [`EntryPriority::ALL`] order
+    queues: [VecDeque<Hash>; 3],
+}
+
+impl AnnouncementQueue {
+    fn queue_mut(&mut self, priority: EntryPriority) -> &mut VecDeque<Hash> {
+        let index = EntryPriority::ALL
+            .iter()
+            .position(|p| *p == priority)
+            .unwrap_or(1);
+        &mut self.queues[index]
+    }
+
+    fn push(&mut self, hash: Hash, priority: EntryPriority) {
+        self.queue_mut(priority).push_back(hash);
+    }
+
+    fn take(&mut self, limit: usize) -> Vec<Hash> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+        let mut taken = Vec::with_capacity(limit.min(self.len()));
+        for queue in &mut self.queues {
+            let count = (limit - taken.len()).min(queue.len());
+            taken.extend(queue.drain(..count));
+        }
+        taken
+    }
+
+    fn len(&self) -> usize {
+        self.queues.iter().map(VecDeque::len).sum()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.queues.iter().all(VecDeque::is_empty)
+    }
+
+    fn counts(&self) -> PriorityCounts {
+        let mut counts = PriorityCounts::default();
+        for (priority, queue) in EntryPriority::ALL.iter().zip(&self.queues) {
+            counts.add(*priority, queue.len() as u64);
+        }
+        counts
+    }
+}
+
 /// Peer gossip state for adaptive timing
 #[derive(Debug, Clone)]
 pub struct PeerGossipState {
@@ -509,6 +745,107 @@ impl Default for PeerGossipState {
     }
 }
 
+/// One bridged direction with its rate limit and waiting entries
+#[derive(Debug)]
+struct BridgeLane {
+    from: TransportKind,
+    to: TransportKind,
+    rate_limiter: TokenBucket,
+    pending: VecDeque<Hash>,
+    bridged: u64,
+    dropped: u64,
+}
+
+/// Relays entries learned on one transport to the peers on the others
+///
+/// Remembers the transport each entry was first learned on, for a bounded
+/// number of entries, and only bridges an entry the first time it is seen.
+#[derive(Debug)]
+struct TransportBridge {
+    lanes: Vec<BridgeLane>,
+    /// Transport each recently seen entry was first learned on
+    origins: HashMap<Hash, TransportKind>,
+    /// `origins` keys in insertion order, for eviction
+    origin_order: VecDeque<Hash>,
+    max_seen: usize,
+    loops_prevented: u64,
+}
+
+impl TransportBridge {
+    fn new(config: &BridgeConfig) -> Self {
+        Self {
+            lanes: config
+                .routes
+                .iter()
+                .map(|route| BridgeLane {
+                    from: route.from,
+                    to: route.to,
+                    rate_limiter: TokenBucket::with_params(route.burst as f64, route.max_per_sec),
+                    pending: VecDeque::new(),
+                    bridged: 0,
+                    dropped: 0,
+                })
+                .collect(),
+            origins: HashMap::new(),
+            origin_order: VecDeque::new(),
+            max_seen: config.seen_cache_size.max(1),
+            loops_prevented: 0,
+        }
+    }
+
+    fn learn(&mut self, hash: Hash, transport: TransportKind) {
+        if let Some(origin) = self.origins.get(&hash) {
+            if *origin != transport {
+                self.loops_prevented += 1;
+            }
+            return;
+        }
+        for lane in self.lanes.iter_mut().filter(|lane| lane.from == transport) {
+            if lane.pending.len() >= self.max_seen {
+                lane.pending.pop_front();
+                lane.dropped += 1;
+            }
+            lane.pending.push_back(hash.clone());
+        }
+        self.origins.insert(hash.clone(), transport);
+        self.origin_order.push_back(hash);
+        while self.origin_order.len() > self.max_seen {
+            if let Some(old) = self.origin_order.pop_front() {
+                self.origins.remove(&old);
+            }
+        }
+    }
+
+    fn take(&mut self, transport: TransportKind, limit: usize, now: Instant) -> Vec<Hash> {
+        let mut taken = Vec::new();
+        for lane in self.lanes.iter_mut().filter(|lane| lane.to == transport) {
+            while taken.len() < limit && !lane.pending.is_empty() {
+                if !lane.rate_limiter.try_consume_at(1.0, now) {
+                    break;
+                }
+                if let Some(hash) = lane.pending.pop_front() {
+                    taken.push(hash);
+                    lane.bridged += 1;
+                }
+            }
+        }
+        taken
+    }
+
+    fn stats(&self) -> Vec<BridgeStats> {
+        self.lanes
+            .iter()
+            .map(|lane| BridgeStats {
+                from: lane.from,
+                to: lane.to,
+                bridged: lane.bridged,
+                pending: lane.pending.len(),
+                dropped: lane.dropped,
+            })
+            .collect()
+    }
+}
+
 /// Enhanced Gossip Manager with optimizations
 #[derive(Debug)]
 pub struct GossipManager {
@@ -516,11 +853,13 @@ pub struct GossipManager {
     /// Last gossip round timestamp
     last_gossip: Instant,
     /// Pending record announcements
-    pending_announcements: VecDeque<Hash>,
+    pending_announcements: AnnouncementQueue,
+    /// Announcements queued since creation, by entry priority
+    announced_by_priority: PriorityCounts,
     /// Token bucket for rate limiting
     rate_limiter: TokenBucket,
-    /// Local bloom filter of known hashes
-    local_filter: BloomFilter,
+    /// Local bloom filter of known hashes, resized to the insertion rate
+    local_filter: AdaptiveBloom,
     /// Set of recently seen hashes (for deduplication)
     recent_hashes: HashSet<Hash>,
     /// Maximum recent hashes to track
@@ -529,6 +868,8 @@ pub struct GossipManager {
     message_queue: MessageQueue<GossipMessage>,
     /// Round counter
     round: u64,
+    /// Relaying between transports, when configured
+    bridge: Option<TransportBridge>,
 }
 
 /// Gossip message types
@@ -546,20 +887,43 @@ pub enum GossipMessage {
 
 impl GossipManager {
     /// Create a new gossip manager
+    ///
+    /// The bloom filter is capped for the default
+    /// [`Config::memory_limit`](crate::Config::memory_limit); use
+    /// [`with_memory_limit`](Self::with_memory_limit) for a node's own limit.
     pub fn new(config: GossipConfig) -> Self {
+        Self::with_memory_limit(config, crate::Config::default().memory_limit)
+    }
+
+    /// Create a new gossip manager whose bloom filters stay within a share
+    /// of `memory_limit`
+    pub fn with_memory_limit(config: GossipConfig, memory_limit: usize) -> Self {
+        let now = Instant::now();
         Self {
             rate_limiter: TokenBucket::new(config.output_target_mbps),
+            local_filter: AdaptiveBloom::new(&config, memory_limit, now),
             config,
-            last_gossip: Instant::now(),
-            pending_announcements: VecDeque::with_capacity(100),
-            local_filter: BloomFilter::new(),
+            last_gossip: now,
+            pending_announcements: AnnouncementQueue::default(),
+            announced_by_priority: PriorityCounts::default(),
             recent_hashes: HashSet::with_capacity(1000),
             max_recent: 1000,
             message_queue: MessageQueue::new(100),
             round: 0,
+            bridge: None,
         }
     }
 
+    /// Relay entries between transports along the routes of `config`
+    ///
+    /// Entries must then be recorded with [`add_known_from`](Self::add_known_from),
+    /// and [`take_bridged`](Self::take_bridged) hands out the ones to re-announce
+    /// on each transport.
+    pub fn with_bridge(mut self, config: &BridgeConfig) -> Self {
+        self.bridge = config.is_enabled().then(|| TransportBridge::new(config));
+        self
+    }
+
     /// Check if gossip should run
     pub fn should_gossip(&self) -> bool {
         self.last_gossip.elapsed() >= self.config.loop_delay
@@ -569,6 +933,7 @@ impl GossipManager {
     pub fn gossip_complete(&mut self, success: bool) {
         self.round += 1;
         self.last_gossip = Instant::now();
+        self.local_filter.rotate_if_due(self.last_gossip);
 
         if success {
             log::debug!("Gossip round {} completed successfully", self.round);
@@ -579,8 +944,16 @@ impl GossipManager {
 
     /// Queue a record for announcement
     pub fn announce(&mut self, hash: Hash) {
+        self.announce_with_priority(hash, EntryPriority::Normal);
+    }
+
+    /// Queue a record for announcement ahead of less important entries
+    ///
+    /// Critical records are handed out by [`take_announcements`](Self::take_announcements)
+    /// and queued as messages before Normal ones, and Normal before Bulk.
+    pub fn announce_with_priority(&mut self, hash: Hash, priority: EntryPriority) {
         // Add to bloom filter
-        self.local_filter.insert(&hash);
+        self.local_filter.insert(&hash, Instant::now());
 
         // Add to recent hashes
         if self.recent_hashes.len() >= self.max_recent {
@@ -590,17 +963,17 @@ impl GossipManager {
         self.recent_hashes.insert(hash.clone());
 
         // Queue announcement
-        self.pending_announcements.push_back(hash.clone());
+        self.pending_announcements.push(hash.clone(), priority);
+        self.announced_by_priority.add(priority, 1);
 
-        // Also add to message queue with high priority
+        // Also add to message queue at the entry's priority
         self.message_queue
-            .push(GossipMessage::Announce { hash }, MessagePriority::High);
+            .push(GossipMessage::Announce { hash }, priority.into());
     }
 
-    /// Get pending announcements
+    /// Get pending announcements, most important entries first
     pub fn take_announcements(&mut self, limit: usize) -> Vec<Hash> {
-        let count = limit.min(self.pending_announcements.len());
-        self.pending_announcements.drain(..count).collect()
+        self.pending_announcements.take(limit)
     }
 
     /// Check if we've recently seen a hash
@@ -610,15 +983,50 @@ impl GossipManager {
 
     /// Add known hash (e.g., from received gossip)
     pub fn add_known(&mut self, hash: Hash) {
-        self.local_filter.insert(&hash);
+        self.local_filter.insert(&hash, Instant::now());
         if self.recent_hashes.len() < self.max_recent {
             self.recent_hashes.insert(hash);
         }
     }
 
+    /// Add a hash learned from a peer on `transport`
+    ///
+    /// The first time an entry is seen it is queued for every bridged transport
+    /// `transport` routes to. Entries seen before, including our own
+    /// re-announcements coming back on another transport, are not bridged again.
+    pub fn add_known_from(&mut self, hash: Hash, transport: TransportKind) {
+        self.add_known(hash.clone());
+        if let Some(bridge) = &mut self.bridge {
+            bridge.learn(hash, transport);
+        }
+    }
+
+    /// Take up to `limit` bridged entries to announce to the peers on `transport`
+    ///
+    /// Each route hands out entries as fast as its rate limit allows; the rest
+    /// wait for a later call.
+    pub fn take_bridged(&mut self, transport: TransportKind, limit: usize) -> Vec<Hash> {
+        self.take_bridged_at(transport, limit, Instant::now())
+    }
+
+    fn take_bridged_at(
+        &mut self,
+        transport: TransportKind,
+        limit: usize,
+        now: Instant,
+    ) -> Vec<Hash> {
+        match &mut self.bridge {
+            Some(bridge) => bridge.take(transport, limit, now),
+            None => Vec::new(),
+        }
+    }
+
     /// Get the local bloom filter for sending to peers
+    ///
+    /// Right after a rotation this is the previous filter, which still holds
+    /// every hash recorded before the rotation.
     pub fn get_bloom_filter(&self) -> &BloomFilter {
-        &self.local_filter
+        self.local_filter.outgoing()
     }
 
     /// Find missing hashes by comparing with peer's bloom filter
@@ -677,7 +1085,12 @@ impl GossipManager {
 
     /// Get estimated false positive rate of bloom filter
     pub fn bloom_filter_fpr(&self) -> f64 {
-        self.local_filter.estimated_false_positive_rate()
+        self.local_filter.outgoing().estimated_false_positive_rate()
+    }
+
+    /// Get the memory used by the bloom filters, including one kept after a rotation
+    pub fn bloom_filter_memory(&self) -> usize {
+        self.local_filter.memory_bytes()
     }
 
     /// Get gossip statistics
@@ -685,22 +1098,38 @@ impl GossipManager {
         GossipStats {
             round: self.round,
             pending_announcements: self.pending_announcements.len(),
+            pending_by_priority: self.pending_announcements.counts(),
+            announced_by_priority: self.announced_by_priority,
             queue_length: self.message_queue.len(),
             known_hashes: self.recent_hashes.len(),
-            bloom_filter_items: self.local_filter.len(),
-            bloom_filter_fpr: self.local_filter.estimated_false_positive_rate(),
+            bloom_filter_items: self.local_filter.outgoing().len(),
+            bloom_filter_fpr: self.bloom_filter_fpr(),
+            bloom_filter_bits: self.local_filter.active.bit_count(),
+            bloom_filter_rotations: self.local_filter.rotations,
             available_tokens: self.rate_limiter.tokens,
+            bridged: self
+                .bridge
+                .as_ref()
+                .map(TransportBridge::stats)
+                .unwrap_or_default(),
+            bridge_loops_prevented: self.bridge.as_ref().map_or(0, |b| b.loops_prevented),
         }
     }
 }
 
 /// Gossip statistics
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GossipStats {
     /// Current gossip round
     pub round: u64,
     /// Pending announcements count
     pub pending_announcements: usize,
+    /// Pending announcements by entry priority
+    #[serde(default)]
+    pub pending_by_priority: PriorityCounts,
+    /// Announcements queued since start, by entry priority
+    #[serde(default)]
+    pub announced_by_priority: PriorityCounts,
     /// Message queue length
     pub queue_length: usize,
     /// Known hashes count
@@ -709,13 +1138,41 @@ pub struct GossipStats {
     pub bloom_filter_items: usize,
     /// Bloom filter false positive rate
     pub bloom_filter_fpr: f64,
+    /// Current bloom filter size in bits
+    #[serde(default)]
+    pub bloom_filter_bits: usize,
+    /// Number of times the bloom filter was rotated to a resized one
+    #[serde(default)]
+    pub bloom_filter_rotations: u64,
     /// Available rate limit tokens
     pub available_tokens: f64,
+    /// Entries relayed on each bridged route
+    #[serde(default)]
+    pub bridged: Vec<BridgeStats>,
+    /// Bridged entries that came back on another transport and were not relayed again
+    #[serde(default)]
+    pub bridge_loops_prevented: u64,
+}
+
+/// Bridging statistics for one route
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BridgeStats {
+    /// Transport the entries were learned on
+    pub from: TransportKind,
+    /// Transport the entries were re-announced on
+    pub to: TransportKind,
+    /// Entries handed out for re-announcement
+    pub bridged: u64,
+    /// Entries waiting for the route's rate limit
+    pub pending: usize,
+    /// Waiting entries discarded because too many were queued
+    pub dropped: u64,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::BridgeRoute;
 
     #[test]
     fn test_bloom_filter_insert_contains() {
@@ -1046,6 +1503,67 @@ mod tests {
         assert!(manager.pending_announcements.is_empty());
     }
 
+    #[test]
+    fn test_announcements_sent_in_priority_order() {
+        let mut manager = GossipManager::new(GossipConfig::default());
+        let hash = |i: u8| Hash([i; 32]);
+
+        manager.announce_with_priority(hash(1), EntryPriority::Bulk);
+        manager.announce_with_priority(hash(2), EntryPriority::Normal);
+        manager.announce_with_priority(hash(3), EntryPriority::Critical);
+        manager.announce_with_priority(hash(4), EntryPriority::Bulk);
+        manager.announce_with_priority(hash(5), EntryPriority::Critical);
+        manager.announce(hash(6));
+
+        let stats = manager.stats();
+        assert_eq!(stats.pending_announcements, 6);
+        assert_eq!(
+            stats.pending_by_priority,
+            PriorityCounts {
+                critical: 2,
+                normal: 2,
+                bulk: 2
+            }
+        );
+
+        // Queued messages come out by priority, FIFO within a class
+        let mut sent = Vec::new();
+        while let Some(message) = manager.next_message() {
+            if let GossipMessage::Announce { hash } = message {
+                sent.push(hash.0[0]);
+            }
+        }
+        assert_eq!(sent, vec![3, 5, 2, 6, 1, 4]);
+
+        // And so do published announcements, across partial takes
+        let first: Vec<u8> = manager
+            .take_announcements(3)
+            .iter()
+            .map(|h| h.0[0])
+            .collect();
+        assert_eq!(first, vec![3, 5, 2]);
+        assert_eq!(manager.stats().pending_by_priority.critical, 0);
+
+        manager.announce_with_priority(hash(7), EntryPriority::Critical);
+        let rest: Vec<u8> = manager
+            .take_announcements(10)
+            .iter()
+            .map(|h| h.0[0])
+            .collect();
+        assert_eq!(rest, vec![7, 6, 1, 4]);
+
+        let stats = manager.stats();
+        assert_eq!(stats.pending_by_priority.total(), 0);
+        assert_eq!(
+            stats.announced_by_priority,
+            PriorityCounts {
+                critical: 3,
+                normal: 2,
+                bulk: 2
+            }
+        );
+    }
+
     #[test]
     fn test_gossip_manager_take_announcements_limited() {
         let config = GossipConfig::default();
@@ -1140,11 +1658,17 @@ mod tests {
         let stats = GossipStats {
             round: 5,
             pending_announcements: 10,
+            pending_by_priority: PriorityCounts::default(),
+            announced_by_priority: PriorityCounts::default(),
             queue_length: 3,
             known_hashes: 100,
             bloom_filter_items: 50,
             bloom_filter_fpr: 0.01,
+            bloom_filter_bits: 1024,
+            bloom_filter_rotations: 2,
             available_tokens: 75.5,
+            bridged: Vec::new(),
+            bridge_loops_prevented: 0,
         };
 
         let cloned = stats.clone();
@@ -1171,23 +1695,17 @@ mod tests {
     #[test]
     fn test_bloom_filter_memory_size() {
         // Verify optimized memory footprint
-        // [u64; 16] = 128 bytes for bits
-        // + hash_count (8 bytes) + item_count (8 bytes) + bit_count (8 bytes)
-        // Total should be ~152 bytes, not 1024+ bytes
+        // 1024 bits packed into 16 u64 words = 128 bytes
+        // Old implementation with Vec<bool> would be 1024 bytes
         let filter = BloomFilter::new();
-        let size = std::mem::size_of_val(&filter);
+        let size = std::mem::size_of_val(&filter) + filter.memory_bytes();
 
-        // Old implementation with Vec<bool> would be ~1048 bytes
-        // New implementation should be ~152 bytes
-        assert!(
-            size < 200,
-            "BloomFilter should use packed bits, got {} bytes",
-            size
-        );
-        assert!(
-            size >= 128,
-            "BloomFilter must have at least 128 bytes for bits"
+        assert_eq!(
+            filter.memory_bytes(),
+            128,
+            "BloomFilter should use packed bits"
         );
+        assert!(size < 200, "BloomFilter should be ~176 bytes, got {}", size);
     }
 
     #[test]
@@ -1227,4 +1745,268 @@ mod tests {
             );
         }
     }
+
+    // ==================== Adaptive Sizing Tests ====================
+
+    /// Distinct hashes whose first 8 bytes (the part the filter hashes) differ
+    fn hashes(start: u64, count: u64) -> Vec<Hash> {
+        (start..start + count)
+            .map(|i| {
+                let mut bytes = [0u8; 32];
+                bytes[..8].copy_from_slice(&i.to_le_bytes());
+                Hash::from_bytes(&bytes)
+            })
+            .collect()
+    }
+
+    fn adaptive_config() -> GossipConfig {
+        GossipConfig {
+            bloom_target_fpr: 0.01,
+            bloom_rotation_interval: Duration::from_secs(60),
+            bloom_overlap: Duration::from_secs(5),
+            ..GossipConfig::default()
+        }
+    }
+
+    /// Insert `rate` hashes per second for `seconds`, starting at `t` seconds
+    fn drive(bloom: &mut AdaptiveBloom, start: Instant, t: &mut u64, rate: u64, seconds: u64) {
+        for _ in 0..seconds {
+            let now = start + Duration::from_secs(*t);
+            for hash in hashes(*t * 1_000_000, rate) {
+                bloom.insert(&hash, now);
+            }
+            *t += 1;
+        }
+    }
+
+    #[test]
+    fn test_bloom_filter_sized_for_target_fpr() {
+        let items = 1_000u64;
+        let mut filter = BloomFilter::for_items(items as usize, 0.01);
+        assert_eq!(filter.bit_count() % 64, 0);
+        assert!(filter.bit_count() >= BloomFilter::required_bits(1_000, 0.01));
+
+        for hash in hashes(0, items) {
+            filter.insert(&hash);
+        }
+        assert!(filter.estimated_false_positive_rate() <= 0.01);
+
+        let false_positives = hashes(items, 20_000)
+            .iter()
+            .filter(|h| filter.may_contain(h))
+            .count();
+        assert!(
+            false_positives < 400,
+            "measured FPR {} too high",
+            false_positives as f64 / 20_000.0
+        );
+
+        // Tighter targets need more bits
+        assert!(BloomFilter::required_bits(1_000, 0.001) > BloomFilter::required_bits(1_000, 0.01));
+        assert_eq!(BloomFilter::required_bits(0, 0.01), 0);
+    }
+
+    #[test]
+    fn test_bloom_filter_from_bytes_keeps_size() {
+        let mut filter = BloomFilter::with_capacity(4000, BLOOM_HASH_COUNT);
+        assert_eq!(filter.bit_count(), 4032);
+        let inserted = hashes(0, 200);
+        for hash in &inserted {
+            filter.insert(hash);
+        }
+
+        let restored = BloomFilter::from_bytes(&filter.to_bytes());
+        assert_eq!(restored.bit_count(), 4032);
+        assert!(inserted.iter().all(|h| restored.may_contain(h)));
+
+        assert_eq!(BloomFilter::from_bytes(&[]).bit_count(), BLOOM_FILTER_BITS);
+    }
+
+    #[test]
+    fn test_adaptive_bloom_converges_to_rate() {
+        let config = adaptive_config();
+        let start = Instant::now();
+        let mut bloom = AdaptiveBloom::new(&config, 4 * 1024 * 1024, start);
+        let mut t = 0;
+
+        // A busy period grows the filter past the default size
+        drive(&mut bloom, start, &mut t, 200, 60 * 3 + 1);
+        let busy_bits = BloomFilter::required_bits(200 * 65, 0.01);
+        assert_eq!(bloom.rotations, 3);
+        assert!(bloom.active.bit_count() >= busy_bits);
+        assert!(bloom.active.bit_count() < busy_bits + 64);
+
+        // Then the network goes quiet and the filter shrinks towards the new rate
+        drive(&mut bloom, start, &mut t, 5, 60 * 12);
+        let quiet_bits = BloomFilter::required_bits(5 * 65, 0.01);
+        let bits = bloom.active.bit_count();
+        assert_eq!(bloom.rotations, 15);
+        assert!(bits >= quiet_bits);
+        assert!(
+            (bits as f64) < quiet_bits as f64 * 1.05,
+            "{} bits did not converge to {}",
+            bits,
+            quiet_bits
+        );
+
+        // A full interval at the steady rate stays near the target FPR
+        let before = bloom.rotations;
+        drive(&mut bloom, start, &mut t, 5, 59);
+        assert_eq!(bloom.rotations, before);
+        assert!(bloom.active.estimated_false_positive_rate() < 0.011);
+    }
+
+    #[test]
+    fn test_adaptive_bloom_respects_memory_cap() {
+        let config = adaptive_config();
+        let memory_limit = 64 * 1024;
+        let start = Instant::now();
+        let mut bloom = AdaptiveBloom::new(&config, memory_limit, start);
+        let cap = memory_limit / BLOOM_MEMORY_SHARE / 2 * 8;
+        let mut t = 0;
+
+        for _ in 0..5 {
+            drive(&mut bloom, start, &mut t, 500, 30);
+            assert!(bloom.active.bit_count() <= cap);
+            assert!(bloom.memory_bytes() <= memory_limit / BLOOM_MEMORY_SHARE);
+        }
+        assert!(bloom.rotations >= 2);
+        assert_eq!(bloom.active.bit_count(), cap);
+
+        // Idle networks never shrink below the floor
+        for _ in 0..30 {
+            t += 60;
+            bloom.rotate_if_due(start + Duration::from_secs(t));
+        }
+        assert_eq!(bloom.active.bit_count(), MIN_ADAPTIVE_BLOOM_BITS);
+    }
+
+    #[test]
+    fn test_adaptive_bloom_overlap() {
+        let config = adaptive_config();
+        let start = Instant::now();
+        let mut bloom = AdaptiveBloom::new(&config, 512 * 1024, start);
+
+        let before = hashes(0, 50);
+        for hash in &before {
+            bloom.insert(hash, start);
+        }
+
+        // Rotation: the previous filter is still consulted and sent
+        let rotated = start + Duration::from_secs(60);
+        let during = hashes(100, 10);
+        for hash in &during {
+            bloom.insert(hash, rotated);
+        }
+        assert_eq!(bloom.rotations, 1);
+        assert!(bloom.previous.is_some());
+        assert!(before.iter().all(|h| bloom.may_contain(h)));
+        assert!(before.iter().all(|h| bloom.outgoing().may_contain(h)));
+        assert!(during.iter().all(|h| bloom.outgoing().may_contain(h)));
+
+        // After the overlap the fresh filter takes over with what it has seen
+        bloom.rotate_if_due(rotated + Duration::from_secs(5));
+        assert!(bloom.previous.is_none());
+        assert_eq!(bloom.outgoing().len(), 10);
+        assert!(during.iter().all(|h| bloom.outgoing().may_contain(h)));
+    }
+
+    #[test]
+    fn test_gossip_manager_bloom_stats() {
+        let manager = GossipManager::with_memory_limit(adaptive_config(), 64 * 1024);
+        let stats = manager.stats();
+        assert_eq!(stats.bloom_filter_bits, BLOOM_FILTER_BITS);
+        assert_eq!(stats.bloom_filter_rotations, 0);
+        assert_eq!(manager.bloom_filter_memory(), 128);
+
+        let mut value = serde_json::to_value(&stats).unwrap();
+        let object = value.as_object_mut().unwrap();
+        object.remove("bloom_filter_bits");
+        object.remove("bloom_filter_rotations");
+        let decoded: GossipStats = serde_json::from_value(value).unwrap();
+        assert_eq!(decoded.bloom_filter_rotations, 0);
+    }
+
+    fn bridged_manager(route: BridgeRoute, seen_cache_size: usize) -> GossipManager {
+        GossipManager::new(GossipConfig::default()).with_bridge(&BridgeConfig {
+            seen_cache_size,
+            ..BridgeConfig::default().route(route)
+        })
+    }
+
+    #[test]
+    fn test_bridge_rate_limit_per_route() {
+        let mut manager = bridged_manager(
+            BridgeRoute::new(TransportKind::Ble, TransportKind::Coap).with_rate(5.0, 10),
+            1000,
+        );
+
+        // A chatty BLE mesh
+        for hash in hashes(0, 100) {
+            manager.add_known_from(hash, TransportKind::Ble);
+        }
+        let start = Instant::now();
+        assert_eq!(
+            manager
+                .take_bridged_at(TransportKind::Coap, 50, start)
+                .len(),
+            10
+        );
+        assert!(manager
+            .take_bridged_at(TransportKind::Coap, 50, start)
+            .is_empty());
+        assert!(manager
+            .take_bridged_at(TransportKind::Ble, 50, start)
+            .is_empty());
+        let later = start + Duration::from_secs(1);
+        assert_eq!(
+            manager
+                .take_bridged_at(TransportKind::Coap, 50, later)
+                .len(),
+            5
+        );
+
+        let stats = manager.stats();
+        assert_eq!(
+            stats.bridged,
+            vec![BridgeStats {
+                from: TransportKind::Ble,
+                to: TransportKind::Coap,
+                bridged: 15,
+                pending: 85,
+                dropped: 0,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_bridge_seen_cache_is_bounded() {
+        let mut manager = bridged_manager(
+            BridgeRoute::new(TransportKind::Ble, TransportKind::Coap).with_rate(1000.0, 1000),
+            4,
+        );
+        let entries = hashes(0, 6);
+        for hash in &entries {
+            manager.add_known_from(hash.clone(), TransportKind::Ble);
+        }
+        let stats = manager.stats();
+        assert_eq!(stats.bridged[0].pending, 4);
+        assert_eq!(stats.bridged[0].dropped, 2);
+
+        // A recent entry echoed back on CoAP is recognized
+        manager.add_known_from(entries[5].clone(), TransportKind::Coap);
+        assert_eq!(manager.stats().bridge_loops_prevented, 1);
+        // The oldest fell out of the cache and is bridged again
+        manager.add_known_from(entries[0].clone(), TransportKind::Ble);
+        assert_eq!(manager.stats().bridged[0].dropped, 3);
+        assert_eq!(manager.take_bridged(TransportKind::Coap, 10).len(), 4);
+    }
+
+    #[test]
+    fn test_no_bridge_by_default() {
+        let mut manager = GossipManager::new(GossipConfig::default());
+        manager.add_known_from(Hash::from_bytes(&[1u8; 32]), TransportKind::Ble);
+        assert!(manager.take_bridged(TransportKind::Coap, 10).is_empty());
+        assert!(manager.stats().bridged.is_empty());
+    }
 }
@@ -41,12 +41,33 @@
 //!     .with_object_literal("agent123")
 //!     .execute()?;
 //! ```
+//!
+//! # Expiry
+//!
+//! Triples inserted with [`SemanticGraph::insert_with_ttl`] stop being
+//! returned by queries as soon as their deadline passes. A periodic
+//! [`SemanticGraph::expire`] sweep drops them from memory, and
+//! [`SemanticGraph::flush_expired`] deletes the records they came from in
+//! batches, so flash storage sees a few large writes instead of many small ones.
+//!
+//! ```rust,ignore
+//! use std::time::Duration;
+//!
+//! graph.insert_with_ttl(reading_triple, Duration::from_secs(3600))?;
+//!
+//! // Later, from the node main loop
+//! graph.expire(graph.now())?;
+//! graph.flush_expired(&storage, 64)?;
+//! ```
 
 use crate::error::{Error, Result};
+use crate::storage_trait::StorageBackend;
 use crate::types::{Action, Entry, Hash, Link, Record};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// A semantic triple representing a fact
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -85,6 +106,30 @@ impl TripleObject {
     }
 }
 
+/// Source of the current time used for triple expiry, in seconds
+pub trait GraphClock: Send + Sync {
+    /// Current time in seconds
+    fn now_secs(&self) -> u64;
+}
+
+/// Wall-clock time in seconds since the Unix epoch
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl GraphClock for SystemClock {
+    fn now_secs(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+    }
+}
+
+/// Returns `true` if a triple with this deadline has expired at `now`
+fn is_expired(expires_at: Option<u64>, now: u64) -> bool {
+    expires_at.is_some_and(|deadline| deadline <= now)
+}
+
 /// In-memory semantic graph index
 struct GraphIndex {
     /// SPO index: subject -> predicate -> objects
@@ -93,6 +138,12 @@ struct GraphIndex {
     pos: HashMap<String, HashMap<String, Vec<String>>>,
     /// All triples stored
     triples: Vec<SemanticTriple>,
+    /// Expiry deadline of each entry in `triples` (`None` never expires)
+    expires_at: Vec<Option<u64>>,
+    /// Triples removed by expiry sweeps
+    expired_count: u64,
+    /// Source records whose triples have all expired, not yet deleted from storage
+    pending_deletes: Vec<Hash>,
 }
 
 impl GraphIndex {
@@ -101,10 +152,17 @@ impl GraphIndex {
             spo: HashMap::new(),
             pos: HashMap::new(),
             triples: Vec::new(),
+            expires_at: Vec::new(),
+            expired_count: 0,
+            pending_deletes: Vec::new(),
         }
     }
 
     fn insert(&mut self, triple: SemanticTriple) {
+        self.insert_with_deadline(triple, None);
+    }
+
+    fn insert_with_deadline(&mut self, triple: SemanticTriple, expires_at: Option<u64>) {
         // SPO index
         self.spo
             .entry(triple.subject.clone())
@@ -124,26 +182,78 @@ impl GraphIndex {
 
         // Store full triple
         self.triples.push(triple);
+        self.expires_at.push(expires_at);
     }
 
-    fn find_by_subject(&self, subject: &str) -> Vec<&SemanticTriple> {
+    /// Triples that have not expired at `now`, whether or not a sweep has run
+    fn live(&self, now: u64) -> impl Iterator<Item = &SemanticTriple> {
         self.triples
             .iter()
-            .filter(|t| t.subject == subject)
-            .collect()
+            .zip(&self.expires_at)
+            .filter(move |(_, expires_at)| !is_expired(**expires_at, now))
+            .map(|(triple, _)| triple)
     }
 
-    fn find_by_predicate(&self, predicate: &str) -> Vec<&SemanticTriple> {
-        self.triples
-            .iter()
+    fn find_by_subject(&self, subject: &str, now: u64) -> Vec<&SemanticTriple> {
+        self.live(now).filter(|t| t.subject == subject).collect()
+    }
+
+    fn find_by_predicate(&self, predicate: &str, now: u64) -> Vec<&SemanticTriple> {
+        self.live(now)
             .filter(|t| t.predicate == predicate)
             .collect()
     }
+
+    /// Drops expired triples and rebuilds the indexes.
+    ///
+    /// Source records are queued for deletion only once none of their
+    /// triples remain, since an action is indexed as several triples.
+    fn sweep(&mut self, now: u64) -> usize {
+        if !self.expires_at.iter().any(|e| is_expired(*e, now)) {
+            return 0;
+        }
+
+        let triples = std::mem::take(&mut self.triples);
+        let deadlines = std::mem::take(&mut self.expires_at);
+        self.spo.clear();
+        self.pos.clear();
+
+        let mut removed = 0;
+        let mut sources = Vec::new();
+        for (triple, expires_at) in triples.into_iter().zip(deadlines) {
+            if is_expired(expires_at, now) {
+                removed += 1;
+                if let Some(hash) = triple.source_hash {
+                    sources.push(hash);
+                }
+            } else {
+                self.insert_with_deadline(triple, expires_at);
+            }
+        }
+
+        let mut seen: HashSet<Hash> = self
+            .triples
+            .iter()
+            .filter_map(|t| t.source_hash.clone())
+            .chain(self.pending_deletes.iter().cloned())
+            .collect();
+        for hash in sources {
+            if seen.insert(hash.clone()) {
+                self.pending_deletes.push(hash);
+            }
+        }
+
+        self.expired_count += removed as u64;
+        removed
+    }
 }
 
 /// Semantic graph view of AIngle data
 pub struct SemanticGraph {
     index: Arc<RwLock<GraphIndex>>,
+    clock: Box<dyn GraphClock>,
+    /// Latest clock reading seen, so expiry never runs backwards
+    high_water: AtomicU64,
 }
 
 impl SemanticGraph {
@@ -151,7 +261,108 @@ impl SemanticGraph {
     pub fn new() -> Self {
         Self {
             index: Arc::new(RwLock::new(GraphIndex::new())),
+            clock: Box::new(SystemClock),
+            high_water: AtomicU64::new(0),
+        }
+    }
+
+    /// Use a different clock for expiry (e.g. for tests)
+    pub fn with_clock(mut self, clock: impl GraphClock + 'static) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+
+    /// Current graph time in seconds.
+    ///
+    /// If the clock jumps backwards (e.g. an RTC resync) the latest reading
+    /// is kept, so triples that already expired never reappear.
+    pub fn now(&self) -> u64 {
+        self.observe(self.clock.now_secs())
+    }
+
+    /// Folds a clock reading into the high-water mark and returns the result
+    fn observe(&self, reading: u64) -> u64 {
+        self.high_water
+            .fetch_max(reading, Ordering::Relaxed)
+            .max(reading)
+    }
+
+    /// Insert a triple that never expires
+    pub fn insert(&self, triple: SemanticTriple) -> Result<()> {
+        let mut index = self
+            .index
+            .write()
+            .map_err(|_| Error::storage("lock poisoned"))?;
+        index.insert(triple);
+        Ok(())
+    }
+
+    /// Insert a triple that expires `ttl` from now.
+    ///
+    /// Sub-second TTLs are rounded up to a whole second. A zero TTL stores
+    /// an already-expired triple: it is never returned by queries and is
+    /// removed, and its source record deleted, like any other expired triple.
+    pub fn insert_with_ttl(&self, triple: SemanticTriple, ttl: Duration) -> Result<()> {
+        let ttl_secs = ttl.as_secs() + u64::from(ttl.subsec_nanos() > 0);
+        let expires_at = self.now().saturating_add(ttl_secs);
+        let mut index = self
+            .index
+            .write()
+            .map_err(|_| Error::storage("lock poisoned"))?;
+        index.insert_with_deadline(triple, Some(expires_at));
+        Ok(())
+    }
+
+    /// Remove triples that have expired at `now` (in [`GraphClock`] seconds).
+    ///
+    /// Queries already skip expired triples; the sweep frees their memory
+    /// and queues their source records for [`flush_expired`](Self::flush_expired).
+    /// Returns the number of triples removed.
+    pub fn expire(&self, now: u64) -> Result<usize> {
+        let now = self.observe(now);
+        let mut index = self
+            .index
+            .write()
+            .map_err(|_| Error::storage("lock poisoned"))?;
+        Ok(index.sweep(now))
+    }
+
+    /// Delete the source records of expired triples from `storage`.
+    ///
+    /// Records are deleted `batch_size` at a time, one storage transaction
+    /// per batch. Hashes from a failed batch stay queued for the next call.
+    /// Returns the number of rows removed from storage.
+    pub fn flush_expired<S: StorageBackend + ?Sized>(
+        &self,
+        storage: &S,
+        batch_size: usize,
+    ) -> Result<usize> {
+        let pending = {
+            let mut index = self
+                .index
+                .write()
+                .map_err(|_| Error::storage("lock poisoned"))?;
+            std::mem::take(&mut index.pending_deletes)
+        };
+
+        let batch_size = batch_size.max(1);
+        let mut removed = 0;
+        for (i, batch) in pending.chunks(batch_size).enumerate() {
+            match storage.expire_records_batch(batch) {
+                Ok(count) => removed += count,
+                Err(e) => {
+                    let mut index = self
+                        .index
+                        .write()
+                        .map_err(|_| Error::storage("lock poisoned"))?;
+                    index
+                        .pending_deletes
+                        .extend_from_slice(&pending[i * batch_size..]);
+                    return Err(e);
+                }
+            }
         }
+        Ok(removed)
     }
 
     /// Index an Action as semantic triples
@@ -286,12 +497,13 @@ impl SemanticGraph {
 
     /// Find all triples for a subject
     pub fn get_subject(&self, subject: &str) -> Result<Vec<SemanticTriple>> {
+        let now = self.now();
         let index = self
             .index
             .read()
             .map_err(|_| Error::storage("lock poisoned"))?;
         Ok(index
-            .find_by_subject(subject)
+            .find_by_subject(subject, now)
             .into_iter()
             .cloned()
             .collect())
@@ -299,12 +511,13 @@ impl SemanticGraph {
 
     /// Find all triples with a predicate
     pub fn get_predicate(&self, predicate: &str) -> Result<Vec<SemanticTriple>> {
+        let now = self.now();
         let index = self
             .index
             .read()
             .map_err(|_| Error::storage("lock poisoned"))?;
         Ok(index
-            .find_by_predicate(predicate)
+            .find_by_predicate(predicate, now)
             .into_iter()
             .cloned()
             .collect())
@@ -317,6 +530,7 @@ impl SemanticGraph {
         predicates: &[&str],
         max_depth: usize,
     ) -> Result<Vec<String>> {
+        let now = self.now();
         let index = self
             .index
             .read()
@@ -333,7 +547,7 @@ impl SemanticGraph {
             visited.insert(current.clone());
 
             // Find outgoing edges
-            for triple in index.find_by_subject(&current) {
+            for triple in index.find_by_subject(&current, now) {
                 // Check if predicate matches
                 if predicates.is_empty() || predicates.contains(&triple.predicate.as_str()) {
                     match &triple.object {
@@ -357,15 +571,18 @@ impl SemanticGraph {
 
     /// Get graph statistics
     pub fn stats(&self) -> Result<GraphStats> {
+        let now = self.now();
         let index = self
             .index
             .read()
             .map_err(|_| Error::storage("lock poisoned"))?;
 
         Ok(GraphStats {
-            triple_count: index.triples.len(),
+            triple_count: index.live(now).count(),
             subject_count: index.spo.len(),
             predicate_count: index.pos.len(),
+            expired_count: index.expired_count,
+            pending_deletes: index.pending_deletes.len(),
         })
     }
 }
@@ -433,6 +650,7 @@ impl<'a> SemanticQuery<'a> {
 
     /// Execute the query
     pub fn execute(self) -> Result<Vec<SemanticTriple>> {
+        let now = self.graph.now();
         let index = self
             .graph
             .index
@@ -440,8 +658,7 @@ impl<'a> SemanticQuery<'a> {
             .map_err(|_| Error::storage("lock poisoned"))?;
 
         let mut results: Vec<_> = index
-            .triples
-            .iter()
+            .live(now)
             .filter(|t| {
                 // Subject filter
                 if let Some(ref s) = self.subject {
@@ -478,12 +695,18 @@ impl<'a> SemanticQuery<'a> {
 /// Graph statistics
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct GraphStats {
-    /// Total number of triples
+    /// Number of unexpired triples
     pub triple_count: usize,
     /// Number of unique subjects
     pub subject_count: usize,
     /// Number of unique predicates
     pub predicate_count: usize,
+    /// Triples removed by expiry sweeps
+    #[serde(default)]
+    pub expired_count: u64,
+    /// Expired source records waiting to be deleted from storage
+    #[serde(default)]
+    pub pending_deletes: usize,
 }
 
 #[cfg(test)]
@@ -866,6 +1089,8 @@ mod tests {
             triple_count: 100,
             subject_count: 50,
             predicate_count: 10,
+            expired_count: 7,
+            pending_deletes: 2,
         };
 
         let json = serde_json::to_string(&stats).unwrap();
@@ -874,6 +1099,8 @@ mod tests {
         assert_eq!(stats.triple_count, deserialized.triple_count);
         assert_eq!(stats.subject_count, deserialized.subject_count);
         assert_eq!(stats.predicate_count, deserialized.predicate_count);
+        assert_eq!(stats.expired_count, deserialized.expired_count);
+        assert_eq!(stats.pending_deletes, deserialized.pending_deletes);
     }
 
     #[test]
@@ -940,4 +1167,165 @@ mod tests {
 
         assert_eq!(results.len(), 10);
     }
+
+    /// Clock the tests can move by hand
+    #[derive(Clone, Default)]
+    struct MockClock(Arc<AtomicU64>);
+
+    impl MockClock {
+        fn set(&self, secs: u64) {
+            self.0.store(secs, Ordering::SeqCst);
+        }
+    }
+
+    impl GraphClock for MockClock {
+        fn now_secs(&self) -> u64 {
+            self.0.load(Ordering::SeqCst)
+        }
+    }
+
+    fn reading(subject: &str, source: Option<Hash>) -> SemanticTriple {
+        SemanticTriple {
+            subject: subject.to_string(),
+            predicate: "sensor:value".to_string(),
+            object: TripleObject::Integer(21),
+            source_hash: source,
+        }
+    }
+
+    #[test]
+    fn test_expired_triples_hidden_before_sweep() {
+        let clock = MockClock::default();
+        clock.set(1_000);
+        let graph = SemanticGraph::new().with_clock(clock.clone());
+
+        graph
+            .insert_with_ttl(reading("sensor:a", None), Duration::from_secs(60))
+            .unwrap();
+        graph.insert(reading("sensor:b", None)).unwrap();
+        assert_eq!(graph.get_predicate("sensor:value").unwrap().len(), 2);
+
+        clock.set(1_060);
+        assert!(graph.get_subject("sensor:a").unwrap().is_empty());
+        assert_eq!(graph.get_predicate("sensor:value").unwrap().len(), 1);
+        assert_eq!(graph.query().subject("sensor:").execute().unwrap().len(), 1);
+
+        let stats = graph.stats().unwrap();
+        assert_eq!(stats.triple_count, 1);
+        assert_eq!(stats.expired_count, 0);
+    }
+
+    #[test]
+    fn test_expire_sweep_removes_triples() {
+        let clock = MockClock::default();
+        let graph = SemanticGraph::new().with_clock(clock.clone());
+        let source = random_hash();
+
+        graph
+            .insert_with_ttl(
+                reading("sensor:a", Some(source.clone())),
+                Duration::from_secs(10),
+            )
+            .unwrap();
+        graph.insert(reading("sensor:b", None)).unwrap();
+
+        assert_eq!(graph.expire(9).unwrap(), 0);
+        assert_eq!(graph.expire(10).unwrap(), 1);
+
+        let stats = graph.stats().unwrap();
+        assert_eq!(stats.triple_count, 1);
+        assert_eq!(stats.subject_count, 1);
+        assert_eq!(stats.expired_count, 1);
+        assert_eq!(stats.pending_deletes, 1);
+        assert_eq!(graph.get_subject("sensor:b").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_expire_keeps_source_with_live_triples() {
+        let clock = MockClock::default();
+        let graph = SemanticGraph::new().with_clock(clock.clone());
+        let source = random_hash();
+
+        graph
+            .insert_with_ttl(
+                reading("sensor:a", Some(source.clone())),
+                Duration::from_secs(5),
+            )
+            .unwrap();
+        graph
+            .insert_with_ttl(reading("sensor:b", Some(source)), Duration::from_secs(50))
+            .unwrap();
+
+        assert_eq!(graph.expire(5).unwrap(), 1);
+        assert_eq!(graph.stats().unwrap().pending_deletes, 0);
+
+        assert_eq!(graph.expire(50).unwrap(), 1);
+        assert_eq!(graph.stats().unwrap().pending_deletes, 1);
+    }
+
+    #[test]
+    fn test_zero_ttl_is_never_visible() {
+        let clock = MockClock::default();
+        clock.set(500);
+        let graph = SemanticGraph::new().with_clock(clock);
+
+        graph
+            .insert_with_ttl(reading("sensor:a", None), Duration::ZERO)
+            .unwrap();
+        assert!(graph.get_subject("sensor:a").unwrap().is_empty());
+        assert_eq!(graph.expire(500).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_clock_moving_backwards_does_not_revive_triples() {
+        let clock = MockClock::default();
+        clock.set(100);
+        let graph = SemanticGraph::new().with_clock(clock.clone());
+
+        graph
+            .insert_with_ttl(reading("sensor:a", None), Duration::from_secs(10))
+            .unwrap();
+        clock.set(120);
+        assert!(graph.get_subject("sensor:a").unwrap().is_empty());
+
+        // RTC resync jumps back before the deadline
+        clock.set(105);
+        assert_eq!(graph.now(), 120);
+        assert!(graph.get_subject("sensor:a").unwrap().is_empty());
+        assert_eq!(graph.expire(105).unwrap(), 1);
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_flush_expired_deletes_from_storage() {
+        use crate::storage::Storage;
+        use crate::storage_trait::StorageBackend;
+
+        let storage = Storage::memory().unwrap();
+        let clock = MockClock::default();
+        let graph = SemanticGraph::new().with_clock(clock);
+
+        let mut hashes = Vec::new();
+        for seq in 0..5 {
+            let mut action = test_action();
+            action.seq = seq;
+            let hash = storage.put_action(&action).unwrap();
+            graph
+                .insert_with_ttl(
+                    reading("sensor:a", Some(hash.clone())),
+                    Duration::from_secs(1),
+                )
+                .unwrap();
+            hashes.push(hash);
+        }
+
+        assert_eq!(graph.expire(1).unwrap(), 5);
+        assert_eq!(graph.flush_expired(&storage, 2).unwrap(), 5);
+
+        let stats = StorageBackend::stats(&storage).unwrap();
+        assert_eq!(stats.action_count, 0);
+        assert_eq!(stats.expired_count, 5);
+        assert_eq!(graph.stats().unwrap().pending_deletes, 0);
+        assert!(storage.get_action(&hashes[0]).unwrap().is_none());
+    }
 }
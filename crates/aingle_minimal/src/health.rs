@@ -0,0 +1,553 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Node health and metrics reporting.
+//!
+//! A running node periodically assembles a [`NodeHealth`] snapshot from its
+//! gossip, sync and storage statistics, the last reported battery reading and
+//! its configuration. The snapshot is published through a [`HealthHandle`], so
+//! readers never touch the node itself and never block its main loop.
+//!
+//! # Endpoints
+//! - **CoAP** `GET /metrics` - CBOR-encoded [`NodeHealth`] (feature: coap)
+//! - **HTTP** `GET /metrics` - Prometheus text format via [`MetricsServer`] (feature: rest)
+//!
+//! # Example
+//!
+//! ```
+//! # use aingle_minimal::{Config, MinimalNode};
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut node = MinimalNode::new(Config::test_mode())?;
+//! let handle = node.health_handle();
+//!
+//! node.refresh_health()?;
+//! let health = handle.latest().expect("snapshot published");
+//! assert!(health.to_prometheus().contains("aingle_uptime_seconds"));
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::config::PowerMode;
+use crate::gossip::GossipStats;
+use crate::power::BatteryInfo;
+use crate::storage_trait::StorageStats;
+use crate::sync::SyncStats;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::sync::{Arc, RwLock};
+
+#[cfg(feature = "coap")]
+use crate::error::{Error, Result};
+
+/// Schema version of [`NodeHealth`]
+///
+/// Bumped whenever a field is removed or changes meaning, so collectors can
+/// tell which layout a node is reporting.
+pub const HEALTH_SCHEMA_VERSION: u32 = 1;
+
+/// Snapshot of a node's health
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeHealth {
+    /// Schema version ([`HEALTH_SCHEMA_VERSION`])
+    pub version: u32,
+    /// Node ID (public key hex)
+    pub node_id: String,
+    /// Crate version the node was built from
+    pub software_version: String,
+    /// Seconds since the node was created
+    pub uptime_secs: u64,
+    /// Configured power mode
+    pub power_mode: PowerMode,
+    /// Configured transport ("coap", "quic", "memory", ...)
+    pub transport: String,
+    /// Number of active peers
+    pub peer_count: usize,
+    /// Unix timestamp (seconds) of the last published announcement
+    pub last_publish_secs: Option<u64>,
+    /// Storage backend statistics
+    pub storage: StorageStats,
+    /// Gossip statistics
+    pub gossip: GossipStats,
+    /// Sync statistics
+    pub sync: SyncStats,
+    /// Last reported battery reading, if the device has one
+    pub battery: Option<BatteryInfo>,
+    /// Unix timestamp (seconds) at which this snapshot was collected
+    pub collected_at_secs: u64,
+}
+
+impl NodeHealth {
+    /// Encode as compact CBOR, the format served over CoAP
+    #[cfg(feature = "coap")]
+    pub fn to_cbor(&self) -> Result<Vec<u8>> {
+        let mut buf = Vec::new();
+        ciborium::into_writer(self, &mut buf).map_err(|e| Error::Serialization(e.to_string()))?;
+        Ok(buf)
+    }
+
+    /// Decode from CBOR
+    #[cfg(feature = "coap")]
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self> {
+        ciborium::from_reader(bytes).map_err(|e| Error::Serialization(e.to_string()))
+    }
+
+    /// Render in the Prometheus text exposition format
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+
+        let _ = writeln!(
+            out,
+            "# HELP aingle_node_info Node identity and configuration."
+        );
+        let _ = writeln!(out, "# TYPE aingle_node_info gauge");
+        let _ = writeln!(
+            out,
+            "aingle_node_info{{node_id=\"{}\",version=\"{}\",power_mode=\"{:?}\",transport=\"{}\",schema=\"{}\"}} 1",
+            escape_label(&self.node_id),
+            escape_label(&self.software_version),
+            self.power_mode,
+            escape_label(&self.transport),
+            self.version
+        );
+
+        let storage = &self.storage;
+        let gossip = &self.gossip;
+        let sync = &self.sync;
+        let mut samples: Vec<(&str, &str, &str, f64)> = vec![
+            (
+                "aingle_uptime_seconds",
+                COUNTER,
+                "Seconds since start.",
+                self.uptime_secs as f64,
+            ),
+            (
+                "aingle_peers",
+                GAUGE,
+                "Active peers.",
+                self.peer_count as f64,
+            ),
+            (
+                "aingle_storage_actions",
+                GAUGE,
+                "Actions stored.",
+                storage.action_count as f64,
+            ),
+            (
+                "aingle_storage_entries",
+                GAUGE,
+                "Entries stored.",
+                storage.entry_count as f64,
+            ),
+            (
+                "aingle_storage_links",
+                GAUGE,
+                "Links stored.",
+                storage.link_count as f64,
+            ),
+            (
+                "aingle_storage_bytes",
+                GAUGE,
+                "Database size in bytes.",
+                storage.db_size as f64,
+            ),
+            (
+                "aingle_storage_expired_total",
+                COUNTER,
+                "Records deleted after their triples expired.",
+                storage.expired_count as f64,
+            ),
+            (
+                "aingle_gossip_rounds_total",
+                COUNTER,
+                "Gossip rounds.",
+                gossip.round as f64,
+            ),
+            (
+                "aingle_gossip_pending_announcements",
+                GAUGE,
+                "Announcements waiting to be published.",
+                gossip.pending_announcements as f64,
+            ),
+            (
+                "aingle_gossip_queue_length",
+                GAUGE,
+                "Queued messages.",
+                gossip.queue_length as f64,
+            ),
+            (
+                "aingle_gossip_bloom_bits",
+                GAUGE,
+                "Bloom filter size in bits.",
+                gossip.bloom_filter_bits as f64,
+            ),
+            (
+                "aingle_gossip_bloom_false_positive_rate",
+                GAUGE,
+                "Estimated bloom filter false positive rate.",
+                gossip.bloom_filter_fpr,
+            ),
+            (
+                "aingle_gossip_bloom_rotations_total",
+                COUNTER,
+                "Bloom filter rotations.",
+                gossip.bloom_filter_rotations as f64,
+            ),
+            (
+                "aingle_sync_peers",
+                GAUGE,
+                "Peers tracked by sync.",
+                sync.peer_count as f64,
+            ),
+            (
+                "aingle_sync_success_total",
+                COUNTER,
+                "Successful peer syncs.",
+                sync.total_successful_syncs as f64,
+            ),
+            (
+                "aingle_sync_failed_total",
+                COUNTER,
+                "Failed peer syncs.",
+                sync.total_failed_syncs as f64,
+            ),
+            (
+                "aingle_sync_banned_peers",
+                GAUGE,
+                "Peers banned for misbehavior.",
+                sync.banned_peers as f64,
+            ),
+            (
+                "aingle_sync_throttled_total",
+                COUNTER,
+                "Sync requests refused by per-peer rate limits.",
+                sync.throttled_requests as f64,
+            ),
+            (
+                "aingle_sync_violations_total",
+                COUNTER,
+                "Protocol violations by tracked peers.",
+                sync.violations as f64,
+            ),
+            (
+                "aingle_collected_timestamp_seconds",
+                GAUGE,
+                "Unix time this snapshot was collected.",
+                self.collected_at_secs as f64,
+            ),
+        ];
+        if let Some(secs) = self.last_publish_secs {
+            samples.push((
+                "aingle_last_publish_timestamp_seconds",
+                GAUGE,
+                "Unix time of the last publish.",
+                secs as f64,
+            ));
+        }
+        if let Some(ref battery) = self.battery {
+            samples.push((
+                "aingle_battery_level_percent",
+                GAUGE,
+                "Battery level.",
+                battery.level.into(),
+            ));
+            samples.push((
+                "aingle_battery_health_percent",
+                GAUGE,
+                "Battery health.",
+                battery.health.into(),
+            ));
+            samples.push((
+                "aingle_battery_charging",
+                GAUGE,
+                "1 if the battery is charging.",
+                u8::from(battery.charging).into(),
+            ));
+            if let Some(voltage) = battery.voltage {
+                samples.push((
+                    "aingle_battery_voltage_volts",
+                    GAUGE,
+                    "Battery voltage.",
+                    voltage.into(),
+                ));
+            }
+        }
+
+        for (name, kind, help, value) in samples {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} {}", name, kind);
+            let _ = writeln!(out, "{} {}", name, value);
+        }
+
+        out
+    }
+}
+
+const GAUGE: &str = "gauge";
+const COUNTER: &str = "counter";
+
+/// Escape a Prometheus label value
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Shared, cheaply readable view of the latest [`NodeHealth`] snapshot
+///
+/// The node publishes a new snapshot from its main loop; endpoints only clone
+/// the last one.
+#[derive(Debug, Clone, Default)]
+pub struct HealthHandle {
+    latest: Arc<RwLock<Option<NodeHealth>>>,
+}
+
+impl HealthHandle {
+    /// Create an empty handle
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The most recent snapshot, if one has been published
+    pub fn latest(&self) -> Option<NodeHealth> {
+        self.latest.read().ok().and_then(|h| h.clone())
+    }
+
+    /// Replace the published snapshot
+    pub fn publish(&self, health: NodeHealth) {
+        if let Ok(mut latest) = self.latest.write() {
+            *latest = Some(health);
+        }
+    }
+}
+
+/// Minimal HTTP listener serving `GET /metrics` in Prometheus format
+///
+/// Runs in a background thread and reads only the [`HealthHandle`], so it
+/// works alongside the node's main loop without sharing the node itself.
+#[cfg(feature = "rest")]
+pub struct MetricsServer {
+    running: Arc<std::sync::atomic::AtomicBool>,
+    handle: Option<std::thread::JoinHandle<()>>,
+}
+
+#[cfg(feature = "rest")]
+impl MetricsServer {
+    /// Start listening on `bind_addr` (e.g. "0.0.0.0:9464")
+    pub fn start(bind_addr: &str, health: HealthHandle) -> crate::error::Result<Self> {
+        use std::sync::atomic::Ordering;
+
+        let server = tiny_http::Server::http(bind_addr).map_err(|e| {
+            crate::error::Error::network(format!("Failed to start metrics server: {}", e))
+        })?;
+        log::info!("Metrics server listening on http://{}/metrics", bind_addr);
+
+        let running = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let running_clone = Arc::clone(&running);
+
+        let handle = std::thread::spawn(move || {
+            while running_clone.load(Ordering::SeqCst) {
+                match server.recv_timeout(std::time::Duration::from_millis(100)) {
+                    Ok(Some(request)) => Self::respond(request, &health),
+                    Ok(None) => continue,
+                    Err(e) => {
+                        log::error!("Metrics server recv error: {}", e);
+                        break;
+                    }
+                }
+            }
+            log::info!("Metrics server stopped");
+        });
+
+        Ok(Self {
+            running,
+            handle: Some(handle),
+        })
+    }
+
+    fn respond(request: tiny_http::Request, health: &HealthHandle) {
+        let (status, body) = match (request.method(), request.url()) {
+            (tiny_http::Method::Get, "/metrics") => match health.latest() {
+                Some(h) => (200, h.to_prometheus()),
+                None => (503, "no health snapshot yet\n".to_string()),
+            },
+            _ => (404, "not found\n".to_string()),
+        };
+
+        let content_type =
+            tiny_http::Header::from_bytes(&b"Content-Type"[..], &b"text/plain; version=0.0.4"[..])
+                .expect("static header is valid");
+        let response = tiny_http::Response::from_string(body)
+            .with_status_code(status)
+            .with_header(content_type);
+
+        if let Err(e) = request.respond(response) {
+            log::error!("Failed to send metrics response: {}", e);
+        }
+    }
+
+    /// Stop the listener and wait for its thread
+    pub fn stop(&mut self) {
+        self.running
+            .store(false, std::sync::atomic::Ordering::SeqCst);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+#[cfg(feature = "rest")]
+impl Drop for MetricsServer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn sample_health(battery: Option<BatteryInfo>) -> NodeHealth {
+        NodeHealth {
+            version: HEALTH_SCHEMA_VERSION,
+            node_id: "ab\"cd".to_string(),
+            software_version: crate::VERSION.to_string(),
+            uptime_secs: 42,
+            power_mode: PowerMode::Low,
+            transport: "coap".to_string(),
+            peer_count: 3,
+            last_publish_secs: Some(1_700_000_000),
+            storage: StorageStats {
+                action_count: 10,
+                entry_count: 9,
+                link_count: 1,
+                db_size: 4096,
+                expired_count: 3,
+                by_priority: Default::default(),
+            },
+            gossip: GossipStats {
+                round: 7,
+                pending_announcements: 2,
+                pending_by_priority: Default::default(),
+                announced_by_priority: Default::default(),
+                queue_length: 0,
+                known_hashes: 19,
+                bloom_filter_items: 19,
+                bloom_filter_fpr: 0.001,
+                bloom_filter_bits: 1024,
+                bloom_filter_rotations: 0,
+                available_tokens: 5.0,
+                bridged: Vec::new(),
+                bridge_loops_prevented: 0,
+            },
+            sync: SyncStats {
+                peer_count: 3,
+                local_hashes: 19,
+                total_successful_syncs: 5,
+                total_failed_syncs: 1,
+                banned_peers: 1,
+                throttled_requests: 4,
+                violations: 6,
+            },
+            battery,
+            collected_at_secs: 1_700_000_042,
+        }
+    }
+
+    /// Parse Prometheus text into `name -> value`, failing on malformed lines
+    fn parse_prometheus(text: &str) -> HashMap<String, f64> {
+        let mut samples = HashMap::new();
+        for line in text.lines() {
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(comment) = line.strip_prefix("# ") {
+                let mut parts = comment.splitn(3, ' ');
+                let kind = parts.next().unwrap();
+                assert!(kind == "HELP" || kind == "TYPE", "bad comment: {}", line);
+                assert!(
+                    parts.next().is_some_and(|n| !n.is_empty()),
+                    "bad comment: {}",
+                    line
+                );
+                continue;
+            }
+            let (series, value) = line.rsplit_once(' ').expect("sample has a value");
+            let name = series.split('{').next().unwrap();
+            assert!(
+                name.chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':'),
+                "bad metric name: {}",
+                name
+            );
+            if series.contains('{') {
+                assert!(series.ends_with('}'), "unterminated labels: {}", line);
+            }
+            let value: f64 = value.parse().expect("numeric value");
+            samples.insert(name.to_string(), value);
+        }
+        samples
+    }
+
+    #[test]
+    fn test_prometheus_output_parses() {
+        let text = sample_health(Some(BatteryInfo::with_level(80.0))).to_prometheus();
+        let samples = parse_prometheus(&text);
+
+        for name in [
+            "aingle_node_info",
+            "aingle_uptime_seconds",
+            "aingle_peers",
+            "aingle_last_publish_timestamp_seconds",
+            "aingle_storage_entries",
+            "aingle_storage_bytes",
+            "aingle_storage_expired_total",
+            "aingle_gossip_rounds_total",
+            "aingle_gossip_bloom_bits",
+            "aingle_gossip_bloom_rotations_total",
+            "aingle_sync_success_total",
+            "aingle_sync_failed_total",
+            "aingle_sync_banned_peers",
+            "aingle_sync_violations_total",
+            "aingle_battery_level_percent",
+        ] {
+            assert!(samples.contains_key(name), "missing {}", name);
+        }
+        assert_eq!(samples["aingle_peers"], 3.0);
+        assert_eq!(samples["aingle_gossip_bloom_bits"], 1024.0);
+        assert_eq!(samples["aingle_sync_banned_peers"], 1.0);
+        assert_eq!(samples["aingle_battery_level_percent"], 80.0);
+        assert!(text.contains("node_id=\"ab\\\"cd\""));
+    }
+
+    #[test]
+    fn test_prometheus_omits_missing_battery() {
+        let samples = parse_prometheus(&sample_health(None).to_prometheus());
+        assert!(!samples.contains_key("aingle_battery_level_percent"));
+        assert!(samples.contains_key("aingle_storage_actions"));
+    }
+
+    #[test]
+    #[cfg(feature = "coap")]
+    fn test_cbor_roundtrip() {
+        let health = sample_health(Some(BatteryInfo::with_level(55.0)));
+        let bytes = health.to_cbor().unwrap();
+        assert!(bytes.len() < serde_json::to_vec(&health).unwrap().len());
+
+        let decoded = NodeHealth::from_cbor(&bytes).unwrap();
+        assert_eq!(decoded.version, HEALTH_SCHEMA_VERSION);
+        assert_eq!(decoded.node_id, health.node_id);
+        assert_eq!(decoded.gossip.round, 7);
+    }
+
+    #[test]
+    fn test_health_handle_publish() {
+        let handle = HealthHandle::new();
+        assert!(handle.latest().is_none());
+
+        let reader = handle.clone();
+        handle.publish(sample_health(None));
+        assert_eq!(reader.latest().unwrap().uptime_secs, 42);
+    }
+}
@@ -57,7 +57,7 @@
 //!
 //!     // Read and publish sensor data
 //!     let reading = sensors.read(SensorType::Temperature)?;
-//!     node.publish_sensor_data(reading)?;
+//!     node.publish_sensor_data(&reading)?;
 //!     Ok(())
 //! }
 //! ```
@@ -197,6 +197,7 @@ pub mod bluetooth;
 #[cfg(feature = "coap")]
 pub mod coap;
 pub mod config;
+pub mod config_loader;
 pub mod crypto;
 pub mod discovery;
 #[cfg(feature = "coap")]
@@ -204,6 +205,7 @@ pub mod dtls;
 pub mod error;
 pub mod gossip;
 pub mod graph;
+pub mod health;
 #[cfg(feature = "ai_memory")]
 pub mod memory;
 pub mod network;
@@ -212,6 +214,7 @@ pub mod ota;
 pub mod power;
 #[cfg(feature = "quic")]
 pub mod quic;
+pub mod replay;
 pub mod sensors;
 #[cfg(feature = "smart_agents")]
 pub mod smart;
@@ -221,12 +224,14 @@ pub mod types;
 pub mod wallet;
 #[cfg(feature = "webrtc")]
 pub mod webrtc;
+pub mod wire;
 
 // REST API server for SDK integration
 #[cfg(feature = "rest")]
 pub mod rest;
 
 // Storage - trait is always available
+pub mod storage_crypto;
 pub mod storage_trait;
 
 // Storage backends (feature-gated)
@@ -241,24 +246,41 @@ pub mod storage_factory;
 
 // Re-export storage types
 pub use config::StorageBackendType;
+pub use storage_crypto::{EncryptionKey, KeyRotation, StorageEncryption};
 #[cfg(any(feature = "sqlite", feature = "rocksdb"))]
 pub use storage_factory::DynamicStorage;
-pub use storage_trait::{StorageBackend, StorageStats};
+pub use storage_trait::{
+    EntryPriority, EvictionPolicy, PriorityCounts, StorageBackend, StorageStats, DEFAULT_PRIORITY,
+};
 
 // Re-exports
 #[cfg(feature = "ble")]
 pub use bluetooth::{BleConfig, BleManager, BlePeer, BleState, BleStats};
 #[cfg(feature = "coap")]
-pub use coap::{CoapConfig, CoapServer};
-pub use config::{Config, GossipConfig, MeshMode, PowerMode, StorageConfig, TransportConfig};
-pub use discovery::{DiscoveredPeer, Discovery};
+pub use coap::{BlockOption, BlockwiseConfig, CoapConfig, CoapServer};
+pub use config::{
+    BridgeConfig, BridgeRoute, Config, DiscoveryConfig, FieldError, GossipConfig, MeshMode,
+    PeerLimitConfig, PowerMode, RecordingConfig, StorageConfig, TransportConfig, TransportKind,
+};
+pub use config_loader::{ConfigLoader, ConfigSource, LoadedConfig, Setting, SettingDiff};
+pub use discovery::{
+    Bootstrap, DiscoveredPeer, Discovery, DiscoverySource, Resolver, SystemResolver,
+};
 #[cfg(feature = "coap")]
 pub use dtls::{DtlsConfig, DtlsSession, SecureCoap, SecurityMode};
-pub use error::{CryptoError, Error, GossipError, NetworkError, Result, StorageError, SyncError};
-pub use gossip::{BloomFilter, GossipManager, GossipStats, MessagePriority, TokenBucket};
+pub use error::{
+    CryptoError, Error, GossipError, NetworkError, Result, StorageError, SyncError, WalletError,
+};
+pub use gossip::{
+    BloomFilter, BridgeStats, GossipManager, GossipStats, MessagePriority, TokenBucket,
+};
 pub use graph::{
-    GraphStats as SemanticGraphStats, SemanticGraph, SemanticQuery, SemanticTriple, TripleObject,
+    GraphClock, GraphStats as SemanticGraphStats, SemanticGraph, SemanticQuery, SemanticTriple,
+    SystemClock, TripleObject,
 };
+#[cfg(feature = "rest")]
+pub use health::MetricsServer;
+pub use health::{HealthHandle, NodeHealth, HEALTH_SCHEMA_VERSION};
 #[cfg(feature = "ai_memory")]
 pub use memory::IoTMemory;
 pub use node::{MinimalNode, PeerRecord};
@@ -266,23 +288,32 @@ pub use ota::{OtaManager, UpdateChannel, UpdateInfo, UpdateState};
 pub use power::{BatteryInfo, PowerManager, PowerProfile};
 #[cfg(feature = "quic")]
 pub use quic::{QuicConfig, QuicServer};
+pub use replay::{Recorder, Recording, Replay, VirtualClock};
 #[cfg(feature = "rest")]
 pub use rest::{RestConfig, RestServer};
-pub use sensors::{CalibrationParams, Sensor, SensorManager, SensorReading, SensorType};
+pub use sensors::{
+    CalibrationParams, Derivation, DerivedSpec, Sensor, SensorBatch, SensorInput, SensorManager,
+    SensorReading, SensorType,
+};
 #[cfg(feature = "smart_agents")]
 pub use smart::{IoTPolicyBuilder, SensorAdapter, SmartNode, SmartNodeConfig, SmartNodeStats};
-pub use sync::{PeerSyncState, SyncManager, SyncResult, SyncStats};
+pub use sync::{
+    BannedPeer, PeerStats, PeerSyncState, SyncManager, SyncResult, SyncStats, Violation,
+};
 pub use types::*;
 #[cfg(feature = "hw_wallet")]
 pub use wallet::{
-    ApduCommand, ApduResponse, DerivationPath, HwPublicKey, HwSignature, WalletConfig, WalletInfo,
-    WalletManager, WalletState, WalletStats, WalletType,
+    ApduCommand, ApduResponse, DerivationPath, HidTransport, HwPublicKey, HwSignature,
+    MockBehavior, MockTransport, TripleDisplay, WalletConfig, WalletInfo, WalletManager,
+    WalletState, WalletStats, WalletTransport, WalletType,
 };
 #[cfg(feature = "webrtc")]
 pub use webrtc::{
-    ConnectionState, PeerConnection, SignalingClient, SignalingConfig, SignalingMessage,
-    SignalingServer, WebRtcConfig, WebRtcServer, WebRtcStats,
+    ConnectionState, IceCandidateType, IceServer, PeerConnection, SignalingAuth, SignalingClient,
+    SignalingConfig, SignalingMessage, SignalingServer, TurnServer, WebRtcConfig, WebRtcServer,
+    WebRtcStats,
 };
+pub use wire::WireEncoding;
 
 /// Version information for the crate.
 ///
@@ -332,3 +363,16 @@ pub const ENV_PUBLISH_INTERVAL: &str = "AINGLE_PUBLISH_INTERVAL_MS";
 /// export AINGLE_IOT_MODE=1
 /// ```
 pub const ENV_IOT_MODE: &str = "AINGLE_IOT_MODE";
+
+/// Environment variable holding the keystore passphrase.
+///
+/// This is the default for [`Config::keystore_passphrase_env`]; the CLI's
+/// `--passphrase-env` flag names a different variable.
+///
+/// # Examples
+///
+/// ```bash
+/// export AINGLE_KEYSTORE_PASSPHRASE='correct horse battery staple'
+/// aingle-minimal run --keystore ./node.key
+/// ```
+pub const ENV_KEYSTORE_PASSPHRASE: &str = "AINGLE_KEYSTORE_PASSPHRASE";
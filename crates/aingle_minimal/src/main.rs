@@ -17,15 +17,33 @@
 //! # Generate new keypair
 //! aingle-minimal keygen
 //!
+//! # Generate a persistent identity and run with it
+//! export AINGLE_KEYSTORE_PASSPHRASE='...'
+//! aingle-minimal keygen --output node.key --save
+//! aingle-minimal run --keystore node.key
+//!
 //! # Show node info
 //! aingle-minimal info
 //!
 //! # Show configuration
 //! aingle-minimal config show
+//!
+//! # Show the merged configuration and where each value came from
+//! AINGLE_GOSSIP_MAX_PEERS=4 aingle-minimal config show --effective --config node.toml
+//!
+//! # Compare a configuration file with the IoT preset
+//! aingle-minimal config diff node.toml iot
+//!
+//! # Run with a configuration file, overriding one setting
+//! aingle-minimal run --config node.toml --memory-limit 256
+//!
+//! # Replay the inputs recorded by a node (recording.path in its config)
+//! aingle-minimal replay --log aingle_inputs.log --config node.toml --keystore node.key
 //! ```
 
-use aingle_minimal::{Config, MinimalNode, Result};
-use clap::{Parser, Subcommand};
+use aingle_minimal::config::ConfigError;
+use aingle_minimal::{Config, ConfigLoader, LoadedConfig, MinimalNode, Result};
+use clap::{Args, Parser, Subcommand};
 use std::path::PathBuf;
 use std::time::Duration;
 
@@ -53,7 +71,14 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Run the node
+    ///
+    /// Settings are merged from the mode preset, the --config file, AINGLE_*
+    /// environment variables and the flags below, in that order.
     Run {
+        /// TOML configuration file
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
         /// Enable IoT mode (sub-second confirmation)
         #[arg(long)]
         iot: bool,
@@ -70,18 +95,22 @@ enum Commands {
         #[arg(short, long)]
         memory_limit: Option<usize>,
 
-        /// Bind address for CoAP server
-        #[arg(short, long, default_value = "0.0.0.0")]
-        bind_addr: String,
+        /// Bind address for CoAP server [default: 0.0.0.0]
+        #[arg(short, long)]
+        bind_addr: Option<String>,
 
-        /// Port for CoAP server
-        #[arg(short = 'P', long, default_value = "5683")]
-        port: u16,
+        /// Port for CoAP server [default: 5683]
+        #[arg(short = 'P', long)]
+        port: Option<u16>,
 
-        /// Peers to connect to (can be specified multiple times)
+        /// Peers to connect to, as host:port (can be specified multiple times)
         #[arg(long)]
         peer: Vec<String>,
 
+        /// DNS name whose address records list peers (re-resolved periodically)
+        #[arg(long)]
+        dns_service: Option<String>,
+
         /// Enable mDNS auto-discovery
         #[arg(long)]
         mdns: bool,
@@ -93,6 +122,23 @@ enum Commands {
         /// Enable REST API server on specified port (for SDK integration)
         #[arg(long)]
         rest_port: Option<u16>,
+
+        /// Collect health metrics (served on CoAP /metrics)
+        #[arg(long)]
+        metrics: bool,
+
+        /// Serve Prometheus metrics over HTTP on specified port (implies --metrics)
+        #[arg(long)]
+        metrics_port: Option<u16>,
+
+        /// Encrypted keystore holding the node identity (created if missing)
+        #[arg(long)]
+        keystore: Option<PathBuf>,
+
+        /// Environment variable holding the keystore passphrase
+        /// [default: AINGLE_KEYSTORE_PASSPHRASE]
+        #[arg(long)]
+        passphrase_env: Option<String>,
     },
 
     /// Generate a new keypair
@@ -104,10 +150,22 @@ enum Commands {
         /// Output format (hex, base64)
         #[arg(short, long, default_value = "hex")]
         format: String,
+
+        /// Save the keypair to an encrypted keystore at --output
+        #[arg(long, requires = "output")]
+        save: bool,
+
+        /// Environment variable holding the keystore passphrase
+        #[arg(long, default_value = aingle_minimal::ENV_KEYSTORE_PASSPHRASE)]
+        passphrase_env: String,
     },
 
     /// Show node information
-    Info,
+    Info {
+        /// Database path of a node whose persisted peer bans to list
+        #[arg(short, long)]
+        db_path: Option<PathBuf>,
+    },
 
     /// Configuration management
     Config {
@@ -118,6 +176,30 @@ enum Commands {
     /// Show version and build information
     Version,
 
+    /// Replay a node's recorded inputs on a fresh in-memory node
+    ///
+    /// Prints the storage stats and the hashes of the entries created, which
+    /// match the recorded session's when the recording node's config and
+    /// keystore are given.
+    Replay {
+        /// Recording log written by a node with recording enabled
+        #[arg(long)]
+        log: PathBuf,
+
+        /// TOML configuration file of the recording node
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Keystore of the recording node
+        #[arg(long)]
+        keystore: Option<PathBuf>,
+
+        /// Environment variable holding the keystore passphrase
+        /// [default: AINGLE_KEYSTORE_PASSPHRASE]
+        #[arg(long)]
+        passphrase_env: Option<String>,
+    },
+
     /// Benchmark the node
     Bench {
         /// Number of entries to create
@@ -133,7 +215,14 @@ enum Commands {
 #[derive(Subcommand)]
 enum ConfigAction {
     /// Show current configuration
-    Show,
+    Show {
+        /// Print every setting with the layer that set it
+        #[arg(long)]
+        effective: bool,
+
+        #[command(flatten)]
+        layers: LayerArgs,
+    },
 
     /// Show IoT mode configuration
     Iot,
@@ -142,7 +231,66 @@ enum ConfigAction {
     LowPower,
 
     /// Validate configuration
-    Validate,
+    Validate {
+        #[command(flatten)]
+        layers: LayerArgs,
+    },
+
+    /// Compare two configurations
+    Diff {
+        /// A TOML file or a mode (default, iot, low-power, production, test)
+        left: String,
+
+        /// A TOML file or a mode (default, iot, low-power, production, test)
+        right: String,
+    },
+}
+
+/// The layers a configuration is merged from
+#[derive(Args)]
+struct LayerArgs {
+    /// Base preset (default, iot, low-power, production, test)
+    #[arg(long, default_value = "default")]
+    mode: String,
+
+    /// TOML configuration file
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// Override a setting, as path=value (can be specified multiple times)
+    #[arg(long = "set", value_name = "PATH=VALUE")]
+    overrides: Vec<String>,
+}
+
+impl LayerArgs {
+    fn loader(self) -> std::result::Result<ConfigLoader, ConfigError> {
+        let mut loader = ConfigLoader::new().base(preset(&self.mode)?);
+        if let Some(path) = self.config {
+            loader = loader.file(path);
+        }
+        for item in self.overrides {
+            let (path, value) = item.split_once('=').ok_or_else(|| {
+                ConfigError::Invalid(format!("--set {} is not of the form path=value", item))
+            })?;
+            loader = loader.set(path.trim(), value);
+        }
+        Ok(loader)
+    }
+}
+
+/// Returns the preset configuration named `mode`.
+fn preset(mode: &str) -> std::result::Result<Config, ConfigError> {
+    match mode {
+        "default" => Ok(Config::default()),
+        "iot" => Ok(Config::iot_mode()),
+        "low-power" => Ok(Config::low_power()),
+        "production" => Ok(Config::production("./aingle_data")),
+        "test" => Ok(Config::test_mode()),
+        _ => Err(ConfigError::Invalid(format!(
+            "unknown mode {}; expected default, iot, low-power, production or test",
+            mode
+        ))),
+    }
 }
 
 fn main() -> Result<()> {
@@ -155,6 +303,7 @@ fn main() -> Result<()> {
 
     match cli.command {
         Some(Commands::Run {
+            config,
             iot,
             low_power,
             publish_interval,
@@ -162,10 +311,16 @@ fn main() -> Result<()> {
             bind_addr,
             port,
             peer,
+            dns_service,
             mdns,
             db_path,
             rest_port,
+            metrics,
+            metrics_port,
+            keystore,
+            passphrase_env,
         }) => run_node(
+            config,
             iot,
             low_power,
             publish_interval,
@@ -173,14 +328,30 @@ fn main() -> Result<()> {
             bind_addr,
             port,
             peer,
+            dns_service,
             mdns,
             db_path,
             rest_port,
+            metrics,
+            metrics_port,
+            keystore,
+            passphrase_env,
         ),
-        Some(Commands::Keygen { output, format }) => keygen(output, format),
-        Some(Commands::Info) => show_info(),
+        Some(Commands::Keygen {
+            output,
+            format,
+            save,
+            passphrase_env,
+        }) => keygen(output, format, save, passphrase_env),
+        Some(Commands::Info { db_path }) => show_info(db_path),
         Some(Commands::Config { action }) => config_action(action),
         Some(Commands::Version) => show_version(),
+        Some(Commands::Replay {
+            log,
+            config,
+            keystore,
+            passphrase_env,
+        }) => replay_log(log, config, keystore, passphrase_env),
         Some(Commands::Bench {
             entries,
             iterations,
@@ -216,21 +387,27 @@ fn print_banner() {
 
 #[allow(clippy::too_many_arguments)]
 fn run_node(
+    config_path: Option<PathBuf>,
     iot: bool,
     low_power: bool,
     publish_interval: Option<u64>,
     memory_limit: Option<usize>,
-    bind_addr: String,
-    port: u16,
+    bind_addr: Option<String>,
+    port: Option<u16>,
     peers: Vec<String>,
+    dns_service: Option<String>,
     mdns: bool,
     db_path: Option<PathBuf>,
     rest_port: Option<u16>,
+    metrics: bool,
+    metrics_port: Option<u16>,
+    keystore: Option<PathBuf>,
+    passphrase_env: Option<String>,
 ) -> Result<()> {
     print_banner();
 
-    // Build configuration
-    let mut config = if iot {
+    // Build configuration: preset, then file, environment and flags
+    let base = if iot {
         println!("Mode: IoT (sub-second confirmation)");
         Config::iot_mode()
     } else if low_power {
@@ -240,23 +417,53 @@ fn run_node(
         println!("Mode: Standard");
         Config::default()
     };
-
-    // Apply overrides
+    let mut loader = ConfigLoader::new().base(base);
+    if let Some(path) = config_path {
+        loader = loader.file(path);
+    }
     if let Some(interval) = publish_interval {
-        config.publish_interval = Duration::from_millis(interval);
+        let interval = Duration::from_millis(interval);
+        loader = loader
+            .set("publish_interval.secs", interval.as_secs().to_string())
+            .set(
+                "publish_interval.nanos",
+                interval.subsec_nanos().to_string(),
+            );
     }
     if let Some(limit) = memory_limit {
-        config.memory_limit = limit * 1024;
+        loader = loader.set("memory_limit", (limit * 1024).to_string());
     }
     if mdns {
-        config.enable_mdns = true;
+        loader = loader.set("enable_mdns", "true");
+    }
+    if metrics || metrics_port.is_some() {
+        loader = loader.set("enable_metrics", "true");
     }
     if let Some(path) = db_path {
-        config.storage.db_path = path.to_string_lossy().to_string();
+        loader = loader.set("storage.db_path", path.to_string_lossy());
     }
-
-    // Update transport config
-    config.transport = aingle_minimal::TransportConfig::Coap { bind_addr, port };
+    // Static peers are resolved and retried with backoff by the node
+    if !peers.is_empty() {
+        loader = loader.set("discovery.bootstrap_peers", peers.join(","));
+    }
+    if let Some(service) = dns_service {
+        loader = loader.set("discovery.dns_service", service);
+    }
+    if let Some(path) = keystore {
+        loader = loader.set("keystore_path", path.to_string_lossy());
+    }
+    if let Some(name) = passphrase_env {
+        loader = loader.set("keystore_passphrase_env", name);
+    }
+    if bind_addr.is_some() || port.is_some() {
+        let transport = aingle_minimal::TransportConfig::Coap {
+            bind_addr: bind_addr.unwrap_or_else(|| "0.0.0.0".to_string()),
+            port: port.unwrap_or(5683),
+        };
+        let transport = serde_json::to_string(&transport).expect("transport serializes");
+        loader = loader.set("transport", transport);
+    }
+    let config = loader.load()?.config;
 
     // Print configuration
     println!("\nConfiguration:");
@@ -265,23 +472,43 @@ fn run_node(
     println!("  Memory limit: {} KB", config.memory_limit / 1024);
     println!("  Gossip delay: {:?}", config.gossip.loop_delay);
     println!("  mDNS discovery: {}", config.enable_mdns);
+    println!("  Metrics: {}", config.enable_metrics);
+    if !config.discovery.bootstrap_peers.is_empty() {
+        println!(
+            "  Bootstrap peers: {}",
+            config.discovery.bootstrap_peers.join(", ")
+        );
+    }
+    if let Some(ref service) = config.discovery.dns_service {
+        println!("  DNS discovery: {}", service);
+    }
     println!("  Storage: {}", config.storage.db_path);
+    match config.keystore_path {
+        Some(ref path) => println!("  Identity: {}", path),
+        None => println!("  Identity: ephemeral (use --keystore to persist)"),
+    }
     println!();
 
     // Create node
     let mut node = MinimalNode::new(config)?;
 
-    // Add peers
-    for peer_addr in peers {
-        match peer_addr.parse() {
-            Ok(addr) => {
-                node.add_peer(addr);
-                println!("Added peer: {}", peer_addr);
-            }
-            Err(e) => {
-                eprintln!("Invalid peer address '{}': {}", peer_addr, e);
-            }
+    // Serve Prometheus metrics from the node's published health snapshot
+    #[cfg(feature = "rest")]
+    let _metrics_server = match metrics_port {
+        Some(port) => {
+            let bind_addr = format!("0.0.0.0:{}", port);
+            println!("Metrics: http://{}/metrics", bind_addr);
+            Some(aingle_minimal::MetricsServer::start(
+                &bind_addr,
+                node.health_handle(),
+            )?)
         }
+        None => None,
+    };
+    #[cfg(not(feature = "rest"))]
+    if metrics_port.is_some() {
+        eprintln!("Warning: HTTP metrics requested but 'rest' feature not enabled");
+        eprintln!("Rebuild with: cargo build --features rest");
     }
 
     println!("\nNode public key: {}", node.public_key().to_hex());
@@ -341,19 +568,47 @@ fn run_node(
     Ok(())
 }
 
-fn keygen(output: Option<PathBuf>, format: String) -> Result<()> {
+fn keygen(
+    output: Option<PathBuf>,
+    format: String,
+    save: bool,
+    passphrase_env: String,
+) -> Result<()> {
     use aingle_minimal::crypto::Keypair;
 
     let keypair = Keypair::generate();
     let pubkey = keypair.public_key();
 
+    if save {
+        // clap enforces --output with --save
+        let path = output.unwrap_or_default();
+        let passphrase = std::env::var(&passphrase_env).map_err(|_| {
+            aingle_minimal::config::ConfigError::Invalid(format!(
+                "set {} to the keystore passphrase",
+                passphrase_env
+            ))
+        })?;
+        if path.exists() {
+            return Err(aingle_minimal::config::ConfigError::Invalid(format!(
+                "{} already exists; refusing to overwrite an identity",
+                path.display()
+            ))
+            .into());
+        }
+        keypair.save(&path, &passphrase)?;
+        println!("Public Key: {}", pubkey.to_hex());
+        println!("Encrypted keystore written to: {}", path.display());
+        println!("Run with: aingle-minimal run --keystore {}", path.display());
+        return Ok(());
+    }
+
     let pubkey_str = match format.as_str() {
         "base64" => base64_encode(pubkey.as_bytes()),
         _ => pubkey.to_hex(),
     };
 
     let output_text = format!(
-        "Public Key: {}\nFormat: {}\n\nNote: Private key is not exported for security.",
+        "Public Key: {}\nFormat: {}\n\nNote: Private key is not exported. Use --save to keep it in an encrypted keystore.",
         pubkey_str, format
     );
 
@@ -367,7 +622,7 @@ fn keygen(output: Option<PathBuf>, format: String) -> Result<()> {
     Ok(())
 }
 
-fn show_info() -> Result<()> {
+fn show_info(db_path: Option<PathBuf>) -> Result<()> {
     print_banner();
 
     println!("Build Information:");
@@ -427,14 +682,50 @@ fn show_info() -> Result<()> {
 
     println!();
 
+    if let Some(path) = db_path {
+        let mut storage_config = Config::default().storage;
+        storage_config.db_path = path.to_string_lossy().to_string();
+        let storage = aingle_minimal::DynamicStorage::from_config(storage_config)?;
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let bans: Vec<_> = aingle_minimal::sync::load_banned_peers(&storage)?
+            .into_iter()
+            .filter(|ban| ban.banned_until_secs > now)
+            .collect();
+
+        println!("Banned Peers:");
+        if bans.is_empty() {
+            println!("  (none)");
+        }
+        for ban in bans {
+            println!(
+                "  {} (score {:.1}, {}s remaining)",
+                ban.addr,
+                ban.score,
+                ban.banned_until_secs - now
+            );
+        }
+        println!();
+    }
+
     Ok(())
 }
 
 fn config_action(action: ConfigAction) -> Result<()> {
     match action {
-        ConfigAction::Show => {
-            let config = Config::default();
-            print_config(&config);
+        ConfigAction::Show { effective, layers } => {
+            let loaded = layers.loader()?.merge()?;
+            if effective {
+                print_effective(&loaded);
+            } else {
+                print_config(&loaded.config);
+            }
+            if let Err(e) = loaded.validate() {
+                println!("\n{}", e);
+            }
         }
         ConfigAction::Iot => {
             let config = Config::iot_mode();
@@ -446,17 +737,53 @@ fn config_action(action: ConfigAction) -> Result<()> {
             println!("Low Power Mode Configuration:\n");
             print_config(&config);
         }
-        ConfigAction::Validate => {
-            let config = Config::from_env();
-            match config.validate() {
-                Ok(()) => println!("Configuration is valid."),
-                Err(e) => println!("Configuration error: {}", e),
+        ConfigAction::Validate { layers } => match layers.loader()?.load() {
+            Ok(_) => println!("Configuration is valid."),
+            Err(e) => println!("Configuration error: {}", e),
+        },
+        ConfigAction::Diff { left, right } => {
+            let diff = diff_operand(&left)?.diff(&diff_operand(&right)?);
+            println!("--- {}", left);
+            println!("+++ {}", right);
+            if diff.is_empty() {
+                println!("No differences.");
+            }
+            let width = diff.iter().map(|d| d.path.len()).max().unwrap_or(0);
+            for d in diff {
+                println!("  {:width$}  {} -> {}", d.path, d.left, d.right);
             }
         }
     }
     Ok(())
 }
 
+/// Loads one side of `config diff`: an existing file over the defaults, or a
+/// preset. The environment is not applied.
+fn diff_operand(name: &str) -> std::result::Result<Config, ConfigError> {
+    if std::path::Path::new(name).is_file() {
+        Ok(ConfigLoader::new().file(name).without_env().merge()?.config)
+    } else {
+        preset(name)
+    }
+}
+
+/// Prints every setting with its value and the layer that set it.
+fn print_effective(loaded: &LoadedConfig) {
+    let width = loaded
+        .settings()
+        .map(|(path, _)| path.len())
+        .max()
+        .unwrap_or(0);
+    for (path, setting) in loaded.settings() {
+        println!(
+            "  {:width$} = {:<24}  # {}",
+            path,
+            setting.value.to_string(),
+            setting.source
+        );
+    }
+}
+
 fn print_config(config: &Config) {
     println!("  node_id: {:?}", config.node_id);
     println!("  publish_interval: {:?}", config.publish_interval);
@@ -472,6 +799,13 @@ fn print_config(config: &Config) {
         "    output_target_mbps: {}",
         config.gossip.output_target_mbps
     );
+    println!(
+        "    peer_limits: {} req/s (burst {}), ban at score {} for {:?}",
+        config.gossip.peer_limits.requests_per_sec,
+        config.gossip.peer_limits.request_burst,
+        config.gossip.peer_limits.ban_threshold,
+        config.gossip.peer_limits.ban_duration
+    );
     println!("\nStorage:");
     println!("    db_path: {}", config.storage.db_path);
     println!("    backend: {:?}", config.storage.backend);
@@ -537,6 +871,54 @@ fn show_version() -> Result<()> {
     Ok(())
 }
 
+fn replay_log(
+    log: PathBuf,
+    config_path: Option<PathBuf>,
+    keystore: Option<PathBuf>,
+    passphrase_env: Option<String>,
+) -> Result<()> {
+    let mut loader = ConfigLoader::new().base(Config::default());
+    if let Some(path) = config_path {
+        loader = loader.file(path);
+    }
+    if let Some(path) = keystore {
+        loader = loader.set("keystore_path", path.to_string_lossy());
+    }
+    if let Some(name) = passphrase_env {
+        loader = loader.set("keystore_passphrase_env", name);
+    }
+    let config = loader.load()?.config;
+
+    let recording = aingle_minimal::Recording::read(&log)?;
+    println!("Replaying {}", log.display());
+    println!("  Node: {}", recording.header.node_key);
+    println!("  Seed: {}", recording.header.seed);
+    println!("  Events: {}", recording.events.len());
+    if recording.wrapped {
+        println!(
+            "  Warning: the first {} events were dropped from the log",
+            recording.header.first_event
+        );
+    }
+
+    let replay = aingle_minimal::replay::replay(&recording, config)?;
+    let stats = replay.node.storage_stats()?;
+    println!();
+    println!("Result:");
+    println!("  Events replayed: {}", replay.events);
+    println!("  Events failed: {}", replay.errors);
+    println!("  Actions: {}", stats.action_count);
+    println!("  Entries: {}", stats.entry_count);
+    println!("  Storage size: {} bytes", stats.db_size);
+    println!();
+    println!("Entries created:");
+    for hash in &replay.hashes {
+        println!("  {}", hash.to_hex());
+    }
+
+    Ok(())
+}
+
 fn run_benchmark(entries: usize, iterations: usize) -> Result<()> {
     println!("Running benchmark...\n");
     println!("  Entries per iteration: {}", entries);
@@ -13,9 +13,10 @@
 //! - **Mesh**: Future support for mesh networking
 
 use crate::config::{GossipConfig, TransportConfig};
-use crate::discovery::Discovery;
+use crate::discovery::{Bootstrap, Discovery};
 use crate::error::{Error, Result};
 use crate::types::{Hash, Record};
+use crate::wire::{keyed_enum, WireEncoding};
 use serde::{Deserialize, Serialize};
 use smol::channel::{bounded, Sender};
 use std::collections::HashMap;
@@ -26,8 +27,13 @@ use std::time::{Duration, Instant};
 /// Counter for generating unique request IDs
 static REQUEST_ID_COUNTER: AtomicU64 = AtomicU64::new(1);
 
+/// Consecutive failures after which a peer is only used when no healthier peers are left
+const MAX_CONSECUTIVE_FAILURES: u32 = 3;
+
+#[cfg(feature = "coap")]
+use crate::coap::{BlockwiseConfig, CoapServer};
 #[cfg(feature = "coap")]
-use crate::coap::CoapServer;
+use crate::health::HealthHandle;
 
 #[cfg(feature = "quic")]
 use crate::quic::QuicServer;
@@ -43,15 +49,32 @@ pub struct PeerInfo {
     pub latest_seq: u32,
     /// Connection quality (0-100)
     pub quality: u8,
+    /// Consecutive failed exchanges since the last success
+    pub failures: u32,
 }
 
 /// Network message types
+///
+/// `Ping` and `Pong` double as the encoding handshake: each lists the
+/// [`WireEncoding`]s its sender can read, and an empty list means JSON only.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 pub enum Message {
     /// Ping for liveness
-    Ping { node_id: String },
+    Ping {
+        node_id: String,
+        /// Encodings the sender can read, most preferred first
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        encodings: Vec<WireEncoding>,
+    },
     /// Pong response
-    Pong { node_id: String, latest_seq: u32 },
+    Pong {
+        node_id: String,
+        latest_seq: u32,
+        /// Encodings the sender can read, most preferred first
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        encodings: Vec<WireEncoding>,
+    },
     /// Gossip request (asking for records)
     GossipRequest { from_seq: u32, limit: u32 },
     /// Gossip response (sending records)
@@ -95,6 +118,48 @@ pub enum Message {
     },
 }
 
+keyed_enum!(Message {
+    0 => Ping { 1 => node_id, 2 => encodings },
+    1 => Pong { 1 => node_id, 2 => latest_seq, 3 => encodings },
+    2 => GossipRequest { 1 => from_seq, 2 => limit },
+    3 => GossipResponse { 1 => records },
+    4 => NewRecord { 1 => hash },
+    5 => GetRecord { 1 => hash },
+    6 => RecordData { 1 => record },
+    7 => RemoteCall { 1 => request_id, 2 => from, 3 => method, 4 => payload as bytes },
+    8 => RemoteCallResponse { 1 => request_id, 2 => success, 3 => data as bytes },
+    9 => MeshRelay { 1 => message_id, 2 => origin, 3 => ttl, 4 => inner },
+});
+
+impl Message {
+    /// Handshake ping advertising the encodings this build supports
+    pub fn ping(node_id: impl Into<String>) -> Self {
+        Message::Ping {
+            node_id: node_id.into(),
+            encodings: WireEncoding::supported(),
+        }
+    }
+
+    /// Handshake pong advertising the encodings this build supports
+    pub fn pong(node_id: impl Into<String>, latest_seq: u32) -> Self {
+        Message::Pong {
+            node_id: node_id.into(),
+            latest_seq,
+            encodings: WireEncoding::supported(),
+        }
+    }
+
+    /// Encodings advertised by a handshake message
+    ///
+    /// `None` if this is not a `Ping` or `Pong`.
+    pub fn advertised_encodings(&self) -> Option<&[WireEncoding]> {
+        match self {
+            Message::Ping { encodings, .. } | Message::Pong { encodings, .. } => Some(encodings),
+            _ => None,
+        }
+    }
+}
+
 /// Pending RPC request tracking
 #[derive(Debug)]
 struct PendingRpc {
@@ -230,10 +295,16 @@ pub struct Network {
     /// CoAP server (when coap feature enabled)
     #[cfg(feature = "coap")]
     coap_server: Option<CoapServer>,
+    /// Block-wise transfer settings applied to the CoAP server
+    #[cfg(feature = "coap")]
+    coap_blockwise: BlockwiseConfig,
+    /// Health snapshot served by the CoAP server on `/metrics`
+    #[cfg(feature = "coap")]
+    coap_health: Option<HealthHandle>,
     /// QUIC server (when quic feature enabled)
     #[cfg(feature = "quic")]
     quic_server: Option<QuicServer>,
-    /// mDNS discovery service
+    /// Peer discovery (mDNS, static and DNS)
     discovery: Option<Discovery>,
     /// Pending RPC requests waiting for responses
     pending_rpcs: HashMap<String, PendingRpc>,
@@ -251,6 +322,10 @@ impl Network {
             node_id,
             #[cfg(feature = "coap")]
             coap_server: None,
+            #[cfg(feature = "coap")]
+            coap_blockwise: BlockwiseConfig::default(),
+            #[cfg(feature = "coap")]
+            coap_health: None,
             #[cfg(feature = "quic")]
             quic_server: None,
             discovery: None,
@@ -259,6 +334,20 @@ impl Network {
         }
     }
 
+    /// Set the CoAP block-wise transfer settings used when the server starts
+    #[cfg(feature = "coap")]
+    pub fn with_coap_blockwise(mut self, config: BlockwiseConfig) -> Self {
+        self.coap_blockwise = config;
+        self
+    }
+
+    /// Serve the given health snapshot on the CoAP `/metrics` resource
+    #[cfg(feature = "coap")]
+    pub fn with_health(mut self, health: HealthHandle) -> Self {
+        self.coap_health = Some(health);
+        self
+    }
+
     /// Start the network
     pub async fn start(&mut self) -> Result<()> {
         match &self.config {
@@ -271,6 +360,10 @@ impl Network {
                 log::info!("Starting CoAP transport on {}:{}", bind_addr, port);
 
                 let mut server = CoapServer::new(bind_addr.clone(), *port, self.node_id.clone());
+                server.set_blockwise_config(self.coap_blockwise.clone())?;
+                if let Some(ref health) = self.coap_health {
+                    server.set_health_source(health.clone());
+                }
                 server.start().await?;
 
                 // Join multicast for discovery
@@ -378,9 +471,24 @@ impl Network {
         Ok(())
     }
 
+    /// Add static and DNS bootstrap peers to discovery
+    ///
+    /// Works with or without mDNS: if mDNS discovery was not started (or failed
+    /// to start), a discovery service without mDNS is created for them.
+    pub fn set_bootstrap(&mut self, port: u16, bootstrap: Bootstrap) {
+        let node_id = self.node_id.clone();
+        self.discovery
+            .get_or_insert_with(|| Discovery::without_mdns(node_id, port))
+            .set_bootstrap(bootstrap);
+    }
+
     /// Sync discovered peers into the peer list
-    pub fn sync_discovered_peers(&mut self) {
-        if let Some(ref discovery) = self.discovery {
+    ///
+    /// Static and DNS entries that are due are resolved again first, so peers
+    /// that changed address are picked up.
+    pub async fn sync_discovered_peers(&mut self) {
+        if let Some(ref mut discovery) = self.discovery {
+            discovery.refresh().await;
             let discovered = discovery.get_peer_addrs();
             for addr in discovered {
                 if !self.peers.contains_key(&addr) {
@@ -442,6 +550,7 @@ impl Network {
                 last_seen: Instant::now(),
                 latest_seq: 0,
                 quality: 50,
+                failures: 0,
             },
         );
     }
@@ -467,6 +576,7 @@ impl Network {
             peer.latest_seq = latest_seq;
             // Increase quality on successful interaction
             peer.quality = (peer.quality + 5).min(100);
+            peer.failures = 0;
         }
     }
 
@@ -475,6 +585,7 @@ impl Network {
         if let Some(peer) = self.peers.get_mut(addr) {
             // Decrease quality on failure
             peer.quality = peer.quality.saturating_sub(10);
+            peer.failures = peer.failures.saturating_add(1);
         }
     }
 
@@ -518,6 +629,15 @@ impl Network {
         self.send(addr, message).await
     }
 
+    /// Ping a peer, advertising the encodings this node can read
+    ///
+    /// Once the peer answers with [`Message::pong`], both sides switch to the
+    /// most compact encoding they share.
+    pub async fn send_handshake(&mut self, addr: &SocketAddr) -> Result<()> {
+        let ping = Message::ping(self.node_id.clone());
+        self.send(addr, &ping).await
+    }
+
     /// Broadcast message to all peers
     pub async fn broadcast(&mut self, message: &Message) -> Result<()> {
         let peers: Vec<SocketAddr> = self.active_peers().iter().map(|p| p.addr).collect();
@@ -563,9 +683,16 @@ impl Network {
     }
 
     /// Get peers for gossip (best quality first)
+    ///
+    /// Peers that failed several exchanges in a row are placed after all others.
     pub fn gossip_peers(&self) -> Vec<SocketAddr> {
         let mut peers: Vec<_> = self.active_peers().into_iter().collect();
-        peers.sort_by(|a, b| b.quality.cmp(&a.quality));
+        peers.sort_by_key(|p| {
+            (
+                p.failures >= MAX_CONSECUTIVE_FAILURES,
+                std::cmp::Reverse(p.quality),
+            )
+        });
         peers
             .into_iter()
             .take(self.gossip_config.max_peers)
@@ -926,6 +1053,7 @@ mod tests {
             last_seen: Instant::now(),
             latest_seq: 42,
             quality: 75,
+            failures: 0,
         };
 
         assert_eq!(peer.addr, addr);
@@ -941,6 +1069,7 @@ mod tests {
             last_seen: Instant::now(),
             latest_seq: 100,
             quality: 90,
+            failures: 0,
         };
 
         let peer2 = peer1.clone();
@@ -957,6 +1086,7 @@ mod tests {
             last_seen: Instant::now(),
             latest_seq: 0,
             quality: 50,
+            failures: 0,
         };
         let debug_str = format!("{:?}", peer);
         assert!(debug_str.contains("PeerInfo"));
@@ -967,13 +1097,14 @@ mod tests {
     fn test_message_ping_serialize() {
         let msg = Message::Ping {
             node_id: "node123".to_string(),
+            encodings: vec![],
         };
         let json = serde_json::to_string(&msg).unwrap();
         assert!(json.contains("Ping"));
         assert!(json.contains("node123"));
 
         let parsed: Message = serde_json::from_str(&json).unwrap();
-        if let Message::Ping { node_id } = parsed {
+        if let Message::Ping { node_id, .. } = parsed {
             assert_eq!(node_id, "node123");
         } else {
             panic!("Expected Ping message");
@@ -985,6 +1116,7 @@ mod tests {
         let msg = Message::Pong {
             node_id: "node456".to_string(),
             latest_seq: 42,
+            encodings: vec![],
         };
         let json = serde_json::to_string(&msg).unwrap();
         assert!(json.contains("Pong"));
@@ -994,6 +1126,7 @@ mod tests {
         if let Message::Pong {
             node_id,
             latest_seq,
+            ..
         } = parsed
         {
             assert_eq!(node_id, "node456");
@@ -1069,10 +1202,13 @@ mod tests {
     fn test_message_clone() {
         let msg1 = Message::Ping {
             node_id: "test".to_string(),
+            encodings: vec![],
         };
         let msg2 = msg1.clone();
 
-        if let (Message::Ping { node_id: n1 }, Message::Ping { node_id: n2 }) = (&msg1, &msg2) {
+        if let (Message::Ping { node_id: n1, .. }, Message::Ping { node_id: n2, .. }) =
+            (&msg1, &msg2)
+        {
             assert_eq!(n1, n2);
         } else {
             panic!("Clone failed");
@@ -1083,6 +1219,7 @@ mod tests {
     fn test_message_debug() {
         let msg = Message::Ping {
             node_id: "debug-test".to_string(),
+            encodings: vec![],
         };
         let debug_str = format!("{:?}", msg);
         assert!(debug_str.contains("Ping"));
@@ -1149,6 +1286,34 @@ mod tests {
         network.mark_peer_failed(&addr);
     }
 
+    #[test]
+    fn test_gossip_peers_deprioritizes_failing_peers() {
+        let config = TransportConfig::Memory;
+        let gossip = GossipConfig::default();
+        let mut network = Network::new(config, gossip, "test-node".to_string());
+
+        let flaky: SocketAddr = "127.0.0.1:19080".parse().unwrap();
+        let weak: SocketAddr = "127.0.0.1:19081".parse().unwrap();
+        network.add_peer(flaky);
+        network.add_peer(weak);
+
+        // Flaky peer has better quality but keeps failing handshakes
+        for _ in 0..10 {
+            network.update_peer(flaky, 1);
+        }
+        for _ in 0..MAX_CONSECUTIVE_FAILURES {
+            network.mark_peer_failed(&flaky);
+        }
+        for _ in 0..2 {
+            network.mark_peer_failed(&weak);
+        }
+        assert_eq!(network.gossip_peers(), vec![weak, flaky]);
+
+        // One success restores its place
+        network.update_peer(flaky, 2);
+        assert_eq!(network.gossip_peers(), vec![flaky, weak]);
+    }
+
     #[test]
     fn test_network_active_peers_timeout() {
         let config = TransportConfig::Memory;
@@ -1369,7 +1534,7 @@ mod tests {
         let mut network = Network::new(config, gossip, "test-node".to_string());
 
         // Should not panic when discovery is None
-        network.sync_discovered_peers();
+        smol::block_on(network.sync_discovered_peers());
         assert_eq!(network.peer_count(), 0);
     }
 
@@ -1480,12 +1645,50 @@ mod tests {
             let addr: SocketAddr = "127.0.0.1:5683".parse().unwrap();
             let msg = Message::Ping {
                 node_id: "test".to_string(),
+                encodings: vec![],
             };
             let result = network.send(&addr, &msg).await;
             assert!(result.is_ok());
         });
     }
 
+    #[test]
+    fn test_handshake_messages() {
+        let ping = Message::ping("node-a");
+        assert_eq!(
+            ping.advertised_encodings(),
+            Some(WireEncoding::supported().as_slice())
+        );
+        let pong = Message::pong("node-b", 9);
+        assert_eq!(
+            pong.advertised_encodings(),
+            Some(WireEncoding::supported().as_slice())
+        );
+        assert!(Message::GossipRequest {
+            from_seq: 0,
+            limit: 1
+        }
+        .advertised_encodings()
+        .is_none());
+
+        // Old peers neither send nor expect the field
+        let old: Message = serde_json::from_str(r#"{"Ping":{"node_id":"old"}}"#).unwrap();
+        assert_eq!(old.advertised_encodings(), Some(&[][..]));
+        let json = serde_json::to_string(&old).unwrap();
+        assert_eq!(json, r#"{"Ping":{"node_id":"old"}}"#);
+    }
+
+    #[test]
+    fn test_network_send_handshake_memory() {
+        let mut network = Network::new(
+            TransportConfig::Memory,
+            GossipConfig::default(),
+            "test-node".to_string(),
+        );
+        let addr: SocketAddr = "127.0.0.1:5683".parse().unwrap();
+        assert!(smol::block_on(network.send_handshake(&addr)).is_ok());
+    }
+
     #[test]
     fn test_network_send_confirmable_memory() {
         let config = TransportConfig::Memory;
@@ -1496,6 +1699,7 @@ mod tests {
             let addr: SocketAddr = "127.0.0.1:5683".parse().unwrap();
             let msg = Message::Ping {
                 node_id: "test".to_string(),
+                encodings: vec![],
             };
             let result = network.send_confirmable(&addr, &msg).await;
             assert!(result.is_ok());
@@ -1517,6 +1721,7 @@ mod tests {
         smol::block_on(async {
             let msg = Message::Ping {
                 node_id: "broadcast".to_string(),
+                encodings: vec![],
             };
             let result = network.broadcast(&msg).await;
             assert!(result.is_ok());
@@ -1853,6 +2058,7 @@ mod tests {
     fn test_mesh_manager_wrap_for_relay() {
         let inner = Message::Ping {
             node_id: "source".to_string(),
+            encodings: vec![],
         };
 
         let wrapped = MeshManager::wrap_for_relay("relay-node", inner, Some(3));
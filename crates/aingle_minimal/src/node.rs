@@ -36,13 +36,20 @@
 //! # }
 //! ```
 
-use crate::config::Config;
+use crate::config::{Config, ConfigError};
 use crate::crypto::Keypair;
+use crate::discovery::Bootstrap;
 use crate::error::Result;
 use crate::gossip::GossipManager;
+use crate::graph::SemanticGraph;
+use crate::health::{HealthHandle, NodeHealth, HEALTH_SCHEMA_VERSION};
 use crate::network::{Message, Network};
+use crate::power::BatteryInfo;
+use crate::replay::{InputEvent, Recorder, RecordingHeader, VirtualClock, RECORDING_VERSION};
+use crate::sensors::SensorReading;
+use crate::storage_crypto::KeyRotation;
 use crate::storage_factory::DynamicStorage;
-use crate::storage_trait::StorageBackend;
+use crate::storage_trait::{EntryPriority, StorageBackend};
 use crate::sync::SyncManager;
 use crate::types::*;
 use serde::{Deserialize, Serialize};
@@ -57,6 +64,15 @@ const PEERS_METADATA_KEY: &str = "known_peers";
 /// Interval for auto-saving peers (in seconds)
 const PEER_SAVE_INTERVAL_SECS: u64 = 300; // 5 minutes
 
+/// Interval for refreshing the published health snapshot (in seconds)
+const HEALTH_REFRESH_INTERVAL_SECS: u64 = 5;
+
+/// Expired records deleted per storage transaction
+const EXPIRY_DELETE_BATCH: usize = 64;
+
+/// Interval for checking storage against its budget watermark (in seconds)
+const STORAGE_BUDGET_CHECK_INTERVAL_SECS: u64 = 30;
+
 /// A serializable record of a known peer for persistence.
 ///
 /// This struct captures essential information about a peer that can be
@@ -136,6 +152,26 @@ pub struct MinimalNode {
     start_time: Instant,
     /// Timestamp of the last peer save operation
     last_peer_save: Instant,
+    /// Latest health snapshot, shared with metrics endpoints
+    health: HealthHandle,
+    /// Timestamp of the last health snapshot
+    last_health_refresh: Instant,
+    /// Last battery reading reported by the host
+    battery: Option<BatteryInfo>,
+    /// Unix timestamp (seconds) of the last published announcement
+    last_publish_secs: Option<u64>,
+    /// Semantic view of local data, with per-triple expiry
+    graph: SemanticGraph,
+    /// Timestamp of the last graph expiry sweep
+    last_expiry_sweep: Instant,
+    /// Timestamp of the last storage budget check
+    last_budget_check: Instant,
+    /// Re-sealing of records left under a previous encryption key
+    key_rotation: Option<KeyRotation>,
+    /// Log of inputs, when recording is enabled
+    recorder: Option<Recorder>,
+    /// Clock set by a replay; `None` uses the system clock
+    clock: Option<VirtualClock>,
 }
 
 impl MinimalNode {
@@ -143,7 +179,8 @@ impl MinimalNode {
     ///
     /// This method performs several initialization steps:
     /// 1. Validates the configuration
-    /// 2. Generates an Ed25519 keypair for the node's identity
+    /// 2. Loads the node's Ed25519 identity from `keystore_path` (creating the
+    ///    keystore on first run), or generates an ephemeral keypair
     /// 3. Initializes the storage backend (SQLite, RocksDB, or Memory)
     /// 4. Sets up the network layer with the configured transport
     /// 5. Initializes gossip and sync managers
@@ -152,6 +189,8 @@ impl MinimalNode {
     ///
     /// Returns an error if:
     /// - Configuration validation fails (see [`Config::validate`])
+    /// - The keystore passphrase variable is unset, or the keystore cannot be
+    ///   decrypted with it
     /// - Storage backend initialization fails
     /// - Network initialization fails
     ///
@@ -172,25 +211,86 @@ impl MinimalNode {
     /// # }
     /// ```
     pub fn new(config: Config) -> Result<Self> {
+        Self::build(config, None)
+    }
+
+    /// Creates a node replaying a recording, drawing its random choices from
+    /// `seed` and reading the time from `clock`.
+    pub(crate) fn replaying(config: Config, seed: u64, clock: VirtualClock) -> Result<Self> {
+        Self::build(config, Some((seed, clock)))
+    }
+
+    fn build(config: Config, replay: Option<(u64, VirtualClock)>) -> Result<Self> {
         // Validate configuration
         config.validate()?;
 
-        // Generate or load keypair
-        let keypair = Keypair::generate();
+        // Recorded and replayed sessions draw from a known seed
+        let (seed, clock) = match replay {
+            Some((seed, clock)) => (Some(seed), Some(clock)),
+            None if config.recording.is_enabled() => (
+                Some(config.recording.seed.unwrap_or_else(rand::random)),
+                None,
+            ),
+            None => (None, None),
+        };
+
+        let keypair = load_identity(&config)?;
 
         // Initialize storage based on configuration
         let storage = DynamicStorage::from_config(config.storage.clone())?;
         log::info!("Using {} storage backend", storage.backend_name());
+        let key_rotation = config
+            .storage
+            .encryption
+            .as_ref()
+            .filter(|encryption| encryption.has_previous_keys())
+            .map(|_| KeyRotation::new(config.memory_limit));
+
+        let recorder = match seed {
+            Some(seed) if clock.is_none() => {
+                let header = RecordingHeader {
+                    version: RECORDING_VERSION,
+                    session: Timestamp::now(),
+                    seed,
+                    node_key: keypair.public_key().to_hex(),
+                    start_seq: storage.get_latest_seq()?,
+                    first_event: 0,
+                };
+                Some(Recorder::open(&config.recording, header)?)
+            }
+            _ => None,
+        };
+        let graph = match &clock {
+            Some(clock) => SemanticGraph::new().with_clock(clock.clone()),
+            None => SemanticGraph::new(),
+        };
 
         // Initialize network
         let node_id = keypair.public_key().to_hex();
         let network = Network::new(config.transport.clone(), config.gossip.clone(), node_id);
+        #[cfg(feature = "coap")]
+        let network = network.with_coap_blockwise(crate::coap::BlockwiseConfig::for_memory_limit(
+            config.memory_limit,
+        ));
+
+        // Metrics endpoints read the snapshot published from the main loop
+        let health = HealthHandle::new();
+        #[cfg(feature = "coap")]
+        let network = if config.enable_metrics {
+            network.with_health(health.clone())
+        } else {
+            network
+        };
 
         // Initialize gossip manager
-        let gossip = GossipManager::new(config.gossip.clone());
+        let gossip = GossipManager::with_memory_limit(config.gossip.clone(), config.memory_limit)
+            .with_bridge(&config.bridge);
 
         // Initialize sync manager with gossip loop delay as sync interval
-        let sync = SyncManager::new(config.gossip.loop_delay * 2);
+        let sync = SyncManager::with_limits(
+            config.gossip.loop_delay * 2,
+            config.gossip.peer_limits.clone(),
+        );
 
         let mut node = Self {
             config,
@@ -202,12 +302,25 @@ impl MinimalNode {
             running: Arc::new(AtomicBool::new(false)),
             start_time: Instant::now(),
             last_peer_save: Instant::now(),
+            health,
+            last_health_refresh: Instant::now(),
+            battery: None,
+            last_publish_secs: None,
+            graph,
+            last_expiry_sweep: Instant::now(),
+            last_budget_check: Instant::now(),
+            key_rotation,
+            recorder,
+            clock,
         };
 
         // Load persisted peers from storage
         if let Err(e) = node.load_peers() {
             log::warn!("Failed to load persisted peers: {}", e);
         }
+        if let Err(e) = node.sync.load_bans(&node.storage) {
+            log::warn!("Failed to load persisted peer bans: {}", e);
+        }
 
         Ok(node)
     }
@@ -323,8 +436,56 @@ impl MinimalNode {
     /// # }
     /// ```
     pub fn create_entry<T: serde::Serialize>(&mut self, content: T) -> Result<Hash> {
-        // Create entry
-        let entry = Entry::app(content)?;
+        self.create_entry_with_priority(content, EntryPriority::Normal)
+    }
+
+    /// Creates a new application entry in the given priority class.
+    ///
+    /// Works like [`create_entry`](Self::create_entry), which uses
+    /// [`EntryPriority::Normal`]. The priority is stored with the record:
+    /// storage budget enforcement evicts Bulk entries before Normal ones and
+    /// Normal before Critical, and Critical entries are announced to peers
+    /// before anything else.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`create_entry`](Self::create_entry).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use aingle_minimal::{Config, EntryPriority, MinimalNode};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut node = MinimalNode::new(Config::test_mode())?;
+    ///
+    /// node.create_entry_with_priority("sample_rate=10s", EntryPriority::Critical)?;
+    /// node.create_entry_with_priority("temperature: 23.5C", EntryPriority::Bulk)?;
+    ///
+    /// let stats = node.storage_stats()?;
+    /// assert_eq!(stats.by_priority.critical, 1);
+    /// assert_eq!(stats.by_priority.bulk, 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn create_entry_with_priority<T: serde::Serialize>(
+        &mut self,
+        content: T,
+        priority: EntryPriority,
+    ) -> Result<Hash> {
+        self.create_app_entry(Entry::app(content)?, priority)
+    }
+
+    /// Signs, stores and announces a serialized application entry
+    pub(crate) fn create_app_entry(
+        &mut self,
+        entry: Entry,
+        priority: EntryPriority,
+    ) -> Result<Hash> {
+        let now = self.now();
+        self.record(now, || InputEvent::Entry {
+            content: String::from_utf8_lossy(&entry.content).into_owned(),
+            priority,
+        });
         let entry_hash = entry.hash();
 
         // Get previous action
@@ -340,7 +501,7 @@ impl MinimalNode {
         let action = Action {
             action_type: ActionType::Create,
             author: self.keypair.public_key(),
-            timestamp: Timestamp::now(),
+            timestamp: now,
             seq,
             prev_action,
             entry_hash: Some(entry_hash.clone()),
@@ -353,10 +514,13 @@ impl MinimalNode {
             entry: Some(entry),
         };
 
-        let action_hash = self.storage.put_record(&record)?;
+        let action_hash = self
+            .storage
+            .put_record_with_priority(&record, Some(priority.eviction_priority()))?;
 
         // Queue for gossip
-        self.gossip.announce(action_hash.clone());
+        self.gossip
+            .announce_with_priority(action_hash.clone(), priority);
 
         // Track in sync manager
         self.sync.add_local_hash(action_hash.clone());
@@ -404,20 +568,34 @@ impl MinimalNode {
         &mut self,
         contents: &[T],
     ) -> Result<Vec<Hash>> {
-        if contents.is_empty() {
+        let entries = contents
+            .iter()
+            .map(Entry::app)
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        self.create_app_entries(entries)
+    }
+
+    /// Signs, stores and announces serialized application entries in one batch
+    pub(crate) fn create_app_entries(&mut self, entries: Vec<Entry>) -> Result<Vec<Hash>> {
+        if entries.is_empty() {
             return Ok(Vec::new());
         }
+        let timestamp = self.now();
+        self.record(timestamp, || InputEvent::Batch {
+            contents: entries
+                .iter()
+                .map(|entry| String::from_utf8_lossy(&entry.content).into_owned())
+                .collect(),
+        });
 
         // Get base sequence number once
         let base_seq = self.storage.get_latest_seq()? + 1;
         let author = self.keypair.public_key();
-        let timestamp = Timestamp::now();
 
         // Build all records
-        let mut records = Vec::with_capacity(contents.len());
+        let mut records = Vec::with_capacity(entries.len());
 
-        for (i, content) in contents.iter().enumerate() {
-            let entry = Entry::app(content)?;
+        for (i, entry) in entries.into_iter().enumerate() {
             let entry_hash = entry.hash();
             let seq = base_seq + i as u32;
 
@@ -454,6 +632,45 @@ impl MinimalNode {
         Ok(hashes)
     }
 
+    /// Publishes a sensor reading as a Normal-priority entry.
+    ///
+    /// Use [`publish_sensor_data_with_priority`](Self::publish_sensor_data_with_priority)
+    /// to mark routine samples as Bulk so they give way to more important data.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`create_entry`](Self::create_entry).
+    pub fn publish_sensor_data(&mut self, reading: &SensorReading) -> Result<Hash> {
+        self.publish_sensor_data_with_priority(reading, EntryPriority::Normal)
+    }
+
+    /// Publishes a sensor reading in the given priority class.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`create_entry`](Self::create_entry).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use aingle_minimal::{Config, EntryPriority, MinimalNode};
+    /// # use aingle_minimal::sensors::{SensorReading, SensorType};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut node = MinimalNode::new(Config::iot_mode())?;
+    ///
+    /// let reading = SensorReading::new(SensorType::Temperature, 23.5, "C".to_string());
+    /// node.publish_sensor_data_with_priority(&reading, EntryPriority::Bulk)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn publish_sensor_data_with_priority(
+        &mut self,
+        reading: &SensorReading,
+        priority: EntryPriority,
+    ) -> Result<Hash> {
+        self.create_entry_with_priority(reading, priority)
+    }
+
     /// Retrieves an [`Entry`] from storage by its content hash.
     ///
     /// This method looks up an entry in the local database. It only returns entries
@@ -486,6 +703,38 @@ impl MinimalNode {
         self.storage.get_entry(hash)
     }
 
+    /// Returns the sequence number of the latest action in storage, or 0 if
+    /// there is none.
+    pub fn latest_seq(&self) -> Result<u32> {
+        self.storage.get_latest_seq()
+    }
+
+    /// Returns up to `limit` records with a sequence number above
+    /// `after_seq`, in sequence order.
+    ///
+    /// Pass the `seq` of the last record seen to follow the chain with a
+    /// cursor; 0 starts from the beginning.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use aingle_minimal::{Config, MinimalNode};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut node = MinimalNode::new(Config::test_mode())?;
+    /// node.create_entry("first")?;
+    /// node.create_entry("second")?;
+    ///
+    /// let records = node.records_since(1, 10)?;
+    /// assert_eq!(records.len(), 1);
+    /// assert_eq!(records[0].action.seq, 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn records_since(&self, after_seq: u32, limit: u32) -> Result<Vec<Record>> {
+        self.storage
+            .get_records_by_seq_range(after_seq.saturating_add(1), u32::MAX, limit)
+    }
+
     /// Signs the essential data of an action.
     fn sign_action_data(&self, seq: u32, entry_hash: &Hash) -> Signature {
         let mut data = Vec::new();
@@ -495,12 +744,21 @@ impl MinimalNode {
     }
 
     /// Publishes pending announcements to the network via gossip.
+    ///
+    /// Entries bridged to this node's transport from another one are published
+    /// along with its own.
     fn publish_pending(&mut self) -> Result<()> {
-        let announcements = self.gossip.take_announcements(50);
+        let mut announcements = self.gossip.take_announcements(50);
+        let room = 50 - announcements.len();
+        announcements.extend(self.gossip.take_bridged(self.config.transport.kind(), room));
+        if announcements.is_empty() {
+            return Ok(());
+        }
         for hash in announcements {
             let message = Message::NewRecord { hash };
             smol::block_on(self.network.broadcast(&message))?;
         }
+        self.last_publish_secs = Some(unix_now_secs());
         Ok(())
     }
 
@@ -511,9 +769,11 @@ impl MinimalNode {
     /// until [`stop()`](Self::stop) is called from another thread.
     ///
     /// The main loop performs these operations:
-    /// - Syncs with discovered mDNS peers
+    /// - Syncs with discovered mDNS, static and DNS peers
     /// - Runs periodic gossip rounds with known peers
     /// - Publishes pending data at configured intervals
+    /// - Sweeps expired graph triples at an interval set by the power mode
+    /// - Evicts old records when storage crosses its budget watermark
     /// - Handles network messages
     ///
     /// # Errors
@@ -549,28 +809,43 @@ impl MinimalNode {
         // Start network
         self.network.start().await?;
 
+        let port = match &self.config.transport {
+            crate::config::TransportConfig::Coap { port, .. } => *port,
+            crate::config::TransportConfig::Quic { port, .. } => *port,
+            #[cfg(feature = "webrtc")]
+            crate::config::TransportConfig::WebRtc { signaling_port, .. } => *signaling_port,
+            _ => 5683,
+        };
+
         // Start mDNS discovery if enabled
         if self.config.enable_mdns {
-            let port = match &self.config.transport {
-                crate::config::TransportConfig::Coap { port, .. } => *port,
-                crate::config::TransportConfig::Quic { port, .. } => *port,
-                #[cfg(feature = "webrtc")]
-                crate::config::TransportConfig::WebRtc { signaling_port, .. } => *signaling_port,
-                _ => 5683,
-            };
             if let Err(e) = self.network.start_discovery(port) {
                 log::warn!("Failed to start mDNS discovery: {}", e);
             }
         }
 
+        // Static and DNS peers, also the fallback when mDNS is unavailable
+        if !self.config.discovery.is_empty() {
+            let bootstrap = Bootstrap::new(&self.config.discovery, port);
+            self.network.set_bootstrap(port, bootstrap);
+            self.network.sync_discovered_peers().await;
+        }
+
+        if self.config.enable_metrics {
+            if let Err(e) = self.refresh_health() {
+                log::warn!("Failed to collect health snapshot: {}", e);
+            }
+        }
+
         let mut discovery_sync_counter = 0u32;
 
         // Main loop
         while self.running.load(Ordering::SeqCst) {
-            // Sync discovered peers periodically (every 100 iterations = ~1 second)
+            // Sync discovered peers periodically (every 100 iterations = ~1 second).
+            // Static and DNS entries are only re-resolved when due.
             discovery_sync_counter = discovery_sync_counter.wrapping_add(1);
             if discovery_sync_counter % 100 == 0 {
-                self.network.sync_discovered_peers();
+                self.network.sync_discovered_peers().await;
             }
 
             // Check gossip timing
@@ -583,6 +858,34 @@ impl MinimalNode {
                 self.publish_pending()?;
             }
 
+            // Refresh the health snapshot read by the metrics endpoints
+            if self.config.enable_metrics
+                && self.last_health_refresh.elapsed().as_secs() >= HEALTH_REFRESH_INTERVAL_SECS
+            {
+                if let Err(e) = self.refresh_health() {
+                    log::warn!("Failed to refresh health snapshot: {}", e);
+                }
+            }
+
+            // Sweep expired triples less often in lower power modes
+            if self.last_expiry_sweep.elapsed() >= self.config.power_mode.expiry_sweep_interval() {
+                if let Err(e) = self.expire_graph() {
+                    log::warn!("Failed to expire graph triples: {}", e);
+                }
+            }
+
+            // Keep storage under its configured budget
+            if self.last_budget_check.elapsed().as_secs() >= STORAGE_BUDGET_CHECK_INTERVAL_SECS {
+                if let Err(e) = self.enforce_storage_budget() {
+                    log::warn!("Failed to enforce storage budget: {}", e);
+                }
+            }
+
+            // Re-seal records under the new key, one batch per iteration
+            if let Err(e) = self.step_key_rotation() {
+                log::warn!("Failed to rotate storage encryption key: {}", e);
+            }
+
             // Periodically save peers to storage
             if self.last_peer_save.elapsed().as_secs() >= PEER_SAVE_INTERVAL_SECS {
                 if let Err(e) = self.save_peers() {
@@ -917,6 +1220,51 @@ impl MinimalNode {
         self.sync.stats()
     }
 
+    /// Returns per-peer request and misbehavior counters.
+    ///
+    /// Includes peers restored from the persisted ban list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use aingle_minimal::{MinimalNode, Config};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let node = MinimalNode::new(Config::test_mode())?;
+    ///
+    /// for peer in node.peer_stats() {
+    ///     println!("{}: score {:.1}", peer.addr, peer.misbehavior_score);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn peer_stats(&self) -> Vec<crate::sync::PeerStats> {
+        self.sync.peer_stats(Instant::now())
+    }
+
+    /// Returns statistics from the storage backend.
+    ///
+    /// Includes stored actions broken down by [`EntryPriority`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the storage backend cannot compute its statistics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use aingle_minimal::{MinimalNode, Config};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let node = MinimalNode::new(Config::test_mode())?;
+    ///
+    /// let stats = node.storage_stats()?;
+    /// println!("Critical actions: {}", stats.by_priority.critical);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn storage_stats(&self) -> Result<crate::storage_trait::StorageStats> {
+        self.storage.stats()
+    }
+
     /// Returns statistics from the gossip manager.
     ///
     /// These statistics provide insights into gossip protocol performance,
@@ -938,6 +1286,288 @@ impl MinimalNode {
     pub fn gossip_stats(&self) -> crate::gossip::GossipStats {
         self.gossip.stats()
     }
+
+    /// Records the latest battery reading, reported in [`NodeHealth`].
+    ///
+    /// The node has no battery driver of its own; the host application reads
+    /// the hardware (e.g. through [`crate::power::PowerManager`]) and forwards it.
+    pub fn update_battery(&mut self, battery: BatteryInfo) {
+        let now = self.now();
+        self.record(now, || InputEvent::Battery {
+            battery: battery.clone(),
+        });
+        self.battery = Some(battery);
+    }
+
+    /// Assembles a [`NodeHealth`] snapshot from the node's current state.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if storage statistics cannot be retrieved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use aingle_minimal::{MinimalNode, Config, HEALTH_SCHEMA_VERSION};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let node = MinimalNode::new(Config::test_mode())?;
+    /// let health = node.health()?;
+    /// assert_eq!(health.version, HEALTH_SCHEMA_VERSION);
+    /// println!("{}", health.to_prometheus());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn health(&self) -> Result<NodeHealth> {
+        Ok(NodeHealth {
+            version: HEALTH_SCHEMA_VERSION,
+            node_id: self.keypair.public_key().to_hex(),
+            software_version: crate::VERSION.to_string(),
+            uptime_secs: self.start_time.elapsed().as_secs(),
+            power_mode: self.config.power_mode,
+            transport: self.config.transport.kind().to_string(),
+            peer_count: self.network.peer_count(),
+            last_publish_secs: self.last_publish_secs,
+            storage: self.storage.stats()?,
+            gossip: self.gossip.stats(),
+            sync: self.sync.stats(),
+            battery: self.battery.clone(),
+            collected_at_secs: unix_now_secs(),
+        })
+    }
+
+    /// Collects a fresh health snapshot and publishes it to the [`HealthHandle`].
+    ///
+    /// The main loop calls this every few seconds when `enable_metrics` is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if storage statistics cannot be retrieved.
+    pub fn refresh_health(&mut self) -> Result<()> {
+        let health = self.health()?;
+        self.health.publish(health);
+        self.last_health_refresh = Instant::now();
+        Ok(())
+    }
+
+    /// Returns the node's semantic graph.
+    ///
+    /// Triples inserted with [`SemanticGraph::insert_with_ttl`] are swept by
+    /// the main loop and their source records deleted from storage.
+    pub fn graph(&self) -> &SemanticGraph {
+        &self.graph
+    }
+
+    /// Sweeps expired graph triples and deletes their source records.
+    ///
+    /// The main loop calls this every
+    /// [`PowerMode::expiry_sweep_interval`](crate::config::PowerMode::expiry_sweep_interval).
+    /// Returns the number of triples removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the graph lock is poisoned or a storage batch
+    /// fails; records from a failed batch are retried on the next sweep.
+    pub fn expire_graph(&mut self) -> Result<usize> {
+        self.last_expiry_sweep = Instant::now();
+        let removed = self.graph.expire(self.graph.now())?;
+        if removed > 0 {
+            let now = self.now();
+            self.record(now, || InputEvent::ExpireGraph);
+        }
+        self.graph
+            .flush_expired(&self.storage, EXPIRY_DELETE_BATCH)?;
+        Ok(removed)
+    }
+
+    /// Evicts records if storage has crossed its budget watermark.
+    ///
+    /// When the database reaches `budget_watermark` of `max_size`, records are
+    /// evicted in the configured [`EvictionPolicy`](crate::storage_trait::EvictionPolicy)
+    /// order until it is back under the watermark, and the storage is compacted.
+    /// The main loop calls this periodically. Returns the number of actions evicted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if storage statistics cannot be read or eviction fails.
+    pub fn enforce_storage_budget(&mut self) -> Result<usize> {
+        self.last_budget_check = Instant::now();
+        let storage = &self.config.storage;
+        let watermark = (storage.max_size as f64 * f64::from(storage.budget_watermark)) as usize;
+        if self.storage.stats()?.db_size < watermark {
+            return Ok(0);
+        }
+
+        let evicted = self
+            .storage
+            .enforce_budget(watermark, storage.eviction_policy)?;
+        self.storage.compact()?;
+        if evicted > 0 {
+            let now = self.now();
+            self.record(now, || InputEvent::StorageBudget);
+            log::info!(
+                "Evicted {} actions to keep storage under {} bytes",
+                evicted,
+                watermark
+            );
+        }
+        Ok(evicted)
+    }
+
+    /// Re-seals one batch of records still sealed with a previous key.
+    ///
+    /// Runs while the node was opened with previous keys in
+    /// [`StorageConfig::encryption`](crate::StorageConfig::encryption). Each batch
+    /// stays within a quarter of `memory_limit`; once every record is sealed
+    /// with the active key, the previous keys are retired and are no longer
+    /// needed to open the store. Returns `true` when no rotation is pending.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a record cannot be read or re-sealed.
+    pub fn step_key_rotation(&mut self) -> Result<bool> {
+        let Some(rotation) = &mut self.key_rotation else {
+            return Ok(true);
+        };
+        if !rotation.step(&self.storage)? {
+            return Ok(false);
+        }
+        log::info!(
+            "Storage key rotation finished, {} values re-sealed",
+            rotation.resealed()
+        );
+        self.key_rotation = None;
+        Ok(true)
+    }
+
+    /// Returns a handle to the latest published health snapshot.
+    ///
+    /// Reading the handle never touches the node, so it can be served from
+    /// another thread (e.g. by the HTTP `MetricsServer`) without blocking
+    /// the main loop.
+    pub fn health_handle(&self) -> HealthHandle {
+        self.health.clone()
+    }
+
+    /// Handles a message received from a peer, returning the reply to send
+    /// back, if any.
+    ///
+    /// Hosts that run their own receive loop pass each message here:
+    /// `Ping` is answered with a `Pong`, `GossipRequest` with the records the
+    /// peer is missing, and records in a `GossipResponse` or `RecordData` are
+    /// verified and stored. Other messages are ignored. The message is
+    /// recorded when recording is enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the peer is banned or sent an oversized batch, or
+    /// if received records cannot be stored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use aingle_minimal::{Config, MinimalNode};
+    /// # use aingle_minimal::network::Message;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut node = MinimalNode::new(Config::test_mode())?;
+    /// let reply = node.handle_message("192.168.1.20:5683".parse()?, Message::ping("peer"))?;
+    /// assert!(matches!(reply, Some(Message::Pong { .. })));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn handle_message(
+        &mut self,
+        from: SocketAddr,
+        message: Message,
+    ) -> Result<Option<Message>> {
+        let now = self.now();
+        self.record(now, || InputEvent::Message {
+            from,
+            message: message.clone(),
+        });
+        match message {
+            Message::Ping { .. } => Ok(Some(Message::pong(
+                self.keypair.public_key().to_hex(),
+                self.storage.get_latest_seq()?,
+            ))),
+            Message::GossipRequest { from_seq, limit } => Ok(self
+                .sync
+                .handle_gossip_request(&from, from_seq, limit, &self.storage)
+                .map(|records| Message::GossipResponse { records })),
+            Message::GossipResponse { records } => {
+                self.receive_records(&from, records)?;
+                Ok(None)
+            }
+            Message::RecordData { record } => {
+                self.receive_records(&from, vec![record])?;
+                Ok(None)
+            }
+            other => {
+                log::debug!("Ignoring message from {}: {:?}", from, other);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Verifies and stores records sent by a peer
+    fn receive_records(&mut self, from: &SocketAddr, records: Vec<Record>) -> Result<()> {
+        let stored =
+            self.sync
+                .handle_gossip_response(from, records, &self.storage, &mut self.gossip)?;
+        log::debug!("Stored {} records from {}", stored, from);
+        Ok(())
+    }
+
+    /// The node's clock: the replay clock during a replay, otherwise the
+    /// system clock
+    fn now(&self) -> Timestamp {
+        self.clock
+            .as_ref()
+            .map_or_else(Timestamp::now, VirtualClock::now)
+    }
+
+    /// Appends an input to the recording, if enabled
+    ///
+    /// A failed write is logged rather than failing the input, so a full or
+    /// broken disk does not stop the node.
+    fn record(&mut self, at: Timestamp, input: impl FnOnce() -> InputEvent) {
+        if let Some(recorder) = &mut self.recorder {
+            if let Err(e) = recorder.record(at, input()) {
+                log::warn!("Failed to record input: {}", e);
+            }
+        }
+    }
+}
+
+/// Loads the node identity from the configured keystore, or creates an
+/// ephemeral one when no keystore is configured
+fn load_identity(config: &Config) -> Result<Keypair> {
+    let Some(path) = &config.keystore_path else {
+        return Ok(Keypair::generate());
+    };
+
+    let passphrase = std::env::var(&config.keystore_passphrase_env).map_err(|_| {
+        ConfigError::Invalid(format!(
+            "keystore passphrase variable {} is not set",
+            config.keystore_passphrase_env
+        ))
+    })?;
+
+    let existed = std::path::Path::new(path).exists();
+    let keypair = Keypair::load_or_create(path, &passphrase)?;
+    if existed {
+        log::info!("Loaded node identity from {}", path);
+    } else {
+        log::info!("Created new node identity in {}", path);
+    }
+    Ok(keypair)
+}
+
+/// Current Unix time in seconds
+fn unix_now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
 }
 
 #[cfg(test)]
@@ -966,6 +1596,92 @@ mod tests {
         assert!(hash.is_ok());
     }
 
+    #[test]
+    fn test_identity_stable_with_keystore() {
+        let path = std::env::temp_dir().join(format!(
+            "aingle_node_identity_{}_{}.key",
+            std::process::id(),
+            hex::encode(crate::crypto::random_bytes::<4>())
+        ));
+        // A variable only this test sets, so parallel tests don't interfere
+        let passphrase_env = "AINGLE_TEST_NODE_IDENTITY_PASSPHRASE";
+        std::env::set_var(passphrase_env, "node identity test");
+
+        let mut config = Config::test_mode();
+        config.keystore_path = Some(path.to_string_lossy().to_string());
+        config.keystore_passphrase_env = passphrase_env.to_string();
+
+        let first = MinimalNode::new(config.clone()).unwrap().public_key();
+        let second = MinimalNode::new(config.clone()).unwrap().public_key();
+        assert_eq!(first, second);
+
+        config.keystore_passphrase_env = "AINGLE_TEST_UNSET_PASSPHRASE".to_string();
+        assert!(MinimalNode::new(config).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_enforce_storage_budget() {
+        let mut config = Config::test_mode();
+        config.storage.max_size = 256 * 1024;
+        let mut node = MinimalNode::new(config).unwrap();
+
+        let padding = "x".repeat(2048);
+        for i in 0..200 {
+            node.create_entry(serde_json::json!({ "i": i, "padding": padding }))
+                .unwrap();
+        }
+
+        let evicted = node.enforce_storage_budget().unwrap();
+        assert!(evicted > 0);
+        let watermark = (256.0 * 1024.0 * 0.9) as usize;
+        assert!(node.storage.stats().unwrap().db_size <= watermark);
+    }
+
+    #[test]
+    fn test_storage_budget_evicts_by_priority() {
+        let mut node = MinimalNode::new(Config::test_mode()).unwrap();
+
+        let padding = "x".repeat(3072);
+        let mut created = Vec::new();
+        for i in 0..150 {
+            let priority = match i % 3 {
+                0 => EntryPriority::Bulk,
+                1 => EntryPriority::Critical,
+                _ => EntryPriority::Normal,
+            };
+            let hash = node
+                .create_entry_with_priority(
+                    serde_json::json!({ "i": i, "padding": padding }),
+                    priority,
+                )
+                .unwrap();
+            created.push((priority, hash));
+        }
+        let before = node.storage_stats().unwrap();
+        assert_eq!(before.by_priority.critical, 50);
+        assert_eq!(before.by_priority.bulk, 50);
+
+        // Put the watermark at half the current size: all Bulk records and
+        // some Normal ones have to go
+        node.config.storage.max_size = (before.db_size as f64 / 0.9 / 2.0) as usize;
+        let evicted = node.enforce_storage_budget().unwrap();
+        assert!(evicted > 50 && evicted < 100, "{} evicted", evicted);
+
+        let after = node.storage_stats().unwrap().by_priority;
+        assert_eq!(after.bulk, 0);
+        assert_eq!(after.critical, 50);
+        assert_eq!(after.normal, 50 - (evicted as u64 - 50));
+        for (priority, hash) in &created {
+            let retained = node.storage.get_action(hash).unwrap().is_some();
+            match priority {
+                EntryPriority::Bulk => assert!(!retained),
+                EntryPriority::Critical => assert!(retained),
+                EntryPriority::Normal => {}
+            }
+        }
+    }
+
     #[test]
     fn test_node_stats() {
         let config = Config::test_mode();
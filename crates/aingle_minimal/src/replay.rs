@@ -0,0 +1,577 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Recording and deterministic replay of node inputs.
+//!
+//! Field bugs are hard to reproduce once the sensor readings, gossip and
+//! timer ticks that caused them are gone. With
+//! [`Config::recording`](crate::Config::recording) set, a [`Recorder`]
+//! appends every input the node acts on to a bounded log on disk:
+//!
+//! - entries created through [`MinimalNode::create_entry`],
+//!   [`MinimalNode::create_entries_batch`] and
+//!   [`MinimalNode::publish_sensor_data`], with their serialized content
+//! - messages handed to [`MinimalNode::handle_message`]
+//! - battery readings passed to [`MinimalNode::update_battery`]
+//! - graph expiry sweeps and storage budget checks that changed something
+//!
+//! [`replay`] builds a node with a [`VirtualClock`] set to each event's
+//! recorded time and feeds the events back in order, from the command line
+//! with `aingle-minimal replay --log <file>`.
+//!
+//! # Determinism
+//!
+//! A replay produces the same entry and action hashes and the same storage
+//! state as the recorded session when:
+//!
+//! - the replaying node uses the recording node's keystore
+//! - the log still holds the whole session ([`Recording::wrapped`] is `false`)
+//! - the session started from empty storage ([`RecordingHeader::start_seq`]
+//!   is 0), since replays run on in-memory storage
+//!
+//! Peer penalties decay with the monotonic clock and are not replayed.
+//!
+//! # Log format
+//!
+//! The log is JSON lines in two segments, the current one at the configured
+//! path and the previous one next to it with a `.prev` suffix. Each segment
+//! starts with a [`RecordingHeader`]. When the current segment would grow
+//! past half of `max_bytes` it replaces the previous one, so the log never
+//! takes much more than `max_bytes` and the oldest events are dropped first.
+//!
+//! The log holds the node's public key and the seed of its random choices,
+//! never its private key. Recording requires a keystore, so the identity
+//! cannot be recovered from the log.
+
+use crate::config::{Config, ConfigError, DiscoveryConfig, RecordingConfig, TransportConfig};
+use crate::error::{Error, Result};
+use crate::graph::GraphClock;
+use crate::network::Message;
+use crate::node::MinimalNode;
+use crate::power::BatteryInfo;
+use crate::storage_trait::EntryPriority;
+use crate::types::{Entry, EntryType, Hash, Timestamp};
+use crate::StorageBackendType;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Version of the log format written by [`Recorder`]
+pub const RECORDING_VERSION: u32 = 1;
+
+/// The first line of each segment of a recording log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordingHeader {
+    /// Log format version
+    pub version: u32,
+    /// When the recorded session started; the same in every segment of a session
+    pub session: Timestamp,
+    /// Seed of the node's random choices; not used for the node's identity
+    pub seed: u64,
+    /// Public key of the recording node, in hex
+    pub node_key: String,
+    /// The node's latest sequence number when the session started
+    pub start_seq: u32,
+    /// Index within the session of the first event in this segment
+    pub first_event: u64,
+}
+
+/// An input the node acted on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputEvent {
+    /// An application entry, with its serialized content
+    Entry {
+        content: String,
+        priority: EntryPriority,
+    },
+    /// Application entries created in one batch
+    Batch { contents: Vec<String> },
+    /// A message received from a peer
+    Message { from: SocketAddr, message: Message },
+    /// A battery reading reported by the host
+    Battery { battery: BatteryInfo },
+    /// A graph expiry sweep that removed triples
+    ExpireGraph,
+    /// A storage budget check that evicted records
+    StorageBudget,
+}
+
+/// An input and the time the node acted on it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    /// The node's clock when the input arrived
+    pub at: Timestamp,
+    /// The input
+    pub input: InputEvent,
+}
+
+/// One line of a recording log
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum LogLine {
+    Header(RecordingHeader),
+    Event(RecordedEvent),
+}
+
+/// Appends node inputs to a two-segment ring log.
+///
+/// Created by the node when [`RecordingConfig::path`] is set. Writes are not
+/// synced to disk, to spare flash; a crash may lose the last few events.
+pub struct Recorder {
+    path: PathBuf,
+    segment_bytes: u64,
+    header: RecordingHeader,
+    file: File,
+    /// Bytes written to the current segment
+    written: u64,
+    /// Events recorded in the session
+    events: u64,
+}
+
+impl Recorder {
+    /// Starts recording a session to `config.path`.
+    ///
+    /// A log left by an earlier session is moved to the `.prev` segment,
+    /// where it stays until the new session fills its first segment.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if recording is disabled in `config` or the log
+    /// cannot be written.
+    pub fn open(config: &RecordingConfig, header: RecordingHeader) -> Result<Self> {
+        let path = PathBuf::from(config.path.as_ref().ok_or_else(|| {
+            Error::Config(ConfigError::Invalid(
+                "recording.path is not set".to_string(),
+            ))
+        })?);
+        if path.exists() {
+            std::fs::rename(&path, prev_path(&path))?;
+        }
+        let mut recorder = Self {
+            file: File::create(&path)?,
+            path,
+            segment_bytes: (config.max_bytes / 2) as u64,
+            header,
+            written: 0,
+            events: 0,
+        };
+        recorder.write_header()?;
+        Ok(recorder)
+    }
+
+    /// The header of the current segment
+    pub fn header(&self) -> &RecordingHeader {
+        &self.header
+    }
+
+    /// Appends an input to the log, starting a new segment if the current
+    /// one is full.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the event cannot be serialized or written.
+    pub fn record(&mut self, at: Timestamp, input: InputEvent) -> Result<()> {
+        let line = to_line(&LogLine::Event(RecordedEvent { at, input }))?;
+        let segment_has_events = self.events > self.header.first_event;
+        if segment_has_events && self.written + line.len() as u64 > self.segment_bytes {
+            self.rotate()?;
+        }
+        self.file.write_all(line.as_bytes())?;
+        self.written += line.len() as u64;
+        self.events += 1;
+        Ok(())
+    }
+
+    /// Replaces the previous segment with the current one
+    fn rotate(&mut self) -> Result<()> {
+        std::fs::rename(&self.path, prev_path(&self.path))?;
+        self.file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&self.path)?;
+        self.header.first_event = self.events;
+        self.written = 0;
+        self.write_header()
+    }
+
+    fn write_header(&mut self) -> Result<()> {
+        let line = to_line(&LogLine::Header(self.header.clone()))?;
+        self.file.write_all(line.as_bytes())?;
+        self.written += line.len() as u64;
+        Ok(())
+    }
+}
+
+/// The events of the latest session in a recording log.
+#[derive(Debug, Clone)]
+pub struct Recording {
+    /// The header of the oldest retained segment
+    pub header: RecordingHeader,
+    /// The retained events, oldest first
+    pub events: Vec<RecordedEvent>,
+    /// `true` if the oldest events of the session were dropped
+    pub wrapped: bool,
+}
+
+impl Recording {
+    /// Reads the latest session from the log at `path`.
+    ///
+    /// The `.prev` segment is included when it belongs to the same session.
+    /// A torn last line, as left by a crash mid-write, is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the log cannot be read or is malformed.
+    pub fn read(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let (mut header, mut events) = read_segment(path)?;
+        let prev = prev_path(path);
+        if prev.exists() {
+            let (prev_header, mut prev_events) = read_segment(&prev)?;
+            if prev_header.session == header.session {
+                prev_events.append(&mut events);
+                events = prev_events;
+                header = prev_header;
+            }
+        }
+        Ok(Self {
+            wrapped: header.first_event > 0,
+            header,
+            events,
+        })
+    }
+}
+
+/// A clock moved by hand, used as the node's clock during replay.
+///
+/// Holds a [`Timestamp`] in microseconds; clones share the same time.
+#[derive(Debug, Clone)]
+pub struct VirtualClock(Arc<AtomicU64>);
+
+impl VirtualClock {
+    /// Creates a clock showing `now`.
+    pub fn new(now: Timestamp) -> Self {
+        Self(Arc::new(AtomicU64::new(now.0)))
+    }
+
+    /// Moves the clock to `now`.
+    pub fn set(&self, now: Timestamp) {
+        self.0.store(now.0, Ordering::SeqCst);
+    }
+
+    /// The time the clock shows.
+    pub fn now(&self) -> Timestamp {
+        Timestamp(self.0.load(Ordering::SeqCst))
+    }
+}
+
+impl GraphClock for VirtualClock {
+    fn now_secs(&self) -> u64 {
+        self.now().0 / 1_000_000
+    }
+}
+
+/// The result of a [`replay`].
+pub struct Replay {
+    /// The replayed node, for inspecting its storage and state
+    pub node: MinimalNode,
+    /// Action hashes of the entries created, in order
+    pub hashes: Vec<Hash>,
+    /// Number of events replayed
+    pub events: usize,
+    /// Number of events the node returned an error for
+    pub errors: usize,
+}
+
+/// Replays a recording on a fresh node built from `config`.
+///
+/// `config` should be the recording node's configuration. The replaying
+/// node uses in-memory storage and the in-memory transport, so nothing is
+/// written to the recording node's database or sent to peers, and it records
+/// nothing. Events the node fails on are counted and skipped, as they were
+/// when recorded.
+///
+/// # Errors
+///
+/// Returns an error if the node cannot be created, or if its identity is not
+/// the recording node's.
+pub fn replay(recording: &Recording, mut config: Config) -> Result<Replay> {
+    config.recording = RecordingConfig::default();
+    config.storage.backend = StorageBackendType::Memory;
+    config.transport = TransportConfig::Memory;
+    config.enable_mdns = false;
+    config.discovery = DiscoveryConfig::default();
+
+    let header = &recording.header;
+    let clock = VirtualClock::new(header.session);
+    let mut node = MinimalNode::replaying(config, header.seed, clock.clone())?;
+    let node_key = node.public_key().to_hex();
+    if node_key != header.node_key {
+        return Err(Error::Config(ConfigError::Invalid(format!(
+            "recording was made by node {}, not {}; replay with its keystore",
+            header.node_key, node_key
+        ))));
+    }
+    if recording.wrapped {
+        log::warn!(
+            "Recording dropped its first {} events; hashes will differ from the session's",
+            header.first_event
+        );
+    }
+    if header.start_seq != 0 {
+        log::warn!(
+            "Recording started at seq {} but replay starts from empty storage",
+            header.start_seq
+        );
+    }
+
+    let mut hashes = Vec::new();
+    let mut errors = 0;
+    for event in &recording.events {
+        clock.set(event.at);
+        let result = match event.input.clone() {
+            InputEvent::Entry { content, priority } => node
+                .create_app_entry(app_entry(content), priority)
+                .map(|hash| hashes.push(hash)),
+            InputEvent::Batch { contents } => node
+                .create_app_entries(contents.into_iter().map(app_entry).collect())
+                .map(|batch| hashes.extend(batch)),
+            InputEvent::Message { from, message } => node.handle_message(from, message).map(drop),
+            InputEvent::Battery { battery } => {
+                node.update_battery(battery);
+                Ok(())
+            }
+            InputEvent::ExpireGraph => node.expire_graph().map(drop),
+            InputEvent::StorageBudget => node.enforce_storage_budget().map(drop),
+        };
+        if let Err(e) = result {
+            log::debug!("Replayed event at {} failed: {}", event.at.0, e);
+            errors += 1;
+        }
+    }
+
+    Ok(Replay {
+        node,
+        hashes,
+        events: recording.events.len(),
+        errors,
+    })
+}
+
+/// An application entry with already serialized content
+fn app_entry(content: String) -> Entry {
+    Entry {
+        entry_type: EntryType::App,
+        content: content.into_bytes(),
+    }
+}
+
+/// The path of the previous segment of the log at `path`
+fn prev_path(path: &Path) -> PathBuf {
+    let mut prev = path.as_os_str().to_owned();
+    prev.push(".prev");
+    PathBuf::from(prev)
+}
+
+fn to_line(line: &LogLine) -> Result<String> {
+    let mut json = serde_json::to_string(line).map_err(|e| Error::Serialization(e.to_string()))?;
+    json.push('\n');
+    Ok(json)
+}
+
+/// Reads the header and events of one segment
+fn read_segment(path: &Path) -> Result<(RecordingHeader, Vec<RecordedEvent>)> {
+    let mut lines = BufReader::new(File::open(path)?).lines().peekable();
+    let header = match lines
+        .next()
+        .transpose()?
+        .as_deref()
+        .map(serde_json::from_str)
+    {
+        Some(Ok(LogLine::Header(header))) => header,
+        _ => {
+            return Err(Error::Serialization(format!(
+                "recording log {} has no header",
+                path.display()
+            )))
+        }
+    };
+    if header.version != RECORDING_VERSION {
+        return Err(Error::Serialization(format!(
+            "recording log {} has version {}, expected {}",
+            path.display(),
+            header.version,
+            RECORDING_VERSION
+        )));
+    }
+
+    let mut events = Vec::new();
+    while let Some(line) = lines.next() {
+        match serde_json::from_str(&line?) {
+            Ok(LogLine::Event(event)) => events.push(event),
+            Ok(LogLine::Header(_)) | Err(_) if lines.peek().is_none() => break,
+            Ok(LogLine::Header(_)) => {
+                return Err(Error::Serialization(format!(
+                    "recording log {} has a header after its first line",
+                    path.display()
+                )))
+            }
+            Err(e) => return Err(Error::Serialization(e.to_string())),
+        }
+    }
+    Ok((header, events))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sensors::{SensorReading, SensorType};
+
+    fn log_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "aingle_replay_{}_{}_{}.log",
+            name,
+            std::process::id(),
+            hex::encode(crate::crypto::random_bytes::<4>())
+        ))
+    }
+
+    fn remove_log(path: &Path) {
+        let _ = std::fs::remove_file(path);
+        let _ = std::fs::remove_file(prev_path(path));
+        let _ = std::fs::remove_file(path.with_extension("key"));
+    }
+
+    /// A node with its own keystore next to the log at `path`
+    fn keystore_config(path: &Path) -> Config {
+        // A variable only these tests set, so parallel tests don't interfere
+        let passphrase_env = "AINGLE_TEST_REPLAY_PASSPHRASE";
+        std::env::set_var(passphrase_env, "replay test");
+
+        let mut config = Config::test_mode();
+        config.keystore_path = Some(path.with_extension("key").to_string_lossy().to_string());
+        config.keystore_passphrase_env = passphrase_env.to_string();
+        config
+    }
+
+    fn recording_config(path: &Path) -> Config {
+        let mut config = keystore_config(path);
+        config.recording = RecordingConfig::at(path.to_string_lossy());
+        config
+    }
+
+    #[test]
+    fn test_replay_reproduces_session() {
+        let path = log_path("session");
+        let mut node = MinimalNode::new(recording_config(&path)).unwrap();
+        let peer_addr: SocketAddr = "192.168.1.20:5683".parse().unwrap();
+
+        let mut hashes = vec![node.create_entry("boot").unwrap()];
+        let reading = SensorReading::new(SensorType::Temperature, 23.5, "C".to_string())
+            .with_metadata("room".to_string(), "lab".to_string());
+        hashes.push(
+            node.publish_sensor_data_with_priority(&reading, EntryPriority::Bulk)
+                .unwrap(),
+        );
+        node.update_battery(BatteryInfo::with_level(41.0));
+        hashes.extend(
+            node.create_entries_batch(&[serde_json::json!({"i": 1}), serde_json::json!({"i": 2})])
+                .unwrap(),
+        );
+
+        // Records gossiped by a peer, then the peer asking for ours
+        let mut peer = MinimalNode::new(Config::test_mode()).unwrap();
+        peer.create_entry("from peer").unwrap();
+        let records = peer.records_since(0, 10).unwrap();
+        node.handle_message(peer_addr, Message::GossipResponse { records })
+            .unwrap();
+        let reply = node
+            .handle_message(
+                peer_addr,
+                Message::GossipRequest {
+                    from_seq: 0,
+                    limit: 10,
+                },
+            )
+            .unwrap();
+        assert!(matches!(reply, Some(Message::GossipResponse { .. })));
+
+        let recording = Recording::read(&path).unwrap();
+        assert!(!recording.wrapped);
+        assert_eq!(recording.events.len(), 6);
+        assert_eq!(recording.header.node_key, node.public_key().to_hex());
+
+        let replayed = replay(&recording, keystore_config(&path)).unwrap();
+        assert_eq!(replayed.events, 6);
+        assert_eq!(replayed.errors, 0);
+        assert_eq!(replayed.hashes, hashes);
+        assert_eq!(replayed.node.public_key(), node.public_key());
+        assert_eq!(
+            serde_json::to_value(replayed.node.storage_stats().unwrap()).unwrap(),
+            serde_json::to_value(node.storage_stats().unwrap()).unwrap()
+        );
+        let action_hashes = |node: &MinimalNode| -> Vec<Hash> {
+            node.records_since(0, 100)
+                .unwrap()
+                .iter()
+                .map(|record| record.action.hash())
+                .collect()
+        };
+        assert_eq!(action_hashes(&replayed.node), action_hashes(&node));
+        remove_log(&path);
+    }
+
+    #[test]
+    fn test_ring_log_drops_oldest_events() {
+        let path = log_path("ring");
+        let mut config = recording_config(&path);
+        config.recording.max_bytes = 4096;
+        let mut node = MinimalNode::new(config).unwrap();
+        for i in 0..200 {
+            node.create_entry(serde_json::json!({ "i": i })).unwrap();
+        }
+
+        let size = |path: &Path| std::fs::metadata(path).unwrap().len();
+        assert!(size(&path) + size(&prev_path(&path)) <= 4096);
+        let recording = Recording::read(&path).unwrap();
+        assert!(recording.wrapped);
+        let retained = recording.events.len() as u64;
+        assert_eq!(recording.header.first_event + retained, 200);
+
+        // A new session ignores the segment left by the previous one
+        drop(node);
+        let node = MinimalNode::new(recording_config(&path)).unwrap();
+        let recording = Recording::read(&path).unwrap();
+        assert!(recording.events.is_empty());
+        assert!(!recording.wrapped);
+        drop(node);
+        remove_log(&path);
+    }
+
+    #[test]
+    fn test_replay_rejects_other_identity() {
+        let path = log_path("identity");
+        let mut node = MinimalNode::new(recording_config(&path)).unwrap();
+        node.create_entry("x").unwrap();
+
+        // The seed alone does not give the recorded identity
+        let recording = Recording::read(&path).unwrap();
+        assert!(replay(&recording, Config::test_mode()).is_err());
+        remove_log(&path);
+    }
+
+    #[test]
+    fn test_recording_requires_keystore() {
+        let path = log_path("no_keystore");
+        let mut config = Config::test_mode();
+        config.recording = RecordingConfig::at(path.to_string_lossy());
+        assert!(MinimalNode::new(config).is_err());
+        assert!(!path.exists());
+    }
+}
@@ -10,9 +10,13 @@
 
 use crate::config::StorageConfig;
 use crate::error::{Error, Result};
-use crate::storage_trait::{StorageBackend, StorageStats};
+use crate::storage_crypto::{self, KeyMetadata, RecordCipher};
+use crate::storage_trait::{
+    EntryPriority, EvictionPolicy, PriorityCounts, StorageBackend, StorageStats, DEFAULT_PRIORITY,
+};
 use crate::types::{Action, Entry, Hash, Link, Record, Timestamp};
-use rocksdb::{ColumnFamily, ColumnFamilyDescriptor, Options, DB};
+use rocksdb::{ColumnFamily, ColumnFamilyDescriptor, Options, WriteBatch, DB};
+use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
 
@@ -22,15 +26,25 @@ const CF_ENTRIES: &str = "entries";
 const CF_LINKS: &str = "links";
 const CF_METADATA: &str = "metadata";
 const CF_SEQUENCES: &str = "sequences";
+const CF_PRIORITIES: &str = "priorities";
+
+/// Metadata key holding the number of records deleted by expiry
+const EXPIRED_COUNT_KEY: &str = "expired_count";
 
 /// RocksDB-backed storage for high-performance nodes
 pub struct RocksStorage {
     db: Arc<DB>,
     config: StorageConfig,
+    /// Seals values when the store is encrypted
+    cipher: Option<RecordCipher>,
 }
 
 impl RocksStorage {
     /// Open or create RocksDB storage
+    ///
+    /// Fails with [`StorageError::WrongEncryptionKey`](crate::StorageError::WrongEncryptionKey)
+    /// or [`StorageError::EncryptionMismatch`](crate::StorageError::EncryptionMismatch)
+    /// if [`StorageConfig::encryption`] does not match the store.
     pub fn open(config: StorageConfig) -> Result<Self> {
         let path = Path::new(&config.db_path);
 
@@ -60,16 +74,20 @@ impl RocksStorage {
             ColumnFamilyDescriptor::new(CF_LINKS, Options::default()),
             ColumnFamilyDescriptor::new(CF_METADATA, Options::default()),
             ColumnFamilyDescriptor::new(CF_SEQUENCES, Options::default()),
+            ColumnFamilyDescriptor::new(CF_PRIORITIES, Options::default()),
         ];
 
         // Open database with column families
         let db = DB::open_cf_descriptors(&opts, path, cf_descriptors)
             .map_err(|e| Error::storage(e.to_string()))?;
 
-        Ok(Self {
+        let mut storage = Self {
             db: Arc::new(db),
             config,
-        })
+            cipher: None,
+        };
+        storage.cipher = RecordCipher::for_store(storage.config.encryption.as_ref(), &storage)?;
+        Ok(storage)
     }
 
     /// Open in-memory storage (for testing)
@@ -153,6 +171,45 @@ impl RocksStorage {
         Ok(next)
     }
 
+    /// Bytes held by keys and values of actions, entries and links
+    fn live_size(&self) -> Result<usize> {
+        let mut size = 0;
+        for name in [CF_ACTIONS, CF_ENTRIES, CF_LINKS] {
+            let iter = self
+                .db
+                .iterator_cf(self.cf(name)?, rocksdb::IteratorMode::Start);
+            for item in iter {
+                let (key, value) = item.map_err(|e| Error::storage(e.to_string()))?;
+                size += key.len() + value.len();
+            }
+        }
+        Ok(size)
+    }
+
+    /// Seal a value stored under `key` in `cf`, if the store is encrypted
+    fn seal(&self, cf: &str, key: &[u8], value: Vec<u8>) -> Result<Vec<u8>> {
+        match &self.cipher {
+            Some(cipher) => cipher.seal(cf, key, &value),
+            None => Ok(value),
+        }
+    }
+
+    /// Open a value sealed by [`seal`](Self::seal)
+    fn open_sealed(&self, cf: &str, key: &[u8], value: &[u8]) -> Result<Vec<u8>> {
+        match &self.cipher {
+            Some(cipher) => cipher.open(cf, key, value),
+            None => Ok(value.to_vec()),
+        }
+    }
+
+    /// The cipher for a metadata value, unless the key is kept in the clear
+    fn metadata_cipher(&self, key: &str) -> Option<&RecordCipher> {
+        if key == EXPIRED_COUNT_KEY || storage_crypto::is_key_metadata(key) {
+            return None;
+        }
+        self.cipher.as_ref()
+    }
+
     /// Prune old data if needed
     fn maybe_prune(&self) -> Result<()> {
         if !self.config.aggressive_pruning {
@@ -170,6 +227,7 @@ impl StorageBackend for RocksStorage {
         let value = serde_json::to_vec(action)?;
         let hash = Hash::from_bytes(&value);
         let key = Self::action_key(&hash);
+        let value = self.seal(CF_ACTIONS, &key, value)?;
 
         self.db
             .put_cf(self.cf(CF_ACTIONS)?, &key, &value)
@@ -188,7 +246,7 @@ impl StorageBackend for RocksStorage {
     fn put_entry(&self, entry: &Entry) -> Result<Hash> {
         let hash = entry.hash();
         let key = Self::entry_key(&hash);
-        let value = serde_json::to_vec(entry)?;
+        let value = self.seal(CF_ENTRIES, &key, serde_json::to_vec(entry)?)?;
 
         self.db
             .put_cf(self.cf(CF_ENTRIES)?, &key, &value)
@@ -205,6 +263,60 @@ impl StorageBackend for RocksStorage {
         self.put_action(&record.action)
     }
 
+    fn put_record_with_priority(&self, record: &Record, priority: Option<u8>) -> Result<Hash> {
+        let hash = self.put_record(record)?;
+        if let Some(priority) = priority {
+            self.db
+                .put_cf(self.cf(CF_PRIORITIES)?, Self::action_key(&hash), [priority])
+                .map_err(|e| Error::storage(e.to_string()))?;
+        }
+        Ok(hash)
+    }
+
+    fn expire_records_batch(&self, hashes: &[Hash]) -> Result<usize> {
+        if hashes.is_empty() {
+            return Ok(0);
+        }
+
+        let mut batch = WriteBatch::default();
+        let mut removed = 0;
+        for hash in hashes {
+            batch.delete_cf(self.cf(CF_PRIORITIES)?, Self::action_key(hash));
+            for (cf, key) in [
+                (self.cf(CF_ACTIONS)?, Self::action_key(hash)),
+                (self.cf(CF_ENTRIES)?, Self::entry_key(hash)),
+            ] {
+                let exists = self
+                    .db
+                    .get_pinned_cf(cf, &key)
+                    .map_err(|e| Error::storage(e.to_string()))?
+                    .is_some();
+                if exists {
+                    batch.delete_cf(cf, &key);
+                    removed += 1;
+                }
+            }
+        }
+
+        if removed > 0 {
+            let total = self
+                .get_metadata(EXPIRED_COUNT_KEY)?
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(0)
+                + removed as u64;
+            batch.put_cf(
+                self.cf(CF_METADATA)?,
+                EXPIRED_COUNT_KEY.as_bytes(),
+                total.to_string().as_bytes(),
+            );
+        }
+
+        self.db
+            .write(batch)
+            .map_err(|e| Error::storage(e.to_string()))?;
+        Ok(removed)
+    }
+
     fn get_action(&self, hash: &Hash) -> Result<Option<Action>> {
         let key = Self::action_key(hash);
 
@@ -213,7 +325,10 @@ impl StorageBackend for RocksStorage {
             .get_cf(self.cf(CF_ACTIONS)?, &key)
             .map_err(|e| Error::storage(e.to_string()))?
         {
-            Some(value) => Ok(Some(serde_json::from_slice(&value)?)),
+            Some(value) => {
+                let value = self.open_sealed(CF_ACTIONS, &key, &value)?;
+                Ok(Some(serde_json::from_slice(&value)?))
+            }
             None => Ok(None),
         }
     }
@@ -226,7 +341,10 @@ impl StorageBackend for RocksStorage {
             .get_cf(self.cf(CF_ENTRIES)?, &key)
             .map_err(|e| Error::storage(e.to_string()))?
         {
-            Some(value) => Ok(Some(serde_json::from_slice(&value)?)),
+            Some(value) => {
+                let value = self.open_sealed(CF_ENTRIES, &key, &value)?;
+                Ok(Some(serde_json::from_slice(&value)?))
+            }
             None => Ok(None),
         }
     }
@@ -266,7 +384,8 @@ impl StorageBackend for RocksStorage {
         let mut collected: Vec<Action> = Vec::new();
 
         for item in iter {
-            if let Ok((_, value)) = item {
+            if let Ok((key, value)) = item {
+                let value = self.open_sealed(CF_ACTIONS, &key, &value)?;
                 if let Ok(action) = serde_json::from_slice::<Action>(&value) {
                     if action.seq >= from_seq && action.seq < to_seq {
                         collected.push(action);
@@ -320,8 +439,9 @@ impl StorageBackend for RocksStorage {
             .db
             .iterator_cf(self.cf(CF_LINKS)?, rocksdb::IteratorMode::Start);
         for item in iter {
-            if let Ok((_, value)) = item {
+            if let Ok((key, value)) = item {
                 // Check if link is not deleted
+                let value = self.open_sealed(CF_LINKS, &key, &value)?;
                 if let Ok(link_data) = serde_json::from_slice::<LinkData>(&value) {
                     if !link_data.deleted {
                         link_count += 1;
@@ -330,16 +450,34 @@ impl StorageBackend for RocksStorage {
             }
         }
 
-        // Estimate database size (RocksDB doesn't expose this directly in simple API)
-        let db_size = std::fs::metadata(&self.config.db_path)
-            .map(|m| m.len() as usize)
+        // RocksDB doesn't expose its on-disk size cheaply; count live keys and values
+        let db_size = self.live_size()?;
+
+        let expired_count = self
+            .get_metadata(EXPIRED_COUNT_KEY)?
+            .and_then(|v| v.parse().ok())
             .unwrap_or(0);
 
+        // Actions stored without a priority have the default (Normal) one
+        let mut by_priority = PriorityCounts::default();
+        let iter = self
+            .db
+            .iterator_cf(self.cf(CF_PRIORITIES)?, rocksdb::IteratorMode::Start);
+        for item in iter {
+            let (_, value) = item.map_err(|e| Error::storage(e.to_string()))?;
+            let priority = value.first().copied().unwrap_or(DEFAULT_PRIORITY);
+            by_priority.add(EntryPriority::from_eviction_priority(priority), 1);
+        }
+        let unprioritized = action_count.saturating_sub(by_priority.total());
+        by_priority.add(EntryPriority::Normal, unprioritized);
+
         Ok(StorageStats {
             action_count,
             entry_count,
             link_count,
             db_size,
+            expired_count,
+            by_priority,
         })
     }
 
@@ -357,7 +495,7 @@ impl StorageBackend for RocksStorage {
             deleted: false,
         };
 
-        let value = serde_json::to_vec(&link_data)?;
+        let value = self.seal(CF_LINKS, &key, serde_json::to_vec(&link_data)?)?;
         self.db
             .put_cf(self.cf(CF_LINKS)?, &key, &value)
             .map_err(|e| Error::storage(e.to_string()))?;
@@ -373,10 +511,12 @@ impl StorageBackend for RocksStorage {
 
         for item in iter {
             if let Ok((key, value)) = item {
+                let value = self.open_sealed(CF_LINKS, &key, &value)?;
                 if let Ok(mut link_data) = serde_json::from_slice::<LinkData>(&value) {
                     if link_data.id == link_id {
                         link_data.deleted = true;
-                        let new_value = serde_json::to_vec(&link_data)?;
+                        let new_value =
+                            self.seal(CF_LINKS, &key, serde_json::to_vec(&link_data)?)?;
                         self.db
                             .put_cf(self.cf(CF_LINKS)?, &key, &new_value)
                             .map_err(|e| Error::storage(e.to_string()))?;
@@ -402,6 +542,7 @@ impl StorageBackend for RocksStorage {
                     break;
                 }
 
+                let value = self.open_sealed(CF_LINKS, &key, &value)?;
                 if let Ok(link_data) = serde_json::from_slice::<LinkData>(&value) {
                     if link_data.deleted {
                         continue;
@@ -428,20 +569,17 @@ impl StorageBackend for RocksStorage {
     }
 
     fn set_metadata(&self, key: &str, value: &str) -> Result<()> {
-        self.db
-            .put_cf(self.cf(CF_METADATA)?, key.as_bytes(), value.as_bytes())
-            .map_err(|e| Error::storage(e.to_string()))?;
-        Ok(())
+        match self.metadata_cipher(key) {
+            Some(cipher) => self.set_raw_metadata(key, &cipher.seal_text(CF_METADATA, key, value)?),
+            None => self.set_raw_metadata(key, value),
+        }
     }
 
     fn get_metadata(&self, key: &str) -> Result<Option<String>> {
-        match self
-            .db
-            .get_cf(self.cf(CF_METADATA)?, key.as_bytes())
-            .map_err(|e| Error::storage(e.to_string()))?
-        {
-            Some(value) => Ok(Some(String::from_utf8_lossy(&value).to_string())),
-            None => Ok(None),
+        let value = self.raw_metadata(key)?;
+        match (self.metadata_cipher(key), value) {
+            (Some(cipher), Some(value)) => Ok(Some(cipher.open_text(CF_METADATA, key, &value)?)),
+            (_, value) => Ok(value),
         }
     }
 
@@ -457,10 +595,219 @@ impl StorageBackend for RocksStorage {
         Ok(())
     }
 
+    fn compact(&self) -> Result<()> {
+        for name in [CF_ACTIONS, CF_ENTRIES, CF_LINKS, CF_PRIORITIES] {
+            self.db
+                .compact_range_cf(self.cf(name)?, None::<&[u8]>, None::<&[u8]>);
+        }
+        Ok(())
+    }
+
+    fn enforce_budget(&self, max_bytes: usize, policy: EvictionPolicy) -> Result<usize> {
+        let mut size = self.live_size()?;
+        if size <= max_bytes {
+            return Ok(0);
+        }
+
+        struct Candidate {
+            key: Box<[u8]>,
+            seq: u32,
+            priority: u8,
+            entry_key: Option<Vec<u8>>,
+            bytes: usize,
+        }
+
+        let actions = self.cf(CF_ACTIONS)?;
+        let entries = self.cf(CF_ENTRIES)?;
+        let priorities = self.cf(CF_PRIORITIES)?;
+
+        // Count references so an entry only goes with its last action
+        let mut entry_refs: HashMap<Vec<u8>, usize> = HashMap::new();
+        let mut candidates = Vec::new();
+        for item in self.db.iterator_cf(actions, rocksdb::IteratorMode::Start) {
+            let (key, value) = item.map_err(|e| Error::storage(e.to_string()))?;
+            let action: Action =
+                serde_json::from_slice(&self.open_sealed(CF_ACTIONS, &key, &value)?)?;
+            let priority = self
+                .db
+                .get_pinned_cf(priorities, &key)
+                .map_err(|e| Error::storage(e.to_string()))?
+                .and_then(|v| v.first().copied())
+                .unwrap_or(DEFAULT_PRIORITY);
+            let entry_key = action.entry_hash.as_ref().map(Self::entry_key);
+            if let Some(entry_key) = &entry_key {
+                *entry_refs.entry(entry_key.clone()).or_default() += 1;
+            }
+            candidates.push(Candidate {
+                bytes: key.len() + value.len(),
+                key,
+                seq: action.seq,
+                priority,
+                entry_key,
+            });
+        }
+
+        // Never evict the chain head
+        let head = candidates.iter().map(|c| c.seq).max().unwrap_or(0);
+        candidates.retain(|c| c.seq < head);
+        match policy {
+            EvictionPolicy::OldestFirst => candidates.sort_by_key(|c| {
+                (
+                    EntryPriority::from_eviction_priority(c.priority).eviction_rank(),
+                    c.seq,
+                )
+            }),
+            EvictionPolicy::LowestPriority => candidates.sort_by_key(|c| (c.priority, c.seq)),
+        }
+
+        let mut batch = WriteBatch::default();
+        let mut evicted = 0;
+        for candidate in candidates {
+            if size <= max_bytes {
+                break;
+            }
+            batch.delete_cf(actions, &candidate.key);
+            batch.delete_cf(priorities, &candidate.key);
+            size = size.saturating_sub(candidate.bytes);
+            evicted += 1;
+
+            let Some(entry_key) = candidate.entry_key else {
+                continue;
+            };
+            let refs = entry_refs.entry(entry_key.clone()).or_default();
+            *refs = refs.saturating_sub(1);
+            if *refs == 0 {
+                if let Some(value) = self
+                    .db
+                    .get_pinned_cf(entries, &entry_key)
+                    .map_err(|e| Error::storage(e.to_string()))?
+                {
+                    size = size.saturating_sub(entry_key.len() + value.len());
+                    batch.delete_cf(entries, &entry_key);
+                }
+            }
+        }
+
+        // Only the chain head is left; drop entries nothing references
+        if size > max_bytes {
+            for item in self.db.iterator_cf(entries, rocksdb::IteratorMode::Start) {
+                if size <= max_bytes {
+                    break;
+                }
+                let (key, value) = item.map_err(|e| Error::storage(e.to_string()))?;
+                if !entry_refs.contains_key(key.as_ref()) {
+                    size = size.saturating_sub(key.len() + value.len());
+                    batch.delete_cf(entries, &key);
+                }
+            }
+        }
+
+        self.db
+            .write(batch)
+            .map_err(|e| Error::storage(e.to_string()))?;
+        Ok(evicted)
+    }
+
     fn check_limits(&self) -> Result<bool> {
         let stats = self.stats()?;
         Ok(stats.db_size <= self.config.max_size)
     }
+
+    fn reencrypt_batch(&self, max_bytes: usize) -> Result<usize> {
+        let Some(cipher) = &self.cipher else {
+            return Ok(0);
+        };
+
+        // One row is always taken so a large value cannot stall a rotation
+        let mut budget = max_bytes;
+        let mut batch = WriteBatch::default();
+        let mut resealed = 0;
+        'scan: for name in [CF_ACTIONS, CF_ENTRIES, CF_LINKS] {
+            let cf = self.cf(name)?;
+            for item in self.db.iterator_cf(cf, rocksdb::IteratorMode::Start) {
+                if budget == 0 {
+                    break 'scan;
+                }
+                let (key, value) = item.map_err(|e| Error::storage(e.to_string()))?;
+                if cipher.is_current(&value) {
+                    continue;
+                }
+                let plaintext = cipher.open(name, &key, &value)?;
+                batch.put_cf(cf, &key, cipher.seal(name, &key, &plaintext)?);
+                budget = budget.saturating_sub(value.len());
+                resealed += 1;
+            }
+        }
+
+        let metadata = self.cf(CF_METADATA)?;
+        for item in self.db.iterator_cf(metadata, rocksdb::IteratorMode::Start) {
+            if budget == 0 {
+                break;
+            }
+            let (key, value) = item.map_err(|e| Error::storage(e.to_string()))?;
+            let key = String::from_utf8_lossy(&key);
+            let value = String::from_utf8_lossy(&value);
+            if self.metadata_cipher(&key).is_none() || cipher.is_current_text(&value) {
+                continue;
+            }
+            let plaintext = cipher.open_text(CF_METADATA, &key, &value)?;
+            batch.put_cf(
+                metadata,
+                key.as_bytes(),
+                cipher.seal_text(CF_METADATA, &key, &plaintext)?,
+            );
+            budget = budget.saturating_sub(value.len());
+            resealed += 1;
+        }
+
+        if resealed == 0 {
+            cipher.retire_previous_keys(self)?;
+            return Ok(0);
+        }
+        self.db
+            .write(batch)
+            .map_err(|e| Error::storage(e.to_string()))?;
+        Ok(resealed)
+    }
+}
+
+impl KeyMetadata for RocksStorage {
+    fn raw_metadata(&self, key: &str) -> Result<Option<String>> {
+        match self
+            .db
+            .get_cf(self.cf(CF_METADATA)?, key.as_bytes())
+            .map_err(|e| Error::storage(e.to_string()))?
+        {
+            Some(value) => Ok(Some(String::from_utf8_lossy(&value).to_string())),
+            None => Ok(None),
+        }
+    }
+
+    fn set_raw_metadata(&self, key: &str, value: &str) -> Result<()> {
+        self.db
+            .put_cf(self.cf(CF_METADATA)?, key.as_bytes(), value.as_bytes())
+            .map_err(|e| Error::storage(e.to_string()))?;
+        Ok(())
+    }
+
+    fn delete_raw_metadata(&self, key: &str) -> Result<()> {
+        self.db
+            .delete_cf(self.cf(CF_METADATA)?, key.as_bytes())
+            .map_err(|e| Error::storage(e.to_string()))?;
+        Ok(())
+    }
+
+    fn is_empty(&self) -> Result<bool> {
+        for name in [CF_ACTIONS, CF_ENTRIES, CF_LINKS, CF_METADATA] {
+            let mut iter = self
+                .db
+                .iterator_cf(self.cf(name)?, rocksdb::IteratorMode::Start);
+            if iter.next().is_some() {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
 }
 
 /// Internal link data structure for serialization
@@ -574,4 +921,45 @@ mod tests {
         let stats = use_backend(&storage).unwrap();
         assert_eq!(stats.action_count, 0);
     }
+
+    #[test]
+    fn test_rocks_enforce_budget() {
+        let storage = RocksStorage::memory().unwrap();
+        let mut records = Vec::new();
+        for seq in 1..=20u32 {
+            let mut content = seq.to_be_bytes().to_vec();
+            content.resize(2048, 0xAB);
+            let entry = Entry {
+                entry_type: EntryType::App,
+                content,
+            };
+            let mut action = create_test_action(seq);
+            action.entry_hash = Some(entry.hash());
+            let entry_hash = entry.hash();
+            let priority = if seq % 2 == 0 { 10 } else { 200 };
+            let hash = storage
+                .put_record_with_priority(
+                    &Record {
+                        action,
+                        entry: Some(entry),
+                    },
+                    Some(priority),
+                )
+                .unwrap();
+            records.push((seq, hash, entry_hash));
+        }
+
+        let budget = storage.stats().unwrap().db_size * 3 / 4;
+        let evicted = storage
+            .enforce_budget(budget, EvictionPolicy::LowestPriority)
+            .unwrap();
+        assert!(evicted > 0 && evicted < 10);
+        assert!(storage.stats().unwrap().db_size <= budget);
+
+        for (seq, hash, entry_hash) in &records {
+            let retained = seq % 2 == 1 || *seq > 2 * evicted as u32;
+            assert_eq!(storage.get_action(hash).unwrap().is_some(), retained);
+            assert_eq!(storage.get_entry(entry_hash).unwrap().is_some(), retained);
+        }
+    }
 }
@@ -15,6 +15,7 @@
 //! - GPS/Location (lat/lon)
 //! - Accelerometer (3-axis)
 //! - Custom sensors via trait implementation
+//! - Derived sensors computed from other sensors' readings
 //!
 //! # Example
 //! ```rust
@@ -26,11 +27,15 @@
 //! ```
 
 use crate::error::{Error, Result};
+use crate::wire::keyed_struct;
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Sensor reading with metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 pub struct SensorReading {
     /// Sensor type
     pub sensor_type: SensorType,
@@ -47,6 +52,15 @@ pub struct SensorReading {
     pub metadata: std::collections::HashMap<String, String>,
 }
 
+keyed_struct!(SensorReading {
+    0 => sensor_type,
+    1 => value,
+    2 => unit,
+    3 => timestamp,
+    4 => quality,
+    5 => metadata,
+});
+
 impl SensorReading {
     /// Create a new sensor reading
     pub fn new(sensor_type: SensorType, value: f64, unit: String) -> Self {
@@ -81,8 +95,52 @@ impl SensorReading {
     }
 }
 
+/// Readings from one device, sent to peers as a single message
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(remote = "Self")]
+pub struct SensorBatch {
+    /// Device or node the readings come from
+    pub source: String,
+    /// The readings, in the order they were taken
+    pub readings: Vec<SensorReading>,
+}
+
+keyed_struct!(SensorBatch {
+    0 => source,
+    1 => readings,
+});
+
+impl SensorBatch {
+    /// Create an empty batch
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            readings: Vec::new(),
+        }
+    }
+
+    /// Add a reading
+    pub fn push(&mut self, reading: SensorReading) {
+        self.readings.push(reading);
+    }
+
+    /// Number of readings
+    pub fn len(&self) -> usize {
+        self.readings.len()
+    }
+
+    /// Check if the batch has no readings
+    pub fn is_empty(&self) -> bool {
+        self.readings.is_empty()
+    }
+}
+
 /// Sensor types supported by the platform
+///
+/// In CBOR a sensor type is a single integer: built-in types use their tag
+/// below [`CUSTOM_SENSOR_TAG`], and `Custom(n)` is sent as that value plus `n`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 pub enum SensorType {
     /// Temperature sensor (Celsius)
     Temperature,
@@ -162,6 +220,78 @@ impl SensorType {
             SensorType::Custom(_) => "Custom",
         }
     }
+
+    /// Integer tag used for this type in CBOR
+    fn wire_tag(&self) -> u32 {
+        match self {
+            SensorType::Temperature => 0,
+            SensorType::Humidity => 1,
+            SensorType::Pressure => 2,
+            SensorType::Light => 3,
+            SensorType::Motion => 4,
+            SensorType::GPS => 5,
+            SensorType::Accelerometer => 6,
+            SensorType::Gyroscope => 7,
+            SensorType::Magnetometer => 8,
+            SensorType::Proximity => 9,
+            SensorType::Sound => 10,
+            SensorType::AirQuality => 11,
+            SensorType::Voltage => 12,
+            SensorType::Current => 13,
+            SensorType::Power => 14,
+            SensorType::Custom(id) => CUSTOM_SENSOR_TAG + u32::from(*id),
+        }
+    }
+
+    /// Sensor type for a CBOR tag
+    fn from_wire_tag(tag: u32) -> Option<Self> {
+        Some(match tag {
+            0 => SensorType::Temperature,
+            1 => SensorType::Humidity,
+            2 => SensorType::Pressure,
+            3 => SensorType::Light,
+            4 => SensorType::Motion,
+            5 => SensorType::GPS,
+            6 => SensorType::Accelerometer,
+            7 => SensorType::Gyroscope,
+            8 => SensorType::Magnetometer,
+            9 => SensorType::Proximity,
+            10 => SensorType::Sound,
+            11 => SensorType::AirQuality,
+            12 => SensorType::Voltage,
+            13 => SensorType::Current,
+            14 => SensorType::Power,
+            tag => SensorType::Custom(u16::try_from(tag.checked_sub(CUSTOM_SENSOR_TAG)?).ok()?),
+        })
+    }
+}
+
+/// CBOR tag of `SensorType::Custom(0)`; lower tags are reserved for built-in types
+pub const CUSTOM_SENSOR_TAG: u32 = 256;
+
+impl Serialize for SensorType {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return SensorType::serialize(self, serializer);
+        }
+        serializer.serialize_u32(self.wire_tag())
+    }
+}
+
+impl<'de> Deserialize<'de> for SensorType {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            return SensorType::deserialize(deserializer);
+        }
+        let tag = u32::deserialize(deserializer)?;
+        SensorType::from_wire_tag(tag)
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown sensor type tag {}", tag)))
+    }
 }
 
 /// Calibration parameters for sensor tuning
@@ -321,6 +451,7 @@ impl Sensor for MockSensor {
 /// Sensor manager for handling multiple sensors
 pub struct SensorManager {
     sensors: Vec<Box<dyn Sensor>>,
+    derived: Vec<DerivedSensor>,
 }
 
 impl SensorManager {
@@ -328,6 +459,7 @@ impl SensorManager {
     pub fn new() -> Self {
         Self {
             sensors: Vec::new(),
+            derived: Vec::new(),
         }
     }
 
@@ -337,11 +469,78 @@ impl SensorManager {
         self.sensors.push(sensor);
     }
 
+    /// Register a sensor computed from other sensors' readings
+    ///
+    /// `name` becomes the `sensor_id` metadata of its readings, so later
+    /// derived sensors can take it as an input.
+    pub fn add_derived_sensor(&mut self, name: impl Into<String>, spec: DerivedSpec) -> Result<()> {
+        let name = name.into();
+        if self.derived.iter().any(|d| d.name == name) {
+            return Err(Error::ValidationFailed(format!(
+                "Derived sensor {} already registered",
+                name
+            )));
+        }
+        spec.check()?;
+        log::info!("Registered derived sensor: {}", name);
+        self.derived.push(DerivedSensor::new(name, spec));
+        Ok(())
+    }
+
+    /// Number of derived sensors
+    pub fn derived_count(&self) -> usize {
+        self.derived.len()
+    }
+
+    /// Feed a reading to the derived sensors, returning the readings it
+    /// produced
+    ///
+    /// A derived sensor evaluates when the reading is one of its inputs and
+    /// every other input has a reading within the staleness window of it.
+    /// Each derived reading is offered in turn to the derived sensors
+    /// registered after the one that produced it.
+    pub fn observe(&self, reading: &SensorReading) -> Vec<SensorReading> {
+        let mut produced: Vec<SensorReading> = Vec::new();
+        for derived in &self.derived {
+            let mut triggered = derived.offer(reading);
+            for earlier in &produced {
+                triggered |= derived.offer(earlier);
+            }
+            if !triggered {
+                continue;
+            }
+            if let Some(output) = derived.evaluate() {
+                produced.push(output);
+            }
+        }
+        produced
+    }
+
     /// Read all sensors
     pub fn read_all(&self) -> Vec<Result<SensorReading>> {
         self.sensors.iter().map(|s| s.read()).collect()
     }
 
+    /// Read all sensors into a batch, skipping sensors that failed
+    ///
+    /// Readings of derived sensors follow the reading that triggered them.
+    pub fn read_batch(&self, source: impl Into<String>) -> SensorBatch {
+        let mut batch = SensorBatch::new(source);
+        for reading in self.read_all() {
+            match reading {
+                Ok(reading) => {
+                    let derived = self.observe(&reading);
+                    batch.push(reading);
+                    for reading in derived {
+                        batch.push(reading);
+                    }
+                }
+                Err(e) => log::warn!("Sensor read failed: {}", e),
+            }
+        }
+        batch
+    }
+
     /// Read sensors of a specific type
     pub fn read_by_type(&self, sensor_type: SensorType) -> Vec<Result<SensorReading>> {
         self.sensors
@@ -372,6 +571,298 @@ impl Default for SensorManager {
     }
 }
 
+/// A reading a derived sensor takes as input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SensorInput {
+    /// Any reading of this type
+    Type(SensorType),
+    /// Readings whose `sensor_id` metadata matches
+    Id(String),
+}
+
+impl SensorInput {
+    /// Check if a reading feeds this input
+    pub fn matches(&self, reading: &SensorReading) -> bool {
+        match self {
+            SensorInput::Type(sensor_type) => reading.sensor_type == *sensor_type,
+            SensorInput::Id(id) => reading.metadata.get("sensor_id") == Some(id),
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            SensorInput::Type(sensor_type) => sensor_type.name().to_string(),
+            SensorInput::Id(id) => id.clone(),
+        }
+    }
+}
+
+/// User computation over the input values, in input order
+///
+/// Returning `None` skips the evaluation.
+pub type DeriveFn = Arc<dyn Fn(&[f64]) -> Option<f64> + Send + Sync>;
+
+/// How a derived sensor computes its value
+#[derive(Clone)]
+pub enum Derivation {
+    /// `offset + Σ weights[i] · inputs[i]`
+    Linear { weights: Vec<f64>, offset: f64 },
+    /// `inputs[0] / inputs[1]`
+    Ratio,
+    /// Change per second of the single input between the oldest and newest
+    /// readings within the last `window_ms`
+    RateOfChange { window_ms: u64 },
+    /// A user closure, recorded as `label` in the reading metadata
+    Custom { label: String, f: DeriveFn },
+}
+
+impl Derivation {
+    /// A user closure derivation
+    pub fn custom(
+        label: impl Into<String>,
+        f: impl Fn(&[f64]) -> Option<f64> + Send + Sync + 'static,
+    ) -> Self {
+        Derivation::Custom {
+            label: label.into(),
+            f: Arc::new(f),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Derivation::Linear { weights, offset } => {
+                format!("linear(weights={:?}, offset={})", weights, offset)
+            }
+            Derivation::Ratio => "ratio".to_string(),
+            Derivation::RateOfChange { window_ms } => {
+                format!("rate_of_change(window={}ms)", window_ms)
+            }
+            Derivation::Custom { label, .. } => format!("custom({})", label),
+        }
+    }
+}
+
+impl std::fmt::Debug for Derivation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.describe())
+    }
+}
+
+/// Definition of a derived sensor
+#[derive(Debug, Clone)]
+pub struct DerivedSpec {
+    /// Readings the value is computed from
+    pub inputs: Vec<SensorInput>,
+    /// The computation
+    pub derivation: Derivation,
+    /// Type of the produced readings
+    pub sensor_type: SensorType,
+    /// Unit of the produced readings
+    pub unit: String,
+    /// Oldest an input reading may be, relative to the triggering reading,
+    /// for the derived sensor to evaluate (ms)
+    pub max_age_ms: u64,
+}
+
+impl DerivedSpec {
+    /// Create a spec producing readings of `sensor_type` in its default unit
+    pub fn new(sensor_type: SensorType, inputs: Vec<SensorInput>, derivation: Derivation) -> Self {
+        Self {
+            inputs,
+            derivation,
+            sensor_type,
+            unit: sensor_type.default_unit().to_string(),
+            max_age_ms: 5_000,
+        }
+    }
+
+    /// Heat index (°C) from a temperature (°C) and a relative humidity (%)
+    pub fn heat_index(temperature: SensorInput, humidity: SensorInput) -> Self {
+        Self::new(
+            SensorType::Temperature,
+            vec![temperature, humidity],
+            Derivation::custom("heat_index", |v| Some(heat_index(v[0], v[1]))),
+        )
+    }
+
+    /// Rate of change per second of one sensor over `window_ms`
+    pub fn rate_of_change(input: SensorInput, window_ms: u64) -> Self {
+        Self::new(
+            SensorType::Custom(0),
+            vec![input],
+            Derivation::RateOfChange { window_ms },
+        )
+        .with_unit("/s")
+    }
+
+    /// Set the unit of the produced readings
+    pub fn with_unit(mut self, unit: impl Into<String>) -> Self {
+        self.unit = unit.into();
+        self
+    }
+
+    /// Set the staleness window (ms)
+    pub fn with_max_age(mut self, max_age_ms: u64) -> Self {
+        self.max_age_ms = max_age_ms;
+        self
+    }
+
+    /// Check the inputs fit the derivation
+    fn check(&self) -> Result<()> {
+        let expected = match &self.derivation {
+            Derivation::Linear { weights, .. } => Some(weights.len()),
+            Derivation::Ratio => Some(2),
+            Derivation::RateOfChange { .. } => Some(1),
+            Derivation::Custom { .. } => None,
+        };
+        match expected {
+            Some(n) if self.inputs.len() != n => Err(Error::ValidationFailed(format!(
+                "{:?} takes {} inputs, got {}",
+                self.derivation,
+                n,
+                self.inputs.len()
+            ))),
+            None if self.inputs.is_empty() => Err(Error::ValidationFailed(
+                "Derived sensor has no inputs".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Heat index (°C) for a temperature (°C) and a relative humidity (%)
+///
+/// The NWS algorithm: Steadman's simple formula in mild conditions, the
+/// Rothfusz regression with its low- and high-humidity adjustments above
+/// 80°F.
+pub fn heat_index(temperature_c: f64, humidity: f64) -> f64 {
+    let t = temperature_c * 1.8 + 32.0;
+    let rh = humidity;
+
+    let simple = 0.5 * (t + 61.0 + (t - 68.0) * 1.2 + rh * 0.094);
+    let hi = if (simple + t) / 2.0 < 80.0 {
+        simple
+    } else {
+        let mut hi = -42.379 + 2.049_015_23 * t + 10.143_331_27 * rh
+            - 0.224_755_41 * t * rh
+            - 0.006_837_83 * t * t
+            - 0.054_817_17 * rh * rh
+            + 0.001_228_74 * t * t * rh
+            + 0.000_852_82 * t * rh * rh
+            - 0.000_001_99 * t * t * rh * rh;
+        if rh < 13.0 && (80.0..=112.0).contains(&t) {
+            hi -= ((13.0 - rh) / 4.0) * ((17.0 - (t - 95.0).abs()) / 17.0).sqrt();
+        } else if rh > 85.0 && (80.0..=87.0).contains(&t) {
+            hi += ((rh - 85.0) / 10.0) * ((87.0 - t) / 5.0);
+        }
+        hi
+    };
+
+    (hi - 32.0) / 1.8
+}
+
+/// A registered derived sensor and the readings it has seen
+struct DerivedSensor {
+    name: String,
+    spec: DerivedSpec,
+    state: Mutex<DerivedState>,
+}
+
+#[derive(Default)]
+struct DerivedState {
+    /// Latest reading per input
+    latest: Vec<Option<SensorReading>>,
+    /// `(timestamp, value)` of the single input, for rate of change
+    history: VecDeque<(u64, f64)>,
+    /// Timestamp of the reading that last fed an input
+    now: u64,
+}
+
+impl DerivedSensor {
+    fn new(name: String, spec: DerivedSpec) -> Self {
+        let state = DerivedState {
+            latest: vec![None; spec.inputs.len()],
+            ..Default::default()
+        };
+        Self {
+            name,
+            spec,
+            state: Mutex::new(state),
+        }
+    }
+
+    /// Record `reading` against the inputs it matches
+    fn offer(&self, reading: &SensorReading) -> bool {
+        let mut state = self.state.lock().unwrap();
+        let mut matched = false;
+        for (i, input) in self.spec.inputs.iter().enumerate() {
+            if input.matches(reading) {
+                state.latest[i] = Some(reading.clone());
+                matched = true;
+            }
+        }
+        if !matched {
+            return false;
+        }
+
+        state.now = reading.timestamp;
+        if let Derivation::RateOfChange { window_ms } = self.spec.derivation {
+            state.history.push_back((reading.timestamp, reading.value));
+            let cutoff = reading.timestamp.saturating_sub(window_ms);
+            while state.history.front().is_some_and(|&(ts, _)| ts < cutoff) {
+                state.history.pop_front();
+            }
+        }
+        true
+    }
+
+    /// The derived reading, or `None` when an input is missing or stale or
+    /// the value is not a finite number
+    fn evaluate(&self) -> Option<SensorReading> {
+        let state = self.state.lock().unwrap();
+        let mut values = Vec::with_capacity(state.latest.len());
+        let mut quality: f64 = 1.0;
+        for latest in &state.latest {
+            let reading = latest.as_ref()?;
+            if state.now.saturating_sub(reading.timestamp) > self.spec.max_age_ms {
+                log::debug!("Derived sensor {}: stale input, skipped", self.name);
+                return None;
+            }
+            values.push(reading.value);
+            quality = quality.min(reading.quality);
+        }
+
+        let value = match &self.spec.derivation {
+            Derivation::Linear { weights, offset } => {
+                offset + weights.iter().zip(&values).map(|(w, v)| w * v).sum::<f64>()
+            }
+            Derivation::Ratio => values[0] / values[1],
+            Derivation::RateOfChange { .. } => {
+                let &(first_ts, first) = state.history.front()?;
+                let &(last_ts, last) = state.history.back()?;
+                if last_ts <= first_ts {
+                    return None;
+                }
+                (last - first) / ((last_ts - first_ts) as f64 / 1000.0)
+            }
+            Derivation::Custom { f, .. } => f(&values)?,
+        };
+        if !value.is_finite() {
+            log::debug!("Derived sensor {}: non-finite value, skipped", self.name);
+            return None;
+        }
+
+        let inputs: Vec<String> = self.spec.inputs.iter().map(SensorInput::label).collect();
+        let mut reading = SensorReading::new(self.spec.sensor_type, value, self.spec.unit.clone())
+            .with_quality(quality)
+            .with_metadata("sensor_id".to_string(), self.name.clone())
+            .with_metadata("derived_from".to_string(), inputs.join(","))
+            .with_metadata("derivation".to_string(), self.spec.derivation.describe());
+        reading.timestamp = state.now;
+        Some(reading)
+    }
+}
+
 /// Sensor statistics
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SensorStats {
@@ -1305,6 +1796,18 @@ mod tests {
         assert_eq!(temp_readings.len(), 1);
     }
 
+    #[test]
+    fn test_sensor_manager_read_batch() {
+        let mut manager = SensorManager::new();
+        manager.register(Box::new(MockSensor::new(SensorType::Temperature)));
+        manager.register(Box::new(MockSensor::new(SensorType::Humidity)));
+
+        let batch = manager.read_batch("node-1");
+        assert_eq!(batch.source, "node-1");
+        assert_eq!(batch.len(), 2);
+        assert_eq!(batch.readings[1].sensor_type, SensorType::Humidity);
+    }
+
     #[test]
     fn test_sensor_manager_default() {
         let manager = SensorManager::default();
@@ -1331,6 +1834,225 @@ mod tests {
         assert!(pressure_readings.is_empty());
     }
 
+    fn reading_at(sensor_type: SensorType, value: f64, timestamp: u64) -> SensorReading {
+        let mut reading =
+            SensorReading::new(sensor_type, value, sensor_type.default_unit().to_string());
+        reading.timestamp = timestamp;
+        reading
+    }
+
+    fn heat_index_manager() -> SensorManager {
+        let mut manager = SensorManager::new();
+        manager
+            .add_derived_sensor(
+                "heat_index",
+                DerivedSpec::heat_index(
+                    SensorInput::Type(SensorType::Temperature),
+                    SensorInput::Type(SensorType::Humidity),
+                )
+                .with_max_age(1_000),
+            )
+            .unwrap();
+        manager
+    }
+
+    #[test]
+    fn test_heat_index_known_values() {
+        // NWS heat index chart: (°F, %RH) -> °F
+        let chart = [
+            (80.0, 40.0, 80.0),
+            (86.0, 90.0, 105.0),
+            (90.0, 70.0, 106.0),
+            (100.0, 50.0, 118.0),
+        ];
+        for (t_f, rh, expected_f) in chart {
+            let hi_f = heat_index((t_f - 32.0) / 1.8, rh) * 1.8 + 32.0;
+            assert!(
+                (hi_f - expected_f).abs() < 1.0,
+                "{}°F at {}%: got {}",
+                t_f,
+                rh,
+                hi_f
+            );
+        }
+    }
+
+    #[test]
+    fn test_derived_heat_index_reading() {
+        let manager = heat_index_manager();
+        let temp_c = (90.0 - 32.0) / 1.8;
+
+        // Nothing until both inputs have a reading
+        assert!(manager
+            .observe(&reading_at(SensorType::Temperature, temp_c, 10_000))
+            .is_empty());
+        let derived = manager.observe(&reading_at(SensorType::Humidity, 70.0, 10_500));
+        assert_eq!(derived.len(), 1);
+
+        let reading = &derived[0];
+        assert_eq!(reading.sensor_type, SensorType::Temperature);
+        assert_eq!(reading.timestamp, 10_500);
+        assert!((reading.value * 1.8 + 32.0 - 106.0).abs() < 1.0);
+        assert_eq!(reading.metadata["sensor_id"], "heat_index");
+        assert_eq!(reading.metadata["derived_from"], "Temperature,Humidity");
+        assert_eq!(reading.metadata["derivation"], "custom(heat_index)");
+    }
+
+    #[test]
+    fn test_derived_skips_stale_inputs() {
+        let manager = heat_index_manager();
+
+        manager.observe(&reading_at(SensorType::Temperature, 32.0, 10_000));
+        // The temperature is 1.5 s old, past the 1 s window
+        assert!(manager
+            .observe(&reading_at(SensorType::Humidity, 70.0, 11_500))
+            .is_empty());
+
+        // A fresh temperature brings it back
+        let derived = manager.observe(&reading_at(SensorType::Temperature, 32.0, 12_000));
+        assert_eq!(derived.len(), 1);
+        assert!(derived[0].value.is_finite());
+    }
+
+    #[test]
+    fn test_derived_rate_of_change() {
+        let mut manager = SensorManager::new();
+        manager
+            .add_derived_sensor(
+                "temp_rate",
+                DerivedSpec::rate_of_change(SensorInput::Type(SensorType::Temperature), 5_000),
+            )
+            .unwrap();
+        manager
+            .add_derived_sensor(
+                "temp_rate_short",
+                DerivedSpec::rate_of_change(SensorInput::Type(SensorType::Temperature), 1_500),
+            )
+            .unwrap();
+
+        // A single reading has no rate yet
+        assert!(manager
+            .observe(&reading_at(SensorType::Temperature, 10.0, 0))
+            .is_empty());
+
+        let second = manager.observe(&reading_at(SensorType::Temperature, 12.0, 1_000));
+        assert_eq!(second.len(), 2);
+        assert!((second[0].value - 2.0).abs() < 1e-9);
+        assert!((second[1].value - 2.0).abs() < 1e-9);
+
+        let third = manager.observe(&reading_at(SensorType::Temperature, 16.0, 2_000));
+        assert_eq!(third.len(), 2);
+        // (16 - 10) / 2 s over the full window
+        assert_eq!(third[0].metadata["sensor_id"], "temp_rate");
+        assert!((third[0].value - 3.0).abs() < 1e-9);
+        // The short window has dropped the first reading: (16 - 12) / 1 s
+        assert_eq!(third[1].metadata["sensor_id"], "temp_rate_short");
+        assert!((third[1].value - 4.0).abs() < 1e-9);
+        assert_eq!(third[1].unit, "/s");
+    }
+
+    #[test]
+    fn test_derived_builtin_formulas() {
+        let mut manager = SensorManager::new();
+        manager
+            .add_derived_sensor(
+                "power",
+                DerivedSpec::new(
+                    SensorType::Power,
+                    vec![
+                        SensorInput::Id("bus_v".to_string()),
+                        SensorInput::Id("bus_a".to_string()),
+                    ],
+                    Derivation::custom("v*a", |v| Some(v[0] * v[1])),
+                ),
+            )
+            .unwrap();
+        manager
+            .add_derived_sensor(
+                "efficiency",
+                DerivedSpec::new(
+                    SensorType::Custom(1),
+                    vec![
+                        SensorInput::Id("power".to_string()),
+                        SensorInput::Type(SensorType::Light),
+                    ],
+                    Derivation::Ratio,
+                ),
+            )
+            .unwrap();
+        manager
+            .add_derived_sensor(
+                "offset_temp",
+                DerivedSpec::new(
+                    SensorType::Temperature,
+                    vec![SensorInput::Type(SensorType::Pressure)],
+                    Derivation::Linear {
+                        weights: vec![0.5],
+                        offset: 1.0,
+                    },
+                ),
+            )
+            .unwrap();
+
+        let volts = reading_at(SensorType::Voltage, 12.0, 1_000)
+            .with_metadata("sensor_id".to_string(), "bus_v".to_string());
+        let amps = reading_at(SensorType::Current, 2.0, 1_000)
+            .with_metadata("sensor_id".to_string(), "bus_a".to_string());
+        manager.observe(&reading_at(SensorType::Light, 0.0, 1_000));
+        manager.observe(&volts);
+
+        // The ratio's denominator is zero, so only power is produced
+        let derived = manager.observe(&amps);
+        assert_eq!(derived.len(), 1);
+        assert_eq!(derived[0].value, 24.0);
+
+        manager.observe(&reading_at(SensorType::Light, 8.0, 1_000));
+        let derived = manager.observe(&amps);
+        assert_eq!(derived.len(), 2);
+        assert_eq!(derived[1].metadata["sensor_id"], "efficiency");
+        assert_eq!(derived[1].value, 3.0);
+
+        let derived = manager.observe(&reading_at(SensorType::Pressure, 10.0, 1_000));
+        assert_eq!(derived.len(), 1);
+        assert_eq!(derived[0].value, 6.0);
+    }
+
+    #[test]
+    fn test_add_derived_sensor_validation() {
+        let mut manager = SensorManager::new();
+        let ratio = DerivedSpec::new(
+            SensorType::Custom(0),
+            vec![SensorInput::Type(SensorType::Voltage)],
+            Derivation::Ratio,
+        );
+        assert!(manager.add_derived_sensor("bad", ratio).is_err());
+
+        let rate = DerivedSpec::rate_of_change(SensorInput::Type(SensorType::Light), 1_000);
+        manager.add_derived_sensor("rate", rate.clone()).unwrap();
+        assert!(manager.add_derived_sensor("rate", rate).is_err());
+        assert_eq!(manager.derived_count(), 1);
+    }
+
+    #[test]
+    fn test_read_batch_includes_derived() {
+        let mut manager = SensorManager::new();
+        manager.register(Box::new(MockSensor::new(SensorType::Temperature)));
+        manager.register(Box::new(MockSensor::new(SensorType::Humidity)));
+        manager
+            .add_derived_sensor(
+                "heat_index",
+                DerivedSpec::heat_index(
+                    SensorInput::Type(SensorType::Temperature),
+                    SensorInput::Type(SensorType::Humidity),
+                ),
+            )
+            .unwrap();
+
+        let batch = manager.read_batch("node-1");
+        assert_eq!(batch.len(), 3);
+        assert_eq!(batch.readings[2].metadata["sensor_id"], "heat_index");
+    }
+
     #[test]
     fn test_sensor_stats() {
         let mut stats = SensorStats::new();
@@ -1523,6 +2245,73 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "coap")]
+    #[test]
+    fn test_sensor_type_cbor_tags() {
+        use crate::wire::WireEncoding;
+        for sensor_type in [
+            SensorType::Temperature,
+            SensorType::Power,
+            SensorType::Custom(0),
+            SensorType::Custom(u16::MAX),
+        ] {
+            let bytes = WireEncoding::Cbor.encode(&sensor_type).unwrap();
+            let parsed: SensorType = WireEncoding::Cbor.decode(&bytes).unwrap();
+            assert_eq!(parsed, sensor_type);
+        }
+        // Temperature is the single byte 0x00
+        assert_eq!(
+            WireEncoding::Cbor.encode(&SensorType::Temperature).unwrap(),
+            vec![0x00]
+        );
+        // Between the built-in types and the custom range
+        let bytes = WireEncoding::Cbor.encode(&100u32).unwrap();
+        assert!(WireEncoding::Cbor.decode::<SensorType>(&bytes).is_err());
+    }
+
+    #[cfg(feature = "coap")]
+    #[test]
+    fn test_sensor_batch_cbor_is_smaller() {
+        use crate::wire::WireEncoding;
+        let mut batch = SensorBatch::new("field-node-07");
+        for (i, sensor_type) in [
+            SensorType::Temperature,
+            SensorType::Humidity,
+            SensorType::Pressure,
+            SensorType::Light,
+        ]
+        .into_iter()
+        .cycle()
+        .take(16)
+        .enumerate()
+        {
+            let mut reading = SensorReading::new(
+                sensor_type,
+                20.0 + i as f64 * 0.37,
+                sensor_type.default_unit().to_string(),
+            );
+            reading.timestamp = 1_760_000_000_000 + i as u64 * 1000;
+            reading.quality = 0.95;
+            batch.push(reading);
+        }
+
+        let json = WireEncoding::Json.encode(&batch).unwrap();
+        let cbor = WireEncoding::Cbor.encode(&batch).unwrap();
+        assert!(
+            cbor.len() * 10 <= json.len() * 7,
+            "CBOR {} bytes vs JSON {} bytes",
+            cbor.len(),
+            json.len()
+        );
+
+        let parsed: SensorBatch = WireEncoding::Cbor.decode(&cbor).unwrap();
+        assert_eq!(parsed.source, "field-node-07");
+        assert_eq!(parsed.len(), 16);
+        assert_eq!(parsed.readings[5].sensor_type, SensorType::Humidity);
+        assert_eq!(parsed.readings[5].value, batch.readings[5].value);
+        assert_eq!(parsed.readings[15].timestamp, batch.readings[15].timestamp);
+    }
+
     #[test]
     fn test_calibration_params_serialize() {
         let params = CalibrationParams {
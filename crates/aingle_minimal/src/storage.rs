@@ -8,11 +8,20 @@
 
 use crate::config::StorageConfig;
 use crate::error::Result;
-use crate::storage_trait::{StorageBackend, StorageStats};
+use crate::storage_crypto::{self, KeyMetadata, RecordCipher};
+use crate::storage_trait::{
+    EntryPriority, EvictionPolicy, PriorityCounts, StorageBackend, StorageStats, DEFAULT_PRIORITY,
+};
 use crate::types::{
     Action, ActionType, AgentPubKey, Entry, Hash, Link, Record, Signature, Timestamp,
 };
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, Row};
+
+/// Metadata key holding the number of records deleted by expiry
+const EXPIRED_COUNT_KEY: &str = "expired_count";
+
+/// Actions evicted per transaction while enforcing the storage budget
+const EVICTION_BATCH: i64 = 16;
 
 // ============================================================================
 // SQLite Storage Implementation
@@ -22,15 +31,26 @@ use rusqlite::{params, Connection};
 pub struct Storage {
     conn: Connection,
     config: StorageConfig,
+    /// Seals record payloads when the store is encrypted
+    cipher: Option<RecordCipher>,
 }
 
 impl Storage {
     /// Open or create storage
+    ///
+    /// Fails with [`StorageError::WrongEncryptionKey`](crate::StorageError::WrongEncryptionKey)
+    /// or [`StorageError::EncryptionMismatch`](crate::StorageError::EncryptionMismatch)
+    /// if [`StorageConfig::encryption`] does not match the store.
     pub fn open(config: StorageConfig) -> Result<Self> {
         let conn = Connection::open(&config.db_path)?;
 
-        let storage = Self { conn, config };
+        let mut storage = Self {
+            conn,
+            config,
+            cipher: None,
+        };
         storage.init_schema()?;
+        storage.cipher = RecordCipher::for_store(storage.config.encryption.as_ref(), &storage)?;
 
         Ok(storage)
     }
@@ -40,7 +60,11 @@ impl Storage {
         let conn = Connection::open_in_memory()?;
         let config = StorageConfig::default();
 
-        let storage = Self { conn, config };
+        let storage = Self {
+            conn,
+            config,
+            cipher: None,
+        };
         storage.init_schema()?;
 
         Ok(storage)
@@ -59,7 +83,8 @@ impl Storage {
                 author BLOB NOT NULL,
                 prev_action BLOB,
                 entry_hash BLOB,
-                data BLOB NOT NULL
+                data BLOB NOT NULL,
+                priority INTEGER NOT NULL DEFAULT 128
             );
 
             -- Entries table
@@ -84,6 +109,7 @@ impl Storage {
             -- Indices for efficient queries
             CREATE INDEX IF NOT EXISTS idx_actions_seq ON actions(seq);
             CREATE INDEX IF NOT EXISTS idx_actions_timestamp ON actions(timestamp);
+            CREATE INDEX IF NOT EXISTS idx_actions_entry ON actions(entry_hash);
             CREATE INDEX IF NOT EXISTS idx_entries_created ON entries(created_at);
             CREATE INDEX IF NOT EXISTS idx_links_base ON links(base);
             CREATE INDEX IF NOT EXISTS idx_links_target ON links(target);
@@ -96,6 +122,22 @@ impl Storage {
             "#,
         )?;
 
+        // Databases created before eviction priorities lack the column
+        if self
+            .conn
+            .prepare("SELECT priority FROM actions LIMIT 0")
+            .is_err()
+        {
+            self.conn.execute(
+                "ALTER TABLE actions ADD COLUMN priority INTEGER NOT NULL DEFAULT 128",
+                [],
+            )?;
+        }
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_actions_priority ON actions(priority, seq)",
+            [],
+        )?;
+
         Ok(())
     }
 
@@ -104,15 +146,21 @@ impl Storage {
     /// Optimized to serialize only once - the same bytes are used for
     /// both hash computation and storage.
     pub fn put_action(&self, action: &Action) -> Result<Hash> {
+        self.put_action_with_priority(action, DEFAULT_PRIORITY)
+    }
+
+    /// Store an action with an eviction priority
+    fn put_action_with_priority(&self, action: &Action, priority: u8) -> Result<Hash> {
         // Serialize once, use for both hash and storage
         let data = serde_json::to_vec(action)?;
         let hash = Hash::from_bytes(&data);
         let action_type = format!("{:?}", action.action_type);
+        let data = self.seal("actions", hash.as_bytes(), data)?;
 
         self.conn.execute(
             r#"INSERT OR REPLACE INTO actions
-               (hash, seq, timestamp, action_type, author, prev_action, entry_hash, data)
-               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)"#,
+               (hash, seq, timestamp, action_type, author, prev_action, entry_hash, data, priority)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)"#,
             params![
                 hash.as_bytes().as_ref(),
                 action.seq,
@@ -122,6 +170,7 @@ impl Storage {
                 action.prev_action.as_ref().map(|h| h.as_bytes().to_vec()),
                 action.entry_hash.as_ref().map(|h| h.as_bytes().to_vec()),
                 data,
+                priority,
             ],
         )?;
 
@@ -138,17 +187,13 @@ impl Storage {
         let hash = entry.hash();
         let entry_type = format!("{:?}", entry.entry_type);
         let timestamp = Timestamp::now().0 as i64;
+        let content = self.seal("entries", hash.as_bytes(), entry.content.clone())?;
 
         self.conn.execute(
             r#"INSERT OR REPLACE INTO entries
                (hash, entry_type, content, created_at)
                VALUES (?1, ?2, ?3, ?4)"#,
-            params![
-                hash.as_bytes().as_ref(),
-                entry_type,
-                &entry.content,
-                timestamp,
-            ],
+            params![hash.as_bytes().as_ref(), entry_type, content, timestamp,],
         )?;
 
         // Prune if needed
@@ -161,10 +206,15 @@ impl Storage {
 
     /// Store a record (action + optional entry)
     pub fn put_record(&self, record: &Record) -> Result<Hash> {
+        self.put_record_with_priority(record, None)
+    }
+
+    /// Store a record with an eviction priority (higher is kept longer)
+    pub fn put_record_with_priority(&self, record: &Record, priority: Option<u8>) -> Result<Hash> {
         if let Some(entry) = &record.entry {
             self.put_entry(entry)?;
         }
-        self.put_action(&record.action)
+        self.put_action_with_priority(&record.action, priority.unwrap_or(DEFAULT_PRIORITY))
     }
 
     /// Store multiple records in a single transaction (optimized batch operation)
@@ -204,11 +254,13 @@ impl Storage {
                 if let Some(entry) = &record.entry {
                     let entry_hash = entry.hash();
                     let entry_type = format!("{:?}", entry.entry_type);
+                    let content =
+                        self.seal("entries", entry_hash.as_bytes(), entry.content.clone())?;
 
                     entry_stmt.execute(params![
                         entry_hash.as_bytes().as_ref(),
                         entry_type,
-                        &entry.content,
+                        content,
                         timestamp,
                     ])?;
                 }
@@ -218,6 +270,7 @@ impl Storage {
                 let data = serde_json::to_vec(action)?;
                 let hash = Hash::from_bytes(&data);
                 let action_type = format!("{:?}", action.action_type);
+                let data = self.seal("actions", hash.as_bytes(), data)?;
 
                 action_stmt.execute(params![
                     hash.as_bytes().as_ref(),
@@ -255,6 +308,54 @@ impl Storage {
         }
     }
 
+    /// Delete expired actions and entries in a single transaction
+    ///
+    /// The running total is kept in the metadata table so it survives restarts.
+    pub fn expire_records_batch(&self, hashes: &[Hash]) -> Result<usize> {
+        if hashes.is_empty() {
+            return Ok(0);
+        }
+
+        self.conn.execute("BEGIN IMMEDIATE", [])?;
+
+        let result = (|| {
+            let mut action_stmt = self
+                .conn
+                .prepare_cached("DELETE FROM actions WHERE hash = ?1")?;
+            let mut entry_stmt = self
+                .conn
+                .prepare_cached("DELETE FROM entries WHERE hash = ?1")?;
+
+            let mut removed = 0;
+            for hash in hashes {
+                removed += action_stmt.execute(params![hash.as_bytes().as_ref()])?;
+                removed += entry_stmt.execute(params![hash.as_bytes().as_ref()])?;
+            }
+
+            if removed > 0 {
+                self.conn.execute(
+                    r#"INSERT INTO metadata (key, value) VALUES (?1, ?2)
+                       ON CONFLICT(key) DO UPDATE
+                       SET value = CAST(value AS INTEGER) + excluded.value"#,
+                    params![EXPIRED_COUNT_KEY, removed as i64],
+                )?;
+            }
+
+            Ok(removed)
+        })();
+
+        match result {
+            Ok(removed) => {
+                self.conn.execute("COMMIT", [])?;
+                Ok(removed)
+            }
+            Err(e) => {
+                let _ = self.conn.execute("ROLLBACK", []);
+                Err(e)
+            }
+        }
+    }
+
     /// Get action by hash
     pub fn get_action(&self, hash: &Hash) -> Result<Option<Action>> {
         let mut stmt = self
@@ -266,7 +367,10 @@ impl Storage {
             .ok();
 
         match result {
-            Some(data) => Ok(Some(serde_json::from_slice(&data)?)),
+            Some(data) => {
+                let data = self.open_sealed("actions", hash.as_bytes(), data)?;
+                Ok(Some(serde_json::from_slice(&data)?))
+            }
             None => Ok(None),
         }
     }
@@ -288,7 +392,7 @@ impl Storage {
                 // Reconstruct entry
                 Ok(Some(Entry {
                     entry_type: crate::types::EntryType::App,
-                    content,
+                    content: self.open_sealed("entries", hash.as_bytes(), content)?,
                 }))
             }
             None => Ok(None),
@@ -326,7 +430,7 @@ impl Storage {
         let rows = stmt.query_map(params![from_seq, to_seq, limit], |row| {
             let hash_bytes: Vec<u8> = row.get(0)?;
             let seq: u32 = row.get(1)?;
-            let timestamp_ms: i64 = row.get(2)?;
+            let timestamp: i64 = row.get(2)?;
             let action_type_str: String = row.get(3)?;
             let author_bytes: Vec<u8> = row.get(4)?;
             let prev_action_bytes: Option<Vec<u8>> = row.get(5)?;
@@ -336,7 +440,7 @@ impl Storage {
             Ok((
                 hash_bytes,
                 seq,
-                timestamp_ms,
+                timestamp,
                 action_type_str,
                 author_bytes,
                 prev_action_bytes,
@@ -350,7 +454,7 @@ impl Storage {
             let (
                 _hash_bytes,
                 seq,
-                timestamp_ms,
+                timestamp,
                 action_type_str,
                 author_bytes,
                 prev_action_bytes,
@@ -390,7 +494,7 @@ impl Storage {
             let action = Action {
                 action_type,
                 author: AgentPubKey(author),
-                timestamp: Timestamp::from_millis(timestamp_ms as u64),
+                timestamp: Timestamp(timestamp as u64),
                 seq,
                 prev_action,
                 entry_hash: entry_hash.clone(),
@@ -426,38 +530,64 @@ impl Storage {
                     row.get(0)
                 })?;
 
-        // Get database file size
-        let db_size = std::fs::metadata(&self.config.db_path)
-            .map(|m| m.len() as usize)
+        // Page accounting matches the file size and also covers in-memory databases
+        let page_size = self.pragma_usize("page_size")?;
+        let db_size = self.pragma_usize("page_count")? * page_size;
+
+        let expired_count = self
+            .get_metadata(EXPIRED_COUNT_KEY)?
+            .and_then(|v| v.parse().ok())
             .unwrap_or(0);
 
+        let mut by_priority = PriorityCounts::default();
+        let mut stmt = self
+            .conn
+            .prepare_cached("SELECT priority, COUNT(*) FROM actions GROUP BY priority")?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, u8>(0)?, row.get::<_, u64>(1)?)))?;
+        for row in rows {
+            let (priority, count) = row?;
+            by_priority.add(EntryPriority::from_eviction_priority(priority), count);
+        }
+
         Ok(StorageStats {
             action_count,
             entry_count,
             link_count,
             db_size,
+            expired_count,
+            by_priority,
         })
     }
 
     /// Prune old actions (keep only recent)
+    ///
+    /// Higher priority classes are kept first, the most recent within a class.
     fn prune_old_actions(&self) -> Result<()> {
         let keep = self.config.keep_recent as i64;
-        self.conn.execute(
+        let sql = format!(
             r#"DELETE FROM actions WHERE seq NOT IN
-               (SELECT seq FROM actions ORDER BY seq DESC LIMIT ?1)"#,
-            params![keep],
-        )?;
+               (SELECT seq FROM actions ORDER BY {} DESC, seq DESC LIMIT ?1)"#,
+            EntryPriority::sql_class_rank("priority")
+        );
+        self.conn.execute(&sql, params![keep])?;
         Ok(())
     }
 
     /// Prune old entries (keep only recent)
+    ///
+    /// Entries take the highest priority class of the actions referencing
+    /// them; unreferenced entries count as Normal.
     fn prune_old_entries(&self) -> Result<()> {
         let keep = self.config.keep_recent as i64;
-        self.conn.execute(
+        let sql = format!(
             r#"DELETE FROM entries WHERE hash NOT IN
-               (SELECT hash FROM entries ORDER BY created_at DESC LIMIT ?1)"#,
-            params![keep],
-        )?;
+               (SELECT e.hash FROM entries e
+                LEFT JOIN actions a ON a.entry_hash = e.hash
+                GROUP BY e.hash
+                ORDER BY MAX({}) DESC, e.created_at DESC LIMIT ?1)"#,
+            EntryPriority::sql_class_rank(&format!("COALESCE(a.priority, {})", DEFAULT_PRIORITY))
+        );
+        self.conn.execute(&sql, params![keep])?;
         Ok(())
     }
 
@@ -467,6 +597,105 @@ impl Storage {
         Ok(())
     }
 
+    /// Vacuum only if deletions left free pages behind
+    pub fn compact(&self) -> Result<()> {
+        if self.pragma_usize("freelist_count")? > 0 {
+            self.vacuum()?;
+        }
+        Ok(())
+    }
+
+    /// Read an integer pragma
+    fn pragma_usize(&self, name: &str) -> Result<usize> {
+        let value: i64 = self
+            .conn
+            .query_row(&format!("PRAGMA {}", name), [], |row| row.get(0))?;
+        Ok(value.max(0) as usize)
+    }
+
+    /// Bytes held by live pages (excludes pages freed but not yet vacuumed)
+    fn live_size(&self) -> Result<usize> {
+        let pages = self
+            .pragma_usize("page_count")?
+            .saturating_sub(self.pragma_usize("freelist_count")?);
+        Ok(pages * self.pragma_usize("page_size")?)
+    }
+
+    /// Evict records until live pages fit in `max_bytes`
+    ///
+    /// See [`StorageBackend::enforce_budget`] for the invariants kept.
+    pub fn enforce_budget(&self, max_bytes: usize, policy: EvictionPolicy) -> Result<usize> {
+        let order = match policy {
+            EvictionPolicy::OldestFirst => {
+                format!("{} ASC, seq ASC", EntryPriority::sql_class_rank("priority"))
+            }
+            EvictionPolicy::LowestPriority => "priority ASC, seq ASC".to_string(),
+        };
+        let select = format!(
+            r#"SELECT hash, entry_hash FROM actions
+               WHERE seq < (SELECT MAX(seq) FROM actions)
+               ORDER BY {} LIMIT ?1"#,
+            order
+        );
+
+        let mut evicted = 0;
+        while self.live_size()? > max_bytes {
+            let victims = {
+                let mut stmt = self.conn.prepare_cached(&select)?;
+                let rows = stmt.query_map(params![EVICTION_BATCH], |row| {
+                    Ok((row.get::<_, Vec<u8>>(0)?, row.get::<_, Option<Vec<u8>>>(1)?))
+                })?;
+                rows.collect::<std::result::Result<Vec<_>, _>>()?
+            };
+
+            if victims.is_empty() {
+                // Only the chain head is left; drop entries nothing references
+                let removed = self.conn.execute(
+                    r#"DELETE FROM entries WHERE hash IN
+                       (SELECT hash FROM entries
+                        WHERE hash NOT IN
+                          (SELECT entry_hash FROM actions WHERE entry_hash IS NOT NULL)
+                        ORDER BY created_at ASC LIMIT ?1)"#,
+                    params![EVICTION_BATCH],
+                )?;
+                if removed == 0 {
+                    break;
+                }
+                continue;
+            }
+
+            self.conn.execute("BEGIN IMMEDIATE", [])?;
+            let result = (|| {
+                let mut action_stmt = self
+                    .conn
+                    .prepare_cached("DELETE FROM actions WHERE hash = ?1")?;
+                // An entry goes with the last action that references it
+                let mut entry_stmt = self.conn.prepare_cached(
+                    r#"DELETE FROM entries WHERE hash = ?1
+                       AND NOT EXISTS (SELECT 1 FROM actions WHERE entry_hash = ?1)"#,
+                )?;
+                for (hash, entry_hash) in &victims {
+                    action_stmt.execute(params![hash])?;
+                    if let Some(entry_hash) = entry_hash {
+                        entry_stmt.execute(params![entry_hash])?;
+                    }
+                }
+                Ok(())
+            })();
+
+            match result {
+                Ok(()) => self.conn.execute("COMMIT", [])?,
+                Err(e) => {
+                    let _ = self.conn.execute("ROLLBACK", []);
+                    return Err(e);
+                }
+            };
+            evicted += victims.len();
+        }
+
+        Ok(evicted)
+    }
+
     /// Check if storage is within limits
     pub fn check_limits(&self) -> Result<bool> {
         let stats = self.stats()?;
@@ -479,6 +708,11 @@ impl Storage {
 
     /// Add a link
     pub fn add_link(&self, link: &Link) -> Result<i64> {
+        let tag = self.seal(
+            "links",
+            &link_key(&link.base, &link.target),
+            link.tag.clone(),
+        )?;
         self.conn.execute(
             r#"INSERT INTO links (base, target, link_type, tag, timestamp, deleted)
                VALUES (?1, ?2, ?3, ?4, ?5, 0)"#,
@@ -486,7 +720,7 @@ impl Storage {
                 link.base.as_bytes().as_ref(),
                 link.target.as_bytes().as_ref(),
                 link.link_type as i64,
-                &link.tag,
+                tag,
                 link.timestamp.0 as i64,
             ],
         )?;
@@ -548,9 +782,12 @@ impl Storage {
 
         for row_result in rows {
             let (base_bytes, target_bytes, link_type, tag, timestamp) = row_result?;
+            let base = Hash::from_raw(&base_bytes);
+            let target = Hash::from_raw(&target_bytes);
+            let tag = self.open_sealed("links", &link_key(&base, &target), tag)?;
             links.push(Link {
-                base: Hash::from_raw(&base_bytes),
-                target: Hash::from_raw(&target_bytes),
+                base,
+                target,
                 link_type: link_type as u8,
                 tag,
                 timestamp: Timestamp(timestamp as u64),
@@ -566,20 +803,19 @@ impl Storage {
 
     /// Set metadata value
     pub fn set_metadata(&self, key: &str, value: &str) -> Result<()> {
-        self.conn.execute(
-            "INSERT OR REPLACE INTO metadata (key, value) VALUES (?1, ?2)",
-            params![key, value],
-        )?;
-        Ok(())
+        match self.metadata_cipher(key) {
+            Some(cipher) => self.set_raw_metadata(key, &cipher.seal_text("metadata", key, value)?),
+            None => self.set_raw_metadata(key, value),
+        }
     }
 
     /// Get metadata value
     pub fn get_metadata(&self, key: &str) -> Result<Option<String>> {
-        let mut stmt = self
-            .conn
-            .prepare("SELECT value FROM metadata WHERE key = ?1")?;
-        let result: Option<String> = stmt.query_row(params![key], |row| row.get(0)).ok();
-        Ok(result)
+        let value = self.raw_metadata(key)?;
+        match (self.metadata_cipher(key), value) {
+            (Some(cipher), Some(value)) => Ok(Some(cipher.open_text("metadata", key, &value)?)),
+            (_, value) => Ok(value),
+        }
     }
 
     /// Delete metadata key
@@ -596,10 +832,206 @@ impl Storage {
 
         let mut result = Vec::new();
         for row in rows {
-            result.push(row?);
+            let (key, value): (String, String) = row?;
+            let value = match self.metadata_cipher(&key) {
+                Some(cipher) => cipher.open_text("metadata", &key, &value)?,
+                None => value,
+            };
+            result.push((key, value));
+        }
+        Ok(result)
+    }
+
+    // ========================================================================
+    // Encryption
+    // ========================================================================
+
+    /// Seal a payload stored under `key` in `table`, if the store is encrypted
+    fn seal(&self, table: &str, key: &[u8], data: Vec<u8>) -> Result<Vec<u8>> {
+        match &self.cipher {
+            Some(cipher) => cipher.seal(table, key, &data),
+            None => Ok(data),
+        }
+    }
+
+    /// Open a payload sealed by [`seal`](Self::seal)
+    fn open_sealed(&self, table: &str, key: &[u8], data: Vec<u8>) -> Result<Vec<u8>> {
+        match &self.cipher {
+            Some(cipher) => cipher.open(table, key, &data),
+            None => Ok(data),
+        }
+    }
+
+    /// The cipher for a metadata value, unless the key is kept in the clear
+    ///
+    /// The expiry counter is updated in SQL, and the key bookkeeping must be
+    /// readable before the key is checked.
+    fn metadata_cipher(&self, key: &str) -> Option<&RecordCipher> {
+        if key == EXPIRED_COUNT_KEY || storage_crypto::is_key_metadata(key) {
+            return None;
+        }
+        self.cipher.as_ref()
+    }
+
+    /// Re-seal values still sealed with a previous key
+    ///
+    /// See [`StorageBackend::reencrypt_batch`].
+    pub fn reencrypt_batch(&self, max_bytes: usize) -> Result<usize> {
+        let Some(cipher) = &self.cipher else {
+            return Ok(0);
+        };
+        let mut budget = max_bytes;
+
+        let mut resealed = self.reseal_rows(
+            "actions",
+            "SELECT rowid, hash, data FROM actions WHERE substr(data, 2, 4) != ?1",
+            "UPDATE actions SET data = ?1 WHERE rowid = ?2",
+            cipher,
+            &mut budget,
+            |row| row.get(1),
+        )?;
+        resealed += self.reseal_rows(
+            "entries",
+            "SELECT rowid, hash, content FROM entries WHERE substr(content, 2, 4) != ?1",
+            "UPDATE entries SET content = ?1 WHERE rowid = ?2",
+            cipher,
+            &mut budget,
+            |row| row.get(1),
+        )?;
+        resealed += self.reseal_rows(
+            "links",
+            r#"SELECT rowid, base, tag, target FROM links
+               WHERE tag IS NOT NULL AND substr(tag, 2, 4) != ?1"#,
+            "UPDATE links SET tag = ?1 WHERE rowid = ?2",
+            cipher,
+            &mut budget,
+            |row| {
+                let base: Vec<u8> = row.get(1)?;
+                let target: Vec<u8> = row.get(3)?;
+                Ok(link_key(&Hash::from_raw(&base), &Hash::from_raw(&target)))
+            },
+        )?;
+
+        // Sealed metadata values are text, so check the key id in Rust
+        let sealed: Vec<(String, String)> = {
+            let mut stmt = self
+                .conn
+                .prepare("SELECT key, value FROM metadata WHERE value LIKE 'enc1:%'")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            rows.collect::<std::result::Result<_, _>>()?
+        };
+        let stale = sealed
+            .into_iter()
+            .filter(|(_, value)| !cipher.is_current_text(value));
+        for (key, value) in stale {
+            if budget == 0 && resealed > 0 {
+                break;
+            }
+            let plaintext = cipher.open_text("metadata", &key, &value)?;
+            self.set_metadata(&key, &plaintext)?;
+            budget = budget.saturating_sub(value.len());
+            resealed += 1;
+        }
+
+        if resealed == 0 {
+            cipher.retire_previous_keys(self)?;
+        }
+        Ok(resealed)
+    }
+
+    /// Re-seal rows of one table selected by `select`
+    ///
+    /// `select` yields the rowid, the value at index 2 and whatever `key`
+    /// needs to rebuild the record key. Rows are loaded until `budget` is
+    /// spent; one row is always loaded so a large value cannot stall a
+    /// rotation.
+    fn reseal_rows(
+        &self,
+        table: &str,
+        select: &str,
+        update: &str,
+        cipher: &RecordCipher,
+        budget: &mut usize,
+        key: impl Fn(&Row<'_>) -> rusqlite::Result<Vec<u8>>,
+    ) -> Result<usize> {
+        if *budget == 0 {
+            return Ok(0);
+        }
+        let mut batch = Vec::new();
+        {
+            let mut stmt = self.conn.prepare_cached(select)?;
+            let mut rows = stmt.query(params![cipher.active_id_bytes().as_ref()])?;
+            while let Some(row) = rows.next()? {
+                let value: Vec<u8> = row.get(2)?;
+                *budget = budget.saturating_sub(value.len());
+                batch.push((row.get::<_, i64>(0)?, key(row)?, value));
+                if *budget == 0 {
+                    break;
+                }
+            }
+        }
+        if batch.is_empty() {
+            return Ok(0);
         }
+
+        self.conn.execute("BEGIN IMMEDIATE", [])?;
+        let result = (|| {
+            let mut stmt = self.conn.prepare_cached(update)?;
+            for (rowid, key, value) in &batch {
+                let plaintext = cipher.open(table, key, value)?;
+                stmt.execute(params![cipher.seal(table, key, &plaintext)?, rowid])?;
+            }
+            Ok(batch.len())
+        })();
+
+        match result {
+            Ok(resealed) => {
+                self.conn.execute("COMMIT", [])?;
+                Ok(resealed)
+            }
+            Err(e) => {
+                let _ = self.conn.execute("ROLLBACK", []);
+                Err(e)
+            }
+        }
+    }
+}
+
+/// Record key a link tag is sealed under
+fn link_key(base: &Hash, target: &Hash) -> Vec<u8> {
+    [base.as_bytes().as_ref(), target.as_bytes().as_ref()].concat()
+}
+
+impl KeyMetadata for Storage {
+    fn raw_metadata(&self, key: &str) -> Result<Option<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT value FROM metadata WHERE key = ?1")?;
+        let result: Option<String> = stmt.query_row(params![key], |row| row.get(0)).ok();
         Ok(result)
     }
+
+    fn set_raw_metadata(&self, key: &str, value: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO metadata (key, value) VALUES (?1, ?2)",
+            params![key, value],
+        )?;
+        Ok(())
+    }
+
+    fn delete_raw_metadata(&self, key: &str) -> Result<()> {
+        self.delete_metadata(key)
+    }
+
+    fn is_empty(&self) -> Result<bool> {
+        let rows: i64 = self.conn.query_row(
+            r#"SELECT (SELECT COUNT(*) FROM actions) + (SELECT COUNT(*) FROM entries)
+                    + (SELECT COUNT(*) FROM links) + (SELECT COUNT(*) FROM metadata)"#,
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(rows == 0)
+    }
 }
 
 // ============================================================================
@@ -623,6 +1055,14 @@ impl StorageBackend for Storage {
         Storage::put_records_batch(self, records)
     }
 
+    fn expire_records_batch(&self, hashes: &[Hash]) -> Result<usize> {
+        Storage::expire_records_batch(self, hashes)
+    }
+
+    fn put_record_with_priority(&self, record: &Record, priority: Option<u8>) -> Result<Hash> {
+        Storage::put_record_with_priority(self, record, priority)
+    }
+
     fn get_action(&self, hash: &Hash) -> Result<Option<Action>> {
         Storage::get_action(self, hash)
     }
@@ -672,9 +1112,21 @@ impl StorageBackend for Storage {
         Storage::vacuum(self)
     }
 
+    fn compact(&self) -> Result<()> {
+        Storage::compact(self)
+    }
+
+    fn enforce_budget(&self, max_bytes: usize, policy: EvictionPolicy) -> Result<usize> {
+        Storage::enforce_budget(self, max_bytes, policy)
+    }
+
     fn check_limits(&self) -> Result<bool> {
         Storage::check_limits(self)
     }
+
+    fn reencrypt_batch(&self, max_bytes: usize) -> Result<usize> {
+        Storage::reencrypt_batch(self, max_bytes)
+    }
 }
 
 #[cfg(test)]
@@ -774,6 +1226,199 @@ mod tests {
         assert_eq!(links.len(), 0);
     }
 
+    fn create_test_record(seq: u32) -> Record {
+        let mut content = seq.to_be_bytes().to_vec();
+        content.resize(2048, 0xAB);
+        let entry = Entry {
+            entry_type: EntryType::App,
+            content,
+        };
+        let mut action = create_test_action(seq);
+        action.entry_hash = Some(entry.hash());
+        Record {
+            action,
+            entry: Some(entry),
+        }
+    }
+
+    /// Stores records 1..=count, returning (seq, action hash, entry hash)
+    fn fill(storage: &Storage, count: u32, priority: impl Fn(u32) -> u8) -> Vec<(u32, Hash, Hash)> {
+        (1..=count)
+            .map(|seq| {
+                let record = create_test_record(seq);
+                let entry_hash = record.entry.as_ref().unwrap().hash();
+                let hash = storage
+                    .put_record_with_priority(&record, Some(priority(seq)))
+                    .unwrap();
+                (seq, hash, entry_hash)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_enforce_budget_oldest_first() {
+        let storage = Storage::memory().unwrap();
+        let records = fill(&storage, 40, |_| DEFAULT_PRIORITY);
+
+        let budget = storage.stats().unwrap().db_size / 2;
+        let evicted = storage
+            .enforce_budget(budget, EvictionPolicy::OldestFirst)
+            .unwrap();
+        assert!(evicted > 0);
+
+        for (seq, hash, entry_hash) in &records {
+            let retained = *seq > evicted as u32;
+            assert_eq!(storage.get_action(hash).unwrap().is_some(), retained);
+            assert_eq!(storage.get_entry(entry_hash).unwrap().is_some(), retained);
+        }
+
+        storage.compact().unwrap();
+        assert!(storage.stats().unwrap().db_size <= budget);
+    }
+
+    #[test]
+    fn test_enforce_budget_lowest_priority() {
+        let storage = Storage::memory().unwrap();
+        // Even sequence numbers are low priority
+        let records = fill(&storage, 40, |seq| if seq % 2 == 0 { 10 } else { 200 });
+
+        let budget = storage.stats().unwrap().db_size * 3 / 4;
+        let evicted = storage
+            .enforce_budget(budget, EvictionPolicy::LowestPriority)
+            .unwrap();
+        assert!(evicted > 0 && evicted < 20);
+
+        for (seq, hash, _) in &records {
+            let retained = seq % 2 == 1 || *seq > 2 * evicted as u32;
+            assert_eq!(storage.get_action(hash).unwrap().is_some(), retained);
+        }
+    }
+
+    #[test]
+    fn test_enforce_budget_honours_priority_classes() {
+        let class = |seq: u32| match seq % 3 {
+            0 => EntryPriority::Critical,
+            1 => EntryPriority::Normal,
+            _ => EntryPriority::Bulk,
+        };
+
+        for policy in [EvictionPolicy::OldestFirst, EvictionPolicy::LowestPriority] {
+            let storage = Storage::memory().unwrap();
+            let records = fill(&storage, 45, |seq| class(seq).eviction_priority());
+            let stats = storage.stats().unwrap();
+            assert_eq!(stats.by_priority.critical, 15);
+            assert_eq!(stats.by_priority.normal, 15);
+            assert_eq!(stats.by_priority.bulk, 15);
+
+            // More than the Bulk records have to go
+            let budget = stats.db_size * 4 / 5;
+            let evicted = storage.enforce_budget(budget, policy).unwrap();
+            assert!(
+                evicted > 15 && evicted < 30,
+                "{:?} evicted {}",
+                policy,
+                evicted
+            );
+
+            // Bulk goes first, then the oldest Normal records; Critical stays
+            let mut normal_evicted = 0;
+            for (seq, hash, _) in &records {
+                let retained = storage.get_action(hash).unwrap().is_some();
+                match class(*seq) {
+                    EntryPriority::Bulk => assert!(!retained, "bulk {} kept", seq),
+                    EntryPriority::Critical => assert!(retained, "critical {} evicted", seq),
+                    EntryPriority::Normal => {
+                        if !retained {
+                            normal_evicted += 1;
+                            assert_eq!(*seq, 3 * normal_evicted - 2, "normal out of order");
+                        }
+                    }
+                }
+            }
+            assert_eq!(normal_evicted, evicted as u32 - 15);
+
+            let stats = storage.stats().unwrap();
+            assert_eq!(stats.by_priority.bulk, 0);
+            assert_eq!(stats.by_priority.critical, 15);
+        }
+    }
+
+    #[test]
+    fn test_pruning_keeps_higher_priority_classes() {
+        let storage = Storage::open(StorageConfig {
+            db_path: ":memory:".to_string(),
+            keep_recent: 4,
+            aggressive_pruning: true,
+            ..StorageConfig::default()
+        })
+        .unwrap();
+
+        // Old critical records, then a stream of newer bulk samples
+        let records = fill(&storage, 12, |seq| {
+            if seq <= 3 {
+                EntryPriority::Critical.eviction_priority()
+            } else {
+                EntryPriority::Bulk.eviction_priority()
+            }
+        });
+
+        for (seq, hash, entry_hash) in &records {
+            let retained = *seq <= 3 || *seq == 12;
+            assert_eq!(storage.get_action(hash).unwrap().is_some(), retained);
+            assert_eq!(storage.get_entry(entry_hash).unwrap().is_some(), retained);
+        }
+    }
+
+    #[test]
+    fn test_enforce_budget_keeps_referenced_entries() {
+        let storage = Storage::memory().unwrap();
+        let records = fill(&storage, 10, |_| DEFAULT_PRIORITY);
+        let standalone = storage
+            .put_entry(&Entry {
+                entry_type: EntryType::App,
+                content: vec![0xCD; 2048],
+            })
+            .unwrap();
+
+        // The chain head shares the oldest action's entry
+        let shared = records[0].2.clone();
+        let mut head = create_test_action(11);
+        head.entry_hash = Some(shared.clone());
+        let head_hash = storage.put_action(&head).unwrap();
+
+        let evicted = storage
+            .enforce_budget(0, EvictionPolicy::OldestFirst)
+            .unwrap();
+        assert_eq!(evicted, 10);
+
+        assert!(storage.get_action(&head_hash).unwrap().is_some());
+        assert!(storage.get_entry(&shared).unwrap().is_some());
+        assert!(storage.get_entry(&standalone).unwrap().is_none());
+        for (_, hash, entry_hash) in &records[1..] {
+            assert!(storage.get_action(hash).unwrap().is_none());
+            assert!(storage.get_entry(entry_hash).unwrap().is_none());
+        }
+    }
+
+    #[test]
+    fn test_expire_records_batch() {
+        let storage = Storage::memory().unwrap();
+        let kept = storage.put_action(&create_test_action(1)).unwrap();
+        let expired = storage.put_action(&create_test_action(2)).unwrap();
+
+        let removed = storage
+            .expire_records_batch(&[expired.clone(), Hash::from_bytes(b"missing")])
+            .unwrap();
+        assert_eq!(removed, 1);
+        assert!(storage.get_action(&expired).unwrap().is_none());
+        assert!(storage.get_action(&kept).unwrap().is_some());
+
+        storage.expire_records_batch(&[kept]).unwrap();
+        let stats = storage.stats().unwrap();
+        assert_eq!(stats.action_count, 0);
+        assert_eq!(stats.expired_count, 2);
+    }
+
     #[test]
     fn test_get_links_by_type() {
         let storage = Storage::memory().unwrap();
@@ -957,4 +1602,150 @@ mod tests {
         let records = storage.get_records_by_seq_range(100, 200, 10).unwrap();
         assert!(records.is_empty());
     }
+
+    // ========================================================================
+    // Encryption Tests
+    // ========================================================================
+
+    use crate::error::{Error, StorageError};
+    use crate::storage_crypto::{EncryptionKey, KeyRotation, StorageEncryption};
+
+    fn encrypted_config(path: &str, encryption: StorageEncryption) -> StorageConfig {
+        StorageConfig {
+            encryption: Some(encryption),
+            aggressive_pruning: false,
+            ..StorageConfig::sqlite(path)
+        }
+    }
+
+    fn temp_db_path(name: &str) -> String {
+        let path = std::env::temp_dir().join(format!("{}_{}.db", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path.to_string_lossy().to_string()
+    }
+
+    /// Stores three records, a link and a metadata value
+    fn fill_encrypted(storage: &Storage) -> Vec<(Hash, Hash)> {
+        let hashes = (1..=3)
+            .map(|seq| {
+                let record = create_test_record(seq);
+                let entry_hash = record.entry.as_ref().unwrap().hash();
+                (storage.put_record(&record).unwrap(), entry_hash)
+            })
+            .collect();
+        storage.add_link(&create_test_link()).unwrap();
+        storage.set_metadata("node_id", "sensor-7").unwrap();
+        hashes
+    }
+
+    fn assert_intact(storage: &Storage, hashes: &[(Hash, Hash)]) {
+        for (seq, (hash, entry_hash)) in (1..).zip(hashes) {
+            assert_eq!(storage.get_action(hash).unwrap().unwrap().seq, seq);
+            let entry = storage.get_entry(entry_hash).unwrap().unwrap();
+            assert_eq!(
+                entry.content,
+                create_test_record(seq).entry.unwrap().content
+            );
+        }
+        let links = storage.get_links(&create_test_link().base, None).unwrap();
+        assert_eq!(links[0].tag, vec![1, 2, 3]);
+        assert_eq!(
+            storage.get_metadata("node_id").unwrap(),
+            Some("sensor-7".to_string())
+        );
+    }
+
+    #[test]
+    fn test_encrypted_reopen() {
+        let db_path = temp_db_path("aingle_test_encrypted");
+        let key = || StorageEncryption::new(EncryptionKey::new(1, [0x11; 32]));
+
+        let hashes = {
+            let storage = Storage::open(encrypted_config(&db_path, key())).unwrap();
+            fill_encrypted(&storage)
+        };
+
+        // Payloads are not on disk in the clear
+        let file = std::fs::read(&db_path).unwrap();
+        assert!(!file.windows(8).any(|w| w == b"sensor-7"));
+        assert!(!file.windows(64).any(|w| w.iter().all(|b| *b == 0xAB)));
+
+        let wrong = StorageEncryption::new(EncryptionKey::new(1, [0x22; 32]));
+        assert!(matches!(
+            Storage::open(encrypted_config(&db_path, wrong)),
+            Err(Error::Storage(StorageError::WrongEncryptionKey {
+                key_id: 1
+            }))
+        ));
+        assert!(matches!(
+            Storage::open(StorageConfig::sqlite(&db_path)),
+            Err(Error::Storage(StorageError::EncryptionMismatch { .. }))
+        ));
+
+        let storage = Storage::open(encrypted_config(&db_path, key())).unwrap();
+        assert_intact(&storage, &hashes);
+        assert_eq!(storage.reencrypt_batch(usize::MAX).unwrap(), 0);
+
+        drop(storage);
+        let _ = std::fs::remove_file(&db_path);
+    }
+
+    #[test]
+    fn test_encryption_rejects_plaintext_store() {
+        let db_path = temp_db_path("aingle_test_plaintext");
+        {
+            let storage = Storage::open(StorageConfig::sqlite(&db_path)).unwrap();
+            storage.put_action(&create_test_action(1)).unwrap();
+        }
+
+        let key = StorageEncryption::new(EncryptionKey::new(1, [0x11; 32]));
+        assert!(matches!(
+            Storage::open(encrypted_config(&db_path, key)),
+            Err(Error::Storage(StorageError::EncryptionMismatch { .. }))
+        ));
+        let _ = std::fs::remove_file(&db_path);
+    }
+
+    #[test]
+    fn test_key_rotation() {
+        let db_path = temp_db_path("aingle_test_rotation");
+        let old = || EncryptionKey::new(1, [0x11; 32]);
+        let new = || EncryptionKey::new(2, [0x22; 32]);
+
+        let hashes = {
+            let storage =
+                Storage::open(encrypted_config(&db_path, StorageEncryption::new(old()))).unwrap();
+            fill_encrypted(&storage)
+        };
+
+        {
+            let rotating = StorageEncryption::new(new()).with_previous_key(old());
+            let storage = Storage::open(encrypted_config(&db_path, rotating)).unwrap();
+            // Old records stay readable while the rotation runs
+            assert_intact(&storage, &hashes);
+
+            // The smallest batch re-seals one record per step
+            let mut rotation = KeyRotation::new(0);
+            assert!(!rotation.step(&storage).unwrap());
+            assert!(rotation.resealed() < 8);
+            rotation.run(&storage).unwrap();
+            assert!(rotation.is_finished());
+            // Actions, entries, the link and the metadata value
+            assert_eq!(rotation.resealed(), 8);
+            assert_intact(&storage, &hashes);
+        }
+
+        // The old key is retired and no longer needed
+        let storage =
+            Storage::open(encrypted_config(&db_path, StorageEncryption::new(new()))).unwrap();
+        assert_intact(&storage, &hashes);
+        drop(storage);
+        assert!(matches!(
+            Storage::open(encrypted_config(&db_path, StorageEncryption::new(old()))),
+            Err(Error::Storage(StorageError::WrongEncryptionKey {
+                key_id: 2
+            }))
+        ));
+        let _ = std::fs::remove_file(&db_path);
+    }
 }
@@ -0,0 +1,562 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Encryption at rest for storage backends
+//!
+//! When [`StorageConfig::encryption`](crate::StorageConfig::encryption) is
+//! set, every backend seals record payloads with ChaCha20-Poly1305 before
+//! writing them: action bodies, entry contents, link tags and metadata values.
+//! Each sealed value carries the id of the key that sealed it and a random
+//! nonce:
+//!
+//! ```text
+//! version u8 | key id u32 | nonce [12] | ciphertext | tag [16]
+//! ```
+//!
+//! The table name and the record's key are bound in as associated data, so a
+//! sealed value cannot be moved to another row without detection. Fields the
+//! backends index on (hashes, sequence numbers, timestamps) stay in the clear.
+//!
+//! A store remembers which key ids sealed it, with a check value per key.
+//! Opening it with a wrong key, without a key, or opening an unencrypted
+//! store with one fails with a [`StorageError`] before any record is read.
+//!
+//! # Key rotation
+//!
+//! Open the store with the new key and the old one as a previous key, then
+//! drive a [`KeyRotation`] until it finishes. Records are re-sealed a batch at
+//! a time, and once none is left under an old key, that key is retired and
+//! no longer needed to open the store.
+
+use crate::error::{CryptoError, Result, StorageError};
+use crate::storage_trait::StorageBackend;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use std::collections::HashMap;
+use zeroize::Zeroizing;
+
+/// Sealed value format version
+const FORMAT_VERSION: u8 = 1;
+const NONCE_LEN: usize = 12;
+/// Version and key id
+const HEADER_LEN: usize = 1 + 4;
+
+/// Prefix of sealed metadata values, which are stored as text
+const SEALED_TEXT_PREFIX: &str = "enc1:";
+
+/// Metadata key listing the ids of the keys that sealed the store
+pub(crate) const ENCRYPTION_KEYS_KEY: &str = "encryption_keys";
+/// Prefix of the metadata keys holding each key's check value
+const KEY_CHECK_PREFIX: &str = "encryption_check.";
+/// Plaintext sealed into each key's check value
+const KEY_CHECK_PLAINTEXT: &[u8] = b"aingle storage key check";
+
+/// Smallest batch a [`KeyRotation`] re-seals per step
+const MIN_ROTATION_BATCH_BYTES: usize = 4 * 1024;
+
+/// A 256-bit storage encryption key
+///
+/// The key material is wiped from memory when dropped.
+#[derive(Clone)]
+pub struct EncryptionKey {
+    id: u32,
+    key: Zeroizing<[u8; 32]>,
+}
+
+impl EncryptionKey {
+    /// Create a key from raw bytes, e.g. a device key
+    ///
+    /// `id` is stored with every record sealed under the key and must differ
+    /// between the keys of one store.
+    pub fn new(id: u32, key: [u8; 32]) -> Self {
+        Self {
+            id,
+            key: Zeroizing::new(key),
+        }
+    }
+
+    /// Derive a key from a passphrase with Argon2id
+    ///
+    /// The same passphrase and salt always give the same key. Use a value
+    /// unique to the device as salt, such as the node's public key; it must
+    /// be at least 8 bytes.
+    pub fn from_passphrase(id: u32, passphrase: &str, salt: &[u8]) -> Result<Self> {
+        let key = crate::crypto::derive_passphrase_key(passphrase, salt)?;
+        Ok(Self::new(id, key))
+    }
+
+    /// The key id
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+}
+
+impl std::fmt::Debug for EncryptionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EncryptionKey")
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Encryption settings for a store
+///
+/// New records are sealed with the active key. Previous keys only open
+/// records sealed before a rotation.
+#[derive(Debug, Clone)]
+pub struct StorageEncryption {
+    key: EncryptionKey,
+    previous: Vec<EncryptionKey>,
+}
+
+impl StorageEncryption {
+    /// Encrypt with `key`
+    pub fn new(key: EncryptionKey) -> Self {
+        Self {
+            key,
+            previous: Vec::new(),
+        }
+    }
+
+    /// Also accept `key` for records sealed before a rotation
+    pub fn with_previous_key(mut self, key: EncryptionKey) -> Self {
+        self.previous.push(key);
+        self
+    }
+
+    /// The key new records are sealed with
+    pub fn active_key(&self) -> &EncryptionKey {
+        &self.key
+    }
+
+    /// Whether any previous key is configured, i.e. a rotation may be pending
+    pub fn has_previous_keys(&self) -> bool {
+        !self.previous.is_empty()
+    }
+
+    fn keys(&self) -> impl Iterator<Item = &EncryptionKey> {
+        std::iter::once(&self.key).chain(&self.previous)
+    }
+}
+
+/// Unsealed access to a backend's metadata, used for the key bookkeeping
+pub(crate) trait KeyMetadata {
+    /// Read a metadata value as stored
+    fn raw_metadata(&self, key: &str) -> Result<Option<String>>;
+    /// Write a metadata value as given
+    fn set_raw_metadata(&self, key: &str, value: &str) -> Result<()>;
+    /// Delete a metadata value
+    fn delete_raw_metadata(&self, key: &str) -> Result<()>;
+    /// Whether the store holds no records or metadata at all
+    fn is_empty(&self) -> Result<bool>;
+}
+
+/// Whether `key` is bookkeeping for encryption, and so never sealed
+pub(crate) fn is_key_metadata(key: &str) -> bool {
+    key == ENCRYPTION_KEYS_KEY || key.starts_with(KEY_CHECK_PREFIX)
+}
+
+/// Seals and opens record payloads for one store
+pub(crate) struct RecordCipher {
+    active: u32,
+    ciphers: HashMap<u32, ChaCha20Poly1305>,
+}
+
+impl RecordCipher {
+    /// Check the configured keys against the store and build its cipher
+    ///
+    /// Returns `None` for an unencrypted store opened without encryption.
+    pub(crate) fn for_store(
+        encryption: Option<&StorageEncryption>,
+        store: &impl KeyMetadata,
+    ) -> Result<Option<Self>> {
+        let sealed_by = sealed_key_ids(store)?;
+        let Some(encryption) = encryption else {
+            if sealed_by.is_empty() {
+                return Ok(None);
+            }
+            return Err(StorageError::EncryptionMismatch {
+                reason: "store is encrypted but no key was given".to_string(),
+            }
+            .into());
+        };
+
+        let cipher = Self {
+            active: encryption.key.id,
+            ciphers: encryption
+                .keys()
+                .map(|k| (k.id, ChaCha20Poly1305::new(Key::from_slice(&*k.key))))
+                .collect(),
+        };
+
+        if sealed_by.is_empty() && !store.is_empty()? {
+            return Err(StorageError::EncryptionMismatch {
+                reason: "store holds unencrypted data".to_string(),
+            }
+            .into());
+        }
+        // Every key that sealed records must be present and correct
+        for id in &sealed_by {
+            if !cipher.ciphers.contains_key(id) {
+                return Err(StorageError::WrongEncryptionKey { key_id: *id }.into());
+            }
+            let check = store
+                .raw_metadata(&key_check_name(*id))?
+                .and_then(|check| hex::decode(check).ok())
+                .ok_or(StorageError::WrongEncryptionKey { key_id: *id })?;
+            match cipher.open(KEY_CHECK_PREFIX, &[], &check) {
+                Ok(plaintext) if plaintext == KEY_CHECK_PLAINTEXT => {}
+                _ => return Err(StorageError::WrongEncryptionKey { key_id: *id }.into()),
+            }
+        }
+
+        if !sealed_by.contains(&cipher.active) {
+            let check = cipher.seal(KEY_CHECK_PREFIX, &[], KEY_CHECK_PLAINTEXT)?;
+            store.set_raw_metadata(&key_check_name(cipher.active), &hex::encode(check))?;
+            let mut ids = sealed_by;
+            ids.push(cipher.active);
+            store.set_raw_metadata(ENCRYPTION_KEYS_KEY, &format_key_ids(&ids))?;
+        }
+        Ok(Some(cipher))
+    }
+
+    /// Seal `plaintext` stored under `key` in `table` with the active key
+    pub(crate) fn seal(&self, table: &str, key: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+        let nonce: [u8; NONCE_LEN] = crate::crypto::random_bytes();
+        let mut sealed = Vec::with_capacity(HEADER_LEN + NONCE_LEN + plaintext.len() + 16);
+        sealed.push(FORMAT_VERSION);
+        sealed.extend_from_slice(&self.active.to_be_bytes());
+
+        let aad = associated_data(&sealed, table, key);
+        let ciphertext = self.ciphers[&self.active]
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: plaintext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| CryptoError::EncryptionFailed(format!("could not seal {}", table)))?;
+        sealed.extend_from_slice(&nonce);
+        sealed.extend_from_slice(&ciphertext);
+        Ok(sealed)
+    }
+
+    /// Open a value sealed under `key` in `table`
+    ///
+    /// Fails with [`StorageError::CorruptedData`] if the value was not sealed
+    /// by a known key or was altered.
+    pub(crate) fn open(&self, table: &str, key: &[u8], sealed: &[u8]) -> Result<Vec<u8>> {
+        let corrupted = |reason: &str| StorageError::CorruptedData {
+            table: table.to_string(),
+            reason: reason.to_string(),
+        };
+        let key_id = sealed_key_id(sealed).ok_or_else(|| corrupted("value is not sealed"))?;
+        let cipher = self
+            .ciphers
+            .get(&key_id)
+            .ok_or_else(|| corrupted(&format!("sealed with unknown key {}", key_id)))?;
+
+        let (header, rest) = sealed.split_at(HEADER_LEN);
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+        let aad = associated_data(header, table, key);
+        let plaintext = cipher
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| corrupted("authentication failed"))?;
+        Ok(plaintext)
+    }
+
+    /// Seal a metadata value, returning it as text
+    pub(crate) fn seal_text(&self, table: &str, key: &str, value: &str) -> Result<String> {
+        let sealed = self.seal(table, key.as_bytes(), value.as_bytes())?;
+        Ok(format!("{}{}", SEALED_TEXT_PREFIX, hex::encode(sealed)))
+    }
+
+    /// Open a metadata value sealed by [`seal_text`](Self::seal_text)
+    pub(crate) fn open_text(&self, table: &str, key: &str, value: &str) -> Result<String> {
+        let sealed = value
+            .strip_prefix(SEALED_TEXT_PREFIX)
+            .and_then(|hex_value| hex::decode(hex_value).ok())
+            .ok_or_else(|| StorageError::CorruptedData {
+                table: table.to_string(),
+                reason: format!("metadata '{}' is not sealed", key),
+            })?;
+        let plaintext = self.open(table, key.as_bytes(), &sealed)?;
+        String::from_utf8(plaintext).map_err(|_| {
+            StorageError::CorruptedData {
+                table: table.to_string(),
+                reason: format!("metadata '{}' is not UTF-8", key),
+            }
+            .into()
+        })
+    }
+
+    /// Whether `sealed` is sealed with the active key
+    ///
+    /// SQLite filters on the key id in queries; RocksDB checks each value.
+    #[cfg_attr(not(feature = "rocksdb"), allow(dead_code))]
+    pub(crate) fn is_current(&self, sealed: &[u8]) -> bool {
+        sealed_key_id(sealed) == Some(self.active)
+    }
+
+    /// Whether the sealed metadata text is sealed with the active key
+    pub(crate) fn is_current_text(&self, value: &str) -> bool {
+        value
+            .strip_prefix(SEALED_TEXT_PREFIX)
+            .and_then(|hex_value| hex::decode(hex_value.get(..2 * HEADER_LEN)?).ok())
+            .is_some_and(|header| header_key_id(&header) == Some(self.active))
+    }
+
+    /// The active key id, big-endian, as it appears in sealed values
+    pub(crate) fn active_id_bytes(&self) -> [u8; 4] {
+        self.active.to_be_bytes()
+    }
+
+    /// Forget every key but the active one once no record needs it
+    pub(crate) fn retire_previous_keys(&self, store: &impl KeyMetadata) -> Result<()> {
+        let ids = sealed_key_ids(store)?;
+        if ids.iter().all(|id| *id == self.active) {
+            return Ok(());
+        }
+        for id in ids.iter().filter(|id| **id != self.active) {
+            store.delete_raw_metadata(&key_check_name(*id))?;
+        }
+        store.set_raw_metadata(ENCRYPTION_KEYS_KEY, &format_key_ids(&[self.active]))
+    }
+}
+
+/// Re-seals a store's records under its active key, a batch at a time
+///
+/// Each [`step`](Self::step) loads at most `batch_bytes` of sealed data, so a
+/// rotation can run from the node's main loop without exceeding its memory
+/// budget.
+#[derive(Debug, Clone)]
+pub struct KeyRotation {
+    batch_bytes: usize,
+    resealed: usize,
+    finished: bool,
+}
+
+impl KeyRotation {
+    /// A rotation whose batches use at most a quarter of `memory_limit`
+    pub fn new(memory_limit: usize) -> Self {
+        Self {
+            batch_bytes: (memory_limit / 4).max(MIN_ROTATION_BATCH_BYTES),
+            resealed: 0,
+            finished: false,
+        }
+    }
+
+    /// Re-seal one batch, returning `true` once the rotation is finished
+    pub fn step<B: StorageBackend + ?Sized>(&mut self, storage: &B) -> Result<bool> {
+        if self.finished {
+            return Ok(true);
+        }
+        let resealed = storage.reencrypt_batch(self.batch_bytes)?;
+        self.resealed += resealed;
+        self.finished = resealed == 0;
+        Ok(self.finished)
+    }
+
+    /// Run the rotation to completion
+    pub fn run<B: StorageBackend + ?Sized>(&mut self, storage: &B) -> Result<usize> {
+        while !self.step(storage)? {}
+        Ok(self.resealed)
+    }
+
+    /// Records re-sealed so far
+    pub fn resealed(&self) -> usize {
+        self.resealed
+    }
+
+    /// Whether every record is sealed with the active key
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+}
+
+fn associated_data(header: &[u8], table: &str, key: &[u8]) -> Vec<u8> {
+    let mut aad = Vec::with_capacity(header.len() + table.len() + 1 + key.len());
+    aad.extend_from_slice(header);
+    aad.extend_from_slice(table.as_bytes());
+    aad.push(0);
+    aad.extend_from_slice(key);
+    aad
+}
+
+fn sealed_key_id(sealed: &[u8]) -> Option<u32> {
+    if sealed.len() < HEADER_LEN + NONCE_LEN + 16 {
+        return None;
+    }
+    header_key_id(&sealed[..HEADER_LEN])
+}
+
+fn header_key_id(header: &[u8]) -> Option<u32> {
+    if header.len() != HEADER_LEN || header[0] != FORMAT_VERSION {
+        return None;
+    }
+    Some(u32::from_be_bytes(header[1..].try_into().ok()?))
+}
+
+fn key_check_name(id: u32) -> String {
+    format!("{}{}", KEY_CHECK_PREFIX, id)
+}
+
+fn sealed_key_ids(store: &impl KeyMetadata) -> Result<Vec<u32>> {
+    let Some(ids) = store.raw_metadata(ENCRYPTION_KEYS_KEY)? else {
+        return Ok(Vec::new());
+    };
+    ids.split(',')
+        .filter(|id| !id.is_empty())
+        .map(|id| {
+            id.parse().map_err(|_| {
+                StorageError::CorruptedData {
+                    table: "metadata".to_string(),
+                    reason: format!("invalid encryption key id '{}'", id),
+                }
+                .into()
+            })
+        })
+        .collect()
+}
+
+fn format_key_ids(ids: &[u32]) -> String {
+    ids.iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use std::cell::RefCell;
+
+    #[derive(Default)]
+    struct MemoryMetadata {
+        values: RefCell<HashMap<String, String>>,
+        has_records: bool,
+    }
+
+    impl KeyMetadata for MemoryMetadata {
+        fn raw_metadata(&self, key: &str) -> Result<Option<String>> {
+            Ok(self.values.borrow().get(key).cloned())
+        }
+
+        fn set_raw_metadata(&self, key: &str, value: &str) -> Result<()> {
+            self.values
+                .borrow_mut()
+                .insert(key.to_string(), value.to_string());
+            Ok(())
+        }
+
+        fn delete_raw_metadata(&self, key: &str) -> Result<()> {
+            self.values.borrow_mut().remove(key);
+            Ok(())
+        }
+
+        fn is_empty(&self) -> Result<bool> {
+            Ok(!self.has_records && self.values.borrow().is_empty())
+        }
+    }
+
+    fn encryption(id: u32, byte: u8) -> StorageEncryption {
+        StorageEncryption::new(EncryptionKey::new(id, [byte; 32]))
+    }
+
+    #[test]
+    fn test_seal_open_roundtrip() {
+        let store = MemoryMetadata::default();
+        let cipher = RecordCipher::for_store(Some(&encryption(1, 7)), &store)
+            .unwrap()
+            .unwrap();
+
+        let sealed = cipher.seal("entries", b"hash", b"reading 23.5").unwrap();
+        assert!(!sealed.windows(4).any(|w| w == b"23.5"));
+        assert_eq!(
+            cipher.open("entries", b"hash", &sealed).unwrap(),
+            b"reading 23.5"
+        );
+        assert!(cipher.is_current(&sealed));
+
+        // Bound to its row: another key or table does not open it
+        assert!(cipher.open("entries", b"other", &sealed).is_err());
+        assert!(cipher.open("actions", b"hash", &sealed).is_err());
+
+        let text = cipher.seal_text("metadata", "peers", "[]").unwrap();
+        assert!(cipher.is_current_text(&text));
+        assert_eq!(cipher.open_text("metadata", "peers", &text).unwrap(), "[]");
+        assert!(cipher.open_text("metadata", "peers", "[]").is_err());
+    }
+
+    #[test]
+    fn test_store_key_checks() {
+        let store = MemoryMetadata::default();
+        RecordCipher::for_store(Some(&encryption(1, 7)), &store).unwrap();
+
+        assert!(RecordCipher::for_store(Some(&encryption(1, 7)), &store).is_ok());
+        assert!(matches!(
+            RecordCipher::for_store(Some(&encryption(1, 8)), &store),
+            Err(Error::Storage(StorageError::WrongEncryptionKey {
+                key_id: 1
+            }))
+        ));
+        assert!(matches!(
+            RecordCipher::for_store(Some(&encryption(2, 7)), &store),
+            Err(Error::Storage(StorageError::WrongEncryptionKey {
+                key_id: 1
+            }))
+        ));
+        assert!(matches!(
+            RecordCipher::for_store(None, &store),
+            Err(Error::Storage(StorageError::EncryptionMismatch { .. }))
+        ));
+
+        let plain = MemoryMetadata {
+            has_records: true,
+            ..Default::default()
+        };
+        assert!(RecordCipher::for_store(None, &plain).unwrap().is_none());
+        assert!(matches!(
+            RecordCipher::for_store(Some(&encryption(1, 7)), &plain),
+            Err(Error::Storage(StorageError::EncryptionMismatch { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_rotation_keys() {
+        let store = MemoryMetadata::default();
+        let old = RecordCipher::for_store(Some(&encryption(1, 7)), &store)
+            .unwrap()
+            .unwrap();
+        let sealed = old.seal("entries", b"hash", b"old").unwrap();
+
+        let rotated = encryption(2, 9).with_previous_key(EncryptionKey::new(1, [7; 32]));
+        let new = RecordCipher::for_store(Some(&rotated), &store)
+            .unwrap()
+            .unwrap();
+        assert!(!new.is_current(&sealed));
+        assert_eq!(new.open("entries", b"hash", &sealed).unwrap(), b"old");
+
+        // Until retired, the old key is still required
+        assert!(RecordCipher::for_store(Some(&encryption(2, 9)), &store).is_err());
+        new.retire_previous_keys(&store).unwrap();
+        assert!(RecordCipher::for_store(Some(&encryption(2, 9)), &store).is_ok());
+    }
+
+    #[test]
+    fn test_key_debug_is_redacted() {
+        let key = EncryptionKey::new(3, [0xAB; 32]);
+        let debug = format!("{:?}", key);
+        assert!(debug.contains("id: 3"));
+        assert!(!debug.to_lowercase().contains("ab, "));
+        assert!(!debug.contains("171"));
+    }
+}
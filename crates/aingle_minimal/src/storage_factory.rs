@@ -9,7 +9,7 @@ use crate::config::{StorageBackendType, StorageConfig};
 #[allow(unused_imports)]
 use crate::error::Error;
 use crate::error::Result;
-use crate::storage_trait::{StorageBackend, StorageStats};
+use crate::storage_trait::{EvictionPolicy, StorageBackend, StorageStats};
 use crate::types::{Action, Entry, Hash, Link, Record};
 
 /// Dynamic storage wrapper that can hold any backend
@@ -118,6 +118,24 @@ impl StorageBackend for DynamicStorage {
         }
     }
 
+    fn put_record_with_priority(&self, record: &Record, priority: Option<u8>) -> Result<Hash> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            DynamicStorage::Sqlite(s) => s.put_record_with_priority(record, priority),
+            #[cfg(feature = "rocksdb")]
+            DynamicStorage::Rocksdb(s) => s.put_record_with_priority(record, priority),
+        }
+    }
+
+    fn expire_records_batch(&self, hashes: &[Hash]) -> Result<usize> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            DynamicStorage::Sqlite(s) => s.expire_records_batch(hashes),
+            #[cfg(feature = "rocksdb")]
+            DynamicStorage::Rocksdb(s) => s.expire_records_batch(hashes),
+        }
+    }
+
     fn get_action(&self, hash: &Hash) -> Result<Option<Action>> {
         match self {
             #[cfg(feature = "sqlite")]
@@ -222,6 +240,24 @@ impl StorageBackend for DynamicStorage {
         }
     }
 
+    fn compact(&self) -> Result<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            DynamicStorage::Sqlite(s) => s.compact(),
+            #[cfg(feature = "rocksdb")]
+            DynamicStorage::Rocksdb(s) => s.compact(),
+        }
+    }
+
+    fn enforce_budget(&self, max_bytes: usize, policy: EvictionPolicy) -> Result<usize> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            DynamicStorage::Sqlite(s) => s.enforce_budget(max_bytes, policy),
+            #[cfg(feature = "rocksdb")]
+            DynamicStorage::Rocksdb(s) => s.enforce_budget(max_bytes, policy),
+        }
+    }
+
     fn check_limits(&self) -> Result<bool> {
         match self {
             #[cfg(feature = "sqlite")]
@@ -230,6 +266,15 @@ impl StorageBackend for DynamicStorage {
             DynamicStorage::Rocksdb(s) => s.check_limits(),
         }
     }
+
+    fn reencrypt_batch(&self, max_bytes: usize) -> Result<usize> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            DynamicStorage::Sqlite(s) => s.reencrypt_batch(max_bytes),
+            #[cfg(feature = "rocksdb")]
+            DynamicStorage::Rocksdb(s) => s.reencrypt_batch(max_bytes),
+        }
+    }
 }
 
 #[cfg(test)]
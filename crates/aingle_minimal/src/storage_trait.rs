@@ -10,6 +10,130 @@ use crate::error::Result;
 use crate::types::{Action, Entry, Hash, Link, Record};
 use serde::{Deserialize, Serialize};
 
+/// Eviction priority given to records stored without one
+pub const DEFAULT_PRIORITY: u8 = 128;
+
+/// Priorities at or above this belong to [`EntryPriority::Critical`]
+const CRITICAL_THRESHOLD: u8 = 160;
+/// Priorities at or above this (and below the critical threshold) belong to
+/// [`EntryPriority::Normal`]; anything lower is [`EntryPriority::Bulk`]
+const NORMAL_THRESHOLD: u8 = 96;
+
+/// Importance class of an entry
+///
+/// Classes decide which records survive storage budget enforcement (Bulk is
+/// evicted before Normal, Normal before Critical) and how urgently new
+/// entries are announced over gossip. Each class is stored as an eviction
+/// priority; arbitrary priorities map back to the class whose band they fall in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryPriority {
+    /// Configuration and other entries that must outlive everything else
+    Critical,
+    /// Regular application data
+    #[default]
+    Normal,
+    /// High-volume data that is cheap to lose, such as routine sensor samples
+    Bulk,
+}
+
+impl EntryPriority {
+    /// All classes, most important first
+    pub const ALL: [EntryPriority; 3] = [
+        EntryPriority::Critical,
+        EntryPriority::Normal,
+        EntryPriority::Bulk,
+    ];
+
+    /// The eviction priority stored for this class (higher is kept longer)
+    pub fn eviction_priority(self) -> u8 {
+        match self {
+            EntryPriority::Critical => 192,
+            EntryPriority::Normal => DEFAULT_PRIORITY,
+            EntryPriority::Bulk => 64,
+        }
+    }
+
+    /// The class an eviction priority belongs to
+    pub fn from_eviction_priority(priority: u8) -> Self {
+        if priority >= CRITICAL_THRESHOLD {
+            EntryPriority::Critical
+        } else if priority >= NORMAL_THRESHOLD {
+            EntryPriority::Normal
+        } else {
+            EntryPriority::Bulk
+        }
+    }
+
+    /// SQL expression ranking `column` by class (0 = Bulk, 2 = Critical)
+    pub(crate) fn sql_class_rank(column: &str) -> String {
+        format!(
+            "CASE WHEN {column} >= {CRITICAL_THRESHOLD} THEN 2 WHEN {column} >= {NORMAL_THRESHOLD} THEN 1 ELSE 0 END"
+        )
+    }
+
+    /// Rank of the class for eviction order (0 = Bulk, evicted first)
+    #[cfg(feature = "rocksdb")]
+    pub(crate) fn eviction_rank(self) -> u8 {
+        match self {
+            EntryPriority::Bulk => 0,
+            EntryPriority::Normal => 1,
+            EntryPriority::Critical => 2,
+        }
+    }
+}
+
+/// Counts broken down by [`EntryPriority`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PriorityCounts {
+    /// Count for [`EntryPriority::Critical`]
+    pub critical: u64,
+    /// Count for [`EntryPriority::Normal`]
+    pub normal: u64,
+    /// Count for [`EntryPriority::Bulk`]
+    pub bulk: u64,
+}
+
+impl PriorityCounts {
+    /// The count for `priority`
+    pub fn get(&self, priority: EntryPriority) -> u64 {
+        match priority {
+            EntryPriority::Critical => self.critical,
+            EntryPriority::Normal => self.normal,
+            EntryPriority::Bulk => self.bulk,
+        }
+    }
+
+    /// Add `n` to the count for `priority`
+    pub fn add(&mut self, priority: EntryPriority, n: u64) {
+        match priority {
+            EntryPriority::Critical => self.critical += n,
+            EntryPriority::Normal => self.normal += n,
+            EntryPriority::Bulk => self.bulk += n,
+        }
+    }
+
+    /// Sum over all classes
+    pub fn total(&self) -> u64 {
+        self.critical + self.normal + self.bulk
+    }
+}
+
+/// Order in which records are evicted when storage exceeds its budget
+///
+/// Both policies honour [`EntryPriority`] classes: every Bulk action goes
+/// before any Normal one, and every Normal action before any Critical one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EvictionPolicy {
+    /// Evict the oldest actions (lowest sequence number) first within each
+    /// priority class
+    #[default]
+    OldestFirst,
+    /// Evict the lowest-priority actions first, oldest first within a priority
+    LowestPriority,
+}
+
 /// Trait for storage backends - enables different implementations
 /// (SQLite, RocksDB, LMDB, Memory, etc.)
 ///
@@ -35,6 +159,18 @@ pub trait StorageBackend: Send {
         records.iter().map(|r| self.put_record(r)).collect()
     }
 
+    /// Delete expired records by hash in a single transaction
+    ///
+    /// Each hash may name an action or an entry. Removed rows are added to
+    /// [`StorageStats::expired_count`]. Returns the number of rows removed.
+    fn expire_records_batch(&self, hashes: &[Hash]) -> Result<usize>;
+
+    /// Store a record with an eviction priority (higher is kept longer)
+    ///
+    /// `None` uses [`DEFAULT_PRIORITY`]. Priorities only matter to
+    /// [`EvictionPolicy::LowestPriority`].
+    fn put_record_with_priority(&self, record: &Record, priority: Option<u8>) -> Result<Hash>;
+
     /// Get action by hash
     fn get_action(&self, hash: &Hash) -> Result<Option<Action>>;
 
@@ -76,8 +212,37 @@ pub trait StorageBackend: Send {
     /// Vacuum/compact the storage
     fn vacuum(&self) -> Result<()>;
 
+    /// Reclaim space freed by deletions
+    ///
+    /// Called after every budget enforcement, so it should be cheap when
+    /// there is nothing to reclaim.
+    fn compact(&self) -> Result<()>;
+
+    /// Evict records until the live data fits in `max_bytes`
+    ///
+    /// Actions are evicted in `policy` order. The DAG stays consistent:
+    ///
+    /// - An entry is deleted together with the last action that references
+    ///   it, never while a retained action still points at it.
+    /// - The chain head (the highest sequence number) is never evicted, so
+    ///   new actions keep a monotonic sequence.
+    /// - Entries no action references are evicted last, once no more
+    ///   actions can go.
+    ///
+    /// Space is not returned to the filesystem until [`compact`](Self::compact)
+    /// runs. Returns the number of actions evicted.
+    fn enforce_budget(&self, max_bytes: usize, policy: EvictionPolicy) -> Result<usize>;
+
     /// Check if storage is within size limits
     fn check_limits(&self) -> Result<bool>;
+
+    /// Re-seal records still sealed with a previous encryption key
+    ///
+    /// Loads at most about `max_bytes` of sealed data per call and returns
+    /// the number of values re-sealed under the active key. Once none is
+    /// left, previous keys are retired and `0` is returned. Unencrypted
+    /// stores always return `0`. See [`KeyRotation`](crate::KeyRotation).
+    fn reencrypt_batch(&self, max_bytes: usize) -> Result<usize>;
 }
 
 /// Storage statistics
@@ -91,4 +256,10 @@ pub struct StorageStats {
     pub link_count: u64,
     /// Database size in bytes
     pub db_size: usize,
+    /// Actions and entries deleted because their triples expired
+    #[serde(default)]
+    pub expired_count: u64,
+    /// Stored actions by the priority class of their entry
+    #[serde(default)]
+    pub by_priority: PriorityCounts,
 }
@@ -14,15 +14,24 @@
 //! 4. Node B stores records and updates its bloom filter
 //!
 //! This is a pull-based protocol to minimize bandwidth usage.
-
-use crate::error::Result;
-use crate::gossip::{BloomFilter, GossipManager};
+//!
+//! # Peer Limits
+//!
+//! Inbound requests are metered per peer with a token bucket. Protocol
+//! violations add to a per-peer misbehavior score that decays over time;
+//! peers whose score crosses the configured threshold are temporarily banned.
+//! See [`PeerLimitConfig`].
+
+use crate::config::PeerLimitConfig;
+use crate::error::{Error, GossipError, Result};
+use crate::gossip::{BloomFilter, GossipManager, TokenBucket};
 use crate::network::{Message, Network};
 use crate::storage_trait::StorageBackend;
 use crate::types::{Hash, Record};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::net::SocketAddr;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 /// Maximum records to send in a single batch
 const MAX_BATCH_SIZE: usize = 50;
@@ -30,6 +39,88 @@ const MAX_BATCH_SIZE: usize = 50;
 /// Maximum records to request at once
 const MAX_REQUEST_SIZE: usize = 100;
 
+/// Metadata key for persisted peer bans
+pub const BANNED_PEERS_METADATA_KEY: &str = "banned_peers";
+
+/// A protocol violation committed by a peer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Violation {
+    /// Message that could not be decoded or whose content doesn't match its hash
+    Malformed,
+    /// Request or response outside the protocol's size limits
+    ImpossibleRange,
+    /// Record whose signature doesn't verify against its author
+    InvalidSignature,
+    /// Request refused because the peer's request bucket was empty
+    RateLimited,
+}
+
+impl Violation {
+    /// Misbehavior score added for this violation
+    pub fn penalty(&self) -> f64 {
+        match self {
+            Violation::Malformed => 20.0,
+            Violation::ImpossibleRange => 25.0,
+            Violation::InvalidSignature => 50.0,
+            Violation::RateLimited => 5.0,
+        }
+    }
+}
+
+/// A peer ban as persisted in storage metadata
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BannedPeer {
+    /// The peer's network address (IP:port)
+    pub addr: String,
+    /// Unix time (seconds) when the ban expires
+    pub banned_until_secs: u64,
+    /// Misbehavior score at the time of the ban
+    pub score: f64,
+}
+
+/// Read the persisted ban list from storage
+///
+/// Expired bans are included; callers compare `banned_until_secs` against the
+/// current time.
+pub fn load_banned_peers<S: StorageBackend>(storage: &S) -> Result<Vec<BannedPeer>> {
+    match storage.get_metadata(BANNED_PEERS_METADATA_KEY)? {
+        Some(json) => serde_json::from_str(&json).map_err(|e| Error::Serialization(e.to_string())),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Per-peer request and misbehavior counters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeerStats {
+    /// The peer's network address
+    pub addr: SocketAddr,
+    /// Sync requests received from the peer
+    pub requests_received: u32,
+    /// Sync requests refused by the rate limiter
+    pub requests_throttled: u32,
+    /// Protocol violations committed by the peer
+    pub violations: u32,
+    /// Current (decayed) misbehavior score
+    pub misbehavior_score: f64,
+    /// Time left on the peer's ban, if banned
+    pub banned_for: Option<Duration>,
+}
+
+/// An active ban
+#[derive(Debug, Clone, Copy)]
+struct Ban {
+    until: Instant,
+    score: f64,
+}
+
+fn unix_now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
 /// Sync state for a peer
 #[derive(Debug, Clone)]
 pub struct PeerSyncState {
@@ -47,6 +138,18 @@ pub struct PeerSyncState {
     pub successful_syncs: u32,
     /// Number of failed syncs
     pub failed_syncs: u32,
+    /// Sync requests received from this peer
+    pub requests_received: u32,
+    /// Sync requests refused by the rate limiter
+    pub requests_throttled: u32,
+    /// Protocol violations committed by this peer
+    pub violations: u32,
+    /// Misbehavior score as of the last penalty (see [`Self::score_at`])
+    pub misbehavior_score: f64,
+    /// When `misbehavior_score` was last updated
+    score_updated: Instant,
+    /// Request rate limiter, created on the first request
+    request_bucket: Option<TokenBucket>,
 }
 
 impl PeerSyncState {
@@ -59,9 +162,31 @@ impl PeerSyncState {
             pending_requests: Vec::new(),
             successful_syncs: 0,
             failed_syncs: 0,
+            requests_received: 0,
+            requests_throttled: 0,
+            violations: 0,
+            misbehavior_score: 0.0,
+            score_updated: Instant::now(),
+            request_bucket: None,
         }
     }
 
+    /// Misbehavior score decayed to `now`
+    ///
+    /// The score halves every `half_life`.
+    pub fn score_at(&self, half_life: Duration, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(self.score_updated);
+        let half_lives = elapsed.as_secs_f64() / half_life.as_secs_f64().max(f64::EPSILON);
+        self.misbehavior_score * 0.5f64.powf(half_lives)
+    }
+
+    /// Add a penalty to the decayed score and return the new score
+    fn penalize(&mut self, penalty: f64, half_life: Duration, now: Instant) -> f64 {
+        self.misbehavior_score = self.score_at(half_life, now) + penalty;
+        self.score_updated = now;
+        self.misbehavior_score
+    }
+
     /// Check if we should sync with this peer
     pub fn should_sync(&self, min_interval: Duration) -> bool {
         self.last_sync.elapsed() >= min_interval
@@ -97,16 +222,30 @@ pub struct SyncManager {
     local_hashes: Vec<Hash>,
     /// Maximum hashes to track
     max_local_hashes: usize,
+    /// Per-peer request limits and ban policy
+    limits: PeerLimitConfig,
+    /// Banned peers
+    bans: HashMap<SocketAddr, Ban>,
+    /// Whether `bans` changed since it was last persisted
+    bans_dirty: bool,
 }
 
 impl SyncManager {
-    /// Create a new sync manager
+    /// Create a new sync manager with default peer limits
     pub fn new(sync_interval: Duration) -> Self {
+        Self::with_limits(sync_interval, PeerLimitConfig::default())
+    }
+
+    /// Create a new sync manager with the given peer limits
+    pub fn with_limits(sync_interval: Duration, limits: PeerLimitConfig) -> Self {
         Self {
             peer_states: HashMap::new(),
             sync_interval,
             local_hashes: Vec::with_capacity(1000),
             max_local_hashes: 10000,
+            limits,
+            bans: HashMap::new(),
+            bans_dirty: false,
         }
     }
 
@@ -125,14 +264,234 @@ impl SyncManager {
     }
 
     /// Get peers that need syncing
+    ///
+    /// Banned peers are skipped.
     pub fn peers_needing_sync(&self) -> Vec<SocketAddr> {
+        let now = Instant::now();
         self.peer_states
             .iter()
-            .filter(|(_, state)| state.should_sync(self.sync_interval))
+            .filter(|(addr, state)| {
+                state.should_sync(self.sync_interval) && !self.is_banned(addr, now)
+            })
             .map(|(addr, _)| *addr)
             .collect()
     }
 
+    /// Admit an inbound sync request from a peer
+    ///
+    /// Fails with [`GossipError::PeerBlacklisted`] for banned peers and with
+    /// [`GossipError::RateLimitExceeded`] when the peer's request bucket is
+    /// empty; the latter also counts against the peer's misbehavior score.
+    pub fn check_request(
+        &mut self,
+        addr: &SocketAddr,
+        now: Instant,
+    ) -> std::result::Result<(), GossipError> {
+        if self.is_banned(addr, now) {
+            return Err(GossipError::PeerBlacklisted {
+                peer_id: addr.to_string(),
+            });
+        }
+
+        let burst = f64::from(self.limits.request_burst);
+        let rate = self.limits.requests_per_sec;
+        let state = self.peer_states.entry(*addr).or_default();
+        state.requests_received += 1;
+        let bucket = state
+            .request_bucket
+            .get_or_insert_with(|| TokenBucket::with_params(burst, rate));
+        if bucket.try_consume_at(1.0, now) {
+            return Ok(());
+        }
+
+        self.report_violation(addr, Violation::RateLimited, now);
+        Err(GossipError::RateLimitExceeded {
+            wait_ms: (1000.0 / rate).ceil() as u64,
+        })
+    }
+
+    /// Record a violation by a peer
+    ///
+    /// Returns `true` if the violation pushed the peer's score over the ban
+    /// threshold and the peer was banned.
+    pub fn report_violation(
+        &mut self,
+        addr: &SocketAddr,
+        violation: Violation,
+        now: Instant,
+    ) -> bool {
+        let half_life = self.limits.score_half_life;
+        let state = self.peer_states.entry(*addr).or_default();
+        if violation == Violation::RateLimited {
+            state.requests_throttled += 1;
+        } else {
+            state.violations += 1;
+        }
+        let score = state.penalize(violation.penalty(), half_life, now);
+        log::debug!(
+            "Peer {} committed {:?} (misbehavior score {:.1})",
+            addr,
+            violation,
+            score
+        );
+
+        if score < self.limits.ban_threshold || self.is_banned(addr, now) {
+            return false;
+        }
+
+        self.bans.retain(|_, ban| ban.until > now);
+        self.trim_bans(self.limits.max_banned.saturating_sub(1));
+        self.bans.insert(
+            *addr,
+            Ban {
+                until: now + self.limits.ban_duration,
+                score,
+            },
+        );
+        self.bans_dirty = true;
+        log::warn!(
+            "Banned peer {} for {:?} (misbehavior score {:.1})",
+            addr,
+            self.limits.ban_duration,
+            score
+        );
+        true
+    }
+
+    /// Drop the bans closest to expiry until at most `max` remain
+    fn trim_bans(&mut self, max: usize) {
+        while self.bans.len() > max {
+            let soonest = self
+                .bans
+                .iter()
+                .min_by_key(|(_, ban)| ban.until)
+                .map(|(addr, _)| *addr);
+            match soonest {
+                Some(addr) => {
+                    self.bans.remove(&addr);
+                    self.bans_dirty = true;
+                }
+                None => break,
+            }
+        }
+    }
+
+    /// Check whether a peer is banned at `now`
+    pub fn is_banned(&self, addr: &SocketAddr, now: Instant) -> bool {
+        self.bans.get(addr).is_some_and(|ban| ban.until > now)
+    }
+
+    /// Lift a peer's ban, returning whether it was banned
+    pub fn unban(&mut self, addr: &SocketAddr) -> bool {
+        let removed = self.bans.remove(addr).is_some();
+        self.bans_dirty |= removed;
+        removed
+    }
+
+    /// Per-peer request and misbehavior counters, sorted by address
+    pub fn peer_stats(&self, now: Instant) -> Vec<PeerStats> {
+        let half_life = self.limits.score_half_life;
+        let banned_for = |addr: &SocketAddr| {
+            self.bans
+                .get(addr)
+                .filter(|ban| ban.until > now)
+                .map(|ban| ban.until - now)
+        };
+
+        let mut stats: Vec<PeerStats> = self
+            .peer_states
+            .iter()
+            .map(|(addr, state)| PeerStats {
+                addr: *addr,
+                requests_received: state.requests_received,
+                requests_throttled: state.requests_throttled,
+                violations: state.violations,
+                misbehavior_score: state.score_at(half_life, now),
+                banned_for: banned_for(addr),
+            })
+            .collect();
+        // Bans restored from storage have no sync state yet
+        for (addr, ban) in &self.bans {
+            if ban.until > now && !self.peer_states.contains_key(addr) {
+                stats.push(PeerStats {
+                    addr: *addr,
+                    requests_received: 0,
+                    requests_throttled: 0,
+                    violations: 0,
+                    misbehavior_score: ban.score,
+                    banned_for: Some(ban.until - now),
+                });
+            }
+        }
+        stats.sort_by_key(|s| s.addr);
+        stats
+    }
+
+    /// Persist active bans to storage metadata
+    pub fn save_bans<S: StorageBackend>(&mut self, storage: &S) -> Result<()> {
+        let now = Instant::now();
+        let unix_now = unix_now_secs();
+        self.bans.retain(|_, ban| ban.until > now);
+
+        let records: Vec<BannedPeer> = self
+            .bans
+            .iter()
+            .map(|(addr, ban)| BannedPeer {
+                addr: addr.to_string(),
+                banned_until_secs: unix_now + (ban.until - now).as_secs(),
+                score: ban.score,
+            })
+            .collect();
+        let json =
+            serde_json::to_string(&records).map_err(|e| Error::Serialization(e.to_string()))?;
+
+        storage.set_metadata(BANNED_PEERS_METADATA_KEY, &json)?;
+        self.bans_dirty = false;
+        log::debug!("Saved {} peer bans to storage", records.len());
+        Ok(())
+    }
+
+    /// Restore unexpired bans from storage metadata
+    ///
+    /// Returns the number of bans restored.
+    pub fn load_bans<S: StorageBackend>(&mut self, storage: &S) -> Result<usize> {
+        let now = Instant::now();
+        let unix_now = unix_now_secs();
+        let mut loaded = 0;
+
+        for record in load_banned_peers(storage)? {
+            let remaining = record.banned_until_secs.saturating_sub(unix_now);
+            if remaining == 0 {
+                continue;
+            }
+            let Ok(addr) = record.addr.parse::<SocketAddr>() else {
+                log::warn!("Invalid banned peer address in storage: {}", record.addr);
+                continue;
+            };
+            self.bans.insert(
+                addr,
+                Ban {
+                    until: now + Duration::from_secs(remaining),
+                    score: record.score,
+                },
+            );
+            loaded += 1;
+        }
+        self.trim_bans(self.limits.max_banned);
+
+        log::info!("Loaded {} peer bans from storage", loaded);
+        Ok(loaded)
+    }
+
+    /// Persist bans if they changed, logging failures
+    fn flush_bans<S: StorageBackend>(&mut self, storage: &S) {
+        if self.bans_dirty {
+            if let Err(e) = self.save_bans(storage) {
+                log::warn!("Failed to persist peer bans: {}", e);
+            }
+        }
+    }
+
     /// Build bloom filter from local hashes
     pub fn build_local_filter(&self) -> BloomFilter {
         let mut filter = BloomFilter::new();
@@ -149,6 +508,17 @@ impl SyncManager {
         filter_bytes: &[u8],
         storage: &S,
     ) -> Vec<Hash> {
+        let now = Instant::now();
+        if self.is_banned(addr, now) {
+            return Vec::new();
+        }
+        // Filters are serialized as whole u64 words
+        if filter_bytes.len() % 8 != 0 {
+            self.report_violation(addr, Violation::Malformed, now);
+            self.flush_bans(storage);
+            return Vec::new();
+        }
+
         let peer_filter = BloomFilter::from_bytes(filter_bytes);
         let state = self.get_peer_state(addr);
         state.peer_filter = Some(peer_filter.clone());
@@ -209,6 +579,12 @@ impl SyncManager {
         _storage: &S,
         _gossip: &mut GossipManager,
     ) -> Result<SyncResult> {
+        if self.is_banned(addr, Instant::now()) {
+            return Err(Error::Gossip(GossipError::PeerBlacklisted {
+                peer_id: addr.to_string(),
+            }));
+        }
+
         // Build filter before getting peer state to avoid borrow conflict
         let local_filter = self.build_local_filter();
         let filter_bytes = local_filter.to_bytes();
@@ -251,6 +627,9 @@ impl SyncManager {
     }
 
     /// Handle incoming gossip request
+    ///
+    /// Requests from banned or rate-limited peers, and requests for zero or
+    /// more than `MAX_REQUEST_SIZE` records, get no records.
     pub fn handle_gossip_request<S: StorageBackend>(
         &mut self,
         from: &SocketAddr,
@@ -258,6 +637,18 @@ impl SyncManager {
         limit: u32,
         storage: &S,
     ) -> Option<Vec<Record>> {
+        let now = Instant::now();
+        if let Err(e) = self.check_request(from, now) {
+            log::debug!("Refused gossip request from {}: {}", from, e);
+            self.flush_bans(storage);
+            return None;
+        }
+        if limit == 0 || limit as usize > MAX_REQUEST_SIZE {
+            self.report_violation(from, Violation::ImpossibleRange, now);
+            self.flush_bans(storage);
+            return None;
+        }
+
         let state = self.get_peer_state(from);
         state.remote_seq = from_seq;
 
@@ -288,6 +679,9 @@ impl SyncManager {
     }
 
     /// Handle incoming gossip response
+    ///
+    /// Records whose entry doesn't match its hash, or whose signature doesn't
+    /// verify, are dropped and count against the sending peer.
     pub fn handle_gossip_response<S: StorageBackend>(
         &mut self,
         from: &SocketAddr,
@@ -295,9 +689,48 @@ impl SyncManager {
         storage: &S,
         gossip: &mut GossipManager,
     ) -> Result<usize> {
+        let now = Instant::now();
+        if self.is_banned(from, now) {
+            return Err(Error::Gossip(GossipError::PeerBlacklisted {
+                peer_id: from.to_string(),
+            }));
+        }
+
         let count = records.len();
         log::debug!("Received {} records from peer {}", count, from);
 
+        if count > MAX_BATCH_SIZE {
+            self.report_violation(from, Violation::ImpossibleRange, now);
+            self.flush_bans(storage);
+            return Err(Error::Gossip(GossipError::InvalidMessage {
+                reason: format!("{} records exceeds batch size {}", count, MAX_BATCH_SIZE),
+            }));
+        }
+
+        let mut malformed = false;
+        let mut bad_signature = false;
+        let records: Vec<Record> = records
+            .into_iter()
+            .filter(|record| match check_record(record) {
+                Ok(()) => true,
+                Err(Violation::InvalidSignature) => {
+                    bad_signature = true;
+                    false
+                }
+                Err(_) => {
+                    malformed = true;
+                    false
+                }
+            })
+            .collect();
+        if malformed {
+            self.report_violation(from, Violation::Malformed, now);
+        }
+        if bad_signature {
+            self.report_violation(from, Violation::InvalidSignature, now);
+        }
+        self.flush_bans(storage);
+
         let stored = self.store_records(from, records, storage, gossip)?;
 
         let state = self.get_peer_state(from);
@@ -310,17 +743,25 @@ impl SyncManager {
     pub fn stats(&self) -> SyncStats {
         let mut total_successful = 0;
         let mut total_failed = 0;
+        let mut throttled = 0;
+        let mut violations = 0;
 
         for state in self.peer_states.values() {
             total_successful += state.successful_syncs;
             total_failed += state.failed_syncs;
+            throttled += state.requests_throttled;
+            violations += state.violations;
         }
 
+        let now = Instant::now();
         SyncStats {
             peer_count: self.peer_states.len(),
             local_hashes: self.local_hashes.len(),
             total_successful_syncs: total_successful,
             total_failed_syncs: total_failed,
+            banned_peers: self.bans.values().filter(|ban| ban.until > now).count(),
+            throttled_requests: throttled,
+            violations,
         }
     }
 
@@ -331,6 +772,30 @@ impl SyncManager {
     }
 }
 
+/// Check a received record's entry hash and signature
+///
+/// Records from this crate's storage carry no signature (all zeros), so only
+/// non-zero signatures are verified.
+fn check_record(record: &Record) -> std::result::Result<(), Violation> {
+    let Some(entry_hash) = &record.action.entry_hash else {
+        return Ok(());
+    };
+    if let Some(entry) = &record.entry {
+        if entry.hash() != *entry_hash {
+            return Err(Violation::Malformed);
+        }
+    }
+    if record.action.signature.0 != [0u8; 64] {
+        let mut data = Vec::with_capacity(4 + 32);
+        data.extend_from_slice(&record.action.seq.to_be_bytes());
+        data.extend_from_slice(entry_hash.as_bytes());
+        if crate::crypto::verify(&record.action.author, &data, &record.action.signature).is_err() {
+            return Err(Violation::InvalidSignature);
+        }
+    }
+    Ok(())
+}
+
 /// Result of a sync operation
 #[derive(Debug)]
 pub struct SyncResult {
@@ -345,7 +810,7 @@ pub struct SyncResult {
 }
 
 /// Sync statistics
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SyncStats {
     /// Number of peers tracked
     pub peer_count: usize,
@@ -355,6 +820,15 @@ pub struct SyncStats {
     pub total_successful_syncs: u32,
     /// Total failed syncs
     pub total_failed_syncs: u32,
+    /// Peers currently banned for misbehavior
+    #[serde(default)]
+    pub banned_peers: usize,
+    /// Sync requests refused by per-peer rate limits
+    #[serde(default)]
+    pub throttled_requests: u32,
+    /// Protocol violations committed by tracked peers
+    #[serde(default)]
+    pub violations: u32,
 }
 
 #[cfg(test)]
@@ -565,6 +1039,9 @@ mod tests {
             local_hashes: 100,
             total_successful_syncs: 50,
             total_failed_syncs: 2,
+            banned_peers: 0,
+            throttled_requests: 0,
+            violations: 0,
         };
 
         let cloned = stats.clone();
@@ -581,6 +1058,9 @@ mod tests {
             local_hashes: 10,
             total_successful_syncs: 5,
             total_failed_syncs: 0,
+            banned_peers: 0,
+            throttled_requests: 0,
+            violations: 0,
         };
         let debug_str = format!("{:?}", stats);
         assert!(debug_str.contains("SyncStats"));
@@ -708,6 +1188,9 @@ mod tests {
             local_hashes: 500,
             total_successful_syncs: 100,
             total_failed_syncs: 5,
+            banned_peers: 0,
+            throttled_requests: 0,
+            violations: 0,
         };
 
         assert_eq!(stats.peer_count, 3);
@@ -976,4 +1459,255 @@ mod tests {
         // Each has 1 failure at the end
         assert_eq!(stats.total_failed_syncs, 3);
     }
+
+    fn test_limits() -> PeerLimitConfig {
+        PeerLimitConfig {
+            requests_per_sec: 1.0,
+            request_burst: 3,
+            ban_threshold: 20.0,
+            score_half_life: Duration::from_secs(60),
+            ban_duration: Duration::from_secs(600),
+            max_banned: 8,
+        }
+    }
+
+    fn test_record(seq: u32, content: &str) -> Record {
+        let entry = crate::types::Entry::app(content).unwrap();
+        Record {
+            action: crate::types::Action {
+                action_type: crate::types::ActionType::Create,
+                author: crate::types::AgentPubKey([0u8; 32]),
+                timestamp: crate::types::Timestamp::now(),
+                seq,
+                prev_action: None,
+                entry_hash: Some(entry.hash()),
+                signature: crate::types::Signature([0u8; 64]),
+            },
+            entry: Some(entry),
+        }
+    }
+
+    #[test]
+    fn test_request_bucket_throttles_then_bans() {
+        let mut manager = SyncManager::with_limits(Duration::from_secs(60), test_limits());
+        let addr: SocketAddr = "127.0.0.1:19080".parse().unwrap();
+        let other: SocketAddr = "127.0.0.1:19081".parse().unwrap();
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            assert!(manager.check_request(&addr, now).is_ok());
+        }
+        assert!(matches!(
+            manager.check_request(&addr, now),
+            Err(GossipError::RateLimitExceeded { wait_ms: 1000 })
+        ));
+        // One token refills per second
+        let later = now + Duration::from_millis(1500);
+        assert!(manager.check_request(&addr, later).is_ok());
+
+        // Refusals cost 5.0 each; four more cross the threshold of 20
+        for _ in 0..4 {
+            assert!(matches!(
+                manager.check_request(&addr, later),
+                Err(GossipError::RateLimitExceeded { .. })
+            ));
+        }
+        assert!(manager.is_banned(&addr, later));
+        assert!(matches!(
+            manager.check_request(&addr, later + Duration::from_secs(5)),
+            Err(GossipError::PeerBlacklisted { .. })
+        ));
+        assert!(manager.check_request(&other, later).is_ok());
+
+        let state = manager.get_peer_state(&addr);
+        assert_eq!(state.requests_received, 9);
+        assert_eq!(state.requests_throttled, 5);
+        assert_eq!(state.violations, 0);
+        let stats = manager.stats();
+        assert_eq!(stats.banned_peers, 1);
+        assert_eq!(stats.throttled_requests, 5);
+
+        // Bans expire
+        assert!(!manager.is_banned(&addr, later + Duration::from_secs(601)));
+    }
+
+    #[test]
+    fn test_misbehavior_score_decays() {
+        let limits = PeerLimitConfig {
+            ban_threshold: 100.0,
+            ..test_limits()
+        };
+        let mut manager = SyncManager::with_limits(Duration::from_secs(60), limits);
+        let addr: SocketAddr = "127.0.0.1:19080".parse().unwrap();
+        let now = Instant::now();
+
+        for _ in 0..4 {
+            assert!(!manager.report_violation(&addr, Violation::Malformed, now));
+        }
+        let state = manager.get_peer_state(&addr).clone();
+        assert!((state.score_at(Duration::from_secs(60), now) - 80.0).abs() < 1e-9);
+        let one_half_life = state.score_at(Duration::from_secs(60), now + Duration::from_secs(60));
+        assert!((one_half_life - 40.0).abs() < 1e-9);
+
+        // Two half-lives later the same violation no longer reaches the threshold
+        let later = now + Duration::from_secs(120);
+        assert!(!manager.report_violation(&addr, Violation::Malformed, later));
+        let stats = manager.peer_stats(later);
+        assert!((stats[0].misbehavior_score - 40.0).abs() < 1e-9);
+        assert_eq!(stats[0].violations, 5);
+        assert!(stats[0].banned_for.is_none());
+
+        // Without decay it would have
+        let mut manager = SyncManager::with_limits(
+            Duration::from_secs(60),
+            PeerLimitConfig {
+                ban_threshold: 100.0,
+                ..test_limits()
+            },
+        );
+        for _ in 0..4 {
+            manager.report_violation(&addr, Violation::Malformed, now);
+        }
+        assert!(manager.report_violation(&addr, Violation::Malformed, now));
+    }
+
+    #[test]
+    fn test_ban_list_is_capped() {
+        let limits = PeerLimitConfig {
+            max_banned: 2,
+            ..test_limits()
+        };
+        let mut manager = SyncManager::with_limits(Duration::from_secs(60), limits);
+        let now = Instant::now();
+        let peers: Vec<SocketAddr> = (0..3)
+            .map(|i| format!("127.0.0.1:1908{}", i).parse().unwrap())
+            .collect();
+
+        for (i, addr) in peers.iter().enumerate() {
+            let at = now + Duration::from_secs(i as u64);
+            assert!(manager.report_violation(addr, Violation::InvalidSignature, at));
+        }
+
+        let at = now + Duration::from_secs(2);
+        assert!(!manager.is_banned(&peers[0], at));
+        assert!(manager.is_banned(&peers[1], at));
+        assert!(manager.is_banned(&peers[2], at));
+        assert!(manager.unban(&peers[1]));
+        assert!(!manager.is_banned(&peers[1], at));
+    }
+
+    #[test]
+    fn test_check_record_signature() {
+        let keypair = crate::crypto::Keypair::generate();
+        let mut record = test_record(7, "reading");
+        assert_eq!(check_record(&record), Ok(()));
+
+        let entry_hash = record.action.entry_hash.clone().unwrap();
+        let mut data = 7u32.to_be_bytes().to_vec();
+        data.extend_from_slice(entry_hash.as_bytes());
+        record.action.author = keypair.public_key();
+        record.action.signature = keypair.sign(&data);
+        assert_eq!(check_record(&record), Ok(()));
+
+        record.action.seq = 8;
+        assert_eq!(check_record(&record), Err(Violation::InvalidSignature));
+
+        let mut record = test_record(1, "reading");
+        record.entry = Some(crate::types::Entry::app("tampered").unwrap());
+        assert_eq!(check_record(&record), Err(Violation::Malformed));
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_garbage_responses_until_banned() {
+        use crate::config::GossipConfig;
+        use crate::storage::Storage;
+
+        let storage = Storage::memory().unwrap();
+        let mut gossip = GossipManager::new(GossipConfig::default());
+        let limits = PeerLimitConfig {
+            ban_threshold: 90.0,
+            ..test_limits()
+        };
+        let mut manager = SyncManager::with_limits(Duration::from_secs(60), limits.clone());
+        let addr: SocketAddr = "127.0.0.1:19080".parse().unwrap();
+
+        let stored = manager
+            .handle_gossip_response(&addr, vec![test_record(1, "ok")], &storage, &mut gossip)
+            .unwrap();
+        assert_eq!(stored, 1);
+
+        // Each garbage message costs 20; the fifth crosses the threshold
+        for seq in 2..7 {
+            let mut garbage = test_record(seq, "payload");
+            garbage.entry = Some(crate::types::Entry::app("garbage").unwrap());
+            let stored = manager
+                .handle_gossip_response(&addr, vec![garbage], &storage, &mut gossip)
+                .unwrap();
+            assert_eq!(stored, 0);
+        }
+        assert!(manager.is_banned(&addr, Instant::now()));
+        assert!(matches!(
+            manager.handle_gossip_response(
+                &addr,
+                vec![test_record(9, "ok")],
+                &storage,
+                &mut gossip
+            ),
+            Err(Error::Gossip(GossipError::PeerBlacklisted { .. }))
+        ));
+        assert!(manager
+            .handle_gossip_request(&addr, 0, 10, &storage)
+            .is_none());
+        assert!(manager.peers_needing_sync().is_empty());
+
+        // The ban survives a restart
+        let persisted = load_banned_peers(&storage).unwrap();
+        assert_eq!(persisted.len(), 1);
+        assert_eq!(persisted[0].addr, addr.to_string());
+
+        let mut restarted = SyncManager::with_limits(Duration::from_secs(60), limits);
+        assert_eq!(restarted.load_bans(&storage).unwrap(), 1);
+        assert!(restarted.is_banned(&addr, Instant::now()));
+        let stats = restarted.peer_stats(Instant::now());
+        assert_eq!(stats.len(), 1);
+        assert!(stats[0].banned_for.is_some());
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_impossible_request_range() {
+        use crate::storage::Storage;
+
+        let storage = Storage::memory().unwrap();
+        let mut manager = SyncManager::new(Duration::from_secs(60));
+        let addr: SocketAddr = "127.0.0.1:19080".parse().unwrap();
+
+        assert!(manager
+            .handle_gossip_request(&addr, 0, 0, &storage)
+            .is_none());
+        assert!(manager
+            .handle_gossip_request(&addr, 0, MAX_REQUEST_SIZE as u32 + 1, &storage)
+            .is_none());
+        let state = manager.get_peer_state(&addr);
+        assert_eq!(state.requests_received, 2);
+        assert_eq!(state.violations, 2);
+        assert!(state.misbehavior_score > 49.0);
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_malformed_peer_filter() {
+        use crate::storage::Storage;
+
+        let storage = Storage::memory().unwrap();
+        let mut manager = SyncManager::new(Duration::from_secs(60));
+        let addr: SocketAddr = "127.0.0.1:19080".parse().unwrap();
+
+        assert!(manager
+            .process_peer_filter(&addr, &[0u8; 7], &storage)
+            .is_empty());
+        assert_eq!(manager.get_peer_state(&addr).violations, 1);
+        assert!(manager.get_peer_state(&addr).peer_filter.is_none());
+    }
 }
@@ -17,7 +17,11 @@
 //! - [`Record`] - Complete unit combining an action with its entry
 //! - [`Link`] - Directional relationships between entries
 //! - [`NodeStats`] - Performance and state metrics for a node
+//!
+//! In JSON these types use their field names; in CBOR they are encoded with
+//! the integer keys listed next to each type (see [`crate::wire`]).
 
+use crate::wire::{self, keyed_struct, keyed_unit_enum};
 use serde::{Deserialize, Serialize};
 
 /// A 32-byte Blake3 hash used for content-addressable identification of data.
@@ -145,6 +149,9 @@ impl Serialize for Hash {
     where
         S: serde::Serializer,
     {
+        if !serializer.is_human_readable() {
+            return serializer.serialize_bytes(&self.0);
+        }
         serializer.serialize_str(&self.to_hex())
     }
 }
@@ -154,6 +161,9 @@ impl<'de> Deserialize<'de> for Hash {
     where
         D: serde::Deserializer<'de>,
     {
+        if !deserializer.is_human_readable() {
+            return wire::fixed_bytes(deserializer).map(Self);
+        }
         let s = String::deserialize(deserializer)?;
         Self::from_hex(&s).map_err(serde::de::Error::custom)
     }
@@ -231,6 +241,9 @@ impl Serialize for AgentPubKey {
     where
         S: serde::Serializer,
     {
+        if !serializer.is_human_readable() {
+            return serializer.serialize_bytes(&self.0);
+        }
         serializer.serialize_str(&self.to_hex())
     }
 }
@@ -240,6 +253,9 @@ impl<'de> Deserialize<'de> for AgentPubKey {
     where
         D: serde::Deserializer<'de>,
     {
+        if !deserializer.is_human_readable() {
+            return wire::fixed_bytes(deserializer).map(Self);
+        }
         let s = String::deserialize(deserializer)?;
         let bytes = hex::decode(&s).map_err(serde::de::Error::custom)?;
         let mut arr = [0u8; 32];
@@ -328,6 +344,7 @@ impl Timestamp {
 /// This enum classifies entries into different categories, each serving a specific
 /// purpose in the AIngle network.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 pub enum EntryType {
     /// A general-purpose application data entry.
     ///
@@ -348,6 +365,13 @@ pub enum EntryType {
     CapClaim,
 }
 
+keyed_unit_enum!(EntryType {
+    0 => App,
+    1 => AgentKey,
+    2 => CapGrant,
+    3 => CapClaim,
+});
+
 /// A fundamental unit of data stored on the distributed hash table (DHT).
 ///
 /// Entries contain the actual application data in the AIngle network. Each entry
@@ -372,6 +396,7 @@ pub enum EntryType {
 /// println!("Entry size: {} bytes", entry.size());
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 pub struct Entry {
     /// The type of the entry.
     pub entry_type: EntryType,
@@ -379,6 +404,11 @@ pub struct Entry {
     pub content: Vec<u8>,
 }
 
+keyed_struct!(Entry {
+    0 => entry_type,
+    1 => content as bytes,
+});
+
 impl Entry {
     /// Creates a new application [`Entry`] with serialized content.
     ///
@@ -454,6 +484,7 @@ impl Entry {
 /// Each action type represents a different operation that can be performed
 /// on the distributed hash table (DHT).
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 pub enum ActionType {
     /// The first action in an agent's source chain, establishing their identity.
     ///
@@ -479,6 +510,15 @@ pub enum ActionType {
     DeleteLink,
 }
 
+keyed_unit_enum!(ActionType {
+    0 => Genesis,
+    1 => Create,
+    2 => Update,
+    3 => Delete,
+    4 => CreateLink,
+    5 => DeleteLink,
+});
+
 /// A 64-byte Ed25519 cryptographic signature.
 ///
 /// Signatures are used to authenticate actions and verify that they were created
@@ -505,6 +545,9 @@ impl Serialize for Signature {
     where
         S: serde::Serializer,
     {
+        if !serializer.is_human_readable() {
+            return serializer.serialize_bytes(&self.0);
+        }
         serializer.serialize_str(&hex::encode(&self.0))
     }
 }
@@ -514,6 +557,9 @@ impl<'de> Deserialize<'de> for Signature {
     where
         D: serde::Deserializer<'de>,
     {
+        if !deserializer.is_human_readable() {
+            return wire::fixed_bytes(deserializer).map(Self);
+        }
         let s = String::deserialize(deserializer)?;
         let bytes = hex::decode(&s).map_err(serde::de::Error::custom)?;
         let mut arr = [0u8; 64];
@@ -551,6 +597,7 @@ impl<'de> Deserialize<'de> for Signature {
 /// let hash = action.hash();
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 pub struct Action {
     /// The type of the action.
     pub action_type: ActionType,
@@ -580,6 +627,16 @@ pub struct Action {
     pub signature: Signature,
 }
 
+keyed_struct!(Action {
+    0 => action_type,
+    1 => author,
+    2 => timestamp,
+    3 => seq,
+    4 => prev_action,
+    5 => entry_hash,
+    6 => signature,
+});
+
 impl Action {
     /// Returns the content hash of the action.
     ///
@@ -631,6 +688,7 @@ impl Action {
 /// # }
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 pub struct Record {
     /// The signed action metadata.
     pub action: Action,
@@ -641,6 +699,11 @@ pub struct Record {
     pub entry: Option<Entry>,
 }
 
+keyed_struct!(Record {
+    0 => action,
+    1 => entry,
+});
+
 /// Represents a directional link between two entries on the DHT.
 ///
 /// Links enable creating graph-like relationships between entries, such as
@@ -662,6 +725,7 @@ pub struct Record {
 /// };
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 pub struct Link {
     /// The hash of the source or "base" entry.
     ///
@@ -684,6 +748,14 @@ pub struct Link {
     pub timestamp: Timestamp,
 }
 
+keyed_struct!(Link {
+    0 => base,
+    1 => target,
+    2 => link_type,
+    3 => tag as bytes,
+    4 => timestamp,
+});
+
 /// Collects statistics about the node's performance and state.
 ///
 /// These metrics are useful for monitoring node health, resource usage,
@@ -707,6 +779,7 @@ pub struct Link {
 /// # }
 /// ```
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 pub struct NodeStats {
     /// The total number of entries stored by the node.
     pub entries_count: u64,
@@ -725,6 +798,15 @@ pub struct NodeStats {
     pub uptime_secs: u64,
 }
 
+keyed_struct!(NodeStats {
+    0 => entries_count,
+    1 => actions_count,
+    2 => memory_used,
+    3 => storage_used,
+    4 => peer_count,
+    5 => uptime_secs,
+});
+
 // A simple hex encoding/decoding implementation.
 mod hex {
     pub fn encode(bytes: &[u8]) -> String {
@@ -30,15 +30,27 @@
 //!
 //! - **Ledger Nano S/X/S Plus**: Via USB HID or Bluetooth LE
 //! - **Trezor One/Model T**: Via USB HID (planned)
-
-use crate::error::{Error, Result};
+//!
+//! # Signing Triples
+//!
+//! [`WalletManager::sign_triple`] streams a triple's subject, predicate and
+//! object to the device in `SIGN_TRIPLE` chunks so the app can show them
+//! before the user confirms. A rejection on the device is reported as
+//! [`WalletError::UserRejected`], separate from transport failures, and
+//! [`MockTransport`] runs the whole flow without hardware.
+
+use crate::error::{CryptoError, Error, Result, WalletError};
+use crate::graph::{SemanticTriple, TripleObject};
+use crate::types::Hash;
+use ed25519_dalek::{Signer, SigningKey, Verifier, VerifyingKey};
 use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 #[cfg(feature = "hw_wallet")]
 use ledger_transport::APDUCommand;
 #[cfg(feature = "hw_wallet")]
-use ledger_transport_hid::{hidapi::HidApi, TransportNativeHID};
+use ledger_transport_hid::{hidapi::HidApi, LedgerHIDError, TransportNativeHID};
 
 /// AIngle APDU CLA (Class byte) - custom application identifier
 pub const AINGLE_CLA: u8 = 0xE0;
@@ -55,8 +67,59 @@ pub mod ins {
     pub const SIGN_ENTRY: u8 = 0x03;
     /// Get device info
     pub const GET_DEVICE_INFO: u8 = 0x04;
+    /// Sign a semantic triple sent in chunks (requires user confirmation)
+    pub const SIGN_TRIPLE: u8 = 0x05;
+}
+
+/// APDU status words used by the AIngle app
+pub mod sw {
+    /// Success
+    pub const OK: u16 = 0x9000;
+    /// User rejected the request on the device
+    pub const USER_REJECTED: u16 = 0x6985;
+    /// Malformed request data
+    pub const INVALID_DATA: u16 = 0x6A80;
+    /// Instruction not supported
+    pub const INVALID_INS: u16 = 0x6D00;
+    /// Class byte not supported
+    pub const INVALID_CLA: u16 = 0x6E00;
+    /// Device is locked
+    pub const LOCKED: u16 = 0x6FAA;
+}
+
+/// P1/P2 values for the chunked `SIGN_TRIPLE` exchange
+///
+/// A request is one `P1_START` chunk carrying the derivation path, then
+/// `P1_FIELD` chunks carrying field bytes with the field tag in P2, then a
+/// `P1_CONFIRM` command that shows the triple and answers with the signature
+/// once the user approves.
+pub mod sign_triple {
+    /// Start a request; data is the derivation path
+    pub const P1_START: u8 = 0x00;
+    /// Append bytes to the field named by P2
+    pub const P1_FIELD: u8 = 0x01;
+    /// Show the triple and wait for the user's decision
+    pub const P1_CONFIRM: u8 = 0x02;
+    /// P2 tag for the subject
+    pub const FIELD_SUBJECT: u8 = 0x01;
+    /// P2 tag for the predicate
+    pub const FIELD_PREDICATE: u8 = 0x02;
+    /// P2 tag for the object
+    pub const FIELD_OBJECT: u8 = 0x03;
 }
 
+/// Maximum data bytes in a single short APDU
+pub const MAX_APDU_DATA: usize = 255;
+
+/// Characters of each triple field shown on the device screen
+pub const DISPLAY_FIELD_CHARS: usize = 32;
+
+/// Largest triple signing message the device app buffers
+pub const MAX_TRIPLE_MESSAGE: usize = 1024;
+
+/// Domain tag prefixed to every triple signing message
+pub const TRIPLE_SIGN_DOMAIN: &[u8] = b"aingle:triple:v1";
+
 /// BIP-44 derivation path for AIngle
 /// m/44'/8017'/account'/0/index
 /// 8017 = 0x1F51 (proposed coin type for AIngle)
@@ -292,9 +355,8 @@ pub struct WalletManager {
     stats: WalletStats,
     /// Last connection time
     last_connected: Option<Instant>,
-    /// HID transport for Ledger communication
-    #[cfg(feature = "hw_wallet")]
-    transport: Option<TransportNativeHID>,
+    /// Transport to the device, opened on connect unless injected
+    transport: Option<Box<dyn WalletTransport>>,
 }
 
 impl WalletManager {
@@ -306,11 +368,19 @@ impl WalletManager {
             wallet_info: None,
             stats: WalletStats::default(),
             last_connected: None,
-            #[cfg(feature = "hw_wallet")]
             transport: None,
         }
     }
 
+    /// Create a wallet manager that talks to the device through `transport`
+    ///
+    /// Pair with [`MockTransport`] to run the signing flow without hardware.
+    pub fn with_transport(config: WalletConfig, transport: Box<dyn WalletTransport>) -> Self {
+        let mut manager = Self::new(config);
+        manager.transport = Some(transport);
+        manager
+    }
+
     /// Connect to a hardware wallet
     pub async fn connect(&mut self) -> Result<WalletInfo> {
         if self.state == WalletState::Connected {
@@ -322,83 +392,66 @@ impl WalletManager {
         self.state = WalletState::Connecting;
         log::info!("Connecting to hardware wallet...");
 
-        #[cfg(feature = "hw_wallet")]
-        {
-            // Initialize HID API for device discovery
-            let api = HidApi::new()
-                .map_err(|e| Error::network(format!("Failed to initialize HID API: {}", e)))?;
-
-            // Create HID transport to Ledger device
-            let transport = TransportNativeHID::new(&api)
-                .map_err(|e| Error::network(format!("Failed to open HID transport: {}", e)))?;
-
-            // Query device info via GET_VERSION command
-            let version_cmd = APDUCommand {
-                cla: AINGLE_CLA,
-                ins: ins::GET_VERSION,
-                p1: 0x00,
-                p2: 0x00,
-                data: vec![],
-            };
-
-            let answer = transport
-                .exchange(&version_cmd)
-                .map_err(|e| Error::network(format!("APDU exchange failed: {}", e)))?;
-
-            // Parse version response
-            let (wallet_type, firmware_version) = if answer.data().len() >= 3 {
-                let major = answer.data()[0];
-                let minor = answer.data()[1];
-                let patch = answer.data()[2];
-                (
-                    WalletType::LedgerNanoS, // Default, could be detected
-                    format!("{}.{}.{}", major, minor, patch),
-                )
-            } else {
-                (WalletType::Unknown, "0.0.0".to_string())
-            };
+        if self.transport.is_none() {
+            match Self::open_transport() {
+                Ok(transport) => self.transport = Some(transport),
+                Err(e) => {
+                    self.state = WalletState::Error;
+                    return Err(e.into());
+                }
+            }
+        }
 
-            self.transport = Some(transport);
+        // Query device info via GET_VERSION command
+        let version_cmd = ApduCommand::new(AINGLE_CLA, ins::GET_VERSION, 0x00, 0x00);
+        let version = match self.send(&version_cmd) {
+            Ok(data) => data,
+            Err(e) => {
+                if self.state == WalletState::Connecting {
+                    self.state = WalletState::Error;
+                }
+                return Err(e);
+            }
+        };
 
-            let info = WalletInfo {
-                wallet_type,
-                firmware_version,
-                app_version: None,
-                has_aingle_app: true, // Assume yes if we got a valid response
-                serial: None,
-            };
+        // Parse version response
+        let (wallet_type, firmware_version) = if version.len() >= 3 {
+            (
+                WalletType::LedgerNanoS, // Default, could be detected
+                format!("{}.{}.{}", version[0], version[1], version[2]),
+            )
+        } else {
+            (WalletType::Unknown, "0.0.0".to_string())
+        };
 
-            self.wallet_info = Some(info.clone());
-            self.state = WalletState::Connected;
-            self.last_connected = Some(Instant::now());
-            self.stats.connections += 1;
+        let info = WalletInfo {
+            wallet_type,
+            firmware_version,
+            app_version: None,
+            has_aingle_app: true, // The app answered GET_VERSION
+            serial: None,
+        };
 
-            log::info!("Connected to hardware wallet: {:?}", info.wallet_type);
-            return Ok(info);
-        }
+        self.wallet_info = Some(info.clone());
+        self.state = WalletState::Connected;
+        self.last_connected = Some(Instant::now());
+        self.stats.connections += 1;
 
-        #[cfg(not(feature = "hw_wallet"))]
-        {
-            // Simulated connection when hw_wallet feature is disabled
-            let info = WalletInfo {
-                wallet_type: WalletType::Unknown,
-                firmware_version: "0.0.0".to_string(),
-                app_version: None,
-                has_aingle_app: false,
-                serial: None,
-            };
+        log::info!("Connected to hardware wallet: {:?}", info.wallet_type);
+        Ok(info)
+    }
 
-            self.wallet_info = Some(info.clone());
-            self.state = WalletState::Connected;
-            self.last_connected = Some(Instant::now());
-            self.stats.connections += 1;
+    /// Open the USB HID transport to the first Ledger device found
+    #[cfg(feature = "hw_wallet")]
+    fn open_transport() -> std::result::Result<Box<dyn WalletTransport>, WalletError> {
+        Ok(Box::new(HidTransport::open()?))
+    }
 
-            log::info!(
-                "Connected to hardware wallet (simulated): {:?}",
-                info.wallet_type
-            );
-            Ok(info)
-        }
+    #[cfg(not(feature = "hw_wallet"))]
+    fn open_transport() -> std::result::Result<Box<dyn WalletTransport>, WalletError> {
+        Err(WalletError::Transport(
+            "hardware wallet support not compiled in".to_string(),
+        ))
     }
 
     /// Disconnect from hardware wallet
@@ -409,12 +462,8 @@ impl WalletManager {
 
         log::info!("Disconnecting from hardware wallet");
 
-        #[cfg(feature = "hw_wallet")]
-        {
-            // Drop the transport to close the HID connection
-            self.transport = None;
-        }
-
+        // Drop the transport to close the connection
+        self.transport = None;
         self.state = WalletState::Disconnected;
         self.wallet_info = None;
         Ok(())
@@ -423,80 +472,35 @@ impl WalletManager {
     /// Get public key from derivation path
     pub async fn get_public_key(&mut self, path: &DerivationPath) -> Result<HwPublicKey> {
         if self.state != WalletState::Connected {
-            return Err(Error::network("Wallet not connected".to_string()));
+            return Err(WalletError::NotConnected.into());
         }
 
         log::debug!("Getting public key for path: {}", path.to_string());
 
-        #[cfg(feature = "hw_wallet")]
-        {
-            let transport = self
-                .transport
-                .as_ref()
-                .ok_or_else(|| Error::network("Transport not initialized".to_string()))?;
-
-            // Send GET_PUBLIC_KEY APDU command
-            let cmd = APDUCommand {
-                cla: AINGLE_CLA,
-                ins: ins::GET_PUBLIC_KEY,
-                p1: 0x00,
-                p2: 0x00,
-                data: path.to_bytes(),
-            };
-
-            let answer = transport
-                .exchange(&cmd)
-                .map_err(|e| Error::network(format!("APDU exchange failed: {}", e)))?;
-
-            // Check status code
-            if answer.retcode() != 0x9000 {
-                let error_msg = match answer.retcode() {
-                    0x6985 => "User rejected",
-                    0x6A82 => "App not found",
-                    0x6D00 => "Invalid instruction",
-                    _ => "Unknown error",
-                };
-                return Err(Error::network(format!(
-                    "Device error: {} (0x{:04X})",
-                    error_msg,
-                    answer.retcode()
-                )));
-            }
+        let cmd = ApduCommand::new(AINGLE_CLA, ins::GET_PUBLIC_KEY, 0x00, 0x00)
+            .with_data(path.to_bytes());
+        let data = self.send(&cmd)?;
 
-            // Parse response: public key (32 bytes) + optional chain code (32 bytes)
-            let data = answer.data();
-            if data.len() < 32 {
-                return Err(Error::Serialization(
-                    "Invalid public key response length".to_string(),
-                ));
+        // Parse response: public key (32 bytes) + optional chain code (32 bytes)
+        if data.len() < 32 {
+            return Err(WalletError::InvalidResponse {
+                reason: "Invalid public key response length".to_string(),
             }
-
-            let public_key = HwPublicKey {
-                bytes: data[0..32].to_vec(),
-                path: path.clone(),
-                chain_code: if data.len() >= 64 {
-                    Some(data[32..64].to_vec())
-                } else {
-                    None
-                },
-            };
-
-            self.stats.keys_retrieved += 1;
-            return Ok(public_key);
+            .into());
         }
 
-        #[cfg(not(feature = "hw_wallet"))]
-        {
-            // Simulated response when hw_wallet feature is disabled
-            let public_key = HwPublicKey {
-                bytes: vec![0u8; 32],
-                path: path.clone(),
-                chain_code: None,
-            };
+        let public_key = HwPublicKey {
+            bytes: data[0..32].to_vec(),
+            path: path.clone(),
+            chain_code: if data.len() >= 64 {
+                Some(data[32..64].to_vec())
+            } else {
+                None
+            },
+        };
 
-            self.stats.keys_retrieved += 1;
-            Ok(public_key)
-        }
+        self.stats.keys_retrieved += 1;
+        Ok(public_key)
     }
 
     /// Sign a hash with hardware wallet
@@ -504,7 +508,7 @@ impl WalletManager {
     /// This will prompt the user to confirm on the device.
     pub async fn sign_hash(&mut self, hash: &[u8], path: &DerivationPath) -> Result<HwSignature> {
         if self.state != WalletState::Connected {
-            return Err(Error::network("Wallet not connected".to_string()));
+            return Err(WalletError::NotConnected.into());
         }
 
         if hash.len() != 32 {
@@ -514,94 +518,142 @@ impl WalletManager {
         log::info!("Requesting signature from hardware wallet...");
         log::info!("Please confirm on your device");
 
-        self.state = WalletState::AwaitingConfirmation;
-        let start = Instant::now();
-
-        #[cfg(feature = "hw_wallet")]
-        {
-            let transport = self
-                .transport
-                .as_ref()
-                .ok_or_else(|| Error::network("Transport not initialized".to_string()))?;
-
-            // Build APDU data: derivation path + hash
-            let mut data = path.to_bytes();
-            data.extend_from_slice(hash);
-
-            // Send SIGN_HASH APDU command
-            let cmd = APDUCommand {
-                cla: AINGLE_CLA,
-                ins: ins::SIGN_HASH,
-                p1: 0x00,
-                p2: 0x00,
-                data,
-            };
-
-            // This call blocks until user confirms or rejects on device
-            let answer = transport.exchange(&cmd).map_err(|e| {
-                self.state = WalletState::Connected;
-                self.stats.failures += 1;
-                Error::network(format!("APDU exchange failed: {}", e))
-            })?;
+        // Build APDU data: derivation path + hash
+        let mut data = path.to_bytes();
+        data.extend_from_slice(hash);
+        let cmd = ApduCommand::new(AINGLE_CLA, ins::SIGN_HASH, 0x00, 0x00).with_data(data);
+
+        let bytes = self.confirm(&cmd)?;
+        Ok(HwSignature {
+            bytes,
+            path: path.clone(),
+            hash: hash.to_vec(),
+        })
+    }
 
-            // Check status code
-            if answer.retcode() != 0x9000 {
-                self.state = WalletState::Connected;
-                self.stats.failures += 1;
+    /// Sign a semantic triple with hardware wallet
+    ///
+    /// Subject, predicate and object are streamed to the device field by
+    /// field so it can show them, truncated to [`DISPLAY_FIELD_CHARS`], before
+    /// the user approves. The device signs [`triple_signing_message`]; a
+    /// rejection on the device returns [`WalletError::UserRejected`].
+    pub async fn sign_triple(
+        &mut self,
+        triple: &SemanticTriple,
+        path: &DerivationPath,
+    ) -> Result<HwSignature> {
+        if self.state != WalletState::Connected {
+            return Err(WalletError::NotConnected.into());
+        }
 
-                let error_msg = match answer.retcode() {
-                    0x6985 => "User rejected the signature request",
-                    0x6982 => "Security status not satisfied",
-                    0x6A80 => "Invalid data",
-                    0x6FAA => "Device is locked",
-                    _ => "Unknown error",
-                };
-                return Err(Error::network(format!(
-                    "Signing failed: {} (0x{:04X})",
-                    error_msg,
-                    answer.retcode()
-                )));
-            }
+        let fields = triple_fields(triple);
+        let message = encode_triple_message(&fields)?;
 
-            // Parse signature (64 bytes for Ed25519)
-            let sig_data = answer.data();
-            if sig_data.len() < 64 {
-                self.state = WalletState::Connected;
-                return Err(Error::Serialization(format!(
-                    "Invalid signature length: expected 64, got {}",
-                    sig_data.len()
-                )));
+        for chunk in triple_chunks(&fields, path) {
+            if let Err(e) = self.send(&chunk) {
+                self.stats.failures += 1;
+                return Err(e);
             }
+        }
 
-            let signature = HwSignature {
-                bytes: sig_data[0..64].to_vec(),
-                path: path.clone(),
-                hash: hash.to_vec(),
-            };
+        log::info!(
+            "Please confirm on your device: {}",
+            TripleDisplay::from_fields(&fields)
+        );
+
+        let cmd = ApduCommand::new(AINGLE_CLA, ins::SIGN_TRIPLE, sign_triple::P1_CONFIRM, 0x00);
+        let bytes = self.confirm(&cmd)?;
+        Ok(HwSignature {
+            bytes,
+            path: path.clone(),
+            hash: Hash::from_bytes(&message).as_bytes().to_vec(),
+        })
+    }
 
-            self.state = WalletState::Connected;
-            self.stats.signatures_created += 1;
-            self.stats.confirmation_wait_ms += start.elapsed().as_millis() as u64;
+    /// Check a signature returned by the device against its public key
+    ///
+    /// The device is trusted to hold the key, not to have signed the bytes
+    /// the host asked for, so hosts should verify before using a signature.
+    pub fn verify(public_key: &HwPublicKey, message: &[u8], signature: &HwSignature) -> Result<()> {
+        let key: [u8; 32] =
+            public_key
+                .bytes
+                .as_slice()
+                .try_into()
+                .map_err(|_| CryptoError::InvalidKey {
+                    expected_len: 32,
+                    actual_len: public_key.bytes.len(),
+                })?;
+        let verifying_key = VerifyingKey::from_bytes(&key)
+            .map_err(|e| Error::crypto(format!("Invalid public key: {}", e)))?;
+
+        let sig: [u8; 64] = signature
+            .bytes
+            .as_slice()
+            .try_into()
+            .map_err(|_| CryptoError::InvalidSignature)?;
+
+        verifying_key
+            .verify(message, &ed25519_dalek::Signature::from_bytes(&sig))
+            .map_err(|_| CryptoError::InvalidSignature)?;
+        Ok(())
+    }
 
-            log::info!("Signature received from hardware wallet");
-            return Ok(signature);
-        }
+    /// Send a command that needs the user's approval and return the signature
+    fn confirm(&mut self, command: &ApduCommand) -> Result<Vec<u8>> {
+        self.state = WalletState::AwaitingConfirmation;
+        let start = Instant::now();
 
-        #[cfg(not(feature = "hw_wallet"))]
-        {
-            // Simulated signature when hw_wallet feature is disabled
-            let signature = HwSignature {
-                bytes: vec![0u8; 64],
-                path: path.clone(),
-                hash: hash.to_vec(),
-            };
+        // This call blocks until user confirms or rejects on device
+        let result = self.send(command).and_then(|data| {
+            if data.len() < 64 {
+                return Err(WalletError::InvalidResponse {
+                    reason: format!("Invalid signature length: expected 64, got {}", data.len()),
+                }
+                .into());
+            }
+            Ok(data[0..64].to_vec())
+        });
 
+        if self.state == WalletState::AwaitingConfirmation {
             self.state = WalletState::Connected;
-            self.stats.signatures_created += 1;
-            self.stats.confirmation_wait_ms += start.elapsed().as_millis() as u64;
+        }
+
+        match &result {
+            Ok(_) => {
+                self.stats.signatures_created += 1;
+                self.stats.confirmation_wait_ms += start.elapsed().as_millis() as u64;
+                log::info!("Signature received from hardware wallet");
+            }
+            Err(Error::Wallet(WalletError::UserRejected)) => {
+                self.stats.failures += 1;
+                log::info!("Signature request rejected on the device");
+            }
+            Err(e) => {
+                self.stats.failures += 1;
+                log::warn!("Hardware wallet signing failed: {}", e);
+            }
+        }
+        result
+    }
 
-            log::info!("Signature received from hardware wallet (simulated)");
-            Ok(signature)
+    /// Exchange one APDU and return its data if the device reported success
+    ///
+    /// If the device went away the connection is torn down, so the caller
+    /// sees [`WalletState::Disconnected`] rather than a stale connection.
+    fn send(&mut self, command: &ApduCommand) -> Result<Vec<u8>> {
+        let transport = self.transport.as_mut().ok_or(WalletError::NotConnected)?;
+
+        match transport.exchange(command) {
+            Ok(answer) => Ok(answer.check()?.to_vec()),
+            Err(WalletError::Disconnected) => {
+                log::warn!("Hardware wallet disconnected");
+                self.transport = None;
+                self.wallet_info = None;
+                self.state = WalletState::Disconnected;
+                Err(WalletError::Disconnected.into())
+            }
+            Err(e) => Err(e.into()),
         }
     }
 
@@ -712,6 +764,16 @@ impl ApduResponse {
         self.status == 0x9000
     }
 
+    /// Return the response data, or the [`WalletError`] for the status word
+    pub fn check(&self) -> std::result::Result<&[u8], WalletError> {
+        match self.status {
+            sw::OK => Ok(&self.data),
+            sw::USER_REJECTED => Err(WalletError::UserRejected),
+            sw::LOCKED => Err(WalletError::Locked),
+            status => Err(WalletError::DeviceStatus { status }),
+        }
+    }
+
     /// Get error message for status code
     pub fn error_message(&self) -> Option<&'static str> {
         match self.status {
@@ -729,6 +791,352 @@ impl ApduResponse {
     }
 }
 
+// ============================================================================
+// Triple signing
+// ============================================================================
+
+/// What the device shows for a triple before asking for confirmation
+///
+/// Each field is cut to [`DISPLAY_FIELD_CHARS`] characters, ending in `...`
+/// when it was truncated. The signature always covers the full fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TripleDisplay {
+    /// Subject as shown on screen
+    pub subject: String,
+    /// Predicate as shown on screen
+    pub predicate: String,
+    /// Object as shown on screen
+    pub object: String,
+}
+
+impl TripleDisplay {
+    /// Render a triple the way the device app does
+    pub fn from_triple(triple: &SemanticTriple) -> Self {
+        Self::from_fields(&triple_fields(triple))
+    }
+
+    /// Render the encoded fields; the object's leading kind byte is not shown
+    fn from_fields(fields: &[Vec<u8>; 3]) -> Self {
+        let text = |bytes: &[u8]| truncate_for_display(&String::from_utf8_lossy(bytes));
+        Self {
+            subject: text(&fields[0]),
+            predicate: text(&fields[1]),
+            object: text(fields[2].get(1..).unwrap_or_default()),
+        }
+    }
+}
+
+impl std::fmt::Display for TripleDisplay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.subject, self.predicate, self.object)
+    }
+}
+
+/// Cut `text` to [`DISPLAY_FIELD_CHARS`] characters on a char boundary
+fn truncate_for_display(text: &str) -> String {
+    if text.chars().count() <= DISPLAY_FIELD_CHARS {
+        return text.to_string();
+    }
+    let mut shown: String = text.chars().take(DISPLAY_FIELD_CHARS - 3).collect();
+    shown.push_str("...");
+    shown
+}
+
+/// Encode the object as a kind byte followed by its text form
+///
+/// The kind byte keeps `Literal("42")` and `Integer(42)` from signing to
+/// the same message.
+fn object_field(object: &TripleObject) -> Vec<u8> {
+    let (kind, text) = match object {
+        TripleObject::Literal(s) => (0u8, s.clone()),
+        TripleObject::Integer(i) => (1, i.to_string()),
+        TripleObject::Reference(r) => (2, r.clone()),
+        TripleObject::Hash(h) => (3, h.to_hex()),
+        TripleObject::Boolean(b) => (4, b.to_string()),
+    };
+    let mut field = Vec::with_capacity(1 + text.len());
+    field.push(kind);
+    field.extend_from_slice(text.as_bytes());
+    field
+}
+
+/// Subject, predicate and object in the order they are sent and signed
+fn triple_fields(triple: &SemanticTriple) -> [Vec<u8>; 3] {
+    [
+        triple.subject.as_bytes().to_vec(),
+        triple.predicate.as_bytes().to_vec(),
+        object_field(&triple.object),
+    ]
+}
+
+/// Domain tag followed by each field as a big-endian `u16` length and its bytes
+fn encode_triple_message(fields: &[Vec<u8>; 3]) -> Result<Vec<u8>> {
+    let len = TRIPLE_SIGN_DOMAIN.len() + fields.iter().map(|f| 2 + f.len()).sum::<usize>();
+    if len > MAX_TRIPLE_MESSAGE {
+        return Err(WalletError::PayloadTooLarge {
+            len,
+            max: MAX_TRIPLE_MESSAGE,
+        }
+        .into());
+    }
+
+    let mut message = Vec::with_capacity(len);
+    message.extend_from_slice(TRIPLE_SIGN_DOMAIN);
+    for field in fields {
+        message.extend_from_slice(&(field.len() as u16).to_be_bytes());
+        message.extend_from_slice(field);
+    }
+    Ok(message)
+}
+
+/// The exact bytes a device signs for `triple`
+///
+/// Use with [`WalletManager::verify`] to check a signature from
+/// [`WalletManager::sign_triple`].
+pub fn triple_signing_message(triple: &SemanticTriple) -> Result<Vec<u8>> {
+    encode_triple_message(&triple_fields(triple))
+}
+
+/// APDUs that load a triple onto the device, ahead of the confirm command
+fn triple_chunks(fields: &[Vec<u8>; 3], path: &DerivationPath) -> Vec<ApduCommand> {
+    let mut chunks =
+        vec![
+            ApduCommand::new(AINGLE_CLA, ins::SIGN_TRIPLE, sign_triple::P1_START, 0x00)
+                .with_data(path.to_bytes()),
+        ];
+
+    let tags = [
+        sign_triple::FIELD_SUBJECT,
+        sign_triple::FIELD_PREDICATE,
+        sign_triple::FIELD_OBJECT,
+    ];
+    for (tag, field) in tags.into_iter().zip(fields) {
+        for data in field.chunks(MAX_APDU_DATA) {
+            chunks.push(
+                ApduCommand::new(AINGLE_CLA, ins::SIGN_TRIPLE, sign_triple::P1_FIELD, tag)
+                    .with_data(data.to_vec()),
+            );
+        }
+    }
+    chunks
+}
+
+// ============================================================================
+// Transports
+// ============================================================================
+
+/// Carries APDUs between the host and a device
+///
+/// Implemented by [`HidTransport`] for Ledger devices and by
+/// [`MockTransport`] for tests.
+pub trait WalletTransport: Send {
+    /// Send one command and wait for the device's answer
+    ///
+    /// Returns [`WalletError::Disconnected`] if the device went away.
+    fn exchange(&mut self, command: &ApduCommand)
+        -> std::result::Result<ApduResponse, WalletError>;
+}
+
+/// Ledger transport over USB HID
+#[cfg(feature = "hw_wallet")]
+pub struct HidTransport {
+    inner: TransportNativeHID,
+}
+
+#[cfg(feature = "hw_wallet")]
+impl HidTransport {
+    /// Open the first Ledger device found
+    pub fn open() -> std::result::Result<Self, WalletError> {
+        // Initialize HID API for device discovery
+        let api = HidApi::new()
+            .map_err(|e| WalletError::Transport(format!("Failed to initialize HID API: {}", e)))?;
+
+        let inner = TransportNativeHID::new(&api)
+            .map_err(|e| WalletError::Transport(format!("Failed to open HID transport: {}", e)))?;
+
+        Ok(Self { inner })
+    }
+}
+
+#[cfg(feature = "hw_wallet")]
+impl WalletTransport for HidTransport {
+    fn exchange(
+        &mut self,
+        command: &ApduCommand,
+    ) -> std::result::Result<ApduResponse, WalletError> {
+        let cmd = APDUCommand {
+            cla: command.cla,
+            ins: command.ins,
+            p1: command.p1,
+            p2: command.p2,
+            data: command.data.clone(),
+        };
+
+        let answer = self.inner.exchange(&cmd).map_err(|e| match e {
+            // A read or write on an unplugged device fails at the HID layer
+            LedgerHIDError::DeviceNotFound | LedgerHIDError::Hid(_) | LedgerHIDError::Io(_) => {
+                WalletError::Disconnected
+            }
+            other => WalletError::Transport(other.to_string()),
+        })?;
+
+        Ok(ApduResponse {
+            data: answer.data().to_vec(),
+            status: answer.retcode(),
+        })
+    }
+}
+
+/// How a [`MockTransport`] device responds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MockBehavior {
+    /// The user approves every prompt
+    Approve,
+    /// The user rejects every prompt
+    Reject,
+    /// The device vanishes after answering this many commands
+    DisconnectAfter(usize),
+}
+
+/// In-memory stand-in for a Ledger running the AIngle app
+///
+/// Signs with a real Ed25519 key so signatures verify, records every command
+/// and every triple it displays, and can reject prompts or vanish in the
+/// middle of an exchange. Clones share one device, so a test can keep a
+/// handle after boxing a clone into [`WalletManager::with_transport`].
+#[derive(Clone)]
+pub struct MockTransport {
+    device: Arc<Mutex<MockDevice>>,
+}
+
+struct MockDevice {
+    signing_key: SigningKey,
+    behavior: MockBehavior,
+    commands: Vec<ApduCommand>,
+    /// Derivation path of the triple being loaded, if any
+    path: Option<Vec<u8>>,
+    fields: [Vec<u8>; 3],
+    prompts: Vec<TripleDisplay>,
+}
+
+impl MockTransport {
+    /// Create a mock device holding the key derived from `seed`
+    pub fn new(seed: [u8; 32], behavior: MockBehavior) -> Self {
+        Self {
+            device: Arc::new(Mutex::new(MockDevice {
+                signing_key: SigningKey::from_bytes(&seed),
+                behavior,
+                commands: Vec::new(),
+                path: None,
+                fields: Default::default(),
+                prompts: Vec::new(),
+            })),
+        }
+    }
+
+    /// Public key the device signs with
+    pub fn public_key(&self) -> [u8; 32] {
+        self.device().signing_key.verifying_key().to_bytes()
+    }
+
+    /// Change how the device responds from now on
+    pub fn set_behavior(&self, behavior: MockBehavior) {
+        self.device().behavior = behavior;
+    }
+
+    /// Commands the device has answered, oldest first
+    pub fn commands(&self) -> Vec<ApduCommand> {
+        self.device().commands.clone()
+    }
+
+    /// Triples the device has shown for confirmation, oldest first
+    pub fn prompts(&self) -> Vec<TripleDisplay> {
+        self.device().prompts.clone()
+    }
+
+    fn device(&self) -> std::sync::MutexGuard<'_, MockDevice> {
+        self.device.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl WalletTransport for MockTransport {
+    fn exchange(
+        &mut self,
+        command: &ApduCommand,
+    ) -> std::result::Result<ApduResponse, WalletError> {
+        let mut device = self.device();
+        if let MockBehavior::DisconnectAfter(limit) = device.behavior {
+            if device.commands.len() >= limit {
+                return Err(WalletError::Disconnected);
+            }
+        }
+        device.commands.push(command.clone());
+        Ok(device.answer(command))
+    }
+}
+
+impl MockDevice {
+    fn answer(&mut self, command: &ApduCommand) -> ApduResponse {
+        let path_len = DerivationPath::default().to_bytes().len();
+        if command.cla != AINGLE_CLA {
+            return reply(sw::INVALID_CLA, Vec::new());
+        }
+
+        match (command.ins, command.p1) {
+            (ins::GET_VERSION, _) => reply(sw::OK, vec![1, 0, 0]),
+            (ins::GET_PUBLIC_KEY, _) => {
+                reply(sw::OK, self.signing_key.verifying_key().to_bytes().to_vec())
+            }
+            (ins::SIGN_HASH, _) => match command.data.get(path_len..) {
+                Some(hash) if hash.len() == 32 => self.prompt(hash),
+                _ => reply(sw::INVALID_DATA, Vec::new()),
+            },
+            (ins::SIGN_TRIPLE, sign_triple::P1_START) => {
+                if command.data.len() != path_len {
+                    return reply(sw::INVALID_DATA, Vec::new());
+                }
+                self.path = Some(command.data.clone());
+                self.fields = Default::default();
+                reply(sw::OK, Vec::new())
+            }
+            (ins::SIGN_TRIPLE, sign_triple::P1_FIELD) => {
+                let index = (command.p2 as usize).wrapping_sub(1);
+                match self.fields.get_mut(index) {
+                    Some(field) if self.path.is_some() => {
+                        field.extend_from_slice(&command.data);
+                        reply(sw::OK, Vec::new())
+                    }
+                    _ => reply(sw::INVALID_DATA, Vec::new()),
+                }
+            }
+            (ins::SIGN_TRIPLE, sign_triple::P1_CONFIRM) => {
+                if self.path.take().is_none() {
+                    return reply(sw::INVALID_DATA, Vec::new());
+                }
+                let fields = std::mem::take(&mut self.fields);
+                let Ok(message) = encode_triple_message(&fields) else {
+                    return reply(sw::INVALID_DATA, Vec::new());
+                };
+                self.prompts.push(TripleDisplay::from_fields(&fields));
+                self.prompt(&message)
+            }
+            _ => reply(sw::INVALID_INS, Vec::new()),
+        }
+    }
+
+    /// Ask the simulated user to approve signing `message`
+    fn prompt(&self, message: &[u8]) -> ApduResponse {
+        match self.behavior {
+            MockBehavior::Reject => reply(sw::USER_REJECTED, Vec::new()),
+            _ => reply(sw::OK, self.signing_key.sign(message).to_bytes().to_vec()),
+        }
+    }
+}
+
+fn reply(status: u16, data: Vec<u8>) -> ApduResponse {
+    ApduResponse { data, status }
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -872,4 +1280,208 @@ mod tests {
         assert_eq!(response.data, vec![0x01, 0x02]);
         assert_eq!(response.status, 0x9000);
     }
+
+    fn triple(subject: &str) -> SemanticTriple {
+        SemanticTriple {
+            subject: subject.to_string(),
+            predicate: "has_temperature".to_string(),
+            object: TripleObject::Integer(21),
+            source_hash: None,
+        }
+    }
+
+    /// Manager connected to a mock device, plus a handle to inspect it
+    fn connected(behavior: MockBehavior) -> (WalletManager, MockTransport) {
+        let mock = MockTransport::new([7u8; 32], behavior);
+        let mut manager =
+            WalletManager::with_transport(WalletConfig::default(), Box::new(mock.clone()));
+        smol::block_on(manager.connect()).unwrap();
+        (manager, mock)
+    }
+
+    #[test]
+    fn test_sign_triple_approved() {
+        smol::block_on(async {
+            let (mut manager, mock) = connected(MockBehavior::Approve);
+            let path = DerivationPath::default();
+            let public_key = manager.get_public_key(&path).await.unwrap();
+            assert_eq!(public_key.bytes, mock.public_key());
+
+            let t = triple("sensor:greenhouse-1");
+            let sig = manager.sign_triple(&t, &path).await.unwrap();
+            let message = triple_signing_message(&t).unwrap();
+            WalletManager::verify(&public_key, &message, &sig).unwrap();
+            assert_eq!(sig.hash, Hash::from_bytes(&message).as_bytes().to_vec());
+
+            assert_eq!(manager.state(), WalletState::Connected);
+            assert_eq!(manager.stats().signatures_created, 1);
+            assert_eq!(mock.prompts(), vec![TripleDisplay::from_triple(&t)]);
+
+            let commands = mock.commands();
+            let sign: Vec<_> = commands
+                .iter()
+                .filter(|c| c.ins == ins::SIGN_TRIPLE)
+                .collect();
+            assert_eq!(sign.first().unwrap().p1, sign_triple::P1_START);
+            assert_eq!(sign.last().unwrap().p1, sign_triple::P1_CONFIRM);
+        });
+    }
+
+    #[test]
+    fn test_sign_triple_rejected() {
+        smol::block_on(async {
+            let (mut manager, _mock) = connected(MockBehavior::Reject);
+            let result = manager
+                .sign_triple(&triple("sensor:1"), &DerivationPath::default())
+                .await;
+
+            assert!(matches!(
+                result,
+                Err(Error::Wallet(WalletError::UserRejected))
+            ));
+            assert_eq!(manager.state(), WalletState::Connected);
+            assert_eq!(manager.stats().failures, 1);
+            assert_eq!(manager.stats().signatures_created, 0);
+        });
+    }
+
+    #[test]
+    fn test_sign_triple_device_disconnects() {
+        smol::block_on(async {
+            // GET_VERSION and the START chunk are answered, then the device is gone
+            let (mut manager, mock) = connected(MockBehavior::DisconnectAfter(2));
+            let t = triple("sensor:1");
+            let result = manager.sign_triple(&t, &DerivationPath::default()).await;
+
+            assert!(matches!(
+                result,
+                Err(Error::Wallet(WalletError::Disconnected))
+            ));
+            assert_eq!(manager.state(), WalletState::Disconnected);
+            assert!(manager.wallet_info().is_none());
+            assert!(mock.prompts().is_empty());
+
+            let again = manager.sign_triple(&t, &DerivationPath::default()).await;
+            assert!(matches!(
+                again,
+                Err(Error::Wallet(WalletError::NotConnected))
+            ));
+        });
+    }
+
+    #[test]
+    fn test_sign_hash_rejected_is_distinct_from_transport() {
+        smol::block_on(async {
+            let (mut manager, mock) = connected(MockBehavior::Reject);
+            let path = DerivationPath::default();
+            let result = manager.sign_hash(&[1u8; 32], &path).await;
+            assert!(matches!(
+                result,
+                Err(Error::Wallet(WalletError::UserRejected))
+            ));
+
+            mock.set_behavior(MockBehavior::Approve);
+            let sig = manager.sign_hash(&[1u8; 32], &path).await.unwrap();
+            let public_key = manager.get_public_key(&path).await.unwrap();
+            WalletManager::verify(&public_key, &[1u8; 32], &sig).unwrap();
+        });
+    }
+
+    #[test]
+    fn test_sign_triple_long_field_is_chunked() {
+        smol::block_on(async {
+            let (mut manager, mock) = connected(MockBehavior::Approve);
+            let path = DerivationPath::default();
+            let t = triple(&"s".repeat(600));
+            let sig = manager.sign_triple(&t, &path).await.unwrap();
+
+            let subject_chunks: Vec<_> = mock
+                .commands()
+                .into_iter()
+                .filter(|c| c.p1 == sign_triple::P1_FIELD && c.p2 == sign_triple::FIELD_SUBJECT)
+                .collect();
+            assert_eq!(subject_chunks.len(), 3);
+            assert!(subject_chunks.iter().all(|c| c.serialize().is_ok()));
+
+            let public_key = manager.get_public_key(&path).await.unwrap();
+            let message = triple_signing_message(&t).unwrap();
+            WalletManager::verify(&public_key, &message, &sig).unwrap();
+        });
+    }
+
+    #[test]
+    fn test_sign_triple_too_large() {
+        smol::block_on(async {
+            let (mut manager, mock) = connected(MockBehavior::Approve);
+            let sent = mock.commands().len();
+            let result = manager
+                .sign_triple(&triple(&"s".repeat(2000)), &DerivationPath::default())
+                .await;
+
+            assert!(matches!(
+                result,
+                Err(Error::Wallet(WalletError::PayloadTooLarge { .. }))
+            ));
+            assert_eq!(mock.commands().len(), sent);
+        });
+    }
+
+    #[test]
+    fn test_triple_display_truncation() {
+        let display = TripleDisplay::from_triple(&triple(&"é".repeat(40)));
+        assert_eq!(display.subject.chars().count(), DISPLAY_FIELD_CHARS);
+        assert!(display.subject.ends_with("..."));
+        assert_eq!(display.predicate, "has_temperature");
+        assert_eq!(display.object, "21");
+    }
+
+    #[test]
+    fn test_triple_message_distinguishes_object_kind() {
+        let mut literal = triple("s");
+        literal.object = TripleObject::Literal("21".to_string());
+        assert_ne!(
+            triple_signing_message(&literal).unwrap(),
+            triple_signing_message(&triple("s")).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_message() {
+        smol::block_on(async {
+            let (mut manager, _mock) = connected(MockBehavior::Approve);
+            let path = DerivationPath::default();
+            let public_key = manager.get_public_key(&path).await.unwrap();
+            let sig = manager.sign_triple(&triple("a"), &path).await.unwrap();
+
+            let tampered = triple_signing_message(&triple("b")).unwrap();
+            assert!(matches!(
+                WalletManager::verify(&public_key, &tampered, &sig),
+                Err(Error::Crypto(CryptoError::InvalidSignature))
+            ));
+        });
+    }
+
+    #[test]
+    fn test_apdu_response_check() {
+        let ok = ApduResponse {
+            data: vec![1],
+            status: sw::OK,
+        };
+        assert_eq!(ok.check(), Ok(&[1u8][..]));
+
+        let rejected = ApduResponse {
+            data: vec![],
+            status: sw::USER_REJECTED,
+        };
+        assert_eq!(rejected.check(), Err(WalletError::UserRejected));
+
+        let other = ApduResponse {
+            data: vec![],
+            status: 0x6A82,
+        };
+        assert_eq!(
+            other.check(),
+            Err(WalletError::DeviceStatus { status: 0x6A82 })
+        );
+    }
 }
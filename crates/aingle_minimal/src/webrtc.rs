@@ -24,8 +24,9 @@
 //! - **Data Channels**: Reliable, ordered message delivery
 //! - **Low Latency**: Direct peer-to-peer when possible
 //! - **Browser Compatible**: Works with standard WebRTC APIs
+//! - **Authenticated Signaling**: Shared-secret or per-peer tokens keep strangers off the signaling server
 
-use crate::error::{Error, Result};
+use crate::error::{Error, NetworkError, Result};
 use crate::network::Message;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -79,6 +80,58 @@ pub enum SignalingMessage {
     Join { peer_id: String },
     /// Peer left the signaling room
     Leave { peer_id: String },
+    /// Opens a session on a signaling server that requires authentication
+    Authenticate { peer_id: String, token: String },
+    /// The signaling server refused the session
+    Rejected { reason: String },
+}
+
+impl SignalingMessage {
+    /// Peer ID the message claims to come from, if it names one
+    pub fn sender(&self) -> Option<&str> {
+        match self {
+            SignalingMessage::Offer { from, .. }
+            | SignalingMessage::Answer { from, .. }
+            | SignalingMessage::IceCandidate { from, .. } => Some(from),
+            SignalingMessage::Join { peer_id }
+            | SignalingMessage::Leave { peer_id }
+            | SignalingMessage::Authenticate { peer_id, .. } => Some(peer_id),
+            SignalingMessage::Rejected { .. } => None,
+        }
+    }
+}
+
+/// A TURN relay server entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TurnServer {
+    /// TURN URL (e.g., "turn:relay.example.com:3478?transport=udp")
+    pub url: String,
+    /// TURN username
+    pub username: String,
+    /// TURN credential
+    pub credential: String,
+}
+
+impl TurnServer {
+    /// Create a new TURN server entry
+    pub fn new(url: &str, username: &str, credential: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            username: username.to_string(),
+            credential: credential.to_string(),
+        }
+    }
+}
+
+/// An ICE server as handed to the peer connection
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IceServer {
+    /// STUN or TURN URLs
+    pub urls: Vec<String>,
+    /// Username (empty for STUN)
+    pub username: String,
+    /// Credential (empty for STUN)
+    pub credential: String,
 }
 
 /// Configuration for WebRTC transport
@@ -92,6 +145,8 @@ pub struct WebRtcConfig {
     pub turn_username: Option<String>,
     /// TURN credential (if using TURN)
     pub turn_credential: Option<String>,
+    /// Additional TURN servers, offered alongside `turn_server`
+    pub turn_servers: Vec<TurnServer>,
     /// Port for WebSocket signaling server
     pub signaling_port: u16,
     /// Maximum time to wait for ICE connection
@@ -107,6 +162,7 @@ impl Default for WebRtcConfig {
             turn_server: None,
             turn_username: None,
             turn_credential: None,
+            turn_servers: Vec::new(),
             signaling_port: 19080,
             ice_timeout: Duration::from_secs(30),
             channel_label: "aingle".to_string(),
@@ -130,6 +186,83 @@ impl WebRtcConfig {
         self.turn_credential = Some(credential.to_string());
         self
     }
+
+    /// Add another TURN server for relay support
+    pub fn with_turn_server(mut self, server: TurnServer) -> Self {
+        self.turn_servers.push(server);
+        self
+    }
+
+    /// ICE servers for new peer connections: the STUN server, then every TURN server
+    pub fn ice_servers(&self) -> Vec<IceServer> {
+        let mut servers = vec![IceServer {
+            urls: vec![self.stun_server.clone()],
+            username: String::new(),
+            credential: String::new(),
+        }];
+
+        if let Some(turn) = &self.turn_server {
+            servers.push(IceServer {
+                urls: vec![turn.clone()],
+                username: self.turn_username.clone().unwrap_or_default(),
+                credential: self.turn_credential.clone().unwrap_or_default(),
+            });
+        }
+        servers.extend(self.turn_servers.iter().map(|turn| IceServer {
+            urls: vec![turn.url.clone()],
+            username: turn.username.clone(),
+            credential: turn.credential.clone(),
+        }));
+        servers
+    }
+
+    /// Peer connection configuration carrying the configured ICE servers
+    #[cfg(feature = "webrtc")]
+    pub fn rtc_configuration(&self) -> RTCConfiguration {
+        let ice_servers = self
+            .ice_servers()
+            .into_iter()
+            .map(|server| RTCIceServer {
+                urls: server.urls,
+                username: server.username,
+                credential: server.credential,
+                ..Default::default()
+            })
+            .collect();
+
+        RTCConfiguration {
+            ice_servers,
+            ..Default::default()
+        }
+    }
+}
+
+/// ICE candidate type, from the `typ` attribute of a candidate line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IceCandidateType {
+    /// Local interface address
+    Host,
+    /// Public address discovered through STUN
+    ServerReflexive,
+    /// Address learned from a peer's connectivity checks
+    PeerReflexive,
+    /// Address allocated on a TURN relay
+    Relay,
+}
+
+impl IceCandidateType {
+    /// Parse the type of an ICE candidate line
+    pub fn from_candidate(candidate: &str) -> Option<Self> {
+        let mut fields = candidate.split_whitespace();
+        fields.find(|field| *field == "typ")?;
+        match fields.next()? {
+            "host" => Some(Self::Host),
+            "srflx" => Some(Self::ServerReflexive),
+            "prflx" => Some(Self::PeerReflexive),
+            "relay" => Some(Self::Relay),
+            _ => None,
+        }
+    }
 }
 
 /// Peer connection state
@@ -164,10 +297,32 @@ pub struct WebRtcStats {
     pub rtt_ms: u32,
     /// Number of ICE candidates gathered
     pub ice_candidates: u32,
+    /// Host candidates exchanged
+    pub host_candidates: u32,
+    /// Server-reflexive (STUN) candidates exchanged
+    pub srflx_candidates: u32,
+    /// Peer-reflexive candidates exchanged
+    pub prflx_candidates: u32,
+    /// Relay (TURN) candidates exchanged; non-zero once a TURN allocation succeeds
+    pub relay_candidates: u32,
     /// Whether TURN relay is being used
     pub using_relay: bool,
 }
 
+impl WebRtcStats {
+    /// Count an ICE candidate sent to or received from the peer
+    pub fn record_candidate(&mut self, candidate: &str) {
+        self.ice_candidates += 1;
+        match IceCandidateType::from_candidate(candidate) {
+            Some(IceCandidateType::Host) => self.host_candidates += 1,
+            Some(IceCandidateType::ServerReflexive) => self.srflx_candidates += 1,
+            Some(IceCandidateType::PeerReflexive) => self.prflx_candidates += 1,
+            Some(IceCandidateType::Relay) => self.relay_candidates += 1,
+            None => {}
+        }
+    }
+}
+
 /// A WebRTC peer connection
 #[derive(Debug)]
 pub struct PeerConnection {
@@ -369,26 +524,8 @@ impl WebRtcServer {
                 .as_ref()
                 .ok_or_else(|| Error::network("WebRTC API not initialized".to_string()))?;
 
-            // Configure ICE servers
-            let mut ice_servers = vec![RTCIceServer {
-                urls: vec![self.config.stun_server.clone()],
-                ..Default::default()
-            }];
-
-            // Add TURN server if configured
-            if let Some(turn) = &self.config.turn_server {
-                ice_servers.push(RTCIceServer {
-                    urls: vec![turn.clone()],
-                    username: self.config.turn_username.clone().unwrap_or_default(),
-                    credential: self.config.turn_credential.clone().unwrap_or_default(),
-                    ..Default::default()
-                });
-            }
-
-            let rtc_config = RTCConfiguration {
-                ice_servers,
-                ..Default::default()
-            };
+            // Configure STUN and TURN servers
+            let rtc_config = self.config.rtc_configuration();
 
             // Create new RTCPeerConnection
             let pc = api
@@ -551,6 +688,12 @@ impl WebRtcServer {
             stats.messages_received += peer.stats.messages_received;
             stats.bytes_sent += peer.stats.bytes_sent;
             stats.bytes_received += peer.stats.bytes_received;
+            stats.ice_candidates += peer.stats.ice_candidates;
+            stats.host_candidates += peer.stats.host_candidates;
+            stats.srflx_candidates += peer.stats.srflx_candidates;
+            stats.prflx_candidates += peer.stats.prflx_candidates;
+            stats.relay_candidates += peer.stats.relay_candidates;
+            stats.using_relay |= peer.stats.using_relay;
         }
         stats
     }
@@ -558,6 +701,11 @@ impl WebRtcServer {
     /// Queue a signaling message to be sent
     #[cfg(feature = "webrtc")]
     pub fn queue_signaling(&mut self, message: SignalingMessage) {
+        if let SignalingMessage::IceCandidate { to, candidate, .. } = &message {
+            if let Some(peer) = self.peers.get_mut(to) {
+                peer.stats.record_candidate(candidate);
+            }
+        }
         self.signaling_queue.push(message);
     }
 
@@ -603,9 +751,11 @@ impl WebRtcServer {
                         sdp_mid,
                         sdp_mline_index
                     );
+                    if let Some(peer) = self.peers.get_mut(&from) {
+                        peer.stats.record_candidate(&candidate);
+                    }
                     // In a real implementation:
                     // Add ICE candidate to the peer connection
-                    let _ = candidate; // suppress warning
                 }
             }
             SignalingMessage::Join { peer_id } => {
@@ -616,6 +766,10 @@ impl WebRtcServer {
                 // Disconnect if we were connected
                 let _ = self.disconnect(&peer_id).await;
             }
+            SignalingMessage::Authenticate { .. } => {}
+            SignalingMessage::Rejected { reason } => {
+                log::warn!("Signaling server rejected this node: {}", reason);
+            }
         }
         Ok(())
     }
@@ -649,6 +803,64 @@ impl Default for SignalingConfig {
     }
 }
 
+/// How the signaling server authenticates peers before relaying for them
+///
+/// With anything but `Open`, a client must start its session with
+/// `SignalingMessage::Authenticate`. A plain `Join` is refused, so strangers
+/// cannot enumerate peers or relay offers through a public endpoint.
+#[derive(Debug, Clone, Default)]
+pub enum SignalingAuth {
+    /// Any peer may join (private networks only)
+    #[default]
+    Open,
+    /// Every peer presents the same shared secret
+    SharedSecret(String),
+    /// Each peer presents its own token, keyed by peer ID
+    PeerTokens(HashMap<String, String>),
+}
+
+impl SignalingAuth {
+    /// Check the message that opens a signaling session
+    ///
+    /// Returns the peer ID to register, or `None` if the message does not open
+    /// a session.
+    pub fn admit(&self, message: &SignalingMessage) -> Result<Option<String>> {
+        let (peer_id, token) = match message {
+            SignalingMessage::Join { peer_id } => (peer_id, None),
+            SignalingMessage::Authenticate { peer_id, token } => (peer_id, Some(token.as_str())),
+            _ => return Ok(None),
+        };
+
+        let expected = match self {
+            SignalingAuth::Open => return Ok(Some(peer_id.clone())),
+            SignalingAuth::SharedSecret(secret) => Some(secret),
+            SignalingAuth::PeerTokens(tokens) => tokens.get(peer_id),
+        };
+        let reason = match (expected, token) {
+            (_, None) => "authentication required",
+            (None, Some(_)) => "unknown peer",
+            (Some(expected), Some(token)) if tokens_match(expected, token) => {
+                return Ok(Some(peer_id.clone()));
+            }
+            (Some(_), Some(_)) => "invalid token",
+        };
+        Err(Error::Network(NetworkError::HandshakeFailed {
+            addr: peer_id.clone(),
+            reason: reason.to_string(),
+        }))
+    }
+}
+
+/// Compare tokens without exiting early on the first differing byte
+fn tokens_match(expected: &str, presented: &str) -> bool {
+    expected.len() == presented.len()
+        && expected
+            .bytes()
+            .zip(presented.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
 /// A connected client on the signaling server
 #[cfg(feature = "webrtc")]
 #[derive(Debug)]
@@ -686,6 +898,8 @@ struct ConnectedPeer {
 pub struct SignalingServer {
     /// Server configuration
     config: SignalingConfig,
+    /// Peer authentication policy
+    auth: Arc<SignalingAuth>,
     /// Connected clients indexed by peer_id
     clients: Arc<RwLock<HashMap<String, ConnectedPeer>>>,
     /// Running state
@@ -700,12 +914,19 @@ impl SignalingServer {
     pub fn new(config: SignalingConfig) -> Self {
         Self {
             config,
+            auth: Arc::new(SignalingAuth::Open),
             clients: Arc::new(RwLock::new(HashMap::new())),
             running: Arc::new(std::sync::atomic::AtomicBool::new(false)),
             server_task: None,
         }
     }
 
+    /// Require peers to authenticate before joining
+    pub fn with_auth(mut self, auth: SignalingAuth) -> Self {
+        self.auth = Arc::new(auth);
+        self
+    }
+
     /// Start the signaling server
     pub async fn start(&mut self) -> Result<()> {
         if self.running.load(std::sync::atomic::Ordering::SeqCst) {
@@ -723,6 +944,7 @@ impl SignalingServer {
             .store(true, std::sync::atomic::Ordering::SeqCst);
 
         let clients = self.clients.clone();
+        let auth = self.auth.clone();
         let running = self.running.clone();
         let max_connections = self.config.max_connections;
 
@@ -746,10 +968,13 @@ impl SignalingServer {
                         log::debug!("New signaling connection from {}", addr);
 
                         let clients = clients.clone();
+                        let auth = auth.clone();
 
                         // Handle this connection in a separate task
                         smol::spawn(async move {
-                            if let Err(e) = Self::handle_connection(stream, addr, clients).await {
+                            if let Err(e) =
+                                Self::handle_connection(stream, addr, clients, auth).await
+                            {
                                 log::warn!("Connection error from {}: {}", addr, e);
                             }
                         })
@@ -773,6 +998,7 @@ impl SignalingServer {
         stream: smol::net::TcpStream,
         addr: SocketAddr,
         clients: Arc<RwLock<HashMap<String, ConnectedPeer>>>,
+        auth: Arc<SignalingAuth>,
     ) -> Result<()> {
         // Upgrade to WebSocket
         let ws_stream = async_tungstenite::accept_async(stream)
@@ -781,15 +1007,28 @@ impl SignalingServer {
 
         let (mut ws_sink, mut ws_stream) = ws_stream.split();
 
-        // Wait for Join message to get peer ID
+        // Wait for Join (or Authenticate) message to get peer ID
         let peer_id = loop {
             match ws_stream.next().await {
                 Some(Ok(WsMessage::Text(text))) => {
                     match serde_json::from_str::<SignalingMessage>(&text) {
-                        Ok(SignalingMessage::Join { peer_id }) => break peer_id,
-                        Ok(_) => {
-                            log::warn!("Expected Join message from {}", addr);
-                        }
+                        Ok(msg) => match auth.admit(&msg) {
+                            Ok(Some(peer_id)) => break peer_id,
+                            Ok(None) => {
+                                log::warn!("Expected Join message from {}", addr);
+                            }
+                            Err(e) => {
+                                log::warn!("Rejected signaling peer from {}: {}", addr, e);
+                                let rejected = SignalingMessage::Rejected {
+                                    reason: e.to_string(),
+                                };
+                                if let Ok(json) = serde_json::to_string(&rejected) {
+                                    let _ = ws_sink.send(WsMessage::Text(json)).await;
+                                }
+                                let _ = ws_sink.close().await;
+                                return Ok(());
+                            }
+                        },
                         Err(e) => {
                             log::warn!("Invalid message from {}: {}", addr, e);
                         }
@@ -905,11 +1144,20 @@ impl SignalingServer {
         from: &str,
         message: SignalingMessage,
     ) {
+        // Never relay on behalf of a different peer than the one that joined
+        if message.sender().is_some_and(|sender| sender != from) {
+            log::warn!("Dropping message from '{}' claiming another sender", from);
+            return;
+        }
+
         let target_peer_id = match &message {
             SignalingMessage::Offer { to, .. } => Some(to.clone()),
             SignalingMessage::Answer { to, .. } => Some(to.clone()),
             SignalingMessage::IceCandidate { to, .. } => Some(to.clone()),
-            SignalingMessage::Join { .. } | SignalingMessage::Leave { .. } => None,
+            SignalingMessage::Join { .. }
+            | SignalingMessage::Leave { .. }
+            | SignalingMessage::Authenticate { .. }
+            | SignalingMessage::Rejected { .. } => None,
         };
 
         if let Some(target) = target_peer_id {
@@ -992,6 +1240,8 @@ pub struct SignalingClient {
     server_url: String,
     /// Local peer ID
     peer_id: String,
+    /// Token presented to servers that require authentication
+    token: Option<String>,
     /// Sender for outgoing messages
     tx: Option<Sender<SignalingMessage>>,
     /// Receiver for incoming messages
@@ -1007,12 +1257,19 @@ impl SignalingClient {
         Self {
             server_url: server_url.to_string(),
             peer_id: peer_id.to_string(),
+            token: None,
             tx: None,
             rx: None,
             connected: false,
         }
     }
 
+    /// Authenticate with the given token when connecting
+    pub fn with_token(mut self, token: &str) -> Self {
+        self.token = Some(token.to_string());
+        self
+    }
+
     /// Connect to the signaling server
     pub async fn connect(&mut self) -> Result<()> {
         if self.connected {
@@ -1028,8 +1285,14 @@ impl SignalingClient {
         let (mut ws_sink, mut ws_stream) = ws_stream.split();
 
         // Send Join message
-        let join_msg = SignalingMessage::Join {
-            peer_id: self.peer_id.clone(),
+        let join_msg = match &self.token {
+            Some(token) => SignalingMessage::Authenticate {
+                peer_id: self.peer_id.clone(),
+                token: token.clone(),
+            },
+            None => SignalingMessage::Join {
+                peer_id: self.peer_id.clone(),
+            },
         };
         let json =
             serde_json::to_string(&join_msg).map_err(|e| Error::Serialization(e.to_string()))?;
@@ -1244,4 +1507,135 @@ mod tests {
         let json = serde_json::to_string(&leave).unwrap();
         assert!(json.contains("Leave"));
     }
+
+    #[test]
+    fn test_ice_servers_include_every_turn_server() {
+        let config = WebRtcConfig::with_stun("stun:stun.example.com:3478")
+            .with_turn("turn:a.example.com:3478", "alice", "secret-a")
+            .with_turn_server(TurnServer::new(
+                "turns:b.example.com:5349",
+                "bob",
+                "secret-b",
+            ));
+
+        let servers = config.ice_servers();
+        assert_eq!(servers.len(), 3);
+        assert_eq!(servers[0].urls, vec!["stun:stun.example.com:3478"]);
+        assert!(servers[0].username.is_empty());
+        assert_eq!(
+            servers[2],
+            IceServer {
+                urls: vec!["turns:b.example.com:5349".to_string()],
+                username: "bob".to_string(),
+                credential: "secret-b".to_string(),
+            }
+        );
+
+        let rtc = config.rtc_configuration();
+        assert_eq!(rtc.ice_servers.len(), 3);
+        assert_eq!(rtc.ice_servers[1].urls, vec!["turn:a.example.com:3478"]);
+        assert_eq!(rtc.ice_servers[1].username, "alice");
+        assert_eq!(rtc.ice_servers[1].credential, "secret-a");
+    }
+
+    #[test]
+    fn test_ice_candidate_types_are_counted() {
+        assert_eq!(
+            IceCandidateType::from_candidate(
+                "candidate:1 1 UDP 2130706431 192.168.1.1 54321 typ host"
+            ),
+            Some(IceCandidateType::Host)
+        );
+        assert_eq!(
+            IceCandidateType::from_candidate("candidate:1 1 UDP 1"),
+            None
+        );
+
+        let mut stats = WebRtcStats::default();
+        stats.record_candidate("candidate:1 1 udp 2130706431 10.0.0.2 5000 typ host");
+        stats.record_candidate(
+            "candidate:2 1 udp 1694498815 203.0.113.7 6000 typ srflx raddr 10.0.0.2 rport 5000",
+        );
+        stats.record_candidate(
+            "candidate:3 1 udp 16777215 198.51.100.1 7000 typ relay raddr 203.0.113.7 rport 6000",
+        );
+        assert_eq!(stats.ice_candidates, 3);
+        assert_eq!(
+            (
+                stats.host_candidates,
+                stats.srflx_candidates,
+                stats.relay_candidates
+            ),
+            (1, 1, 1)
+        );
+    }
+
+    #[test]
+    fn test_signaling_auth_open_admits_join() {
+        let join = SignalingMessage::Join {
+            peer_id: "peer-a".to_string(),
+        };
+        let admitted = SignalingAuth::Open.admit(&join).unwrap();
+        assert_eq!(admitted.as_deref(), Some("peer-a"));
+
+        let leave = SignalingMessage::Leave {
+            peer_id: "peer-a".to_string(),
+        };
+        assert!(SignalingAuth::Open.admit(&leave).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_signaling_auth_rejects_missing_and_wrong_secret() {
+        let auth = SignalingAuth::SharedSecret("s3cret".to_string());
+        let authenticate = |token: &str| SignalingMessage::Authenticate {
+            peer_id: "peer-a".to_string(),
+            token: token.to_string(),
+        };
+
+        let join = SignalingMessage::Join {
+            peer_id: "peer-a".to_string(),
+        };
+        let err = auth.admit(&join).unwrap_err();
+        assert!(err.to_string().contains("authentication required"));
+        assert!(auth.admit(&authenticate("s3cre")).is_err());
+        assert!(auth.admit(&authenticate("wrong!")).is_err());
+        assert_eq!(
+            auth.admit(&authenticate("s3cret")).unwrap().as_deref(),
+            Some("peer-a")
+        );
+    }
+
+    #[test]
+    fn test_signaling_auth_per_peer_tokens() {
+        let auth = SignalingAuth::PeerTokens(HashMap::from([
+            ("peer-a".to_string(), "token-a".to_string()),
+            ("peer-b".to_string(), "token-b".to_string()),
+        ]));
+        let authenticate = |peer_id: &str, token: &str| SignalingMessage::Authenticate {
+            peer_id: peer_id.to_string(),
+            token: token.to_string(),
+        };
+
+        assert!(auth.admit(&authenticate("peer-a", "token-a")).is_ok());
+        // Another peer's token does not work
+        assert!(auth.admit(&authenticate("peer-a", "token-b")).is_err());
+        let err = auth
+            .admit(&authenticate("stranger", "token-a"))
+            .unwrap_err();
+        assert!(err.to_string().contains("unknown peer"));
+    }
+
+    #[test]
+    fn test_signaling_message_sender() {
+        let offer = SignalingMessage::Offer {
+            from: "peer-a".to_string(),
+            to: "peer-b".to_string(),
+            sdp: String::new(),
+        };
+        assert_eq!(offer.sender(), Some("peer-a"));
+        let rejected = SignalingMessage::Rejected {
+            reason: "invalid token".to_string(),
+        };
+        assert_eq!(rejected.sender(), None);
+    }
 }
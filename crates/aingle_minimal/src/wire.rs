@@ -0,0 +1,553 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Wire encodings for messages exchanged between nodes.
+//!
+//! Messages travel either as JSON, which every node understands, or as CBOR
+//! (feature: coap), which is much smaller on constrained links. Nodes list
+//! the encodings they support in the `encodings` field of
+//! [`Message::Ping`](crate::network::Message::Ping) and
+//! [`Message::Pong`](crate::network::Message::Pong). A node keeps sending
+//! JSON to a peer until the peer's handshake shows it can read CBOR. Peers
+//! that predate CBOR send no list, so they keep getting JSON.
+//!
+//! The same types have two layouts. In JSON they keep the field names and
+//! hex strings they always had, so old peers can still read them. In CBOR
+//! every struct becomes a map with small integer keys, enums become integer
+//! tags, and hashes, keys, signatures and byte payloads are sent as raw
+//! bytes. The keys are part of the wire format. A key may be added but never
+//! reused, and a field that is missing on decode is only accepted if it is
+//! an `Option`.
+//!
+//! # Example
+//!
+//! ```
+//! # use aingle_minimal::{Hash, WireEncoding};
+//! # use aingle_minimal::network::Message;
+//! # fn main() -> Result<(), aingle_minimal::Error> {
+//! let message = Message::NewRecord { hash: Hash::from_bytes(b"reading") };
+//!
+//! let json = WireEncoding::Json.encode(&message)?;
+//! let decoded: Message = WireEncoding::Json.decode(&json)?;
+//! assert!(matches!(decoded, Message::NewRecord { .. }));
+//!
+//! # #[cfg(feature = "coap")]
+//! # {
+//! let cbor = WireEncoding::Cbor.encode(&message)?;
+//! assert!(cbor.len() < json.len() / 2);
+//! # }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Error, Result};
+use serde::de::{self, DeserializeOwned, IntoDeserializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// How a message is encoded on the wire
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(remote = "Self", rename_all = "lowercase")]
+pub enum WireEncoding {
+    /// JSON with field names, understood by every node
+    Json,
+    /// CBOR with integer keys (feature: coap)
+    Cbor,
+}
+
+impl WireEncoding {
+    /// The encodings this build supports, most preferred first
+    pub fn supported() -> Vec<Self> {
+        if cfg!(feature = "coap") {
+            vec![Self::Cbor, Self::Json]
+        } else {
+            vec![Self::Json]
+        }
+    }
+
+    /// Pick the encoding to use towards a peer
+    ///
+    /// Returns the first of `ours` that the peer also listed in `theirs`.
+    /// JSON is the fallback when the two lists have nothing in common.
+    pub fn negotiate(ours: &[Self], theirs: &[Self]) -> Self {
+        ours.iter()
+            .copied()
+            .find(|encoding| theirs.contains(encoding))
+            .unwrap_or(Self::Json)
+    }
+
+    /// CoAP Content-Format number (RFC 7252 §12.3)
+    pub fn content_format(self) -> u8 {
+        match self {
+            Self::Json => 50,
+            Self::Cbor => 60,
+        }
+    }
+
+    /// Map a CoAP Content-Format number back to an encoding
+    pub fn from_content_format(content_format: u8) -> Option<Self> {
+        match content_format {
+            50 => Some(Self::Json),
+            60 => Some(Self::Cbor),
+            _ => None,
+        }
+    }
+
+    /// Encode a value
+    pub fn encode<T: Serialize>(self, value: &T) -> Result<Vec<u8>> {
+        match self {
+            Self::Json => Ok(serde_json::to_vec(value)?),
+            #[cfg(feature = "coap")]
+            Self::Cbor => {
+                let mut buf = Vec::new();
+                ciborium::into_writer(value, &mut buf)
+                    .map_err(|e| Error::Serialization(e.to_string()))?;
+                Ok(buf)
+            }
+            #[cfg(not(feature = "coap"))]
+            Self::Cbor => Err(Self::cbor_unavailable()),
+        }
+    }
+
+    /// Decode a value
+    pub fn decode<T: DeserializeOwned>(self, bytes: &[u8]) -> Result<T> {
+        match self {
+            Self::Json => Ok(serde_json::from_slice(bytes)?),
+            #[cfg(feature = "coap")]
+            Self::Cbor => {
+                ciborium::from_reader(bytes).map_err(|e| Error::Serialization(e.to_string()))
+            }
+            #[cfg(not(feature = "coap"))]
+            Self::Cbor => Err(Self::cbor_unavailable()),
+        }
+    }
+
+    #[cfg(not(feature = "coap"))]
+    fn cbor_unavailable() -> Error {
+        Error::Serialization("CBOR support requires the coap feature".to_string())
+    }
+}
+
+impl fmt::Display for WireEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json => write!(f, "json"),
+            Self::Cbor => write!(f, "cbor"),
+        }
+    }
+}
+
+/// Serializes a byte slice as a byte string instead of a sequence of numbers
+pub(crate) struct Bytes<'a>(pub &'a [u8]);
+
+impl Serialize for Bytes<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+/// Deserializes a byte string, or a sequence of numbers, into a `Vec<u8>`
+pub(crate) struct ByteBuf(pub Vec<u8>);
+
+impl<'de> Deserialize<'de> for ByteBuf {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct ByteBufVisitor;
+
+        impl<'de> de::Visitor<'de> for ByteBufVisitor {
+            type Value = ByteBuf;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "a byte string")
+            }
+
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> std::result::Result<ByteBuf, E> {
+                Ok(ByteBuf(v.to_vec()))
+            }
+
+            fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> std::result::Result<ByteBuf, E> {
+                Ok(ByteBuf(v))
+            }
+
+            fn visit_seq<A: de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> std::result::Result<ByteBuf, A::Error> {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(byte) = seq.next_element()? {
+                    bytes.push(byte);
+                }
+                Ok(ByteBuf(bytes))
+            }
+        }
+
+        deserializer.deserialize_bytes(ByteBufVisitor)
+    }
+}
+
+/// Deserialize a byte string of exactly `N` bytes
+pub(crate) fn fixed_bytes<'de, D: Deserializer<'de>, const N: usize>(
+    deserializer: D,
+) -> std::result::Result<[u8; N], D::Error> {
+    let ByteBuf(bytes) = ByteBuf::deserialize(deserializer)?;
+    <[u8; N]>::try_from(bytes.as_slice())
+        .map_err(|_| de::Error::invalid_length(bytes.len(), &format!("{} bytes", N).as_str()))
+}
+
+/// Unwrap a decoded integer-keyed field; absent `Option` fields become `None`
+pub(crate) fn keyed_field<'de, T: Deserialize<'de>, E: de::Error>(
+    value: Option<T>,
+    name: &'static str,
+) -> std::result::Result<T, E> {
+    match value {
+        Some(value) => Ok(value),
+        None => T::deserialize(<() as IntoDeserializer<'de, E>>::into_deserializer(()))
+            .map_err(|_| E::missing_field(name)),
+    }
+}
+
+/// Value of one integer-keyed field, optionally encoded as a byte string
+macro_rules! keyed_value {
+    ($value:expr) => {
+        $value
+    };
+    ($value:expr, bytes) => {
+        &$crate::wire::Bytes($value)
+    };
+}
+
+/// Decode the next integer-keyed field value from a `MapAccess`
+macro_rules! keyed_next {
+    ($map:ident) => {
+        $map.next_value()?
+    };
+    ($map:ident, bytes) => {
+        $map.next_value::<$crate::wire::ByteBuf>()?.0
+    };
+}
+
+/// Implement `Serialize` and `Deserialize` for a struct with integer keys
+///
+/// The struct must also derive both traits with `#[serde(remote = "Self")]`;
+/// human-readable formats use that derived layout. Fields marked `as bytes`
+/// are `Vec<u8>` sent as a byte string.
+macro_rules! keyed_struct {
+    ($name:ident { $($key:literal => $field:ident $(as $with:ident)?),+ $(,)? }) => {
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+                if serializer.is_human_readable() {
+                    return $name::serialize(self, serializer);
+                }
+                use serde::ser::SerializeMap;
+                let mut map = serializer.serialize_map(Some(<[u8]>::len(&[$($key),+])))?;
+                $(
+                    map.serialize_entry(
+                        &($key as u8),
+                        $crate::wire::keyed_value!(&self.$field $(, $with)?),
+                    )?;
+                )+
+                map.end()
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+                if deserializer.is_human_readable() {
+                    return $name::deserialize(deserializer);
+                }
+
+                struct KeyedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for KeyedVisitor {
+                    type Value = $name;
+
+                    fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(f, "an integer-keyed {}", stringify!($name))
+                    }
+
+                    fn visit_map<A: serde::de::MapAccess<'de>>(
+                        self,
+                        mut map: A,
+                    ) -> std::result::Result<$name, A::Error> {
+                        $( let mut $field = None; )+
+                        while let Some(key) = map.next_key::<u64>()? {
+                            match key {
+                                $( $key => $field = Some($crate::wire::keyed_next!(map $(, $with)?)), )+
+                                _ => {
+                                    map.next_value::<serde::de::IgnoredAny>()?;
+                                }
+                            }
+                        }
+                        Ok($name {
+                            $( $field: $crate::wire::keyed_field($field, stringify!($field))?, )+
+                        })
+                    }
+                }
+
+                deserializer.deserialize_map(KeyedVisitor)
+            }
+        }
+    };
+}
+
+/// Implement `Serialize` and `Deserialize` for an enum with struct variants
+/// and integer keys
+///
+/// Binary formats get one map per message: key `0` holds the variant tag and
+/// the variant's fields follow under their own keys, which therefore start at
+/// `1`. The enum must also derive both traits with `#[serde(remote = "Self")]`
+/// for human-readable formats.
+macro_rules! keyed_enum {
+    ($name:ident {
+        $($tag:literal => $variant:ident { $($key:literal => $field:ident $(as $with:ident)?),* $(,)? }),+ $(,)?
+    }) => {
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+                if serializer.is_human_readable() {
+                    return $name::serialize(self, serializer);
+                }
+                use serde::ser::SerializeMap;
+                match self {
+                    $(
+                        $name::$variant { $($field),* } => {
+                            let mut map =
+                                serializer.serialize_map(Some(1 + <[u8]>::len(&[$($key),*])))?;
+                            map.serialize_entry(&0u8, &($tag as u8))?;
+                            $(
+                                map.serialize_entry(
+                                    &($key as u8),
+                                    $crate::wire::keyed_value!($field $(, $with)?),
+                                )?;
+                            )*
+                            map.end()
+                        }
+                    )+
+                }
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+                if deserializer.is_human_readable() {
+                    return $name::deserialize(deserializer);
+                }
+
+                struct KeyedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for KeyedVisitor {
+                    type Value = $name;
+
+                    fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(f, "an integer-keyed {}", stringify!($name))
+                    }
+
+                    fn visit_map<A: serde::de::MapAccess<'de>>(
+                        self,
+                        mut map: A,
+                    ) -> std::result::Result<$name, A::Error> {
+                        use serde::de::Error;
+                        let tag: u64 = match map.next_key::<u64>()? {
+                            Some(0) => map.next_value()?,
+                            _ => return Err(A::Error::missing_field("variant tag")),
+                        };
+                        match tag {
+                            $(
+                                $tag => {
+                                    $( let mut $field = None; )*
+                                    while let Some(key) = map.next_key::<u64>()? {
+                                        match key {
+                                            $( $key => $field = Some($crate::wire::keyed_next!(map $(, $with)?)), )*
+                                            _ => {
+                                                map.next_value::<serde::de::IgnoredAny>()?;
+                                            }
+                                        }
+                                    }
+                                    Ok($name::$variant {
+                                        $( $field: $crate::wire::keyed_field($field, stringify!($field))?, )*
+                                    })
+                                }
+                            )+
+                            tag => Err(A::Error::custom(format!(
+                                "unknown {} variant tag {}",
+                                stringify!($name),
+                                tag
+                            ))),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_map(KeyedVisitor)
+            }
+        }
+    };
+}
+
+/// Implement `Serialize` and `Deserialize` for a fieldless enum sent as an
+/// integer tag in binary formats
+///
+/// The enum must also derive both traits with `#[serde(remote = "Self")]`
+/// for human-readable formats.
+macro_rules! keyed_unit_enum {
+    ($name:ident { $($tag:literal => $variant:ident),+ $(,)? }) => {
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+                if serializer.is_human_readable() {
+                    return $name::serialize(self, serializer);
+                }
+                serializer.serialize_u8(match self {
+                    $( $name::$variant => $tag, )+
+                })
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+                if deserializer.is_human_readable() {
+                    return $name::deserialize(deserializer);
+                }
+                match <u8 as serde::Deserialize>::deserialize(deserializer)? {
+                    $( $tag => Ok($name::$variant), )+
+                    tag => Err(<D::Error as serde::de::Error>::custom(format!(
+                        "unknown {} tag {}",
+                        stringify!($name),
+                        tag
+                    ))),
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use {keyed_enum, keyed_next, keyed_struct, keyed_unit_enum, keyed_value};
+
+keyed_unit_enum!(WireEncoding {
+    0 => Json,
+    1 => Cbor,
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::Message;
+    use crate::types::{
+        Action, ActionType, AgentPubKey, Entry, EntryType, Hash, Record, Signature, Timestamp,
+    };
+
+    fn record(content: &[u8]) -> Record {
+        Record {
+            action: Action {
+                action_type: ActionType::Create,
+                author: AgentPubKey([7; 32]),
+                timestamp: Timestamp(1_760_000_000_000_000),
+                seq: 42,
+                prev_action: Some(Hash::from_bytes(b"previous")),
+                entry_hash: None,
+                signature: Signature([9; 64]),
+            },
+            entry: Some(Entry {
+                entry_type: EntryType::App,
+                content: content.to_vec(),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_negotiate() {
+        use WireEncoding::{Cbor, Json};
+        assert_eq!(WireEncoding::negotiate(&[Cbor, Json], &[Json, Cbor]), Cbor);
+        assert_eq!(WireEncoding::negotiate(&[Cbor, Json], &[Json]), Json);
+        assert_eq!(WireEncoding::negotiate(&[Json], &[Cbor, Json]), Json);
+        // Old peers advertise nothing
+        assert_eq!(WireEncoding::negotiate(&[Cbor, Json], &[]), Json);
+    }
+
+    #[test]
+    fn test_content_format() {
+        for encoding in [WireEncoding::Json, WireEncoding::Cbor] {
+            assert_eq!(
+                WireEncoding::from_content_format(encoding.content_format()),
+                Some(encoding)
+            );
+        }
+        assert_eq!(WireEncoding::from_content_format(0), None);
+    }
+
+    #[test]
+    fn test_json_layout_is_unchanged() {
+        let json = serde_json::to_value(record(b"{}")).unwrap();
+        assert_eq!(json["action"]["action_type"], "Create");
+        assert_eq!(json["action"]["seq"], 42);
+        assert_eq!(json["action"]["author"], "07".repeat(32));
+        assert!(json["action"]["entry_hash"].is_null());
+        assert_eq!(json["entry"]["content"], serde_json::json!([123, 125]));
+        assert_eq!(
+            serde_json::to_value(WireEncoding::Cbor).unwrap(),
+            serde_json::json!("cbor")
+        );
+    }
+
+    #[cfg(feature = "coap")]
+    #[test]
+    fn test_cbor_round_trip() {
+        let original = record(&[0xAB; 100]);
+        let message = Message::GossipResponse {
+            records: vec![original.clone()],
+        };
+        let bytes = WireEncoding::Cbor.encode(&message).unwrap();
+        let Message::GossipResponse { records } = WireEncoding::Cbor.decode(&bytes).unwrap() else {
+            panic!("expected a gossip response");
+        };
+        let decoded = &records[0];
+        assert_eq!(decoded.action.hash(), original.action.hash());
+        assert_eq!(decoded.action.prev_action, original.action.prev_action);
+        assert_eq!(decoded.entry.as_ref().unwrap().content, vec![0xAB; 100]);
+
+        let json = WireEncoding::Json.encode(&message).unwrap();
+        assert!(
+            bytes.len() * 2 < json.len(),
+            "{} vs {}",
+            bytes.len(),
+            json.len()
+        );
+    }
+
+    #[cfg(feature = "coap")]
+    #[test]
+    fn test_cbor_keys_are_integers() {
+        let bytes = WireEncoding::Cbor
+            .encode(&Message::GossipRequest {
+                from_seq: 3,
+                limit: 10,
+            })
+            .unwrap();
+        // {0: 2, 1: 3, 2: 10}
+        assert_eq!(bytes, vec![0xA3, 0x00, 0x02, 0x01, 0x03, 0x02, 0x0A]);
+    }
+
+    #[cfg(feature = "coap")]
+    #[test]
+    fn test_cbor_rejects_bad_input() {
+        // Unknown variant tag
+        assert!(WireEncoding::Cbor
+            .decode::<Message>(&[0xA1, 0x00, 0x18, 0x63])
+            .is_err());
+        // Missing required field
+        assert!(WireEncoding::Cbor
+            .decode::<Message>(&[0xA2, 0x00, 0x02, 0x01, 0x03])
+            .is_err());
+        // Hash of the wrong length
+        assert!(WireEncoding::Cbor
+            .decode::<Hash>(&[0x42, 0x01, 0x02])
+            .is_err());
+    }
+
+    #[cfg(feature = "coap")]
+    #[test]
+    fn test_cbor_skips_unknown_keys() {
+        // {0: 4, 1: <hash>, 9: "from a newer node"}
+        let mut bytes = vec![0xA3, 0x00, 0x04, 0x01, 0x58, 0x20];
+        bytes.extend_from_slice(&[5; 32]);
+        bytes.extend_from_slice(&[0x09, 0x63, b'n', b'e', b'w']);
+        let message: Message = WireEncoding::Cbor.decode(&bytes).unwrap();
+        assert!(matches!(message, Message::NewRecord { hash } if hash == Hash([5; 32])));
+    }
+}
@@ -31,6 +31,11 @@ fn test_config_memory() -> Config {
         memory_limit: 256 * 1024,
         enable_metrics: false,
         enable_mdns: false,
+        discovery: Default::default(),
+        bridge: Default::default(),
+        recording: Default::default(),
+        keystore_path: None,
+        keystore_passphrase_env: aingle_minimal::ENV_KEYSTORE_PASSPHRASE.to_string(),
         log_level: "debug".to_string(),
     }
 }
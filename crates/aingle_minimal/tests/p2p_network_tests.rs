@@ -11,8 +11,10 @@
 
 use aingle_minimal::{
     config::{GossipConfig, PowerMode, StorageConfig, TransportConfig},
-    BloomFilter, Config, DiscoveredPeer, Discovery, GossipManager, Hash, MinimalNode, SyncManager,
+    BloomFilter, BridgeConfig, BridgeRoute, BridgeStats, Config, DiscoveredPeer, Discovery,
+    DiscoverySource, GossipManager, Hash, MinimalNode, SyncManager, TransportKind,
 };
+use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::time::{Duration, Instant};
 
@@ -28,6 +30,11 @@ fn test_config() -> Config {
         memory_limit: 256 * 1024,
         enable_metrics: false,
         enable_mdns: false,
+        discovery: Default::default(),
+        bridge: Default::default(),
+        recording: Default::default(),
+        keystore_path: None,
+        keystore_passphrase_env: aingle_minimal::ENV_KEYSTORE_PASSPHRASE.to_string(),
         log_level: "debug".to_string(),
     }
 }
@@ -173,6 +180,7 @@ fn test_discovered_peer_properties() {
             props.insert("version".to_string(), "0.1.0".to_string());
             props
         },
+        source: DiscoverySource::Mdns,
     };
 
     // Should generate socket addresses for both IPs
@@ -321,3 +329,113 @@ fn test_production_p2p_config() {
         _ => panic!("Expected QUIC transport for production mode"),
     }
 }
+
+/// A node in the transport bridging simulation
+struct SimNode {
+    gossip: GossipManager,
+    transports: Vec<TransportKind>,
+    /// Entries to re-announce on the transport they were learned on
+    outbox: Vec<(TransportKind, Hash)>,
+    /// Announcements received, per entry
+    deliveries: HashMap<Hash, usize>,
+}
+
+impl SimNode {
+    fn new(transports: &[TransportKind], bridge: &BridgeConfig) -> Self {
+        Self {
+            gossip: GossipManager::new(GossipConfig::default()).with_bridge(bridge),
+            transports: transports.to_vec(),
+            outbox: Vec::new(),
+            deliveries: HashMap::new(),
+        }
+    }
+
+    fn receive(&mut self, transport: TransportKind, hash: Hash) {
+        *self.deliveries.entry(hash.clone()).or_default() += 1;
+        let new = !self.gossip.is_known(&hash);
+        self.gossip.add_known_from(hash.clone(), transport);
+        if new {
+            self.outbox.push((transport, hash));
+        }
+    }
+}
+
+/// Deliver every pending announcement to the nodes sharing its transport,
+/// until the network is quiet
+fn run_to_quiescence(nodes: &mut [SimNode]) {
+    for _ in 0..10 {
+        let mut sends = Vec::new();
+        for (i, node) in nodes.iter_mut().enumerate() {
+            for hash in node.gossip.take_announcements(100) {
+                for transport in &node.transports {
+                    sends.push((i, *transport, hash.clone()));
+                }
+            }
+            sends.extend(node.outbox.drain(..).map(|(t, hash)| (i, t, hash)));
+            for transport in node.transports.clone() {
+                for hash in node.gossip.take_bridged(transport, 100) {
+                    sends.push((i, transport, hash));
+                }
+            }
+        }
+        if sends.is_empty() {
+            return;
+        }
+        for (from, transport, hash) in sends {
+            for (j, node) in nodes.iter_mut().enumerate() {
+                if j != from && node.transports.contains(&transport) {
+                    node.receive(transport, hash.clone());
+                }
+            }
+        }
+    }
+    panic!("announcements still circulating after 10 rounds");
+}
+
+#[test]
+fn test_gateway_bridges_ble_entry_to_wifi_once() {
+    let ble = TransportKind::Ble;
+    let wifi = TransportKind::Coap;
+    let bridge = BridgeConfig::default()
+        .route(BridgeRoute::new(ble, wifi))
+        .route(BridgeRoute::new(wifi, ble));
+    let mut nodes = vec![
+        SimNode::new(&[ble], &BridgeConfig::default()),
+        SimNode::new(&[ble, wifi], &bridge),
+        SimNode::new(&[wifi], &BridgeConfig::default()),
+    ];
+    let (sensor, gateway, wifi_peer) = (0, 1, 2);
+
+    // A BLE-only sensor creates an entry
+    let entry = Hash::from_bytes(&[42u8; 32]);
+    nodes[sensor].gossip.announce(entry.clone());
+    run_to_quiescence(&mut nodes);
+
+    // The WiFi-only peer hears about it exactly once, through the gateway
+    assert_eq!(nodes[wifi_peer].deliveries.get(&entry), Some(&1));
+    assert!(nodes[wifi_peer].gossip.is_known(&entry));
+
+    // Its re-announcement reaches the gateway on WiFi and is not bridged back
+    let stats = nodes[gateway].gossip.stats();
+    assert_eq!(stats.bridge_loops_prevented, 1);
+    assert_eq!(
+        stats.bridged,
+        vec![
+            BridgeStats {
+                from: ble,
+                to: wifi,
+                bridged: 1,
+                pending: 0,
+                dropped: 0,
+            },
+            BridgeStats {
+                from: wifi,
+                to: ble,
+                bridged: 0,
+                pending: 0,
+                dropped: 0,
+            },
+        ]
+    );
+    assert_eq!(nodes[sensor].deliveries.get(&entry), Some(&1));
+}
@@ -16,6 +16,7 @@ use std::time::Duration;
 
 #[cfg(feature = "webrtc")]
 mod webrtc_tests {
+    use super::*;
     use aingle_minimal::*;
 
     #[test]
@@ -155,6 +156,106 @@ mod webrtc_tests {
         assert_eq!(stats.messages_sent, 100);
         assert_eq!(stats.rtt_ms, 25);
     }
+
+    fn free_signaling_url() -> (String, String) {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        (addr.clone(), format!("ws://{}", addr))
+    }
+
+    async fn next_message(client: &SignalingClient) -> Option<SignalingMessage> {
+        for _ in 0..200 {
+            if let Ok(Some(msg)) = client.recv().await {
+                return Some(msg);
+            }
+            smol::Timer::after(Duration::from_millis(10)).await;
+        }
+        None
+    }
+
+    #[test]
+    fn test_signaling_server_rejects_unauthenticated_peers() {
+        smol::block_on(async {
+            let (bind_addr, url) = free_signaling_url();
+            let config = SignalingConfig {
+                bind_addr,
+                ..Default::default()
+            };
+            let mut server = SignalingServer::new(config)
+                .with_auth(SignalingAuth::SharedSecret("s3cret".to_string()));
+            server.start().await.unwrap();
+
+            let mut member = SignalingClient::new(&url, "member").with_token("s3cret");
+            member.connect().await.unwrap();
+
+            for stranger in [
+                SignalingClient::new(&url, "stranger"),
+                SignalingClient::new(&url, "guesser").with_token("guess"),
+            ] {
+                let mut stranger = stranger;
+                stranger.connect().await.unwrap();
+                match next_message(&stranger).await {
+                    Some(SignalingMessage::Rejected { reason }) => {
+                        assert!(!reason.is_empty())
+                    }
+                    other => panic!("expected rejection, got {:?}", other),
+                }
+            }
+
+            // Only the authenticated peer was registered and nothing was announced
+            for _ in 0..200 {
+                if server.client_count().await > 0 {
+                    break;
+                }
+                smol::Timer::after(Duration::from_millis(10)).await;
+            }
+            assert_eq!(server.peer_ids().await, vec!["member".to_string()]);
+            assert!(member.recv().await.unwrap().is_none());
+
+            server.stop().await.unwrap();
+        });
+    }
+
+    /// Needs a reachable TURN server:
+    /// `AINGLE_TEST_TURN_URL`, `AINGLE_TEST_TURN_USERNAME`, `AINGLE_TEST_TURN_CREDENTIAL`
+    #[test]
+    #[ignore = "requires a TURN server"]
+    fn test_turn_relay_candidates_are_gathered() {
+        use ::webrtc::api::APIBuilder;
+        use ::webrtc::peer_connection::policy::ice_transport_policy::RTCIceTransportPolicy;
+
+        let env = |name: &str| std::env::var(name).unwrap_or_else(|_| panic!("{} not set", name));
+        let config = WebRtcConfig::default().with_turn_server(TurnServer::new(
+            &env("AINGLE_TEST_TURN_URL"),
+            &env("AINGLE_TEST_TURN_USERNAME"),
+            &env("AINGLE_TEST_TURN_CREDENTIAL"),
+        ));
+
+        smol::block_on(async {
+            let mut rtc_config = config.rtc_configuration();
+            rtc_config.ice_transport_policy = RTCIceTransportPolicy::Relay;
+
+            let api = APIBuilder::new().build();
+            let pc = api.new_peer_connection(rtc_config).await.unwrap();
+            pc.create_data_channel(&config.channel_label, None)
+                .await
+                .unwrap();
+
+            let offer = pc.create_offer(None).await.unwrap();
+            let mut gathered = pc.gathering_complete_promise().await;
+            pc.set_local_description(offer).await.unwrap();
+            let _ = gathered.recv().await;
+
+            let sdp = pc.local_description().await.unwrap().sdp;
+            let mut stats = WebRtcStats::default();
+            for line in sdp.lines().filter(|l| l.starts_with("a=candidate:")) {
+                stats.record_candidate(line);
+            }
+            assert!(stats.relay_candidates > 0, "no relay candidates in {}", sdp);
+
+            pc.close().await.unwrap();
+        });
+    }
 }
 
 // ============================================================================
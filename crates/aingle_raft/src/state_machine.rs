@@ -126,7 +126,7 @@ impl CortexStateMachine {
             } => {
                 let entry =
                     ineru::MemoryEntry::new(entry_type, data.clone()).with_importance(*importance);
-                let mut memory = self.memory.write().await;
+                let memory = self.memory.write().await;
                 match memory.remember(entry) {
                     Ok(id) => CortexResponse {
                         success: true,
@@ -142,7 +142,7 @@ impl CortexStateMachine {
             }
             WalEntryKind::MemoryForget { memory_id } => {
                 if let Some(mid) = ineru::MemoryId::from_hex(memory_id) {
-                    let mut memory = self.memory.write().await;
+                    let memory = self.memory.write().await;
                     match memory.forget(&mid) {
                         Ok(()) => CortexResponse {
                             success: true,
@@ -167,7 +167,7 @@ impl CortexStateMachine {
                 consolidated_count: _,
             } => {
                 // Actually perform consolidation on this node
-                let mut memory = self.memory.write().await;
+                let memory = self.memory.write().await;
                 match memory.consolidate() {
                     Ok(count) => CortexResponse {
                         success: true,
@@ -0,0 +1,299 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Access control for exposed DAG explorers.
+//!
+//! An [`AccessPolicy`] guards the `/api` and `/ws` routes of the visualization
+//! server:
+//!
+//! - **Authentication**: an optional [`VizAuth`] (static bearer token or basic-auth
+//!   credentials). Requests without valid credentials get `401 Unauthorized`
+//!   with a `WWW-Authenticate` challenge. The WebSocket upgrade is checked the
+//!   same way; since browsers cannot set headers on WebSocket requests, a bearer
+//!   token may also be passed as the `access_token` query parameter.
+//! - **Read-only mode**: when [`read_only`](AccessPolicy::read_only) is set, any
+//!   request other than `GET`, `HEAD` or `OPTIONS` is refused with
+//!   `403 Forbidden`, and the configured metadata keys are stripped from every
+//!   node returned by the API or streamed over the WebSocket.
+//!
+//! Static assets (the web UI itself) are always served.
+//!
+//! # Examples
+//!
+//! ```
+//! use aingle_viz::access::{AccessPolicy, VizAuth};
+//!
+//! let policy = AccessPolicy {
+//!     auth: Some(VizAuth::Bearer("s3cret".to_string())),
+//!     ..AccessPolicy::default()
+//! };
+//! assert!(policy.read_only);
+//! ```
+
+use crate::dag::DagNode;
+use crate::events::DagEvent;
+
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderMap, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use base64::Engine;
+use std::sync::Arc;
+
+/// Metadata keys stripped in read-only mode unless configured otherwise.
+pub const DEFAULT_REDACTED_METADATA_KEYS: &[&str] = &["content", "payload"];
+
+/// Realm advertised in `WWW-Authenticate` challenges.
+const REALM: &str = "aingle-viz";
+
+/// Credentials required to access the API and WebSocket routes.
+#[derive(Clone, PartialEq, Eq)]
+pub enum VizAuth {
+    /// A static token sent as `Authorization: Bearer <token>`.
+    Bearer(String),
+    /// Credentials sent as `Authorization: Basic <base64(username:password)>`.
+    Basic {
+        /// The expected username.
+        username: String,
+        /// The expected password.
+        password: String,
+    },
+}
+
+impl VizAuth {
+    /// Returns `true` if the request headers (or, for bearer tokens, the
+    /// `access_token` query parameter) carry valid credentials.
+    pub fn authorize(&self, headers: &HeaderMap, query: Option<&str>) -> bool {
+        let header = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok());
+
+        match self {
+            VizAuth::Bearer(token) => {
+                let presented = header
+                    .and_then(|h| h.strip_prefix("Bearer "))
+                    .or_else(|| query.and_then(query_access_token));
+                presented.is_some_and(|p| constant_time_eq(p.as_bytes(), token.as_bytes()))
+            }
+            VizAuth::Basic { username, password } => {
+                let Some(decoded) = header
+                    .and_then(|h| h.strip_prefix("Basic "))
+                    .and_then(|b| base64::engine::general_purpose::STANDARD.decode(b).ok())
+                else {
+                    return false;
+                };
+                let expected = format!("{}:{}", username, password);
+                constant_time_eq(&decoded, expected.as_bytes())
+            }
+        }
+    }
+
+    /// The `WWW-Authenticate` challenge for this scheme.
+    pub fn challenge(&self) -> String {
+        match self {
+            VizAuth::Bearer(_) => format!("Bearer realm=\"{}\"", REALM),
+            VizAuth::Basic { .. } => format!("Basic realm=\"{}\"", REALM),
+        }
+    }
+}
+
+impl std::fmt::Debug for VizAuth {
+    // Never print secrets, e.g. when a `VizConfig` is logged.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VizAuth::Bearer(_) => f.write_str("Bearer(<redacted>)"),
+            VizAuth::Basic { username, .. } => f
+                .debug_struct("Basic")
+                .field("username", username)
+                .field("password", &"<redacted>")
+                .finish(),
+        }
+    }
+}
+
+/// Authentication, read-only and redaction settings for the API router.
+///
+/// See the [module documentation](self) for the behavior of each setting.
+#[derive(Debug, Clone)]
+pub struct AccessPolicy {
+    /// Credentials required on `/api` and `/ws` routes; `None` disables auth.
+    pub auth: Option<VizAuth>,
+
+    /// Refuse mutating requests and strip [`redacted_metadata_keys`](Self::redacted_metadata_keys).
+    ///
+    /// Default is `true`.
+    pub read_only: bool,
+
+    /// Node metadata keys removed from responses in read-only mode.
+    ///
+    /// Default is [`DEFAULT_REDACTED_METADATA_KEYS`].
+    pub redacted_metadata_keys: Vec<String>,
+}
+
+impl Default for AccessPolicy {
+    fn default() -> Self {
+        Self {
+            auth: None,
+            read_only: true,
+            redacted_metadata_keys: DEFAULT_REDACTED_METADATA_KEYS
+                .iter()
+                .map(|k| k.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl AccessPolicy {
+    /// A policy that allows everything: no auth, writable, nothing redacted.
+    pub fn open() -> Self {
+        Self {
+            auth: None,
+            read_only: false,
+            redacted_metadata_keys: Vec::new(),
+        }
+    }
+
+    /// Removes the redacted metadata keys from `node` if in read-only mode.
+    pub fn redact_node(&self, node: &mut DagNode) {
+        if self.read_only && !self.redacted_metadata_keys.is_empty() {
+            node.metadata
+                .retain(|key, _| !self.redacted_metadata_keys.contains(key));
+        }
+    }
+
+    /// Returns a redacted copy of `node`.
+    pub fn redacted(&self, node: &DagNode) -> DagNode {
+        let mut node = node.clone();
+        self.redact_node(&mut node);
+        node
+    }
+
+    /// Redacts any node carried by `event`.
+    pub fn redact_event(&self, mut event: DagEvent) -> DagEvent {
+        match event {
+            DagEvent::NodeAdded { ref mut node } | DagEvent::NodeUpdated { ref mut node } => {
+                self.redact_node(node)
+            }
+            _ => {}
+        }
+        event
+    }
+}
+
+/// Middleware enforcing an [`AccessPolicy`] on the routes it wraps.
+pub(crate) async fn enforce_access(
+    State(policy): State<Arc<AccessPolicy>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if let Some(ref auth) = policy.auth {
+        if !auth.authorize(request.headers(), request.uri().query()) {
+            return (
+                StatusCode::UNAUTHORIZED,
+                [(header::WWW_AUTHENTICATE, auth.challenge())],
+                "Authentication required",
+            )
+                .into_response();
+        }
+    }
+
+    if policy.read_only
+        && !matches!(
+            *request.method(),
+            Method::GET | Method::HEAD | Method::OPTIONS
+        )
+    {
+        return (StatusCode::FORBIDDEN, "The explorer is in read-only mode").into_response();
+    }
+
+    next.run(request).await
+}
+
+/// Extracts `access_token` from a raw query string.
+fn query_access_token(query: &str) -> Option<&str> {
+    query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("access_token="))
+}
+
+/// Compares two byte strings without short-circuiting on the first mismatch.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dag::{DagNodeBuilder, NodeType};
+    use axum::http::HeaderValue;
+
+    fn headers(authorization: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_str(authorization).unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn test_bearer_authorize() {
+        let auth = VizAuth::Bearer("tok".to_string());
+        assert!(auth.authorize(&headers("Bearer tok"), None));
+        assert!(!auth.authorize(&headers("Bearer nope"), None));
+        assert!(!auth.authorize(&HeaderMap::new(), None));
+        assert!(auth.authorize(&HeaderMap::new(), Some("x=1&access_token=tok")));
+        assert!(!auth.authorize(&HeaderMap::new(), Some("access_token=to")));
+    }
+
+    #[test]
+    fn test_basic_authorize() {
+        let auth = VizAuth::Basic {
+            username: "ops".to_string(),
+            password: "pw".to_string(),
+        };
+        let good = base64::engine::general_purpose::STANDARD.encode("ops:pw");
+        let bad = base64::engine::general_purpose::STANDARD.encode("ops:nope");
+        assert!(auth.authorize(&headers(&format!("Basic {}", good)), None));
+        assert!(!auth.authorize(&headers(&format!("Basic {}", bad)), None));
+        assert!(!auth.authorize(&headers("Basic !!!"), None));
+        // The query-string token is only accepted for bearer auth
+        assert!(!auth.authorize(&HeaderMap::new(), Some("access_token=pw")));
+    }
+
+    #[test]
+    fn test_debug_hides_secrets() {
+        let bearer = format!("{:?}", VizAuth::Bearer("s3cret".to_string()));
+        let basic = format!(
+            "{:?}",
+            VizAuth::Basic {
+                username: "ops".to_string(),
+                password: "s3cret".to_string(),
+            }
+        );
+        assert!(!bearer.contains("s3cret"));
+        assert!(!basic.contains("s3cret"));
+        assert!(basic.contains("ops"));
+    }
+
+    #[test]
+    fn test_redaction_only_in_read_only_mode() {
+        let node = DagNodeBuilder::new("n", NodeType::Entry)
+            .metadata("content", serde_json::json!("secret"))
+            .metadata("entry_type", serde_json::json!("post"))
+            .build();
+
+        let redacted = AccessPolicy::default().redacted(&node);
+        assert!(!redacted.metadata.contains_key("content"));
+        assert!(redacted.metadata.contains_key("entry_type"));
+
+        let writable = AccessPolicy {
+            read_only: false,
+            ..AccessPolicy::default()
+        };
+        assert!(writable.redacted(&node).metadata.contains_key("content"));
+    }
+}
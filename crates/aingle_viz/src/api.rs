@@ -13,11 +13,13 @@
 //!
 //! - `GET /api/dag` - Get full DAG with optional filters (type, author, limit)
 //! - `GET /api/dag/d3` - Get DAG in D3.js-compatible JSON format
+//! - `GET /api/dag/export?format=dot|graphml` - Download the DAG as Graphviz DOT or GraphML
 //! - `GET /api/dag/entry/:hash` - Get specific node by ID
 //! - `GET /api/dag/agent/:id` - Get all nodes by author
 //! - `GET /api/dag/recent?n=N` - Get N most recent nodes
+//! - `GET /api/dag/search?q=...&limit=N` - Search nodes by id, label, author or metadata
 //! - `GET /api/stats` - Get DAG and WebSocket statistics
-//! - `POST /api/node` - Create a new node (for testing/demo)
+//! - `POST /api/node` - Create a new node (for testing/demo; refused in read-only mode)
 //!
 //! ## WebSocket Endpoint
 //!
@@ -29,6 +31,12 @@
 //! - `GET /assets/logo.svg` - Logo image
 //! - `GET /favicon.ico` - Favicon
 //!
+//! # Access Control
+//!
+//! [`create_router_with_policy`] guards the `/api` and `/ws` routes with an
+//! [`AccessPolicy`] (authentication, read-only mode and metadata redaction);
+//! [`create_router`] applies no restrictions.
+//!
 //! # Architecture
 //!
 //! The API uses [`ApiState`] as shared state, which is wrapped in `Arc<RwLock<...>>`
@@ -59,17 +67,21 @@
 //! }
 //! ```
 
+use crate::access::{enforce_access, AccessPolicy};
 use crate::dag::{DagEdge, DagNode, DagNodeBuilder, DagView, NodeType};
 use crate::error::Result;
 use crate::events::{DagEvent, EventBroadcaster};
+use crate::export::{DotOptions, ExportChunks, ExportFormat};
+use crate::search::{SearchHit, SearchMode, SearchOptions, DEFAULT_SEARCH_LIMIT};
 
+use axum::body::Body;
 use axum::extract::ws::{Message, WebSocket};
-use axum::http::StatusCode;
+use axum::http::{header, StatusCode};
 use axum::{
     extract::{Path, Query, State, WebSocketUpgrade},
     response::{Html, IntoResponse, Json},
     routing::{get, post},
-    Router,
+    Extension, Router,
 };
 use futures::{sink::SinkExt, stream::StreamExt};
 use serde::Deserialize;
@@ -331,6 +343,55 @@ pub struct RecentQuery {
     pub n: Option<usize>,
 }
 
+/// Query parameters for the `GET /api/dag/search` endpoint.
+///
+/// # Examples
+///
+/// - `/api/dag/search?q=ali` - Nodes with a word starting with "ali"
+/// - `/api/dag/search?q=ali&mode=substring&limit=5` - Up to 5 nodes containing "ali"
+/// - `/api/dag/search?q=post&keys=entry_type` - Also search the `entry_type` metadata
+#[derive(Debug, Deserialize)]
+pub struct SearchQuery {
+    /// The search text.
+    pub q: String,
+
+    /// The maximum number of hits to return.
+    ///
+    /// Defaults to [`DEFAULT_SEARCH_LIMIT`] if not specified.
+    pub limit: Option<usize>,
+
+    /// The match mode: "prefix" or "substring".
+    ///
+    /// Defaults to "prefix" if not specified.
+    pub mode: Option<String>,
+
+    /// Comma-separated metadata keys to search in addition to id, label and
+    /// author.
+    ///
+    /// Keys redacted by the [`AccessPolicy`] are ignored.
+    pub keys: Option<String>,
+}
+
+/// Query parameters for the `GET /api/dag/export` endpoint.
+///
+/// # Examples
+///
+/// - `/api/dag/export?format=dot` - Graphviz DOT (the default)
+/// - `/api/dag/export?format=graphml` - GraphML
+/// - `/api/dag/export?format=dot&label_len=0` - DOT with untruncated labels
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    /// The export format: "dot" or "graphml".
+    ///
+    /// Defaults to "dot" if not specified.
+    pub format: Option<String>,
+
+    /// Maximum node label length in DOT output; `0` disables truncation.
+    ///
+    /// Defaults to [`DotOptions::default`] if not specified.
+    pub label_len: Option<usize>,
+}
+
 /// Constructs the main Axum [`Router`] for the visualization server.
 ///
 /// This function wires up all the API endpoints, WebSocket handler, and static
@@ -341,9 +402,11 @@ pub struct RecentQuery {
 /// ## REST API
 /// - `GET /api/dag` - Full DAG with optional filters
 /// - `GET /api/dag/d3` - DAG in D3.js format
+/// - `GET /api/dag/export` - DAG as a DOT or GraphML download
 /// - `GET /api/dag/entry/:hash` - Specific node details
 /// - `GET /api/dag/agent/:id` - Nodes by author
 /// - `GET /api/dag/recent` - Recent nodes
+/// - `GET /api/dag/search` - Node search
 /// - `GET /api/stats` - Statistics
 /// - `POST /api/node` - Create node
 ///
@@ -368,23 +431,59 @@ pub struct RecentQuery {
 /// axum::serve(listener, router).await?;
 /// ```
 pub fn create_router(state: ApiState) -> Router {
-    Router::new()
+    create_router_with_policy(state, AccessPolicy::open())
+}
+
+/// Constructs the [`Router`] with the `/api` and `/ws` routes guarded by `policy`.
+///
+/// Requests without valid credentials get `401 Unauthorized`, mutating
+/// requests are refused with `403 Forbidden` in read-only mode, and redacted
+/// metadata keys are stripped from every node in responses and WebSocket
+/// events. Static assets stay public.
+///
+/// # Examples
+///
+/// ```
+/// use aingle_viz::access::{AccessPolicy, VizAuth};
+/// use aingle_viz::api::create_router_with_policy;
+/// use aingle_viz::ApiState;
+///
+/// let policy = AccessPolicy {
+///     auth: Some(VizAuth::Bearer("s3cret".to_string())),
+///     ..AccessPolicy::default()
+/// };
+/// let router = create_router_with_policy(ApiState::new(), policy);
+/// ```
+pub fn create_router_with_policy(state: ApiState, policy: AccessPolicy) -> Router {
+    let policy = Arc::new(policy);
+
+    let guarded = Router::new()
         // API endpoints
         .route("/api/dag", get(get_dag))
         .route("/api/dag/d3", get(get_dag_d3))
+        .route("/api/dag/export", get(export_dag))
         .route("/api/dag/entry/{hash}", get(get_entry))
         .route("/api/dag/agent/{id}", get(get_agent_entries))
         .route("/api/dag/recent", get(get_recent))
+        .route("/api/dag/search", get(search_dag))
         .route("/api/stats", get(get_stats))
         .route("/api/node", post(create_node))
         // WebSocket
         .route("/ws/updates", get(ws_handler))
+        .route_layer(axum::middleware::from_fn_with_state(
+            policy.clone(),
+            enforce_access,
+        ));
+
+    Router::new()
         // Static assets
         .route("/assets/logo.svg", get(serve_logo))
         .route("/assets/favicon.ico", get(serve_favicon))
         .route("/favicon.ico", get(serve_favicon))
         // Serve static files (index.html at root)
         .route("/", get(serve_index))
+        .merge(guarded)
+        .layer(Extension(policy))
         .with_state(state)
 }
 
@@ -392,6 +491,7 @@ pub fn create_router(state: ApiState) -> Router {
 /// Returns the full DAG structure, with optional filters.
 async fn get_dag(
     State(state): State<ApiState>,
+    Extension(policy): Extension<Arc<AccessPolicy>>,
     Query(query): Query<DagQuery>,
 ) -> Json<serde_json::Value> {
     let dag = state.dag.read().await;
@@ -413,6 +513,8 @@ async fn get_dag(
         nodes.truncate(limit);
     }
 
+    let nodes: Vec<DagNode> = nodes.into_iter().map(|n| policy.redacted(n)).collect();
+
     Json(serde_json::json!({
         "nodes": nodes,
         "edges": dag.edges,
@@ -427,17 +529,72 @@ async fn get_dag_d3(State(state): State<ApiState>) -> Json<serde_json::Value> {
     Json(dag.to_d3_json())
 }
 
+/// API handler for `GET /api/dag/export`.
+/// Streams the DAG as a DOT or GraphML file attachment.
+///
+/// The nodes and edges are copied under a short read lock, so the export is a
+/// consistent snapshot and a slow download does not hold up new nodes.
+async fn export_dag(
+    State(state): State<ApiState>,
+    Extension(policy): Extension<Arc<AccessPolicy>>,
+    Query(query): Query<ExportQuery>,
+) -> ApiResult<impl IntoResponse> {
+    let format: ExportFormat = query
+        .format
+        .as_deref()
+        .unwrap_or("dot")
+        .parse()
+        .map_err(|e: crate::error::Error| (StatusCode::BAD_REQUEST, e.to_string()))?;
+
+    let mut dot = DotOptions::default();
+    if let Some(len) = query.label_len {
+        dot.max_label_len = (len > 0).then_some(len);
+    }
+    let omitted = if policy.read_only {
+        policy.redacted_metadata_keys.clone()
+    } else {
+        Vec::new()
+    };
+
+    let snapshot = {
+        let dag = state.dag.read().await;
+        DagView {
+            nodes: dag.nodes.clone(),
+            edges: dag.edges.clone(),
+            stats: dag.stats.clone(),
+            search_index: Default::default(),
+        }
+    };
+    let chunks = ExportChunks::new(Box::new(snapshot), format)
+        .with_dot_options(dot)
+        .omit_metadata(omitted);
+    let body = Body::from_stream(futures::stream::iter(
+        chunks.map(Ok::<_, std::convert::Infallible>),
+    ));
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"aingle-dag.{}\"", format.extension()),
+            ),
+        ],
+        body,
+    ))
+}
+
 /// API handler for `GET /api/dag/entry/:hash`.
 /// Returns the details of a single DAG node by its hash.
 async fn get_entry(
     State(state): State<ApiState>,
+    Extension(policy): Extension<Arc<AccessPolicy>>,
     Path(hash): Path<String>,
 ) -> ApiResult<Json<DagNode>> {
     let dag = state.dag.read().await;
 
     dag.get_node(&hash)
-        .cloned()
-        .map(Json)
+        .map(|n| Json(policy.redacted(n)))
         .ok_or((StatusCode::NOT_FOUND, format!("Entry {} not found", hash)))
 }
 
@@ -445,23 +602,58 @@ async fn get_entry(
 /// Returns all entries created by a specific agent.
 async fn get_agent_entries(
     State(state): State<ApiState>,
+    Extension(policy): Extension<Arc<AccessPolicy>>,
     Path(id): Path<String>,
 ) -> Json<Vec<DagNode>> {
     let dag = state.dag.read().await;
     let nodes = dag.nodes_by_author(&id);
-    Json(nodes.into_iter().cloned().collect())
+    Json(nodes.into_iter().map(|n| policy.redacted(n)).collect())
 }
 
 /// API handler for `GET /api/dag/recent`.
 /// Returns the `n` most recent entries.
 async fn get_recent(
     State(state): State<ApiState>,
+    Extension(policy): Extension<Arc<AccessPolicy>>,
     Query(query): Query<RecentQuery>,
 ) -> Json<Vec<DagNode>> {
     let dag = state.dag.read().await;
     let limit = query.n.unwrap_or(100);
     let nodes = dag.recent_nodes(limit);
-    Json(nodes.into_iter().cloned().collect())
+    Json(nodes.into_iter().map(|n| policy.redacted(n)).collect())
+}
+
+/// API handler for `GET /api/dag/search`.
+/// Returns the nodes matching a type-ahead query.
+async fn search_dag(
+    State(state): State<ApiState>,
+    Extension(policy): Extension<Arc<AccessPolicy>>,
+    Query(query): Query<SearchQuery>,
+) -> ApiResult<Json<Vec<SearchHit>>> {
+    let mode: SearchMode = query
+        .mode
+        .as_deref()
+        .unwrap_or("prefix")
+        .parse()
+        .map_err(|e: crate::error::Error| (StatusCode::BAD_REQUEST, e.to_string()))?;
+
+    let metadata_keys = query
+        .keys
+        .iter()
+        .flat_map(|keys| keys.split(','))
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+        .filter(|key| !(policy.read_only && policy.redacted_metadata_keys.iter().any(|r| r == key)))
+        .map(str::to_string)
+        .collect();
+    let options = SearchOptions {
+        mode,
+        limit: query.limit.unwrap_or(DEFAULT_SEARCH_LIMIT),
+        metadata_keys,
+    };
+
+    let dag = state.dag.read().await;
+    Ok(Json(dag.search(&query.q, &options)))
 }
 
 /// API handler for `GET /api/stats`.
@@ -532,6 +724,7 @@ pub struct CreateNodeRequest {
 /// Creates a new node for testing or demonstration purposes.
 async fn create_node(
     State(state): State<ApiState>,
+    Extension(policy): Extension<Arc<AccessPolicy>>,
     Json(req): Json<CreateNodeRequest>,
 ) -> ApiResult<Json<DagNode>> {
     let node_type = match req.node_type.to_lowercase().as_str() {
@@ -555,16 +748,20 @@ async fn create_node(
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
-    Ok(Json(node))
+    Ok(Json(policy.redacted(&node)))
 }
 
 /// API handler for `GET /ws/updates`. Upgrades the connection to a WebSocket.
-async fn ws_handler(ws: WebSocketUpgrade, State(state): State<ApiState>) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| handle_websocket(socket, state))
+async fn ws_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<ApiState>,
+    Extension(policy): Extension<Arc<AccessPolicy>>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_websocket(socket, state, policy))
 }
 
 /// Handles the lifecycle of a single WebSocket connection.
-async fn handle_websocket(socket: WebSocket, state: ApiState) {
+async fn handle_websocket(socket: WebSocket, state: ApiState, policy: Arc<AccessPolicy>) {
     let client_id = uuid::Uuid::new_v4().to_string();
     log::info!("WebSocket client connected: {}", client_id);
 
@@ -594,7 +791,7 @@ async fn handle_websocket(socket: WebSocket, state: ApiState) {
     let client_id_clone = client_id.clone();
     let send_task = tokio::spawn(async move {
         while let Ok(event) = event_rx.recv().await {
-            let json = event.to_json();
+            let json = policy.redact_event(event).to_json();
             if sender.send(Message::Text(json.into())).await.is_err() {
                 // Client disconnected.
                 break;
@@ -1008,4 +1205,342 @@ mod tests {
         let count = state.broadcaster.client_count().await;
         assert_eq!(count, 1);
     }
+
+    const GUARDED_GETS: &[&str] = &[
+        "/api/dag",
+        "/api/dag/d3",
+        "/api/dag/export",
+        "/api/dag/entry/secret-entry",
+        "/api/dag/agent/agent1",
+        "/api/dag/recent",
+        "/api/dag/search?q=entry",
+        "/api/stats",
+        "/ws/updates",
+    ];
+
+    async fn guarded_state() -> ApiState {
+        let state = ApiState::new();
+        state
+            .add_node(
+                DagNodeBuilder::new("secret-entry", NodeType::Entry)
+                    .label("Entry")
+                    .author("agent1")
+                    .metadata("content", serde_json::json!("private payload"))
+                    .metadata("entry_type", serde_json::json!("post"))
+                    .build(),
+            )
+            .await
+            .unwrap();
+        state
+    }
+
+    async fn get_with(app: &Router, uri: &str, authorization: Option<&str>) -> StatusCode {
+        let mut request = Request::builder().uri(uri);
+        if let Some(value) = authorization {
+            request = request.header("authorization", value);
+        }
+        app.clone()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn test_bearer_auth_guards_every_route() {
+        let policy = AccessPolicy {
+            auth: Some(crate::access::VizAuth::Bearer("tok".to_string())),
+            ..AccessPolicy::default()
+        };
+        let app = create_router_with_policy(guarded_state().await, policy);
+
+        for uri in GUARDED_GETS {
+            assert_eq!(
+                get_with(&app, uri, None).await,
+                StatusCode::UNAUTHORIZED,
+                "{uri} without credentials"
+            );
+            assert_eq!(
+                get_with(&app, uri, Some("Bearer wrong")).await,
+                StatusCode::UNAUTHORIZED,
+                "{uri} with a wrong token"
+            );
+            // The WebSocket route rejects a plain GET, but only after auth passes
+            assert_ne!(
+                get_with(&app, uri, Some("Bearer tok")).await,
+                StatusCode::UNAUTHORIZED,
+                "{uri} with the token"
+            );
+        }
+
+        // Browsers pass the WebSocket token in the query string
+        assert_ne!(
+            get_with(&app, "/ws/updates?access_token=tok", None).await,
+            StatusCode::UNAUTHORIZED
+        );
+
+        // The web UI itself stays public
+        assert_eq!(get_with(&app, "/", None).await, StatusCode::OK);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/dag")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            response.headers()["www-authenticate"],
+            "Bearer realm=\"aingle-viz\""
+        );
+    }
+
+    #[tokio::test]
+    async fn test_basic_auth_guards_every_route() {
+        use base64::Engine;
+
+        let policy = AccessPolicy {
+            auth: Some(crate::access::VizAuth::Basic {
+                username: "ops".to_string(),
+                password: "pw".to_string(),
+            }),
+            ..AccessPolicy::default()
+        };
+        let app = create_router_with_policy(guarded_state().await, policy);
+        let good = format!(
+            "Basic {}",
+            base64::engine::general_purpose::STANDARD.encode("ops:pw")
+        );
+
+        for uri in GUARDED_GETS {
+            assert_eq!(
+                get_with(&app, uri, None).await,
+                StatusCode::UNAUTHORIZED,
+                "{uri} without credentials"
+            );
+            assert_ne!(
+                get_with(&app, uri, Some(&good)).await,
+                StatusCode::UNAUTHORIZED,
+                "{uri} with credentials"
+            );
+        }
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/stats")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            response.headers()["www-authenticate"],
+            "Basic realm=\"aingle-viz\""
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_endpoint() {
+        let app = create_router(guarded_state().await);
+
+        for (format, content_type, filename) in [
+            ("dot", "text/vnd.graphviz", "aingle-dag.dot"),
+            ("graphml", "application/graphml+xml", "aingle-dag.graphml"),
+        ] {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .uri(format!("/api/dag/export?format={}", format))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let headers = response.headers();
+            assert!(headers["content-type"]
+                .to_str()
+                .unwrap()
+                .starts_with(content_type));
+            assert_eq!(
+                headers["content-disposition"],
+                format!("attachment; filename=\"{}\"", filename).as_str()
+            );
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            assert!(String::from_utf8(body.to_vec())
+                .unwrap()
+                .contains("secret-entry"));
+        }
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/dag/export?format=gexf")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_export_does_not_block_writers() {
+        let state = guarded_state().await;
+        let app = create_router(state.clone());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/dag/export?format=dot")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // The body has not been read yet, and a node can still be added
+        let added = DagNodeBuilder::new("late-entry", NodeType::Entry).build();
+        tokio::time::timeout(std::time::Duration::from_secs(1), state.add_node(added))
+            .await
+            .expect("export holds the DAG lock")
+            .unwrap();
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains("secret-entry"));
+        assert!(!body.contains("late-entry"));
+    }
+
+    #[tokio::test]
+    async fn test_read_only_refuses_mutations() {
+        let state = ApiState::new();
+        let app = create_router_with_policy(state.clone(), AccessPolicy::default());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/node")
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        r#"{"id": "n", "label": "N", "node_type": "entry"}"#,
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert!(state.dag.read().await.nodes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_read_only_strips_metadata() {
+        let app = create_router_with_policy(guarded_state().await, AccessPolicy::default());
+
+        for uri in [
+            "/api/dag",
+            "/api/dag/entry/secret-entry",
+            "/api/dag/agent/agent1",
+            "/api/dag/recent",
+            "/api/dag/export?format=graphml",
+        ] {
+            let response = app
+                .clone()
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let body = String::from_utf8(body.to_vec()).unwrap();
+            assert!(!body.contains("private payload"), "{uri} leaked content");
+            assert!(body.contains("entry_type"), "{uri} dropped other metadata");
+        }
+
+        // The unrestricted router keeps everything
+        let response = create_router(guarded_state().await)
+            .oneshot(
+                Request::builder()
+                    .uri("/api/dag/entry/secret-entry")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(String::from_utf8_lossy(&body).contains("private payload"));
+    }
+
+    #[tokio::test]
+    async fn test_search_endpoint() {
+        let state = ApiState::new();
+        for (id, author) in [("node1", "alice"), ("node2", "alice"), ("node3", "bob")] {
+            state
+                .add_node(
+                    DagNodeBuilder::new(id, NodeType::Entry)
+                        .label("Entry")
+                        .author(author)
+                        .metadata("content", serde_json::json!("private payload"))
+                        .build(),
+                )
+                .await
+                .unwrap();
+        }
+
+        async fn search(app: &Router, uri: &str) -> (StatusCode, Vec<SearchHit>) {
+            let response = app
+                .clone()
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            let status = response.status();
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            (status, serde_json::from_slice(&body).unwrap_or_default())
+        }
+
+        let app = create_router(state.clone());
+        let (status, hits) = search(&app, "/api/dag/search?q=ali").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(hits.len(), 2);
+        assert!(hits.iter().all(|h| h.matched_value == "alice"));
+
+        let (_, hits) = search(&app, "/api/dag/search?q=ode&mode=substring&limit=1").await;
+        assert_eq!(hits.len(), 1);
+
+        let (status, _) = search(&app, "/api/dag/search?q=ali&mode=fuzzy").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (_, hits) = search(&app, "/api/dag/search?q=payload&keys=content").await;
+        assert_eq!(hits.len(), 3);
+
+        // Redacted metadata is not searchable in read-only mode
+        let app = create_router_with_policy(state, AccessPolicy::default());
+        let (status, hits) = search(&app, "/api/dag/search?q=payload&keys=content").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(hits.is_empty());
+    }
+
+    #[test]
+    fn test_redact_event() {
+        let node = DagNodeBuilder::new("n", NodeType::Entry)
+            .metadata("payload", serde_json::json!("private"))
+            .build();
+        let event = AccessPolicy::default().redact_event(DagEvent::node_added(node));
+        assert!(!event.to_json().contains("private"));
+    }
 }
@@ -0,0 +1,875 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Live attachment of a [`DagView`] to a running [`GraphDB`] or [`MinimalNode`].
+//!
+//! Instead of mirroring every write into the view by hand, attach the view to
+//! the data source: existing data is backfilled on attach, up to
+//! [`AttachOptions::backfill_limit`] items, and later changes arrive as
+//! [`DagChanges`].
+//!
+//! - [`DagView::attach_graph`] follows the graph's change feed through a
+//!   [`GraphFeed`]. Subjects and node objects become nodes, and each triple an
+//!   edge labelled with its predicate, as configured by a [`GraphMapping`].
+//! - [`DagView::attach_node`] follows the node's source chain with a
+//!   [`NodeFeed`] cursor, drawing agents, actions and entries with the
+//!   matching [`NodeType`]s and [`EdgeType`]s.
+//!
+//! [`ApiState::apply`] adds changes to the served view and broadcasts them to
+//! WebSocket clients. [`ApiState::attach_graph`] does both for a graph, on a
+//! background task, until the returned [`Attachment`] is dropped.
+//!
+//! # Examples
+//!
+//! ```
+//! use aingle_graph::{GraphDB, NodeId, Predicate, Triple, Value};
+//! use aingle_viz::{ApiState, AttachOptions};
+//!
+//! #[tokio::main]
+//! async fn main() -> aingle_viz::Result<()> {
+//!     let graph = GraphDB::memory()?;
+//!     let state = ApiState::new();
+//!
+//!     let _attachment = state.attach_graph(&graph, &AttachOptions::default()).await?;
+//!
+//!     // Streamed to the view and to WebSocket clients in the background
+//!     graph.insert(Triple::new(
+//!         NodeId::named("alice"),
+//!         Predicate::named("knows"),
+//!         Value::Node(NodeId::named("bob")),
+//!     ))?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! A [`MinimalNode`] is polled instead, as it is usually driven by its own loop:
+//!
+//! ```
+//! use aingle_minimal::{Config, MinimalNode};
+//! use aingle_viz::{ApiState, AttachOptions};
+//!
+//! #[tokio::main]
+//! async fn main() -> aingle_viz::Result<()> {
+//!     let mut node = MinimalNode::new(Config::test_mode())?;
+//!     let state = ApiState::new();
+//!
+//!     let mut feed = state
+//!         .dag
+//!         .write()
+//!         .await
+//!         .attach_node(&node, &AttachOptions::default())?;
+//!
+//!     node.create_entry("reading")?;
+//!     state.apply(feed.poll(&node)?).await?;
+//!
+//!     assert_eq!(state.dag.read().await.stats.entry_count, 1);
+//!     Ok(())
+//! }
+//! ```
+
+use crate::api::ApiState;
+use crate::dag::{DagEdge, DagNode, DagNodeBuilder, DagView, EdgeType, NodeType};
+use crate::error::Result;
+use crate::events::DagEvent;
+use aingle_graph::{
+    GraphDB, GraphEvent, NodeId, OrderKey, Receiver, SortOrder, Triple, TripleId, Value,
+};
+use aingle_minimal::{ActionType, MinimalNode, Record};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// The default maximum number of items backfilled on attach.
+pub const DEFAULT_BACKFILL_LIMIT: usize = 10_000;
+
+/// The maximum number of records read by one [`NodeFeed::poll`].
+const NODE_POLL_BATCH: u32 = 256;
+
+/// How often a streaming [`Attachment`] checks whether it was dropped.
+const FEED_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How triples are drawn as nodes and edges.
+///
+/// Subjects and objects that are nodes become [`DagNode`]s, identified by
+/// their name. Each triple becomes a [`DagEdge`] from its subject to its
+/// object, labelled with its predicate. Literal objects are drawn as nodes of
+/// their own, one per triple, unless disabled.
+///
+/// # Examples
+///
+/// ```
+/// use aingle_viz::{EdgeType, GraphMapping, NodeType};
+///
+/// let mapping = GraphMapping::default()
+///     .namespace_type("agent", NodeType::Agent)
+///     .predicate_label("foaf:knows", "knows")
+///     .edge_type(EdgeType::Link)
+///     .literal_nodes(false);
+/// ```
+#[derive(Debug, Clone)]
+pub struct GraphMapping {
+    /// The type of nodes whose namespace has no entry in `namespace_types`.
+    pub node_type: NodeType,
+
+    /// Node types by namespace, the part of a node's name before the first
+    /// colon (`agent` for `agent:alice`).
+    pub namespace_types: HashMap<String, NodeType>,
+
+    /// The type of the nodes drawn for literal objects.
+    pub literal_type: NodeType,
+
+    /// Whether literal objects are drawn as nodes.
+    ///
+    /// When `false`, triples with a literal object only draw their subject.
+    pub literal_nodes: bool,
+
+    /// The type of the edge drawn for each triple.
+    pub edge_type: EdgeType,
+
+    /// Edge labels by predicate. Other predicates label their edges as is.
+    pub predicate_labels: HashMap<String, String>,
+}
+
+impl Default for GraphMapping {
+    fn default() -> Self {
+        Self {
+            node_type: NodeType::Entry,
+            namespace_types: HashMap::new(),
+            literal_type: NodeType::Entry,
+            literal_nodes: true,
+            edge_type: EdgeType::Link,
+            predicate_labels: HashMap::new(),
+        }
+    }
+}
+
+impl GraphMapping {
+    /// Draws nodes in `namespace` with `node_type`.
+    pub fn namespace_type(mut self, namespace: impl Into<String>, node_type: NodeType) -> Self {
+        self.namespace_types.insert(namespace.into(), node_type);
+        self
+    }
+
+    /// Labels edges drawn for `predicate` with `label`.
+    pub fn predicate_label(
+        mut self,
+        predicate: impl Into<String>,
+        label: impl Into<String>,
+    ) -> Self {
+        self.predicate_labels.insert(predicate.into(), label.into());
+        self
+    }
+
+    /// Sets the type of the edge drawn for each triple.
+    pub fn edge_type(mut self, edge_type: EdgeType) -> Self {
+        self.edge_type = edge_type;
+        self
+    }
+
+    /// Sets whether literal objects are drawn as nodes.
+    pub fn literal_nodes(mut self, enabled: bool) -> Self {
+        self.literal_nodes = enabled;
+        self
+    }
+
+    /// The type of the node drawn for `id`.
+    fn type_of(&self, id: &NodeId) -> NodeType {
+        id.namespace()
+            .and_then(|ns| self.namespace_types.get(ns))
+            .copied()
+            .unwrap_or(self.node_type)
+    }
+
+    /// The label of the edge drawn for `triple`.
+    fn label_of(&self, triple: &Triple) -> String {
+        let predicate = triple.predicate.as_str();
+        self.predicate_labels
+            .get(predicate)
+            .cloned()
+            .unwrap_or_else(|| predicate.to_string())
+    }
+
+    /// The edge drawn for a triple, or `None` for a literal object that is
+    /// not drawn.
+    fn edge(&self, id: &TripleId, triple: &Triple) -> Option<DagEdge> {
+        let target = match &triple.object {
+            Value::Node(object) => node_key(object),
+            _ if self.literal_nodes => id.to_hex(),
+            _ => return None,
+        };
+        Some(DagEdge {
+            source: node_key(&triple.subject),
+            target,
+            edge_type: self.edge_type,
+            label: Some(self.label_of(triple)),
+        })
+    }
+}
+
+/// Options for attaching a [`DagView`] to a data source.
+#[derive(Debug, Clone)]
+pub struct AttachOptions {
+    /// The maximum number of triples or records backfilled on attach, the
+    /// most recent first. `0` attaches without backfilling.
+    pub backfill_limit: usize,
+
+    /// How triples are drawn, for [`DagView::attach_graph`].
+    pub mapping: GraphMapping,
+}
+
+impl Default for AttachOptions {
+    fn default() -> Self {
+        Self {
+            backfill_limit: DEFAULT_BACKFILL_LIMIT,
+            mapping: GraphMapping::default(),
+        }
+    }
+}
+
+impl AttachOptions {
+    /// Sets the maximum number of items backfilled on attach.
+    pub fn backfill_limit(mut self, limit: usize) -> Self {
+        self.backfill_limit = limit;
+        self
+    }
+
+    /// Sets how triples are drawn.
+    pub fn mapping(mut self, mapping: GraphMapping) -> Self {
+        self.mapping = mapping;
+        self
+    }
+}
+
+/// Nodes and edges to add to, and edges to remove from, a [`DagView`].
+///
+/// Produced by [`GraphFeed`] and [`NodeFeed`], and applied with
+/// [`DagView::apply`] or [`ApiState::apply`].
+#[derive(Debug, Clone, Default)]
+pub struct DagChanges {
+    /// Nodes to add.
+    pub nodes: Vec<DagNode>,
+    /// Edges to add, after the nodes.
+    pub edges: Vec<DagEdge>,
+    /// Edges to remove.
+    pub removed_edges: Vec<DagEdge>,
+}
+
+impl DagChanges {
+    /// Returns `true` if there is nothing to apply.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty() && self.edges.is_empty() && self.removed_edges.is_empty()
+    }
+
+    /// Adds `node` unless a node with its id is in `known`.
+    fn add_node(&mut self, known: &mut HashSet<String>, node: DagNode) {
+        if known.insert(node.id.clone()) {
+            self.nodes.push(node);
+        }
+    }
+}
+
+/// Changes to a [`GraphDB`], drawn as [`DagChanges`].
+///
+/// Returned by [`DagView::attach_graph`]. Deleted triples remove their edge;
+/// nodes are kept. Dropping the feed unsubscribes from the graph.
+pub struct GraphFeed {
+    events: Receiver<GraphEvent>,
+    mapping: GraphMapping,
+    /// Ids of the nodes already in the view
+    known: HashSet<String>,
+    /// Triples backfilled after subscribing, whose insert event may follow
+    backfilled: HashSet<TripleId>,
+}
+
+impl GraphFeed {
+    /// Waits up to `timeout` for changes, and returns them along with every
+    /// other change already queued.
+    ///
+    /// Returns `None` if nothing changed in time, or once the graph has been
+    /// dropped; see [`is_closed`](Self::is_closed).
+    pub fn recv_timeout(&mut self, timeout: Duration) -> Option<DagChanges> {
+        let first = self.events.recv_timeout(timeout)?;
+        let mut changes = DagChanges::default();
+        self.map_event(first, &mut changes);
+        while let Some(event) = self.events.try_recv() {
+            self.map_event(event, &mut changes);
+        }
+        Some(changes)
+    }
+
+    /// Returns the changes already queued, if any.
+    pub fn try_recv(&mut self) -> Option<DagChanges> {
+        let mut changes = DagChanges::default();
+        while let Some(event) = self.events.try_recv() {
+            self.map_event(event, &mut changes);
+        }
+        (!changes.is_empty()).then_some(changes)
+    }
+
+    /// Returns `true` once the graph has been dropped and every change has
+    /// been received.
+    pub fn is_closed(&self) -> bool {
+        self.events.is_closed()
+    }
+
+    /// Number of graph events lost because the feed fell behind.
+    ///
+    /// The view misses those changes until it is attached again.
+    pub fn dropped(&self) -> u64 {
+        self.events.dropped()
+    }
+
+    fn map_event(&mut self, event: GraphEvent, changes: &mut DagChanges) {
+        match event {
+            GraphEvent::Inserted(id, triple) => {
+                if !self.backfilled.remove(&id) {
+                    self.map_triple(&id, &triple, changes);
+                }
+            }
+            GraphEvent::Deleted(id, triple) => {
+                self.backfilled.remove(&id);
+                changes
+                    .removed_edges
+                    .extend(self.mapping.edge(&id, &triple));
+            }
+        }
+    }
+
+    fn map_triple(&mut self, id: &TripleId, triple: &Triple, changes: &mut DagChanges) {
+        let timestamp = triple.meta.created_at.timestamp();
+        let author = triple.meta.author.as_ref().map(node_key);
+
+        let subject = node_key(&triple.subject);
+        let mut node = DagNodeBuilder::new(&subject, self.mapping.type_of(&triple.subject))
+            .label(&subject)
+            .timestamp(timestamp);
+        if let Some(author) = &author {
+            node = node.author(author);
+        }
+        changes.add_node(&mut self.known, node.build());
+
+        let object = match &triple.object {
+            Value::Node(object) => {
+                let key = node_key(object);
+                DagNodeBuilder::new(&key, self.mapping.type_of(object)).label(key)
+            }
+            _ if self.mapping.literal_nodes => {
+                let label = match &triple.object {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                DagNodeBuilder::new(id.to_hex(), self.mapping.literal_type)
+                    .label(label)
+                    .metadata("predicate", serde_json::json!(triple.predicate.as_str()))
+            }
+            _ => return,
+        };
+        let mut object = object.timestamp(timestamp);
+        if let Some(author) = &author {
+            object = object.author(author);
+        }
+        changes.add_node(&mut self.known, object.build());
+
+        changes.edges.extend(self.mapping.edge(id, triple));
+    }
+}
+
+/// A cursor over a [`MinimalNode`]'s source chain, drawn as [`DagChanges`].
+///
+/// Returned by [`DagView::attach_node`]. Agents, actions and entries become
+/// nodes of the matching [`NodeType`]; actions are linked to their author, to
+/// the previous action and to their entry.
+#[derive(Debug, Clone)]
+pub struct NodeFeed {
+    /// Sequence number of the last record drawn
+    cursor: u32,
+    /// Ids of the nodes already in the view
+    known: HashSet<String>,
+    /// Sequence number and id of the last action drawn
+    last_action: Option<(u32, String)>,
+}
+
+impl NodeFeed {
+    /// Reads the records added since the last call, up to 256 at a time.
+    pub fn poll(&mut self, node: &MinimalNode) -> Result<DagChanges> {
+        let records = node.records_since(self.cursor, NODE_POLL_BATCH)?;
+        let mut changes = DagChanges::default();
+        for record in &records {
+            self.map_record(record, &mut changes);
+        }
+        Ok(changes)
+    }
+
+    /// The sequence number of the last record read.
+    pub fn cursor(&self) -> u32 {
+        self.cursor
+    }
+
+    fn map_record(&mut self, record: &Record, changes: &mut DagChanges) {
+        let action = &record.action;
+        self.cursor = self.cursor.max(action.seq);
+
+        let action_id = action.hash().to_hex();
+        if self.known.contains(&action_id) {
+            return;
+        }
+        let author = action.author.to_hex();
+        let timestamp = (action.timestamp.0 / 1_000_000) as i64;
+
+        changes.add_node(
+            &mut self.known,
+            DagNodeBuilder::new(&author, NodeType::Agent)
+                .label(format!("Agent: {}", &author[..8]))
+                .timestamp(timestamp)
+                .build(),
+        );
+
+        let node_type = match action.action_type {
+            ActionType::Genesis => NodeType::Genesis,
+            _ => NodeType::Action,
+        };
+        changes.add_node(
+            &mut self.known,
+            DagNodeBuilder::new(&action_id, node_type)
+                .label(format!("{:?} #{}", action.action_type, action.seq))
+                .author(&author)
+                .timestamp(timestamp)
+                .metadata("seq", serde_json::json!(action.seq))
+                .build(),
+        );
+        changes.edges.push(DagEdge {
+            source: action_id.clone(),
+            target: author.clone(),
+            edge_type: EdgeType::Author,
+            label: Some("author".to_string()),
+        });
+
+        let prev = match &action.prev_action {
+            Some(hash) => Some(hash.to_hex()),
+            None => self
+                .last_action
+                .take()
+                .filter(|(seq, _)| seq + 1 == action.seq)
+                .map(|(_, id)| id),
+        };
+        if let Some(prev) = prev.filter(|id| self.known.contains(id)) {
+            changes.edges.push(DagEdge {
+                source: action_id.clone(),
+                target: prev,
+                edge_type: EdgeType::PrevAction,
+                label: Some("prev".to_string()),
+            });
+        }
+
+        if let Some(entry_hash) = &action.entry_hash {
+            let entry_id = entry_hash.to_hex();
+            let mut entry = DagNodeBuilder::new(&entry_id, NodeType::Entry)
+                .label(format!("Entry: {}", &entry_id[..8]))
+                .author(&author)
+                .timestamp(timestamp);
+            if let Some(content) = &record.entry {
+                entry = entry
+                    .metadata(
+                        "entry_type",
+                        serde_json::json!(format!("{:?}", content.entry_type)),
+                    )
+                    .metadata("size", serde_json::json!(content.size()));
+            }
+            changes.add_node(&mut self.known, entry.build());
+
+            let (edge_type, label) = match action.action_type {
+                ActionType::Create => (EdgeType::Create, "creates"),
+                ActionType::Update => (EdgeType::Update, "updates"),
+                ActionType::Delete => (EdgeType::Delete, "deletes"),
+                ActionType::CreateLink | ActionType::DeleteLink => (EdgeType::Link, "links"),
+                ActionType::Genesis => (EdgeType::EntryRef, "references"),
+            };
+            changes.edges.push(DagEdge {
+                source: action_id.clone(),
+                target: entry_id,
+                edge_type,
+                label: Some(label.to_string()),
+            });
+        }
+
+        self.last_action = Some((action.seq, action_id));
+    }
+}
+
+impl DagView {
+    /// Applies `changes`: adds their nodes, then their edges, then removes
+    /// their removed edges.
+    pub fn apply(&mut self, changes: &DagChanges) {
+        for node in &changes.nodes {
+            self.add_node(node.clone());
+        }
+        for edge in &changes.edges {
+            self.add_edge(edge.clone());
+        }
+        for edge in &changes.removed_edges {
+            self.remove_edge(edge);
+        }
+    }
+
+    /// Removes the first edge with the same endpoints, type and label as
+    /// `edge`. Returns `false` if there is none.
+    pub fn remove_edge(&mut self, edge: &DagEdge) -> bool {
+        let position = self.edges.iter().position(|e| {
+            e.source == edge.source
+                && e.target == edge.target
+                && e.edge_type == edge.edge_type
+                && e.label == edge.label
+        });
+        match position {
+            Some(i) => {
+                self.edges.remove(i);
+                self.stats.edge_count = self.edges.len();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Attaches the view to `graph`: backfills up to
+    /// [`backfill_limit`](AttachOptions::backfill_limit) of its most recently
+    /// inserted triples, and returns a [`GraphFeed`] of the changes made after.
+    ///
+    /// The feed subscribes before the backfill, so no change is missed.
+    pub fn attach_graph(&mut self, graph: &GraphDB, options: &AttachOptions) -> Result<GraphFeed> {
+        let mut feed = GraphFeed {
+            events: graph.subscribe(),
+            mapping: options.mapping.clone(),
+            known: self.nodes.iter().map(|n| n.id.clone()).collect(),
+            backfilled: HashSet::new(),
+        };
+
+        let mut changes = DagChanges::default();
+        if options.backfill_limit > 0 {
+            let recent = graph
+                .query()
+                .order_by(OrderKey::InsertionTime, SortOrder::Desc)
+                .limit(options.backfill_limit)
+                .execute()?;
+            for triple in recent.triples.iter().rev() {
+                let id = triple.id();
+                feed.map_triple(&id, triple, &mut changes);
+                feed.backfilled.insert(id);
+            }
+            log::debug!(
+                "Backfilled {} of {} triples",
+                recent.triples.len(),
+                recent.total_count
+            );
+        }
+        self.apply(&changes);
+
+        Ok(feed)
+    }
+
+    /// Attaches the view to `node`: backfills up to
+    /// [`backfill_limit`](AttachOptions::backfill_limit) of its latest
+    /// records, and returns a [`NodeFeed`] reading the records added after.
+    pub fn attach_node(&mut self, node: &MinimalNode, options: &AttachOptions) -> Result<NodeFeed> {
+        let latest = node.latest_seq()?;
+        let limit = u32::try_from(options.backfill_limit).unwrap_or(u32::MAX);
+        let mut feed = NodeFeed {
+            cursor: latest.saturating_sub(limit),
+            known: self.nodes.iter().map(|n| n.id.clone()).collect(),
+            last_action: None,
+        };
+
+        let mut changes = DagChanges::default();
+        while feed.cursor < latest {
+            let records = node.records_since(feed.cursor, NODE_POLL_BATCH)?;
+            if records.is_empty() {
+                break;
+            }
+            for record in &records {
+                feed.map_record(record, &mut changes);
+            }
+        }
+        feed.cursor = feed.cursor.max(latest);
+        self.apply(&changes);
+
+        Ok(feed)
+    }
+}
+
+/// A [`GraphFeed`] streaming into an [`ApiState`] on a background task.
+///
+/// Returned by [`ApiState::attach_graph`]. Dropping it stops the stream.
+pub struct Attachment {
+    stop: Arc<AtomicBool>,
+    task: JoinHandle<()>,
+}
+
+impl Attachment {
+    /// Returns `true` once the stream has ended, because the graph was
+    /// dropped or the attachment was stopped.
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+
+    /// Stops the stream and waits for its task to end.
+    pub async fn detach(mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        let _ = (&mut self.task).await;
+    }
+}
+
+impl Drop for Attachment {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+impl ApiState {
+    /// Adds `changes` to the DAG and broadcasts a `NodeAdded`, `EdgeAdded` or
+    /// `EdgeRemoved` event for each.
+    ///
+    /// # Errors
+    ///
+    /// Currently this method always returns `Ok(())`, but the signature
+    /// allows for future error handling.
+    pub async fn apply(&self, changes: DagChanges) -> Result<()> {
+        if changes.is_empty() {
+            return Ok(());
+        }
+        self.dag.write().await.apply(&changes);
+
+        for node in changes.nodes {
+            self.broadcaster.node_added(node).await;
+        }
+        for edge in changes.edges {
+            self.broadcaster.edge_added(edge).await;
+        }
+        for edge in changes.removed_edges {
+            self.broadcaster
+                .broadcast(DagEvent::EdgeRemoved {
+                    source: edge.source,
+                    target: edge.target,
+                })
+                .await;
+        }
+        Ok(())
+    }
+
+    /// Attaches the DAG to `graph` and streams its changes to WebSocket
+    /// clients until the returned [`Attachment`] is dropped.
+    ///
+    /// The backfill is not broadcast item by item: clients are sent a
+    /// `Refresh` event instead. Must be called from a Tokio runtime.
+    ///
+    /// See [`DagView::attach_graph`].
+    pub async fn attach_graph(
+        &self,
+        graph: &GraphDB,
+        options: &AttachOptions,
+    ) -> Result<Attachment> {
+        let mut feed = self.dag.write().await.attach_graph(graph, options)?;
+        self.broadcaster.refresh("Attached to graph").await;
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = stop.clone();
+        let state = self.clone();
+        let runtime = tokio::runtime::Handle::current();
+        let task = tokio::task::spawn_blocking(move || {
+            while !stopped.load(Ordering::Relaxed) {
+                match feed.recv_timeout(FEED_POLL_INTERVAL) {
+                    Some(changes) => {
+                        let _ = runtime.block_on(state.apply(changes));
+                    }
+                    None if feed.is_closed() => break,
+                    None => {}
+                }
+            }
+            log::debug!("Graph attachment ended");
+        });
+
+        Ok(Attachment { stop, task })
+    }
+}
+
+/// The id of the node drawn for `id`: its name, or its display form for
+/// unnamed nodes.
+fn node_key(id: &NodeId) -> String {
+    match id.as_name() {
+        Some(name) => name.to_string(),
+        None => id.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aingle_graph::Predicate;
+    use aingle_minimal::Config;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use tower::ServiceExt;
+
+    fn knows(subject: &str, object: &str) -> Triple {
+        Triple::new(
+            NodeId::named(subject),
+            Predicate::named("knows"),
+            Value::Node(NodeId::named(object)),
+        )
+    }
+
+    #[test]
+    fn test_graph_mapping() {
+        let graph = GraphDB::memory().unwrap();
+        let mut dag = DagView::new();
+        let options = AttachOptions::default().mapping(
+            GraphMapping::default()
+                .namespace_type("agent", NodeType::Agent)
+                .predicate_label("knows", "is friends with"),
+        );
+        let mut feed = dag.attach_graph(&graph, &options).unwrap();
+
+        graph.insert(knows("agent:alice", "bob")).unwrap();
+        graph
+            .insert(Triple::new(
+                NodeId::named("bob"),
+                Predicate::named("name"),
+                Value::literal("Bob"),
+            ))
+            .unwrap();
+        let changes = feed.try_recv().unwrap();
+        dag.apply(&changes);
+
+        assert_eq!(dag.nodes.len(), 3);
+        assert_eq!(
+            dag.get_node("agent:alice").unwrap().node_type,
+            NodeType::Agent
+        );
+        assert_eq!(dag.get_node("bob").unwrap().node_type, NodeType::Entry);
+        assert!(dag.nodes.iter().any(|n| n.label == "Bob"));
+        assert_eq!(dag.edges.len(), 2);
+        assert_eq!(dag.edges[0].source, "agent:alice");
+        assert_eq!(dag.edges[0].target, "bob");
+        assert_eq!(dag.edges[0].label.as_deref(), Some("is friends with"));
+    }
+
+    #[test]
+    fn test_graph_backfill_limit_and_deletes() {
+        let graph = GraphDB::memory().unwrap();
+        for i in 0..5 {
+            graph.insert(knows(&format!("n{}", i), "hub")).unwrap();
+        }
+
+        let mut dag = DagView::new();
+        let options = AttachOptions::default().backfill_limit(3);
+        let mut feed = dag.attach_graph(&graph, &options).unwrap();
+        assert_eq!(dag.edges.len(), 3);
+        assert!(dag.get_node("n4").is_some());
+        assert!(dag.get_node("n0").is_none());
+
+        graph.delete(&knows("n4", "hub").id()).unwrap();
+        dag.apply(&feed.try_recv().unwrap());
+        assert_eq!(dag.edges.len(), 2);
+        assert_eq!(dag.stats.edge_count, 2);
+        assert!(feed.try_recv().is_none());
+
+        drop(graph);
+        assert!(feed.is_closed());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_attached_graph_is_served_and_streamed() {
+        let graph = GraphDB::memory().unwrap();
+        graph.insert(knows("alice", "bob")).unwrap();
+
+        let state = ApiState::new();
+        let mut events = state.broadcaster.subscribe();
+        let attachment = state
+            .attach_graph(&graph, &AttachOptions::default())
+            .await
+            .unwrap();
+        assert!(matches!(
+            events.recv().await.unwrap(),
+            DagEvent::Refresh { .. }
+        ));
+
+        graph.insert(knows("bob", "carol")).unwrap();
+        graph.insert(knows("carol", "dave")).unwrap();
+
+        let mut streamed = HashSet::new();
+        let mut edges = 0;
+        while streamed.len() < 2 || edges < 2 {
+            let event = tokio::time::timeout(Duration::from_secs(5), events.recv())
+                .await
+                .expect("attached changes are streamed")
+                .unwrap();
+            match event {
+                DagEvent::NodeAdded { node } => {
+                    streamed.insert(node.id);
+                }
+                DagEvent::EdgeAdded { .. } => edges += 1,
+                other => panic!("unexpected event {:?}", other),
+            }
+        }
+        assert_eq!(streamed, HashSet::from(["carol".into(), "dave".into()]));
+
+        let app = crate::api::create_router(state);
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/dag")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let dag: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        let mut ids: Vec<&str> = dag["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|n| n["id"].as_str().unwrap())
+            .collect();
+        ids.sort_unstable();
+        assert_eq!(ids, vec!["alice", "bob", "carol", "dave"]);
+        assert_eq!(dag["edges"].as_array().unwrap().len(), 3);
+        assert_eq!(dag["edges"][0]["label"], "knows");
+
+        attachment.detach().await;
+    }
+
+    #[test]
+    fn test_node_mapping() {
+        let mut node = MinimalNode::new(Config::test_mode()).unwrap();
+        node.create_entry("first").unwrap();
+        node.create_entry("second").unwrap();
+
+        let mut dag = DagView::new();
+        let options = AttachOptions::default().backfill_limit(1);
+        let mut feed = dag.attach_node(&node, &options).unwrap();
+        assert_eq!(feed.cursor(), 2);
+        assert_eq!(dag.stats.agent_count, 1);
+        assert_eq!(dag.stats.action_count, 1);
+        assert_eq!(dag.stats.entry_count, 1);
+
+        node.create_entry("third").unwrap();
+        let changes = feed.poll(&node).unwrap();
+        dag.apply(&changes);
+        assert_eq!(feed.cursor(), 3);
+        assert_eq!(dag.stats.agent_count, 1);
+        assert_eq!(dag.stats.action_count, 2);
+        assert_eq!(dag.stats.entry_count, 2);
+
+        let edge_types: Vec<EdgeType> = changes.edges.iter().map(|e| e.edge_type).collect();
+        assert_eq!(
+            edge_types,
+            vec![EdgeType::Author, EdgeType::PrevAction, EdgeType::Create]
+        );
+        assert!(feed.poll(&node).unwrap().is_empty());
+    }
+}
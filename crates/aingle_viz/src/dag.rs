@@ -45,6 +45,8 @@
 //! The [`DagView::to_d3_json`] method converts the DAG to a format compatible with
 //! D3.js force-directed graphs, making it easy to visualize in a web browser.
 
+use crate::search::SearchIndex;
+
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -466,6 +468,10 @@ pub struct DagView {
     /// Automatically updated when nodes and edges are added via
     /// [`add_node`](Self::add_node) and [`add_edge`](Self::add_edge).
     pub stats: DagStats,
+
+    /// Index behind [`search`](Self::search), maintained by [`add_node`](Self::add_node).
+    #[serde(skip)]
+    pub(crate) search_index: SearchIndex,
 }
 
 /// Statistics about the state of the DAG.
@@ -545,13 +551,14 @@ impl DagView {
             nodes: Vec::new(),
             edges: Vec::new(),
             stats: DagStats::default(),
+            search_index: SearchIndex::default(),
         }
     }
 
     /// Adds a node to the DAG and updates statistics.
     ///
     /// This method automatically updates the DAG statistics including node counts
-    /// and timestamp ranges.
+    /// and timestamp ranges, and indexes the node for [`search`](Self::search).
     ///
     /// # Examples
     ///
@@ -589,6 +596,7 @@ impl DagView {
             self.stats.latest_timestamp = Some(node.timestamp);
         }
 
+        self.search_index.insert(self.nodes.len(), &node);
         self.nodes.push(node);
         self.stats.node_count = self.nodes.len();
     }
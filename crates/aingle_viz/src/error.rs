@@ -44,6 +44,12 @@ pub enum Error {
     Config(String),
 }
 
+impl From<aingle_graph::Error> for Error {
+    fn from(e: aingle_graph::Error) -> Self {
+        Error::Graph(e.to_string())
+    }
+}
+
 impl From<aingle_minimal::error::Error> for Error {
     fn from(e: aingle_minimal::error::Error) -> Self {
         Error::Node(e.to_string())
@@ -106,6 +112,14 @@ mod tests {
         assert!(format!("{}", error).contains("Node error"));
     }
 
+    #[test]
+    fn test_from_graph_error() {
+        let graph_err = aingle_graph::Error::NotFound("triple".into());
+        let error: Error = graph_err.into();
+        assert!(matches!(error, Error::Graph(_)));
+        assert!(format!("{}", error).contains("Graph error"));
+    }
+
     #[test]
     fn test_error_debug() {
         let error = Error::Server("test".into());
@@ -142,7 +142,7 @@ pub enum DagEvent {
 
     /// An edge has been removed from the DAG.
     ///
-    /// Currently not used, reserved for future functionality.
+    /// Sent when [`ApiState::apply`](crate::ApiState::apply) removes an edge.
     EdgeRemoved {
         /// The source node ID of the removed edge.
         source: String,
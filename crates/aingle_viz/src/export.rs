@@ -0,0 +1,670 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Graphviz DOT and GraphML export of a [`DagView`].
+//!
+//! Exports are produced incrementally by [`ExportChunks`], an iterator of text
+//! chunks that each cover a bounded number of nodes or edges, so a large DAG
+//! can be written to a file or streamed over HTTP without building the whole
+//! document in memory. [`DagView::to_dot`] and [`DagView::to_graphml`] collect
+//! the chunks into a `String` for convenience.
+//!
+//! - **DOT**: nodes are colored and shaped by [`NodeType`], edges are labeled and
+//!   colored by [`EdgeType`]. Long node labels are truncated (see
+//!   [`DotOptions::max_label_len`]).
+//! - **GraphML**: nodes carry typed attributes (`timestamp` as `long`, `author`,
+//!   and one key per metadata field, typed from its JSON values), ready for Gephi.
+//!
+//! # Examples
+//!
+//! ```
+//! use aingle_viz::{DagView, DagNodeBuilder, NodeType};
+//! use aingle_viz::export::DotOptions;
+//!
+//! let mut dag = DagView::new();
+//! dag.add_node(DagNodeBuilder::new("g", NodeType::Genesis).label("Genesis").build());
+//!
+//! let dot = dag.to_dot(&DotOptions::default());
+//! assert!(dot.starts_with("digraph \"aingle_dag\" {"));
+//!
+//! let graphml = dag.to_graphml();
+//! assert!(graphml.contains("<node id=\"g\">"));
+//! ```
+
+use crate::dag::{DagEdge, DagNode, DagView, EdgeType, NodeType};
+use crate::error::Error;
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io;
+use std::ops::Deref;
+use std::str::FromStr;
+
+/// Number of nodes or edges rendered into a single chunk.
+const CHUNK_ITEMS: usize = 256;
+
+/// A machine-readable graph export format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Graphviz DOT.
+    Dot,
+    /// GraphML (XML).
+    GraphMl,
+}
+
+impl ExportFormat {
+    /// The MIME type of the format.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Dot => "text/vnd.graphviz; charset=utf-8",
+            ExportFormat::GraphMl => "application/graphml+xml; charset=utf-8",
+        }
+    }
+
+    /// The conventional file extension, without the dot.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Dot => "dot",
+            ExportFormat::GraphMl => "graphml",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "dot" | "gv" | "graphviz" => Ok(ExportFormat::Dot),
+            "graphml" => Ok(ExportFormat::GraphMl),
+            other => Err(Error::Config(format!("Unknown export format: {}", other))),
+        }
+    }
+}
+
+/// Options for DOT export.
+#[derive(Debug, Clone)]
+pub struct DotOptions {
+    /// The name of the generated `digraph`.
+    ///
+    /// Default is `"aingle_dag"`.
+    pub graph_name: String,
+
+    /// Node labels longer than this many characters are cut and end in `…`.
+    ///
+    /// `None` keeps labels whole. Default is `Some(32)`.
+    pub max_label_len: Option<usize>,
+
+    /// Whether edges are labeled with their [`EdgeType`].
+    ///
+    /// Default is `true`.
+    pub edge_labels: bool,
+}
+
+impl Default for DotOptions {
+    fn default() -> Self {
+        Self {
+            graph_name: "aingle_dag".to_string(),
+            max_label_len: Some(32),
+            edge_labels: true,
+        }
+    }
+}
+
+/// Stages of an export, in document order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    Header,
+    Nodes,
+    Edges,
+    Footer,
+    Done,
+}
+
+/// The GraphML type of a metadata key, inferred from its values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AttrType {
+    Boolean,
+    Long,
+    Double,
+    String,
+}
+
+impl AttrType {
+    fn of(value: &serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::Bool(_) => AttrType::Boolean,
+            serde_json::Value::Number(n) if n.is_i64() || n.is_u64() => AttrType::Long,
+            serde_json::Value::Number(_) => AttrType::Double,
+            _ => AttrType::String,
+        }
+    }
+
+    /// The narrowest type able to hold values of both types.
+    fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (AttrType::Long, AttrType::Double) | (AttrType::Double, AttrType::Long) => {
+                AttrType::Double
+            }
+            _ => AttrType::String,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            AttrType::Boolean => "boolean",
+            AttrType::Long => "long",
+            AttrType::Double => "double",
+            AttrType::String => "string",
+        }
+    }
+}
+
+/// An incremental export of a [`DagView`].
+///
+/// Iterating yields the document as a sequence of chunks; concatenated, they
+/// form the complete export. Each chunk covers at most a few hundred nodes or
+/// edges, so memory use stays bounded regardless of the size of the DAG.
+///
+/// `D` is anything that dereferences to a [`DagView`]: a plain reference, or an
+/// owned lock guard when the export must outlive the current scope (e.g. an
+/// HTTP response body).
+///
+/// # Examples
+///
+/// ```
+/// use aingle_viz::export::{ExportChunks, ExportFormat};
+/// use aingle_viz::{DagView, DagNodeBuilder, NodeType};
+///
+/// let mut dag = DagView::new();
+/// dag.add_node(DagNodeBuilder::new("n1", NodeType::Entry).build());
+///
+/// let mut out = Vec::new();
+/// ExportChunks::new(&dag, ExportFormat::GraphMl)
+///     .write_to(&mut out)
+///     .unwrap();
+/// assert!(String::from_utf8(out).unwrap().ends_with("</graphml>\n"));
+/// ```
+pub struct ExportChunks<D> {
+    dag: D,
+    format: ExportFormat,
+    dot: DotOptions,
+    omitted_metadata: Vec<String>,
+    stage: Stage,
+    position: usize,
+}
+
+impl<D: Deref<Target = DagView>> ExportChunks<D> {
+    /// Starts an export of `dag` in `format` with default options.
+    pub fn new(dag: D, format: ExportFormat) -> Self {
+        Self {
+            dag,
+            format,
+            dot: DotOptions::default(),
+            omitted_metadata: Vec::new(),
+            stage: Stage::Header,
+            position: 0,
+        }
+    }
+
+    /// Sets the options used for DOT output.
+    pub fn with_dot_options(mut self, options: DotOptions) -> Self {
+        self.dot = options;
+        self
+    }
+
+    /// Leaves the given metadata keys out of the export.
+    pub fn omit_metadata(mut self, keys: Vec<String>) -> Self {
+        self.omitted_metadata = keys;
+        self
+    }
+
+    /// Writes the whole export to `writer`, one chunk at a time.
+    pub fn write_to<W: io::Write>(self, mut writer: W) -> io::Result<()> {
+        for chunk in self {
+            writer.write_all(chunk.as_bytes())?;
+        }
+        writer.flush()
+    }
+
+    fn render_header(&self) -> String {
+        match self.format {
+            ExportFormat::Dot => format!(
+                "digraph \"{}\" {{\n  node [style=filled, fontname=\"Helvetica\"];\n  edge [fontname=\"Helvetica\", fontsize=10];\n",
+                escape_dot(&self.dot.graph_name)
+            ),
+            ExportFormat::GraphMl => self.graphml_header(),
+        }
+    }
+
+    fn render_footer(&self) -> String {
+        match self.format {
+            ExportFormat::Dot => "}\n".to_string(),
+            ExportFormat::GraphMl => "  </graph>\n</graphml>\n".to_string(),
+        }
+    }
+
+    fn render_node(&self, out: &mut String, node: &DagNode) {
+        match self.format {
+            ExportFormat::Dot => {
+                let label = match self.dot.max_label_len {
+                    Some(max) => truncate_label(&node.label, max),
+                    None => node.label.clone(),
+                };
+                let _ = writeln!(
+                    out,
+                    "  \"{}\" [label=\"{}\", shape={}, fillcolor=\"{}\"];",
+                    escape_dot(&node.id),
+                    escape_dot(&label),
+                    dot_shape(node.node_type),
+                    node.node_type.color()
+                );
+            }
+            ExportFormat::GraphMl => {
+                let _ = writeln!(out, "    <node id=\"{}\">", escape_xml(&node.id));
+                push_data(out, "label", &node.label);
+                push_data(out, "node_type", &node_type_name(node.node_type));
+                push_data(out, "timestamp", &node.timestamp.to_string());
+                if let Some(ref author) = node.author {
+                    push_data(out, "author", author);
+                }
+                let mut metadata: Vec<_> = node
+                    .metadata
+                    .iter()
+                    .filter(|(key, _)| !self.omitted_metadata.contains(key))
+                    .collect();
+                metadata.sort_by(|a, b| a.0.cmp(b.0));
+                for (key, value) in metadata {
+                    let text = match value {
+                        serde_json::Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    };
+                    push_data(out, &metadata_key_id(key), &text);
+                }
+                out.push_str("    </node>\n");
+            }
+        }
+    }
+
+    fn render_edge(&self, out: &mut String, index: usize, edge: &DagEdge) {
+        match self.format {
+            ExportFormat::Dot => {
+                let _ = write!(
+                    out,
+                    "  \"{}\" -> \"{}\" [color=\"{}\"",
+                    escape_dot(&edge.source),
+                    escape_dot(&edge.target),
+                    edge.edge_type.color()
+                );
+                if self.dot.edge_labels {
+                    let _ = write!(out, ", label=\"{}\"", edge_type_name(edge.edge_type));
+                }
+                out.push_str("];\n");
+            }
+            ExportFormat::GraphMl => {
+                let _ = writeln!(
+                    out,
+                    "    <edge id=\"e{}\" source=\"{}\" target=\"{}\">",
+                    index,
+                    escape_xml(&edge.source),
+                    escape_xml(&edge.target)
+                );
+                push_data(out, "edge_type", &edge_type_name(edge.edge_type));
+                if let Some(ref label) = edge.label {
+                    push_data(out, "edge_label", label);
+                }
+                out.push_str("    </edge>\n");
+            }
+        }
+    }
+
+    /// The GraphML preamble, declaring a typed key per attribute.
+    ///
+    /// Metadata keys are collected in a pass over the nodes; only the key
+    /// names and their types are kept.
+    fn graphml_header(&self) -> String {
+        let mut metadata: BTreeMap<&str, AttrType> = BTreeMap::new();
+        for node in &self.dag.nodes {
+            for (key, value) in &node.metadata {
+                if self.omitted_metadata.contains(key) {
+                    continue;
+                }
+                let ty = AttrType::of(value);
+                metadata
+                    .entry(key.as_str())
+                    .and_modify(|t| *t = t.merge(ty))
+                    .or_insert(ty);
+            }
+        }
+
+        let mut out = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\" \
+             xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" \
+             xsi:schemaLocation=\"http://graphml.graphdrawing.org/xmlns \
+             http://graphml.graphdrawing.org/xmlns/1.0/graphml.xsd\">\n",
+        );
+        for (id, target, ty) in [
+            ("label", "node", "string"),
+            ("node_type", "node", "string"),
+            ("timestamp", "node", "long"),
+            ("author", "node", "string"),
+            ("edge_type", "edge", "string"),
+            ("edge_label", "edge", "string"),
+        ] {
+            let _ = writeln!(
+                out,
+                "  <key id=\"{id}\" for=\"{target}\" attr.name=\"{id}\" attr.type=\"{ty}\"/>"
+            );
+        }
+        for (key, ty) in metadata {
+            let _ = writeln!(
+                out,
+                "  <key id=\"{}\" for=\"node\" attr.name=\"{}\" attr.type=\"{}\"/>",
+                escape_xml(&metadata_key_id(key)),
+                escape_xml(key),
+                ty.name()
+            );
+        }
+        out.push_str("  <graph id=\"aingle_dag\" edgedefault=\"directed\">\n");
+        out
+    }
+}
+
+impl<D: Deref<Target = DagView>> Iterator for ExportChunks<D> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        loop {
+            match self.stage {
+                Stage::Header => {
+                    self.stage = Stage::Nodes;
+                    return Some(self.render_header());
+                }
+                Stage::Nodes => {
+                    let end = (self.position + CHUNK_ITEMS).min(self.dag.nodes.len());
+                    if self.position >= end {
+                        self.stage = Stage::Edges;
+                        self.position = 0;
+                        continue;
+                    }
+                    let mut out = String::new();
+                    for node in &self.dag.nodes[self.position..end] {
+                        self.render_node(&mut out, node);
+                    }
+                    self.position = end;
+                    return Some(out);
+                }
+                Stage::Edges => {
+                    let end = (self.position + CHUNK_ITEMS).min(self.dag.edges.len());
+                    if self.position >= end {
+                        self.stage = Stage::Footer;
+                        continue;
+                    }
+                    let mut out = String::new();
+                    for index in self.position..end {
+                        self.render_edge(&mut out, index, &self.dag.edges[index]);
+                    }
+                    self.position = end;
+                    return Some(out);
+                }
+                Stage::Footer => {
+                    self.stage = Stage::Done;
+                    return Some(self.render_footer());
+                }
+                Stage::Done => return None,
+            }
+        }
+    }
+}
+
+impl DagView {
+    /// Renders the DAG as a Graphviz DOT document.
+    ///
+    /// For large DAGs prefer streaming with [`ExportChunks`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aingle_viz::export::DotOptions;
+    /// use aingle_viz::{DagEdge, DagNodeBuilder, DagView, EdgeType, NodeType};
+    ///
+    /// let mut dag = DagView::new();
+    /// dag.add_node(DagNodeBuilder::new("a", NodeType::Action).label("Create").build());
+    /// dag.add_node(DagNodeBuilder::new("e", NodeType::Entry).label("Entry").build());
+    /// dag.add_edge(DagEdge {
+    ///     source: "a".to_string(),
+    ///     target: "e".to_string(),
+    ///     edge_type: EdgeType::Create,
+    ///     label: None,
+    /// });
+    ///
+    /// let dot = dag.to_dot(&DotOptions::default());
+    /// assert!(dot.contains("\"a\" -> \"e\" [color=\"#2196F3\", label=\"create\"];"));
+    /// ```
+    pub fn to_dot(&self, options: &DotOptions) -> String {
+        ExportChunks::new(self, ExportFormat::Dot)
+            .with_dot_options(options.clone())
+            .collect()
+    }
+
+    /// Renders the DAG as a GraphML document.
+    ///
+    /// For large DAGs prefer streaming with [`ExportChunks`].
+    pub fn to_graphml(&self) -> String {
+        ExportChunks::new(self, ExportFormat::GraphMl).collect()
+    }
+}
+
+/// The lowercase name of a node type, as used by the D3 export.
+fn node_type_name(node_type: NodeType) -> String {
+    format!("{:?}", node_type).to_lowercase()
+}
+
+/// The lowercase name of an edge type, as used by the D3 export.
+fn edge_type_name(edge_type: EdgeType) -> String {
+    format!("{:?}", edge_type).to_lowercase()
+}
+
+/// The Graphviz shape for a node type, following [`NodeType::icon`].
+fn dot_shape(node_type: NodeType) -> &'static str {
+    match node_type {
+        NodeType::Genesis => "star",
+        NodeType::Entry => "ellipse",
+        NodeType::Action => "box",
+        NodeType::Agent => "house",
+        NodeType::Link => "diamond",
+        NodeType::System => "hexagon",
+    }
+}
+
+/// The GraphML key id of a metadata field.
+fn metadata_key_id(key: &str) -> String {
+    format!("meta.{}", key)
+}
+
+/// Cuts `label` to at most `max` characters, marking the cut with `…`.
+fn truncate_label(label: &str, max: usize) -> String {
+    if label.chars().count() <= max {
+        return label.to_string();
+    }
+    let mut cut: String = label.chars().take(max.saturating_sub(1)).collect();
+    cut.push('…');
+    cut
+}
+
+fn push_data(out: &mut String, key: &str, value: &str) {
+    let _ = writeln!(
+        out,
+        "      <data key=\"{}\">{}</data>",
+        escape_xml(key),
+        escape_xml(value)
+    );
+}
+
+/// Escapes a string for use inside a double-quoted DOT ID.
+fn escape_dot(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Escapes a string for XML text and attribute values.
+fn escape_xml(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            // Control characters other than tab/newline are not allowed in XML 1.0
+            c if c.is_control() && c != '\t' && c != '\n' && c != '\r' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dag::DagNodeBuilder;
+
+    /// A small DAG covering every node shape, escaping and typed metadata.
+    fn fixture() -> DagView {
+        let mut dag = DagView::new();
+        dag.add_node(
+            DagNodeBuilder::new("genesis", NodeType::Genesis)
+                .label("Genesis")
+                .timestamp(1_700_000_000)
+                .build(),
+        );
+        dag.add_node(
+            DagNodeBuilder::new("agent_alice", NodeType::Agent)
+                .label("Agent: alice")
+                .timestamp(1_700_000_010)
+                .build(),
+        );
+        dag.add_node(
+            DagNodeBuilder::new("entry_1", NodeType::Entry)
+                .label("A \"quoted\" entry with a label that is far too long to show")
+                .author("agent_alice")
+                .timestamp(1_700_000_020)
+                .metadata("index", serde_json::json!(1))
+                .metadata("score", serde_json::json!(0.5))
+                .metadata("visible", serde_json::json!(true))
+                .metadata("content", serde_json::json!("<b>hello</b> & bye"))
+                .build(),
+        );
+        dag.add_node(
+            DagNodeBuilder::new("action_1", NodeType::Action)
+                .label("Create #1")
+                .author("agent_alice")
+                .timestamp(1_700_000_020)
+                .metadata("index", serde_json::json!(1.5))
+                .build(),
+        );
+        dag.add_node(
+            DagNodeBuilder::new("link_1", NodeType::Link)
+                .label("Link")
+                .timestamp(1_700_000_030)
+                .build(),
+        );
+        dag.add_node(
+            DagNodeBuilder::new("system", NodeType::System)
+                .label("System")
+                .timestamp(1_700_000_040)
+                .build(),
+        );
+        for (source, target, edge_type, label) in [
+            ("action_1", "genesis", EdgeType::PrevAction, Some("prev")),
+            ("action_1", "entry_1", EdgeType::Create, None),
+            ("action_1", "agent_alice", EdgeType::Author, None),
+            ("link_1", "entry_1", EdgeType::Link, Some("links to")),
+        ] {
+            dag.add_edge(DagEdge {
+                source: source.to_string(),
+                target: target.to_string(),
+                edge_type,
+                label: label.map(str::to_string),
+            });
+        }
+        dag
+    }
+
+    #[test]
+    fn test_dot_golden() {
+        let dot = fixture().to_dot(&DotOptions::default());
+        assert_eq!(dot, include_str!("../tests/golden/fixture.dot"));
+    }
+
+    #[test]
+    fn test_graphml_golden() {
+        let graphml = fixture().to_graphml();
+        assert_eq!(graphml, include_str!("../tests/golden/fixture.graphml"));
+    }
+
+    #[test]
+    fn test_dot_options() {
+        let options = DotOptions {
+            graph_name: "g".to_string(),
+            max_label_len: None,
+            edge_labels: false,
+        };
+        let dot = fixture().to_dot(&options);
+        assert!(dot.starts_with("digraph \"g\" {"));
+        assert!(dot.contains("far too long to show"));
+        assert!(!dot.contains("label=\"create\""));
+    }
+
+    #[test]
+    fn test_omitted_metadata() {
+        let dag = fixture();
+        let graphml: String = ExportChunks::new(&dag, ExportFormat::GraphMl)
+            .omit_metadata(vec!["content".to_string()])
+            .collect();
+        assert!(!graphml.contains("meta.content"));
+        assert!(!graphml.contains("hello"));
+        assert!(graphml.contains("meta.index"));
+    }
+
+    #[test]
+    fn test_chunks_are_bounded() {
+        let mut dag = DagView::new();
+        for i in 0..(CHUNK_ITEMS * 3 + 7) {
+            dag.add_node(DagNodeBuilder::new(format!("n{}", i), NodeType::Entry).build());
+        }
+        let chunks: Vec<String> = ExportChunks::new(&dag, ExportFormat::Dot).collect();
+        // header, 4 node chunks, footer
+        assert_eq!(chunks.len(), 6);
+        assert_eq!(chunks.concat(), dag.to_dot(&DotOptions::default()));
+    }
+
+    #[test]
+    fn test_format_parsing() {
+        assert_eq!("dot".parse::<ExportFormat>().unwrap(), ExportFormat::Dot);
+        assert_eq!(
+            "GraphML".parse::<ExportFormat>().unwrap(),
+            ExportFormat::GraphMl
+        );
+        assert!("gexf".parse::<ExportFormat>().is_err());
+    }
+
+    #[test]
+    fn test_truncate_label() {
+        assert_eq!(truncate_label("short", 10), "short");
+        assert_eq!(truncate_label("abcdefghij", 5), "abcd…");
+        assert_eq!(truncate_label("ñandú ñandú", 6), "ñandú…");
+    }
+}
@@ -89,14 +89,20 @@
 //! - `GET /api/dag` - Retrieve full DAG structure
 //! - `GET /api/dag/entry/:hash` - Get specific entry details
 //! - `GET /api/dag/recent?limit=N` - Get N most recent entries
+//! - `GET /api/dag/search?q=...` - Type-ahead node search
 //! - `GET /api/stats` - Network statistics (node count, edge count, etc.)
 //! - `WS /ws/updates` - WebSocket stream for real-time updates
 //!
+//! These routes can require a bearer token or basic-auth credentials
+//! ([`VizConfig::auth`]). By default the explorer is read-only and strips entry
+//! payloads from node metadata; see [`access`].
+//!
 //! ## JavaScript Integration
 //!
 //! Connect to the WebSocket for real-time updates:
 //!
 //! ```javascript
+//! // Append `?access_token=<token>` when bearer auth is enabled
 //! const ws = new WebSocket('ws://localhost:8888/ws/updates');
 //!
 //! ws.onmessage = (event) => {
@@ -126,6 +132,26 @@
 //! // Your application continues and updates the DAG
 //! dag.add_entry(...);
 //! ```
+//!
+//! Rather than updating the DAG by hand, it can follow a running graph, which
+//! also streams the changes to WebSocket clients (see [`attach`]):
+//!
+//! ```rust,ignore
+//! let _attachment = state.attach_graph(&graph, &AttachOptions::default()).await?;
+//! ```
+
+/// Authentication, read-only mode and metadata redaction for the API.
+///
+/// This module provides the [`AccessPolicy`](access::AccessPolicy) applied to the
+/// `/api` and `/ws` routes, configured through [`VizConfig`].
+pub mod access;
+
+/// Live attachment of a DAG view to a running graph or node.
+///
+/// This module provides [`DagView::attach_graph`] and [`DagView::attach_node`],
+/// which backfill a view from a [`GraphDB`](aingle_graph::GraphDB) or
+/// [`MinimalNode`](aingle_minimal::MinimalNode) and follow its changes.
+pub mod attach;
 
 /// HTTP and WebSocket API endpoints for the visualization server.
 ///
@@ -148,6 +174,13 @@ pub mod dag;
 /// Error types and result aliases for the visualization crate.
 pub mod error;
 
+/// Graphviz DOT and GraphML export of DAG views.
+///
+/// This module provides [`ExportChunks`](export::ExportChunks), which renders a
+/// [`DagView`] incrementally so large DAGs can be streamed to files or HTTP
+/// clients.
+pub mod export;
+
 /// Real-time event broadcasting system for WebSocket clients.
 ///
 /// This module provides the [`EventBroadcaster`](events::EventBroadcaster) which manages WebSocket
@@ -156,6 +189,13 @@ pub mod error;
 /// See [`EventBroadcaster`](events::EventBroadcaster) for details on the event system.
 pub mod events;
 
+/// Type-ahead search over DAG nodes.
+///
+/// This module provides the options and hit types for [`DagView::search`],
+/// which looks nodes up by id, label, author or metadata through an index
+/// maintained as nodes are added.
+pub mod search;
+
 /// HTTP server configuration and initialization.
 ///
 /// This module provides the main [`VizServer`] struct that configures and
@@ -164,10 +204,14 @@ pub mod events;
 /// See [`VizServer`] for the main server interface.
 pub mod server;
 
+pub use access::{AccessPolicy, VizAuth};
 pub use api::ApiState;
+pub use attach::{AttachOptions, Attachment, DagChanges, GraphFeed, GraphMapping, NodeFeed};
 pub use dag::{DagEdge, DagNode, DagNodeBuilder, DagStats, DagView, EdgeType, NodeType};
 pub use error::{Error, Result};
 pub use events::{DagEvent, EventBroadcaster};
+pub use export::{DotOptions, ExportFormat};
+pub use search::{SearchField, SearchHit, SearchMode, SearchOptions};
 pub use server::{VizConfig, VizServer};
 
 /// Version information from Cargo.toml.
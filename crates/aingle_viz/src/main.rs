@@ -6,7 +6,7 @@
 //! A standalone web server for visualizing AIngle DAG structures.
 
 use aingle_viz::dag::{DagEdge, DagNodeBuilder, EdgeType, NodeType};
-use aingle_viz::{DagView, Result, VizConfig, VizServer};
+use aingle_viz::{DagView, Error, Result, VizAuth, VizConfig, VizServer};
 use clap::Parser;
 
 /// AIngle DAG Visualization Server
@@ -33,6 +33,22 @@ struct Args {
     #[arg(long, default_value_t = true)]
     cors: bool,
 
+    /// Require this bearer token on API and WebSocket routes
+    #[arg(long, env = "AINGLE_VIZ_TOKEN", conflicts_with = "basic_auth")]
+    token: Option<String>,
+
+    /// Require basic-auth credentials (`user:password`) on API and WebSocket routes
+    #[arg(long, env = "AINGLE_VIZ_BASIC_AUTH")]
+    basic_auth: Option<String>,
+
+    /// Allow mutating endpoints and disable metadata redaction
+    #[arg(long)]
+    read_write: bool,
+
+    /// Metadata keys to strip from responses in read-only mode (comma separated)
+    #[arg(long, value_delimiter = ',')]
+    redact: Option<Vec<String>>,
+
     /// Generate demo data
     #[arg(long)]
     demo: bool,
@@ -54,12 +70,30 @@ async fn main() -> Result<()> {
     };
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(log_level)).init();
 
+    let auth = match (args.token, args.basic_auth) {
+        (Some(token), _) => Some(VizAuth::Bearer(token)),
+        (None, Some(credentials)) => {
+            let (username, password) = credentials
+                .split_once(':')
+                .ok_or_else(|| Error::Config("--basic-auth expects `user:password`".to_string()))?;
+            Some(VizAuth::Basic {
+                username: username.to_string(),
+                password: password.to_string(),
+            })
+        }
+        (None, None) => None,
+    };
+
     // Create config
+    let defaults = VizConfig::default();
     let config = VizConfig {
         host: args.host,
         port: args.port,
         enable_cors: args.cors,
         enable_tracing: args.verbose > 0,
+        auth,
+        read_only: !args.read_write,
+        redacted_metadata_keys: args.redact.unwrap_or(defaults.redacted_metadata_keys),
     };
 
     // Create server
@@ -0,0 +1,524 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Type-ahead search over the nodes of a [`DagView`].
+//!
+//! [`DagView::search`] matches a query case-insensitively against node ids,
+//! labels, authors and a configurable set of metadata keys. Two modes are
+//! supported (see [`SearchMode`]):
+//!
+//! - **Prefix**: the query matches the start of any word in a field, so `ali`
+//!   finds `"Agent: alice"`.
+//! - **Substring**: the query matches anywhere in a field.
+//!
+//! Lookups go through an index that [`DagView::add_node`] maintains as nodes
+//! arrive, so a search costs the number of matches rather than a scan of the
+//! whole DAG. Nodes pushed to [`DagView::nodes`] directly, or loaded by
+//! deserializing a view, are still found by a scan until
+//! [`DagView::rebuild_search_index`] is called.
+//!
+//! # Examples
+//!
+//! ```
+//! use aingle_viz::{DagView, DagNodeBuilder, NodeType};
+//! use aingle_viz::search::{SearchField, SearchOptions};
+//!
+//! let mut dag = DagView::new();
+//! dag.add_node(DagNodeBuilder::new("e1", NodeType::Entry).label("Sensor reading").build());
+//! dag.add_node(DagNodeBuilder::new("e2", NodeType::Entry).label("Sensor config").build());
+//!
+//! let hits = dag.search("read", &SearchOptions::default());
+//! assert_eq!(hits.len(), 1);
+//! assert_eq!(hits[0].id, "e1");
+//! assert_eq!(hits[0].matched_field, SearchField::Label);
+//! ```
+
+use crate::dag::{DagNode, DagView, NodeType};
+use crate::error::Error;
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Bound;
+use std::str::FromStr;
+
+/// Default number of hits returned by a search.
+pub const DEFAULT_SEARCH_LIMIT: usize = 20;
+
+/// How a query is matched against field values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchMode {
+    /// The query matches the start of a word.
+    #[default]
+    Prefix,
+    /// The query matches anywhere in the value.
+    Substring,
+}
+
+impl FromStr for SearchMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "prefix" => Ok(SearchMode::Prefix),
+            "substring" | "contains" => Ok(SearchMode::Substring),
+            other => Err(Error::Config(format!("Unknown search mode: {}", other))),
+        }
+    }
+}
+
+/// Options for [`DagView::search`].
+#[derive(Debug, Clone)]
+pub struct SearchOptions {
+    /// How the query is matched.
+    ///
+    /// Default is [`SearchMode::Prefix`].
+    pub mode: SearchMode,
+
+    /// Maximum number of hits returned.
+    ///
+    /// Default is [`DEFAULT_SEARCH_LIMIT`].
+    pub limit: usize,
+
+    /// Metadata keys searched in addition to id, label and author.
+    ///
+    /// Default is none.
+    pub metadata_keys: Vec<String>,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            mode: SearchMode::default(),
+            limit: DEFAULT_SEARCH_LIMIT,
+            metadata_keys: Vec::new(),
+        }
+    }
+}
+
+/// The node field a search hit matched in.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchField {
+    /// The node id.
+    Id,
+    /// The node label.
+    Label,
+    /// The node author.
+    Author,
+    /// The metadata value under the given key.
+    Metadata(String),
+}
+
+impl SearchField {
+    /// Hits in earlier fields are listed first.
+    fn rank(&self) -> u8 {
+        match self {
+            SearchField::Id => 0,
+            SearchField::Label => 1,
+            SearchField::Author => 2,
+            SearchField::Metadata(_) => 3,
+        }
+    }
+}
+
+/// A node matched by [`DagView::search`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchHit {
+    /// The id of the matched node.
+    pub id: String,
+    /// The type of the matched node.
+    pub node_type: NodeType,
+    /// The timestamp of the matched node.
+    pub timestamp: i64,
+    /// The label of the matched node.
+    pub label: String,
+    /// The field the query matched in.
+    pub matched_field: SearchField,
+    /// The value of the matched field.
+    pub matched_value: String,
+}
+
+/// A field occurrence in the index.
+#[derive(Debug, Clone)]
+struct Posting {
+    node: usize,
+    field: SearchField,
+}
+
+/// Incrementally maintained index over the searchable fields of a [`DagView`].
+///
+/// `words` is keyed by every word-start suffix of a lowercased field value and
+/// answers prefix queries with a range scan. `values` is keyed by the distinct
+/// lowercased values; substring queries scan its keys, which repeat far less
+/// than nodes do (authors and metadata values are shared).
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SearchIndex {
+    words: BTreeMap<String, Vec<Posting>>,
+    values: HashMap<String, Vec<Posting>>,
+    /// Number of leading nodes of the view covered by the index.
+    indexed: usize,
+    /// Id of the last covered node, to detect a replaced node list.
+    last_id: Option<String>,
+}
+
+impl SearchIndex {
+    /// Indexes `node`, stored at position `position` of the view.
+    ///
+    /// Does nothing if the index is behind the view; the gap is scanned
+    /// until the index is rebuilt.
+    pub(crate) fn insert(&mut self, position: usize, node: &DagNode) {
+        if position != self.indexed {
+            return;
+        }
+        for (field, value) in indexed_fields(node) {
+            let value = value.to_lowercase();
+            for start in word_starts(&value) {
+                self.words
+                    .entry(value[start..].to_string())
+                    .or_default()
+                    .push(Posting {
+                        node: position,
+                        field: field.clone(),
+                    });
+            }
+            self.values.entry(value).or_default().push(Posting {
+                node: position,
+                field,
+            });
+        }
+        self.indexed += 1;
+        self.last_id = Some(node.id.clone());
+    }
+
+    /// Number of leading nodes of `nodes` the index can answer for.
+    fn coverage(&self, nodes: &[DagNode]) -> usize {
+        let in_sync = match self.indexed.checked_sub(1) {
+            Some(last) => nodes.get(last).map(|n| &n.id) == self.last_id.as_ref(),
+            None => true,
+        };
+        if in_sync {
+            self.indexed
+        } else {
+            0
+        }
+    }
+
+    fn postings<'a>(
+        &'a self,
+        query: &'a str,
+        mode: SearchMode,
+    ) -> Box<dyn Iterator<Item = &'a Posting> + 'a> {
+        match mode {
+            SearchMode::Prefix => Box::new(
+                self.words
+                    .range::<str, _>((Bound::Included(query), Bound::Unbounded))
+                    .take_while(move |(term, _)| term.starts_with(query))
+                    .flat_map(|(_, postings)| postings),
+            ),
+            SearchMode::Substring => Box::new(
+                self.values
+                    .iter()
+                    .filter(move |(value, _)| value.contains(query))
+                    .flat_map(|(_, postings)| postings),
+            ),
+        }
+    }
+}
+
+impl DagView {
+    /// Searches node ids, labels, authors and the metadata keys in `options`.
+    ///
+    /// Matching is case-insensitive. Each node is returned at most once, for
+    /// the first field it matched in (id, label, author, then metadata). Hits
+    /// are ordered by that field and then newest first, and capped at
+    /// [`SearchOptions::limit`]. An empty query returns no hits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aingle_viz::{DagView, DagNodeBuilder, NodeType};
+    /// use aingle_viz::search::{SearchMode, SearchOptions};
+    ///
+    /// let mut dag = DagView::new();
+    /// dag.add_node(DagNodeBuilder::new("e1", NodeType::Entry)
+    ///     .label("Entry")
+    ///     .metadata("entry_type", serde_json::json!("temperature"))
+    ///     .build());
+    ///
+    /// let options = SearchOptions {
+    ///     mode: SearchMode::Substring,
+    ///     metadata_keys: vec!["entry_type".to_string()],
+    ///     ..SearchOptions::default()
+    /// };
+    /// let hits = dag.search("erat", &options);
+    /// assert_eq!(hits[0].matched_value, "temperature");
+    ///
+    /// // Metadata is only searched under the requested keys
+    /// assert!(dag.search("erat", &SearchOptions { mode: SearchMode::Substring, ..SearchOptions::default() }).is_empty());
+    /// ```
+    pub fn search(&self, query: &str, options: &SearchOptions) -> Vec<SearchHit> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() || options.limit == 0 {
+            return Vec::new();
+        }
+
+        // Postings can be stale if `nodes` was edited directly; every
+        // candidate is checked against the node itself
+        let index = &self.search_index;
+        let covered = index.coverage(&self.nodes);
+
+        let mut best: HashMap<usize, SearchField> = HashMap::new();
+        let mut consider = |node: usize, field: &SearchField| {
+            if best.get(&node).is_some_and(|f| f.rank() <= field.rank()) {
+                return;
+            }
+            if let SearchField::Metadata(key) = field {
+                if !options.metadata_keys.contains(key) {
+                    return;
+                }
+            }
+            let matches = self
+                .nodes
+                .get(node)
+                .and_then(|n| field_value(n, field))
+                .is_some_and(|value| matches(&value.to_lowercase(), &query, options.mode));
+            if matches {
+                best.insert(node, field.clone());
+            }
+        };
+
+        if covered > 0 {
+            for posting in index.postings(&query, options.mode) {
+                if posting.node < covered {
+                    consider(posting.node, &posting.field);
+                }
+            }
+        }
+        for (position, node) in self.nodes.iter().enumerate().skip(covered) {
+            for (field, _) in indexed_fields(node) {
+                consider(position, &field);
+            }
+        }
+
+        let mut hits: Vec<(usize, SearchField)> = best.into_iter().collect();
+        hits.sort_by(|(a, fa), (b, fb)| {
+            let (na, nb) = (&self.nodes[*a], &self.nodes[*b]);
+            fa.rank()
+                .cmp(&fb.rank())
+                .then(nb.timestamp.cmp(&na.timestamp))
+                .then(na.id.cmp(&nb.id))
+        });
+        hits.truncate(options.limit);
+
+        hits.into_iter()
+            .map(|(position, field)| {
+                let node = &self.nodes[position];
+                SearchHit {
+                    id: node.id.clone(),
+                    node_type: node.node_type,
+                    timestamp: node.timestamp,
+                    label: node.label.clone(),
+                    matched_value: field_value(node, &field).unwrap_or_default(),
+                    matched_field: field,
+                }
+            })
+            .collect()
+    }
+
+    /// Rebuilds the search index from [`nodes`](Self::nodes).
+    ///
+    /// Only needed after modifying `nodes` directly or deserializing a view;
+    /// [`add_node`](Self::add_node) keeps the index up to date.
+    pub fn rebuild_search_index(&mut self) {
+        let mut index = SearchIndex::default();
+        for (position, node) in self.nodes.iter().enumerate() {
+            index.insert(position, node);
+        }
+        self.search_index = index;
+    }
+}
+
+/// The searchable fields of `node` with their values.
+fn indexed_fields(node: &DagNode) -> Vec<(SearchField, String)> {
+    let mut fields = vec![
+        (SearchField::Id, node.id.clone()),
+        (SearchField::Label, node.label.clone()),
+    ];
+    if let Some(author) = &node.author {
+        fields.push((SearchField::Author, author.clone()));
+    }
+    for key in node.metadata.keys() {
+        let field = SearchField::Metadata(key.clone());
+        if let Some(value) = field_value(node, &field) {
+            fields.push((field, value));
+        }
+    }
+    fields
+}
+
+/// The text of `field` in `node`, if it has one.
+///
+/// Metadata strings, numbers and booleans are searchable; other JSON values
+/// are not.
+fn field_value(node: &DagNode, field: &SearchField) -> Option<String> {
+    match field {
+        SearchField::Id => Some(node.id.clone()),
+        SearchField::Label => Some(node.label.clone()),
+        SearchField::Author => node.author.clone(),
+        SearchField::Metadata(key) => match node.metadata.get(key)? {
+            serde_json::Value::String(s) => Some(s.clone()),
+            v @ (serde_json::Value::Number(_) | serde_json::Value::Bool(_)) => Some(v.to_string()),
+            _ => None,
+        },
+    }
+}
+
+/// Byte offsets in `value` where a word starts.
+fn word_starts(value: &str) -> Vec<usize> {
+    let mut starts = Vec::new();
+    let mut previous_alphanumeric = false;
+    for (offset, c) in value.char_indices() {
+        if c.is_alphanumeric() && (offset == 0 || !previous_alphanumeric) {
+            starts.push(offset);
+        }
+        previous_alphanumeric = c.is_alphanumeric();
+    }
+    starts
+}
+
+/// Whether the lowercased `value` matches `query` in `mode`.
+fn matches(value: &str, query: &str, mode: SearchMode) -> bool {
+    match mode {
+        SearchMode::Prefix => word_starts(value)
+            .into_iter()
+            .any(|start| value[start..].starts_with(query)),
+        SearchMode::Substring => value.contains(query),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dag::DagNodeBuilder;
+
+    fn entry(id: &str, label: &str, author: &str, timestamp: i64) -> DagNode {
+        DagNodeBuilder::new(id, NodeType::Entry)
+            .label(label)
+            .author(author)
+            .timestamp(timestamp)
+            .build()
+    }
+
+    fn ids(hits: &[SearchHit]) -> Vec<&str> {
+        hits.iter().map(|h| h.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_prefix_and_substring() {
+        let mut dag = DagView::new();
+        dag.add_node(entry("e1", "Sensor reading", "alice", 1));
+        dag.add_node(entry("e2", "Thread config", "bob", 2));
+
+        let prefix = SearchOptions::default();
+        assert_eq!(ids(&dag.search("READ", &prefix)), ["e1"]);
+        assert_eq!(ids(&dag.search("sensor re", &prefix)), ["e1"]);
+        // "read" appears inside "Thread" but not at a word start
+        assert!(dag.search("ead", &prefix).is_empty());
+
+        let substring = SearchOptions {
+            mode: SearchMode::Substring,
+            ..SearchOptions::default()
+        };
+        assert_eq!(ids(&dag.search("ead", &substring)), ["e2", "e1"]);
+        assert!(dag.search("  ", &substring).is_empty());
+    }
+
+    #[test]
+    fn test_author_search_returns_all_nodes() {
+        let mut dag = DagView::new();
+        for i in 0..30 {
+            let author = if i % 3 == 0 { "alice" } else { "bob" };
+            dag.add_node(entry(&format!("e{}", i), "Entry", author, i));
+        }
+
+        let options = SearchOptions {
+            limit: 100,
+            ..SearchOptions::default()
+        };
+        let hits = dag.search("alice", &options);
+        assert_eq!(hits.len(), 10);
+        assert!(hits.iter().all(|h| h.matched_field == SearchField::Author));
+        assert!(hits.iter().all(|h| h.matched_value == "alice"));
+        // Newest first
+        assert_eq!(hits[0].id, "e27");
+        assert_eq!(hits[9].id, "e0");
+
+        assert_eq!(dag.search("alice", &SearchOptions::default()).len(), 10);
+        assert_eq!(dag.search("b", &SearchOptions::default()).len(), 20);
+    }
+
+    #[test]
+    fn test_index_follows_additions() {
+        let mut dag = DagView::new();
+        let options = SearchOptions {
+            metadata_keys: vec!["entry_type".to_string()],
+            ..SearchOptions::default()
+        };
+        assert!(dag.search("temp", &options).is_empty());
+
+        dag.add_node(
+            DagNodeBuilder::new("e1", NodeType::Entry)
+                .label("Reading")
+                .metadata("entry_type", serde_json::json!("temperature"))
+                .metadata("private", serde_json::json!("temporary"))
+                .build(),
+        );
+        let hits = dag.search("temp", &options);
+        assert_eq!(ids(&hits), ["e1"]);
+        assert_eq!(
+            hits[0].matched_field,
+            SearchField::Metadata("entry_type".to_string())
+        );
+
+        dag.add_node(entry("temp-2", "Other", "carol", 0));
+        // The id match ranks before the metadata match
+        assert_eq!(ids(&dag.search("temp", &options)), ["temp-2", "e1"]);
+        assert_eq!(dag.search_index.indexed, 2);
+    }
+
+    #[test]
+    fn test_unindexed_nodes_are_scanned() {
+        let mut dag = DagView::new();
+        dag.add_node(entry("e1", "Indexed", "alice", 1));
+        dag.nodes.push(entry("e2", "Pushed", "alice", 2));
+        let options = SearchOptions::default();
+        assert_eq!(ids(&dag.search("alice", &options)), ["e2", "e1"]);
+
+        // Adding after a direct push leaves the index behind, not wrong
+        dag.add_node(entry("e3", "Added", "alice", 3));
+        assert_eq!(ids(&dag.search("alice", &options)), ["e3", "e2", "e1"]);
+
+        // A replaced node list never yields hits for nodes that are gone
+        dag.nodes = vec![entry("x", "Fresh", "dave", 0)];
+        assert!(dag.search("alice", &options).is_empty());
+        assert_eq!(ids(&dag.search("dave", &options)), ["x"]);
+
+        let json = serde_json::to_string(&dag).unwrap();
+        let mut restored: DagView = serde_json::from_str(&json).unwrap();
+        assert_eq!(ids(&restored.search("fresh", &options)), ["x"]);
+        restored.rebuild_search_index();
+        assert_eq!(restored.search_index.indexed, 1);
+        assert_eq!(ids(&restored.search("fresh", &options)), ["x"]);
+    }
+
+    #[test]
+    fn test_search_mode_parse() {
+        assert_eq!("Prefix".parse::<SearchMode>().unwrap(), SearchMode::Prefix);
+        assert_eq!(
+            "substring".parse::<SearchMode>().unwrap(),
+            SearchMode::Substring
+        );
+        assert!("fuzzy".parse::<SearchMode>().is_err());
+    }
+}
@@ -37,15 +37,17 @@
 //! ## Server with custom configuration
 //!
 //! ```rust,ignore
+//! use aingle_viz::access::VizAuth;
 //! use aingle_viz::{VizServer, VizConfig};
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     // Reachable from the LAN, so require a token
 //!     let config = VizConfig {
 //!         host: "0.0.0.0".to_string(),
 //!         port: 3000,
-//!         enable_cors: true,
-//!         enable_tracing: true,
+//!         auth: Some(VizAuth::Bearer("s3cret".to_string())),
+//!         ..VizConfig::default()
 //!     };
 //!
 //!     let server = VizServer::new(config);
@@ -75,7 +77,8 @@
 //! }
 //! ```
 
-use crate::api::{create_router, ApiState};
+use crate::access::{AccessPolicy, VizAuth};
+use crate::api::{create_router_with_policy, ApiState};
 use crate::dag::DagView;
 use crate::error::{Error, Result};
 
@@ -110,6 +113,7 @@ use tower_http::trace::TraceLayer;
 ///     port: 3000,
 ///     enable_cors: true,
 ///     enable_tracing: false,
+///     ..VizConfig::default()
 /// };
 /// ```
 ///
@@ -157,6 +161,27 @@ pub struct VizConfig {
     ///
     /// Default is `true`.
     pub enable_tracing: bool,
+
+    /// Credentials required on all `/api` and `/ws` routes.
+    ///
+    /// Set this whenever the server is reachable from other machines.
+    ///
+    /// Default is `None` (no authentication).
+    pub auth: Option<VizAuth>,
+
+    /// Whether the explorer is read-only.
+    ///
+    /// In read-only mode mutating endpoints (such as `POST /api/node`) are
+    /// refused and [`redacted_metadata_keys`](Self::redacted_metadata_keys)
+    /// are stripped from API responses and WebSocket events.
+    ///
+    /// Default is `true`.
+    pub read_only: bool,
+
+    /// Node metadata keys stripped from responses in read-only mode.
+    ///
+    /// Default is [`DEFAULT_REDACTED_METADATA_KEYS`](crate::access::DEFAULT_REDACTED_METADATA_KEYS).
+    pub redacted_metadata_keys: Vec<String>,
 }
 
 impl Default for VizConfig {
@@ -167,6 +192,9 @@ impl Default for VizConfig {
             port: 8888,
             enable_cors: true,
             enable_tracing: true,
+            auth: None,
+            read_only: true,
+            redacted_metadata_keys: AccessPolicy::default().redacted_metadata_keys,
         }
     }
 }
@@ -193,6 +221,9 @@ impl VizConfig {
             port: 8888,
             enable_cors: true,
             enable_tracing: true,
+            auth: None,
+            read_only: true,
+            redacted_metadata_keys: AccessPolicy::default().redacted_metadata_keys,
         }
     }
 
@@ -217,6 +248,9 @@ impl VizConfig {
             port: 8888,
             enable_cors: false,
             enable_tracing: false,
+            auth: None,
+            read_only: true,
+            redacted_metadata_keys: AccessPolicy::default().redacted_metadata_keys,
         }
     }
 
@@ -245,6 +279,25 @@ impl VizConfig {
             .parse()
             .map_err(|e| Error::Config(format!("Invalid address: {}", e)))
     }
+
+    /// Returns the [`AccessPolicy`] the server applies to its API routes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aingle_viz::VizConfig;
+    ///
+    /// let policy = VizConfig::default().access_policy();
+    /// assert!(policy.auth.is_none());
+    /// assert!(policy.read_only);
+    /// ```
+    pub fn access_policy(&self) -> AccessPolicy {
+        AccessPolicy {
+            auth: self.auth.clone(),
+            read_only: self.read_only,
+            redacted_metadata_keys: self.redacted_metadata_keys.clone(),
+        }
+    }
 }
 
 /// The main visualization server.
@@ -437,8 +490,16 @@ impl VizServer {
     pub async fn start(self) -> Result<()> {
         let addr = self.config.socket_addr()?;
 
+        if self.config.auth.is_none() && !addr.ip().is_loopback() {
+            log::warn!(
+                "Serving the explorer on {} without authentication; set VizConfig::auth",
+                addr
+            );
+        }
+
         // Create router
-        let mut app = create_router(self.state);
+        let policy = self.config.access_policy();
+        let mut app = create_router_with_policy(self.state, policy);
 
         // Add middleware
         if self.config.enable_cors {
@@ -527,8 +588,16 @@ impl VizServer {
     {
         let addr = self.config.socket_addr()?;
 
+        if self.config.auth.is_none() && !addr.ip().is_loopback() {
+            log::warn!(
+                "Serving the explorer on {} without authentication; set VizConfig::auth",
+                addr
+            );
+        }
+
         // Create router
-        let mut app = create_router(self.state);
+        let policy = self.config.access_policy();
+        let mut app = create_router_with_policy(self.state, policy);
 
         // Add middleware
         if self.config.enable_cors {
@@ -622,6 +691,7 @@ mod tests {
             port: 3000,
             enable_cors: false,
             enable_tracing: true,
+            ..VizConfig::default()
         };
 
         let cloned = config.clone();
@@ -638,6 +708,7 @@ mod tests {
             port: 9000,
             enable_cors: true,
             enable_tracing: true,
+            ..VizConfig::default()
         };
 
         let addr = config.socket_addr().unwrap();
@@ -652,6 +723,7 @@ mod tests {
             port: 8888,
             enable_cors: true,
             enable_tracing: true,
+            ..VizConfig::default()
         };
 
         let result = config.socket_addr();
@@ -718,6 +790,7 @@ mod tests {
             port: 12345,
             enable_cors: true,
             enable_tracing: false,
+            ..VizConfig::default()
         };
 
         let addr = config.socket_addr().unwrap();
@@ -749,6 +822,7 @@ mod tests {
             port: 8888,
             enable_cors: false,
             enable_tracing: true,
+            ..VizConfig::default()
         };
 
         assert!(!config.enable_cors);
@@ -761,6 +835,7 @@ mod tests {
             port: 8888,
             enable_cors: true,
             enable_tracing: false,
+            ..VizConfig::default()
         };
 
         assert!(!config.enable_tracing);
@@ -0,0 +1,109 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Memory behaviour of streaming DAG exports
+//!
+//! Exports a 50k-node DAG through `ExportChunks` and checks, with a counting
+//! global allocator, that the live heap never grows by more than a small
+//! fraction of the document size: the export must not be buffered whole.
+//!
+//! This file holds a single test so no other test allocates concurrently.
+
+use aingle_viz::export::{ExportChunks, ExportFormat};
+use aingle_viz::{DagEdge, DagNodeBuilder, DagView, EdgeType, NodeType};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct CountingAllocator;
+
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let live = LIVE.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+            PEAK.fetch_max(live, Ordering::SeqCst);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE.fetch_sub(layout.size(), Ordering::SeqCst);
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Discards output, counting the bytes written.
+#[derive(Default)]
+struct CountingSink {
+    written: usize,
+}
+
+impl Write for CountingSink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.written += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn large_dag(nodes: usize) -> DagView {
+    let mut dag = DagView::new();
+    for i in 0..nodes {
+        dag.add_node(
+            DagNodeBuilder::new(format!("entry_{:06}", i), NodeType::Entry)
+                .label(format!("Entry number {} with a reasonably long label", i))
+                .author("agent_alice")
+                .timestamp(1_700_000_000 + i as i64)
+                .metadata("index", serde_json::json!(i))
+                .metadata("entry_type", serde_json::json!("post"))
+                .build(),
+        );
+        if i > 0 {
+            dag.add_edge(DagEdge {
+                source: format!("entry_{:06}", i),
+                target: format!("entry_{:06}", i - 1),
+                edge_type: EdgeType::PrevAction,
+                label: None,
+            });
+        }
+    }
+    dag
+}
+
+#[test]
+fn test_streaming_export_of_50k_nodes_stays_bounded() {
+    let dag = large_dag(50_000);
+
+    for format in [ExportFormat::Dot, ExportFormat::GraphMl] {
+        let mut sink = CountingSink::default();
+        let baseline = LIVE.load(Ordering::SeqCst);
+        PEAK.store(baseline, Ordering::SeqCst);
+
+        ExportChunks::new(&dag, format).write_to(&mut sink).unwrap();
+
+        let growth = PEAK.load(Ordering::SeqCst) - baseline;
+        assert!(
+            sink.written > 5_000_000,
+            "{:?} export unexpectedly small: {} bytes",
+            format,
+            sink.written
+        );
+        assert!(
+            growth * 20 < sink.written,
+            "{:?} export held {} bytes live for a {} byte document",
+            format,
+            growth,
+            sink.written
+        );
+    }
+}
@@ -14,13 +14,12 @@ use aingle_zk::{
     aggregation::{aggregate_proofs, ProofAggregator},
     batch::{verify_schnorr_batch, BatchVerifier},
     commitment::{HashCommitment, PedersenCommitment},
+    generators::pedersen_h,
     merkle::{MerkleTree, SparseMerkleTree},
     proof::{EqualityProof, SchnorrProof, ZkProof},
 };
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
-use curve25519_dalek::{
-    constants::RISTRETTO_BASEPOINT_POINT, ristretto::RistrettoPoint, scalar::Scalar,
-};
+use curve25519_dalek::{constants::RISTRETTO_BASEPOINT_POINT, scalar::Scalar};
 use rand::rngs::OsRng;
 
 #[cfg(feature = "bulletproofs")]
@@ -112,7 +111,7 @@ fn benchmark_equality_proofs(c: &mut Criterion) {
     let r2 = Scalar::random(&mut OsRng);
 
     let g = RISTRETTO_BASEPOINT_POINT;
-    let h = generator_h();
+    let h = pedersen_h();
     let v = Scalar::from(value);
 
     let c1 = g * v + h * r1;
@@ -314,7 +313,7 @@ fn benchmark_mixed_batch(c: &mut Criterion) {
         }
 
         // Add equality proofs
-        let h = generator_h();
+        let h = pedersen_h();
         for i in 0..equality_count {
             let value = 100u64 + i as u64;
             let r1 = Scalar::random(&mut OsRng);
@@ -468,15 +467,6 @@ fn benchmark_memory(c: &mut Criterion) {
     group.finish();
 }
 
-// Helper function for equality proofs
-fn generator_h() -> RistrettoPoint {
-    use sha2::{Digest, Sha512};
-    let mut hasher = Sha512::new();
-    hasher.update(RISTRETTO_BASEPOINT_POINT.compress().as_bytes());
-    hasher.update(b"aingle_zk_pedersen_h");
-    RistrettoPoint::from_uniform_bytes(&hasher.finalize().into())
-}
-
 criterion_group!(commitments, benchmark_commitments,);
 
 criterion_group!(
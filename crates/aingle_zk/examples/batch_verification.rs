@@ -8,24 +8,14 @@
 
 use aingle_zk::{
     batch::{verify_schnorr_batch, BatchVerifier},
+    generators::pedersen_h,
     merkle::SparseMerkleTree,
     proof::{EqualityProof, SchnorrProof},
 };
-use curve25519_dalek::{
-    constants::RISTRETTO_BASEPOINT_POINT, ristretto::RistrettoPoint, scalar::Scalar,
-};
+use curve25519_dalek::{constants::RISTRETTO_BASEPOINT_POINT, scalar::Scalar};
 use rand::rngs::OsRng;
-use sha2::{Digest, Sha512};
 use std::time::Instant;
 
-/// Helper function to get second generator H
-fn generator_h() -> RistrettoPoint {
-    let mut hasher = Sha512::new();
-    hasher.update(RISTRETTO_BASEPOINT_POINT.compress().as_bytes());
-    hasher.update(b"aingle_zk_pedersen_h");
-    RistrettoPoint::from_uniform_bytes(&hasher.finalize().into())
-}
-
 fn main() {
     println!("=== AIngle ZK Batch Verification Example ===\n");
 
@@ -86,7 +76,7 @@ fn main() {
 
     // Add Equality proofs
     println!("   Adding 30 Equality proofs...");
-    let h = generator_h();
+    let h = pedersen_h();
     for i in 0..30 {
         let value = 1000u64 + i;
         let r1 = Scalar::random(&mut OsRng);
@@ -39,6 +39,8 @@
 //! assert_eq!(result.total_count, 10);
 //! ```
 
+use crate::encoding;
+use crate::envelope::{ZkEncode, ZkKind};
 use crate::error::{Result, ZkError};
 use crate::proof::{ProofType, ZkProof};
 use rayon::prelude::*;
@@ -370,6 +372,36 @@ pub fn verify_proofs(proofs: &[ZkProof]) -> AggregationResult {
     }
 }
 
+impl ZkEncode for AggregatedProof {
+    const KIND: ZkKind = ZkKind::AggregatedProof;
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        encoding::put_usize(out, self.proofs.len());
+        for proof in &self.proofs {
+            proof.encode(out);
+        }
+        encoding::put_usize(out, self.metadata.count);
+        encoding::put_usize(out, self.metadata.individual_size);
+        encoding::put_usize(out, self.metadata.aggregated_size);
+        encoding::put_u64(out, self.metadata.timestamp);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self> {
+        // Smallest proof: type, data tag, hash opening, timestamp, no metadata
+        let count = encoding::get_len(input, 75)?;
+        let proofs = (0..count)
+            .map(|_| ZkProof::decode(input))
+            .collect::<Result<_>>()?;
+        let metadata = AggregationMetadata {
+            count: encoding::get_usize(input)?,
+            individual_size: encoding::get_usize(input)?,
+            aggregated_size: encoding::get_usize(input)?,
+            timestamp: encoding::get_u64(input)?,
+        };
+        Ok(Self { proofs, metadata })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
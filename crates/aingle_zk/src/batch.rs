@@ -49,19 +49,9 @@ use curve25519_dalek::{
 };
 use rand::{rngs::OsRng, Rng};
 use rayon::prelude::*;
-#[cfg(test)]
-use sha2::Sha512;
 use sha2::{Digest, Sha256};
 use std::time::Instant;
-
-/// Helper function to get second generator H (same as in commitment.rs and proof.rs)
-#[cfg(test)]
-fn generator_h() -> RistrettoPoint {
-    let mut hasher = Sha512::new();
-    hasher.update(RISTRETTO_BASEPOINT_POINT.compress().as_bytes());
-    hasher.update(b"aingle_zk_pedersen_h");
-    RistrettoPoint::from_uniform_bytes(&hasher.finalize().into())
-}
+use subtle::ConstantTimeEq;
 
 /// Batch verifier for zero-knowledge proofs
 ///
@@ -190,7 +180,7 @@ impl BatchVerifier {
             hasher.update(message);
             let expected_challenge: [u8; 32] = hasher.finalize().into();
 
-            if expected_challenge != proof.challenge {
+            if !bool::from(expected_challenge.ct_eq(&proof.challenge)) {
                 // Challenge mismatch - this proof is invalid
                 // Return individual results for all proofs
                 return self
@@ -404,6 +394,7 @@ pub fn verify_merkle_batch(proofs: &[SparseMerkleProof]) -> Vec<bool> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generators::pedersen_h;
     use crate::merkle::SparseMerkleTree;
     use curve25519_dalek::scalar::Scalar;
 
@@ -528,7 +519,7 @@ mod tests {
         }
 
         // Add equality proofs
-        let h = generator_h();
+        let h = pedersen_h();
         for i in 0..10 {
             let value = 42u64 + i;
             let r1 = Scalar::random(&mut OsRng);
@@ -684,7 +675,7 @@ mod tests {
     #[test]
     fn test_equality_batch() {
         let mut verifier = BatchVerifier::new();
-        let h = generator_h();
+        let h = pedersen_h();
 
         // Add 10 equality proofs
         for i in 0..10 {
@@ -710,7 +701,7 @@ mod tests {
 
     #[test]
     fn test_verify_equality_batch_convenience() {
-        let h = generator_h();
+        let h = pedersen_h();
         let mut equality_proofs = Vec::new();
 
         for i in 0..10 {
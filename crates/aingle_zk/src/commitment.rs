@@ -13,14 +13,23 @@ use curve25519_dalek::{
 };
 use rand::rngs::OsRng;
 use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256, Sha512};
+use sha2::{Digest, Sha256};
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
+use crate::encoding;
+use crate::envelope::{ZkEncode, ZkKind};
 use crate::error::{Result, ZkError};
+use crate::generators;
 
 /// Opening information for a Pedersen commitment
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// The blinding factor is wiped on drop, compared in constant time and
+/// never printed by `Debug`.
+#[derive(Clone, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
 pub struct CommitmentOpening {
     /// The blinding factor (randomness)
+    #[serde(with = "encoding::serde_scalar")]
     pub blinding: [u8; 32],
 }
 
@@ -31,11 +40,36 @@ impl CommitmentOpening {
     }
 
     /// Get as scalar
+    ///
+    /// The caller owns the returned secret; wrap it in [`Zeroizing`] to
+    /// have it wiped.
     pub fn to_scalar(&self) -> Scalar {
         Scalar::from_bytes_mod_order(self.blinding)
     }
 }
 
+impl ConstantTimeEq for CommitmentOpening {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.blinding.ct_eq(&other.blinding)
+    }
+}
+
+impl PartialEq for CommitmentOpening {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for CommitmentOpening {}
+
+impl std::fmt::Debug for CommitmentOpening {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CommitmentOpening")
+            .field("blinding", &"<redacted>")
+            .finish()
+    }
+}
+
 /// Pedersen commitment
 ///
 /// A Pedersen commitment to value `v` with blinding factor `r` is:
@@ -49,31 +83,24 @@ impl CommitmentOpening {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct PedersenCommitment {
     /// Compressed point representation
+    #[serde(with = "encoding::serde_point")]
     pub point: [u8; 32],
 }
 
 impl PedersenCommitment {
-    /// Second generator H (derived from hashing G)
-    fn generator_h() -> RistrettoPoint {
-        let mut hasher = Sha512::new();
-        hasher.update(RISTRETTO_BASEPOINT_POINT.compress().as_bytes());
-        hasher.update(b"aingle_zk_pedersen_h");
-        RistrettoPoint::from_uniform_bytes(&hasher.finalize().into())
-    }
-
     /// Create a commitment to a value
     ///
     /// Returns the commitment and opening (blinding factor)
     pub fn commit(value: u64) -> (Self, CommitmentOpening) {
         let mut rng = OsRng;
-        let blinding = Scalar::random(&mut rng);
+        let blinding = Zeroizing::new(Scalar::random(&mut rng));
         let value_scalar = Scalar::from(value);
 
         let g = RISTRETTO_BASEPOINT_POINT;
-        let h = Self::generator_h();
+        let h = generators::pedersen_h();
 
         // C = v*G + r*H
-        let commitment_point = g * value_scalar + h * blinding;
+        let commitment_point = g * value_scalar + h * *blinding;
         let compressed = commitment_point.compress();
 
         let opening = CommitmentOpening {
@@ -92,7 +119,7 @@ impl PedersenCommitment {
     pub fn commit_with_blinding(value: u64, blinding: &Scalar) -> Self {
         let value_scalar = Scalar::from(value);
         let g = RISTRETTO_BASEPOINT_POINT;
-        let h = Self::generator_h();
+        let h = generators::pedersen_h();
 
         let commitment_point = g * value_scalar + h * blinding;
         let compressed = commitment_point.compress();
@@ -104,8 +131,7 @@ impl PedersenCommitment {
 
     /// Verify that this commitment opens to the given value (constant-time)
     pub fn verify(&self, value: u64, opening: &CommitmentOpening) -> bool {
-        use subtle::ConstantTimeEq;
-        let blinding = opening.to_scalar();
+        let blinding = Zeroizing::new(opening.to_scalar());
         let expected = Self::commit_with_blinding(value, &blinding);
         bool::from(self.point.ct_eq(&expected.point))
     }
@@ -198,7 +224,6 @@ impl HashCommitment {
 
     /// Verify that this commitment opens to the given data (constant-time)
     pub fn verify(&self, data: &[u8]) -> bool {
-        use subtle::ConstantTimeEq;
         let mut hasher = Sha256::new();
         hasher.update(self.salt);
         hasher.update(data);
@@ -245,6 +270,75 @@ impl BlindedValue {
     }
 }
 
+impl ZkEncode for CommitmentOpening {
+    const KIND: ZkKind = ZkKind::CommitmentOpening;
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        encoding::put_array(out, &self.blinding);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self> {
+        Ok(Self {
+            blinding: encoding::get_scalar(input)?,
+        })
+    }
+}
+
+impl ZkEncode for PedersenCommitment {
+    const KIND: ZkKind = ZkKind::PedersenCommitment;
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        encoding::put_array(out, &self.point);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self> {
+        Ok(Self {
+            point: encoding::get_point(input)?,
+        })
+    }
+}
+
+impl ZkEncode for HashCommitment {
+    const KIND: ZkKind = ZkKind::HashCommitment;
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        encoding::put_array(out, &self.hash);
+        encoding::put_array(out, &self.salt);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self> {
+        Ok(Self {
+            hash: encoding::get_array(input)?,
+            salt: encoding::get_array(input)?,
+        })
+    }
+}
+
+impl ZkEncode for BlindedValue {
+    const KIND: ZkKind = ZkKind::BlindedValue;
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.commitment.encode(out);
+        encoding::put_bool(out, self.encrypted_value.is_some());
+        if let Some(encrypted) = &self.encrypted_value {
+            encoding::put_bytes(out, encrypted);
+        }
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self> {
+        let commitment = PedersenCommitment::decode(input)?;
+        let encrypted_value = if encoding::get_bool(input)? {
+            Some(encoding::get_bytes(input)?)
+        } else {
+            None
+        };
+        Ok(Self {
+            commitment,
+            encrypted_value,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -325,4 +419,37 @@ mod tests {
         assert_eq!(commitment.point, deserialized.point);
         assert!(deserialized.verify(42u64, &opening));
     }
+
+    #[test]
+    fn test_opening_is_zeroized_on_drop() {
+        fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+        assert_zeroize_on_drop::<CommitmentOpening>();
+
+        let (_, mut opening) = PedersenCommitment::commit(7u64);
+        opening.zeroize();
+        assert_eq!(opening.blinding, [0u8; 32]);
+    }
+
+    #[test]
+    fn test_opening_debug_is_redacted() {
+        let (_, opening) = PedersenCommitment::commit(7u64);
+        let debug = format!("{:?}", opening);
+
+        assert_eq!(debug, "CommitmentOpening { blinding: \"<redacted>\" }");
+        assert!(!debug.contains(&hex::encode(opening.blinding)));
+    }
+
+    #[test]
+    fn test_opening_equality_is_constant_time() {
+        fn assert_ct_eq<T: ConstantTimeEq + Eq>() {}
+        assert_ct_eq::<CommitmentOpening>();
+
+        let (_, a) = PedersenCommitment::commit(7u64);
+        let (_, b) = PedersenCommitment::commit(7u64);
+
+        assert!(bool::from(a.ct_eq(&a.clone())));
+        assert_eq!(a, a.clone());
+        assert!(!bool::from(a.ct_eq(&b)));
+        assert_ne!(a, b);
+    }
 }
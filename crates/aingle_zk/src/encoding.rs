@@ -0,0 +1,246 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Canonical encodings for group elements and scalars
+//!
+//! Group elements are 32-byte compressed Ristretto points and scalars are
+//! 32-byte little-endian integers below the group order. Decoding checks
+//! both, so malformed input is reported as an error instead of surfacing
+//! later as a failed or panicking verification.
+//!
+//! Used by the serde implementations (through `#[serde(with = ...)]`) and
+//! by the binary payloads of [`ZkEnvelope`](crate::envelope::ZkEnvelope).
+
+use curve25519_dalek::{
+    ristretto::{CompressedRistretto, RistrettoPoint},
+    scalar::Scalar,
+};
+
+use crate::error::{Result, ZkError};
+
+/// Decode a compressed Ristretto point, rejecting non-canonical and
+/// off-curve encodings
+pub(crate) fn point(bytes: &[u8; 32]) -> Result<RistrettoPoint> {
+    CompressedRistretto(*bytes)
+        .decompress()
+        .ok_or_else(|| ZkError::InvalidEncoding("not a canonical Ristretto point".into()))
+}
+
+/// Decode a scalar, rejecting values at or above the group order
+pub(crate) fn scalar(bytes: &[u8; 32]) -> Result<Scalar> {
+    Option::from(Scalar::from_canonical_bytes(*bytes))
+        .ok_or_else(|| ZkError::InvalidEncoding("scalar is not reduced".into()))
+}
+
+// Writing
+
+pub(crate) fn put_u8(out: &mut Vec<u8>, value: u8) {
+    out.push(value);
+}
+
+pub(crate) fn put_u64(out: &mut Vec<u8>, value: u64) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+pub(crate) fn put_usize(out: &mut Vec<u8>, value: usize) {
+    put_u64(out, value as u64);
+}
+
+pub(crate) fn put_bool(out: &mut Vec<u8>, value: bool) {
+    put_u8(out, value as u8);
+}
+
+pub(crate) fn put_array(out: &mut Vec<u8>, bytes: &[u8; 32]) {
+    out.extend_from_slice(bytes);
+}
+
+/// Length-prefixed byte string
+pub(crate) fn put_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    put_usize(out, bytes.len());
+    out.extend_from_slice(bytes);
+}
+
+// Reading
+
+pub(crate) fn take<'a>(input: &mut &'a [u8], len: usize) -> Result<&'a [u8]> {
+    if input.len() < len {
+        return Err(ZkError::SerializationError(format!(
+            "unexpected end of input: needed {} bytes, {} left",
+            len,
+            input.len()
+        )));
+    }
+    let (head, rest) = input.split_at(len);
+    *input = rest;
+    Ok(head)
+}
+
+pub(crate) fn get_u8(input: &mut &[u8]) -> Result<u8> {
+    Ok(take(input, 1)?[0])
+}
+
+pub(crate) fn get_u64(input: &mut &[u8]) -> Result<u64> {
+    let bytes = take(input, 8)?;
+    Ok(u64::from_le_bytes(bytes.try_into().expect("8 bytes")))
+}
+
+pub(crate) fn get_usize(input: &mut &[u8]) -> Result<usize> {
+    let value = get_u64(input)?;
+    usize::try_from(value)
+        .map_err(|_| ZkError::SerializationError(format!("{} does not fit in usize", value)))
+}
+
+pub(crate) fn get_bool(input: &mut &[u8]) -> Result<bool> {
+    match get_u8(input)? {
+        0 => Ok(false),
+        1 => Ok(true),
+        other => Err(ZkError::SerializationError(format!(
+            "invalid bool byte {}",
+            other
+        ))),
+    }
+}
+
+pub(crate) fn get_array(input: &mut &[u8]) -> Result<[u8; 32]> {
+    Ok(take(input, 32)?.try_into().expect("32 bytes"))
+}
+
+/// A 32-byte compressed point, checked to decompress
+pub(crate) fn get_point(input: &mut &[u8]) -> Result<[u8; 32]> {
+    let bytes = get_array(input)?;
+    point(&bytes)?;
+    Ok(bytes)
+}
+
+/// A 32-byte scalar, checked to be canonical
+pub(crate) fn get_scalar(input: &mut &[u8]) -> Result<[u8; 32]> {
+    let bytes = get_array(input)?;
+    scalar(&bytes)?;
+    Ok(bytes)
+}
+
+/// Number of items that follow, each at least `item_size` bytes long
+///
+/// Checked against the remaining input so a corrupt length cannot
+/// trigger a huge allocation.
+pub(crate) fn get_len(input: &mut &[u8], item_size: usize) -> Result<usize> {
+    let len = get_usize(input)?;
+    if len.saturating_mul(item_size.max(1)) > input.len() {
+        return Err(ZkError::SerializationError(format!(
+            "length {} exceeds remaining input",
+            len
+        )));
+    }
+    Ok(len)
+}
+
+/// Length-prefixed byte string
+pub(crate) fn get_bytes(input: &mut &[u8]) -> Result<Vec<u8>> {
+    let len = get_len(input, 1)?;
+    Ok(take(input, len)?.to_vec())
+}
+
+/// Serde adapter for `[u8; 32]` fields holding a compressed point
+pub(crate) mod serde_point {
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error> {
+        bytes.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 32], D::Error> {
+        let bytes = <[u8; 32]>::deserialize(deserializer)?;
+        super::point(&bytes).map_err(D::Error::custom)?;
+        Ok(bytes)
+    }
+}
+
+/// Serde adapter for `[u8; 32]` fields holding a scalar
+pub(crate) mod serde_scalar {
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error> {
+        bytes.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 32], D::Error> {
+        let bytes = <[u8; 32]>::deserialize(deserializer)?;
+        super::scalar(&bytes).map_err(D::Error::custom)?;
+        Ok(bytes)
+    }
+}
+
+/// Serde adapter for `Vec<[u8; 32]>` fields holding scalars
+pub(crate) mod serde_scalars {
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        scalars: &Vec<[u8; 32]>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        scalars.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<[u8; 32]>, D::Error> {
+        let scalars = Vec::<[u8; 32]>::deserialize(deserializer)?;
+        for bytes in &scalars {
+            super::scalar(bytes).map_err(D::Error::custom)?;
+        }
+        Ok(scalars)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+
+    #[test]
+    fn test_point_validation() {
+        let valid = RISTRETTO_BASEPOINT_POINT.compress().to_bytes();
+        assert!(point(&valid).is_ok());
+        assert!(point(&[0u8; 32]).is_ok()); // identity
+
+        // Non-canonical field element, negative s, and not on the curve
+        assert!(point(&[0xff; 32]).is_err());
+        let mut negative = [0u8; 32];
+        negative[0] = 1;
+        assert!(point(&negative).is_err());
+        let mut off_curve = valid;
+        off_curve[0] ^= 2;
+        assert!(matches!(
+            point(&off_curve),
+            Err(ZkError::InvalidEncoding(_))
+        ));
+    }
+
+    #[test]
+    fn test_scalar_validation() {
+        assert!(scalar(&Scalar::ONE.to_bytes()).is_ok());
+        assert!(scalar(&(-Scalar::ONE).to_bytes()).is_ok());
+        assert!(matches!(
+            scalar(&[0xff; 32]),
+            Err(ZkError::InvalidEncoding(_))
+        ));
+    }
+
+    #[test]
+    fn test_reader_rejects_truncation_and_huge_lengths() {
+        let mut out = Vec::new();
+        put_bytes(&mut out, b"abc");
+        put_bool(&mut out, true);
+
+        let mut input = out.as_slice();
+        assert_eq!(get_bytes(&mut input).unwrap(), b"abc");
+        assert!(get_bool(&mut input).unwrap());
+        assert!(get_u8(&mut input).is_err());
+
+        let mut huge = Vec::new();
+        put_u64(&mut huge, u64::MAX);
+        assert!(get_bytes(&mut huge.as_slice()).is_err());
+
+        assert!(get_bool(&mut [2u8].as_slice()).is_err());
+    }
+}
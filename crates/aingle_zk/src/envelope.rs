@@ -0,0 +1,622 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Versioned binary envelope for proofs and commitments
+//!
+//! Every public proof and commitment type implements [`ZkEncode`], a
+//! fixed-layout binary encoding that needs no serde format. A
+//! [`ZkEnvelope`] tags the payload with a format version and the kind of
+//! value it holds, so readers can reject data they don't understand
+//! instead of misinterpreting it.
+//!
+//! ## Layout
+//!
+//! ```text
+//! magic "AIZK" | version u16 LE | kind u8 | payload length u64 LE | payload
+//! ```
+//!
+//! Inside payloads, points and scalars are 32 bytes, integers are
+//! little-endian and variable-length fields carry a u64 length prefix.
+//! Decoding checks every point and scalar.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use aingle_zk::{envelope, PedersenCommitment};
+//!
+//! let (commitment, opening) = PedersenCommitment::commit(42);
+//! let bytes = envelope::to_bytes(&commitment);
+//!
+//! let decoded: PedersenCommitment = envelope::from_bytes(&bytes).unwrap();
+//! assert!(decoded.verify(42, &opening));
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::encoding;
+use crate::error::{Result, ZkError};
+
+/// Current envelope format version
+pub const ENVELOPE_VERSION: u16 = 1;
+
+/// Leading bytes of an encoded envelope
+const MAGIC: [u8; 4] = *b"AIZK";
+
+/// Kind of value held in an envelope
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ZkKind {
+    PedersenCommitment = 1,
+    CommitmentOpening = 2,
+    HashCommitment = 3,
+    BlindedValue = 4,
+    SchnorrProof = 5,
+    EqualityProof = 6,
+    ZkProof = 7,
+    MerkleProof = 8,
+    SparseMerkleProof = 9,
+    AggregatedProof = 10,
+    RangeProof = 11,
+    AggregatedRangeProof = 12,
+    VectorCommitment = 13,
+    VectorOpening = 14,
+    PositionOpening = 15,
+    ThresholdProof = 16,
+    NonceCommitment = 17,
+    PartialSignature = 18,
+    MultiSignature = 19,
+}
+
+impl ZkKind {
+    /// All kinds, in code order
+    pub const ALL: [ZkKind; 19] = [
+        ZkKind::PedersenCommitment,
+        ZkKind::CommitmentOpening,
+        ZkKind::HashCommitment,
+        ZkKind::BlindedValue,
+        ZkKind::SchnorrProof,
+        ZkKind::EqualityProof,
+        ZkKind::ZkProof,
+        ZkKind::MerkleProof,
+        ZkKind::SparseMerkleProof,
+        ZkKind::AggregatedProof,
+        ZkKind::RangeProof,
+        ZkKind::AggregatedRangeProof,
+        ZkKind::VectorCommitment,
+        ZkKind::VectorOpening,
+        ZkKind::PositionOpening,
+        ZkKind::ThresholdProof,
+        ZkKind::NonceCommitment,
+        ZkKind::PartialSignature,
+        ZkKind::MultiSignature,
+    ];
+
+    /// Wire code of this kind
+    pub fn code(&self) -> u8 {
+        *self as u8
+    }
+
+    /// Kind for a wire code
+    pub fn from_code(code: u8) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.code() == code)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ZkKind::PedersenCommitment => "PedersenCommitment",
+            ZkKind::CommitmentOpening => "CommitmentOpening",
+            ZkKind::HashCommitment => "HashCommitment",
+            ZkKind::BlindedValue => "BlindedValue",
+            ZkKind::SchnorrProof => "SchnorrProof",
+            ZkKind::EqualityProof => "EqualityProof",
+            ZkKind::ZkProof => "ZkProof",
+            ZkKind::MerkleProof => "MerkleProof",
+            ZkKind::SparseMerkleProof => "SparseMerkleProof",
+            ZkKind::AggregatedProof => "AggregatedProof",
+            ZkKind::RangeProof => "RangeProof",
+            ZkKind::AggregatedRangeProof => "AggregatedRangeProof",
+            ZkKind::VectorCommitment => "VectorCommitment",
+            ZkKind::VectorOpening => "VectorOpening",
+            ZkKind::PositionOpening => "PositionOpening",
+            ZkKind::ThresholdProof => "ThresholdProof",
+            ZkKind::NonceCommitment => "NonceCommitment",
+            ZkKind::PartialSignature => "PartialSignature",
+            ZkKind::MultiSignature => "MultiSignature",
+        }
+    }
+}
+
+impl fmt::Display for ZkKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Canonical binary encoding of a proof or commitment
+pub trait ZkEncode: Sized {
+    /// Kind recorded in the envelope
+    const KIND: ZkKind;
+
+    /// Append the encoding of `self` to `out`
+    fn encode(&self, out: &mut Vec<u8>);
+
+    /// Read a value from the front of `input`, advancing past it
+    ///
+    /// Rejects truncated input and invalid points or scalars.
+    fn decode(input: &mut &[u8]) -> Result<Self>;
+}
+
+/// A versioned, kind-tagged encoded value
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ZkEnvelope {
+    /// Format version of the payload
+    pub version: u16,
+    /// Kind of value in the payload
+    pub kind: ZkKind,
+    /// The value's [`ZkEncode`] encoding
+    pub payload: Vec<u8>,
+}
+
+impl ZkEnvelope {
+    /// Wrap a value in an envelope of the current version
+    pub fn wrap<T: ZkEncode>(value: &T) -> Self {
+        let mut payload = Vec::new();
+        value.encode(&mut payload);
+        Self {
+            version: ENVELOPE_VERSION,
+            kind: T::KIND,
+            payload,
+        }
+    }
+
+    /// Decode the value in the envelope
+    ///
+    /// Fails if the version is unsupported, the kind is not `T`'s, or
+    /// the payload is malformed or has trailing bytes.
+    pub fn open<T: ZkEncode>(&self) -> Result<T> {
+        if self.version != ENVELOPE_VERSION {
+            return Err(ZkError::UnsupportedVersion(self.version));
+        }
+        if self.kind != T::KIND {
+            return Err(ZkError::KindMismatch(T::KIND, self.kind));
+        }
+
+        let mut input = self.payload.as_slice();
+        let value = T::decode(&mut input)?;
+        if !input.is_empty() {
+            return Err(ZkError::SerializationError(format!(
+                "{} trailing bytes after {}",
+                input.len(),
+                self.kind
+            )));
+        }
+        Ok(value)
+    }
+
+    /// Encode the envelope, header included
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(MAGIC.len() + 2 + 1 + 8 + self.payload.len());
+        bytes.extend_from_slice(&MAGIC);
+        bytes.extend_from_slice(&self.version.to_le_bytes());
+        encoding::put_u8(&mut bytes, self.kind.code());
+        encoding::put_bytes(&mut bytes, &self.payload);
+        bytes
+    }
+
+    /// Decode an envelope produced by [`to_bytes`](Self::to_bytes)
+    ///
+    /// Only the header is checked here; [`open`](Self::open) validates
+    /// the payload.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut input = bytes;
+
+        if encoding::take(&mut input, MAGIC.len())? != MAGIC {
+            return Err(ZkError::SerializationError("missing envelope magic".into()));
+        }
+        let version =
+            u16::from_le_bytes(encoding::take(&mut input, 2)?.try_into().expect("2 bytes"));
+        let code = encoding::get_u8(&mut input)?;
+        let kind = ZkKind::from_code(code)
+            .ok_or_else(|| ZkError::SerializationError(format!("unknown payload kind {}", code)))?;
+        let payload = encoding::get_bytes(&mut input)?;
+
+        if !input.is_empty() {
+            return Err(ZkError::SerializationError(format!(
+                "{} trailing bytes after envelope",
+                input.len()
+            )));
+        }
+
+        Ok(Self {
+            version,
+            kind,
+            payload,
+        })
+    }
+}
+
+/// Encode a value as envelope bytes
+pub fn to_bytes<T: ZkEncode>(value: &T) -> Vec<u8> {
+    ZkEnvelope::wrap(value).to_bytes()
+}
+
+/// Decode a value from envelope bytes
+pub fn from_bytes<T: ZkEncode>(bytes: &[u8]) -> Result<T> {
+    ZkEnvelope::from_bytes(bytes)?.open()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregation::AggregatedProof;
+    use crate::commitment::{BlindedValue, CommitmentOpening, HashCommitment, PedersenCommitment};
+    use crate::merkle::{MerkleProof, MerkleTree, SparseMerkleProof, SparseMerkleTree};
+    use crate::proof::{EqualityProof, ProofData, ProofType, SchnorrProof, ZkProof};
+    use crate::vector::{PositionOpening, VectorCommitment, VectorOpening};
+    use curve25519_dalek::{
+        constants::RISTRETTO_BASEPOINT_POINT, ristretto::RistrettoPoint, scalar::Scalar,
+    };
+    use rand::rngs::OsRng;
+    use serde::de::DeserializeOwned;
+
+    /// Round-trip through envelope bytes and JSON, comparing re-encodings
+    fn round_trip<T: ZkEncode + Serialize + DeserializeOwned>(value: &T) -> T {
+        let bytes = to_bytes(value);
+        let decoded: T = from_bytes(&bytes).unwrap();
+        assert_eq!(to_bytes(&decoded), bytes, "{} envelope round trip", T::KIND);
+
+        let json = serde_json::to_string(value).unwrap();
+        let from_json: T = serde_json::from_str(&json).unwrap();
+        assert_eq!(to_bytes(&from_json), bytes, "{} JSON round trip", T::KIND);
+
+        decoded
+    }
+
+    fn schnorr() -> (SchnorrProof, RistrettoPoint) {
+        let secret = Scalar::random(&mut OsRng);
+        let public = RISTRETTO_BASEPOINT_POINT * secret;
+        (
+            SchnorrProof::prove_knowledge(&secret, &public, b"msg"),
+            public,
+        )
+    }
+
+    fn merkle_proof() -> MerkleProof {
+        let leaves: Vec<&[u8]> = vec![b"a", b"b", b"c", b"d", b"e"];
+        MerkleTree::new(&leaves).unwrap().prove(2).unwrap()
+    }
+
+    fn sample_zk_proofs() -> Vec<ZkProof> {
+        let (schnorr, public) = schnorr();
+        vec![
+            ZkProof::membership(merkle_proof().root, merkle_proof())
+                .with_metadata(serde_json::json!({"source": "test", "n": [1, 2]})),
+            ZkProof::hash_opening(&HashCommitment::commit(b"data")),
+            ZkProof::new(
+                ProofType::KnowledgeProof,
+                ProofData::Knowledge {
+                    commitment: public.compress().to_bytes(),
+                    challenge: schnorr.challenge,
+                    response: schnorr.response,
+                },
+            ),
+            ZkProof::new(
+                ProofType::EqualityProof,
+                ProofData::Equality {
+                    commitment1: schnorr.commitment,
+                    commitment2: public.compress().to_bytes(),
+                    proof: vec![7; 64],
+                },
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_commitment_round_trips() {
+        let (commitment, opening) = PedersenCommitment::commit(42);
+        assert!(round_trip(&commitment).verify(42, &opening));
+        assert!(commitment.verify(42, &round_trip(&opening)));
+
+        let hash = HashCommitment::commit(b"secret");
+        assert!(round_trip(&hash).verify(b"secret"));
+
+        let (mut blinded, opening) = BlindedValue::new(7);
+        assert!(round_trip(&blinded).verify(7, &opening));
+        blinded.encrypted_value = Some(b"ciphertext".to_vec());
+        let decoded = round_trip(&blinded);
+        assert_eq!(decoded.encrypted_value.as_deref(), Some(&b"ciphertext"[..]));
+    }
+
+    #[test]
+    fn test_proof_round_trips() {
+        let (proof, public) = schnorr();
+        assert!(round_trip(&proof).verify(&public, b"msg").unwrap());
+
+        let h = RISTRETTO_BASEPOINT_POINT * Scalar::from(99u64);
+        let (r1, r2) = (Scalar::random(&mut OsRng), Scalar::random(&mut OsRng));
+        let c1 = RISTRETTO_BASEPOINT_POINT * Scalar::from(5u64) + h * r1;
+        let c2 = RISTRETTO_BASEPOINT_POINT * Scalar::from(5u64) + h * r2;
+        let equality = EqualityProof::prove_equality(5, &r1, &r2, &c1, &c2);
+        let decoded = round_trip(&equality);
+        assert_eq!(decoded.verify().unwrap(), equality.verify().unwrap());
+
+        for proof in sample_zk_proofs() {
+            let decoded = round_trip(&proof);
+            assert_eq!(decoded.id(), proof.id());
+            assert_eq!(decoded.proof_type, proof.proof_type);
+            assert_eq!(decoded.timestamp, proof.timestamp);
+            assert_eq!(decoded.metadata, proof.metadata);
+        }
+
+        let aggregated = AggregatedProof::new(sample_zk_proofs());
+        let decoded = round_trip(&aggregated);
+        assert_eq!(decoded.count(), 4);
+        assert_eq!(
+            decoded.metadata().aggregated_size,
+            aggregated.metadata().aggregated_size
+        );
+    }
+
+    #[test]
+    fn test_merkle_round_trips() {
+        let proof = merkle_proof();
+        assert!(round_trip(&proof).verify(b"c"));
+
+        let mut tree = SparseMerkleTree::new();
+        tree.insert(b"key", b"value").unwrap();
+        let root = tree.root();
+        let member = tree.prove(b"key").unwrap();
+        assert!(SparseMerkleTree::verify_proof(&round_trip(&member), &root));
+        let absent = tree.prove_non_membership(b"other").unwrap();
+        let decoded = round_trip(&absent);
+        assert_eq!(decoded.value, None);
+        assert!(SparseMerkleTree::verify_proof(&decoded, &root));
+    }
+
+    #[test]
+    fn test_vector_round_trips() {
+        let (commitment, opening) = VectorCommitment::commit(&[3, 1, 4, 1, 5]);
+        let position = opening.open_at(2).unwrap();
+
+        let commitment = round_trip(&commitment);
+        let opening = round_trip(&opening);
+        let position = round_trip(&position);
+        assert_eq!(opening.values, vec![3, 1, 4, 1, 5]);
+        assert!(commitment.verify_at(2, 4, &position).unwrap());
+        let empty = PositionOpening {
+            responses: Vec::new(),
+            ..position
+        };
+        round_trip(&empty);
+    }
+
+    #[cfg(feature = "bulletproofs")]
+    #[test]
+    fn test_range_round_trips() {
+        use crate::range::{AggregatedRangeProof, RangeProof, RangeProofGenerator};
+
+        let generator = RangeProofGenerator::new(16);
+        let proof: RangeProof = generator.prove(1000).unwrap();
+        let decoded = round_trip(&proof);
+        assert!(generator.verify(&decoded).unwrap());
+        assert!(decoded.verify_value(1000));
+
+        let aggregated = AggregatedRangeProof::prove(&[1, 2, 3], 16).unwrap();
+        let decoded = round_trip(&aggregated);
+        assert_eq!(decoded.len(), 3);
+        assert!(decoded.verify(16).unwrap());
+
+        let mut bad = proof.clone();
+        bad.proof_bytes.truncate(10);
+        assert!(from_bytes::<RangeProof>(&to_bytes(&bad)).is_err());
+    }
+
+    #[test]
+    fn test_envelope_header() {
+        let (commitment, _) = PedersenCommitment::commit(1);
+        let envelope = ZkEnvelope::wrap(&commitment);
+        assert_eq!(envelope.version, ENVELOPE_VERSION);
+        assert_eq!(envelope.kind, ZkKind::PedersenCommitment);
+        assert_eq!(envelope.payload, commitment.point);
+
+        let bytes = envelope.to_bytes();
+        assert_eq!(&bytes[..4], b"AIZK");
+        assert_eq!(ZkEnvelope::from_bytes(&bytes).unwrap(), envelope);
+
+        let json = serde_json::to_string(&envelope).unwrap();
+        assert_eq!(serde_json::from_str::<ZkEnvelope>(&json).unwrap(), envelope);
+
+        for kind in ZkKind::ALL {
+            assert_eq!(ZkKind::from_code(kind.code()), Some(kind));
+        }
+        assert_eq!(ZkKind::from_code(0), None);
+    }
+
+    #[test]
+    fn test_rejects_bad_envelopes() {
+        let (commitment, _) = PedersenCommitment::commit(1);
+        let good = to_bytes(&commitment);
+
+        let mut bad_magic = good.clone();
+        bad_magic[0] = b'X';
+        let mut unknown_kind = good.clone();
+        unknown_kind[6] = 200;
+        let mut long_length = good.clone();
+        long_length[7] = 33;
+        let mut short_length = good.clone();
+        short_length[7] = 31;
+        let mut trailing = good.clone();
+        trailing.push(0);
+        let mut inner_trailing = ZkEnvelope::wrap(&commitment);
+        inner_trailing.payload.push(0);
+        let mut huge_length = good[..7].to_vec();
+        huge_length.extend_from_slice(&u64::MAX.to_le_bytes());
+
+        let corpus: Vec<(&str, Vec<u8>)> = vec![
+            ("empty", Vec::new()),
+            ("magic only", b"AIZK".to_vec()),
+            ("bad magic", bad_magic),
+            ("unknown kind", unknown_kind),
+            ("length past end", long_length),
+            ("length short of end", short_length),
+            ("huge length", huge_length),
+            ("trailing bytes", trailing),
+            ("trailing payload bytes", inner_trailing.to_bytes()),
+            ("truncated", good[..good.len() - 1].to_vec()),
+        ];
+        for (name, bytes) in corpus {
+            assert!(
+                from_bytes::<PedersenCommitment>(&bytes).is_err(),
+                "{} was accepted",
+                name
+            );
+        }
+
+        assert!(matches!(
+            from_bytes::<HashCommitment>(&good),
+            Err(ZkError::KindMismatch(
+                ZkKind::HashCommitment,
+                ZkKind::PedersenCommitment
+            ))
+        ));
+
+        let mut future = ZkEnvelope::wrap(&commitment);
+        future.version = ENVELOPE_VERSION + 1;
+        let bytes = future.to_bytes();
+        assert!(matches!(
+            from_bytes::<PedersenCommitment>(&bytes),
+            Err(ZkError::UnsupportedVersion(2))
+        ));
+    }
+
+    #[test]
+    fn test_rejects_bad_payloads() {
+        fn open<T: ZkEncode>(payload: Vec<u8>) -> Result<T> {
+            ZkEnvelope {
+                version: ENVELOPE_VERSION,
+                kind: T::KIND,
+                payload,
+            }
+            .open()
+        }
+
+        let (proof, _) = schnorr();
+        let off_curve = [0xff; 32];
+        let unreduced = [0xff; 32];
+
+        // Points that don't decompress
+        assert!(matches!(
+            open::<PedersenCommitment>(off_curve.to_vec()),
+            Err(ZkError::InvalidEncoding(_))
+        ));
+        let mut payload = to_payload(&proof);
+        payload[..32].copy_from_slice(&off_curve);
+        assert!(open::<SchnorrProof>(payload).is_err());
+
+        // Scalars at or above the group order
+        assert!(matches!(
+            open::<CommitmentOpening>(unreduced.to_vec()),
+            Err(ZkError::InvalidEncoding(_))
+        ));
+        let mut payload = to_payload(&proof);
+        payload[64..].copy_from_slice(&unreduced);
+        assert!(open::<SchnorrProof>(payload).is_err());
+
+        // Option tags and bools other than 0 or 1
+        let (blinded, _) = BlindedValue::new(1);
+        let mut payload = to_payload(&blinded);
+        payload[32] = 2;
+        assert!(open::<BlindedValue>(payload).is_err());
+        let mut payload = to_payload(&merkle_proof());
+        payload[16 + 32] = 2;
+        assert!(open::<MerkleProof>(payload).is_err());
+
+        // Lengths beyond the remaining input
+        let mut payload = to_payload(&merkle_proof());
+        payload[8..16].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(open::<MerkleProof>(payload).is_err());
+        let (_, opening) = VectorCommitment::commit(&[1, 2]);
+        let mut payload = to_payload(&opening);
+        payload[0] = 200;
+        assert!(open::<VectorOpening>(payload).is_err());
+
+        // Unknown enum tags and malformed metadata
+        let zk = ZkProof::hash_opening(&HashCommitment::commit(b"data"))
+            .with_metadata(serde_json::json!({"k": 1}));
+        let payload = to_payload(&zk);
+        let mut bad_type = payload.clone();
+        bad_type[0] = 9;
+        assert!(open::<ZkProof>(bad_type).is_err());
+        let mut bad_data = payload.clone();
+        bad_data[1] = 9;
+        assert!(open::<ZkProof>(bad_data).is_err());
+        let mut bad_json = payload.clone();
+        let last = bad_json.len() - 1;
+        bad_json[last] = b'!';
+        assert!(open::<ZkProof>(bad_json).is_err());
+
+        // Every truncation of every payload fails cleanly
+        let (vector, opening) = VectorCommitment::commit(&[1, 2, 3]);
+        let payloads = vec![
+            (ZkKind::SchnorrProof, to_payload(&proof)),
+            (ZkKind::ZkProof, payload),
+            (ZkKind::MerkleProof, to_payload(&merkle_proof())),
+            (ZkKind::VectorCommitment, to_payload(&vector)),
+            (
+                ZkKind::PositionOpening,
+                to_payload(&opening.open_at(0).unwrap()),
+            ),
+        ];
+        for (kind, payload) in payloads {
+            for len in 0..payload.len() {
+                let truncated = payload[..len].to_vec();
+                let result = match kind {
+                    ZkKind::SchnorrProof => open::<SchnorrProof>(truncated).map(drop),
+                    ZkKind::ZkProof => open::<ZkProof>(truncated).map(drop),
+                    ZkKind::MerkleProof => open::<MerkleProof>(truncated).map(drop),
+                    ZkKind::VectorCommitment => open::<VectorCommitment>(truncated).map(drop),
+                    _ => open::<PositionOpening>(truncated).map(drop),
+                };
+                assert!(result.is_err(), "{} truncated to {} bytes", kind, len);
+            }
+        }
+
+        let _: SparseMerkleProof = open(to_payload(
+            &SparseMerkleTree::new().prove_non_membership(b"k").unwrap(),
+        ))
+        .unwrap();
+    }
+
+    #[test]
+    fn test_serde_rejects_invalid_points_and_scalars() {
+        let bad_point = format!("{{\"point\":{:?}}}", [0xffu8; 32]);
+        assert!(serde_json::from_str::<PedersenCommitment>(&bad_point).is_err());
+
+        let bad_scalar = format!("{{\"blinding\":{:?}}}", [0xffu8; 32]);
+        assert!(serde_json::from_str::<CommitmentOpening>(&bad_scalar).is_err());
+
+        let (proof, _) = schnorr();
+        let mut json = serde_json::to_value(&proof).unwrap();
+        json["response"] = serde_json::to_value([0xffu8; 32]).unwrap();
+        assert!(serde_json::from_value::<SchnorrProof>(json).is_err());
+
+        let (_, opening) = VectorCommitment::commit(&[1, 2]);
+        let mut json = serde_json::to_value(opening.open_at(0).unwrap()).unwrap();
+        json["responses"][0] = serde_json::to_value([0xffu8; 32]).unwrap();
+        assert!(serde_json::from_value::<PositionOpening>(json).is_err());
+
+        let zk = ZkProof::new(
+            ProofType::KnowledgeProof,
+            ProofData::Knowledge {
+                commitment: [0xff; 32],
+                challenge: [0; 32],
+                response: [0; 32],
+            },
+        );
+        assert!(ZkProof::from_json(&zk.to_json().unwrap()).is_err());
+    }
+
+    fn to_payload<T: ZkEncode>(value: &T) -> Vec<u8> {
+        ZkEnvelope::wrap(value).payload
+    }
+}
@@ -5,6 +5,8 @@
 
 use thiserror::Error;
 
+use crate::envelope::ZkKind;
+
 /// Result type for ZK operations
 pub type Result<T> = std::result::Result<T, ZkError>;
 
@@ -46,4 +48,24 @@ pub enum ZkError {
     /// Leaf not found
     #[error("Leaf not found in tree")]
     LeafNotFound,
+
+    /// Vector index out of range
+    #[error("Index {0} out of range for vector of length {1}")]
+    IndexOutOfRange(usize, usize),
+
+    /// Vector length mismatch
+    #[error("Vector length mismatch: expected {0}, got {1}")]
+    LengthMismatch(usize, usize),
+
+    /// Point or scalar that is not canonically encoded
+    #[error("Invalid encoding: {0}")]
+    InvalidEncoding(String),
+
+    /// Envelope written by an unsupported format version
+    #[error("Unsupported envelope version {0}")]
+    UnsupportedVersion(u16),
+
+    /// Envelope holds a different kind of value than requested
+    #[error("Expected {0} payload, found {1}")]
+    KindMismatch(ZkKind, ZkKind),
 }
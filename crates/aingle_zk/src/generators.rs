@@ -0,0 +1,378 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Domain-separated generator derivation
+//!
+//! Commitment schemes need generator points whose discrete log relations
+//! nobody knows. Every generator in this crate comes from
+//! [`derive_generator`], which hashes a domain string and an index to the
+//! Ristretto group, so two modules can only share a generator by naming the
+//! same domain.
+//!
+//! ## Construction
+//!
+//! `derive_generator(domain, index)` is `hash_to_ristretto255` from
+//! RFC 9380 (suite `ristretto255_XMD:SHA-512_R255MAP_RO_`): the 8-byte
+//! big-endian index is expanded with `expand_message_xmd` over SHA-512 into
+//! 64 uniform bytes, with the domain as the domain separation tag, and
+//! mapped to the group with the Elligator-based map of RFC 9496.
+//!
+//! ## Domains
+//!
+//! Domain strings are part of the wire and security contract: changing one
+//! changes every commitment and proof made with its generators, and two
+//! uses of the same domain get the same generators. Choose a new domain for
+//! each new use and never reuse or rename an existing one.
+//!
+//! | Domain | Constant | Used for |
+//! |--------|----------|----------|
+//! | `aingle_zk_pedersen_h` | [`LEGACY_PEDERSEN_DOMAIN`] | The blinding generator `H` of Pedersen, vector and threshold commitments and equality proofs (index 0) |
+//! | `aingle_zk_vector_generator` | [`LEGACY_VECTOR_DOMAIN`] | The per-index generators `G_i` of vector commitments |
+//!
+//! The two legacy domains predate this module. They keep their original
+//! SHA-512 derivations, so existing commitments and proofs stay valid;
+//! index 0 of [`LEGACY_PEDERSEN_DOMAIN`] is the only index with a legacy
+//! derivation, and its other indices follow the construction above.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use aingle_zk::generators::{derive_generator, GeneratorSet};
+//!
+//! let g = derive_generator("example_app_generators", 0);
+//! let set = GeneratorSet::new("example_app_generators", 4);
+//! assert_eq!(set.len(), 4);
+//! assert_eq!(set.get(0), Some(&g));
+//! ```
+
+use curve25519_dalek::{constants::RISTRETTO_BASEPOINT_POINT, ristretto::RistrettoPoint};
+use sha2::{Digest, Sha512};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+/// Domain of the Pedersen blinding generator `H`, derived as
+/// `SHA-512(compress(G) || domain)` before this module existed
+pub const LEGACY_PEDERSEN_DOMAIN: &str = "aingle_zk_pedersen_h";
+
+/// Domain of the vector commitment generators `G_i`, derived as
+/// `SHA-512(domain || index as u64 little-endian)` before this module existed
+pub const LEGACY_VECTOR_DOMAIN: &str = "aingle_zk_vector_generator";
+
+/// Prefix of the tag that replaces a domain longer than 255 bytes (RFC 9380)
+const OVERSIZE_DST_PREFIX: &[u8] = b"H2C-OVERSIZE-DST-";
+
+/// Derive generator `index` of `domain`
+///
+/// The same domain and index always give the same point; see the
+/// [module documentation](self) for the construction and the domains in use.
+///
+/// # Panics
+///
+/// Panics if `domain` is empty.
+pub fn derive_generator(domain: &str, index: u64) -> RistrettoPoint {
+    assert!(!domain.is_empty(), "generator domain must not be empty");
+    if let Some(point) = legacy_generator(domain, index) {
+        return point;
+    }
+
+    let mut uniform = [0u8; 64];
+    expand_message_xmd(&index.to_be_bytes(), domain.as_bytes(), &mut uniform);
+    RistrettoPoint::from_uniform_bytes(&uniform)
+}
+
+/// The blinding generator `H` shared by Pedersen commitments and the proofs
+/// over them
+pub fn pedersen_h() -> RistrettoPoint {
+    static H: OnceLock<RistrettoPoint> = OnceLock::new();
+    *H.get_or_init(|| derive_generator(LEGACY_PEDERSEN_DOMAIN, 0))
+}
+
+/// The original derivation of the generators of a legacy domain
+fn legacy_generator(domain: &str, index: u64) -> Option<RistrettoPoint> {
+    let mut hasher = Sha512::new();
+    match domain {
+        LEGACY_PEDERSEN_DOMAIN if index == 0 => {
+            hasher.update(RISTRETTO_BASEPOINT_POINT.compress().as_bytes());
+            hasher.update(domain);
+        }
+        LEGACY_VECTOR_DOMAIN => {
+            hasher.update(domain);
+            hasher.update(index.to_le_bytes());
+        }
+        _ => return None,
+    }
+    Some(RistrettoPoint::from_uniform_bytes(
+        &hasher.finalize().into(),
+    ))
+}
+
+/// `expand_message_xmd` with SHA-512 (RFC 9380, section 5.3.1), filling
+/// `out` with uniform bytes
+///
+/// `out` must be at most 255 blocks of 64 bytes.
+fn expand_message_xmd(msg: &[u8], dst: &[u8], out: &mut [u8]) {
+    const B_IN_BYTES: usize = 64;
+    const S_IN_BYTES: usize = 128;
+
+    let oversize;
+    let dst = if dst.len() > 255 {
+        oversize = Sha512::new()
+            .chain_update(OVERSIZE_DST_PREFIX)
+            .chain_update(dst)
+            .finalize();
+        oversize.as_slice()
+    } else {
+        dst
+    };
+    let ell = out.len().div_ceil(B_IN_BYTES);
+    assert!(ell <= 255, "expand_message_xmd output too long");
+
+    let b_0 = Sha512::new()
+        .chain_update([0u8; S_IN_BYTES])
+        .chain_update(msg)
+        .chain_update((out.len() as u16).to_be_bytes())
+        .chain_update([0u8])
+        .chain_update(dst)
+        .chain_update([dst.len() as u8])
+        .finalize();
+
+    let mut b_i = Sha512::new()
+        .chain_update(b_0)
+        .chain_update([1u8])
+        .chain_update(dst)
+        .chain_update([dst.len() as u8])
+        .finalize();
+    for (i, chunk) in out.chunks_mut(B_IN_BYTES).enumerate() {
+        if i > 0 {
+            let mixed: Vec<u8> = b_0.iter().zip(b_i.iter()).map(|(a, b)| a ^ b).collect();
+            b_i = Sha512::new()
+                .chain_update(mixed)
+                .chain_update([i as u8 + 1])
+                .chain_update(dst)
+                .chain_update([dst.len() as u8])
+                .finalize();
+        }
+        chunk.copy_from_slice(&b_i[..chunk.len()]);
+    }
+}
+
+/// Generators derived so far, per domain
+fn cache() -> &'static Mutex<HashMap<String, Vec<RistrettoPoint>>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, Vec<RistrettoPoint>>>> = OnceLock::new();
+    CACHE.get_or_init(Default::default)
+}
+
+/// The first `n` generators of a domain, for vector uses
+///
+/// Generators are derived once per process and domain; later sets of the
+/// same domain reuse them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratorSet {
+    domain: String,
+    points: Vec<RistrettoPoint>,
+}
+
+impl GeneratorSet {
+    /// The generators `0..n` of `domain`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `domain` is empty.
+    pub fn new(domain: &str, n: usize) -> Self {
+        assert!(!domain.is_empty(), "generator domain must not be empty");
+        let mut cache = cache().lock().unwrap_or_else(PoisonError::into_inner);
+        let derived = cache.entry(domain.to_string()).or_default();
+        for index in derived.len()..n {
+            derived.push(derive_generator(domain, index as u64));
+        }
+
+        Self {
+            domain: domain.to_string(),
+            points: derived[..n].to_vec(),
+        }
+    }
+
+    /// The domain the generators were derived from
+    pub fn domain(&self) -> &str {
+        &self.domain
+    }
+
+    /// Number of generators
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Whether the set has no generators
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Generator `index`, if the set has it
+    pub fn get(&self, index: usize) -> Option<&RistrettoPoint> {
+        self.points.get(index)
+    }
+
+    /// The generators, in index order
+    pub fn points(&self) -> &[RistrettoPoint] {
+        &self.points
+    }
+
+    /// Iterate over the generators in index order
+    pub fn iter(&self) -> std::slice::Iter<'_, RistrettoPoint> {
+        self.points.iter()
+    }
+}
+
+impl std::ops::Index<usize> for GeneratorSet {
+    type Output = RistrettoPoint;
+
+    fn index(&self, index: usize) -> &RistrettoPoint {
+        &self.points[index]
+    }
+}
+
+impl<'a> IntoIterator for &'a GeneratorSet {
+    type Item = &'a RistrettoPoint;
+    type IntoIter = std::slice::Iter<'a, RistrettoPoint>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.points.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn hex_point(point: &RistrettoPoint) -> String {
+        hex::encode(point.compress().as_bytes())
+    }
+
+    #[test]
+    fn test_derivation_is_deterministic() {
+        for domain in [
+            "aingle_zk_test",
+            LEGACY_PEDERSEN_DOMAIN,
+            LEGACY_VECTOR_DOMAIN,
+        ] {
+            for index in [0, 1, 7, u64::MAX] {
+                assert_eq!(
+                    derive_generator(domain, index),
+                    derive_generator(domain, index)
+                );
+            }
+        }
+        assert_eq!(
+            GeneratorSet::new("aingle_zk_test", 5),
+            GeneratorSet::new("aingle_zk_test", 5)
+        );
+    }
+
+    #[test]
+    fn test_generators_are_distinct_across_domains_and_indices() {
+        let domains = [
+            "aingle_zk_test",
+            "aingle_zk_test2",
+            "aingle_zk_tes",
+            LEGACY_PEDERSEN_DOMAIN,
+            LEGACY_VECTOR_DOMAIN,
+        ];
+        let mut seen = HashSet::new();
+        for domain in domains {
+            for index in 0..16 {
+                let point = derive_generator(domain, index);
+                assert_ne!(point, RISTRETTO_BASEPOINT_POINT);
+                assert!(
+                    seen.insert(point.compress().to_bytes()),
+                    "{domain} {index} repeats a generator"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_legacy_generators_are_unchanged() {
+        // Points derived by the code this module replaced; they must never change.
+        assert_eq!(
+            hex_point(&pedersen_h()),
+            "aeeb9a5607046c9f8d68447da365129fb4f53ef7550e095c2b99a6e06b23824c"
+        );
+        let vector = GeneratorSet::new(LEGACY_VECTOR_DOMAIN, 3);
+        let expected = [
+            "72d2c378adcfb9afb70a55529d0ddd46901c26cbccdcf49ae6523001da918d43",
+            "e22b08d1c92bafae998691e21f93e7a8d71e0ded30dac9644229e9b3f1378a21",
+            "9ee40621510e5d8e8116234b9a33e521a51010c3c63473af010c8a7302cd2f3a",
+        ];
+        for (point, expected) in vector.iter().zip(expected) {
+            assert_eq!(hex_point(point), expected);
+        }
+    }
+
+    #[test]
+    fn test_generator_set_matches_derivation() {
+        let short = GeneratorSet::new("aingle_zk_set_test", 2);
+        let long = GeneratorSet::new("aingle_zk_set_test", 6);
+        let again = GeneratorSet::new("aingle_zk_set_test", 3);
+
+        assert_eq!(short.domain(), "aingle_zk_set_test");
+        assert_eq!((short.len(), long.len(), again.len()), (2, 6, 3));
+        assert_eq!(short.points(), &long.points()[..2]);
+        assert_eq!(again.points(), &long.points()[..3]);
+        for (index, point) in long.iter().enumerate() {
+            assert_eq!(*point, derive_generator("aingle_zk_set_test", index as u64));
+        }
+        assert!(GeneratorSet::new("aingle_zk_set_test", 0).is_empty());
+    }
+
+    #[test]
+    fn test_expand_message_xmd_vectors() {
+        // RFC 9380, appendix K.3 (expand_message_xmd with SHA-512)
+        let dst = b"QUUX-V01-CS02-with-expander-SHA512-256";
+        let cases: [(&[u8], &str); 3] = [
+            (
+                b"",
+                "6b9a7312411d92f921c6f68ca0b6380730a1a4d982c507211a90964c394179ba",
+            ),
+            (
+                b"abc",
+                "0da749f12fbe5483eb066a5f595055679b976e93abe9be6f0f6318bce7aca8dc",
+            ),
+            (
+                b"abcdef0123456789",
+                "087e45a86e2939ee8b91100af1583c4938e0f5fc6c9db4b107b83346bc967f58",
+            ),
+        ];
+        for (msg, expected) in cases {
+            let mut out = [0u8; 32];
+            expand_message_xmd(msg, dst, &mut out);
+            assert_eq!(hex::encode(out), expected);
+        }
+
+        let mut long = [0u8; 128];
+        expand_message_xmd(b"", dst, &mut long);
+        assert_eq!(
+            hex::encode(long),
+            "41b037d1734a5f8df225dd8c7de38f851efdb45c372887be655212d07251b921\
+             b052b62eaed99b46f72f2ef4cc96bfaf254ebbbec091e1a3b9e4fb5e5b619d2e\
+             0c5414800a1d882b62bb5cd1778f098b8eb6cb399d5d9d18f5d5842cf5d13d7e\
+             b00a7cff859b605da678b318bd0e65ebff70bec88c753b159a805d2c89c55961"
+        );
+    }
+
+    #[test]
+    fn test_oversize_domain_is_hashed() {
+        let long = "d".repeat(300);
+        assert_eq!(derive_generator(&long, 0), derive_generator(&long, 0));
+        assert_ne!(
+            derive_generator(&long, 0),
+            derive_generator(&long[..299], 0)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "must not be empty")]
+    fn test_empty_domain_panics() {
+        derive_generator("", 0);
+    }
+}
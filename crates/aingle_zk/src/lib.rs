@@ -9,13 +9,18 @@
 //! ## Features
 //!
 //! - **Pedersen Commitments**: Hide values while allowing verification
+//! - **Vector Commitments**: Commit to many values at once and open one position at a time
 //! - **Range Proofs**: Prove a value is within a range without revealing it (Bulletproofs)
+//! - **Threshold Proofs**: Prove a committed value is below or above a public threshold (Bulletproofs)
 //! - **Membership Proofs**: Prove inclusion in a set using Merkle trees
 //! - **Hash Commitments**: Simple commitment scheme using cryptographic hashes
 //! - **Batch Verification**: Efficiently verify multiple proofs at once (2-5x faster)
 //! - **Proof Aggregation**: Combine multiple proofs for efficient storage and transmission
 //! - **Schnorr Signatures**: Non-interactive zero-knowledge proofs of knowledge
+//! - **Multi-Signatures**: Two-round MuSig2 Schnorr signatures under one aggregate key
 //! - **Equality Proofs**: Prove two commitments hide the same value
+//! - **Generators**: Domain-separated hash-to-curve derivation of commitment generators
+//! - **Serialization**: Serde support and a versioned binary envelope that validates points and scalars
 //!
 //! ## Architecture
 //!
@@ -83,7 +88,9 @@
 //!
 //! - **Blinding factors must be random**: Never reuse blinding factors across commitments
 //! - **Proof replayability**: Schnorr proofs are deterministic and can be replayed
-//! - **Side-channel attacks**: This library is NOT constant-time for all operations
+//! - **Side-channel attacks**: This library is NOT constant-time for all operations.
+//!   Openings and proof challenges are compared in constant time, and openings,
+//!   embedded range-proof blinding factors and proof nonces are zeroized once dropped
 //! - **Production use**: Audit before using in production systems
 //!
 //! ### Recommended Practices
@@ -101,20 +108,36 @@
 pub mod aggregation;
 pub mod batch;
 pub mod commitment;
+mod encoding;
+pub mod envelope;
 pub mod error;
+pub mod generators;
 pub mod merkle;
+pub mod musig;
 pub mod proof;
+pub mod vector;
 
 #[cfg(feature = "bulletproofs")]
 pub mod range;
+#[cfg(feature = "bulletproofs")]
+pub mod threshold;
 
 // Re-export main types
 pub use aggregation::{AggregatedProof, AggregationResult, ProofAggregator};
 pub use batch::{BatchResult, BatchVerifier};
 pub use commitment::{BlindedValue, CommitmentOpening, HashCommitment, PedersenCommitment};
+pub use envelope::{ZkEncode, ZkEnvelope, ZkKind};
 pub use error::{Result, ZkError};
+pub use generators::{derive_generator, GeneratorSet};
 pub use merkle::{MerkleProof, MerkleTree, SparseMerkleTree};
+pub use musig::{
+    AggregatePublicKey, MultiSignature, NonceCommitment, PartialSignature, PublicKey, SecretNonce,
+    SigningSession,
+};
 pub use proof::{EqualityProof, ProofBuilder, ProofType, ProofVerifier, SchnorrProof, ZkProof};
+pub use vector::{PositionOpening, VectorCommitment, VectorOpening};
 
 #[cfg(feature = "bulletproofs")]
 pub use range::{AggregatedRangeProof, RangeProof, RangeProofGenerator};
+#[cfg(feature = "bulletproofs")]
+pub use threshold::{ThresholdDirection, ThresholdProof};
@@ -9,6 +9,8 @@
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
+use crate::encoding;
+use crate::envelope::{ZkEncode, ZkKind};
 use crate::error::{Result, ZkError};
 
 /// Hash type for Merkle tree nodes
@@ -597,6 +599,74 @@ impl Default for SparseMerkleTree {
     }
 }
 
+impl ZkEncode for MerkleProof {
+    const KIND: ZkKind = ZkKind::MerkleProof;
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        encoding::put_usize(out, self.leaf_index);
+        encoding::put_usize(out, self.proof_nodes.len());
+        for node in &self.proof_nodes {
+            encoding::put_array(out, &node.hash);
+            encoding::put_bool(out, node.is_left);
+        }
+        encoding::put_array(out, &self.root);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self> {
+        let leaf_index = encoding::get_usize(input)?;
+        let count = encoding::get_len(input, 33)?;
+        let proof_nodes = (0..count)
+            .map(|_| {
+                Ok(ProofNode {
+                    hash: encoding::get_array(input)?,
+                    is_left: encoding::get_bool(input)?,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            leaf_index,
+            proof_nodes,
+            root: encoding::get_array(input)?,
+        })
+    }
+}
+
+impl ZkEncode for SparseMerkleProof {
+    const KIND: ZkKind = ZkKind::SparseMerkleProof;
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        encoding::put_array(out, &self.key);
+        encoding::put_bool(out, self.value.is_some());
+        if let Some(value) = &self.value {
+            encoding::put_array(out, value);
+        }
+        encoding::put_usize(out, self.siblings.len());
+        for sibling in &self.siblings {
+            encoding::put_array(out, sibling);
+        }
+        encoding::put_array(out, &self.root);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self> {
+        let key = encoding::get_array(input)?;
+        let value = if encoding::get_bool(input)? {
+            Some(encoding::get_array(input)?)
+        } else {
+            None
+        };
+        let count = encoding::get_len(input, 32)?;
+        let siblings = (0..count)
+            .map(|_| encoding::get_array(input))
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            key,
+            value,
+            siblings,
+            root: encoding::get_array(input)?,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
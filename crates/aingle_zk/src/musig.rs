@@ -0,0 +1,754 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Schnorr multi-signatures
+//!
+//! Two-round MuSig2 multi-signatures: several signers jointly produce one
+//! Schnorr signature that verifies against a single aggregate key. A triple
+//! attested by a handler and an inspector carries one signature, costs one
+//! verification, and neither attestation can be detached from the other.
+//!
+//! ## Protocol
+//!
+//! 1. The signers agree on the order of their [`PublicKey`]s and each builds
+//!    the same [`AggregatePublicKey`]. Every key is weighted by a
+//!    coefficient hashed from the whole key list, so no signer can pick its
+//!    key as a function of the others' to cancel them out (the rogue-key
+//!    attack).
+//! 2. Round one: each signer draws a [`SecretNonce`] and sends the matching
+//!    [`NonceCommitment`] to the others.
+//! 3. Round two: with every commitment and the message, each signer opens a
+//!    [`SigningSession`], signs with its secret nonce and sends the
+//!    [`PartialSignature`].
+//! 4. Anyone holding the session combines the partial signatures into a
+//!    [`MultiSignature`], checking each against its signer's commitment.
+//!
+//! The result is an ordinary Schnorr signature under the aggregate key: it
+//! verifies with [`MultiSignature::verify`], and as a [`SchnorrProof`] for
+//! the aggregate point.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use aingle_zk::musig::{AggregatePublicKey, PublicKey, SecretNonce, SigningSession};
+//! use curve25519_dalek::scalar::Scalar;
+//! use rand::rngs::OsRng;
+//!
+//! let handler = Scalar::random(&mut OsRng);
+//! let inspector = Scalar::random(&mut OsRng);
+//! let keys = [PublicKey::from_secret(&handler), PublicKey::from_secret(&inspector)];
+//! let aggregate = AggregatePublicKey::from(&keys[..]);
+//!
+//! // Round one: exchange nonce commitments
+//! let (handler_nonce, handler_commitment) = SecretNonce::generate(&keys[0]);
+//! let (inspector_nonce, inspector_commitment) = SecretNonce::generate(&keys[1]);
+//! let commitments = [handler_commitment, inspector_commitment];
+//!
+//! // Round two: exchange partial signatures
+//! let message = b"shipment:42 inspected_by inspector:7";
+//! let session = SigningSession::new(&aggregate, &commitments, message).unwrap();
+//! let partials = [
+//!     handler_nonce.sign(&session, &handler).unwrap(),
+//!     inspector_nonce.sign(&session, &inspector).unwrap(),
+//! ];
+//!
+//! let signature = session.combine(&partials).unwrap();
+//! assert!(signature.verify(&aggregate, message).unwrap());
+//! ```
+//!
+//! ## Nonce reuse
+//!
+//! Signing twice with the same nonce reveals the secret key. A
+//! [`SecretNonce`] cannot be cloned or serialized and is consumed by
+//! [`SecretNonce::sign`], so each one signs at most once:
+//!
+//! ```compile_fail
+//! use aingle_zk::musig::{SecretNonce, SigningSession};
+//! use curve25519_dalek::scalar::Scalar;
+//!
+//! fn sign_twice(nonce: SecretNonce, a: &SigningSession, b: &SigningSession, secret: &Scalar) {
+//!     let _ = nonce.sign(a, secret);
+//!     let _ = nonce.sign(b, secret); // use of moved value
+//! }
+//! ```
+
+use curve25519_dalek::{
+    constants::RISTRETTO_BASEPOINT_POINT, ristretto::RistrettoPoint, scalar::Scalar,
+    traits::Identity,
+};
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha512};
+use std::fmt;
+use zeroize::Zeroizing;
+
+use crate::encoding;
+use crate::envelope::{ZkEncode, ZkKind};
+use crate::error::{Result, ZkError};
+use crate::proof::SchnorrProof;
+
+/// Domain tag of the key list hash
+const KEY_LIST_TAG: &[u8] = b"aingle_zk_musig_key_list";
+/// Domain tag of the key coefficients
+const KEY_COEFFICIENT_TAG: &[u8] = b"aingle_zk_musig_key_coefficient";
+/// Domain tag of the nonce coefficient
+const NONCE_COEFFICIENT_TAG: &[u8] = b"aingle_zk_musig_nonce_coefficient";
+
+/// Hash a domain tag and fixed-size parts, then a trailing variable-length
+/// part, to a scalar
+fn hash_to_scalar(tag: &[u8], parts: &[&[u8]]) -> Scalar {
+    let mut hasher = Sha512::new();
+    hasher.update(tag);
+    for part in parts {
+        hasher.update(part);
+    }
+    Scalar::from_bytes_mod_order_wide(&hasher.finalize().into())
+}
+
+/// Schnorr challenge `H(R || X || message)`, as checked by
+/// [`SchnorrProof::verify`]
+fn challenge(nonce: &[u8; 32], key: &RistrettoPoint, message: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(nonce);
+    hasher.update(key.compress().as_bytes());
+    hasher.update(message);
+    hasher.finalize().into()
+}
+
+/// A signer's public key `x*G`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PublicKey {
+    #[serde(with = "encoding::serde_point")]
+    bytes: [u8; 32],
+}
+
+impl PublicKey {
+    /// The public key of `secret`
+    pub fn from_secret(secret: &Scalar) -> Self {
+        Self::from_point(&(RISTRETTO_BASEPOINT_POINT * secret))
+    }
+
+    /// Wrap a public point
+    pub fn from_point(point: &RistrettoPoint) -> Self {
+        Self {
+            bytes: point.compress().to_bytes(),
+        }
+    }
+
+    /// Decode a compressed public key
+    ///
+    /// # Errors
+    /// Returns `ZkError::InvalidEncoding` if the bytes are not a valid point
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<Self> {
+        encoding::point(bytes)?;
+        Ok(Self { bytes: *bytes })
+    }
+
+    /// The compressed public key
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.bytes
+    }
+
+    /// The public point
+    pub fn to_point(&self) -> RistrettoPoint {
+        encoding::point(&self.bytes).expect("public keys are checked on construction")
+    }
+}
+
+/// The combined key of a group of signers
+///
+/// `X = a_1*P_1 + ... + a_n*P_n`, where each coefficient `a_i` is hashed
+/// from the whole key list and `P_i`. The order of the keys matters: all
+/// signers and verifiers must use the same order. Keys must be distinct.
+///
+/// Serialized as its key list; the aggregate is recomputed on
+/// deserialization.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "Vec<PublicKey>", into = "Vec<PublicKey>")]
+pub struct AggregatePublicKey {
+    keys: Vec<PublicKey>,
+    coefficients: Vec<Scalar>,
+    point: RistrettoPoint,
+}
+
+impl AggregatePublicKey {
+    /// The signers' keys, in aggregation order
+    pub fn keys(&self) -> &[PublicKey] {
+        &self.keys
+    }
+
+    /// The aggregate point `X`
+    pub fn to_point(&self) -> RistrettoPoint {
+        self.point
+    }
+
+    /// Position of `key` among the signers
+    fn index_of(&self, key: &PublicKey) -> Option<usize> {
+        self.keys.iter().position(|k| k == key)
+    }
+}
+
+impl From<&[PublicKey]> for AggregatePublicKey {
+    fn from(keys: &[PublicKey]) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(KEY_LIST_TAG);
+        for key in keys {
+            hasher.update(key.bytes);
+        }
+        let list_hash: [u8; 32] = hasher.finalize().into();
+
+        let coefficients: Vec<Scalar> = keys
+            .iter()
+            .map(|key| hash_to_scalar(KEY_COEFFICIENT_TAG, &[&list_hash, &key.bytes]))
+            .collect();
+        let point = keys
+            .iter()
+            .zip(&coefficients)
+            .map(|(key, a)| key.to_point() * a)
+            .sum();
+
+        Self {
+            keys: keys.to_vec(),
+            coefficients,
+            point,
+        }
+    }
+}
+
+impl<const N: usize> From<&[PublicKey; N]> for AggregatePublicKey {
+    fn from(keys: &[PublicKey; N]) -> Self {
+        Self::from(&keys[..])
+    }
+}
+
+impl From<Vec<PublicKey>> for AggregatePublicKey {
+    fn from(keys: Vec<PublicKey>) -> Self {
+        Self::from(keys.as_slice())
+    }
+}
+
+impl From<AggregatePublicKey> for Vec<PublicKey> {
+    fn from(key: AggregatePublicKey) -> Self {
+        key.keys
+    }
+}
+
+/// Round-one message: a signer's public nonces `R1 = k1*G`, `R2 = k2*G`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NonceCommitment {
+    /// The signer the nonces belong to
+    pub signer: PublicKey,
+    #[serde(with = "encoding::serde_point")]
+    pub r1: [u8; 32],
+    #[serde(with = "encoding::serde_point")]
+    pub r2: [u8; 32],
+}
+
+/// A signer's secret nonces for one signing session
+///
+/// Cannot be cloned or serialized, and is consumed by [`sign`](Self::sign).
+/// The nonces are zeroized when dropped.
+pub struct SecretNonce {
+    k1: Zeroizing<Scalar>,
+    k2: Zeroizing<Scalar>,
+    commitment: NonceCommitment,
+}
+
+impl SecretNonce {
+    /// Draw fresh nonces for `signer`, returning them with the commitment
+    /// to send to the other signers
+    pub fn generate(signer: &PublicKey) -> (Self, NonceCommitment) {
+        let k1 = Zeroizing::new(Scalar::random(&mut OsRng));
+        let k2 = Zeroizing::new(Scalar::random(&mut OsRng));
+        let commitment = NonceCommitment {
+            signer: *signer,
+            r1: (RISTRETTO_BASEPOINT_POINT * *k1).compress().to_bytes(),
+            r2: (RISTRETTO_BASEPOINT_POINT * *k2).compress().to_bytes(),
+        };
+        let nonce = Self {
+            k1,
+            k2,
+            commitment: commitment.clone(),
+        };
+        (nonce, commitment)
+    }
+
+    /// The commitment to these nonces
+    pub fn commitment(&self) -> &NonceCommitment {
+        &self.commitment
+    }
+
+    /// Produce this signer's partial signature `s = k1 + b*k2 + c*a*x`
+    ///
+    /// # Errors
+    /// Returns `ZkError::InvalidInput` if `secret` is not the signer's key
+    /// or the nonces were not committed to `session`
+    pub fn sign(self, session: &SigningSession, secret: &Scalar) -> Result<PartialSignature> {
+        if PublicKey::from_secret(secret) != self.commitment.signer {
+            return Err(ZkError::InvalidInput(
+                "Secret key does not belong to the nonce's signer".into(),
+            ));
+        }
+        let index = session
+            .key
+            .index_of(&self.commitment.signer)
+            .filter(|&i| session.commitments[i] == self.commitment)
+            .ok_or_else(|| {
+                ZkError::InvalidInput("Nonce was not committed to this session".into())
+            })?;
+
+        let c = Scalar::from_bytes_mod_order(session.challenge);
+        let x = Zeroizing::new(*secret);
+        let s = *self.k1 + session.b * *self.k2 + c * session.key.coefficients[index] * *x;
+
+        Ok(PartialSignature {
+            signer: self.commitment.signer,
+            response: s.to_bytes(),
+        })
+    }
+}
+
+impl fmt::Debug for SecretNonce {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecretNonce")
+            .field("commitment", &self.commitment)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Round-two message: one signer's share of the signature
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartialSignature {
+    /// The signer that produced it
+    pub signer: PublicKey,
+    #[serde(with = "encoding::serde_scalar")]
+    pub response: [u8; 32],
+}
+
+/// The state shared by all signers for one message and one set of nonce
+/// commitments
+#[derive(Clone, Debug)]
+pub struct SigningSession {
+    key: AggregatePublicKey,
+    /// One commitment per signer, in key order
+    commitments: Vec<NonceCommitment>,
+    /// Nonce coefficient `b = H(R1 || R2 || X || message)`
+    b: Scalar,
+    /// Effective nonce `R = R1 + b*R2`
+    nonce: RistrettoPoint,
+    /// Challenge `c = H(R || X || message)`
+    challenge: [u8; 32],
+}
+
+impl SigningSession {
+    /// Start a session from every signer's commitment, in any order
+    ///
+    /// # Errors
+    /// Returns `ZkError::LengthMismatch` unless there is one commitment per
+    /// signer, and `ZkError::InvalidInput` for a commitment from outside the
+    /// group or a second one from the same signer
+    pub fn new(
+        key: &AggregatePublicKey,
+        commitments: &[NonceCommitment],
+        message: &[u8],
+    ) -> Result<Self> {
+        if key.keys.is_empty() {
+            return Err(ZkError::InvalidInput("No signers".into()));
+        }
+        if commitments.len() != key.keys.len() {
+            return Err(ZkError::LengthMismatch(key.keys.len(), commitments.len()));
+        }
+
+        let mut ordered: Vec<Option<NonceCommitment>> = vec![None; key.keys.len()];
+        for commitment in commitments {
+            let index = key.index_of(&commitment.signer).ok_or_else(|| {
+                ZkError::InvalidInput("Nonce commitment from a signer outside the group".into())
+            })?;
+            if ordered[index].is_some() {
+                return Err(ZkError::InvalidInput(
+                    "Two nonce commitments from the same signer".into(),
+                ));
+            }
+            ordered[index] = Some(commitment.clone());
+        }
+        let commitments: Vec<NonceCommitment> = ordered.into_iter().flatten().collect();
+
+        let mut r1 = RistrettoPoint::identity();
+        let mut r2 = RistrettoPoint::identity();
+        for commitment in &commitments {
+            r1 += encoding::point(&commitment.r1)?;
+            r2 += encoding::point(&commitment.r2)?;
+        }
+
+        let b = hash_to_scalar(
+            NONCE_COEFFICIENT_TAG,
+            &[
+                r1.compress().as_bytes(),
+                r2.compress().as_bytes(),
+                key.point.compress().as_bytes(),
+                message,
+            ],
+        );
+        let nonce = r1 + r2 * b;
+        let challenge = challenge(&nonce.compress().to_bytes(), &key.point, message);
+
+        Ok(Self {
+            key: key.clone(),
+            commitments,
+            b,
+            nonce,
+            challenge,
+        })
+    }
+
+    /// The aggregate key being signed for
+    pub fn key(&self) -> &AggregatePublicKey {
+        &self.key
+    }
+
+    /// Check one partial signature against its signer's commitment
+    ///
+    /// A partial signature made in another session, or by a signer outside
+    /// the group, does not verify.
+    pub fn verify_partial(&self, partial: &PartialSignature) -> Result<bool> {
+        let Some(index) = self.key.index_of(&partial.signer) else {
+            return Ok(false);
+        };
+        let s = encoding::scalar(&partial.response)?;
+        let commitment = &self.commitments[index];
+        let r = encoding::point(&commitment.r1)? + encoding::point(&commitment.r2)? * self.b;
+        let c = Scalar::from_bytes_mod_order(self.challenge);
+
+        // s*G == R1 + b*R2 + c*a*P
+        let lhs = RISTRETTO_BASEPOINT_POINT * s;
+        let rhs = r + partial.signer.to_point() * (c * self.key.coefficients[index]);
+        Ok(lhs == rhs)
+    }
+
+    /// Combine one partial signature per signer into the multi-signature
+    ///
+    /// # Errors
+    /// Returns `ZkError::LengthMismatch` unless there is one partial
+    /// signature per signer, `ZkError::InvalidInput` for a signer outside
+    /// the group or a duplicate, and `ZkError::InvalidProof` if a partial
+    /// signature does not verify
+    pub fn combine(&self, partials: &[PartialSignature]) -> Result<MultiSignature> {
+        if partials.len() != self.key.keys.len() {
+            return Err(ZkError::LengthMismatch(self.key.keys.len(), partials.len()));
+        }
+
+        let mut seen = vec![false; self.key.keys.len()];
+        let mut s = Scalar::ZERO;
+        for partial in partials {
+            let index = self.key.index_of(&partial.signer).ok_or_else(|| {
+                ZkError::InvalidInput("Partial signature from a signer outside the group".into())
+            })?;
+            if seen[index] {
+                return Err(ZkError::InvalidInput(
+                    "Two partial signatures from the same signer".into(),
+                ));
+            }
+            if !self.verify_partial(partial)? {
+                return Err(ZkError::InvalidProof(format!(
+                    "Partial signature of signer {} does not verify",
+                    index
+                )));
+            }
+            seen[index] = true;
+            s += encoding::scalar(&partial.response)?;
+        }
+
+        Ok(MultiSignature {
+            commitment: self.nonce.compress().to_bytes(),
+            challenge: self.challenge,
+            response: s.to_bytes(),
+        })
+    }
+}
+
+/// A Schnorr signature under an [`AggregatePublicKey`]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultiSignature {
+    #[serde(with = "encoding::serde_point")]
+    pub commitment: [u8; 32], // R = R1 + b*R2
+    pub challenge: [u8; 32], // c = H(R || X || message)
+    #[serde(with = "encoding::serde_scalar")]
+    pub response: [u8; 32], // s = sum of partial responses
+}
+
+impl MultiSignature {
+    /// Verify the signature against the group's aggregate key
+    pub fn verify(&self, key: &AggregatePublicKey, message: &[u8]) -> Result<bool> {
+        if key.keys.is_empty() {
+            return Ok(false);
+        }
+        self.to_schnorr_proof().verify(&key.point, message)
+    }
+
+    /// The signature as a proof of knowledge of the aggregate key's
+    /// discrete log, verifiable with the aggregate point
+    pub fn to_schnorr_proof(&self) -> SchnorrProof {
+        SchnorrProof {
+            commitment: self.commitment,
+            challenge: self.challenge,
+            response: self.response,
+        }
+    }
+}
+
+impl ZkEncode for NonceCommitment {
+    const KIND: ZkKind = ZkKind::NonceCommitment;
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        encoding::put_array(out, &self.signer.bytes);
+        encoding::put_array(out, &self.r1);
+        encoding::put_array(out, &self.r2);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self> {
+        Ok(Self {
+            signer: PublicKey {
+                bytes: encoding::get_point(input)?,
+            },
+            r1: encoding::get_point(input)?,
+            r2: encoding::get_point(input)?,
+        })
+    }
+}
+
+impl ZkEncode for PartialSignature {
+    const KIND: ZkKind = ZkKind::PartialSignature;
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        encoding::put_array(out, &self.signer.bytes);
+        encoding::put_array(out, &self.response);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self> {
+        Ok(Self {
+            signer: PublicKey {
+                bytes: encoding::get_point(input)?,
+            },
+            response: encoding::get_scalar(input)?,
+        })
+    }
+}
+
+impl ZkEncode for MultiSignature {
+    const KIND: ZkKind = ZkKind::MultiSignature;
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        encoding::put_array(out, &self.commitment);
+        encoding::put_array(out, &self.challenge);
+        encoding::put_array(out, &self.response);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self> {
+        Ok(Self {
+            commitment: encoding::get_point(input)?,
+            challenge: encoding::get_array(input)?,
+            response: encoding::get_scalar(input)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::envelope;
+
+    const MESSAGE: &[u8] = b"shipment:42 inspected_by inspector:7";
+
+    fn secrets(n: usize) -> Vec<Scalar> {
+        (0..n).map(|_| Scalar::random(&mut OsRng)).collect()
+    }
+
+    fn keys(secrets: &[Scalar]) -> Vec<PublicKey> {
+        secrets.iter().map(PublicKey::from_secret).collect()
+    }
+
+    /// Run both rounds for all signers
+    fn sign_all(
+        secrets: &[Scalar],
+        key: &AggregatePublicKey,
+        message: &[u8],
+    ) -> (SigningSession, Vec<PartialSignature>) {
+        let (nonces, commitments): (Vec<_>, Vec<_>) =
+            key.keys().iter().map(SecretNonce::generate).unzip();
+        let session = SigningSession::new(key, &commitments, message).unwrap();
+        let partials = nonces
+            .into_iter()
+            .zip(secrets)
+            .map(|(nonce, secret)| nonce.sign(&session, secret).unwrap())
+            .collect();
+        (session, partials)
+    }
+
+    #[test]
+    fn test_two_of_two() {
+        let secrets = secrets(2);
+        let key = AggregatePublicKey::from(keys(&secrets));
+
+        let (session, partials) = sign_all(&secrets, &key, MESSAGE);
+        assert!(partials.iter().all(|p| session.verify_partial(p).unwrap()));
+        let signature = session.combine(&partials).unwrap();
+
+        assert!(signature.verify(&key, MESSAGE).unwrap());
+        assert!(!signature.verify(&key, b"another message").unwrap());
+        assert!(signature
+            .to_schnorr_proof()
+            .verify(&key.to_point(), MESSAGE)
+            .unwrap());
+
+        // Either signer alone is not the group
+        let alone = AggregatePublicKey::from(&key.keys()[..1]);
+        assert!(!signature.verify(&alone, MESSAGE).unwrap());
+        // Nor is the same group in another order
+        let reversed: Vec<PublicKey> = key.keys().iter().rev().copied().collect();
+        assert!(!signature
+            .verify(&AggregatePublicKey::from(reversed), MESSAGE)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_three_of_three() {
+        let secrets = secrets(3);
+        let key = AggregatePublicKey::from(keys(&secrets));
+
+        let (session, mut partials) = sign_all(&secrets, &key, MESSAGE);
+        // Partial signatures can arrive in any order
+        partials.rotate_left(1);
+        let signature = session.combine(&partials).unwrap();
+        assert!(signature.verify(&key, MESSAGE).unwrap());
+
+        // All signers are required
+        assert!(matches!(
+            session.combine(&partials[..2]),
+            Err(ZkError::LengthMismatch(3, 2))
+        ));
+        let mut duplicated = partials.clone();
+        duplicated[2] = duplicated[0].clone();
+        assert!(matches!(
+            session.combine(&duplicated),
+            Err(ZkError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_rogue_key_attack_fails() {
+        let honest = Scalar::random(&mut OsRng);
+        let honest_key = PublicKey::from_secret(&honest);
+
+        // The attacker announces P_a = x*G - P_h, so that naively summing
+        // the keys gives x*G, which the attacker can sign for alone
+        let attacker = Scalar::random(&mut OsRng);
+        let target = RISTRETTO_BASEPOINT_POINT * attacker;
+        let rogue_key = PublicKey::from_point(&(target - honest_key.to_point()));
+        assert_eq!(honest_key.to_point() + rogue_key.to_point(), target);
+
+        let key = AggregatePublicKey::from(&[honest_key, rogue_key]);
+        assert_ne!(key.to_point(), target);
+
+        let forged = SchnorrProof::prove_knowledge(&attacker, &target, MESSAGE);
+        let forged = MultiSignature {
+            commitment: forged.commitment,
+            challenge: forged.challenge,
+            response: forged.response,
+        };
+        assert!(!forged.verify(&key, MESSAGE).unwrap());
+    }
+
+    #[test]
+    fn test_partial_signature_from_other_round_rejected() {
+        let secrets = secrets(2);
+        let key = AggregatePublicKey::from(keys(&secrets));
+
+        // Two rounds over the same message with fresh nonces
+        let (round_one, partials_one) = sign_all(&secrets, &key, MESSAGE);
+        let (nonces, commitments): (Vec<_>, Vec<_>) =
+            key.keys().iter().map(SecretNonce::generate).unzip();
+        let round_two = SigningSession::new(&key, &commitments, MESSAGE).unwrap();
+
+        let mut nonces = nonces.into_iter();
+        let first = nonces.next().unwrap();
+        let second = nonces.next().unwrap();
+        let partial_two = second.sign(&round_two, &secrets[1]).unwrap();
+
+        // Round one's partial signature does not verify in round two
+        assert!(!round_two.verify_partial(&partials_one[0]).unwrap());
+        assert!(matches!(
+            round_two.combine(&[partials_one[0].clone(), partial_two.clone()]),
+            Err(ZkError::InvalidProof(_))
+        ));
+
+        // A nonce committed to round two cannot sign round one
+        assert!(matches!(
+            first.sign(&round_one, &secrets[0]),
+            Err(ZkError::InvalidInput(_))
+        ));
+
+        // Nor can a signer use another signer's nonce
+        let (nonce, commitment) = SecretNonce::generate(&key.keys()[0]);
+        let session =
+            SigningSession::new(&key, &[commitment, commitments[1].clone()], MESSAGE).unwrap();
+        assert!(matches!(
+            nonce.sign(&session, &secrets[1]),
+            Err(ZkError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_session_rejects_bad_commitments() {
+        let secrets = secrets(2);
+        let key = AggregatePublicKey::from(keys(&secrets));
+        let (_, first) = SecretNonce::generate(&key.keys()[0]);
+        let (_, again) = SecretNonce::generate(&key.keys()[0]);
+        let (_, outsider) = SecretNonce::generate(&PublicKey::from_secret(&Scalar::ONE));
+
+        assert!(matches!(
+            SigningSession::new(&key, std::slice::from_ref(&first), MESSAGE),
+            Err(ZkError::LengthMismatch(2, 1))
+        ));
+        assert!(matches!(
+            SigningSession::new(&key, &[first.clone(), again], MESSAGE),
+            Err(ZkError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            SigningSession::new(&key, &[first, outsider], MESSAGE),
+            Err(ZkError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_transport_round_trips() {
+        let secrets = secrets(2);
+        let key = AggregatePublicKey::from(keys(&secrets));
+
+        let json = serde_json::to_string(&key).unwrap();
+        assert_eq!(
+            serde_json::from_str::<AggregatePublicKey>(&json).unwrap(),
+            key
+        );
+
+        let (nonce, commitment) = SecretNonce::generate(&key.keys()[0]);
+        let decoded: NonceCommitment =
+            envelope::from_bytes(&envelope::to_bytes(&commitment)).unwrap();
+        assert_eq!(decoded, commitment);
+        assert_eq!(nonce.commitment(), &commitment);
+
+        let (session, partials) = sign_all(&secrets, &key, MESSAGE);
+        let decoded: PartialSignature =
+            envelope::from_bytes(&envelope::to_bytes(&partials[0])).unwrap();
+        assert!(session.verify_partial(&decoded).unwrap());
+
+        let signature = session.combine(&partials).unwrap();
+        let decoded: MultiSignature =
+            envelope::from_bytes(&envelope::to_bytes(&signature)).unwrap();
+        assert!(decoded.verify(&key, MESSAGE).unwrap());
+        let json = serde_json::to_string(&signature).unwrap();
+        assert_eq!(
+            serde_json::from_str::<MultiSignature>(&json).unwrap(),
+            signature
+        );
+    }
+}
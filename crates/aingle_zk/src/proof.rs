@@ -12,26 +12,25 @@ use curve25519_dalek::{
 };
 use rand::rngs::OsRng;
 use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256, Sha512};
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+use zeroize::Zeroizing;
 
 use crate::commitment::HashCommitment;
+use crate::encoding;
+use crate::envelope::{ZkEncode, ZkKind};
 use crate::error::{Result, ZkError};
+use crate::generators::pedersen_h;
 use crate::merkle::{Hash, MerkleProof};
 
-/// Helper function to get second generator H (same as in commitment.rs)
-fn generator_h() -> RistrettoPoint {
-    let mut hasher = Sha512::new();
-    hasher.update(RISTRETTO_BASEPOINT_POINT.compress().as_bytes());
-    hasher.update(b"aingle_zk_pedersen_h");
-    RistrettoPoint::from_uniform_bytes(&hasher.finalize().into())
-}
-
 /// Schnorr proof of knowledge of discrete log
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SchnorrProof {
+    #[serde(with = "encoding::serde_point")]
     pub commitment: [u8; 32], // R = k*G
-    pub challenge: [u8; 32],  // c = H(R || P || message)
-    pub response: [u8; 32],   // s = k + c*x
+    pub challenge: [u8; 32], // c = H(R || P || message)
+    #[serde(with = "encoding::serde_scalar")]
+    pub response: [u8; 32], // s = k + c*x
 }
 
 impl SchnorrProof {
@@ -39,11 +38,11 @@ impl SchnorrProof {
     pub fn prove_knowledge(secret: &Scalar, public_point: &RistrettoPoint, message: &[u8]) -> Self {
         let g = RISTRETTO_BASEPOINT_POINT;
 
-        // 1. Generate random k
-        let k = Scalar::random(&mut OsRng);
+        // 1. Generate random k (wiped on return)
+        let k = Zeroizing::new(Scalar::random(&mut OsRng));
 
         // 2. Compute R = k*G
-        let r = g * k;
+        let r = g * *k;
         let r_bytes: [u8; 32] = r.compress().to_bytes();
 
         // 3. Compute challenge c = H(R || P || message)
@@ -55,7 +54,7 @@ impl SchnorrProof {
         let c = Scalar::from_bytes_mod_order(challenge_bytes);
 
         // 4. Compute response s = k + c*x
-        let s = k + c * secret;
+        let s = *k + c * secret;
         let s_bytes: [u8; 32] = s.to_bytes();
 
         SchnorrProof {
@@ -85,7 +84,7 @@ impl SchnorrProof {
         hasher.update(message);
         let expected_challenge: [u8; 32] = hasher.finalize().into();
 
-        if expected_challenge != self.challenge {
+        if !bool::from(expected_challenge.ct_eq(&self.challenge)) {
             return Ok(false);
         }
 
@@ -100,9 +99,12 @@ impl SchnorrProof {
 /// Proof that two Pedersen commitments hide the same value
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct EqualityProof {
+    #[serde(with = "encoding::serde_point")]
     pub commitment1: [u8; 32],
+    #[serde(with = "encoding::serde_point")]
     pub commitment2: [u8; 32],
     pub challenge: [u8; 32],
+    #[serde(with = "encoding::serde_scalar")]
     pub response: [u8; 32],
 }
 
@@ -116,13 +118,13 @@ impl EqualityProof {
         commitment2: &RistrettoPoint,
     ) -> Self {
         // Prove knowledge of (r1 - r2) such that C1 - C2 = (r1 - r2)*H
-        let h = generator_h();
+        let h = pedersen_h();
         let diff = commitment1 - commitment2; // Should equal (r1 - r2)*H
-        let r_diff = blinding1 - blinding2;
+        let r_diff = Zeroizing::new(blinding1 - blinding2);
 
         // Schnorr proof of knowledge of r_diff
-        let k = Scalar::random(&mut OsRng);
-        let r = h * k;
+        let k = Zeroizing::new(Scalar::random(&mut OsRng));
+        let r = h * *k;
 
         let mut hasher = Sha256::new();
         hasher.update(r.compress().as_bytes());
@@ -130,7 +132,7 @@ impl EqualityProof {
         let challenge: [u8; 32] = hasher.finalize().into();
         let c = Scalar::from_bytes_mod_order(challenge);
 
-        let response = k + c * r_diff;
+        let response = *k + c * *r_diff;
 
         EqualityProof {
             commitment1: commitment1.compress().to_bytes(),
@@ -142,7 +144,7 @@ impl EqualityProof {
 
     /// Verify equality proof
     pub fn verify(&self) -> Result<bool> {
-        let h = generator_h();
+        let h = pedersen_h();
 
         let c1 = CompressedRistretto::from_slice(&self.commitment1)
             .map_err(|_| ZkError::InvalidProof("Invalid C1".into()))?
@@ -166,7 +168,7 @@ impl EqualityProof {
         hasher.update(diff.compress().as_bytes());
         let computed_challenge: [u8; 32] = hasher.finalize().into();
 
-        Ok(computed_challenge == self.challenge)
+        Ok(bool::from(computed_challenge.ct_eq(&self.challenge)))
     }
 }
 
@@ -204,15 +206,19 @@ pub struct ZkProof {
 pub enum ProofData {
     /// Schnorr-like knowledge proof
     Knowledge {
+        #[serde(with = "encoding::serde_point")]
         commitment: [u8; 32],
         challenge: [u8; 32],
+        #[serde(with = "encoding::serde_scalar")]
         response: [u8; 32],
     },
     /// Merkle membership proof
     Membership { root: [u8; 32], proof: MerkleProof },
     /// Equality proof between commitments
     Equality {
+        #[serde(with = "encoding::serde_point")]
         commitment1: [u8; 32],
+        #[serde(with = "encoding::serde_point")]
         commitment2: [u8; 32],
         proof: Vec<u8>,
     },
@@ -306,7 +312,6 @@ impl ProofVerifier {
                 // Without data, we can only validate the proof structure is well-formed.
                 // Callers must use ProofVerifier::verify_hash_opening() with data
                 // for actual verification. This path returns false to be safe.
-                use subtle::ConstantTimeEq;
                 let non_zero_commitment = commitment.ct_ne(&[0u8; 32]);
                 let non_zero_salt = salt.ct_ne(&[0u8; 32]);
                 // Structural check only — reject zero commitment/salt as malformed
@@ -367,7 +372,6 @@ impl ProofVerifier {
     pub fn verify_hash_opening(proof: &ZkProof, data: &[u8]) -> Result<bool> {
         match &proof.proof_data {
             ProofData::HashOpening { commitment, salt } => {
-                use subtle::ConstantTimeEq;
                 let expected = HashCommitment::commit_with_salt(data, *salt);
                 Ok(bool::from(expected.hash.ct_eq(commitment)))
             }
@@ -401,7 +405,161 @@ impl ProofVerifier {
         hasher.update(commitment);
         let computed_challenge: [u8; 32] = hasher.finalize().into();
 
-        Ok(&computed_challenge == challenge)
+        Ok(bool::from(computed_challenge.ct_eq(challenge)))
+    }
+}
+
+impl ZkEncode for SchnorrProof {
+    const KIND: ZkKind = ZkKind::SchnorrProof;
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        encoding::put_array(out, &self.commitment);
+        encoding::put_array(out, &self.challenge);
+        encoding::put_array(out, &self.response);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self> {
+        Ok(Self {
+            commitment: encoding::get_point(input)?,
+            challenge: encoding::get_array(input)?,
+            response: encoding::get_scalar(input)?,
+        })
+    }
+}
+
+impl ZkEncode for EqualityProof {
+    const KIND: ZkKind = ZkKind::EqualityProof;
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        encoding::put_array(out, &self.commitment1);
+        encoding::put_array(out, &self.commitment2);
+        encoding::put_array(out, &self.challenge);
+        encoding::put_array(out, &self.response);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self> {
+        Ok(Self {
+            commitment1: encoding::get_point(input)?,
+            commitment2: encoding::get_point(input)?,
+            challenge: encoding::get_array(input)?,
+            response: encoding::get_scalar(input)?,
+        })
+    }
+}
+
+impl ZkEncode for ZkProof {
+    const KIND: ZkKind = ZkKind::ZkProof;
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        let proof_type = match self.proof_type {
+            ProofType::KnowledgeProof => 0,
+            ProofType::RangeProof => 1,
+            ProofType::MembershipProof => 2,
+            ProofType::EqualityProof => 3,
+            ProofType::NonMembershipProof => 4,
+        };
+        encoding::put_u8(out, proof_type);
+
+        match &self.proof_data {
+            ProofData::Knowledge {
+                commitment,
+                challenge,
+                response,
+            } => {
+                encoding::put_u8(out, 0);
+                encoding::put_array(out, commitment);
+                encoding::put_array(out, challenge);
+                encoding::put_array(out, response);
+            }
+            ProofData::Membership { root, proof } => {
+                encoding::put_u8(out, 1);
+                encoding::put_array(out, root);
+                proof.encode(out);
+            }
+            ProofData::Equality {
+                commitment1,
+                commitment2,
+                proof,
+            } => {
+                encoding::put_u8(out, 2);
+                encoding::put_array(out, commitment1);
+                encoding::put_array(out, commitment2);
+                encoding::put_bytes(out, proof);
+            }
+            ProofData::HashOpening { commitment, salt } => {
+                encoding::put_u8(out, 3);
+                encoding::put_array(out, commitment);
+                encoding::put_array(out, salt);
+            }
+        }
+
+        encoding::put_u64(out, self.timestamp);
+        encoding::put_bool(out, self.metadata.is_some());
+        if let Some(metadata) = &self.metadata {
+            let json = serde_json::to_vec(metadata).expect("JSON values always serialize");
+            encoding::put_bytes(out, &json);
+        }
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self> {
+        let proof_type = match encoding::get_u8(input)? {
+            0 => ProofType::KnowledgeProof,
+            1 => ProofType::RangeProof,
+            2 => ProofType::MembershipProof,
+            3 => ProofType::EqualityProof,
+            4 => ProofType::NonMembershipProof,
+            other => {
+                return Err(ZkError::SerializationError(format!(
+                    "unknown proof type {}",
+                    other
+                )))
+            }
+        };
+
+        let proof_data = match encoding::get_u8(input)? {
+            0 => ProofData::Knowledge {
+                commitment: encoding::get_point(input)?,
+                challenge: encoding::get_array(input)?,
+                response: encoding::get_scalar(input)?,
+            },
+            1 => ProofData::Membership {
+                root: encoding::get_array(input)?,
+                proof: MerkleProof::decode(input)?,
+            },
+            2 => ProofData::Equality {
+                commitment1: encoding::get_point(input)?,
+                commitment2: encoding::get_point(input)?,
+                proof: encoding::get_bytes(input)?,
+            },
+            3 => ProofData::HashOpening {
+                commitment: encoding::get_array(input)?,
+                salt: encoding::get_array(input)?,
+            },
+            other => {
+                return Err(ZkError::SerializationError(format!(
+                    "unknown proof data {}",
+                    other
+                )))
+            }
+        };
+
+        let timestamp = encoding::get_u64(input)?;
+        let metadata = if encoding::get_bool(input)? {
+            let json = encoding::get_bytes(input)?;
+            Some(
+                serde_json::from_slice(&json)
+                    .map_err(|e| ZkError::SerializationError(e.to_string()))?,
+            )
+        } else {
+            None
+        };
+
+        Ok(Self {
+            proof_type,
+            proof_data,
+            timestamp,
+            metadata,
+        })
     }
 }
 
@@ -618,7 +776,7 @@ mod tests {
         let r2 = Scalar::random(&mut OsRng);
 
         let g = RISTRETTO_BASEPOINT_POINT;
-        let h = generator_h();
+        let h = pedersen_h();
         let v = Scalar::from(value);
 
         let c1 = g * v + h * r1;
@@ -636,7 +794,7 @@ mod tests {
         let r2 = Scalar::random(&mut OsRng);
 
         let g = RISTRETTO_BASEPOINT_POINT;
-        let h = generator_h();
+        let h = pedersen_h();
 
         let c1 = g * Scalar::from(value1) + h * r1;
         let c2 = g * Scalar::from(value2) + h * r2;
@@ -695,7 +853,7 @@ mod tests {
         let r2 = Scalar::random(&mut OsRng);
 
         let g = RISTRETTO_BASEPOINT_POINT;
-        let h = generator_h();
+        let h = pedersen_h();
         let v = Scalar::from(value);
 
         let c1 = g * v + h * r1;
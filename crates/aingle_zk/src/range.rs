@@ -44,24 +44,18 @@ use bulletproofs::{BulletproofGens, PedersenGens, RangeProof as BPRangeProof};
 use merlin::Transcript;
 use rand::rngs::OsRng;
 use serde::{Deserialize, Serialize};
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
-// Note: bulletproofs uses curve25519-dalek-ng, we need to convert types
+// bulletproofs 5 is built on the same curve25519-dalek 4 as the rest of the crate
+use curve25519_dalek::ristretto::CompressedRistretto;
 use curve25519_dalek::scalar::Scalar;
-use curve25519_dalek_ng::ristretto::CompressedRistretto as CompressedRistrettoNG;
-use curve25519_dalek_ng::scalar::Scalar as ScalarNG;
 
 use crate::commitment::PedersenCommitment;
+use crate::encoding;
+use crate::envelope::{ZkEncode, ZkKind};
 use crate::error::{Result, ZkError};
 
-// Helper functions to convert between curve25519-dalek and curve25519-dalek-ng
-fn scalar_to_ng(s: &Scalar) -> ScalarNG {
-    ScalarNG::from_bytes_mod_order(s.to_bytes())
-}
-
-fn scalar_from_ng(s: &ScalarNG) -> Scalar {
-    Scalar::from_bytes_mod_order(s.to_bytes())
-}
-
 /// Range proof generators (cached for efficiency)
 ///
 /// Generators should be created once and reused for multiple proofs
@@ -165,8 +159,7 @@ impl RangeProofGenerator {
         }
 
         let mut rng = OsRng;
-        let blinding = Scalar::random(&mut rng);
-        let blinding_ng = scalar_to_ng(&blinding);
+        let blinding = Zeroizing::new(Scalar::random(&mut rng));
 
         let mut transcript = Transcript::new(b"aingle_range_proof");
 
@@ -175,7 +168,7 @@ impl RangeProofGenerator {
             &self.pc_gens,
             &mut transcript,
             value,
-            &blinding_ng,
+            &blinding,
             self.n_bits,
         )
         .map_err(|e| ZkError::CryptoError(format!("Range proof generation failed: {:?}", e)))?;
@@ -203,7 +196,6 @@ impl RangeProofGenerator {
             return Err(ZkError::InvalidRange(0, max_value));
         }
 
-        let blinding_ng = scalar_to_ng(blinding);
         let mut transcript = Transcript::new(b"aingle_range_proof");
 
         let (proof, commitment) = BPRangeProof::prove_single(
@@ -211,7 +203,7 @@ impl RangeProofGenerator {
             &self.pc_gens,
             &mut transcript,
             value,
-            &blinding_ng,
+            blinding,
             self.n_bits,
         )
         .map_err(|e| ZkError::CryptoError(format!("Range proof generation failed: {:?}", e)))?;
@@ -259,7 +251,7 @@ impl RangeProofGenerator {
         let bp_proof = BPRangeProof::from_bytes(&proof.proof_bytes)
             .map_err(|e| ZkError::InvalidProof(format!("Invalid proof bytes: {:?}", e)))?;
 
-        let commitment = CompressedRistrettoNG::from_slice(&proof.commitment);
+        let commitment = CompressedRistretto(proof.commitment);
 
         let mut transcript = Transcript::new(b"aingle_range_proof");
 
@@ -305,7 +297,7 @@ impl RangeProofGenerator {
             let bp_proof = BPRangeProof::from_bytes(&proof.proof_bytes)
                 .map_err(|e| ZkError::InvalidProof(format!("Invalid proof bytes: {:?}", e)))?;
 
-            let commitment = CompressedRistrettoNG::from_slice(&proof.commitment);
+            let commitment = CompressedRistretto(proof.commitment);
 
             bp_proofs.push(bp_proof);
             commitments.push(commitment);
@@ -348,11 +340,15 @@ impl RangeProofGenerator {
 ///
 /// # Serialization
 /// Proofs can be serialized to JSON or binary formats using serde.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// The embedded blinding factor is wiped on drop and never printed by
+/// `Debug`.
+#[derive(Clone, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
 pub struct RangeProof {
     /// Serialized bulletproof (inner product proof)
     pub proof_bytes: Vec<u8>,
     /// Pedersen commitment to the value (32-byte compressed point)
+    #[serde(with = "encoding::serde_point")]
     pub commitment: [u8; 32],
     /// Number of bits determining the range [0, 2^n_bits)
     pub n_bits: usize,
@@ -360,6 +356,7 @@ pub struct RangeProof {
     /// This is optional and should only be included when needed for opening
     #[serde(skip_serializing_if = "is_zero_bytes")]
     #[serde(default = "default_blinding")]
+    #[serde(with = "encoding::serde_scalar")]
     pub blinding: [u8; 32],
 }
 
@@ -439,12 +436,11 @@ impl RangeProof {
             return false; // No blinding available
         }
 
-        let blinding_ng = ScalarNG::from_bytes_mod_order(self.blinding);
+        let blinding = Zeroizing::new(Scalar::from_bytes_mod_order(self.blinding));
         let pc_gens = PedersenGens::default();
 
-        let value_ng = ScalarNG::from(value);
-        let expected = pc_gens.commit(value_ng, blinding_ng);
-        expected.compress().to_bytes() == self.commitment
+        let expected = pc_gens.commit(Scalar::from(value), *blinding);
+        bool::from(expected.compress().to_bytes().ct_eq(&self.commitment))
     }
 
     /// Serialize to compact binary format
@@ -520,7 +516,45 @@ impl RangeProof {
     }
 }
 
+impl std::fmt::Debug for RangeProof {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RangeProof")
+            .field("proof_bytes", &self.proof_bytes)
+            .field("commitment", &self.commitment)
+            .field("n_bits", &self.n_bits)
+            .field("blinding", &"<redacted>")
+            .finish()
+    }
+}
+
+impl ZkEncode for RangeProof {
+    const KIND: ZkKind = ZkKind::RangeProof;
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        encoding::put_usize(out, self.n_bits);
+        encoding::put_array(out, &self.commitment);
+        encoding::put_array(out, &self.blinding);
+        encoding::put_bytes(out, &self.proof_bytes);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self> {
+        let n_bits = encoding::get_usize(input)?;
+        let commitment = encoding::get_point(input)?;
+        let blinding = encoding::get_scalar(input)?;
+        let proof_bytes = encoding::get_bytes(input)?;
+        BPRangeProof::from_bytes(&proof_bytes)
+            .map_err(|e| ZkError::InvalidEncoding(format!("bulletproof: {:?}", e)))?;
+        Ok(Self {
+            proof_bytes,
+            commitment,
+            n_bits,
+            blinding,
+        })
+    }
+}
+
 /// Aggregated range proofs for multiple values
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AggregatedRangeProof {
     /// Individual proofs
     proofs: Vec<RangeProof>,
@@ -557,6 +591,25 @@ impl AggregatedRangeProof {
     }
 }
 
+impl ZkEncode for AggregatedRangeProof {
+    const KIND: ZkKind = ZkKind::AggregatedRangeProof;
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        encoding::put_usize(out, self.proofs.len());
+        for proof in &self.proofs {
+            proof.encode(out);
+        }
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self> {
+        let count = encoding::get_len(input, 80)?;
+        let proofs = (0..count)
+            .map(|_| RangeProof::decode(input))
+            .collect::<Result<_>>()?;
+        Ok(Self { proofs })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -615,4 +668,15 @@ mod tests {
         assert_eq!(proof.n_bits, deserialized.n_bits);
         assert_eq!(proof.commitment, deserialized.commitment);
     }
+
+    #[test]
+    fn test_range_proof_blinding_is_secret() {
+        fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+        assert_zeroize_on_drop::<RangeProof>();
+
+        let proof = RangeProofGenerator::new(8).prove(42).unwrap();
+        let debug = format!("{:?}", proof);
+        assert!(debug.contains("blinding: \"<redacted>\""));
+        assert!(!debug.contains(&format!("{:?}", proof.blinding)));
+    }
 }
@@ -0,0 +1,339 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Threshold proofs over Pedersen commitments
+//!
+//! Prove that a committed value is at most (or at least) a public
+//! threshold without revealing it, e.g. "the committed transaction amount
+//! is below the reporting threshold".
+//!
+//! For a commitment `C = v*G + r*H` and threshold `T`, the prover builds a
+//! commitment to the difference:
+//!
+//! - below: `D = T*G - C = (T - v)*G + (-r)*H`
+//! - above: `D = C - T*G = (v - T)*G + r*H`
+//!
+//! and proves with a Bulletproof that `D` opens to a 64-bit value. The
+//! verifier derives `D` from `C` and `T` itself, and the transcript binds
+//! the direction, `C` and `T`, so a proof cannot be replayed against a
+//! different commitment or threshold.
+//!
+//! The proof shows `T - v` (or `v - T`) is in `[0, 2^64)` modulo the group
+//! order. To rule out wrap-around for an arbitrary committed scalar, pair
+//! it with a [`RangeProof`](crate::range::RangeProof) on `C` itself.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use aingle_zk::{PedersenCommitment, ThresholdProof};
+//!
+//! let (commitment, opening) = PedersenCommitment::commit(9_500);
+//!
+//! let proof = ThresholdProof::prove_below(9_500, 10_000, &opening).unwrap();
+//! assert!(proof.verify(&commitment, 10_000).unwrap());
+//! assert!(!proof.verify(&commitment, 9_000).unwrap());
+//! ```
+//!
+//! This module requires the `bulletproofs` feature.
+
+use bulletproofs::{BulletproofGens, PedersenGens, RangeProof as BPRangeProof};
+use curve25519_dalek::{constants::RISTRETTO_BASEPOINT_POINT, scalar::Scalar};
+use merlin::Transcript;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::commitment::{CommitmentOpening, PedersenCommitment};
+use crate::encoding;
+use crate::envelope::{ZkEncode, ZkKind};
+use crate::error::{Result, ZkError};
+use crate::generators;
+
+/// Bits of the range proof on the difference
+const DIFFERENCE_BITS: usize = 64;
+
+/// Pedersen generators matching [`PedersenCommitment`]
+fn pedersen_gens() -> PedersenGens {
+    PedersenGens {
+        B: RISTRETTO_BASEPOINT_POINT,
+        B_blinding: generators::pedersen_h(),
+    }
+}
+
+/// Which side of the threshold the committed value lies on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ThresholdDirection {
+    /// The value is at most the threshold
+    Below,
+    /// The value is at least the threshold
+    Above,
+}
+
+impl ThresholdDirection {
+    fn label(&self) -> &'static [u8] {
+        match self {
+            ThresholdDirection::Below => b"below",
+            ThresholdDirection::Above => b"above",
+        }
+    }
+}
+
+/// Proof that a committed value is at most or at least a threshold
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThresholdProof {
+    /// Side of the threshold proven
+    pub direction: ThresholdDirection,
+    /// Threshold the proof was made against
+    pub threshold: u64,
+    /// Serialized bulletproof over the difference commitment
+    pub proof_bytes: Vec<u8>,
+}
+
+impl ThresholdProof {
+    /// Prove that the value committed with `opening` is at most `threshold`
+    ///
+    /// The commitment is `PedersenCommitment::commit_with_blinding(value,
+    /// &opening.to_scalar())`.
+    ///
+    /// # Errors
+    /// Returns `ZkError::InvalidInput` if `value > threshold`
+    pub fn prove_below(value: u64, threshold: u64, opening: &CommitmentOpening) -> Result<Self> {
+        if value > threshold {
+            return Err(ZkError::InvalidInput(format!(
+                "Value is above the threshold {}",
+                threshold
+            )));
+        }
+        let blinding = Zeroizing::new(opening.to_scalar());
+        Self::prove(
+            ThresholdDirection::Below,
+            threshold,
+            &PedersenCommitment::commit_with_blinding(value, &blinding),
+            threshold - value,
+            &Zeroizing::new(-*blinding),
+        )
+    }
+
+    /// Prove that the value committed with `opening` is at least `threshold`
+    ///
+    /// # Errors
+    /// Returns `ZkError::InvalidInput` if `value < threshold`
+    pub fn prove_above(value: u64, threshold: u64, opening: &CommitmentOpening) -> Result<Self> {
+        if value < threshold {
+            return Err(ZkError::InvalidInput(format!(
+                "Value is below the threshold {}",
+                threshold
+            )));
+        }
+        let blinding = Zeroizing::new(opening.to_scalar());
+        Self::prove(
+            ThresholdDirection::Above,
+            threshold,
+            &PedersenCommitment::commit_with_blinding(value, &blinding),
+            value - threshold,
+            &blinding,
+        )
+    }
+
+    fn prove(
+        direction: ThresholdDirection,
+        threshold: u64,
+        commitment: &PedersenCommitment,
+        difference: u64,
+        blinding: &Scalar,
+    ) -> Result<Self> {
+        let bp_gens = BulletproofGens::new(DIFFERENCE_BITS, 1);
+        let mut transcript = transcript(direction, commitment, threshold);
+
+        let (proof, _) = BPRangeProof::prove_single(
+            &bp_gens,
+            &pedersen_gens(),
+            &mut transcript,
+            difference,
+            blinding,
+            DIFFERENCE_BITS,
+        )
+        .map_err(|e| ZkError::CryptoError(format!("Threshold proof generation failed: {:?}", e)))?;
+
+        Ok(Self {
+            direction,
+            threshold,
+            proof_bytes: proof.to_bytes(),
+        })
+    }
+
+    /// Verify the proof against a commitment and threshold
+    ///
+    /// Returns `Ok(false)` if `threshold` is not the one proven against
+    /// or the committed value is on the wrong side of it.
+    ///
+    /// # Errors
+    /// Returns an error if the commitment or proof bytes are malformed
+    pub fn verify(&self, commitment: &PedersenCommitment, threshold: u64) -> Result<bool> {
+        if threshold != self.threshold {
+            return Ok(false);
+        }
+
+        let c = commitment
+            .to_point()
+            .ok_or_else(|| ZkError::InvalidInput("Invalid commitment point".into()))?;
+        let t = RISTRETTO_BASEPOINT_POINT * Scalar::from(threshold);
+        let difference = match self.direction {
+            ThresholdDirection::Below => t - c,
+            ThresholdDirection::Above => c - t,
+        };
+
+        let bp_proof = BPRangeProof::from_bytes(&self.proof_bytes)
+            .map_err(|e| ZkError::InvalidProof(format!("Invalid proof bytes: {:?}", e)))?;
+
+        let bp_gens = BulletproofGens::new(DIFFERENCE_BITS, 1);
+        let mut transcript = transcript(self.direction, commitment, threshold);
+
+        Ok(bp_proof
+            .verify_single(
+                &bp_gens,
+                &pedersen_gens(),
+                &mut transcript,
+                &difference.compress(),
+                DIFFERENCE_BITS,
+            )
+            .is_ok())
+    }
+}
+
+/// Transcript binding the statement into the proof
+fn transcript(
+    direction: ThresholdDirection,
+    commitment: &PedersenCommitment,
+    threshold: u64,
+) -> Transcript {
+    let mut transcript = Transcript::new(b"aingle_threshold_proof");
+    transcript.append_message(b"direction", direction.label());
+    transcript.append_message(b"commitment", &commitment.point);
+    transcript.append_u64(b"threshold", threshold);
+    transcript
+}
+
+impl ZkEncode for ThresholdProof {
+    const KIND: ZkKind = ZkKind::ThresholdProof;
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        encoding::put_bool(out, self.direction == ThresholdDirection::Above);
+        encoding::put_u64(out, self.threshold);
+        encoding::put_bytes(out, &self.proof_bytes);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self> {
+        let direction = if encoding::get_bool(input)? {
+            ThresholdDirection::Above
+        } else {
+            ThresholdDirection::Below
+        };
+        let threshold = encoding::get_u64(input)?;
+        let proof_bytes = encoding::get_bytes(input)?;
+        BPRangeProof::from_bytes(&proof_bytes)
+            .map_err(|e| ZkError::InvalidEncoding(format!("bulletproof: {:?}", e)))?;
+        Ok(Self {
+            direction,
+            threshold,
+            proof_bytes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(value: u64) -> (PedersenCommitment, CommitmentOpening) {
+        PedersenCommitment::commit(value)
+    }
+
+    #[test]
+    fn test_prove_below() {
+        let (commitment, opening) = commit(9_500);
+        let proof = ThresholdProof::prove_below(9_500, 10_000, &opening).unwrap();
+
+        assert_eq!(proof.direction, ThresholdDirection::Below);
+        assert!(proof.verify(&commitment, 10_000).unwrap());
+
+        let (other, _) = commit(9_500);
+        assert!(!proof.verify(&other, 10_000).unwrap());
+    }
+
+    #[test]
+    fn test_prove_above() {
+        let (commitment, opening) = commit(250);
+        let proof = ThresholdProof::prove_above(250, 18, &opening).unwrap();
+
+        assert_eq!(proof.direction, ThresholdDirection::Above);
+        assert!(proof.verify(&commitment, 18).unwrap());
+        assert!(!proof.verify(&commitment, 17).unwrap());
+    }
+
+    #[test]
+    fn test_boundary() {
+        let (commitment, opening) = commit(1_000);
+
+        let below = ThresholdProof::prove_below(1_000, 1_000, &opening).unwrap();
+        assert!(below.verify(&commitment, 1_000).unwrap());
+        let above = ThresholdProof::prove_above(1_000, 1_000, &opening).unwrap();
+        assert!(above.verify(&commitment, 1_000).unwrap());
+
+        assert!(ThresholdProof::prove_below(1_001, 1_000, &opening).is_err());
+        assert!(ThresholdProof::prove_above(999, 1_000, &opening).is_err());
+
+        let (zero, opening) = commit(0);
+        let proof = ThresholdProof::prove_below(0, 0, &opening).unwrap();
+        assert!(proof.verify(&zero, 0).unwrap());
+
+        let (max, opening) = commit(u64::MAX);
+        let proof = ThresholdProof::prove_above(u64::MAX, u64::MAX, &opening).unwrap();
+        assert!(proof.verify(&max, u64::MAX).unwrap());
+        let proof = ThresholdProof::prove_below(u64::MAX, u64::MAX, &opening).unwrap();
+        assert!(proof.verify(&max, u64::MAX).unwrap());
+    }
+
+    #[test]
+    fn test_threshold_is_bound() {
+        let (commitment, opening) = commit(500);
+        let proof = ThresholdProof::prove_below(500, 1_000, &opening).unwrap();
+
+        // Relabelling the proof does not move the threshold it proves
+        for threshold in [999, 1_001, 500, 499] {
+            let mut replayed = proof.clone();
+            replayed.threshold = threshold;
+            assert!(!replayed.verify(&commitment, threshold).unwrap());
+        }
+
+        let mut flipped = proof.clone();
+        flipped.direction = ThresholdDirection::Above;
+        assert!(!flipped.verify(&commitment, 1_000).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let (commitment, opening) = commit(5);
+        let mut proof = ThresholdProof::prove_below(5, 10, &opening).unwrap();
+
+        let bad_point = PedersenCommitment::from_bytes([0xff; 32]);
+        assert!(proof.verify(&bad_point, 10).is_err());
+
+        proof.proof_bytes.truncate(10);
+        assert!(proof.verify(&commitment, 10).is_err());
+    }
+
+    #[test]
+    fn test_serialization() {
+        let (commitment, opening) = commit(42);
+        let proof = ThresholdProof::prove_above(42, 40, &opening).unwrap();
+
+        let json = serde_json::to_string(&proof).unwrap();
+        let from_json: ThresholdProof = serde_json::from_str(&json).unwrap();
+        assert!(from_json.verify(&commitment, 40).unwrap());
+
+        let bytes = crate::envelope::to_bytes(&proof);
+        let decoded: ThresholdProof = crate::envelope::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.direction, ThresholdDirection::Above);
+        assert!(decoded.verify(&commitment, 40).unwrap());
+    }
+}
@@ -0,0 +1,467 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Pedersen vector commitments
+//!
+//! Commits to a whole vector of values with a single group element, and
+//! later opens any one position without revealing the others.
+//!
+//! A commitment to `v_0, ..., v_{n-1}` with blinding factor `r` is:
+//! `C = v_0*G_0 + ... + v_{n-1}*G_{n-1} + r*H`
+//!
+//! Each index has its own generator `G_i`, derived by hashing the index to
+//! the curve under a dedicated domain (see [`crate::generators`]), so nobody
+//! knows the discrete log relations between them. To open position `j` to
+//! `v_j`, the prover shows that `C - v_j*G_j` is a commitment to the
+//! remaining values, using a Schnorr-style proof of knowledge that hides
+//! them.
+
+use curve25519_dalek::{
+    ristretto::{CompressedRistretto, RistrettoPoint},
+    scalar::Scalar,
+    traits::{Identity, MultiscalarMul, VartimeMultiscalarMul},
+};
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha512};
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use crate::encoding;
+use crate::envelope::{ZkEncode, ZkKind};
+use crate::error::{Result, ZkError};
+use crate::generators::{pedersen_h, GeneratorSet, LEGACY_VECTOR_DOMAIN};
+
+/// Domain separator for position opening challenges
+const OPENING_DOMAIN: &[u8] = b"aingle_zk_vector_opening";
+
+/// Generators `G_0, ..., G_{len-1}`, one per index
+fn generators(len: usize) -> GeneratorSet {
+    GeneratorSet::new(LEGACY_VECTOR_DOMAIN, len)
+}
+
+/// Fiat-Shamir challenge for opening `index` of `commitment` to `value`
+fn opening_challenge(
+    commitment: &[u8; 32],
+    len: usize,
+    index: usize,
+    value: u64,
+    announcement: &[u8; 32],
+) -> Scalar {
+    let mut hasher = Sha512::new();
+    hasher.update(OPENING_DOMAIN);
+    hasher.update(commitment);
+    hasher.update((len as u64).to_le_bytes());
+    hasher.update((index as u64).to_le_bytes());
+    hasher.update(value.to_le_bytes());
+    hasher.update(announcement);
+    Scalar::from_bytes_mod_order_wide(&hasher.finalize().into())
+}
+
+/// Pedersen commitment to a vector of values
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct VectorCommitment {
+    /// Compressed point representation
+    #[serde(with = "encoding::serde_point")]
+    pub point: [u8; 32],
+    /// Number of committed values
+    pub len: usize,
+}
+
+/// Opening information for a vector commitment (kept by the committer)
+///
+/// Wiped on drop and compared in constant time; `Debug` shows only the
+/// number of values.
+#[derive(Clone, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
+pub struct VectorOpening {
+    /// The committed values
+    pub values: Vec<u64>,
+    /// The blinding factor (randomness)
+    #[serde(with = "encoding::serde_scalar")]
+    pub blinding: [u8; 32],
+}
+
+/// Proof that one position of a vector commitment holds a given value
+///
+/// Reveals nothing about the values at other positions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionOpening {
+    /// Announcement `A = sum(k_i*G_i for i != index) + k_r*H`
+    #[serde(with = "encoding::serde_point")]
+    pub announcement: [u8; 32],
+    /// Responses `s_i = k_i + c*v_i` for every other index, in order
+    #[serde(with = "encoding::serde_scalars")]
+    pub responses: Vec<[u8; 32]>,
+    /// Response `s_r = k_r + c*r` for the blinding factor
+    #[serde(with = "encoding::serde_scalar")]
+    pub blinding_response: [u8; 32],
+}
+
+impl VectorCommitment {
+    /// Create a commitment to a vector of values
+    ///
+    /// Returns the commitment and opening (values and blinding factor)
+    pub fn commit(values: &[u64]) -> (Self, VectorOpening) {
+        let blinding = Zeroizing::new(Scalar::random(&mut OsRng));
+        let commitment = Self::commit_with_blinding(values, &blinding);
+
+        let opening = VectorOpening {
+            values: values.to_vec(),
+            blinding: blinding.to_bytes(),
+        };
+
+        (commitment, opening)
+    }
+
+    /// Create a commitment with a specific blinding factor
+    pub fn commit_with_blinding(values: &[u64], blinding: &Scalar) -> Self {
+        let point = commitment_point(values, blinding);
+
+        Self {
+            point: point.compress().to_bytes(),
+            len: values.len(),
+        }
+    }
+
+    /// Verify that position `index` holds `value`
+    ///
+    /// Returns an error if `index` is out of range or the opening was made
+    /// for a vector of a different length.
+    pub fn verify_at(&self, index: usize, value: u64, opening: &PositionOpening) -> Result<bool> {
+        if index >= self.len {
+            return Err(ZkError::IndexOutOfRange(index, self.len));
+        }
+        if opening.responses.len() != self.len - 1 {
+            return Err(ZkError::LengthMismatch(
+                self.len,
+                opening.responses.len() + 1,
+            ));
+        }
+
+        let commitment = decompress(&self.point, "commitment")?;
+        let announcement = decompress(&opening.announcement, "announcement")?;
+        let c = opening_challenge(&self.point, self.len, index, value, &opening.announcement);
+
+        // sum(s_i*G_i) + s_r*H == A + c*(C - v*G_index)
+        let gens = generators(self.len);
+        let mut scalars = Vec::with_capacity(self.len + 3);
+        let mut points = Vec::with_capacity(self.len + 3);
+
+        let others = gens
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != index)
+            .map(|(_, g)| g);
+        for (response, g) in opening.responses.iter().zip(others) {
+            scalars.push(Scalar::from_bytes_mod_order(*response));
+            points.push(*g);
+        }
+        scalars.push(Scalar::from_bytes_mod_order(opening.blinding_response));
+        points.push(pedersen_h());
+
+        scalars.push(-Scalar::ONE);
+        points.push(announcement);
+        scalars.push(-c);
+        points.push(commitment);
+        scalars.push(c * Scalar::from(value));
+        points.push(gens[index]);
+
+        let check = RistrettoPoint::vartime_multiscalar_mul(scalars, points);
+        Ok(check == RistrettoPoint::identity())
+    }
+
+    /// Get the commitment as a RistrettoPoint
+    pub fn to_point(&self) -> Option<RistrettoPoint> {
+        CompressedRistretto::from_slice(&self.point)
+            .ok()
+            .and_then(|c| c.decompress())
+    }
+}
+
+impl VectorOpening {
+    /// Get the blinding factor as a scalar
+    pub fn to_scalar(&self) -> Scalar {
+        Scalar::from_bytes_mod_order(self.blinding)
+    }
+
+    /// Open position `index` without revealing the other values
+    pub fn open_at(&self, index: usize) -> Result<PositionOpening> {
+        let len = self.values.len();
+        if index >= len {
+            return Err(ZkError::IndexOutOfRange(index, len));
+        }
+
+        let gens = generators(len);
+        let h = pedersen_h();
+        let blinding = Zeroizing::new(self.to_scalar());
+        let commitment = commitment_point(&self.values, &blinding)
+            .compress()
+            .to_bytes();
+
+        // Prove knowledge of the other values and r behind C - v_index*G_index
+        // (nonces are wiped on return)
+        let others: Vec<usize> = (0..len).filter(|&i| i != index).collect();
+        let nonces: Zeroizing<Vec<Scalar>> =
+            Zeroizing::new(others.iter().map(|_| Scalar::random(&mut OsRng)).collect());
+        let blinding_nonce = Zeroizing::new(Scalar::random(&mut OsRng));
+
+        let announcement = RistrettoPoint::multiscalar_mul(
+            nonces.iter().chain(std::iter::once(&*blinding_nonce)),
+            others.iter().map(|&i| gens[i]).chain(std::iter::once(h)),
+        )
+        .compress()
+        .to_bytes();
+
+        let c = opening_challenge(&commitment, len, index, self.values[index], &announcement);
+
+        let responses = others
+            .iter()
+            .zip(nonces.iter())
+            .map(|(&i, k)| (k + c * Scalar::from(self.values[i])).to_bytes())
+            .collect();
+
+        Ok(PositionOpening {
+            announcement,
+            responses,
+            blinding_response: (*blinding_nonce + c * *blinding).to_bytes(),
+        })
+    }
+}
+
+impl ConstantTimeEq for VectorOpening {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.values.as_slice().ct_eq(other.values.as_slice()) & self.blinding.ct_eq(&other.blinding)
+    }
+}
+
+impl PartialEq for VectorOpening {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for VectorOpening {}
+
+impl std::fmt::Debug for VectorOpening {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VectorOpening")
+            .field("len", &self.values.len())
+            .field("values", &"<redacted>")
+            .field("blinding", &"<redacted>")
+            .finish()
+    }
+}
+
+/// `C = sum(v_i*G_i) + r*H`
+fn commitment_point(values: &[u64], blinding: &Scalar) -> RistrettoPoint {
+    let gens = generators(values.len());
+    RistrettoPoint::multiscalar_mul(
+        values
+            .iter()
+            .map(|&v| Scalar::from(v))
+            .chain(std::iter::once(*blinding)),
+        gens.iter().copied().chain(std::iter::once(pedersen_h())),
+    )
+}
+
+fn decompress(bytes: &[u8; 32], what: &str) -> Result<RistrettoPoint> {
+    CompressedRistretto::from_slice(bytes)
+        .map_err(|_| ZkError::InvalidProof(format!("Invalid {}", what)))?
+        .decompress()
+        .ok_or_else(|| ZkError::InvalidProof(format!("Cannot decompress {}", what)))
+}
+
+impl ZkEncode for VectorCommitment {
+    const KIND: ZkKind = ZkKind::VectorCommitment;
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        encoding::put_array(out, &self.point);
+        encoding::put_usize(out, self.len);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self> {
+        Ok(Self {
+            point: encoding::get_point(input)?,
+            len: encoding::get_usize(input)?,
+        })
+    }
+}
+
+impl ZkEncode for VectorOpening {
+    const KIND: ZkKind = ZkKind::VectorOpening;
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        encoding::put_usize(out, self.values.len());
+        for value in &self.values {
+            encoding::put_u64(out, *value);
+        }
+        encoding::put_array(out, &self.blinding);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self> {
+        let count = encoding::get_len(input, 8)?;
+        let values = (0..count)
+            .map(|_| encoding::get_u64(input))
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            values,
+            blinding: encoding::get_scalar(input)?,
+        })
+    }
+}
+
+impl ZkEncode for PositionOpening {
+    const KIND: ZkKind = ZkKind::PositionOpening;
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        encoding::put_array(out, &self.announcement);
+        encoding::put_usize(out, self.responses.len());
+        for response in &self.responses {
+            encoding::put_array(out, response);
+        }
+        encoding::put_array(out, &self.blinding_response);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self> {
+        let announcement = encoding::get_point(input)?;
+        let count = encoding::get_len(input, 32)?;
+        let responses = (0..count)
+            .map(|_| encoding::get_scalar(input))
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            announcement,
+            responses,
+            blinding_response: encoding::get_scalar(input)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    fn random_values(len: usize) -> Vec<u64> {
+        let mut rng = rand::thread_rng();
+        (0..len).map(|_| rng.gen()).collect()
+    }
+
+    #[test]
+    fn test_open_every_index() {
+        let values = random_values(64);
+        let (commitment, opening) = VectorCommitment::commit(&values);
+
+        assert_eq!(commitment.len, 64);
+        for (index, &value) in values.iter().enumerate() {
+            let proof = opening.open_at(index).unwrap();
+            assert!(commitment.verify_at(index, value, &proof).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_flipped_value_or_index_fails() {
+        let mut rng = rand::thread_rng();
+
+        for len in [1usize, 2, 8, 64] {
+            let values = random_values(len);
+            let (commitment, opening) = VectorCommitment::commit(&values);
+
+            for (index, &value) in values.iter().enumerate() {
+                let proof = opening.open_at(index).unwrap();
+
+                // Any other value at the same index
+                let bit = 1u64 << rng.gen_range(0..64);
+                assert!(!commitment.verify_at(index, value ^ bit, &proof).unwrap());
+
+                // The same value claimed at any other index
+                for other in (0..len).filter(|&i| i != index) {
+                    assert!(!commitment.verify_at(other, value, &proof).unwrap());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_tampered_opening_fails() {
+        let values = random_values(16);
+        let (commitment, opening) = VectorCommitment::commit(&values);
+        let proof = opening.open_at(5).unwrap();
+
+        let mut tampered = proof.clone();
+        tampered.responses[3][0] ^= 1;
+        assert!(!commitment.verify_at(5, values[5], &tampered).unwrap());
+
+        let mut tampered = proof.clone();
+        tampered.blinding_response[0] ^= 1;
+        assert!(!commitment.verify_at(5, values[5], &tampered).unwrap());
+
+        // A different commitment to the same values
+        let (other, _) = VectorCommitment::commit(&values);
+        assert!(!other.verify_at(5, values[5], &proof).unwrap());
+    }
+
+    #[test]
+    fn test_out_of_range_and_length_mismatch() {
+        let (commitment, opening) = VectorCommitment::commit(&[1, 2, 3]);
+
+        assert!(matches!(
+            opening.open_at(3),
+            Err(ZkError::IndexOutOfRange(3, 3))
+        ));
+
+        let proof = opening.open_at(0).unwrap();
+        assert!(matches!(
+            commitment.verify_at(7, 1, &proof),
+            Err(ZkError::IndexOutOfRange(7, 3))
+        ));
+
+        let (longer, longer_opening) = VectorCommitment::commit(&[1, 2, 3, 4]);
+        let longer_proof = longer_opening.open_at(0).unwrap();
+        assert!(matches!(
+            commitment.verify_at(0, 1, &longer_proof),
+            Err(ZkError::LengthMismatch(3, 4))
+        ));
+        assert!(matches!(
+            longer.verify_at(0, 1, &proof),
+            Err(ZkError::LengthMismatch(4, 3))
+        ));
+
+        let (empty, empty_opening) = VectorCommitment::commit(&[]);
+        assert_eq!(empty.len, 0);
+        assert!(matches!(
+            empty_opening.open_at(0),
+            Err(ZkError::IndexOutOfRange(0, 0))
+        ));
+    }
+
+    #[test]
+    fn test_commitment_is_single_point() {
+        let values = random_values(64);
+        let blinding = Scalar::random(&mut OsRng);
+
+        let c1 = VectorCommitment::commit_with_blinding(&values, &blinding);
+        let c2 = VectorCommitment::commit_with_blinding(&values, &blinding);
+        assert_eq!(c1, c2);
+        assert!(c1.to_point().is_some());
+
+        let json = serde_json::to_string(&c1).unwrap();
+        let deserialized: VectorCommitment = serde_json::from_str(&json).unwrap();
+        assert_eq!(c1, deserialized);
+    }
+
+    #[test]
+    fn test_opening_secrets() {
+        fn assert_secret<T: ZeroizeOnDrop + ConstantTimeEq + Eq>() {}
+        assert_secret::<VectorOpening>();
+
+        let (_, opening) = VectorCommitment::commit(&[3, 1, 4]);
+        assert_eq!(
+            format!("{:?}", opening),
+            "VectorOpening { len: 3, values: \"<redacted>\", blinding: \"<redacted>\" }"
+        );
+
+        let (_, other) = VectorCommitment::commit(&[3, 1, 4]);
+        assert!(bool::from(opening.ct_eq(&opening.clone())));
+        assert_ne!(opening, other);
+    }
+}
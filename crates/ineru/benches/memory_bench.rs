@@ -16,7 +16,7 @@ fn bench_stm_store(c: &mut Criterion) {
 
     for size in [10, 100, 1000].iter() {
         group.bench_with_input(BenchmarkId::new("entries", size), size, |b, &size| {
-            let mut memory = IneruMemory::iot_mode();
+            let memory = IneruMemory::iot_mode();
             let entries: Vec<_> = (0..size)
                 .map(|i| MemoryEntry::new("sensor", serde_json::json!({"value": i})))
                 .collect();
@@ -37,7 +37,7 @@ fn bench_stm_recall(c: &mut Criterion) {
     let mut group = c.benchmark_group("STM Recall");
 
     // Prepare memory with data
-    let mut memory = IneruMemory::agent_mode();
+    let memory = IneruMemory::agent_mode();
     for i in 0..500 {
         let entry = MemoryEntry::new("sensor", serde_json::json!({"value": i}))
             .with_tags(&["temperature", "iot"]);
@@ -67,7 +67,7 @@ fn bench_consolidation(c: &mut Criterion) {
     group.bench_function("consolidate_100", |b| {
         b.iter_batched(
             || {
-                let mut memory = IneruMemory::new(MemoryConfig {
+                let memory = IneruMemory::new(MemoryConfig {
                     stm: StmConfig {
                         max_entries: 200,
                         ..Default::default()
@@ -80,6 +80,7 @@ fn bench_consolidation(c: &mut Criterion) {
                         batch_size: 50,
                         ..Default::default()
                     },
+                    ..Default::default()
                 });
 
                 // Fill with important entries
@@ -91,7 +92,7 @@ fn bench_consolidation(c: &mut Criterion) {
                 }
                 memory
             },
-            |mut memory| black_box(memory.consolidate()),
+            |memory| black_box(memory.consolidate()),
             criterion::BatchSize::SmallInput,
         );
     });
@@ -107,7 +108,7 @@ fn bench_decay(c: &mut Criterion) {
         group.bench_with_input(BenchmarkId::new("entries", size), size, |b, &size| {
             b.iter_batched(
                 || {
-                    let mut memory = IneruMemory::new(MemoryConfig {
+                    let memory = IneruMemory::new(MemoryConfig {
                         stm: StmConfig {
                             max_entries: size + 100,
                             decay_interval: std::time::Duration::from_secs(0),
@@ -122,7 +123,7 @@ fn bench_decay(c: &mut Criterion) {
                     }
                     memory
                 },
-                |mut memory| black_box(memory.decay()),
+                |memory| black_box(memory.decay()),
                 criterion::BatchSize::SmallInput,
             );
         });
@@ -138,7 +139,7 @@ fn bench_memory_size(c: &mut Criterion) {
     group.bench_function("iot_mode_1000_entries", |b| {
         b.iter_batched(
             || {
-                let mut memory = IneruMemory::iot_mode();
+                let memory = IneruMemory::iot_mode();
                 for i in 0..1000 {
                     let entry = MemoryEntry::new(
                         "sensor",
@@ -166,7 +167,7 @@ fn bench_iot_mode(c: &mut Criterion) {
     let mut group = c.benchmark_group("IoT Mode");
 
     group.bench_function("sensor_reading_cycle", |b| {
-        let mut memory = IneruMemory::iot_mode();
+        let memory = IneruMemory::iot_mode();
         let mut counter = 0u64;
 
         b.iter(|| {
@@ -3,7 +3,10 @@
 
 //! Configuration for the Ineru memory system.
 
+use crate::importance::ImportanceScorer;
+use crate::types::SemanticTag;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use std::time::Duration;
 
 /// Main configuration for the `IneruMemory` system.
@@ -18,6 +21,17 @@ pub struct MemoryConfig {
     pub ltm: LtmConfig,
     /// Configuration for the consolidation process.
     pub consolidation: ConsolidationConfig,
+    /// Tag-based retention policies applied by `IneruMemory::apply_retention`.
+    ///
+    /// Empty by default: memories are kept until they are forgotten or pruned.
+    #[serde(default)]
+    pub retention: Vec<RetentionPolicy>,
+    /// Rates memories stored without an explicit importance.
+    ///
+    /// `None` by default: such memories keep the default importance of `0.5`.
+    /// Not serialized; set it again after loading a configuration.
+    #[serde(skip)]
+    pub importance_scorer: Option<Arc<dyn ImportanceScorer>>,
 }
 
 impl MemoryConfig {
@@ -49,6 +63,8 @@ impl MemoryConfig {
                 max_stm_before_consolidate: 40,
                 batch_size: 5,
             },
+            retention: Vec::new(),
+            importance_scorer: None,
         }
     }
 
@@ -80,6 +96,8 @@ impl MemoryConfig {
                 max_stm_before_consolidate: 400,
                 batch_size: 20,
             },
+            retention: Vec::new(),
+            importance_scorer: None,
         }
     }
 
@@ -111,8 +129,74 @@ impl MemoryConfig {
                 max_stm_before_consolidate: 4000,
                 batch_size: 100,
             },
+            retention: Vec::new(),
+            importance_scorer: None,
+        }
+    }
+
+    /// Adds a retention policy deleting memories tagged with `tag_pattern`
+    /// once they are older than `max_age`.
+    pub fn with_retention(mut self, tag_pattern: &str, max_age: Duration) -> Self {
+        self.retention
+            .push(RetentionPolicy::new(tag_pattern, max_age));
+        self
+    }
+
+    /// Sets the scorer rating memories stored without an explicit importance.
+    pub fn with_importance_scorer(mut self, scorer: impl ImportanceScorer + 'static) -> Self {
+        self.importance_scorer = Some(Arc::new(scorer));
+        self
+    }
+}
+
+/// A rule deleting memories with matching tags once they reach a maximum age.
+///
+/// Retention is a hard deletion, typically for privacy: expired memories are
+/// removed from both STM and LTM together with their embeddings and any
+/// knowledge-graph entities and links extracted only from them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    /// The tag to match. `*` matches any run of characters, so `"user_*"`
+    /// matches both `"user_msg"` and `"user_profile"`. Matching is
+    /// case-insensitive, like `SemanticTag`.
+    pub tag_pattern: String,
+    /// How long a matching memory is kept after its creation.
+    pub max_age: Duration,
+}
+
+impl RetentionPolicy {
+    /// Creates a new retention policy.
+    pub fn new(tag_pattern: &str, max_age: Duration) -> Self {
+        Self {
+            tag_pattern: tag_pattern.to_lowercase().trim().to_string(),
+            max_age,
+        }
+    }
+
+    /// Returns `true` if `tag` matches this policy's pattern.
+    pub fn matches(&self, tag: &SemanticTag) -> bool {
+        glob_match(&self.tag_pattern.to_lowercase(), &tag.0)
+    }
+}
+
+/// Matches `text` against a pattern where `*` stands for any run of characters.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let Some((first, rest)) = pattern.split_once('*') else {
+        return pattern == text;
+    };
+    let Some(mut remaining) = text.strip_prefix(first) else {
+        return false;
+    };
+
+    let mut parts: Vec<&str> = rest.split('*').collect();
+    let last = parts.pop().unwrap_or_default();
+    for part in parts {
+        match remaining.find(part) {
+            Some(pos) => remaining = &remaining[pos + part.len()..],
+            None => return false,
         }
     }
+    remaining.len() >= last.len() && remaining.ends_with(last)
 }
 
 /// Configuration for the Short-Term Memory (STM).
@@ -227,4 +311,32 @@ mod tests {
         // Agent mode should enable embeddings
         assert!(config.ltm.enable_embeddings);
     }
+
+    #[test]
+    fn test_retention_policy_matching() {
+        let exact = RetentionPolicy::new("User_Msg", Duration::from_secs(60));
+        assert!(exact.matches(&SemanticTag::new("user_msg")));
+        assert!(!exact.matches(&SemanticTag::new("user_msgs")));
+
+        let prefix = RetentionPolicy::new("user_*", Duration::from_secs(60));
+        assert!(prefix.matches(&SemanticTag::new("user_msg")));
+        assert!(prefix.matches(&SemanticTag::new("user_")));
+        assert!(!prefix.matches(&SemanticTag::new("telemetry")));
+
+        let infix = RetentionPolicy::new("*pii*", Duration::from_secs(60));
+        assert!(infix.matches(&SemanticTag::new("contains_pii_data")));
+        assert!(!infix.matches(&SemanticTag::new("telemetry")));
+
+        let ends = RetentionPolicy::new("a*a", Duration::from_secs(60));
+        assert!(ends.matches(&SemanticTag::new("aa")));
+        assert!(!ends.matches(&SemanticTag::new("a")));
+    }
+
+    #[test]
+    fn test_config_without_retention_deserializes() {
+        let mut json = serde_json::to_value(MemoryConfig::default()).unwrap();
+        json.as_object_mut().unwrap().remove("retention");
+        let config: MemoryConfig = serde_json::from_value(json).unwrap();
+        assert!(config.retention.is_empty());
+    }
 }
@@ -11,7 +11,7 @@ use crate::config::ConsolidationConfig;
 use crate::error::Result;
 use crate::ltm::LongTermMemory;
 use crate::stm::ShortTermMemory;
-use crate::types::{Entity, Link, MemoryEntry, Relation, Timestamp};
+use crate::types::{Entity, Link, MemoryEntry, MemoryId, Relation, Timestamp};
 
 /// The engine responsible for consolidating memories from STM to LTM.
 ///
@@ -52,31 +52,48 @@ impl Consolidator {
     pub fn run(&mut self, stm: &mut ShortTermMemory, ltm: &mut LongTermMemory) -> Result<usize> {
         let mut consolidated_count = 0;
 
-        // Get candidates based on importance and access patterns
-        let candidates = self.select_candidates(stm);
-
-        // Consolidate in batches
-        for entry in candidates.into_iter().take(self.config.batch_size) {
-            let id = entry.id.clone();
-
-            // Store in LTM
-            ltm.store(entry.clone())?;
-
-            // Extract entities and relationships from the memory
-            self.extract_knowledge(&entry, ltm)?;
+        for entry in self.next_batch(stm) {
+            let id = self.transfer(entry, ltm)?;
 
             // Mark as consolidated in STM (don't remove yet - keeps for quick access)
             stm.mark_consolidated(&id)?;
 
             consolidated_count += 1;
-            self.stats.total_consolidated += 1;
         }
 
+        self.finish_run();
+        Ok(consolidated_count)
+    }
+
+    /// Selects the next batch of entries to consolidate.
+    ///
+    /// Candidates are chosen based on importance and access patterns, and the
+    /// batch is bounded by `ConsolidationConfig::batch_size`.
+    pub(crate) fn next_batch(&self, stm: &ShortTermMemory) -> Vec<MemoryEntry> {
+        let mut candidates = self.select_candidates(stm);
+        candidates.truncate(self.config.batch_size);
+        candidates
+    }
+
+    /// Stores one entry in LTM and extracts its entities and relationships.
+    ///
+    /// The entry is not marked as consolidated in STM; that is left to the caller.
+    pub(crate) fn transfer(
+        &mut self,
+        entry: MemoryEntry,
+        ltm: &mut LongTermMemory,
+    ) -> Result<MemoryId> {
+        let id = ltm.store(entry.clone())?;
+        self.extract_knowledge(&entry, ltm)?;
+        self.stats.total_consolidated += 1;
+        Ok(id)
+    }
+
+    /// Records the completion of a consolidation run.
+    pub(crate) fn finish_run(&mut self) {
         self.last_run = Timestamp::now();
         self.stats.last_run = self.last_run;
         self.stats.runs += 1;
-
-        Ok(consolidated_count)
     }
 
     /// Checks if the consolidation process should be run based on the current state.
@@ -153,15 +170,15 @@ impl Consolidator {
             }
 
             // Store entity
-            let entity_id = ltm.add_entity(entity)?;
+            let entity_id = ltm.add_entity_from(entity, &entry.id)?;
 
             // Extract relationships from tags
             for tag in &entry.tags {
                 // Create tag entities and link them
                 let tag_entity = Entity::new("tag", &tag.0);
-                if let Ok(tag_id) = ltm.add_entity(tag_entity) {
+                if let Ok(tag_id) = ltm.add_entity_from(tag_entity, &entry.id) {
                     let link = Link::new(entity_id.clone(), Relation::new("TAGGED"), tag_id);
-                    let _ = ltm.add_link(link); // Ignore capacity errors for tags
+                    let _ = ltm.add_link_from(link, &entry.id); // Ignore capacity errors for tags
                 }
             }
         }
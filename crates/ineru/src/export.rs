@@ -0,0 +1,390 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! GraphViz DOT and Turtle export of the LTM knowledge graph.
+//!
+//! The exports are meant for inspecting what an agent has learned, not for
+//! restoring it; use [`IneruMemory::save_to_file`](crate::IneruMemory::save_to_file)
+//! for that.
+//!
+//! - **DOT**: entities are nodes labeled with their name, type and importance;
+//!   links are edges labeled with their [`LinkType`](crate::LinkType).
+//! - **Turtle**: entities, links and entity properties are mapped onto
+//!   triples using a small fixed vocabulary under [`VOCAB_IRI`]. Links carry
+//!   a weight, so each one is a resource of its own rather than a single
+//!   triple.
+//!
+//! Both exports keep at most [`ExportOptions::max_nodes`] entities, the most
+//! important first, and end with a truncation marker saying how many were
+//! left out. Output is deterministic for a given graph.
+//!
+//! # Examples
+//!
+//! ```
+//! use ineru::export::ExportOptions;
+//! use ineru::{Entity, LongTermMemory, LtmConfig};
+//!
+//! let mut ltm = LongTermMemory::new(LtmConfig::default());
+//! ltm.add_entity(Entity::new("person", "Alice")).unwrap();
+//!
+//! let dot = ltm.export_dot(&ExportOptions::default());
+//! assert!(dot.starts_with("digraph \"ineru_ltm\" {"));
+//! assert!(ltm.export_turtle(&ExportOptions::default()).contains("\"Alice\""));
+//! ```
+
+use crate::ltm::LongTermMemory;
+use crate::types::{Entity, EntityId, Link};
+
+use std::collections::HashSet;
+use std::fmt::Write as _;
+
+/// Default for [`ExportOptions::max_nodes`].
+pub const DEFAULT_MAX_NODES: usize = 1000;
+
+/// Namespace of the vocabulary used by Turtle exports (prefix `ineru:`).
+pub const VOCAB_IRI: &str = "urn:ineru:vocab#";
+
+/// Namespace of entity IRIs in Turtle exports (prefix `entity:`), followed by
+/// the entity id in hex.
+pub const ENTITY_IRI: &str = "urn:ineru:entity:";
+
+/// A knowledge graph export format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Graphviz DOT.
+    Dot,
+    /// RDF Turtle.
+    Turtle,
+}
+
+impl ExportFormat {
+    /// The conventional file extension, without the dot.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Dot => "dot",
+            ExportFormat::Turtle => "ttl",
+        }
+    }
+}
+
+/// Which part of the knowledge graph is exported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportOptions {
+    /// Links lighter than this weight are left out.
+    ///
+    /// Default is `None`, keeping every link.
+    pub min_link_weight: Option<f32>,
+
+    /// Only entities of this type are exported, with the links between them.
+    ///
+    /// Default is `None`, keeping every entity.
+    pub entity_type: Option<String>,
+
+    /// The most entities exported; the least important of the rest are left
+    /// out and replaced by a truncation marker.
+    ///
+    /// Default is [`DEFAULT_MAX_NODES`].
+    pub max_nodes: usize,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            min_link_weight: None,
+            entity_type: None,
+            max_nodes: DEFAULT_MAX_NODES,
+        }
+    }
+}
+
+impl ExportOptions {
+    /// Leaves out links lighter than `weight`.
+    pub fn with_min_link_weight(mut self, weight: f32) -> Self {
+        self.min_link_weight = Some(weight);
+        self
+    }
+
+    /// Only exports entities of the given type.
+    pub fn with_entity_type(mut self, entity_type: &str) -> Self {
+        self.entity_type = Some(entity_type.to_string());
+        self
+    }
+
+    /// Sets the most entities exported.
+    pub fn with_max_nodes(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = max_nodes;
+        self
+    }
+}
+
+/// The entities and links an export covers.
+struct Selection<'a> {
+    /// Most important first, ties broken by id.
+    entities: Vec<&'a Entity>,
+    /// Links between selected entities, grouped by source in entity order.
+    links: Vec<&'a Link>,
+    /// The number of matching entities beyond [`ExportOptions::max_nodes`].
+    omitted: usize,
+}
+
+impl LongTermMemory {
+    /// Renders the knowledge graph as a Graphviz DOT `digraph`.
+    pub fn export_dot(&self, options: &ExportOptions) -> String {
+        let selection = self.select(options);
+        let mut out = String::new();
+        out.push_str("digraph \"ineru_ltm\" {\n");
+        out.push_str("  node [shape=box, style=rounded, fontname=\"Helvetica\"];\n");
+        out.push_str("  edge [fontname=\"Helvetica\", fontsize=10];\n");
+        for entity in &selection.entities {
+            let _ = writeln!(
+                out,
+                "  \"{}\" [label=\"{}\\n{}, importance {}\"];",
+                entity.id.to_hex(),
+                escape_dot(&entity.name),
+                escape_dot(&entity.entity_type),
+                entity.metadata.importance
+            );
+        }
+        for link in &selection.links {
+            let _ = writeln!(
+                out,
+                "  \"{}\" -> \"{}\" [label=\"{}\"];",
+                link.source.to_hex(),
+                link.target.to_hex(),
+                escape_dot(&link.relation.0)
+            );
+        }
+        if selection.omitted > 0 {
+            let _ = writeln!(
+                out,
+                "  \"truncated\" [label=\"{} more entities not shown\", shape=note];",
+                selection.omitted
+            );
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Renders the knowledge graph as RDF Turtle.
+    ///
+    /// Each entity is an `ineru:Entity` with its `ineru:entityType`,
+    /// `ineru:name`, `ineru:importance` and one `ineru:attribute` per
+    /// property. Each link is an `ineru:Link` with its `ineru:source`,
+    /// `ineru:target`, `ineru:linkType` and `ineru:weight`. A truncated export
+    /// ends with an `ineru:Truncated` resource counting the
+    /// `ineru:omittedEntities`.
+    pub fn export_turtle(&self, options: &ExportOptions) -> String {
+        let selection = self.select(options);
+        let mut out = String::new();
+        let _ = writeln!(out, "@prefix ineru: <{}> .", VOCAB_IRI);
+        let _ = writeln!(out, "@prefix entity: <{}> .", ENTITY_IRI);
+        out.push_str("@prefix xsd: <http://www.w3.org/2001/XMLSchema#> .\n");
+        for entity in &selection.entities {
+            let _ = write!(
+                out,
+                "\nentity:{} a ineru:Entity ;\n    ineru:entityType {} ;\n    ineru:name {} ;\n    ineru:importance \"{}\"^^xsd:float",
+                entity.id.to_hex(),
+                turtle_string(&entity.entity_type),
+                turtle_string(&entity.name),
+                entity.metadata.importance
+            );
+            let mut keys: Vec<&String> = entity.properties.keys().collect();
+            keys.sort();
+            for key in keys {
+                let _ = write!(
+                    out,
+                    " ;\n    ineru:attribute [ ineru:key {} ; ineru:value {} ]",
+                    turtle_string(key),
+                    turtle_value(&entity.properties[key])
+                );
+            }
+            out.push_str(" .\n");
+        }
+        for link in &selection.links {
+            let _ = write!(
+                out,
+                "\n[] a ineru:Link ;\n    ineru:source entity:{} ;\n    ineru:target entity:{} ;\n    ineru:linkType {} ;\n    ineru:weight \"{}\"^^xsd:float .\n",
+                link.source.to_hex(),
+                link.target.to_hex(),
+                turtle_string(&link.relation.0),
+                link.weight
+            );
+        }
+        if selection.omitted > 0 {
+            let _ = write!(
+                out,
+                "\n[] a ineru:Truncated ;\n    ineru:omittedEntities {} .\n",
+                selection.omitted
+            );
+        }
+        out
+    }
+
+    fn select(&self, options: &ExportOptions) -> Selection<'_> {
+        let mut entities: Vec<&Entity> = self
+            .entities()
+            .filter(|e| {
+                options
+                    .entity_type
+                    .as_ref()
+                    .is_none_or(|t| &e.entity_type == t)
+            })
+            .collect();
+        entities.sort_by(|a, b| {
+            b.metadata
+                .importance
+                .total_cmp(&a.metadata.importance)
+                .then_with(|| a.id.as_bytes().cmp(b.id.as_bytes()))
+        });
+        let omitted = entities.len().saturating_sub(options.max_nodes);
+        entities.truncate(options.max_nodes);
+
+        let kept: HashSet<&EntityId> = entities.iter().map(|e| &e.id).collect();
+        let links = entities
+            .iter()
+            .flat_map(|e| self.get_links_from(&e.id))
+            .filter(|link| kept.contains(&link.target))
+            .filter(|link| options.min_link_weight.is_none_or(|w| link.weight >= w))
+            .collect();
+
+        Selection {
+            entities,
+            links,
+            omitted,
+        }
+    }
+}
+
+/// Escapes a string for a double-quoted DOT id or label.
+fn escape_dot(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Quotes and escapes a Turtle string literal.
+fn turtle_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Renders a property value as a Turtle literal.
+///
+/// Strings, booleans and integers map onto their Turtle literals, other
+/// numbers onto `xsd:double`; arrays, objects and `null` are kept as JSON text.
+fn turtle_value(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => turtle_string(s),
+        serde_json::Value::Bool(b) => b.to_string(),
+        serde_json::Value::Number(n) if n.is_i64() || n.is_u64() => n.to_string(),
+        serde_json::Value::Number(n) => format!("\"{}\"^^xsd:double", n),
+        other => turtle_string(&other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LtmConfig;
+    use crate::types::Relation;
+
+    fn entity(byte: u8, entity_type: &str, name: &str, importance: f32) -> Entity {
+        let mut entity = Entity::new(entity_type, name);
+        // Fixed ids, so the golden files do not depend on the id hash
+        entity.id = EntityId::from_bytes([byte; 32]);
+        entity.metadata.importance = importance;
+        entity
+    }
+
+    /// Three entities and two links, covering escaping and typed properties.
+    fn fixture() -> LongTermMemory {
+        let mut ltm = LongTermMemory::new(LtmConfig::default());
+        let alice = ltm
+            .add_entity(
+                entity(1, "person", "Alice", 0.9)
+                    .with_property("role", serde_json::json!("engineer"))
+                    .with_property("age", serde_json::json!(34)),
+            )
+            .unwrap();
+        let sensor = ltm
+            .add_entity(
+                entity(2, "sensor", "Sensor \"A\"", 0.5)
+                    .with_property("calibrated", serde_json::json!(true))
+                    .with_property("offset", serde_json::json!(0.25)),
+            )
+            .unwrap();
+        let lab = ltm.add_entity(entity(3, "place", "Lab", 0.25)).unwrap();
+        ltm.add_link(Link::new(alice, Relation::observed(), sensor.clone()).with_weight(0.75))
+            .unwrap();
+        ltm.add_link(Link::new(sensor, Relation::located_at(), lab).with_weight(0.5))
+            .unwrap();
+        ltm
+    }
+
+    #[test]
+    fn test_dot_golden() {
+        let dot = fixture().export_dot(&ExportOptions::default());
+        assert_eq!(dot, include_str!("../tests/golden/ltm.dot"));
+    }
+
+    #[test]
+    fn test_turtle_golden() {
+        let turtle = fixture().export_turtle(&ExportOptions::default());
+        assert_eq!(turtle, include_str!("../tests/golden/ltm.ttl"));
+    }
+
+    #[test]
+    fn test_filters() {
+        let ltm = fixture();
+
+        let dot = ltm.export_dot(&ExportOptions::default().with_min_link_weight(0.6));
+        assert!(dot.contains("OBSERVED"));
+        assert!(!dot.contains("LOCATED_AT"));
+        assert_eq!(dot.matches("[label=").count(), 4);
+
+        let turtle = ltm.export_turtle(&ExportOptions::default().with_entity_type("sensor"));
+        assert!(turtle.contains("ineru:name \"Sensor \\\"A\\\"\""));
+        assert!(!turtle.contains("\"Alice\""));
+        assert!(!turtle.contains("ineru:Link"));
+    }
+
+    #[test]
+    fn test_node_cap_marks_truncation() {
+        let ltm = fixture();
+        let options = ExportOptions::default().with_max_nodes(2);
+
+        // The least important entity and its link are left out
+        let dot = ltm.export_dot(&options);
+        assert!(!dot.contains("Lab"));
+        assert!(!dot.contains("LOCATED_AT"));
+        assert!(dot.contains("\"truncated\" [label=\"1 more entities not shown\", shape=note];"));
+
+        let turtle = ltm.export_turtle(&options);
+        assert!(!turtle.contains("\"Lab\""));
+        assert!(turtle.ends_with("[] a ineru:Truncated ;\n    ineru:omittedEntities 1 .\n"));
+
+        assert!(!ltm
+            .export_dot(&ExportOptions::default())
+            .contains("truncated"));
+    }
+}
@@ -0,0 +1,309 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Pluggable importance scoring for new memories.
+//!
+//! Importance decides which memories are consolidated into LTM, but most
+//! callers store entries without knowing how salient they are. An
+//! [`ImportanceScorer`] configured with
+//! [`MemoryConfig::with_importance_scorer`](crate::MemoryConfig::with_importance_scorer)
+//! fills it in: `IneruMemory::remember` asks the scorer for every entry whose
+//! importance was not set explicitly, clamps the score to `[0.0, 1.0]` and
+//! records the scorer's name in [`MemoryMetadata::importance_source`].
+//!
+//! Two scorers are built in:
+//!
+//! - [`NoveltyScorer`]: the further an entry is from everything already in
+//!   STM, the more important it is.
+//! - [`KeywordBoostScorer`]: a base score raised by configured tags and
+//!   keywords, e.g. an `"error"` tag or a `"critical"` severity.
+//!
+//! [`MemoryMetadata::importance_source`]: crate::MemoryMetadata::importance_source
+
+use crate::stm::ShortTermMemory;
+use crate::types::{Embedding, MemoryEntry};
+use std::cell::OnceCell;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Value of [`MemoryMetadata::importance_source`](crate::MemoryMetadata::importance_source)
+/// for importance set by the caller.
+pub const EXPLICIT_IMPORTANCE: &str = "explicit";
+
+/// Computes the importance of a memory that is about to be stored.
+///
+/// Scores outside `[0.0, 1.0]` are clamped by the caller, and `NaN` is
+/// treated as `0.0`.
+pub trait ImportanceScorer: Send + Sync {
+    /// The name recorded in the metadata of the entries this scorer rated.
+    fn name(&self) -> &str;
+
+    /// Rates `entry` given the current contents of STM.
+    fn score(&self, entry: &MemoryEntry, context: &ScoringContext<'_>) -> f32;
+}
+
+impl fmt::Debug for dyn ImportanceScorer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ImportanceScorer")
+            .field(&self.name())
+            .finish()
+    }
+}
+
+/// What a scorer knows about STM when rating a new entry.
+///
+/// Statistics are computed on demand, so a scorer only pays for what it reads.
+pub struct ScoringContext<'a> {
+    stm: &'a ShortTermMemory,
+    entry: &'a MemoryEntry,
+    nearest: OnceCell<Option<f32>>,
+}
+
+impl<'a> ScoringContext<'a> {
+    /// Creates the context for rating `entry` against the contents of `stm`.
+    pub fn new(stm: &'a ShortTermMemory, entry: &'a MemoryEntry) -> Self {
+        Self {
+            stm,
+            entry,
+            nearest: OnceCell::new(),
+        }
+    }
+
+    /// The number of entries in STM.
+    pub fn stm_len(&self) -> usize {
+        self.stm.len()
+    }
+
+    /// The estimated memory usage of STM in bytes.
+    pub fn stm_memory_usage(&self) -> usize {
+        self.stm.memory_usage()
+    }
+
+    /// The mean importance of the entries in STM, or `None` if it is empty.
+    pub fn mean_importance(&self) -> Option<f32> {
+        let len = self.stm.len();
+        if len == 0 {
+            return None;
+        }
+        let total: f32 = self.stm.iter().map(|e| e.metadata.importance).sum();
+        Some(total / len as f32)
+    }
+
+    /// The highest similarity between the entry and any entry in STM, or
+    /// `None` if STM is empty.
+    ///
+    /// Two entries are compared by their embeddings when both have one of the
+    /// same dimension, and by a lexical embedding of their type, data and
+    /// tags otherwise. Negative similarities are reported as `0.0`.
+    pub fn nearest_similarity(&self) -> Option<f32> {
+        *self.nearest.get_or_init(|| {
+            let lexical = lexical_embedding(self.entry);
+            self.stm
+                .iter()
+                .filter(|other| other.id != self.entry.id)
+                .map(|other| similarity(self.entry, &lexical, other).max(0.0))
+                .reduce(f32::max)
+        })
+    }
+}
+
+fn lexical_embedding(entry: &MemoryEntry) -> Embedding {
+    let mut text = entry.entry_type.clone();
+    text.push(' ');
+    text.push_str(&entry.data.to_string());
+    for tag in &entry.tags {
+        text.push(' ');
+        text.push_str(&tag.0);
+    }
+    Embedding::from_text_simple(&text)
+}
+
+fn similarity(entry: &MemoryEntry, lexical: &Embedding, other: &MemoryEntry) -> f32 {
+    match (&entry.embedding, &other.embedding) {
+        (Some(a), Some(b)) if a.0.len() == b.0.len() => a.cosine_similarity(b),
+        _ => lexical.cosine_similarity(&lexical_embedding(other)),
+    }
+}
+
+/// Rates entries by how different they are from everything in STM.
+///
+/// The score is `1.0 - nearest_similarity`: a duplicate of a stored entry
+/// scores close to `0.0`, and the first entry in an empty STM scores `1.0`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoveltyScorer;
+
+impl NoveltyScorer {
+    /// Creates a novelty scorer.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ImportanceScorer for NoveltyScorer {
+    fn name(&self) -> &str {
+        "novelty"
+    }
+
+    fn score(&self, _entry: &MemoryEntry, context: &ScoringContext<'_>) -> f32 {
+        1.0 - context.nearest_similarity().unwrap_or(0.0)
+    }
+}
+
+/// Rates entries by a base score plus weights for the tags and keywords
+/// they carry.
+///
+/// Tags match case-insensitively against the entry's tags. Keywords match
+/// case-insensitively against its type and the text of its data, so a
+/// `"critical"` keyword catches `{"severity": "critical"}`. Every matching
+/// tag and keyword adds its weight once; weights may be negative.
+#[derive(Debug, Clone)]
+pub struct KeywordBoostScorer {
+    base: f32,
+    tags: HashMap<String, f32>,
+    keywords: HashMap<String, f32>,
+}
+
+impl KeywordBoostScorer {
+    /// Creates a scorer with the default base importance of `0.5` and no
+    /// boosts.
+    pub fn new() -> Self {
+        Self {
+            base: 0.5,
+            tags: HashMap::new(),
+            keywords: HashMap::new(),
+        }
+    }
+
+    /// Sets the score of an entry that matches nothing.
+    pub fn with_base(mut self, base: f32) -> Self {
+        self.base = base;
+        self
+    }
+
+    /// Adds `weight` for entries tagged with `tag`.
+    pub fn with_tag(mut self, tag: &str, weight: f32) -> Self {
+        self.tags.insert(tag.to_lowercase(), weight);
+        self
+    }
+
+    /// Adds `weight` for entries whose type or data mention `keyword`.
+    pub fn with_keyword(mut self, keyword: &str, weight: f32) -> Self {
+        self.keywords.insert(keyword.to_lowercase(), weight);
+        self
+    }
+}
+
+impl Default for KeywordBoostScorer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ImportanceScorer for KeywordBoostScorer {
+    fn name(&self) -> &str {
+        "keyword_boost"
+    }
+
+    fn score(&self, entry: &MemoryEntry, _context: &ScoringContext<'_>) -> f32 {
+        let tag_boost: f32 = self
+            .tags
+            .iter()
+            .filter(|(tag, _)| entry.tags.iter().any(|t| t.0.to_lowercase() == **tag))
+            .map(|(_, weight)| weight)
+            .sum();
+
+        let keyword_boost: f32 = if self.keywords.is_empty() {
+            0.0
+        } else {
+            let text = format!("{} {}", entry.entry_type, entry.data).to_lowercase();
+            self.keywords
+                .iter()
+                .filter(|(keyword, _)| text.contains(keyword.as_str()))
+                .map(|(_, weight)| weight)
+                .sum()
+        };
+
+        self.base + tag_boost + keyword_boost
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::StmConfig;
+    use serde_json::json;
+
+    fn stm_with(entries: &[MemoryEntry]) -> ShortTermMemory {
+        let mut stm = ShortTermMemory::new(StmConfig::default());
+        for entry in entries {
+            stm.store(entry.clone()).unwrap();
+        }
+        stm
+    }
+
+    #[test]
+    fn test_duplicate_is_less_novel() {
+        let stored = MemoryEntry::new("sensor", json!({"room": "kitchen", "temp": 21.5}))
+            .with_tags(&["temperature"]);
+        let stm = stm_with(&[stored]);
+
+        let duplicate = MemoryEntry::new("sensor", json!({"room": "kitchen", "temp": 21.5}))
+            .with_tags(&["temperature"]);
+        let novel = MemoryEntry::new("alarm", json!({"door": "front", "state": "forced"}))
+            .with_tags(&["security"]);
+
+        let scorer = NoveltyScorer::new();
+        let duplicate_score = scorer.score(&duplicate, &ScoringContext::new(&stm, &duplicate));
+        let novel_score = scorer.score(&novel, &ScoringContext::new(&stm, &novel));
+        assert!(duplicate_score < 0.01, "{duplicate_score}");
+        assert!(novel_score > duplicate_score + 0.5, "{novel_score}");
+    }
+
+    #[test]
+    fn test_novelty_prefers_embeddings() {
+        let stored = MemoryEntry::new("note", json!("alpha"))
+            .with_embedding(Embedding::new(vec![1.0, 0.0, 0.0]));
+        let stm = stm_with(&[stored]);
+
+        let scorer = NoveltyScorer::new();
+        let same = MemoryEntry::new("note", json!("something else entirely"))
+            .with_embedding(Embedding::new(vec![1.0, 0.0, 0.0]));
+        let orthogonal = MemoryEntry::new("note", json!("alpha"))
+            .with_embedding(Embedding::new(vec![0.0, 1.0, 0.0]));
+        assert!(scorer.score(&same, &ScoringContext::new(&stm, &same)) < 0.01);
+        assert!(
+            (scorer.score(&orthogonal, &ScoringContext::new(&stm, &orthogonal)) - 1.0).abs() < 1e-6
+        );
+    }
+
+    #[test]
+    fn test_empty_stm_context() {
+        let stm = stm_with(&[]);
+        let entry = MemoryEntry::new("note", json!("first"));
+        let context = ScoringContext::new(&stm, &entry);
+        assert_eq!(context.stm_len(), 0);
+        assert_eq!(context.mean_importance(), None);
+        assert_eq!(context.nearest_similarity(), None);
+        assert_eq!(NoveltyScorer.score(&entry, &context), 1.0);
+    }
+
+    #[test]
+    fn test_keyword_boost() {
+        let stm = stm_with(&[MemoryEntry::new("note", json!("x")).with_importance(0.2)]);
+        let scorer = KeywordBoostScorer::new()
+            .with_base(0.2)
+            .with_tag("Error", 0.3)
+            .with_keyword("critical", 0.4)
+            .with_keyword("debug", -0.1);
+
+        let plain = MemoryEntry::new("log", json!({"message": "started"}));
+        let error = MemoryEntry::new("log", json!({"severity": "CRITICAL"})).with_tags(&["error"]);
+        let debug = MemoryEntry::new("debug", json!({"message": "tick"}));
+
+        let context = ScoringContext::new(&stm, &plain);
+        assert_eq!(context.mean_importance(), Some(0.2));
+        assert!((scorer.score(&plain, &context) - 0.2).abs() < 1e-6);
+        assert!((scorer.score(&error, &context) - 0.9).abs() < 1e-6);
+        assert!((scorer.score(&debug, &context) - 0.1).abs() < 1e-6);
+    }
+}
@@ -42,7 +42,7 @@
 //! use ineru::{IneruMemory, MemoryConfig, MemoryEntry};
 //!
 //! // Create memory system
-//! let mut memory = IneruMemory::new(MemoryConfig::default());
+//! let memory = IneruMemory::new(MemoryConfig::default());
 //!
 //! // Store in short-term memory
 //! let entry = MemoryEntry::new("sensor_data", json!({"temp": 23.5}));
@@ -67,35 +67,67 @@ pub mod config;
 pub mod consolidation;
 mod embedder;
 pub mod error;
+pub mod export;
 pub mod hnsw;
+pub mod importance;
 pub mod ltm;
+pub mod retention;
 pub mod stm;
 pub mod types;
 
-pub use config::{ConsolidationConfig, LtmConfig, MemoryConfig, StmConfig};
+pub use config::{ConsolidationConfig, LtmConfig, MemoryConfig, RetentionPolicy, StmConfig};
 pub use consolidation::Consolidator;
 #[cfg(feature = "neural-embeddings")]
 pub use embedder::NeuralEmbedder;
 pub use embedder::{Embedder, HashEmbedder};
 pub use error::{Error, Result};
-pub use ltm::{KnowledgeGraph, LongTermMemory};
+pub use export::{ExportFormat, ExportOptions};
+pub use importance::{
+    ImportanceScorer, KeywordBoostScorer, NoveltyScorer, ScoringContext, EXPLICIT_IMPORTANCE,
+};
+pub use ltm::{KnowledgeGraph, LongTermMemory, PurgeStats};
+pub use retention::{RetentionReport, RuleRetention};
 pub use stm::ShortTermMemory;
 pub use types::{
     Embedding, Entity, EntityId, Link, LinkType, MemoryEntry, MemoryId, MemoryMetadata,
-    MemoryQuery, MemoryResult, Relation, SemanticTag,
+    MemoryQuery, MemoryResult, Relation, SemanticTag, Timestamp,
 };
 
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
 /// The main interface for the Ineru memory system.
 ///
 /// This struct integrates a `ShortTermMemory` (STM) and a `LongTermMemory` (LTM)
 /// to provide a comprehensive, neural-inspired memory solution for AI agents.
+///
+/// # Concurrency
+///
+/// `IneruMemory` is `Send + Sync` and every operation takes `&self`, so it can be
+/// shared across threads behind an `Arc`. STM and LTM sit behind their own
+/// read-write locks: recalls run concurrently with each other and only wait for
+/// the brief moments a writer holds the tier they are reading.
+///
+/// Consistency model:
+///
+/// - Each tier is always observed in a consistent state, but a `recall` reads
+///   STM and LTM one after the other, not as a single atomic snapshot.
+/// - `consolidate` copies an entry into LTM before marking it in STM, so a
+///   concurrent `recall` sees an entry being moved in at least one tier. A result
+///   present in both tiers is returned once.
+/// - An entry forgotten or expired while a `recall` runs may or may not be
+///   returned by it.
+/// - A panic in another thread while holding a lock does not make the memory
+///   unusable; the lock is recovered rather than propagating the poison.
 pub struct IneruMemory {
     /// The fast, volatile, and bounded Short-Term Memory.
-    pub stm: ShortTermMemory,
+    stm: RwLock<ShortTermMemory>,
     /// The persistent, graph-based Long-Term Memory.
-    pub ltm: LongTermMemory,
+    ltm: RwLock<LongTermMemory>,
     /// The engine responsible for moving memories from STM to LTM.
-    consolidator: Consolidator,
+    ///
+    /// Its lock serializes consolidation runs without blocking readers.
+    consolidator: Mutex<Consolidator>,
     /// The configuration for the entire memory system.
     config: MemoryConfig,
 }
@@ -109,9 +141,9 @@ impl IneruMemory {
     ///   of the STM, LTM, and consolidation process.
     pub fn new(config: MemoryConfig) -> Self {
         Self {
-            stm: ShortTermMemory::new(config.stm.clone()),
-            ltm: LongTermMemory::new(config.ltm.clone()),
-            consolidator: Consolidator::new(config.consolidation.clone()),
+            stm: RwLock::new(ShortTermMemory::new(config.stm.clone())),
+            ltm: RwLock::new(LongTermMemory::new(config.ltm.clone())),
+            consolidator: Mutex::new(Consolidator::new(config.consolidation.clone())),
             config,
         }
     }
@@ -130,11 +162,47 @@ impl IneruMemory {
         Self::new(MemoryConfig::agent_mode())
     }
 
+    /// Returns shared access to the Short-Term Memory.
+    ///
+    /// Writers to STM wait while the guard is held, so keep it short-lived.
+    pub fn stm(&self) -> RwLockReadGuard<'_, ShortTermMemory> {
+        self.stm.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns exclusive access to the Short-Term Memory.
+    pub fn stm_mut(&self) -> RwLockWriteGuard<'_, ShortTermMemory> {
+        self.stm.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns shared access to the Long-Term Memory.
+    ///
+    /// Do not acquire an STM guard while holding an LTM guard: locks are always
+    /// taken STM first, and the reverse order can deadlock.
+    pub fn ltm(&self) -> RwLockReadGuard<'_, LongTermMemory> {
+        self.ltm.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns exclusive access to the Long-Term Memory.
+    ///
+    /// The same lock ordering as [`IneruMemory::ltm`] applies.
+    pub fn ltm_mut(&self) -> RwLockWriteGuard<'_, LongTermMemory> {
+        self.ltm.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn consolidator(&self) -> MutexGuard<'_, Consolidator> {
+        self.consolidator
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
     /// Stores a new `MemoryEntry` in the Short-Term Memory.
     ///
     /// All memories begin their lifecycle in the STM. They may be moved to LTM later
     /// during consolidation if they are deemed important.
     ///
+    /// If the configuration has an `importance_scorer` and the entry's importance
+    /// was not set explicitly, the scorer rates the entry first.
+    ///
     /// # Arguments
     ///
     /// * `entry` - The `MemoryEntry` to store.
@@ -142,8 +210,20 @@ impl IneruMemory {
     /// # Returns
     ///
     /// A `Result` containing the unique `MemoryId` assigned to the new entry.
-    pub fn remember(&mut self, entry: MemoryEntry) -> Result<MemoryId> {
-        self.stm.store(entry)
+    pub fn remember(&self, entry: MemoryEntry) -> Result<MemoryId> {
+        let mut entry = entry;
+        if entry.metadata.importance_source.is_none() {
+            if let Some(scorer) = &self.config.importance_scorer {
+                let score = scorer.score(&entry, &ScoringContext::new(&self.stm(), &entry));
+                entry.metadata.importance = if score.is_nan() {
+                    0.0
+                } else {
+                    score.clamp(0.0, 1.0)
+                };
+                entry.metadata.importance_source = Some(scorer.name().to_string());
+            }
+        }
+        self.stm_mut().store(entry)
     }
 
     /// Stores a new `MemoryEntry` with an explicit importance score.
@@ -157,16 +237,15 @@ impl IneruMemory {
     /// # Returns
     ///
     /// A `Result` containing the unique `MemoryId` assigned to the new entry.
-    pub fn remember_important(&mut self, entry: MemoryEntry, importance: f32) -> Result<MemoryId> {
-        let mut entry = entry;
-        entry.metadata.importance = importance;
-        self.stm.store(entry)
+    pub fn remember_important(&self, entry: MemoryEntry, importance: f32) -> Result<MemoryId> {
+        self.stm_mut().store(entry.with_importance(importance))
     }
 
     /// Recalls a list of memories that match a given `MemoryQuery`.
     ///
     /// This method searches both STM and LTM, combining the results and sorting
-    /// them by relevance.
+    /// them by relevance. An entry found in both tiers is returned once, with
+    /// the higher of its two relevance scores.
     ///
     /// # Arguments
     ///
@@ -176,13 +255,28 @@ impl IneruMemory {
     ///
     /// A `Result` containing a vector of `MemoryResult` structs, sorted by relevance.
     pub fn recall(&self, query: &MemoryQuery) -> Result<Vec<MemoryResult>> {
-        let mut results = Vec::new();
-
         // Search STM first (recent memories)
-        results.extend(self.stm.query(query)?);
+        let mut results = self.stm().query(query)?;
 
-        // Then search LTM (consolidated knowledge)
-        results.extend(self.ltm.query(query)?);
+        // Then search LTM (consolidated knowledge), skipping entries STM already returned
+        let mut seen: HashMap<MemoryId, usize> = results
+            .iter()
+            .enumerate()
+            .map(|(i, r)| (r.entry.id.clone(), i))
+            .collect();
+        for result in self.ltm().query(query)? {
+            match seen.get(&result.entry.id) {
+                Some(&i) => {
+                    if result.relevance > results[i].relevance {
+                        results[i] = result;
+                    }
+                }
+                None => {
+                    seen.insert(result.entry.id.clone(), results.len());
+                    results.push(result);
+                }
+            }
+        }
 
         // Sort by relevance
         results.sort_by(|a, b| {
@@ -225,7 +319,7 @@ impl IneruMemory {
 
     /// Recalls the `count` most recent memories from STM.
     pub fn recall_recent(&self, count: usize) -> Result<Vec<MemoryResult>> {
-        self.stm.get_recent(count)
+        self.stm().get_recent(count)
     }
 
     /// Runs the consolidation process, moving important memories from STM to LTM.
@@ -235,11 +329,38 @@ impl IneruMemory {
     ///
     /// This should be called periodically (e.g., every few minutes or on idle).
     ///
+    /// Candidates are selected under a read lock on STM. Each entry is then
+    /// copied into LTM and marked in STM, holding each write lock only for that
+    /// single step, so `remember` and `recall` keep making progress during a run.
+    /// Concurrent calls are serialized. An entry forgotten while it is being
+    /// moved is removed from LTM again rather than resurrected.
+    ///
     /// # Returns
     ///
     /// A `Result` containing the number of entries that were successfully consolidated.
-    pub fn consolidate(&mut self) -> Result<usize> {
-        self.consolidator.run(&mut self.stm, &mut self.ltm)
+    pub fn consolidate(&self) -> Result<usize> {
+        let mut consolidator = self.consolidator();
+        let batch = consolidator.next_batch(&self.stm());
+
+        let mut consolidated = 0;
+        for entry in batch {
+            let id = consolidator.transfer(entry, &mut self.ltm_mut())?;
+
+            let still_in_stm = {
+                let mut stm = self.stm_mut();
+                let present = stm.get(&id)?.is_some();
+                stm.mark_consolidated(&id)?;
+                present
+            };
+            if still_in_stm {
+                consolidated += 1;
+            } else {
+                self.ltm_mut().purge(&HashSet::from([id]))?;
+            }
+        }
+
+        consolidator.finish_run();
+        Ok(consolidated)
     }
 
     /// Forces a specific memory entry to be consolidated from STM to LTM.
@@ -249,10 +370,13 @@ impl IneruMemory {
     /// # Arguments
     ///
     /// * `id` - The `MemoryId` of the entry to consolidate.
-    pub fn consolidate_memory(&mut self, id: &MemoryId) -> Result<()> {
-        if let Some(entry) = self.stm.get(id)? {
-            self.ltm.store(entry)?;
-            self.stm.remove(id)?;
+    pub fn consolidate_memory(&self, id: &MemoryId) -> Result<()> {
+        let _serialized = self.consolidator();
+        let entry = self.stm().get(id)?;
+        if let Some(entry) = entry {
+            // Store before removing so the entry stays visible to concurrent recalls
+            self.ltm_mut().store(entry)?;
+            self.stm_mut().remove(id)?;
         }
         Ok(())
     }
@@ -271,12 +395,13 @@ impl IneruMemory {
     /// `None` otherwise.
     pub fn get(&self, id: &MemoryId) -> Result<Option<MemoryEntry>> {
         // Try STM first
-        if let Some(entry) = self.stm.get(id)? {
-            return Ok(Some(entry));
+        let entry = self.stm().get(id)?;
+        if entry.is_some() {
+            return Ok(entry);
         }
 
         // Then try LTM
-        self.ltm.get(id)
+        self.ltm().get(id)
     }
 
     /// Forgets a memory, removing it from both STM and LTM.
@@ -284,17 +409,70 @@ impl IneruMemory {
     /// # Arguments
     ///
     /// * `id` - The `MemoryId` of the entry to remove.
-    pub fn forget(&mut self, id: &MemoryId) -> Result<()> {
-        self.stm.remove(id)?;
-        self.ltm.remove(id)?;
+    pub fn forget(&self, id: &MemoryId) -> Result<()> {
+        self.stm_mut().remove(id)?;
+        self.ltm_mut().remove(id)?;
         Ok(())
     }
 
+    /// Hard-deletes memories that have outlived the configured retention policies.
+    ///
+    /// Every entry in STM and LTM is checked against `MemoryConfig::retention`;
+    /// an entry expires once it is older than the shortest `max_age` among the
+    /// policies matching its tags. Expired entries are removed from both
+    /// stores together with their embeddings, and LTM entities and links
+    /// extracted only from expired entries are removed as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The time to evaluate entry ages against.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a `RetentionReport` with removal counts per policy.
+    pub fn apply_retention(&self, now: Timestamp) -> Result<RetentionReport> {
+        let policies = &self.config.retention;
+        let mut report = RetentionReport::new(policies, now);
+        let mut expired = HashSet::new();
+
+        let mut stm = self.stm_mut();
+        let mut ltm = self.ltm_mut();
+        for entry in stm.iter() {
+            if let Some(rule) = retention::expiring_policy(entry, policies, now) {
+                report.rules[rule].stm_removed += 1;
+                expired.insert(entry.id.clone());
+            }
+        }
+        for entry in ltm.iter() {
+            if let Some(rule) = retention::expiring_policy(entry, policies, now) {
+                report.rules[rule].ltm_removed += 1;
+                expired.insert(entry.id.clone());
+            }
+        }
+
+        for id in &expired {
+            stm.remove(id)?;
+        }
+        let purged = ltm.purge(&expired)?;
+        report.entities_removed = purged.entities;
+        report.links_removed = purged.links;
+
+        if !expired.is_empty() {
+            log::info!(
+                "Retention removed {} memories, {} entities and {} links",
+                report.memories_removed(),
+                report.entities_removed,
+                report.links_removed
+            );
+        }
+        Ok(report)
+    }
+
     /// Applies a decay factor to memories in STM, reducing their importance over time.
     ///
     /// This helps ensure that only persistently important memories are consolidated.
-    pub fn decay(&mut self) -> Result<()> {
-        self.stm.decay()
+    pub fn decay(&self) -> Result<()> {
+        self.stm_mut().decay()
     }
 
     /// Prunes the STM if it has exceeded its configured capacity.
@@ -304,25 +482,32 @@ impl IneruMemory {
     /// # Returns
     ///
     /// A `Result` containing the number of entries pruned.
-    pub fn prune_stm(&mut self) -> Result<usize> {
-        self.stm.prune()
+    pub fn prune_stm(&self) -> Result<usize> {
+        self.stm_mut().prune()
     }
 
     /// Gathers and returns statistics about the current state of the memory system.
     pub fn stats(&self) -> MemoryStats {
+        let (stm_count, stm_bytes) = {
+            let stm = self.stm();
+            (stm.len(), stm.memory_usage())
+        };
+        let ltm = self.ltm();
         MemoryStats {
-            stm_count: self.stm.len(),
+            stm_count,
             stm_capacity: self.config.stm.max_entries,
-            ltm_entity_count: self.ltm.entity_count(),
-            ltm_link_count: self.ltm.link_count(),
-            total_memory_bytes: self.stm.memory_usage() + self.ltm.memory_usage(),
+            ltm_entity_count: ltm.entity_count(),
+            ltm_link_count: ltm.link_count(),
+            total_memory_bytes: stm_bytes + ltm.memory_usage(),
         }
     }
 
     /// Clears all memories from both STM and LTM.
-    pub fn clear(&mut self) -> Result<()> {
-        self.stm.clear()?;
-        self.ltm.clear()?;
+    pub fn clear(&self) -> Result<()> {
+        let mut stm = self.stm_mut();
+        let mut ltm = self.ltm_mut();
+        stm.clear()?;
+        ltm.clear()?;
         Ok(())
     }
 }
@@ -359,8 +544,8 @@ struct IneruSnapshot {
 impl IneruMemory {
     /// Exports the current memory state as a JSON byte vector.
     pub fn export_snapshot(&self) -> Result<Vec<u8>> {
-        let stm_entries = self.stm.all_entries();
-        let ltm_entries = self.ltm.all_entries();
+        let stm_entries = self.stm().all_entries();
+        let ltm_entries = self.ltm().all_entries();
 
         let snapshot = IneruSnapshot {
             stm_entries,
@@ -377,16 +562,16 @@ impl IneruMemory {
         let snapshot: IneruSnapshot = serde_json::from_slice(data)
             .map_err(|e| Error::internal(format!("snapshot import: {}", e)))?;
 
-        let mut memory = Self::new(snapshot.config);
+        let memory = Self::new(snapshot.config);
 
         // Restore STM entries
         for entry in snapshot.stm_entries {
-            let _ = memory.stm.store(entry);
+            let _ = memory.stm_mut().store(entry);
         }
 
         // Restore LTM entries
         for entry in snapshot.ltm_entries {
-            let _ = memory.ltm.store(entry);
+            let _ = memory.ltm_mut().store(entry);
         }
 
         Ok(memory)
@@ -410,6 +595,21 @@ impl IneruMemory {
             std::fs::read(path).map_err(|e| Error::internal(format!("snapshot read: {}", e)))?;
         Self::import_snapshot(&data)
     }
+
+    /// Writes the LTM knowledge graph to a file in the given format, for
+    /// inspection.
+    ///
+    /// Uses the default [`ExportOptions`]; render with
+    /// [`LongTermMemory::export_dot`] or [`LongTermMemory::export_turtle`]
+    /// through [`IneruMemory::ltm`] to filter the graph.
+    pub fn export_ltm_snapshot(&self, path: &std::path::Path, format: ExportFormat) -> Result<()> {
+        let options = ExportOptions::default();
+        let text = match format {
+            ExportFormat::Dot => self.ltm().export_dot(&options),
+            ExportFormat::Turtle => self.ltm().export_turtle(&options),
+        };
+        std::fs::write(path, text).map_err(|e| Error::internal(format!("ltm export write: {}", e)))
+    }
 }
 
 impl Default for IneruMemory {
@@ -430,7 +630,7 @@ mod tests {
 
     #[test]
     fn test_remember_recall() {
-        let mut memory = IneruMemory::default();
+        let memory = IneruMemory::default();
 
         let entry = MemoryEntry::new("test", serde_json::json!({"value": 42}));
         let id = memory.remember(entry).unwrap();
@@ -455,7 +655,7 @@ mod tests {
 
     #[test]
     fn test_remember_important() {
-        let mut memory = IneruMemory::default();
+        let memory = IneruMemory::default();
 
         let entry = MemoryEntry::new("important", serde_json::json!({"critical": true}));
         let id = memory.remember_important(entry, 0.95).unwrap();
@@ -464,6 +664,43 @@ mod tests {
         assert_eq!(retrieved.metadata.importance, 0.95);
     }
 
+    #[test]
+    fn test_importance_scorer() {
+        let memory =
+            IneruMemory::new(MemoryConfig::default().with_importance_scorer(NoveltyScorer));
+        let reading = || MemoryEntry::new("sensor", serde_json::json!({"temp": 21.5}));
+
+        let first = memory.remember(reading()).unwrap();
+        let repeat = memory.remember(reading()).unwrap();
+        let explicit = memory.remember(reading().with_importance(0.9)).unwrap();
+
+        let first = memory.get(&first).unwrap().unwrap().metadata;
+        assert_eq!(first.importance, 1.0);
+        assert_eq!(first.importance_source.as_deref(), Some("novelty"));
+        let repeat = memory.get(&repeat).unwrap().unwrap().metadata;
+        assert!(repeat.importance < 0.01);
+        let explicit = memory.get(&explicit).unwrap().unwrap().metadata;
+        assert_eq!(explicit.importance, 0.9);
+        assert_eq!(
+            explicit.importance_source.as_deref(),
+            Some(EXPLICIT_IMPORTANCE)
+        );
+
+        // Out-of-range scores are clamped
+        struct Loud;
+        impl ImportanceScorer for Loud {
+            fn name(&self) -> &str {
+                "loud"
+            }
+            fn score(&self, _: &MemoryEntry, _: &ScoringContext<'_>) -> f32 {
+                7.0
+            }
+        }
+        let memory = IneruMemory::new(MemoryConfig::default().with_importance_scorer(Loud));
+        let id = memory.remember(reading()).unwrap();
+        assert_eq!(memory.get(&id).unwrap().unwrap().metadata.importance, 1.0);
+    }
+
     #[test]
     fn test_recall_empty() {
         let memory = IneruMemory::default();
@@ -474,7 +711,7 @@ mod tests {
 
     #[test]
     fn test_recall_with_limit() {
-        let mut memory = IneruMemory::default();
+        let memory = IneruMemory::default();
 
         // Add multiple entries
         for i in 0..10 {
@@ -490,7 +727,7 @@ mod tests {
 
     #[test]
     fn test_recall_text() {
-        let mut memory = IneruMemory::default();
+        let memory = IneruMemory::default();
 
         let entry = MemoryEntry::new("sensor_data", serde_json::json!({"temp": 25.0}));
         memory.remember(entry).unwrap();
@@ -502,7 +739,7 @@ mod tests {
 
     #[test]
     fn test_recall_tagged() {
-        let mut memory = IneruMemory::default();
+        let memory = IneruMemory::default();
 
         let mut entry = MemoryEntry::new("tagged_entry", serde_json::json!({"data": 123}));
         entry.tags.push(SemanticTag::new("test_tag"));
@@ -514,7 +751,7 @@ mod tests {
 
     #[test]
     fn test_recall_recent() {
-        let mut memory = IneruMemory::default();
+        let memory = IneruMemory::default();
 
         for i in 0..5 {
             let entry = MemoryEntry::new(&format!("recent_{}", i), serde_json::json!({"n": i}));
@@ -527,7 +764,7 @@ mod tests {
 
     #[test]
     fn test_consolidate() {
-        let mut memory = IneruMemory::default();
+        let memory = IneruMemory::default();
 
         // Add some important entries
         for i in 0..3 {
@@ -541,7 +778,7 @@ mod tests {
 
     #[test]
     fn test_consolidate_memory() {
-        let mut memory = IneruMemory::default();
+        let memory = IneruMemory::default();
 
         let entry = MemoryEntry::new("to_consolidate", serde_json::json!({"data": 1}));
         let id = memory.remember(entry).unwrap();
@@ -555,7 +792,7 @@ mod tests {
 
     #[test]
     fn test_consolidate_nonexistent() {
-        let mut memory = IneruMemory::default();
+        let memory = IneruMemory::default();
         let fake_id = MemoryId::from_bytes([0u8; 32]);
 
         // Should not panic
@@ -564,7 +801,7 @@ mod tests {
 
     #[test]
     fn test_forget() {
-        let mut memory = IneruMemory::default();
+        let memory = IneruMemory::default();
 
         let entry = MemoryEntry::new("to_forget", serde_json::json!({"temp": 1}));
         let id = memory.remember(entry).unwrap();
@@ -581,7 +818,7 @@ mod tests {
 
     #[test]
     fn test_decay() {
-        let mut memory = IneruMemory::default();
+        let memory = IneruMemory::default();
 
         let entry = MemoryEntry::new("decaying", serde_json::json!({"val": 1}));
         memory.remember_important(entry, 1.0).unwrap();
@@ -595,7 +832,7 @@ mod tests {
 
     #[test]
     fn test_prune_stm() {
-        let mut memory = IneruMemory::iot_mode(); // Smaller capacity
+        let memory = IneruMemory::iot_mode(); // Smaller capacity
 
         // Add many entries to exceed capacity
         for i in 0..200 {
@@ -609,7 +846,7 @@ mod tests {
 
     #[test]
     fn test_stats() {
-        let mut memory = IneruMemory::default();
+        let memory = IneruMemory::default();
 
         for i in 0..5 {
             let entry = MemoryEntry::new(&format!("stat_{}", i), serde_json::json!({"n": i}));
@@ -653,7 +890,7 @@ mod tests {
 
     #[test]
     fn test_clear() {
-        let mut memory = IneruMemory::default();
+        let memory = IneruMemory::default();
 
         for i in 0..5 {
             let entry = MemoryEntry::new(&format!("clear_{}", i), serde_json::json!({"n": i}));
@@ -669,7 +906,7 @@ mod tests {
 
     #[test]
     fn test_get_from_ltm() {
-        let mut memory = IneruMemory::default();
+        let memory = IneruMemory::default();
 
         let entry = MemoryEntry::new("ltm_entry", serde_json::json!({"data": 42}));
         let id = memory.remember(entry).unwrap();
@@ -695,7 +932,7 @@ mod tests {
 
     #[test]
     fn test_multiple_operations() {
-        let mut memory = IneruMemory::default();
+        let memory = IneruMemory::default();
 
         // Add entries
         let ids: Vec<MemoryId> = (0..10)
@@ -723,4 +960,257 @@ mod tests {
         memory.clear().unwrap();
         assert_eq!(memory.stats().stm_count, 0);
     }
+
+    #[test]
+    fn test_apply_retention_deletes_expired_user_messages() {
+        const DAY: u64 = 24 * 3600;
+        let config = MemoryConfig {
+            consolidation: ConsolidationConfig {
+                importance_threshold: 0.0,
+                min_access_count: 0,
+                min_age_secs: 0,
+                batch_size: 100,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+        .with_retention("user_msg", std::time::Duration::from_secs(30 * DAY));
+        let memory = IneruMemory::new(config);
+
+        // Mocked clock: every entry is created at day 1000
+        let created = Timestamp::from_secs(1000 * DAY);
+        let remember = |name: &str, tag: &str| {
+            let mut entry = MemoryEntry::new("event", serde_json::json!({ "name": name }))
+                .with_tags(&[tag])
+                .with_embedding(Embedding::from_text_simple(name));
+            entry.metadata.created_at = created;
+            entry.metadata.access_count = 2;
+            memory.remember(entry).unwrap()
+        };
+        let user_ids = [remember("hello", "user_msg"), remember("bye", "user_msg")];
+        let telemetry_id = remember("cpu_load", "telemetry");
+
+        assert_eq!(memory.consolidate().unwrap(), 3);
+        // Three named entities, two tag entities, one TAGGED link per entry
+        assert_eq!(memory.stats().ltm_entity_count, 5);
+        assert_eq!(memory.stats().ltm_link_count, 3);
+
+        // Inside the window nothing is removed
+        let report = memory
+            .apply_retention(Timestamp::from_secs(1029 * DAY))
+            .unwrap();
+        assert_eq!(report.memories_removed(), 0);
+        assert_eq!(memory.stats().ltm_link_count, 3);
+
+        let report = memory
+            .apply_retention(Timestamp::from_secs(1031 * DAY))
+            .unwrap();
+        assert_eq!(report.rules.len(), 1);
+        assert_eq!(report.rules[0].tag_pattern, "user_msg");
+        assert_eq!(report.rules[0].stm_removed, 2);
+        assert_eq!(report.rules[0].ltm_removed, 2);
+        assert_eq!(report.entities_removed, 3);
+        assert_eq!(report.links_removed, 2);
+
+        for id in &user_ids {
+            assert!(memory.get(id).unwrap().is_none());
+        }
+        assert!(memory.stm().get(&telemetry_id).unwrap().is_some());
+        assert!(memory.ltm().get(&telemetry_id).unwrap().is_some());
+
+        let stats = memory.stats();
+        assert_eq!(stats.stm_count, 1);
+        assert_eq!(stats.ltm_entity_count, 2);
+        assert_eq!(stats.ltm_link_count, 1);
+        assert_eq!(memory.ltm().hnsw_index().unwrap().len(), 1);
+        assert_eq!(memory.ltm().hnsw_index().unwrap().stats().deleted_count, 0);
+
+        // Telemetry has no policy and survives indefinitely
+        let report = memory
+            .apply_retention(Timestamp::from_secs(100_000 * DAY))
+            .unwrap();
+        assert_eq!(report.memories_removed(), 0);
+        assert!(memory.get(&telemetry_id).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_apply_retention_uses_shortest_matching_policy() {
+        const DAY: u64 = 24 * 3600;
+        let config = MemoryConfig::default()
+            .with_retention("user_*", std::time::Duration::from_secs(30 * DAY))
+            .with_retention("pii", std::time::Duration::from_secs(7 * DAY));
+        let memory = IneruMemory::new(config);
+
+        let mut entry =
+            MemoryEntry::new("chat", serde_json::json!({})).with_tags(&["user_msg", "pii"]);
+        entry.metadata.created_at = Timestamp::from_secs(0);
+        memory.remember(entry).unwrap();
+
+        let report = memory
+            .apply_retention(Timestamp::from_secs(8 * DAY))
+            .unwrap();
+        assert_eq!(report.rules[0].stm_removed, 0);
+        assert_eq!(report.rules[1].stm_removed, 1);
+        assert_eq!(memory.stats().stm_count, 0);
+    }
+
+    fn concurrent_config() -> MemoryConfig {
+        let mut config = MemoryConfig::default();
+        config.stm.max_entries = 10_000;
+        config.stm.max_memory_bytes = 64 * 1024 * 1024;
+        config.consolidation.min_age_secs = 0;
+        config.consolidation.batch_size = 25;
+        config
+    }
+
+    fn stress_entry(thread: usize, n: usize) -> MemoryEntry {
+        let mut entry = MemoryEntry::new("stress", serde_json::json!({"thread": thread, "n": n}))
+            .with_tags(&["stress"]);
+        entry.metadata.access_count = 2;
+        entry
+    }
+
+    #[test]
+    fn test_concurrent_remember_recall_consolidate() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+        use std::thread;
+
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<IneruMemory>();
+
+        const WRITERS: usize = 4;
+        const PER_WRITER: usize = 200;
+        let memory = Arc::new(IneruMemory::new(concurrent_config()));
+        let done = Arc::new(AtomicBool::new(false));
+
+        // Entries stored up front are consolidated while readers are running
+        let canaries: Arc<HashSet<MemoryId>> = Arc::new(
+            (0..50)
+                .map(|n| memory.remember_important(stress_entry(WRITERS, n), 0.9))
+                .collect::<Result<_>>()
+                .unwrap(),
+        );
+
+        let writers: Vec<_> = (0..WRITERS)
+            .map(|t| {
+                let memory = Arc::clone(&memory);
+                thread::spawn(move || {
+                    (0..PER_WRITER)
+                        .map(|n| memory.remember_important(stress_entry(t, n), 0.9).unwrap())
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        let consolidators: Vec<_> = (0..2)
+            .map(|_| {
+                let (memory, done) = (Arc::clone(&memory), Arc::clone(&done));
+                thread::spawn(move || {
+                    let mut moved = 0;
+                    while !done.load(Ordering::SeqCst) {
+                        moved += memory.consolidate().unwrap();
+                    }
+                    moved
+                })
+            })
+            .collect();
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let (memory, done, canaries) = (
+                    Arc::clone(&memory),
+                    Arc::clone(&done),
+                    Arc::clone(&canaries),
+                );
+                thread::spawn(move || {
+                    while !done.load(Ordering::SeqCst) {
+                        let results = memory.recall_tagged(&["stress"]).unwrap();
+                        let ids: HashSet<_> = results.iter().map(|r| r.entry.id.clone()).collect();
+                        assert_eq!(ids.len(), results.len(), "recall returned an entry twice");
+                        assert!(canaries.is_subset(&ids), "recall lost an entry being moved");
+                    }
+                })
+            })
+            .collect();
+
+        let written: Vec<MemoryId> = writers
+            .into_iter()
+            .flat_map(|w| w.join().unwrap())
+            .collect();
+        done.store(true, Ordering::SeqCst);
+        let mut moved: usize = consolidators.into_iter().map(|c| c.join().unwrap()).sum();
+        for reader in readers {
+            reader.join().unwrap();
+        }
+        loop {
+            let n = memory.consolidate().unwrap();
+            if n == 0 {
+                break;
+            }
+            moved += n;
+        }
+
+        // Every entry was consolidated exactly once and is still in STM as well
+        let total = written.len() + canaries.len();
+        assert_eq!(moved, total);
+        assert_eq!(memory.ltm().memory_count(), total);
+        assert_eq!(memory.stats().stm_count, total);
+        assert_eq!(memory.recall_tagged(&["stress"]).unwrap().len(), total);
+        for id in written.iter().chain(canaries.iter()) {
+            assert!(memory.get(id).unwrap().is_some());
+        }
+    }
+
+    #[test]
+    fn test_forget_during_consolidation_does_not_resurrect() {
+        use std::sync::Arc;
+        use std::thread;
+
+        let memory = Arc::new(IneruMemory::new(concurrent_config()));
+        let ids: Vec<MemoryId> = (0..400)
+            .map(|n| memory.remember_important(stress_entry(0, n), 0.9).unwrap())
+            .collect();
+
+        let consolidator = {
+            let memory = Arc::clone(&memory);
+            thread::spawn(move || while memory.consolidate().unwrap() > 0 {})
+        };
+        for id in ids.iter().step_by(2) {
+            memory.forget(id).unwrap();
+        }
+        consolidator.join().unwrap();
+        while memory.consolidate().unwrap() > 0 {}
+
+        for (n, id) in ids.iter().enumerate() {
+            assert_eq!(memory.get(id).unwrap().is_some(), n % 2 == 1, "entry {n}");
+            assert_eq!(memory.ltm().get(id).unwrap().is_some(), n % 2 == 1);
+        }
+        assert_eq!(memory.ltm().memory_count(), ids.len() / 2);
+    }
+
+    #[test]
+    fn test_export_ltm_snapshot() {
+        let memory = IneruMemory::default();
+        let alice = memory
+            .ltm_mut()
+            .add_entity(Entity::new("person", "Alice"))
+            .unwrap();
+        let bob = memory
+            .ltm_mut()
+            .add_entity(Entity::new("person", "Bob"))
+            .unwrap();
+        memory
+            .ltm_mut()
+            .add_link(Link::new(alice, Relation::related_to(), bob))
+            .unwrap();
+
+        let dir = std::env::temp_dir().join(format!("ineru-export-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for format in [ExportFormat::Dot, ExportFormat::Turtle] {
+            let path = dir.join(format!("ltm.{}", format.extension()));
+            memory.export_ltm_snapshot(&path, format).unwrap();
+            let text = std::fs::read_to_string(&path).unwrap();
+            assert!(text.contains("Alice") && text.contains("RELATED_TO"));
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -38,6 +38,33 @@ pub struct LongTermMemory {
     memory_usage: usize,
     /// Optional HNSW index for fast vector search on memory embeddings.
     hnsw_index: Option<HnswIndex>,
+    /// The memories each entity was extracted from.
+    entity_sources: HashMap<EntityId, HashSet<MemoryId>>,
+    /// The memories each link was extracted from.
+    link_sources: HashMap<LinkKey, HashSet<MemoryId>>,
+}
+
+/// Identifies the links between two entities with a given relation.
+type LinkKey = (EntityId, Relation, EntityId);
+
+fn link_key(link: &Link) -> LinkKey {
+    (
+        link.source.clone(),
+        link.relation.clone(),
+        link.target.clone(),
+    )
+}
+
+/// What a hard deletion of memories removed from the LTM.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PurgeStats {
+    /// The number of memory entries removed.
+    pub memories: usize,
+    /// The number of entities removed because all their source memories were deleted.
+    pub entities: usize,
+    /// The number of links removed, either because all their source memories
+    /// were deleted or because one of their endpoints was removed.
+    pub links: usize,
 }
 
 impl LongTermMemory {
@@ -59,6 +86,8 @@ impl LongTermMemory {
             config,
             memory_usage: 0,
             hnsw_index,
+            entity_sources: HashMap::new(),
+            link_sources: HashMap::new(),
         }
     }
 
@@ -140,6 +169,57 @@ impl LongTermMemory {
         Ok(())
     }
 
+    /// Permanently deletes memories and the knowledge extracted only from them.
+    ///
+    /// Unlike [`remove`](Self::remove), which only unlinks an entry, this also
+    /// rebuilds the HNSW index so the deleted embeddings are dropped, and
+    /// removes every entity and link whose source memories are all in `ids`.
+    /// Entities and links added without a source memory are kept.
+    pub fn purge(&mut self, ids: &HashSet<MemoryId>) -> Result<PurgeStats> {
+        let mut stats = PurgeStats::default();
+        if ids.is_empty() {
+            return Ok(stats);
+        }
+
+        for id in ids {
+            if self.memories.contains_key(id) {
+                self.remove(id)?;
+                stats.memories += 1;
+            }
+        }
+        if let Some(hnsw) = self.hnsw_index.as_mut() {
+            if hnsw.stats().deleted_count > 0 {
+                hnsw.rebuild();
+            }
+        }
+
+        let orphaned_entities = release_sources(&mut self.entity_sources, ids);
+        let orphaned_links = release_sources(&mut self.link_sources, ids);
+
+        for id in &orphaned_entities {
+            if self.entities.remove(id).is_some() {
+                stats.entities += 1;
+            }
+        }
+
+        let before = self.link_count();
+        for links in self.links_out.values_mut() {
+            links.retain(|link| {
+                !orphaned_entities.contains(&link.source)
+                    && !orphaned_entities.contains(&link.target)
+                    && !orphaned_links.contains(&link_key(link))
+            });
+        }
+        self.links_out.retain(|_, links| !links.is_empty());
+        self.link_sources.retain(|(source, _, target), _| {
+            !orphaned_entities.contains(source) && !orphaned_entities.contains(target)
+        });
+        self.rebuild_links_in();
+        stats.links = before - self.link_count();
+
+        Ok(stats)
+    }
+
     /// Queries the LTM for memories matching the given `MemoryQuery`.
     pub fn query(&self, query: &MemoryQuery) -> Result<Vec<MemoryResult>> {
         let mut results = Vec::new();
@@ -198,6 +278,19 @@ impl LongTermMemory {
         Ok(id)
     }
 
+    /// Adds an `Entity` extracted from the memory `source`.
+    ///
+    /// The entity is recorded as derived from `source`; if every memory it was
+    /// derived from is later [purged](Self::purge), the entity is removed too.
+    pub fn add_entity_from(&mut self, entity: Entity, source: &MemoryId) -> Result<EntityId> {
+        let id = self.add_entity(entity)?;
+        self.entity_sources
+            .entry(id.clone())
+            .or_default()
+            .insert(source.clone());
+        Ok(id)
+    }
+
     /// Retrieves an `Entity` by its ID from the knowledge graph.
     pub fn get_entity(&self, id: &EntityId) -> Option<&Entity> {
         self.entities.get(id)
@@ -229,6 +322,20 @@ impl LongTermMemory {
         Ok(())
     }
 
+    /// Adds a `Link` extracted from the memory `source`.
+    ///
+    /// Like [`add_entity_from`](Self::add_entity_from), the link is removed
+    /// once every memory it was derived from has been purged.
+    pub fn add_link_from(&mut self, link: Link, source: &MemoryId) -> Result<()> {
+        let key = link_key(&link);
+        self.add_link(link)?;
+        self.link_sources
+            .entry(key)
+            .or_default()
+            .insert(source.clone());
+        Ok(())
+    }
+
     /// Retrieves all outgoing links from a given entity.
     pub fn get_links_from(&self, id: &EntityId) -> Vec<&Link> {
         self.links_out
@@ -416,6 +523,16 @@ impl LongTermMemory {
         self.memories.values().cloned().collect()
     }
 
+    /// Iterates over the memory entries stored in the LTM.
+    pub fn iter(&self) -> impl Iterator<Item = &MemoryEntry> {
+        self.memories.values()
+    }
+
+    /// Iterates over the entities of the knowledge graph, in no particular order.
+    pub fn entities(&self) -> impl Iterator<Item = &Entity> {
+        self.entities.values()
+    }
+
     /// Returns the number of entities in the knowledge graph.
     pub fn entity_count(&self) -> usize {
         self.entities.len()
@@ -439,6 +556,8 @@ impl LongTermMemory {
         self.links_in.clear();
         self.tag_index.clear();
         self.type_index.clear();
+        self.entity_sources.clear();
+        self.link_sources.clear();
         self.memory_usage = 0;
         if let Some(hnsw) = self.hnsw_index.as_mut() {
             hnsw.rebuild(); // clears all points
@@ -448,6 +567,17 @@ impl LongTermMemory {
 
     // ============ Private helpers ============
 
+    /// Rebuilds the incoming-link index from the outgoing links.
+    fn rebuild_links_in(&mut self) {
+        self.links_in.clear();
+        for link in self.links_out.values().flatten() {
+            self.links_in
+                .entry(link.target.clone())
+                .or_default()
+                .push(link.source.clone());
+        }
+    }
+
     /// Gets a list of candidate memory IDs based on query indices (tags or type).
     fn get_candidates(&self, query: &MemoryQuery) -> Vec<MemoryId> {
         // If we have specific tags, use tag index
@@ -545,6 +675,26 @@ impl LongTermMemory {
     }
 }
 
+/// Removes `ids` from every source set, returning the keys left without any
+/// source. Their entries are dropped from `sources`.
+fn release_sources<K: Clone + Eq + std::hash::Hash>(
+    sources: &mut HashMap<K, HashSet<MemoryId>>,
+    ids: &HashSet<MemoryId>,
+) -> HashSet<K> {
+    let mut orphaned = HashSet::new();
+    sources.retain(|key, set| {
+        let before = set.len();
+        set.retain(|id| !ids.contains(id));
+        if set.is_empty() && before > 0 {
+            orphaned.insert(key.clone());
+            false
+        } else {
+            true
+        }
+    });
+    orphaned
+}
+
 /// A wrapper providing a simplified API for interacting with the LTM as a knowledge graph.
 pub struct KnowledgeGraph<'a> {
     ltm: &'a mut LongTermMemory,
@@ -669,4 +819,55 @@ mod tests {
 
         assert_eq!(results.len(), 1);
     }
+
+    #[test]
+    fn test_purge_removes_knowledge_only_from_purged_memories() {
+        let mut ltm = LongTermMemory::new(LtmConfig::default());
+        let first = make_entry("first").with_embedding(Embedding::new(vec![1.0, 0.0]));
+        let second = make_entry("second").with_embedding(Embedding::new(vec![0.0, 1.0]));
+        let (first_id, second_id) = (first.id.clone(), second.id.clone());
+        ltm.store(first).unwrap();
+        ltm.store(second).unwrap();
+
+        // `a` comes from the first memory, `shared` from both, `manual` from neither
+        let a = ltm
+            .add_entity_from(Entity::new("node", "a"), &first_id)
+            .unwrap();
+        let shared = Entity::new("node", "shared");
+        let shared = ltm.add_entity_from(shared.clone(), &first_id).unwrap();
+        ltm.add_entity_from(Entity::new("node", "shared"), &second_id)
+            .unwrap();
+        let manual = ltm.add_entity(Entity::new("node", "manual")).unwrap();
+        ltm.add_link_from(
+            Link::new(a.clone(), Relation::related_to(), shared.clone()),
+            &first_id,
+        )
+        .unwrap();
+        ltm.add_link(Link::new(manual.clone(), Relation::has(), a.clone()))
+            .unwrap();
+        ltm.add_link(Link::new(manual.clone(), Relation::has(), shared.clone()))
+            .unwrap();
+
+        let stats = ltm.purge(&HashSet::from([first_id.clone()])).unwrap();
+
+        assert_eq!(
+            stats,
+            PurgeStats {
+                memories: 1,
+                entities: 1,
+                links: 2,
+            }
+        );
+        assert!(ltm.get(&first_id).unwrap().is_none());
+        assert!(ltm.get_entity(&a).is_none());
+        assert!(ltm.get_entity(&shared).is_some());
+        assert_eq!(ltm.link_count(), 1);
+        assert_eq!(ltm.get_links_to(&shared).len(), 1);
+        assert!(ltm.get_links_to(&a).is_empty());
+
+        // The deleted embedding is gone from the index, not just tombstoned
+        let hnsw = ltm.hnsw_index().unwrap();
+        assert_eq!(hnsw.len(), 1);
+        assert_eq!(hnsw.stats().deleted_count, 0);
+    }
 }
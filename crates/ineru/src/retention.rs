@@ -0,0 +1,121 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Tag-based retention for privacy-motivated hard deletion.
+//!
+//! A [`RetentionPolicy`] deletes memories carrying a matching tag once they
+//! reach a maximum age. `IneruMemory::apply_retention` evaluates every policy
+//! in the `MemoryConfig` against STM and LTM and reports what it removed, per
+//! policy, for auditing.
+//!
+//! When several policies match a memory, the shortest `max_age` wins and the
+//! deletion is attributed to that policy.
+
+use crate::config::RetentionPolicy;
+use crate::types::{MemoryEntry, Timestamp};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// What a retention run removed on behalf of a single policy.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleRetention {
+    /// The policy's tag pattern.
+    pub tag_pattern: String,
+    /// The policy's maximum age.
+    pub max_age: Duration,
+    /// The number of expired entries removed from STM.
+    pub stm_removed: usize,
+    /// The number of expired entries removed from LTM.
+    pub ltm_removed: usize,
+}
+
+/// The outcome of `IneruMemory::apply_retention`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionReport {
+    /// The time the retention run was evaluated at.
+    pub evaluated_at: Timestamp,
+    /// Removal counts for each configured policy, in configuration order.
+    pub rules: Vec<RuleRetention>,
+    /// The number of LTM entities removed because all their source memories expired.
+    pub entities_removed: usize,
+    /// The number of LTM links removed along with expired memories or entities.
+    pub links_removed: usize,
+}
+
+impl RetentionReport {
+    /// Creates an empty report with one line per policy.
+    pub fn new(policies: &[RetentionPolicy], evaluated_at: Timestamp) -> Self {
+        Self {
+            evaluated_at,
+            rules: policies
+                .iter()
+                .map(|p| RuleRetention {
+                    tag_pattern: p.tag_pattern.clone(),
+                    max_age: p.max_age,
+                    stm_removed: 0,
+                    ltm_removed: 0,
+                })
+                .collect(),
+            entities_removed: 0,
+            links_removed: 0,
+        }
+    }
+
+    /// The total number of memory entries removed from STM and LTM.
+    pub fn memories_removed(&self) -> usize {
+        self.rules
+            .iter()
+            .map(|r| r.stm_removed + r.ltm_removed)
+            .sum()
+    }
+}
+
+/// Returns the index of the policy that expires `entry` at `now`, if any.
+///
+/// Among the policies matching one of the entry's tags, the one with the
+/// shortest `max_age` governs; the entry has expired once it is older than
+/// that age.
+pub(crate) fn expiring_policy(
+    entry: &MemoryEntry,
+    policies: &[RetentionPolicy],
+    now: Timestamp,
+) -> Option<usize> {
+    let (index, policy) = policies
+        .iter()
+        .enumerate()
+        .filter(|(_, p)| entry.tags.iter().any(|tag| p.matches(tag)))
+        .min_by_key(|(_, p)| p.max_age)?;
+
+    let age_micros = now.0.saturating_sub(entry.metadata.created_at.0);
+    (u128::from(age_micros) > policy.max_age.as_micros()).then_some(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: Duration = Duration::from_secs(24 * 3600);
+
+    #[test]
+    fn test_shortest_matching_policy_governs() {
+        let policies = vec![
+            RetentionPolicy::new("user_*", DAY * 30),
+            RetentionPolicy::new("pii", DAY * 7),
+            RetentionPolicy::new("telemetry", DAY),
+        ];
+        let mut entry =
+            MemoryEntry::new("chat", serde_json::json!({})).with_tags(&["user_msg", "pii"]);
+        entry.metadata.created_at = Timestamp::from_secs(0);
+
+        let at = |days: u64| Timestamp::from_secs(days * 24 * 3600 + 1);
+        assert_eq!(expiring_policy(&entry, &policies, at(6)), None);
+        assert_eq!(expiring_policy(&entry, &policies, at(7)), Some(1));
+
+        entry.tags.pop();
+        assert_eq!(expiring_policy(&entry, &policies, at(7)), None);
+        assert_eq!(expiring_policy(&entry, &policies, at(30)), Some(0));
+
+        entry.tags.clear();
+        assert_eq!(expiring_policy(&entry, &policies, at(365)), None);
+    }
+}
@@ -269,6 +269,11 @@ impl ShortTermMemory {
         self.entries.values().cloned().collect()
     }
 
+    /// Iterates over the entries currently in the STM.
+    pub fn iter(&self) -> impl Iterator<Item = &MemoryEntry> {
+        self.entries.values()
+    }
+
     /// Returns the number of entries currently in the STM.
     pub fn len(&self) -> usize {
         self.entries.len()
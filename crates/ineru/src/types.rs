@@ -3,6 +3,7 @@
 
 //! Core data types for the Ineru memory system.
 
+use crate::importance::EXPLICIT_IMPORTANCE;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -140,6 +141,7 @@ impl MemoryEntry {
     /// Sets the importance score for the memory entry.
     pub fn with_importance(mut self, importance: f32) -> Self {
         self.metadata.importance = importance;
+        self.metadata.importance_source = Some(EXPLICIT_IMPORTANCE.to_string());
         self
     }
 
@@ -176,6 +178,10 @@ pub struct MemoryMetadata {
     pub consolidated: bool,
     /// A string indicating the origin of this memory (e.g., "sensor", "user", "inference").
     pub source: String,
+    /// Where `importance` came from: `"explicit"` when set by the caller, the
+    /// name of the `ImportanceScorer` that computed it, or `None` for the default.
+    #[serde(default)]
+    pub importance_source: Option<String>,
 }
 
 impl Default for MemoryMetadata {
@@ -189,6 +195,7 @@ impl Default for MemoryMetadata {
             attention: 1.0,
             consolidated: false,
             source: "unknown".to_string(),
+            importance_source: None,
         }
     }
 }
@@ -6,6 +6,7 @@
 //! Actions represent what an agent can do in its environment. They are the
 //! output of the agent's decision-making process.
 
+use crate::schema::ActionBuilder;
 use crate::types::{Priority, Timestamp, Value};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -180,6 +181,24 @@ impl Action {
         }
     }
 
+    /// Starts building the custom action named `name`.
+    ///
+    /// Without a schema every parameter is accepted; build through
+    /// [`SchemaRegistry::build`](crate::schema::SchemaRegistry::build) to
+    /// check parameters as they are set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use kaneru::{Action, ActionType};
+    /// let action = Action::build("set_valve").param("position", 0.7)?.finish()?;
+    /// assert_eq!(action.action_type, ActionType::Custom("set_valve".into()));
+    /// # Ok::<(), kaneru::SchemaError>(())
+    /// ```
+    pub fn build(name: &str) -> ActionBuilder<'static> {
+        ActionBuilder::new(ActionType::Custom(name.to_string()))
+    }
+
     /// Creates a `NoOp` (no-operation) action.
     ///
     /// This action does nothing when executed. It's useful as a default or placeholder.
@@ -355,6 +374,9 @@ pub struct ActionResult {
     pub executed_at: Timestamp,
     /// The duration of the execution in microseconds.
     pub duration_us: u64,
+    /// `true` if a safety interlock blocked the action, so nothing was executed.
+    #[serde(default)]
+    pub vetoed: bool,
 }
 
 impl ActionResult {
@@ -380,6 +402,7 @@ impl ActionResult {
             error: None,
             executed_at: Timestamp::now(),
             duration_us: 0,
+            vetoed: false,
         }
     }
 
@@ -406,6 +429,7 @@ impl ActionResult {
             error: None,
             executed_at: Timestamp::now(),
             duration_us: 0,
+            vetoed: false,
         }
     }
 
@@ -432,6 +456,27 @@ impl ActionResult {
             error: Some(error.to_string()),
             executed_at: Timestamp::now(),
             duration_us: 0,
+            vetoed: false,
+        }
+    }
+
+    /// Creates the no-op result of an action blocked by a safety interlock.
+    ///
+    /// The result is unsuccessful and its error is the reason the action was
+    /// blocked. See [`SafetyLayer`](crate::safety::SafetyLayer).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use kaneru::ActionResult;
+    /// let result = ActionResult::vetoed("action_123", "tank level below 5%");
+    /// assert!(result.vetoed);
+    /// assert!(!result.success);
+    /// ```
+    pub fn vetoed(action_id: &str, reason: &str) -> Self {
+        Self {
+            vetoed: true,
+            ..Self::failure(action_id, reason)
         }
     }
 
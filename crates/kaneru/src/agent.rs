@@ -12,6 +12,10 @@ use crate::goal::{Goal, GoalManager};
 use crate::learning::{ActionId, LearningConfig, LearningEngine, StateId};
 use crate::observation::{Observation, ObservationBuffer};
 use crate::policy::{Policy, PolicyEngine, Rule};
+use crate::preprocessing::{ObservationPipeline, SensorPipeline};
+use crate::safety::SafetyLayer;
+use crate::schedule::{ObservationTemplate, Schedule, Scheduler};
+use crate::schema::SchemaRegistry;
 use crate::types::Timestamp;
 use serde::{Deserialize, Serialize};
 
@@ -390,6 +394,14 @@ pub struct SimpleAgent {
     learning_engine: Option<LearningEngine>,
     /// The last state-action pair, used for Q-learning updates.
     last_state_action: Option<(StateId, ActionId)>,
+    /// Per-sensor preprocessing applied before observations are stored.
+    preprocessing: ObservationPipeline,
+    /// Interlocks and rate limits checked before each action is executed.
+    safety: SafetyLayer,
+    /// Parameter schemas checked before each action is executed.
+    schemas: SchemaRegistry,
+    /// Schedules whose observations are delivered on each step.
+    scheduler: Scheduler,
 }
 
 impl SimpleAgent {
@@ -470,6 +482,10 @@ impl SimpleAgent {
             stats: AgentStats::default(),
             learning_engine,
             last_state_action: None,
+            preprocessing: ObservationPipeline::new(),
+            safety: SafetyLayer::new(),
+            schemas: SchemaRegistry::new(),
+            scheduler: Scheduler::new(),
         }
     }
 
@@ -824,6 +840,151 @@ impl SimpleAgent {
         self.learning_engine.as_mut()
     }
 
+    /// Sets the preprocessing pipeline for readings of the sensor named `name`.
+    ///
+    /// Readings are cleaned before the agent stores them, so rules and learning
+    /// only ever see the processed value. Readings rejected as outliers are
+    /// counted in [`AgentStats::outliers_rejected`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use kaneru::{Agent, SimpleAgent, Observation};
+    /// # use kaneru::preprocessing::{OutlierFilter, SensorPipeline};
+    /// let mut agent = SimpleAgent::new("my_agent");
+    /// agent.set_sensor_pipeline(
+    ///     "temp",
+    ///     SensorPipeline::new().reject_outliers(OutlierFilter::mad(3.5)),
+    /// );
+    ///
+    /// for _ in 0..5 {
+    ///     agent.observe(Observation::sensor("temp", 20.0));
+    /// }
+    /// agent.observe(Observation::sensor("temp", 500.0));
+    /// assert_eq!(agent.stats().outliers_rejected, 1);
+    /// ```
+    pub fn set_sensor_pipeline(&mut self, name: &str, pipeline: SensorPipeline) {
+        self.preprocessing.set_sensor(name, pipeline);
+    }
+
+    /// Whether rejected readings are observed as
+    /// [`GLITCH_OBSERVATION`](crate::preprocessing::GLITCH_OBSERVATION) state
+    /// changes, so a rule can alert on a glitching sensor.
+    pub fn set_glitch_observations(&mut self, enabled: bool) {
+        self.preprocessing.set_glitch_observations(enabled);
+    }
+
+    /// Sets the safety layer checked before each action is executed.
+    ///
+    /// A blocked action is not executed: [`execute`](Agent::execute) returns
+    /// an [`ActionResult::vetoed`] carrying the reason, counts it in
+    /// [`AgentStats::actions_vetoed`] or [`AgentStats::actions_rate_limited`],
+    /// and [`learn`](Agent::learn) rewards it with the layer's penalty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use kaneru::{Action, ActionType, Agent, Condition, Observation, SimpleAgent};
+    /// # use kaneru::safety::SafetyLayer;
+    /// let pump = ActionType::Custom("pump_on".into());
+    /// let mut agent = SimpleAgent::new("pump_controller");
+    /// agent.set_safety_layer(SafetyLayer::new().veto(
+    ///     pump.clone(),
+    ///     Condition::below("tank_level", 5.0),
+    ///     "tank level below 5%",
+    /// ));
+    ///
+    /// agent.observe(Observation::sensor("tank_level", 3.0));
+    /// let result = agent.execute(Action::new(pump));
+    /// assert!(result.vetoed);
+    /// assert_eq!(agent.stats().actions_vetoed, 1);
+    /// ```
+    pub fn set_safety_layer(&mut self, safety: SafetyLayer) {
+        self.safety = safety;
+    }
+
+    /// Returns the safety layer checked before each action is executed.
+    pub fn safety_layer(&self) -> &SafetyLayer {
+        &self.safety
+    }
+
+    /// Sets the parameter schemas checked before each action is executed.
+    ///
+    /// An action breaking its schema is not executed: [`execute`](Agent::execute)
+    /// returns an [`ActionResult::failure`] listing the violations and counts it
+    /// in [`AgentStats::actions_invalid`]. Use
+    /// [`PolicyEngine::validate_against`] to check the agent's policies when
+    /// they are loaded instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use kaneru::{Action, ActionSchema, Agent, ParamSpec, SchemaRegistry, SimpleAgent};
+    /// let mut schemas = SchemaRegistry::new();
+    /// schemas.register(
+    ///     ActionSchema::custom("set_valve").param("position", ParamSpec::float().range(0.0, 1.0)),
+    /// );
+    /// let mut agent = SimpleAgent::new("valve_controller");
+    /// agent.set_action_schemas(schemas);
+    ///
+    /// let action = Action::build("set_valve").param("position", 1.5)?.finish()?;
+    /// let result = agent.execute(action);
+    /// assert!(!result.success);
+    /// assert_eq!(agent.stats().actions_invalid, 1);
+    /// # Ok::<(), kaneru::SchemaError>(())
+    /// ```
+    pub fn set_action_schemas(&mut self, schemas: SchemaRegistry) {
+        self.schemas = schemas;
+    }
+
+    /// Returns the parameter schemas checked before each action is executed.
+    pub fn action_schemas(&self) -> &SchemaRegistry {
+        &self.schemas
+    }
+
+    /// Adds a schedule whose observations the agent receives when due.
+    ///
+    /// Each [`step`](Agent::step) first observes the earliest due
+    /// [`Scheduled`](crate::ObservationType::Scheduled) observation, if any,
+    /// so rules on the template's name decide on it. A schedule with the same
+    /// name is replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use kaneru::{Action, Agent, Condition, Rule, SimpleAgent};
+    /// # use kaneru::schedule::{ObservationTemplate, Schedule};
+    /// let mut agent = SimpleAgent::new("battery_monitor");
+    /// agent.add_rule(Rule::new(
+    ///     "nightly_report",
+    ///     Condition::equals("battery_report", "battery_report"),
+    ///     Action::alert("Battery report"),
+    /// ));
+    /// agent.add_schedule(Schedule::daily(2, 0)?, ObservationTemplate::new("battery_report"));
+    /// # Ok::<(), kaneru::Error>(())
+    /// ```
+    pub fn add_schedule(&mut self, schedule: Schedule, template: ObservationTemplate) {
+        self.scheduler.add(schedule, template);
+    }
+
+    /// Sets the scheduler polled on each step, replacing its schedules.
+    ///
+    /// Use a scheduler built with [`Scheduler::with_clock`] to control the
+    /// time schedules see.
+    pub fn set_scheduler(&mut self, scheduler: Scheduler) {
+        self.scheduler = scheduler;
+    }
+
+    /// Returns the scheduler polled on each step, e.g. to save its state.
+    pub fn scheduler(&self) -> &Scheduler {
+        &self.scheduler
+    }
+
+    /// Returns the scheduler polled on each step, e.g. to restore its state.
+    pub fn scheduler_mut(&mut self) -> &mut Scheduler {
+        &mut self.scheduler
+    }
+
     /// Gets a list of available actions from the policy engine for a given observation.
     fn get_available_actions(&self, obs: &Observation) -> Vec<ActionId> {
         // Get action from policy engine
@@ -854,9 +1015,18 @@ impl Agent for SimpleAgent {
 
     fn observe(&mut self, observation: Observation) {
         self.state = AgentState::Processing;
+        self.stats.observations_received += 1;
+
+        let preprocessed = self.preprocessing.process(observation);
+        if preprocessed.outlier {
+            self.stats.outliers_rejected += 1;
+        }
+        let Some(observation) = preprocessed.observation else {
+            return;
+        };
+        self.safety.observe(&observation);
         self.observations.push(observation.clone());
         self.last_observation = Some(observation);
-        self.stats.observations_received += 1;
     }
 
     fn decide(&self) -> Action {
@@ -869,19 +1039,38 @@ impl Agent for SimpleAgent {
 
     fn execute(&mut self, action: Action) -> ActionResult {
         self.state = AgentState::Executing;
-        self.stats.actions_executed += 1;
 
-        // Store state-action pair for learning
+        // Store state-action pair for learning, blocked actions included
         if self.config.learning_enabled && self.last_observation.is_some() {
             let state_id = StateId::from_observation(self.last_observation.as_ref().unwrap());
             let action_id = ActionId::from_action(&action);
             self.last_state_action = Some((state_id, action_id));
         }
 
-        // Simple execution - just log and return success
-        log::debug!("Executing action: {:?}", action.action_type);
-
-        let result = ActionResult::success(&action.id);
+        let result = if let Err(error) = self.schemas.validate(&action) {
+            self.stats.actions_invalid += 1;
+            log::warn!("Rejected action: {}", error);
+            ActionResult::failure(&action.id, &error.to_string())
+        } else {
+            match self.safety.check(&action) {
+                Ok(()) => {
+                    self.stats.actions_executed += 1;
+
+                    // Simple execution - just log and return success
+                    log::debug!("Executing action: {:?}", action.action_type);
+                    ActionResult::success(&action.id)
+                }
+                Err(violation) => {
+                    if violation.is_interlock() {
+                        self.stats.actions_vetoed += 1;
+                    } else {
+                        self.stats.actions_rate_limited += 1;
+                    }
+                    log::warn!("Blocked action {:?}: {}", action.action_type, violation);
+                    ActionResult::vetoed(&action.id, &violation.to_string())
+                }
+            }
+        };
 
         // Store in history
         if self.action_history.len() >= 100 {
@@ -893,6 +1082,19 @@ impl Agent for SimpleAgent {
         result
     }
 
+    /// Observes the earliest due scheduled observation, if any, then decides
+    /// and executes as usual.
+    fn step(&mut self) -> Option<ActionResult> {
+        if let Some(observation) = self.scheduler.poll() {
+            self.observe(observation);
+        }
+        let action = self.decide();
+        if action.is_noop() {
+            return None;
+        }
+        Some(self.execute(action))
+    }
+
     fn learn(&mut self, observation: &Observation, action: &Action, result: &ActionResult) {
         if !self.config.learning_enabled {
             return;
@@ -908,7 +1110,13 @@ impl Agent for SimpleAgent {
         // Prepare learning data before borrowing engine
         let next_state = StateId::from_observation(observation);
         let available_actions = self.get_available_actions(observation);
-        let reward = if result.success { 1.0 } else { -1.0 };
+        let reward = if result.vetoed {
+            self.safety.penalty()
+        } else if result.success {
+            1.0
+        } else {
+            -1.0
+        };
         let next_action = ActionId::from_action(action);
 
         // Use learning engine if available
@@ -974,6 +1182,18 @@ pub struct AgentStats {
     pub goals_failed: u64,
     /// The total number of times the learning model has been updated.
     pub learning_updates: u64,
+    /// The number of sensor readings rejected as outliers by preprocessing.
+    #[serde(default)]
+    pub outliers_rejected: u64,
+    /// The number of actions blocked by a safety veto or exclusive group.
+    #[serde(default)]
+    pub actions_vetoed: u64,
+    /// The number of actions blocked by a safety rate limit.
+    #[serde(default)]
+    pub actions_rate_limited: u64,
+    /// The number of actions rejected for breaking their parameter schema.
+    #[serde(default)]
+    pub actions_invalid: u64,
 }
 
 impl AgentStats {
@@ -1163,4 +1383,110 @@ mod tests {
         assert!(engine.total_updates() >= 10);
         assert!(engine.state_action_count() > 0);
     }
+
+    #[test]
+    fn test_agent_rejects_spike_before_rules() {
+        use crate::preprocessing::OutlierFilter;
+
+        let mut agent = SimpleAgent::new("temp_monitor");
+        agent.set_exploration_rate(0.0);
+        agent.add_rule(Rule::new(
+            "high_temp",
+            Condition::above("temperature", 30.0),
+            Action::alert("Temperature too high!"),
+        ));
+        agent.set_sensor_pipeline(
+            "temperature",
+            SensorPipeline::new().reject_outliers(OutlierFilter::mad(3.5)),
+        );
+
+        for _ in 0..10 {
+            agent.observe(Observation::sensor("temperature", 21.0));
+        }
+        agent.observe(Observation::sensor("temperature", 400.0));
+
+        // The spike was replaced with the last good reading
+        assert_eq!(agent.stats().observations_received, 11);
+        assert_eq!(agent.stats().outliers_rejected, 1);
+        let last = agent.last_observation.as_ref().unwrap();
+        assert_eq!(last.value.as_f64(), Some(21.0));
+        assert_eq!(agent.decide().action_type, Action::noop().action_type);
+    }
+
+    #[test]
+    fn test_agent_alerts_on_glitch_observation() {
+        use crate::preprocessing::{OutlierFilter, GLITCH_OBSERVATION};
+
+        let mut agent = SimpleAgent::new("temp_monitor");
+        agent.set_exploration_rate(0.0);
+        agent.add_rule(Rule::new(
+            "sensor_glitching",
+            Condition::equals(GLITCH_OBSERVATION, "temperature"),
+            Action::alert("Temperature sensor glitching"),
+        ));
+        agent.set_sensor_pipeline(
+            "temperature",
+            SensorPipeline::new().reject_outliers(OutlierFilter::z_score(3.0)),
+        );
+        agent.set_glitch_observations(true);
+
+        for _ in 0..10 {
+            agent.observe(Observation::sensor("temperature", 21.0));
+        }
+        assert_eq!(agent.decide().action_type, Action::noop().action_type);
+
+        agent.observe(Observation::sensor("temperature", 400.0));
+        assert_eq!(agent.stats().outliers_rejected, 1);
+        assert!(matches!(
+            agent.decide().action_type,
+            crate::action::ActionType::Alert(_)
+        ));
+    }
+
+    #[test]
+    fn test_agent_steps_on_scheduled_observations() {
+        use crate::coordination::Clock;
+        use std::sync::atomic::{AtomicU64, Ordering};
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        #[derive(Default)]
+        struct ManualClock(AtomicU64);
+
+        impl Clock for ManualClock {
+            fn now(&self) -> Timestamp {
+                Timestamp(self.0.load(Ordering::SeqCst))
+            }
+        }
+
+        let clock = Arc::new(ManualClock::default());
+        let mut agent = SimpleAgent::new("battery_monitor");
+        agent.set_exploration_rate(0.0);
+        agent.add_rule(Rule::new(
+            "report",
+            Condition::equals("battery_report", "battery_report"),
+            Action::alert("Battery report"),
+        ));
+        agent.set_scheduler(Scheduler::with_clock(clock.clone()));
+        agent.add_schedule(
+            Schedule::every(Duration::from_secs(3600)),
+            ObservationTemplate::new("battery_report"),
+        );
+
+        assert!(agent.step().is_none());
+        assert_eq!(agent.stats().observations_received, 0);
+
+        clock.0.store(3_600_000_000, Ordering::SeqCst);
+        let result = agent.step().unwrap();
+        assert!(result.success);
+        assert_eq!(agent.stats().observations_received, 1);
+        assert!(matches!(
+            agent.action_history.last().unwrap().0.action_type,
+            crate::action::ActionType::Alert(_)
+        ));
+
+        // The next step has nothing new to observe
+        agent.step();
+        assert_eq!(agent.stats().observations_received, 1);
+    }
 }
@@ -6,8 +6,12 @@
 //! Enables multiple Kaneru agents to coordinate their actions through:
 //! - A central `AgentCoordinator`.
 //! - A `MessageBus` for inter-agent communication.
-//! - `SharedMemory` for common knowledge.
+//! - `SharedMemory` for common knowledge, with namespaces, TTLs, compare-and-swap
+//!   writes and change notifications.
 //! - Consensus mechanisms for group decisions.
+//! - Lease-based leader election per task group, and task claims so that
+//!   exactly one agent executes a given task.
+//! - Group schedules, whose observations every member of a task group steps on.
 //!
 //! ## Example
 //!
@@ -34,9 +38,13 @@
 //! let actions = coordinator.step_all(observations);
 //! ```
 
-use crate::{Action, AgentId, KaneruAgent, Observation, Outcome};
+use crate::schedule::{ObservationTemplate, Schedule, Scheduler};
+use crate::{Action, AgentId, KaneruAgent, Observation, ObservationType, Outcome, SchemaRegistry};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
 
 /// A unique identifier for a `Message`.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -111,6 +119,24 @@ pub enum MessagePayload {
         /// The new value.
         value: String,
     },
+    /// A new leader was elected for a task group.
+    LeaderElected {
+        /// The task group.
+        group: String,
+        /// The elected agent.
+        leader: AgentId,
+        /// The election term, which increases with every new leader.
+        term: u64,
+    },
+    /// An agent was granted the lease on a task.
+    TaskClaimed {
+        /// The task group.
+        group: String,
+        /// The claimed task.
+        task_id: String,
+        /// The agent that will execute the task.
+        agent: AgentId,
+    },
     /// A flexible JSON-encoded payload for custom data.
     Json(serde_json::Value),
 }
@@ -166,11 +192,157 @@ impl Message {
     }
 }
 
-/// A simple key-value store accessible by all agents through the coordinator.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// A source of time for `SharedMemory` entry expiry.
+///
+/// `SharedMemory` reads the clock before taking its internal lock, so
+/// implementations may do arbitrary work.
+pub trait Clock: Send + Sync {
+    /// Returns the current time.
+    fn now(&self) -> crate::types::Timestamp;
+}
+
+/// A `Clock` backed by the system wall clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> crate::types::Timestamp {
+        crate::types::Timestamp::now()
+    }
+}
+
+/// The scope a `SharedMemory` entry belongs to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Namespace {
+    /// Keys visible to and shared by every agent.
+    Global,
+    /// Keys owned by a single agent.
+    Agent(AgentId),
+}
+
+impl Namespace {
+    /// Returns the path prefix for keys in this namespace, as matched by
+    /// [`SharedMemory::watch`]: `global/` or `agent/<id>/`.
+    pub fn prefix(&self) -> String {
+        match self {
+            Namespace::Global => "global/".to_string(),
+            Namespace::Agent(id) => format!("agent/{}/", id.0),
+        }
+    }
+}
+
+/// A key qualified by its namespace.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ScopedKey {
+    /// The namespace the key lives in.
+    pub namespace: Namespace,
+    /// The key within the namespace.
+    pub key: String,
+}
+
+impl ScopedKey {
+    /// Creates a new `ScopedKey`.
+    pub fn new(namespace: Namespace, key: &str) -> Self {
+        Self {
+            namespace,
+            key: key.to_string(),
+        }
+    }
+
+    /// Returns the full path of the key, e.g. `agent/agent_0/temperature`.
+    pub fn path(&self) -> String {
+        format!("{}{}", self.namespace.prefix(), self.key)
+    }
+}
+
+impl std::fmt::Display for ScopedKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.namespace.prefix(), self.key)
+    }
+}
+
+/// A value stored in `SharedMemory`, with its version and expiry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SharedEntry {
+    /// The stored value.
+    pub value: String,
+    /// The revision at which the value was written. Revisions increase
+    /// monotonically across the whole store, so a key that is deleted and
+    /// recreated never reuses a version.
+    pub version: u64,
+    /// When the entry expires, if it has a TTL.
+    pub expires_at: Option<crate::types::Timestamp>,
+}
+
+impl SharedEntry {
+    fn is_expired(&self, now: crate::types::Timestamp) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| now.0 >= expires_at.0)
+    }
+}
+
+/// A change to `SharedMemory`, delivered to watchers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SharedMemoryEvent {
+    /// A key was created or overwritten.
+    Updated {
+        /// The key that changed.
+        key: ScopedKey,
+        /// The new value.
+        value: String,
+        /// The version of the new value.
+        version: u64,
+    },
+    /// A key was deleted.
+    Deleted {
+        /// The key that was deleted.
+        key: ScopedKey,
+    },
+    /// A key reached the end of its TTL and was removed.
+    Expired {
+        /// The key that expired.
+        key: ScopedKey,
+    },
+}
+
+impl SharedMemoryEvent {
+    /// Returns the key the event refers to.
+    pub fn key(&self) -> &ScopedKey {
+        match self {
+            SharedMemoryEvent::Updated { key, .. }
+            | SharedMemoryEvent::Deleted { key }
+            | SharedMemoryEvent::Expired { key } => key,
+        }
+    }
+}
+
+/// A key-value blackboard shared by all agents through the coordinator.
+///
+/// Entries live in a [`Namespace`] (global or per-agent), may carry a TTL after
+/// which they are treated as absent and removed lazily, and carry a version for
+/// compare-and-swap writes. Changes can be observed with [`SharedMemory::watch`].
+///
+/// `SharedMemory` is a handle: clones share the same store, and all methods
+/// take `&self`, so it can be used from multiple threads. No caller-supplied
+/// code runs while the internal lock is held; watchers receive events over
+/// channels, in the order the changes were applied.
+///
+/// The un-namespaced methods (`set`, `get`, `delete`, ...) operate on the
+/// global namespace.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(from = "SharedMemoryState", into = "SharedMemoryState")]
 pub struct SharedMemory {
+    inner: Arc<Mutex<SharedMemoryInner>>,
+    clock: Arc<dyn Clock>,
+}
+
+struct SharedMemoryInner {
     /// The underlying key-value data store.
-    data: HashMap<String, String>,
+    data: HashMap<ScopedKey, SharedEntry>,
+    /// The last revision handed out.
+    revision: u64,
+    /// Registered watchers, as `(path prefix, sender)` pairs.
+    watchers: Vec<(String, Sender<SharedMemoryEvent>)>,
     /// A log of recent access events for debugging purposes.
     access_log: VecDeque<(crate::types::Timestamp, String, AccessType)>,
     /// The maximum size of the access log.
@@ -184,65 +356,314 @@ enum AccessType {
     Delete,
 }
 
+/// The serialized form of `SharedMemory`.
+#[derive(Serialize, Deserialize)]
+struct SharedMemoryState {
+    data: Vec<(ScopedKey, SharedEntry)>,
+    revision: u64,
+}
+
+impl From<SharedMemoryState> for SharedMemory {
+    fn from(state: SharedMemoryState) -> Self {
+        let memory = Self::new();
+        {
+            let mut inner = memory.lock();
+            inner.data = state.data.into_iter().collect();
+            inner.revision = state.revision;
+        }
+        memory
+    }
+}
+
+impl From<SharedMemory> for SharedMemoryState {
+    fn from(memory: SharedMemory) -> Self {
+        let inner = memory.lock();
+        Self {
+            data: inner
+                .data
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            revision: inner.revision,
+        }
+    }
+}
+
+impl std::fmt::Debug for SharedMemory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let inner = self.lock();
+        f.debug_struct("SharedMemory")
+            .field("entries", &inner.data.len())
+            .field("revision", &inner.revision)
+            .field("watchers", &inner.watchers.len())
+            .finish()
+    }
+}
+
 impl SharedMemory {
-    /// Creates a new, empty `SharedMemory`.
+    /// Creates a new, empty `SharedMemory` using the system clock.
     pub fn new() -> Self {
+        Self::with_clock(Arc::new(SystemClock))
+    }
+
+    /// Creates a new, empty `SharedMemory` that reads time from `clock`.
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
         Self {
-            data: HashMap::new(),
-            access_log: VecDeque::new(),
-            max_log_size: 1000,
+            inner: Arc::new(Mutex::new(SharedMemoryInner {
+                data: HashMap::new(),
+                revision: 0,
+                watchers: Vec::new(),
+                access_log: VecDeque::new(),
+                max_log_size: 1000,
+            })),
+            clock,
         }
     }
 
-    /// Sets a value for a given key.
-    pub fn set(&mut self, key: String, value: String) {
-        self.data.insert(key.clone(), value);
-        self.log_access(key, AccessType::Write);
+    /// Sets a value for a given key in the global namespace.
+    pub fn set(&self, key: String, value: String) {
+        self.put(&Namespace::Global, &key, value, None);
     }
 
-    /// Retrieves a value for a given key.
-    pub fn get(&mut self, key: &str) -> Option<String> {
-        self.log_access(key.to_string(), AccessType::Read);
-        self.data.get(key).cloned()
+    /// Retrieves a value for a given key in the global namespace.
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.get_in(&Namespace::Global, key)
     }
 
-    /// Returns `true` if the memory contains a value for the specified key.
+    /// Returns `true` if the global namespace contains a live value for `key`.
     pub fn contains(&self, key: &str) -> bool {
-        self.data.contains_key(key)
+        self.entry(&Namespace::Global, key).is_some()
     }
 
-    /// Deletes a key-value pair from the memory.
-    pub fn delete(&mut self, key: &str) -> Option<String> {
-        self.log_access(key.to_string(), AccessType::Delete);
-        self.data.remove(key)
+    /// Deletes a key-value pair from the global namespace.
+    pub fn delete(&self, key: &str) -> Option<String> {
+        self.delete_in(&Namespace::Global, key)
     }
 
-    /// Returns a list of all keys in the memory.
+    /// Returns a list of all live keys in the global namespace.
     pub fn keys(&self) -> Vec<String> {
-        self.data.keys().cloned().collect()
+        self.keys_in(&Namespace::Global)
+    }
+
+    /// Writes `value` under `key` in `namespace`, returning the new version.
+    ///
+    /// If `ttl` is set, the entry expires that long after the write.
+    pub fn put(
+        &self,
+        namespace: &Namespace,
+        key: &str,
+        value: String,
+        ttl: Option<Duration>,
+    ) -> u64 {
+        let now = self.clock.now();
+        let mut inner = self.lock();
+        inner.write(ScopedKey::new(namespace.clone(), key), value, ttl, now)
+    }
+
+    /// Writes `value` under `key` only if the current version matches `expected`.
+    ///
+    /// `expected` is `None` to require that the key is absent (or expired).
+    /// On success returns the new version; otherwise returns
+    /// `CoordinationError::VersionConflict` with the version actually found.
+    pub fn compare_and_swap(
+        &self,
+        namespace: &Namespace,
+        key: &str,
+        expected: Option<u64>,
+        value: String,
+        ttl: Option<Duration>,
+    ) -> Result<u64, CoordinationError> {
+        let now = self.clock.now();
+        let key = ScopedKey::new(namespace.clone(), key);
+        let mut inner = self.lock();
+
+        let actual = inner.live_entry(&key, now).map(|entry| entry.version);
+        if actual != expected {
+            return Err(CoordinationError::VersionConflict { expected, actual });
+        }
+
+        Ok(inner.write(key, value, ttl, now))
+    }
+
+    /// Retrieves the value for `key` in `namespace`.
+    pub fn get_in(&self, namespace: &Namespace, key: &str) -> Option<String> {
+        self.entry(namespace, key).map(|entry| entry.value)
+    }
+
+    /// Retrieves the value, version and expiry for `key` in `namespace`.
+    pub fn entry(&self, namespace: &Namespace, key: &str) -> Option<SharedEntry> {
+        let now = self.clock.now();
+        let key = ScopedKey::new(namespace.clone(), key);
+        let mut inner = self.lock();
+        inner.log_access(key.path(), AccessType::Read, now);
+        inner.live_entry(&key, now).cloned()
+    }
+
+    /// Deletes `key` from `namespace`, returning its value if it was live.
+    pub fn delete_in(&self, namespace: &Namespace, key: &str) -> Option<String> {
+        let now = self.clock.now();
+        let key = ScopedKey::new(namespace.clone(), key);
+        let mut inner = self.lock();
+        inner.log_access(key.path(), AccessType::Delete, now);
+
+        inner.live_entry(&key, now)?;
+        let entry = inner.data.remove(&key)?;
+        inner.notify(SharedMemoryEvent::Deleted { key });
+        Some(entry.value)
+    }
+
+    /// Returns the live keys in `namespace`.
+    pub fn keys_in(&self, namespace: &Namespace) -> Vec<String> {
+        let now = self.clock.now();
+        let inner = self.lock();
+        inner
+            .data
+            .iter()
+            .filter(|(key, entry)| &key.namespace == namespace && !entry.is_expired(now))
+            .map(|(key, _)| key.key.clone())
+            .collect()
+    }
+
+    /// Deletes every key in `namespace`, returning the number removed.
+    pub fn clear_namespace(&self, namespace: &Namespace) -> usize {
+        let mut inner = self.lock();
+        let keys: Vec<ScopedKey> = inner
+            .data
+            .keys()
+            .filter(|key| &key.namespace == namespace)
+            .cloned()
+            .collect();
+        for key in &keys {
+            inner.data.remove(key);
+            inner.notify(SharedMemoryEvent::Deleted { key: key.clone() });
+        }
+        keys.len()
+    }
+
+    /// Removes every expired entry, returning the number removed.
+    ///
+    /// Expired entries are otherwise removed lazily when they are read.
+    pub fn purge_expired(&self) -> usize {
+        let now = self.clock.now();
+        let mut inner = self.lock();
+        let expired: Vec<ScopedKey> = inner
+            .data
+            .iter()
+            .filter(|(_, entry)| entry.is_expired(now))
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in &expired {
+            inner.data.remove(key);
+            inner.notify(SharedMemoryEvent::Expired { key: key.clone() });
+        }
+        expired.len()
+    }
+
+    /// Subscribes to changes of keys whose path starts with `prefix`.
+    ///
+    /// Paths have the form `global/<key>` or `agent/<id>/<key>` (see
+    /// [`Namespace::prefix`]); an empty prefix watches everything. The watcher
+    /// is removed once the receiver is dropped.
+    pub fn watch(&self, prefix: &str) -> Receiver<SharedMemoryEvent> {
+        let (sender, receiver) = mpsc::channel();
+        self.lock().watchers.push((prefix.to_string(), sender));
+        receiver
     }
 
     /// Clears all data from the memory.
-    pub fn clear(&mut self) {
-        self.data.clear();
+    pub fn clear(&self) {
+        let mut inner = self.lock();
+        let keys: Vec<ScopedKey> = inner.data.drain().map(|(key, _)| key).collect();
+        for key in keys {
+            inner.notify(SharedMemoryEvent::Deleted { key });
+        }
     }
 
-    /// Returns the number of key-value pairs in the memory.
+    /// Returns the number of live entries across all namespaces.
     pub fn len(&self) -> usize {
-        self.data.len()
+        let now = self.clock.now();
+        let inner = self.lock();
+        inner
+            .data
+            .values()
+            .filter(|entry| !entry.is_expired(now))
+            .count()
     }
 
-    /// Returns `true` if the memory is empty.
+    /// Returns `true` if the memory holds no live entries.
     pub fn is_empty(&self) -> bool {
-        self.data.is_empty()
+        self.len() == 0
+    }
+
+    fn lock(&self) -> MutexGuard<'_, SharedMemoryInner> {
+        // The inner state is consistent between statements, so a panic in another
+        // thread cannot leave it half-updated.
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
     }
+}
 
-    fn log_access(&mut self, key: String, access_type: AccessType) {
+impl SharedMemoryInner {
+    /// Returns the entry for `key` if it is live, expiring it otherwise.
+    fn live_entry(
+        &mut self,
+        key: &ScopedKey,
+        now: crate::types::Timestamp,
+    ) -> Option<&SharedEntry> {
+        if self.data.get(key)?.is_expired(now) {
+            self.data.remove(key);
+            self.notify(SharedMemoryEvent::Expired { key: key.clone() });
+            return None;
+        }
+        self.data.get(key)
+    }
+
+    fn write(
+        &mut self,
+        key: ScopedKey,
+        value: String,
+        ttl: Option<Duration>,
+        now: crate::types::Timestamp,
+    ) -> u64 {
+        self.revision += 1;
+        let version = self.revision;
+        let expires_at = ttl.map(|ttl| {
+            let micros = u64::try_from(ttl.as_micros()).unwrap_or(u64::MAX);
+            crate::types::Timestamp(now.0.saturating_add(micros))
+        });
+
+        self.log_access(key.path(), AccessType::Write, now);
+        self.data.insert(
+            key.clone(),
+            SharedEntry {
+                value: value.clone(),
+                version,
+                expires_at,
+            },
+        );
+        self.notify(SharedMemoryEvent::Updated {
+            key,
+            value,
+            version,
+        });
+        version
+    }
+
+    /// Sends `event` to every matching watcher, dropping disconnected ones.
+    fn notify(&mut self, event: SharedMemoryEvent) {
+        if self.watchers.is_empty() {
+            return;
+        }
+        let path = event.key().path();
+        self.watchers.retain(|(prefix, sender)| {
+            !path.starts_with(prefix.as_str()) || sender.send(event.clone()).is_ok()
+        });
+    }
+
+    fn log_access(&mut self, key: String, access_type: AccessType, now: crate::types::Timestamp) {
         if self.access_log.len() >= self.max_log_size {
             self.access_log.pop_front();
         }
-        self.access_log
-            .push_back((crate::types::Timestamp::now(), key, access_type));
+        self.access_log.push_back((now, key, access_type));
     }
 }
 
@@ -354,6 +775,17 @@ pub enum CoordinationError {
     QueueFull,
     /// The provided message was invalid.
     InvalidMessage,
+    /// A compare-and-swap write found a different version than expected.
+    VersionConflict {
+        /// The version the writer expected (`None` for "absent").
+        expected: Option<u64>,
+        /// The version actually found (`None` if absent).
+        actual: Option<u64>,
+    },
+    /// The specified task group has no members.
+    GroupNotFound,
+    /// The agent does not hold a live lease on the task.
+    LeaseNotHeld,
 }
 
 impl std::fmt::Display for CoordinationError {
@@ -362,12 +794,95 @@ impl std::fmt::Display for CoordinationError {
             CoordinationError::AgentNotFound => write!(f, "Agent not found"),
             CoordinationError::QueueFull => write!(f, "Message queue is full"),
             CoordinationError::InvalidMessage => write!(f, "Invalid message"),
+            CoordinationError::VersionConflict { expected, actual } => write!(
+                f,
+                "Version conflict: expected {}, found {}",
+                describe_version(*expected),
+                describe_version(*actual)
+            ),
+            CoordinationError::GroupNotFound => write!(f, "Task group not found"),
+            CoordinationError::LeaseNotHeld => write!(f, "Lease not held"),
         }
     }
 }
 
 impl std::error::Error for CoordinationError {}
 
+fn describe_version(version: Option<u64>) -> String {
+    match version {
+        Some(v) => format!("version {}", v),
+        None => "no entry".to_string(),
+    }
+}
+
+/// The key under which [`AgentCoordinator::publish_observation`] stores an
+/// agent's latest observation in its namespace.
+pub const LATEST_OBSERVATION_KEY: &str = "latest_observation";
+
+/// The default duration of leader and task leases.
+pub const DEFAULT_LEASE_DURATION: Duration = Duration::from_secs(10);
+
+/// Global `SharedMemory` key prefix of group leader leases.
+const LEADER_PREFIX: &str = "leader/";
+
+/// Global `SharedMemory` key prefix of task leases.
+const TASK_PREFIX: &str = "task/";
+
+/// A leader or task lease, stored as JSON in the global namespace of
+/// `SharedMemory`. The entry's TTL is the lease expiry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Lease {
+    holder: AgentId,
+    /// The election term, for leader leases.
+    #[serde(default)]
+    term: u64,
+    /// Set once a task is done; completed leases do not expire.
+    #[serde(default)]
+    completed: bool,
+}
+
+impl Lease {
+    fn encode(&self) -> Result<String, CoordinationError> {
+        serde_json::to_string(self).map_err(|_| CoordinationError::InvalidMessage)
+    }
+
+    fn decode(entry: &SharedEntry) -> Result<Self, CoordinationError> {
+        serde_json::from_str(&entry.value).map_err(|_| CoordinationError::InvalidMessage)
+    }
+}
+
+/// The outcome of [`AgentCoordinator::claim_task`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClaimResult {
+    /// The agent holds the task's lease and should execute it.
+    ///
+    /// The lease lapses at `expires_at` unless the agent keeps sending
+    /// heartbeats or completes the task.
+    Granted {
+        /// When the lease expires.
+        expires_at: crate::types::Timestamp,
+    },
+    /// Another agent holds a live lease on the task.
+    Held {
+        /// The agent executing the task.
+        holder: AgentId,
+        /// When its lease expires, unless renewed.
+        expires_at: crate::types::Timestamp,
+    },
+    /// The task has already been completed.
+    Completed {
+        /// The agent that completed it.
+        by: AgentId,
+    },
+}
+
+impl ClaimResult {
+    /// Returns `true` if the claiming agent should execute the task.
+    pub fn is_granted(&self) -> bool {
+        matches!(self, ClaimResult::Granted { .. })
+    }
+}
+
 /// Orchestrates a system of multiple agents, facilitating communication and coordination.
 pub struct AgentCoordinator {
     /// The collection of agents managed by the coordinator.
@@ -378,6 +893,18 @@ pub struct AgentCoordinator {
     message_bus: MessageBus,
     /// A map of active proposals for consensus.
     proposals: HashMap<String, Proposal>,
+    /// The members of each task group, in the order they joined.
+    groups: HashMap<String, Vec<AgentId>>,
+    /// The latest election term of each task group.
+    terms: HashMap<String, u64>,
+    /// How long leader and task leases last without a heartbeat.
+    lease_duration: Duration,
+    /// The action schemas installed on every agent, if set.
+    action_schemas: Option<SchemaRegistry>,
+    /// The group schedules.
+    scheduler: Scheduler,
+    /// The task group each group schedule is delivered to, by schedule name.
+    schedule_groups: HashMap<String, String>,
     /// A counter to generate unique agent IDs.
     next_id: usize,
 }
@@ -397,17 +924,35 @@ struct Proposal {
 impl AgentCoordinator {
     /// Creates a new `AgentCoordinator`.
     pub fn new() -> Self {
+        Self::with_clock(Arc::new(SystemClock))
+    }
+
+    /// Creates a new `AgentCoordinator` whose shared memory, leases and group
+    /// schedules read time from `clock`.
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
         Self {
             agents: HashMap::new(),
-            shared_memory: SharedMemory::new(),
+            scheduler: Scheduler::with_clock(clock.clone()),
+            schedule_groups: HashMap::new(),
+            shared_memory: SharedMemory::with_clock(clock),
             message_bus: MessageBus::new(),
             proposals: HashMap::new(),
+            groups: HashMap::new(),
+            terms: HashMap::new(),
+            lease_duration: DEFAULT_LEASE_DURATION,
+            action_schemas: None,
             next_id: 0,
         }
     }
 
     /// Registers a new agent with the coordinator and returns its assigned `AgentId`.
-    pub fn register_agent(&mut self, agent: KaneruAgent) -> AgentId {
+    ///
+    /// The agent's action schemas are replaced by the coordinator's, if set
+    /// with [`set_action_schemas`](Self::set_action_schemas).
+    pub fn register_agent(&mut self, mut agent: KaneruAgent) -> AgentId {
+        if let Some(schemas) = &self.action_schemas {
+            agent.set_action_schemas(schemas.clone());
+        }
         let id = AgentId(format!("agent_{}", self.next_id));
         self.next_id += 1;
 
@@ -418,15 +963,40 @@ impl AgentCoordinator {
         id
     }
 
+    /// Sets the action schemas of every registered agent, and of agents
+    /// registered later, so the whole system validates actions alike.
+    pub fn set_action_schemas(&mut self, schemas: SchemaRegistry) {
+        for handle in self.agents.values_mut() {
+            handle.agent.set_action_schemas(schemas.clone());
+        }
+        self.action_schemas = Some(schemas);
+    }
+
+    /// Returns the action schemas installed on every agent, if set.
+    pub fn action_schemas(&self) -> Option<&SchemaRegistry> {
+        self.action_schemas.as_ref()
+    }
+
     /// Unregisters an agent from the coordinator.
     pub fn unregister_agent(
         &mut self,
         agent_id: &AgentId,
     ) -> Result<KaneruAgent, CoordinationError> {
-        self.agents
+        let handle = self
+            .agents
             .remove(agent_id)
-            .map(|handle| handle.agent)
-            .ok_or(CoordinationError::AgentNotFound)
+            .ok_or(CoordinationError::AgentNotFound)?;
+
+        // Drop whatever the agent left in its namespace so it does not go stale.
+        self.shared_memory
+            .clear_namespace(&Namespace::Agent(agent_id.clone()));
+
+        // Its leases are left to expire, as they would for a crashed agent.
+        for members in self.groups.values_mut() {
+            members.retain(|member| member != agent_id);
+        }
+
+        Ok(handle.agent)
     }
 
     /// Broadcasts a message to all registered agents.
@@ -463,6 +1033,10 @@ impl AgentCoordinator {
     ///
     /// * `observations` - A map from `AgentId` to the `Observation` for that agent.
     ///
+    /// Agents then step on the due observations of their own schedules, and
+    /// the members of a group on those of its group schedules, so an agent
+    /// may decide more than once per call.
+    ///
     /// # Returns
     ///
     /// A vector of tuples containing the `AgentId` and the `Action` it decided to take.
@@ -472,6 +1046,9 @@ impl AgentCoordinator {
     ) -> Vec<(AgentId, Action)> {
         let mut actions = Vec::new();
 
+        // Replace group leaders whose lease has lapsed
+        self.fail_over();
+
         // First, collect all messages and process them
         let agent_ids: Vec<_> = self.agents.keys().cloned().collect();
 
@@ -497,11 +1074,32 @@ impl AgentCoordinator {
             }
         }
 
-        // Finally, step each agent with its observation
+        // Finally, step each agent with its observation and its due schedules
         for agent_id in agent_ids {
             if let Some(handle) = self.agents.get_mut(&agent_id) {
                 if let Some(obs) = observations.get(&agent_id) {
                     let action = handle.agent.step(obs.clone());
+                    actions.push((agent_id.clone(), action));
+                }
+                while let Some(action) = handle.agent.step_scheduled() {
+                    actions.push((agent_id.clone(), action));
+                }
+            }
+        }
+
+        // Then step the members of each group with a due group schedule
+        while let Some(observation) = self.scheduler.poll() {
+            let ObservationType::Scheduled(name) = &observation.obs_type else {
+                continue;
+            };
+            let members = self
+                .schedule_groups
+                .get(name)
+                .map(|group| self.group_members(group))
+                .unwrap_or_default();
+            for agent_id in members {
+                if let Some(handle) = self.agents.get_mut(&agent_id) {
+                    let action = handle.agent.step(observation.clone());
                     actions.push((agent_id, action));
                 }
             }
@@ -529,6 +1127,341 @@ impl AgentCoordinator {
         &mut self.shared_memory
     }
 
+    /// Publishes `observation` as the latest observation of `agent_id`.
+    ///
+    /// The observation is stored under [`LATEST_OBSERVATION_KEY`] in the agent's
+    /// namespace, optionally expiring after `ttl`. Returns the new version.
+    pub fn publish_observation(
+        &self,
+        agent_id: &AgentId,
+        observation: &Observation,
+        ttl: Option<Duration>,
+    ) -> Result<u64, CoordinationError> {
+        if !self.agents.contains_key(agent_id) {
+            return Err(CoordinationError::AgentNotFound);
+        }
+        let value =
+            serde_json::to_string(observation).map_err(|_| CoordinationError::InvalidMessage)?;
+
+        Ok(self.shared_memory.put(
+            &Namespace::Agent(agent_id.clone()),
+            LATEST_OBSERVATION_KEY,
+            value,
+            ttl,
+        ))
+    }
+
+    /// Returns the latest observation published by `agent_id`, if it is still live.
+    pub fn latest_observation(&self, agent_id: &AgentId) -> Option<Observation> {
+        let value = self
+            .shared_memory
+            .get_in(&Namespace::Agent(agent_id.clone()), LATEST_OBSERVATION_KEY)?;
+        serde_json::from_str(&value).ok()
+    }
+
+    /// Returns the latest live observation of every registered agent that has published one.
+    pub fn latest_observations(&self) -> HashMap<AgentId, Observation> {
+        self.agents
+            .keys()
+            .filter_map(|id| Some((id.clone(), self.latest_observation(id)?)))
+            .collect()
+    }
+
+    /// Sets how long leader and task leases last without a heartbeat.
+    ///
+    /// Applies to leases granted or renewed from now on.
+    pub fn set_lease_duration(&mut self, duration: Duration) {
+        self.lease_duration = duration;
+    }
+
+    /// Adds `agent_id` to the task group `group`, creating the group if needed.
+    ///
+    /// Members are candidates in the group's leader elections, in the order
+    /// they joined.
+    pub fn join_group(&mut self, group: &str, agent_id: &AgentId) -> Result<(), CoordinationError> {
+        if !self.agents.contains_key(agent_id) {
+            return Err(CoordinationError::AgentNotFound);
+        }
+        let members = self.groups.entry(group.to_string()).or_default();
+        if !members.contains(agent_id) {
+            members.push(agent_id.clone());
+        }
+        Ok(())
+    }
+
+    /// Removes `agent_id` from the task group `group`.
+    ///
+    /// A lease the agent holds as leader is left to expire.
+    pub fn leave_group(&mut self, group: &str, agent_id: &AgentId) {
+        if let Some(members) = self.groups.get_mut(group) {
+            members.retain(|member| member != agent_id);
+        }
+    }
+
+    /// Adds a schedule whose observations every member of the task group
+    /// `group` steps on in [`step_all`](Self::step_all) when it is due.
+    ///
+    /// Members are looked up when the schedule fires, so agents joining the
+    /// group later receive it too; a slot firing while the group has no
+    /// members is dropped. A group schedule with the same name is replaced.
+    pub fn add_group_schedule(
+        &mut self,
+        group: &str,
+        schedule: Schedule,
+        template: ObservationTemplate,
+    ) {
+        self.schedule_groups
+            .insert(template.name.clone(), group.to_string());
+        self.scheduler.add(schedule, template);
+    }
+
+    /// Removes the group schedule named `name`, returning whether it existed.
+    pub fn remove_group_schedule(&mut self, name: &str) -> bool {
+        self.schedule_groups.remove(name);
+        self.scheduler.remove(name)
+    }
+
+    /// Returns the scheduler of the group schedules, e.g. to save its state.
+    pub fn group_scheduler(&self) -> &Scheduler {
+        &self.scheduler
+    }
+
+    /// Returns the scheduler of the group schedules, e.g. to restore its state.
+    pub fn group_scheduler_mut(&mut self) -> &mut Scheduler {
+        &mut self.scheduler
+    }
+
+    /// Returns the members of the task group `group`, in the order they joined.
+    pub fn group_members(&self, group: &str) -> Vec<AgentId> {
+        self.groups.get(group).cloned().unwrap_or_default()
+    }
+
+    /// Returns the holder of the live leader lease of `group`, if any.
+    pub fn leader(&self, group: &str) -> Option<AgentId> {
+        let entry = self
+            .shared_memory
+            .entry(&Namespace::Global, &leader_key(group))?;
+        Lease::decode(&entry).ok().map(|lease| lease.holder)
+    }
+
+    /// Elects a leader for the task group `group`.
+    ///
+    /// The holder of a live leader lease stays leader, even if it has been
+    /// unregistered: a stopped leader is replaced only once its lease lapses.
+    /// Otherwise the earliest-joined registered member acquires the lease for
+    /// a new term, and a [`MessagePayload::LeaderElected`] message is sent on
+    /// the message bus.
+    ///
+    /// Returns [`ConsensusResult::Elected`], or [`ConsensusResult::Pending`]
+    /// if the group has no registered member.
+    pub fn elect_leader(&mut self, group: &str) -> Result<ConsensusResult, CoordinationError> {
+        let members = self
+            .groups
+            .get(group)
+            .cloned()
+            .ok_or(CoordinationError::GroupNotFound)?;
+        let key = leader_key(group);
+
+        loop {
+            if let Some(entry) = self.shared_memory.entry(&Namespace::Global, &key) {
+                let lease = Lease::decode(&entry)?;
+                return Ok(ConsensusResult::Elected {
+                    leader: lease.holder,
+                    term: lease.term,
+                });
+            }
+
+            let Some(candidate) = members.iter().find(|m| self.agents.contains_key(m)) else {
+                return Ok(ConsensusResult::Pending);
+            };
+            let term = self.terms.get(group).copied().unwrap_or(0) + 1;
+            let lease = Lease {
+                holder: candidate.clone(),
+                term,
+                completed: false,
+            };
+
+            match self.shared_memory.compare_and_swap(
+                &Namespace::Global,
+                &key,
+                None,
+                lease.encode()?,
+                Some(self.lease_duration),
+            ) {
+                Ok(_) => {
+                    self.terms.insert(group.to_string(), term);
+                    log::info!(
+                        "Agent {:?} elected leader of {} (term {})",
+                        candidate,
+                        group,
+                        term
+                    );
+                    self.announce(
+                        "election",
+                        MessagePayload::LeaderElected {
+                            group: group.to_string(),
+                            leader: candidate.clone(),
+                            term,
+                        },
+                    );
+                    return Ok(ConsensusResult::Elected {
+                        leader: lease.holder,
+                        term,
+                    });
+                }
+                // Written through another handle to the shared memory; read it back
+                Err(CoordinationError::VersionConflict { .. }) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Claims the task `task_id` of `group` for `agent_id`.
+    ///
+    /// The first agent to claim a task is granted a lease on it and should
+    /// execute it; later claims see [`ClaimResult::Held`] until the lease
+    /// lapses, at which point the next claim takes the task over. The holder
+    /// keeps its lease with [`heartbeat`](Self::heartbeat), and claiming
+    /// again renews it. Once the holder calls
+    /// [`complete_task`](Self::complete_task), every claim returns
+    /// [`ClaimResult::Completed`].
+    ///
+    /// A grant is announced with a [`MessagePayload::TaskClaimed`] message on
+    /// the message bus.
+    pub fn claim_task(
+        &mut self,
+        group: &str,
+        task_id: &str,
+        agent_id: &AgentId,
+    ) -> Result<ClaimResult, CoordinationError> {
+        if !self.agents.contains_key(agent_id) {
+            return Err(CoordinationError::AgentNotFound);
+        }
+        let key = task_key(group, task_id);
+
+        loop {
+            let current = self.shared_memory.entry(&Namespace::Global, &key);
+            if let Some(entry) = &current {
+                let lease = Lease::decode(entry)?;
+                if lease.completed {
+                    return Ok(ClaimResult::Completed { by: lease.holder });
+                }
+                if &lease.holder != agent_id {
+                    return Ok(ClaimResult::Held {
+                        holder: lease.holder,
+                        expires_at: entry.expires_at.unwrap_or_default(),
+                    });
+                }
+            }
+
+            let lease = Lease {
+                holder: agent_id.clone(),
+                term: 0,
+                completed: false,
+            };
+            match self.shared_memory.compare_and_swap(
+                &Namespace::Global,
+                &key,
+                current.as_ref().map(|entry| entry.version),
+                lease.encode()?,
+                Some(self.lease_duration),
+            ) {
+                Ok(_) => {
+                    if current.is_none() {
+                        log::debug!("Agent {:?} claimed task {}/{}", agent_id, group, task_id);
+                        self.announce(
+                            "task",
+                            MessagePayload::TaskClaimed {
+                                group: group.to_string(),
+                                task_id: task_id.to_string(),
+                                agent: agent_id.clone(),
+                            },
+                        );
+                    }
+                    return Ok(ClaimResult::Granted {
+                        expires_at: self.lease_expiry(&key),
+                    });
+                }
+                Err(CoordinationError::VersionConflict { .. }) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Marks the task `task_id` of `group` as completed by its lease holder.
+    ///
+    /// Returns [`CoordinationError::LeaseNotHeld`] if `agent_id` does not hold
+    /// a live lease on the task.
+    pub fn complete_task(
+        &mut self,
+        group: &str,
+        task_id: &str,
+        agent_id: &AgentId,
+    ) -> Result<(), CoordinationError> {
+        let key = task_key(group, task_id);
+        let entry = self
+            .shared_memory
+            .entry(&Namespace::Global, &key)
+            .ok_or(CoordinationError::LeaseNotHeld)?;
+        let mut lease = Lease::decode(&entry)?;
+        if &lease.holder != agent_id || lease.completed {
+            return Err(CoordinationError::LeaseNotHeld);
+        }
+
+        lease.completed = true;
+        self.shared_memory
+            .compare_and_swap(
+                &Namespace::Global,
+                &key,
+                Some(entry.version),
+                lease.encode()?,
+                None,
+            )
+            .map_err(|_| CoordinationError::LeaseNotHeld)?;
+        Ok(())
+    }
+
+    /// Renews every live leader and task lease held by `agent_id`.
+    ///
+    /// Agents must call this more often than the lease duration to keep
+    /// their leases. Returns the number of leases renewed.
+    pub fn heartbeat(&mut self, agent_id: &AgentId) -> Result<usize, CoordinationError> {
+        if !self.agents.contains_key(agent_id) {
+            return Err(CoordinationError::AgentNotFound);
+        }
+
+        let mut renewed = 0;
+        for key in self.shared_memory.keys_in(&Namespace::Global) {
+            if !key.starts_with(LEADER_PREFIX) && !key.starts_with(TASK_PREFIX) {
+                continue;
+            }
+            let Some(entry) = self.shared_memory.entry(&Namespace::Global, &key) else {
+                continue;
+            };
+            let Ok(lease) = Lease::decode(&entry) else {
+                continue;
+            };
+            if &lease.holder != agent_id || lease.completed {
+                continue;
+            }
+            // A conflict means the lease changed hands in between
+            if self
+                .shared_memory
+                .compare_and_swap(
+                    &Namespace::Global,
+                    &key,
+                    Some(entry.version),
+                    entry.value,
+                    Some(self.lease_duration),
+                )
+                .is_ok()
+            {
+                renewed += 1;
+            }
+        }
+        Ok(renewed)
+    }
+
     /// Creates a new proposal for consensus and broadcasts it to all agents.
     ///
     /// # Returns
@@ -601,6 +1534,33 @@ impl AgentCoordinator {
 
     // Private helper methods
 
+    /// Elects a new leader for every group whose leader lease has lapsed.
+    fn fail_over(&mut self) {
+        let groups: Vec<String> = self.groups.keys().cloned().collect();
+        for group in groups {
+            if self.leader(&group).is_none() {
+                if let Err(e) = self.elect_leader(&group) {
+                    log::warn!("Leader election for {} failed: {}", group, e);
+                }
+            }
+        }
+    }
+
+    /// Sends a coordinator message to all agents through the message bus.
+    fn announce(&mut self, topic: &str, payload: MessagePayload) {
+        if let Err(e) = self.message_bus.send(Message::with_payload(topic, payload)) {
+            log::warn!("Dropped {} announcement: {}", topic, e);
+        }
+    }
+
+    /// Returns when the lease stored under `key` expires.
+    fn lease_expiry(&self, key: &str) -> crate::types::Timestamp {
+        self.shared_memory
+            .entry(&Namespace::Global, key)
+            .and_then(|entry| entry.expires_at)
+            .unwrap_or_default()
+    }
+
     fn process_message(&mut self, agent_id: &AgentId, msg: &Message) {
         match &msg.payload {
             MessagePayload::Vote { proposal_id, vote } => {
@@ -656,6 +1616,23 @@ pub enum ConsensusResult {
         /// The approval rate (votes_for / total_votes).
         approval_rate: f64,
     },
+    /// A task group leader has been elected.
+    Elected {
+        /// The agent holding the group's leader lease.
+        leader: AgentId,
+        /// The election term, which increases with every new leader.
+        term: u64,
+    },
+}
+
+/// The shared memory key of the leader lease of `group`.
+fn leader_key(group: &str) -> String {
+    format!("{}{}", LEADER_PREFIX, group)
+}
+
+/// The shared memory key of the lease on task `task_id` of `group`.
+fn task_key(group: &str, task_id: &str) -> String {
+    format!("{}{}/{}", TASK_PREFIX, group, task_id)
 }
 
 // Simple UUID v4 generator (simplified version)
@@ -703,6 +1680,24 @@ mod tests {
         assert_eq!(coordinator.agent_count(), 0);
     }
 
+    #[test]
+    fn test_action_schemas_installed_on_all_agents() {
+        use crate::schema::{ActionSchema, ParamSpec};
+
+        let mut coordinator = AgentCoordinator::new();
+        let early = coordinator.register_agent(KaneruAgent::with_default_config());
+
+        let mut schemas = SchemaRegistry::new();
+        schemas.register(ActionSchema::custom("set_valve").param("position", ParamSpec::float()));
+        coordinator.set_action_schemas(schemas);
+        let late = coordinator.register_agent(KaneruAgent::with_default_config());
+
+        for id in [&early, &late] {
+            let agent = coordinator.get_agent(id).unwrap();
+            assert_eq!(agent.action_schemas().len(), 1);
+        }
+    }
+
     #[test]
     fn test_broadcast_message() {
         let mut coordinator = AgentCoordinator::new();
@@ -837,4 +1832,467 @@ mod tests {
 
         assert!(high_priority.priority > low_priority.priority);
     }
+
+    /// A clock that only moves when told to.
+    #[derive(Default)]
+    struct ManualClock(std::sync::atomic::AtomicU64);
+
+    impl ManualClock {
+        fn advance(&self, by: Duration) {
+            self.0
+                .fetch_add(by.as_micros() as u64, std::sync::atomic::Ordering::SeqCst);
+        }
+    }
+
+    impl Clock for ManualClock {
+        fn now(&self) -> crate::types::Timestamp {
+            crate::types::Timestamp(self.0.load(std::sync::atomic::Ordering::SeqCst))
+        }
+    }
+
+    fn agent_ns(id: &str) -> Namespace {
+        Namespace::Agent(AgentId(id.to_string()))
+    }
+
+    #[test]
+    fn test_shared_memory_namespaces_are_isolated() {
+        let memory = SharedMemory::new();
+
+        memory.set("mode".to_string(), "global".to_string());
+        memory.put(&agent_ns("a"), "mode", "a".to_string(), None);
+        memory.put(&agent_ns("b"), "mode", "b".to_string(), None);
+
+        assert_eq!(memory.get("mode"), Some("global".to_string()));
+        assert_eq!(memory.get_in(&agent_ns("a"), "mode"), Some("a".to_string()));
+        assert_eq!(memory.get_in(&agent_ns("b"), "mode"), Some("b".to_string()));
+        assert_eq!(memory.keys(), vec!["mode".to_string()]);
+        assert_eq!(memory.len(), 3);
+
+        assert_eq!(memory.clear_namespace(&agent_ns("a")), 1);
+        assert_eq!(memory.get_in(&agent_ns("a"), "mode"), None);
+        assert_eq!(memory.len(), 2);
+    }
+
+    #[test]
+    fn test_shared_memory_cas_conflicts() {
+        let memory = SharedMemory::new();
+        let ns = Namespace::Global;
+
+        let v1 = memory
+            .compare_and_swap(&ns, "leader", None, "a".to_string(), None)
+            .unwrap();
+
+        // Creating again fails: the key already exists.
+        assert_eq!(
+            memory.compare_and_swap(&ns, "leader", None, "b".to_string(), None),
+            Err(CoordinationError::VersionConflict {
+                expected: None,
+                actual: Some(v1),
+            })
+        );
+
+        // Two writers read v1; only the first update wins.
+        let v2 = memory
+            .compare_and_swap(&ns, "leader", Some(v1), "b".to_string(), None)
+            .unwrap();
+        assert!(v2 > v1);
+        assert_eq!(
+            memory.compare_and_swap(&ns, "leader", Some(v1), "c".to_string(), None),
+            Err(CoordinationError::VersionConflict {
+                expected: Some(v1),
+                actual: Some(v2),
+            })
+        );
+        assert_eq!(memory.get("leader"), Some("b".to_string()));
+
+        // Versions are never reused after a delete.
+        memory.delete("leader");
+        let v3 = memory
+            .compare_and_swap(&ns, "leader", None, "d".to_string(), None)
+            .unwrap();
+        assert!(v3 > v2);
+    }
+
+    #[test]
+    fn test_shared_memory_cas_under_contention() {
+        let memory = SharedMemory::new();
+        memory.set("counter".to_string(), "0".to_string());
+
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let memory = memory.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..50 {
+                        loop {
+                            let entry = memory.entry(&Namespace::Global, "counter").unwrap();
+                            let next = entry.value.parse::<u64>().unwrap() + 1;
+                            if memory
+                                .compare_and_swap(
+                                    &Namespace::Global,
+                                    "counter",
+                                    Some(entry.version),
+                                    next.to_string(),
+                                    None,
+                                )
+                                .is_ok()
+                            {
+                                break;
+                            }
+                        }
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        assert_eq!(memory.get("counter"), Some("200".to_string()));
+    }
+
+    #[test]
+    fn test_shared_memory_ttl_expiry() {
+        let clock = Arc::new(ManualClock::default());
+        let memory = SharedMemory::with_clock(clock.clone());
+        let events = memory.watch("");
+
+        memory.put(
+            &agent_ns("a"),
+            "reading",
+            "21.5".to_string(),
+            Some(Duration::from_secs(5)),
+        );
+        memory.put(&agent_ns("a"), "name", "probe".to_string(), None);
+
+        clock.advance(Duration::from_secs(4));
+        assert_eq!(
+            memory.get_in(&agent_ns("a"), "reading"),
+            Some("21.5".to_string())
+        );
+
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(memory.len(), 1);
+        assert_eq!(memory.get_in(&agent_ns("a"), "reading"), None);
+        assert_eq!(memory.keys_in(&agent_ns("a")), vec!["name".to_string()]);
+
+        // An expired entry counts as absent for compare-and-swap.
+        memory.put(
+            &agent_ns("a"),
+            "lease",
+            "x".to_string(),
+            Some(Duration::from_secs(1)),
+        );
+        clock.advance(Duration::from_secs(2));
+        assert_eq!(memory.purge_expired(), 1);
+        assert!(memory
+            .compare_and_swap(&agent_ns("a"), "lease", None, "y".to_string(), None)
+            .is_ok());
+
+        let expired: Vec<String> = events
+            .try_iter()
+            .filter(|e| matches!(e, SharedMemoryEvent::Expired { .. }))
+            .map(|e| e.key().key.clone())
+            .collect();
+        assert_eq!(expired, vec!["reading".to_string(), "lease".to_string()]);
+    }
+
+    #[test]
+    fn test_shared_memory_watch_ordering() {
+        let memory = SharedMemory::new();
+        let events = memory.watch("agent/a/");
+        let all = memory.watch("");
+
+        let v1 = memory.put(&agent_ns("a"), "x", "1".to_string(), None);
+        memory.put(&agent_ns("b"), "x", "ignored".to_string(), None);
+        let v2 = memory.put(&agent_ns("a"), "y", "2".to_string(), None);
+        let v3 = memory.put(&agent_ns("a"), "x", "3".to_string(), None);
+        memory.delete_in(&agent_ns("a"), "y");
+
+        let x = ScopedKey::new(agent_ns("a"), "x");
+        let y = ScopedKey::new(agent_ns("a"), "y");
+        let received: Vec<SharedMemoryEvent> = events.try_iter().collect();
+        assert_eq!(
+            received,
+            vec![
+                SharedMemoryEvent::Updated {
+                    key: x.clone(),
+                    value: "1".to_string(),
+                    version: v1,
+                },
+                SharedMemoryEvent::Updated {
+                    key: y.clone(),
+                    value: "2".to_string(),
+                    version: v2,
+                },
+                SharedMemoryEvent::Updated {
+                    key: x,
+                    value: "3".to_string(),
+                    version: v3,
+                },
+                SharedMemoryEvent::Deleted { key: y },
+            ]
+        );
+        assert_eq!(all.try_iter().count(), 5);
+
+        // Dropped receivers are pruned on the next matching change.
+        drop(events);
+        drop(all);
+        memory.put(&agent_ns("a"), "z", "1".to_string(), None);
+        assert_eq!(memory.lock().watchers.len(), 0);
+    }
+
+    #[test]
+    fn test_shared_memory_serialization_round_trip() {
+        let memory = SharedMemory::new();
+        memory.set("a".to_string(), "1".to_string());
+        let version = memory.put(&agent_ns("x"), "b", "2".to_string(), None);
+
+        let json = serde_json::to_string(&memory).unwrap();
+        let restored: SharedMemory = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.get("a"), Some("1".to_string()));
+        assert_eq!(
+            restored.entry(&agent_ns("x"), "b").unwrap().version,
+            version
+        );
+        assert!(restored.put(&Namespace::Global, "c", "3".to_string(), None) > version);
+    }
+
+    #[test]
+    fn test_coordinator_publish_observation() {
+        let mut coordinator = AgentCoordinator::new();
+        let id1 = coordinator.register_agent(KaneruAgent::with_default_config());
+        let id2 = coordinator.register_agent(KaneruAgent::with_default_config());
+        let events = coordinator
+            .shared_memory()
+            .watch(&Namespace::Agent(id1.clone()).prefix());
+
+        coordinator
+            .publish_observation(&id1, &Observation::sensor("temperature", 22.0), None)
+            .unwrap();
+
+        let latest = coordinator.latest_observation(&id1).unwrap();
+        assert_eq!(latest.value.as_f64(), Some(22.0));
+        assert!(coordinator.latest_observation(&id2).is_none());
+        assert_eq!(coordinator.latest_observations().len(), 1);
+        assert_eq!(events.try_iter().count(), 1);
+
+        assert_eq!(
+            coordinator.publish_observation(
+                &AgentId("missing".to_string()),
+                &Observation::sensor("temperature", 1.0),
+                None,
+            ),
+            Err(CoordinationError::AgentNotFound)
+        );
+
+        // Unregistering clears the agent's namespace.
+        coordinator.unregister_agent(&id1).unwrap();
+        assert!(coordinator
+            .shared_memory()
+            .keys_in(&Namespace::Agent(id1))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_claim_task_single_executor_and_takeover() {
+        let clock = Arc::new(ManualClock::default());
+        let mut coordinator = AgentCoordinator::with_clock(clock.clone());
+        coordinator.set_lease_duration(Duration::from_secs(5));
+        let ids: Vec<AgentId> = (0..5)
+            .map(|_| coordinator.register_agent(KaneruAgent::with_default_config()))
+            .collect();
+
+        // All five agents react to the same moisture observation.
+        let results: Vec<ClaimResult> = ids
+            .iter()
+            .map(|id| {
+                coordinator
+                    .claim_task("irrigation", "open_valve", id)
+                    .unwrap()
+            })
+            .collect();
+        let granted: Vec<&AgentId> = ids
+            .iter()
+            .zip(&results)
+            .filter(|(_, result)| result.is_granted())
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(granted.len(), 1);
+        let claimant = granted[0].clone();
+        for result in results.iter().filter(|result| !result.is_granted()) {
+            assert!(matches!(result, ClaimResult::Held { holder, .. } if *holder == claimant));
+        }
+
+        // Heartbeats keep the lease past its original expiry.
+        clock.advance(Duration::from_secs(4));
+        assert_eq!(coordinator.heartbeat(&claimant).unwrap(), 1);
+        clock.advance(Duration::from_secs(4));
+        let successor = ids.iter().find(|id| **id != claimant).unwrap().clone();
+        assert!(!coordinator
+            .claim_task("irrigation", "open_valve", &successor)
+            .unwrap()
+            .is_granted());
+
+        // The claimant stops mid-lease; nobody takes over until the lease lapses.
+        coordinator.unregister_agent(&claimant).unwrap();
+        assert_eq!(
+            coordinator.heartbeat(&claimant),
+            Err(CoordinationError::AgentNotFound)
+        );
+        assert!(!coordinator
+            .claim_task("irrigation", "open_valve", &successor)
+            .unwrap()
+            .is_granted());
+
+        clock.advance(Duration::from_secs(2));
+        assert!(coordinator
+            .claim_task("irrigation", "open_valve", &successor)
+            .unwrap()
+            .is_granted());
+        for id in ids
+            .iter()
+            .filter(|id| **id != claimant && **id != successor)
+        {
+            assert!(matches!(
+                coordinator.claim_task("irrigation", "open_valve", id).unwrap(),
+                ClaimResult::Held { holder, .. } if holder == successor
+            ));
+        }
+        assert_eq!(coordinator.message_bus.pending_count(), 2);
+
+        // Completed tasks are never handed out again.
+        let bystander = ids
+            .iter()
+            .find(|id| **id != claimant && **id != successor)
+            .unwrap();
+        assert_eq!(
+            coordinator.complete_task("irrigation", "open_valve", bystander),
+            Err(CoordinationError::LeaseNotHeld)
+        );
+        coordinator
+            .complete_task("irrigation", "open_valve", &successor)
+            .unwrap();
+        clock.advance(Duration::from_secs(60));
+        assert_eq!(
+            coordinator
+                .claim_task("irrigation", "open_valve", bystander)
+                .unwrap(),
+            ClaimResult::Completed { by: successor }
+        );
+    }
+
+    #[test]
+    fn test_leader_election_fails_over() {
+        let clock = Arc::new(ManualClock::default());
+        let mut coordinator = AgentCoordinator::with_clock(clock.clone());
+        coordinator.set_lease_duration(Duration::from_secs(5));
+        let ids: Vec<AgentId> = (0..5)
+            .map(|_| coordinator.register_agent(KaneruAgent::with_default_config()))
+            .collect();
+        for id in &ids {
+            coordinator.join_group("irrigation", id).unwrap();
+        }
+        assert_eq!(
+            coordinator.elect_leader("pumps"),
+            Err(CoordinationError::GroupNotFound)
+        );
+
+        assert_eq!(
+            coordinator.elect_leader("irrigation").unwrap(),
+            ConsensusResult::Elected {
+                leader: ids[0].clone(),
+                term: 1
+            }
+        );
+        // Electing again agrees on the current leader.
+        assert_eq!(
+            coordinator.elect_leader("irrigation").unwrap(),
+            ConsensusResult::Elected {
+                leader: ids[0].clone(),
+                term: 1
+            }
+        );
+
+        clock.advance(Duration::from_secs(4));
+        assert_eq!(coordinator.heartbeat(&ids[0]).unwrap(), 1);
+        clock.advance(Duration::from_secs(4));
+        coordinator.step_all(HashMap::new());
+        assert_eq!(coordinator.leader("irrigation"), Some(ids[0].clone()));
+
+        // The leader stops; it keeps the group until its lease lapses.
+        coordinator.unregister_agent(&ids[0]).unwrap();
+        coordinator.step_all(HashMap::new());
+        assert_eq!(coordinator.leader("irrigation"), Some(ids[0].clone()));
+
+        clock.advance(Duration::from_secs(2));
+        coordinator.step_all(HashMap::new());
+        assert_eq!(coordinator.leader("irrigation"), Some(ids[1].clone()));
+        assert_eq!(
+            coordinator.elect_leader("irrigation").unwrap(),
+            ConsensusResult::Elected {
+                leader: ids[1].clone(),
+                term: 2
+            }
+        );
+        assert_eq!(coordinator.message_bus.stats().0, 2);
+
+        // A group without registered members has no leader to elect.
+        coordinator.join_group("pumps", &ids[4]).unwrap();
+        coordinator.unregister_agent(&ids[4]).unwrap();
+        assert_eq!(
+            coordinator.elect_leader("pumps").unwrap(),
+            ConsensusResult::Pending
+        );
+    }
+
+    #[test]
+    fn test_group_schedule_steps_members() {
+        let clock = Arc::new(ManualClock::default());
+        let mut coordinator = AgentCoordinator::with_clock(clock.clone());
+        let ids: Vec<AgentId> = (0..3)
+            .map(|_| coordinator.register_agent(KaneruAgent::with_default_config()))
+            .collect();
+        coordinator.join_group("irrigation", &ids[0]).unwrap();
+        coordinator.join_group("irrigation", &ids[2]).unwrap();
+        coordinator.add_group_schedule(
+            "irrigation",
+            Schedule::every(Duration::from_secs(3600)),
+            ObservationTemplate::new("water"),
+        );
+
+        assert!(coordinator.step_all(HashMap::new()).is_empty());
+
+        clock.advance(Duration::from_secs(3600));
+        let mut stepped: Vec<AgentId> = coordinator
+            .step_all(HashMap::new())
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        stepped.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(stepped, vec![ids[0].clone(), ids[2].clone()]);
+        let member = coordinator.get_agent(&ids[0]).unwrap();
+        assert_eq!(
+            member.observation_history().back().unwrap().obs_type,
+            ObservationType::Scheduled("water".into())
+        );
+        assert!(coordinator.step_all(HashMap::new()).is_empty());
+
+        // Agents' own schedules are stepped too
+        let agent = coordinator.get_agent_mut(&ids[1]).unwrap();
+        agent.set_scheduler(Scheduler::with_clock(clock.clone()));
+        agent.add_schedule(
+            Schedule::every(Duration::from_secs(60)),
+            ObservationTemplate::new("own"),
+        );
+        clock.advance(Duration::from_secs(60));
+        let stepped = coordinator.step_all(HashMap::new());
+        assert_eq!(stepped.len(), 1);
+        assert_eq!(stepped[0].0, ids[1]);
+
+        assert!(coordinator.remove_group_schedule("water"));
+        clock.advance(Duration::from_secs(3600));
+        let stepped = coordinator.step_all(HashMap::new());
+        assert!(stepped.iter().all(|(id, _)| *id == ids[1]));
+    }
 }
@@ -0,0 +1,147 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Running agents through episodes of an environment.
+
+use super::{Environment, EpisodeAgent};
+use serde::{Deserialize, Serialize};
+
+/// Default cap on the steps of one episode
+pub const DEFAULT_MAX_EPISODE_STEPS: usize = 1000;
+
+/// Runs an [`EpisodeAgent`] in an [`Environment`] for a number of episodes.
+#[derive(Debug, Clone)]
+pub struct EpisodeRunner {
+    episodes: usize,
+    max_steps: usize,
+}
+
+impl EpisodeRunner {
+    /// Creates a runner for `episodes` episodes of at most
+    /// [`DEFAULT_MAX_EPISODE_STEPS`] steps.
+    pub fn new(episodes: usize) -> Self {
+        Self {
+            episodes,
+            max_steps: DEFAULT_MAX_EPISODE_STEPS,
+        }
+    }
+
+    /// Caps the steps of each episode.
+    ///
+    /// An episode cut short is reported to the agent as done, so it starts the
+    /// next one afresh.
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps.max(1);
+        self
+    }
+
+    /// Runs all episodes, returning the reward and length of each.
+    pub fn run<A, E>(&self, agent: &mut A, env: &mut E) -> LearningCurve
+    where
+        A: EpisodeAgent + ?Sized,
+        E: Environment + ?Sized,
+    {
+        let action_space = env.action_space();
+        let mut curve = LearningCurve::default();
+
+        for _ in 0..self.episodes {
+            let mut observation = env.reset();
+            let mut total = 0.0;
+            let mut steps = 0;
+
+            loop {
+                let action = agent.act(&observation, &action_space);
+                let (next, reward, done) = env.step(&action);
+                steps += 1;
+                total += reward;
+
+                let done = done || steps >= self.max_steps;
+                agent.reward(&action, reward, &next, done);
+                observation = next;
+                if done {
+                    break;
+                }
+            }
+
+            curve.rewards.push(total);
+            curve.lengths.push(steps);
+        }
+
+        curve
+    }
+}
+
+/// The total reward and length of each episode of a run, in order.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LearningCurve {
+    /// The total reward of each episode.
+    pub rewards: Vec<f64>,
+    /// The number of steps of each episode.
+    pub lengths: Vec<usize>,
+}
+
+impl LearningCurve {
+    /// Returns the number of episodes run.
+    pub fn episodes(&self) -> usize {
+        self.rewards.len()
+    }
+
+    /// Returns the mean episode reward, or 0 if no episodes were run.
+    pub fn mean_reward(&self) -> f64 {
+        mean(&self.rewards)
+    }
+
+    /// Returns the mean reward of the last `n` episodes.
+    pub fn final_mean_reward(&self, n: usize) -> f64 {
+        mean(&self.rewards[self.rewards.len().saturating_sub(n)..])
+    }
+
+    /// Returns the mean reward of each consecutive window of `window`
+    /// episodes, smoothing the curve for plotting.
+    pub fn moving_average(&self, window: usize) -> Vec<f64> {
+        self.rewards.windows(window.max(1)).map(mean).collect()
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        0.0
+    } else {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Action, GridWorld, Observation, RandomAgent};
+
+    /// Always walks right, then down.
+    struct Stairs;
+
+    impl EpisodeAgent for Stairs {
+        fn act(&mut self, observation: &Observation, action_space: &[Action]) -> Action {
+            let right = observation.value.as_i64().unwrap_or(0) % 2 == 0;
+            action_space[if right { 3 } else { 1 }].clone()
+        }
+
+        fn reward(&mut self, _: &Action, _: f64, _: &Observation, _: bool) {}
+    }
+
+    #[test]
+    fn test_runner_records_curve() {
+        let curve = EpisodeRunner::new(3).run(&mut Stairs, &mut GridWorld::new(2, 2));
+        assert_eq!(curve.episodes(), 3);
+        assert_eq!(curve.lengths, vec![2, 2, 2]);
+        assert_eq!(curve.mean_reward(), 9.0);
+        assert_eq!(curve.moving_average(2), vec![9.0, 9.0]);
+    }
+
+    #[test]
+    fn test_runner_caps_episode_length() {
+        let runner = EpisodeRunner::new(4).with_max_steps(5);
+        let curve = runner.run(&mut RandomAgent::new(1), &mut GridWorld::new(50, 50));
+        assert!(curve.lengths.iter().all(|&steps| steps == 5));
+        assert_eq!(curve.final_mean_reward(2), -5.0);
+    }
+}
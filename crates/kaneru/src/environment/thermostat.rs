@@ -0,0 +1,184 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! A setpoint-tracking room temperature simulation.
+
+use super::Environment;
+use crate::{Action, ActionType, Observation};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// Configuration for a [`Thermostat`] environment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThermostatConfig {
+    /// The temperature to hold, in °C.
+    pub setpoint: f64,
+    /// The outside temperature the room drifts towards, in °C.
+    pub outside: f64,
+    /// The fraction of the indoor/outdoor difference lost each step.
+    pub loss_rate: f64,
+    /// The temperature change of one step of heating or cooling, in °C.
+    pub power: f64,
+    /// The largest random disturbance per step, in °C.
+    pub noise: f64,
+    /// The largest initial distance from the setpoint, in °C.
+    pub initial_spread: f64,
+    /// Steps per episode.
+    pub horizon: usize,
+    /// The reward subtracted for each step of heating or cooling.
+    pub energy_cost: f64,
+}
+
+impl Default for ThermostatConfig {
+    fn default() -> Self {
+        Self {
+            setpoint: 21.0,
+            outside: 10.0,
+            loss_rate: 0.05,
+            power: 1.5,
+            noise: 0.3,
+            initial_spread: 6.0,
+            horizon: 50,
+            energy_cost: 0.1,
+        }
+    }
+}
+
+/// Keep a room at a setpoint while it loses heat to the outside.
+///
+/// The observation is the `temperature` sensor, rounded to whole degrees.
+/// Actions are `Custom("heat")`, `Custom("cool")` and `Custom("idle")`. Each
+/// step earns minus the distance from the setpoint, less the energy cost when
+/// heating or cooling. Episodes last [`horizon`](ThermostatConfig::horizon)
+/// steps and start at a random temperature near the setpoint; the same seed
+/// gives the same starts and disturbances.
+#[derive(Debug, Clone)]
+pub struct Thermostat {
+    config: ThermostatConfig,
+    rng: StdRng,
+    temperature: f64,
+    steps: usize,
+}
+
+impl Thermostat {
+    /// Creates a thermostat with the default configuration.
+    pub fn new(seed: u64) -> Self {
+        Self::with_config(ThermostatConfig::default(), seed)
+    }
+
+    /// Creates a thermostat with a custom configuration.
+    pub fn with_config(config: ThermostatConfig, seed: u64) -> Self {
+        let temperature = config.setpoint;
+        Self {
+            config,
+            rng: StdRng::seed_from_u64(seed),
+            temperature,
+            steps: 0,
+        }
+    }
+
+    /// The exact room temperature.
+    pub fn temperature(&self) -> f64 {
+        self.temperature
+    }
+
+    /// Returns the configuration.
+    pub fn config(&self) -> &ThermostatConfig {
+        &self.config
+    }
+
+    fn disturbance(&mut self, spread: f64) -> f64 {
+        if spread > 0.0 {
+            self.rng.random_range(-spread..=spread)
+        } else {
+            0.0
+        }
+    }
+
+    fn observation(&self) -> Observation {
+        Observation::sensor("temperature", self.temperature.round() as i64)
+    }
+}
+
+impl Environment for Thermostat {
+    fn reset(&mut self) -> Observation {
+        self.steps = 0;
+        self.temperature = self.config.setpoint + self.disturbance(self.config.initial_spread);
+        self.observation()
+    }
+
+    fn step(&mut self, action: &Action) -> (Observation, f64, bool) {
+        let (drive, energy) = match &action.action_type {
+            ActionType::Custom(name) if name == "heat" => (self.config.power, true),
+            ActionType::Custom(name) if name == "cool" => (-self.config.power, true),
+            _ => (0.0, false),
+        };
+
+        let loss = self.config.loss_rate * (self.temperature - self.config.outside);
+        let noise = self.disturbance(self.config.noise);
+        self.temperature += drive - loss + noise;
+        self.steps += 1;
+
+        let mut reward = -(self.temperature - self.config.setpoint).abs();
+        if energy {
+            reward -= self.config.energy_cost;
+        }
+        let done = self.steps >= self.config.horizon;
+        (self.observation(), reward, done)
+    }
+
+    fn action_space(&self) -> Vec<Action> {
+        ["heat", "cool", "idle"]
+            .into_iter()
+            .map(|name| Action::new(ActionType::Custom(name.to_string())))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn act(name: &str) -> Action {
+        Action::new(ActionType::Custom(name.to_string()))
+    }
+
+    #[test]
+    fn test_thermostat_is_seeded() {
+        let mut a = Thermostat::new(11);
+        let mut b = Thermostat::new(11);
+        assert_eq!(a.reset().value.as_i64(), b.reset().value.as_i64());
+        for _ in 0..10 {
+            let (_, ra, _) = a.step(&act("heat"));
+            let (_, rb, _) = b.step(&act("heat"));
+            assert_eq!(ra, rb);
+        }
+    }
+
+    #[test]
+    fn test_thermostat_dynamics() {
+        let config = ThermostatConfig {
+            noise: 0.0,
+            initial_spread: 0.0,
+            horizon: 3,
+            ..Default::default()
+        };
+        let mut env = Thermostat::with_config(config, 0);
+        assert_eq!(env.reset().value.as_i64(), Some(21));
+
+        // Idle loses heat to the outside
+        let (_, reward, done) = env.step(&act("idle"));
+        assert!((env.temperature() - 20.45).abs() < 1e-9);
+        assert!((reward + 0.55).abs() < 1e-9);
+        assert!(!done);
+
+        // Heating pays the energy cost
+        let (obs, reward, _) = env.step(&act("heat"));
+        assert_eq!(obs.value.as_i64(), Some(21));
+        assert!(reward < -0.1);
+
+        let (_, _, done) = env.step(&act("cool"));
+        assert!(done);
+    }
+}
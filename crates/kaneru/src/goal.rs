@@ -5,6 +5,11 @@
 //!
 //! Goals define what an agent is trying to achieve, providing the primary
 //! motivation for its actions.
+//!
+//! A [`CompositeGoal`] balances several competing objectives, such as keeping
+//! a room warm while using little energy. The environment rewards each
+//! objective separately, and a [`Scalarization`] turns that reward vector
+//! into the single reward a learning engine works with.
 
 use crate::types::{Priority, Timestamp, Value, ValueRange};
 use serde::{Deserialize, Serialize};
@@ -259,6 +264,177 @@ impl Goal {
     }
 }
 
+/// Multiplier between consecutive objectives under
+/// [`Scalarization::Lexicographic`].
+///
+/// Per-step rewards of an objective are expected to stay well below it in
+/// magnitude, so that a lower-priority objective cannot outweigh a
+/// higher-priority one.
+pub const LEXICOGRAPHIC_BASE: f64 = 1000.0;
+
+/// How a reward vector is reduced to the scalar reward that drives learning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum Scalarization {
+    /// The sum of each objective's reward times its weight.
+    #[default]
+    WeightedSum,
+    /// Objectives in order of priority: an objective only matters once the
+    /// ones before it are satisfied.
+    ///
+    /// Each reward is capped at its objective's threshold, if any, so that
+    /// doing better than the threshold buys nothing, and the capped rewards
+    /// are combined with weights of decreasing powers of
+    /// [`LEXICOGRAPHIC_BASE`]. The objectives' own weights are ignored.
+    Lexicographic,
+}
+
+/// One of the objectives of a [`CompositeGoal`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Objective {
+    /// What the objective is about, e.g. `Goal::minimize("energy")`.
+    pub goal: Goal,
+    /// The objective's weight under [`Scalarization::WeightedSum`].
+    pub weight: f64,
+    /// The reward beyond which the objective counts as satisfied under
+    /// [`Scalarization::Lexicographic`].
+    pub threshold: Option<f64>,
+}
+
+impl Objective {
+    /// The name of the objective's goal.
+    pub fn name(&self) -> &str {
+        &self.goal.name
+    }
+}
+
+/// A goal made of several weighted objectives.
+///
+/// The reward of a step is a vector with one component per objective, in the
+/// order the objectives were added.
+///
+/// # Examples
+///
+/// ```
+/// # use kaneru::{CompositeGoal, Goal, Scalarization};
+/// let goal = CompositeGoal::new("Comfort on a budget")
+///     .with_objective(Goal::maintain("temperature", 20.0..23.0), 0.7)
+///     .with_objective(Goal::minimize("energy"), 0.3);
+///
+/// let reward = goal.scalarize(&[1.0, -2.0], Scalarization::WeightedSum);
+/// assert!((reward - 0.1).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompositeGoal {
+    /// A human-readable name for the goal.
+    pub name: String,
+    /// The objectives, in order of priority.
+    pub objectives: Vec<Objective>,
+}
+
+impl CompositeGoal {
+    /// Creates a composite goal without objectives.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            objectives: Vec::new(),
+        }
+    }
+
+    /// Adds an objective with the given weight.
+    pub fn with_objective(mut self, goal: Goal, weight: f64) -> Self {
+        self.objectives.push(Objective {
+            goal,
+            weight,
+            threshold: None,
+        });
+        self
+    }
+
+    /// Adds an objective that is satisfied at `threshold` under
+    /// [`Scalarization::Lexicographic`].
+    pub fn with_thresholded_objective(mut self, goal: Goal, weight: f64, threshold: f64) -> Self {
+        self.objectives.push(Objective {
+            goal,
+            weight,
+            threshold: Some(threshold),
+        });
+        self
+    }
+
+    /// Changes the weight of the objective at `index`.
+    ///
+    /// Returns `false` if there is no such objective.
+    pub fn set_weight(&mut self, index: usize, weight: f64) -> bool {
+        match self.objectives.get_mut(index) {
+            Some(objective) => {
+                objective.weight = weight;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the number of objectives.
+    pub fn len(&self) -> usize {
+        self.objectives.len()
+    }
+
+    /// Returns `true` if the goal has no objectives.
+    pub fn is_empty(&self) -> bool {
+        self.objectives.is_empty()
+    }
+
+    /// Reduces a reward vector to a scalar reward.
+    ///
+    /// Components beyond the number of objectives are ignored, and missing
+    /// ones count as `0.0`.
+    pub fn scalarize(&self, rewards: &[f64], strategy: Scalarization) -> f64 {
+        let reward = |i: usize| rewards.get(i).copied().unwrap_or(0.0);
+        match strategy {
+            Scalarization::WeightedSum => self
+                .objectives
+                .iter()
+                .enumerate()
+                .map(|(i, objective)| objective.weight * reward(i))
+                .sum(),
+            Scalarization::Lexicographic => {
+                let n = self.objectives.len();
+                self.objectives
+                    .iter()
+                    .enumerate()
+                    .map(|(i, objective)| {
+                        let capped = match objective.threshold {
+                            Some(threshold) => reward(i).min(threshold),
+                            None => reward(i),
+                        };
+                        capped * LEXICOGRAPHIC_BASE.powi((n - 1 - i) as i32)
+                    })
+                    .sum()
+            }
+        }
+    }
+
+    /// Recomputes the reward of every experience that carries a reward
+    /// vector, e.g. to retrain offline with different weights.
+    ///
+    /// Returns the number of experiences updated.
+    pub fn rescalarize(
+        &self,
+        experiences: &mut [crate::learning::Experience],
+        strategy: Scalarization,
+    ) -> usize {
+        let mut updated = 0;
+        for experience in experiences
+            .iter_mut()
+            .filter(|e| !e.reward_vector.is_empty())
+        {
+            experience.reward = self.scalarize(&experience.reward_vector, strategy);
+            updated += 1;
+        }
+        updated
+    }
+}
+
 /// Manages a collection of goals for an agent.
 pub struct GoalManager {
     goals: Vec<Goal>,
@@ -1138,4 +1314,80 @@ mod tests {
         manager.get_mut(&g2).unwrap().fail();
         assert_eq!(manager.active_goals().len(), 1);
     }
+
+    // ==================== CompositeGoal Tests ====================
+
+    fn comfort_on_a_budget() -> CompositeGoal {
+        CompositeGoal::new("Comfort on a budget")
+            .with_thresholded_objective(Goal::maintain("temperature", 20.0..23.0), 0.2, 0.5)
+            .with_objective(Goal::minimize("energy"), 0.8)
+    }
+
+    #[test]
+    fn test_composite_goal_weighted_sum() {
+        let goal = comfort_on_a_budget();
+        assert_eq!(goal.len(), 2);
+        assert_eq!(goal.objectives[1].name(), "Minimize energy");
+
+        let reward = goal.scalarize(&[1.0, -1.0], Scalarization::WeightedSum);
+        assert!((reward + 0.6).abs() < 1e-9);
+
+        // Missing components count as zero, extra ones are ignored
+        assert!((goal.scalarize(&[1.0], Scalarization::WeightedSum) - 0.2).abs() < 1e-9);
+        assert!((goal.scalarize(&[0.0, 1.0, 5.0], Scalarization::WeightedSum) - 0.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_composite_goal_lexicographic() {
+        let goal = comfort_on_a_budget();
+        let lex = |rewards: &[f64]| goal.scalarize(rewards, Scalarization::Lexicographic);
+
+        // The first objective dominates regardless of weights
+        assert!(lex(&[0.3, -1.0]) > lex(&[0.2, 0.0]));
+        // Past its threshold it stops mattering, and the second one decides
+        assert!(lex(&[0.5, 0.0]) > lex(&[0.9, -1.0]));
+        assert_eq!(lex(&[0.5, 0.0]), lex(&[0.9, 0.0]));
+    }
+
+    #[test]
+    fn test_composite_goal_set_weight() {
+        let mut goal = comfort_on_a_budget();
+        assert!(goal.set_weight(0, 0.9));
+        assert!(!goal.set_weight(2, 0.1));
+        assert_eq!(goal.objectives[0].weight, 0.9);
+        assert!(CompositeGoal::new("empty").is_empty());
+    }
+
+    #[test]
+    fn test_composite_goal_rescalarize() {
+        use crate::learning::{ActionId, Experience, StateId};
+
+        let experience = |reward_vector: Vec<f64>| {
+            let state = StateId::from_string("s".to_string());
+            Experience::new(
+                state.clone(),
+                ActionId::from_string("a".to_string()),
+                1.0,
+                state,
+                false,
+            )
+            .with_reward_vector(reward_vector)
+        };
+        let mut experiences = vec![experience(vec![1.0, -1.0]), experience(Vec::new())];
+
+        let updated =
+            comfort_on_a_budget().rescalarize(&mut experiences, Scalarization::WeightedSum);
+        assert_eq!(updated, 1);
+        assert!((experiences[0].reward + 0.6).abs() < 1e-9);
+        assert_eq!(experiences[1].reward, 1.0);
+    }
+
+    #[test]
+    fn test_composite_goal_serialize() {
+        let goal = comfort_on_a_budget();
+        let json = serde_json::to_string(&goal).unwrap();
+        let restored: CompositeGoal = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.len(), 2);
+        assert_eq!(restored.objectives[0].threshold, Some(0.5));
+    }
 }
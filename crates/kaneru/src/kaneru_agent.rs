@@ -27,9 +27,13 @@
 //! └─────────────────────────────────────────────────────────────┘
 //! ```
 
+#[cfg(feature = "memory")]
+use crate::memory::{EpisodicMemory, RecalledEpisode, RetrievalFailure};
 use crate::{
-    Action, ActionId, ActionResult, ActionType, Goal, HierarchicalGoalSolver, LearningConfig,
-    LearningEngine, Observation, PredictiveConfig, PredictiveModel, StateId,
+    Action, ActionId, ActionResult, ActionType, CompositeGoal, ExperienceLogger, Goal,
+    HierarchicalGoalSolver, LearningConfig, LearningEngine, Observation, ObservationPipeline,
+    ObservationTemplate, PredictiveConfig, PredictiveModel, SafetyLayer, Scalarization, Schedule,
+    ScheduleState, Scheduler, SchemaRegistry, SensorPipeline, StateId,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
@@ -73,6 +77,10 @@ pub struct KaneruConfig {
     /// If `true`, the agent will automatically attempt to decompose high-level goals
     /// into smaller, more manageable sub-goals.
     pub auto_decompose_goals: bool,
+    /// How the reward vector of an `Outcome` is reduced to a scalar reward
+    /// when a `CompositeGoal` is set.
+    #[serde(default)]
+    pub scalarization: Scalarization,
 }
 
 impl Default for KaneruConfig {
@@ -86,6 +94,7 @@ impl Default for KaneruConfig {
             anomaly_sensitivity: 0.7,
             goal_strategy: GoalSelectionStrategy::Priority,
             auto_decompose_goals: true,
+            scalarization: Scalarization::WeightedSum,
         }
     }
 }
@@ -124,6 +133,24 @@ pub struct AgentStats {
     pub avg_reward: f64,
     /// The agent's success rate, typically calculated as `goals_achieved / total_goals`.
     pub success_rate: f64,
+    /// The number of sensor readings rejected as outliers by preprocessing.
+    #[serde(default)]
+    pub outliers_rejected: u64,
+    /// The number of episodic memory reads or writes that failed.
+    #[serde(default)]
+    pub memory_failures: u64,
+    /// The number of actions blocked by a safety veto or exclusive group.
+    #[serde(default)]
+    pub actions_vetoed: u64,
+    /// The number of actions blocked by a safety rate limit.
+    #[serde(default)]
+    pub actions_rate_limited: u64,
+    /// The number of actions rejected for breaking their parameter schema.
+    #[serde(default)]
+    pub actions_invalid: u64,
+    /// The running performance of each objective of the composite goal.
+    #[serde(default)]
+    pub objectives: Vec<ObjectiveStats>,
 }
 
 impl Default for AgentStats {
@@ -138,13 +165,54 @@ impl Default for AgentStats {
             current_epsilon: 0.1,
             avg_reward: 0.0,
             success_rate: 0.0,
+            outliers_rejected: 0,
+            memory_failures: 0,
+            actions_vetoed: 0,
+            actions_rate_limited: 0,
+            actions_invalid: 0,
+            objectives: Vec::new(),
         }
     }
 }
 
+/// Smoothing factor of [`ObjectiveStats::recent_reward`].
+pub const OBJECTIVE_RECENT_SMOOTHING: f64 = 0.1;
+
+/// The running performance of one objective of a `CompositeGoal`.
+///
+/// Comparing objectives shows which one the learned policy sacrifices.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ObjectiveStats {
+    /// The name of the objective.
+    pub name: String,
+    /// The objective's weight.
+    pub weight: f64,
+    /// The number of rewards received for the objective.
+    pub samples: u64,
+    /// The mean reward received for the objective.
+    pub mean_reward: f64,
+    /// An exponential moving average of the objective's reward, weighting
+    /// each new reward by [`OBJECTIVE_RECENT_SMOOTHING`].
+    pub recent_reward: f64,
+}
+
+impl ObjectiveStats {
+    fn record(&mut self, reward: f64) {
+        self.samples += 1;
+        self.mean_reward += (reward - self.mean_reward) / self.samples as f64;
+        self.recent_reward = if self.samples == 1 {
+            reward
+        } else {
+            self.recent_reward + OBJECTIVE_RECENT_SMOOTHING * (reward - self.recent_reward)
+        };
+    }
+}
+
 /// A serializable representation of an agent's state for persistence.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SerializedState {
+    /// The version of this layout; see [`STATE_VERSION`](crate::persistence::STATE_VERSION).
+    pub version: u32,
     /// The agent's configuration.
     pub config: KaneruConfig,
     /// The agent's performance statistics.
@@ -159,6 +227,15 @@ pub struct SerializedState {
     pub action_history: Vec<Action>,
     /// The serialized state of the learning engine (e.g., Q-table).
     pub learning_state: Vec<u8>,
+    /// The agent's composite goal, if any.
+    #[serde(default)]
+    pub composite_goal: Option<CompositeGoal>,
+    /// The parameter schemas checked on each selected action.
+    #[serde(default)]
+    pub action_schemas: SchemaRegistry,
+    /// The latest slot handled by each of the agent's schedules.
+    #[serde(default)]
+    pub schedule_state: ScheduleState,
 }
 
 /// Represents the outcome of an agent's step, used for learning.
@@ -170,6 +247,9 @@ pub struct Outcome {
     pub result: ActionResult,
     /// The reward (positive or negative) received from the environment after the action.
     pub reward: f64,
+    /// The reward of each objective of the agent's `CompositeGoal`, in order.
+    /// When set, it replaces `reward` with its scalarization.
+    pub reward_vector: Vec<f64>,
     /// The new observation of the environment after the action.
     pub new_observation: Observation,
     /// `true` if this outcome concludes a learning episode.
@@ -189,10 +269,17 @@ impl Outcome {
             action,
             result,
             reward,
+            reward_vector: Vec::new(),
             new_observation,
             done,
         }
     }
+
+    /// Sets the reward of each objective of the agent's `CompositeGoal`.
+    pub fn with_reward_vector(mut self, rewards: Vec<f64>) -> Self {
+        self.reward_vector = rewards;
+        self
+    }
 }
 
 /// The main Kaneru Agent, integrating learning, planning, and predictive capabilities.
@@ -211,6 +298,8 @@ pub struct KaneruAgent {
     current_state: Option<StateId>,
     /// The ID of the goal the agent is currently pursuing.
     active_goal: Option<String>,
+    /// The objectives reward vectors are scalarized against.
+    composite_goal: Option<CompositeGoal>,
 
     /// A history of recent observations.
     observation_history: VecDeque<Observation>,
@@ -229,6 +318,30 @@ pub struct KaneruAgent {
 
     /// A cached list of actions the agent can perform.
     available_actions: Vec<ActionId>,
+    /// The actions set by `set_action_space`, chosen among instead of the
+    /// defaults.
+    action_space: Vec<Action>,
+
+    /// An optional log that records every experience the agent learns from.
+    experience_logger: Option<ExperienceLogger>,
+    /// Per-sensor preprocessing applied before each step.
+    preprocessing: ObservationPipeline,
+    /// Interlocks and rate limits checked on each selected action.
+    safety: SafetyLayer,
+    /// Parameter schemas checked on each selected action.
+    schemas: SchemaRegistry,
+    /// Schedules stepped on by `step_scheduled`.
+    scheduler: Scheduler,
+
+    /// Optional episodic memory consulted before each decision.
+    #[cfg(feature = "memory")]
+    episodic: Option<EpisodicMemory>,
+    /// The episodes recalled for the most recent decision.
+    #[cfg(feature = "memory")]
+    recalled: Vec<RecalledEpisode>,
+    /// The situation of the most recent decision, recorded by `learn`.
+    #[cfg(feature = "memory")]
+    last_situation: Option<String>,
 }
 
 impl KaneruAgent {
@@ -244,6 +357,7 @@ impl KaneruAgent {
             predictive,
             current_state: None,
             active_goal: None,
+            composite_goal: None,
             observation_history: VecDeque::with_capacity(config.max_observations),
             action_history: VecDeque::with_capacity(config.max_actions),
             config,
@@ -251,6 +365,18 @@ impl KaneruAgent {
             episode_reward: 0.0,
             episode_steps: 0,
             available_actions: Self::default_actions(),
+            action_space: Vec::new(),
+            experience_logger: None,
+            preprocessing: ObservationPipeline::new(),
+            safety: SafetyLayer::new(),
+            schemas: SchemaRegistry::new(),
+            scheduler: Scheduler::new(),
+            #[cfg(feature = "memory")]
+            episodic: None,
+            #[cfg(feature = "memory")]
+            recalled: Vec::new(),
+            #[cfg(feature = "memory")]
+            last_situation: None,
         }
     }
 
@@ -268,10 +394,23 @@ impl KaneruAgent {
     ///
     /// # Returns
     ///
-    /// The `Action` the agent has decided to take.
+    /// The `Action` the agent has decided to take. If preprocessing drops the
+    /// observation as an outlier, no step is taken and `Action::noop()` is
+    /// returned. `Action::noop()` is also returned when episodic recall fails
+    /// and its configuration blocks on failure, and in place of an action the
+    /// safety layer blocks or that breaks its parameter schema.
     pub fn step(&mut self, observation: Observation) -> Action {
+        let preprocessed = self.preprocessing.process(observation);
+        if preprocessed.outlier {
+            self.stats.outliers_rejected += 1;
+        }
+        let Some(observation) = preprocessed.observation else {
+            return Action::noop();
+        };
+
         self.stats.total_steps += 1;
         self.episode_steps += 1;
+        self.safety.observe(&observation);
 
         // 1. Update state from observation
         self.update_state(&observation);
@@ -285,22 +424,51 @@ impl KaneruAgent {
         // 3. Update goals based on state (Hierarchical)
         self.update_goals(&observation);
 
-        // 4. Select action (Learning + Goal-directed)
+        // 4. Recall similar past episodes (Memory)
+        #[cfg(feature = "memory")]
+        {
+            if !self.recall_episodes(&observation) {
+                return Action::noop();
+            }
+        }
+
+        // 5. Select action (Learning + Goal-directed + Memory)
         let action = self.select_action(&observation);
+        let action = self.screen_action(&observation, action);
 
-        // 5. Record action
+        // 6. Record action
         self.record_action(&action);
 
         action
     }
 
+    /// Steps on the earliest due observation of the agent's schedules.
+    ///
+    /// Returns `None` if no schedule is due. Call it regularly, alongside
+    /// [`step`](Self::step) for external observations; call it until it
+    /// returns `None` to handle every due schedule.
+    pub fn step_scheduled(&mut self) -> Option<Action> {
+        let observation = self.scheduler.poll()?;
+        Some(self.step(observation))
+    }
+
     /// Updates the agent's internal models based on the outcome of an action.
     ///
     /// # Arguments
     ///
     /// * `outcome` - An `Outcome` struct containing the action, result, reward,
     ///   and new observation.
-    pub fn learn(&mut self, outcome: Outcome) {
+    ///
+    /// If a [`CompositeGoal`] is set and the outcome carries a reward vector,
+    /// the agent learns from the vector's scalarization under
+    /// [`KaneruConfig::scalarization`], and the vector is kept in the
+    /// experience.
+    pub fn learn(&mut self, mut outcome: Outcome) {
+        outcome.reward = self.scalarize_reward(&outcome);
+
+        #[cfg(feature = "memory")]
+        self.record_episode(&outcome);
+
         let prev_state = match self.current_state.as_ref() {
             Some(s) => s,
             None => {
@@ -379,7 +547,13 @@ impl KaneruAgent {
             outcome.reward,
             new_state.clone(),
             outcome.done,
-        );
+        )
+        .with_reward_vector(outcome.reward_vector.clone());
+        if let Some(logger) = self.experience_logger.as_mut() {
+            if let Err(e) = logger.log(&exp) {
+                log::warn!("Failed to record experience: {}", e);
+            }
+        }
         self.learning.add_experience(exp);
 
         // 5. Perform experience replay
@@ -420,6 +594,30 @@ impl KaneruAgent {
         id
     }
 
+    /// Sets the objectives that reward vectors are scalarized against.
+    ///
+    /// Resets [`AgentStats::objectives`] and returns the previous composite
+    /// goal, if any.
+    pub fn set_composite_goal(&mut self, goal: CompositeGoal) -> Option<CompositeGoal> {
+        self.stats.objectives = goal
+            .objectives
+            .iter()
+            .map(|objective| ObjectiveStats {
+                name: objective.name().to_string(),
+                weight: objective.weight,
+                samples: 0,
+                mean_reward: 0.0,
+                recent_reward: 0.0,
+            })
+            .collect();
+        self.composite_goal.replace(goal)
+    }
+
+    /// Returns the agent's composite goal, if any.
+    pub fn composite_goal(&self) -> Option<&CompositeGoal> {
+        self.composite_goal.as_ref()
+    }
+
     /// Returns a reference to the agent's current statistics.
     pub fn get_statistics(&self) -> &AgentStats {
         &self.stats
@@ -431,6 +629,7 @@ impl KaneruAgent {
         let learning_state = serde_json::to_vec(&self.learning).unwrap_or_default();
 
         SerializedState {
+            version: crate::persistence::STATE_VERSION,
             config: self.config.clone(),
             stats: self.stats.clone(),
             current_state: self.current_state.clone(),
@@ -438,6 +637,9 @@ impl KaneruAgent {
             observation_history: self.observation_history.iter().cloned().collect(),
             action_history: self.action_history.iter().cloned().collect(),
             learning_state,
+            composite_goal: self.composite_goal.clone(),
+            action_schemas: self.schemas.clone(),
+            schedule_state: self.scheduler.state().clone(),
         }
     }
 
@@ -447,6 +649,9 @@ impl KaneruAgent {
         self.stats = state.stats;
         self.current_state = state.current_state;
         self.active_goal = state.active_goal;
+        self.composite_goal = state.composite_goal;
+        self.schemas = state.action_schemas;
+        self.scheduler.restore(state.schedule_state);
 
         self.observation_history = state.observation_history.into();
         self.action_history = state.action_history.into();
@@ -495,6 +700,24 @@ impl KaneruAgent {
         self.goal_solver.get_executable_goals()
     }
 
+    /// Restricts the agent to choosing among `actions`, such as the action
+    /// space of an [`Environment`](crate::Environment).
+    ///
+    /// Learned values are kept. An empty slice restores the default actions.
+    pub fn set_action_space(&mut self, actions: &[Action]) {
+        self.action_space = actions.to_vec();
+        self.available_actions = if actions.is_empty() {
+            Self::default_actions()
+        } else {
+            actions.iter().map(ActionId::from_action).collect()
+        };
+    }
+
+    /// Returns the actions set by [`set_action_space`](Self::set_action_space).
+    pub fn action_space(&self) -> &[Action] {
+        &self.action_space
+    }
+
     /// Resets the agent's state for the beginning of a new learning episode.
     pub fn reset(&mut self) {
         self.current_state = None;
@@ -516,6 +739,29 @@ impl KaneruAgent {
         self.predictive.record(observation);
     }
 
+    /// The reward to learn from: the scalarized reward vector if there is a
+    /// composite goal and a vector, `outcome.reward` otherwise.
+    fn scalarize_reward(&mut self, outcome: &Outcome) -> f64 {
+        let Some(goal) = self.composite_goal.as_ref() else {
+            return outcome.reward;
+        };
+        if outcome.reward_vector.is_empty() {
+            return outcome.reward;
+        }
+        if outcome.reward_vector.len() != goal.len() {
+            log::warn!(
+                "Reward vector has {} components for {} objectives",
+                outcome.reward_vector.len(),
+                goal.len()
+            );
+        }
+
+        for (stats, reward) in self.stats.objectives.iter_mut().zip(&outcome.reward_vector) {
+            stats.record(*reward);
+        }
+        goal.scalarize(&outcome.reward_vector, self.config.scalarization)
+    }
+
     fn handle_anomaly(&mut self, observation: &Observation) {
         // In case of anomaly, we might want to:
         // 1. Increase exploration temporarily
@@ -607,6 +853,13 @@ impl KaneruAgent {
             }
         };
 
+        // Let recalled episodes override the policy, except when exploring
+        #[cfg(feature = "memory")]
+        let action_id = match self.config.mode {
+            OperationMode::Exploration => action_id,
+            _ => self.consult_episodes(action_id),
+        };
+
         // Convert ActionId back to Action
         if let Some(action_id) = action_id {
             self.action_from_id(&action_id)
@@ -616,7 +869,49 @@ impl KaneruAgent {
         }
     }
 
+    /// Replaces an action that breaks its parameter schema or that the safety
+    /// layer blocks with a no-op, learning the layer's penalty for the
+    /// rejected action in the current state.
+    fn screen_action(&mut self, observation: &Observation, action: Action) -> Action {
+        if let Err(error) = self.schemas.validate(&action) {
+            self.stats.actions_invalid += 1;
+            log::warn!("Rejected action: {}", error);
+        } else {
+            let Err(violation) = self.safety.check(&action) else {
+                return action;
+            };
+            if violation.is_interlock() {
+                self.stats.actions_vetoed += 1;
+            } else {
+                self.stats.actions_rate_limited += 1;
+            }
+            log::warn!("Blocked action {:?}: {}", action.action_type, violation);
+        }
+
+        let state = StateId::from_observation(observation);
+        self.learning.update(
+            &state,
+            &ActionId::from_action(&action),
+            self.safety.penalty(),
+            &state,
+            None,
+            &self.available_actions,
+        );
+        self.stats.learning_updates += 1;
+        Action::noop()
+    }
+
     fn action_from_id(&self, action_id: &ActionId) -> Action {
+        if let Some(action) = self
+            .action_space
+            .iter()
+            .find(|a| ActionId::from_action(a) == *action_id)
+        {
+            let mut chosen = Action::new(action.action_type.clone());
+            chosen.params = action.params.clone();
+            return chosen;
+        }
+
         // Parse action ID back to ActionType
         let action_str = action_id.as_str();
 
@@ -635,6 +930,94 @@ impl KaneruAgent {
         }
     }
 
+    /// Maps an executed action back to the available action it was chosen as.
+    #[cfg(feature = "memory")]
+    fn available_action_id(&self, action: &Action) -> ActionId {
+        let id = ActionId::from_action(action);
+        self.available_actions
+            .iter()
+            .find(|a| id.as_str().starts_with(a.as_str()))
+            .cloned()
+            .unwrap_or(id)
+    }
+
+    /// Recalls the episodes most similar to `observation`.
+    ///
+    /// Returns `false` if recall failed and the configuration blocks on failure.
+    #[cfg(feature = "memory")]
+    fn recall_episodes(&mut self, observation: &Observation) -> bool {
+        self.recalled.clear();
+        let Some(episodic) = self.episodic.as_ref() else {
+            return true;
+        };
+
+        let situation = episodic.situation(observation);
+        let recalled = episodic.recall(&situation);
+        self.last_situation = Some(situation);
+        match recalled {
+            Ok(episodes) => {
+                self.recalled = episodes;
+                true
+            }
+            Err(e) => {
+                log::warn!("Episodic recall failed: {}", e);
+                self.stats.memory_failures += 1;
+                episodic.config().on_failure == RetrievalFailure::Degrade
+            }
+        }
+    }
+
+    /// Adjusts the policy's choice using the recalled episodes.
+    ///
+    /// The best action that earned a positive reward in a similar situation is
+    /// repeated. Otherwise, if the proposed action was penalized in a similar
+    /// situation, the first available action that wasn't is taken instead.
+    #[cfg(feature = "memory")]
+    fn consult_episodes(&self, proposed: Option<ActionId>) -> Option<ActionId> {
+        if self.recalled.is_empty() {
+            return proposed;
+        }
+        let values = EpisodicMemory::action_values(&self.recalled);
+
+        let best = self
+            .available_actions
+            .iter()
+            .filter_map(|a| values.get(a).map(|v| (a, *v)))
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+        if let Some((action, value)) = best {
+            if value > 0.0 {
+                return Some(action.clone());
+            }
+        }
+
+        let penalized = |a: &ActionId| values.get(a).is_some_and(|v| *v < 0.0);
+        match proposed {
+            Some(action) if penalized(&action) => self
+                .available_actions
+                .iter()
+                .find(|a| !penalized(a))
+                .cloned()
+                .or(Some(action)),
+            other => other,
+        }
+    }
+
+    /// Records the outcome of the most recent decision as an episode.
+    #[cfg(feature = "memory")]
+    fn record_episode(&mut self, outcome: &Outcome) {
+        let Some(situation) = self.last_situation.take() else {
+            return;
+        };
+        let action = self.available_action_id(&outcome.action);
+        let Some(episodic) = self.episodic.as_mut() else {
+            return;
+        };
+        if let Err(e) = episodic.record(&situation, &action, outcome.reward) {
+            log::warn!("Failed to record episode: {}", e);
+            self.stats.memory_failures += 1;
+        }
+    }
+
     fn record_observation(&mut self, observation: Observation) {
         if self.observation_history.len() >= self.config.max_observations {
             self.observation_history.pop_front();
@@ -700,6 +1083,124 @@ impl KaneruAgent {
         &self.learning
     }
 
+    /// Replaces the agent's learning engine, e.g. with one trained offline.
+    pub fn set_learning_engine(&mut self, engine: LearningEngine) {
+        self.learning = engine;
+    }
+
+    /// Attaches a logger that records every experience passed to `learn`.
+    ///
+    /// Returns the previously attached logger, if any.
+    pub fn attach_experience_logger(
+        &mut self,
+        logger: ExperienceLogger,
+    ) -> Option<ExperienceLogger> {
+        self.experience_logger.replace(logger)
+    }
+
+    /// Detaches and returns the experience logger, if any.
+    pub fn detach_experience_logger(&mut self) -> Option<ExperienceLogger> {
+        self.experience_logger.take()
+    }
+
+    /// Sets the preprocessing pipeline for readings of the sensor named `name`.
+    ///
+    /// Readings are cleaned before [`step`](Self::step) updates state, goals
+    /// and learning. Readings rejected as outliers are counted in
+    /// [`AgentStats::outliers_rejected`].
+    pub fn set_sensor_pipeline(&mut self, name: &str, pipeline: SensorPipeline) {
+        self.preprocessing.set_sensor(name, pipeline);
+    }
+
+    /// Whether rejected readings are stepped on as
+    /// [`GLITCH_OBSERVATION`](crate::preprocessing::GLITCH_OBSERVATION) state
+    /// changes instead of the cleaned reading.
+    pub fn set_glitch_observations(&mut self, enabled: bool) {
+        self.preprocessing.set_glitch_observations(enabled);
+    }
+
+    /// Attaches episodic memory, consulted before each decision and updated by
+    /// [`learn`](Self::learn).
+    ///
+    /// Returns the previously attached memory, if any.
+    #[cfg(feature = "memory")]
+    pub fn attach_episodic_memory(&mut self, memory: EpisodicMemory) -> Option<EpisodicMemory> {
+        self.episodic.replace(memory)
+    }
+
+    /// Detaches and returns the episodic memory, if any.
+    #[cfg(feature = "memory")]
+    pub fn detach_episodic_memory(&mut self) -> Option<EpisodicMemory> {
+        self.recalled.clear();
+        self.last_situation = None;
+        self.episodic.take()
+    }
+
+    /// Returns the attached episodic memory, if any.
+    #[cfg(feature = "memory")]
+    pub fn episodic_memory(&self) -> Option<&EpisodicMemory> {
+        self.episodic.as_ref()
+    }
+
+    /// Returns the past episodes recalled for the most recent decision.
+    #[cfg(feature = "memory")]
+    pub fn recalled_episodes(&self) -> &[RecalledEpisode] {
+        &self.recalled
+    }
+
+    /// Sets the safety layer checked on each action [`step`](Self::step)
+    /// selects.
+    ///
+    /// A blocked action is replaced with `Action::noop()`, counted in
+    /// [`AgentStats::actions_vetoed`] or [`AgentStats::actions_rate_limited`],
+    /// and learned from right away with the layer's penalty.
+    pub fn set_safety_layer(&mut self, safety: SafetyLayer) {
+        self.safety = safety;
+    }
+
+    /// Returns the safety layer checked on each selected action.
+    pub fn safety_layer(&self) -> &SafetyLayer {
+        &self.safety
+    }
+
+    /// Sets the parameter schemas checked on each action [`step`](Self::step)
+    /// selects, before the safety layer.
+    ///
+    /// An invalid action is replaced with `Action::noop()`, counted in
+    /// [`AgentStats::actions_invalid`], and learned from like a blocked one.
+    /// The schemas are saved with the agent's state.
+    pub fn set_action_schemas(&mut self, schemas: SchemaRegistry) {
+        self.schemas = schemas;
+    }
+
+    /// Returns the parameter schemas checked on each selected action.
+    pub fn action_schemas(&self) -> &SchemaRegistry {
+        &self.schemas
+    }
+
+    /// Adds a schedule whose observations [`step_scheduled`](Self::step_scheduled)
+    /// steps on when due, replacing any with the same name.
+    ///
+    /// Schedule progress is saved with the agent's state, so a restored agent
+    /// does not fire a slot again. The schedules themselves are not saved and
+    /// are added again after loading.
+    pub fn add_schedule(&mut self, schedule: Schedule, template: ObservationTemplate) {
+        self.scheduler.add(schedule, template);
+    }
+
+    /// Sets the scheduler of the agent, replacing its schedules.
+    ///
+    /// Use a scheduler built with [`Scheduler::with_clock`] to control the
+    /// time schedules see.
+    pub fn set_scheduler(&mut self, scheduler: Scheduler) {
+        self.scheduler = scheduler;
+    }
+
+    /// Returns the scheduler of the agent.
+    pub fn scheduler(&self) -> &Scheduler {
+        &self.scheduler
+    }
+
     /// Returns a reference to the agent's hierarchical goal solver.
     pub fn goal_solver(&self) -> &HierarchicalGoalSolver {
         &self.goal_solver
@@ -771,6 +1272,54 @@ mod tests {
         assert_eq!(agent.active_goal, Some(goal_id));
     }
 
+    #[test]
+    fn test_action_space_replaces_default_actions() {
+        let mut agent = KaneruAgent::with_default_config();
+        let space = vec![
+            Action::new(ActionType::Custom("open".to_string())),
+            Action::new(ActionType::Custom("close".to_string())).with_param("speed", 2i64),
+        ];
+        agent.set_action_space(&space);
+        assert_eq!(agent.action_space().len(), 2);
+
+        for _ in 0..20 {
+            let action = agent.step(Observation::sensor("valve", 1));
+            assert!(space.iter().any(|a| a.action_type == action.action_type));
+            if action.action_type == space[1].action_type {
+                assert!(action.params.contains_key("speed"));
+            }
+        }
+
+        agent.set_action_space(&[]);
+        assert_eq!(agent.available_actions, KaneruAgent::default_actions());
+    }
+
+    #[test]
+    fn test_invalid_actions_replaced_with_noop() {
+        use crate::schema::{ActionSchema, ParamSpec};
+
+        let mut schemas = SchemaRegistry::new();
+        schemas.register(
+            ActionSchema::custom("set_valve").param("position", ParamSpec::float().range(0.0, 1.0)),
+        );
+        let mut agent = KaneruAgent::with_default_config();
+        agent.set_action_schemas(schemas);
+        agent.set_action_space(&[Action::build("set_valve")
+            .param("position", 1.5)
+            .unwrap()
+            .finish()
+            .unwrap()]);
+
+        for _ in 0..5 {
+            assert!(agent.step(Observation::sensor("flow", 1.0)).is_noop());
+        }
+        assert_eq!(agent.get_statistics().actions_invalid, 5);
+
+        let mut restored = KaneruAgent::with_default_config();
+        restored.load_state(agent.save_state());
+        assert_eq!(restored.action_schemas().len(), 1);
+    }
+
     #[test]
     fn test_mode_switching() {
         let mut agent = KaneruAgent::with_default_config();
@@ -844,6 +1393,55 @@ mod tests {
         assert_eq!(new_agent.stats.total_steps, 1);
     }
 
+    #[test]
+    fn test_scheduled_steps_survive_restart() {
+        use crate::coordination::Clock;
+        use crate::types::Timestamp;
+        use crate::MissedSlots;
+        use std::sync::atomic::{AtomicU64, Ordering};
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        #[derive(Default)]
+        struct ManualClock(AtomicU64);
+
+        impl Clock for ManualClock {
+            fn now(&self) -> Timestamp {
+                Timestamp(self.0.load(Ordering::SeqCst))
+            }
+        }
+
+        const HOUR: u64 = 3_600_000_000;
+        let clock = Arc::new(ManualClock::default());
+        let schedule = Schedule::every(Duration::from_secs(3600)).on_missed(MissedSlots::FireOnce);
+        let started = |clock: &Arc<ManualClock>| {
+            let mut agent = KaneruAgent::with_default_config();
+            agent.set_scheduler(Scheduler::with_clock(clock.clone()));
+            agent.add_schedule(schedule.clone(), ObservationTemplate::new("hourly"));
+            agent
+        };
+
+        let mut agent = started(&clock);
+        assert!(agent.step_scheduled().is_none());
+        clock.0.store(HOUR, Ordering::SeqCst);
+        assert!(agent.step_scheduled().is_some());
+        assert!(agent.step_scheduled().is_none());
+        assert_eq!(agent.stats.total_steps, 1);
+        let state = agent.save_state();
+
+        // Restarted within the same hour, the slot does not fire again
+        clock.0.store(HOUR + HOUR / 2, Ordering::SeqCst);
+        let mut restarted = started(&clock);
+        restarted.load_state(state.clone());
+        assert!(restarted.step_scheduled().is_none());
+
+        // A state saved before schedules existed loads without any
+        let mut json = serde_json::to_value(&state).unwrap();
+        json.as_object_mut().unwrap().remove("schedule_state");
+        let old: SerializedState = serde_json::from_value(json).unwrap();
+        assert_eq!(old.schedule_state, ScheduleState::default());
+    }
+
     #[test]
     fn test_multiple_episodes() {
         let mut agent = KaneruAgent::with_default_config();
@@ -914,4 +1512,105 @@ mod tests {
         // Exploration should have higher epsilon
         assert!(epsilon_explore > epsilon_exploit);
     }
+
+    #[test]
+    fn test_preprocessing_before_step() {
+        use crate::preprocessing::{OutlierAction, OutlierFilter};
+
+        let mut agent = KaneruAgent::with_default_config();
+        agent.set_sensor_pipeline(
+            "temperature",
+            SensorPipeline::new()
+                .reject_outliers(OutlierFilter::mad(3.5).with_action(OutlierAction::Drop)),
+        );
+
+        for _ in 0..10 {
+            agent.step(Observation::sensor("temperature", 21.0));
+        }
+        let action = agent.step(Observation::sensor("temperature", 400.0));
+
+        // The dropped spike never reaches the agent's models
+        assert_eq!(action.action_type, Action::noop().action_type);
+        assert_eq!(agent.stats.total_steps, 10);
+        assert_eq!(agent.stats.outliers_rejected, 1);
+        assert!(agent
+            .observation_history()
+            .iter()
+            .all(|obs| obs.value.as_f64() == Some(21.0)));
+    }
+
+    #[cfg(feature = "memory")]
+    #[test]
+    fn test_episodic_memory_repeats_rewarded_action() {
+        use crate::memory::{EpisodicConfig, EpisodicMemory};
+
+        // The scripted environment pays +2 for waiting at ~35 degrees and
+        // penalizes everything else. The agent is shown the rewarded action
+        // once, then meets the same situation again with sensor noise.
+        fn run(agent: &mut KaneruAgent) -> usize {
+            let obs = Observation::sensor("temperature", 35.0);
+            agent.step(obs.clone());
+            let wait = Action::new(ActionType::Wait);
+            let result = ActionResult::success(&wait.id);
+            agent.learn(Outcome::new(wait, result, 2.0, obs, false));
+
+            let mut repeats = 0;
+            for i in 1..=20 {
+                let obs = Observation::sensor("temperature", 35.0 + i as f64 * 0.01);
+                let action = agent.step(obs.clone());
+                let reward = if action.action_type == ActionType::Wait {
+                    repeats += 1;
+                    2.0
+                } else {
+                    -1.0
+                };
+                let result = ActionResult::success(&action.id);
+                agent.learn(Outcome::new(action, result, reward, obs, false));
+            }
+            repeats
+        }
+
+        let mut memoryless = KaneruAgent::with_default_config();
+        let baseline = run(&mut memoryless);
+
+        let mut agent = KaneruAgent::with_default_config();
+        agent.attach_episodic_memory(EpisodicMemory::new(EpisodicConfig::default()));
+        let with_memory = run(&mut agent);
+
+        assert_eq!(with_memory, 20);
+        assert!(with_memory > baseline, "{} vs {}", with_memory, baseline);
+        assert!(!agent.recalled_episodes().is_empty());
+        assert_eq!(
+            agent.recalled_episodes()[0].situation,
+            "sensor temperature 35"
+        );
+        assert_eq!(agent.stats.memory_failures, 0);
+    }
+
+    #[cfg(feature = "memory")]
+    #[test]
+    fn test_episodic_memory_avoids_penalized_action() {
+        use crate::memory::{EpisodicConfig, EpisodicMemory};
+
+        let mut agent = KaneruAgent::with_default_config();
+        agent.set_mode(OperationMode::Exploitation);
+        agent.attach_episodic_memory(EpisodicMemory::new(EpisodicConfig::default()));
+
+        let obs = Observation::sensor("humidity", 80.0);
+        let first = agent.step(obs.clone());
+        let result = ActionResult::success(&first.id);
+        agent.learn(Outcome::new(
+            first.clone(),
+            result,
+            -1.0,
+            obs.clone(),
+            false,
+        ));
+
+        let second = agent.step(obs);
+        assert_ne!(
+            ActionId::from_action(&second),
+            ActionId::from_action(&first)
+        );
+    }
 }
@@ -158,6 +158,9 @@ pub struct Experience {
     pub next_action: Option<ActionId>,
     pub done: bool,
     pub timestamp: Timestamp,
+    /// The per-objective rewards `reward` was scalarized from, if any.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reward_vector: Vec<f64>,
 }
 
 impl Experience {
@@ -177,6 +180,7 @@ impl Experience {
             next_action: None,
             done,
             timestamp: Timestamp::now(),
+            reward_vector: Vec::new(),
         }
     }
 
@@ -185,6 +189,14 @@ impl Experience {
         self.next_action = Some(next_action);
         self
     }
+
+    /// Records the per-objective rewards the scalar reward was computed from,
+    /// so it can be recomputed with
+    /// [`CompositeGoal::rescalarize`](crate::CompositeGoal::rescalarize).
+    pub fn with_reward_vector(mut self, rewards: Vec<f64>) -> Self {
+        self.reward_vector = rewards;
+        self
+    }
 }
 
 /// Configuration for the `LearningEngine`.
@@ -226,10 +238,52 @@ impl Default for LearningConfig {
     }
 }
 
+/// Configuration for [`LearningEngine::train_offline`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfflineTrainingConfig {
+    /// The number of experiences per batch. Targets within a batch are computed
+    /// against the Q-table as it stood at the start of the batch.
+    pub batch_size: usize,
+    /// If set, each epoch samples this many batches with replacement (experience
+    /// replay) instead of sweeping the whole dataset once.
+    pub replay_batches: Option<usize>,
+    /// Seed for shuffling and sampling. Runs with the same seed, data and engine
+    /// state produce identical Q-tables.
+    pub seed: Option<u64>,
+    /// The action set used for bootstrapping. If empty, it is inferred from the
+    /// actions present in the training data.
+    pub actions: Vec<ActionId>,
+}
+
+impl Default for OfflineTrainingConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 32,
+            replay_batches: None,
+            seed: None,
+            actions: Vec::new(),
+        }
+    }
+}
+
+/// Summary of an offline training run.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OfflineTrainingReport {
+    /// The number of experiences in the training set.
+    pub experiences: usize,
+    /// The number of epochs run.
+    pub epochs: usize,
+    /// The number of Q-value updates applied.
+    pub updates: u64,
+    /// The mean absolute TD error over the final epoch.
+    pub final_mean_td_error: f64,
+}
+
 /// The main reinforcement learning engine for Kaneru.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LearningEngine {
     /// The table of learned Q-values for state-action pairs.
+    #[serde(with = "q_table")]
     q_values: HashMap<StateActionPair, QValue>,
     /// The configuration for the learning process.
     config: LearningConfig,
@@ -557,6 +611,170 @@ impl LearningEngine {
     pub fn get_all_q_values(&self) -> &HashMap<StateActionPair, QValue> {
         &self.q_values
     }
+
+    /// Trains the engine from recorded experiences, e.g. a log collected on-device.
+    ///
+    /// Runs `epochs` passes of batched updates using the configured algorithm.
+    /// Terminal experiences (`done`) do not bootstrap from the next state. The
+    /// replay buffer and epsilon are left untouched.
+    pub fn train_offline(
+        &mut self,
+        experiences: impl Iterator<Item = Experience>,
+        epochs: usize,
+        config: &OfflineTrainingConfig,
+    ) -> OfflineTrainingReport {
+        use rand::rngs::StdRng;
+        use rand::seq::SliceRandom;
+        use rand::{Rng, SeedableRng};
+
+        let experiences: Vec<Experience> = experiences.collect();
+        let mut report = OfflineTrainingReport {
+            experiences: experiences.len(),
+            ..Default::default()
+        };
+        if experiences.is_empty() || epochs == 0 {
+            return report;
+        }
+
+        let actions = if config.actions.is_empty() {
+            let mut actions: Vec<ActionId> = Vec::new();
+            for exp in &experiences {
+                if !actions.contains(&exp.action) {
+                    actions.push(exp.action.clone());
+                }
+            }
+            actions
+        } else {
+            config.actions.clone()
+        };
+
+        let mut rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_rng(&mut rand::rng()),
+        };
+        let batch_size = config.batch_size.max(1);
+        let mut order: Vec<usize> = (0..experiences.len()).collect();
+
+        for _ in 0..epochs {
+            let batches: Vec<Vec<usize>> = match config.replay_batches {
+                Some(count) => (0..count)
+                    .map(|_| {
+                        (0..batch_size)
+                            .map(|_| rng.random_range(0..experiences.len()))
+                            .collect()
+                    })
+                    .collect(),
+                None => {
+                    order.shuffle(&mut rng);
+                    order.chunks(batch_size).map(<[usize]>::to_vec).collect()
+                }
+            };
+
+            let mut td_error_sum = 0.0;
+            let mut td_error_count = 0usize;
+            for batch in batches {
+                // Compute all targets before applying any update.
+                let targets: Vec<(usize, f64)> = batch
+                    .into_iter()
+                    .map(|i| {
+                        let exp = &experiences[i];
+                        let current_q = self.get_q_value(&exp.state, &exp.action);
+                        let td_error = self.td_target(exp, &actions) - current_q;
+                        td_error_sum += td_error.abs();
+                        td_error_count += 1;
+                        (i, current_q + self.config.learning_rate * td_error)
+                    })
+                    .collect();
+
+                for (i, new_q) in targets {
+                    let exp = &experiences[i];
+                    self.set_q_value(&exp.state, &exp.action, new_q);
+                    report.updates += 1;
+                }
+            }
+
+            report.epochs += 1;
+            report.final_mean_td_error = if td_error_count == 0 {
+                0.0
+            } else {
+                td_error_sum / td_error_count as f64
+            };
+        }
+
+        report
+    }
+
+    /// Computes the TD target for an experience under the configured algorithm.
+    fn td_target(&self, exp: &Experience, available_actions: &[ActionId]) -> f64 {
+        if exp.done {
+            return exp.reward;
+        }
+
+        let next_value = match self.config.algorithm {
+            LearningAlgorithm::QLearning => {
+                self.get_max_q_value(&exp.next_state, available_actions)
+            }
+            LearningAlgorithm::SARSA => match &exp.next_action {
+                Some(next_action) => self.get_q_value(&exp.next_state, next_action),
+                None => self.get_max_q_value(&exp.next_state, available_actions),
+            },
+            LearningAlgorithm::ExpectedSARSA | LearningAlgorithm::TemporalDifference => {
+                self.get_avg_q_value(&exp.next_state, available_actions)
+            }
+        };
+
+        exp.reward + self.config.discount_factor * next_value
+    }
+}
+
+/// Serializes the Q-table as a list of entries, since formats like JSON only
+/// allow string map keys.
+mod q_table {
+    use super::{QValue, StateActionPair};
+    use serde::de::{MapAccess, SeqAccess, Visitor};
+    use serde::{Deserializer, Serializer};
+    use std::collections::HashMap;
+    use std::fmt;
+
+    pub fn serialize<S: Serializer>(
+        table: &HashMap<StateActionPair, QValue>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(table.iter())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<StateActionPair, QValue>, D::Error> {
+        struct TableVisitor;
+
+        impl<'de> Visitor<'de> for TableVisitor {
+            type Value = HashMap<StateActionPair, QValue>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a list of (state-action, Q-value) entries")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut table = HashMap::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some((pair, qvalue)) = seq.next_element::<(StateActionPair, QValue)>()? {
+                    table.insert(pair, qvalue);
+                }
+                Ok(table)
+            }
+
+            // Older snapshots encoded the table as a map.
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut table = HashMap::with_capacity(map.size_hint().unwrap_or(0));
+                while let Some((pair, qvalue)) = map.next_entry::<StateActionPair, QValue>()? {
+                    table.insert(pair, qvalue);
+                }
+                Ok(table)
+            }
+        }
+
+        deserializer.deserialize_any(TableVisitor)
+    }
 }
 
 #[cfg(test)]
@@ -704,4 +922,139 @@ mod tests {
         let action_id = ActionId::from_action(&action);
         assert!(action_id.as_str().contains("Alert"));
     }
+
+    /// A three-state corridor. Leaving left from `s0` pays 0.9 immediately;
+    /// leaving right from `s2` pays 1.0. With gamma = 0.9 the optimal policy is
+    /// `s0 -> left`, `s1 -> right`, `s2 -> right`.
+    fn corridor_experiences() -> Vec<Experience> {
+        let transitions = [
+            ("s0", "left", 0.9, "end", true),
+            ("s0", "right", 0.0, "s1", false),
+            ("s1", "left", 0.0, "s0", false),
+            ("s1", "right", 0.0, "s2", false),
+            ("s2", "left", 0.0, "s1", false),
+            ("s2", "right", 1.0, "end", true),
+        ];
+        (0..5)
+            .flat_map(|_| transitions.iter())
+            .map(|&(s, a, r, next, done)| {
+                Experience::new(
+                    create_state_id(s),
+                    create_action_id(a),
+                    r,
+                    create_state_id(next),
+                    done,
+                )
+            })
+            .collect()
+    }
+
+    fn corridor_engine() -> LearningEngine {
+        LearningEngine::new(LearningConfig {
+            learning_rate: 0.5,
+            discount_factor: 0.9,
+            ..Default::default()
+        })
+    }
+
+    fn assert_corridor_policy(engine: &LearningEngine) {
+        let actions = vec![create_action_id("left"), create_action_id("right")];
+        for (state, expected) in [("s0", "left"), ("s1", "right"), ("s2", "right")] {
+            let best = engine
+                .get_best_action(&create_state_id(state), &actions)
+                .unwrap();
+            assert_eq!(best.as_str(), expected, "wrong action in {}", state);
+        }
+    }
+
+    #[test]
+    fn test_train_offline_learns_optimal_policy() {
+        let mut engine = corridor_engine();
+        let config = OfflineTrainingConfig {
+            batch_size: 4,
+            seed: Some(42),
+            ..Default::default()
+        };
+
+        let report = engine.train_offline(corridor_experiences().into_iter(), 200, &config);
+
+        assert_eq!(report.experiences, 30);
+        assert_eq!(report.epochs, 200);
+        assert_eq!(report.updates, 30 * 200);
+        assert!(report.final_mean_td_error < 1e-3);
+        assert_corridor_policy(&engine);
+
+        let q = engine.get_q_value(&create_state_id("s1"), &create_action_id("left"));
+        assert!((q - 0.81).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_train_offline_with_replay_sampling() {
+        let mut engine = corridor_engine();
+        let config = OfflineTrainingConfig {
+            batch_size: 8,
+            replay_batches: Some(4),
+            seed: Some(7),
+            ..Default::default()
+        };
+
+        let report = engine.train_offline(corridor_experiences().into_iter(), 300, &config);
+
+        assert_eq!(report.updates, 8 * 4 * 300);
+        assert_corridor_policy(&engine);
+    }
+
+    #[test]
+    fn test_train_offline_is_deterministic_with_seed() {
+        let config = OfflineTrainingConfig {
+            batch_size: 5,
+            replay_batches: Some(3),
+            seed: Some(1234),
+            ..Default::default()
+        };
+
+        let mut a = corridor_engine();
+        let mut b = corridor_engine();
+        // Few epochs, so the tables have not yet converged to the same values.
+        a.train_offline(corridor_experiences().into_iter(), 3, &config);
+        b.train_offline(corridor_experiences().into_iter(), 3, &config);
+
+        assert_eq!(a.state_action_count(), b.state_action_count());
+        for (pair, qv) in a.get_all_q_values() {
+            assert_eq!(qv.mean, b.get_q_value(&pair.state, &pair.action));
+        }
+    }
+
+    #[test]
+    fn test_train_offline_empty_input() {
+        let mut engine = corridor_engine();
+        let report =
+            engine.train_offline(std::iter::empty(), 10, &OfflineTrainingConfig::default());
+
+        assert_eq!(report, OfflineTrainingReport::default());
+        assert_eq!(engine.total_updates(), 0);
+    }
+
+    #[test]
+    fn test_trained_engine_json_round_trip() {
+        let mut engine = corridor_engine();
+        let config = OfflineTrainingConfig {
+            seed: Some(3),
+            ..Default::default()
+        };
+        engine.train_offline(corridor_experiences().into_iter(), 50, &config);
+
+        let json = serde_json::to_vec(&engine).unwrap();
+        let restored: LearningEngine = serde_json::from_slice(&json).unwrap();
+
+        assert_eq!(restored.state_action_count(), engine.state_action_count());
+        assert_corridor_policy(&restored);
+
+        // Tables written before entries were encoded as a list still load.
+        let legacy = serde_json::to_string(&LearningEngine::default_config())
+            .unwrap()
+            .replace("\"q_values\":[]", "\"q_values\":{}");
+        let legacy: LearningEngine = serde_json::from_str(&legacy).unwrap();
+        assert_eq!(legacy.state_action_count(), 0);
+    }
 }
@@ -0,0 +1,343 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Append-only logs of `Experience` tuples.
+//!
+//! Agents running on devices can record every transition they learn from and
+//! ship the log home, where [`LearningEngine::train_offline`] replays it to
+//! produce an improved policy.
+//!
+//! ## Format
+//!
+//! ```text
+//! "KXPL" | version (u8) | record*
+//! record = length (u32, little-endian) | JSON-encoded Experience
+//! ```
+//!
+//! A log that ends in the middle of a record (e.g. power loss during a write)
+//! yields an error for the torn record; every complete record before it is
+//! still readable.
+//!
+//! [`LearningEngine::train_offline`]: super::LearningEngine::train_offline
+
+use super::engine::Experience;
+use crate::persistence::PersistenceError;
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::Path;
+
+/// Magic bytes at the start of every experience log.
+const LOG_MAGIC: &[u8; 4] = b"KXPL";
+/// The current log format version.
+const LOG_VERSION: u8 = 1;
+/// Upper bound on a single record, guarding against corrupt length prefixes.
+const MAX_RECORD_LEN: u32 = 16 * 1024 * 1024;
+
+/// Writes `Experience` tuples to an append-only log.
+pub struct ExperienceLogger {
+    writer: Box<dyn Write + Send>,
+    records_written: u64,
+}
+
+impl ExperienceLogger {
+    /// Starts a new log on `writer`, writing the log header immediately.
+    pub fn new<W: Write + Send + 'static>(writer: W) -> Result<Self, PersistenceError> {
+        let mut writer: Box<dyn Write + Send> = Box::new(writer);
+        writer.write_all(LOG_MAGIC)?;
+        writer.write_all(&[LOG_VERSION])?;
+        Ok(Self {
+            writer,
+            records_written: 0,
+        })
+    }
+
+    /// Opens the log at `path` for appending, creating it if it does not exist.
+    ///
+    /// Returns `PersistenceError::InvalidFormat` if the file exists but is not an
+    /// experience log.
+    pub fn append(path: &Path) -> Result<Self, PersistenceError> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+
+        if file.metadata()?.len() == 0 {
+            return Self::new(BufWriter::new(file));
+        }
+
+        let mut header = [0u8; 5];
+        File::open(path)?.read_exact(&mut header).map_err(|e| {
+            if e.kind() == ErrorKind::UnexpectedEof {
+                PersistenceError::InvalidFormat("experience log header is truncated".to_string())
+            } else {
+                PersistenceError::Io(e)
+            }
+        })?;
+        check_header(&header)?;
+
+        Ok(Self {
+            writer: Box::new(BufWriter::new(file)),
+            records_written: 0,
+        })
+    }
+
+    /// Appends a single experience to the log.
+    pub fn log(&mut self, experience: &Experience) -> Result<(), PersistenceError> {
+        let body = serde_json::to_vec(experience)?;
+        let len = u32::try_from(body.len())
+            .ok()
+            .filter(|len| *len <= MAX_RECORD_LEN)
+            .ok_or_else(|| {
+                PersistenceError::Serialization(format!(
+                    "experience record of {} bytes exceeds the log limit",
+                    body.len()
+                ))
+            })?;
+
+        // Write the prefix and body in one call so a record is never interleaved.
+        let mut record = Vec::with_capacity(4 + body.len());
+        record.extend_from_slice(&len.to_le_bytes());
+        record.extend_from_slice(&body);
+        self.writer.write_all(&record)?;
+
+        self.records_written += 1;
+        Ok(())
+    }
+
+    /// Flushes any buffered records to the underlying writer.
+    pub fn flush(&mut self) -> Result<(), PersistenceError> {
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Returns the number of records written by this logger.
+    pub fn records_written(&self) -> u64 {
+        self.records_written
+    }
+}
+
+impl std::fmt::Debug for ExperienceLogger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExperienceLogger")
+            .field("records_written", &self.records_written)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for ExperienceLogger {
+    fn drop(&mut self) {
+        let _ = self.writer.flush();
+    }
+}
+
+/// Reads `Experience` tuples back from a log written by [`ExperienceLogger`].
+///
+/// Iteration stops at the first error.
+#[derive(Debug)]
+pub struct ExperienceLogReader<R: Read> {
+    reader: R,
+    done: bool,
+}
+
+impl ExperienceLogReader<BufReader<File>> {
+    /// Opens the log at `path` for reading.
+    pub fn open(path: &Path) -> Result<Self, PersistenceError> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> ExperienceLogReader<R> {
+    /// Creates a reader over `reader`, validating the log header.
+    pub fn new(mut reader: R) -> Result<Self, PersistenceError> {
+        let mut header = [0u8; 5];
+        reader.read_exact(&mut header).map_err(|e| {
+            if e.kind() == ErrorKind::UnexpectedEof {
+                PersistenceError::InvalidFormat("experience log header is truncated".to_string())
+            } else {
+                PersistenceError::Io(e)
+            }
+        })?;
+        check_header(&header)?;
+        Ok(Self {
+            reader,
+            done: false,
+        })
+    }
+
+    fn read_record(&mut self) -> Result<Option<Experience>, PersistenceError> {
+        let mut len_bytes = [0u8; 4];
+        let mut filled = 0;
+        while filled < len_bytes.len() {
+            match self.reader.read(&mut len_bytes[filled..]) {
+                Ok(0) if filled == 0 => return Ok(None),
+                Ok(0) => {
+                    return Err(PersistenceError::InvalidFormat(
+                        "experience log ends inside a record length".to_string(),
+                    ))
+                }
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        let len = u32::from_le_bytes(len_bytes);
+        if len > MAX_RECORD_LEN {
+            return Err(PersistenceError::InvalidFormat(format!(
+                "experience record length {} exceeds the log limit",
+                len
+            )));
+        }
+
+        let mut body = vec![0u8; len as usize];
+        self.reader.read_exact(&mut body).map_err(|e| {
+            if e.kind() == ErrorKind::UnexpectedEof {
+                PersistenceError::InvalidFormat("experience log ends inside a record".to_string())
+            } else {
+                PersistenceError::Io(e)
+            }
+        })?;
+
+        serde_json::from_slice(&body)
+            .map(Some)
+            .map_err(|e| PersistenceError::Deserialization(e.to_string()))
+    }
+}
+
+impl<R: Read> Iterator for ExperienceLogReader<R> {
+    type Item = Result<Experience, PersistenceError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.read_record() {
+            Ok(Some(experience)) => Some(Ok(experience)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+fn check_header(header: &[u8; 5]) -> Result<(), PersistenceError> {
+    if &header[..4] != LOG_MAGIC {
+        return Err(PersistenceError::InvalidFormat(
+            "not an experience log".to_string(),
+        ));
+    }
+    if header[4] != LOG_VERSION {
+        return Err(PersistenceError::InvalidFormat(format!(
+            "unsupported experience log version {}",
+            header[4]
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::learning::{ActionId, StateId};
+    use std::io::Cursor;
+    use std::sync::{Arc, Mutex};
+
+    /// A writer whose contents remain inspectable after the logger takes ownership.
+    #[derive(Clone, Default)]
+    struct SharedBuf(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuf {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn experience(i: usize) -> Experience {
+        Experience::new(
+            StateId::from_string(format!("s{}", i)),
+            ActionId::from_string("go".to_string()),
+            i as f64,
+            StateId::from_string(format!("s{}", i + 1)),
+            i == 2,
+        )
+    }
+
+    #[test]
+    fn test_log_round_trip() {
+        let buf = SharedBuf::default();
+        let mut logger = ExperienceLogger::new(buf.clone()).unwrap();
+        for i in 0..3 {
+            logger.log(&experience(i)).unwrap();
+        }
+        assert_eq!(logger.records_written(), 3);
+
+        let bytes = buf.0.lock().unwrap().clone();
+        let read: Vec<Experience> = ExperienceLogReader::new(Cursor::new(bytes))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(read.len(), 3);
+        assert_eq!(read[1].state.as_str(), "s1");
+        assert_eq!(read[1].reward, 1.0);
+        assert!(read[2].done);
+    }
+
+    #[test]
+    fn test_torn_tail_is_reported() {
+        let buf = SharedBuf::default();
+        let mut logger = ExperienceLogger::new(buf.clone()).unwrap();
+        logger.log(&experience(0)).unwrap();
+        logger.log(&experience(1)).unwrap();
+
+        let mut bytes = buf.0.lock().unwrap().clone();
+        bytes.truncate(bytes.len() - 3);
+
+        let mut reader = ExperienceLogReader::new(Cursor::new(bytes)).unwrap();
+        assert!(reader.next().unwrap().is_ok());
+        assert!(matches!(
+            reader.next(),
+            Some(Err(PersistenceError::InvalidFormat(_)))
+        ));
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn test_rejects_foreign_files() {
+        let err = ExperienceLogReader::new(Cursor::new(b"{\"json\": true}".to_vec())).unwrap_err();
+        assert!(matches!(err, PersistenceError::InvalidFormat(_)));
+    }
+
+    #[test]
+    fn test_append_to_existing_file() {
+        let path =
+            std::env::temp_dir().join(format!("kaneru_experience_log_{}.kxpl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        {
+            let mut logger = ExperienceLogger::append(&path).unwrap();
+            logger.log(&experience(0)).unwrap();
+        }
+        {
+            let mut logger = ExperienceLogger::append(&path).unwrap();
+            logger.log(&experience(1)).unwrap();
+            logger.flush().unwrap();
+        }
+
+        let read: Vec<Experience> = ExperienceLogReader::open(&path)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(read.len(), 2);
+        assert_eq!(read[1].state.as_str(), "s1");
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -8,6 +8,7 @@
 //! - SARSA
 //! - Expected SARSA
 //! - Temporal Difference learning
+//! - Offline batch training from recorded experience logs
 //!
 //! ## Overview
 //!
@@ -91,12 +92,61 @@
 //! engine.add_experience(exp);
 //! engine.replay_batch(32, &actions);
 //! ```
+//!
+//! ## Offline Training
+//!
+//! Experiences recorded on a device with an [`ExperienceLogger`] can be replayed
+//! centrally to train a new policy, which is then shipped back through the
+//! persistence layer.
+//!
+//! ```rust
+//! use kaneru::learning::{
+//!     ActionId, Experience, ExperienceLogReader, ExperienceLogger, LearningEngine,
+//!     OfflineTrainingConfig, StateId,
+//! };
+//! use kaneru::AgentPersistence;
+//!
+//! let path = std::env::temp_dir().join("kaneru_doc_experiences.kxpl");
+//! # let _ = std::fs::remove_file(&path);
+//! {
+//!     let mut logger = ExperienceLogger::append(&path).unwrap();
+//!     let exp = Experience::new(
+//!         StateId::from_string("idle".to_string()),
+//!         ActionId::from_string("charge".to_string()),
+//!         1.0,
+//!         StateId::from_string("charged".to_string()),
+//!         true,
+//!     );
+//!     logger.log(&exp).unwrap();
+//! }
+//!
+//! let experiences = ExperienceLogReader::open(&path)
+//!     .unwrap()
+//!     .collect::<Result<Vec<_>, _>>()
+//!     .unwrap();
+//!
+//! let mut engine = LearningEngine::default_config();
+//! let config = OfflineTrainingConfig {
+//!     seed: Some(7),
+//!     ..Default::default()
+//! };
+//! let report = engine.train_offline(experiences.into_iter(), 10, &config);
+//! assert_eq!(report.updates, 10);
+//!
+//! // Ship the trained engine to the device.
+//! let bytes = engine.to_bytes();
+//! let restored = LearningEngine::from_bytes(&bytes).unwrap();
+//! assert_eq!(restored.state_action_count(), 1);
+//! # let _ = std::fs::remove_file(&path);
+//! ```
 
 pub mod engine;
+pub mod experience_log;
 pub mod value_function;
 
 pub use engine::{
-    ActionId, Experience, LearningAlgorithm, LearningConfig, LearningEngine, QValue,
-    StateActionPair, StateId,
+    ActionId, Experience, LearningAlgorithm, LearningConfig, LearningEngine, OfflineTrainingConfig,
+    OfflineTrainingReport, QValue, StateActionPair, StateId,
 };
+pub use experience_log::{ExperienceLogReader, ExperienceLogger};
 pub use value_function::{LinearValueFunction, TabularValueFunction, ValueFunction};
@@ -151,6 +151,7 @@ pub mod action;
 pub mod agent;
 pub mod config;
 pub mod coordination;
+pub mod environment;
 pub mod error;
 pub mod goal;
 pub mod hierarchical;
@@ -162,40 +163,67 @@ pub mod observation;
 pub mod persistence;
 pub mod policy;
 pub mod predictive;
+pub mod preprocessing;
+pub mod safety;
+pub mod schedule;
+pub mod schema;
 pub mod types;
 
 pub use action::{Action, ActionResult, ActionType};
 pub use agent::{Agent, AgentId, AgentState, SimpleAgent};
 pub use config::AgentConfig;
 pub use coordination::{
-    AgentCoordinator, ConsensusResult, CoordinationError, Message, MessageBus, MessageId,
-    MessagePayload, MessagePriority, SharedMemory,
+    AgentCoordinator, ClaimResult, Clock, ConsensusResult, CoordinationError, Message, MessageBus,
+    MessageId, MessagePayload, MessagePriority, Namespace, ScopedKey, SharedEntry, SharedMemory,
+    SharedMemoryEvent, SystemClock, DEFAULT_LEASE_DURATION, LATEST_OBSERVATION_KEY,
+};
+pub use environment::{
+    Environment, EpisodeAgent, EpisodeRunner, GridWorld, LearningCurve, RandomAgent, Thermostat,
+    ThermostatConfig,
 };
 pub use error::{Error, Result};
-pub use goal::{Goal, GoalPriority, GoalStatus, GoalType};
+pub use goal::{
+    CompositeGoal, Goal, GoalPriority, GoalStatus, GoalType, Objective, Scalarization,
+    LEXICOGRAPHIC_BASE,
+};
 pub use hierarchical::{
     default_decomposition_rules, ConflictResolution, ConflictType, DecompositionResult,
     DecompositionRule, DecompositionStrategy, GoalConflict, GoalTree, GoalTypeFilter,
     HierarchicalGoalSolver, ParallelStrategy, SequentialStrategy,
 };
 pub use kaneru_agent::{
-    AgentStats, GoalSelectionStrategy, KaneruAgent, KaneruConfig, OperationMode, Outcome,
-    SerializedState,
+    AgentStats, GoalSelectionStrategy, KaneruAgent, KaneruConfig, ObjectiveStats, OperationMode,
+    Outcome, SerializedState,
 };
 pub use learning::{
-    ActionId, Experience, LearningAlgorithm, LearningConfig, LearningEngine, QValue,
-    StateActionPair, StateId,
+    ActionId, Experience, ExperienceLogReader, ExperienceLogger, LearningAlgorithm, LearningConfig,
+    LearningEngine, OfflineTrainingConfig, OfflineTrainingReport, QValue, StateActionPair, StateId,
 };
+#[cfg(feature = "memory")]
+pub use memory::{EpisodicConfig, EpisodicMemory, MemoryAgent, RecalledEpisode, RetrievalFailure};
 pub use observation::{Observation, ObservationType, Sensor};
 pub use persistence::{
-    AgentPersistence, CheckpointManager, LearningSnapshot, PersistenceError, PersistenceFormat,
-    PersistenceOptions,
+    migrate_state, AgentPersistence, CheckpointManager, LearningSnapshot, PersistenceError,
+    PersistenceFormat, PersistenceOptions, STATE_VERSION,
 };
 pub use policy::{Condition, Policy, PolicyEngine, Rule};
 pub use predictive::{
     AnomalyDetector, PredictedState, PredictiveConfig, PredictiveModel, StateEncoder,
     StateSnapshot, Trajectory, TransitionModel,
 };
+pub use preprocessing::{
+    ObservationPipeline, OutlierAction, OutlierFilter, OutlierMethod, SensorPipeline, Smoothing,
+    GLITCH_OBSERVATION,
+};
+pub use safety::{ActionLimits, SafetyLayer, Violation, DEFAULT_VETO_PENALTY};
+pub use schedule::{
+    MissedSlots, ObservationTemplate, Schedule, ScheduleState, Scheduler, CATCH_UP_KEY,
+    SCHEDULED_FOR_KEY,
+};
+pub use schema::{
+    ActionBuilder, ActionSchema, ParamSpec, ParamType, PolicySchemaError, SchemaError,
+    SchemaRegistry, SchemaViolation, UnknownActionPolicy,
+};
 pub use types::*;
 
 /// Kaneru framework version
@@ -4,14 +4,20 @@
 //! Memory integration for Kaneru.
 //!
 //! This module provides a `MemoryAgent`, a wrapper that integrates the `ineru`
-//! system with a `SimpleAgent` to give it memory capabilities.
+//! system with a `SimpleAgent` to give it memory capabilities, and
+//! `EpisodicMemory`, which lets a `KaneruAgent` recall how similar past
+//! situations turned out before it decides.
 
 use crate::action::{Action, ActionResult};
 use crate::agent::{Agent, AgentId, AgentState, SimpleAgent};
 use crate::config::AgentConfig;
 use crate::error::Result;
-use crate::observation::Observation;
-use ineru::{IneruMemory, MemoryConfig, MemoryEntry, MemoryQuery};
+use crate::learning::ActionId;
+use crate::observation::{Observation, ObservationType};
+use ineru::{Embedding, IneruMemory, MemoryConfig, MemoryEntry, MemoryQuery};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
 
 /// An agent wrapper that adds memory capabilities using `IneruMemory`.
 ///
@@ -191,6 +197,195 @@ impl Agent for MemoryAgent {
     }
 }
 
+/// What a `KaneruAgent` does when episodic retrieval fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RetrievalFailure {
+    /// Skip the decision: `step` returns a no-op action.
+    Block,
+    /// Decide without recalled episodes.
+    #[default]
+    Degrade,
+}
+
+/// Configuration for episodic retrieval in a `KaneruAgent`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EpisodicConfig {
+    /// The number of most similar past episodes recalled before each decision.
+    pub k: usize,
+    /// The minimum cosine similarity (0.0 to 1.0) between two situations for a
+    /// past episode to be recalled.
+    pub similarity_threshold: f32,
+    /// Numeric readings are rounded to a multiple of this step when describing a
+    /// situation, so nearby readings count as the same situation.
+    pub value_resolution: f64,
+    /// What to do when retrieval fails.
+    pub on_failure: RetrievalFailure,
+}
+
+impl Default for EpisodicConfig {
+    fn default() -> Self {
+        Self {
+            k: 5,
+            similarity_threshold: 0.9,
+            value_resolution: 1.0,
+            on_failure: RetrievalFailure::Degrade,
+        }
+    }
+}
+
+/// A past (situation, action, outcome) tuple recalled for the current decision.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecalledEpisode {
+    /// The situation the action was taken in, e.g. `"sensor temperature 35"`.
+    pub situation: String,
+    /// The action that was taken.
+    pub action: ActionId,
+    /// The reward the action earned.
+    pub reward: f64,
+    /// The similarity between that situation and the current one.
+    pub similarity: f32,
+}
+
+impl fmt::Display for RecalledEpisode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "last time {}, {} earned {:+}",
+            self.situation,
+            self.action.as_str(),
+            self.reward
+        )
+    }
+}
+
+/// Episodic memory for a `KaneruAgent`, backed by `IneruMemory`.
+///
+/// After each `learn`, the agent records the situation it acted in, the action
+/// and the reward. Before each decision it recalls the `k` most similar past
+/// episodes; an action that earned a positive reward in a similar situation is
+/// repeated, and actions that were penalized there are avoided.
+pub struct EpisodicMemory {
+    memory: IneruMemory,
+    config: EpisodicConfig,
+}
+
+impl EpisodicMemory {
+    /// Creates episodic memory backed by an IoT-optimized `IneruMemory`.
+    pub fn new(config: EpisodicConfig) -> Self {
+        Self::with_memory(IneruMemory::iot_mode(), config)
+    }
+
+    /// Creates episodic memory backed by the given `IneruMemory`.
+    pub fn with_memory(memory: IneruMemory, config: EpisodicConfig) -> Self {
+        Self { memory, config }
+    }
+
+    /// Returns the retrieval configuration.
+    pub fn config(&self) -> &EpisodicConfig {
+        &self.config
+    }
+
+    /// Returns a reference to the underlying `IneruMemory`.
+    pub fn memory(&self) -> &IneruMemory {
+        &self.memory
+    }
+
+    /// Returns a mutable reference to the underlying `IneruMemory`.
+    pub fn memory_mut(&mut self) -> &mut IneruMemory {
+        &mut self.memory
+    }
+
+    /// Describes an observation as situation text, e.g. `"sensor temperature 35"`.
+    pub fn situation(&self, observation: &Observation) -> String {
+        let (kind, name) = match &observation.obs_type {
+            ObservationType::Sensor(n) => ("sensor", n),
+            ObservationType::Network(n) => ("network", n),
+            ObservationType::UserInput(n) => ("input", n),
+            ObservationType::StateChange(n) => ("state", n),
+            ObservationType::Timer(n) => ("timer", n),
+            ObservationType::Scheduled(n) => ("scheduled", n),
+            ObservationType::Alert(n) => ("alert", n),
+            ObservationType::Custom(n) => ("custom", n),
+        };
+        let value = match observation.value.as_f64() {
+            Some(v) if self.config.value_resolution > 0.0 => {
+                let step = self.config.value_resolution;
+                // `+ 0.0` turns -0 into 0 so both round to the same text
+                ((v / step).round() * step + 0.0).to_string()
+            }
+            _ => observation.value.as_string(),
+        };
+        format!("{} {} {}", kind, name, value)
+    }
+
+    /// Recalls the `k` past episodes most similar to `situation`, most similar first.
+    pub fn recall(&self, situation: &str) -> Result<Vec<RecalledEpisode>> {
+        let embedding = Embedding::from_text_simple(situation);
+        let query = MemoryQuery::tags(&["episode"]).with_embedding(embedding.clone());
+
+        let mut episodes: Vec<RecalledEpisode> = self
+            .memory
+            .recall(&query)?
+            .into_iter()
+            .filter_map(|result| {
+                let data = result.entry.data;
+                let past = data.get("situation")?.as_str()?.to_string();
+                let similarity = embedding.cosine_similarity(&Embedding::from_text_simple(&past));
+                if similarity < self.config.similarity_threshold {
+                    return None;
+                }
+                Some(RecalledEpisode {
+                    situation: past,
+                    action: serde_json::from_value(data.get("action")?.clone()).ok()?,
+                    reward: data.get("reward")?.as_f64()?,
+                    similarity,
+                })
+            })
+            .collect();
+        episodes.sort_by(|a, b| {
+            b.similarity
+                .partial_cmp(&a.similarity)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        episodes.truncate(self.config.k);
+        Ok(episodes)
+    }
+
+    /// Records that `action` earned `reward` in `situation`.
+    pub fn record(&mut self, situation: &str, action: &ActionId, reward: f64) -> Result<()> {
+        let entry = MemoryEntry::new(
+            "episode",
+            serde_json::json!({
+                "situation": situation,
+                "action": action,
+                "reward": reward,
+            }),
+        )
+        .with_tags(&["episode"])
+        .with_embedding(Embedding::from_text_simple(situation))
+        .with_importance((0.5 + reward.abs() as f32 * 0.1).min(1.0));
+
+        self.memory.remember(entry)?;
+        Ok(())
+    }
+
+    /// Returns the similarity-weighted mean reward of each recalled action.
+    pub fn action_values(episodes: &[RecalledEpisode]) -> HashMap<ActionId, f64> {
+        let mut totals: HashMap<ActionId, (f64, f64)> = HashMap::new();
+        for episode in episodes {
+            let weight = f64::from(episode.similarity.max(0.0));
+            let total = totals.entry(episode.action.clone()).or_default();
+            total.0 += episode.reward * weight;
+            total.1 += weight;
+        }
+        totals
+            .into_iter()
+            .filter(|(_, (_, weight))| *weight > 0.0)
+            .map(|(action, (sum, weight))| (action, sum / weight))
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -210,4 +405,48 @@ mod tests {
         agent.remember_observation(&obs).unwrap();
         assert_eq!(agent.memory_stats().stm_count, 1);
     }
+
+    #[test]
+    fn test_situation_rounds_readings() {
+        let memory = EpisodicMemory::new(EpisodicConfig::default());
+        assert_eq!(
+            memory.situation(&Observation::sensor("temp", 35.2)),
+            "sensor temp 35"
+        );
+        assert_eq!(
+            memory.situation(&Observation::sensor("temp", -0.3)),
+            "sensor temp 0"
+        );
+    }
+
+    #[test]
+    fn test_episodic_recall_by_similarity() {
+        let mut memory = EpisodicMemory::new(EpisodicConfig::default());
+        let wait = ActionId::from_string("Wait".to_string());
+        let alert = ActionId::from_string("Alert".to_string());
+        memory.record("sensor temp 35", &wait, 2.0).unwrap();
+        memory.record("sensor temp 35", &alert, -1.0).unwrap();
+        memory.record("sensor temp 35", &wait, 1.0).unwrap();
+        memory.record("sensor humidity 80", &wait, 5.0).unwrap();
+
+        let episodes = memory.recall("sensor temp 35").unwrap();
+        assert_eq!(episodes.len(), 3);
+        assert!(episodes.iter().all(|e| e.situation == "sensor temp 35"));
+        assert!(memory.recall("sensor pressure 1013").unwrap().is_empty());
+
+        let values = EpisodicMemory::action_values(&episodes);
+        assert!((values[&wait] - 1.5).abs() < 1e-6);
+        assert!((values[&alert] + 1.0).abs() < 1e-6);
+
+        let episode = RecalledEpisode {
+            situation: "sensor temp 35".into(),
+            action: wait,
+            reward: 2.0,
+            similarity: 1.0,
+        };
+        assert_eq!(
+            episode.to_string(),
+            "last time sensor temp 35, Wait earned +2"
+        );
+    }
 }
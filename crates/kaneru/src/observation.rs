@@ -35,6 +35,8 @@ pub enum ObservationType {
     StateChange(String),
     /// An event triggered by a timer or schedule.
     Timer(String),
+    /// A slot of an agent's own schedule fell due; see [`crate::schedule`].
+    Scheduled(String),
     /// An alert or error condition.
     Alert(String),
     /// A user-defined custom observation type.
@@ -286,6 +288,26 @@ impl Observation {
         )
     }
 
+    /// Creates a new `Scheduled` observation, as fired by a
+    /// [`Scheduler`](crate::schedule::Scheduler).
+    ///
+    /// # Arguments
+    ///
+    /// * `schedule_name` - The name of the schedule
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use kaneru::Observation;
+    /// let obs = Observation::scheduled("battery_report");
+    /// ```
+    pub fn scheduled(schedule_name: &str) -> Self {
+        Self::new(
+            ObservationType::Scheduled(schedule_name.to_string()),
+            Value::String(schedule_name.to_string()),
+        )
+    }
+
     /// Sets the confidence score for the observation.
     ///
     /// Confidence indicates the reliability or certainty of the observation,
@@ -729,6 +751,7 @@ mod tests {
             ObservationType::UserInput("u".into()),
             ObservationType::StateChange("sc".into()),
             ObservationType::Timer("t".into()),
+            ObservationType::Scheduled("sch".into()),
             ObservationType::Alert("a".into()),
             ObservationType::Custom("c".into()),
         ];
@@ -782,6 +805,12 @@ mod tests {
         assert!(matches!(obs.obs_type, ObservationType::Timer(_)));
     }
 
+    #[test]
+    fn test_observation_scheduled() {
+        let obs = Observation::scheduled("battery_report");
+        assert!(matches!(obs.obs_type, ObservationType::Scheduled(_)));
+    }
+
     #[test]
     fn test_observation_with_confidence() {
         let obs = Observation::sensor("noisy_sensor", 42.0).with_confidence(0.7);
@@ -25,9 +25,19 @@
 //! // Later, load from the file
 //! let loaded_agent = KaneruAgent::load_from_file(Path::new("agent_state.json")).unwrap();
 //! ```
+//!
+//! ## Versioning
+//!
+//! Agent states carry the [`STATE_VERSION`] they were written with. Loading an
+//! older state runs it through the registered migrations, one version at a
+//! time, before deserializing it; a state newer than this build is rejected
+//! with [`PersistenceError::UnsupportedVersion`]. States written before
+//! versioning have no `version` field and are read as version 1.
 
+use crate::kaneru_agent::SerializedState;
 use crate::{KaneruAgent, LearningConfig, LearningEngine};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::fs;
 use std::io::{Read, Write};
 use std::path::Path;
@@ -45,6 +55,21 @@ pub enum PersistenceError {
     InvalidFormat(String),
     /// An error occurred during compression or decompression.
     Compression(String),
+    /// The state was written by a newer build, with a version this build
+    /// cannot read.
+    UnsupportedVersion {
+        /// The version of the state.
+        found: u32,
+        /// The newest version this build reads.
+        supported: u32,
+    },
+    /// A migration failed to upgrade the state from version `from`.
+    Migration {
+        /// The version the migration started from.
+        from: u32,
+        /// Why the migration failed.
+        reason: String,
+    },
 }
 
 impl std::fmt::Display for PersistenceError {
@@ -55,6 +80,14 @@ impl std::fmt::Display for PersistenceError {
             PersistenceError::Deserialization(e) => write!(f, "Deserialization error: {}", e),
             PersistenceError::InvalidFormat(e) => write!(f, "Invalid format: {}", e),
             PersistenceError::Compression(e) => write!(f, "Compression error: {}", e),
+            PersistenceError::UnsupportedVersion { found, supported } => write!(
+                f,
+                "State is newer than this build: version {} (this build reads up to version {})",
+                found, supported
+            ),
+            PersistenceError::Migration { from, reason } => {
+                write!(f, "Migration from version {} failed: {}", from, reason)
+            }
         }
     }
 }
@@ -223,8 +256,7 @@ impl AgentPersistence for KaneruAgent {
         let mut bytes = Vec::new();
         file.read_to_end(&mut bytes)?;
 
-        let state: crate::kaneru_agent::SerializedState =
-            deserialize_with_options(&bytes, options)?;
+        let state = deserialize_state(&bytes, options)?;
 
         let mut agent = KaneruAgent::new(state.config.clone());
         agent.load_state(state);
@@ -254,7 +286,7 @@ impl AgentPersistence for KaneruAgent {
         bytes: &[u8],
         options: &PersistenceOptions,
     ) -> Result<Self, PersistenceError> {
-        let state: crate::kaneru_agent::SerializedState = deserialize_with_options(bytes, options)?;
+        let state = deserialize_state(bytes, options)?;
 
         let mut agent = KaneruAgent::new(state.config.clone());
         agent.load_state(state);
@@ -343,7 +375,21 @@ impl From<&LearningEngine> for LearningSnapshot {
         Self {
             config: engine.config().clone(),
             total_updates: engine.total_updates(),
-            q_values: Vec::new(), // Would need to export from engine
+            q_values: {
+                let mut q_values: Vec<(String, String, f64)> = engine
+                    .get_all_q_values()
+                    .iter()
+                    .map(|(pair, qv)| {
+                        (
+                            pair.state.as_str().to_string(),
+                            pair.action.as_str().to_string(),
+                            qv.mean,
+                        )
+                    })
+                    .collect();
+                q_values.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+                q_values
+            },
             episode_count: engine.total_episodes(),
         }
     }
@@ -398,6 +444,157 @@ fn deserialize_with_options<T: for<'de> Deserialize<'de>>(
     }
 }
 
+/// Deserializes an agent state, migrating it to [`STATE_VERSION`] first.
+fn deserialize_state(
+    bytes: &[u8],
+    options: &PersistenceOptions,
+) -> Result<SerializedState, PersistenceError> {
+    let state: Value = deserialize_with_options(bytes, options)?;
+    serde_json::from_value(migrate_state(state)?)
+        .map_err(|e| PersistenceError::Deserialization(e.to_string()))
+}
+
+// State migrations
+
+/// The version of the agent state written by this build.
+pub const STATE_VERSION: u32 = 1 + MIGRATIONS.len() as u32;
+
+/// The version of states written before states were versioned.
+const UNVERSIONED_STATE_VERSION: u32 = 1;
+
+/// A migration upgrading a serialized agent state by one version.
+type Migration = fn(Value) -> Result<Value, String>;
+
+/// The migrations of serialized agent states, in order: `MIGRATIONS[i]`
+/// upgrades version `i + 1` to version `i + 2`.
+///
+/// Append a migration, with a test and a fixture in `tests/fixtures`, whenever
+/// `SerializedState` changes in a way `#[serde(default)]` cannot absorb.
+const MIGRATIONS: &[Migration] = &[q_table_as_entries, explicit_extension_fields];
+
+/// Upgrades a serialized agent state to [`STATE_VERSION`].
+///
+/// A state without a `version` field is read as version 1. Each registered
+/// migration from the state's version on is applied in turn, and the
+/// `version` field is updated after each.
+///
+/// # Errors
+///
+/// Returns [`PersistenceError::UnsupportedVersion`] if the state is newer
+/// than this build, and [`PersistenceError::Migration`] if a migration fails.
+pub fn migrate_state(mut state: Value) -> Result<Value, PersistenceError> {
+    let version = state_version(&state)?;
+    if version > STATE_VERSION {
+        return Err(PersistenceError::UnsupportedVersion {
+            found: version,
+            supported: STATE_VERSION,
+        });
+    }
+
+    for (from, migration) in (version..).zip(&MIGRATIONS[(version - 1) as usize..]) {
+        state = migration(state).map_err(|reason| PersistenceError::Migration { from, reason })?;
+        match state.as_object_mut() {
+            Some(fields) => fields.insert("version".to_string(), Value::from(from + 1)),
+            None => {
+                return Err(PersistenceError::Migration {
+                    from,
+                    reason: "migration did not return an object".to_string(),
+                })
+            }
+        };
+        log::debug!("Migrated agent state from version {} to {}", from, from + 1);
+    }
+    Ok(state)
+}
+
+/// The version of a serialized agent state.
+fn state_version(state: &Value) -> Result<u32, PersistenceError> {
+    let fields = state.as_object().ok_or_else(|| {
+        PersistenceError::InvalidFormat("agent state is not a JSON object".to_string())
+    })?;
+    match fields.get("version") {
+        None => Ok(UNVERSIONED_STATE_VERSION),
+        Some(version) => version
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .filter(|&v| v >= 1)
+            .ok_or_else(|| {
+                PersistenceError::InvalidFormat(format!("invalid state version {}", version))
+            }),
+    }
+}
+
+/// Version 1 to 2: the learning engine's Q-table is a list of
+/// `(state-action, Q-value)` entries rather than a map.
+///
+/// JSON maps only have string keys, so a version 1 engine could only be
+/// saved with an empty Q-table; an engine that failed to save left
+/// `learning_state` empty, and stays so.
+fn q_table_as_entries(mut state: Value) -> Result<Value, String> {
+    let Some(learning_state) = state.get_mut("learning_state") else {
+        return Ok(state);
+    };
+    let bytes: Vec<u8> = serde_json::from_value(learning_state.take())
+        .map_err(|e| format!("learning_state is not a byte array: {}", e))?;
+    if bytes.is_empty() {
+        log::warn!("Agent state has no learning state; its Q-values were not saved");
+        *learning_state = Value::Array(Vec::new());
+        return Ok(state);
+    }
+
+    let mut engine: Value = serde_json::from_slice(&bytes)
+        .map_err(|e| format!("learning_state is not a learning engine: {}", e))?;
+    if let Some(q_values) = engine.get_mut("q_values") {
+        match q_values {
+            Value::Object(table) if table.is_empty() => *q_values = Value::Array(Vec::new()),
+            Value::Object(_) => return Err("Q-table map has non-empty string keys".to_string()),
+            _ => {}
+        }
+    }
+    let bytes = serde_json::to_vec(&engine).map_err(|e| e.to_string())?;
+    *learning_state = Value::from(bytes);
+    Ok(state)
+}
+
+/// Version 2 to 3: the statistics, configuration and goal fields added since
+/// version 2 are written out, with the values they default to.
+fn explicit_extension_fields(mut state: Value) -> Result<Value, String> {
+    fn object<'a>(
+        value: &'a mut Value,
+        field: &str,
+    ) -> Result<&'a mut serde_json::Map<String, Value>, String> {
+        value
+            .get_mut(field)
+            .and_then(Value::as_object_mut)
+            .ok_or_else(|| format!("missing or invalid `{}`", field))
+    }
+
+    let stats = object(&mut state, "stats")?;
+    for counter in [
+        "outliers_rejected",
+        "memory_failures",
+        "actions_vetoed",
+        "actions_rate_limited",
+        "actions_invalid",
+    ] {
+        stats.entry(counter).or_insert(Value::from(0u64));
+    }
+    stats
+        .entry("objectives")
+        .or_insert(Value::Array(Vec::new()));
+
+    object(&mut state, "config")?
+        .entry("scalarization")
+        .or_insert(Value::from("WeightedSum"));
+
+    let fields = state.as_object_mut().ok_or("state is not an object")?;
+    fields.entry("composite_goal").or_insert(Value::Null);
+    fields
+        .entry("action_schemas")
+        .or_insert(serde_json::json!({"schemas": [], "unknown_actions": "Warn"}));
+    Ok(state)
+}
+
 // Simple compression/decompression (in production, use a real compression library)
 fn compress_bytes(bytes: &[u8]) -> Result<Vec<u8>, PersistenceError> {
     // Placeholder: in production, use flate2, zstd, or similar
@@ -690,6 +887,91 @@ mod tests {
         );
     }
 
+    fn engine_bytes(engine: Value) -> Value {
+        Value::from(serde_json::to_vec(&engine).unwrap())
+    }
+
+    fn learning_state(state: &Value) -> Value {
+        let bytes: Vec<u8> = serde_json::from_value(state["learning_state"].clone()).unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[test]
+    fn test_migrations_cover_every_version() {
+        assert_eq!(STATE_VERSION as usize, MIGRATIONS.len() + 1);
+        assert_eq!(
+            KaneruAgent::with_default_config().save_state().version,
+            STATE_VERSION
+        );
+    }
+
+    #[test]
+    fn test_q_table_as_entries() {
+        let engine = serde_json::json!({"q_values": {}, "total_updates": 0});
+        let state = serde_json::json!({"learning_state": engine_bytes(engine)});
+        let migrated = q_table_as_entries(state).unwrap();
+        assert_eq!(
+            learning_state(&migrated),
+            serde_json::json!({"q_values": [], "total_updates": 0})
+        );
+
+        // Entries and unsaved engines are left as they are.
+        let engine = serde_json::json!({"q_values": [[{"state": "s", "action": "a"}, {}]]});
+        let state = serde_json::json!({"learning_state": engine_bytes(engine.clone())});
+        assert_eq!(learning_state(&q_table_as_entries(state).unwrap()), engine);
+        let state = serde_json::json!({"learning_state": []});
+        assert_eq!(q_table_as_entries(state.clone()).unwrap(), state);
+
+        let state = serde_json::json!({"learning_state": "not bytes"});
+        assert!(q_table_as_entries(state).is_err());
+    }
+
+    #[test]
+    fn test_explicit_extension_fields() {
+        let state = serde_json::json!({
+            "config": {"mode": "Adaptive"},
+            "stats": {"total_steps": 4, "memory_failures": 2},
+        });
+        let migrated = explicit_extension_fields(state).unwrap();
+
+        assert_eq!(migrated["stats"]["total_steps"], 4);
+        assert_eq!(migrated["stats"]["memory_failures"], 2);
+        assert_eq!(migrated["stats"]["actions_invalid"], 0);
+        assert_eq!(migrated["stats"]["objectives"], serde_json::json!([]));
+        assert_eq!(migrated["config"]["scalarization"], "WeightedSum");
+        assert_eq!(migrated["composite_goal"], Value::Null);
+        let schemas: crate::SchemaRegistry =
+            serde_json::from_value(migrated["action_schemas"].clone()).unwrap();
+        assert!(schemas.is_empty());
+
+        assert!(explicit_extension_fields(serde_json::json!({"config": {}})).is_err());
+    }
+
+    #[test]
+    fn test_migrate_state_versions() {
+        let state = serde_json::to_value(KaneruAgent::with_default_config().save_state()).unwrap();
+        assert_eq!(migrate_state(state.clone()).unwrap(), state);
+
+        let mut unversioned = state.clone();
+        unversioned.as_object_mut().unwrap().remove("version");
+        let migrated = migrate_state(unversioned).unwrap();
+        assert_eq!(migrated["version"], STATE_VERSION);
+
+        let mut newer = state.clone();
+        newer["version"] = Value::from(STATE_VERSION + 1);
+        assert!(matches!(
+            migrate_state(newer),
+            Err(PersistenceError::UnsupportedVersion { .. })
+        ));
+
+        let mut invalid = state;
+        invalid["version"] = Value::from(0);
+        assert!(matches!(
+            migrate_state(invalid),
+            Err(PersistenceError::InvalidFormat(_))
+        ));
+    }
+
     #[test]
     fn test_persistence_error_handling() {
         let invalid_path = PathBuf::from("/invalid/path/that/does/not/exist/agent.json");
@@ -7,6 +7,7 @@
 
 use crate::action::Action;
 use crate::observation::Observation;
+use crate::schema::{PolicySchemaError, SchemaRegistry};
 use crate::types::{Priority, Timestamp, Value, ValueRange};
 use serde::{Deserialize, Serialize};
 
@@ -158,6 +159,7 @@ impl Condition {
         match &obs.obs_type {
             crate::observation::ObservationType::Sensor(name) => name == source,
             crate::observation::ObservationType::StateChange(name) => name == source,
+            crate::observation::ObservationType::Scheduled(name) => name == source,
             _ => false,
         }
     }
@@ -377,6 +379,37 @@ impl PolicyEngine {
     pub fn all_rules(&self) -> Vec<&Rule> {
         self.policies.iter().flat_map(|p| &p.rules).collect()
     }
+
+    /// Checks the action of every rule, and every policy's default action,
+    /// against `schemas`.
+    ///
+    /// Call after loading policies, so a rule emitting an invalid action
+    /// fails then rather than when it first fires.
+    ///
+    /// # Errors
+    ///
+    /// Returns every invalid rule with its violations. A default action is
+    /// reported under the rule name `default`.
+    pub fn validate_against(&self, schemas: &SchemaRegistry) -> Result<(), PolicySchemaError> {
+        let mut rules = Vec::new();
+        for policy in &self.policies {
+            let actions = policy
+                .rules
+                .iter()
+                .map(|rule| (rule.name.as_str(), &rule.action))
+                .chain([("default", &policy.default_action)]);
+            for (rule, action) in actions {
+                if let Err(error) = schemas.validate(action) {
+                    rules.push((policy.name.clone(), rule.to_string(), error));
+                }
+            }
+        }
+        if rules.is_empty() {
+            Ok(())
+        } else {
+            Err(PolicySchemaError { rules })
+        }
+    }
 }
 
 impl Default for PolicyEngine {
@@ -820,4 +853,38 @@ mod tests {
 
         assert_eq!(engine.all_rules().len(), 3);
     }
+
+    #[test]
+    fn test_validate_loaded_policy_against_schemas() {
+        use crate::schema::{ActionSchema, ParamSpec, SchemaViolation};
+
+        let mut policy = Policy::new("climate");
+        let action = Action::build("set_thermostat")
+            .param("temprature", 21.0)
+            .unwrap()
+            .finish()
+            .unwrap();
+        policy.add_rule(Rule::new("cool", Condition::above("temp", 30.0), action));
+        let json = serde_json::to_string(&policy).unwrap();
+
+        let mut engine = PolicyEngine::new();
+        engine.add_policy(serde_json::from_str(&json).unwrap());
+
+        let mut schemas = SchemaRegistry::new();
+        assert!(engine.validate_against(&schemas).is_ok());
+
+        schemas.register(ActionSchema::custom("set_thermostat").param(
+            "temperature",
+            ParamSpec::float().required().range(5.0, 30.0),
+        ));
+        let err = engine.validate_against(&schemas).unwrap_err();
+        assert_eq!(err.rules.len(), 1);
+        let (policy, rule, error) = &err.rules[0];
+        assert_eq!((policy.as_str(), rule.as_str()), ("climate", "cool"));
+        assert!(error.violations.iter().any(|v| matches!(
+            v,
+            SchemaViolation::UnknownParam { suggestion: Some(s), .. } if s == "temperature"
+        )));
+        assert!(err.to_string().contains("did you mean 'temperature'"));
+    }
 }
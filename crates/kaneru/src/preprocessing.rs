@@ -0,0 +1,641 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Observation preprocessing for Kaneru.
+//!
+//! Raw sensor readings are often noisy, and a single glitched value (a 400°C
+//! spike from a loose wire) is enough to trigger an alert or pollute learning.
+//! An [`ObservationPipeline`] cleans `Sensor` observations before an agent's
+//! rules or learning engine see them. Each sensor name gets its own
+//! [`SensorPipeline`], a sequence of stages applied in order:
+//!
+//! - **Outlier rejection** ([`OutlierFilter`]): z-score or MAD test against a
+//!   window of recent readings; a rejected reading is replaced with the last
+//!   accepted one or dropped.
+//! - **Smoothing** ([`Smoothing`]): median or exponential moving average.
+//! - **Normalization** ([`Normalization`]): min-max scaling of a declared
+//!   range into `[0, 1]`.
+//!
+//! When glitch observations are enabled, a rejected reading is surfaced as a
+//! [`GLITCH_OBSERVATION`] state change so that a rule can still alert on a
+//! misbehaving sensor.
+//!
+//! # Examples
+//!
+//! ```
+//! # use kaneru::Observation;
+//! # use kaneru::preprocessing::{ObservationPipeline, OutlierFilter, SensorPipeline, Smoothing};
+//! let mut pipeline = ObservationPipeline::new();
+//! pipeline.set_sensor(
+//!     "temperature",
+//!     SensorPipeline::new()
+//!         .reject_outliers(OutlierFilter::mad(3.5))
+//!         .smooth(Smoothing::Ema { alpha: 0.5 }),
+//! );
+//!
+//! for _ in 0..10 {
+//!     pipeline.process(Observation::sensor("temperature", 21.0));
+//! }
+//! let spike = pipeline.process(Observation::sensor("temperature", 400.0));
+//! assert!(spike.outlier);
+//! assert_eq!(spike.observation.unwrap().value.as_f64(), Some(21.0));
+//! ```
+
+use crate::observation::{Observation, ObservationType};
+use crate::types::Value;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+/// State-change name of the observations surfaced for rejected readings.
+///
+/// The observation's value is the sensor name and its `raw_value` metadata
+/// holds the rejected reading, so `Condition::equals(GLITCH_OBSERVATION,
+/// "temperature")` matches glitches of the `temperature` sensor.
+pub const GLITCH_OBSERVATION: &str = "sensor_glitch";
+
+/// Fewest accepted readings needed before outliers are tested for.
+const MIN_OUTLIER_SAMPLES: usize = 3;
+
+/// Scale factor making the MAD a consistent estimator of the standard deviation.
+const MAD_SCALE: f64 = 0.6745;
+
+/// The statistical test used to detect outliers.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum OutlierMethod {
+    /// Distance from the window mean in standard deviations.
+    ZScore,
+    /// Modified z-score: distance from the window median in scaled median
+    /// absolute deviations. Robust to earlier outliers in the window.
+    Mad,
+}
+
+/// What to do with a reading rejected as an outlier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum OutlierAction {
+    /// Pass on the last accepted reading instead.
+    #[default]
+    ReplaceWithLast,
+    /// Discard the observation.
+    Drop,
+}
+
+/// Rejects readings that deviate too far from a window of recent readings.
+///
+/// Rejected readings are not added to the window. So that a genuine level
+/// shift is not rejected forever, the reading after `max_consecutive`
+/// consecutive rejections is accepted and restarts the window.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutlierFilter {
+    /// The test applied to each reading.
+    pub method: OutlierMethod,
+    /// Score above which a reading is an outlier (e.g. 3.0 for z-score,
+    /// 3.5 for MAD).
+    pub threshold: f64,
+    /// Number of accepted readings the test compares against.
+    pub window: usize,
+    /// Deviations up to this absolute amount are never outliers, which keeps
+    /// small changes in a perfectly flat series from being rejected.
+    pub min_deviation: f64,
+    /// Consecutive rejections after which the next reading is accepted.
+    pub max_consecutive: usize,
+    /// What happens to a rejected reading.
+    pub action: OutlierAction,
+}
+
+impl OutlierFilter {
+    /// Creates a z-score filter with the given threshold.
+    pub fn z_score(threshold: f64) -> Self {
+        Self::new(OutlierMethod::ZScore, threshold)
+    }
+
+    /// Creates a MAD (modified z-score) filter with the given threshold.
+    pub fn mad(threshold: f64) -> Self {
+        Self::new(OutlierMethod::Mad, threshold)
+    }
+
+    fn new(method: OutlierMethod, threshold: f64) -> Self {
+        Self {
+            method,
+            threshold,
+            window: 20,
+            min_deviation: 0.0,
+            max_consecutive: 5,
+            action: OutlierAction::default(),
+        }
+    }
+
+    /// Sets the number of readings the test compares against.
+    pub fn with_window(mut self, window: usize) -> Self {
+        self.window = window.max(MIN_OUTLIER_SAMPLES);
+        self
+    }
+
+    /// Sets the deviation below which readings are never outliers.
+    pub fn with_min_deviation(mut self, min_deviation: f64) -> Self {
+        self.min_deviation = min_deviation;
+        self
+    }
+
+    /// Sets what happens to rejected readings.
+    pub fn with_action(mut self, action: OutlierAction) -> Self {
+        self.action = action;
+        self
+    }
+
+    /// Returns `true` if `value` is an outlier with respect to `history`.
+    fn is_outlier(&self, history: &VecDeque<f64>, value: f64) -> bool {
+        if history.len() < MIN_OUTLIER_SAMPLES {
+            return false;
+        }
+        let (center, scale) = match self.method {
+            OutlierMethod::ZScore => {
+                let n = history.len() as f64;
+                let mean = history.iter().sum::<f64>() / n;
+                let variance = history.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+                (mean, variance.sqrt())
+            }
+            OutlierMethod::Mad => {
+                let median = median(history.iter().copied());
+                let mad = median_abs_deviation(history, median);
+                (median, mad / MAD_SCALE)
+            }
+        };
+
+        let deviation = (value - center).abs();
+        if deviation <= self.min_deviation {
+            return false;
+        }
+        if scale == 0.0 {
+            return deviation > 0.0;
+        }
+        deviation / scale > self.threshold
+    }
+}
+
+/// Smoothing applied to a sensor's readings.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Smoothing {
+    /// Median of the last `window` readings.
+    Median {
+        /// Number of readings the median is taken over.
+        window: usize,
+    },
+    /// Exponential moving average: `s = alpha * x + (1 - alpha) * s_prev`,
+    /// starting from the first reading.
+    Ema {
+        /// Weight of the newest reading, in `(0, 1]`.
+        alpha: f64,
+    },
+}
+
+impl Smoothing {
+    /// An EMA with the conventional smoothing factor for an `n`-reading
+    /// window, `alpha = 2 / (n + 1)`.
+    pub fn ema_window(n: usize) -> Self {
+        Smoothing::Ema {
+            alpha: 2.0 / (n as f64 + 1.0),
+        }
+    }
+}
+
+/// Min-max scaling of a sensor's declared range into `[0, 1]`.
+///
+/// Readings outside the range are clamped.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Normalization {
+    /// Reading mapped to 0.0.
+    pub min: f64,
+    /// Reading mapped to 1.0.
+    pub max: f64,
+}
+
+impl Normalization {
+    fn apply(&self, value: f64) -> f64 {
+        let span = self.max - self.min;
+        if span <= 0.0 {
+            return 0.0;
+        }
+        ((value - self.min) / span).clamp(0.0, 1.0)
+    }
+}
+
+/// A stage of a [`SensorPipeline`] together with its running state.
+#[derive(Debug, Clone)]
+enum Stage {
+    RejectOutliers {
+        filter: OutlierFilter,
+        history: VecDeque<f64>,
+        consecutive: usize,
+    },
+    Median {
+        window: usize,
+        values: VecDeque<f64>,
+    },
+    Ema {
+        alpha: f64,
+        current: Option<f64>,
+    },
+    Normalize(Normalization),
+}
+
+/// The result of running one stage.
+enum StageOutput {
+    Value(f64),
+    Rejected(Option<f64>),
+}
+
+impl Stage {
+    fn apply(&mut self, value: f64) -> StageOutput {
+        match self {
+            Stage::RejectOutliers {
+                filter,
+                history,
+                consecutive,
+            } => {
+                if filter.is_outlier(history, value) && *consecutive < filter.max_consecutive {
+                    *consecutive += 1;
+                    let replacement = match filter.action {
+                        OutlierAction::ReplaceWithLast => history.back().copied(),
+                        OutlierAction::Drop => None,
+                    };
+                    return StageOutput::Rejected(replacement);
+                }
+                if *consecutive >= filter.max_consecutive {
+                    // Persistently "out of bounds": the level has shifted
+                    history.clear();
+                }
+                *consecutive = 0;
+                push_bounded(history, value, filter.window);
+                StageOutput::Value(value)
+            }
+            Stage::Median { window, values } => {
+                push_bounded(values, value, *window);
+                StageOutput::Value(median(values.iter().copied()))
+            }
+            Stage::Ema { alpha, current } => {
+                let smoothed = match *current {
+                    Some(previous) => *alpha * value + (1.0 - *alpha) * previous,
+                    None => value,
+                };
+                *current = Some(smoothed);
+                StageOutput::Value(smoothed)
+            }
+            Stage::Normalize(normalization) => StageOutput::Value(normalization.apply(value)),
+        }
+    }
+}
+
+/// The result of running a reading through a [`SensorPipeline`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SensorOutput {
+    /// The cleaned reading, or `None` when an outlier was dropped.
+    pub value: Option<f64>,
+    /// `true` if an outlier stage rejected the reading.
+    pub outlier: bool,
+}
+
+/// The preprocessing stages for a single sensor, applied in the order added.
+///
+/// # Examples
+///
+/// ```
+/// # use kaneru::preprocessing::{SensorPipeline, Smoothing};
+/// let mut pipeline = SensorPipeline::new()
+///     .smooth(Smoothing::Median { window: 3 })
+///     .normalize(0.0, 100.0);
+///
+/// pipeline.process(10.0);
+/// pipeline.process(90.0);
+/// assert_eq!(pipeline.process(30.0).value, Some(0.3));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SensorPipeline {
+    stages: Vec<Stage>,
+}
+
+impl SensorPipeline {
+    /// Creates a pipeline with no stages, which passes readings through.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an outlier rejection stage.
+    pub fn reject_outliers(mut self, filter: OutlierFilter) -> Self {
+        self.stages.push(Stage::RejectOutliers {
+            filter,
+            history: VecDeque::new(),
+            consecutive: 0,
+        });
+        self
+    }
+
+    /// Adds a smoothing stage.
+    pub fn smooth(mut self, smoothing: Smoothing) -> Self {
+        self.stages.push(match smoothing {
+            Smoothing::Median { window } => Stage::Median {
+                window: window.max(1),
+                values: VecDeque::new(),
+            },
+            Smoothing::Ema { alpha } => Stage::Ema {
+                alpha: alpha.clamp(f64::MIN_POSITIVE, 1.0),
+                current: None,
+            },
+        });
+        self
+    }
+
+    /// Adds a stage scaling `[min, max]` into `[0, 1]`.
+    pub fn normalize(mut self, min: f64, max: f64) -> Self {
+        self.stages
+            .push(Stage::Normalize(Normalization { min, max }));
+        self
+    }
+
+    /// Runs a reading through every stage.
+    ///
+    /// A reading replaced by an outlier stage continues through the remaining
+    /// stages as the replacement value; a dropped reading stops there.
+    pub fn process(&mut self, value: f64) -> SensorOutput {
+        let mut current = value;
+        let mut outlier = false;
+        for stage in &mut self.stages {
+            match stage.apply(current) {
+                StageOutput::Value(v) => current = v,
+                StageOutput::Rejected(Some(replacement)) => {
+                    outlier = true;
+                    current = replacement;
+                }
+                StageOutput::Rejected(None) => {
+                    return SensorOutput {
+                        value: None,
+                        outlier: true,
+                    }
+                }
+            }
+        }
+        SensorOutput {
+            value: Some(current),
+            outlier,
+        }
+    }
+}
+
+/// The result of [`ObservationPipeline::process`].
+#[derive(Debug, Clone)]
+pub struct Preprocessed {
+    /// The observation the agent should see, or `None` when a rejected
+    /// reading was dropped and no glitch observation is surfaced.
+    pub observation: Option<Observation>,
+    /// `true` if the reading was rejected as an outlier.
+    pub outlier: bool,
+}
+
+/// Per-sensor preprocessing applied by an agent before its rules and
+/// learning see an observation.
+///
+/// Only `Sensor` observations with a numeric value and a configured pipeline
+/// are touched; everything else passes through unchanged.
+#[derive(Debug, Clone, Default)]
+pub struct ObservationPipeline {
+    sensors: HashMap<String, SensorPipeline>,
+    glitch_observations: bool,
+}
+
+impl ObservationPipeline {
+    /// Creates a pipeline with no sensors configured.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the pipeline for the sensor named `name`, replacing any existing
+    /// one and its state.
+    pub fn set_sensor(&mut self, name: &str, pipeline: SensorPipeline) {
+        self.sensors.insert(name.to_string(), pipeline);
+    }
+
+    /// Removes the pipeline for the sensor named `name`.
+    pub fn remove_sensor(&mut self, name: &str) -> Option<SensorPipeline> {
+        self.sensors.remove(name)
+    }
+
+    /// Whether rejected readings are surfaced as [`GLITCH_OBSERVATION`]s in
+    /// place of the cleaned observation. Disabled by default.
+    pub fn set_glitch_observations(&mut self, enabled: bool) {
+        self.glitch_observations = enabled;
+    }
+
+    /// Returns `true` if no sensor has a pipeline.
+    pub fn is_empty(&self) -> bool {
+        self.sensors.is_empty()
+    }
+
+    /// Runs an observation through its sensor's pipeline.
+    ///
+    /// A changed value is stored as a float, with the original reading kept in
+    /// the `raw_value` metadata.
+    pub fn process(&mut self, mut observation: Observation) -> Preprocessed {
+        let pipeline = match &observation.obs_type {
+            ObservationType::Sensor(name) => self.sensors.get_mut(name),
+            _ => None,
+        };
+        let (Some(pipeline), Some(raw)) = (pipeline, observation.value.as_f64()) else {
+            return Preprocessed {
+                observation: Some(observation),
+                outlier: false,
+            };
+        };
+
+        let output = pipeline.process(raw);
+        if output.outlier && self.glitch_observations {
+            return Preprocessed {
+                observation: Some(glitch_observation(&observation, raw)),
+                outlier: true,
+            };
+        }
+
+        let observation = output.value.map(|value| {
+            if value != raw {
+                observation.value = Value::Float(value);
+                observation
+                    .metadata
+                    .insert("raw_value".to_string(), Value::Float(raw));
+            }
+            observation
+        });
+        Preprocessed {
+            observation,
+            outlier: output.outlier,
+        }
+    }
+}
+
+/// The observation surfaced for a rejected reading of `observation`'s sensor.
+fn glitch_observation(observation: &Observation, raw: f64) -> Observation {
+    let sensor = match &observation.obs_type {
+        ObservationType::Sensor(name) => name.as_str(),
+        _ => "",
+    };
+    let mut glitch =
+        Observation::state_change(GLITCH_OBSERVATION, sensor).with_metadata("raw_value", raw);
+    glitch.timestamp = observation.timestamp;
+    glitch
+}
+
+fn push_bounded(values: &mut VecDeque<f64>, value: f64, capacity: usize) {
+    if values.len() >= capacity {
+        values.pop_front();
+    }
+    values.push_back(value);
+}
+
+fn median(values: impl Iterator<Item = f64>) -> f64 {
+    let mut sorted: Vec<f64> = values.collect();
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    sorted.sort_by(f64::total_cmp);
+    let mid = sorted.len() / 2;
+    if sorted.len() % 2 == 0 {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    }
+}
+
+fn median_abs_deviation(values: &VecDeque<f64>, center: f64) -> f64 {
+    median(values.iter().map(|x| (x - center).abs()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(pipeline: &mut SensorPipeline, values: &[f64]) -> Vec<SensorOutput> {
+        values.iter().map(|v| pipeline.process(*v)).collect()
+    }
+
+    #[test]
+    fn test_spike_in_flat_series_is_rejected() {
+        for filter in [OutlierFilter::z_score(3.0), OutlierFilter::mad(3.5)] {
+            let mut pipeline = SensorPipeline::new().reject_outliers(filter);
+            let mut series = vec![21.0; 10];
+            series.push(400.0);
+            series.extend([21.0; 3]);
+
+            let outputs = run(&mut pipeline, &series);
+            assert!(outputs[..10].iter().all(|o| !o.outlier));
+            assert_eq!(
+                outputs[10],
+                SensorOutput {
+                    value: Some(21.0),
+                    outlier: true
+                }
+            );
+            assert!(outputs[11..]
+                .iter()
+                .all(|o| !o.outlier && o.value == Some(21.0)));
+        }
+    }
+
+    #[test]
+    fn test_noisy_series_keeps_normal_readings() {
+        let mut pipeline = SensorPipeline::new().reject_outliers(OutlierFilter::mad(3.5));
+        let series = [20.1, 19.8, 20.3, 20.0, 19.9, 20.2, 20.4, 19.7, 55.0, 20.0];
+
+        let outputs = run(&mut pipeline, &series);
+        let rejected: Vec<usize> = (0..outputs.len()).filter(|&i| outputs[i].outlier).collect();
+        assert_eq!(rejected, vec![8]);
+    }
+
+    #[test]
+    fn test_drop_action_and_level_shift() {
+        let mut pipeline = SensorPipeline::new()
+            .reject_outliers(OutlierFilter::z_score(3.0).with_action(OutlierAction::Drop));
+        run(&mut pipeline, &[5.0; 5]);
+
+        // Five rejections, then the new level is accepted
+        let shifted = run(&mut pipeline, &[50.0; 7]);
+        assert!(shifted[..5].iter().all(|o| o.outlier && o.value.is_none()));
+        assert_eq!(shifted[5].value, Some(50.0));
+        assert_eq!(shifted[6].value, Some(50.0));
+        assert!(!shifted[6].outlier);
+    }
+
+    #[test]
+    fn test_ema_matches_hand_computed_series() {
+        let mut pipeline = SensorPipeline::new().smooth(Smoothing::Ema { alpha: 0.5 });
+        let outputs = run(&mut pipeline, &[10.0, 20.0, 20.0, 0.0]);
+
+        // s0 = 10; s1 = 0.5*20 + 0.5*10 = 15; s2 = 0.5*20 + 0.5*15 = 17.5;
+        // s3 = 0.5*0 + 0.5*17.5 = 8.75
+        let smoothed: Vec<f64> = outputs.iter().map(|o| o.value.unwrap()).collect();
+        assert_eq!(smoothed, vec![10.0, 15.0, 17.5, 8.75]);
+
+        assert_eq!(Smoothing::ema_window(3), Smoothing::Ema { alpha: 0.5 });
+    }
+
+    #[test]
+    fn test_median_smoothing_and_normalization() {
+        let mut pipeline = SensorPipeline::new()
+            .smooth(Smoothing::Median { window: 3 })
+            .normalize(0.0, 50.0);
+        let outputs = run(&mut pipeline, &[10.0, 40.0, 20.0, 100.0, 30.0]);
+
+        // Medians: 10, 25, 20, 40, 30
+        let normalized: Vec<f64> = outputs.iter().map(|o| o.value.unwrap()).collect();
+        assert_eq!(normalized, vec![0.2, 0.5, 0.4, 0.8, 0.6]);
+
+        let mut clamped = SensorPipeline::new().normalize(0.0, 10.0);
+        assert_eq!(clamped.process(-5.0).value, Some(0.0));
+        assert_eq!(clamped.process(15.0).value, Some(1.0));
+    }
+
+    #[test]
+    fn test_observation_pipeline_only_touches_configured_sensors() {
+        let mut pipeline = ObservationPipeline::new();
+        pipeline.set_sensor("temperature", SensorPipeline::new().normalize(0.0, 40.0));
+
+        let cleaned = pipeline
+            .process(Observation::sensor("temperature", 10.0))
+            .observation
+            .unwrap();
+        assert_eq!(cleaned.value.as_f64(), Some(0.25));
+        assert_eq!(cleaned.metadata["raw_value"].as_f64(), Some(10.0));
+
+        let other = pipeline
+            .process(Observation::sensor("humidity", 10.0))
+            .observation
+            .unwrap();
+        assert_eq!(other.value.as_f64(), Some(10.0));
+        assert!(other.metadata.is_empty());
+
+        let text = pipeline
+            .process(Observation::sensor("temperature", "n/a"))
+            .observation
+            .unwrap();
+        assert_eq!(text.value.as_string(), "n/a");
+    }
+
+    #[test]
+    fn test_glitch_observation_replaces_rejected_reading() {
+        let mut pipeline = ObservationPipeline::new();
+        pipeline.set_sensor(
+            "temperature",
+            SensorPipeline::new().reject_outliers(OutlierFilter::mad(3.5)),
+        );
+        pipeline.set_glitch_observations(true);
+        for _ in 0..5 {
+            pipeline.process(Observation::sensor("temperature", 21.0));
+        }
+
+        let result = pipeline.process(Observation::sensor("temperature", 400.0));
+        assert!(result.outlier);
+        let glitch = result.observation.unwrap();
+        assert_eq!(
+            glitch.obs_type,
+            ObservationType::StateChange(GLITCH_OBSERVATION.to_string())
+        );
+        assert_eq!(glitch.value.as_string(), "temperature");
+        assert_eq!(glitch.metadata["raw_value"].as_f64(), Some(400.0));
+    }
+}
@@ -0,0 +1,443 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Safety interlocks for Kaneru.
+//!
+//! A learned or rule-based policy can pick any action, including ones that
+//! wear out or endanger physical equipment: a buggy policy toggling a relay
+//! hundreds of times a minute, or a pump started on an empty tank. A
+//! [`SafetyLayer`] sits between deciding on an action and executing it, and
+//! enforces constraints configured per [`ActionType`]:
+//!
+//! - **Rate limits** ([`ActionLimits`]): at most a number of invocations per
+//!   time window, and a minimum interval between two invocations (each of
+//!   which changes the actuator's state).
+//! - **Exclusive groups**: at most one action of a group runs per tick, so a
+//!   valve is never opened and closed in the same tick. A tick ends with each
+//!   observation.
+//! - **Vetoes**: conditions that block an action whenever they hold for the
+//!   latest observation of a sensor, such as never running the pump while the
+//!   tank level is below 5%.
+//!
+//! A blocked action is not executed. Agents replace it with a no-op
+//! [`ActionResult::vetoed`] carrying the reason, count it in their stats and
+//! learn from it with the layer's [`penalty`](SafetyLayer::penalty), so that
+//! the policy learns to avoid it.
+//!
+//! # Examples
+//!
+//! ```
+//! # use kaneru::{Action, ActionType, Condition, Observation};
+//! # use kaneru::safety::{ActionLimits, SafetyLayer};
+//! # use std::time::Duration;
+//! let pump = ActionType::Custom("pump_on".into());
+//! let mut safety = SafetyLayer::new()
+//!     .limit(pump.clone(), ActionLimits::new().min_interval(Duration::from_secs(30)))
+//!     .veto(pump.clone(), Condition::below("tank_level", 5.0), "tank level below 5%");
+//!
+//! safety.observe(&Observation::sensor("tank_level", 3.0));
+//! let violation = safety.check(&Action::new(pump)).unwrap_err();
+//! assert_eq!(violation.to_string(), "vetoed: tank level below 5%");
+//! ```
+
+use crate::action::{Action, ActionType};
+use crate::coordination::{Clock, SystemClock};
+use crate::observation::{Observation, ObservationType};
+use crate::policy::Condition;
+use crate::types::Timestamp;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Reward learned from a blocked action unless set with
+/// [`SafetyLayer::with_penalty`]. Worse than the -1.0 of a failed action.
+pub const DEFAULT_VETO_PENALTY: f64 = -2.0;
+
+/// Rate limits for one action type.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ActionLimits {
+    /// The most invocations allowed within `window`.
+    pub max_invocations: Option<u32>,
+    /// The sliding window `max_invocations` applies to.
+    pub window: Duration,
+    /// The least time allowed between two invocations.
+    pub min_interval: Option<Duration>,
+}
+
+impl ActionLimits {
+    /// Creates limits that allow everything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allows at most `max` invocations within any `window`.
+    pub fn max_per_window(mut self, max: u32, window: Duration) -> Self {
+        self.max_invocations = Some(max);
+        self.window = window;
+        self
+    }
+
+    /// Requires at least `interval` between two invocations.
+    pub fn min_interval(mut self, interval: Duration) -> Self {
+        self.min_interval = Some(interval);
+        self
+    }
+}
+
+/// Why a [`SafetyLayer`] blocked an action.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Violation {
+    /// A veto condition holds.
+    Veto {
+        /// The reason the veto was configured with.
+        reason: String,
+    },
+    /// Another action of an exclusive group already ran this tick.
+    Exclusive {
+        /// The name of the group.
+        group: String,
+        /// The action of the group that ran.
+        ran: ActionType,
+    },
+    /// The action already ran the maximum number of times in the window.
+    RateLimit {
+        /// The most invocations allowed.
+        max: u32,
+        /// The window they are counted in.
+        window: Duration,
+    },
+    /// The action ran less than the minimum interval ago.
+    MinInterval {
+        /// The least time allowed between invocations.
+        interval: Duration,
+    },
+}
+
+impl Violation {
+    /// Returns `true` for interlocks (vetoes and exclusive groups), `false`
+    /// for rate limits.
+    pub fn is_interlock(&self) -> bool {
+        matches!(self, Violation::Veto { .. } | Violation::Exclusive { .. })
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::Veto { reason } => write!(f, "vetoed: {}", reason),
+            Violation::Exclusive { group, ran } => {
+                write!(
+                    f,
+                    "interlocked: {:?} of group '{}' ran this tick",
+                    ran, group
+                )
+            }
+            Violation::RateLimit { max, window } => write!(
+                f,
+                "rate limited: {} invocations within {:?} already",
+                max, window
+            ),
+            Violation::MinInterval { interval } => write!(
+                f,
+                "rate limited: less than {:?} since the last invocation",
+                interval
+            ),
+        }
+    }
+}
+
+/// A condition that blocks an action while it holds.
+#[derive(Debug, Clone)]
+struct Veto {
+    condition: Condition,
+    reason: String,
+}
+
+/// Enforces rate limits, exclusive groups and vetoes on actions.
+///
+/// Actions without any configured constraint always pass. Call
+/// [`observe`](Self::observe) with every observation and
+/// [`check`](Self::check) before executing each action; agents with a layer
+/// set do both themselves.
+pub struct SafetyLayer {
+    limits: HashMap<ActionType, ActionLimits>,
+    vetoes: HashMap<ActionType, Vec<Veto>>,
+    groups: Vec<(String, Vec<ActionType>)>,
+    penalty: f64,
+    clock: Arc<dyn Clock>,
+    /// Times of the invocations still relevant to each action's limits.
+    invocations: HashMap<ActionType, VecDeque<Timestamp>>,
+    /// Actions run since the last observation.
+    tick: Vec<ActionType>,
+    /// The latest observation of each sensor, for vetoes.
+    latest: HashMap<ObservationType, Observation>,
+}
+
+impl SafetyLayer {
+    /// Creates a layer without constraints, using the system clock.
+    pub fn new() -> Self {
+        Self::with_clock(Arc::new(SystemClock))
+    }
+
+    /// Creates a layer without constraints that reads time from `clock`.
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
+        Self {
+            limits: HashMap::new(),
+            vetoes: HashMap::new(),
+            groups: Vec::new(),
+            penalty: DEFAULT_VETO_PENALTY,
+            clock,
+            invocations: HashMap::new(),
+            tick: Vec::new(),
+            latest: HashMap::new(),
+        }
+    }
+
+    /// Sets the rate limits of an action type, replacing earlier ones.
+    pub fn limit(mut self, action_type: ActionType, limits: ActionLimits) -> Self {
+        self.limits.insert(action_type, limits);
+        self
+    }
+
+    /// Blocks an action type whenever `condition` holds for the latest
+    /// observation of any sensor.
+    ///
+    /// A reading stays in force until the same sensor reports again, so a
+    /// veto on `tank_level` is not lifted by an unrelated `temperature`
+    /// reading. Conditions should name the sensor they test, since a negated
+    /// condition holds for every other sensor.
+    pub fn veto(mut self, action_type: ActionType, condition: Condition, reason: &str) -> Self {
+        self.vetoes.entry(action_type).or_default().push(Veto {
+            condition,
+            reason: reason.to_string(),
+        });
+        self
+    }
+
+    /// Allows at most one of `members` to run per tick.
+    pub fn exclusive(mut self, group: &str, members: Vec<ActionType>) -> Self {
+        self.groups.push((group.to_string(), members));
+        self
+    }
+
+    /// Sets the reward agents learn from a blocked action.
+    pub fn with_penalty(mut self, penalty: f64) -> Self {
+        self.penalty = penalty;
+        self
+    }
+
+    /// Returns the reward agents learn from a blocked action.
+    pub fn penalty(&self) -> f64 {
+        self.penalty
+    }
+
+    /// Records an observation for vetoes and starts a new tick.
+    pub fn observe(&mut self, observation: &Observation) {
+        self.latest
+            .insert(observation.obs_type.clone(), observation.clone());
+        self.tick.clear();
+    }
+
+    /// Checks whether `action` may run now and, if it may, records it as run.
+    ///
+    /// Vetoes are checked first, then exclusive groups, then rate limits.
+    pub fn check(&mut self, action: &Action) -> Result<(), Violation> {
+        let action_type = &action.action_type;
+        let now = self.clock.now();
+
+        if let Some(veto) = self.vetoes.get(action_type).and_then(|vetoes| {
+            vetoes
+                .iter()
+                .find(|veto| self.latest.values().any(|obs| veto.condition.evaluate(obs)))
+        }) {
+            return Err(Violation::Veto {
+                reason: veto.reason.clone(),
+            });
+        }
+
+        for (group, members) in &self.groups {
+            if !members.contains(action_type) {
+                continue;
+            }
+            if let Some(ran) = self
+                .tick
+                .iter()
+                .find(|ran| *ran != action_type && members.contains(ran))
+            {
+                return Err(Violation::Exclusive {
+                    group: group.clone(),
+                    ran: ran.clone(),
+                });
+            }
+        }
+
+        if let Some(limits) = self.limits.get(action_type) {
+            let history = self.invocations.entry(action_type.clone()).or_default();
+            let keep = limits.window.max(limits.min_interval.unwrap_or_default());
+            while history.front().is_some_and(|t| elapsed(*t, now) >= keep) {
+                history.pop_front();
+            }
+
+            if let (Some(interval), Some(last)) = (limits.min_interval, history.back()) {
+                if elapsed(*last, now) < interval {
+                    return Err(Violation::MinInterval { interval });
+                }
+            }
+            if let Some(max) = limits.max_invocations {
+                let in_window = history
+                    .iter()
+                    .filter(|t| elapsed(**t, now) < limits.window)
+                    .count();
+                if in_window >= max as usize {
+                    return Err(Violation::RateLimit {
+                        max,
+                        window: limits.window,
+                    });
+                }
+            }
+            history.push_back(now);
+        }
+
+        self.tick.push(action_type.clone());
+        Ok(())
+    }
+}
+
+impl Default for SafetyLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Time from `since` to `now`, zero if the clock went backwards.
+fn elapsed(since: Timestamp, now: Timestamp) -> Duration {
+    Duration::from_micros(now.0.saturating_sub(since.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    /// A clock that only moves when told to.
+    #[derive(Default)]
+    struct ManualClock(AtomicU64);
+
+    impl ManualClock {
+        fn advance(&self, by: Duration) {
+            self.0.fetch_add(by.as_micros() as u64, Ordering::SeqCst);
+        }
+    }
+
+    impl Clock for ManualClock {
+        fn now(&self) -> Timestamp {
+            Timestamp(self.0.load(Ordering::SeqCst))
+        }
+    }
+
+    fn custom(name: &str) -> ActionType {
+        ActionType::Custom(name.to_string())
+    }
+
+    #[test]
+    fn test_unconstrained_actions_pass() {
+        let mut safety = SafetyLayer::new().limit(custom("relay"), ActionLimits::new());
+        for _ in 0..100 {
+            assert!(safety.check(&Action::new(custom("relay"))).is_ok());
+            assert!(safety.check(&Action::noop()).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_window_limit_slides() {
+        let clock = Arc::new(ManualClock::default());
+        let mut safety = SafetyLayer::with_clock(clock.clone()).limit(
+            custom("relay"),
+            ActionLimits::new().max_per_window(3, Duration::from_secs(10)),
+        );
+        let relay = Action::new(custom("relay"));
+
+        for _ in 0..3 {
+            assert!(safety.check(&relay).is_ok());
+            clock.advance(Duration::from_secs(1));
+        }
+        assert!(matches!(
+            safety.check(&relay),
+            Err(Violation::RateLimit { max: 3, .. })
+        ));
+
+        // The first invocation leaves the window at t=10s
+        clock.advance(Duration::from_secs(7));
+        assert!(safety.check(&relay).is_ok());
+        assert!(safety.check(&relay).is_err());
+    }
+
+    #[test]
+    fn test_min_interval() {
+        let clock = Arc::new(ManualClock::default());
+        let mut safety = SafetyLayer::with_clock(clock.clone()).limit(
+            custom("relay"),
+            ActionLimits::new().min_interval(Duration::from_secs(5)),
+        );
+        let relay = Action::new(custom("relay"));
+
+        assert!(safety.check(&relay).is_ok());
+        clock.advance(Duration::from_secs(4));
+        let violation = safety.check(&relay).unwrap_err();
+        assert!(!violation.is_interlock());
+        assert_eq!(
+            violation.to_string(),
+            "rate limited: less than 5s since the last invocation"
+        );
+
+        // Blocked attempts do not restart the interval
+        clock.advance(Duration::from_secs(1));
+        assert!(safety.check(&relay).is_ok());
+    }
+
+    #[test]
+    fn test_exclusive_group_per_tick() {
+        let mut safety =
+            SafetyLayer::new().exclusive("valve", vec![custom("open"), custom("close")]);
+        let tick = Observation::sensor("flow", 1.0);
+
+        safety.observe(&tick);
+        assert!(safety.check(&Action::new(custom("open"))).is_ok());
+        assert!(safety.check(&Action::new(custom("open"))).is_ok());
+        let violation = safety.check(&Action::new(custom("close"))).unwrap_err();
+        assert!(violation.is_interlock());
+        assert_eq!(
+            violation,
+            Violation::Exclusive {
+                group: "valve".into(),
+                ran: custom("open"),
+            }
+        );
+
+        safety.observe(&tick);
+        assert!(safety.check(&Action::new(custom("close"))).is_ok());
+    }
+
+    #[test]
+    fn test_veto_uses_latest_reading_of_each_sensor() {
+        let mut safety = SafetyLayer::new().veto(
+            custom("pump_on"),
+            Condition::below("tank_level", 5.0),
+            "tank level below 5%",
+        );
+        let pump = Action::new(custom("pump_on"));
+
+        assert!(safety.check(&pump).is_ok());
+        safety.observe(&Observation::sensor("tank_level", 4.0));
+        assert!(safety.check(&pump).is_err());
+
+        // An unrelated reading does not lift the veto, a new level does
+        safety.observe(&Observation::sensor("temperature", 20.0));
+        assert!(safety.check(&pump).is_err());
+        safety.observe(&Observation::sensor("tank_level", 40.0));
+        assert!(safety.check(&pump).is_ok());
+        assert_eq!(safety.penalty(), DEFAULT_VETO_PENALTY);
+    }
+}
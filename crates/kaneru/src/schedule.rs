@@ -0,0 +1,816 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Scheduled observations for Kaneru.
+//!
+//! Agents otherwise act only when an observation arrives from outside, so a
+//! nightly report would need an external scheduler poking the agent. A
+//! [`Scheduler`] holds [`Schedule`]s, each paired with an
+//! [`ObservationTemplate`], and turns every due slot into a synthetic
+//! [`ObservationType::Scheduled`](crate::ObservationType::Scheduled) observation named after the template.
+//! Agents poll their scheduler on each step; see
+//! [`SimpleAgent::add_schedule`](crate::SimpleAgent::add_schedule),
+//! [`KaneruAgent::add_schedule`](crate::KaneruAgent::add_schedule) and
+//! [`AgentCoordinator::add_group_schedule`](crate::AgentCoordinator::add_group_schedule).
+//!
+//! Slots come from one of three triggers, all in UTC:
+//!
+//! - **Fixed interval** ([`Schedule::every`]): multiples of the interval since
+//!   the Unix epoch, so every 15 minutes fires at :00, :15, :30 and :45.
+//! - **Daily** ([`Schedule::daily`]): once a day at the given time.
+//! - **Cron** ([`Schedule::cron`]): a five-field `minute hour day month
+//!   weekday` expression.
+//!
+//! A slot delivered more than the schedule's grace period after it fell was
+//! missed, because the agent was not stepped or not running. [`MissedSlots`]
+//! decides whether it still fires. Several slots passing between two polls
+//! fire once, never in a burst.
+//!
+//! The latest slot handled for each schedule is kept in a [`ScheduleState`].
+//! Saved with the agent and restored after a restart, it keeps slots that
+//! already fired from firing again.
+//!
+//! # Examples
+//!
+//! ```
+//! # use kaneru::schedule::{ObservationTemplate, Schedule, Scheduler};
+//! # use kaneru::ObservationType;
+//! # use std::time::Duration;
+//! let mut scheduler = Scheduler::new();
+//! scheduler.add(
+//!     Schedule::daily(2, 0)?,
+//!     ObservationTemplate::new("battery_report"),
+//! );
+//! scheduler.add(
+//!     Schedule::every(Duration::from_secs(3600)),
+//!     ObservationTemplate::new("hourly_check"),
+//! );
+//!
+//! // Nothing has fallen due since the schedules were added
+//! assert!(scheduler.poll().is_none());
+//! # Ok::<(), kaneru::Error>(())
+//! ```
+
+use crate::coordination::{Clock, SystemClock};
+use crate::error::{Error, Result};
+use crate::observation::Observation;
+use crate::types::{Timestamp, Value};
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Metadata key of the slot a scheduled observation was fired for, in
+/// microseconds since the Unix epoch.
+pub const SCHEDULED_FOR_KEY: &str = "scheduled_for";
+
+/// Metadata key set to `true` on an observation fired late, for a missed slot.
+pub const CATCH_UP_KEY: &str = "catch_up";
+
+/// How late a slot may be delivered and still count as on time, unless set
+/// with [`Schedule::with_grace`].
+pub const DEFAULT_GRACE: Duration = Duration::from_secs(60);
+
+const MICROS_PER_MINUTE: u64 = 60_000_000;
+const MINUTES_PER_DAY: u64 = 1440;
+
+/// Days from 0001-01-01 to the Unix epoch.
+const EPOCH_DAYS_FROM_CE: u64 = 719_163;
+
+/// How far a cron search looks before giving up, enough to reach the next
+/// 29 February across a century.
+const CRON_SEARCH_DAYS: u64 = 366 * 8 + 1;
+
+/// What happens to a slot delivered later than the schedule's grace period.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MissedSlots {
+    /// Drop it and wait for the next slot.
+    #[default]
+    Skip,
+    /// Fire once as soon as possible, however many slots were missed. The
+    /// observation carries [`CATCH_UP_KEY`].
+    FireOnce,
+}
+
+/// When a schedule is due.
+#[derive(Debug, Clone, PartialEq)]
+enum Trigger {
+    /// Multiples of the interval, in microseconds, since the Unix epoch.
+    Every(u64),
+    /// Whole minutes matching a cron expression.
+    Calendar(CronExpr),
+}
+
+/// A parsed five-field cron expression, one bit per allowed value.
+#[derive(Debug, Clone, PartialEq)]
+struct CronExpr {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    /// Whether the day-of-month field was `*`.
+    any_day: bool,
+    /// Whether the weekday field was `*`.
+    any_weekday: bool,
+}
+
+impl CronExpr {
+    fn parse(expr: &str) -> Result<Self> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(Error::Config(format!(
+                "cron expression '{}' must have 5 fields",
+                expr
+            )));
+        };
+
+        let mut weekdays = parse_field(weekday, 0, 7)?;
+        // Both 0 and 7 are Sunday
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays | 1) & !(1 << 7);
+        }
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59)?,
+            hours: parse_field(hour, 0, 23)?,
+            days: parse_field(day, 1, 31)?,
+            months: parse_field(month, 1, 12)?,
+            weekdays,
+            any_day: day.starts_with('*'),
+            any_weekday: weekday.starts_with('*'),
+        })
+    }
+
+    /// Whether the day `day` days after the Unix epoch matches.
+    ///
+    /// As in cron, a day matches either restricted day field when both are.
+    fn matches_day(&self, day: u64) -> bool {
+        let Some(date) = i32::try_from(day + EPOCH_DAYS_FROM_CE)
+            .ok()
+            .and_then(NaiveDate::from_num_days_from_ce_opt)
+        else {
+            return false;
+        };
+        if self.months & (1 << date.month()) == 0 {
+            return false;
+        }
+
+        let by_day = self.days & (1 << date.day()) != 0;
+        // 1970-01-01 was a Thursday
+        let by_weekday = self.weekdays & (1 << ((day + 4) % 7)) != 0;
+        match (self.any_day, self.any_weekday) {
+            (true, true) => true,
+            (true, false) => by_weekday,
+            (false, true) => by_day,
+            (false, false) => by_day || by_weekday,
+        }
+    }
+
+    fn matches_minute_of_day(&self, minute: u64) -> bool {
+        self.hours & (1 << (minute / 60)) != 0 && self.minutes & (1 << (minute % 60)) != 0
+    }
+
+    /// The first matching minute at or after `from`, counted from the epoch.
+    fn next_minute(&self, from: u64) -> Option<u64> {
+        let first = from / MINUTES_PER_DAY;
+        let mut start = from % MINUTES_PER_DAY;
+        for day in first..first + CRON_SEARCH_DAYS {
+            if self.matches_day(day) {
+                if let Some(minute) =
+                    (start..MINUTES_PER_DAY).find(|m| self.matches_minute_of_day(*m))
+                {
+                    return Some(day * MINUTES_PER_DAY + minute);
+                }
+            }
+            start = 0;
+        }
+        None
+    }
+
+    /// The last matching minute at or before `to`, counted from the epoch.
+    fn prev_minute(&self, to: u64) -> Option<u64> {
+        let mut day = to / MINUTES_PER_DAY;
+        let mut end = to % MINUTES_PER_DAY;
+        for _ in 0..CRON_SEARCH_DAYS {
+            if self.matches_day(day) {
+                if let Some(minute) = (0..=end).rev().find(|m| self.matches_minute_of_day(*m)) {
+                    return Some(day * MINUTES_PER_DAY + minute);
+                }
+            }
+            day = day.checked_sub(1)?;
+            end = MINUTES_PER_DAY - 1;
+        }
+        None
+    }
+}
+
+/// Parses one cron field into a bit set of the values in `min..=max`.
+///
+/// Accepts `*`, values, ranges `a-b` and steps `*/n` or `a-b/n`, separated by
+/// commas.
+fn parse_field(field: &str, min: u64, max: u64) -> Result<u64> {
+    let invalid = || Error::Config(format!("invalid cron field '{}'", field));
+    let number = |s: &str| {
+        s.parse::<u64>()
+            .ok()
+            .filter(|n| (min..=max).contains(n))
+            .ok_or_else(invalid)
+    };
+
+    let mut bits = 0;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u64>().map_err(|_| invalid())?),
+            None => (part, 1),
+        };
+        if step == 0 {
+            return Err(invalid());
+        }
+        let (low, high) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((low, high)) => (number(low)?, number(high)?),
+                // `a/n` runs from `a` to the end of the field
+                None if step > 1 => (number(range)?, max),
+                None => (number(range)?, number(range)?),
+            },
+        };
+        if low > high {
+            return Err(invalid());
+        }
+        for value in (low..=high).step_by(step as usize) {
+            bits |= 1 << value;
+        }
+    }
+    Ok(bits)
+}
+
+/// When a scheduled observation is due, and what happens to missed slots.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schedule {
+    trigger: Trigger,
+    missed: MissedSlots,
+    grace: Duration,
+}
+
+impl Schedule {
+    fn new(trigger: Trigger) -> Self {
+        Self {
+            trigger,
+            missed: MissedSlots::default(),
+            grace: DEFAULT_GRACE,
+        }
+    }
+
+    /// Fires at every multiple of `interval` since the Unix epoch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is shorter than a microsecond.
+    pub fn every(interval: Duration) -> Self {
+        let micros = interval.as_micros() as u64;
+        assert!(micros > 0, "schedule interval must be at least 1µs");
+        Self::new(Trigger::Every(micros))
+    }
+
+    /// Fires once a day at `hour:minute` UTC.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Config`] if the time is not a valid time of day.
+    pub fn daily(hour: u32, minute: u32) -> Result<Self> {
+        if hour > 23 || minute > 59 {
+            return Err(Error::Config(format!(
+                "invalid time of day {:02}:{:02}",
+                hour, minute
+            )));
+        }
+        Self::cron(&format!("{} {} * * *", minute, hour))
+    }
+
+    /// Fires on the minutes matching a cron expression, in UTC.
+    ///
+    /// The five fields are minute (0-59), hour (0-23), day of month (1-31),
+    /// month (1-12) and weekday (0-7, Sunday being 0 or 7). Each is `*`, a
+    /// value, a range `a-b`, a step `*/n` or `a-b/n`, or a comma-separated
+    /// list of those. As in cron, when both the day of month and the weekday
+    /// are restricted, a day matching either fires.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Config`] if the expression cannot be parsed.
+    pub fn cron(expr: &str) -> Result<Self> {
+        Ok(Self::new(Trigger::Calendar(CronExpr::parse(expr)?)))
+    }
+
+    /// Sets what happens to slots delivered later than the grace period.
+    pub fn on_missed(mut self, missed: MissedSlots) -> Self {
+        self.missed = missed;
+        self
+    }
+
+    /// Sets how late a slot may be delivered and still count as on time.
+    pub fn with_grace(mut self, grace: Duration) -> Self {
+        self.grace = grace;
+        self
+    }
+
+    /// Returns what happens to missed slots.
+    pub fn missed_slots(&self) -> MissedSlots {
+        self.missed
+    }
+
+    /// Returns how late a slot may be delivered and still count as on time.
+    pub fn grace(&self) -> Duration {
+        self.grace
+    }
+
+    /// Returns the first slot after `time`, or `None` if the schedule never
+    /// fires again, like a cron expression for 31 February.
+    pub fn next_after(&self, time: Timestamp) -> Option<Timestamp> {
+        match &self.trigger {
+            Trigger::Every(interval) => (time.0 / interval + 1)
+                .checked_mul(*interval)
+                .map(Timestamp),
+            Trigger::Calendar(cron) => cron
+                .next_minute(time.0 / MICROS_PER_MINUTE + 1)
+                .map(|minute| Timestamp(minute * MICROS_PER_MINUTE)),
+        }
+    }
+
+    /// Returns the last slot at or before `time`.
+    pub fn last_at_or_before(&self, time: Timestamp) -> Option<Timestamp> {
+        match &self.trigger {
+            Trigger::Every(interval) => Some(Timestamp(time.0 - time.0 % interval)),
+            Trigger::Calendar(cron) => cron
+                .prev_minute(time.0 / MICROS_PER_MINUTE)
+                .map(|minute| Timestamp(minute * MICROS_PER_MINUTE)),
+        }
+    }
+}
+
+/// The observation a schedule fires.
+///
+/// Fired observations are of type
+/// [`ObservationType::Scheduled`](crate::ObservationType::Scheduled) with the
+/// template's name, are timestamped when they fire, and carry the slot under
+/// [`SCHEDULED_FOR_KEY`] in addition to the template's metadata.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObservationTemplate {
+    /// The name of the schedule and of the observations it fires.
+    pub name: String,
+    /// The value of the observations, the name unless set.
+    pub value: Value,
+    /// The confidence of the observations, the default unless set.
+    pub confidence: Option<f32>,
+    /// Metadata added to the observations.
+    pub metadata: HashMap<String, Value>,
+}
+
+impl ObservationTemplate {
+    /// Creates a template for observations named `name`.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            value: Value::String(name.to_string()),
+            confidence: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    /// Sets the value of the observations.
+    pub fn with_value(mut self, value: impl Into<Value>) -> Self {
+        self.value = value.into();
+        self
+    }
+
+    /// Sets the confidence of the observations.
+    pub fn with_confidence(mut self, confidence: f32) -> Self {
+        self.confidence = Some(confidence);
+        self
+    }
+
+    /// Adds metadata to the observations.
+    pub fn with_metadata(mut self, key: &str, value: impl Into<Value>) -> Self {
+        self.metadata.insert(key.to_string(), value.into());
+        self
+    }
+
+    /// Creates the observation fired for `slot` at `now`.
+    fn fire(&self, slot: Timestamp, now: Timestamp, catch_up: bool) -> Observation {
+        let mut observation = Observation::scheduled(&self.name).with_metadata(
+            SCHEDULED_FOR_KEY,
+            Value::Int(i64::try_from(slot.0).unwrap_or(i64::MAX)),
+        );
+        observation.value = self.value.clone();
+        observation.timestamp = now;
+        if let Some(confidence) = self.confidence {
+            observation = observation.with_confidence(confidence);
+        }
+        observation.metadata.extend(self.metadata.clone());
+        if catch_up {
+            observation = observation.with_metadata(CATCH_UP_KEY, true);
+        }
+        observation
+    }
+}
+
+/// The progress of a [`Scheduler`]'s schedules, to persist across restarts.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduleState {
+    /// The latest slot fired or skipped by each schedule, by name. Slots up to
+    /// it never fire again.
+    pub handled: BTreeMap<String, Timestamp>,
+}
+
+/// Fires the observations of a set of schedules as they fall due.
+///
+/// Schedules are identified by the name of their template. Call
+/// [`poll`](Self::poll) regularly, such as on every agent step; agents with
+/// schedules do so themselves.
+pub struct Scheduler {
+    clock: Arc<dyn Clock>,
+    entries: Vec<(Schedule, ObservationTemplate)>,
+    state: ScheduleState,
+}
+
+impl Scheduler {
+    /// Creates a scheduler without schedules, using the system clock.
+    pub fn new() -> Self {
+        Self::with_clock(Arc::new(SystemClock))
+    }
+
+    /// Creates a scheduler without schedules that reads time from `clock`.
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
+        Self {
+            clock,
+            entries: Vec::new(),
+            state: ScheduleState::default(),
+        }
+    }
+
+    /// Adds a schedule, replacing any with the same template name.
+    ///
+    /// Only slots after now fire, unless restored state says otherwise.
+    pub fn add(&mut self, schedule: Schedule, template: ObservationTemplate) {
+        let now = self.clock.now();
+        self.state
+            .handled
+            .entry(template.name.clone())
+            .or_insert(now);
+        self.entries.retain(|(_, t)| t.name != template.name);
+        self.entries.push((schedule, template));
+    }
+
+    /// Removes the schedule named `name`, returning whether it existed.
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.state.handled.remove(name);
+        let before = self.entries.len();
+        self.entries.retain(|(_, t)| t.name != name);
+        self.entries.len() < before
+    }
+
+    /// Returns the number of schedules.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if there are no schedules.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the progress of the schedules, to save with the agent.
+    pub fn state(&self) -> &ScheduleState {
+        &self.state
+    }
+
+    /// Restores progress saved with [`state`](Self::state), before or after
+    /// the schedules are added again.
+    pub fn restore(&mut self, state: ScheduleState) {
+        self.state.handled.extend(state.handled);
+    }
+
+    /// Returns when the next slot of any schedule falls; in the past if one
+    /// is due.
+    pub fn next_due(&self) -> Option<Timestamp> {
+        self.entries
+            .iter()
+            .filter_map(|(schedule, template)| schedule.next_after(self.handled(template)))
+            .min()
+    }
+
+    /// Fires the schedule with the earliest due slot, if any.
+    ///
+    /// Each call fires at most one observation, so call it until it returns
+    /// `None` to drain every due schedule. Missed slots of
+    /// [`MissedSlots::Skip`] schedules are dropped along the way.
+    pub fn poll(&mut self) -> Option<Observation> {
+        let now = self.clock.now();
+        let mut skipped = Vec::new();
+        let mut due: Option<(Timestamp, usize)> = None;
+
+        for (i, (schedule, template)) in self.entries.iter().enumerate() {
+            let Some(slot) = schedule
+                .last_at_or_before(now)
+                .filter(|slot| *slot > self.handled(template))
+            else {
+                continue;
+            };
+            if is_late(slot, now, schedule) && schedule.missed == MissedSlots::Skip {
+                log::debug!("Skipping missed slot {} of '{}'", slot.0, template.name);
+                skipped.push((template.name.clone(), slot));
+                continue;
+            }
+            if due.is_none_or(|(earliest, _)| slot < earliest) {
+                due = Some((slot, i));
+            }
+        }
+        self.state.handled.extend(skipped);
+
+        let (slot, i) = due?;
+        let (schedule, template) = &self.entries[i];
+        let observation = template.fire(slot, now, is_late(slot, now, schedule));
+        self.state.handled.insert(template.name.clone(), slot);
+        Some(observation)
+    }
+
+    fn handled(&self, template: &ObservationTemplate) -> Timestamp {
+        self.state
+            .handled
+            .get(&template.name)
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether `slot` is delivered at `now` past the schedule's grace period.
+fn is_late(slot: Timestamp, now: Timestamp, schedule: &Schedule) -> bool {
+    Duration::from_micros(now.0.saturating_sub(slot.0)) > schedule.grace
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observation::ObservationType;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    /// A clock that only moves when told to.
+    #[derive(Default)]
+    struct ManualClock(AtomicU64);
+
+    impl ManualClock {
+        fn at(time: Timestamp) -> Arc<Self> {
+            Arc::new(Self(AtomicU64::new(time.0)))
+        }
+
+        fn advance(&self, by: Duration) {
+            self.0.fetch_add(by.as_micros() as u64, Ordering::SeqCst);
+        }
+    }
+
+    impl Clock for ManualClock {
+        fn now(&self) -> Timestamp {
+            Timestamp(self.0.load(Ordering::SeqCst))
+        }
+    }
+
+    const MINUTE: Duration = Duration::from_secs(60);
+    const HOUR: Duration = Duration::from_secs(3600);
+
+    /// 2026-03-14 (a Saturday) at `hour:minute` UTC.
+    fn on_14_march(hour: u64, minute: u64) -> Timestamp {
+        let day = 20_526;
+        Timestamp((day * MINUTES_PER_DAY + hour * 60 + minute) * MICROS_PER_MINUTE)
+    }
+
+    fn fired(scheduler: &mut Scheduler) -> Vec<Observation> {
+        std::iter::from_fn(|| scheduler.poll()).collect()
+    }
+
+    #[test]
+    fn test_interval_fires_once_per_slot() {
+        let clock = ManualClock::at(on_14_march(10, 7));
+        let mut scheduler = Scheduler::with_clock(clock.clone());
+        scheduler.add(
+            Schedule::every(15 * MINUTE),
+            ObservationTemplate::new("check").with_value(1),
+        );
+        assert!(scheduler.poll().is_none());
+        assert_eq!(scheduler.next_due(), Some(on_14_march(10, 15)));
+
+        clock.advance(7 * MINUTE);
+        assert!(scheduler.poll().is_none());
+
+        clock.advance(MINUTE);
+        let observation = scheduler.poll().unwrap();
+        assert_eq!(
+            observation.obs_type,
+            ObservationType::Scheduled("check".into())
+        );
+        assert_eq!(observation.value.as_i64(), Some(1));
+        assert_eq!(observation.timestamp, on_14_march(10, 15));
+        assert_eq!(
+            observation.metadata[SCHEDULED_FOR_KEY].as_i64(),
+            Some(on_14_march(10, 15).0 as i64)
+        );
+        assert!(!observation.metadata.contains_key(CATCH_UP_KEY));
+        assert!(scheduler.poll().is_none());
+
+        clock.advance(15 * MINUTE);
+        assert_eq!(fired(&mut scheduler).len(), 1);
+    }
+
+    #[test]
+    fn test_daily_fires_across_day_boundary() {
+        let clock = ManualClock::at(on_14_march(23, 50));
+        let mut scheduler = Scheduler::with_clock(clock.clone());
+        scheduler.add(
+            Schedule::daily(0, 5).unwrap(),
+            ObservationTemplate::new("nightly"),
+        );
+        assert_eq!(scheduler.next_due(), Some(on_14_march(24, 5)));
+
+        clock.advance(10 * MINUTE);
+        assert!(scheduler.poll().is_none());
+
+        clock.advance(5 * MINUTE);
+        let observation = scheduler.poll().unwrap();
+        assert_eq!(observation.timestamp, on_14_march(24, 5));
+        assert!(scheduler.poll().is_none());
+
+        // Once a day only
+        clock.advance(12 * HOUR);
+        assert!(scheduler.poll().is_none());
+        clock.advance(12 * HOUR);
+        assert_eq!(fired(&mut scheduler).len(), 1);
+        assert_eq!(scheduler.next_due(), Some(on_14_march(72, 5)));
+    }
+
+    #[test]
+    fn test_missed_slots_are_skipped() {
+        let clock = ManualClock::at(on_14_march(1, 0));
+        let mut scheduler = Scheduler::with_clock(clock.clone());
+        scheduler.add(
+            Schedule::daily(2, 0).unwrap(),
+            ObservationTemplate::new("report"),
+        );
+
+        // Polled three hours after the slot, well past the grace period
+        clock.advance(4 * HOUR);
+        assert!(scheduler.poll().is_none());
+        assert_eq!(scheduler.state().handled["report"], on_14_march(2, 0));
+
+        // The next slot fires as usual
+        clock.advance(21 * HOUR);
+        assert_eq!(fired(&mut scheduler).len(), 1);
+    }
+
+    #[test]
+    fn test_missed_slots_fire_once() {
+        let clock = ManualClock::at(on_14_march(10, 0));
+        let mut scheduler = Scheduler::with_clock(clock.clone());
+        scheduler.add(
+            Schedule::every(HOUR).on_missed(MissedSlots::FireOnce),
+            ObservationTemplate::new("hourly"),
+        );
+
+        // Five slots pass unpolled and fire once, for the latest
+        clock.advance(5 * HOUR + 30 * MINUTE);
+        let observations = fired(&mut scheduler);
+        assert_eq!(observations.len(), 1);
+        assert_eq!(
+            observations[0].metadata[SCHEDULED_FOR_KEY].as_i64(),
+            Some(on_14_march(15, 0).0 as i64)
+        );
+        assert_eq!(observations[0].metadata[CATCH_UP_KEY].as_bool(), Some(true));
+
+        // A slot within the grace period is on time
+        clock.advance(30 * MINUTE + MINUTE / 2);
+        let on_time = scheduler.poll().unwrap();
+        assert!(!on_time.metadata.contains_key(CATCH_UP_KEY));
+    }
+
+    #[test]
+    fn test_restored_state_does_not_fire_twice() {
+        let clock = ManualClock::at(on_14_march(1, 59));
+        let mut scheduler = Scheduler::with_clock(clock.clone());
+        let schedule = Schedule::daily(2, 0)
+            .unwrap()
+            .on_missed(MissedSlots::FireOnce);
+        scheduler.add(schedule.clone(), ObservationTemplate::new("report"));
+        clock.advance(2 * MINUTE);
+        assert_eq!(fired(&mut scheduler).len(), 1);
+        let saved = scheduler.state().clone();
+
+        // Restarted an hour later, restoring before or after adding
+        clock.advance(HOUR);
+        let mut restored = Scheduler::with_clock(clock.clone());
+        restored.restore(saved.clone());
+        restored.add(schedule.clone(), ObservationTemplate::new("report"));
+        assert!(restored.poll().is_none());
+
+        let mut restored = Scheduler::with_clock(clock.clone());
+        restored.add(schedule.clone(), ObservationTemplate::new("report"));
+        restored.restore(saved.clone());
+        assert!(restored.poll().is_none());
+
+        // Down over the next slot, the restart catches up on it alone
+        clock.advance(24 * HOUR);
+        let mut restored = Scheduler::with_clock(clock.clone());
+        restored.restore(saved);
+        restored.add(schedule, ObservationTemplate::new("report"));
+        let observations = fired(&mut restored);
+        assert_eq!(observations.len(), 1);
+        assert_eq!(
+            observations[0].metadata[SCHEDULED_FOR_KEY].as_i64(),
+            Some(on_14_march(26, 0).0 as i64)
+        );
+        assert_eq!(observations[0].metadata[CATCH_UP_KEY].as_bool(), Some(true));
+    }
+
+    #[test]
+    fn test_earliest_slot_fires_first() {
+        let clock = ManualClock::at(on_14_march(8, 0));
+        let mut scheduler = Scheduler::with_clock(clock.clone());
+        scheduler.add(
+            Schedule::cron("30 8 * * *").unwrap(),
+            ObservationTemplate::new("late"),
+        );
+        scheduler.add(
+            Schedule::cron("15 8 * * *")
+                .unwrap()
+                .on_missed(MissedSlots::FireOnce),
+            ObservationTemplate::new("early"),
+        );
+
+        clock.advance(30 * MINUTE);
+        let names: Vec<ObservationType> = fired(&mut scheduler)
+            .into_iter()
+            .map(|o| o.obs_type)
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ObservationType::Scheduled("early".into()),
+                ObservationType::Scheduled("late".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cron_expressions() {
+        let at =
+            |expr: &str, after: Timestamp| Schedule::cron(expr).unwrap().next_after(after).unwrap();
+        let saturday = on_14_march(12, 0);
+
+        assert_eq!(at("*/20 * * * *", saturday), on_14_march(12, 20));
+        assert_eq!(at("0 9-17/4 * * *", saturday), on_14_march(13, 0));
+        assert_eq!(at("0 9 * * 1-5", saturday), on_14_march(48 + 9, 0));
+        assert_eq!(at("0 9 * * 7", saturday), on_14_march(24 + 9, 0));
+        assert_eq!(at("0 0 1 4 *", saturday), on_14_march(18 * 24, 0));
+        // A restricted day of month or weekday matches either
+        assert_eq!(at("0 0 20 * 0", saturday), on_14_march(24, 0));
+
+        let schedule = Schedule::cron("0 0 29 2 *").unwrap();
+        assert!(schedule.next_after(saturday).unwrap() > saturday);
+        assert!(Schedule::cron("0 0 31 2 *")
+            .unwrap()
+            .next_after(saturday)
+            .is_none());
+        assert_eq!(
+            Schedule::cron("0 12 * * *")
+                .unwrap()
+                .last_at_or_before(saturday),
+            Some(saturday)
+        );
+    }
+
+    #[test]
+    fn test_invalid_schedules() {
+        for expr in [
+            "* * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "a * * * *",
+        ] {
+            assert!(
+                matches!(Schedule::cron(expr), Err(Error::Config(_))),
+                "{}",
+                expr
+            );
+        }
+        assert!(Schedule::daily(24, 0).is_err());
+        assert!(Schedule::daily(2, 60).is_err());
+    }
+}
@@ -0,0 +1,697 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Action schemas for Kaneru.
+//!
+//! An [`Action`] carries its parameters as a loosely typed map, so a
+//! misspelled parameter name is only noticed when the actuator ignores it.
+//! An [`ActionSchema`] declares the parameters an action type takes: their
+//! types, numeric ranges and whether they are required. Schemas are kept in
+//! a [`SchemaRegistry`], which can be set on an agent or on an
+//! [`AgentCoordinator`](crate::AgentCoordinator) for all its agents.
+//!
+//! Schemas are checked at three points:
+//!
+//! - **Building**: [`SchemaRegistry::build`] returns an [`ActionBuilder`] that
+//!   rejects each bad parameter as it is set.
+//! - **Loading rules**: [`PolicyEngine::validate_against`](crate::PolicyEngine::validate_against)
+//!   checks the action of every rule, so a bad rule file fails when it is
+//!   loaded.
+//! - **Executing**: agents with a registry check each action before running
+//!   it. An invalid action is not run; it fails with a [`SchemaError`]
+//!   listing every violation.
+//!
+//! Actions without a schema are handled by the registry's
+//! [`UnknownActionPolicy`]. A registry without any schema accepts every
+//! action, so agents behave as before until schemas are registered.
+//!
+//! # Examples
+//!
+//! ```
+//! # use kaneru::schema::{ActionSchema, ParamSpec, SchemaRegistry, SchemaViolation};
+//! let mut schemas = SchemaRegistry::new();
+//! schemas.register(ActionSchema::custom("set_valve").param(
+//!     "position",
+//!     ParamSpec::float().range(0.0, 1.0).required(),
+//! ));
+//!
+//! let action = schemas.build("set_valve").param("position", 0.7)?.finish()?;
+//! assert!(schemas.validate(&action).is_ok());
+//!
+//! let error = schemas.build("set_valve").param("position", 1.5).unwrap_err();
+//! assert!(matches!(error.violations[0], SchemaViolation::OutOfRange { .. }));
+//! # Ok::<(), kaneru::schema::SchemaError>(())
+//! ```
+
+use crate::action::{Action, ActionType};
+use crate::types::{Priority, Value, ValueRange};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// The type a parameter's value must have.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ParamType {
+    /// A [`Value::Bool`].
+    Bool,
+    /// A [`Value::Int`].
+    Int,
+    /// A [`Value::Float`], or a [`Value::Int`] read as one.
+    Float,
+    /// A [`Value::String`].
+    String,
+    /// A [`Value::Bytes`].
+    Bytes,
+    /// A [`Value::Json`].
+    Json,
+    /// Any value.
+    Any,
+}
+
+impl ParamType {
+    /// Returns `true` if `value` has this type.
+    pub fn accepts(&self, value: &Value) -> bool {
+        matches!(
+            (self, value),
+            (ParamType::Any, _)
+                | (ParamType::Bool, Value::Bool(_))
+                | (ParamType::Int, Value::Int(_))
+                | (ParamType::Float, Value::Float(_) | Value::Int(_))
+                | (ParamType::String, Value::String(_))
+                | (ParamType::Bytes, Value::Bytes(_))
+                | (ParamType::Json, Value::Json(_))
+        )
+    }
+}
+
+impl fmt::Display for ParamType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ParamType::Bool => "bool",
+            ParamType::Int => "int",
+            ParamType::Float => "float",
+            ParamType::String => "string",
+            ParamType::Bytes => "bytes",
+            ParamType::Json => "json",
+            ParamType::Any => "any",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The declaration of one parameter of an [`ActionSchema`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParamSpec {
+    /// The type the value must have.
+    pub param_type: ParamType,
+    /// Whether every action must set the parameter.
+    pub required: bool,
+    /// The range a numeric value must lie in, bounds included.
+    pub range: Option<ValueRange>,
+}
+
+impl ParamSpec {
+    /// Declares an optional parameter of the given type.
+    pub fn new(param_type: ParamType) -> Self {
+        Self {
+            param_type,
+            required: false,
+            range: None,
+        }
+    }
+
+    /// Declares an optional boolean parameter.
+    pub fn bool() -> Self {
+        Self::new(ParamType::Bool)
+    }
+
+    /// Declares an optional integer parameter.
+    pub fn int() -> Self {
+        Self::new(ParamType::Int)
+    }
+
+    /// Declares an optional floating-point parameter.
+    pub fn float() -> Self {
+        Self::new(ParamType::Float)
+    }
+
+    /// Declares an optional string parameter.
+    pub fn string() -> Self {
+        Self::new(ParamType::String)
+    }
+
+    /// Makes the parameter required.
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    /// Restricts a numeric value to `min..=max`.
+    pub fn range(mut self, min: f64, max: f64) -> Self {
+        self.range = Some(ValueRange::new(min, max));
+        self
+    }
+
+    /// Checks a value set for the parameter named `name`.
+    fn check(&self, name: &str, value: &Value) -> Option<SchemaViolation> {
+        if !self.param_type.accepts(value) {
+            return Some(SchemaViolation::WrongType {
+                param: name.to_string(),
+                expected: self.param_type,
+                found: value.clone(),
+            });
+        }
+        let (Some(range), Some(number)) = (&self.range, value.as_f64()) else {
+            return None;
+        };
+        (!range.contains(number)).then(|| SchemaViolation::OutOfRange {
+            param: name.to_string(),
+            value: number,
+            range: range.clone(),
+        })
+    }
+}
+
+/// The parameters an action type takes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionSchema {
+    /// The action type the schema applies to.
+    pub action_type: ActionType,
+    /// The declared parameters, by name.
+    pub params: BTreeMap<String, ParamSpec>,
+}
+
+impl ActionSchema {
+    /// Creates a schema for `action_type` that takes no parameters.
+    pub fn new(action_type: ActionType) -> Self {
+        Self {
+            action_type,
+            params: BTreeMap::new(),
+        }
+    }
+
+    /// Creates a schema for the custom action named `name`.
+    pub fn custom(name: &str) -> Self {
+        Self::new(ActionType::Custom(name.to_string()))
+    }
+
+    /// Declares a parameter, replacing an earlier declaration of it.
+    pub fn param(mut self, name: &str, spec: ParamSpec) -> Self {
+        self.params.insert(name.to_string(), spec);
+        self
+    }
+
+    /// Starts building an action of this schema's type.
+    pub fn build(&self) -> ActionBuilder<'_> {
+        ActionBuilder {
+            schema: Some(self),
+            action: Action::new(self.action_type.clone()),
+        }
+    }
+
+    /// Checks an action against the schema, returning every violation.
+    pub fn validate(&self, action: &Action) -> Result<(), SchemaError> {
+        let mut violations: Vec<SchemaViolation> = action
+            .params
+            .iter()
+            .filter_map(|(name, value)| self.check_param(name, value))
+            .collect();
+        violations.extend(
+            self.params
+                .iter()
+                .filter(|(name, spec)| spec.required && !action.params.contains_key(*name))
+                .map(|(name, _)| SchemaViolation::MissingParam {
+                    param: name.clone(),
+                }),
+        );
+        self.error(violations)
+    }
+
+    /// Checks one parameter of an action.
+    fn check_param(&self, name: &str, value: &Value) -> Option<SchemaViolation> {
+        match self.params.get(name) {
+            Some(spec) => spec.check(name, value),
+            None => Some(SchemaViolation::UnknownParam {
+                param: name.to_string(),
+                suggestion: self.closest_param(name),
+            }),
+        }
+    }
+
+    /// The declared parameter a misspelled `name` most likely meant.
+    fn closest_param(&self, name: &str) -> Option<String> {
+        self.params
+            .keys()
+            .map(|param| (edit_distance(name, param), param))
+            .filter(|(distance, param)| *distance <= 2.max(param.len() / 3))
+            .min()
+            .map(|(_, param)| param.clone())
+    }
+
+    fn error(&self, mut violations: Vec<SchemaViolation>) -> Result<(), SchemaError> {
+        if violations.is_empty() {
+            return Ok(());
+        }
+        // Parameters come from a map, so order them for stable messages
+        violations.sort_by(|a, b| a.param().cmp(&b.param()));
+        Err(SchemaError {
+            action_type: self.action_type.clone(),
+            violations,
+        })
+    }
+}
+
+/// What to do with an action whose type has no schema.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UnknownActionPolicy {
+    /// Accept it silently.
+    Allow,
+    /// Accept it, logging a warning.
+    #[default]
+    Warn,
+    /// Reject it with [`SchemaViolation::UnknownAction`].
+    Reject,
+}
+
+/// A set of action schemas, with the policy for actions outside it.
+///
+/// `NoOp` and `Wait` actions are always accepted.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SchemaRegistry {
+    /// The registered schemas, at most one per action type.
+    schemas: Vec<ActionSchema>,
+    /// What to do with an action whose type has no schema.
+    #[serde(default)]
+    pub unknown_actions: UnknownActionPolicy,
+}
+
+impl SchemaRegistry {
+    /// Creates an empty registry, which accepts every action.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets what to do with an action whose type has no schema.
+    pub fn with_unknown_actions(mut self, policy: UnknownActionPolicy) -> Self {
+        self.unknown_actions = policy;
+        self
+    }
+
+    /// Registers a schema, replacing the one for the same action type.
+    pub fn register(&mut self, schema: ActionSchema) {
+        match self
+            .schemas
+            .iter_mut()
+            .find(|s| s.action_type == schema.action_type)
+        {
+            Some(existing) => *existing = schema,
+            None => self.schemas.push(schema),
+        }
+    }
+
+    /// Returns the schema for an action type, if one is registered.
+    pub fn get(&self, action_type: &ActionType) -> Option<&ActionSchema> {
+        self.schemas.iter().find(|s| &s.action_type == action_type)
+    }
+
+    /// Returns the registered schemas, in the order they were registered.
+    pub fn schemas(&self) -> &[ActionSchema] {
+        &self.schemas
+    }
+
+    /// Returns the number of registered schemas.
+    pub fn len(&self) -> usize {
+        self.schemas.len()
+    }
+
+    /// Returns `true` if no schema is registered.
+    pub fn is_empty(&self) -> bool {
+        self.schemas.is_empty()
+    }
+
+    /// Checks an action against the schema for its type, returning every
+    /// violation.
+    pub fn validate(&self, action: &Action) -> Result<(), SchemaError> {
+        if let Some(schema) = self.get(&action.action_type) {
+            return schema.validate(action);
+        }
+        if self.is_empty() || matches!(action.action_type, ActionType::NoOp | ActionType::Wait) {
+            return Ok(());
+        }
+        match self.unknown_actions {
+            UnknownActionPolicy::Allow => Ok(()),
+            UnknownActionPolicy::Warn => {
+                log::warn!("No schema for action {:?}", action.action_type);
+                Ok(())
+            }
+            UnknownActionPolicy::Reject => Err(SchemaError {
+                action_type: action.action_type.clone(),
+                violations: vec![SchemaViolation::UnknownAction],
+            }),
+        }
+    }
+
+    /// Starts building the custom action named `name`, checking each
+    /// parameter against its schema as it is set.
+    pub fn build(&self, name: &str) -> ActionBuilder<'_> {
+        let action_type = ActionType::Custom(name.to_string());
+        ActionBuilder {
+            schema: self.get(&action_type),
+            action: Action::new(action_type),
+        }
+    }
+}
+
+/// Builds an [`Action`] parameter by parameter.
+///
+/// With a schema, each parameter is checked as it is set and required
+/// parameters are checked by [`finish`](Self::finish). Created by
+/// [`Action::build`], [`SchemaRegistry::build`] or [`ActionSchema::build`].
+#[derive(Debug, Clone)]
+pub struct ActionBuilder<'a> {
+    schema: Option<&'a ActionSchema>,
+    action: Action,
+}
+
+impl<'a> ActionBuilder<'a> {
+    /// Starts building an action of the given type without a schema.
+    pub(crate) fn new(action_type: ActionType) -> Self {
+        Self {
+            schema: None,
+            action: Action::new(action_type),
+        }
+    }
+
+    /// Sets a parameter.
+    ///
+    /// # Errors
+    ///
+    /// Returns the violation if the schema does not declare the parameter, or
+    /// the value has the wrong type or is out of range.
+    pub fn param(mut self, name: &str, value: impl Into<Value>) -> Result<Self, SchemaError> {
+        let value = value.into();
+        if let Some(schema) = self.schema {
+            if let Some(violation) = schema.check_param(name, &value) {
+                schema.error(vec![violation])?;
+            }
+        }
+        self.action.params.insert(name.to_string(), value);
+        Ok(self)
+    }
+
+    /// Sets the priority of the action.
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.action.priority = priority;
+        self
+    }
+
+    /// Returns the action.
+    ///
+    /// # Errors
+    ///
+    /// Returns every required parameter that was not set.
+    pub fn finish(self) -> Result<Action, SchemaError> {
+        if let Some(schema) = self.schema {
+            schema.validate(&self.action)?;
+        }
+        Ok(self.action)
+    }
+}
+
+/// One way an action breaks its schema.
+#[derive(Debug, Clone)]
+pub enum SchemaViolation {
+    /// No schema is registered for the action type.
+    UnknownAction,
+    /// The schema does not declare the parameter.
+    UnknownParam {
+        /// The parameter set on the action.
+        param: String,
+        /// The declared parameter it most likely misspells.
+        suggestion: Option<String>,
+    },
+    /// A required parameter is not set.
+    MissingParam {
+        /// The missing parameter.
+        param: String,
+    },
+    /// The value has the wrong type.
+    WrongType {
+        /// The parameter.
+        param: String,
+        /// The declared type.
+        expected: ParamType,
+        /// The value set.
+        found: Value,
+    },
+    /// A numeric value is outside the declared range.
+    OutOfRange {
+        /// The parameter.
+        param: String,
+        /// The value set.
+        value: f64,
+        /// The declared range.
+        range: ValueRange,
+    },
+}
+
+impl SchemaViolation {
+    /// The parameter the violation is about, if any.
+    pub fn param(&self) -> Option<&str> {
+        match self {
+            SchemaViolation::UnknownAction => None,
+            SchemaViolation::UnknownParam { param, .. }
+            | SchemaViolation::MissingParam { param }
+            | SchemaViolation::WrongType { param, .. }
+            | SchemaViolation::OutOfRange { param, .. } => Some(param),
+        }
+    }
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaViolation::UnknownAction => write!(f, "no schema for the action"),
+            SchemaViolation::UnknownParam {
+                param,
+                suggestion: Some(suggestion),
+            } => write!(
+                f,
+                "unknown parameter '{}' (did you mean '{}'?)",
+                param, suggestion
+            ),
+            SchemaViolation::UnknownParam { param, .. } => {
+                write!(f, "unknown parameter '{}'", param)
+            }
+            SchemaViolation::MissingParam { param } => {
+                write!(f, "missing required parameter '{}'", param)
+            }
+            SchemaViolation::WrongType {
+                param,
+                expected,
+                found,
+            } => write!(
+                f,
+                "parameter '{}' must be {}, got {:?}",
+                param, expected, found
+            ),
+            SchemaViolation::OutOfRange {
+                param,
+                value,
+                range,
+            } => write!(
+                f,
+                "parameter '{}' is {}, outside {}..={}",
+                param,
+                value,
+                range.min.map_or("".to_string(), |min| min.to_string()),
+                range.max.map_or("".to_string(), |max| max.to_string())
+            ),
+        }
+    }
+}
+
+/// Every way an action breaks its schema.
+#[derive(Debug, Clone)]
+pub struct SchemaError {
+    /// The type of the invalid action.
+    pub action_type: ActionType,
+    /// The violations, ordered by parameter.
+    pub violations: Vec<SchemaViolation>,
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid action {:?}: ", self.action_type)?;
+        for (i, violation) in self.violations.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{}", violation)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+impl From<SchemaError> for crate::Error {
+    fn from(e: SchemaError) -> Self {
+        crate::Error::Action(e.to_string())
+    }
+}
+
+/// The rules of a [`PolicyEngine`](crate::PolicyEngine) whose actions break
+/// their schemas.
+#[derive(Debug, Clone)]
+pub struct PolicySchemaError {
+    /// The name of each invalid rule's policy and rule, with its error.
+    pub rules: Vec<(String, String, SchemaError)>,
+}
+
+impl fmt::Display for PolicySchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} rule(s) emit invalid actions", self.rules.len())?;
+        for (policy, rule, error) in &self.rules {
+            write!(f, "\n  {}/{}: {}", policy, rule, error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for PolicySchemaError {}
+
+impl From<PolicySchemaError> for crate::Error {
+    fn from(e: PolicySchemaError) -> Self {
+        crate::Error::Policy(e.to_string())
+    }
+}
+
+/// Levenshtein distance between two strings, by characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substituted = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substituted.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn valve() -> ActionSchema {
+        ActionSchema::custom("set_valve")
+            .param("position", ParamSpec::float().range(0.0, 1.0).required())
+            .param("ramp_seconds", ParamSpec::int())
+    }
+
+    fn registry() -> SchemaRegistry {
+        let mut schemas = SchemaRegistry::new();
+        schemas.register(valve());
+        schemas
+    }
+
+    #[test]
+    fn test_out_of_range_parameter_rejected() {
+        let schemas = registry();
+        let error = schemas
+            .build("set_valve")
+            .param("position", 1.5)
+            .unwrap_err();
+        assert_eq!(error.violations.len(), 1);
+        assert!(matches!(
+            &error.violations[0],
+            SchemaViolation::OutOfRange { param, value, .. } if param == "position" && *value == 1.5
+        ));
+
+        // Built without a schema, the action is caught when validated
+        let action = Action::build("set_valve")
+            .param("position", -0.2)
+            .unwrap()
+            .param("ramp_seconds", "fast")
+            .unwrap()
+            .finish()
+            .unwrap();
+        let error = schemas.validate(&action).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid action Custom(\"set_valve\"): parameter 'position' is -0.2, outside 0..=1; \
+             parameter 'ramp_seconds' must be int, got String(\"fast\")"
+        );
+
+        let action = schemas
+            .build("set_valve")
+            .param("position", 1i64)
+            .unwrap()
+            .finish()
+            .unwrap();
+        assert!(schemas.validate(&action).is_ok());
+    }
+
+    #[test]
+    fn test_missing_and_misspelled_parameters() {
+        let schema =
+            ActionSchema::custom("set_heater").param("temperature", ParamSpec::float().required());
+        let error = schema.build().param("temprature", 21.0).unwrap_err();
+        assert_eq!(
+            error.violations[0].to_string(),
+            "unknown parameter 'temprature' (did you mean 'temperature'?)"
+        );
+
+        let error = schema.build().finish().unwrap_err();
+        assert!(matches!(
+            &error.violations[0],
+            SchemaViolation::MissingParam { param } if param == "temperature"
+        ));
+    }
+
+    #[test]
+    fn test_unknown_actions_per_policy() {
+        let unknown = Action::build("open_door").finish().unwrap();
+
+        // Without schemas every action is accepted
+        assert!(SchemaRegistry::new()
+            .with_unknown_actions(UnknownActionPolicy::Reject)
+            .validate(&unknown)
+            .is_ok());
+
+        let warn = registry();
+        assert!(warn.validate(&unknown).is_ok());
+
+        let reject = registry().with_unknown_actions(UnknownActionPolicy::Reject);
+        let error = reject.validate(&unknown).unwrap_err();
+        assert!(matches!(
+            error.violations.as_slice(),
+            [SchemaViolation::UnknownAction]
+        ));
+        assert!(reject.validate(&Action::noop()).is_ok());
+        assert!(reject.validate(&Action::wait()).is_ok());
+    }
+
+    #[test]
+    fn test_registry_round_trips() {
+        let schemas = registry().with_unknown_actions(UnknownActionPolicy::Reject);
+        let json = serde_json::to_string(&schemas).unwrap();
+        let parsed: SchemaRegistry = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed.unknown_actions, UnknownActionPolicy::Reject);
+        assert!(parsed
+            .get(&ActionType::Custom("set_valve".into()))
+            .is_some_and(|schema| schema.params["position"].required));
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("temprature", "temperature"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+}
@@ -4,12 +4,15 @@
 //! Integration tests for agent persistence
 //!
 //! Tests agent state serialization, learning state persistence,
-//! goal manager persistence, and checkpoint management.
+//! goal manager persistence, checkpoint management, and the migration of
+//! states saved by older versions.
 
 use kaneru::policy::Condition;
 use kaneru::{
-    Action, Agent, AgentConfig, Goal, GoalStatus, Observation, Policy, Rule, SimpleAgent,
+    Action, Agent, AgentConfig, AgentPersistence, Goal, GoalStatus, KaneruAgent, Observation,
+    PersistenceError, Policy, Rule, SimpleAgent, STATE_VERSION,
 };
+use std::path::PathBuf;
 
 // ============================================================================
 // Agent State Serialization Tests
@@ -278,3 +281,63 @@ fn test_json_pretty_format() {
     let restored: Goal = serde_json::from_str(&pretty_json).unwrap();
     assert_eq!(goal.name, restored.name);
 }
+
+// ============================================================================
+// State Migration Tests
+// ============================================================================
+
+/// A state saved by an older version, kept in `tests/fixtures/agent_state`.
+///
+/// Each fixture is an agent that took three steps.
+fn state_fixture(version: u32) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/agent_state")
+        .join(format!("v{}.json", version))
+}
+
+#[test]
+fn test_every_state_version_fixture_loads() {
+    for version in 1..=STATE_VERSION {
+        let path = state_fixture(version);
+        let agent = KaneruAgent::load_from_file(&path)
+            .unwrap_or_else(|e| panic!("fixture {:?} failed to load: {}", path, e));
+
+        assert_eq!(agent.get_statistics().total_steps, 3);
+        assert_eq!(agent.save_state().version, STATE_VERSION);
+    }
+}
+
+#[test]
+fn test_migrated_state_keeps_learned_q_values() {
+    let v2 = KaneruAgent::load_from_file(&state_fixture(2)).unwrap();
+    assert_eq!(v2.learning_engine().state_action_count(), 3);
+    assert_eq!(v2.get_statistics().learning_updates, 3);
+
+    // Version 1 could only save an empty Q-table.
+    let v1 = KaneruAgent::load_from_file(&state_fixture(1)).unwrap();
+    assert_eq!(v1.learning_engine().state_action_count(), 0);
+}
+
+#[test]
+fn test_migrated_state_roundtrips() {
+    let agent = KaneruAgent::load_from_file(&state_fixture(1)).unwrap();
+    let restored = KaneruAgent::from_bytes(&agent.to_bytes()).unwrap();
+    assert_eq!(restored.get_statistics().total_steps, 3);
+}
+
+#[test]
+fn test_newer_state_is_rejected() {
+    let mut state: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(state_fixture(STATE_VERSION)).unwrap()).unwrap();
+    state["version"] = serde_json::json!(STATE_VERSION + 1);
+
+    let err = KaneruAgent::from_bytes(&serde_json::to_vec(&state).unwrap())
+        .err()
+        .expect("a newer state must not load");
+    assert!(matches!(
+        err,
+        PersistenceError::UnsupportedVersion { found, supported }
+            if found == STATE_VERSION + 1 && supported == STATE_VERSION
+    ));
+    assert!(err.to_string().contains("newer than this build"));
+}
@@ -0,0 +1,67 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Learning regression tests against the built-in environments
+//!
+//! Environments and the random baseline are seeded; the agent's own
+//! exploration is not, so the margins leave room for it.
+
+use kaneru::{
+    EpisodeRunner, GridWorld, KaneruAgent, KaneruConfig, LearningConfig, OperationMode,
+    RandomAgent, Thermostat,
+};
+
+const EPISODES: usize = 200;
+const SEED: u64 = 42;
+
+fn learning_agent() -> KaneruAgent {
+    KaneruAgent::new(KaneruConfig {
+        learning: LearningConfig {
+            learning_rate: 0.2,
+            discount_factor: 0.9,
+            epsilon: 0.2,
+            epsilon_decay: 0.98,
+            epsilon_min: 0.01,
+            ..Default::default()
+        },
+        mode: OperationMode::GoalDriven,
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_kaneru_agent_beats_random_on_thermostat() {
+    let runner = EpisodeRunner::new(EPISODES);
+
+    let learned = runner.run(&mut learning_agent(), &mut Thermostat::new(SEED));
+    let baseline = runner.run(&mut RandomAgent::new(SEED), &mut Thermostat::new(SEED));
+    assert_eq!(learned.episodes(), EPISODES);
+
+    // A random policy lets the room drift far from the setpoint; the trained
+    // agent must hold it at least 2 °C closer per step on average
+    let margin = 2.0 * 50.0;
+    assert!(
+        learned.final_mean_reward(50) > baseline.mean_reward() + margin,
+        "learned {:.1}, random {:.1}",
+        learned.final_mean_reward(50),
+        baseline.mean_reward()
+    );
+
+    // And it gets there by learning
+    let early = learned.rewards[..20].iter().sum::<f64>() / 20.0;
+    assert!(learned.final_mean_reward(50) > early);
+}
+
+#[test]
+fn test_kaneru_agent_learns_gridworld() {
+    let runner = EpisodeRunner::new(EPISODES).with_max_steps(200);
+
+    let learned = runner.run(&mut learning_agent(), &mut GridWorld::new(5, 5));
+    let baseline = runner.run(&mut RandomAgent::new(SEED), &mut GridWorld::new(5, 5));
+
+    // The shortest path takes 8 moves
+    let final_lengths = &learned.lengths[EPISODES - 50..];
+    let mean_length = final_lengths.iter().sum::<usize>() as f64 / 50.0;
+    assert!(mean_length < 12.0, "mean episode length {mean_length}");
+    assert!(learned.final_mean_reward(50) > baseline.mean_reward());
+}
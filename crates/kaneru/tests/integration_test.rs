@@ -434,6 +434,55 @@ fn test_learning_engine_persistence() {
     let _ = std::fs::remove_file(&temp_path);
 }
 
+/// Test recording experiences on a device agent, training offline and redeploying
+#[test]
+fn test_offline_training_round_trip() {
+    let log_path = std::env::temp_dir().join("kaneru_offline_training.kxpl");
+    let engine_path = std::env::temp_dir().join("kaneru_offline_engine.json");
+    let _ = std::fs::remove_file(&log_path);
+
+    // Record on the device
+    let mut device = KaneruAgent::with_default_config();
+    device.attach_experience_logger(ExperienceLogger::append(&log_path).unwrap());
+    for i in 0..5 {
+        let action = device.step(Observation::sensor("temperature", 20.0 + i as f64));
+        let result = ActionResult::success(&action.id);
+        let next = Observation::sensor("temperature", 21.0 + i as f64);
+        device.learn(Outcome::new(action, result, 1.0, next, i == 4));
+    }
+    let logger = device.detach_experience_logger().unwrap();
+    assert_eq!(logger.records_written(), 5);
+    drop(logger);
+
+    // Train centrally
+    let experiences: Vec<Experience> = ExperienceLogReader::open(&log_path)
+        .unwrap()
+        .collect::<std::result::Result<_, _>>()
+        .unwrap();
+    assert_eq!(experiences.len(), 5);
+
+    let mut engine = LearningEngine::new(LearningConfig::default());
+    let config = OfflineTrainingConfig {
+        seed: Some(11),
+        ..Default::default()
+    };
+    let report = engine.train_offline(experiences.into_iter(), 20, &config);
+    assert_eq!(report.updates, 100);
+    engine.save_to_file(&engine_path).unwrap();
+
+    // Redeploy
+    let mut redeployed = KaneruAgent::with_default_config();
+    redeployed.set_learning_engine(LearningEngine::load_from_file(&engine_path).unwrap());
+    assert_eq!(
+        redeployed.learning_engine().state_action_count(),
+        engine.state_action_count()
+    );
+    assert!(redeployed.learning_engine().state_action_count() > 0);
+
+    let _ = std::fs::remove_file(&log_path);
+    let _ = std::fs::remove_file(&engine_path);
+}
+
 /// Test complete multi-agent scenario with coordination and learning
 #[test]
 fn test_complete_multi_agent_scenario() {
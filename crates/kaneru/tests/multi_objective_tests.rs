@@ -0,0 +1,165 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Multi-objective learning on a two-objective toy environment
+//!
+//! A space heater keeps a room comfortable at the cost of energy. Heating
+//! earns comfort and costs energy; idling saves energy and lets the room get
+//! cold. Which one the agent learns to prefer must follow the weights of its
+//! composite goal.
+
+use kaneru::{
+    Action, ActionId, ActionResult, ActionType, CompositeGoal, Experience, ExperienceLogReader,
+    ExperienceLogger, Goal, KaneruAgent, KaneruConfig, LearningConfig, Observation, OperationMode,
+    Outcome, Scalarization, StateId,
+};
+
+const STEPS: usize = 400;
+
+/// One state, two actions, rewards `[comfort, energy]`.
+struct SpaceHeater;
+
+impl SpaceHeater {
+    fn observation(&self) -> Observation {
+        Observation::sensor("temperature", 18.0)
+    }
+
+    fn action_space(&self) -> Vec<Action> {
+        ["heat", "idle"]
+            .iter()
+            .map(|name| Action::new(ActionType::Custom(name.to_string())))
+            .collect()
+    }
+
+    fn step(&self, action: &Action) -> Vec<f64> {
+        match &action.action_type {
+            ActionType::Custom(name) if name == "heat" => vec![1.0, -1.0],
+            _ => vec![-1.0, 0.0],
+        }
+    }
+}
+
+fn heater_goal(comfort: f64, energy: f64) -> CompositeGoal {
+    CompositeGoal::new("Comfort on a budget")
+        .with_objective(Goal::maintain("temperature", 20.0..23.0), comfort)
+        .with_objective(Goal::minimize("energy"), energy)
+}
+
+fn heater_agent(goal: CompositeGoal, scalarization: Scalarization) -> KaneruAgent {
+    let mut agent = KaneruAgent::new(KaneruConfig {
+        learning: LearningConfig {
+            learning_rate: 0.2,
+            // One state, so every step is a bandit pull
+            discount_factor: 0.0,
+            epsilon: 0.3,
+            ..Default::default()
+        },
+        mode: OperationMode::GoalDriven,
+        scalarization,
+        ..Default::default()
+    });
+    agent.set_action_space(&SpaceHeater.action_space());
+    agent.set_composite_goal(goal);
+    agent
+}
+
+/// Trains `agent` on the heater and returns the action it learned to prefer.
+fn run(agent: &mut KaneruAgent) -> Option<ActionId> {
+    let env = SpaceHeater;
+    for _ in 0..STEPS {
+        let action = agent.step(env.observation());
+        let rewards = env.step(&action);
+        let result = ActionResult::success(&action.id);
+        agent.learn(
+            Outcome::new(action, result, 0.0, env.observation(), false).with_reward_vector(rewards),
+        );
+    }
+
+    let ids: Vec<ActionId> = env
+        .action_space()
+        .iter()
+        .map(ActionId::from_action)
+        .collect();
+    agent
+        .learning_engine()
+        .get_best_action(&StateId::from_observation(&env.observation()), &ids)
+}
+
+fn train(goal: CompositeGoal, scalarization: Scalarization) -> (KaneruAgent, Option<ActionId>) {
+    let mut agent = heater_agent(goal, scalarization);
+    let best = run(&mut agent);
+    (agent, best)
+}
+
+fn action_id(name: &str) -> ActionId {
+    ActionId::from_action(&Action::new(ActionType::Custom(name.to_string())))
+}
+
+#[test]
+fn test_weights_steer_the_learned_policy() {
+    let (comfort_first, best) = train(heater_goal(0.8, 0.2), Scalarization::WeightedSum);
+    assert_eq!(best, Some(action_id("heat")));
+
+    let (energy_first, best) = train(heater_goal(0.2, 0.8), Scalarization::WeightedSum);
+    assert_eq!(best, Some(action_id("idle")));
+
+    // The stats show which objective each policy sacrifices
+    let comfort_stats = &comfort_first.get_statistics().objectives;
+    let energy_stats = &energy_first.get_statistics().objectives;
+    assert_eq!(comfort_stats.len(), 2);
+    assert_eq!(comfort_stats[1].name, "Minimize energy");
+    assert_eq!(comfort_stats[0].samples, STEPS as u64);
+    assert!(comfort_stats[0].mean_reward > energy_stats[0].mean_reward);
+    assert!(comfort_stats[1].mean_reward < energy_stats[1].mean_reward);
+    assert!(comfort_stats[1].recent_reward < 0.0);
+    assert!(energy_stats[0].recent_reward < 0.0);
+}
+
+#[test]
+fn test_lexicographic_puts_the_first_objective_first() {
+    // Weights favour energy, but lexicographic order puts comfort first
+    let (_, best) = train(heater_goal(0.1, 0.9), Scalarization::Lexicographic);
+    assert_eq!(best, Some(action_id("heat")));
+
+    // Once comfort is capped at its threshold, energy decides
+    let goal = CompositeGoal::new("Budget first")
+        .with_thresholded_objective(Goal::maintain("temperature", 20.0..23.0), 0.9, -1.0)
+        .with_objective(Goal::minimize("energy"), 0.1);
+    let (_, best) = train(goal, Scalarization::Lexicographic);
+    assert_eq!(best, Some(action_id("idle")));
+}
+
+#[test]
+fn test_reward_vectors_are_kept_for_offline_rescalarization() {
+    let log_path = std::env::temp_dir().join("kaneru_multi_objective.kxpl");
+    let _ = std::fs::remove_file(&log_path);
+
+    let mut agent = heater_agent(heater_goal(0.8, 0.2), Scalarization::WeightedSum);
+    agent.attach_experience_logger(ExperienceLogger::append(&log_path).unwrap());
+    run(&mut agent);
+    drop(agent.detach_experience_logger());
+
+    let mut experiences: Vec<Experience> = ExperienceLogReader::open(&log_path)
+        .unwrap()
+        .collect::<std::result::Result<_, _>>()
+        .unwrap();
+    assert_eq!(experiences.len(), STEPS);
+    assert!(experiences.iter().all(|e| e.reward_vector.len() == 2));
+
+    let heat = action_id("heat");
+    let heat_reward = |experiences: &[Experience]| {
+        experiences
+            .iter()
+            .find(|e| e.action == heat)
+            .map(|e| e.reward)
+            .unwrap()
+    };
+    assert!((heat_reward(&experiences) - 0.6).abs() < 1e-9);
+
+    // Retrain offline with the weights flipped, without replaying the device
+    let updated = heater_goal(0.2, 0.8).rescalarize(&mut experiences, Scalarization::WeightedSum);
+    assert_eq!(updated, experiences.len());
+    assert!((heat_reward(&experiences) + 0.6).abs() < 1e-9);
+
+    let _ = std::fs::remove_file(&log_path);
+}
@@ -0,0 +1,130 @@
+// Copyright 2019-2026 Apilium Technologies OÜ. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR Commercial
+
+//! Safety layer scenarios: a flapping relay and a vetoed pump
+
+use kaneru::{
+    Action, ActionId, ActionLimits, ActionType, Agent, Clock, Condition, KaneruAgent, KaneruConfig,
+    LearningEngine, Observation, OperationMode, Rule, SafetyLayer, SimpleAgent, StateId, Timestamp,
+};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A clock that only moves when told to.
+#[derive(Default)]
+struct ManualClock(AtomicU64);
+
+impl ManualClock {
+    fn advance(&self, by: Duration) {
+        self.0.fetch_add(by.as_micros() as u64, Ordering::SeqCst);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Timestamp {
+        Timestamp(self.0.load(Ordering::SeqCst))
+    }
+}
+
+#[test]
+fn test_relay_flapping_is_clamped_to_rate() {
+    let toggle = ActionType::Custom("toggle_relay".into());
+    let clock = Arc::new(ManualClock::default());
+
+    // A buggy rule toggles the relay on every reading
+    let mut agent = SimpleAgent::new("relay_controller");
+    agent.set_exploration_rate(0.0);
+    agent.add_rule(Rule::new(
+        "flap",
+        Condition::Always,
+        Action::new(toggle.clone()),
+    ));
+    agent.set_safety_layer(
+        SafetyLayer::with_clock(clock.clone()).limit(
+            toggle.clone(),
+            ActionLimits::new()
+                .max_per_window(10, Duration::from_secs(60))
+                .min_interval(Duration::from_secs(2)),
+        ),
+    );
+
+    // 400 decisions a minute
+    let mut executed_at = Vec::new();
+    for step in 0..400u64 {
+        let obs = Observation::sensor("relay_state", (step % 2) as f64);
+        agent.observe(obs.clone());
+        let action = agent.decide();
+        assert_eq!(action.action_type, toggle);
+
+        let result = agent.execute(action.clone());
+        if result.success {
+            executed_at.push(clock.now().0);
+        } else {
+            assert!(result.vetoed);
+            assert!(result.error.as_deref().unwrap().starts_with("rate limited"));
+        }
+        agent.learn(&obs, &action, &result);
+        clock.advance(Duration::from_millis(150));
+    }
+
+    let stats = agent.stats();
+    assert_eq!(stats.actions_executed, 10);
+    assert_eq!(stats.actions_rate_limited, 390);
+    assert_eq!(stats.actions_vetoed, 0);
+    assert!(executed_at.windows(2).all(|w| w[1] - w[0] >= 2_000_000));
+
+    // The blocked toggles were learned as penalized
+    let state = StateId::from_observation(&Observation::sensor("relay_state", 1.0));
+    let toggle_id = ActionId::from_action(&Action::new(toggle));
+    let engine = agent.learning_engine().unwrap();
+    assert!(engine.get_q_value(&state, &toggle_id) < 0.0);
+}
+
+#[test]
+fn test_veto_blocks_pump_regardless_of_q_values() {
+    let pump = Action::new(ActionType::Custom("pump_on".into()));
+    let low_tank = Observation::sensor("tank_level", 3.0);
+    let state = StateId::from_observation(&low_tank);
+    let pump_id = ActionId::from_action(&pump);
+    let actions = [pump.clone(), Action::noop()];
+    let action_ids: Vec<ActionId> = actions.iter().map(ActionId::from_action).collect();
+
+    // Learned values strongly favor running the pump
+    let mut engine = LearningEngine::default_config();
+    for _ in 0..20 {
+        engine.update(&state, &pump_id, 100.0, &state, None, &action_ids);
+    }
+    assert_eq!(
+        engine.get_best_action(&state, &action_ids),
+        Some(pump_id.clone())
+    );
+    let favored = engine.get_q_value(&state, &pump_id);
+
+    let mut agent = KaneruAgent::new(KaneruConfig {
+        mode: OperationMode::Exploitation,
+        ..Default::default()
+    });
+    agent.set_action_space(&actions);
+    agent.set_learning_engine(engine);
+
+    // Without the interlock the pump runs
+    let action = agent.step(low_tank.clone());
+    assert_eq!(action.action_type, pump.action_type);
+
+    agent.set_safety_layer(SafetyLayer::new().with_penalty(-50.0).veto(
+        pump.action_type.clone(),
+        Condition::below("tank_level", 5.0),
+        "tank level below 5%",
+    ));
+    for _ in 0..5 {
+        let action = agent.step(low_tank.clone());
+        assert!(action.is_noop());
+    }
+    assert_eq!(agent.get_statistics().actions_vetoed, 5);
+    assert!(agent.learning_engine().get_q_value(&state, &pump_id) < favored);
+
+    // A full tank lifts the veto
+    agent.step(Observation::sensor("tank_level", 80.0));
+    assert_eq!(agent.get_statistics().actions_vetoed, 5);
+}
@@ -0,0 +1,294 @@
+use crate::models::ArchitecturalDecision;
+use globset::{GlobBuilder, GlobMatcher};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path};
+
+/// How a related-file pattern matched a path, weakest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum FileMatch {
+    /// A directory pattern ending in `/` that contains the path
+    Prefix,
+
+    /// A glob pattern (`*`, `**`, `?`, `[...]`, `{...}`)
+    Glob,
+
+    /// The exact path
+    Exact,
+}
+
+impl FileMatch {
+    pub fn as_str(&self) -> &str {
+        match self {
+            FileMatch::Prefix => "prefix",
+            FileMatch::Glob => "glob",
+            FileMatch::Exact => "exact",
+        }
+    }
+}
+
+/// A decision related to a file through one of its patterns
+#[derive(Debug, Clone)]
+pub struct FileDecision {
+    /// The related decision
+    pub decision: ArchitecturalDecision,
+
+    /// The related-file pattern that matched, as stored
+    pub pattern: String,
+
+    /// How the pattern matched
+    pub kind: FileMatch,
+}
+
+/// A decision ID matched to a path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternMatch {
+    pub decision_id: String,
+    pub pattern: String,
+    pub kind: FileMatch,
+}
+
+/// A related-file pattern compiled for matching
+#[derive(Debug, Clone)]
+enum FilePattern {
+    Exact(String),
+    Prefix(String),
+    Glob(GlobMatcher),
+}
+
+impl FilePattern {
+    fn compile(pattern: &str) -> Self {
+        let pattern = pattern.strip_prefix("./").unwrap_or(pattern);
+
+        if pattern.contains(['*', '?', '[', '{']) {
+            // `*` and `?` stay within one path segment; only `**` crosses `/`
+            match GlobBuilder::new(pattern).literal_separator(true).build() {
+                Ok(glob) => return FilePattern::Glob(glob.compile_matcher()),
+                Err(e) => log::warn!("Treating invalid glob {:?} as a path: {}", pattern, e),
+            }
+        }
+
+        if pattern.ends_with('/') {
+            FilePattern::Prefix(pattern.to_string())
+        } else {
+            FilePattern::Exact(pattern.to_string())
+        }
+    }
+
+    fn matches(&self, path: &str) -> Option<FileMatch> {
+        match self {
+            FilePattern::Exact(exact) => (exact == path).then_some(FileMatch::Exact),
+            FilePattern::Prefix(prefix) => path
+                .starts_with(prefix.as_str())
+                .then_some(FileMatch::Prefix),
+            FilePattern::Glob(glob) => glob.is_match(path).then_some(FileMatch::Glob),
+        }
+    }
+}
+
+/// Matches paths against the related-file patterns of all decisions
+///
+/// Patterns are compiled once and results are cached per path, so a
+/// single matcher should serve a whole batch of files.
+#[derive(Debug)]
+pub struct FileMatcher {
+    /// Pattern as stored, compiled pattern and the decisions using it
+    patterns: Vec<(String, FilePattern, Vec<String>)>,
+
+    /// Matches already computed, by path
+    cache: HashMap<String, Vec<PatternMatch>>,
+}
+
+impl FileMatcher {
+    /// Build a matcher from a map of pattern to decision IDs
+    pub fn new(file_to_decisions: &HashMap<String, HashSet<String>>) -> Self {
+        let patterns = file_to_decisions
+            .iter()
+            .map(|(pattern, ids)| {
+                let mut ids: Vec<String> = ids.iter().cloned().collect();
+                ids.sort();
+                (pattern.clone(), FilePattern::compile(pattern), ids)
+            })
+            .collect();
+
+        Self {
+            patterns,
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Decisions whose patterns match a repository-relative path
+    ///
+    /// Each decision appears once, with its strongest match; results are
+    /// ordered exact, then glob, then prefix, then by decision ID.
+    pub fn matches(&mut self, path: &str) -> Vec<PatternMatch> {
+        if let Some(cached) = self.cache.get(path) {
+            return cached.clone();
+        }
+
+        let mut best: HashMap<&str, (FileMatch, &str)> = HashMap::new();
+        for (pattern, compiled, ids) in &self.patterns {
+            let Some(kind) = compiled.matches(path) else {
+                continue;
+            };
+            for id in ids {
+                let entry = best.entry(id).or_insert((kind, pattern));
+                if kind > entry.0 {
+                    *entry = (kind, pattern);
+                }
+            }
+        }
+
+        let mut matches: Vec<PatternMatch> = best
+            .into_iter()
+            .map(|(id, (kind, pattern))| PatternMatch {
+                decision_id: id.to_string(),
+                pattern: pattern.to_string(),
+                kind,
+            })
+            .collect();
+        matches.sort_by(|a, b| {
+            b.kind
+                .cmp(&a.kind)
+                .then_with(|| a.decision_id.cmp(&b.decision_id))
+        });
+
+        self.cache.insert(path.to_string(), matches.clone());
+        matches
+    }
+}
+
+/// `path` relative to the repository root, with `/` separators
+///
+/// Absolute paths must lie inside `root`. Returns `None` for paths that
+/// leave the repository or name the root itself.
+pub fn repo_relative(root: &Path, path: &str) -> Option<String> {
+    let path = Path::new(path);
+    let relative = if path.is_absolute() {
+        path.strip_prefix(root).ok()?
+    } else {
+        path
+    };
+
+    let mut parts: Vec<&str> = Vec::new();
+    for component in relative.components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_str()?),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                parts.pop()?;
+            }
+            Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pattern_matrix() {
+        use FileMatch::*;
+
+        let cases: &[(&str, &str, Option<FileMatch>)] = &[
+            ("src/lib.rs", "src/lib.rs", Some(Exact)),
+            ("./src/lib.rs", "src/lib.rs", Some(Exact)),
+            ("src/lib.rs", "src/lib.rs.bak", None),
+            ("src/Lib.rs", "src/lib.rs", None),
+            ("src/storage/**", "src/storage/sled.rs", Some(Glob)),
+            ("src/storage/**", "src/storage/backends/rocks.rs", Some(Glob)),
+            ("src/storage/**", "src/storagex/sled.rs", None),
+            ("src/storage/**", "SRC/storage/sled.rs", None),
+            ("src/*.rs", "src/main.rs", Some(Glob)),
+            ("src/*.rs", "src/bin/tool.rs", None),
+            ("**/*.toml", "Cargo.toml", Some(Glob)),
+            ("**/*.toml", "crates/aingle_graph/Cargo.toml", Some(Glob)),
+            ("src/mod?.rs", "src/mod1.rs", Some(Glob)),
+            ("src/mod?.rs", "src/mod10.rs", None),
+            ("src/{lib,main}.rs", "src/main.rs", Some(Glob)),
+            ("crates/aingle_graph/", "crates/aingle_graph/src/store.rs", Some(Prefix)),
+            ("crates/aingle_graph/", "crates/aingle_graph_ext/src/lib.rs", None),
+            ("crates/aingle_graph/", "Crates/aingle_graph/src/store.rs", None),
+            ("crates/aingle_graph", "crates/aingle_graph/src/store.rs", None),
+        ];
+
+        for (pattern, path, expected) in cases {
+            assert_eq!(
+                FilePattern::compile(pattern).matches(path),
+                *expected,
+                "{} against {}",
+                pattern,
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_matcher_precedence_and_cache() {
+        let mut file_to_decisions: HashMap<String, HashSet<String>> = HashMap::new();
+        let mut relate = |pattern: &str, id: &str| {
+            file_to_decisions
+                .entry(pattern.to_string())
+                .or_default()
+                .insert(id.to_string());
+        };
+        relate("crates/aingle_graph/", "ADR-001");
+        relate("crates/aingle_graph/src/*.rs", "ADR-002");
+        relate("crates/aingle_graph/src/store.rs", "ADR-003");
+        // ADR-004 relates through both a prefix and a glob; the glob wins
+        relate("crates/", "ADR-004");
+        relate("crates/**/store.rs", "ADR-004");
+
+        let mut matcher = FileMatcher::new(&file_to_decisions);
+        let matches = matcher.matches("crates/aingle_graph/src/store.rs");
+        let summary: Vec<(&str, FileMatch, &str)> = matches
+            .iter()
+            .map(|m| (m.decision_id.as_str(), m.kind, m.pattern.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "ADR-003",
+                    FileMatch::Exact,
+                    "crates/aingle_graph/src/store.rs"
+                ),
+                ("ADR-002", FileMatch::Glob, "crates/aingle_graph/src/*.rs"),
+                ("ADR-004", FileMatch::Glob, "crates/**/store.rs"),
+                ("ADR-001", FileMatch::Prefix, "crates/aingle_graph/"),
+            ]
+        );
+
+        assert_eq!(matcher.cache.len(), 1);
+        assert_eq!(matcher.matches("crates/aingle_graph/src/store.rs"), matches);
+        assert_eq!(matcher.cache.len(), 1);
+        assert!(matcher.matches("README.md").is_empty());
+    }
+
+    #[test]
+    fn test_repo_relative() {
+        let root = Path::new("/work/repo");
+
+        assert_eq!(
+            repo_relative(root, "src/lib.rs").as_deref(),
+            Some("src/lib.rs")
+        );
+        assert_eq!(
+            repo_relative(root, "./src/../src/lib.rs").as_deref(),
+            Some("src/lib.rs")
+        );
+        assert_eq!(
+            repo_relative(root, "/work/repo/src/lib.rs").as_deref(),
+            Some("src/lib.rs")
+        );
+
+        assert_eq!(repo_relative(root, "../other/src/lib.rs"), None);
+        assert_eq!(repo_relative(root, "src/../../lib.rs"), None);
+        assert_eq!(repo_relative(root, "/work/other/src/lib.rs"), None);
+        assert_eq!(repo_relative(root, "/work/repository/src/lib.rs"), None);
+        assert_eq!(repo_relative(root, "/work/repo"), None);
+    }
+}
@@ -0,0 +1,162 @@
+//! RDF vocabulary for exporting decisions into an AIngle graph
+//!
+//! Every decision becomes a node with these edges:
+//!
+//! | Predicate          | Object                                 |
+//! |--------------------|----------------------------------------|
+//! | `has_title`        | title literal                          |
+//! | `has_status`       | status literal (`Accepted`, ...)       |
+//! | `has_author`       | author node                            |
+//! | `supersedes`       | decision node it replaced              |
+//! | `relates_to_file`  | file node                              |
+//! | `tagged_with`      | tag node                               |
+//!
+//! Node IRIs are derived only from decision IDs, file paths, tags and
+//! authors, so exporting the same knowledge base twice yields the same
+//! triples.
+
+use crate::models::ArchitecturalDecision;
+use aingle_graph::{NodeId, Predicate, Triple, Value};
+use std::collections::BTreeSet;
+
+/// Namespace of the Deep Context vocabulary and node IRIs
+pub const NAMESPACE: &str = "https://aingle.ai/deep-context#";
+
+/// Decision → title literal
+pub const HAS_TITLE: &str = "has_title";
+/// Decision → status literal
+pub const HAS_STATUS: &str = "has_status";
+/// Decision → author node
+pub const HAS_AUTHOR: &str = "has_author";
+/// Newer decision → the decision it superseded
+pub const SUPERSEDES: &str = "supersedes";
+/// Decision → file node
+pub const RELATES_TO_FILE: &str = "relates_to_file";
+/// Decision → tag node
+pub const TAGGED_WITH: &str = "tagged_with";
+
+/// IRI of a vocabulary predicate
+pub fn predicate(name: &str) -> Predicate {
+    Predicate::named(format!("{}{}", NAMESPACE, name))
+}
+
+/// IRI of a decision node
+pub fn decision_node(id: &str) -> NodeId {
+    node("decision", id)
+}
+
+/// IRI of a file node, keyed by its repository-relative path
+pub fn file_node(path: &str) -> NodeId {
+    node("file", path)
+}
+
+/// IRI of a tag node
+pub fn tag_node(tag: &str) -> NodeId {
+    node("tag", tag)
+}
+
+/// IRI of an author node
+pub fn author_node(author: &str) -> NodeId {
+    node("author", author)
+}
+
+fn node(kind: &str, key: &str) -> NodeId {
+    NodeId::named(format!("{}{}/{}", NAMESPACE, kind, encode_segment(key)))
+}
+
+/// Triples describing one decision
+///
+/// Repeated tags or files are emitted once. The `supersedes` edge is not
+/// included here: it belongs to the newer decision and is derived from the
+/// superseded one's status by [`supersession_triple`].
+pub fn decision_triples(decision: &ArchitecturalDecision) -> Vec<Triple> {
+    let subject = decision_node(&decision.id);
+    let mut triples = vec![
+        Triple::new(
+            subject.clone(),
+            predicate(HAS_TITLE),
+            Value::literal(&decision.title),
+        ),
+        Triple::new(
+            subject.clone(),
+            predicate(HAS_STATUS),
+            Value::literal(decision.status.as_str()),
+        ),
+    ];
+
+    if !decision.author.is_empty() {
+        triples.push(Triple::new(
+            subject.clone(),
+            predicate(HAS_AUTHOR),
+            Value::node(author_node(&decision.author)),
+        ));
+    }
+
+    let files: BTreeSet<&str> = decision.related_files.iter().map(String::as_str).collect();
+    for file in files {
+        triples.push(Triple::new(
+            subject.clone(),
+            predicate(RELATES_TO_FILE),
+            Value::node(file_node(file)),
+        ));
+    }
+
+    let tags: BTreeSet<&str> = decision.tags.iter().map(String::as_str).collect();
+    for tag in tags {
+        triples.push(Triple::new(
+            subject.clone(),
+            predicate(TAGGED_WITH),
+            Value::node(tag_node(tag)),
+        ));
+    }
+
+    triples
+}
+
+/// The `supersedes` edge pointing at `superseded`, if it has been superseded
+pub fn supersession_triple(superseded: &ArchitecturalDecision) -> Option<Triple> {
+    superseded.superseded_by().map(|newer| {
+        Triple::new(
+            decision_node(newer),
+            predicate(SUPERSEDES),
+            Value::node(decision_node(&superseded.id)),
+        )
+    })
+}
+
+/// Percent-encode everything but unreserved characters and `/`
+///
+/// Keeps file paths readable while making tags with spaces or `#` safe to
+/// use inside an IRI.
+fn encode_segment(key: &str) -> String {
+    let mut encoded = String::with_capacity(key.len());
+    for byte in key.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~/".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_node_iris_are_encoded() {
+        assert_eq!(
+            tag_node("data store"),
+            NodeId::named("https://aingle.ai/deep-context#tag/data%20store")
+        );
+        assert_eq!(
+            file_node("src/db/mod.rs"),
+            NodeId::named("https://aingle.ai/deep-context#file/src/db/mod.rs")
+        );
+        assert_eq!(
+            author_node("dev@example.com"),
+            NodeId::named("https://aingle.ai/deep-context#author/dev%40example.com")
+        );
+    }
+}
@@ -1,11 +1,19 @@
+pub mod file_patterns;
 pub mod git_integration;
+pub mod knowledge_graph;
 pub mod models;
 pub mod semantic_index;
+pub mod text_index;
 
-use anyhow::Result;
+use aingle_graph::rdf::{RdfSerializer, TurtleSerializer};
+use aingle_graph::{GraphDB, Triple, TriplePattern};
+use anyhow::{Context, Result};
+use chrono::Utc;
+use file_patterns::FileDecision;
 use git_integration::GitIntegration;
 use models::{Alternative, ArchitecturalDecision, DecisionQuery, DecisionStatus};
 use semantic_index::SemanticIndex;
+use text_index::SearchHit;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -167,14 +175,85 @@ output_dir = "docs/decisions"
         self.index.query(query)
     }
 
+    /// Search decisions, with relevance scores and matched passages
+    pub fn search_decisions(&self, query: &DecisionQuery) -> Result<Vec<SearchHit>> {
+        self.index.search(query)
+    }
+
+    /// Rebuild the full-text index from the stored decisions
+    pub fn reindex(&mut self) -> Result<usize> {
+        self.index.reindex()
+    }
+
     /// Get a specific decision
     pub fn get_decision(&self, id: &str) -> Result<Option<ArchitecturalDecision>> {
         self.index.get_decision(id)
     }
 
-    /// Get decisions for a file
+    /// Supersede a decision with a newer one
+    ///
+    /// Marks `old_id` as superseded by `new_id`, records the reason and
+    /// time on it, and links `old_id` from the new decision. Fails if
+    /// either decision does not exist or `old_id` is already superseded.
+    pub fn supersede_decision(&mut self, old_id: &str, new_id: &str, reason: &str) -> Result<()> {
+        if old_id == new_id {
+            anyhow::bail!("Decision {} cannot supersede itself", old_id);
+        }
+
+        let mut old = self
+            .get_decision(old_id)?
+            .with_context(|| format!("Decision {} not found", old_id))?;
+        let mut new = self
+            .get_decision(new_id)?
+            .with_context(|| format!("Decision {} not found", new_id))?;
+
+        if let Some(by) = old.superseded_by() {
+            anyhow::bail!("Decision {} is already superseded by {}", old_id, by);
+        }
+        if let Some(by) = new.superseded_by() {
+            anyhow::bail!(
+                "Decision {} is itself superseded by {} and cannot supersede {}",
+                new_id,
+                by,
+                old_id
+            );
+        }
+
+        old.supersede_with_reason(new_id.to_string(), reason.to_string(), Utc::now());
+        new.link_decision(old_id.to_string());
+
+        self.index.store_decision(old)?;
+        self.index.store_decision(new)?;
+
+        log::info!("{} superseded by {}", old_id, new_id);
+
+        Ok(())
+    }
+
+    /// Get decisions for a file, best match first
+    ///
+    /// Relative paths are taken from the repository root; paths outside the
+    /// repository are rejected.
     pub fn decisions_for_file(&self, file_path: &str) -> Result<Vec<ArchitecturalDecision>> {
-        self.index.decisions_for_file(file_path)
+        let path = self.repo_path(file_path)?;
+        self.index.decisions_for_file(&path)
+    }
+
+    /// Get decisions for each of a batch of files
+    pub fn decisions_for_files(&self, file_paths: &[String]) -> Result<Vec<Vec<FileDecision>>> {
+        let paths = file_paths
+            .iter()
+            .map(|path| self.repo_path(path))
+            .collect::<Result<Vec<_>>>()?;
+        let paths: Vec<&str> = paths.iter().map(String::as_str).collect();
+
+        self.index.decisions_for_files(&paths)
+    }
+
+    /// A path relative to the repository root
+    fn repo_path(&self, file_path: &str) -> Result<String> {
+        file_patterns::repo_relative(&self.root_dir, file_path)
+            .with_context(|| format!("{} is outside the repository", file_path))
     }
 
     /// Get all tags
@@ -197,7 +276,7 @@ output_dir = "docs/decisions"
             let filename = format!("{}.md", decision.id);
             let file_path = output_dir.join(filename);
 
-            let markdown = self.decision_to_markdown(decision);
+            let markdown = self.decision_to_markdown(decision, &all_decisions);
             fs::write(&file_path, markdown)?;
 
             log::info!("Exported {} to {:?}", decision.id, file_path);
@@ -212,11 +291,21 @@ output_dir = "docs/decisions"
     }
 
     /// Convert a decision to Markdown format
-    fn decision_to_markdown(&self, decision: &ArchitecturalDecision) -> String {
+    ///
+    /// `all_decisions` is used to find the decisions this one supersedes.
+    fn decision_to_markdown(
+        &self,
+        decision: &ArchitecturalDecision,
+        all_decisions: &[ArchitecturalDecision],
+    ) -> String {
         let mut md = String::new();
 
         md.push_str(&format!("# {}: {}\n\n", decision.id, decision.title));
-        md.push_str(&format!("- **Status:** {}\n", decision.status.as_str()));
+        let status = match decision.superseded_by() {
+            Some(by) => format!("Superseded by [{}]({}.md)", by, by),
+            None => decision.status.as_str().to_string(),
+        };
+        md.push_str(&format!("- **Status:** {}\n", status));
         md.push_str(&format!("- **Date:** {}\n", decision.timestamp.format("%Y-%m-%d")));
         md.push_str(&format!("- **Author:** {}\n", decision.author));
 
@@ -277,6 +366,27 @@ output_dir = "docs/decisions"
             md.push_str("\n");
         }
 
+        let supersedes: Vec<&ArchitecturalDecision> = all_decisions
+            .iter()
+            .filter(|d| d.superseded_by() == Some(decision.id.as_str()))
+            .collect();
+
+        if !supersedes.is_empty() || decision.superseded_by().is_some() {
+            md.push_str("## Supersession\n\n");
+            for old in supersedes {
+                md.push_str(&format!("- Supersedes [{}]({}.md): {}", old.id, old.id, old.title));
+                md.push_str(&supersession_details(old));
+            }
+            if let Some(by) = decision.superseded_by() {
+                md.push_str(&format!("- Superseded by [{}]({}.md)", by, by));
+                if let Some(new) = all_decisions.iter().find(|d| d.id == by) {
+                    md.push_str(&format!(": {}", new.title));
+                }
+                md.push_str(&supersession_details(decision));
+            }
+            md.push('\n');
+        }
+
         if !decision.related_decisions.is_empty() {
             md.push_str("## Related Decisions\n\n");
             for related_id in &decision.related_decisions {
@@ -372,12 +482,62 @@ output_dir = "docs/decisions"
         md
     }
 
+    /// Build an in-memory AIngle graph of the knowledge base
+    ///
+    /// See [`knowledge_graph`] for the vocabulary. Supersession edges point
+    /// from the newer decision to the one it replaced.
+    pub fn export_graph(&self) -> Result<GraphDB> {
+        let graph = GraphDB::memory()?;
+        for triple in self.knowledge_triples()? {
+            graph.insert(triple)?;
+        }
+        Ok(graph)
+    }
+
+    /// Export the knowledge base as RDF Turtle
+    ///
+    /// Triples are written in a fixed order so that repeated exports of the
+    /// same decisions diff cleanly.
+    pub fn export_rdf(&self) -> Result<String> {
+        let mut triples = self.export_graph()?.find(TriplePattern::any())?;
+        triples.sort_by_cached_key(|t| {
+            (t.subject.clone(), t.predicate.clone(), t.object.to_string())
+        });
+        Ok(TurtleSerializer::serialize_triples(&triples)?)
+    }
+
+    /// Triples for every decision, sorted by decision ID
+    fn knowledge_triples(&self) -> Result<Vec<Triple>> {
+        let mut decisions = self.index.query(&DecisionQuery::default())?;
+        decisions.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let mut triples = Vec::new();
+        for decision in &decisions {
+            triples.extend(knowledge_graph::decision_triples(decision));
+            triples.extend(knowledge_graph::supersession_triple(decision));
+        }
+        Ok(triples)
+    }
+
     /// Get statistics about the knowledge base
     pub fn statistics(&self) -> Result<semantic_index::IndexStats> {
         self.index.statistics()
     }
 }
 
+/// Date and reason of a supersession, as the rest of a Markdown list item
+fn supersession_details(superseded: &ArchitecturalDecision) -> String {
+    let mut details = String::new();
+    if let Some(at) = superseded.superseded_at() {
+        details.push_str(&format!(" ({})", at.format("%Y-%m-%d")));
+    }
+    if let Some(reason) = superseded.supersession_reason() {
+        details.push_str(&format!(" — {}", reason));
+    }
+    details.push('\n');
+    details
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,9 +2,11 @@ use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use clap::{Parser, Subcommand};
 use colored::*;
+use deep_context::file_patterns::FileDecision;
 use deep_context::models::{Alternative, DecisionQuery};
 use deep_context::DeepContext;
 use dialoguer::{Confirm, Input, MultiSelect};
+use std::collections::HashMap;
 use std::env;
 use std::path::PathBuf;
 
@@ -136,6 +138,23 @@ enum Commands {
         id: String,
     },
 
+    /// Supersede a decision with a newer one
+    Supersede {
+        /// Decision ID being superseded (e.g., ADR-003)
+        id: String,
+
+        /// Decision ID that replaces it
+        #[arg(long)]
+        by: String,
+
+        /// Why the decision was superseded
+        #[arg(long)]
+        reason: String,
+    },
+
+    /// Rebuild the full-text search index from stored decisions
+    Reindex,
+
     /// Link a commit to a decision (used by Git hooks)
     LinkCommit {
         /// Decision ID
@@ -216,6 +235,8 @@ fn main() -> Result<()> {
         Commands::Stats => cmd_stats(repo_path),
         Commands::Tags => cmd_tags(repo_path),
         Commands::Show { id } => cmd_show(repo_path, id),
+        Commands::Supersede { id, by, reason } => cmd_supersede(repo_path, id, by, reason),
+        Commands::Reindex => cmd_reindex(repo_path),
         Commands::LinkCommit {
             decision_id,
             commit_ref,
@@ -449,7 +470,7 @@ fn cmd_query(
 
     query = query.with_date_range(since_date, until_date);
 
-    let results = deep_context.query_decisions(&query)?;
+    let results = deep_context.search_decisions(&query)?;
 
     if results.is_empty() {
         println!("{}", "No decisions found matching your query.".yellow());
@@ -464,8 +485,11 @@ fn cmd_query(
     );
     println!();
 
-    for decision in results {
-        print_decision_summary(&decision);
+    for hit in results {
+        print_decision_summary(&hit.decision);
+        for snippet in &hit.snippets {
+            println!("  {} {}", format!("{}:", snippet.field.as_str()).dimmed(), snippet.text);
+        }
         println!();
     }
 
@@ -530,8 +554,20 @@ fn cmd_export(repo_path: PathBuf, format: String, output: PathBuf) -> Result<()>
                     .bold()
             );
         }
+        "rdf" | "ttl" | "turtle" => {
+            std::fs::write(&output, deep_context.export_rdf()?)?;
+            println!(
+                "{}",
+                format!("✓ Exported decisions to {:?}", output)
+                    .green()
+                    .bold()
+            );
+        }
         _ => {
-            anyhow::bail!("Unsupported format: {}. Use 'markdown' or 'json'", format);
+            anyhow::bail!(
+                "Unsupported format: {}. Use 'markdown', 'json' or 'rdf'",
+                format
+            );
         }
     }
 
@@ -587,6 +623,32 @@ fn cmd_show(repo_path: PathBuf, id: String) -> Result<()> {
     Ok(())
 }
 
+fn cmd_supersede(repo_path: PathBuf, id: String, by: String, reason: String) -> Result<()> {
+    let mut deep_context = DeepContext::open(repo_path)?;
+
+    deep_context.supersede_decision(&id, &by, &reason)?;
+
+    println!(
+        "{}",
+        format!("✓ {} superseded by {}", id, by).green().bold()
+    );
+
+    Ok(())
+}
+
+fn cmd_reindex(repo_path: PathBuf) -> Result<()> {
+    let mut deep_context = DeepContext::open(repo_path)?;
+
+    let count = deep_context.reindex()?;
+
+    println!(
+        "{}",
+        format!("✓ Reindexed {} decision(s)", count).green().bold()
+    );
+
+    Ok(())
+}
+
 fn cmd_link_commit(repo_path: PathBuf, decision_id: String, commit_ref: String) -> Result<()> {
     let mut deep_context = DeepContext::open(repo_path)?;
 
@@ -607,14 +669,30 @@ fn cmd_link_commit(repo_path: PathBuf, decision_id: String, commit_ref: String)
 fn cmd_suggest_decisions(repo_path: PathBuf, files: Vec<String>) -> Result<()> {
     let deep_context = DeepContext::open(repo_path)?;
 
-    for file in files {
-        let decisions = deep_context.decisions_for_file(&file)?;
-
-        for decision in decisions {
-            println!("  {} - {}", decision.id, decision.title);
+    // Each decision once, with its strongest match across all files
+    let mut suggestions: HashMap<String, FileDecision> = HashMap::new();
+    for found in deep_context.decisions_for_files(&files)?.into_iter().flatten() {
+        match suggestions.get(&found.decision.id) {
+            Some(existing) if existing.kind >= found.kind => {}
+            _ => {
+                suggestions.insert(found.decision.id.clone(), found);
+            }
         }
     }
 
+    let mut suggestions: Vec<FileDecision> = suggestions.into_values().collect();
+    suggestions.sort_by(|a, b| b.kind.cmp(&a.kind).then_with(|| a.decision.id.cmp(&b.decision.id)));
+
+    for found in suggestions {
+        println!(
+            "  {} - {} ({} match: {})",
+            found.decision.id,
+            found.decision.title,
+            found.kind.as_str(),
+            found.pattern
+        );
+    }
+
     Ok(())
 }
 
@@ -694,6 +772,12 @@ fn print_decision_detailed(decision: &deep_context::models::ArchitecturalDecisio
     println!("{}", format!("# {}: {}", decision.id, decision.title).cyan().bold());
     println!();
     println!("Status:    {}", decision.status.as_str().green());
+    if let Some(by) = decision.superseded_by() {
+        println!("By:        {}", by.yellow());
+        if let Some(reason) = decision.supersession_reason() {
+            println!("Reason:    {}", reason);
+        }
+    }
     println!("Date:      {}", decision.timestamp.format("%Y-%m-%d %H:%M:%S"));
     println!("Author:    {}", decision.author);
 
@@ -2,6 +2,12 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Metadata key recording why a decision was superseded
+pub const SUPERSEDED_REASON_KEY: &str = "superseded_reason";
+
+/// Metadata key recording when a decision was superseded (RFC 3339)
+pub const SUPERSEDED_AT_KEY: &str = "superseded_at";
+
 /// Represents an architectural decision record
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArchitecturalDecision {
@@ -112,6 +118,39 @@ impl ArchitecturalDecision {
         self.status = DecisionStatus::Superseded(by_decision_id);
     }
 
+    /// Supersede this decision, recording why and when
+    pub fn supersede_with_reason(
+        &mut self,
+        by_decision_id: String,
+        reason: String,
+        at: DateTime<Utc>,
+    ) {
+        self.supersede(by_decision_id);
+        self.metadata.insert(SUPERSEDED_REASON_KEY.to_string(), reason);
+        self.metadata.insert(SUPERSEDED_AT_KEY.to_string(), at.to_rfc3339());
+    }
+
+    /// ID of the decision that superseded this one
+    pub fn superseded_by(&self) -> Option<&str> {
+        match &self.status {
+            DecisionStatus::Superseded(id) => Some(id),
+            _ => None,
+        }
+    }
+
+    /// Why this decision was superseded
+    pub fn supersession_reason(&self) -> Option<&str> {
+        self.metadata.get(SUPERSEDED_REASON_KEY).map(String::as_str)
+    }
+
+    /// When this decision was superseded
+    pub fn superseded_at(&self) -> Option<DateTime<Utc>> {
+        self.metadata
+            .get(SUPERSEDED_AT_KEY)
+            .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+            .map(|at| at.with_timezone(&Utc))
+    }
+
     /// Mark as deprecated
     pub fn deprecate(&mut self, reason: String) {
         self.status = DecisionStatus::Deprecated(reason);
@@ -415,4 +454,24 @@ mod tests {
         decision.supersede("ADR-002".to_string());
         assert!(!decision.status.is_active());
     }
+
+    #[test]
+    fn test_supersede_with_reason() {
+        let mut decision = ArchitecturalDecision::new(
+            "ADR-001".to_string(),
+            "Test".to_string(),
+            "Context".to_string(),
+            "Decision".to_string(),
+            "Rationale".to_string(),
+            "author".to_string(),
+        );
+
+        assert_eq!(decision.superseded_by(), None);
+
+        let at = Utc::now();
+        decision.supersede_with_reason("ADR-002".to_string(), "Outgrown".to_string(), at);
+        assert_eq!(decision.superseded_by(), Some("ADR-002"));
+        assert_eq!(decision.supersession_reason(), Some("Outgrown"));
+        assert_eq!(decision.superseded_at().map(|t| t.timestamp()), Some(at.timestamp()));
+    }
 }
@@ -1,4 +1,6 @@
+use crate::file_patterns::{FileDecision, FileMatcher};
 use crate::models::{ArchitecturalDecision, CodeContext, DecisionQuery, LinkedCommit};
+use crate::text_index::{self, SearchHit, TextIndex};
 use anyhow::{Context, Result};
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
@@ -111,8 +113,71 @@ impl SemanticIndex {
         Ok(())
     }
 
+    /// Rebuild the full-text index from the stored decisions
+    ///
+    /// Returns the number of decisions indexed.
+    pub fn reindex(&mut self) -> Result<usize> {
+        self.graph.text.clear();
+
+        let tree = self.db.open_tree("decisions")?;
+        for item in tree.iter() {
+            let (_, value) = item?;
+            let decision: ArchitecturalDecision = bincode::serde::decode_from_slice(&value, bincode::config::standard()).map(|(v, _)| v)?;
+            self.graph.text.insert(&decision);
+        }
+
+        Ok(self.graph.text.len())
+    }
+
+    /// Search decisions, ranking free-text matches by relevance
+    ///
+    /// Without a text query this returns the same decisions as
+    /// [`SemanticIndex::query`], unscored.
+    pub fn search(&self, query: &DecisionQuery) -> Result<Vec<SearchHit>> {
+        let Some(text) = &query.text else {
+            return Ok(self
+                .query(query)?
+                .into_iter()
+                .map(|decision| SearchHit {
+                    decision,
+                    score: 0.0,
+                    snippets: Vec::new(),
+                })
+                .collect());
+        };
+
+        let mut hits = Vec::new();
+        for (id, score) in self.graph.text.search(text) {
+            let Some(decision) = self.get_decision(&id)? else {
+                continue;
+            };
+            if !self.matches_query(&decision, query) {
+                continue;
+            }
+
+            let snippets = text_index::snippets(&decision, text);
+            hits.push(SearchHit {
+                decision,
+                score,
+                snippets,
+            });
+
+            if query.limit.is_some_and(|limit| hits.len() >= limit) {
+                break;
+            }
+        }
+
+        Ok(hits)
+    }
+
     /// Query decisions
+    ///
+    /// Text queries are ranked by relevance, everything else is newest first.
     pub fn query(&self, query: &DecisionQuery) -> Result<Vec<ArchitecturalDecision>> {
+        if query.text.is_some() {
+            return Ok(self.search(query)?.into_iter().map(|hit| hit.decision).collect());
+        }
+
         let mut results = Vec::new();
 
         let tree = self.db.open_tree("decisions")?;
@@ -136,21 +201,10 @@ impl SemanticIndex {
         Ok(results)
     }
 
-    /// Check if a decision matches a query
+    /// Check if a decision matches the non-text filters of a query
+    ///
+    /// Free text is matched by the text index in [`SemanticIndex::search`].
     fn matches_query(&self, decision: &ArchitecturalDecision, query: &DecisionQuery) -> bool {
-        // Text search
-        if let Some(text) = &query.text {
-            let text_lower = text.to_lowercase();
-            let matches_title = decision.title.to_lowercase().contains(&text_lower);
-            let matches_context = decision.context.to_lowercase().contains(&text_lower);
-            let matches_decision = decision.decision.to_lowercase().contains(&text_lower);
-            let matches_rationale = decision.rationale.to_lowercase().contains(&text_lower);
-
-            if !(matches_title || matches_context || matches_decision || matches_rationale) {
-                return false;
-            }
-        }
-
         // Tag filter
         if !query.tags.is_empty() {
             let has_all_tags = query
@@ -203,18 +257,47 @@ impl SemanticIndex {
         true
     }
 
-    /// Get decisions related to a file
+    /// Matcher over the related-file patterns of all decisions
+    pub fn file_matcher(&self) -> FileMatcher {
+        FileMatcher::new(&self.graph.file_to_decisions)
+    }
+
+    /// Get decisions related to a file, best match first
+    ///
+    /// `file_path` is relative to the repository root and is matched against
+    /// each decision's related-file patterns (exact paths, globs and
+    /// directory prefixes ending in `/`).
     pub fn decisions_for_file(&self, file_path: &str) -> Result<Vec<ArchitecturalDecision>> {
         Ok(self
-            .graph
-            .file_to_decisions
-            .get(file_path)
-            .map(|ids| {
-                ids.iter()
-                    .filter_map(|id| self.get_decision(id).ok().flatten())
-                    .collect()
+            .decisions_for_files(&[file_path])?
+            .into_iter()
+            .flatten()
+            .map(|found| found.decision)
+            .collect())
+    }
+
+    /// Get decisions related to each of a batch of files
+    ///
+    /// Patterns are compiled once for the whole batch.
+    pub fn decisions_for_files(&self, file_paths: &[&str]) -> Result<Vec<Vec<FileDecision>>> {
+        let mut matcher = self.file_matcher();
+
+        file_paths
+            .iter()
+            .map(|path| {
+                let mut found = Vec::new();
+                for m in matcher.matches(path) {
+                    if let Some(decision) = self.get_decision(&m.decision_id)? {
+                        found.push(FileDecision {
+                            decision,
+                            pattern: m.pattern,
+                            kind: m.kind,
+                        });
+                    }
+                }
+                Ok(found)
             })
-            .unwrap_or_default())
+            .collect()
     }
 
     /// Get decisions related to another decision
@@ -291,6 +374,9 @@ struct KnowledgeGraph {
 
     /// Commits
     commits: IndexMap<String, LinkedCommit>,
+
+    /// Full-text index over decision bodies
+    text: TextIndex,
 }
 
 impl KnowledgeGraph {
@@ -331,6 +417,7 @@ impl KnowledgeGraph {
                 .insert(id.clone());
         }
 
+        self.text.insert(&decision);
         self.decisions.insert(id, decision);
     }
 
@@ -359,6 +446,7 @@ pub struct IndexStats {
 mod tests {
     use super::*;
     use crate::models::DecisionStatus;
+    use crate::text_index::TextField;
     use chrono::Utc;
     use tempfile::TempDir;
 
@@ -431,4 +519,100 @@ mod tests {
         let results = index.decisions_by_tag("architecture").unwrap();
         assert_eq!(results.len(), 1);
     }
+
+    #[test]
+    fn test_search_ranks_by_field_weight() {
+        let (mut index, _temp) = create_test_index();
+
+        // Mentioned once, in the context
+        index
+            .store_decision(ArchitecturalDecision::new(
+                "ADR-001".to_string(),
+                "Database driver".to_string(),
+                "The old driver had no connection pool".to_string(),
+                "Use sqlx".to_string(),
+                "Async support".to_string(),
+                "author".to_string(),
+            ))
+            .unwrap();
+
+        // Only in the rationale, but discussed at length
+        index
+            .store_decision(ArchitecturalDecision::new(
+                "ADR-002".to_string(),
+                "Size database access per service".to_string(),
+                "Latency spikes under load".to_string(),
+                "Cap concurrent queries at 32".to_string(),
+                "A shared connection pool let one service starve the rest. \
+                 Each service now owns a connection pool, and every connection pool \
+                 is sized to its worker count, so connection pooling limits load."
+                    .to_string(),
+                "author".to_string(),
+            ))
+            .unwrap();
+
+        // Mentioned once, in the decision
+        index
+            .store_decision(ArchitecturalDecision::new(
+                "ADR-003".to_string(),
+                "Redis client".to_string(),
+                "Cache reads".to_string(),
+                "Use a connection pool for Redis".to_string(),
+                "Fewer handshakes".to_string(),
+                "author".to_string(),
+            ))
+            .unwrap();
+
+        let query = DecisionQuery::new().with_text("connection pooling".to_string());
+        let hits = index.search(&query).unwrap();
+
+        let ids: Vec<&str> = hits.iter().map(|h| h.decision.id.as_str()).collect();
+        assert_eq!(ids, vec!["ADR-002", "ADR-003", "ADR-001"]);
+        assert!(hits[0].score > hits[1].score && hits[1].score > hits[2].score);
+
+        assert_eq!(hits[0].snippets.len(), 1);
+        assert_eq!(hits[0].snippets[0].field, TextField::Rationale);
+        assert_eq!(
+            hits[0].snippets[0].text,
+            "A shared connection pool let one service starve the rest. Each service..."
+        );
+        assert_eq!(hits[2].snippets[0].field, TextField::Context);
+        assert_eq!(hits[2].snippets[0].text, "...old driver had no connection pool");
+
+        // Plain queries rank the same way
+        let results = index.query(&query.with_limit(1)).unwrap();
+        assert_eq!(results[0].id, "ADR-002");
+    }
+
+    #[test]
+    fn test_text_index_follows_updates() {
+        let (mut index, _temp) = create_test_index();
+
+        let mut decision = ArchitecturalDecision::new(
+            "ADR-001".to_string(),
+            "Use Redis".to_string(),
+            "Context".to_string(),
+            "Decision".to_string(),
+            "Rationale".to_string(),
+            "author".to_string(),
+        );
+        index.store_decision(decision.clone()).unwrap();
+
+        decision.title = "Use Memcached".to_string();
+        index.store_decision(decision).unwrap();
+
+        let search = |index: &SemanticIndex, text: &str| {
+            index
+                .query(&DecisionQuery::new().with_text(text.to_string()))
+                .unwrap()
+                .len()
+        };
+        assert_eq!(search(&index, "redis"), 0);
+        assert_eq!(search(&index, "memcached"), 1);
+
+        index.graph.text.clear();
+        assert_eq!(search(&index, "memcached"), 0);
+        assert_eq!(index.reindex().unwrap(), 1);
+        assert_eq!(search(&index, "memcached"), 1);
+    }
 }
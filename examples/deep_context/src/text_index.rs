@@ -0,0 +1,428 @@
+use crate::models::ArchitecturalDecision;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Words around the first match that make up a snippet
+const SNIPPET_WORDS: usize = 12;
+
+/// Words kept before the first match in a snippet
+const SNIPPET_LEAD: usize = 4;
+
+/// Words too common to be worth indexing
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "did", "do", "for", "from", "how", "in", "is",
+    "it", "of", "on", "or", "that", "the", "this", "to", "was", "we", "what", "why", "with",
+];
+
+/// Decision fields covered by full-text search
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TextField {
+    Title,
+    Decision,
+    Rationale,
+    Context,
+    Consequences,
+}
+
+impl TextField {
+    /// All indexed fields, highest weight first
+    pub const ALL: [TextField; 5] = [
+        TextField::Title,
+        TextField::Decision,
+        TextField::Rationale,
+        TextField::Context,
+        TextField::Consequences,
+    ];
+
+    /// How much a match in this field counts towards relevance
+    pub fn weight(&self) -> f64 {
+        match self {
+            TextField::Title => 5.0,
+            TextField::Decision => 3.0,
+            TextField::Rationale => 2.0,
+            TextField::Context => 1.0,
+            TextField::Consequences => 1.0,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            TextField::Title => "title",
+            TextField::Decision => "decision",
+            TextField::Rationale => "rationale",
+            TextField::Context => "context",
+            TextField::Consequences => "consequences",
+        }
+    }
+
+    /// The field's text in a decision
+    pub fn text<'a>(&self, decision: &'a ArchitecturalDecision) -> &'a str {
+        match self {
+            TextField::Title => &decision.title,
+            TextField::Decision => &decision.decision,
+            TextField::Rationale => &decision.rationale,
+            TextField::Context => &decision.context,
+            TextField::Consequences => &decision.consequences,
+        }
+    }
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+/// A passage of a decision that matched a search
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snippet {
+    /// Field the passage comes from
+    pub field: TextField,
+
+    /// The passage, with `...` where it was cut
+    pub text: String,
+}
+
+/// A decision matched by a full-text search
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    /// The matching decision
+    pub decision: ArchitecturalDecision,
+
+    /// Relevance score (higher is better; 0 when no text was searched)
+    pub score: f64,
+
+    /// Matched passages, best field first
+    pub snippets: Vec<Snippet>,
+}
+
+/// Inverted index over the text fields of decisions
+///
+/// Terms are lowercased, stop words dropped and suffixes stripped, so
+/// "pooling" finds "pool" and "connections" finds "connection".
+#[derive(Debug, Default)]
+pub struct TextIndex {
+    /// Map from term to decision ID to occurrences per field
+    postings: HashMap<String, HashMap<String, [u32; TextField::ALL.len()]>>,
+
+    /// Map from decision ID to its terms, for removal
+    documents: HashMap<String, HashSet<String>>,
+}
+
+impl TextIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of indexed decisions
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// Index a decision, replacing any earlier version of it
+    pub fn insert(&mut self, decision: &ArchitecturalDecision) {
+        self.remove(&decision.id);
+
+        let mut terms = HashSet::new();
+        for field in TextField::ALL {
+            for token in tokenize(field.text(decision)) {
+                let counts = self
+                    .postings
+                    .entry(token.term.clone())
+                    .or_default()
+                    .entry(decision.id.clone())
+                    .or_insert([0; TextField::ALL.len()]);
+                counts[field.index()] += 1;
+                terms.insert(token.term);
+            }
+        }
+
+        self.documents.insert(decision.id.clone(), terms);
+    }
+
+    /// Drop a decision from the index
+    pub fn remove(&mut self, decision_id: &str) {
+        let Some(terms) = self.documents.remove(decision_id) else {
+            return;
+        };
+
+        for term in terms {
+            if let Some(docs) = self.postings.get_mut(&term) {
+                docs.remove(decision_id);
+                if docs.is_empty() {
+                    self.postings.remove(&term);
+                }
+            }
+        }
+    }
+
+    /// Drop every decision from the index
+    pub fn clear(&mut self) {
+        self.postings.clear();
+        self.documents.clear();
+    }
+
+    /// IDs of decisions matching any term of `text`, best match first
+    ///
+    /// Each field contributes its weight times a damped term frequency,
+    /// scaled by how rare the term is across decisions. The total is then
+    /// scaled by the share of query terms the decision contains, so
+    /// decisions matching the whole query come first.
+    pub fn search(&self, text: &str) -> Vec<(String, f64)> {
+        let terms: HashSet<String> = tokenize(text).into_iter().map(|t| t.term).collect();
+        let total = self.documents.len() as f64;
+        let mut scores: HashMap<&str, (usize, f64)> = HashMap::new();
+
+        for docs in terms.iter().filter_map(|term| self.postings.get(term)) {
+            let idf = (1.0 + total / docs.len() as f64).ln();
+
+            for (id, counts) in docs {
+                let weighted: f64 = TextField::ALL
+                    .iter()
+                    .filter(|field| counts[field.index()] > 0)
+                    .map(|field| field.weight() * (1.0 + (counts[field.index()] as f64).ln()))
+                    .sum();
+
+                let entry = scores.entry(id).or_insert((0, 0.0));
+                entry.0 += 1;
+                entry.1 += weighted * idf;
+            }
+        }
+
+        let mut results: Vec<(String, f64)> = scores
+            .into_iter()
+            .map(|(id, (matched, score))| {
+                let coverage = matched as f64 / terms.len() as f64;
+                (id.to_string(), score * coverage)
+            })
+            .collect();
+
+        results.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        results
+    }
+}
+
+/// Passages of `decision` matching the terms of `text`, best field first
+pub fn snippets(decision: &ArchitecturalDecision, text: &str) -> Vec<Snippet> {
+    let terms: HashSet<String> = tokenize(text).into_iter().map(|t| t.term).collect();
+
+    TextField::ALL
+        .iter()
+        .filter_map(|&field| {
+            let body = field.text(decision);
+            let tokens = tokenize_words(body);
+            let first = tokens.iter().position(|t| terms.contains(&t.term))?;
+
+            let start = first.saturating_sub(SNIPPET_LEAD);
+            let end = (start + SNIPPET_WORDS).min(tokens.len());
+
+            let mut passage = String::new();
+            if start > 0 {
+                passage.push_str("...");
+            }
+            passage.push_str(&body[tokens[start].start..tokens[end - 1].end]);
+            if end < tokens.len() {
+                passage.push_str("...");
+            }
+
+            Some(Snippet {
+                field,
+                text: passage,
+            })
+        })
+        .collect()
+}
+
+/// A word of indexed text
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Token {
+    /// Normalized term
+    term: String,
+
+    /// Byte offsets of the word in the original text
+    start: usize,
+    end: usize,
+}
+
+/// Split text into indexable terms
+fn tokenize(text: &str) -> Vec<Token> {
+    tokenize_words(text)
+        .into_iter()
+        .filter(|t| !STOP_WORDS.contains(&t.term.as_str()))
+        .collect()
+}
+
+/// Split text into words, keeping stop words so snippets stay aligned
+fn tokenize_words(text: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+
+    for (i, c) in text
+        .char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+    {
+        match (c.is_alphanumeric(), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                let word = text[s..i].to_lowercase();
+                tokens.push(Token {
+                    term: stem(&word),
+                    start: s,
+                    end: i,
+                });
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    tokens
+}
+
+/// Strip common English suffixes
+///
+/// A light stemmer rather than full Porter: it only needs to map the
+/// inflections of a word onto the same term.
+fn stem(word: &str) -> String {
+    const SUFFIXES: &[(&str, &str)] = &[
+        ("ingly", ""),
+        ("edly", ""),
+        ("ings", ""),
+        ("ions", ""),
+        ("ness", ""),
+        ("ment", ""),
+        ("ies", "y"),
+        ("ied", "y"),
+        ("ing", ""),
+        ("ion", ""),
+        ("ly", ""),
+        ("ed", ""),
+    ];
+    const MIN_STEM: usize = 3;
+
+    if !word.is_ascii() {
+        return word.to_string();
+    }
+
+    let mut stem = SUFFIXES
+        .iter()
+        .find_map(|(suffix, replacement)| {
+            let base = word.strip_suffix(suffix)?;
+            (base.len() >= MIN_STEM).then(|| format!("{}{}", base, replacement))
+        })
+        .unwrap_or_else(|| strip_plural(word).to_string());
+
+    if stem.len() > MIN_STEM && stem.ends_with('e') {
+        stem.pop();
+    }
+
+    stem
+}
+
+/// Drop a plural `s` or `es`, leaving words like "class" alone
+fn strip_plural(word: &str) -> &str {
+    if word.ends_with("ss") || word.len() <= 3 {
+        return word;
+    }
+    if let Some(base) = word.strip_suffix("es") {
+        if ["s", "x", "z", "ch", "sh"]
+            .iter()
+            .any(|end| base.ends_with(end))
+        {
+            return base;
+        }
+    }
+    word.strip_suffix('s').unwrap_or(word)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decision(
+        id: &str,
+        title: &str,
+        context: &str,
+        decision: &str,
+        rationale: &str,
+    ) -> ArchitecturalDecision {
+        ArchitecturalDecision::new(
+            id.to_string(),
+            title.to_string(),
+            context.to_string(),
+            decision.to_string(),
+            rationale.to_string(),
+            "author".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_stem() {
+        assert_eq!(stem("pooling"), "pool");
+        assert_eq!(stem("pools"), "pool");
+        assert_eq!(stem("connections"), stem("connection"));
+        assert_eq!(stem("connected"), stem("connect"));
+        assert_eq!(stem("caches"), stem("caching"));
+        assert_eq!(stem("policies"), stem("policy"));
+        assert_eq!(stem("databases"), stem("database"));
+        assert_eq!(stem("class"), "class");
+    }
+
+    #[test]
+    fn test_search_prefers_all_terms() {
+        let mut index = TextIndex::new();
+        index.insert(&decision("ADR-001", "Connection pool", "", "", ""));
+        index.insert(&decision("ADR-002", "Connection timeouts", "", "", ""));
+
+        let ids: Vec<String> = index
+            .search("connection pooling")
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(ids, vec!["ADR-001", "ADR-002"]);
+
+        index.insert(&decision("ADR-003", "Use Redis for sessions", "", "", ""));
+        let hits = index.search("Why did we choose Redis?");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].0, "ADR-003");
+
+        assert!(index.search("the").is_empty());
+        assert!(index.search("kafka").is_empty());
+    }
+
+    #[test]
+    fn test_reinsert_replaces_terms() {
+        let mut index = TextIndex::new();
+        index.insert(&decision("ADR-001", "Use Redis", "", "", ""));
+        index.insert(&decision("ADR-001", "Use Memcached", "", "", ""));
+
+        assert_eq!(index.len(), 1);
+        assert!(index.search("redis").is_empty());
+        assert_eq!(index.search("memcached").len(), 1);
+
+        index.remove("ADR-001");
+        assert!(index.is_empty());
+        assert!(index.postings.is_empty());
+    }
+
+    #[test]
+    fn test_snippets() {
+        let adr = decision(
+            "ADR-001",
+            "Database access",
+            "",
+            "",
+            "Opening a new socket per request was slow, so every service keeps a connection pool sized to its worker count and reuses it",
+        );
+
+        let found = snippets(&adr, "pooled connections");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].field, TextField::Rationale);
+        assert_eq!(
+            found[0].text,
+            "...every service keeps a connection pool sized to its worker count and..."
+        );
+    }
+}
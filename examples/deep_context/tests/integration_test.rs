@@ -1,3 +1,6 @@
+use aingle_graph::{NodeId, Triple, TriplePattern, Value};
+use deep_context::file_patterns::FileMatch;
+use deep_context::knowledge_graph::{self as kg, predicate};
 use deep_context::models::{Alternative, DecisionQuery, DecisionStatus};
 use deep_context::DeepContext;
 use std::fs;
@@ -293,3 +296,280 @@ fn test_next_decision_id() {
     let id2 = deep_context.next_decision_id().unwrap();
     assert_eq!(id2, "ADR-002");
 }
+
+fn capture_titled(deep_context: &mut DeepContext, title: &str) -> String {
+    deep_context
+        .capture_decision(
+            title.to_string(),
+            "Context".to_string(),
+            "Decision".to_string(),
+            "Rationale".to_string(),
+            vec![],
+            "".to_string(),
+            vec![],
+            vec![],
+        )
+        .unwrap()
+        .id
+}
+
+#[test]
+fn test_supersede_decision() {
+    let (_temp, repo_path) = create_test_repo();
+    let mut deep_context = DeepContext::init(repo_path).unwrap();
+
+    let old_id = capture_titled(&mut deep_context, "Use REST");
+    let new_id = capture_titled(&mut deep_context, "Use gRPC");
+
+    deep_context
+        .supersede_decision(&old_id, &new_id, "Streaming support")
+        .unwrap();
+
+    let old = deep_context.get_decision(&old_id).unwrap().unwrap();
+    assert_eq!(old.status, DecisionStatus::Superseded(new_id.clone()));
+    assert_eq!(old.supersession_reason(), Some("Streaming support"));
+    assert!(old.superseded_at().is_some());
+
+    let new = deep_context.get_decision(&new_id).unwrap().unwrap();
+    assert_eq!(new.status, DecisionStatus::Accepted);
+    assert_eq!(new.related_decisions, vec![old_id]);
+}
+
+#[test]
+fn test_supersede_decision_rejects_invalid() {
+    let (_temp, repo_path) = create_test_repo();
+    let mut deep_context = DeepContext::init(repo_path).unwrap();
+
+    let first = capture_titled(&mut deep_context, "First");
+    let second = capture_titled(&mut deep_context, "Second");
+    let third = capture_titled(&mut deep_context, "Third");
+
+    deep_context
+        .supersede_decision(&first, &second, "Better")
+        .unwrap();
+
+    // Already superseded
+    let err = deep_context
+        .supersede_decision(&first, &third, "Even better")
+        .unwrap_err();
+    assert!(err.to_string().contains("already superseded by ADR-002"));
+
+    // Nonexistent decisions
+    let err = deep_context
+        .supersede_decision("ADR-999", &third, "Missing")
+        .unwrap_err();
+    assert!(err.to_string().contains("ADR-999 not found"));
+    assert!(deep_context
+        .supersede_decision(&third, "ADR-999", "Missing")
+        .is_err());
+
+    // Nothing changed on the rejected calls
+    let first = deep_context.get_decision(&first).unwrap().unwrap();
+    assert_eq!(first.status, DecisionStatus::Superseded(second));
+    let third = deep_context.get_decision(&third).unwrap().unwrap();
+    assert!(third.related_decisions.is_empty());
+}
+
+#[test]
+fn test_export_supersession_chain() {
+    let (_temp, repo_path) = create_test_repo();
+    let mut deep_context = DeepContext::init(repo_path.clone()).unwrap();
+
+    let first = capture_titled(&mut deep_context, "Use REST");
+    let second = capture_titled(&mut deep_context, "Use gRPC");
+    let third = capture_titled(&mut deep_context, "Use Connect");
+
+    deep_context
+        .supersede_decision(&first, &second, "Streaming support")
+        .unwrap();
+    deep_context
+        .supersede_decision(&second, &third, "Browser clients")
+        .unwrap();
+
+    let output_dir = repo_path.join("docs");
+    deep_context.export_markdown(&output_dir).unwrap();
+
+    let first_md = fs::read_to_string(output_dir.join("ADR-001.md")).unwrap();
+    assert!(first_md.contains("- **Status:** Superseded by [ADR-002](ADR-002.md)"));
+    assert!(first_md.contains("- Superseded by [ADR-002](ADR-002.md): Use gRPC ("));
+    assert!(first_md.contains("— Streaming support"));
+
+    let second_md = fs::read_to_string(output_dir.join("ADR-002.md")).unwrap();
+    assert!(second_md.contains("## Supersession"));
+    assert!(second_md.contains("- Supersedes [ADR-001](ADR-001.md): Use REST ("));
+    assert!(second_md.contains("- Superseded by [ADR-003](ADR-003.md): Use Connect ("));
+    assert!(second_md.contains("— Browser clients"));
+
+    let third_md = fs::read_to_string(output_dir.join("ADR-003.md")).unwrap();
+    assert!(third_md.contains("- **Status:** Accepted"));
+    assert!(third_md.contains("- Supersedes [ADR-002](ADR-002.md): Use gRPC ("));
+    assert!(!third_md.contains("Superseded by"));
+
+    let index = fs::read_to_string(output_dir.join("README.md")).unwrap();
+    assert!(index.contains("## Superseded Decisions"));
+}
+
+#[test]
+fn test_export_graph_triples() {
+    let (_temp, repo_path) = create_test_repo();
+    let mut deep_context = DeepContext::init(repo_path).unwrap();
+
+    let id = deep_context
+        .capture_decision(
+            "Use Sled".to_string(),
+            "Context".to_string(),
+            "Decision".to_string(),
+            "Rationale".to_string(),
+            vec![],
+            "".to_string(),
+            vec!["src/storage.rs".to_string()],
+            vec!["storage".to_string(), "data store".to_string()],
+        )
+        .unwrap()
+        .id;
+
+    let graph = deep_context.export_graph().unwrap();
+    let subject = kg::decision_node(&id);
+    let mut triples: Vec<String> = graph
+        .find(TriplePattern::any())
+        .unwrap()
+        .iter()
+        .map(Triple::to_string)
+        .collect();
+    triples.sort();
+
+    let mut expected = [
+        Triple::new(
+            subject.clone(),
+            predicate(kg::HAS_TITLE),
+            Value::literal("Use Sled"),
+        ),
+        Triple::new(
+            subject.clone(),
+            predicate(kg::HAS_STATUS),
+            Value::literal("Accepted"),
+        ),
+        Triple::new(
+            subject.clone(),
+            predicate(kg::HAS_AUTHOR),
+            Value::node(kg::author_node("test@example.com")),
+        ),
+        Triple::new(
+            subject.clone(),
+            predicate(kg::RELATES_TO_FILE),
+            Value::node(kg::file_node("src/storage.rs")),
+        ),
+        Triple::new(
+            subject.clone(),
+            predicate(kg::TAGGED_WITH),
+            Value::node(kg::tag_node("storage")),
+        ),
+        Triple::new(
+            subject,
+            predicate(kg::TAGGED_WITH),
+            Value::node(kg::tag_node("data store")),
+        ),
+    ]
+    .iter()
+    .map(Triple::to_string)
+    .collect::<Vec<_>>();
+    expected.sort();
+    assert_eq!(triples, expected);
+}
+
+#[test]
+fn test_export_rdf_supersession() {
+    let (_temp, repo_path) = create_test_repo();
+    let mut deep_context = DeepContext::init(repo_path).unwrap();
+
+    let old_id = capture_titled(&mut deep_context, "Use REST");
+    let new_id = capture_titled(&mut deep_context, "Use gRPC");
+    deep_context
+        .supersede_decision(&old_id, &new_id, "Streaming support")
+        .unwrap();
+
+    let graph = deep_context.export_graph().unwrap();
+    let edges = graph
+        .find(TriplePattern::predicate(predicate(kg::SUPERSEDES)))
+        .unwrap();
+    assert_eq!(edges.len(), 1);
+    assert_eq!(edges[0].subject, kg::decision_node(&new_id));
+    assert_eq!(edges[0].object, Value::node(kg::decision_node(&old_id)));
+
+    let turtle = deep_context.export_rdf().unwrap();
+    let node_iri = |node: NodeId| match node {
+        NodeId::Named(iri) => format!("<{}>", iri),
+        other => panic!("unexpected node {:?}", other),
+    };
+    let edge = format!(
+        "<{}{}> {}",
+        kg::NAMESPACE,
+        kg::SUPERSEDES,
+        node_iri(kg::decision_node(&old_id))
+    );
+    assert!(turtle.contains(&edge), "missing {} in\n{}", edge, turtle);
+    assert!(turtle.contains("\"Superseded\""));
+
+    // Repeated exports are identical
+    assert_eq!(deep_context.export_rdf().unwrap(), turtle);
+}
+
+#[test]
+fn test_decisions_for_file_patterns() {
+    let (_temp, repo_path) = create_test_repo();
+    let mut deep_context = DeepContext::init(repo_path.clone()).unwrap();
+
+    for (title, file) in [
+        ("Graph crate layout", "crates/aingle_graph/"),
+        ("Storage backends", "crates/*/src/store.rs"),
+        ("Store API", "crates/aingle_graph/src/store.rs"),
+    ] {
+        deep_context
+            .capture_decision(
+                title.to_string(),
+                "Context".to_string(),
+                "Decision".to_string(),
+                "Rationale".to_string(),
+                vec![],
+                "".to_string(),
+                vec![file.to_string()],
+                vec![],
+            )
+            .unwrap();
+    }
+
+    // Exact, then glob, then prefix
+    let ids: Vec<String> = deep_context
+        .decisions_for_file("crates/aingle_graph/src/store.rs")
+        .unwrap()
+        .into_iter()
+        .map(|d| d.id)
+        .collect();
+    assert_eq!(ids, vec!["ADR-003", "ADR-002", "ADR-001"]);
+
+    // Absolute paths inside the repository work too
+    let absolute = repo_path.join("crates/aingle_graph/src/lib.rs");
+    let results = deep_context
+        .decisions_for_file(absolute.to_str().unwrap())
+        .unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].id, "ADR-001");
+
+    // Paths outside the repository are rejected
+    let err = deep_context
+        .decisions_for_file("../elsewhere/src/store.rs")
+        .unwrap_err();
+    assert!(err.to_string().contains("outside the repository"));
+
+    // Batches keep the order of the files given
+    let batch = deep_context
+        .decisions_for_files(&[
+            "README.md".to_string(),
+            "crates/aingle_ai/src/store.rs".to_string(),
+        ])
+        .unwrap();
+    assert!(batch[0].is_empty());
+    assert_eq!(batch[1].len(), 1);
+    assert_eq!(batch[1][0].decision.id, "ADR-002");
+    assert_eq!(batch[1][0].kind, FileMatch::Glob);
+}
@@ -84,6 +84,7 @@ impl AuditTrail {
         data.insert("alert_id".to_string(), serde_json::to_value(&alert.id)?);
         data.insert("severity".to_string(), serde_json::to_value(&alert.severity)?);
         data.insert("confidence".to_string(), serde_json::to_value(alert.confidence)?);
+        data.insert("category".to_string(), serde_json::to_value(alert.category)?);
         data.insert("matched_list".to_string(), serde_json::to_value(&alert.matched_list)?);
 
         let entry = self.create_entry(
@@ -91,8 +92,9 @@ impl AuditTrail {
             Some(alert.entity_id.clone()),
             user_id.to_string(),
             format!(
-                "Alert {} created: {} severity, {:.2} confidence",
+                "Alert {} created: {} {} severity, {:.2} confidence",
                 alert.id,
+                alert.category.as_str(),
                 alert.severity.as_str(),
                 alert.confidence
             ),
@@ -280,6 +282,173 @@ impl AuditTrail {
         Ok(entry)
     }
 
+    /// Record a change of an entity's PEP status
+    pub fn record_pep_status(
+        &mut self,
+        entity_id: &str,
+        user_id: &str,
+        status: Option<&PepStatus>,
+    ) -> Result<AuditEntry> {
+        info!("Recording PEP status change for entity: {}", entity_id);
+
+        let mut data = HashMap::new();
+        data.insert("status".to_string(), serde_json::to_value(status)?);
+
+        let description = match status {
+            Some(status) if status.is_pep => format!("Entity {} marked as PEP", entity_id),
+            Some(status) if status.exposure > 0.0 => format!(
+                "Entity {} PEP exposure set to {:.2}",
+                entity_id, status.exposure
+            ),
+            Some(_) => format!("Entity {} marked as not a PEP", entity_id),
+            None => format!("Entity {} PEP exposure cleared", entity_id),
+        };
+
+        let entry = self.create_entry(
+            AuditEventType::PepStatusChanged,
+            Some(entity_id.to_string()),
+            user_id.to_string(),
+            description,
+            AuditResult::Success,
+            data,
+        )?;
+
+        self.add_entry(entry.clone())?;
+
+        Ok(entry)
+    }
+
+    /// Record a new investigation case
+    pub fn record_case_created(&mut self, case: &Case, user_id: &str) -> Result<AuditEntry> {
+        info!("Recording case creation: {}", case.id);
+
+        let mut data = HashMap::new();
+        data.insert("case_id".to_string(), serde_json::to_value(&case.id)?);
+        data.insert("title".to_string(), serde_json::to_value(&case.title)?);
+        data.insert("entity_ids".to_string(), serde_json::to_value(&case.entity_ids)?);
+        data.insert("alert_ids".to_string(), serde_json::to_value(&case.alert_ids)?);
+
+        let entry = self.create_entry(
+            AuditEventType::CaseCreated,
+            None,
+            user_id.to_string(),
+            format!(
+                "Case {} opened: {} ({} alerts, {} entities)",
+                case.id,
+                case.title,
+                case.alert_ids.len(),
+                case.entity_ids.len()
+            ),
+            AuditResult::Success,
+            data,
+        )?;
+
+        self.add_entry(entry.clone())?;
+
+        Ok(entry)
+    }
+
+    /// Record alerts and entities added to a case
+    pub fn record_case_updated(
+        &mut self,
+        case_id: &str,
+        user_id: &str,
+        entity_ids: &[String],
+        alert_ids: &[String],
+    ) -> Result<AuditEntry> {
+        info!("Recording case update: {}", case_id);
+
+        let mut data = HashMap::new();
+        data.insert("case_id".to_string(), serde_json::to_value(case_id)?);
+        data.insert("entity_ids".to_string(), serde_json::to_value(entity_ids)?);
+        data.insert("alert_ids".to_string(), serde_json::to_value(alert_ids)?);
+
+        let entry = self.create_entry(
+            AuditEventType::CaseUpdated,
+            None,
+            user_id.to_string(),
+            format!(
+                "Case {} extended: {} alerts, {} entities added",
+                case_id,
+                alert_ids.len(),
+                entity_ids.len()
+            ),
+            AuditResult::Success,
+            data,
+        )?;
+
+        self.add_entry(entry.clone())?;
+
+        Ok(entry)
+    }
+
+    /// Record a case assignment
+    pub fn record_case_assigned(
+        &mut self,
+        case_id: &str,
+        user_id: &str,
+        assignee: &str,
+    ) -> Result<AuditEntry> {
+        info!("Recording case assignment: {} -> {}", case_id, assignee);
+
+        let mut data = HashMap::new();
+        data.insert("case_id".to_string(), serde_json::to_value(case_id)?);
+        data.insert("assignee".to_string(), serde_json::to_value(assignee)?);
+
+        let entry = self.create_entry(
+            AuditEventType::CaseAssigned,
+            None,
+            user_id.to_string(),
+            format!("Case {} assigned to {}", case_id, assignee),
+            AuditResult::Success,
+            data,
+        )?;
+
+        self.add_entry(entry.clone())?;
+
+        Ok(entry)
+    }
+
+    /// Record a case status transition
+    pub fn record_case_transition(
+        &mut self,
+        case_id: &str,
+        user_id: &str,
+        from: &CaseStatus,
+        to: &CaseStatus,
+        notes: &str,
+    ) -> Result<AuditEntry> {
+        info!("Recording case transition: {} {:?} -> {:?}", case_id, from, to);
+
+        let mut data = HashMap::new();
+        data.insert("case_id".to_string(), serde_json::to_value(case_id)?);
+        data.insert("from".to_string(), serde_json::to_value(from)?);
+        data.insert("to".to_string(), serde_json::to_value(to)?);
+        data.insert("notes".to_string(), serde_json::to_value(notes)?);
+
+        let entry = self.create_entry(
+            AuditEventType::CaseStatusChanged,
+            None,
+            user_id.to_string(),
+            format!("Case {} moved from {} to {}", case_id, from.as_str(), to.as_str()),
+            AuditResult::Success,
+            data,
+        )?;
+
+        self.add_entry(entry.clone())?;
+
+        Ok(entry)
+    }
+
+    /// Entries recorded for a case, oldest first
+    pub fn get_case_entries(&self, case_id: &str) -> Vec<&AuditEntry> {
+        self.entries.iter()
+            .filter(|entry| {
+                entry.data.get("case_id").and_then(|v| v.as_str()) == Some(case_id)
+            })
+            .collect()
+    }
+
     /// Generate compliance audit report
     pub fn generate_report(&self, period: ReportingPeriod) -> Result<AuditReport> {
         info!("Generating audit report for period: {}", period.description);
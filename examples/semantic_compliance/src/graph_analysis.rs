@@ -1,7 +1,8 @@
 //! Graph analysis for relationship detection
 //!
 //! This module provides graph-based analysis to detect hidden relationships,
-//! beneficial ownership structures, and suspicious entity clusters.
+//! beneficial ownership structures, suspicious entity clusters, and the PEP
+//! exposure of relatives and associates of politically exposed persons.
 
 use crate::models::*;
 use anyhow::Result;
@@ -9,7 +10,7 @@ use serde::{Deserialize, Serialize};
 use petgraph::graph::{DiGraph, NodeIndex};
 use petgraph::algo::kosaraju_scc;
 use petgraph::visit::EdgeRef;
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use tracing::{debug, info};
 
 // ============================================================================
@@ -464,6 +465,76 @@ impl GraphAnalyzer {
         neighborhood
     }
 
+    /// Compute the PEP exposure of every entity within `max_hops` active
+    /// relationships of one of the `peps`
+    ///
+    /// Relationships are followed in both directions. Each hop multiplies the
+    /// exposure by the closeness of the relationship (see
+    /// [`exposure_factor`]), and an entity keeps the highest exposure over
+    /// all paths and PEPs. The PEPs themselves have exposure 1.0 at 0 hops.
+    /// Results are sorted by exposure, highest first.
+    pub fn pep_exposure(&self, peps: &HashSet<String>, max_hops: usize) -> Vec<PepExposure> {
+        info!("Computing PEP exposure from {} PEPs within {} hops", peps.len(), max_hops);
+
+        // Best exposure of each node as (score, hops, originating PEP)
+        let mut best: BTreeMap<NodeIndex, (f64, usize, NodeIndex)> = BTreeMap::new();
+        let mut frontier: BTreeMap<NodeIndex, (f64, NodeIndex)> = BTreeMap::new();
+        for pep in peps {
+            if let Some(&idx) = self.entity_index.get(pep) {
+                best.insert(idx, (1.0, 0, idx));
+                frontier.insert(idx, (1.0, idx));
+            }
+        }
+
+        // Exposure of walks of exactly `hop` relationships; a walk that
+        // revisits a node never beats the shorter one, as factors are below 1
+        for hop in 1..=max_hops {
+            let mut next: BTreeMap<NodeIndex, (f64, NodeIndex)> = BTreeMap::new();
+            for (&node, &(score, pep)) in &frontier {
+                let outgoing = self.graph.edges(node).map(|e| (e.target(), e.weight()));
+                let incoming = self.graph
+                    .edges_directed(node, petgraph::Direction::Incoming)
+                    .map(|e| (e.source(), e.weight()));
+
+                for (neighbor, edge) in outgoing.chain(incoming) {
+                    if !edge.is_active {
+                        continue;
+                    }
+                    let exposure = score * exposure_factor(&edge.relationship_type);
+                    if next.get(&neighbor).is_none_or(|&(s, _)| exposure > s) {
+                        next.insert(neighbor, (exposure, pep));
+                    }
+                }
+            }
+
+            for (&node, &(score, pep)) in &next {
+                if best.get(&node).is_none_or(|&(s, _, _)| score > s) {
+                    best.insert(node, (score, hop, pep));
+                }
+            }
+            frontier = next;
+        }
+
+        let mut exposures: Vec<PepExposure> = best.into_iter()
+            .filter(|(idx, _)| self.entities.contains_key(&self.graph[*idx].id))
+            .map(|(idx, (score, hops, pep))| PepExposure {
+                entity_id: self.graph[idx].id.clone(),
+                score,
+                hops,
+                pep_entity_id: self.graph[pep].id.clone(),
+            })
+            .collect();
+        exposures.sort_by(|a, b| {
+            b.score.partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.entity_id.cmp(&b.entity_id))
+        });
+
+        debug!("{} entities exposed to PEPs", exposures.len());
+
+        exposures
+    }
+
     /// Get statistics about the graph
     pub fn get_statistics(&self) -> GraphStatistics {
         let node_count = self.graph.node_count();
@@ -504,6 +575,26 @@ impl Default for GraphAnalyzer {
     }
 }
 
+/// Share of a PEP's exposure passed on through one relationship
+///
+/// Family members and business partners are the close associates PEP rules
+/// name explicitly; control relationships come next, and looser ties pass
+/// on the least.
+pub fn exposure_factor(relationship_type: &RelationshipType) -> f64 {
+    match relationship_type {
+        RelationshipType::Family | RelationshipType::Partner => 0.8,
+        RelationshipType::Owner
+        | RelationshipType::BeneficialOwner
+        | RelationshipType::Director
+        | RelationshipType::Signatory
+        | RelationshipType::Parent
+        | RelationshipType::Subsidiary => 0.6,
+        RelationshipType::Shareholder
+        | RelationshipType::Associate
+        | RelationshipType::Custom(_) => 0.4,
+    }
+}
+
 // ============================================================================
 // Graph Node and Edge Types
 // ============================================================================
@@ -554,6 +645,18 @@ pub struct PathStep {
     pub ownership_percent: Option<f64>,
 }
 
+/// PEP exposure of one entity
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PepExposure {
+    pub entity_id: String,
+    /// Exposure (0.0 - 1.0), 1.0 for the PEP itself
+    pub score: f64,
+    /// Relationship hops to the PEP
+    pub hops: usize,
+    /// The PEP the exposure comes from
+    pub pep_entity_id: String,
+}
+
 /// Ownership tree structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OwnershipTree {
@@ -621,6 +724,7 @@ mod tests {
             last_checked: Utc::now(),
             created_at: Utc::now(),
             metadata: HashMap::new(),
+            pep_status: None,
         }
     }
 
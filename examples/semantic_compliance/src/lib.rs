@@ -5,6 +5,7 @@
 //! - Semantic entity matching
 //! - Risk scoring and assessment
 //! - Graph-based relationship analysis
+//! - PEP screening, with exposure derived through relationships
 //! - Immutable audit trails
 //!
 //! ## Example Usage
@@ -30,6 +31,7 @@
 //!     # last_checked: chrono::Utc::now(),
 //!     # created_at: chrono::Utc::now(),
 //!     # metadata: std::collections::HashMap::new(),
+//!     # pep_status: None,
 //! };
 //!
 //! system.add_entity(entity).await?;
@@ -49,6 +51,7 @@
 pub mod audit_trail;
 pub mod graph_analysis;
 pub mod models;
+pub mod notifications;
 pub mod risk_scoring;
 pub mod sanctions_monitor;
 
@@ -56,15 +59,26 @@ pub mod sanctions_monitor;
 pub use audit_trail::{AuditTrail, CheckResult, ExportFormat, VerificationResult};
 pub use graph_analysis::{
     ClusterAlgorithm, EntityCluster, GraphAnalyzer, GraphStatistics, OwnershipTree, Path,
+    PepExposure,
 };
 pub use models::*;
+pub use notifications::{
+    FailedNotification, Notification, NotificationEvent, NotificationSink, Notifier, WebhookSink,
+};
 pub use risk_scoring::{RiskEngine, RiskExplanation, RiskWeights};
-pub use sanctions_monitor::{SanctionMatch, SanctionsMonitor, SanctionsStatistics, SemanticMatcher};
+pub use sanctions_monitor::{
+    parse_eu_xml, parse_sdn_csv, ImportReport, NameComparison, RomanizationScheme, RowError,
+    SanctionMatch, SanctionsMonitor, SanctionsStatistics, Script, SemanticMatcher,
+    Transliteration,
+};
 
 use anyhow::Result;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use tracing::info;
 
+/// User recorded in the audit trail for scheduled re-screenings
+const SCREENING_USER: &str = "scheduler";
+
 // ============================================================================
 // Compliance System
 // ============================================================================
@@ -91,6 +105,12 @@ pub struct ComplianceSystem {
 
     /// Active alerts
     alerts: HashMap<String, ComplianceAlert>,
+
+    /// Investigation cases
+    cases: HashMap<String, Case>,
+
+    /// Alert notification delivery
+    notifier: Notifier,
 }
 
 impl ComplianceSystem {
@@ -102,6 +122,7 @@ impl ComplianceSystem {
         let risk_engine = RiskEngine::new(config.risk_scoring.clone());
         let graph_analyzer = GraphAnalyzer::new();
         let audit_trail = AuditTrail::new();
+        let notifier = Notifier::new(&config.alerts);
 
         Self {
             config,
@@ -111,6 +132,8 @@ impl ComplianceSystem {
             audit_trail,
             entities: HashMap::new(),
             alerts: HashMap::new(),
+            cases: HashMap::new(),
+            notifier,
         }
     }
 
@@ -132,34 +155,63 @@ impl ComplianceSystem {
         self.entities.get(entity_id)
     }
 
+    /// Load a sanctions list, replacing any list already loaded from its source
+    pub async fn load_sanctions_list(&mut self, list: SanctionsList) {
+        self.sanctions_monitor.insert_list(list).await;
+    }
+
     /// Check an entity against sanctions lists
     pub async fn check_entity(&mut self, entity_id: &str) -> Result<Vec<SanctionMatch>> {
-        let entity = self.entities.get(entity_id)
-            .ok_or_else(|| anyhow::anyhow!("Entity not found"))?.clone();
-
-        let matches = self.sanctions_monitor.check_entity(&entity).await?;
+        self.check_entity_at(entity_id, "system", chrono::Utc::now()).await
+    }
 
-        // Record in audit trail
-        let result = audit_trail::CheckResult {
-            matches: matches.iter().map(|m| m.entry.id.clone()).collect(),
-            lists_checked: matches.iter()
-                .map(|m| m.source.as_str().to_string())
-                .collect::<std::collections::HashSet<_>>()
-                .into_iter()
-                .collect(),
-            timestamp: chrono::Utc::now(),
+    /// Re-screen every entity whose periodic screening is due at `now`
+    ///
+    /// An entity is due once the cadence for its risk level (see
+    /// [`ScreeningConfig`]) has passed since it was last checked. Each
+    /// screening is recorded in the audit trail and raises alerts like
+    /// [`ComplianceSystem::check_entity`]. A failed screening is reported and
+    /// retried on the next run.
+    pub async fn run_due_screenings(&mut self, now: chrono::DateTime<chrono::Utc>) -> ScreeningRunReport {
+        let mut due: Vec<String> = self.entities.values()
+            .filter(|e| e.last_checked + self.config.screening.cadence(&e.risk_level) <= now)
+            .map(|e| e.id.clone())
+            .collect();
+        due.sort();
+
+        let alerts_before = self.alerts.len();
+        let mut report = ScreeningRunReport {
+            run_at: now,
+            screened: Vec::new(),
+            matches: 0,
+            alerts_raised: 0,
+            failures: Vec::new(),
         };
 
-        self.audit_trail.record_check(entity_id, "system", result)?;
-
-        // Create alerts for high-confidence matches
-        for m in &matches {
-            if m.confidence >= self.config.matching.critical_threshold {
-                self.create_alert(&entity, m)?;
+        for entity_id in due {
+            match self.check_entity_at(&entity_id, SCREENING_USER, now).await {
+                Ok(matches) => {
+                    report.matches += matches.len();
+                    report.screened.push(entity_id);
+                }
+                Err(e) => report.failures.push(ScreeningFailure {
+                    entity_id,
+                    error: e.to_string(),
+                }),
             }
         }
 
-        Ok(matches)
+        report.alerts_raised = self.alerts.len() - alerts_before;
+        info!("Screening run: {} screened, {} alerts raised",
+            report.screened.len(), report.alerts_raised);
+
+        report
+    }
+
+    /// When an entity's next periodic screening is due
+    pub fn next_due(&self, entity_id: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.entities.get(entity_id)
+            .map(|e| e.last_checked + self.config.screening.cadence(&e.risk_level))
     }
 
     /// Assess risk for an entity
@@ -181,6 +233,90 @@ impl ComplianceSystem {
         Ok(assessment)
     }
 
+    /// Set whether an entity is a PEP, as declared or confirmed by `user_id`
+    ///
+    /// A status set this way is kept over later list matches and derived
+    /// exposure, so clearing a false PEP match sticks.
+    pub fn set_pep_status(&mut self, entity_id: &str, is_pep: bool, user_id: &str) -> Result<()> {
+        let entity = self.entities.get_mut(entity_id)
+            .ok_or_else(|| anyhow::anyhow!("Entity not found"))?;
+
+        let status = PepStatus::direct(
+            is_pep,
+            PepProvenance::Manual { user_id: user_id.to_string() },
+            chrono::Utc::now(),
+        );
+        self.audit_trail.record_pep_status(entity_id, user_id, Some(&status))?;
+        entity.pep_status = Some(status);
+
+        Ok(())
+    }
+
+    /// Derive the PEP exposure of every entity related to a PEP
+    ///
+    /// Entities within [`PepMatchingConfig::max_hops`] relationships of an
+    /// entity whose status marks it as a PEP get a derived [`PepStatus`]
+    /// with the exposure computed by [`GraphAnalyzer::pep_exposure`].
+    /// Statuses set for the entity itself are left alone, and derived
+    /// statuses of entities no longer in reach are cleared. Changes are
+    /// recorded in the audit trail.
+    pub fn update_pep_exposure(&mut self, user_id: &str) -> Result<PepExposureReport> {
+        let pep_config = &self.config.matching.pep;
+        let peps: HashSet<String> = self.entities.values()
+            .filter(|e| e.pep_status.as_ref().is_some_and(|s| s.is_pep))
+            .map(|e| e.id.clone())
+            .collect();
+
+        let exposures = self.graph_analyzer.pep_exposure(&peps, pep_config.max_hops);
+        let flagged: Vec<String> = exposures.iter()
+            .filter(|e| e.score >= pep_config.exposure_alert_threshold)
+            .map(|e| e.entity_id.clone())
+            .collect();
+
+        let now = chrono::Utc::now();
+        let mut derived: HashMap<&str, PepStatus> = exposures.iter()
+            .filter(|e| e.hops > 0)
+            .map(|e| {
+                let status = PepStatus {
+                    is_pep: false,
+                    exposure: e.score,
+                    provenance: PepProvenance::Relationship {
+                        pep_entity_id: e.pep_entity_id.clone(),
+                        hops: e.hops,
+                    },
+                    updated_at: now,
+                };
+                (e.entity_id.as_str(), status)
+            })
+            .collect();
+
+        let mut entity_ids: Vec<String> = self.entities.keys().cloned().collect();
+        entity_ids.sort();
+        for entity_id in entity_ids {
+            let entity = self.entities.get_mut(&entity_id).expect("listed entity");
+            if entity.pep_status.as_ref().is_some_and(|s| !s.is_derived()) {
+                continue;
+            }
+            let status = derived.remove(entity_id.as_str());
+            let unchanged = match (&entity.pep_status, &status) {
+                (Some(old), Some(new)) => {
+                    old.exposure == new.exposure && old.provenance == new.provenance
+                }
+                (None, None) => true,
+                _ => false,
+            };
+            if !unchanged {
+                self.audit_trail.record_pep_status(&entity_id, user_id, status.as_ref())?;
+                entity.pep_status = status;
+            }
+        }
+
+        info!("PEP exposure: {} PEPs, {} exposed entities, {} flagged",
+            peps.len(), exposures.len(), flagged.len());
+
+        Ok(PepExposureReport { exposures, flagged })
+    }
+
     /// Find connections between entities
     pub fn find_connections(&self, entity_a: &str, entity_b: &str) -> Result<Vec<Path>> {
         self.graph_analyzer.find_connections(entity_a, entity_b)
@@ -197,6 +333,9 @@ impl ComplianceSystem {
     }
 
     /// Get all active alerts
+    ///
+    /// Alerts grouped into an investigation carry its ID in
+    /// [`ComplianceAlert::case_id`].
     pub fn get_alerts(&self, severity: Option<AlertSeverity>) -> Vec<&ComplianceAlert> {
         self.alerts.values()
             .filter(|alert| {
@@ -236,6 +375,212 @@ impl ComplianceSystem {
         Ok(())
     }
 
+    /// Open an investigation case linking related entities and alerts
+    ///
+    /// An alert belongs to at most one case. Returns the new case ID.
+    pub fn create_case(
+        &mut self,
+        title: &str,
+        entity_ids: Vec<String>,
+        alert_ids: Vec<String>,
+        user_id: &str,
+    ) -> Result<String> {
+        self.check_case_members(&entity_ids, &alert_ids)?;
+
+        let now = chrono::Utc::now();
+        let case = Case {
+            id: format!("CASE-{}-{}", now.timestamp(), uuid::Uuid::new_v4()),
+            title: title.to_string(),
+            entity_ids,
+            alert_ids,
+            status: CaseStatus::Open,
+            assigned_to: None,
+            created_at: now,
+            updated_at: now,
+        };
+
+        self.audit_trail.record_case_created(&case, user_id)?;
+
+        for alert_id in &case.alert_ids {
+            if let Some(alert) = self.alerts.get_mut(alert_id) {
+                alert.case_id = Some(case.id.clone());
+            }
+        }
+        let case_id = case.id.clone();
+        self.cases.insert(case_id.clone(), case);
+
+        info!("Opened case {}", case_id);
+        Ok(case_id)
+    }
+
+    /// Add entities and alerts to an open case
+    pub fn add_to_case(
+        &mut self,
+        case_id: &str,
+        entity_ids: Vec<String>,
+        alert_ids: Vec<String>,
+        user_id: &str,
+    ) -> Result<()> {
+        let case = self.cases.get(case_id)
+            .ok_or_else(|| anyhow::anyhow!("Case not found"))?;
+        if !case.status.is_open() {
+            return Err(anyhow::anyhow!("Case {} is closed", case_id));
+        }
+        let entity_ids: Vec<String> = entity_ids.into_iter()
+            .filter(|id| !case.entity_ids.contains(id))
+            .collect();
+        let alert_ids: Vec<String> = alert_ids.into_iter()
+            .filter(|id| !case.alert_ids.contains(id))
+            .collect();
+        self.check_case_members(&entity_ids, &alert_ids)?;
+
+        self.audit_trail.record_case_updated(case_id, user_id, &entity_ids, &alert_ids)?;
+
+        for alert_id in &alert_ids {
+            if let Some(alert) = self.alerts.get_mut(alert_id) {
+                alert.case_id = Some(case_id.to_string());
+            }
+        }
+        if let Some(case) = self.cases.get_mut(case_id) {
+            case.entity_ids.extend(entity_ids);
+            case.alert_ids.extend(alert_ids);
+            case.updated_at = chrono::Utc::now();
+        }
+
+        Ok(())
+    }
+
+    /// Assign a case to an analyst
+    pub fn assign_case(&mut self, case_id: &str, assignee: &str, user_id: &str) -> Result<()> {
+        let case = self.cases.get_mut(case_id)
+            .ok_or_else(|| anyhow::anyhow!("Case not found"))?;
+
+        self.audit_trail.record_case_assigned(case_id, user_id, assignee)?;
+
+        case.assigned_to = Some(assignee.to_string());
+        case.updated_at = chrono::Utc::now();
+
+        Ok(())
+    }
+
+    /// Move a case to a new status
+    ///
+    /// Fails without changing the case if [`CaseStatus::can_transition_to`]
+    /// does not allow the move. Use [`ComplianceSystem::close_case`] to
+    /// resolve the case's alerts along with it.
+    pub fn transition_case(
+        &mut self,
+        case_id: &str,
+        next: CaseStatus,
+        notes: &str,
+        user_id: &str,
+    ) -> Result<()> {
+        let case = self.cases.get_mut(case_id)
+            .ok_or_else(|| anyhow::anyhow!("Case not found"))?;
+        if !case.status.can_transition_to(&next) {
+            return Err(anyhow::anyhow!(
+                "Invalid case transition: {} -> {}",
+                case.status.as_str(),
+                next.as_str()
+            ));
+        }
+
+        self.audit_trail.record_case_transition(case_id, user_id, &case.status, &next, notes)?;
+
+        info!("Case {} moved to {}", case_id, next.as_str());
+        case.status = next;
+        case.updated_at = chrono::Utc::now();
+
+        Ok(())
+    }
+
+    /// Close a case with `outcome`
+    ///
+    /// With `resolve_alerts` set, every unresolved alert in the case is
+    /// resolved with the outcome's [`AlertStatus`] and that one resolution
+    /// note.
+    pub fn close_case(
+        &mut self,
+        case_id: &str,
+        outcome: CaseOutcome,
+        notes: &str,
+        resolve_alerts: bool,
+        user_id: &str,
+    ) -> Result<()> {
+        self.transition_case(case_id, CaseStatus::Closed { outcome }, notes, user_id)?;
+        if !resolve_alerts {
+            return Ok(());
+        }
+
+        let mut alert_ids: Vec<String> = self.cases[case_id].alert_ids.iter()
+            .filter(|id| self.alerts.get(*id).is_some_and(|a| a.resolved_at.is_none()))
+            .cloned()
+            .collect();
+        alert_ids.sort();
+        for alert_id in alert_ids {
+            self.resolve_alert(&alert_id, outcome.alert_status(), notes, user_id)?;
+        }
+
+        Ok(())
+    }
+
+    /// Get a case by ID
+    pub fn get_case(&self, case_id: &str) -> Option<&Case> {
+        self.cases.get(case_id)
+    }
+
+    /// Get all cases, optionally only those still open
+    pub fn get_cases(&self, open_only: bool) -> Vec<&Case> {
+        self.cases.values()
+            .filter(|case| !open_only || case.status.is_open())
+            .collect()
+    }
+
+    /// Escalate every new alert left unreviewed past its deadline at `now`
+    ///
+    /// Deadlines come from the [`EscalationConfig`]. Escalated alerts move to
+    /// [`AlertStatus::Escalated`] and are notified again. Returns the IDs of
+    /// the escalated alerts.
+    pub async fn run_escalations(&mut self, now: chrono::DateTime<chrono::Utc>) -> Vec<String> {
+        let escalation = &self.config.alerts.escalation;
+        let mut escalated: Vec<String> = self.alerts.values()
+            .filter(|a| a.status == AlertStatus::New)
+            .filter(|a| {
+                escalation.escalation_delay(&a.severity)
+                    .is_some_and(|delay| a.created_at + delay <= now)
+            })
+            .map(|a| a.id.clone())
+            .collect();
+        escalated.sort();
+
+        for alert_id in &escalated {
+            if let Some(alert) = self.alerts.get_mut(alert_id) {
+                alert.status = AlertStatus::Escalated;
+                self.notifier.notify(NotificationEvent::AlertEscalated, alert);
+            }
+        }
+
+        if !escalated.is_empty() {
+            info!("Escalated {} alerts", escalated.len());
+        }
+        escalated
+    }
+
+    /// Register an additional notification sink
+    pub fn add_notification_sink(&mut self, sink: std::sync::Arc<dyn NotificationSink>) {
+        self.notifier.add_sink(sink);
+    }
+
+    /// Notifications that could not be delivered after all retries
+    pub fn get_failed_notifications(&self) -> Vec<FailedNotification> {
+        self.notifier.failed()
+    }
+
+    /// Wait for every notification sent so far to be delivered or fail
+    pub async fn flush_notifications(&self) {
+        self.notifier.flush().await;
+    }
+
     /// Generate compliance report
     pub fn generate_report(&self, period: ReportingPeriod) -> Result<AuditReport> {
         self.audit_trail.generate_report(period)
@@ -254,6 +599,12 @@ impl ComplianceSystem {
         let active_alerts = self.alerts.values()
             .filter(|a| !matches!(a.status, AlertStatus::Cleared | AlertStatus::FalsePositive))
             .count();
+        let open_cases = self.cases.values()
+            .filter(|c| c.status.is_open())
+            .count();
+        let escalated_cases = self.cases.values()
+            .filter(|c| c.status == CaseStatus::EscalatedToSAR)
+            .count();
 
         ComplianceStatistics {
             total_entities: self.entities.len(),
@@ -264,6 +615,8 @@ impl ComplianceSystem {
             sanctions_lists_loaded: sanctions_stats.total_lists,
             total_sanctions_entries: sanctions_stats.total_entries,
             graph_connections: graph_stats.total_relationships,
+            open_cases,
+            escalated_cases,
         }
     }
 
@@ -271,15 +624,111 @@ impl ComplianceSystem {
     // Internal Methods
     // ========================================================================
 
+    /// Check that case members exist and no alert is already in a case
+    fn check_case_members(&self, entity_ids: &[String], alert_ids: &[String]) -> Result<()> {
+        if let Some(id) = entity_ids.iter().find(|id| !self.entities.contains_key(*id)) {
+            return Err(anyhow::anyhow!("Entity not found: {}", id));
+        }
+        for alert_id in alert_ids {
+            let alert = self.alerts.get(alert_id)
+                .ok_or_else(|| anyhow::anyhow!("Alert not found: {}", alert_id))?;
+            if let Some(case_id) = &alert.case_id {
+                return Err(anyhow::anyhow!("Alert {} already belongs to case {}", alert_id, case_id));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check an entity as `user_id` at `now`, updating its `last_checked`
+    async fn check_entity_at(
+        &mut self,
+        entity_id: &str,
+        user_id: &str,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<SanctionMatch>> {
+        let entity = self.entities.get(entity_id)
+            .ok_or_else(|| anyhow::anyhow!("Entity not found"))?.clone();
+
+        let matches = self.sanctions_monitor.check_entity(&entity).await?;
+
+        // Record in audit trail
+        let result = audit_trail::CheckResult {
+            matches: matches.iter().map(|m| m.entry.id.clone()).collect(),
+            lists_checked: matches.iter()
+                .map(|m| m.source.as_str().to_string())
+                .collect::<std::collections::HashSet<_>>()
+                .into_iter()
+                .collect(),
+            timestamp: now,
+        };
+
+        self.audit_trail.record_check(entity_id, user_id, result)?;
+
+        if let Some(entity) = self.entities.get_mut(entity_id) {
+            entity.last_checked = now;
+        }
+
+        // Create alerts for high-confidence matches
+        for m in &matches {
+            let category = m.source.category();
+            if m.confidence < self.config.matching.alert_threshold(category) {
+                continue;
+            }
+            self.create_alert(&entity, m)?;
+
+            if category == ListCategory::PEP {
+                self.record_pep_match(entity_id, user_id, m, now)?;
+            }
+        }
+
+        Ok(matches)
+    }
+
+    /// Mark an entity as a PEP after an alerting PEP list match, unless its
+    /// status was set manually or by an earlier match
+    fn record_pep_match(
+        &mut self,
+        entity_id: &str,
+        user_id: &str,
+        match_info: &SanctionMatch,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<()> {
+        let Some(entity) = self.entities.get_mut(entity_id) else {
+            return Ok(());
+        };
+        if entity.pep_status.as_ref().is_some_and(|s| !s.is_derived()) {
+            return Ok(());
+        }
+
+        let status = PepStatus::direct(
+            true,
+            PepProvenance::ListMatch {
+                source: match_info.source.clone(),
+                entry_id: match_info.entry.id.clone(),
+                confidence: match_info.confidence,
+            },
+            now,
+        );
+        self.audit_trail.record_pep_status(entity_id, user_id, Some(&status))?;
+        entity.pep_status = Some(status);
+
+        Ok(())
+    }
+
     fn create_alert(&mut self, entity: &Entity, match_info: &SanctionMatch) -> Result<()> {
         let alert_id = format!("ALERT-{}-{}", chrono::Utc::now().timestamp(), uuid::Uuid::new_v4());
 
-        let severity = if match_info.confidence >= 0.95 {
-            AlertSeverity::Critical
-        } else if match_info.confidence >= 0.85 {
-            AlertSeverity::High
-        } else {
-            AlertSeverity::Medium
+        // A PEP match calls for enhanced due diligence, not a freeze
+        let category = match_info.source.category();
+        let severity = match category {
+            ListCategory::PEP => AlertSeverity::High,
+            ListCategory::Sanctions if match_info.confidence >= 0.95 => AlertSeverity::Critical,
+            ListCategory::Sanctions if match_info.confidence >= 0.85 => AlertSeverity::High,
+            ListCategory::Sanctions => AlertSeverity::Medium,
+        };
+        let kind = match category {
+            ListCategory::Sanctions => "Sanctions",
+            ListCategory::PEP => "PEP",
         };
 
         let alert = ComplianceAlert {
@@ -288,10 +737,12 @@ impl ComplianceSystem {
             entity_id: entity.id.clone(),
             entity_name: entity.name.clone(),
             reason: format!(
-                "Sanctions match detected: {} (confidence: {:.2})",
+                "{} match detected: {} (confidence: {:.2})",
+                kind,
                 match_info.entry.names.first().unwrap_or(&"Unknown".to_string()),
                 match_info.confidence
             ),
+            category,
             matched_list: match_info.source.clone(),
             matched_entry: match_info.entry.clone(),
             confidence: match_info.confidence,
@@ -301,18 +752,25 @@ impl ComplianceSystem {
                 list_value: match_info.list_value.clone(),
                 algorithm: match_info.algorithm.clone(),
                 edit_distance: None,
-                context: HashMap::new(),
+                context: match_info
+                    .transliteration
+                    .iter()
+                    .map(|t| ("transliteration".to_string(), t.to_string()))
+                    .collect(),
             },
             created_at: chrono::Utc::now(),
             status: AlertStatus::New,
             assigned_to: None,
             resolution_notes: None,
             resolved_at: None,
+            case_id: None,
         };
 
         // Record in audit trail
         self.audit_trail.record_alert_created(&alert, "system")?;
 
+        self.notifier.notify(NotificationEvent::AlertCreated, &alert);
+
         // Store alert
         self.alerts.insert(alert_id, alert);
 
@@ -338,6 +796,7 @@ impl Default for ComplianceConfig {
             risk_scoring: RiskScoringConfig::default(),
             alerts: AlertConfig::default(),
             audit: AuditConfig::default(),
+            screening: ScreeningConfig::default(),
         }
     }
 }
@@ -350,6 +809,18 @@ impl Default for MatchingConfig {
             phonetic_matching: true,
             transliteration: false,
             max_edit_distance: 3,
+            pep: PepMatchingConfig::default(),
+        }
+    }
+}
+
+impl Default for PepMatchingConfig {
+    fn default() -> Self {
+        Self {
+            match_threshold: 0.85,
+            alert_threshold: 0.9,
+            max_hops: 3,
+            exposure_alert_threshold: 0.5,
         }
     }
 }
@@ -385,6 +856,9 @@ impl Default for AlertConfig {
             slack_webhook: None,
             sms: vec![],
             escalation: EscalationConfig::default(),
+            webhooks: vec![],
+            routing: NotificationRouting::default(),
+            delivery: DeliveryConfig::default(),
         }
     }
 }
@@ -400,6 +874,28 @@ impl Default for EscalationConfig {
     }
 }
 
+impl Default for NotificationRouting {
+    fn default() -> Self {
+        Self {
+            critical: SinkRoute::All,
+            high: SinkRoute::All,
+            medium: SinkRoute::Only(vec!["webhook".to_string()]),
+            low: SinkRoute::Off,
+            info: SinkRoute::Off,
+        }
+    }
+}
+
+impl Default for DeliveryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff_ms: 500,
+            max_backoff_ms: 30_000,
+        }
+    }
+}
+
 impl Default for AuditConfig {
     fn default() -> Self {
         Self {
@@ -411,6 +907,18 @@ impl Default for AuditConfig {
     }
 }
 
+impl Default for ScreeningConfig {
+    fn default() -> Self {
+        Self {
+            critical_days: 1,
+            high_days: 1,
+            medium_days: 7,
+            low_days: 30,
+            minimal_days: 90,
+        }
+    }
+}
+
 // ============================================================================
 // Statistics
 // ============================================================================
@@ -435,6 +943,54 @@ pub struct ComplianceStatistics {
 
     /// Graph connections
     pub graph_connections: usize,
+
+    /// Investigation cases not yet closed
+    #[serde(default)]
+    pub open_cases: usize,
+
+    /// Open cases escalated for a SAR
+    #[serde(default)]
+    pub escalated_cases: usize,
+}
+
+/// Outcome of [`ComplianceSystem::run_due_screenings`]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ScreeningRunReport {
+    /// Time the run screened at
+    pub run_at: chrono::DateTime<chrono::Utc>,
+
+    /// Entities re-screened, by ID
+    pub screened: Vec<String>,
+
+    /// Sanctions matches found across all screened entities
+    pub matches: usize,
+
+    /// Alerts raised by this run
+    pub alerts_raised: usize,
+
+    /// Entities whose screening failed
+    pub failures: Vec<ScreeningFailure>,
+}
+
+/// Outcome of [`ComplianceSystem::update_pep_exposure`]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PepExposureReport {
+    /// Exposure of every PEP and related entity, highest first
+    pub exposures: Vec<PepExposure>,
+
+    /// Entities at or above [`PepMatchingConfig::exposure_alert_threshold`],
+    /// highest exposure first
+    pub flagged: Vec<String>,
+}
+
+/// A scheduled screening that failed
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ScreeningFailure {
+    /// Entity that was being screened
+    pub entity_id: String,
+
+    /// Why the screening failed
+    pub error: String,
 }
 
 // ============================================================================
@@ -469,6 +1025,7 @@ mod tests {
             last_checked: chrono::Utc::now(),
             created_at: chrono::Utc::now(),
             metadata: HashMap::new(),
+            pep_status: None,
         };
 
         system.add_entity(entity).await.unwrap();
@@ -476,4 +1033,376 @@ mod tests {
         let stats = system.get_statistics().await;
         assert_eq!(stats.total_entities, 1);
     }
+
+    fn entity_checked_at(
+        id: &str,
+        name: &str,
+        risk_level: RiskLevel,
+        last_checked: chrono::DateTime<chrono::Utc>,
+    ) -> Entity {
+        Entity {
+            id: id.to_string(),
+            name: name.to_string(),
+            entity_type: EntityType::Company,
+            aliases: vec![],
+            identifiers: vec![],
+            relationships: vec![],
+            risk_score: 0.0,
+            risk_level,
+            last_checked,
+            created_at: last_checked,
+            metadata: HashMap::new(),
+            pep_status: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_due_screenings_follow_risk_cadence() {
+        use chrono::TimeZone;
+
+        let start = chrono::Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).unwrap();
+        let mut system = ComplianceSystem::new(ComplianceConfig::default());
+        for entity in [
+            entity_checked_at("HIGH-1", "Northwind Freight", RiskLevel::High, start),
+            entity_checked_at("HIGH-2", "Contoso Metals", RiskLevel::High, start),
+            entity_checked_at("LOW-1", "Fabrikam Bakery", RiskLevel::Low, start),
+        ] {
+            system.add_entity(entity).await.unwrap();
+        }
+        assert_eq!(system.next_due("LOW-1"), Some(start + chrono::Duration::days(30)));
+
+        // Nothing is due before its first cadence has passed
+        assert!(system.run_due_screenings(start).await.screened.is_empty());
+
+        let mut screenings: HashMap<String, usize> = HashMap::new();
+        for day in 1..=31 {
+            let now = start + chrono::Duration::days(day);
+            let report = system.run_due_screenings(now).await;
+            assert!(report.failures.is_empty());
+
+            let expected: Vec<&str> = if day == 30 {
+                vec!["HIGH-1", "HIGH-2", "LOW-1"]
+            } else {
+                vec!["HIGH-1", "HIGH-2"]
+            };
+            assert_eq!(report.screened, expected, "day {}", day);
+            for id in report.screened {
+                *screenings.entry(id).or_default() += 1;
+            }
+
+            // A second run at the same time finds nothing left to screen
+            assert!(system.run_due_screenings(now).await.screened.is_empty());
+        }
+
+        assert_eq!(screenings["LOW-1"], 1);
+        assert_eq!(screenings["HIGH-1"], 31);
+        assert_eq!(screenings["HIGH-2"], 31);
+        assert_eq!(
+            system.next_due("LOW-1"),
+            Some(start + chrono::Duration::days(60))
+        );
+        assert_eq!(system.next_due("MISSING"), None);
+
+        let low_checks = system
+            .audit_trail
+            .get_entity_entries("LOW-1")
+            .iter()
+            .filter(|e| e.user_id == SCREENING_USER)
+            .count();
+        assert_eq!(low_checks, 1);
+    }
+
+    #[tokio::test]
+    async fn test_due_screening_raises_alerts() {
+        let start = chrono::Utc::now() - chrono::Duration::days(2);
+        let mut system = ComplianceSystem::new(ComplianceConfig::default());
+        system
+            .add_entity(entity_checked_at("HIGH-1", "Example Shipping Lines", RiskLevel::High, start))
+            .await
+            .unwrap();
+        let report = parse_sdn_csv(
+            b"101,\"EXAMPLE SHIPPING LINES\",-0- ,\"SDGT\",-0- ,-0- ,-0- ,-0- ,-0- ,-0- ,-0- ,-0- \n",
+            None,
+            None,
+        );
+        system.load_sanctions_list(report.into_list(SanctionSource::OFAC)).await;
+
+        let report = system.run_due_screenings(chrono::Utc::now()).await;
+        assert_eq!(report.screened, vec!["HIGH-1"]);
+        assert_eq!(report.matches, 1);
+        assert_eq!(report.alerts_raised, 1);
+        assert_eq!(system.get_alerts(None).len(), 1);
+    }
+
+    fn relationship(target: &str, relationship_type: RelationshipType) -> Relationship {
+        Relationship {
+            target_entity_id: target.to_string(),
+            relationship_type,
+            ownership_percent: None,
+            established_date: None,
+            is_active: true,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pep_exposure_through_relationships() {
+        let now = chrono::Utc::now();
+        let mut system = ComplianceSystem::new(ComplianceConfig::default());
+
+        // A is a PEP, B is A's business partner, C is three hops from A
+        let mut a = entity_checked_at("A", "Elena Marchetti", RiskLevel::Low, now);
+        a.entity_type = EntityType::Person;
+        let mut b = entity_checked_at("B", "Northwind Advisory", RiskLevel::Low, now);
+        b.relationships.push(relationship("A", RelationshipType::Partner));
+        let mut d = entity_checked_at("D", "Contoso Metals", RiskLevel::Low, now);
+        d.relationships.push(relationship("B", RelationshipType::Associate));
+        let mut c = entity_checked_at("C", "Fabrikam Bakery", RiskLevel::Low, now);
+        c.relationships.push(relationship("D", RelationshipType::Associate));
+        for entity in [a, b, c, d] {
+            system.add_entity(entity).await.unwrap();
+        }
+
+        let source = SanctionSource::PEP("WorldPEP".to_string());
+        system.load_sanctions_list(SanctionsList {
+            id: "worldpep-1".to_string(),
+            source: source.clone(),
+            entries: vec![SanctionEntry {
+                id: "PEP-7".to_string(),
+                names: vec!["ELENA MARCHETTI".to_string()],
+                aliases: vec![],
+                entity_type: EntityType::Person,
+                programs: vec![],
+                identifiers: vec![],
+                addresses: vec![],
+                dates_of_birth: vec![],
+                nationalities: vec![],
+                remarks: Some("Deputy minister of finance".to_string()),
+                listed_date: None,
+            }],
+            last_updated: now,
+            version: "1".to_string(),
+        }).await;
+
+        for id in ["A", "B", "C", "D"] {
+            system.check_entity(id).await.unwrap();
+        }
+
+        // The direct match raises a PEP alert, not a sanctions alert
+        let alerts = system.get_alerts(None);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].entity_id, "A");
+        assert_eq!(alerts[0].category, ListCategory::PEP);
+        assert_eq!(alerts[0].severity, AlertSeverity::High);
+        assert_eq!(alerts[0].matched_list, source);
+        let status = system.get_entity("A").unwrap().pep_status.clone().unwrap();
+        assert!(status.is_pep);
+        assert!(matches!(
+            status.provenance,
+            PepProvenance::ListMatch { ref entry_id, .. } if entry_id == "PEP-7"
+        ));
+
+        let report = system.update_pep_exposure("analyst").unwrap();
+        let score = |id: &str| report.exposures.iter().find(|e| e.entity_id == id).unwrap().score;
+        assert_eq!(score("A"), 1.0);
+        assert!(score("A") > score("B"));
+        assert!(score("B") > score("C"));
+        assert_eq!(report.flagged, ids(&["A", "B"]));
+
+        let c_status = system.get_entity("C").unwrap().pep_status.clone().unwrap();
+        assert!(!c_status.is_pep);
+        assert_eq!(c_status.provenance, PepProvenance::Relationship {
+            pep_entity_id: "A".to_string(),
+            hops: 3,
+        });
+
+        // Exposure feeds the PEP risk factor, graded by distance
+        let pep_factor = |system: &ComplianceSystem, id: &str| {
+            let entity = system.get_entity(id).unwrap();
+            system.risk_engine.calculate_risk(entity).unwrap().factors.into_iter()
+                .find(|f| f.factor_type == RiskFactorType::PEP)
+                .map(|f| (f.score, f.weight))
+        };
+        let (a_score, a_weight) = pep_factor(&system, "A").unwrap();
+        let (b_score, b_weight) = pep_factor(&system, "B").unwrap();
+        let (c_score, _) = pep_factor(&system, "C").unwrap();
+        assert!(a_score > b_score && b_score > c_score);
+        assert_eq!(a_weight, b_weight);
+
+        // Clearing the PEP by hand removes the exposure derived from it
+        system.set_pep_status("A", false, "analyst").unwrap();
+        let report = system.update_pep_exposure("analyst").unwrap();
+        assert!(report.exposures.is_empty());
+        assert!(system.get_entity("B").unwrap().pep_status.is_none());
+        assert!(pep_factor(&system, "A").is_none());
+
+        // A later match does not override the analyst
+        system.check_entity("A").await.unwrap();
+        assert!(!system.get_entity("A").unwrap().pep_status.as_ref().unwrap().is_pep);
+        assert!(system.verify_audit_integrity().is_valid);
+    }
+
+    #[tokio::test]
+    async fn test_alerts_notify_on_creation_and_escalation() {
+        use notifications::tests::MockServer;
+        use notifications::EVENT_HEADER;
+
+        let server = MockServer::start(&[]).await;
+        let mut config = ComplianceConfig::default();
+        config.alerts = notifications::tests::config(&server.url, 1);
+        let mut system = ComplianceSystem::new(config);
+        system
+            .add_entity(entity_checked_at("HIGH-1", "Example Shipping Lines", RiskLevel::High, chrono::Utc::now()))
+            .await
+            .unwrap();
+        let report = parse_sdn_csv(
+            b"101,\"EXAMPLE SHIPPING LINES\",-0- ,\"SDGT\",-0- ,-0- ,-0- ,-0- ,-0- ,-0- ,-0- ,-0- \n",
+            None,
+            None,
+        );
+        system.load_sanctions_list(report.into_list(SanctionSource::OFAC)).await;
+
+        system.check_entity("HIGH-1").await.unwrap();
+        system.flush_notifications().await;
+        let alert_id = system.get_alerts(Some(AlertSeverity::Critical))[0].id.clone();
+        assert_eq!(server.received().len(), 1);
+
+        // Critical alerts escalate on the first run; escalated alerts don't again
+        let now = chrono::Utc::now();
+        assert_eq!(system.run_escalations(now).await, vec![alert_id.clone()]);
+        assert!(system.run_escalations(now).await.is_empty());
+        system.flush_notifications().await;
+
+        let events: Vec<String> = server.received().iter()
+            .map(|r| r.header(EVENT_HEADER).unwrap().to_string())
+            .collect();
+        assert_eq!(events, vec!["alert_created", "alert_escalated"]);
+        assert_eq!(system.alerts[&alert_id].status, AlertStatus::Escalated);
+        assert!(system.get_failed_notifications().is_empty());
+    }
+
+    #[test]
+    fn test_escalation_delays() {
+        let escalation = EscalationConfig::default();
+        assert_eq!(escalation.escalation_delay(&AlertSeverity::Critical), Some(chrono::Duration::zero()));
+        assert_eq!(escalation.escalation_delay(&AlertSeverity::High), Some(chrono::Duration::minutes(15)));
+        assert_eq!(escalation.escalation_delay(&AlertSeverity::Medium), Some(chrono::Duration::hours(4)));
+        assert_eq!(escalation.escalation_delay(&AlertSeverity::Low), None);
+
+        let deferred = EscalationConfig { critical_immediate: false, ..EscalationConfig::default() };
+        assert_eq!(deferred.escalation_delay(&AlertSeverity::Critical), Some(chrono::Duration::minutes(15)));
+    }
+    /// System with three shell companies, each with a medium alert
+    async fn system_with_alerts() -> ComplianceSystem {
+        let mut system = ComplianceSystem::new(ComplianceConfig::default());
+        for i in 1..=3 {
+            let entity_id = format!("SHELL-{}", i);
+            system
+                .add_entity(entity_checked_at(&entity_id, "Harbor Holdings", RiskLevel::Medium, chrono::Utc::now()))
+                .await
+                .unwrap();
+            let mut alert = notifications::tests::alert(AlertSeverity::Medium);
+            alert.id = format!("ALERT-{}", i);
+            alert.entity_id = entity_id;
+            system.alerts.insert(alert.id.clone(), alert);
+        }
+        system
+    }
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_case_membership() {
+        let mut system = system_with_alerts().await;
+        let case_id = system
+            .create_case("Harbor shell network", ids(&["SHELL-1", "SHELL-2"]), ids(&["ALERT-1", "ALERT-2"]), "analyst")
+            .unwrap();
+
+        assert_eq!(system.alerts["ALERT-1"].case_id.as_deref(), Some(case_id.as_str()));
+        assert_eq!(system.alerts["ALERT-3"].case_id, None);
+        let in_case = system.get_alerts(None).iter().filter(|a| a.case_id.is_some()).count();
+        assert_eq!(in_case, 2);
+
+        // An alert is only ever in one case, and members must exist
+        assert!(system.create_case("Duplicate", vec![], ids(&["ALERT-1"]), "analyst").is_err());
+        assert!(system.create_case("Unknown", ids(&["MISSING"]), vec![], "analyst").is_err());
+        assert!(system.add_to_case(&case_id, vec![], ids(&["ALERT-9"]), "analyst").is_err());
+
+        system.add_to_case(&case_id, ids(&["SHELL-3"]), ids(&["ALERT-1", "ALERT-3"]), "analyst").unwrap();
+        let case = system.get_case(&case_id).unwrap();
+        assert_eq!(case.alert_ids, ids(&["ALERT-1", "ALERT-2", "ALERT-3"]));
+        assert_eq!(case.entity_ids, ids(&["SHELL-1", "SHELL-2", "SHELL-3"]));
+        assert_eq!(system.get_cases(true).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_case_transitions_rejected() {
+        let mut system = system_with_alerts().await;
+        let case_id = system.create_case("Harbor shell network", vec![], ids(&["ALERT-1"]), "analyst").unwrap();
+        let entries = system.audit_trail.get_case_entries(&case_id).len();
+
+        for next in [CaseStatus::Open, CaseStatus::EscalatedToSAR] {
+            assert!(system.transition_case(&case_id, next, "", "analyst").is_err());
+        }
+        assert_eq!(system.get_case(&case_id).unwrap().status, CaseStatus::Open);
+
+        system.close_case(&case_id, CaseOutcome::Cleared, "Legitimate trade", false, "analyst").unwrap();
+        assert!(system.transition_case(&case_id, CaseStatus::InReview, "", "analyst").is_err());
+        assert!(system.add_to_case(&case_id, vec![], ids(&["ALERT-2"]), "analyst").is_err());
+        assert!(system.transition_case("CASE-MISSING", CaseStatus::InReview, "", "analyst").is_err());
+
+        // Only the accepted close was audited
+        assert_eq!(system.audit_trail.get_case_entries(&case_id).len(), entries + 1);
+        // Alerts are left as they were unless resolved with the case
+        assert_eq!(system.alerts["ALERT-1"].status, AlertStatus::New);
+    }
+
+    #[tokio::test]
+    async fn test_case_workflow_audit() {
+        let mut system = system_with_alerts().await;
+        let case_id = system
+            .create_case("Harbor shell network", ids(&["SHELL-1"]), ids(&["ALERT-1", "ALERT-2", "ALERT-3"]), "lead")
+            .unwrap();
+        system.assign_case(&case_id, "analyst", "lead").unwrap();
+        system.transition_case(&case_id, CaseStatus::InReview, "Starting review", "analyst").unwrap();
+        system.transition_case(&case_id, CaseStatus::EscalatedToSAR, "Layered ownership", "analyst").unwrap();
+
+        let stats = system.get_statistics().await;
+        assert_eq!((stats.open_cases, stats.escalated_cases), (1, 1));
+
+        system.resolve_alert("ALERT-3", AlertStatus::FalsePositive, "Different company", "analyst").unwrap();
+        system.close_case(&case_id, CaseOutcome::SARFiled, "SAR 2024-17 filed", true, "lead").unwrap();
+
+        let events: Vec<AuditEventType> = system.audit_trail.get_case_entries(&case_id).iter()
+            .map(|e| e.event_type.clone())
+            .collect();
+        assert_eq!(events, vec![
+            AuditEventType::CaseCreated,
+            AuditEventType::CaseAssigned,
+            AuditEventType::CaseStatusChanged,
+            AuditEventType::CaseStatusChanged,
+            AuditEventType::CaseStatusChanged,
+        ]);
+        let close = system.audit_trail.get_case_entries(&case_id)[4].clone();
+        assert_eq!(close.user_id, "lead");
+        assert_eq!(close.data["from"], serde_json::to_value(CaseStatus::EscalatedToSAR).unwrap());
+
+        // Unresolved alerts were resolved with the case's note
+        for alert_id in ["ALERT-1", "ALERT-2"] {
+            let alert = &system.alerts[alert_id];
+            assert_eq!(alert.status, AlertStatus::SARFiled);
+            assert_eq!(alert.resolution_notes.as_deref(), Some("SAR 2024-17 filed"));
+        }
+        assert_eq!(system.alerts["ALERT-3"].status, AlertStatus::FalsePositive);
+        let resolutions = system.audit_trail.get_entity_entries("SHELL-1").iter()
+            .filter(|e| e.event_type == AuditEventType::AlertResolved)
+            .count();
+        assert_eq!(resolutions, 1);
+
+        let stats = system.get_statistics().await;
+        assert_eq!((stats.open_cases, stats.escalated_cases), (0, 0));
+        assert!(system.verify_audit_integrity().is_valid);
+    }
 }
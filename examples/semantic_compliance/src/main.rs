@@ -120,15 +120,23 @@ enum Commands {
         action: GraphAction,
     },
 
-    /// Import entities from file
+    /// Import entities or a sanctions list from file
     Import {
-        /// Input file (JSON, CSV)
+        /// Input file (JSON entities, OFAC sdn.csv or EU consolidated XML)
         #[arg(short, long)]
         file: PathBuf,
 
-        /// File format
+        /// File format: json, sdn-csv or eu-xml
         #[arg(short = 't', long, default_value = "json")]
         format: String,
+
+        /// OFAC alternate names file (alt.csv), for sdn-csv
+        #[arg(long)]
+        alt: Option<PathBuf>,
+
+        /// OFAC addresses file (add.csv), for sdn-csv
+        #[arg(long)]
+        addresses: Option<PathBuf>,
     },
 
     /// Configure system settings
@@ -358,8 +366,8 @@ async fn main() -> Result<()> {
         Commands::Graph { action } => {
             cmd_graph(&system, action).await?;
         }
-        Commands::Import { file, format } => {
-            cmd_import(&mut system, &file, &format).await?;
+        Commands::Import { file, format, alt, addresses } => {
+            cmd_import(&mut system, &file, &format, alt, addresses).await?;
         }
         Commands::Config { action } => {
             cmd_config(action).await?;
@@ -434,16 +442,22 @@ async fn cmd_watch(
     );
     println!();
 
-    // In a real implementation, this would start background monitoring
     println!("{}", "Press Ctrl+C to stop monitoring".dimmed());
 
-    // Simulate monitoring
+    // Re-screen entities whose cadence has elapsed on every tick
+    let mut tick = tokio::time::interval(tokio::time::Duration::from_secs(interval));
     loop {
-        tokio::time::sleep(tokio::time::Duration::from_secs(interval)).await;
-        println!("{} {}",
-            chrono::Utc::now().format("%Y-%m-%d %H:%M:%S"),
-            "Checking for sanctions list updates...".dimmed()
+        tick.tick().await;
+        let report = system.run_due_screenings(chrono::Utc::now()).await;
+        println!("{} Screened {} entities: {} matches, {} alerts raised",
+            report.run_at.format("%Y-%m-%d %H:%M:%S"),
+            report.screened.len(),
+            report.matches,
+            report.alerts_raised
         );
+        for failure in &report.failures {
+            println!("  {} {}: {}", "✗".red(), failure.entity_id, failure.error);
+        }
     }
 }
 
@@ -471,6 +485,7 @@ async fn cmd_check(
             last_checked: chrono::Utc::now(),
             created_at: chrono::Utc::now(),
             metadata: std::collections::HashMap::new(),
+            pep_status: None,
         };
         system.add_entity(test_entity).await?;
     }
@@ -741,24 +756,54 @@ async fn cmd_graph(system: &ComplianceSystem, action: GraphAction) -> Result<()>
     Ok(())
 }
 
-async fn cmd_import(_system: &mut ComplianceSystem, file: &PathBuf, format: &str) -> Result<()> {
-    println!("{}", format!("Importing entities from: {}", file.display()).bold().cyan());
+async fn cmd_import(
+    system: &mut ComplianceSystem,
+    file: &PathBuf,
+    format: &str,
+    alt: Option<PathBuf>,
+    addresses: Option<PathBuf>,
+) -> Result<()> {
+    println!("{}", format!("Importing from: {}", file.display()).bold().cyan());
     println!("Format: {}", format);
     println!();
 
     // Read file
-    let content = tokio::fs::read_to_string(file).await?;
+    let content = tokio::fs::read(file).await?;
 
-    match format {
+    let (source, report) = match format {
         "json" => {
-            let _entities: Vec<Entity> = serde_json::from_str(&content)?;
+            let _entities: Vec<Entity> = serde_json::from_slice(&content)?;
             println!("(Import functionality not yet fully implemented)");
+            return Ok(());
         }
+        "sdn-csv" => {
+            let alt = match alt {
+                Some(path) => Some(tokio::fs::read(path).await?),
+                None => None,
+            };
+            let addresses = match addresses {
+                Some(path) => Some(tokio::fs::read(path).await?),
+                None => None,
+            };
+            let report = parse_sdn_csv(&content, alt.as_deref(), addresses.as_deref());
+            (SanctionSource::OFAC, report)
+        }
+        "eu-xml" => (SanctionSource::EU, parse_eu_xml(&content)?),
         _ => {
             return Err(anyhow::anyhow!("Unsupported format: {}", format));
         }
+    };
+
+    println!("  {} {} entries imported", "✓".green(), report.entries.len());
+    if !report.errors.is_empty() {
+        println!("  {} {} rows skipped:", "!".yellow(), report.errors.len());
+        for error in &report.errors {
+            println!("    {}", error);
+        }
     }
 
+    system.load_sanctions_list(report.into_list(source)).await;
+
     Ok(())
 }
 
@@ -795,6 +840,7 @@ async fn cmd_stats(system: &ComplianceSystem) -> Result<()> {
     println!("├─ Total Entities: {}", stats.total_entities);
     println!("├─ High-Risk Entities: {}", stats.high_risk_entities);
     println!("├─ Active Alerts: {}", stats.active_alerts);
+    println!("├─ Open Cases: {} ({} escalated to SAR)", stats.open_cases, stats.escalated_cases);
     println!("├─ Sanctions Lists Loaded: {}", stats.sanctions_lists_loaded);
     println!("├─ Total Sanctions Entries: {}", stats.total_sanctions_entries);
     println!("└─ Graph Connections: {}", stats.graph_connections);
@@ -860,6 +906,12 @@ phonetic_matching = true
 transliteration = false
 max_edit_distance = 3
 
+[matching.pep]
+match_threshold = 0.85
+alert_threshold = 0.9
+max_hops = 3
+exposure_alert_threshold = 0.5
+
 [risk_scoring]
 enable_ml = false
 
@@ -892,6 +944,13 @@ medium_within_hours = 4
 retention_years = 7
 allowed_formats = ["json", "xml", "pdf"]
 sign_reports = true
+
+[screening]
+critical_days = 1
+high_days = 1
+medium_days = 7
+low_days = 30
+minimal_days = 90
 "#.to_string()
 }
 
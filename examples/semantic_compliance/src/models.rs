@@ -46,6 +46,10 @@ pub struct Entity {
 
     /// Additional metadata
     pub metadata: HashMap<String, serde_json::Value>,
+
+    /// Politically exposed person status, `None` until screened or declared
+    #[serde(default)]
+    pub pep_status: Option<PepStatus>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -210,6 +214,9 @@ pub enum SanctionSource {
     /// UK HM Treasury
     HMTreasury,
 
+    /// Politically exposed persons list from the named provider
+    PEP(String),
+
     /// Custom watch list
     Custom(String),
 }
@@ -221,9 +228,38 @@ impl SanctionSource {
             Self::EU => "EU",
             Self::UN => "UN",
             Self::HMTreasury => "HM Treasury",
+            Self::PEP(s) => s,
             Self::Custom(s) => s,
         }
     }
+
+    /// Whether this is a sanctions or a PEP list
+    pub fn category(&self) -> ListCategory {
+        match self {
+            Self::PEP(_) => ListCategory::PEP,
+            _ => ListCategory::Sanctions,
+        }
+    }
+}
+
+/// What a watch list, and an alert raised from it, is about
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum ListCategory {
+    /// Sanctioned persons and entities
+    #[default]
+    Sanctions,
+
+    /// Politically exposed persons
+    PEP,
+}
+
+impl ListCategory {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Sanctions => "SANCTIONS",
+            Self::PEP => "PEP",
+        }
+    }
 }
 
 /// An entry in a sanctions list
@@ -303,6 +339,10 @@ pub struct ComplianceAlert {
     /// Reason for alert
     pub reason: String,
 
+    /// Whether the alert is for a sanctions or a PEP match
+    #[serde(default)]
+    pub category: ListCategory,
+
     /// Which list was matched
     pub matched_list: SanctionSource,
 
@@ -329,6 +369,10 @@ pub struct ComplianceAlert {
 
     /// When alert was resolved
     pub resolved_at: Option<DateTime<Utc>>,
+
+    /// Investigation case the alert belongs to
+    #[serde(default)]
+    pub case_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -446,6 +490,189 @@ pub enum MatchAlgorithm {
     Semantic,
 }
 
+// ============================================================================
+// Politically Exposed Persons
+// ============================================================================
+
+/// Whether an entity is, or is exposed to, a politically exposed person
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PepStatus {
+    /// The entity is itself a politically exposed person
+    pub is_pep: bool,
+
+    /// PEP exposure (0.0 - 1.0): 1.0 for a PEP, graded by relationship
+    /// distance for relatives and associates
+    pub exposure: f64,
+
+    /// Where the status came from
+    pub provenance: PepProvenance,
+
+    /// When the status was set
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PepStatus {
+    /// Status set from a PEP list match or by an analyst
+    pub fn direct(is_pep: bool, provenance: PepProvenance, updated_at: DateTime<Utc>) -> Self {
+        Self {
+            is_pep,
+            exposure: if is_pep { 1.0 } else { 0.0 },
+            provenance,
+            updated_at,
+        }
+    }
+
+    /// Whether the status was derived from relationships rather than set
+    /// for the entity itself
+    pub fn is_derived(&self) -> bool {
+        matches!(self.provenance, PepProvenance::Relationship { .. })
+    }
+}
+
+/// Source of a [`PepStatus`]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum PepProvenance {
+    /// Declared by the customer or set by an analyst
+    Manual {
+        /// Who set the status
+        user_id: String,
+    },
+
+    /// Matched against a PEP list
+    ListMatch {
+        /// The list matched
+        source: SanctionSource,
+
+        /// The matched list entry
+        entry_id: String,
+
+        /// Match confidence (0.0 - 1.0)
+        confidence: f64,
+    },
+
+    /// Related to a confirmed PEP
+    Relationship {
+        /// The PEP the exposure comes from
+        pep_entity_id: String,
+
+        /// Relationship hops between the entity and the PEP
+        hops: usize,
+    },
+}
+
+// ============================================================================
+// Investigation Cases
+// ============================================================================
+
+/// An investigation linking related alerts and entities
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Case {
+    /// Unique case ID
+    pub id: String,
+
+    /// Short description of what is being investigated
+    pub title: String,
+
+    /// Entities under investigation
+    pub entity_ids: Vec<String>,
+
+    /// Alerts grouped into this case
+    pub alert_ids: Vec<String>,
+
+    /// Current status
+    pub status: CaseStatus,
+
+    /// Analyst responsible for the case
+    pub assigned_to: Option<String>,
+
+    /// When the case was opened
+    pub created_at: DateTime<Utc>,
+
+    /// When the case last changed
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Case workflow status
+///
+/// Cases move Open → InReview → EscalatedToSAR → Closed. An analyst may send
+/// a case under review back to Open, and Open or InReview cases may be closed
+/// directly. Closed is final.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CaseStatus {
+    /// Opened, not yet under review
+    Open,
+
+    /// Being reviewed by an analyst
+    InReview,
+
+    /// Escalated for a Suspicious Activity Report
+    EscalatedToSAR,
+
+    /// Investigation finished
+    Closed {
+        /// How the investigation ended
+        outcome: CaseOutcome,
+    },
+}
+
+impl CaseStatus {
+    /// Status name for reports and logs
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Open => "OPEN",
+            Self::InReview => "IN_REVIEW",
+            Self::EscalatedToSAR => "ESCALATED_TO_SAR",
+            Self::Closed { .. } => "CLOSED",
+        }
+    }
+
+    /// Whether a case may move from this status to `next`
+    pub fn can_transition_to(&self, next: &CaseStatus) -> bool {
+        matches!(
+            (self, next),
+            (Self::Open, Self::InReview)
+                | (Self::Open, Self::Closed { .. })
+                | (Self::InReview, Self::Open)
+                | (Self::InReview, Self::EscalatedToSAR)
+                | (Self::InReview, Self::Closed { .. })
+                | (Self::EscalatedToSAR, Self::Closed { .. })
+        )
+    }
+
+    /// Whether the case is still being worked on
+    pub fn is_open(&self) -> bool {
+        !matches!(self, Self::Closed { .. })
+    }
+}
+
+/// How a closed case ended
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CaseOutcome {
+    /// Suspicious activity confirmed and a SAR was filed
+    SARFiled,
+
+    /// Activity confirmed but did not warrant a SAR
+    Confirmed,
+
+    /// Reviewed and found to be legitimate
+    Cleared,
+
+    /// Alerts were false positives
+    FalsePositive,
+}
+
+impl CaseOutcome {
+    /// Status given to the case's alerts when they are resolved with it
+    pub fn alert_status(&self) -> AlertStatus {
+        match self {
+            Self::SARFiled => AlertStatus::SARFiled,
+            Self::Confirmed => AlertStatus::Confirmed,
+            Self::Cleared => AlertStatus::Cleared,
+            Self::FalsePositive => AlertStatus::FalsePositive,
+        }
+    }
+}
+
 // ============================================================================
 // Risk Assessment
 // ============================================================================
@@ -647,6 +874,21 @@ pub enum AuditEventType {
     /// Sanctions list updated
     SanctionsListUpdated,
 
+    /// Investigation case opened
+    CaseCreated,
+
+    /// Alerts or entities added to a case
+    CaseUpdated,
+
+    /// Case assigned to an analyst
+    CaseAssigned,
+
+    /// Case moved to a new status
+    CaseStatusChanged,
+
+    /// PEP status set or changed
+    PepStatusChanged,
+
     /// Configuration changed
     ConfigurationChanged,
 
@@ -690,6 +932,10 @@ pub struct ComplianceConfig {
 
     /// Audit configuration
     pub audit: AuditConfig,
+
+    /// Periodic re-screening cadence
+    #[serde(default)]
+    pub screening: ScreeningConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -729,6 +975,50 @@ pub struct MatchingConfig {
 
     /// Maximum edit distance for fuzzy matching
     pub max_edit_distance: usize,
+
+    /// Thresholds for PEP lists, which replace the ones above for them
+    #[serde(default)]
+    pub pep: PepMatchingConfig,
+}
+
+impl MatchingConfig {
+    /// Lowest confidence reported as a match against a list of `category`
+    pub fn match_threshold(&self, category: ListCategory) -> f64 {
+        match category {
+            ListCategory::Sanctions => self.default_threshold,
+            ListCategory::PEP => self.pep.match_threshold,
+        }
+    }
+
+    /// Lowest match confidence that raises an alert for `category`
+    pub fn alert_threshold(&self, category: ListCategory) -> f64 {
+        match category {
+            ListCategory::Sanctions => self.critical_threshold,
+            ListCategory::PEP => self.pep.alert_threshold,
+        }
+    }
+}
+
+/// PEP matching and relationship exposure settings
+///
+/// A PEP match at or above `alert_threshold` raises a PEP alert and marks
+/// the entity as a PEP. Entities within `max_hops` relationships of a PEP
+/// get a derived exposure; at or above `exposure_alert_threshold` they are
+/// reported for review.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PepMatchingConfig {
+    /// Lowest confidence reported as a PEP match
+    pub match_threshold: f64,
+
+    /// Lowest PEP match confidence that raises an alert
+    pub alert_threshold: f64,
+
+    /// How many relationship hops PEP exposure propagates
+    pub max_hops: usize,
+
+    /// Lowest derived exposure (0.0 - 1.0) reported for review
+    pub exposure_alert_threshold: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -756,6 +1046,18 @@ pub struct AlertConfig {
 
     /// Escalation rules
     pub escalation: EscalationConfig,
+
+    /// Webhooks alert notifications are POSTed to
+    #[serde(default)]
+    pub webhooks: Vec<WebhookConfig>,
+
+    /// Which notification sinks are used for each severity
+    #[serde(default)]
+    pub routing: NotificationRouting,
+
+    /// Retries for failed notification deliveries
+    #[serde(default)]
+    pub delivery: DeliveryConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -773,6 +1075,130 @@ pub struct EscalationConfig {
     pub medium_within_hours: u64,
 }
 
+impl EscalationConfig {
+    /// How long an alert at `severity` may stay unreviewed before it is
+    /// escalated, or `None` if it never is
+    ///
+    /// With `critical_immediate`, critical alerts are escalated on the first
+    /// escalation run after they are raised.
+    pub fn escalation_delay(&self, severity: &AlertSeverity) -> Option<chrono::Duration> {
+        match severity {
+            AlertSeverity::Critical if self.critical_immediate => Some(chrono::Duration::zero()),
+            AlertSeverity::Critical | AlertSeverity::High => {
+                Some(chrono::Duration::minutes(self.high_within_minutes as i64))
+            }
+            AlertSeverity::Medium => Some(chrono::Duration::hours(self.medium_within_hours as i64)),
+            AlertSeverity::Low | AlertSeverity::Info => None,
+        }
+    }
+}
+
+/// A webhook that receives alert notifications
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    /// Sink name, used in routing and in failed-delivery records
+    pub name: String,
+
+    /// URL the notification is POSTed to
+    pub url: String,
+
+    /// Shared secret for the HMAC-SHA256 signature header
+    #[serde(default)]
+    pub secret: Option<String>,
+
+    /// Request timeout in seconds (default 10)
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+}
+
+/// Which notification sinks receive an alert
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SinkRoute {
+    /// Every configured sink
+    All,
+
+    /// Sinks whose name or kind (e.g. `"webhook"`) is listed
+    Only(Vec<String>),
+
+    /// No sink
+    Off,
+}
+
+impl SinkRoute {
+    /// Whether a sink with the given name and kind is selected
+    pub fn selects(&self, name: &str, kind: &str) -> bool {
+        match self {
+            Self::All => true,
+            Self::Only(sinks) => sinks.iter().any(|s| s == name || s == kind),
+            Self::Off => false,
+        }
+    }
+}
+
+/// Notification routing by alert severity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationRouting {
+    /// Critical alerts
+    pub critical: SinkRoute,
+
+    /// High severity alerts
+    pub high: SinkRoute,
+
+    /// Medium severity alerts
+    pub medium: SinkRoute,
+
+    /// Low severity alerts
+    pub low: SinkRoute,
+
+    /// Informational alerts
+    pub info: SinkRoute,
+}
+
+impl NotificationRouting {
+    /// The route for alerts at `severity`
+    pub fn route(&self, severity: &AlertSeverity) -> &SinkRoute {
+        match severity {
+            AlertSeverity::Critical => &self.critical,
+            AlertSeverity::High => &self.high,
+            AlertSeverity::Medium => &self.medium,
+            AlertSeverity::Low => &self.low,
+            AlertSeverity::Info => &self.info,
+        }
+    }
+}
+
+/// Retry policy for notification delivery
+///
+/// A failed attempt is retried after a backoff that starts at
+/// `initial_backoff_ms` and doubles up to `max_backoff_ms`. After
+/// `max_attempts` attempts the notification goes to the dead-letter list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DeliveryConfig {
+    /// Attempts per notification and sink, including the first
+    pub max_attempts: u32,
+
+    /// Wait before the first retry, in milliseconds
+    pub initial_backoff_ms: u64,
+
+    /// Longest wait between retries, in milliseconds
+    pub max_backoff_ms: u64,
+}
+
+impl DeliveryConfig {
+    /// Wait after failed attempt number `attempt` (starting at 1)
+    pub fn backoff(&self, attempt: u32) -> std::time::Duration {
+        let factor = 1u64 << attempt.saturating_sub(1).min(32);
+        let millis = self
+            .initial_backoff_ms
+            .saturating_mul(factor)
+            .min(self.max_backoff_ms);
+        std::time::Duration::from_millis(millis)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuditConfig {
     /// Retention period in years
@@ -788,6 +1214,42 @@ pub struct AuditConfig {
     pub key_id: Option<String>,
 }
 
+/// How often entities are re-screened, in days, by risk level
+///
+/// A cadence of 0 re-screens the entity on every scheduled run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScreeningConfig {
+    /// Critical-risk entities
+    pub critical_days: u32,
+
+    /// High-risk entities
+    pub high_days: u32,
+
+    /// Medium-risk entities
+    pub medium_days: u32,
+
+    /// Low-risk entities
+    pub low_days: u32,
+
+    /// Minimal-risk entities
+    pub minimal_days: u32,
+}
+
+impl ScreeningConfig {
+    /// Time between screenings for an entity at `level`
+    pub fn cadence(&self, level: &RiskLevel) -> chrono::Duration {
+        let days = match level {
+            RiskLevel::Critical => self.critical_days,
+            RiskLevel::High => self.high_days,
+            RiskLevel::Medium => self.medium_days,
+            RiskLevel::Low => self.low_days,
+            RiskLevel::Minimal => self.minimal_days,
+        };
+        chrono::Duration::days(i64::from(days))
+    }
+}
+
 // ============================================================================
 // Search and Query Types
 // ============================================================================
@@ -911,6 +1373,7 @@ mod tests {
             last_checked: Utc::now(),
             created_at: Utc::now(),
             metadata: HashMap::new(),
+            pep_status: None,
         };
 
         let json = serde_json::to_string(&entity).unwrap();
@@ -927,6 +1390,42 @@ mod tests {
         assert_eq!(RiskLevel::from_score(1.5), RiskLevel::Minimal);
     }
 
+    #[test]
+    fn test_case_transitions() {
+        let closed = CaseStatus::Closed {
+            outcome: CaseOutcome::Cleared,
+        };
+        let all = [
+            CaseStatus::Open,
+            CaseStatus::InReview,
+            CaseStatus::EscalatedToSAR,
+            closed.clone(),
+        ];
+        let allowed = [
+            (CaseStatus::Open, CaseStatus::InReview),
+            (CaseStatus::Open, closed.clone()),
+            (CaseStatus::InReview, CaseStatus::Open),
+            (CaseStatus::InReview, CaseStatus::EscalatedToSAR),
+            (CaseStatus::InReview, closed.clone()),
+            (CaseStatus::EscalatedToSAR, closed.clone()),
+        ];
+
+        for from in &all {
+            for to in &all {
+                let expected = allowed.iter().any(|(f, t)| f == from && t == to);
+                assert_eq!(
+                    from.can_transition_to(to),
+                    expected,
+                    "{:?} -> {:?}",
+                    from,
+                    to
+                );
+            }
+        }
+        assert!(!closed.is_open());
+        assert!(CaseStatus::EscalatedToSAR.is_open());
+    }
+
     #[test]
     fn test_sanction_source_as_str() {
         assert_eq!(SanctionSource::OFAC.as_str(), "OFAC");
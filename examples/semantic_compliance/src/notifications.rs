@@ -0,0 +1,610 @@
+//! Alert notification delivery
+//!
+//! Alerts are delivered to [`NotificationSink`]s when they are raised and
+//! when they escalate. Which sinks receive an alert depends on its severity
+//! (see [`NotificationRouting`]). Each delivery runs in its own task and is
+//! retried with exponential backoff per [`DeliveryConfig`]. A notification
+//! that still fails after the last attempt lands in a dead-letter list.
+//!
+//! [`WebhookSink`] POSTs the notification as JSON. When the webhook has a
+//! secret, the body is signed with HMAC-SHA256 in the
+//! [`SIGNATURE_HEADER`] so the receiver can authenticate it.
+
+use crate::models::*;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+/// Header carrying `sha256=<hex HMAC of the body>`
+pub const SIGNATURE_HEADER: &str = "X-Compliance-Signature";
+
+/// Header carrying the notification event, e.g. `alert_created`
+pub const EVENT_HEADER: &str = "X-Compliance-Event";
+
+/// Header carrying the notification ID, the same for every retry
+pub const DELIVERY_HEADER: &str = "X-Compliance-Delivery";
+
+/// Webhook request timeout when none is configured
+const DEFAULT_WEBHOOK_TIMEOUT_SECS: u64 = 10;
+
+// ============================================================================
+// Notifications
+// ============================================================================
+
+/// Why a notification was sent
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationEvent {
+    /// The alert was raised
+    AlertCreated,
+
+    /// The alert went unreviewed past its escalation deadline
+    AlertEscalated,
+}
+
+impl NotificationEvent {
+    /// The event's name, as sent in the [`EVENT_HEADER`]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::AlertCreated => "alert_created",
+            Self::AlertEscalated => "alert_escalated",
+        }
+    }
+}
+
+/// An alert notification, as delivered to sinks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Notification {
+    /// Unique notification ID
+    pub id: String,
+
+    /// Why the notification was sent
+    pub event: NotificationEvent,
+
+    /// The alert, as of when the notification was sent
+    pub alert: ComplianceAlert,
+
+    /// When the notification was sent
+    pub sent_at: DateTime<Utc>,
+}
+
+impl Notification {
+    /// Create a notification for `alert`
+    pub fn new(event: NotificationEvent, alert: &ComplianceAlert) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            event,
+            alert: alert.clone(),
+            sent_at: Utc::now(),
+        }
+    }
+}
+
+/// A notification that could not be delivered
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailedNotification {
+    /// Name of the sink it was meant for
+    pub sink: String,
+
+    /// The undelivered notification
+    pub notification: Notification,
+
+    /// Delivery attempts made
+    pub attempts: u32,
+
+    /// Error of the last attempt
+    pub last_error: String,
+
+    /// When delivery was given up
+    pub failed_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// Sinks
+// ============================================================================
+
+/// A destination for alert notifications
+pub trait NotificationSink: Send + Sync {
+    /// Sink name, unique among the configured sinks
+    fn name(&self) -> &str;
+
+    /// Kind of sink (e.g. `"webhook"`), which routes may select by
+    fn kind(&self) -> &str;
+
+    /// Deliver a notification; an error makes the delivery be retried
+    fn send<'a>(&'a self, notification: &'a Notification) -> BoxFuture<'a, Result<()>>;
+}
+
+/// Sink that POSTs notifications as JSON to a URL
+pub struct WebhookSink {
+    /// Sink name
+    name: String,
+
+    /// Target URL
+    url: String,
+
+    /// Secret for the HMAC signature header
+    secret: Option<String>,
+
+    /// HTTP client
+    client: reqwest::Client,
+}
+
+impl WebhookSink {
+    /// Create a webhook sink from its configuration
+    pub fn new(config: &WebhookConfig) -> Result<Self> {
+        let timeout = config.timeout_secs.unwrap_or(DEFAULT_WEBHOOK_TIMEOUT_SECS);
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(timeout))
+            .build()?;
+
+        Ok(Self {
+            name: config.name.clone(),
+            url: config.url.clone(),
+            secret: config.secret.clone(),
+            client,
+        })
+    }
+}
+
+impl NotificationSink for WebhookSink {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn kind(&self) -> &str {
+        "webhook"
+    }
+
+    fn send<'a>(&'a self, notification: &'a Notification) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let body = serde_json::to_vec(notification)?;
+
+            let mut request = self
+                .client
+                .post(&self.url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header(EVENT_HEADER, notification.event.as_str())
+                .header(DELIVERY_HEADER, &notification.id);
+            if let Some(secret) = &self.secret {
+                request = request.header(SIGNATURE_HEADER, sign(secret, &body));
+            }
+
+            let status = request.body(body).send().await?.status();
+            if !status.is_success() {
+                anyhow::bail!("webhook {} responded with {}", self.url, status);
+            }
+            Ok(())
+        })
+    }
+}
+
+/// The [`SIGNATURE_HEADER`] value for `body` signed with `secret`
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+// ============================================================================
+// Notifier
+// ============================================================================
+
+/// Routes alert notifications to sinks and delivers them in the background
+pub struct Notifier {
+    /// Registered sinks
+    sinks: Vec<Arc<dyn NotificationSink>>,
+
+    /// Sinks per severity
+    routing: NotificationRouting,
+
+    /// Retry policy
+    delivery: DeliveryConfig,
+
+    /// Notifications that exhausted their retries
+    dead_letters: Arc<Mutex<Vec<FailedNotification>>>,
+
+    /// Deliveries in progress
+    pending: Mutex<Vec<JoinHandle<()>>>,
+}
+
+impl Notifier {
+    /// Create a notifier with a [`WebhookSink`] for each configured webhook
+    ///
+    /// A webhook whose client cannot be built is skipped with a warning.
+    pub fn new(config: &AlertConfig) -> Self {
+        let mut notifier = Self {
+            sinks: Vec::new(),
+            routing: config.routing.clone(),
+            delivery: config.delivery.clone(),
+            dead_letters: Arc::new(Mutex::new(Vec::new())),
+            pending: Mutex::new(Vec::new()),
+        };
+
+        for webhook in &config.webhooks {
+            match WebhookSink::new(webhook) {
+                Ok(sink) => notifier.add_sink(Arc::new(sink)),
+                Err(e) => warn!("Skipping webhook {}: {}", webhook.name, e),
+            }
+        }
+
+        notifier
+    }
+
+    /// Register a sink
+    pub fn add_sink(&mut self, sink: Arc<dyn NotificationSink>) {
+        self.sinks.push(sink);
+    }
+
+    /// Send a notification for `alert` to every sink routed for its severity
+    ///
+    /// Deliveries run on the current Tokio runtime; this returns without
+    /// waiting for them. Must be called within a runtime if any sink is
+    /// routed.
+    pub fn notify(&self, event: NotificationEvent, alert: &ComplianceAlert) {
+        let route = self.routing.route(&alert.severity);
+        let sinks: Vec<_> = self
+            .sinks
+            .iter()
+            .filter(|sink| route.selects(sink.name(), sink.kind()))
+            .cloned()
+            .collect();
+        if sinks.is_empty() {
+            return;
+        }
+
+        let notification = Notification::new(event, alert);
+        let mut pending = self.pending.lock().unwrap();
+        pending.retain(|handle| !handle.is_finished());
+        for sink in sinks {
+            pending.push(tokio::spawn(deliver(
+                sink,
+                notification.clone(),
+                self.delivery.clone(),
+                Arc::clone(&self.dead_letters),
+            )));
+        }
+    }
+
+    /// Wait until every delivery started so far has succeeded or failed
+    pub async fn flush(&self) {
+        let pending = std::mem::take(&mut *self.pending.lock().unwrap());
+        for handle in pending {
+            if let Err(e) = handle.await {
+                warn!("Notification delivery task failed: {}", e);
+            }
+        }
+    }
+
+    /// Notifications that could not be delivered, oldest first
+    pub fn failed(&self) -> Vec<FailedNotification> {
+        self.dead_letters.lock().unwrap().clone()
+    }
+}
+
+/// Deliver `notification` to `sink`, retrying with backoff, and record it as
+/// failed once the attempts are exhausted
+async fn deliver(
+    sink: Arc<dyn NotificationSink>,
+    notification: Notification,
+    delivery: DeliveryConfig,
+    dead_letters: Arc<Mutex<Vec<FailedNotification>>>,
+) {
+    let max_attempts = delivery.max_attempts.max(1);
+    let mut attempt = 1;
+    loop {
+        let error = match sink.send(&notification).await {
+            Ok(()) => {
+                debug!(
+                    "Notification {} delivered to {}",
+                    notification.id,
+                    sink.name()
+                );
+                return;
+            }
+            Err(e) => e,
+        };
+
+        if attempt >= max_attempts {
+            warn!(
+                "Notification {} to {} failed after {} attempts: {}",
+                notification.id,
+                sink.name(),
+                attempt,
+                error
+            );
+            dead_letters.lock().unwrap().push(FailedNotification {
+                sink: sink.name().to_string(),
+                notification,
+                attempts: attempt,
+                last_error: error.to_string(),
+                failed_at: Utc::now(),
+            });
+            return;
+        }
+
+        debug!(
+            "Notification {} to {} failed (attempt {}): {}",
+            notification.id,
+            sink.name(),
+            attempt,
+            error
+        );
+        tokio::time::sleep(delivery.backoff(attempt)).await;
+        attempt += 1;
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// A request received by [`MockServer`]
+    #[derive(Debug, Clone)]
+    pub(crate) struct Received {
+        pub headers: Vec<(String, String)>,
+        pub body: Vec<u8>,
+    }
+
+    impl Received {
+        pub fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find(|(n, _)| n.eq_ignore_ascii_case(name))
+                .map(|(_, v)| v.as_str())
+        }
+    }
+
+    /// Local HTTP server answering with scripted status codes, then 200
+    pub(crate) struct MockServer {
+        pub url: String,
+        received: Arc<Mutex<Vec<Received>>>,
+    }
+
+    impl MockServer {
+        pub async fn start(statuses: &[u16]) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let url = format!("http://{}/hook", listener.local_addr().unwrap());
+            let received = Arc::new(Mutex::new(Vec::new()));
+            let mut statuses: VecDeque<u16> = statuses.iter().copied().collect();
+
+            let log = Arc::clone(&received);
+            tokio::spawn(async move {
+                while let Ok((mut stream, _)) = listener.accept().await {
+                    let Some(request) = read_request(&mut stream).await else {
+                        continue;
+                    };
+                    log.lock().unwrap().push(request);
+                    let status = statuses.pop_front().unwrap_or(200);
+                    let response = format!(
+                        "HTTP/1.1 {} Mock\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+                        status
+                    );
+                    let _ = stream.write_all(response.as_bytes()).await;
+                    let _ = stream.shutdown().await;
+                }
+            });
+
+            Self { url, received }
+        }
+
+        pub fn received(&self) -> Vec<Received> {
+            self.received.lock().unwrap().clone()
+        }
+    }
+
+    async fn read_request(stream: &mut tokio::net::TcpStream) -> Option<Received> {
+        let mut buf = Vec::new();
+        let mut chunk = [0u8; 4096];
+        let header_end = loop {
+            let n = stream.read(&mut chunk).await.ok()?;
+            if n == 0 {
+                return None;
+            }
+            buf.extend_from_slice(&chunk[..n]);
+            if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                break pos + 4;
+            }
+        };
+
+        let head = String::from_utf8_lossy(&buf[..header_end]).to_string();
+        let headers: Vec<(String, String)> = head
+            .lines()
+            .skip(1)
+            .filter_map(|line| line.split_once(':'))
+            .map(|(n, v)| (n.trim().to_string(), v.trim().to_string()))
+            .collect();
+        let length: usize = headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case("content-length"))
+            .and_then(|(_, v)| v.parse().ok())
+            .unwrap_or(0);
+
+        while buf.len() < header_end + length {
+            let n = stream.read(&mut chunk).await.ok()?;
+            if n == 0 {
+                return None;
+            }
+            buf.extend_from_slice(&chunk[..n]);
+        }
+
+        Some(Received {
+            headers,
+            body: buf[header_end..header_end + length].to_vec(),
+        })
+    }
+
+    pub(crate) fn alert(severity: AlertSeverity) -> ComplianceAlert {
+        ComplianceAlert {
+            id: "ALERT-1".to_string(),
+            severity,
+            entity_id: "CUST-001".to_string(),
+            entity_name: "Example Shipping Lines".to_string(),
+            reason: "Sanctions match detected".to_string(),
+            category: ListCategory::Sanctions,
+            matched_list: SanctionSource::OFAC,
+            matched_entry: SanctionEntry {
+                id: "101".to_string(),
+                names: vec!["EXAMPLE SHIPPING LINES".to_string()],
+                aliases: vec![],
+                entity_type: EntityType::Company,
+                programs: vec!["SDGT".to_string()],
+                identifiers: vec![],
+                addresses: vec![],
+                dates_of_birth: vec![],
+                nationalities: vec![],
+                remarks: None,
+                listed_date: None,
+            },
+            confidence: 0.97,
+            match_details: MatchDetails {
+                matched_field: MatchedField::Name,
+                entity_value: "Example Shipping Lines".to_string(),
+                list_value: "EXAMPLE SHIPPING LINES".to_string(),
+                algorithm: MatchAlgorithm::Exact,
+                edit_distance: None,
+                context: Default::default(),
+            },
+            created_at: Utc::now(),
+            status: AlertStatus::New,
+            assigned_to: None,
+            resolution_notes: None,
+            resolved_at: None,
+            case_id: None,
+        }
+    }
+
+    pub(crate) fn config(url: &str, max_attempts: u32) -> AlertConfig {
+        AlertConfig {
+            webhooks: vec![WebhookConfig {
+                name: "siem".to_string(),
+                url: url.to_string(),
+                secret: Some("s3cret".to_string()),
+                timeout_secs: Some(5),
+            }],
+            delivery: DeliveryConfig {
+                max_attempts,
+                initial_backoff_ms: 10,
+                max_backoff_ms: 40,
+            },
+            ..AlertConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_webhook_delivers_signed_json() {
+        let server = MockServer::start(&[]).await;
+        let notifier = Notifier::new(&config(&server.url, 3));
+
+        notifier.notify(
+            NotificationEvent::AlertCreated,
+            &alert(AlertSeverity::Critical),
+        );
+        notifier.flush().await;
+
+        let received = server.received();
+        assert_eq!(received.len(), 1);
+        let request = &received[0];
+        assert_eq!(request.header(EVENT_HEADER), Some("alert_created"));
+        assert_eq!(
+            request.header(SIGNATURE_HEADER),
+            Some(sign("s3cret", &request.body).as_str())
+        );
+
+        let notification: Notification = serde_json::from_slice(&request.body).unwrap();
+        assert_eq!(
+            request.header(DELIVERY_HEADER),
+            Some(notification.id.as_str())
+        );
+        assert_eq!(notification.alert.id, "ALERT-1");
+        assert!(notifier.failed().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_webhook_retries_until_success() {
+        let server = MockServer::start(&[503, 500]).await;
+        let notifier = Notifier::new(&config(&server.url, 3));
+
+        notifier.notify(NotificationEvent::AlertCreated, &alert(AlertSeverity::High));
+        notifier.flush().await;
+
+        let received = server.received();
+        assert_eq!(received.len(), 3);
+        // Retries redeliver the same notification
+        assert!(received.iter().all(|r| r.body == received[0].body));
+        assert!(notifier.failed().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_retries_land_in_dead_letters() {
+        let server = MockServer::start(&[500, 500, 500, 500]).await;
+        let notifier = Notifier::new(&config(&server.url, 3));
+
+        notifier.notify(
+            NotificationEvent::AlertEscalated,
+            &alert(AlertSeverity::Critical),
+        );
+        notifier.flush().await;
+
+        assert_eq!(server.received().len(), 3);
+        let failed = notifier.failed();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].sink, "siem");
+        assert_eq!(failed[0].attempts, 3);
+        assert_eq!(
+            failed[0].notification.event,
+            NotificationEvent::AlertEscalated
+        );
+        assert!(
+            failed[0].last_error.contains("500"),
+            "{}",
+            failed[0].last_error
+        );
+    }
+
+    #[tokio::test]
+    async fn test_routing_by_severity() {
+        let server = MockServer::start(&[]).await;
+        let mut config = config(&server.url, 1);
+        config.routing.high = SinkRoute::Only(vec!["pager".to_string()]);
+        let notifier = Notifier::new(&config);
+
+        // Medium goes to webhooks, high only to the (absent) pager, low nowhere
+        notifier.notify(
+            NotificationEvent::AlertCreated,
+            &alert(AlertSeverity::Medium),
+        );
+        notifier.notify(NotificationEvent::AlertCreated, &alert(AlertSeverity::High));
+        notifier.notify(NotificationEvent::AlertCreated, &alert(AlertSeverity::Low));
+        notifier.flush().await;
+
+        assert_eq!(server.received().len(), 1);
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_max() {
+        let delivery = DeliveryConfig::default();
+        let backoffs: Vec<u64> = (1..=8)
+            .map(|attempt| delivery.backoff(attempt).as_millis() as u64)
+            .collect();
+        assert_eq!(
+            backoffs,
+            vec![500, 1000, 2000, 4000, 8000, 16000, 30000, 30000]
+        );
+    }
+}
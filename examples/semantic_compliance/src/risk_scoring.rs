@@ -203,22 +203,48 @@ impl RiskEngine {
     }
 
     fn assess_pep_risk(&self, entity: &Entity) -> Option<RiskFactor> {
-        // Check if entity is a Politically Exposed Person
-        let is_pep = entity.metadata.get("is_pep")
-            .and_then(|v| v.as_bool())
-            .unwrap_or(false);
+        // Entities without a screened PEP status fall back to the `is_pep` flag
+        let Some(status) = &entity.pep_status else {
+            let is_pep = entity.metadata.get("is_pep")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
 
-        if is_pep {
-            Some(RiskFactor {
+            return is_pep.then(|| RiskFactor {
                 factor_type: RiskFactorType::PEP,
                 score: 7.0,
                 weight: self.weights.pep_status,
                 description: "Politically Exposed Person".to_string(),
                 evidence: vec!["PEP status confirmed".to_string()],
-            })
-        } else {
-            None
+            });
+        };
+
+        if status.exposure <= 0.0 {
+            return None;
         }
+
+        let (description, evidence) = match &status.provenance {
+            PepProvenance::Manual { user_id } => (
+                "Politically Exposed Person".to_string(),
+                format!("PEP status set by {}", user_id),
+            ),
+            PepProvenance::ListMatch { source, entry_id, confidence } => (
+                "Politically Exposed Person".to_string(),
+                format!("{} entry {} matched ({:.2} confidence)", source.as_str(), entry_id, confidence),
+            ),
+            PepProvenance::Relationship { pep_entity_id, hops } => (
+                format!("Related to a Politically Exposed Person (exposure {:.2})", status.exposure),
+                format!("{} relationship hops from PEP {}", hops, pep_entity_id),
+            ),
+        };
+
+        // Derived exposure scales the PEP score down by relationship distance
+        Some(RiskFactor {
+            factor_type: RiskFactorType::PEP,
+            score: 7.0 * status.exposure,
+            weight: self.weights.pep_status,
+            description,
+            evidence: vec![evidence],
+        })
     }
 
     fn assess_jurisdiction_risk(&self, entity: &Entity) -> Option<RiskFactor> {
@@ -636,6 +662,7 @@ mod tests {
             last_checked: Utc::now(),
             created_at: Utc::now(),
             metadata: HashMap::new(),
+            pep_status: None,
         };
 
         let assessment = engine.calculate_risk(&entity).unwrap();
@@ -0,0 +1,202 @@
+//! EU consolidated financial sanctions list (XML export)
+//!
+//! Each `sanctionEntity` becomes one [`SanctionEntry`]. The first
+//! `nameAlias` is the primary name and the rest are aliases; `regulation`
+//! elements carry the programme tags, and `identification`, `citizenship`,
+//! `birthdate` and `address` fill in the remaining fields. Elements are
+//! matched by local name, so the export namespace does not matter.
+
+use super::import::{decode_text, parse_date, ImportReport, RowError};
+use crate::models::*;
+use anyhow::Result;
+use roxmltree::{Document, Node};
+
+const EU_FILE: &str = "eu.xml";
+
+/// Parse the EU consolidated list
+///
+/// Fails only if the document is not well-formed XML; entities that cannot
+/// be mapped are reported in [`ImportReport::errors`] and skipped.
+pub fn parse_eu_xml(xml: &[u8]) -> Result<ImportReport> {
+    let text = decode_text(xml);
+    let doc = Document::parse(&text)
+        .map_err(|e| anyhow::anyhow!("Failed to parse EU sanctions XML: {}", e))?;
+
+    let mut report = ImportReport::default();
+    for node in doc
+        .root_element()
+        .children()
+        .filter(|n| n.has_tag_name("sanctionEntity"))
+    {
+        match sanction_entity(node) {
+            Ok(entry) => report.entries.push(entry),
+            Err(message) => {
+                let line = doc.text_pos_at(node.range().start).row as u64;
+                report.errors.push(RowError::new(EU_FILE, line, message));
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+fn sanction_entity(node: Node) -> Result<SanctionEntry, String> {
+    let id = attr(node, "euReferenceNumber")
+        .or_else(|| attr(node, "logicalId"))
+        .ok_or("missing euReferenceNumber and logicalId")?;
+
+    let mut names = node
+        .children()
+        .filter(|n| n.has_tag_name("nameAlias"))
+        .filter_map(|n| attr(n, "wholeName"));
+    let name = names
+        .next()
+        .ok_or_else(|| format!("{} has no nameAlias", id))?;
+    let mut aliases: Vec<String> = Vec::new();
+    for alias in names {
+        if alias != name && !aliases.contains(&alias) {
+            aliases.push(alias);
+        }
+    }
+
+    let entity_type = match children(node, "subjectType")
+        .next()
+        .and_then(|n| attr(n, "code"))
+        .as_deref()
+    {
+        Some("person") => EntityType::Person,
+        Some("enterprise") => EntityType::Company,
+        Some(other) => EntityType::Other(other.to_string()),
+        None => EntityType::Other("unknown".to_string()),
+    };
+
+    let mut programs: Vec<String> = Vec::new();
+    for programme in children(node, "regulation").filter_map(|n| attr(n, "programme")) {
+        if !programs.contains(&programme) {
+            programs.push(programme);
+        }
+    }
+
+    let listed_date = attr(node, "designationDate")
+        .and_then(|d| parse_date(&d))
+        .or_else(|| {
+            children(node, "regulation")
+                .filter_map(|n| attr(n, "publicationDate").and_then(|d| parse_date(&d)))
+                .min()
+        });
+
+    let remarks: Vec<&str> = children(node, "remark")
+        .filter_map(|n| n.text())
+        .map(str::trim)
+        .filter(|r| !r.is_empty())
+        .collect();
+
+    Ok(SanctionEntry {
+        id,
+        names: vec![name],
+        aliases,
+        entity_type,
+        programs,
+        identifiers: children(node, "identification")
+            .filter_map(identifier)
+            .collect(),
+        addresses: children(node, "address").map(address).collect(),
+        dates_of_birth: children(node, "birthdate")
+            .filter_map(|n| attr(n, "birthdate").or_else(|| attr(n, "year")))
+            .collect(),
+        nationalities: children(node, "citizenship")
+            .filter_map(|n| attr(n, "countryDescription").or_else(|| attr(n, "countryIso2Code")))
+            .collect(),
+        remarks: (!remarks.is_empty()).then(|| remarks.join("; ")),
+        listed_date,
+    })
+}
+
+fn identifier(node: Node) -> Option<Identifier> {
+    let value = attr(node, "number").or_else(|| attr(node, "latinNumber"))?;
+    let code = attr(node, "identificationTypeCode").unwrap_or_default();
+    let id_type = match code.as_str() {
+        "passport" => IdentifierType::Passport,
+        "id" => IdentifierType::NationalId,
+        "fiscalcode" | "tin" => IdentifierType::TaxId,
+        "regnumber" => IdentifierType::BusinessRegistration,
+        "swiftbic" => IdentifierType::Swift,
+        _ => IdentifierType::Custom(attr(node, "identificationTypeDescription").unwrap_or(code)),
+    };
+
+    Some(Identifier {
+        id_type,
+        value,
+        issuer: attr(node, "issuedBy").or_else(|| attr(node, "countryDescription")),
+        issue_date: attr(node, "issueDate").and_then(|d| parse_date(&d)),
+        expiry_date: attr(node, "validTo").and_then(|d| parse_date(&d)),
+    })
+}
+
+fn address(node: Node) -> Address {
+    Address {
+        street: attr(node, "street").or_else(|| attr(node, "poBox")),
+        city: attr(node, "city").or_else(|| attr(node, "place")),
+        state: attr(node, "region"),
+        postal_code: attr(node, "zipCode"),
+        country: attr(node, "countryDescription").unwrap_or_default(),
+    }
+}
+
+fn children<'a, 'input>(
+    node: Node<'a, 'input>,
+    name: &'static str,
+) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children().filter(move |n| n.has_tag_name(name))
+}
+
+/// A non-empty attribute value
+fn attr(node: Node, name: &str) -> Option<String> {
+    node.attribute(name)
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EU: &[u8] = include_bytes!("../../tests/fixtures/eu_consolidated.xml");
+
+    #[test]
+    fn test_parse_eu_fixture() {
+        let report = parse_eu_xml(EU).unwrap();
+
+        assert_eq!(report.entries.len(), 2);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].line, 25);
+
+        let person = &report.entries[0];
+        assert_eq!(person.id, "EU.1001.01");
+        assert_eq!(person.names, vec!["Ivan Example"]);
+        assert_eq!(person.aliases, vec!["Иван Экземпляр", "Vanya Example"]);
+        assert_eq!(person.entity_type, EntityType::Person);
+        assert_eq!(person.programs, vec!["SYR", "UKR"]);
+        assert_eq!(person.identifiers[0].id_type, IdentifierType::Passport);
+        assert_eq!(person.identifiers[0].value, "P0012345");
+        assert_eq!(person.nationalities, vec!["FREEDONIA"]);
+        assert_eq!(person.dates_of_birth, vec!["1960-01-01"]);
+        assert_eq!(person.addresses[0].postal_code.as_deref(), Some("12345"));
+        assert_eq!(person.listed_date, parse_date("2011-05-10"));
+
+        let company = &report.entries[1];
+        assert_eq!(company.names, vec!["Example Trading & Logistics LLC"]);
+        assert_eq!(company.entity_type, EntityType::Company);
+        assert_eq!(
+            company.identifiers[0].id_type,
+            IdentifierType::BusinessRegistration
+        );
+        assert_eq!(company.listed_date, parse_date("2014-03-17"));
+    }
+
+    #[test]
+    fn test_malformed_xml_is_an_error() {
+        assert!(parse_eu_xml(b"<export><sanctionEntity></export>").is_err());
+    }
+}
@@ -0,0 +1,93 @@
+//! Shared types for importing official sanctions list files
+//!
+//! The OFAC and EU parsers both collect what they could map into
+//! [`SanctionEntry`] values and report the rows they could not, so one bad
+//! row never aborts a whole import.
+
+use crate::models::*;
+use chrono::{NaiveDate, TimeZone, Utc};
+use std::fmt;
+
+/// Outcome of importing a sanctions list file
+#[derive(Debug, Clone, Default)]
+pub struct ImportReport {
+    /// Entries parsed successfully
+    pub entries: Vec<SanctionEntry>,
+
+    /// Rows that were skipped or only partly applied
+    pub errors: Vec<RowError>,
+}
+
+impl ImportReport {
+    /// Wrap the parsed entries into a list from `source`
+    pub fn into_list(self, source: SanctionSource) -> SanctionsList {
+        let now = Utc::now();
+        SanctionsList {
+            id: format!("{}-{}", source.as_str(), now.timestamp()),
+            source,
+            entries: self.entries,
+            last_updated: now,
+            version: format!("import-{}", now.timestamp()),
+        }
+    }
+}
+
+/// A row that could not be imported
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowError {
+    /// File the row came from (e.g. `sdn.csv`)
+    pub file: String,
+
+    /// Line number of the row, starting at 1
+    pub line: u64,
+
+    /// What was wrong with the row
+    pub message: String,
+}
+
+impl RowError {
+    pub(crate) fn new(file: &str, line: u64, message: impl Into<String>) -> Self {
+        Self {
+            file: file.to_string(),
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for RowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.file, self.line, self.message)
+    }
+}
+
+/// Decode text that is usually UTF-8 but sometimes Latin-1
+///
+/// Older list exports mix encodings between rows, so each field is decoded
+/// on its own. A byte order mark and DOS end-of-file markers are dropped.
+pub(crate) fn decode_text(bytes: &[u8]) -> String {
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    let text = match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => bytes.iter().map(|&b| b as char).collect(),
+    };
+    text.replace('\u{1A}', "")
+}
+
+/// Parse a `YYYY-MM-DD` date as midnight UTC
+pub(crate) fn parse_date(value: &str) -> Option<chrono::DateTime<Utc>> {
+    let date = NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").ok()?;
+    Some(Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_text_falls_back_to_latin1() {
+        assert_eq!(decode_text(b"\xEF\xBB\xBFCAF\xC3\x89"), "CAFÉ");
+        assert_eq!(decode_text(b"CAF\xC9"), "CAFÉ");
+        assert_eq!(decode_text(b"\x1A"), "");
+    }
+}
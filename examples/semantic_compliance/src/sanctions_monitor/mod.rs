@@ -1,7 +1,23 @@
 //! Sanctions list monitoring and matching
 //!
 //! This module provides real-time monitoring of sanctions lists
-//! with semantic matching capabilities.
+//! with semantic matching capabilities. Lists can also be imported from the
+//! official OFAC SDN CSV files and the EU consolidated XML export.
+//!
+//! PEP lists ([`SanctionSource::PEP`]) are loaded and matched the same way,
+//! with their own thresholds from [`PepMatchingConfig`].
+
+mod eu;
+mod import;
+mod ofac;
+pub mod transliteration;
+
+pub use eu::parse_eu_xml;
+pub use import::{ImportReport, RowError};
+pub use ofac::parse_sdn_csv;
+pub use transliteration::{RomanizationScheme, Script, Transliteration};
+
+use transliteration::{comparable_skeleton, romanize, skeleton, CONSONANTAL_CONFIDENCE};
 
 use crate::models::*;
 use anyhow::Result;
@@ -129,7 +145,8 @@ impl SanctionsMonitor {
         Ok(all_matches)
     }
 
-    /// Check entity against a specific sanctions list
+    /// Check entity against a specific sanctions or PEP list, using the
+    /// match threshold of the list's category
     async fn check_against_list(
         &self,
         entity: &Entity,
@@ -139,7 +156,7 @@ impl SanctionsMonitor {
 
         for entry in &list.entries {
             if let Some(m) = self.matcher.match_entity(entity, entry).await? {
-                if m.confidence >= self.config.default_threshold {
+                if m.confidence >= self.config.match_threshold(list.source.category()) {
                     matches.push(m);
                 }
             }
@@ -175,17 +192,22 @@ impl SanctionsMonitor {
         for (source, list) in lists.iter() {
             for entry in &list.entries {
                 for entry_name in entry.names.iter().chain(entry.aliases.iter()) {
-                    let confidence = self.matcher.compare_names(name, entry_name);
+                    let comparison = self.matcher.compare_names_detailed(name, entry_name);
 
-                    if confidence >= threshold {
+                    if comparison.score >= threshold {
                         matches.push(SanctionMatch {
                             source: source.clone(),
                             entry: entry.clone(),
-                            confidence,
+                            confidence: comparison.score,
                             matched_field: MatchedField::Name,
                             entity_value: name.to_string(),
                             list_value: entry_name.clone(),
-                            algorithm: MatchAlgorithm::Fuzzy,
+                            algorithm: if comparison.transliteration.is_some() {
+                                MatchAlgorithm::Transliteration
+                            } else {
+                                MatchAlgorithm::Fuzzy
+                            },
+                            transliteration: comparison.transliteration,
                         });
                     }
                 }
@@ -265,6 +287,12 @@ impl SanctionsMonitor {
         Ok(entries)
     }
 
+    /// Load a sanctions list, replacing any list already loaded from its source
+    pub async fn insert_list(&self, list: SanctionsList) {
+        info!("Loaded {} list: {} entries", list.source.as_str(), list.entries.len());
+        self.lists.write().await.insert(list.source.clone(), list);
+    }
+
     /// Load sanctions lists from files (for offline mode or testing)
     pub async fn load_from_files(&self, paths: HashMap<SanctionSource, String>) -> Result<()> {
         info!("Loading sanctions lists from files");
@@ -310,7 +338,8 @@ impl SemanticMatcher {
         // 1. Match on names
         for entity_name in std::iter::once(&entity.name).chain(entity.aliases.iter()) {
             for entry_name in entry.names.iter().chain(entry.aliases.iter()) {
-                let confidence = self.compare_names(entity_name, entry_name);
+                let comparison = self.compare_names_detailed(entity_name, entry_name);
+                let confidence = comparison.score;
 
                 if confidence > best_confidence {
                     best_confidence = confidence;
@@ -321,11 +350,14 @@ impl SemanticMatcher {
                         matched_field: MatchedField::Name,
                         entity_value: entity_name.clone(),
                         list_value: entry_name.clone(),
-                        algorithm: if confidence == 1.0 {
+                        algorithm: if comparison.transliteration.is_some() {
+                            MatchAlgorithm::Transliteration
+                        } else if confidence == 1.0 {
                             MatchAlgorithm::Exact
                         } else {
                             MatchAlgorithm::Fuzzy
                         },
+                        transliteration: comparison.transliteration,
                     });
                 }
             }
@@ -350,6 +382,7 @@ impl SemanticMatcher {
                         entity_value: entity_id.value.clone(),
                         list_value: entry_id.value.clone(),
                         algorithm: MatchAlgorithm::Exact,
+                        transliteration: None,
                     }));
                 }
             }
@@ -360,6 +393,74 @@ impl SemanticMatcher {
 
     /// Compare two names with fuzzy matching
     pub fn compare_names(&self, name1: &str, name2: &str) -> f64 {
+        self.compare_names_detailed(name1, name2).score
+    }
+
+    /// Compare two names, reporting the transliteration behind the score
+    ///
+    /// With transliteration enabled and one of the names in Cyrillic or
+    /// Arabic script, each romanization of that name is scored against the
+    /// other name exactly like a direct comparison, and the best score wins.
+    pub fn compare_names_detailed(&self, name1: &str, name2: &str) -> NameComparison {
+        let mut best = NameComparison {
+            score: self.similarity(name1, name2),
+            transliteration: None,
+        };
+        if !self.config.transliteration {
+            return best;
+        }
+
+        // Names in the same script, or two different non-Latin scripts,
+        // are compared as written
+        let (original, other, script) = match (Script::detect(name1), Script::detect(name2)) {
+            (Script::Latin, Script::Latin) => return best,
+            (script, Script::Latin) => (name1, name2, script),
+            (Script::Latin, script) => (name2, name1, script),
+            _ => return best,
+        };
+
+        // On equal scores, prefer a romanization that spells the other name
+        // exactly, so the reported scheme is the one the list used
+        let target = Self::normalize_name(other);
+        let mut best_exact = false;
+        let mut consider = |score: f64, scheme: RomanizationScheme, romanized: String| {
+            let exact = Self::normalize_name(&romanized) == target;
+            if score > best.score || (score == best.score && exact && !best_exact) {
+                best_exact = exact;
+                best = NameComparison {
+                    score,
+                    transliteration: Some(Transliteration {
+                        script,
+                        scheme,
+                        original: original.to_string(),
+                        romanized,
+                    }),
+                };
+            }
+        };
+
+        for &scheme in script.schemes() {
+            if let Some(romanized) = romanize(original, scheme) {
+                consider(self.similarity(&romanized, other), scheme, romanized);
+            }
+        }
+
+        if script == Script::Arabic {
+            let scheme = RomanizationScheme::Consonantal;
+            let other_skeleton = skeleton(&target);
+            if let Some(romanized) = romanize(original, scheme) {
+                if comparable_skeleton(&romanized) && comparable_skeleton(&other_skeleton) {
+                    let score = strsim::jaro_winkler(&romanized, &other_skeleton);
+                    consider(CONSONANTAL_CONFIDENCE * score, scheme, romanized);
+                }
+            }
+        }
+
+        best
+    }
+
+    /// Similarity of two names as written
+    fn similarity(&self, name1: &str, name2: &str) -> f64 {
         // Normalize names
         let n1 = Self::normalize_name(name1);
         let n2 = Self::normalize_name(name2);
@@ -507,6 +608,19 @@ pub struct SanctionMatch {
 
     /// Algorithm used for matching
     pub algorithm: MatchAlgorithm,
+
+    /// Romanization that produced a name match, if one was needed
+    pub transliteration: Option<Transliteration>,
+}
+
+/// Score of a name comparison and the transliteration behind it
+#[derive(Debug, Clone, PartialEq)]
+pub struct NameComparison {
+    /// Similarity score (0.0 - 1.0)
+    pub score: f64,
+
+    /// Romanization that produced the score, if it beat the names as written
+    pub transliteration: Option<Transliteration>,
 }
 
 /// Statistics about loaded sanctions lists
@@ -534,6 +648,7 @@ mod tests {
             phonetic_matching: true,
             transliteration: false,
             max_edit_distance: 3,
+            pep: PepMatchingConfig::default(),
         };
 
         let monitor = SanctionsMonitor::new(config);
@@ -557,6 +672,7 @@ mod tests {
             phonetic_matching: false,
             transliteration: false,
             max_edit_distance: 3,
+            pep: PepMatchingConfig::default(),
         };
 
         let matcher = SemanticMatcher::new(config);
@@ -573,6 +689,131 @@ mod tests {
         assert!(score < 0.5);
     }
 
+    fn transliterating_matcher() -> SemanticMatcher {
+        SemanticMatcher::new(MatchingConfig {
+            transliteration: true,
+            ..MatchingConfig::default()
+        })
+    }
+
+    #[test]
+    fn test_transliterated_name_pairs() {
+        use RomanizationScheme::*;
+
+        let matcher = transliterating_matcher();
+        let config = MatchingConfig::default();
+        let positives = [
+            ("Сергей Иванов", "Sergei Ivanov", Icao9303),
+            ("Елена Петрова", "Yelena Petrova", BgnPcgn),
+            ("Юрий Щукин", "Yuriy Shchukin", BgnPcgn),
+            ("Наталья Ковальчук", "Natalya Kovalchuk", BgnPcgn),
+            ("Dmitry Medvedev", "Дмитрий Медведев", BgnPcgn),
+            ("علي", "Ali", BgnPcgn),
+            ("محمد علي", "Muhammad Ali", BgnPcgn),
+            ("عمر البشير", "Omar al-Bashir", BgnPcgn),
+            ("حسين", "Hussein", BgnPcgn),
+            ("أسامة بن لادن", "Osama bin Laden", BgnPcgn),
+        ];
+        for (name, list_name, scheme) in positives {
+            let comparison = matcher.compare_names_detailed(name, list_name);
+            assert!(
+                comparison.score >= config.default_threshold && comparison.score <= 1.0,
+                "{} / {}: {}",
+                name,
+                list_name,
+                comparison.score
+            );
+            assert_eq!(comparison.transliteration.unwrap().scheme, scheme, "{}", name);
+        }
+
+        let negatives = [
+            ("Сергей Иванов", "Viktor Bout"),
+            ("Владимир Козлов", "Anna Smirnova"),
+            ("محمد علي", "Hassan Nasrallah"),
+            ("عمر البشير", "Yusuf Qaradawi"),
+        ];
+        for (name, list_name) in negatives {
+            let score = matcher.compare_names(name, list_name);
+            assert!(score < config.default_threshold, "{} / {}: {}", name, list_name, score);
+        }
+    }
+
+    #[test]
+    fn test_transliteration_respects_config_and_thresholds() {
+        let config = MatchingConfig::default();
+        assert!(!config.transliteration);
+        let plain = SemanticMatcher::new(config.clone());
+        let comparison = plain.compare_names_detailed("Сергей Иванов", "Sergei Ivanov");
+        assert!(comparison.score < 0.5);
+        assert!(comparison.transliteration.is_none());
+
+        // Consonant skeletons are lossy and never reach critical on their own
+        let skeletons = SemanticMatcher::new(MatchingConfig {
+            transliteration: true,
+            phonetic_matching: false,
+            ..MatchingConfig::default()
+        });
+        let comparison = skeletons.compare_names_detailed("محمد علي", "Muhammad Ali");
+        assert_eq!(comparison.score, CONSONANTAL_CONFIDENCE);
+        assert!(comparison.score < config.critical_threshold);
+        let path = comparison.transliteration.unwrap();
+        assert_eq!(path.scheme, RomanizationScheme::Consonantal);
+        assert_eq!(path.romanized, "mhmdl");
+
+        // Latin pairs are untouched
+        let matcher = transliterating_matcher();
+        assert_eq!(
+            matcher.compare_names("John Doe", "Jon Doe"),
+            plain.compare_names("John Doe", "Jon Doe")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_match_records_transliteration_path() {
+        let matcher = transliterating_matcher();
+        let entity = Entity {
+            id: "CUST-1".to_string(),
+            name: "Сергей Иванов".to_string(),
+            entity_type: EntityType::Person,
+            aliases: vec![],
+            identifiers: vec![],
+            relationships: vec![],
+            risk_score: 0.0,
+            risk_level: RiskLevel::Low,
+            last_checked: Utc::now(),
+            created_at: Utc::now(),
+            metadata: HashMap::new(),
+            pep_status: None,
+        };
+        let entry = SanctionEntry {
+            id: "SDN-1".to_string(),
+            names: vec!["Sergei Ivanov".to_string()],
+            aliases: vec!["IVANOV, Sergei".to_string()],
+            entity_type: EntityType::Person,
+            programs: vec![],
+            identifiers: vec![],
+            addresses: vec![],
+            dates_of_birth: vec![],
+            nationalities: vec![],
+            remarks: None,
+            listed_date: None,
+        };
+
+        let m = matcher.match_entity(&entity, &entry).await.unwrap().unwrap();
+        assert_eq!(m.confidence, 1.0);
+        assert_eq!(m.matched_field, MatchedField::Name);
+        assert_eq!(m.algorithm, MatchAlgorithm::Transliteration);
+        assert_eq!(m.list_value, "Sergei Ivanov");
+        let path = m.transliteration.unwrap();
+        assert_eq!(path.script, Script::Cyrillic);
+        assert_eq!(path.scheme, RomanizationScheme::Icao9303);
+        assert_eq!(path.romanized, "sergei ivanov");
+        assert_eq!(
+            path.to_string(),
+            "Cyrillic via ICAO 9303: Сергей Иванов -> sergei ivanov"
+        );
+    }
+
     #[test]
     fn test_soundex() {
         let s1 = SemanticMatcher::simple_soundex("Robert");
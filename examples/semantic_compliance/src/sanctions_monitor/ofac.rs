@@ -0,0 +1,332 @@
+//! OFAC SDN list in the legacy CSV layout
+//!
+//! The list ships as three header-less files joined by `ent_num`:
+//!
+//! - `sdn.csv`: ent_num, name, type, program, title, call sign, vessel
+//!   type, tonnage, GRT, vessel flag, vessel owner, remarks
+//! - `alt.csv`: ent_num, alt_num, alt type, alt name, alt remarks
+//! - `add.csv`: ent_num, add_num, address, city/state/postal code, country,
+//!   address remarks
+//!
+//! Empty fields hold the `-0-` placeholder. Identifiers, dates of birth and
+//! nationalities only appear inside the free-text remarks, which are not
+//! always quoted; extra columns are folded back into the last field.
+
+use super::import::{decode_text, ImportReport, RowError};
+use crate::models::*;
+use csv::{ByteRecord, ReaderBuilder};
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+const SDN_FILE: &str = "sdn.csv";
+const ALT_FILE: &str = "alt.csv";
+const ADD_FILE: &str = "add.csv";
+
+const SDN_COLUMNS: usize = 12;
+const ALT_COLUMNS: usize = 5;
+const ADD_COLUMNS: usize = 6;
+
+/// Parse the SDN list, attaching alternate names and addresses by `ent_num`
+///
+/// Rows that cannot be parsed, and alternate names or addresses for unknown
+/// entries, are reported in [`ImportReport::errors`] and skipped.
+pub fn parse_sdn_csv(sdn: &[u8], alt: Option<&[u8]>, add: Option<&[u8]>) -> ImportReport {
+    let mut report = ImportReport::default();
+    let mut index = HashMap::new();
+
+    for_each_row(sdn, SDN_FILE, SDN_COLUMNS, &mut report.errors, |fields| {
+        let entry = sdn_entry(&fields)?;
+        if index.contains_key(&entry.id) {
+            return Err(format!("duplicate ent_num {}", entry.id));
+        }
+        index.insert(entry.id.clone(), report.entries.len());
+        report.entries.push(entry);
+        Ok(())
+    });
+
+    if let Some(alt) = alt {
+        for_each_row(alt, ALT_FILE, ALT_COLUMNS, &mut report.errors, |fields| {
+            let entry = lookup(&index, &mut report.entries, &fields[0])?;
+            let name = field(&fields[3]).ok_or("missing alternate name")?;
+            if !entry.aliases.contains(&name) {
+                entry.aliases.push(name);
+            }
+            Ok(())
+        });
+    }
+
+    if let Some(add) = add {
+        for_each_row(add, ADD_FILE, ADD_COLUMNS, &mut report.errors, |fields| {
+            let entry = lookup(&index, &mut report.entries, &fields[0])?;
+            entry.addresses.push(Address {
+                street: field(&fields[2]),
+                city: field(&fields[3]),
+                state: None,
+                postal_code: None,
+                country: field(&fields[4]).unwrap_or_default(),
+            });
+            Ok(())
+        });
+    }
+
+    report
+}
+
+/// Run `apply` on every non-blank row, recording failures against the row
+fn for_each_row(
+    data: &[u8],
+    file: &str,
+    columns: usize,
+    errors: &mut Vec<RowError>,
+    mut apply: impl FnMut(Vec<String>) -> Result<(), String>,
+) {
+    let mut reader = ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(data);
+    let mut record = ByteRecord::new();
+    let (mut scanned, mut line) = (0, 1);
+
+    loop {
+        match reader.read_byte_record(&mut record) {
+            Ok(false) => break,
+            Ok(true) => {}
+            Err(e) => {
+                errors.push(RowError::new(file, reader.position().line(), e.to_string()));
+                continue;
+            }
+        }
+
+        // The reader's line count lags on CRLF files, where a record's byte
+        // offset points at the previous row's `\n`, so count newlines here
+        let start = record.position().map_or(scanned, |p| p.byte() as usize);
+        let upto = (start + 1).clamp(scanned, data.len());
+        line += data[scanned..upto].iter().filter(|&&b| b == b'\n').count() as u64;
+        scanned = upto;
+
+        let mut fields: Vec<String> = record.iter().map(decode_text).collect();
+        if fields.iter().all(|f| f.trim().is_empty()) {
+            continue;
+        }
+        if fields.len() < columns {
+            errors.push(RowError::new(
+                file,
+                line,
+                format!("expected {} columns, found {}", columns, fields.len()),
+            ));
+            continue;
+        }
+        // Unquoted commas in the trailing remarks split them into extra columns
+        if fields.len() > columns {
+            let remarks = fields.split_off(columns - 1).join(",");
+            fields.push(remarks);
+        }
+
+        if let Err(message) = apply(fields) {
+            errors.push(RowError::new(file, line, message));
+        }
+    }
+}
+
+fn lookup<'a>(
+    index: &HashMap<String, usize>,
+    entries: &'a mut [SanctionEntry],
+    ent_num: &str,
+) -> Result<&'a mut SanctionEntry, String> {
+    let id = ent_num.trim();
+    index
+        .get(id)
+        .map(|&i| &mut entries[i])
+        .ok_or_else(|| format!("unknown ent_num {}", id))
+}
+
+/// A field's trimmed value, or `None` for blanks and the `-0-` placeholder
+fn field(raw: &str) -> Option<String> {
+    let value = raw.trim();
+    (!value.is_empty() && value != "-0-").then(|| value.to_string())
+}
+
+fn sdn_entry(fields: &[String]) -> Result<SanctionEntry, String> {
+    let id = fields[0].trim();
+    if id.parse::<u64>().is_err() {
+        return Err(format!("invalid ent_num {:?}", id));
+    }
+    let name = field(&fields[1]).ok_or("missing name")?;
+
+    let entity_type = match field(&fields[2]).map(|t| t.to_lowercase()).as_deref() {
+        None => EntityType::Company,
+        Some("individual") => EntityType::Person,
+        Some(other) => EntityType::Other(other.to_string()),
+    };
+
+    let programs = field(&fields[3])
+        .map(|p| {
+            p.split("] [")
+                .map(|p| p.trim_matches(|c: char| c == '[' || c == ']' || c.is_whitespace()))
+                .filter(|p| !p.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut entry = SanctionEntry {
+        id: id.to_string(),
+        names: vec![name],
+        aliases: vec![],
+        entity_type,
+        programs,
+        identifiers: vec![],
+        addresses: vec![],
+        dates_of_birth: vec![],
+        nationalities: vec![],
+        remarks: field(&fields[11]),
+        listed_date: None,
+    };
+
+    if let Some(call_sign) = field(&fields[5]) {
+        entry.identifiers.push(Identifier {
+            id_type: IdentifierType::Custom("Call Sign".to_string()),
+            value: call_sign,
+            issuer: field(&fields[9]),
+            issue_date: None,
+            expiry_date: None,
+        });
+    }
+    if let Some(remarks) = entry.remarks.clone() {
+        apply_remarks(&mut entry, &remarks);
+    }
+
+    Ok(entry)
+}
+
+/// Pull identifiers, dates of birth and nationalities out of the remarks
+///
+/// Remarks are `;`-separated, e.g.
+/// `DOB 01 Jan 1960; nationality Freedonia; Passport X123 (Freedonia).`
+fn apply_remarks(entry: &mut SanctionEntry, remarks: &str) {
+    for segment in remarks.trim().trim_end_matches('.').split(';') {
+        let segment = segment.trim();
+        let segment = segment.strip_prefix("alt. ").unwrap_or(segment);
+
+        if let Some(dob) = segment.strip_prefix("DOB ") {
+            entry.dates_of_birth.push(dob.trim().to_string());
+        } else if let Some(country) = segment
+            .strip_prefix("nationality ")
+            .or_else(|| segment.strip_prefix("citizen "))
+        {
+            let country = country.trim().to_string();
+            if !entry.nationalities.contains(&country) {
+                entry.nationalities.push(country);
+            }
+        } else if let Some(identifier) = remark_identifier(segment) {
+            entry.identifiers.push(identifier);
+        }
+    }
+}
+
+fn remark_identifier(segment: &str) -> Option<Identifier> {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    let pattern = PATTERN.get_or_init(|| {
+        Regex::new(
+            r"^(Passport|National ID No\.|Cedula No\.|Tax ID No\.|RFC|Driver's License No\.|Business Registration (?:Number|Document #)|Registration ID|Company Number|SWIFT/BIC|Legal Entity Number) ([^(]+)(?:\(([^)]*)\))?",
+        )
+        .expect("valid remark pattern")
+    });
+
+    let captures = pattern.captures(segment)?;
+    let id_type = match &captures[1] {
+        "Passport" => IdentifierType::Passport,
+        "National ID No." | "Cedula No." => IdentifierType::NationalId,
+        "Tax ID No." | "RFC" => IdentifierType::TaxId,
+        "Driver's License No." => IdentifierType::DriversLicense,
+        "SWIFT/BIC" => IdentifierType::Swift,
+        "Legal Entity Number" => IdentifierType::LEI,
+        _ => IdentifierType::BusinessRegistration,
+    };
+    let value = captures[2].trim();
+    (!value.is_empty()).then(|| Identifier {
+        id_type,
+        value: value.to_string(),
+        issuer: captures.get(3).map(|m| m.as_str().trim().to_string()),
+        issue_date: None,
+        expiry_date: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SDN: &[u8] = include_bytes!("../../tests/fixtures/sdn.csv");
+    const ALT: &[u8] = include_bytes!("../../tests/fixtures/alt.csv");
+    const ADD: &[u8] = include_bytes!("../../tests/fixtures/add.csv");
+
+    fn entry<'a>(report: &'a ImportReport, id: &str) -> &'a SanctionEntry {
+        report.entries.iter().find(|e| e.id == id).unwrap()
+    }
+
+    #[test]
+    fn test_parse_sdn_fixture() {
+        let report = parse_sdn_csv(SDN, Some(ALT), Some(ADD));
+
+        assert_eq!(report.entries.len(), 5);
+        let lines: Vec<_> = report
+            .errors
+            .iter()
+            .map(|e| (e.file.as_str(), e.line))
+            .collect();
+        assert_eq!(lines, vec![(SDN_FILE, 5), (ALT_FILE, 4), (ADD_FILE, 3)]);
+
+        let doe = entry(&report, "102");
+        assert_eq!(doe.names, vec!["DOE, John Quincy"]);
+        assert_eq!(doe.aliases, vec!["DOE, Johnny", "QUINCY, J."]);
+        assert_eq!(doe.entity_type, EntityType::Person);
+        assert_eq!(doe.programs, vec!["SDNTK"]);
+        assert_eq!(doe.dates_of_birth, vec!["01 Jan 1960"]);
+        assert_eq!(doe.nationalities, vec!["Freedonia"]);
+        let passports: Vec<_> = doe
+            .identifiers
+            .iter()
+            .filter(|i| i.id_type == IdentifierType::Passport)
+            .map(|i| (i.value.as_str(), i.issuer.as_deref()))
+            .collect();
+        assert_eq!(
+            passports,
+            vec![
+                ("X1234567", Some("Freedonia")),
+                ("Y7654321", Some("Freedonia"))
+            ]
+        );
+        assert_eq!(doe.addresses[0].city.as_deref(), Some("Springfield"));
+
+        let shipping = entry(&report, "101");
+        assert_eq!(shipping.programs, vec!["SDGT", "IRAN"]);
+        assert_eq!(shipping.entity_type, EntityType::Company);
+        assert_eq!(shipping.aliases, vec!["EXAMPLE SHIPPING CO"]);
+        assert_eq!(
+            shipping.identifiers[0].id_type,
+            IdentifierType::BusinessRegistration
+        );
+    }
+
+    #[test]
+    fn test_sdn_quirks() {
+        let report = parse_sdn_csv(SDN, None, None);
+
+        // Unquoted remarks with commas are rejoined
+        let roe = entry(&report, "104");
+        assert_eq!(
+            roe.remarks.as_deref(),
+            Some("DOB 1975; POB Capital City, Freedonia; Passport Z555 (Freedonia).")
+        );
+        assert_eq!(roe.identifiers[0].value, "Z555");
+
+        // Latin-1 bytes decode instead of failing the row
+        assert_eq!(entry(&report, "105").names, vec!["CAFÉ LATIN TRADING"]);
+
+        let vessel = entry(&report, "103");
+        assert_eq!(vessel.entity_type, EntityType::Other("vessel".to_string()));
+        assert_eq!(vessel.identifiers[0].value, "ABCD1");
+    }
+}
@@ -0,0 +1,321 @@
+//! Romanization of Cyrillic and Arabic names
+//!
+//! Sanctions lists publish names in Latin script while customer records
+//! often keep the original spelling. Before fuzzy matching, a name in
+//! another script is romanized with each scheme for its script and every
+//! candidate is compared; the winning candidate is reported as a
+//! [`Transliteration`] so analysts can see why two names matched.
+//!
+//! Arabic script leaves short vowels unwritten, so Arabic names are also
+//! compared by their consonant skeleton. Skeleton hits are capped at
+//! [`CONSONANTAL_CONFIDENCE`] and never reach the critical threshold alone.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use unicode_normalization::UnicodeNormalization;
+
+/// Highest score a consonant skeleton comparison can produce
+pub const CONSONANTAL_CONFIDENCE: f64 = 0.85;
+
+/// Shortest skeleton worth comparing; shorter ones match too much
+const MIN_SKELETON_LEN: usize = 3;
+
+/// Writing system of a name
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Script {
+    /// Latin (no transliteration needed)
+    Latin,
+
+    /// Cyrillic (Russian, Ukrainian, ...)
+    Cyrillic,
+
+    /// Arabic (Arabic, Persian, ...)
+    Arabic,
+}
+
+impl Script {
+    /// The script of the first non-Latin letter in `name`, or Latin
+    pub fn detect(name: &str) -> Self {
+        name.chars()
+            .filter(|c| c.is_alphabetic())
+            .find_map(|c| match c {
+                '\u{0400}'..='\u{04FF}' => Some(Self::Cyrillic),
+                '\u{0600}'..='\u{06FF}'
+                | '\u{0750}'..='\u{077F}'
+                | '\u{FB50}'..='\u{FDFF}'
+                | '\u{FE70}'..='\u{FEFF}' => Some(Self::Arabic),
+                _ => None,
+            })
+            .unwrap_or(Self::Latin)
+    }
+
+    /// Romanization schemes tried for this script, most common first
+    pub fn schemes(self) -> &'static [RomanizationScheme] {
+        match self {
+            Self::Latin => &[],
+            Self::Cyrillic => &[RomanizationScheme::BgnPcgn, RomanizationScheme::Icao9303],
+            Self::Arabic => &[RomanizationScheme::BgnPcgn],
+        }
+    }
+}
+
+impl fmt::Display for Script {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Latin => write!(f, "Latin"),
+            Self::Cyrillic => write!(f, "Cyrillic"),
+            Self::Arabic => write!(f, "Arabic"),
+        }
+    }
+}
+
+/// How a name was brought into Latin script
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RomanizationScheme {
+    /// BGN/PCGN, without diacritics (as used on US and UK sanctions lists)
+    BgnPcgn,
+
+    /// ICAO Doc 9303, as printed in machine-readable passports
+    Icao9303,
+
+    /// Arabic BGN/PCGN reduced to its consonants, compared against the
+    /// consonants of the Latin name
+    Consonantal,
+}
+
+impl fmt::Display for RomanizationScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BgnPcgn => write!(f, "BGN/PCGN"),
+            Self::Icao9303 => write!(f, "ICAO 9303"),
+            Self::Consonantal => write!(f, "consonant skeleton"),
+        }
+    }
+}
+
+/// The transliteration that produced a name match
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Transliteration {
+    /// Script of the original name
+    pub script: Script,
+
+    /// Scheme that produced the winning candidate
+    pub scheme: RomanizationScheme,
+
+    /// Name as written
+    pub original: String,
+
+    /// Candidate that was compared
+    pub romanized: String,
+}
+
+impl fmt::Display for Transliteration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} via {}: {} -> {}",
+            self.script, self.scheme, self.original, self.romanized
+        )
+    }
+}
+
+/// Romanize `name` with `scheme`
+///
+/// Characters outside the name's script are kept as they are. Returns
+/// `None` if the scheme does not apply to the name's script.
+pub fn romanize(name: &str, scheme: RomanizationScheme) -> Option<String> {
+    let name: String = name.nfkc().collect();
+    match (Script::detect(&name), scheme) {
+        (Script::Cyrillic, RomanizationScheme::BgnPcgn | RomanizationScheme::Icao9303) => {
+            Some(romanize_cyrillic(&name, scheme))
+        }
+        (Script::Arabic, RomanizationScheme::BgnPcgn) => Some(romanize_arabic(&name)),
+        (Script::Arabic, RomanizationScheme::Consonantal) => {
+            Some(skeleton(&romanize_arabic(&name)))
+        }
+        _ => None,
+    }
+}
+
+/// Consonants of a romanized name, with vowels, `y`, `w` and doubled
+/// letters dropped so that `Muhammad` and `mhmd` agree
+pub(crate) fn skeleton(romanized: &str) -> String {
+    let mut out = String::new();
+    for c in romanized.to_lowercase().chars() {
+        if !c.is_ascii_alphabetic() || "aeiouyw".contains(c) || out.ends_with(c) {
+            continue;
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Whether a skeleton is long enough to compare
+pub(crate) fn comparable_skeleton(skeleton: &str) -> bool {
+    skeleton.len() >= MIN_SKELETON_LEN
+}
+
+fn romanize_cyrillic(name: &str, scheme: RomanizationScheme) -> String {
+    let icao = scheme == RomanizationScheme::Icao9303;
+    let mut out = String::new();
+    let mut previous: Option<char> = None;
+
+    for c in name.chars() {
+        let lower = c.to_lowercase().next().unwrap_or(c);
+        // BGN/PCGN writes е as ye at the start of a word and after vowels
+        let after_vowel =
+            previous.is_none_or(|p| !p.is_alphabetic() || "аеёиоуыэюяйъь".contains(p));
+        let latin = match lower {
+            'а' => "a",
+            'б' => "b",
+            'в' => "v",
+            'г' => "g",
+            'ґ' => "g",
+            'д' => "d",
+            'е' if !icao && after_vowel => "ye",
+            'е' => "e",
+            'ё' if icao => "e",
+            'ё' => "yo",
+            'є' if icao => "ie",
+            'є' => "ye",
+            'ж' => "zh",
+            'з' => "z",
+            'и' => "i",
+            'і' => "i",
+            'ї' if icao => "i",
+            'ї' => "yi",
+            'й' if icao => "i",
+            'й' => "y",
+            'к' => "k",
+            'л' => "l",
+            'м' => "m",
+            'н' => "n",
+            'о' => "o",
+            'п' => "p",
+            'р' => "r",
+            'с' => "s",
+            'т' => "t",
+            'у' => "u",
+            'ў' => "w",
+            'ф' => "f",
+            'х' => "kh",
+            'ц' => "ts",
+            'ч' => "ch",
+            'ш' => "sh",
+            'щ' => "shch",
+            'ъ' if icao => "ie",
+            'ъ' => "",
+            'ы' => "y",
+            'ь' => "",
+            'э' => "e",
+            'ю' if icao => "iu",
+            'ю' => "yu",
+            'я' if icao => "ia",
+            'я' => "ya",
+            _ => {
+                out.push(lower);
+                previous = Some(lower);
+                continue;
+            }
+        };
+        out.push_str(latin);
+        previous = Some(lower);
+    }
+
+    out
+}
+
+fn romanize_arabic(name: &str) -> String {
+    let mut out = String::new();
+    let mut word_start = true;
+
+    for c in name.chars() {
+        let latin = match c {
+            // Short vowel marks, shadda, sukun, superscript alef and tatweel
+            '\u{064B}'..='\u{065F}' | '\u{0670}' | '\u{0640}' => continue,
+            'ا' | 'آ' | 'ى' | 'ة' => "a",
+            'أ' if word_start => "a",
+            'إ' => "i",
+            'أ' | 'ء' | 'ؤ' | 'ئ' => "",
+            'ع' if word_start => "a",
+            'ع' => "",
+            'ب' => "b",
+            'پ' => "p",
+            'ت' => "t",
+            'ث' => "th",
+            'ج' => "j",
+            'چ' => "ch",
+            'ح' | 'ه' | 'ھ' => "h",
+            'خ' => "kh",
+            'د' => "d",
+            'ذ' => "dh",
+            'ر' => "r",
+            'ز' => "z",
+            'ژ' => "zh",
+            'س' | 'ص' => "s",
+            'ش' => "sh",
+            'ض' => "d",
+            'ط' => "t",
+            'ظ' => "z",
+            'غ' => "gh",
+            'ف' => "f",
+            'ق' => "q",
+            'ك' | 'ک' => "k",
+            'گ' => "g",
+            'ل' => "l",
+            'م' => "m",
+            'ن' => "n",
+            'و' if word_start => "w",
+            'و' => "u",
+            'ي' | 'ی' if word_start => "y",
+            'ي' | 'ی' => "i",
+            _ => {
+                word_start = !c.is_alphabetic();
+                out.push(c);
+                continue;
+            }
+        };
+        out.push_str(latin);
+        word_start = false;
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_script() {
+        assert_eq!(Script::detect("Sergei Ivanov"), Script::Latin);
+        assert_eq!(Script::detect("Сергей Иванов"), Script::Cyrillic);
+        assert_eq!(Script::detect("محمد علي"), Script::Arabic);
+        assert_eq!(Script::detect("123 Сергей"), Script::Cyrillic);
+    }
+
+    #[test]
+    fn test_romanize_cyrillic() {
+        let bgn = |n| romanize(n, RomanizationScheme::BgnPcgn).unwrap();
+        let icao = |n| romanize(n, RomanizationScheme::Icao9303).unwrap();
+
+        assert_eq!(bgn("Сергей Иванов"), "sergey ivanov");
+        assert_eq!(icao("Сергей Иванов"), "sergei ivanov");
+        assert_eq!(bgn("Елена Щукина"), "yelena shchukina");
+        assert_eq!(icao("Юлия Ковальчук"), "iuliia kovalchuk");
+        assert_eq!(romanize("Sergei", RomanizationScheme::BgnPcgn), None);
+    }
+
+    #[test]
+    fn test_romanize_arabic() {
+        assert_eq!(
+            romanize("عُمَر البشير", RomanizationScheme::BgnPcgn).unwrap(),
+            "amr albshir"
+        );
+        assert_eq!(
+            romanize("محمد علي", RomanizationScheme::Consonantal).unwrap(),
+            "mhmdl"
+        );
+        assert_eq!(skeleton("Muhammad Ali"), "mhmdl");
+    }
+}
@@ -19,6 +19,13 @@
 
 use adk::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet, VecDeque};
+
+/// Default short-term memory window (interactions)
+pub const DEFAULT_STM_WINDOW: u32 = 100;
+
+/// Deepest neighborhood `get_neighborhood` will walk
+pub const MAX_NEIGHBORHOOD_DEPTH: u32 = 5;
 
 // ============================================================================
 // Memory Types (Ineru-inspired)
@@ -34,16 +41,97 @@ pub struct ShortTermMemory {
     /// Memory timestamp
     pub timestamp: u64,
 
-    /// Recent interactions (sliding window)
+    /// Recent interactions, oldest first
     pub interactions: Vec<Interaction>,
 
-    /// Attention weights for context
+    /// Attention weight of each interaction, parallel to `interactions`
     pub attention_weights: Vec<f32>,
 
     /// Working memory state
     pub working_state: Vec<u8>,
 }
 
+impl ShortTermMemory {
+    /// Add an interaction, evicting the lowest-attention interactions while
+    /// more than `window` are held
+    ///
+    /// Ties are broken by evicting the oldest. `window` is treated as at
+    /// least 1.
+    pub fn record(&mut self, interaction: Interaction, weight: f32, window: u32) {
+        self.align_weights();
+        self.interactions.push(interaction);
+        self.attention_weights.push(weight);
+
+        let window = window.max(1) as usize;
+        while self.interactions.len() > window {
+            let evict = self
+                .attention_weights
+                .iter()
+                .enumerate()
+                .min_by(|a, b| a.1.total_cmp(b.1))
+                .map(|(i, _)| i)
+                .unwrap_or(0);
+            self.interactions.remove(evict);
+            self.attention_weights.remove(evict);
+        }
+    }
+
+    /// Make `attention_weights` the same length as `interactions`
+    ///
+    /// Missing weights default to each interaction's absolute reward.
+    pub fn align_weights(&mut self) {
+        let len = self.interactions.len();
+        self.attention_weights.truncate(len);
+        let start = self.attention_weights.len();
+        self.attention_weights
+            .extend(self.interactions[start..].iter().map(default_attention));
+    }
+
+    /// Summarize the memory against the configured `window`
+    pub fn summary(&self, window: u32) -> StmSummary {
+        let mut aligned = self.clone();
+        aligned.align_weights();
+        let mut weights = aligned.attention_weights;
+        weights.sort_by(f32::total_cmp);
+
+        let n = weights.len();
+        let weight_stats = (n > 0).then(|| WeightStats {
+            min: weights[0],
+            max: weights[n - 1],
+            mean: weights.iter().sum::<f32>() / n as f32,
+            median: (weights[(n - 1) / 2] + weights[n / 2]) / 2.0,
+        });
+
+        StmSummary {
+            agent_id: self.agent_id.clone(),
+            interaction_count: self.interactions.len(),
+            window,
+            weight_stats,
+            oldest_timestamp: self.interactions.iter().map(|i| i.timestamp).min(),
+            newest_timestamp: self.interactions.iter().map(|i| i.timestamp).max(),
+        }
+    }
+}
+
+/// Attention weight used when none was given
+fn default_attention(interaction: &Interaction) -> f32 {
+    interaction.reward.abs()
+}
+
+/// Per-agent configuration
+#[hdk_entry_helper]
+#[derive(Clone)]
+pub struct AgentConfig {
+    /// Agent identifier
+    pub agent_id: String,
+
+    /// Maximum interactions kept in short-term memory
+    pub stm_window: u32,
+
+    /// When this version of the config was written
+    pub updated_at: u64,
+}
+
 /// Long-term memory checkpoint
 #[hdk_entry_helper]
 #[derive(Clone)]
@@ -99,6 +187,58 @@ pub struct KnowledgeGraph {
     pub embeddings: Vec<u8>,
 }
 
+impl KnowledgeGraph {
+    /// Set node and edge counts from on-chain facts
+    ///
+    /// Nodes are the distinct subjects and objects; every fact is an edge.
+    pub fn fold_facts(&mut self, facts: &[KnowledgeFact]) {
+        let nodes: HashSet<&str> = facts
+            .iter()
+            .flat_map(|f| [f.subject.as_str(), f.object.as_str()])
+            .collect();
+        self.node_count = nodes.len() as u32;
+        self.edge_count = facts.len() as u32;
+    }
+}
+
+/// A public fact asserted by an agent
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct KnowledgeFact {
+    /// Agent identifier
+    pub agent_id: String,
+
+    /// Subject node
+    pub subject: String,
+
+    /// Relation
+    pub predicate: String,
+
+    /// Object node
+    pub object: String,
+
+    /// Assertion timestamp
+    pub asserted_at: u64,
+}
+
+/// Subject/predicate filter for `query_knowledge`
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct KnowledgePattern {
+    /// Match only this subject
+    pub subject: Option<String>,
+
+    /// Match only this predicate
+    pub predicate: Option<String>,
+}
+
+impl KnowledgePattern {
+    /// Whether `fact` matches every given field
+    pub fn matches(&self, fact: &KnowledgeFact) -> bool {
+        self.subject.as_ref().is_none_or(|s| *s == fact.subject)
+            && self.predicate.as_ref().is_none_or(|p| *p == fact.predicate)
+    }
+}
+
 /// Model parameters
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct ModelParams {
@@ -223,6 +363,12 @@ pub enum EntryTypes {
 
     #[entry_def(visibility = "public")]
     LearningEvent(LearningEvent),
+
+    #[entry_def(visibility = "public")]
+    AgentConfig(AgentConfig),
+
+    #[entry_def(visibility = "public")]
+    KnowledgeFact(KnowledgeFact),
 }
 
 #[hdk_link_types]
@@ -239,8 +385,15 @@ pub enum LinkTypes {
     /// All agents anchor
     AllAgents,
 
-    /// Knowledge graph edges
+    /// Knowledge graph edges: subject node anchor -> object node anchor,
+    /// tagged with predicate and object
     KnowledgeEdge,
+
+    /// Agent -> Configuration versions
+    AgentToConfig,
+
+    /// Agent -> Knowledge facts
+    AgentToKnowledge,
 }
 
 // ============================================================================
@@ -260,6 +413,7 @@ pub fn create_agent(agent_id: String) -> ExternResult<ActionHash> {
         metrics: AgentMetrics::default(),
     };
 
+    let created_at = ltm.created_at;
     let action_hash = create_entry(EntryTypes::LongTermMemory(ltm))?;
 
     // Link to all agents anchor
@@ -271,35 +425,66 @@ pub fn create_agent(agent_id: String) -> ExternResult<ActionHash> {
         agent_id.as_bytes().to_vec(),
     )?;
 
+    // Default configuration
+    store_agent_config(
+        action_hash.clone(),
+        AgentConfig {
+            agent_id,
+            stm_window: DEFAULT_STM_WINDOW,
+            updated_at: created_at,
+        },
+    )?;
+
     Ok(action_hash)
 }
 
+/// Update an agent's configuration
+///
+/// A smaller `stm_window` takes effect on the next short-term memory update.
+#[hdk_extern]
+pub fn update_agent_config(input: UpdateAgentConfigInput) -> ExternResult<ActionHash> {
+    if input.stm_window == 0 {
+        return Err(wasm_error!(WasmErrorInner::Guest("stm_window must be > 0".into())));
+    }
+    let agent_hash = get_agent_hash(&input.agent_id)?
+        .ok_or(wasm_error!(WasmErrorInner::Guest("Agent not found".into())))?;
+
+    store_agent_config(
+        agent_hash,
+        AgentConfig {
+            agent_id: input.agent_id,
+            stm_window: input.stm_window,
+            updated_at: sys_time()?.as_micros() as u64 / 1000,
+        },
+    )
+}
+
+/// Get an agent's current configuration
+#[hdk_extern]
+pub fn get_agent_config(agent_id: String) -> ExternResult<AgentConfig> {
+    get_latest_config(&agent_id)
+}
+
 /// Update short-term memory with new interaction
 #[hdk_extern]
 pub fn update_short_term_memory(input: UpdateSTMInput) -> ExternResult<ActionHash> {
     let timestamp = sys_time()?.as_micros() as u64 / 1000;
 
-    // Get existing STM or create new
-    let stm = if let Some(existing) = get_latest_stm(&input.agent_id)? {
-        let mut updated = existing;
-        updated.timestamp = timestamp;
-        updated.interactions.push(input.interaction);
-
-        // Keep sliding window (last 100 interactions)
-        if updated.interactions.len() > 100 {
-            updated.interactions.remove(0);
-        }
+    let window = get_latest_config(&input.agent_id)?.stm_window;
+    let weight = input
+        .attention_weight
+        .unwrap_or_else(|| default_attention(&input.interaction));
 
-        updated
-    } else {
-        ShortTermMemory {
-            agent_id: input.agent_id.clone(),
-            timestamp,
-            interactions: vec![input.interaction],
-            attention_weights: vec![],
-            working_state: vec![],
-        }
-    };
+    // Get existing STM or create new
+    let mut stm = get_latest_stm(&input.agent_id)?.unwrap_or_else(|| ShortTermMemory {
+        agent_id: input.agent_id.clone(),
+        timestamp,
+        interactions: vec![],
+        attention_weights: vec![],
+        working_state: vec![],
+    });
+    stm.timestamp = timestamp;
+    stm.record(input.interaction, weight, window);
 
     let action_hash = create_entry(EntryTypes::ShortTermMemory(stm))?;
 
@@ -324,7 +509,7 @@ pub fn checkpoint_memory(input: CheckpointInput) -> ExternResult<ActionHash> {
     // Get latest checkpoint version
     let version = get_latest_ltm_version(&input.agent_id)?.unwrap_or(0) + 1;
 
-    let ltm = LongTermMemory {
+    let mut ltm = LongTermMemory {
         agent_id: input.agent_id.clone(),
         version,
         created_at: timestamp,
@@ -332,6 +517,10 @@ pub fn checkpoint_memory(input: CheckpointInput) -> ExternResult<ActionHash> {
         model_params: input.model_params,
         metrics: input.metrics,
     };
+    if input.fold_knowledge {
+        let facts = get_knowledge_facts(&input.agent_id, None)?;
+        ltm.knowledge_graph.fold_facts(&facts);
+    }
 
     let action_hash = create_entry(EntryTypes::LongTermMemory(ltm))?;
 
@@ -404,6 +593,82 @@ pub fn get_agent_metrics(agent_id: String) -> ExternResult<AgentMetrics> {
     Ok(ltm.metrics)
 }
 
+/// Summarize an agent's short-term memory
+#[hdk_extern]
+pub fn get_stm_summary(agent_id: String) -> ExternResult<StmSummary> {
+    let window = get_latest_config(&agent_id)?.stm_window;
+    let stm = get_latest_stm(&agent_id)?.unwrap_or_else(|| ShortTermMemory {
+        agent_id: agent_id.clone(),
+        timestamp: 0,
+        interactions: vec![],
+        attention_weights: vec![],
+        working_state: vec![],
+    });
+
+    Ok(stm.summary(window))
+}
+
+/// Assert a public fact for an agent
+///
+/// Creates the fact entry and a `KnowledgeEdge` link from the subject node to
+/// the object node, so other agents can traverse the graph.
+#[hdk_extern]
+pub fn assert_knowledge(input: AssertKnowledgeInput) -> ExternResult<ActionHash> {
+    let agent_hash = get_agent_hash(&input.agent_id)?
+        .ok_or(wasm_error!(WasmErrorInner::Guest("Agent not found".into())))?;
+
+    let fact = KnowledgeFact {
+        agent_id: input.agent_id,
+        subject: input.subject,
+        predicate: input.predicate,
+        object: input.object,
+        asserted_at: sys_time()?.as_micros() as u64 / 1000,
+    };
+    let action_hash = create_entry(EntryTypes::KnowledgeFact(fact.clone()))?;
+
+    create_link(
+        agent_hash,
+        action_hash.clone(),
+        LinkTypes::AgentToKnowledge,
+        fact.subject.as_bytes().to_vec(),
+    )?;
+    create_link(
+        node_anchor_hash(&fact.agent_id, &fact.subject)?,
+        node_anchor_hash(&fact.agent_id, &fact.object)?,
+        LinkTypes::KnowledgeEdge,
+        edge_tag(&fact.predicate, &fact.object),
+    )?;
+
+    Ok(action_hash)
+}
+
+/// Query an agent's facts by subject and/or predicate
+#[hdk_extern]
+pub fn query_knowledge(input: QueryKnowledgeInput) -> ExternResult<Vec<KnowledgeFact>> {
+    let mut facts = get_knowledge_facts(&input.agent_id, input.pattern.subject.as_deref())?;
+    facts.retain(|f| input.pattern.matches(f));
+    facts.sort_by_key(|f| f.asserted_at);
+
+    Ok(facts)
+}
+
+/// Walk an agent's knowledge graph outwards from a node
+///
+/// Follows `KnowledgeEdge` links from subject to object up to `depth` hops
+/// (at most `MAX_NEIGHBORHOOD_DEPTH`).
+#[hdk_extern]
+pub fn get_neighborhood(input: NeighborhoodInput) -> ExternResult<Neighborhood> {
+    let depth = input.depth.min(MAX_NEIGHBORHOOD_DEPTH);
+    walk_neighborhood(&input.node, depth, |node| {
+        let links = get_links(
+            node_anchor_hash(&input.agent_id, node)?,
+            LinkTypes::KnowledgeEdge,
+            None,
+        )?;
+        Ok(links.iter().filter_map(|l| parse_edge_tag(&l.tag.0)).collect())
+    })
+}
+
 /// Get all agents
 #[hdk_extern]
 pub fn get_all_agents(_: ()) -> ExternResult<Vec<String>> {
@@ -426,6 +691,16 @@ pub fn get_all_agents(_: ()) -> ExternResult<Vec<String>> {
 pub struct UpdateSTMInput {
     pub agent_id: String,
     pub interaction: Interaction,
+
+    /// Attention weight; defaults to the interaction's absolute reward
+    #[serde(default)]
+    pub attention_weight: Option<f32>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UpdateAgentConfigInput {
+    pub agent_id: String,
+    pub stm_window: u32,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -434,6 +709,79 @@ pub struct CheckpointInput {
     pub knowledge_graph: KnowledgeGraph,
     pub model_params: ModelParams,
     pub metrics: AgentMetrics,
+
+    /// Replace the knowledge graph counts with those of the on-chain facts
+    #[serde(default)]
+    pub fold_knowledge: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AssertKnowledgeInput {
+    pub agent_id: String,
+    pub subject: String,
+    pub predicate: String,
+    pub object: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct QueryKnowledgeInput {
+    pub agent_id: String,
+    pub pattern: KnowledgePattern,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct NeighborhoodInput {
+    pub agent_id: String,
+    pub node: String,
+    pub depth: u32,
+}
+
+// ============================================================================
+// Response Types
+// ============================================================================
+
+/// Result of `get_stm_summary`
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StmSummary {
+    /// Agent identifier
+    pub agent_id: String,
+
+    /// Interactions currently held
+    pub interaction_count: usize,
+
+    /// Configured window
+    pub window: u32,
+
+    /// Attention weight distribution, if any interactions are held
+    pub weight_stats: Option<WeightStats>,
+
+    /// Timestamp of the oldest interaction held
+    pub oldest_timestamp: Option<u64>,
+
+    /// Timestamp of the newest interaction held
+    pub newest_timestamp: Option<u64>,
+}
+
+/// Result of `get_neighborhood`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Neighborhood {
+    /// Starting node
+    pub root: String,
+
+    /// Reached nodes and their distance from the root, root included
+    pub nodes: BTreeMap<String, u32>,
+
+    /// Traversed edges as (subject, predicate, object)
+    pub edges: Vec<(String, String, String)>,
+}
+
+/// Attention weight distribution
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct WeightStats {
+    pub min: f32,
+    pub max: f32,
+    pub mean: f32,
+    pub median: f32,
 }
 
 // ============================================================================
@@ -451,6 +799,128 @@ fn get_agent_hash(agent_id: &str) -> ExternResult<Option<ActionHash>> {
     Ok(links.first().and_then(|l| l.target.clone().into_action_hash()))
 }
 
+fn node_anchor_hash(agent_id: &str, node: &str) -> ExternResult<EntryHash> {
+    anchor_hash(&format!("kg:{}:{}", agent_id, node))
+}
+
+/// `KnowledgeEdge` tag: predicate and object separated by a NUL byte
+fn edge_tag(predicate: &str, object: &str) -> Vec<u8> {
+    let mut tag = predicate.as_bytes().to_vec();
+    tag.push(0);
+    tag.extend_from_slice(object.as_bytes());
+    tag
+}
+
+fn parse_edge_tag(tag: &[u8]) -> Option<(String, String)> {
+    let split = tag.iter().position(|&b| b == 0)?;
+    let predicate = String::from_utf8(tag[..split].to_vec()).ok()?;
+    let object = String::from_utf8(tag[split + 1..].to_vec()).ok()?;
+    Some((predicate, object))
+}
+
+/// Breadth-first walk from `root` up to `depth` hops
+///
+/// `edges` returns the outgoing (predicate, object) pairs of a node.
+fn walk_neighborhood<E>(
+    root: &str,
+    depth: u32,
+    mut edges: impl FnMut(&str) -> Result<Vec<(String, String)>, E>,
+) -> Result<Neighborhood, E> {
+    let mut nodes = BTreeMap::from([(root.to_string(), 0)]);
+    let mut traversed = Vec::new();
+    let mut queue = VecDeque::from([(root.to_string(), 0)]);
+
+    while let Some((node, distance)) = queue.pop_front() {
+        if distance == depth {
+            continue;
+        }
+        for (predicate, object) in edges(&node)? {
+            if !nodes.contains_key(&object) {
+                nodes.insert(object.clone(), distance + 1);
+                queue.push_back((object.clone(), distance + 1));
+            }
+            traversed.push((node.clone(), predicate, object));
+        }
+    }
+
+    Ok(Neighborhood {
+        root: root.to_string(),
+        nodes,
+        edges: traversed,
+    })
+}
+
+/// Facts asserted by an agent, optionally only those about `subject`
+fn get_knowledge_facts(agent_id: &str, subject: Option<&str>) -> ExternResult<Vec<KnowledgeFact>> {
+    let agent_hash = match get_agent_hash(agent_id)? {
+        Some(h) => h,
+        None => return Ok(vec![]),
+    };
+
+    let tag = subject.map(|s| LinkTag::new(s.as_bytes()));
+    let links = get_links(agent_hash, LinkTypes::AgentToKnowledge, tag)?;
+
+    let mut facts = Vec::new();
+    for link in links {
+        if let Some(hash) = link.target.into_action_hash() {
+            if let Some(record) = get(hash, GetOptions::default())? {
+                if let Some(fact) = record.entry().to_app_option::<KnowledgeFact>()? {
+                    facts.push(fact);
+                }
+            }
+        }
+    }
+
+    Ok(facts)
+}
+
+fn store_agent_config(agent_hash: ActionHash, config: AgentConfig) -> ExternResult<ActionHash> {
+    let updated_at = config.updated_at;
+    let action_hash = create_entry(EntryTypes::AgentConfig(config))?;
+    create_link(
+        agent_hash,
+        action_hash.clone(),
+        LinkTypes::AgentToConfig,
+        updated_at.to_be_bytes().to_vec(),
+    )?;
+
+    Ok(action_hash)
+}
+
+/// Latest configuration, or the defaults for agents created without one
+fn get_latest_config(agent_id: &str) -> ExternResult<AgentConfig> {
+    let default = AgentConfig {
+        agent_id: agent_id.to_string(),
+        stm_window: DEFAULT_STM_WINDOW,
+        updated_at: 0,
+    };
+    let agent_hash = match get_agent_hash(agent_id)? {
+        Some(h) => h,
+        None => return Ok(default),
+    };
+
+    let links = get_links(agent_hash, LinkTypes::AgentToConfig, None)?;
+
+    // Find latest by timestamp in tag
+    let latest_link = links.into_iter().max_by_key(|link| {
+        link.tag.0.as_slice().try_into()
+            .map(|bytes: [u8; 8]| u64::from_be_bytes(bytes))
+            .unwrap_or(0)
+    });
+
+    if let Some(link) = latest_link {
+        if let Some(hash) = link.target.into_action_hash() {
+            if let Some(record) = get(hash, GetOptions::default())? {
+                if let Some(config) = record.entry().to_app_option::<AgentConfig>()? {
+                    return Ok(config);
+                }
+            }
+        }
+    }
+
+    Ok(default)
+}
+
 fn get_latest_stm(agent_id: &str) -> ExternResult<Option<ShortTermMemory>> {
     let agent_hash = match get_agent_hash(agent_id)? {
         Some(h) => h,
@@ -534,4 +1004,154 @@ mod tests {
         assert_eq!(kg.node_count, 0);
         assert_eq!(kg.edge_count, 0);
     }
+
+    fn interaction(timestamp: u64, reward: f32) -> Interaction {
+        Interaction {
+            timestamp,
+            input_hash: format!("in{}", timestamp),
+            output_hash: format!("out{}", timestamp),
+            reward,
+        }
+    }
+
+    fn empty_stm() -> ShortTermMemory {
+        ShortTermMemory {
+            agent_id: "agent".to_string(),
+            timestamp: 0,
+            interactions: vec![],
+            attention_weights: vec![],
+            working_state: vec![],
+        }
+    }
+
+    #[test]
+    fn test_stm_evicts_lowest_attention() {
+        let mut stm = empty_stm();
+        stm.record(interaction(1, 0.0), 0.9, 3);
+        for t in 2..10 {
+            stm.record(interaction(t, 0.0), 0.1, 3);
+        }
+
+        assert_eq!(stm.interactions.len(), 3);
+        assert_eq!(stm.attention_weights.len(), 3);
+        // The rare important event survives; among the chatter the newest stay
+        let kept: Vec<u64> = stm.interactions.iter().map(|i| i.timestamp).collect();
+        assert_eq!(kept, vec![1, 8, 9]);
+        assert_eq!(stm.attention_weights, vec![0.9, 0.1, 0.1]);
+    }
+
+    #[test]
+    fn test_stm_weights_stay_aligned() {
+        // Legacy memory without weights
+        let mut stm = empty_stm();
+        stm.interactions = vec![interaction(1, -0.5), interaction(2, 0.2)];
+        stm.record(interaction(3, 0.0), 0.3, 2);
+
+        assert_eq!(stm.interactions.len(), stm.attention_weights.len());
+        let kept: Vec<u64> = stm.interactions.iter().map(|i| i.timestamp).collect();
+        assert_eq!(kept, vec![1, 3]);
+        assert_eq!(stm.attention_weights, vec![0.5, 0.3]);
+
+        // More weights than interactions are trimmed
+        stm.attention_weights.push(7.0);
+        stm.align_weights();
+        assert_eq!(stm.attention_weights, vec![0.5, 0.3]);
+    }
+
+    #[test]
+    fn test_stm_summary() {
+        let mut stm = empty_stm();
+        for (t, w) in [(10, 0.4), (20, 0.1), (30, 0.7), (40, 0.2)] {
+            stm.record(interaction(t, 0.0), w, DEFAULT_STM_WINDOW);
+        }
+
+        let summary = stm.summary(DEFAULT_STM_WINDOW);
+        assert_eq!(summary.interaction_count, 4);
+        assert_eq!(summary.window, DEFAULT_STM_WINDOW);
+        assert_eq!(summary.oldest_timestamp, Some(10));
+        assert_eq!(summary.newest_timestamp, Some(40));
+        let stats = summary.weight_stats.unwrap();
+        assert_eq!(stats.min, 0.1);
+        assert_eq!(stats.max, 0.7);
+        assert!((stats.mean - 0.35).abs() < 1e-6);
+        assert!((stats.median - 0.3).abs() < 1e-6);
+
+        assert!(empty_stm().summary(5).weight_stats.is_none());
+    }
+
+    fn fact(subject: &str, predicate: &str, object: &str) -> KnowledgeFact {
+        KnowledgeFact {
+            agent_id: "agent".to_string(),
+            subject: subject.to_string(),
+            predicate: predicate.to_string(),
+            object: object.to_string(),
+            asserted_at: 1702500000000,
+        }
+    }
+
+    #[test]
+    fn test_knowledge_facts_round_trip() {
+        let facts = vec![
+            fact("alice", "knows", "bob"),
+            fact("alice", "likes", "tea"),
+            fact("bob", "knows", "carol"),
+        ];
+
+        for f in &facts {
+            let json = serde_json::to_string(f).unwrap();
+            let parsed: KnowledgeFact = serde_json::from_str(&json).unwrap();
+            assert_eq!(&parsed, f);
+
+            let tag = edge_tag(&f.predicate, &f.object);
+            assert_eq!(parse_edge_tag(&tag), Some((f.predicate.clone(), f.object.clone())));
+        }
+        assert_eq!(parse_edge_tag(b"no separator"), None);
+
+        let pattern = KnowledgePattern {
+            subject: Some("alice".to_string()),
+            predicate: Some("knows".to_string()),
+        };
+        let matched: Vec<_> = facts.iter().filter(|f| pattern.matches(f)).collect();
+        assert_eq!(matched, vec![&facts[0]]);
+        let by_predicate = KnowledgePattern {
+            predicate: Some("knows".to_string()),
+            ..KnowledgePattern::default()
+        };
+        assert_eq!(facts.iter().filter(|f| by_predicate.matches(f)).count(), 2);
+        assert!(facts.iter().all(|f| KnowledgePattern::default().matches(f)));
+
+        let mut graph = KnowledgeGraph::default();
+        graph.fold_facts(&facts);
+        assert_eq!(graph.node_count, 4);
+        assert_eq!(graph.edge_count, 3);
+    }
+
+    #[test]
+    fn test_neighborhood_depth_bound() {
+        // a -> b -> c -> d, plus a cycle back from c to a
+        let chain = [
+            fact("a", "next", "b"),
+            fact("b", "next", "c"),
+            fact("c", "next", "d"),
+            fact("c", "back", "a"),
+        ];
+        let edges = |node: &str| -> Result<Vec<(String, String)>, ()> {
+            Ok(chain
+                .iter()
+                .filter(|f| f.subject == node)
+                .map(|f| (f.predicate.clone(), f.object.clone()))
+                .collect())
+        };
+
+        let hood = walk_neighborhood("a", 2, edges).unwrap();
+        assert_eq!(hood.nodes.get("a"), Some(&0));
+        assert_eq!(hood.nodes.get("b"), Some(&1));
+        assert_eq!(hood.nodes.get("c"), Some(&2));
+        assert!(!hood.nodes.contains_key("d"));
+        assert_eq!(hood.edges.len(), 2);
+
+        let hood = walk_neighborhood("a", 3, edges).unwrap();
+        assert_eq!(hood.nodes.get("d"), Some(&3));
+        assert_eq!(hood.edges.len(), 4);
+    }
 }
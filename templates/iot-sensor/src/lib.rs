@@ -7,6 +7,7 @@
 //! - Lightweight sensor reading storage
 //! - Batch upload support
 //! - Configurable aggregation
+//! - Threshold alerts with cooldown deduplication
 //!
 //! ## Usage
 //! ```bash
@@ -19,11 +20,15 @@
 
 use adk::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Configuration for IoT mode
 /// Set AINGLE_PUBLISH_INTERVAL_MS=0 for sub-second confirmation
 pub const IOT_MODE_ENV: &str = "AINGLE_PUBLISH_INTERVAL_MS";
 
+/// Default window in which repeated violations update an existing alert
+pub const DEFAULT_ALERT_COOLDOWN_SECS: u32 = 300;
+
 // ============================================================================
 // Entry Types
 // ============================================================================
@@ -113,6 +118,74 @@ pub struct SensorConfig {
 
     /// Batch size before upload
     pub batch_size: Option<u32>,
+
+    /// Seconds after a violation during which further violations update the
+    /// same alert (defaults to `DEFAULT_ALERT_COOLDOWN_SECS`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alert_cooldown_secs: Option<u32>,
+}
+
+/// A threshold violation raised by `record_reading`
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct Alert {
+    /// Unique sensor identifier
+    pub sensor_id: String,
+
+    /// Which threshold was crossed
+    pub kind: AlertKind,
+
+    /// The threshold value that was crossed
+    pub threshold: f64,
+
+    /// Value of the most recent violating reading
+    pub value: f64,
+
+    /// Timestamp of the first violating reading (Unix ms)
+    pub first_timestamp: u64,
+
+    /// Timestamp of the most recent violating reading (Unix ms)
+    pub last_timestamp: u64,
+
+    /// Number of violating readings folded into this alert
+    pub count: u32,
+
+    /// Worst severity seen
+    pub severity: AlertSeverity,
+
+    /// Lifecycle state
+    pub status: AlertStatus,
+}
+
+/// Threshold crossed by an alert
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertKind {
+    /// Reading fell below `alert_min`
+    BelowMin,
+    /// Reading rose above `alert_max`
+    AboveMax,
+}
+
+/// How far past the threshold a reading went
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AlertSeverity {
+    /// Less than 5% past the threshold
+    Low,
+    /// 5% to 20% past the threshold
+    Medium,
+    /// 20% to 50% past the threshold
+    High,
+    /// 50% or more past the threshold
+    Critical,
+}
+
+/// Alert lifecycle state
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertStatus {
+    /// Raised and not yet acknowledged
+    Active,
+    /// Acknowledged by an operator
+    Acknowledged,
 }
 
 // ============================================================================
@@ -130,6 +203,9 @@ pub enum EntryTypes {
 
     #[entry_def(visibility = "public")]
     SensorDevice(SensorDevice),
+
+    #[entry_def(visibility = "public")]
+    Alert(Alert),
 }
 
 #[hdk_link_types]
@@ -142,6 +218,9 @@ pub enum LinkTypes {
 
     /// All devices anchor
     AllDevices,
+
+    /// Device -> Alerts
+    DeviceToAlerts,
 }
 
 // ============================================================================
@@ -166,6 +245,10 @@ pub fn register_device(device: SensorDevice) -> ExternResult<ActionHash> {
 }
 
 /// Record a single sensor reading
+///
+/// If the device has alert thresholds and the reading violates one, an
+/// alert is raised, or the device's active alert for the same threshold is
+/// updated when the violation falls within the cooldown.
 #[hdk_extern]
 pub fn record_reading(reading: SensorReading) -> ExternResult<ActionHash> {
     let action_hash = create_entry(EntryTypes::SensorReading(reading.clone()))?;
@@ -173,11 +256,17 @@ pub fn record_reading(reading: SensorReading) -> ExternResult<ActionHash> {
     // Link to device if exists
     if let Some(device_hash) = get_device_hash(&reading.sensor_id)? {
         create_link(
-            device_hash,
+            device_hash.clone(),
             action_hash.clone(),
             LinkTypes::DeviceToReadings,
             reading.timestamp.to_be_bytes().to_vec(),
         )?;
+
+        if let Some(record) = get(device_hash.clone(), GetOptions::default())? {
+            if let Some(device) = record.entry().to_app_option::<SensorDevice>()? {
+                evaluate_alert(device_hash, &device.config, &reading)?;
+            }
+        }
     }
 
     Ok(action_hash)
@@ -278,10 +367,112 @@ pub fn get_latest_reading(sensor_id: String) -> ExternResult<Option<SensorReadin
     Ok(None)
 }
 
+/// Aggregate readings for a device into fixed-width time buckets
+///
+/// Includes both single readings and readings stored in batches. Buckets
+/// without readings are omitted.
+#[hdk_extern]
+pub fn get_aggregated_readings(input: AggregateInput) -> ExternResult<Vec<AggregatedPoint>> {
+    if input.bucket_ms == 0 {
+        return Err(wasm_error!(WasmErrorInner::Guest("bucket_ms must be > 0".into())));
+    }
+
+    let device_hash = get_device_hash(&input.sensor_id)?
+        .ok_or(wasm_error!(WasmErrorInner::Guest("Device not found".into())))?;
+
+    let mut samples = Vec::new();
+
+    let links = get_links(device_hash.clone(), LinkTypes::DeviceToReadings, None)?;
+    for link in links {
+        if let Ok(ts_bytes) = link.tag.0.as_slice().try_into() {
+            let timestamp = u64::from_be_bytes(ts_bytes);
+            if timestamp >= input.start_time && timestamp <= input.end_time {
+                if let Some(hash) = link.target.into_action_hash() {
+                    if let Some(record) = get(hash, GetOptions::default())? {
+                        if let Some(reading) = record.entry().to_app_option::<SensorReading>()? {
+                            samples.push((reading.timestamp, reading.value));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Batch links are tagged with the batch start time
+    let links = get_links(device_hash, LinkTypes::DeviceToBatches, None)?;
+    for link in links {
+        if let Ok(ts_bytes) = link.tag.0.as_slice().try_into() {
+            if u64::from_be_bytes(ts_bytes) <= input.end_time {
+                if let Some(hash) = link.target.into_action_hash() {
+                    if let Some(record) = get(hash, GetOptions::default())? {
+                        if let Some(batch) = record.entry().to_app_option::<SensorBatch>()? {
+                            samples.extend(batch_samples(&batch));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(aggregate(samples, &input))
+}
+
+/// Get unacknowledged alerts for a device
+#[hdk_extern]
+pub fn get_active_alerts(sensor_id: String) -> ExternResult<Vec<AlertRecord>> {
+    let device_hash = get_device_hash(&sensor_id)?
+        .ok_or(wasm_error!(WasmErrorInner::Guest("Device not found".into())))?;
+
+    let mut alerts: Vec<AlertRecord> = get_alert_links(device_hash)?
+        .into_iter()
+        .filter(|(_, record)| record.alert.status == AlertStatus::Active)
+        .map(|(_, record)| record)
+        .collect();
+    alerts.sort_by_key(|r| r.alert.first_timestamp);
+
+    Ok(alerts)
+}
+
+/// Acknowledge an active alert
+///
+/// Returns the hash of the acknowledged version of the alert. Later
+/// violations raise a new alert instead of updating an acknowledged one.
+#[hdk_extern]
+pub fn acknowledge_alert(alert_hash: ActionHash) -> ExternResult<ActionHash> {
+    let record = get(alert_hash.clone(), GetOptions::default())?
+        .ok_or(wasm_error!(WasmErrorInner::Guest("Alert not found".into())))?;
+    let alert = record
+        .entry()
+        .to_app_option::<Alert>()?
+        .ok_or(wasm_error!(WasmErrorInner::Guest("Not an alert".into())))?;
+    let acknowledged = alert
+        .acknowledge()
+        .map_err(|e| wasm_error!(WasmErrorInner::Guest(e.into())))?;
+
+    let device_hash = get_device_hash(&alert.sensor_id)?
+        .ok_or(wasm_error!(WasmErrorInner::Guest("Device not found".into())))?;
+    let link = get_links(device_hash.clone(), LinkTypes::DeviceToAlerts, None)?
+        .into_iter()
+        .find(|link| link.target.clone().into_action_hash().as_ref() == Some(&alert_hash))
+        .ok_or(wasm_error!(WasmErrorInner::Guest("Alert is not linked to its device".into())))?;
+
+    replace_alert(device_hash, link, alert_hash, acknowledged)
+}
+
 // ============================================================================
 // Input Types
 // ============================================================================
 
+/// An alert together with the hash of its current version
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AlertRecord {
+    /// Hash to pass to `acknowledge_alert`
+    pub alert_hash: ActionHash,
+
+    /// The alert
+    pub alert: Alert,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct GetReadingsInput {
     pub sensor_id: String,
@@ -289,6 +480,47 @@ pub struct GetReadingsInput {
     pub end_time: u64,
 }
 
+/// Input for `get_aggregated_readings`
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AggregateInput {
+    /// Unique sensor identifier
+    pub sensor_id: String,
+
+    /// Window start (Unix ms, inclusive); buckets are aligned to it
+    pub start_time: u64,
+
+    /// Window end (Unix ms, inclusive)
+    pub end_time: u64,
+
+    /// Bucket width in ms
+    pub bucket_ms: u64,
+
+    /// Statistic computed per bucket
+    pub stat: AggregateStat,
+}
+
+/// Statistic computed over the readings in a bucket
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AggregateStat {
+    Min,
+    Max,
+    Avg,
+    Count,
+}
+
+/// One bucket of aggregated readings
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AggregatedPoint {
+    /// Start of the bucket (Unix ms)
+    pub bucket_start: u64,
+
+    /// The requested statistic
+    pub value: f64,
+
+    /// Number of readings in the bucket
+    pub sample_count: u64,
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
@@ -304,6 +536,200 @@ fn get_device_hash(sensor_id: &str) -> ExternResult<Option<ActionHash>> {
     Ok(links.first().and_then(|l| l.target.clone().into_action_hash()))
 }
 
+/// Raise or update an alert if `reading` violates the device thresholds
+fn evaluate_alert(
+    device_hash: ActionHash,
+    config: &SensorConfig,
+    reading: &SensorReading,
+) -> ExternResult<()> {
+    let Some((kind, threshold)) = check_thresholds(config, reading.value) else {
+        return Ok(());
+    };
+
+    // Fold into the newest active alert for the same threshold, if in cooldown
+    let current = get_alert_links(device_hash.clone())?
+        .into_iter()
+        .filter(|(_, record)| record.alert.kind == kind)
+        .max_by_key(|(_, record)| record.alert.last_timestamp);
+    if let Some((link, record)) = current {
+        if let Some(updated) = record.alert.merge(reading, config.alert_cooldown_ms()) {
+            replace_alert(device_hash, link, record.alert_hash, updated)?;
+            return Ok(());
+        }
+    }
+
+    let alert = Alert::raise(reading, kind, threshold);
+    let alert_hash = create_entry(EntryTypes::Alert(alert))?;
+    create_link(
+        device_hash,
+        alert_hash,
+        LinkTypes::DeviceToAlerts,
+        reading.timestamp.to_be_bytes().to_vec(),
+    )?;
+
+    Ok(())
+}
+
+/// All alerts linked from a device, with the links pointing at them
+fn get_alert_links(device_hash: ActionHash) -> ExternResult<Vec<(Link, AlertRecord)>> {
+    let links = get_links(device_hash, LinkTypes::DeviceToAlerts, None)?;
+
+    let mut alerts = Vec::new();
+    for link in links {
+        if let Some(hash) = link.target.clone().into_action_hash() {
+            if let Some(record) = get(hash.clone(), GetOptions::default())? {
+                if let Some(alert) = record.entry().to_app_option::<Alert>()? {
+                    alerts.push((link, AlertRecord { alert_hash: hash, alert }));
+                }
+            }
+        }
+    }
+
+    Ok(alerts)
+}
+
+/// Store a new version of an alert and move the device link to it
+fn replace_alert(
+    device_hash: ActionHash,
+    link: Link,
+    alert_hash: ActionHash,
+    alert: Alert,
+) -> ExternResult<ActionHash> {
+    let new_hash = update_entry(alert_hash, EntryTypes::Alert(alert))?;
+    delete_link(link.create_link_hash)?;
+    create_link(device_hash, new_hash.clone(), LinkTypes::DeviceToAlerts, link.tag)?;
+    Ok(new_hash)
+}
+
+/// The threshold `value` violates, if any
+fn check_thresholds(config: &SensorConfig, value: f64) -> Option<(AlertKind, f64)> {
+    match (config.alert_min, config.alert_max) {
+        (Some(min), _) if value < min => Some((AlertKind::BelowMin, min)),
+        (_, Some(max)) if value > max => Some((AlertKind::AboveMax, max)),
+        _ => None,
+    }
+}
+
+impl SensorConfig {
+    /// Alert cooldown in ms
+    fn alert_cooldown_ms(&self) -> u64 {
+        u64::from(self.alert_cooldown_secs.unwrap_or(DEFAULT_ALERT_COOLDOWN_SECS)) * 1000
+    }
+}
+
+impl AlertSeverity {
+    /// Severity of `value` crossing `threshold`, by distance past the
+    /// threshold relative to its magnitude (at least 1.0)
+    fn of(threshold: f64, value: f64) -> Self {
+        let excess = (value - threshold).abs() / threshold.abs().max(1.0);
+        if excess < 0.05 {
+            AlertSeverity::Low
+        } else if excess < 0.2 {
+            AlertSeverity::Medium
+        } else if excess < 0.5 {
+            AlertSeverity::High
+        } else {
+            AlertSeverity::Critical
+        }
+    }
+}
+
+impl Alert {
+    /// A new active alert for a violating reading
+    fn raise(reading: &SensorReading, kind: AlertKind, threshold: f64) -> Self {
+        Alert {
+            sensor_id: reading.sensor_id.clone(),
+            kind,
+            threshold,
+            value: reading.value,
+            first_timestamp: reading.timestamp,
+            last_timestamp: reading.timestamp,
+            count: 1,
+            severity: AlertSeverity::of(threshold, reading.value),
+            status: AlertStatus::Active,
+        }
+    }
+
+    /// Fold a violating reading into this alert
+    ///
+    /// Returns `None` when the alert is acknowledged or the reading is more
+    /// than `cooldown_ms` after the alert's last violation.
+    fn merge(&self, reading: &SensorReading, cooldown_ms: u64) -> Option<Self> {
+        if self.status != AlertStatus::Active
+            || reading.timestamp.saturating_sub(self.last_timestamp) > cooldown_ms
+        {
+            return None;
+        }
+        Some(Alert {
+            value: reading.value,
+            last_timestamp: self.last_timestamp.max(reading.timestamp),
+            count: self.count.saturating_add(1),
+            severity: self
+                .severity
+                .max(AlertSeverity::of(self.threshold, reading.value)),
+            ..self.clone()
+        })
+    }
+
+    /// Move an active alert to acknowledged
+    fn acknowledge(&self) -> Result<Self, &'static str> {
+        match self.status {
+            AlertStatus::Active => Ok(Alert {
+                status: AlertStatus::Acknowledged,
+                ..self.clone()
+            }),
+            AlertStatus::Acknowledged => Err("Alert already acknowledged"),
+        }
+    }
+}
+
+/// Expand a batch into `(timestamp, value)` samples
+fn batch_samples(batch: &SensorBatch) -> impl Iterator<Item = (u64, f64)> + '_ {
+    batch
+        .readings
+        .iter()
+        .map(|r| (batch.start_time + u64::from(r.offset_ms), r.value))
+}
+
+/// Bucket samples falling inside the input window and compute the statistic
+/// per non-empty bucket, in bucket order
+fn aggregate(
+    samples: impl IntoIterator<Item = (u64, f64)>,
+    input: &AggregateInput,
+) -> Vec<AggregatedPoint> {
+    // bucket start -> (min, max, sum, count)
+    let mut buckets: BTreeMap<u64, (f64, f64, f64, u64)> = BTreeMap::new();
+
+    for (timestamp, value) in samples {
+        if timestamp < input.start_time || timestamp > input.end_time {
+            continue;
+        }
+        let offset = timestamp - input.start_time;
+        let bucket_start = input.start_time + offset - offset % input.bucket_ms;
+        let acc = buckets
+            .entry(bucket_start)
+            .or_insert((f64::INFINITY, f64::NEG_INFINITY, 0.0, 0));
+        acc.0 = acc.0.min(value);
+        acc.1 = acc.1.max(value);
+        acc.2 += value;
+        acc.3 += 1;
+    }
+
+    buckets
+        .into_iter()
+        .map(|(bucket_start, (min, max, sum, count))| AggregatedPoint {
+            bucket_start,
+            value: match input.stat {
+                AggregateStat::Min => min,
+                AggregateStat::Max => max,
+                AggregateStat::Avg => sum / count as f64,
+                AggregateStat::Count => count as f64,
+            },
+            sample_count: count,
+        })
+        .collect()
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -344,4 +770,163 @@ mod tests {
         let batch_json = serde_json::to_string(&batch).unwrap();
         assert!(batch_json.len() < 200);
     }
+
+    fn aggregate_input(stat: AggregateStat) -> AggregateInput {
+        AggregateInput {
+            sensor_id: "temp_001".to_string(),
+            start_time: 1_000,
+            end_time: 4_999,
+            bucket_ms: 1_000,
+            stat,
+        }
+    }
+
+    fn synthetic_samples() -> Vec<(u64, f64)> {
+        vec![
+            (500, 99.0),   // before the window
+            (1_000, 20.0), // first bucket, on its boundary
+            (1_999, 22.0), // first bucket, last ms
+            (2_000, 30.0), // second bucket, on its boundary
+            (4_000, 10.0), // fourth bucket; third is empty
+            (4_500, 14.0),
+            (4_999, 12.0), // last ms of the window
+            (5_000, 99.0), // after the window
+        ]
+    }
+
+    #[test]
+    fn test_aggregate_avg_omits_empty_buckets() {
+        let points = aggregate(synthetic_samples(), &aggregate_input(AggregateStat::Avg));
+
+        assert_eq!(
+            points,
+            vec![
+                AggregatedPoint { bucket_start: 1_000, value: 21.0, sample_count: 2 },
+                AggregatedPoint { bucket_start: 2_000, value: 30.0, sample_count: 1 },
+                AggregatedPoint { bucket_start: 4_000, value: 12.0, sample_count: 3 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_aggregate_min_max_count() {
+        let values = |stat| -> Vec<f64> {
+            aggregate(synthetic_samples(), &aggregate_input(stat))
+                .into_iter()
+                .map(|p| p.value)
+                .collect()
+        };
+
+        assert_eq!(values(AggregateStat::Min), vec![20.0, 30.0, 10.0]);
+        assert_eq!(values(AggregateStat::Max), vec![22.0, 30.0, 14.0]);
+        assert_eq!(values(AggregateStat::Count), vec![2.0, 1.0, 3.0]);
+    }
+
+    #[test]
+    fn test_aggregate_includes_batch_readings() {
+        let batch = SensorBatch {
+            sensor_id: "temp_001".to_string(),
+            start_time: 1_500,
+            end_time: 2_500,
+            readings: vec![
+                BatchReading { offset_ms: 0, value: 1.0 },
+                BatchReading { offset_ms: 499, value: 3.0 },
+                BatchReading { offset_ms: 500, value: 5.0 },
+            ],
+        };
+
+        let mut samples = vec![(1_200, 2.0)];
+        samples.extend(batch_samples(&batch));
+        let points = aggregate(samples, &aggregate_input(AggregateStat::Avg));
+
+        assert_eq!(
+            points,
+            vec![
+                AggregatedPoint { bucket_start: 1_000, value: 2.0, sample_count: 3 },
+                AggregatedPoint { bucket_start: 2_000, value: 5.0, sample_count: 1 },
+            ]
+        );
+    }
+
+    fn alert_config(cooldown_secs: Option<u32>) -> SensorConfig {
+        SensorConfig {
+            interval_secs: 10,
+            alert_min: Some(10.0),
+            alert_max: Some(30.0),
+            batch_mode: false,
+            batch_size: None,
+            alert_cooldown_secs: cooldown_secs,
+        }
+    }
+
+    fn reading_at(timestamp: u64, value: f64) -> SensorReading {
+        SensorReading {
+            sensor_id: "temp_001".to_string(),
+            timestamp,
+            value,
+            unit: "celsius".to_string(),
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_threshold_crossing() {
+        let config = alert_config(None);
+
+        assert_eq!(check_thresholds(&config, 10.0), None);
+        assert_eq!(check_thresholds(&config, 30.0), None);
+        assert_eq!(check_thresholds(&config, 9.5), Some((AlertKind::BelowMin, 10.0)));
+        assert_eq!(check_thresholds(&config, 31.0), Some((AlertKind::AboveMax, 30.0)));
+
+        let no_thresholds = SensorConfig {
+            alert_min: None,
+            alert_max: None,
+            ..alert_config(None)
+        };
+        assert_eq!(check_thresholds(&no_thresholds, -1000.0), None);
+
+        assert_eq!(AlertSeverity::of(30.0, 31.0), AlertSeverity::Low);
+        assert_eq!(AlertSeverity::of(30.0, 33.0), AlertSeverity::Medium);
+        assert_eq!(AlertSeverity::of(30.0, 40.0), AlertSeverity::High);
+        assert_eq!(AlertSeverity::of(30.0, 50.0), AlertSeverity::Critical);
+        assert_eq!(AlertSeverity::of(10.0, 4.0), AlertSeverity::Critical);
+    }
+
+    #[test]
+    fn test_alert_cooldown_dedup() {
+        let config = alert_config(Some(60));
+        let cooldown = config.alert_cooldown_ms();
+        assert_eq!(cooldown, 60_000);
+        assert_eq!(alert_config(None).alert_cooldown_ms(), 300_000);
+
+        let alert = Alert::raise(&reading_at(1_000, 31.0), AlertKind::AboveMax, 30.0);
+        assert_eq!(alert.count, 1);
+        assert_eq!(alert.severity, AlertSeverity::Low);
+
+        // Within the cooldown: same alert, bumped count and worst severity
+        let merged = alert.merge(&reading_at(50_000, 42.0), cooldown).unwrap();
+        let merged = merged.merge(&reading_at(100_000, 31.5), cooldown).unwrap();
+        assert_eq!(merged.count, 3);
+        assert_eq!(merged.value, 31.5);
+        assert_eq!(merged.first_timestamp, 1_000);
+        assert_eq!(merged.last_timestamp, 100_000);
+        assert_eq!(merged.severity, AlertSeverity::High);
+
+        // Past the cooldown since the last violation: a new alert is due
+        assert_eq!(merged.merge(&reading_at(160_001, 32.0), cooldown), None);
+    }
+
+    #[test]
+    fn test_alert_acknowledgment() {
+        let alert = Alert::raise(&reading_at(1_000, 5.0), AlertKind::BelowMin, 10.0);
+        assert_eq!(alert.status, AlertStatus::Active);
+
+        let acknowledged = alert.acknowledge().unwrap();
+        assert_eq!(acknowledged.status, AlertStatus::Acknowledged);
+        assert_eq!(acknowledged.count, alert.count);
+        assert!(acknowledged.acknowledge().is_err());
+
+        // Acknowledged alerts no longer absorb violations
+        assert_eq!(acknowledged.merge(&reading_at(2_000, 4.0), 300_000), None);
+    }
 }
@@ -8,6 +8,8 @@
 //! - Cold chain monitoring
 //! - Authenticity verification
 //! - Regulatory compliance
+//! - Lot-based recalls
+//! - Inspection document verification
 //!
 //! ## Usage
 //! ```bash
@@ -43,6 +45,10 @@ pub struct Product {
 
     /// Creation timestamp
     pub created_at: u64,
+
+    /// Manufacturing lot/batch number, used to target recalls
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lot_number: Option<String>,
 }
 
 /// Manufacturer information
@@ -180,8 +186,94 @@ pub struct InspectionRecord {
     /// Detailed findings
     pub findings: Vec<InspectionFinding>,
 
-    /// Attachments (hashes of images/documents)
-    pub attachments: Vec<String>,
+    /// Images/documents recorded with the inspection, by content hash
+    ///
+    /// Attachments added later are separate [`InspectionAttachment`] entries.
+    pub attachments: Vec<AttachmentRef>,
+}
+
+/// A document or image anchored by its content hash
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AttachmentRef {
+    /// Lowercase hex digest of the file contents
+    pub content_hash: String,
+
+    /// Algorithm that produced `content_hash`
+    pub hash_algo: HashAlgo,
+
+    /// MIME type, e.g. `application/pdf`
+    pub mime_type: String,
+
+    /// File size in bytes
+    pub size_bytes: u64,
+
+    /// What the file shows
+    pub description: Option<String>,
+}
+
+/// Hash algorithms accepted for attachments
+///
+/// Any other algorithm fails to deserialize, so it is rejected when the
+/// inspection or attachment is created.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgo {
+    Sha256,
+    Blake3,
+}
+
+impl HashAlgo {
+    /// Digest length in bytes
+    pub fn digest_len(self) -> usize {
+        match self {
+            HashAlgo::Sha256 | HashAlgo::Blake3 => 32,
+        }
+    }
+}
+
+impl AttachmentRef {
+    /// Check that `content_hash` is a well-formed digest for `hash_algo`
+    pub fn validate(&self) -> Result<(), String> {
+        let expected = self.hash_algo.digest_len() * 2;
+        if self.content_hash.len() != expected
+            || !self
+                .content_hash
+                .bytes()
+                .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+        {
+            return Err(format!(
+                "content_hash must be {} lowercase hex characters for {:?}",
+                expected, self.hash_algo
+            ));
+        }
+        if self.mime_type.is_empty() {
+            return Err("mime_type is required".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// An attachment appended to an inspection after it was recorded
+///
+/// Inspections are never updated; later documents are kept as separate
+/// entries linked from the inspection.
+#[hdk_entry_helper]
+#[derive(Clone)]
+pub struct InspectionAttachment {
+    /// Product the inspection is for
+    pub product_id: String,
+
+    /// Inspection the attachment belongs to
+    pub inspection_hash: ActionHash,
+
+    /// The attached document
+    pub attachment: AttachmentRef,
+
+    /// Agent who added the attachment
+    pub added_by: AgentPubKey,
+
+    /// When the attachment was added
+    pub added_at: u64,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -207,6 +299,30 @@ pub enum FindingSeverity {
     Critical,
 }
 
+/// A recall of every product in a manufacturing lot
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct Recall {
+    /// Lot being recalled
+    pub lot_number: String,
+
+    /// Reason for the recall
+    pub reason: String,
+
+    /// Who issued the recall
+    pub issued_by: String,
+
+    /// Issue timestamp
+    pub timestamp: u64,
+}
+
+impl Recall {
+    /// Whether this recall covers `product`
+    pub fn applies_to(&self, product: &Product) -> bool {
+        product.lot_number.as_deref() == Some(self.lot_number.as_str())
+    }
+}
+
 // ============================================================================
 // Entry Definitions
 // ============================================================================
@@ -225,6 +341,12 @@ pub enum EntryTypes {
 
     #[entry_def(visibility = "public")]
     InspectionRecord(InspectionRecord),
+
+    #[entry_def(visibility = "public")]
+    Recall(Recall),
+
+    #[entry_def(visibility = "public")]
+    InspectionAttachment(InspectionAttachment),
 }
 
 #[hdk_link_types]
@@ -243,6 +365,18 @@ pub enum LinkTypes {
 
     /// All locations anchor
     AllLocations,
+
+    /// Lot anchor -> Products in the lot
+    LotToProducts,
+
+    /// Lot anchor -> Recalls of the lot
+    LotToRecalls,
+
+    /// Product -> Recalls covering it
+    ProductToRecalls,
+
+    /// Inspection -> Attachments added after it was recorded
+    InspectionToAttachments,
 }
 
 // ============================================================================
@@ -263,9 +397,96 @@ pub fn register_product(product: Product) -> ExternResult<ActionHash> {
         product.product_id.as_bytes().to_vec(),
     )?;
 
+    // Index by lot, and pick up recalls issued before registration
+    if let Some(lot_number) = &product.lot_number {
+        let lot = lot_anchor_hash(lot_number)?;
+        create_link(
+            lot.clone(),
+            action_hash.clone(),
+            LinkTypes::LotToProducts,
+            product.product_id.as_bytes().to_vec(),
+        )?;
+
+        for link in get_links(lot, LinkTypes::LotToRecalls, None)? {
+            if let Some(recall_hash) = link.target.into_action_hash() {
+                create_link(
+                    action_hash.clone(),
+                    recall_hash,
+                    LinkTypes::ProductToRecalls,
+                    link.tag.0,
+                )?;
+            }
+        }
+    }
+
     Ok(action_hash)
 }
 
+/// Recall a lot, flagging every product registered in it
+///
+/// Products registered in the lot later are flagged at registration.
+#[hdk_extern]
+pub fn issue_recall(recall: Recall) -> ExternResult<ActionHash> {
+    let recall_hash = create_entry(EntryTypes::Recall(recall.clone()))?;
+    let tag = recall.timestamp.to_be_bytes().to_vec();
+
+    let lot = lot_anchor_hash(&recall.lot_number)?;
+    create_link(
+        lot.clone(),
+        recall_hash.clone(),
+        LinkTypes::LotToRecalls,
+        tag.clone(),
+    )?;
+
+    for link in get_links(lot, LinkTypes::LotToProducts, None)? {
+        if let Some(product_hash) = link.target.into_action_hash() {
+            create_link(
+                product_hash,
+                recall_hash.clone(),
+                LinkTypes::ProductToRecalls,
+                tag.clone(),
+            )?;
+        }
+    }
+
+    Ok(recall_hash)
+}
+
+/// Get the recalls covering a product and where it was last seen
+#[hdk_extern]
+pub fn get_recall_status(product_id: String) -> ExternResult<RecallStatus> {
+    let product_hash = get_product_hash(&product_id)?
+        .ok_or(wasm_error!(WasmErrorInner::Guest("Product not found".into())))?;
+
+    let mut recalls = Vec::new();
+    for link in get_links(product_hash.clone(), LinkTypes::ProductToRecalls, None)? {
+        if let Some(hash) = link.target.into_action_hash() {
+            if let Some(record) = get(hash, GetOptions::default())? {
+                if let Some(recall) = record.entry().to_app_option::<Recall>()? {
+                    recalls.push(recall);
+                }
+            }
+        }
+    }
+
+    // Latest custody event, by the timestamp in the link tag
+    let latest_link = get_links(product_hash, LinkTypes::ProductToCustody, None)?
+        .into_iter()
+        .max_by_key(|link| {
+            link.tag.0.as_slice().try_into()
+                .map(|bytes: [u8; 8]| u64::from_be_bytes(bytes))
+                .unwrap_or(0)
+        });
+    let mut last_event = None;
+    if let Some(hash) = latest_link.and_then(|link| link.target.into_action_hash()) {
+        if let Some(record) = get(hash, GetOptions::default())? {
+            last_event = record.entry().to_app_option::<CustodyEvent>()?;
+        }
+    }
+
+    Ok(recall_status(product_id, recalls, last_event.as_ref()))
+}
+
 /// Register a new location
 #[hdk_extern]
 pub fn register_location(location: Location) -> ExternResult<ActionHash> {
@@ -328,6 +549,12 @@ pub fn record_custody_event(event: CustodyEvent) -> ExternResult<ActionHash> {
 /// Record an inspection
 #[hdk_extern]
 pub fn record_inspection(record: InspectionRecord) -> ExternResult<ActionHash> {
+    for attachment in &record.attachments {
+        attachment
+            .validate()
+            .map_err(|e| wasm_error!(WasmErrorInner::Guest(e)))?;
+    }
+
     let action_hash = create_entry(EntryTypes::InspectionRecord(record.clone()))?;
 
     // Link to product
@@ -343,6 +570,54 @@ pub fn record_inspection(record: InspectionRecord) -> ExternResult<ActionHash> {
     Ok(action_hash)
 }
 
+/// Append an attachment to a recorded inspection
+///
+/// The inspection itself is left untouched; the attachment is stored as an
+/// [`InspectionAttachment`] authored by the calling agent. A content hash
+/// already recorded on the inspection is rejected.
+#[hdk_extern]
+pub fn add_attachment_to_inspection(input: AddAttachmentInput) -> ExternResult<ActionHash> {
+    input
+        .attachment
+        .validate()
+        .map_err(|e| wasm_error!(WasmErrorInner::Guest(e)))?;
+
+    let recorded = get_inspection_attachments(&input.product_id, &input.inspection_hash)?;
+    if find_attachment(&recorded, &input.attachment.content_hash).is_some() {
+        return Err(wasm_error!(WasmErrorInner::Guest(
+            "Attachment already recorded on this inspection".into()
+        )));
+    }
+
+    let entry = InspectionAttachment {
+        product_id: input.product_id,
+        inspection_hash: input.inspection_hash.clone(),
+        attachment: input.attachment,
+        added_by: agent_info()?.agent_latest_pubkey,
+        added_at: sys_time()?.as_micros() as u64 / 1000,
+    };
+    let action_hash = create_entry(EntryTypes::InspectionAttachment(entry.clone()))?;
+    create_link(
+        input.inspection_hash,
+        action_hash.clone(),
+        LinkTypes::InspectionToAttachments,
+        entry.attachment.content_hash.as_bytes().to_vec(),
+    )?;
+
+    Ok(action_hash)
+}
+
+/// Confirm a document's hash was recorded on an inspection of a product
+///
+/// Checks the attachments recorded with the inspection and those appended
+/// later, and returns the recorded metadata and who recorded it.
+#[hdk_extern]
+pub fn verify_attachment(input: VerifyAttachmentInput) -> ExternResult<AttachmentVerification> {
+    let recorded = get_inspection_attachments(&input.product_id, &input.inspection_hash)?;
+
+    Ok(attachment_verification(input, &recorded))
+}
+
 /// Get full provenance history for a product
 #[hdk_extern]
 pub fn get_product_history(product_id: String) -> ExternResult<ProductHistory> {
@@ -411,11 +686,331 @@ pub fn get_products_at_location(location_id: String) -> ExternResult<Vec<Product
     Ok(products)
 }
 
+/// Check a product's custody history against a cold-chain policy
+#[hdk_extern]
+pub fn check_cold_chain(input: CheckColdChainInput) -> ExternResult<ColdChainReport> {
+    let history = get_product_history(input.product_id)?;
+
+    Ok(cold_chain_report(&history.custody_events, &input.policy))
+}
+
 /// Verify product authenticity by checking provenance chain
 #[hdk_extern]
 pub fn verify_authenticity(product_id: String) -> ExternResult<AuthenticityResult> {
     let history = get_product_history(product_id)?;
 
+    Ok(authenticity(&history, None))
+}
+
+/// Verify product authenticity, also checking the cold chain
+///
+/// Excursions longer than the policy tolerates are added to `issues` and
+/// lower the confidence score.
+#[hdk_extern]
+pub fn verify_authenticity_with_cold_chain(
+    input: CheckColdChainInput,
+) -> ExternResult<AuthenticityResult> {
+    let history = get_product_history(input.product_id)?;
+
+    Ok(authenticity(&history, Some(&input.policy)))
+}
+
+// ============================================================================
+// Input Types
+// ============================================================================
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CheckColdChainInput {
+    pub product_id: String,
+    pub policy: ColdChainPolicy,
+}
+
+/// Acceptable environmental bounds for a cold chain
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ColdChainPolicy {
+    /// Maximum temperature in Celsius
+    pub max_temp: f64,
+
+    /// Minimum temperature in Celsius
+    pub min_temp: f64,
+
+    /// Maximum humidity percentage
+    pub max_humidity: f64,
+
+    /// Longest tolerated excursion; shorter ones are reported as minor
+    pub max_excursion_minutes: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AddAttachmentInput {
+    pub product_id: String,
+    pub inspection_hash: ActionHash,
+    pub attachment: AttachmentRef,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct VerifyAttachmentInput {
+    pub product_id: String,
+    pub inspection_hash: ActionHash,
+    /// Hex digest of the document being checked
+    pub content_hash: String,
+}
+
+// ============================================================================
+// Response Types
+// ============================================================================
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ProductHistory {
+    pub product: Product,
+    pub custody_events: Vec<CustodyEvent>,
+    pub inspections: Vec<InspectionRecord>,
+}
+
+/// Result of `get_recall_status`
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RecallStatus {
+    /// The product queried
+    pub product_id: String,
+
+    /// Recalls covering the product, oldest first
+    pub recalls: Vec<Recall>,
+
+    /// Destination of the latest custody event
+    pub last_known_location: Option<String>,
+
+    /// Handler of the latest custody event
+    pub last_handler: Option<String>,
+
+    /// Timestamp of the latest custody event
+    pub last_seen: Option<u64>,
+}
+
+impl RecallStatus {
+    /// Whether any recall covers the product
+    pub fn is_recalled(&self) -> bool {
+        !self.recalls.is_empty()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AuthenticityResult {
+    pub is_authentic: bool,
+    pub confidence: f32,
+    pub issues: Vec<String>,
+    pub total_custody_events: usize,
+    pub total_inspections: usize,
+}
+
+/// Result of `verify_attachment`
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AttachmentVerification {
+    /// True when the hash was recorded on the inspection
+    pub verified: bool,
+
+    /// The product queried
+    pub product_id: String,
+
+    /// The inspection queried
+    pub inspection_hash: ActionHash,
+
+    /// The hash queried
+    pub content_hash: String,
+
+    /// Metadata recorded for the attachment
+    pub attachment: Option<AttachmentRef>,
+
+    /// Author of the inspection or of the appended attachment
+    pub recorded_by: Option<AgentPubKey>,
+
+    /// When the attachment was recorded
+    pub recorded_at: Option<u64>,
+
+    /// True when the attachment was added after the inspection
+    pub appended: bool,
+}
+
+/// Result of `check_cold_chain`
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ColdChainReport {
+    /// True when no excursion exceeded the tolerated duration
+    pub compliant: bool,
+
+    /// Number of custody events examined
+    pub events_checked: usize,
+
+    /// Events without temperature or humidity readings
+    pub unmeasured_events: usize,
+
+    /// Excursions in timestamp order
+    pub violations: Vec<ColdChainViolation>,
+}
+
+/// A period with conditions outside the cold-chain policy
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ColdChainViolation {
+    /// Location of the first out-of-bounds event
+    pub location: String,
+
+    /// Handler of the first out-of-bounds event
+    pub handler: String,
+
+    /// Timestamp of the first out-of-bounds event
+    pub start_time: u64,
+
+    /// Timestamp at which conditions were next seen in bounds
+    pub end_time: u64,
+
+    /// Estimated duration in minutes
+    pub duration_minutes: f64,
+
+    /// Number of consecutive out-of-bounds events
+    pub event_count: usize,
+
+    /// True when the last recorded event was still out of bounds
+    pub ongoing: bool,
+
+    /// Minor within `max_excursion_minutes`, Major up to twice that,
+    /// Critical beyond
+    pub severity: FindingSeverity,
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn anchor_hash(anchor: &str) -> ExternResult<EntryHash> {
+    hash_entry(anchor.to_string())
+}
+
+fn lot_anchor_hash(lot_number: &str) -> ExternResult<EntryHash> {
+    anchor_hash(&format!("lot:{}", lot_number))
+}
+
+fn get_product_hash(product_id: &str) -> ExternResult<Option<ActionHash>> {
+    let anchor = anchor_hash("all_products")?;
+    let links = get_links(anchor, LinkTypes::AllProducts, Some(LinkTag::new(product_id.as_bytes())))?;
+
+    Ok(links.first().and_then(|l| l.target.clone().into_action_hash()))
+}
+
+fn get_location_hash(location_id: &str) -> ExternResult<Option<ActionHash>> {
+    let anchor = anchor_hash("all_locations")?;
+    let links = get_links(anchor, LinkTypes::AllLocations, Some(LinkTag::new(location_id.as_bytes())))?;
+
+    Ok(links.first().and_then(|l| l.target.clone().into_action_hash()))
+}
+
+/// An attachment of an inspection together with who recorded it and when
+#[derive(Debug, Clone)]
+struct RecordedAttachment {
+    attachment: AttachmentRef,
+    recorded_by: AgentPubKey,
+    recorded_at: u64,
+    appended: bool,
+}
+
+/// Every attachment of an inspection, original ones first
+fn get_inspection_attachments(
+    product_id: &str,
+    inspection_hash: &ActionHash,
+) -> ExternResult<Vec<RecordedAttachment>> {
+    let record = get(inspection_hash.clone(), GetOptions::default())?
+        .ok_or(wasm_error!(WasmErrorInner::Guest("Inspection not found".into())))?;
+    let inspection = record
+        .entry()
+        .to_app_option::<InspectionRecord>()?
+        .ok_or(wasm_error!(WasmErrorInner::Guest("Inspection not found".into())))?;
+    if inspection.product_id != product_id {
+        return Err(wasm_error!(WasmErrorInner::Guest(
+            "Inspection is not for this product".into()
+        )));
+    }
+
+    let author = record.action().author().clone();
+    let mut recorded: Vec<RecordedAttachment> = inspection
+        .attachments
+        .into_iter()
+        .map(|attachment| RecordedAttachment {
+            attachment,
+            recorded_by: author.clone(),
+            recorded_at: inspection.timestamp,
+            appended: false,
+        })
+        .collect();
+
+    let mut appended = Vec::new();
+    for link in get_links(inspection_hash.clone(), LinkTypes::InspectionToAttachments, None)? {
+        if let Some(hash) = link.target.into_action_hash() {
+            if let Some(record) = get(hash, GetOptions::default())? {
+                if let Some(added) = record.entry().to_app_option::<InspectionAttachment>()? {
+                    appended.push(RecordedAttachment {
+                        attachment: added.attachment,
+                        recorded_by: added.added_by,
+                        recorded_at: added.added_at,
+                        appended: true,
+                    });
+                }
+            }
+        }
+    }
+    appended.sort_by_key(|a| a.recorded_at);
+    recorded.extend(appended);
+
+    Ok(recorded)
+}
+
+/// The first recorded attachment with `content_hash`, ignoring hex case
+fn find_attachment<'a>(
+    recorded: &'a [RecordedAttachment],
+    content_hash: &str,
+) -> Option<&'a RecordedAttachment> {
+    recorded
+        .iter()
+        .find(|r| r.attachment.content_hash.eq_ignore_ascii_case(content_hash))
+}
+
+fn attachment_verification(
+    input: VerifyAttachmentInput,
+    recorded: &[RecordedAttachment],
+) -> AttachmentVerification {
+    let found = find_attachment(recorded, &input.content_hash);
+
+    AttachmentVerification {
+        verified: found.is_some(),
+        product_id: input.product_id,
+        inspection_hash: input.inspection_hash,
+        content_hash: input.content_hash,
+        attachment: found.map(|r| r.attachment.clone()),
+        recorded_by: found.map(|r| r.recorded_by.clone()),
+        recorded_at: found.map(|r| r.recorded_at),
+        appended: found.is_some_and(|r| r.appended),
+    }
+}
+
+fn recall_status(
+    product_id: String,
+    mut recalls: Vec<Recall>,
+    last_event: Option<&CustodyEvent>,
+) -> RecallStatus {
+    recalls.sort_by_key(|r| r.timestamp);
+    recalls.dedup();
+
+    RecallStatus {
+        product_id,
+        recalls,
+        last_known_location: last_event.map(|e| e.to_location.clone()),
+        last_handler: last_event.map(|e| e.handler.clone()),
+        last_seen: last_event.map(|e| e.timestamp),
+    }
+}
+
+/// Authenticity checks over a product history, optionally including the
+/// cold chain
+fn authenticity(
+    history: &ProductHistory,
+    cold_chain: Option<&ColdChainPolicy>,
+) -> AuthenticityResult {
     let mut issues = Vec::new();
 
     // Check 1: Product exists
@@ -454,57 +1049,122 @@ pub fn verify_authenticity(product_id: String) -> ExternResult<AuthenticityResul
         }
     }
 
+    // Check 5: Cold chain held within the tolerated excursions
+    let mut penalty: f32 = 0.0;
+    if let Some(policy) = cold_chain {
+        let report = cold_chain_report(&history.custody_events, policy);
+        for violation in &report.violations {
+            let weight = match violation.severity {
+                FindingSeverity::Info | FindingSeverity::Minor => continue,
+                FindingSeverity::Major => 0.2,
+                FindingSeverity::Critical => 0.4,
+            };
+            penalty += weight;
+            issues.push(format!(
+                "Cold-chain excursion of {:.0} min at {} (handler {})",
+                violation.duration_minutes, violation.location, violation.handler
+            ));
+        }
+    }
+
     let is_authentic = issues.is_empty();
+    let confidence: f32 = if is_authentic { 1.0 } else { 0.5 };
 
-    Ok(AuthenticityResult {
+    AuthenticityResult {
         is_authentic,
-        confidence: if is_authentic { 1.0 } else { 0.5 },
+        confidence: confidence * (1.0 - penalty).max(0.0),
         issues,
         total_custody_events: history.custody_events.len(),
         total_inspections: history.inspections.len(),
-    })
-}
-
-// ============================================================================
-// Response Types
-// ============================================================================
-
-#[derive(Serialize, Deserialize, Debug)]
-pub struct ProductHistory {
-    pub product: Product,
-    pub custody_events: Vec<CustodyEvent>,
-    pub inspections: Vec<InspectionRecord>,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-pub struct AuthenticityResult {
-    pub is_authentic: bool,
-    pub confidence: f32,
-    pub issues: Vec<String>,
-    pub total_custody_events: usize,
-    pub total_inspections: usize,
+    }
 }
 
-// ============================================================================
-// Helper Functions
-// ============================================================================
-
-fn anchor_hash(anchor: &str) -> ExternResult<EntryHash> {
-    hash_entry(anchor.to_string())
+/// Whether an event's recorded conditions are outside the policy bounds
+///
+/// `None` when the event carries no temperature or humidity reading.
+fn out_of_bounds(event: &CustodyEvent, policy: &ColdChainPolicy) -> Option<bool> {
+    let conditions = event.conditions.as_ref()?;
+    let temperature = conditions
+        .temperature
+        .map(|t| t < policy.min_temp || t > policy.max_temp);
+    let humidity = conditions.humidity.map(|h| h > policy.max_humidity);
+    match (temperature, humidity) {
+        (None, None) => None,
+        (t, h) => Some(t.unwrap_or(false) || h.unwrap_or(false)),
+    }
 }
 
-fn get_product_hash(product_id: &str) -> ExternResult<Option<ActionHash>> {
-    let anchor = anchor_hash("all_products")?;
-    let links = get_links(anchor, LinkTypes::AllProducts, Some(LinkTag::new(product_id.as_bytes())))?;
+/// Find excursions in custody events
+///
+/// An excursion starts at an event whose conditions are out of bounds and
+/// ends at the next event whose conditions are back in bounds; its duration
+/// is estimated from those two timestamps. Events without readings neither
+/// start nor end an excursion. An excursion still open at the last event is
+/// measured up to that event and marked ongoing.
+fn cold_chain_report(events: &[CustodyEvent], policy: &ColdChainPolicy) -> ColdChainReport {
+    let mut events: Vec<&CustodyEvent> = events.iter().collect();
+    events.sort_by_key(|e| e.timestamp);
+
+    let mut violations = Vec::new();
+    let mut unmeasured_events = 0;
+    // (first out-of-bounds event, number of out-of-bounds events)
+    let mut open: Option<(&CustodyEvent, usize)> = None;
+
+    for event in &events {
+        match out_of_bounds(event, policy) {
+            None => unmeasured_events += 1,
+            Some(true) => match &mut open {
+                Some((_, count)) => *count += 1,
+                None => open = Some((event, 1)),
+            },
+            Some(false) => {
+                if let Some((start, count)) = open.take() {
+                    violations.push(excursion(start, event.timestamp, count, false, policy));
+                }
+            }
+        }
+    }
+    if let (Some((start, count)), Some(last)) = (open, events.last()) {
+        violations.push(excursion(start, last.timestamp, count, true, policy));
+    }
 
-    Ok(links.first().and_then(|l| l.target.clone().into_action_hash()))
+    ColdChainReport {
+        compliant: violations
+            .iter()
+            .all(|v| matches!(v.severity, FindingSeverity::Info | FindingSeverity::Minor)),
+        events_checked: events.len(),
+        unmeasured_events,
+        violations,
+    }
 }
 
-fn get_location_hash(location_id: &str) -> ExternResult<Option<ActionHash>> {
-    let anchor = anchor_hash("all_locations")?;
-    let links = get_links(anchor, LinkTypes::AllLocations, Some(LinkTag::new(location_id.as_bytes())))?;
-
-    Ok(links.first().and_then(|l| l.target.clone().into_action_hash()))
+fn excursion(
+    start: &CustodyEvent,
+    end_time: u64,
+    event_count: usize,
+    ongoing: bool,
+    policy: &ColdChainPolicy,
+) -> ColdChainViolation {
+    let duration_minutes = end_time.saturating_sub(start.timestamp) as f64 / 60_000.0;
+    let allowed = policy.max_excursion_minutes as f64;
+    let severity = if duration_minutes <= allowed {
+        FindingSeverity::Minor
+    } else if duration_minutes <= allowed * 2.0 {
+        FindingSeverity::Major
+    } else {
+        FindingSeverity::Critical
+    };
+
+    ColdChainViolation {
+        location: start.to_location.clone(),
+        handler: start.handler.clone(),
+        start_time: start.timestamp,
+        end_time,
+        duration_minutes,
+        event_count,
+        ongoing,
+        severity,
+    }
 }
 
 // ============================================================================
@@ -532,6 +1192,7 @@ mod tests {
                 "roast": "Medium"
             }),
             created_at: 1702500000000,
+            lot_number: None,
         };
 
         let json = serde_json::to_string(&product).unwrap();
@@ -552,4 +1213,284 @@ mod tests {
         let json = serde_json::to_string(&conditions).unwrap();
         assert!(json.contains("4.0"));
     }
+
+    const MINUTE: u64 = 60_000;
+
+    fn event_at(minute: u64, location: &str, temperature: Option<f64>) -> CustodyEvent {
+        CustodyEvent {
+            product_id: "VAX-001".to_string(),
+            timestamp: 1702500000000 + minute * MINUTE,
+            event_type: CustodyEventType::Stored,
+            from_location: Some(location.to_string()),
+            to_location: location.to_string(),
+            handler: format!("handler@{}", location),
+            conditions: temperature.map(|t| EnvironmentConditions {
+                temperature: Some(t),
+                humidity: Some(40.0),
+                light: None,
+                shock: None,
+                sensor_id: None,
+            }),
+            signature: None,
+            metadata: None,
+        }
+    }
+
+    fn policy() -> ColdChainPolicy {
+        ColdChainPolicy {
+            max_temp: 8.0,
+            min_temp: 2.0,
+            max_humidity: 60.0,
+            max_excursion_minutes: 30,
+        }
+    }
+
+    /// A brief excursion at the port and a prolonged one at the warehouse,
+    /// given out of order
+    fn excursion_events() -> Vec<CustodyEvent> {
+        let mut created = event_at(0, "factory", Some(4.0));
+        created.event_type = CustodyEventType::Created;
+        created.from_location = None;
+        vec![
+            event_at(300, "warehouse", Some(9.0)),
+            created,
+            event_at(60, "port", Some(10.0)),
+            event_at(70, "port", Some(5.0)),
+            event_at(200, "warehouse", Some(12.0)),
+            event_at(230, "warehouse", None),
+            event_at(320, "warehouse", Some(6.0)),
+        ]
+    }
+
+    #[test]
+    fn test_cold_chain_excursions() {
+        let report = cold_chain_report(&excursion_events(), &policy());
+
+        assert!(!report.compliant);
+        assert_eq!(report.events_checked, 7);
+        assert_eq!(report.unmeasured_events, 1);
+        assert_eq!(report.violations.len(), 2);
+
+        let brief = &report.violations[0];
+        assert_eq!(brief.location, "port");
+        assert_eq!(brief.handler, "handler@port");
+        assert_eq!(brief.duration_minutes, 10.0);
+        assert_eq!(brief.event_count, 1);
+        assert!(matches!(brief.severity, FindingSeverity::Minor));
+
+        let prolonged = &report.violations[1];
+        assert_eq!(prolonged.location, "warehouse");
+        assert_eq!(prolonged.duration_minutes, 120.0);
+        assert_eq!(prolonged.event_count, 2);
+        assert!(!prolonged.ongoing);
+        assert!(matches!(prolonged.severity, FindingSeverity::Critical));
+    }
+
+    #[test]
+    fn test_cold_chain_ongoing_excursion() {
+        let mut events = excursion_events();
+        events.retain(|e| e.timestamp < 1702500000000 + 320 * MINUTE);
+
+        let report = cold_chain_report(&events, &policy());
+        let last = report.violations.last().unwrap();
+        assert!(last.ongoing);
+        assert_eq!(last.duration_minutes, 100.0);
+        assert!(matches!(last.severity, FindingSeverity::Critical));
+    }
+
+    #[test]
+    fn test_authenticity_folds_cold_chain() {
+        let mut custody_events = excursion_events();
+        custody_events.sort_by_key(|e| e.timestamp);
+        let history = ProductHistory {
+            product: Product {
+                product_id: "VAX-001".to_string(),
+                name: "Vaccine".to_string(),
+                category: "Pharma".to_string(),
+                manufacturer: Manufacturer {
+                    name: "Pharma Co".to_string(),
+                    location: "factory".to_string(),
+                    certifications: vec![],
+                },
+                attributes: serde_json::json!({}),
+                created_at: 1702500000000,
+                lot_number: None,
+            },
+            custody_events,
+            inspections: vec![],
+        };
+
+        let plain = authenticity(&history, None);
+        assert!(plain.issues.iter().all(|i| !i.contains("Cold-chain")));
+
+        let checked = authenticity(&history, Some(&policy()));
+        let cold_chain_issues: Vec<_> = checked
+            .issues
+            .iter()
+            .filter(|i| i.contains("Cold-chain"))
+            .collect();
+        assert_eq!(cold_chain_issues.len(), 1);
+        assert!(cold_chain_issues[0].contains("warehouse"));
+        assert!(!checked.is_authentic);
+        assert!(checked.confidence < plain.confidence.min(0.5));
+    }
+
+    fn lot_product(product_id: &str, lot_number: Option<&str>, created_at: u64) -> Product {
+        Product {
+            product_id: product_id.to_string(),
+            name: "Infant Formula".to_string(),
+            category: "Food".to_string(),
+            manufacturer: Manufacturer {
+                name: "Dairy Co".to_string(),
+                location: "factory".to_string(),
+                certifications: vec![],
+            },
+            attributes: serde_json::json!({}),
+            created_at,
+            lot_number: lot_number.map(str::to_string),
+        }
+    }
+
+    fn recall() -> Recall {
+        Recall {
+            lot_number: "L-2024-03".to_string(),
+            reason: "Contamination".to_string(),
+            issued_by: "Dairy Co QA".to_string(),
+            timestamp: 1702600000000,
+        }
+    }
+
+    #[test]
+    fn test_recall_covers_lot_before_and_after_registration() {
+        let recall = recall();
+
+        let registered_before = lot_product("P-1", Some("L-2024-03"), 1702500000000);
+        let registered_after = lot_product("P-2", Some("L-2024-03"), 1702700000000);
+        let other_lot = lot_product("P-3", Some("L-2024-04"), 1702500000000);
+        let no_lot = lot_product("P-4", None, 1702500000000);
+
+        assert!(recall.applies_to(&registered_before));
+        assert!(recall.applies_to(&registered_after));
+        assert!(!recall.applies_to(&other_lot));
+        assert!(!recall.applies_to(&no_lot));
+
+        // Products stored before the field existed still deserialize
+        let json = serde_json::to_value(&no_lot).unwrap();
+        assert!(json.get("lot_number").is_none());
+        let parsed: Product = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.lot_number, None);
+    }
+
+    #[test]
+    fn test_recall_status_last_known_location() {
+        let earlier = Recall {
+            timestamp: 1702550000000,
+            reason: "Labeling".to_string(),
+            ..recall()
+        };
+        // Linked twice: once at registration, once when the recall was issued
+        let recalls = vec![recall(), earlier.clone(), recall()];
+        let last = event_at(90, "distribution-center", Some(4.0));
+
+        let status = recall_status("P-1".to_string(), recalls, Some(&last));
+        assert!(status.is_recalled());
+        assert_eq!(status.recalls, vec![earlier, recall()]);
+        assert_eq!(status.last_known_location.as_deref(), Some("distribution-center"));
+        assert_eq!(status.last_handler.as_deref(), Some("handler@distribution-center"));
+        assert_eq!(status.last_seen, Some(last.timestamp));
+
+        let unseen = recall_status("P-2".to_string(), vec![], None);
+        assert!(!unseen.is_recalled());
+        assert_eq!(unseen.last_known_location, None);
+    }
+
+    const PDF_SHA256: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+    fn attachment(content_hash: &str, hash_algo: HashAlgo) -> AttachmentRef {
+        AttachmentRef {
+            content_hash: content_hash.to_string(),
+            hash_algo,
+            mime_type: "application/pdf".to_string(),
+            size_bytes: 48_213,
+            description: Some("Certificate of analysis".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_attachment_serialization() {
+        let json = serde_json::to_value(attachment(PDF_SHA256, HashAlgo::Sha256)).unwrap();
+        assert_eq!(json["hash_algo"], "sha256");
+        let parsed: AttachmentRef = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(parsed, attachment(PDF_SHA256, HashAlgo::Sha256));
+
+        let mut blake3 = json.clone();
+        blake3["hash_algo"] = "blake3".into();
+        assert!(serde_json::from_value::<AttachmentRef>(blake3).is_ok());
+
+        // The algorithm is explicit: unknown or missing algorithms are rejected
+        let mut md5 = json.clone();
+        md5["hash_algo"] = "md5".into();
+        assert!(serde_json::from_value::<AttachmentRef>(md5).is_err());
+        let mut missing = json;
+        missing.as_object_mut().unwrap().remove("hash_algo");
+        assert!(serde_json::from_value::<AttachmentRef>(missing).is_err());
+    }
+
+    #[test]
+    fn test_attachment_validation() {
+        assert!(attachment(PDF_SHA256, HashAlgo::Sha256).validate().is_ok());
+        assert!(attachment(PDF_SHA256, HashAlgo::Blake3).validate().is_ok());
+
+        assert!(attachment(&PDF_SHA256[..40], HashAlgo::Sha256).validate().is_err());
+        assert!(attachment(&PDF_SHA256.to_uppercase(), HashAlgo::Sha256).validate().is_err());
+        assert!(attachment(&PDF_SHA256.replace('9', "z"), HashAlgo::Sha256).validate().is_err());
+
+        let mut untyped = attachment(PDF_SHA256, HashAlgo::Sha256);
+        untyped.mime_type.clear();
+        assert!(untyped.validate().is_err());
+    }
+
+    #[test]
+    fn test_attachment_verification() {
+        let inspector = AgentPubKey::from_raw_36(vec![1; 36]);
+        let auditor = AgentPubKey::from_raw_36(vec![2; 36]);
+        let appended_hash = "a".repeat(64);
+        let recorded = vec![
+            RecordedAttachment {
+                attachment: attachment(PDF_SHA256, HashAlgo::Sha256),
+                recorded_by: inspector.clone(),
+                recorded_at: 1702500000000,
+                appended: false,
+            },
+            RecordedAttachment {
+                attachment: attachment(&appended_hash, HashAlgo::Blake3),
+                recorded_by: auditor.clone(),
+                recorded_at: 1702600000000,
+                appended: true,
+            },
+        ];
+        let input = |content_hash: &str| VerifyAttachmentInput {
+            product_id: "P-1".to_string(),
+            inspection_hash: ActionHash::from_raw_36(vec![0; 36]),
+            content_hash: content_hash.to_string(),
+        };
+
+        let original = attachment_verification(input(&PDF_SHA256.to_uppercase()), &recorded);
+        assert!(original.verified);
+        assert!(!original.appended);
+        assert_eq!(original.attachment, Some(attachment(PDF_SHA256, HashAlgo::Sha256)));
+        assert_eq!(original.recorded_by, Some(inspector));
+        assert_eq!(original.recorded_at, Some(1702500000000));
+
+        let appended = attachment_verification(input(&appended_hash), &recorded);
+        assert!(appended.verified);
+        assert!(appended.appended);
+        assert_eq!(appended.attachment.unwrap().hash_algo, HashAlgo::Blake3);
+        assert_eq!(appended.recorded_by, Some(auditor));
+
+        let unknown = attachment_verification(input(&"b".repeat(64)), &recorded);
+        assert!(!unknown.verified);
+        assert!(unknown.attachment.is_none());
+        assert!(unknown.recorded_by.is_none());
+    }
 }